@@ -0,0 +1,21 @@
+-- Create table for backup verification runs
+-- Each run snapshots the critical tables into a scratch schema, compares the restored
+-- copy against the source and re-checks game scores against their live score events
+
+CREATE TABLE IF NOT EXISTS backup_verification_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    status VARCHAR(20) NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'passed', 'failed', 'error')),
+    triggered_by VARCHAR(20) NOT NULL DEFAULT 'scheduler' CHECK (triggered_by IN ('scheduler', 'admin')),
+    table_results JSONB NOT NULL DEFAULT '[]'::jsonb,
+    score_divergences JSONB NOT NULL DEFAULT '[]'::jsonb,
+    error_message TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+-- Index for the admin dashboard listing (most recent runs first)
+CREATE INDEX IF NOT EXISTS idx_backup_verification_runs_started_at ON backup_verification_runs(started_at DESC);
+
+COMMENT ON TABLE backup_verification_runs IS 'History of nightly/admin-triggered backup restore verifications';
+COMMENT ON COLUMN backup_verification_runs.table_results IS 'Per-table row counts and checksums of source vs restored copy';
+COMMENT ON COLUMN backup_verification_runs.score_divergences IS 'Games whose stored score differs from the sum of their live score events in the restored copy';
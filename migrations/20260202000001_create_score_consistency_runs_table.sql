@@ -0,0 +1,17 @@
+-- Create table for score consistency check runs
+-- Each run recomputes in-progress and finished game scores from live_score_events and
+-- workouts and records every game whose stored score or scoring events look wrong
+
+CREATE TABLE IF NOT EXISTS score_consistency_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    games_checked BIGINT NOT NULL DEFAULT 0,
+    games_with_issues INTEGER NOT NULL DEFAULT 0,
+    issues JSONB NOT NULL DEFAULT '[]'::jsonb,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for the admin dashboard listing (most recent runs first)
+CREATE INDEX IF NOT EXISTS idx_score_consistency_runs_checked_at ON score_consistency_runs(checked_at DESC);
+
+COMMENT ON TABLE score_consistency_runs IS 'History of periodic game score consistency checks';
+COMMENT ON COLUMN score_consistency_runs.issues IS 'Flagged games with the issue type (score_mismatch, duplicate_workout_events, overlapping_workouts, stale_event_points, uncredited_workout) and its details';
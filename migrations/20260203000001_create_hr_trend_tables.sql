@@ -0,0 +1,29 @@
+-- Create tables for personal heart rate trend analytics
+-- Both tables are filled by the nightly HR trend job and read by /health/analytics/hr-trends
+
+-- Daily snapshot of each user's resting heart rate (the profile only keeps the latest value)
+CREATE TABLE IF NOT EXISTS user_resting_hr_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recorded_on DATE NOT NULL,
+    resting_heart_rate INTEGER NOT NULL CHECK (resting_heart_rate BETWEEN 10 AND 250),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_resting_hr_day UNIQUE (user_id, recorded_on)
+);
+
+-- Per-workout heart rate metrics derived from the raw heart rate samples
+CREATE TABLE IF NOT EXISTS workout_hr_metrics (
+    workout_data_id UUID PRIMARY KEY REFERENCES workout_data(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    workout_start TIMESTAMPTZ NOT NULL,
+    activity_name VARCHAR(255),
+    steady_state_avg_hr REAL,
+    hr_drift_pct REAL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_workout_hr_metrics_user_start ON workout_hr_metrics(user_id, workout_start DESC);
+
+COMMENT ON TABLE user_resting_hr_history IS 'Daily snapshots of user_health_profiles.resting_heart_rate for trend charts';
+COMMENT ON COLUMN workout_hr_metrics.steady_state_avg_hr IS 'Average heart rate after the warm-up, compared per activity as a proxy for HR at a fixed workload';
+COMMENT ON COLUMN workout_hr_metrics.hr_drift_pct IS 'Cardiac drift: second-half vs first-half average heart rate of long workouts, in percent';
@@ -0,0 +1,43 @@
+-- Create tables for the weekly digest job
+-- The job snapshots league standings, composes one digest per user and delivers it
+-- through the channels enabled in notification_preferences
+
+-- Per-user notification channel settings; users without a row get the defaults
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    push_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    in_app_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    weekly_digest_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Weekly copy of league_standings positions, used to find the week's movers
+CREATE TABLE IF NOT EXISTS league_standing_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    week_start DATE NOT NULL,
+    position INTEGER NOT NULL,
+    points INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_standing_snapshot_week UNIQUE (season_id, team_id, week_start)
+);
+
+-- Composed digests, one per user and week
+CREATE TABLE IF NOT EXISTS weekly_digests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    week_start DATE NOT NULL,
+    content JSONB NOT NULL,
+    delivered_channels TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_digest_week UNIQUE (user_id, week_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_weekly_digests_user_week ON weekly_digests(user_id, week_start DESC);
+
+COMMENT ON TABLE notification_preferences IS 'Notification channels each user opted into; missing rows mean all defaults';
+COMMENT ON COLUMN league_standing_snapshots.week_start IS 'Monday of the week the digest job took the snapshot';
+COMMENT ON COLUMN weekly_digests.week_start IS 'Monday the digest was generated; it covers the seven days before';
+COMMENT ON COLUMN weekly_digests.delivered_channels IS 'Channels the digest was delivered through (in_app, push)';
@@ -0,0 +1,31 @@
+-- Inactivity re-engagement nudges
+-- A daily job nudges team members who have not uploaded a workout for a season's
+-- configured number of days and lets the team captain know
+
+ALTER TABLE league_seasons
+ADD COLUMN IF NOT EXISTS inactivity_nudge_days INTEGER DEFAULT 3
+    CHECK (inactivity_nudge_days IS NULL OR inactivity_nudge_days BETWEEN 1 AND 60);
+
+ALTER TABLE notification_preferences
+ADD COLUMN IF NOT EXISTS inactivity_nudges_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+ADD COLUMN IF NOT EXISTS team_inactivity_alerts_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE TABLE IF NOT EXISTS inactivity_nudges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    last_upload_at TIMESTAMPTZ,
+    inactive_days INTEGER NOT NULL,
+    delivered_channels TEXT[] NOT NULL DEFAULT '{}',
+    captain_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    captain_channels TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_inactivity_nudges_user_created ON inactivity_nudges(user_id, created_at DESC);
+
+COMMENT ON COLUMN league_seasons.inactivity_nudge_days IS 'Days without an upload before a member is nudged; NULL disables nudges for the season';
+COMMENT ON COLUMN notification_preferences.inactivity_nudges_enabled IS 'Receive nudges when you have not uploaded a workout for a while';
+COMMENT ON COLUMN notification_preferences.team_inactivity_alerts_enabled IS 'As team captain, get notified when a member has been inactive';
+COMMENT ON TABLE inactivity_nudges IS 'Nudges sent by the inactivity job; a member is nudged at most once per inactivity period';
@@ -0,0 +1,125 @@
+-- Delta sync for the mobile offline cache
+-- `/sync/changes?since=` returns rows changed after `since` plus tombstones for rows deleted since then.
+-- Change tracking relies on updated_at being maintained on every write, so it is enforced by triggers here.
+
+ALTER TABLE notifications
+ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE notifications SET updated_at = created_at;
+
+CREATE OR REPLACE FUNCTION update_notifications_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_notifications_updated_at ON notifications;
+CREATE TRIGGER trigger_update_notifications_updated_at
+    BEFORE UPDATE ON notifications
+    FOR EACH ROW
+    EXECUTE FUNCTION update_notifications_updated_at();
+
+CREATE OR REPLACE FUNCTION update_workout_data_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_workout_data_updated_at ON workout_data;
+CREATE TRIGGER trigger_update_workout_data_updated_at
+    BEFORE UPDATE ON workout_data
+    FOR EACH ROW
+    EXECUTE FUNCTION update_workout_data_updated_at();
+
+CREATE OR REPLACE FUNCTION update_games_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_games_updated_at ON games;
+CREATE TRIGGER trigger_update_games_updated_at
+    BEFORE UPDATE ON games
+    FOR EACH ROW
+    EXECUTE FUNCTION update_games_updated_at();
+
+CREATE OR REPLACE FUNCTION update_league_standings_last_updated()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.last_updated = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_league_standings_last_updated ON league_standings;
+CREATE TRIGGER trigger_update_league_standings_last_updated
+    BEFORE UPDATE ON league_standings
+    FOR EACH ROW
+    EXECUTE FUNCTION update_league_standings_last_updated();
+
+-- Deleted rows, kept long enough for clients to pick up the deletion
+CREATE TABLE IF NOT EXISTS sync_tombstones (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type VARCHAR(20) NOT NULL CHECK (entity_type IN ('workout', 'game', 'standing', 'notification')),
+    entity_id UUID NOT NULL,
+    -- Owner of user-scoped entities (workouts, notifications)
+    user_id UUID,
+    -- Season of season-scoped entities (games, standings)
+    season_id UUID,
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_tombstones_user ON sync_tombstones(user_id, deleted_at) WHERE user_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_sync_tombstones_season ON sync_tombstones(season_id, deleted_at) WHERE season_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_sync_tombstones_deleted_at ON sync_tombstones(deleted_at);
+
+CREATE OR REPLACE FUNCTION record_sync_tombstone()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_TABLE_NAME = 'workout_data' THEN
+        INSERT INTO sync_tombstones (entity_type, entity_id, user_id) VALUES ('workout', OLD.id, OLD.user_id);
+    ELSIF TG_TABLE_NAME = 'notifications' THEN
+        INSERT INTO sync_tombstones (entity_type, entity_id, user_id) VALUES ('notification', OLD.id, OLD.recipient_id);
+    ELSIF TG_TABLE_NAME = 'games' THEN
+        INSERT INTO sync_tombstones (entity_type, entity_id, season_id) VALUES ('game', OLD.id, OLD.season_id);
+    ELSIF TG_TABLE_NAME = 'league_standings' THEN
+        INSERT INTO sync_tombstones (entity_type, entity_id, season_id) VALUES ('standing', OLD.id, OLD.season_id);
+    END IF;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_workout_data_sync_tombstone ON workout_data;
+CREATE TRIGGER trigger_workout_data_sync_tombstone
+    AFTER DELETE ON workout_data
+    FOR EACH ROW
+    EXECUTE FUNCTION record_sync_tombstone();
+
+DROP TRIGGER IF EXISTS trigger_notifications_sync_tombstone ON notifications;
+CREATE TRIGGER trigger_notifications_sync_tombstone
+    AFTER DELETE ON notifications
+    FOR EACH ROW
+    EXECUTE FUNCTION record_sync_tombstone();
+
+DROP TRIGGER IF EXISTS trigger_games_sync_tombstone ON games;
+CREATE TRIGGER trigger_games_sync_tombstone
+    AFTER DELETE ON games
+    FOR EACH ROW
+    EXECUTE FUNCTION record_sync_tombstone();
+
+DROP TRIGGER IF EXISTS trigger_league_standings_sync_tombstone ON league_standings;
+CREATE TRIGGER trigger_league_standings_sync_tombstone
+    AFTER DELETE ON league_standings
+    FOR EACH ROW
+    EXECUTE FUNCTION record_sync_tombstone();
+
+CREATE INDEX IF NOT EXISTS idx_workout_data_user_updated ON workout_data(user_id, updated_at);
+CREATE INDEX IF NOT EXISTS idx_notifications_recipient_updated ON notifications(recipient_id, updated_at);
+
+COMMENT ON TABLE sync_tombstones IS 'Deletions reported by /sync/changes; pruned after the sync retention window';
@@ -0,0 +1,12 @@
+-- Optimistic concurrency for team and health profile edits
+-- Each user-facing update bumps `version`; clients send it back in If-Match and get 409
+-- when someone else saved in between, instead of silently overwriting their changes.
+
+ALTER TABLE teams
+ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE user_health_profiles
+ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+COMMENT ON COLUMN teams.version IS 'Incremented on every team edit; matched against If-Match on updates';
+COMMENT ON COLUMN user_health_profiles.version IS 'Incremented on every profile edit; matched against If-Match on updates';
@@ -0,0 +1,58 @@
+-- Activity catalog
+-- Reference list of sports a workout can be labelled with. User edits of a workout's
+-- activity (workout_data.user_activity) are validated against it instead of accepting free text.
+
+CREATE TABLE IF NOT EXISTS activities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    key VARCHAR(50) NOT NULL UNIQUE,
+    name VARCHAR(100) NOT NULL,
+    -- Metabolic equivalent of task, for calorie estimates
+    met_value REAL NOT NULL CHECK (met_value > 0 AND met_value <= 25),
+    -- Scoring profile used when a workout cannot be classified otherwise
+    default_scoring_profile VARCHAR(20) NOT NULL DEFAULT 'other'
+        CHECK (default_scoring_profile IN ('strength', 'cardio', 'hiit', 'other')),
+    -- Icon identifier understood by the apps
+    icon_key VARCHAR(50) NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_activities_name_lower ON activities (LOWER(name));
+
+CREATE OR REPLACE FUNCTION update_activities_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_activities_updated_at ON activities;
+CREATE TRIGGER trigger_update_activities_updated_at
+    BEFORE UPDATE ON activities
+    FOR EACH ROW
+    EXECUTE FUNCTION update_activities_updated_at();
+
+INSERT INTO activities (key, name, met_value, default_scoring_profile, icon_key) VALUES
+    ('run', 'Running', 9.8, 'cardio', 'run'),
+    ('trail_run', 'Trail Running', 10.0, 'cardio', 'trail_run'),
+    ('walk', 'Walking', 3.5, 'cardio', 'walk'),
+    ('hike', 'Hiking', 6.0, 'cardio', 'hike'),
+    ('ride', 'Cycling', 7.5, 'cardio', 'ride'),
+    ('swim', 'Swimming', 7.0, 'cardio', 'swim'),
+    ('row', 'Rowing', 7.0, 'cardio', 'row'),
+    ('hiit', 'HIIT', 8.0, 'hiit', 'hiit'),
+    ('crossfit', 'CrossFit', 8.0, 'hiit', 'crossfit'),
+    ('strength', 'Strength Training', 5.0, 'strength', 'strength'),
+    ('climbing', 'Climbing', 8.0, 'strength', 'climbing'),
+    ('yoga', 'Yoga', 2.5, 'other', 'yoga'),
+    ('pilates', 'Pilates', 3.0, 'other', 'pilates'),
+    ('dance', 'Dance', 5.0, 'cardio', 'dance'),
+    ('football', 'Football', 7.0, 'cardio', 'football'),
+    ('basketball', 'Basketball', 6.5, 'cardio', 'basketball'),
+    ('tennis', 'Tennis', 7.3, 'cardio', 'tennis'),
+    ('other', 'Other', 4.0, 'other', 'other')
+ON CONFLICT (key) DO NOTHING;
+
+COMMENT ON TABLE activities IS 'Activity catalog; workout_data.user_activity must name an active entry';
@@ -0,0 +1,12 @@
+-- Workout notes and tags
+-- Free-form annotations users add to their own workouts
+
+ALTER TABLE workout_data
+ADD COLUMN IF NOT EXISTS notes TEXT,
+ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+-- Tag filter in the workout history
+CREATE INDEX IF NOT EXISTS idx_workout_data_tags ON workout_data USING GIN (tags);
+
+COMMENT ON COLUMN workout_data.notes IS 'User notes, shown on the workout post to whoever can see it';
+COMMENT ON COLUMN workout_data.tags IS 'User tags, normalized to lowercase without a leading #';
@@ -0,0 +1,20 @@
+-- Workout share links
+-- Unguessable tokens giving read-only public access to one workout's summary until revoked
+
+CREATE TABLE IF NOT EXISTS workout_share_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    token VARCHAR(64) NOT NULL UNIQUE,
+    workout_data_id UUID NOT NULL REFERENCES workout_data(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    view_count INTEGER NOT NULL DEFAULT 0,
+    last_viewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+-- At most one live link per workout
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workout_share_links_active
+    ON workout_share_links(workout_data_id) WHERE revoked_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_workout_share_links_user_id ON workout_share_links(user_id);
+
+COMMENT ON TABLE workout_share_links IS 'Public share links for single workouts; revoked links stay for auditing';
@@ -0,0 +1,37 @@
+-- Photo moderation
+-- Perceptual hashes of confirmed uploads, a blocklist of hashes that may not be uploaded again,
+-- and a record of media taken down by admins.
+
+CREATE TABLE IF NOT EXISTS media_hashes (
+    object_key TEXT PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    sha256 VARCHAR(64) NOT NULL,
+    -- 64-bit DCT hash; near-identical images differ in only a few bits
+    phash BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_hashes_user_id ON media_hashes(user_id);
+
+CREATE TABLE IF NOT EXISTS media_hash_blocklist (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    phash BIGINT NOT NULL UNIQUE,
+    reason TEXT NOT NULL,
+    source_object_key TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS media_takedowns (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    object_key TEXT NOT NULL UNIQUE,
+    file_url TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    taken_down_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    posts_updated INTEGER NOT NULL DEFAULT 0,
+    workouts_updated INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE media_hash_blocklist IS 'Uploads within a small Hamming distance of a blocked hash are rejected on confirm';
+COMMENT ON TABLE media_takedowns IS 'Media removed by admins; download URLs for these keys are refused';
@@ -0,0 +1,73 @@
+-- Unify workout and comment reactions into one polymorphic table so posts,
+-- game summaries and score events can be reacted to as well
+
+CREATE TABLE reactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_type VARCHAR(20) NOT NULL CHECK (target_type IN ('workout', 'comment', 'post', 'game_summary', 'score_event')),
+    target_id UUID NOT NULL,
+    reaction_type VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- One reaction per user and target
+    CONSTRAINT unique_user_target_reaction UNIQUE (user_id, target_type, target_id)
+);
+
+CREATE INDEX idx_reactions_target ON reactions(target_type, target_id, reaction_type);
+CREATE INDEX idx_reactions_user_id ON reactions(user_id);
+CREATE INDEX idx_reactions_created_at ON reactions(created_at DESC);
+
+-- Carry over existing reactions, keeping their ids
+INSERT INTO reactions (id, user_id, target_type, target_id, reaction_type, created_at)
+SELECT id, user_id, 'workout', workout_id, reaction_type, created_at
+FROM post_reactions
+WHERE workout_id IS NOT NULL;
+
+INSERT INTO reactions (id, user_id, target_type, target_id, reaction_type, created_at)
+SELECT id, user_id, 'post', post_id, reaction_type, created_at
+FROM post_reactions
+WHERE workout_id IS NULL AND post_id IS NOT NULL;
+
+INSERT INTO reactions (id, user_id, target_type, target_id, reaction_type, created_at)
+SELECT id, user_id, 'comment', comment_id, reaction_type, created_at
+FROM post_comment_reactions;
+
+DROP TABLE post_reactions;
+DROP TABLE post_comment_reactions;
+
+-- target_id cannot carry a foreign key, so reactions are removed with their target
+CREATE OR REPLACE FUNCTION delete_target_reactions()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM reactions WHERE target_type = TG_ARGV[0] AND target_id = OLD.id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_workout_data_delete_reactions
+    AFTER DELETE ON workout_data
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_target_reactions('workout');
+
+CREATE TRIGGER trigger_post_comments_delete_reactions
+    AFTER DELETE ON post_comments
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_target_reactions('comment');
+
+CREATE TRIGGER trigger_posts_delete_reactions
+    AFTER DELETE ON posts
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_target_reactions('post');
+
+CREATE TRIGGER trigger_game_summaries_delete_reactions
+    AFTER DELETE ON game_summaries
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_target_reactions('game_summary');
+
+CREATE TRIGGER trigger_live_score_events_delete_reactions
+    AFTER DELETE ON live_score_events
+    FOR EACH ROW
+    EXECUTE FUNCTION delete_target_reactions('score_event');
+
+COMMENT ON TABLE reactions IS 'User reactions on workouts, comments, posts, game summaries and score events';
+COMMENT ON COLUMN reactions.target_id IS 'Id of the reacted row in the table named by target_type';
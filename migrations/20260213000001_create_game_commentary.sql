@@ -0,0 +1,29 @@
+-- Automated live game commentary, configurable per league
+
+ALTER TABLE leagues
+    ADD COLUMN commentary_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN commentary_locale VARCHAR(10) NOT NULL DEFAULT 'en',
+    ADD COLUMN commentary_milestones TEXT[] NOT NULL
+        DEFAULT ARRAY['first_score', 'lead_change', 'final_minutes', 'mvp_candidate'];
+
+CREATE TABLE game_commentary (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    milestone VARCHAR(20) NOT NULL CHECK (milestone IN ('first_score', 'lead_change', 'final_minutes', 'mvp_candidate')),
+    locale VARCHAR(10) NOT NULL,
+    message TEXT NOT NULL,
+    params JSONB NOT NULL DEFAULT '{}',
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    team_id UUID REFERENCES teams(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_game_commentary_game ON game_commentary(game_id, created_at);
+
+-- The first score and the final minutes are only called once per game
+CREATE UNIQUE INDEX idx_game_commentary_once_per_game ON game_commentary(game_id, milestone)
+    WHERE milestone IN ('first_score', 'final_minutes');
+
+COMMENT ON TABLE game_commentary IS 'Templated commentary posted at game milestones';
+COMMENT ON COLUMN game_commentary.params IS 'Template parameters, so clients can re-render the line in their own language';
+COMMENT ON COLUMN leagues.commentary_milestones IS 'Milestones the commentator calls out for games in this league';
@@ -0,0 +1,23 @@
+-- Season recaps, generated once every game of a season has been evaluated
+-- One recap covers the whole league (team_id NULL), plus one per team
+
+CREATE TABLE season_recaps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    team_id UUID REFERENCES teams(id) ON DELETE CASCADE,
+    content JSONB NOT NULL,
+    card_status VARCHAR(20) NOT NULL DEFAULT 'none' CHECK (card_status IN ('none', 'pending', 'rendered', 'failed')),
+    card_object_key TEXT,
+    card_rendered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_season_recaps_league ON season_recaps(season_id) WHERE team_id IS NULL;
+CREATE UNIQUE INDEX idx_season_recaps_team ON season_recaps(season_id, team_id) WHERE team_id IS NOT NULL;
+CREATE INDEX idx_season_recaps_pending_cards ON season_recaps(updated_at) WHERE card_status = 'pending';
+
+COMMENT ON TABLE season_recaps IS 'Season narrative recaps (biggest upset, longest streak, top scorer arc) per league and team';
+COMMENT ON COLUMN season_recaps.team_id IS 'Team the recap is about; NULL for the league-wide recap';
+COMMENT ON COLUMN season_recaps.card_status IS 'Shareable card rendering: none (not requested), pending, rendered or failed';
+COMMENT ON COLUMN season_recaps.card_object_key IS 'MinIO key of the rendered card image';
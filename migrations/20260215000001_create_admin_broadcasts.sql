@@ -0,0 +1,33 @@
+-- Admin announcements to all users, a league or a team
+-- Delivered through WebSocket, push and the notification center, now or at a scheduled time
+
+CREATE TABLE admin_broadcasts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(100) NOT NULL,
+    message TEXT NOT NULL,
+    target_type VARCHAR(20) NOT NULL CHECK (target_type IN ('all', 'league', 'team')),
+    target_id UUID,
+    channels TEXT[] NOT NULL DEFAULT ARRAY['websocket', 'push', 'in_app'],
+    status VARCHAR(20) NOT NULL DEFAULT 'scheduled' CHECK (status IN ('scheduled', 'sending', 'sent', 'cancelled', 'failed')),
+    scheduled_for TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    recipient_count INTEGER NOT NULL DEFAULT 0,
+    websocket_count INTEGER NOT NULL DEFAULT 0,
+    push_count INTEGER NOT NULL DEFAULT 0,
+    in_app_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT admin_broadcasts_target_check CHECK ((target_type = 'all') = (target_id IS NULL))
+);
+
+CREATE INDEX idx_admin_broadcasts_due ON admin_broadcasts(scheduled_for) WHERE status = 'scheduled';
+CREATE INDEX idx_admin_broadcasts_created ON admin_broadcasts(created_at DESC);
+
+COMMENT ON TABLE admin_broadcasts IS 'Announcements sent by admins; scheduled ones are picked up by the broadcast job';
+COMMENT ON COLUMN admin_broadcasts.target_id IS 'League or team the announcement goes to; NULL when sent to all users';
+COMMENT ON COLUMN admin_broadcasts.channels IS 'Channels to deliver through: websocket, push and/or in_app';
+COMMENT ON COLUMN admin_broadcasts.websocket_count IS 'Recipients connected over WebSocket when the announcement went out';
+COMMENT ON COLUMN admin_broadcasts.failed_count IS 'Recipients the announcement could not be delivered to';
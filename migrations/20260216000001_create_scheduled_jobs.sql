@@ -0,0 +1,28 @@
+-- Cron schedules of the background jobs, editable by admins
+-- The scheduler reads these at startup and whenever an admin changes them
+
+CREATE TABLE scheduled_jobs (
+    job_name VARCHAR(100) PRIMARY KEY,
+    cron_expression VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('game_cycle', '0 * * * * *', 'Start, finish and evaluate the games of every active season'),
+    ('poll_expiration', '0 */5 * * * *', 'Expire team polls past their deadline'),
+    ('backup_verification', '0 30 3 * * *', 'Restore and verify the latest backup'),
+    ('score_consistency', '0 */15 * * * *', 'Compare live game scores with their score events'),
+    ('hr_trends', '0 15 2 * * *', 'Compute resting heart rate and HR zone trends'),
+    ('weekly_digest', '0 0 7 * * Mon', 'Compose and deliver the weekly digest'),
+    ('inactivity_nudges', '0 0 10 * * *', 'Nudge inactive team members and their captains'),
+    ('sync_tombstone_prune', '0 0 4 * * *', 'Prune expired delta sync tombstones'),
+    ('game_commentary', '0 * * * * *', 'Call the final minutes of running games'),
+    ('broadcasts', '15 * * * * *', 'Send scheduled admin announcements that are due'),
+    ('recap_card_render', '30 * * * * *', 'Render requested season recap cards');
+
+COMMENT ON TABLE scheduled_jobs IS 'Cron schedules of the background jobs; jobs without a row run on their built-in schedule';
+COMMENT ON COLUMN scheduled_jobs.job_name IS 'Registered job name; game_cycle applies to the game cycle of every season';
+COMMENT ON COLUMN scheduled_jobs.cron_expression IS 'Six-field cron expression including seconds, evaluated in UTC';
@@ -0,0 +1,25 @@
+-- Games the watchdog found past their end time without being finalized
+-- One row per game; admins are notified when the row is created
+
+CREATE TABLE game_watchdog_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL UNIQUE REFERENCES games(id) ON DELETE CASCADE,
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    game_status VARCHAR(50) NOT NULL,
+    game_end_time TIMESTAMPTZ NOT NULL,
+    first_detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    admins_notified INTEGER NOT NULL DEFAULT 0,
+    auto_finalized_at TIMESTAMPTZ,
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_game_watchdog_alerts_open ON game_watchdog_alerts(first_detected_at DESC) WHERE resolved_at IS NULL;
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('game_watchdog', '45 */5 * * * *', 'Alert on games past their end time that were never finalized');
+
+COMMENT ON TABLE game_watchdog_alerts IS 'Games found past their end time without being evaluated, raised by the game watchdog job';
+COMMENT ON COLUMN game_watchdog_alerts.game_status IS 'Status of the game when it was last found overdue';
+COMMENT ON COLUMN game_watchdog_alerts.auto_finalized_at IS 'When the watchdog finished and evaluated the game itself, if enabled';
+COMMENT ON COLUMN game_watchdog_alerts.resolved_at IS 'When the game was no longer overdue, i.e. it got evaluated';
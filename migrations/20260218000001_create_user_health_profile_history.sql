@@ -0,0 +1,74 @@
+-- Health profile history
+-- Every change to the scoring-relevant profile values is kept as a snapshot valid from the time of the change,
+-- so workouts are scored against the profile that was current when they happened and trends can be charted.
+-- Snapshots are written by triggers so that every path that edits the profile is covered.
+
+CREATE TABLE IF NOT EXISTS user_health_profile_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    profile_version INTEGER NOT NULL,
+    age INTEGER,
+    gender VARCHAR(10),
+    resting_heart_rate INTEGER NOT NULL,
+    max_heart_rate INTEGER NOT NULL,
+    weight REAL,
+    height REAL,
+    vt_off_threshold INTEGER,
+    vt0_threshold INTEGER,
+    vt1_threshold INTEGER,
+    vt2_threshold INTEGER,
+    valid_from TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_health_profile_history_user_valid_from
+    ON user_health_profile_history(user_id, valid_from DESC);
+
+CREATE OR REPLACE FUNCTION record_user_health_profile_history()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO user_health_profile_history (
+        user_id, profile_version, age, gender, resting_heart_rate, max_heart_rate, weight, height,
+        vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, valid_from
+    )
+    VALUES (
+        NEW.user_id, NEW.version, NEW.age, NEW.gender, NEW.resting_heart_rate, NEW.max_heart_rate, NEW.weight, NEW.height,
+        NEW.vt_off_threshold, NEW.vt0_threshold, NEW.vt1_threshold, NEW.vt2_threshold, NOW()
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_user_health_profile_history_insert ON user_health_profiles;
+CREATE TRIGGER trigger_user_health_profile_history_insert
+    AFTER INSERT ON user_health_profiles
+    FOR EACH ROW
+    EXECUTE FUNCTION record_user_health_profile_history();
+
+DROP TRIGGER IF EXISTS trigger_user_health_profile_history_update ON user_health_profiles;
+CREATE TRIGGER trigger_user_health_profile_history_update
+    AFTER UPDATE ON user_health_profiles
+    FOR EACH ROW
+    WHEN (
+        OLD.age IS DISTINCT FROM NEW.age
+        OR OLD.gender IS DISTINCT FROM NEW.gender
+        OR OLD.resting_heart_rate IS DISTINCT FROM NEW.resting_heart_rate
+        OR OLD.max_heart_rate IS DISTINCT FROM NEW.max_heart_rate
+        OR OLD.weight IS DISTINCT FROM NEW.weight
+        OR OLD.height IS DISTINCT FROM NEW.height
+        OR OLD.vt_off_threshold IS DISTINCT FROM NEW.vt_off_threshold
+        OR OLD.vt0_threshold IS DISTINCT FROM NEW.vt0_threshold
+        OR OLD.vt1_threshold IS DISTINCT FROM NEW.vt1_threshold
+        OR OLD.vt2_threshold IS DISTINCT FROM NEW.vt2_threshold
+    )
+    EXECUTE FUNCTION record_user_health_profile_history();
+
+-- Existing profiles start their history with the current values
+INSERT INTO user_health_profile_history (
+    user_id, profile_version, age, gender, resting_heart_rate, max_heart_rate, weight, height,
+    vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, valid_from
+)
+SELECT
+    p.user_id, p.version, p.age, p.gender, p.resting_heart_rate, p.max_heart_rate, p.weight, p.height,
+    p.vt_off_threshold, p.vt0_threshold, p.vt1_threshold, p.vt2_threshold, p.last_updated
+FROM user_health_profiles p
+WHERE NOT EXISTS (SELECT 1 FROM user_health_profile_history h WHERE h.user_id = p.user_id);
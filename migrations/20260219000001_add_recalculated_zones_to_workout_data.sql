@@ -0,0 +1,12 @@
+-- Retroactive zone recalculation
+-- When max HR or VT thresholds change, users can opt in to recalculating the zone distribution of past workouts.
+-- The recalculated view is stored next to the original `heart_rate_zones`, which together with the
+-- stamina/strength/points of the workout stays as it was scored so settled game scores are unaffected.
+
+ALTER TABLE workout_data
+ADD COLUMN IF NOT EXISTS recalculated_heart_rate_zones JSONB,
+ADD COLUMN IF NOT EXISTS zones_recalculated_at TIMESTAMPTZ,
+ADD COLUMN IF NOT EXISTS zones_recalculated_max_heart_rate INTEGER,
+ADD COLUMN IF NOT EXISTS zones_recalculated_resting_heart_rate INTEGER;
+
+COMMENT ON COLUMN workout_data.recalculated_heart_rate_zones IS 'Zone breakdown recalculated with the profile at zones_recalculated_at; heart_rate_zones keeps the original';
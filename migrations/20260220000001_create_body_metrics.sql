@@ -0,0 +1,24 @@
+-- Body metrics
+-- Time series of weight, heart rate variability and resting heart rate, entered manually or synced from a device.
+-- Device entries carry the device's own id for the sample so re-syncs update instead of duplicating.
+
+CREATE TABLE IF NOT EXISTS body_metrics (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    metric_type VARCHAR(20) NOT NULL CHECK (metric_type IN ('weight', 'hrv', 'resting_hr')),
+    value REAL NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    source VARCHAR(20) NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'device')),
+    device_id VARCHAR(255),
+    external_id VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_body_metrics_user_type_recorded
+    ON body_metrics(user_id, metric_type, recorded_at DESC);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_body_metrics_external_id
+    ON body_metrics(user_id, metric_type, external_id)
+    WHERE external_id IS NOT NULL;
+
+COMMENT ON COLUMN body_metrics.value IS 'kg for weight, ms (RMSSD) for hrv, bpm for resting_hr';
@@ -0,0 +1,16 @@
+-- Health Connect change tokens
+-- The app syncs workouts incrementally with Health Connect's changes API. The last token of each device is
+-- kept here so a delta sync can be checked against it; when it is stale or expired the app is told to do a
+-- full resync instead of re-uploading history on every sync.
+
+CREATE TABLE IF NOT EXISTS health_connect_change_tokens (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id VARCHAR(255) NOT NULL,
+    change_token TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id, device_id)
+);
+
+COMMENT ON COLUMN health_connect_change_tokens.expires_at IS 'Health Connect drops tokens unused for 30 days; bumped on every use';
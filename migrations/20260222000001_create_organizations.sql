@@ -0,0 +1,47 @@
+-- Organizations
+-- Branding and defaults of the white-labeled apps served by this backend. The row marked
+-- as default is used when an app does not name its organization and for new league seasons.
+
+CREATE TABLE IF NOT EXISTS organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug VARCHAR(50) NOT NULL UNIQUE,
+    name VARCHAR(100) NOT NULL,
+    -- Hex colors (#RRGGBB) the apps theme themselves with
+    primary_color VARCHAR(7) NOT NULL DEFAULT '#1E88E5',
+    secondary_color VARCHAR(7) NOT NULL DEFAULT '#43A047',
+    -- Object key of the logo in media storage
+    logo_key TEXT,
+    locale VARCHAR(10) NOT NULL DEFAULT 'en',
+    -- Defaults for new league seasons, used when the admin does not set them
+    default_game_duration_seconds BIGINT NOT NULL DEFAULT 518400
+        CHECK (default_game_duration_seconds BETWEEN 1 AND 2592000),
+    default_games_per_matchup INTEGER NOT NULL DEFAULT 1
+        CHECK (default_games_per_matchup BETWEEN 1 AND 2),
+    default_inactivity_nudge_days INTEGER NOT NULL DEFAULT 3
+        CHECK (default_inactivity_nudge_days BETWEEN 0 AND 60),
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one default organization
+CREATE UNIQUE INDEX IF NOT EXISTS idx_organizations_default ON organizations (is_default) WHERE is_default;
+
+CREATE OR REPLACE FUNCTION update_organizations_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_update_organizations_updated_at ON organizations;
+CREATE TRIGGER trigger_update_organizations_updated_at
+    BEFORE UPDATE ON organizations
+    FOR EACH ROW
+    EXECUTE FUNCTION update_organizations_updated_at();
+
+INSERT INTO organizations (slug, name, is_default) VALUES ('riina', 'Riina', true)
+ON CONFLICT (slug) DO NOTHING;
+
+COMMENT ON TABLE organizations IS 'Branding and defaults per white-labeled app; served from /config/branding';
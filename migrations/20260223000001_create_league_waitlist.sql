@@ -0,0 +1,16 @@
+-- League waitlist
+-- Teams that asked to join a league while it was at max_teams. They are promoted in
+-- position order when a slot opens (a team leaves or the cap is raised).
+
+CREATE TABLE IF NOT EXISTS league_waitlist (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    league_id UUID NOT NULL REFERENCES leagues(id) ON DELETE CASCADE,
+    -- A team waits for one league at a time
+    team_id UUID NOT NULL UNIQUE REFERENCES teams(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL CHECK (position > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_league_waitlist_league_position ON league_waitlist (league_id, position);
+
+COMMENT ON TABLE league_waitlist IS 'Queue of teams waiting for a slot in a full league';
@@ -0,0 +1,18 @@
+-- Fixture difficulty balancing
+-- Generator settings per league and the fairness report of each generated season schedule.
+
+ALTER TABLE leagues
+    -- Orient games so every team alternates home and away as evenly as possible
+    ADD COLUMN IF NOT EXISTS schedule_balance_home_away BOOLEAN NOT NULL DEFAULT true,
+    -- Order game weeks so no team meets the strongest teams (by previous season rating) back to back
+    ADD COLUMN IF NOT EXISTS schedule_spread_strong_teams BOOLEAN NOT NULL DEFAULT true;
+
+CREATE TABLE IF NOT EXISTS season_schedule_reports (
+    season_id UUID PRIMARY KEY REFERENCES league_seasons(id) ON DELETE CASCADE,
+    balance_home_away BOOLEAN NOT NULL,
+    spread_strong_teams BOOLEAN NOT NULL,
+    fairness JSONB NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE season_schedule_reports IS 'Generator settings and fairness metrics of a season schedule';
@@ -0,0 +1,17 @@
+-- Schedule rules in the league timezone
+-- Kickoffs follow the league's wall clock; game times are materialized to UTC from these rules
+-- and re-materialized when timezone (DST) rules change.
+
+ALTER TABLE leagues
+    ADD COLUMN IF NOT EXISTS timezone TEXT NOT NULL DEFAULT 'UTC'; -- IANA timezone, e.g. Europe/Berlin
+
+CREATE TABLE IF NOT EXISTS season_schedule_rules (
+    season_id UUID PRIMARY KEY REFERENCES league_seasons(id) ON DELETE CASCADE,
+    timezone TEXT NOT NULL,
+    local_start TIMESTAMP NOT NULL, -- Wall-clock kickoff of week 1 in the timezone
+    game_duration_seconds BIGINT NOT NULL CHECK (game_duration_seconds > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE season_schedule_rules IS 'Week N kicks off at local_start + (N - 1) * game_duration_seconds on the wall clock of timezone';
@@ -0,0 +1,81 @@
+-- Reaction and comment counts of workouts, kept up to date by triggers so the feed
+-- reads them with its post query instead of aggregating reactions and comments per request
+
+CREATE TABLE feed_workout_summaries (
+    workout_id UUID PRIMARY KEY REFERENCES workout_data(id) ON DELETE CASCADE,
+    reaction_count BIGINT NOT NULL DEFAULT 0 CHECK (reaction_count >= 0),
+    comment_count BIGINT NOT NULL DEFAULT 0 CHECK (comment_count >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO feed_workout_summaries (workout_id, reaction_count, comment_count)
+SELECT w.id,
+    (SELECT COUNT(*) FROM reactions r WHERE r.target_type = 'workout' AND r.target_id = w.id),
+    (SELECT COUNT(*) FROM post_comments c WHERE c.workout_id = w.id)
+FROM workout_data w
+WHERE EXISTS (SELECT 1 FROM reactions r WHERE r.target_type = 'workout' AND r.target_id = w.id)
+   OR EXISTS (SELECT 1 FROM post_comments c WHERE c.workout_id = w.id);
+
+-- Adds `delta` to one counter of a workout. Decrements only touch existing rows, so
+-- counts of a workout that is being deleted are not recreated by its cascading deletes.
+CREATE OR REPLACE FUNCTION bump_feed_workout_summary(p_workout_id UUID, p_reactions BIGINT, p_comments BIGINT)
+RETURNS VOID AS $$
+BEGIN
+    IF p_reactions > 0 OR p_comments > 0 THEN
+        INSERT INTO feed_workout_summaries (workout_id, reaction_count, comment_count)
+        SELECT p_workout_id, p_reactions, p_comments
+        WHERE EXISTS (SELECT 1 FROM workout_data WHERE id = p_workout_id)
+        ON CONFLICT (workout_id) DO UPDATE
+        SET reaction_count = feed_workout_summaries.reaction_count + EXCLUDED.reaction_count,
+            comment_count = feed_workout_summaries.comment_count + EXCLUDED.comment_count,
+            updated_at = NOW();
+    ELSE
+        UPDATE feed_workout_summaries
+        SET reaction_count = GREATEST(reaction_count + p_reactions, 0),
+            comment_count = GREATEST(comment_count + p_comments, 0),
+            updated_at = NOW()
+        WHERE workout_id = p_workout_id;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_feed_summary_on_reaction()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' AND NEW.target_type = 'workout' THEN
+        PERFORM bump_feed_workout_summary(NEW.target_id, 1, 0);
+    ELSIF TG_OP = 'DELETE' AND OLD.target_type = 'workout' THEN
+        PERFORM bump_feed_workout_summary(OLD.target_id, -1, 0);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_reactions_feed_summary
+    AFTER INSERT OR DELETE ON reactions
+    FOR EACH ROW
+    EXECUTE FUNCTION update_feed_summary_on_reaction();
+
+CREATE OR REPLACE FUNCTION update_feed_summary_on_comment()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' AND NEW.workout_id IS NOT NULL THEN
+        PERFORM bump_feed_workout_summary(NEW.workout_id, 0, 1);
+    ELSIF TG_OP = 'DELETE' AND OLD.workout_id IS NOT NULL THEN
+        PERFORM bump_feed_workout_summary(OLD.workout_id, 0, -1);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_post_comments_feed_summary
+    AFTER INSERT OR DELETE ON post_comments
+    FOR EACH ROW
+    EXECUTE FUNCTION update_feed_summary_on_comment();
+
+-- Matches the feed's ORDER BY, so a page of public posts is a single index scan
+CREATE INDEX IF NOT EXISTS idx_posts_public_feed_order
+    ON posts(created_at DESC, id DESC)
+    WHERE visibility = 'public';
+
+COMMENT ON TABLE feed_workout_summaries IS 'Trigger-maintained reaction and comment counts per workout, read by the feed';
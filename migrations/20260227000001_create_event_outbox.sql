@@ -0,0 +1,51 @@
+-- Events written in the same transaction as the data they announce, and published to
+-- Redis only after that transaction committed. Rows left unpublished are retried by the scheduler,
+-- which also prunes published rows after a retention period.
+
+CREATE TABLE event_outbox (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channels TEXT[] NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    published_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_event_outbox_unpublished ON event_outbox(created_at) WHERE published_at IS NULL;
+-- Published rows are pruned after a retention period
+CREATE INDEX idx_event_outbox_published ON event_outbox(published_at) WHERE published_at IS NOT NULL;
+
+-- A workout scores at most once per game, so a retried upload cannot score it twice
+CREATE TEMPORARY TABLE rescored_games ON COMMIT DROP AS
+WITH duplicates AS (
+    DELETE FROM live_score_events e
+    USING live_score_events earlier
+    WHERE e.workout_data_id IS NOT NULL
+      AND e.game_id = earlier.game_id
+      AND e.workout_data_id = earlier.workout_data_id
+      AND (e.occurred_at, e.id) > (earlier.occurred_at, earlier.id)
+    RETURNING e.game_id
+)
+SELECT DISTINCT game_id FROM duplicates;
+
+-- Scores of the games that counted a workout twice, summed again from their remaining events
+UPDATE games g
+SET home_score = totals.home_total::INTEGER,
+    away_score = totals.away_total::INTEGER,
+    updated_at = NOW()
+FROM (
+    SELECT r.game_id,
+           TRUNC(COALESCE(SUM(lse.score_points) FILTER (WHERE lse.team_side = 'home'), 0)) AS home_total,
+           TRUNC(COALESCE(SUM(lse.score_points) FILTER (WHERE lse.team_side = 'away'), 0)) AS away_total
+    FROM rescored_games r
+    LEFT JOIN live_score_events lse ON lse.game_id = r.game_id
+    GROUP BY r.game_id
+) totals
+WHERE g.id = totals.game_id;
+
+CREATE UNIQUE INDEX idx_live_score_events_game_workout
+    ON live_score_events(game_id, workout_data_id)
+    WHERE workout_data_id IS NOT NULL;
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('event_outbox', '40 * * * * *', 'Publish outbox events that were not published after their commit');
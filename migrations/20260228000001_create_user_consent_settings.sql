@@ -0,0 +1,14 @@
+-- Per-user data sharing consent; users without a row get the defaults.
+-- Users who turn off research_data_sharing are left out of every research dataset.
+CREATE TABLE IF NOT EXISTS user_consent_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    research_data_sharing BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Opted-out users are the exception, so the research queries only look them up
+CREATE INDEX IF NOT EXISTS idx_user_consent_settings_research_opt_out
+    ON user_consent_settings(user_id) WHERE NOT research_data_sharing;
+
+COMMENT ON COLUMN user_consent_settings.research_data_sharing IS 'Whether anonymized aggregates may include this user''s workouts';
@@ -0,0 +1,14 @@
+-- Optional comeback rule: workouts of the trailing team in the final quarter of a game
+-- score with this multiplier. NULL disables the rule for the league.
+ALTER TABLE leagues
+    ADD COLUMN IF NOT EXISTS comeback_multiplier REAL
+        CHECK (comeback_multiplier > 1.0 AND comeback_multiplier <= 1.5);
+
+-- Score events keep the applied multiplier and the bonus separately, so the bonus can be
+-- explained in the game ticker and told apart from the workout's own points
+ALTER TABLE live_score_events
+    ADD COLUMN IF NOT EXISTS comeback_multiplier REAL,
+    ADD COLUMN IF NOT EXISTS comeback_bonus REAL NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN leagues.comeback_multiplier IS 'Score multiplier for the trailing team in the final quarter of a game; NULL disables it';
+COMMENT ON COLUMN live_score_events.comeback_bonus IS 'Points added by the comeback multiplier; included in score_points';
@@ -0,0 +1,51 @@
+-- Limited-use boosters teams earn from achievements and activate during a live game
+-- A booster is used once: available -> active (in a game) -> consumed, or expired if the game ends first
+
+CREATE TABLE team_boosters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    booster_type VARCHAR(30) NOT NULL CHECK (booster_type IN ('double_next_workout', 'shield')),
+    status VARCHAR(20) NOT NULL DEFAULT 'available' CHECK (status IN ('available', 'active', 'consumed', 'expired')),
+    earned_for VARCHAR(30) NOT NULL,
+    earned_in_game_id UUID REFERENCES games(id) ON DELETE SET NULL,
+    earned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    game_id UUID REFERENCES games(id) ON DELETE SET NULL,
+    activated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    activated_at TIMESTAMPTZ,
+    consumed_at TIMESTAMPTZ,
+    score_event_id UUID REFERENCES live_score_events(id) ON DELETE SET NULL,
+    bonus_points REAL NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_team_boosters_inventory ON team_boosters(team_id, booster_type) WHERE status = 'available';
+CREATE INDEX idx_team_boosters_game ON team_boosters(game_id) WHERE game_id IS NOT NULL;
+-- An achievement earns a team one booster per game
+CREATE UNIQUE INDEX idx_team_boosters_earned ON team_boosters(earned_in_game_id, team_id, earned_for);
+-- A team can run each kind of booster only once per game
+CREATE UNIQUE INDEX idx_team_boosters_one_per_game ON team_boosters(game_id, team_id, booster_type)
+    WHERE game_id IS NOT NULL;
+
+-- Every change to a booster, for support and fairness reviews
+CREATE TABLE team_booster_audit (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booster_id UUID NOT NULL REFERENCES team_boosters(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    game_id UUID REFERENCES games(id) ON DELETE SET NULL,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(20) NOT NULL CHECK (action IN ('earned', 'activated', 'applied', 'expired')),
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_team_booster_audit_booster ON team_booster_audit(booster_id, created_at);
+CREATE INDEX idx_team_booster_audit_team ON team_booster_audit(team_id, created_at DESC);
+
+-- Score events carry the points a booster added, like the comeback bonus
+ALTER TABLE live_score_events
+    ADD COLUMN IF NOT EXISTS booster_bonus REAL NOT NULL DEFAULT 0;
+
+COMMENT ON TABLE team_boosters IS 'Single-use boosters of a team; applied by the scoring engine while active in a game';
+COMMENT ON COLUMN team_boosters.earned_for IS 'Achievement the booster was awarded for, e.g. game_win or full_squad';
+COMMENT ON COLUMN team_boosters.score_event_id IS 'Score event the booster was applied to';
+COMMENT ON COLUMN team_boosters.bonus_points IS 'Points the booster added when it was applied';
+COMMENT ON COLUMN live_score_events.booster_bonus IS 'Points added by a team booster; included in score_points';
@@ -0,0 +1,51 @@
+-- Cooperative league quests: every member's workouts count toward one collective target
+-- (e.g. "Climb Everest", 8848 points), with milestones announced as they are reached
+
+CREATE TABLE league_quests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    league_id UUID NOT NULL REFERENCES leagues(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    target_points REAL NOT NULL CHECK (target_points > 0),
+    unit_label VARCHAR(20) NOT NULL DEFAULT 'points',
+    milestone_percents INTEGER[] NOT NULL DEFAULT ARRAY[25, 50, 75, 100],
+    starts_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ends_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT league_quests_window_check CHECK (ends_at IS NULL OR ends_at > starts_at)
+);
+
+CREATE INDEX idx_league_quests_league ON league_quests(league_id, starts_at DESC);
+CREATE INDEX idx_league_quests_open ON league_quests(league_id) WHERE completed_at IS NULL;
+
+-- Points each workout added to a quest; a workout counts once per quest
+CREATE TABLE league_quest_contributions (
+    quest_id UUID NOT NULL REFERENCES league_quests(id) ON DELETE CASCADE,
+    workout_data_id UUID NOT NULL REFERENCES workout_data(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    points REAL NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (quest_id, workout_data_id)
+);
+
+CREATE INDEX idx_league_quest_contributions_user ON league_quest_contributions(quest_id, user_id);
+
+-- Milestones reached, each once, with the workout that got the league there
+CREATE TABLE league_quest_milestones (
+    quest_id UUID NOT NULL REFERENCES league_quests(id) ON DELETE CASCADE,
+    percent INTEGER NOT NULL CHECK (percent > 0 AND percent <= 100),
+    progress_points REAL NOT NULL,
+    reached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    workout_data_id UUID REFERENCES workout_data(id) ON DELETE SET NULL,
+    reached_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (quest_id, percent)
+);
+
+COMMENT ON TABLE league_quests IS 'Collective targets a whole league works toward alongside its games';
+COMMENT ON COLUMN league_quests.unit_label IS 'How progress is shown, e.g. "m" for a climb; progress is always workout points';
+COMMENT ON COLUMN league_quests.milestone_percents IS 'Shares of the target at which the league is told about its progress';
+COMMENT ON COLUMN league_quests.completed_at IS 'When the target was reached; completed quests take no further workouts';
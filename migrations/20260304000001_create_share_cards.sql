@@ -0,0 +1,26 @@
+-- Shareable PNG cards (badges, game results, personal records) users post to other social networks
+-- Content is captured when the card is requested; a render job draws it and stores the image in MinIO
+
+CREATE TABLE share_cards (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    card_type VARCHAR(20) NOT NULL CHECK (card_type IN ('badge', 'game_result', 'personal_record')),
+    subject_id UUID NOT NULL,
+    content JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'rendered', 'failed')),
+    object_key TEXT,
+    rendered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_share_cards_subject ON share_cards(user_id, card_type, subject_id);
+CREATE INDEX idx_share_cards_pending ON share_cards(updated_at) WHERE status = 'pending';
+
+COMMENT ON TABLE share_cards IS 'Shareable result cards, served publicly at /share/cards/{id} once rendered';
+COMMENT ON COLUMN share_cards.subject_id IS 'Game of a badge or game result card, workout of a personal record card';
+COMMENT ON COLUMN share_cards.content IS 'Text drawn on the card, captured when it was requested';
+COMMENT ON COLUMN share_cards.object_key IS 'MinIO key of the rendered PNG';
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('share_card_render', '*/15 * * * * *', 'Render requested badge, game result and personal record cards');
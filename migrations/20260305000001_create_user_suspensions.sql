@@ -0,0 +1,21 @@
+-- Time-boxed suspensions imposed by admins. While one is in effect the user can still sign in
+-- and read, but cannot upload or take social actions, and workouts recorded during it don't
+-- count toward games or quests. A suspension without an end is a soft ban until lifted.
+
+CREATE TABLE user_suspensions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ends_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    lifted_at TIMESTAMPTZ,
+    lifted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    lift_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT user_suspensions_window_check CHECK (ends_at IS NULL OR ends_at > starts_at)
+);
+
+CREATE INDEX idx_user_suspensions_user ON user_suspensions(user_id, starts_at DESC);
+CREATE INDEX idx_user_suspensions_unlifted ON user_suspensions(user_id) WHERE lifted_at IS NULL;
@@ -0,0 +1,3 @@
+-- Work/rest intervals detected from the heart rate while a workout is processed,
+-- e.g. "8 × 2min high intensity". NULL for steady workouts and ones processed before detection.
+ALTER TABLE workout_data ADD COLUMN intervals JSONB;
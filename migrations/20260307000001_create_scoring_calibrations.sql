@@ -0,0 +1,17 @@
+-- Personal scoring calibration from perceived exertion: effort ratings given in scoring
+-- feedback nudge a user's points up or down (at most 15%), recalculated weekly
+
+CREATE TABLE user_scoring_calibrations (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    factor REAL NOT NULL DEFAULT 1.0 CHECK (factor BETWEEN 0.85 AND 1.15),
+    rated_workouts INTEGER NOT NULL DEFAULT 0,
+    -- Average of effort rating minus the effort the heart rate implies, on the 0-10 scale
+    mean_effort_gap REAL,
+    calculated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Factor a workout was scored with, so calibration can work from uncalibrated points
+ALTER TABLE workout_data ADD COLUMN scoring_calibration REAL NOT NULL DEFAULT 1.0;
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('scoring_calibration', '0 30 3 * * Mon', 'Calibrate personal scoring from effort ratings');
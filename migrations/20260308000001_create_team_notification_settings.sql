@@ -0,0 +1,10 @@
+-- Team events captains choose to notify their members about; teams without a row get all of them
+
+CREATE TABLE team_notification_settings (
+    team_id UUID PRIMARY KEY REFERENCES teams(id) ON DELETE CASCADE,
+    game_start_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    fell_behind_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    member_milestone_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,25 @@
+-- Do-not-disturb windows: push notifications arriving in a user's quiet hours wait for the end
+-- of the window; the notification center still gets them right away
+
+ALTER TABLE notification_preferences
+    ADD COLUMN quiet_hours_start TIME,
+    ADD COLUMN quiet_hours_end TIME,
+    ADD COLUMN quiet_hours_timezone TEXT NOT NULL DEFAULT 'UTC',
+    ADD CONSTRAINT quiet_hours_complete CHECK ((quiet_hours_start IS NULL) = (quiet_hours_end IS NULL));
+
+CREATE TABLE deferred_push_notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    data JSONB,
+    category TEXT,
+    deliver_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_deferred_push_notifications_due ON deferred_push_notifications(deliver_at) WHERE sent_at IS NULL;
+
+INSERT INTO scheduled_jobs (job_name, cron_expression, description) VALUES
+    ('deferred_push', '45 * * * * *', 'Send push notifications held back by quiet hours');
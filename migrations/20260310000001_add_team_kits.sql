@@ -0,0 +1,15 @@
+-- Team kits: an away color for games where the team color clashes with the opponent's,
+-- and an emoji shown next to the team name
+ALTER TABLE teams
+    ADD COLUMN away_color VARCHAR(7),
+    ADD COLUMN kit_emoji VARCHAR(32),
+    ADD CONSTRAINT valid_away_color CHECK (away_color ~ '^#[0-9A-Fa-f]{6}$');
+
+-- Colors the teams wear in a game, fixed when it starts so the ticker doesn't change
+-- colors mid-game when a team edits its kit
+CREATE TABLE game_kits (
+    game_id UUID PRIMARY KEY REFERENCES games(id) ON DELETE CASCADE,
+    home_color VARCHAR(7) NOT NULL,
+    away_color VARCHAR(7) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,21 @@
+-- Names league admins give to game weeks of a season, e.g. "Derby Week"
+CREATE TABLE game_week_names (
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    week_number INTEGER NOT NULL,
+    name VARCHAR(60) NOT NULL,
+    description TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (season_id, week_number)
+);
+
+-- Title, venue, description and banner of a single fixture
+CREATE TABLE fixture_flavors (
+    game_id UUID PRIMARY KEY REFERENCES games(id) ON DELETE CASCADE,
+    title VARCHAR(80),
+    venue_name VARCHAR(80),
+    description TEXT,
+    banner_url TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Ephemeral sandbox organizations for QA and app store review, torn down once expired.
+-- Their users and league are listed so teardown removes everything that was provisioned.
+CREATE TABLE sandboxes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    league_id UUID NOT NULL REFERENCES leagues(id) ON DELETE CASCADE,
+    user_ids UUID[] NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_sandboxes_expires_at ON sandboxes(expires_at);
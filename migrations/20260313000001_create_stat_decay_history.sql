@@ -0,0 +1,14 @@
+-- Weekly decay applied to avatar stats, so the player card reflects current fitness.
+-- One row per user and week keeps the job from decaying a week twice.
+CREATE TABLE stat_decay_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    week_start DATE NOT NULL,
+    weekly_percent REAL NOT NULL,
+    stamina_before REAL NOT NULL,
+    stamina_after REAL NOT NULL,
+    strength_before REAL NOT NULL,
+    strength_after REAL NOT NULL,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, week_start)
+);
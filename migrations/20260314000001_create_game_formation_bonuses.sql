@@ -0,0 +1,12 @@
+-- Team bonuses for covering several workout archetypes (cardio, strength, HIIT) in a game.
+-- One row per game and team, written at evaluation, explains the bonus in the game summary.
+CREATE TABLE game_formation_bonuses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    archetypes TEXT[] NOT NULL,
+    bonus_points REAL NOT NULL,
+    description TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (game_id, team_id)
+);
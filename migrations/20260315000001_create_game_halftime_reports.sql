@@ -0,0 +1,13 @@
+-- Halftime report of a live game, written once at the midpoint of its window:
+-- scores, top contributors and each team's pace against previous meetings.
+CREATE TABLE game_halftime_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL UNIQUE REFERENCES games(id) ON DELETE CASCADE,
+    home_score INTEGER NOT NULL,
+    away_score INTEGER NOT NULL,
+    top_contributors JSONB NOT NULL DEFAULT '[]',
+    home_pace JSONB NOT NULL,
+    away_pace JSONB NOT NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Per-user choices for the feed posts workout uploads create; users without a row get the defaults.
+-- 'always' publishes the post, 'ask' keeps it private until the user publishes it from the upload
+-- prompt, 'never' keeps it private.
+CREATE TABLE IF NOT EXISTS user_posting_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    workout_auto_post VARCHAR(10) NOT NULL DEFAULT 'always'
+        CHECK (workout_auto_post IN ('always', 'ask', 'never')),
+    caption_template TEXT CHECK (char_length(caption_template) <= 280),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN user_posting_settings.caption_template IS 'Default caption of workout posts, with {activity}, {minutes}, {points} and {calories} placeholders';
@@ -0,0 +1,14 @@
+-- Posts waiting to be published, e.g. so photos from the camera roll can be added to a workout
+-- post later. The post itself stays private, which keeps it out of every feed, until it is
+-- published by its author or at publish_at.
+CREATE TABLE post_drafts (
+    post_id UUID PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    publish_visibility post_visibility NOT NULL DEFAULT 'public',
+    publish_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_post_drafts_publish_at ON post_drafts(publish_at) WHERE publish_at IS NOT NULL;
+
+COMMENT ON COLUMN post_drafts.publish_visibility IS 'Visibility the post gets once published';
+COMMENT ON COLUMN post_drafts.publish_at IS 'When the post is published automatically; NULL waits for the author';
@@ -0,0 +1,33 @@
+-- Slack and Microsoft Teams incoming webhooks of a league, at most one per provider.
+-- Every evaluated game of the league is posted to them as a result card.
+CREATE TABLE league_chat_webhooks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    league_id UUID NOT NULL REFERENCES leagues(id) ON DELETE CASCADE,
+    provider VARCHAR(10) NOT NULL CHECK (provider IN ('slack', 'teams')),
+    webhook_url TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_delivered_at TIMESTAMPTZ,
+    last_error TEXT,
+    last_error_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (league_id, provider)
+);
+
+-- One result card per webhook and game, retried with backoff until it goes through or runs
+-- out of attempts
+CREATE TABLE league_webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    webhook_id UUID NOT NULL REFERENCES league_chat_webhooks(id) ON DELETE CASCADE,
+    game_id UUID NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (webhook_id, game_id)
+);
+
+CREATE INDEX idx_league_webhook_deliveries_pending
+    ON league_webhook_deliveries(next_attempt_at) WHERE delivered_at IS NULL;
@@ -0,0 +1,46 @@
+-- OIDC provider an organization's members sign in with
+CREATE TABLE organization_sso_providers (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    issuer_url TEXT NOT NULL,
+    client_id VARCHAR(255) NOT NULL,
+    -- Claim of the ID token listing the user's directory groups
+    groups_claim VARCHAR(100) NOT NULL DEFAULT 'groups',
+    -- Email domains new accounts are created for on first login; empty allows any domain
+    allowed_email_domains TEXT[] NOT NULL DEFAULT '{}',
+    jit_provisioning BOOLEAN NOT NULL DEFAULT TRUE,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    -- SHA-256 of the bearer token the directory deactivates users with
+    scim_token_hash VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_organization_sso_providers_scim_token
+    ON organization_sso_providers(scim_token_hash) WHERE scim_token_hash IS NOT NULL;
+
+-- Directory groups whose members join a team on login
+CREATE TABLE organization_sso_group_mappings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    group_name VARCHAR(255) NOT NULL,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (organization_id, group_name)
+);
+
+-- Directory accounts of an organization and the users they sign in as
+CREATE TABLE organization_sso_users (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- The directory's stable account id (`sub` claim)
+    subject VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email VARCHAR(255),
+    -- Set when the user left the company directory
+    deactivated_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, subject),
+    UNIQUE (organization_id, user_id)
+);
+
+CREATE INDEX idx_organization_sso_users_user_id ON organization_sso_users(user_id);
@@ -0,0 +1,26 @@
+-- League rules page
+-- Admins write the parts of the rules the code doesn't know about; caps and the comeback rule
+-- are read from the league's configuration when the page is served, so they can't go stale.
+
+CREATE TABLE IF NOT EXISTS league_rules (
+    league_id UUID PRIMARY KEY REFERENCES leagues(id) ON DELETE CASCADE,
+    scoring_summary TEXT NOT NULL,
+    forfeit_policy TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS league_transfer_windows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    league_id UUID NOT NULL REFERENCES league_rules(league_id) ON DELETE CASCADE,
+    opens_at TIMESTAMPTZ NOT NULL,
+    closes_at TIMESTAMPTZ NOT NULL,
+    note TEXT,
+    CHECK (closes_at > opens_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_league_transfer_windows_league ON league_transfer_windows(league_id, opens_at);
+
+COMMENT ON TABLE league_rules IS 'Admin-written rules of a league, shown on its rules page';
+COMMENT ON TABLE league_transfer_windows IS 'Periods in which players may move between the teams of a league';
@@ -0,0 +1,18 @@
+-- Logged in devices, so users can see where they are signed in and log out a lost or stolen one.
+-- A session is a refresh token family: its id is the family_id of the tokens issued to the device,
+-- and access tokens carry it, so revoking the session also stops its unexpired access tokens.
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id TEXT,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user ON user_sessions(user_id) WHERE revoked_at IS NULL;
+
+COMMENT ON COLUMN user_sessions.device_id IS 'Id the app sent in the X-Device-Id header when logging in';
+COMMENT ON COLUMN user_sessions.expires_at IS 'Expiry of the latest refresh token of the session';
@@ -0,0 +1,15 @@
+-- Optional anticheat review lock: a finished game isn't evaluated while workouts that scored in it
+-- have pending suspicious-workout reports, for at most this many hours after the game ended.
+-- NULL disables the lock for the league.
+ALTER TABLE leagues
+    ADD COLUMN IF NOT EXISTS review_lock_hours INTEGER
+        CHECK (review_lock_hours BETWEEN 1 AND 168);
+
+-- Finished games evaluation held back, so they're evaluated once their reports are resolved or the lock ends
+CREATE TABLE IF NOT EXISTS game_review_holds (
+    game_id UUID PRIMARY KEY REFERENCES games(id) ON DELETE CASCADE,
+    held_since TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN leagues.review_lock_hours IS 'Hours a finished game waits for pending reports of its workouts before it is evaluated anyway; NULL disables the lock';
+COMMENT ON TABLE game_review_holds IS 'Finished games waiting for pending workout reports before evaluation';
@@ -0,0 +1,13 @@
+-- Accounts whose owners asked for deletion. The account is deactivated right away and purged
+-- with all its data after the grace period; logging in again before then cancels the deletion.
+CREATE TABLE IF NOT EXISTS account_deletions (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    previous_status VARCHAR(20) NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    purge_after TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_deletions_purge_after ON account_deletions (purge_after);
+
+COMMENT ON TABLE account_deletions IS 'Requested account deletions, purged by the scheduler once purge_after has passed';
+COMMENT ON COLUMN account_deletions.previous_status IS 'User status restored when the deletion is cancelled';
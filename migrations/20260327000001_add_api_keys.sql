@@ -0,0 +1,21 @@
+-- API keys for server-to-server integrations, e.g. gyms pushing workouts from their own backend.
+-- A key acts as its user within its scopes. Only the hash of a key is stored.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user_id ON api_keys (user_id);
+
+COMMENT ON TABLE api_keys IS 'Server-to-server API keys, sent in the X-Api-Key header';
+COMMENT ON COLUMN api_keys.key_prefix IS 'Start of the key, shown to tell keys apart';
+COMMENT ON COLUMN api_keys.scopes IS 'What the key may do, e.g. workouts:write';
@@ -0,0 +1,30 @@
+-- Login attempts, so users can review where their account was accessed from and
+-- suspicious logins can be told apart from the usual devices
+CREATE TABLE IF NOT EXISTS login_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    username TEXT NOT NULL,
+    ip_address TEXT,
+    user_agent TEXT,
+    succeeded BOOLEAN NOT NULL,
+    failure_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_attempts_user ON login_attempts(user_id, created_at DESC);
+
+COMMENT ON COLUMN login_attempts.user_id IS 'NULL when the username did not belong to an account';
+COMMENT ON COLUMN login_attempts.failure_reason IS 'invalid_credentials or locked; NULL for successful logins';
+
+-- Security relevant events for admins to review: lockouts, unlocks and suspicious logins
+CREATE TABLE IF NOT EXISTS security_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event_type TEXT NOT NULL,
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    ip_address TEXT,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_security_events_created ON security_events(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_security_events_user ON security_events(user_id, created_at DESC);
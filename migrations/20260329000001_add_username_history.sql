@@ -0,0 +1,14 @@
+-- Username changes, so users can change their name at most once per cooldown period and
+-- admins can trace who used a name before
+CREATE TABLE IF NOT EXISTS username_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    old_username VARCHAR(255) NOT NULL,
+    new_username VARCHAR(255) NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_username_history_user ON username_history(user_id, changed_at DESC);
+
+-- Availability checks compare usernames regardless of case
+CREATE INDEX IF NOT EXISTS idx_users_username_lower ON users(LOWER(username));
@@ -0,0 +1,24 @@
+-- League-wide season aggregates for media and recap pages. Each evaluated game is added once,
+-- so the stats are read without scanning the season's games and workouts.
+CREATE TABLE IF NOT EXISTS season_stats (
+    season_id UUID PRIMARY KEY REFERENCES league_seasons(id) ON DELETE CASCADE,
+    games_counted INTEGER NOT NULL DEFAULT 0,
+    total_workouts INTEGER NOT NULL DEFAULT 0,
+    total_zone_minutes DOUBLE PRECISION NOT NULL DEFAULT 0,
+    total_points BIGINT NOT NULL DEFAULT 0,
+    biggest_blowout_game_id UUID REFERENCES games(id) ON DELETE SET NULL,
+    biggest_blowout_margin INTEGER NOT NULL,
+    closest_game_id UUID REFERENCES games(id) ON DELETE SET NULL,
+    closest_game_margin INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Games already added to their season's stats, so a game is never counted twice
+CREATE TABLE IF NOT EXISTS season_stats_games (
+    game_id UUID PRIMARY KEY REFERENCES games(id) ON DELETE CASCADE,
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    counted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN season_stats.total_points IS 'Points of both teams over all counted games';
+COMMENT ON COLUMN season_stats.total_zone_minutes IS 'Heart rate zone minutes of the workouts credited to counted games';
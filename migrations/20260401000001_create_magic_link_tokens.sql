@@ -0,0 +1,12 @@
+-- Single-use tokens behind the links of passwordless sign-in emails. Only a hash of each token
+-- is stored; requesting a new link invalidates the earlier ones of the user.
+CREATE TABLE magic_link_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_magic_link_tokens_user ON magic_link_tokens(user_id);
@@ -0,0 +1,29 @@
+-- VT1/VT2 thresholds estimated from a user's recent workouts by the weekly detection job. Users
+-- accept or decline a proposal; a newer proposal supersedes a pending one.
+CREATE TABLE threshold_proposals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    vt1_threshold INTEGER,
+    vt2_threshold INTEGER,
+    -- The profile's values when the proposal was made
+    current_vt1_threshold INTEGER,
+    current_vt2_threshold INTEGER,
+    -- Low-drift steady workouts behind the VT1 estimate, sustained efforts behind the VT2 estimate
+    aerobic_workouts INTEGER NOT NULL DEFAULT 0,
+    sustained_workouts INTEGER NOT NULL DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'accepted', 'declined', 'superseded')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at TIMESTAMPTZ,
+    CONSTRAINT threshold_proposal_has_value CHECK (vt1_threshold IS NOT NULL OR vt2_threshold IS NOT NULL)
+);
+
+CREATE INDEX idx_threshold_proposals_user ON threshold_proposals(user_id, created_at DESC);
+CREATE UNIQUE INDEX idx_threshold_proposals_one_pending ON threshold_proposals(user_id) WHERE status = 'pending';
+
+-- Where the profile's VT1 and VT2 come from: the heart rate reserve (max and resting heart rate)
+-- or an accepted proposal, which is referenced
+ALTER TABLE user_health_profiles
+    ADD COLUMN vt1_source VARCHAR(20) NOT NULL DEFAULT 'hr_reserve' CHECK (vt1_source IN ('hr_reserve', 'detected')),
+    ADD COLUMN vt2_source VARCHAR(20) NOT NULL DEFAULT 'hr_reserve' CHECK (vt2_source IN ('hr_reserve', 'detected')),
+    ADD COLUMN threshold_proposal_id UUID REFERENCES threshold_proposals(id) ON DELETE SET NULL;
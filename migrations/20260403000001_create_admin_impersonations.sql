@@ -0,0 +1,13 @@
+-- Audit trail of support staff acting as a user. Every impersonation token an admin is issued
+-- is recorded with the reason given, before the token is handed out.
+CREATE TABLE admin_impersonations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admin_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_admin_impersonations_user ON admin_impersonations(user_id, created_at DESC);
+CREATE INDEX idx_admin_impersonations_admin ON admin_impersonations(admin_id, created_at DESC);
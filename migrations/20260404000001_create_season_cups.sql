@@ -0,0 +1,38 @@
+-- Knockout cups played alongside a league's regular season. Cup games live in the games table
+-- like league games, so the live game engine starts, scores and evaluates them, but they are
+-- marked with their cup and don't count towards the standings.
+CREATE TABLE season_cups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    season_id UUID NOT NULL REFERENCES league_seasons(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    -- First round kickoff; later rounds follow every round_interval_hours
+    start_date TIMESTAMPTZ NOT NULL,
+    round_interval_hours INTEGER NOT NULL DEFAULT 168 CHECK (round_interval_hours BETWEEN 1 AND 720),
+    status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'finished')),
+    winner_team_id UUID REFERENCES teams(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_season_cups_season ON season_cups(season_id);
+
+-- Seed 1 is the strongest team; the top seeds get the byes of an uneven draw
+CREATE TABLE season_cup_teams (
+    cup_id UUID NOT NULL REFERENCES season_cups(id) ON DELETE CASCADE,
+    team_id UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    seed INTEGER NOT NULL CHECK (seed >= 1),
+    PRIMARY KEY (cup_id, team_id),
+    UNIQUE (cup_id, seed)
+);
+
+ALTER TABLE games
+    ADD COLUMN cup_id UUID REFERENCES season_cups(id) ON DELETE CASCADE,
+    ADD COLUMN cup_round INTEGER,
+    -- Place of the game in the round's bracket, counted from the top
+    ADD COLUMN cup_slot INTEGER,
+    ADD CONSTRAINT games_cup_fixture CHECK (
+        (cup_id IS NULL AND cup_round IS NULL AND cup_slot IS NULL)
+        OR (cup_id IS NOT NULL AND cup_round >= 1 AND cup_slot >= 0)
+    );
+
+CREATE UNIQUE INDEX idx_games_cup_slot ON games(cup_id, cup_round, cup_slot) WHERE cup_id IS NOT NULL;
@@ -0,0 +1,23 @@
+-- Outcome of each workout the app tried to upload, so support can tell why a workout didn't count
+-- without looking into the database. Repeated attempts of the same workout with the same outcome
+-- (a device re-checking its history on every sync) bump one row instead of adding new ones.
+CREATE TABLE workout_upload_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id VARCHAR(255),
+    -- The app's id of the workout
+    workout_uuid VARCHAR(255) NOT NULL,
+    outcome VARCHAR(20) NOT NULL
+        CHECK (outcome IN ('accepted', 'duplicate', 'out_of_window', 'flagged', 'rejected')),
+    detail TEXT,
+    workout_data_id UUID REFERENCES workout_data(id) ON DELETE SET NULL,
+    workout_start TIMESTAMPTZ,
+    workout_end TIMESTAMPTZ,
+    attempt_count INTEGER NOT NULL DEFAULT 1,
+    first_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, workout_uuid, outcome)
+);
+
+CREATE INDEX idx_workout_upload_attempts_user ON workout_upload_attempts(user_id, last_attempt_at DESC);
+CREATE INDEX idx_workout_upload_attempts_last_attempt ON workout_upload_attempts(last_attempt_at);
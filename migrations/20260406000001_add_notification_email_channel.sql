@@ -0,0 +1,7 @@
+-- Email as a notification channel next to in-app and push, for jobs that reach users by email
+-- (inactivity nudges); users turn it off in their notification preferences
+
+ALTER TABLE notification_preferences
+    ADD COLUMN IF NOT EXISTS email_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+
+COMMENT ON COLUMN notification_preferences.email_enabled IS 'Receive notifications by email where a job offers it';
@@ -0,0 +1,8 @@
+-- When a user proved they own their email: by opening a sign-in link, resetting their password
+-- or signing up through a provider that verified it. Apple and Google logins only take over
+-- existing accounts with a verified email; others have to link the login while signed in.
+-- Existing accounts start unverified, since earlier provider logins linked them unchecked.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.email_verified_at IS 'When the user proved they own their email; NULL if they never did';
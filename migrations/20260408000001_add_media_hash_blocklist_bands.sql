@@ -0,0 +1,15 @@
+-- Indexed lookup of blocked hashes near an upload.
+-- Each 64-bit hash is split into four 16-bit bands. Hashes within the similarity threshold
+-- (10 bits) differ in at most 2 bits of at least one band, so only entries with a band close
+-- to the upload's are candidates, and those are found through the band indexes.
+
+ALTER TABLE media_hash_blocklist
+    ADD COLUMN IF NOT EXISTS phash_band_0 INTEGER GENERATED ALWAYS AS ((phash & 65535)::int) STORED,
+    ADD COLUMN IF NOT EXISTS phash_band_1 INTEGER GENERATED ALWAYS AS (((phash >> 16) & 65535)::int) STORED,
+    ADD COLUMN IF NOT EXISTS phash_band_2 INTEGER GENERATED ALWAYS AS (((phash >> 32) & 65535)::int) STORED,
+    ADD COLUMN IF NOT EXISTS phash_band_3 INTEGER GENERATED ALWAYS AS (((phash >> 48) & 65535)::int) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_media_hash_blocklist_band_0 ON media_hash_blocklist(phash_band_0);
+CREATE INDEX IF NOT EXISTS idx_media_hash_blocklist_band_1 ON media_hash_blocklist(phash_band_1);
+CREATE INDEX IF NOT EXISTS idx_media_hash_blocklist_band_2 ON media_hash_blocklist(phash_band_2);
+CREATE INDEX IF NOT EXISTS idx_media_hash_blocklist_band_3 ON media_hash_blocklist(phash_band_3);
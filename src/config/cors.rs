@@ -0,0 +1,82 @@
+use serde::Deserialize;
+
+/// Browser origins allowed to call the API. An entry is either an exact origin such as
+/// `https://riina.fly.dev` or a wildcard subdomain such as `https://*.riina.app`, which
+/// matches any subdomain (but not the bare domain) with the same scheme and port.
+#[derive(Deserialize, Debug, Clone)]
+pub struct CorsSettings {
+    #[serde(default = "default_allowed_origins")]
+    pub allowed_origins: Vec<String>,
+}
+
+fn default_allowed_origins() -> Vec<String> {
+    [
+        "http://localhost:3000",
+        "http://localhost:3001",
+        "https://riina.fly.dev",
+        "https://evolveme-admin.fly.dev",
+        "https://riina-dev.fly.dev",
+        "https://evolveme-admin-dev.fly.dev",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for CorsSettings {
+    fn default() -> Self {
+        Self {
+            allowed_origins: default_allowed_origins(),
+        }
+    }
+}
+
+impl CorsSettings {
+    /// Rejects entries that can't be matched against an `Origin` header, so a typo fails at
+    /// startup instead of silently locking browsers out.
+    pub fn validate(&self) -> Result<(), String> {
+        for entry in &self.allowed_origins {
+            let Some((scheme, host)) = entry.split_once("://") else {
+                return Err(format!("CORS origin '{}' is missing a scheme", entry));
+            };
+            if scheme.is_empty() || host.is_empty() || host.contains('/') {
+                return Err(format!("CORS origin '{}' must be scheme://host[:port]", entry));
+            }
+            let domain = host.strip_prefix("*.").unwrap_or(host);
+            if domain.is_empty() || domain.contains('*') {
+                return Err(format!(
+                    "CORS origin '{}' may only use a wildcard as the leading subdomain",
+                    entry
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| origin_matches(allowed, origin))
+    }
+}
+
+fn origin_matches(allowed: &str, origin: &str) -> bool {
+    let Some((prefix, suffix)) = allowed.split_once("*.") else {
+        return allowed.eq_ignore_ascii_case(origin);
+    };
+
+    let origin = origin.to_ascii_lowercase();
+    let (prefix, suffix) = (prefix.to_ascii_lowercase(), suffix.to_ascii_lowercase());
+    let Some(subdomain) = origin
+        .strip_prefix(&prefix)
+        .and_then(|rest| rest.strip_suffix(&suffix))
+        .and_then(|rest| rest.strip_suffix('.'))
+    else {
+        return false;
+    };
+
+    !subdomain.is_empty()
+        && subdomain.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+use secrecy::SecretString;
+
+/// Settings of the transactional email API. Without an API url emails are only logged, which
+/// is what local runs and tests use.
+#[derive(Deserialize, Debug, Clone)]
+pub struct EmailSettings {
+    #[serde(default)]
+    pub api_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<SecretString>,
+    #[serde(default = "default_sender")]
+    pub sender: String,
+    /// Page the password reset link points to; the token is appended as a query parameter
+    #[serde(default = "default_password_reset_url")]
+    pub password_reset_url: String,
+    /// Page the link of a sign-in email points to; the token is appended as a query parameter
+    #[serde(default = "default_magic_link_url")]
+    pub magic_link_url: String,
+}
+
+fn default_sender() -> String {
+    "Riina <no-reply@riina.app>".to_string()
+}
+
+fn default_password_reset_url() -> String {
+    "https://riina.fly.dev/reset-password".to_string()
+}
+
+fn default_magic_link_url() -> String {
+    "https://riina.fly.dev/magic-link".to_string()
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self {
+            api_url: None,
+            api_key: None,
+            sender: default_sender(),
+            password_reset_url: default_password_reset_url(),
+            magic_link_url: default_magic_link_url(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use secrecy::SecretString;
+use serde::Deserialize;
+
+/// Settings of error reporting to Sentry or a compatible service. Without a DSN nothing is
+/// reported, which is what local runs and tests use.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ErrorReportingSettings {
+    #[serde(default)]
+    pub dsn: Option<SecretString>,
+    /// Environment reports are filed under, e.g. production or dev
+    #[serde(default = "default_environment")]
+    pub environment: String,
+    /// Release reports are attributed to, e.g. the deployed commit; defaults to the crate version
+    #[serde(default)]
+    pub release: Option<String>,
+    /// Share of errors reported, from 0.0 to 1.0
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f32,
+}
+
+fn default_environment() -> String {
+    "local".to_string()
+}
+
+fn default_sample_rate() -> f32 {
+    1.0
+}
+
+impl Default for ErrorReportingSettings {
+    fn default() -> Self {
+        Self {
+            dsn: None,
+            environment: default_environment(),
+            release: None,
+            sample_rate: default_sample_rate(),
+        }
+    }
+}
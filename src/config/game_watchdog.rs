@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+/// Settings of the watchdog that looks for games left unfinalized past their end time
+#[derive(Deserialize, Debug, Clone)]
+pub struct GameWatchdogSettings {
+    /// Minutes past its end time before a game that is not evaluated raises an alert
+    #[serde(default = "default_overdue_minutes")]
+    pub overdue_minutes: i64,
+    /// Finish and evaluate overdue games instead of only alerting
+    #[serde(default)]
+    pub auto_finalize: bool,
+}
+
+fn default_overdue_minutes() -> i64 {
+    15
+}
+
+impl Default for GameWatchdogSettings {
+    fn default() -> Self {
+        Self {
+            overdue_minutes: default_overdue_minutes(),
+            auto_finalize: false,
+        }
+    }
+}
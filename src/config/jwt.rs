@@ -1,17 +1,172 @@
-use secrecy::SecretString;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::errors::{Error, ErrorKind};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub struct JwtSettings {
+    /// Key new tokens are signed with
     pub secret: SecretString,
+    /// Sent as the `kid` header of tokens signed with `secret`
+    #[serde(default = "default_key_id")]
+    pub key_id: String,
+    /// Keys rotated out. They still verify the tokens they signed, so sessions survive a rotation;
+    /// drop them once those tokens have expired.
+    #[serde(default)]
+    pub previous_keys: Vec<JwtKey>,
+    #[serde(default = "default_issuer")]
+    pub issuer: String,
+    #[serde(default = "default_audience")]
+    pub audience: String,
     pub expiration_hours: i64,
+    /// How long a refresh token can be used to get a new access token
+    #[serde(default = "default_refresh_token_days")]
+    pub refresh_token_days: i64,
+    /// Until then, tokens from before issuers and audiences were checked are accepted without
+    /// those claims. Wrong values are refused all the same.
+    #[serde(default)]
+    pub legacy_tokens_until: Option<DateTime<Utc>>,
+}
+
+/// A signing key and the `kid` it is known by
+#[derive(Debug, Deserialize)]
+pub struct JwtKey {
+    pub id: String,
+    pub secret: SecretString,
+}
+
+impl JwtKey {
+    pub fn new(id: String, secret: String) -> Self {
+        Self {
+            id,
+            secret: SecretString::new(secret.into_boxed_str()),
+        }
+    }
+
+    /// Parse keys given as `kid=secret` pairs separated by commas, e.g. from `JWT_PREVIOUS_KEYS`
+    pub fn parse_list(value: &str) -> Result<Vec<JwtKey>, String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((id, secret)) if !id.trim().is_empty() && !secret.is_empty() => {
+                    Ok(JwtKey::new(id.trim().to_string(), secret.to_string()))
+                }
+                _ => Err("JWT keys must be given as kid=secret pairs".to_string()),
+            })
+            .collect()
+    }
+}
+
+fn default_key_id() -> String {
+    "primary".to_string()
+}
+
+fn default_issuer() -> String {
+    "riina-backend".to_string()
+}
+
+fn default_audience() -> String {
+    "riina-app".to_string()
+}
+
+fn default_refresh_token_days() -> i64 {
+    30
 }
 
 impl JwtSettings {
-    pub fn new(secret: String, expiration_hours: i64) -> Self {
+    pub fn new(secret: String, expiration_hours: i64, refresh_token_days: i64) -> Self {
         Self {
             secret: SecretString::new(secret.into_boxed_str()),
+            key_id: default_key_id(),
+            previous_keys: Vec::new(),
+            issuer: default_issuer(),
+            audience: default_audience(),
             expiration_hours,
+            refresh_token_days,
+            legacy_tokens_until: None,
+        }
+    }
+
+    /// Every key needs its own id, or tokens couldn't be told apart
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, key) in self.previous_keys.iter().enumerate() {
+            if key.id == self.key_id || self.previous_keys[..i].iter().any(|other| other.id == key.id) {
+                return Err(format!("JWT key id {} is used more than once", key.id));
+            }
         }
+        Ok(())
+    }
+
+    /// Sign claims with the current key
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, Error> {
+        let header = Header {
+            kid: Some(self.key_id.clone()),
+            ..Header::new(Algorithm::HS256)
+        };
+        encode(&header, claims, &EncodingKey::from_secret(self.secret.expose_secret().as_bytes()))
+    }
+
+    /// Decode a token signed with the current or a previous key, checking its expiry, issuer and audience
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T, Error> {
+        self.decode(token, self.validation())
     }
-}
\ No newline at end of file
+
+    /// Like `verify`, but also accepts expired tokens
+    pub fn verify_ignoring_expiry<T: DeserializeOwned>(&self, token: &str) -> Result<T, Error> {
+        let mut validation = self.validation();
+        validation.validate_exp = false;
+        validation.validate_nbf = false;
+        self.decode(token, validation)
+    }
+
+    /// Audience of guest spectator tokens. It differs from `audience`, so `verify` refuses them
+    /// wherever a user's token is expected.
+    pub fn spectator_audience(&self) -> String {
+        format!("{}:spectator", self.audience)
+    }
+
+    /// Like `verify`, for tokens issued to another audience than users
+    pub fn verify_for_audience<T: DeserializeOwned>(&self, token: &str, audience: &str) -> Result<T, Error> {
+        let mut validation = self.validation();
+        validation.set_audience(&[audience]);
+        self.decode(token, validation)
+    }
+
+    fn validation(&self) -> Validation {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        // Issuer and audience are checked whenever present, but only required once legacy tokens expired
+        if self.legacy_tokens_until.is_some_and(|until| Utc::now() < until) {
+            validation.set_required_spec_claims(&["exp"]);
+        } else {
+            validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+        }
+        validation
+    }
+
+    fn decode<T: DeserializeOwned>(&self, token: &str, validation: Validation) -> Result<T, Error> {
+        let secret = self.verifying_secret(decode_header(token)?.kid.as_deref())?;
+        decode::<T>(token, &DecodingKey::from_secret(secret.expose_secret().as_bytes()), &validation)
+            .map(|data| data.claims)
+    }
+
+    /// The key a token's `kid` names. Tokens without one predate key ids and were signed with the
+    /// current key.
+    fn verifying_secret(&self, kid: Option<&str>) -> Result<&SecretString, Error> {
+        match kid {
+            None => Ok(&self.secret),
+            Some(kid) if kid == self.key_id => Ok(&self.secret),
+            Some(kid) => self
+                .previous_keys
+                .iter()
+                .find(|key| key.id == kid)
+                .map(|key| &key.secret)
+                .ok_or_else(|| ErrorKind::InvalidToken.into()),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Deserialize;
+
+use crate::utils::client_ip::TrustedProxy;
+
+/// Lockout of accounts and addresses that keep failing to log in. Each lockout of the same
+/// account or address in a row lasts twice as long as the one before, up to the maximum.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LoginProtectionSettings {
+    /// Failed logins to one account within the window that lock it
+    #[serde(default = "default_max_failed_attempts")]
+    pub max_failed_attempts: u32,
+    /// Failed logins from one IP address within the window, to any account, that block it
+    #[serde(default = "default_max_failed_attempts_per_ip")]
+    pub max_failed_attempts_per_ip: u32,
+    /// How long failed logins count towards a lockout
+    #[serde(default = "default_failure_window_secs")]
+    pub failure_window_secs: u64,
+    /// Length of the first lockout
+    #[serde(default = "default_base_lockout_secs")]
+    pub base_lockout_secs: u64,
+    #[serde(default = "default_max_lockout_secs")]
+    pub max_lockout_secs: u64,
+    /// Password reset and sign-in emails that may be requested for one email address within
+    /// the email request window
+    #[serde(default = "default_max_email_requests_per_email")]
+    pub max_email_requests_per_email: u32,
+    /// Password reset and sign-in emails that may be requested from one IP address within the
+    /// email request window, whichever addresses they are for
+    #[serde(default = "default_max_email_requests_per_ip")]
+    pub max_email_requests_per_ip: u32,
+    #[serde(default = "default_email_request_window_secs")]
+    pub email_request_window_secs: u64,
+    /// Proxies in front of the app, as addresses or CIDR networks. Only their `X-Forwarded-For`
+    /// is believed; without any, the address of the connection is the client's.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+fn default_max_failed_attempts() -> u32 {
+    5
+}
+
+fn default_max_failed_attempts_per_ip() -> u32 {
+    20
+}
+
+fn default_failure_window_secs() -> u64 {
+    15 * 60
+}
+
+fn default_base_lockout_secs() -> u64 {
+    60
+}
+
+fn default_max_lockout_secs() -> u64 {
+    60 * 60
+}
+
+fn default_max_email_requests_per_email() -> u32 {
+    3
+}
+
+fn default_max_email_requests_per_ip() -> u32 {
+    10
+}
+
+fn default_email_request_window_secs() -> u64 {
+    60 * 60
+}
+
+impl Default for LoginProtectionSettings {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: default_max_failed_attempts(),
+            max_failed_attempts_per_ip: default_max_failed_attempts_per_ip(),
+            failure_window_secs: default_failure_window_secs(),
+            base_lockout_secs: default_base_lockout_secs(),
+            max_lockout_secs: default_max_lockout_secs(),
+            max_email_requests_per_email: default_max_email_requests_per_email(),
+            max_email_requests_per_ip: default_max_email_requests_per_ip(),
+            email_request_window_secs: default_email_request_window_secs(),
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl LoginProtectionSettings {
+    pub fn trusted_proxies(&self) -> Result<Vec<TrustedProxy>, String> {
+        self.trusted_proxies.iter().map(|proxy| TrustedProxy::parse(proxy)).collect()
+    }
+
+    /// Rejects trusted proxies that can't be parsed, so a typo fails at startup
+    pub fn validate(&self) -> Result<(), String> {
+        self.trusted_proxies().map(|_| ())
+    }
+}
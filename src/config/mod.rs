@@ -2,4 +2,13 @@ pub mod settings;
 pub mod jwt;
 pub mod redis;
 pub mod minio;
-pub mod ml;
\ No newline at end of file
+pub mod ml;
+pub mod game_watchdog;
+pub mod upload_limits;
+pub mod stat_decay;
+pub mod email;
+pub mod oauth;
+pub mod error_reporting;
+pub mod cors;
+pub mod login_protection;
+pub mod password_policy;
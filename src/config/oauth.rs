@@ -0,0 +1,36 @@
+use serde::Deserialize;
+
+/// Apps allowed to sign users in with Apple and Google. A provider without client ids is
+/// turned off.
+#[derive(Deserialize, Debug, Clone)]
+pub struct OAuthSettings {
+    /// Audiences accepted in Google ID tokens, i.e. the OAuth client ids of our apps
+    #[serde(default)]
+    pub google_client_ids: Vec<String>,
+    /// Audiences accepted in Apple identity tokens, i.e. the bundle / service ids of our apps
+    #[serde(default)]
+    pub apple_client_ids: Vec<String>,
+    #[serde(default = "default_google_jwks_url")]
+    pub google_jwks_url: String,
+    #[serde(default = "default_apple_jwks_url")]
+    pub apple_jwks_url: String,
+}
+
+fn default_google_jwks_url() -> String {
+    "https://www.googleapis.com/oauth2/v3/certs".to_string()
+}
+
+fn default_apple_jwks_url() -> String {
+    "https://appleid.apple.com/auth/keys".to_string()
+}
+
+impl Default for OAuthSettings {
+    fn default() -> Self {
+        Self {
+            google_client_ids: Vec::new(),
+            apple_client_ids: Vec::new(),
+            google_jwks_url: default_google_jwks_url(),
+            apple_jwks_url: default_apple_jwks_url(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+/// Rules new passwords have to follow. The breach check asks Have I Been Pwned with the
+/// first five characters of the password's SHA-1 hash only (k-anonymity), and lets the
+/// password through if the service can't be reached.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PasswordPolicySettings {
+    #[serde(default = "default_min_length")]
+    pub min_length: usize,
+    /// bcrypt ignores everything after 72 bytes
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+    /// Reject passwords from the built-in list of the most common ones
+    #[serde(default = "default_reject_common_passwords")]
+    pub reject_common_passwords: bool,
+    /// Reject passwords found in known data breaches
+    #[serde(default)]
+    pub breach_check: bool,
+    /// Range endpoint of the breach check, the hash prefix is appended
+    #[serde(default = "default_breach_check_url")]
+    pub breach_check_url: String,
+    #[serde(default = "default_breach_check_timeout_ms")]
+    pub breach_check_timeout_ms: u64,
+}
+
+fn default_min_length() -> usize {
+    8
+}
+
+fn default_max_length() -> usize {
+    72
+}
+
+fn default_reject_common_passwords() -> bool {
+    true
+}
+
+fn default_breach_check_url() -> String {
+    "https://api.pwnedpasswords.com/range/".to_string()
+}
+
+fn default_breach_check_timeout_ms() -> u64 {
+    2000
+}
+
+impl Default for PasswordPolicySettings {
+    fn default() -> Self {
+        Self {
+            min_length: default_min_length(),
+            max_length: default_max_length(),
+            reject_common_passwords: default_reject_common_passwords(),
+            breach_check: false,
+            breach_check_url: default_breach_check_url(),
+            breach_check_timeout_ms: default_breach_check_timeout_ms(),
+        }
+    }
+}
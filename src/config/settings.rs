@@ -4,10 +4,19 @@ use dotenv::dotenv;
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 
-use crate::config::jwt::JwtSettings;
+use crate::config::jwt::{JwtKey, JwtSettings};
 use crate::config::redis::RedisSettings;
 use crate::config::minio::MinIOSettings;
 use crate::config::ml::MLSettings;
+use crate::config::game_watchdog::GameWatchdogSettings;
+use crate::config::stat_decay::StatDecaySettings;
+use crate::config::upload_limits::UploadLimitsSettings;
+use crate::config::email::EmailSettings;
+use crate::config::oauth::OAuthSettings;
+use crate::config::error_reporting::ErrorReportingSettings;
+use crate::config::cors::CorsSettings;
+use crate::config::login_protection::LoginProtectionSettings;
+use crate::config::password_policy::PasswordPolicySettings;
 
 #[derive(Deserialize, Debug)]
 pub struct Settings{
@@ -17,6 +26,24 @@ pub struct Settings{
     pub redis: RedisSettings,
     pub minio: MinIOSettings,
     pub ml: MLSettings,
+    #[serde(default)]
+    pub game_watchdog: GameWatchdogSettings,
+    #[serde(default)]
+    pub upload_limits: UploadLimitsSettings,
+    #[serde(default)]
+    pub stat_decay: StatDecaySettings,
+    #[serde(default)]
+    pub email: EmailSettings,
+    #[serde(default)]
+    pub oauth: OAuthSettings,
+    #[serde(default)]
+    pub error_reporting: ErrorReportingSettings,
+    #[serde(default)]
+    pub cors: CorsSettings,
+    #[serde(default)]
+    pub login_protection: LoginProtectionSettings,
+    #[serde(default)]
+    pub password_policy: PasswordPolicySettings,
 }
 
 #[derive(Deserialize, Debug)]
@@ -106,6 +133,31 @@ pub fn get_config() -> Result<Settings, ConfigError> {
                 .prefix_separator("__")
                 .separator("__")
         )
+        .add_source(
+            config::Environment::default()
+                .prefix("EMAIL")
+                .prefix_separator("__")
+                .separator("__")
+        )
+        .add_source(
+            config::Environment::default()
+                .prefix("OAUTH")
+                .prefix_separator("__")
+                .separator("__")
+                .list_separator(",")
+                .with_list_parse_key("google_client_ids")
+                .with_list_parse_key("apple_client_ids")
+                .try_parsing(true)
+        )
+        .add_source(
+            config::Environment::default()
+                .prefix("CORS")
+                .prefix_separator("__")
+                .separator("__")
+                .list_separator(",")
+                .with_list_parse_key("cors.allowed_origins")
+                .try_parsing(true)
+        )
         .build()?;
 
     let mut settings = config.try_deserialize::<Settings>()?;
@@ -120,6 +172,23 @@ pub fn get_config() -> Result<Settings, ConfigError> {
         settings.jwt.secret = SecretString::new(jwt_secret.into_boxed_str());
     }
 
+    // Rotating the JWT secret moves the old one here, so tokens it signed keep working
+    if let Ok(key_id) = env::var("JWT_KEY_ID") {
+        settings.jwt.key_id = key_id;
+    }
+    if let Ok(previous_keys) = env::var("JWT_PREVIOUS_KEYS") {
+        settings.jwt.previous_keys = JwtKey::parse_list(&previous_keys).map_err(ConfigError::Message)?;
+    }
+
+    // Error reporting services hand out their DSN as SENTRY_DSN
+    if let Ok(dsn) = env::var("SENTRY_DSN") {
+        settings.error_reporting.dsn = Some(SecretString::new(dsn.into_boxed_str()));
+    }
+
+    settings.cors.validate().map_err(ConfigError::Message)?;
+    settings.jwt.validate().map_err(ConfigError::Message)?;
+    settings.login_protection.validate().map_err(ConfigError::Message)?;
+
     Ok(settings)
 }
 
@@ -156,8 +225,19 @@ impl TryFrom<String> for Environment {
 }
 
 pub fn get_jwt_settings(settings: &Settings) -> JwtSettings {
-    JwtSettings::new(
-        settings.jwt.secret.expose_secret().to_string().clone(),
-        settings.jwt.expiration_hours,
-    )
+    JwtSettings {
+        key_id: settings.jwt.key_id.clone(),
+        previous_keys: settings.jwt.previous_keys
+            .iter()
+            .map(|key| JwtKey::new(key.id.clone(), key.secret.expose_secret().to_string()))
+            .collect(),
+        issuer: settings.jwt.issuer.clone(),
+        audience: settings.jwt.audience.clone(),
+        legacy_tokens_until: settings.jwt.legacy_tokens_until,
+        ..JwtSettings::new(
+            settings.jwt.secret.expose_secret().to_string().clone(),
+            settings.jwt.expiration_hours,
+            settings.jwt.refresh_token_days,
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+/// Settings of the weekly decay of avatar stamina and strength
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatDecaySettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Share of a stat lost each week, in percent
+    #[serde(default = "default_weekly_percent")]
+    pub weekly_percent: f32,
+    /// Decay never takes stamina below this
+    #[serde(default = "default_floor")]
+    pub stamina_floor: f32,
+    /// Decay never takes strength below this
+    #[serde(default = "default_floor")]
+    pub strength_floor: f32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_weekly_percent() -> f32 {
+    5.0
+}
+
+fn default_floor() -> f32 {
+    10.0
+}
+
+impl Default for StatDecaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            weekly_percent: default_weekly_percent(),
+            stamina_floor: default_floor(),
+            strength_floor: default_floor(),
+        }
+    }
+}
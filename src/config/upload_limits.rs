@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+/// Caps on workout uploads, checked while the body is read and parsed
+#[derive(Deserialize, Debug, Clone)]
+pub struct UploadLimitsSettings {
+    /// Largest accepted request body in bytes
+    #[serde(default = "default_max_payload_bytes")]
+    pub max_payload_bytes: usize,
+    /// Most heart rate samples accepted in one workout
+    #[serde(default = "default_max_heart_rate_samples")]
+    pub max_heart_rate_samples: usize,
+}
+
+fn default_max_payload_bytes() -> usize {
+    8 * 1024 * 1024
+}
+
+fn default_max_heart_rate_samples() -> usize {
+    50_000 // About 14 hours at one sample per second
+}
+
+impl Default for UploadLimitsSettings {
+    fn default() -> Self {
+        Self {
+            max_payload_bytes: default_max_payload_bytes(),
+            max_heart_rate_samples: default_max_heart_rate_samples(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::user::AccountDeletion;
+
+/// Deactivate the account and schedule its purge. Asking again keeps the original schedule.
+/// Returns None for unknown users.
+pub async fn request_deletion(pool: &PgPool, user_id: Uuid) -> Result<Option<AccountDeletion>, sqlx::Error> {
+    let deletion = AccountDeletion::requested(chrono::Utc::now());
+    let mut tx = pool.begin().await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO account_deletions (user_id, previous_status, requested_at, purge_after)
+        SELECT id, status, $2, $3 FROM users WHERE id = $1
+        ON CONFLICT (user_id) DO NOTHING
+        "#,
+        user_id,
+        deletion.requested_at,
+        deletion.purge_after
+    )
+    .execute(&mut *tx)
+    .await?;
+    let deletion = sqlx::query_as!(
+        AccountDeletion,
+        "SELECT requested_at, purge_after FROM account_deletions WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if deletion.is_some() {
+        sqlx::query!(
+            "UPDATE users SET status = 'inactive', updated_at = NOW() WHERE id = $1",
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(deletion)
+}
+
+/// Cancel a pending deletion of the account, restoring the status it had before.
+/// Returns the restored status if a deletion was pending.
+pub async fn cancel_deletion(conn: &mut PgConnection, user_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    let previous_status = sqlx::query_scalar!(
+        "DELETE FROM account_deletions WHERE user_id = $1 RETURNING previous_status",
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(previous_status) = previous_status else {
+        return Ok(None);
+    };
+    sqlx::query!(
+        "UPDATE users SET status = $2, updated_at = NOW() WHERE id = $1",
+        user_id,
+        previous_status
+    )
+    .execute(conn)
+    .await?;
+    Ok(Some(previous_status))
+}
+
+/// Accounts whose grace period has passed, oldest requests first
+pub async fn due_deletions(pool: &PgPool, limit: i64) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT user_id FROM account_deletions WHERE purge_after <= NOW() ORDER BY purge_after LIMIT $1",
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
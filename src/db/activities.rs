@@ -0,0 +1,34 @@
+use sqlx::PgPool;
+
+use crate::models::activity::Activity;
+
+/// Activities in the catalog, by name; inactive ones only if asked for
+pub async fn list_activities(pool: &PgPool, include_inactive: bool) -> Result<Vec<Activity>, sqlx::Error> {
+    sqlx::query_as!(
+        Activity,
+        r#"
+        SELECT id, key, name, met_value, default_scoring_profile, icon_key, is_active, created_at, updated_at
+        FROM activities
+        WHERE is_active OR $1
+        ORDER BY name
+        "#,
+        include_inactive
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Active catalog entry matching a key or (case-insensitive) name
+pub async fn find_active_activity(pool: &PgPool, key_or_name: &str) -> Result<Option<Activity>, sqlx::Error> {
+    sqlx::query_as!(
+        Activity,
+        r#"
+        SELECT id, key, name, met_value, default_scoring_profile, icon_key, is_active, created_at, updated_at
+        FROM activities
+        WHERE is_active AND (key = $1 OR LOWER(name) = LOWER($1))
+        "#,
+        key_or_name.trim()
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::api_key::{ApiKey, IssuedApiKey};
+use crate::models::user::{UserRole, UserStatus};
+use crate::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// Keys start with this, so leaked keys are easy to recognise
+const KEY_PREFIX: &str = "riina_";
+
+/// Characters of the key kept in the clear to tell keys apart
+const SHOWN_PREFIX_LENGTH: usize = 12;
+
+/// The user an API key acts as
+#[derive(Debug)]
+pub struct ApiKeyUser {
+    pub user_id: Uuid,
+    pub username: String,
+    pub role: UserRole,
+    pub status: UserStatus,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Issue a key for the user. Returns None for unknown users.
+pub async fn issue_api_key(
+    pool: &PgPool,
+    user_id: Uuid,
+    name: &str,
+    scopes: &[String],
+    expires_at: Option<DateTime<Utc>>,
+    created_by: Uuid,
+) -> Result<Option<IssuedApiKey>, sqlx::Error> {
+    let key = format!("{KEY_PREFIX}{}", generate_opaque_token());
+    let api_key = sqlx::query_as!(
+        ApiKey,
+        r#"
+        INSERT INTO api_keys (user_id, name, key_prefix, key_hash, scopes, created_by, expires_at)
+        SELECT id, $2, $3, $4, $5, $6, $7 FROM users WHERE id = $1
+        RETURNING id, user_id, name, key_prefix, scopes, created_by, created_at, expires_at, last_used_at, revoked_at
+        "#,
+        user_id,
+        name,
+        &key[..SHOWN_PREFIX_LENGTH],
+        hash_opaque_token(&key),
+        scopes,
+        created_by,
+        expires_at
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(api_key.map(|api_key| IssuedApiKey { api_key, key }))
+}
+
+/// Issued keys, newest first, optionally only those of one user
+pub async fn list_api_keys(pool: &PgPool, user_id: Option<Uuid>) -> Result<Vec<ApiKey>, sqlx::Error> {
+    sqlx::query_as!(
+        ApiKey,
+        r#"
+        SELECT id, user_id, name, key_prefix, scopes, created_by, created_at, expires_at, last_used_at, revoked_at
+        FROM api_keys
+        WHERE $1::uuid IS NULL OR user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Revoke the key. Returns false for unknown or already revoked keys.
+pub async fn revoke_api_key(pool: &PgPool, key_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        key_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke every key of the user, e.g. when the account is deactivated or deleted. Returns how many were revoked.
+pub async fn revoke_all_api_keys_for_user(pool: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// The active user a key acts as, if the key is known, unrevoked and unexpired. Records that it was used,
+/// at most once a minute so busy integrations don't write on every request.
+pub async fn authenticate_api_key(pool: &PgPool, key: &str) -> Result<Option<ApiKeyUser>, sqlx::Error> {
+    if !key.starts_with(KEY_PREFIX) {
+        return Ok(None);
+    }
+    let Some(row) = sqlx::query!(
+        r#"
+        SELECT k.id, k.scopes, k.expires_at,
+            k.last_used_at IS NULL OR k.last_used_at < NOW() - INTERVAL '1 minute' as "stale!",
+            u.id as user_id, u.username, u.role as "role: UserRole", u.status as "status: UserStatus"
+        FROM api_keys k
+        JOIN users u ON u.id = k.user_id
+        WHERE k.key_hash = $1
+            AND k.revoked_at IS NULL
+            AND (k.expires_at IS NULL OR k.expires_at > NOW())
+            AND u.status = 'active'
+        "#,
+        hash_opaque_token(key)
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    if row.stale {
+        sqlx::query!("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1", row.id)
+            .execute(pool)
+            .await?;
+    }
+    Ok(Some(ApiKeyUser {
+        user_id: row.user_id,
+        username: row.username,
+        role: row.role,
+        status: row.status,
+        scopes: row.scopes,
+        expires_at: row.expires_at,
+    }))
+}
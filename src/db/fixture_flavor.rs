@@ -0,0 +1,147 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::league::{FixtureFlavor, GameWeekName, SetFixtureFlavorRequest, SetGameWeekNameRequest};
+
+/// Name a week of a season. Returns None if the season has no games that week.
+pub async fn set_week_name(
+    pool: &PgPool,
+    season_id: Uuid,
+    week_number: i32,
+    request: &SetGameWeekNameRequest,
+    admin_id: Uuid,
+) -> Result<Option<GameWeekName>, sqlx::Error> {
+    sqlx::query_as!(
+        GameWeekName,
+        r#"
+        INSERT INTO game_week_names (season_id, week_number, name, description, updated_by)
+        SELECT $1, $2, $3, $4, $5
+        WHERE EXISTS (SELECT 1 FROM games WHERE season_id = $1 AND week_number = $2)
+        ON CONFLICT (season_id, week_number) DO UPDATE SET
+            name = EXCLUDED.name,
+            description = EXCLUDED.description,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = NOW()
+        RETURNING season_id, week_number, name, description
+        "#,
+        season_id,
+        week_number,
+        request.name.trim(),
+        request.description,
+        admin_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns whether the week had a name
+pub async fn remove_week_name(pool: &PgPool, season_id: Uuid, week_number: i32) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM game_week_names WHERE season_id = $1 AND week_number = $2",
+        season_id,
+        week_number
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Named weeks of a season, in week order
+pub async fn season_week_names(pool: &PgPool, season_id: Uuid) -> Result<Vec<GameWeekName>, sqlx::Error> {
+    sqlx::query_as!(
+        GameWeekName,
+        r#"
+        SELECT season_id, week_number, name, description
+        FROM game_week_names
+        WHERE season_id = $1
+        ORDER BY week_number
+        "#,
+        season_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Set a fixture's flavor. Returns None if the game does not exist.
+pub async fn set_fixture_flavor(
+    pool: &PgPool,
+    game_id: Uuid,
+    request: &SetFixtureFlavorRequest,
+    admin_id: Uuid,
+) -> Result<Option<FixtureFlavor>, sqlx::Error> {
+    sqlx::query_as!(
+        FixtureFlavor,
+        r#"
+        INSERT INTO fixture_flavors (game_id, title, venue_name, description, banner_url, updated_by)
+        SELECT $1, $2, $3, $4, $5, $6
+        WHERE EXISTS (SELECT 1 FROM games WHERE id = $1)
+        ON CONFLICT (game_id) DO UPDATE SET
+            title = EXCLUDED.title,
+            venue_name = EXCLUDED.venue_name,
+            description = EXCLUDED.description,
+            banner_url = EXCLUDED.banner_url,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = NOW()
+        RETURNING game_id, title, venue_name, description, banner_url
+        "#,
+        game_id,
+        request.title.as_deref().map(str::trim),
+        request.venue_name.as_deref().map(str::trim),
+        request.description,
+        request.banner_url,
+        admin_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns whether the fixture had flavor
+pub async fn remove_fixture_flavor(pool: &PgPool, game_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM fixture_flavors WHERE game_id = $1", game_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Flavor of the season's fixtures that have any
+pub async fn season_fixture_flavors(pool: &PgPool, season_id: Uuid) -> Result<Vec<FixtureFlavor>, sqlx::Error> {
+    sqlx::query_as!(
+        FixtureFlavor,
+        r#"
+        SELECT ff.game_id, ff.title, ff.venue_name, ff.description, ff.banner_url
+        FROM fixture_flavors ff
+        JOIN games g ON g.id = ff.game_id
+        WHERE g.season_id = $1
+        ORDER BY g.week_number, g.game_start_time
+        "#,
+        season_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// The name of the game's week and the game's own flavor, where set
+pub async fn game_flavor(pool: &PgPool, game_id: Uuid) -> Result<(Option<GameWeekName>, Option<FixtureFlavor>), sqlx::Error> {
+    let week_name = sqlx::query_as!(
+        GameWeekName,
+        r#"
+        SELECT wn.season_id, wn.week_number, wn.name, wn.description
+        FROM games g
+        JOIN game_week_names wn ON wn.season_id = g.season_id AND wn.week_number = g.week_number
+        WHERE g.id = $1
+        "#,
+        game_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let fixture = sqlx::query_as!(
+        FixtureFlavor,
+        "SELECT game_id, title, venue_name, description, banner_url FROM fixture_flavors WHERE game_id = $1",
+        game_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok((week_name, fixture))
+}
@@ -1,10 +1,14 @@
 // Removed unused imports: use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 use tracing::info;
 
 use crate::models::league::{LeagueGame, GameStatus, LiveGameScoreUpdate};
 
+/// How long after a game ends a workout recorded during it can still be uploaded
+/// (watches syncing late), as long as the game has not been evaluated yet
+pub const WORKOUT_UPLOAD_GRACE_MINUTES: i32 = 60;
+
 #[derive(Debug)]
 pub struct GameQueries {
     pool: PgPool,
@@ -37,7 +41,7 @@ impl GameQueries {
 
     /// Calculate team scores from live_score_events using all players
     /// This is a public method that can be called to recalculate scores for a game
-    pub async fn calculate_team_scores_best_4(&self, game_id: Uuid) -> Result<(i32, i32), sqlx::Error> {
+    pub async fn calculate_team_scores_best_4(conn: &mut PgConnection, game_id: Uuid) -> Result<(i32, i32), sqlx::Error> {
         // Get all player scores grouped by team and user
         let player_scores = sqlx::query!(
             r#"
@@ -52,7 +56,7 @@ impl GameQueries {
             "#,
             game_id
         )
-        .fetch_all(&self.pool)
+        .fetch_all(conn)
         .await?;
 
         let mut home_scores: Vec<f64> = Vec::new();
@@ -78,9 +82,9 @@ impl GameQueries {
         Ok((home_score, away_score))
     }
 
-    /// Update game score from a workout
+    /// Update game score from a workout, within the caller's transaction
     pub async fn update_game_score(
-        &self,
+        conn: &mut PgConnection,
         game_id: Uuid,
         update: &LiveGameScoreUpdate,
     ) -> Result<(), sqlx::Error> {
@@ -105,7 +109,7 @@ impl GameQueries {
             game_id,
             update.user_id
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await?;
 
         let game_info = match game_info {
@@ -118,7 +122,7 @@ impl GameQueries {
 
         // NOTE: Score is already recorded in live_score_events by the caller
         // Now we recalculate team totals from live_score_events (all players)
-        let (home_score, away_score) = self.calculate_team_scores_best_4(game_id).await?;
+        let (home_score, away_score) = Self::calculate_team_scores_best_4(&mut *conn, game_id).await?;
 
         // Update game with new calculated scores
         sqlx::query!(
@@ -141,7 +145,7 @@ impl GameQueries {
             update.username,
             game_info.team_side
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         info!("✅ Score updated for game {} by {} ({}): home={}, away={}",
@@ -193,6 +197,50 @@ winner_team_id,
         Ok(games)
     }
 
+    /// Games a workout upload can still score in: live games, plus finished games that
+    /// ended within the upload grace period and have not been evaluated yet
+    pub async fn get_games_accepting_workouts(&self) -> Result<Vec<LeagueGame>, sqlx::Error> {
+        let games = sqlx::query_as!(
+            LeagueGame,
+            r#"
+            SELECT
+                id, season_id, home_team_id, away_team_id,
+                week_number, is_first_leg, status as "status: GameStatus",
+                winner_team_id,
+                created_at, updated_at,
+                home_score, away_score, game_start_time, game_end_time,
+                last_score_time, last_scorer_id, last_scorer_name, last_scorer_team
+            FROM games
+            WHERE status = 'in_progress'
+            OR (status = 'finished' AND game_end_time > NOW() - make_interval(mins => $1))
+            ORDER BY game_start_time ASC
+            "#,
+            WORKOUT_UPLOAD_GRACE_MINUTES
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(games)
+    }
+
+    /// Finished games whose upload grace period is over, so no late workout can change their
+    /// score anymore. Games held for workout reviews are left to the review lock.
+    pub async fn get_games_ready_to_evaluate(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT g.id
+            FROM games g
+            WHERE g.status = 'finished'
+            AND g.game_end_time <= NOW() - make_interval(mins => $1)
+            AND NOT EXISTS (SELECT 1 FROM game_review_holds h WHERE h.game_id = g.id)
+            ORDER BY g.game_end_time ASC
+            "#,
+            WORKOUT_UPLOAD_GRACE_MINUTES
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /// Get games that need to be started
     pub async fn get_games_ready_to_start(&self) -> Result<Vec<LeagueGame>, sqlx::Error> {
         info!("🔍 [DB] Querying for games ready to start (status='scheduled', start_time <= now)");
@@ -0,0 +1,177 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::game_events::ScoreEventSummary;
+
+/// A game's live state together with the names of both teams
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveGameState {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub week_number: i32,
+    pub status: String,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+}
+
+impl LiveGameState {
+    pub fn is_in_progress(&self) -> bool {
+        self.status == "in_progress"
+    }
+
+    /// Share of the game's live window that has elapsed at `now`, in percent
+    pub fn progress_percent(&self, now: DateTime<Utc>) -> f32 {
+        let (Some(start), Some(end)) = (self.game_start_time, self.game_end_time) else {
+            return 0.0;
+        };
+        let total_seconds = (end - start).num_seconds();
+        let elapsed_seconds = (now - start).num_seconds();
+
+        if elapsed_seconds < 0 {
+            0.0
+        } else if elapsed_seconds > total_seconds {
+            100.0
+        } else {
+            (elapsed_seconds as f32 / total_seconds as f32) * 100.0
+        }
+    }
+}
+
+/// Read access to games, implemented by `GameRepo` and by mocks in tests
+pub trait GameRepository {
+    fn find_live_game_state(&self, game_id: Uuid) -> impl Future<Output = Result<Option<LiveGameState>, sqlx::Error>> + Send;
+
+    fn game_exists(&self, game_id: Uuid) -> impl Future<Output = Result<bool, sqlx::Error>> + Send;
+
+    /// Games in progress that one of the teams plays in
+    fn find_live_games_of_teams(&self, team_ids: &[Uuid]) -> impl Future<Output = Result<Vec<LiveGameState>, sqlx::Error>> + Send;
+
+    /// Games in progress of a season
+    fn find_live_games_of_season(&self, season_id: Uuid) -> impl Future<Output = Result<Vec<LiveGameState>, sqlx::Error>> + Send;
+
+    /// The latest `per_game` scoring events of each game, newest first
+    fn find_recent_score_events(&self, game_ids: &[Uuid], per_game: i64) -> impl Future<Output = Result<Vec<ScoreEventSummary>, sqlx::Error>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct GameRepo {
+    pool: PgPool,
+}
+
+impl GameRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl GameRepository for GameRepo {
+    async fn find_live_game_state(&self, game_id: Uuid) -> Result<Option<LiveGameState>, sqlx::Error> {
+        sqlx::query_as!(
+            LiveGameState,
+            r#"
+            SELECT
+                g.id, g.season_id, g.week_number, g.status,
+                ht.id as home_team_id,
+                ht.team_name as home_team_name,
+                at.id as away_team_id,
+                at.team_name as away_team_name,
+                g.home_score, g.away_score,
+                g.game_start_time, g.game_end_time
+            FROM games g
+            JOIN teams ht ON g.home_team_id = ht.id
+            JOIN teams at ON g.away_team_id = at.id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn game_exists(&self, game_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM games WHERE id = $1) as "exists!""#, game_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn find_live_games_of_teams(&self, team_ids: &[Uuid]) -> Result<Vec<LiveGameState>, sqlx::Error> {
+        sqlx::query_as!(
+            LiveGameState,
+            r#"
+            SELECT
+                g.id, g.season_id, g.week_number, g.status,
+                ht.id as home_team_id,
+                ht.team_name as home_team_name,
+                at.id as away_team_id,
+                at.team_name as away_team_name,
+                g.home_score, g.away_score,
+                g.game_start_time, g.game_end_time
+            FROM games g
+            JOIN teams ht ON g.home_team_id = ht.id
+            JOIN teams at ON g.away_team_id = at.id
+            WHERE g.status = 'in_progress'
+              AND (g.home_team_id = ANY($1) OR g.away_team_id = ANY($1))
+            ORDER BY g.game_start_time
+            "#,
+            team_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_live_games_of_season(&self, season_id: Uuid) -> Result<Vec<LiveGameState>, sqlx::Error> {
+        sqlx::query_as!(
+            LiveGameState,
+            r#"
+            SELECT
+                g.id, g.season_id, g.week_number, g.status,
+                ht.id as home_team_id,
+                ht.team_name as home_team_name,
+                at.id as away_team_id,
+                at.team_name as away_team_name,
+                g.home_score, g.away_score,
+                g.game_start_time, g.game_end_time
+            FROM games g
+            JOIN teams ht ON g.home_team_id = ht.id
+            JOIN teams at ON g.away_team_id = at.id
+            WHERE g.season_id = $1 AND g.status = 'in_progress'
+            ORDER BY g.game_start_time
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_recent_score_events(&self, game_ids: &[Uuid], per_game: i64) -> Result<Vec<ScoreEventSummary>, sqlx::Error> {
+        sqlx::query_as!(
+            ScoreEventSummary,
+            r#"
+            SELECT id as "id!", game_id as "game_id!", user_id as "user_id!", username as "username!",
+                team_id as "team_id!", team_side as "team_side!", score_points as "score_points!",
+                description as "description!", occurred_at as "occurred_at!"
+            FROM (
+                SELECT lse.*, ROW_NUMBER() OVER (PARTITION BY lse.game_id ORDER BY lse.occurred_at DESC) as position
+                FROM live_score_events lse
+                WHERE lse.game_id = ANY($1)
+            ) ranked
+            WHERE position <= $2
+            ORDER BY occurred_at DESC
+            "#,
+            game_ids,
+            per_game
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
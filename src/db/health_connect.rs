@@ -0,0 +1,92 @@
+use chrono::{Duration, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::models::workout_data::{ChangeTokenStatus, HealthConnectChangeToken, CHANGE_TOKEN_TTL_DAYS};
+
+pub async fn get_change_token(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    device_id: &str,
+) -> Result<Option<HealthConnectChangeToken>, sqlx::Error> {
+    sqlx::query_as!(
+        HealthConnectChangeToken,
+        r#"
+        SELECT device_id, change_token, last_used_at, expires_at
+        FROM health_connect_change_tokens
+        WHERE user_id = $1 AND device_id = $2
+        "#,
+        user_id,
+        device_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Check the token a delta sync is based on against the one stored for the device.
+/// A stale or expired token is dropped so the next sync starts over with a full sync.
+pub async fn check_change_token(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    device_id: &str,
+    change_token: Option<&str>,
+) -> Result<ChangeTokenStatus, sqlx::Error> {
+    let Some(change_token) = change_token else {
+        return Ok(ChangeTokenStatus::FullSync);
+    };
+
+    let status = match get_change_token(pool, user_id, device_id).await? {
+        Some(stored) if stored.expires_at <= Utc::now() => ChangeTokenStatus::Expired,
+        Some(stored) if stored.change_token == change_token => ChangeTokenStatus::Valid,
+        _ => ChangeTokenStatus::Unknown,
+    };
+
+    if status.requires_full_resync() {
+        clear_change_token(pool, user_id, device_id).await?;
+    }
+    Ok(status)
+}
+
+/// Store the token Health Connect returned after the device's latest sync
+pub async fn store_change_token(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    device_id: &str,
+    change_token: &str,
+) -> Result<HealthConnectChangeToken, sqlx::Error> {
+    let expires_at = Utc::now() + Duration::days(CHANGE_TOKEN_TTL_DAYS);
+    sqlx::query_as!(
+        HealthConnectChangeToken,
+        r#"
+        INSERT INTO health_connect_change_tokens (user_id, device_id, change_token, expires_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, device_id) DO UPDATE
+        SET change_token = EXCLUDED.change_token,
+            last_used_at = NOW(),
+            expires_at = EXCLUDED.expires_at
+        RETURNING device_id, change_token, last_used_at, expires_at
+        "#,
+        user_id,
+        device_id,
+        change_token,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn clear_change_token(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    device_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM health_connect_change_tokens WHERE user_id = $1 AND device_id = $2",
+        user_id,
+        device_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -1,9 +1,20 @@
-use sqlx::{Pool, Postgres, Error};
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, Pool, Postgres, Error};
 use uuid::Uuid;
 
 use crate::models::health::{UserHealthProfile, Gender};
+use crate::models::profile::{DeviceHealthProfile, HealthProfileSnapshot};
+use crate::utils::health_calculations::calc_max_heart_rate;
 use crate::workout::universal_hr_based_scoring::{P_VT_OFF, P_VT0, P_VT1, P_VT2};
 
+/// Where a profile's VT1 or VT2 comes from
+pub const THRESHOLD_SOURCE_HR_RESERVE: &str = "hr_reserve";
+pub const THRESHOLD_SOURCE_DETECTED: &str = "detected";
+
+/// Heart rates of users who haven't told us theirs, the same as the column defaults
+const DEFAULT_RESTING_HEART_RATE: i32 = 65;
+const DEFAULT_MAX_HEART_RATE: i32 = 190;
+
 pub async fn get_user_health_profile_details(pool: &Pool<Postgres>, user_id: Uuid) -> Result<UserHealthProfile, Error> {
     tracing::info!("🔍 Fetching health profile for user: {}", user_id);
     let result = sqlx::query!(
@@ -19,15 +30,9 @@ pub async fn get_user_health_profile_details(pool: &Pool<Postgres>, user_id: Uui
 
     match result {
         Some(row) => {
-            let gender = match row.gender.as_deref() {
-                Some("male") | Some("m") => Gender::Male,
-                Some("female") | Some("f") => Gender::Female,
-                _ => Gender::Other,
-            };
-
             let profile = UserHealthProfile {
                 age: row.age.unwrap_or(30), // Default age if not provided
-                gender,
+                gender: parse_gender(row.gender.as_deref()),
                 resting_heart_rate: row.resting_heart_rate,
                 max_heart_rate: row.max_heart_rate,
             };
@@ -43,28 +48,134 @@ pub async fn get_user_health_profile_details(pool: &Pool<Postgres>, user_id: Uui
             Ok(UserHealthProfile {
                 age: 30,
                 gender: Gender::Other,
-                resting_heart_rate: 65,
-                max_heart_rate: 190,
+                resting_heart_rate: DEFAULT_RESTING_HEART_RATE,
+                max_heart_rate: DEFAULT_MAX_HEART_RATE,
             })
         }
     }
 }
 
-/// Update max heart rate and calculate VT thresholds
+/// The health profile that was current at `at`, so past workouts are scored against the
+/// values of their time. Workouts from before the first snapshot use the earliest one.
+pub async fn get_user_health_profile_at(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    at: DateTime<Utc>,
+) -> Result<UserHealthProfile, Error> {
+    let snapshot = sqlx::query!(
+        r#"
+        SELECT age, gender, resting_heart_rate, max_heart_rate
+        FROM user_health_profile_history
+        WHERE user_id = $1
+        ORDER BY (valid_from <= $2) DESC,
+                 CASE WHEN valid_from <= $2 THEN valid_from END DESC,
+                 valid_from ASC
+        LIMIT 1
+        "#,
+        user_id,
+        at
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match snapshot {
+        Some(row) => Ok(UserHealthProfile {
+            age: row.age.unwrap_or(30),
+            gender: parse_gender(row.gender.as_deref()),
+            resting_heart_rate: row.resting_heart_rate,
+            max_heart_rate: row.max_heart_rate,
+        }),
+        None => get_user_health_profile_details(pool, user_id).await,
+    }
+}
+
+/// Health profile snapshots of a user in a time range, oldest first
+pub async fn get_health_profile_history(
+    pool: &Pool<Postgres>,
+    user_id: Uuid,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: i64,
+) -> Result<Vec<HealthProfileSnapshot>, Error> {
+    sqlx::query_as!(
+        HealthProfileSnapshot,
+        r#"
+        SELECT profile_version as "profile_version!", age, gender,
+               resting_heart_rate as "resting_heart_rate!", max_heart_rate as "max_heart_rate!", weight, height,
+               vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, valid_from as "valid_from!"
+        FROM (
+            SELECT *
+            FROM user_health_profile_history
+            WHERE user_id = $1
+            AND ($2::timestamptz IS NULL OR valid_from >= $2)
+            AND ($3::timestamptz IS NULL OR valid_from <= $3)
+            ORDER BY valid_from DESC
+            LIMIT $4
+        ) recent
+        ORDER BY valid_from ASC
+        "#,
+        user_id,
+        from,
+        to,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+fn parse_gender(gender: Option<&str>) -> Gender {
+    match gender {
+        Some("male") | Some("m") => Gender::Male,
+        Some("female") | Some("f") => Gender::Female,
+        _ => Gender::Other,
+    }
+}
+
+/// VT off, VT0, VT1 and VT2 thresholds, as shares of the heart rate reserve
+fn vt_thresholds(max_hr: i32, resting_hr: i32) -> (i32, i32, i32, i32) {
+    let hr_reserve = (max_hr - resting_hr) as f32;
+    (
+        resting_hr + (hr_reserve * P_VT_OFF) as i32,
+        resting_hr + (hr_reserve * P_VT0) as i32,
+        resting_hr + (hr_reserve * P_VT1) as i32,
+        resting_hr + (hr_reserve * P_VT2) as i32,
+    )
+}
+
+/// Update max heart rate and calculate VT thresholds. VT1 and VT2 detected from the user's
+/// workouts are kept as long as the thresholds stay in order with them.
 pub async fn update_max_heart_rate_and_vt_thresholds(
     pool: &Pool<Postgres>,
     user_id: Uuid,
     new_max_hr: i32,
     resting_hr: i32,
 ) -> Result<(), Error> {
-    // Calculate heart rate reserve
-    let hr_reserve = new_max_hr - resting_hr;
+    let (vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold) = vt_thresholds(new_max_hr, resting_hr);
 
-    // Calculate VT thresholds
-    let vt_off_threshold = resting_hr + (hr_reserve as f32 * P_VT_OFF) as i32;
-    let vt0_threshold = resting_hr + (hr_reserve as f32 * P_VT0) as i32;
-    let vt1_threshold = resting_hr + (hr_reserve as f32 * P_VT1) as i32;
-    let vt2_threshold = resting_hr + (hr_reserve as f32 * P_VT2) as i32;
+    let detected = sqlx::query!(
+        r#"
+        SELECT vt1_threshold, vt1_source, vt2_threshold, vt2_source
+        FROM user_health_profiles
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+    let (detected_vt1, detected_vt2) = match &detected {
+        Some(row) => (
+            row.vt1_threshold.filter(|_| row.vt1_source == THRESHOLD_SOURCE_DETECTED),
+            row.vt2_threshold.filter(|_| row.vt2_source == THRESHOLD_SOURCE_DETECTED),
+        ),
+        None => (None, None),
+    };
+    let (vt1, vt2) = (detected_vt1.unwrap_or(vt1_threshold), detected_vt2.unwrap_or(vt2_threshold));
+    let (vt1, vt2, detected_vt1, detected_vt2) = if vt0_threshold < vt1 && vt1 < vt2 && vt2 < new_max_hr {
+        (vt1, vt2, detected_vt1, detected_vt2)
+    } else {
+        (vt1_threshold, vt2_threshold, None, None)
+    };
+    let source = |detected: Option<i32>| if detected.is_some() { THRESHOLD_SOURCE_DETECTED } else { THRESHOLD_SOURCE_HR_RESERVE };
 
     // Update in database
     sqlx::query!(
@@ -75,18 +186,67 @@ pub async fn update_max_heart_rate_and_vt_thresholds(
             vt0_threshold = $3,
             vt1_threshold = $4,
             vt2_threshold = $5,
+            vt1_source = $6,
+            vt2_source = $7,
+            threshold_proposal_id = CASE WHEN $8 THEN threshold_proposal_id END,
             last_updated = NOW()
-        WHERE user_id = $6
+        WHERE user_id = $9
         "#,
         new_max_hr,
         vt_off_threshold,
         vt0_threshold,
-        vt1_threshold,
-        vt2_threshold,
+        vt1,
+        vt2,
+        source(detected_vt1),
+        source(detected_vt2),
+        detected_vt1.is_some() || detected_vt2.is_some(),
         user_id
     )
     .execute(pool)
     .await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Create the health profile of a user who has none from the values their device provided,
+/// with max heart rate and VT thresholds filled in. Missing heart rates fall back to the defaults
+/// scoring uses. Returns false if the user already has a profile, which is left as it is.
+pub async fn import_device_health_profile(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    device_profile: &DeviceHealthProfile,
+) -> Result<bool, Error> {
+    let resting_hr = device_profile.resting_heart_rate.unwrap_or(DEFAULT_RESTING_HEART_RATE);
+    let max_hr = match (device_profile.max_heart_rate, device_profile.age) {
+        (Some(max_hr), _) => max_hr,
+        (None, Some(age)) => calc_max_heart_rate(age, parse_gender(device_profile.gender.as_deref())),
+        (None, None) => DEFAULT_MAX_HEART_RATE,
+    };
+    let (vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold) = vt_thresholds(max_hr, resting_hr);
+
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO user_health_profiles (
+            user_id, age, gender, weight, height, resting_heart_rate, max_heart_rate,
+            vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, last_updated
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+        ON CONFLICT (user_id) DO NOTHING
+        "#,
+        user_id,
+        device_profile.age,
+        device_profile.gender.as_deref(),
+        device_profile.weight,
+        device_profile.height,
+        resting_hr,
+        max_hr,
+        vt_off_threshold,
+        vt0_threshold,
+        vt1_threshold,
+        vt2_threshold
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(inserted.rows_affected() > 0)
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::impersonation::Impersonation;
+
+/// Record that an admin is issued a token to act as the user
+pub async fn record_impersonation(
+    pool: &PgPool,
+    admin_id: Uuid,
+    user_id: Uuid,
+    reason: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO admin_impersonations (admin_id, user_id, reason, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        admin_id,
+        user_id,
+        reason,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Impersonations of the user, newest first
+pub async fn list_impersonations(pool: &PgPool, user_id: Uuid) -> Result<Vec<Impersonation>, sqlx::Error> {
+    sqlx::query_as!(
+        Impersonation,
+        r#"
+        SELECT i.id, i.admin_id, a.username AS "admin_username?", i.user_id, i.reason, i.expires_at, i.created_at
+        FROM admin_impersonations i
+        LEFT JOIN users a ON a.id = i.admin_id
+        WHERE i.user_id = $1
+        ORDER BY i.created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::team_invitation::InviteCode;
+
+/// Without 0/O and 1/I, so codes read out or typed from a screenshot come out right
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 8;
+
+fn generate_invite_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LENGTH)
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Codes are shown in upper case but accepted in any case
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+pub async fn create_invite_code(
+    pool: &PgPool,
+    team_id: Uuid,
+    created_by: Uuid,
+    max_uses: i32,
+    expires_at: DateTime<Utc>,
+) -> Result<InviteCode, sqlx::Error> {
+    sqlx::query_as!(
+        InviteCode,
+        r#"
+        INSERT INTO invites (code, team_id, created_by, max_uses, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, code, team_id, created_by, max_uses, use_count, expires_at, revoked_at, created_at
+        "#,
+        generate_invite_code(),
+        team_id,
+        created_by,
+        max_uses,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn find_invite_code(pool: &PgPool, code: &str) -> Result<Option<InviteCode>, sqlx::Error> {
+    sqlx::query_as!(
+        InviteCode,
+        r#"
+        SELECT id, code, team_id, created_by, max_uses, use_count, expires_at, revoked_at, created_at
+        FROM invites
+        WHERE code = $1
+        "#,
+        normalize_code(code)
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Invite codes, newest first, optionally only those of one team
+pub async fn list_invite_codes(pool: &PgPool, team_id: Option<Uuid>) -> Result<Vec<InviteCode>, sqlx::Error> {
+    sqlx::query_as!(
+        InviteCode,
+        r#"
+        SELECT id, code, team_id, created_by, max_uses, use_count, expires_at, revoked_at, created_at
+        FROM invites
+        WHERE $1::uuid IS NULL OR team_id = $1
+        ORDER BY created_at DESC
+        "#,
+        team_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Revoke the code. Returns false for unknown or already revoked codes.
+pub async fn revoke_invite_code(pool: &PgPool, invite_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE invites SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        invite_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Use up one registration of the code and return its team. Fails with `RowNotFound` if the code
+/// stopped being usable, e.g. because a concurrent registration took its last use.
+pub async fn redeem_invite_code(conn: &mut PgConnection, code: &str) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE invites
+        SET use_count = use_count + 1
+        WHERE code = $1
+            AND revoked_at IS NULL
+            AND expires_at > NOW()
+            AND use_count < max_uses
+        RETURNING team_id
+        "#,
+        normalize_code(code)
+    )
+    .fetch_one(conn)
+    .await
+}
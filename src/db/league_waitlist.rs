@@ -0,0 +1,193 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::league::LeagueWaitlistEntry;
+
+/// Free slots of a league, or None if it does not exist. Locks the league row so
+/// concurrent assignments cannot push it past its cap.
+pub async fn lock_open_slots(conn: &mut PgConnection, league_id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+    let league = sqlx::query!(
+        "SELECT max_teams FROM leagues WHERE id = $1 FOR UPDATE",
+        league_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(league) = league else {
+        return Ok(None);
+    };
+
+    let team_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM teams WHERE league_id = $1"#,
+        league_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some((league.max_teams as i64 - team_count).max(0)))
+}
+
+/// Queue a team at the end of a league's waitlist and return its position.
+/// Returns None if the team is already waiting for a league.
+pub async fn add_to_waitlist(conn: &mut PgConnection, league_id: Uuid, team_id: Uuid) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO league_waitlist (league_id, team_id, position)
+        SELECT $1, $2, COALESCE(MAX(position), 0) + 1 FROM league_waitlist WHERE league_id = $1
+        ON CONFLICT (team_id) DO NOTHING
+        RETURNING position
+        "#,
+        league_id,
+        team_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+/// A league's waitlist in promotion order
+pub async fn list_waitlist(pool: &PgPool, league_id: Uuid) -> Result<Vec<LeagueWaitlistEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        LeagueWaitlistEntry,
+        r#"
+        SELECT w.team_id, t.team_name, w.position, w.created_at
+        FROM league_waitlist w
+        JOIN teams t ON t.id = w.team_id
+        WHERE w.league_id = $1
+        ORDER BY w.position
+        "#,
+        league_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Put the waitlist in the given order. `team_ids` has to contain exactly the waiting teams;
+/// returns false otherwise.
+pub async fn reorder_waitlist(conn: &mut PgConnection, league_id: Uuid, team_ids: &[Uuid]) -> Result<bool, sqlx::Error> {
+    let mut waiting = sqlx::query_scalar!(
+        "SELECT team_id FROM league_waitlist WHERE league_id = $1 FOR UPDATE",
+        league_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut requested = team_ids.to_vec();
+    requested.sort();
+    requested.dedup();
+    waiting.sort();
+    if requested.len() != team_ids.len() || requested != waiting {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE league_waitlist w
+        SET position = o.position::int
+        FROM UNNEST($2::uuid[]) WITH ORDINALITY AS o(team_id, position)
+        WHERE w.league_id = $1 AND w.team_id = o.team_id
+        "#,
+        league_id,
+        team_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
+
+/// Take a team off a league's waitlist. Returns false if it was not waiting.
+pub async fn remove_from_waitlist(conn: &mut PgConnection, league_id: Uuid, team_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM league_waitlist WHERE league_id = $1 AND team_id = $2",
+        league_id,
+        team_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+    compact_positions(conn, league_id).await?;
+    Ok(true)
+}
+
+/// Take a team off whichever waitlist it is on, e.g. because it was assigned to a league directly
+pub async fn remove_team_from_waitlists(conn: &mut PgConnection, team_id: Uuid) -> Result<(), sqlx::Error> {
+    let league_id = sqlx::query_scalar!(
+        "DELETE FROM league_waitlist WHERE team_id = $1 RETURNING league_id",
+        team_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(league_id) = league_id {
+        compact_positions(conn, league_id).await?;
+    }
+    Ok(())
+}
+
+/// Move waitlisted teams into the league while it has free slots, in position order.
+/// Teams that joined another league in the meantime are dropped from the waitlist.
+/// Returns the promoted teams.
+pub async fn promote_waitlisted_teams(conn: &mut PgConnection, league_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+    let Some(mut open_slots) = lock_open_slots(conn, league_id).await? else {
+        return Ok(Vec::new());
+    };
+
+    let mut promoted = Vec::new();
+    while open_slots > 0 {
+        let next = sqlx::query_scalar!(
+            r#"
+            DELETE FROM league_waitlist
+            WHERE id = (
+                SELECT id FROM league_waitlist WHERE league_id = $1 ORDER BY position LIMIT 1
+            )
+            RETURNING team_id
+            "#,
+            league_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let Some(team_id) = next else {
+            break;
+        };
+
+        let assigned = sqlx::query!(
+            "UPDATE teams SET league_id = $1, updated_at = NOW() WHERE id = $2 AND league_id IS NULL",
+            league_id,
+            team_id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        if assigned.rows_affected() > 0 {
+            promoted.push(team_id);
+            open_slots -= 1;
+        }
+    }
+
+    compact_positions(conn, league_id).await?;
+    Ok(promoted)
+}
+
+/// Renumber positions 1..n after entries left the waitlist
+async fn compact_positions(conn: &mut PgConnection, league_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE league_waitlist w
+        SET position = ranked.new_position::int
+        FROM (
+            SELECT id, ROW_NUMBER() OVER (ORDER BY position) AS new_position
+            FROM league_waitlist
+            WHERE league_id = $1
+        ) ranked
+        WHERE w.id = ranked.id AND w.position <> ranked.new_position
+        "#,
+        league_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
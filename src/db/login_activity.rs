@@ -0,0 +1,127 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::login_activity::{LoginAttempt, LoginFailure, SecurityEvent, SecurityEventType};
+
+/// How far back earlier logins make an address known
+const KNOWN_ADDRESS_DAYS: i32 = 90;
+
+/// Who tried to log in and from where
+#[derive(Debug, Clone)]
+pub struct LoginContext {
+    /// None when the username doesn't belong to an account
+    pub user_id: Option<Uuid>,
+    pub username: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+pub async fn record_login_attempt(
+    pool: &PgPool,
+    login: &LoginContext,
+    failure: Option<LoginFailure>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO login_attempts (user_id, username, ip_address, user_agent, succeeded, failure_reason)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        login.user_id,
+        login.username,
+        login.ip_address,
+        login.user_agent,
+        failure.is_none(),
+        failure.map(|f| f.as_str()),
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// The latest logins to the user's account, newest first
+pub async fn recent_login_attempts(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> Result<Vec<LoginAttempt>, sqlx::Error> {
+    sqlx::query_as!(
+        LoginAttempt,
+        r#"
+        SELECT id, ip_address, user_agent, succeeded, failure_reason, created_at
+        FROM login_attempts
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Whether the user logged in successfully before, and whether from this address
+pub async fn known_login_address(
+    pool: &PgPool,
+    user_id: Uuid,
+    ip_address: &str,
+) -> Result<(bool, bool), sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) > 0 as "logged_in_before!", COALESCE(BOOL_OR(ip_address = $2), false) as "known_address!"
+        FROM login_attempts
+        WHERE user_id = $1 AND succeeded AND created_at > NOW() - make_interval(days => $3)
+        "#,
+        user_id,
+        ip_address,
+        KNOWN_ADDRESS_DAYS
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok((row.logged_in_before, row.known_address))
+}
+
+pub async fn record_security_event(
+    pool: &PgPool,
+    event_type: SecurityEventType,
+    user_id: Option<Uuid>,
+    ip_address: Option<&str>,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO security_events (event_type, user_id, ip_address, details) VALUES ($1, $2, $3, $4)",
+        event_type.as_str(),
+        user_id,
+        ip_address,
+        details
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Security events, newest first, optionally of one user or one type
+pub async fn list_security_events(
+    pool: &PgPool,
+    user_id: Option<Uuid>,
+    event_type: Option<SecurityEventType>,
+    limit: i64,
+) -> Result<Vec<SecurityEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        SecurityEvent,
+        r#"
+        SELECT e.id, e.event_type, e.user_id, u.username as "username?", e.ip_address, e.details, e.created_at
+        FROM security_events e
+        LEFT JOIN users u ON u.id = e.user_id
+        WHERE ($1::uuid IS NULL OR e.user_id = $1)
+          AND ($2::text IS NULL OR e.event_type = $2)
+        ORDER BY e.created_at DESC
+        LIMIT $3
+        "#,
+        user_id,
+        event_type.map(|t| t.as_str()),
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
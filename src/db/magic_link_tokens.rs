@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// Issue a sign-in token for the user, invalidating any earlier unused ones
+pub async fn issue_magic_link_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<String, sqlx::Error> {
+    let token = generate_opaque_token();
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE magic_link_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO magic_link_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        user_id,
+        hash_opaque_token(&token),
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(token)
+}
+
+/// Use up a presented sign-in token, returning the user it was issued to. None if the token is
+/// unknown, expired or already used. Opening the link proves the user owns their email.
+pub async fn consume_magic_link_token(pool: &PgPool, token: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        WITH used AS (
+            UPDATE magic_link_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+        )
+        UPDATE users SET email_verified_at = COALESCE(email_verified_at, NOW())
+        FROM used
+        WHERE users.id = used.user_id
+        RETURNING users.id
+        "#,
+        hash_opaque_token(token)
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -1,6 +1,29 @@
 pub mod workout_data;
+pub mod workout_repo;
 pub mod game_queries;
+pub mod game_repo;
+pub mod team_repo;
 pub mod social;
 pub mod health_data;
 pub mod chat;
-pub mod helpers;
\ No newline at end of file
+pub mod helpers;
+pub mod activities;
+pub mod health_connect;
+pub mod organizations;
+pub mod league_waitlist;
+
+pub mod research_datasets;
+
+pub mod fixture_flavor;
+pub mod refresh_tokens;
+pub mod password_reset_tokens;
+pub mod magic_link_tokens;
+pub mod oauth_identities;
+pub mod user_sessions;
+pub mod account_deletions;
+pub mod api_keys;
+pub mod login_activity;
+pub mod usernames;
+pub mod invites;
+pub mod impersonations;
+pub mod upload_attempts;
@@ -0,0 +1,62 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::auth::OAuthProvider;
+
+/// User a provider account is linked to, recording the login
+pub async fn login_linked_user(
+    pool: &PgPool,
+    provider: OAuthProvider,
+    subject: &str,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE oauth_identities SET last_login_at = NOW()
+        WHERE provider = $1 AND subject = $2
+        RETURNING user_id
+        "#,
+        provider as OAuthProvider,
+        subject
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// User a provider account is linked to, without counting it as a login
+pub async fn linked_user(
+    pool: &PgPool,
+    provider: OAuthProvider,
+    subject: &str,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT user_id FROM oauth_identities WHERE provider = $1 AND subject = $2",
+        provider as OAuthProvider,
+        subject
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Link a provider account to a user. Fails with a unique violation if the user already has
+/// another account of the provider linked.
+pub async fn link_identity(
+    pool: &PgPool,
+    user_id: Uuid,
+    provider: OAuthProvider,
+    subject: &str,
+    email: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO oauth_identities (user_id, provider, subject, email)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        user_id,
+        provider as OAuthProvider,
+        subject,
+        email
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::organization::Organization;
+
+/// All organizations, the default one first
+pub async fn list_organizations(pool: &PgPool) -> Result<Vec<Organization>, sqlx::Error> {
+    sqlx::query_as!(
+        Organization,
+        r#"
+        SELECT id, slug, name, primary_color, secondary_color, logo_key, locale,
+               default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days,
+               is_default, created_at, updated_at
+        FROM organizations
+        ORDER BY is_default DESC, name
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Organization with the given slug, or the default organization if no slug is given
+pub async fn find_organization(pool: &PgPool, slug: Option<&str>) -> Result<Option<Organization>, sqlx::Error> {
+    sqlx::query_as!(
+        Organization,
+        r#"
+        SELECT id, slug, name, primary_color, secondary_color, logo_key, locale,
+               default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days,
+               is_default, created_at, updated_at
+        FROM organizations
+        WHERE ($1::text IS NULL AND is_default) OR slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Organization by id
+pub async fn get_organization(pool: &PgPool, organization_id: Uuid) -> Result<Option<Organization>, sqlx::Error> {
+    sqlx::query_as!(
+        Organization,
+        r#"
+        SELECT id, slug, name, primary_color, secondary_color, logo_key, locale,
+               default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days,
+               is_default, created_at, updated_at
+        FROM organizations
+        WHERE id = $1
+        "#,
+        organization_id
+    )
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// Issue a password reset token for the user, invalidating any earlier unused ones
+pub async fn issue_password_reset_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<String, sqlx::Error> {
+    let token = generate_opaque_token();
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE password_reset_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+        user_id,
+        hash_opaque_token(&token),
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(token)
+}
+
+/// Username and email of the user a presented reset token is valid for, without using it up
+pub async fn password_reset_token_user(pool: &PgPool, token: &str) -> Result<Option<(String, String)>, sqlx::Error> {
+    let user = sqlx::query!(
+        r#"
+        SELECT u.username, u.email
+        FROM password_reset_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = $1 AND t.used_at IS NULL AND t.expires_at > NOW()
+        "#,
+        hash_opaque_token(token)
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(user.map(|user| (user.username, user.email)))
+}
+
+/// Use up a presented reset token, returning the user it was issued to. None if the token is
+/// unknown, expired or already used.
+pub async fn consume_password_reset_token(conn: &mut PgConnection, token: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE password_reset_tokens
+        SET used_at = NOW()
+        WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+        RETURNING user_id
+        "#,
+        hash_opaque_token(token)
+    )
+    .fetch_optional(conn)
+    .await
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// Stored refresh token, looked up by the token a client presented
+#[derive(Debug)]
+pub struct StoredRefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Issue a refresh token in the family, returning its id and the token for the client
+pub async fn issue_refresh_token(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    family_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<(Uuid, String), sqlx::Error> {
+    let token = generate_opaque_token();
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        user_id,
+        family_id,
+        hash_opaque_token(&token),
+        expires_at
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok((id, token))
+}
+
+/// The stored token matching a presented one, locked so it can only be rotated once
+pub async fn lock_refresh_token(conn: &mut PgConnection, token: &str) -> Result<Option<StoredRefreshToken>, sqlx::Error> {
+    sqlx::query_as!(
+        StoredRefreshToken,
+        r#"
+        SELECT id, user_id, family_id, expires_at, revoked_at
+        FROM refresh_tokens
+        WHERE token_hash = $1
+        FOR UPDATE
+        "#,
+        hash_opaque_token(token)
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// Revoke a token that was exchanged for its successor
+pub async fn mark_rotated(conn: &mut PgConnection, id: Uuid, replaced_by: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW(), replaced_by = $2 WHERE id = $1",
+        id,
+        replaced_by
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Revoke every live token of a family and its session, i.e. log out the device it was issued to
+pub async fn revoke_family(conn: &mut PgConnection, family_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE family_id = $1 AND revoked_at IS NULL",
+        family_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query!(
+        "UPDATE user_sessions SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        family_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Revoke every live token and session of a user, logging them out on all devices
+pub async fn revoke_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let result = sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "UPDATE user_sessions SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,157 @@
+//! Aggregate queries behind the research datasets.
+//! Only adults with a known age who did not opt out of research data sharing are counted,
+//! and no query returns anything that identifies a user.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::research::{AdherenceRow, ZoneMinutesRow};
+
+/// Minutes per heart rate zone and age band for workouts started in [from, to)
+pub async fn zone_minutes_by_age_band(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<ZoneMinutesRow>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        WITH participants AS (
+            SELECT
+                hp.user_id,
+                CASE
+                    WHEN hp.age < 30 THEN '18-29'
+                    WHEN hp.age < 40 THEN '30-39'
+                    WHEN hp.age < 50 THEN '40-49'
+                    WHEN hp.age < 60 THEN '50-59'
+                    ELSE '60+'
+                END AS age_band
+            FROM user_health_profiles hp
+            WHERE hp.age >= 18
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_consent_settings cs
+                  WHERE cs.user_id = hp.user_id AND NOT cs.research_data_sharing
+              )
+        ),
+        zone_rows AS (
+            SELECT p.user_id, p.age_band, wd.id AS workout_id,
+                   z.value->>'zone' AS zone, (z.value->>'minutes')::float8 AS minutes
+            FROM participants p
+            JOIN workout_data wd ON wd.user_id = p.user_id
+            CROSS JOIN LATERAL jsonb_array_elements(
+                CASE WHEN jsonb_typeof(wd.heart_rate_zones) = 'array' THEN wd.heart_rate_zones ELSE '[]'::jsonb END
+            ) z
+            WHERE z.value ? 'zone'
+              AND wd.workout_start >= $1
+              AND wd.workout_start < $2
+        )
+        SELECT
+            age_band as "age_band!",
+            zone as "zone!",
+            COUNT(DISTINCT user_id) as "user_count!",
+            COUNT(DISTINCT workout_id) as "workout_count!",
+            COALESCE(SUM(minutes), 0) as "total_minutes!"
+        FROM zone_rows
+        GROUP BY age_band, zone
+        ORDER BY age_band, zone
+        "#,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ZoneMinutesRow {
+            avg_minutes_per_user: round1(row.total_minutes / row.user_count.max(1) as f64),
+            total_minutes: round1(row.total_minutes),
+            age_band: row.age_band,
+            zone: row.zone,
+            user_count: row.user_count,
+            workout_count: row.workout_count,
+        })
+        .collect())
+}
+
+/// Weekly adherence per age band. Each user's week 0 starts at their first workout in [from, to),
+/// and a week only counts users whose whole week lies before `to`.
+pub async fn adherence_curves(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    weeks: i32,
+) -> Result<Vec<AdherenceRow>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        WITH participants AS (
+            SELECT
+                hp.user_id,
+                CASE
+                    WHEN hp.age < 30 THEN '18-29'
+                    WHEN hp.age < 40 THEN '30-39'
+                    WHEN hp.age < 50 THEN '40-49'
+                    WHEN hp.age < 60 THEN '50-59'
+                    ELSE '60+'
+                END AS age_band
+            FROM user_health_profiles hp
+            WHERE hp.age >= 18
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_consent_settings cs
+                  WHERE cs.user_id = hp.user_id AND NOT cs.research_data_sharing
+              )
+        ),
+        workouts AS (
+            SELECT p.user_id, p.age_band, wd.workout_start
+            FROM participants p
+            JOIN workout_data wd ON wd.user_id = p.user_id
+            WHERE wd.workout_start >= $1 AND wd.workout_start < $2
+        ),
+        cohort AS (
+            SELECT user_id, age_band, MIN(workout_start) AS first_workout
+            FROM workouts
+            GROUP BY user_id, age_band
+        ),
+        observed AS (
+            SELECT c.user_id, c.age_band, w.week
+            FROM cohort c
+            CROSS JOIN generate_series(0, $3 - 1) AS w(week)
+            WHERE c.first_workout + (w.week + 1) * INTERVAL '7 days' <= $2
+        ),
+        active AS (
+            SELECT DISTINCT w.user_id,
+                   FLOOR(EXTRACT(EPOCH FROM (w.workout_start - c.first_workout)) / 604800)::int AS week
+            FROM workouts w
+            JOIN cohort c ON c.user_id = w.user_id
+        )
+        SELECT
+            o.age_band as "age_band!",
+            o.week as "week!",
+            COUNT(*) as "cohort_size!",
+            COUNT(a.user_id) as "active_users!"
+        FROM observed o
+        LEFT JOIN active a ON a.user_id = o.user_id AND a.week = o.week
+        GROUP BY o.age_band, o.week
+        ORDER BY o.age_band, o.week
+        "#,
+        from,
+        to,
+        weeks
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| AdherenceRow {
+            adherence_rate: (row.active_users as f64 / row.cohort_size.max(1) as f64 * 1000.0).round() / 1000.0,
+            age_band: row.age_band,
+            week: row.week,
+            cohort_size: row.cohort_size,
+            active_users: row.active_users,
+        })
+        .collect())
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
@@ -6,25 +6,104 @@ use crate::models::social::{
     WorkoutReactionWithUser, WorkoutReactionSummary,
     CommentReaction, CommentReactionWithUser, CommentReactionSummary,
     NotificationWithUser, NotificationListResponse,
+    Reaction, ReactionTarget, ReactionWithUser, TargetReactionSummary,
 };
 
-pub async fn create_reaction(
+// ============================================================================
+// REACTION FUNCTIONS
+// ============================================================================
+
+/// Whether workout `wd` is visible to viewer `$2`: it is their own, or it has a post that isn't private
+const WORKOUT_VISIBLE: &str =
+    "(wd.user_id = $2 OR EXISTS (SELECT 1 FROM posts wp WHERE wp.workout_id = wd.id AND wp.visibility <> 'private'))";
+
+/// Owner of a reaction target, `None` if the target does not exist or the viewer cannot see it.
+/// Workouts and comments are only as visible as the post they belong to. Game summaries exist
+/// without an owner.
+pub async fn get_reaction_target_owner(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_id: Uuid,
+    viewer_id: Uuid,
+) -> Result<Option<Option<Uuid>>, sqlx::Error> {
+    let owner = match target {
+        ReactionTarget::Workout => {
+            sqlx::query_scalar::<_, Uuid>(&format!(
+                "SELECT wd.user_id FROM workout_data wd WHERE wd.id = $1 AND {WORKOUT_VISIBLE}"
+            ))
+            .bind(target_id)
+            .bind(viewer_id)
+            .fetch_optional(pool)
+            .await?
+            .map(Some)
+        }
+        ReactionTarget::Comment => {
+            // Comments belong either to a post or, from before posts, to a workout
+            sqlx::query_scalar::<_, Uuid>(&format!(
+                r#"
+                SELECT c.user_id
+                FROM post_comments c
+                LEFT JOIN posts p ON p.id = c.post_id
+                LEFT JOIN workout_data wd ON wd.id = c.workout_id
+                WHERE c.id = $1
+                  AND ((p.id IS NOT NULL AND (p.visibility <> 'private' OR p.user_id = $2))
+                       OR (wd.id IS NOT NULL AND {WORKOUT_VISIBLE}))
+                "#
+            ))
+            .bind(target_id)
+            .bind(viewer_id)
+            .fetch_optional(pool)
+            .await?
+            .map(Some)
+        }
+        ReactionTarget::Post => {
+            sqlx::query_scalar::<_, Uuid>(
+                "SELECT user_id FROM posts WHERE id = $1 AND (visibility <> 'private' OR user_id = $2)",
+            )
+            .bind(target_id)
+            .bind(viewer_id)
+            .fetch_optional(pool)
+            .await?
+            .map(Some)
+        }
+        ReactionTarget::GameSummary => {
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM game_summaries WHERE id = $1")
+                .bind(target_id)
+                .fetch_optional(pool)
+                .await?
+                .map(|_| None)
+        }
+        ReactionTarget::ScoreEvent => {
+            sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM live_score_events WHERE id = $1")
+                .bind(target_id)
+                .fetch_optional(pool)
+                .await?
+                .map(Some)
+        }
+    };
+
+    Ok(owner)
+}
+
+pub async fn create_target_reaction(
     pool: &PgPool,
     user_id: Uuid,
-    workout_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
     reaction_type: &str,
-) -> Result<WorkoutReaction, sqlx::Error> {
-    let reaction = sqlx::query_as::<_, WorkoutReaction>(
+) -> Result<Reaction, sqlx::Error> {
+    let reaction = sqlx::query_as::<_, Reaction>(
         r#"
-        INSERT INTO post_reactions (user_id, workout_id, reaction_type)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (user_id, workout_id)
-        DO UPDATE SET reaction_type = $3, created_at = NOW()
-        RETURNING id, user_id, workout_id, reaction_type, created_at
+        INSERT INTO reactions (user_id, target_type, target_id, reaction_type)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, target_type, target_id)
+        DO UPDATE SET reaction_type = $4, created_at = NOW()
+        RETURNING id, user_id, target_type, target_id, reaction_type, created_at
         "#,
     )
     .bind(user_id)
-    .bind(workout_id)
+    .bind(target.as_str())
+    .bind(target_id)
     .bind(reaction_type)
     .fetch_one(pool)
     .await?;
@@ -32,96 +111,86 @@ pub async fn create_reaction(
     Ok(reaction)
 }
 
-pub async fn delete_reaction(
+pub async fn delete_target_reaction(
     pool: &PgPool,
     user_id: Uuid,
-    workout_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
 ) -> Result<bool, sqlx::Error> {
     let result = sqlx::query(
         r#"
-        DELETE FROM post_reactions
-        WHERE user_id = $1 AND workout_id = $2
+        DELETE FROM reactions
+        WHERE user_id = $1 AND target_type = $2 AND target_id = $3
         "#,
     )
     .bind(user_id)
-    .bind(workout_id)
+    .bind(target.as_str())
+    .bind(target_id)
     .execute(pool)
     .await?;
 
     Ok(result.rows_affected() > 0)
 }
 
-pub async fn get_workout_reactions(
+pub async fn get_target_reactions(
     pool: &PgPool,
-    workout_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
     current_user_id: Option<Uuid>,
-) -> Result<WorkoutReactionSummary, sqlx::Error> {
+) -> Result<TargetReactionSummary, sqlx::Error> {
     // Get count of fire reactions and whether current user reacted
     let result = sqlx::query(
         r#"
         SELECT
-            COALESCE(COUNT(wr.id), 0) as fire_count,
-            COALESCE(BOOL_OR(wr.user_id = $2), false) as user_reacted
-        FROM post_reactions wr
-        WHERE wr.workout_id = $1 AND wr.reaction_type = 'fire'
+            COALESCE(COUNT(r.id), 0) as fire_count,
+            COALESCE(BOOL_OR(r.user_id = $3), false) as user_reacted
+        FROM reactions r
+        WHERE r.target_type = $1 AND r.target_id = $2 AND r.reaction_type = 'fire'
         "#,
     )
-    .bind(workout_id)
+    .bind(target.as_str())
+    .bind(target_id)
     .bind(current_user_id)
     .fetch_one(pool)
     .await?;
 
-    Ok(WorkoutReactionSummary {
-        workout_id,
+    Ok(TargetReactionSummary {
+        target_type: target.as_str().to_string(),
+        target_id,
         fire_count: result.get("fire_count"),
         user_reacted: result.get("user_reacted"),
     })
 }
 
-pub async fn get_reaction_users(
+pub async fn get_target_reaction_users(
     pool: &PgPool,
-    workout_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
     reaction_type: Option<&str>,
-) -> Result<Vec<WorkoutReactionWithUser>, sqlx::Error> {
-    let query = if let Some(reaction_type) = reaction_type {
-        sqlx::query(
-            r#"
-            SELECT
-                wr.id,
-                wr.user_id,
-                u.username,
-                wr.reaction_type,
-                wr.created_at
-            FROM post_reactions wr
-            INNER JOIN users u ON u.id = wr.user_id
-            WHERE wr.workout_id = $1 AND wr.reaction_type = $2
-            ORDER BY wr.created_at DESC
-            "#,
-        )
-        .bind(workout_id)
-        .bind(reaction_type)
-    } else {
-        sqlx::query(
-            r#"
-            SELECT
-                wr.id,
-                wr.user_id,
-                u.username,
-                wr.reaction_type,
-                wr.created_at
-            FROM post_reactions wr
-            INNER JOIN users u ON u.id = wr.user_id
-            WHERE wr.workout_id = $1
-            ORDER BY wr.created_at DESC
-            "#,
-        )
-        .bind(workout_id)
-    };
+) -> Result<Vec<ReactionWithUser>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            r.id,
+            r.user_id,
+            u.username,
+            r.reaction_type,
+            r.created_at
+        FROM reactions r
+        INNER JOIN users u ON u.id = r.user_id
+        WHERE r.target_type = $1 AND r.target_id = $2 AND ($3::text IS NULL OR r.reaction_type = $3)
+        ORDER BY r.created_at DESC
+        "#,
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .bind(reaction_type)
+    .fetch_all(pool)
+    .await?;
 
-    let rows = query.fetch_all(pool).await?;
     let reactions = rows
         .into_iter()
-        .map(|row| WorkoutReactionWithUser {
+        .map(|row| ReactionWithUser {
             id: row.get("id"),
             user_id: row.get("user_id"),
             username: row.get("username"),
@@ -132,6 +201,53 @@ pub async fn get_reaction_users(
     Ok(reactions)
 }
 
+pub async fn create_reaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    workout_id: Uuid,
+    reaction_type: &str,
+) -> Result<WorkoutReaction, sqlx::Error> {
+    let reaction = create_target_reaction(pool, user_id, ReactionTarget::Workout, workout_id, reaction_type).await?;
+
+    Ok(WorkoutReaction {
+        id: reaction.id,
+        user_id: reaction.user_id,
+        workout_id: reaction.target_id,
+        reaction_type: reaction.reaction_type,
+        created_at: reaction.created_at,
+    })
+}
+
+pub async fn delete_reaction(
+    pool: &PgPool,
+    user_id: Uuid,
+    workout_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    delete_target_reaction(pool, user_id, ReactionTarget::Workout, workout_id).await
+}
+
+pub async fn get_workout_reactions(
+    pool: &PgPool,
+    workout_id: Uuid,
+    current_user_id: Option<Uuid>,
+) -> Result<WorkoutReactionSummary, sqlx::Error> {
+    let summary = get_target_reactions(pool, ReactionTarget::Workout, workout_id, current_user_id).await?;
+
+    Ok(WorkoutReactionSummary {
+        workout_id,
+        fire_count: summary.fire_count,
+        user_reacted: summary.user_reacted,
+    })
+}
+
+pub async fn get_reaction_users(
+    pool: &PgPool,
+    workout_id: Uuid,
+    reaction_type: Option<&str>,
+) -> Result<Vec<WorkoutReactionWithUser>, sqlx::Error> {
+    get_target_reaction_users(pool, ReactionTarget::Workout, workout_id, reaction_type).await
+}
+
 pub async fn create_comment(
     pool: &PgPool,
     user_id: Uuid,
@@ -235,7 +351,7 @@ pub async fn get_workout_comments(
             COALESCE(BOOL_OR(cr.user_id = $4), false) as user_reacted
         FROM post_comments c
         INNER JOIN users u ON u.id = c.user_id
-        LEFT JOIN post_comment_reactions cr ON cr.comment_id = c.id AND cr.reaction_type = 'fire'
+        LEFT JOIN reactions cr ON cr.target_type = 'comment' AND cr.target_id = c.id AND cr.reaction_type = 'fire'
         WHERE c.workout_id = $1 AND c.parent_id IS NULL
         GROUP BY c.id, c.user_id, u.username, c.workout_id, c.parent_id, c.content, c.is_edited, c.created_at, c.updated_at
         ORDER BY c.created_at DESC
@@ -284,7 +400,7 @@ pub async fn get_workout_comments(
                 COALESCE(BOOL_OR(cr.user_id = $2), false) as user_reacted
             FROM post_comments c
             INNER JOIN users u ON u.id = c.user_id
-            LEFT JOIN post_comment_reactions cr ON cr.comment_id = c.id AND cr.reaction_type = 'fire'
+            LEFT JOIN reactions cr ON cr.target_type = 'comment' AND cr.target_id = c.id AND cr.reaction_type = 'fire'
             WHERE c.parent_id = $1
             GROUP BY c.id, c.user_id, u.username, c.workout_id, c.parent_id, c.content, c.is_edited, c.created_at, c.updated_at
             ORDER BY c.created_at ASC
@@ -376,22 +492,15 @@ pub async fn create_comment_reaction(
     comment_id: Uuid,
     reaction_type: &str,
 ) -> Result<CommentReaction, sqlx::Error> {
-    let reaction = sqlx::query_as::<_, CommentReaction>(
-        r#"
-        INSERT INTO post_comment_reactions (user_id, comment_id, reaction_type)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (user_id, comment_id)
-        DO UPDATE SET reaction_type = $3, created_at = NOW()
-        RETURNING id, user_id, comment_id, reaction_type, created_at
-        "#,
-    )
-    .bind(user_id)
-    .bind(comment_id)
-    .bind(reaction_type)
-    .fetch_one(pool)
-    .await?;
-
-    Ok(reaction)
+    let reaction = create_target_reaction(pool, user_id, ReactionTarget::Comment, comment_id, reaction_type).await?;
+
+    Ok(CommentReaction {
+        id: reaction.id,
+        user_id: reaction.user_id,
+        comment_id: reaction.target_id,
+        reaction_type: reaction.reaction_type,
+        created_at: reaction.created_at,
+    })
 }
 
 pub async fn delete_comment_reaction(
@@ -399,18 +508,7 @@ pub async fn delete_comment_reaction(
     user_id: Uuid,
     comment_id: Uuid,
 ) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query(
-        r#"
-        DELETE FROM post_comment_reactions
-        WHERE user_id = $1 AND comment_id = $2
-        "#,
-    )
-    .bind(user_id)
-    .bind(comment_id)
-    .execute(pool)
-    .await?;
-
-    Ok(result.rows_affected() > 0)
+    delete_target_reaction(pool, user_id, ReactionTarget::Comment, comment_id).await
 }
 
 pub async fn get_comment_reactions(
@@ -418,25 +516,12 @@ pub async fn get_comment_reactions(
     comment_id: Uuid,
     current_user_id: Option<Uuid>,
 ) -> Result<CommentReactionSummary, sqlx::Error> {
-    // Get count of fire reactions and whether current user reacted
-    let result = sqlx::query(
-        r#"
-        SELECT
-            COALESCE(COUNT(cr.id), 0) as fire_count,
-            COALESCE(BOOL_OR(cr.user_id = $2), false) as user_reacted
-        FROM post_comment_reactions cr
-        WHERE cr.comment_id = $1 AND cr.reaction_type = 'fire'
-        "#,
-    )
-    .bind(comment_id)
-    .bind(current_user_id)
-    .fetch_one(pool)
-    .await?;
+    let summary = get_target_reactions(pool, ReactionTarget::Comment, comment_id, current_user_id).await?;
 
     Ok(CommentReactionSummary {
         comment_id,
-        fire_count: result.get("fire_count"),
-        user_reacted: result.get("user_reacted"),
+        fire_count: summary.fire_count,
+        user_reacted: summary.user_reacted,
     })
 }
 
@@ -445,53 +530,7 @@ pub async fn get_comment_reaction_users(
     comment_id: Uuid,
     reaction_type: Option<&str>,
 ) -> Result<Vec<CommentReactionWithUser>, sqlx::Error> {
-    let query = if let Some(reaction_type) = reaction_type {
-        sqlx::query(
-            r#"
-            SELECT
-                cr.id,
-                cr.user_id,
-                u.username,
-                cr.reaction_type,
-                cr.created_at
-            FROM post_comment_reactions cr
-            INNER JOIN users u ON u.id = cr.user_id
-            WHERE cr.comment_id = $1 AND cr.reaction_type = $2
-            ORDER BY cr.created_at DESC
-            "#,
-        )
-        .bind(comment_id)
-        .bind(reaction_type)
-    } else {
-        sqlx::query(
-            r#"
-            SELECT
-                cr.id,
-                cr.user_id,
-                u.username,
-                cr.reaction_type,
-                cr.created_at
-            FROM post_comment_reactions cr
-            INNER JOIN users u ON u.id = cr.user_id
-            WHERE cr.comment_id = $1
-            ORDER BY cr.created_at DESC
-            "#,
-        )
-        .bind(comment_id)
-    };
-
-    let rows = query.fetch_all(pool).await?;
-    let reactions = rows
-        .into_iter()
-        .map(|row| CommentReactionWithUser {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            username: row.get("username"),
-            reaction_type: row.get("reaction_type"),
-            created_at: row.get("created_at"),
-        })
-        .collect();
-    Ok(reactions)
+    get_target_reaction_users(pool, ReactionTarget::Comment, comment_id, reaction_type).await
 }
 
 // ============================================================================
@@ -0,0 +1,73 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::team::TeamRole;
+
+/// A user's active membership in a team
+#[derive(Debug, Clone, Copy)]
+pub struct TeamMembership {
+    pub team_id: Uuid,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Read access to teams, implemented by `TeamRepo` and by mocks in tests
+pub trait TeamRepository {
+    /// Role of an active member, `None` if the user is not an active member of the team
+    fn find_active_role(&self, team_id: Uuid, user_id: Uuid) -> impl Future<Output = Result<Option<TeamRole>, sqlx::Error>> + Send;
+
+    /// The user's active membership in either of the given teams
+    fn find_active_membership(
+        &self,
+        user_id: Uuid,
+        team_ids: [Uuid; 2],
+    ) -> impl Future<Output = Result<Option<TeamMembership>, sqlx::Error>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamRepo {
+    pool: PgPool,
+}
+
+impl TeamRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TeamRepository for TeamRepo {
+    async fn find_active_role(&self, team_id: Uuid, user_id: Uuid) -> Result<Option<TeamRole>, sqlx::Error> {
+        let result = sqlx::query!(
+            "SELECT role FROM team_members WHERE team_id = $1 AND user_id = $2 AND status = 'active'",
+            team_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match result {
+            Some(row) => Ok(Some(row.role.parse().map_err(|_| sqlx::Error::TypeNotFound { type_name: "TeamRole".to_string() })?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_active_membership(&self, user_id: Uuid, team_ids: [Uuid; 2]) -> Result<Option<TeamMembership>, sqlx::Error> {
+        sqlx::query_as!(
+            TeamMembership,
+            r#"
+            SELECT team_id, joined_at
+            FROM team_members
+            WHERE user_id = $1
+            AND status = 'active'
+            AND (team_id = $2 OR team_id = $3)
+            "#,
+            user_id,
+            team_ids[0],
+            team_ids[1]
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
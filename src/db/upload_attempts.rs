@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::diagnostics::{DeviceSync, UploadAttempt, UploadOutcome, UploadOutcomeCount};
+
+/// An upload attempt to record
+pub struct NewUploadAttempt<'a> {
+    pub device_id: Option<&'a str>,
+    pub workout_uuid: &'a str,
+    pub outcome: UploadOutcome,
+    pub detail: Option<&'a str>,
+    pub workout_data_id: Option<Uuid>,
+    pub workout_start: Option<DateTime<Utc>>,
+    pub workout_end: Option<DateTime<Utc>>,
+}
+
+/// Record an upload attempt; another attempt of the workout with the same outcome is counted on
+/// its row
+pub async fn record_upload_attempt(
+    pool: &PgPool,
+    user_id: Uuid,
+    attempt: &NewUploadAttempt<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO workout_upload_attempts
+            (user_id, device_id, workout_uuid, outcome, detail, workout_data_id, workout_start, workout_end)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (user_id, workout_uuid, outcome) DO UPDATE
+        SET device_id = COALESCE(EXCLUDED.device_id, workout_upload_attempts.device_id),
+            detail = EXCLUDED.detail,
+            workout_data_id = COALESCE(EXCLUDED.workout_data_id, workout_upload_attempts.workout_data_id),
+            workout_start = COALESCE(EXCLUDED.workout_start, workout_upload_attempts.workout_start),
+            workout_end = COALESCE(EXCLUDED.workout_end, workout_upload_attempts.workout_end),
+            attempt_count = workout_upload_attempts.attempt_count + 1,
+            last_attempt_at = NOW()
+        "#,
+        user_id,
+        attempt.device_id,
+        attempt.workout_uuid,
+        attempt.outcome.as_str(),
+        attempt.detail,
+        attempt.workout_data_id,
+        attempt.workout_start,
+        attempt.workout_end
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// The user's upload attempts since the given time, newest first
+pub async fn list_upload_attempts(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<UploadAttempt>, sqlx::Error> {
+    sqlx::query_as!(
+        UploadAttempt,
+        r#"
+        SELECT id, device_id, workout_uuid, outcome, detail, workout_data_id, workout_start, workout_end,
+               attempt_count, first_attempt_at, last_attempt_at
+        FROM workout_upload_attempts
+        WHERE user_id = $1 AND last_attempt_at >= $2
+        ORDER BY last_attempt_at DESC
+        LIMIT $3
+        "#,
+        user_id,
+        since,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Number of upload attempts by outcome since the given time
+pub async fn count_upload_outcomes(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<Vec<UploadOutcomeCount>, sqlx::Error> {
+    sqlx::query_as!(
+        UploadOutcomeCount,
+        r#"
+        SELECT outcome, SUM(attempt_count)::BIGINT AS "attempts!"
+        FROM workout_upload_attempts
+        WHERE user_id = $1 AND last_attempt_at >= $2
+        GROUP BY outcome
+        ORDER BY outcome
+        "#,
+        user_id,
+        since
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Latest syncs of each of the user's devices, most recently active first
+pub async fn device_syncs(pool: &PgPool, user_id: Uuid) -> Result<Vec<DeviceSync>, sqlx::Error> {
+    sqlx::query_as!(
+        DeviceSync,
+        r#"
+        SELECT device_id AS "device_id!",
+               MAX(last_upload_at) AS last_upload_at,
+               MAX(last_attempt_at) AS last_attempt_at,
+               MAX(last_delta_sync_at) AS last_delta_sync_at,
+               MAX(change_token_expires_at) AS change_token_expires_at
+        FROM (
+            SELECT device_id, MAX(created_at) AS last_upload_at, NULL::TIMESTAMPTZ AS last_attempt_at,
+                   NULL::TIMESTAMPTZ AS last_delta_sync_at, NULL::TIMESTAMPTZ AS change_token_expires_at
+            FROM workout_data
+            WHERE user_id = $1 AND device_id IS NOT NULL
+            GROUP BY device_id
+            UNION ALL
+            SELECT device_id, NULL, MAX(last_attempt_at), NULL, NULL
+            FROM workout_upload_attempts
+            WHERE user_id = $1 AND device_id IS NOT NULL
+            GROUP BY device_id
+            UNION ALL
+            SELECT device_id, NULL, NULL, last_used_at, expires_at
+            FROM health_connect_change_tokens
+            WHERE user_id = $1
+        ) syncs
+        GROUP BY device_id
+        ORDER BY GREATEST(MAX(last_upload_at), MAX(last_attempt_at), MAX(last_delta_sync_at)) DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete attempts last made before the given time
+pub async fn prune_upload_attempts(pool: &PgPool, before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM workout_upload_attempts WHERE last_attempt_at < $1", before)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
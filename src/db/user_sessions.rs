@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::auth::{SessionDevice, UserSession};
+
+/// Record a newly logged in device. The session id doubles as the family id of its refresh tokens.
+pub async fn start_session(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    device: &SessionDevice,
+    expires_at: DateTime<Utc>,
+) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO user_sessions (id, user_id, device_id, user_agent, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+        Uuid::new_v4(),
+        user_id,
+        device.device_id,
+        device.user_agent,
+        expires_at
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Note that the session's device refreshed its tokens, extending the session to the new refresh
+/// token. Families issued before sessions existed get their session here.
+pub async fn refresh_session(
+    conn: &mut PgConnection,
+    session_id: Uuid,
+    user_id: Uuid,
+    device: &SessionDevice,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO user_sessions (id, user_id, device_id, user_agent, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (id) DO UPDATE SET
+            device_id = COALESCE(user_sessions.device_id, EXCLUDED.device_id),
+            user_agent = COALESCE(EXCLUDED.user_agent, user_sessions.user_agent),
+            last_seen_at = NOW(),
+            expires_at = EXCLUDED.expires_at
+        "#,
+        session_id,
+        user_id,
+        device.device_id,
+        device.user_agent,
+        expires_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Whether access tokens of the session may still be used: it is neither revoked nor expired.
+/// Records that the device was seen, at most once a minute so busy clients don't write on every request.
+pub async fn check_session(pool: &PgPool, session_id: Uuid) -> Result<bool, sqlx::Error> {
+    let Some(session) = sqlx::query!(
+        r#"
+        SELECT revoked_at IS NULL as "active!", last_seen_at < NOW() - INTERVAL '1 minute' as "stale!"
+        FROM user_sessions
+        WHERE id = $1 AND expires_at > NOW()
+        "#,
+        session_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    if session.active && session.stale {
+        sqlx::query!("UPDATE user_sessions SET last_seen_at = NOW() WHERE id = $1", session_id)
+            .execute(pool)
+            .await?;
+    }
+    Ok(session.active)
+}
+
+/// The user's sessions that are neither revoked nor expired, most recently seen first
+pub async fn list_sessions(pool: &PgPool, user_id: Uuid, current: Option<Uuid>) -> Result<Vec<UserSession>, sqlx::Error> {
+    sqlx::query_as!(
+        UserSession,
+        r#"
+        SELECT id, device_id, user_agent, created_at, last_seen_at, expires_at,
+            id IS NOT DISTINCT FROM $2 as "current!"
+        FROM user_sessions
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ORDER BY last_seen_at DESC
+        "#,
+        user_id,
+        current
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Log out one of the user's devices. Returns false if the user has no such live session.
+pub async fn revoke_session(pool: &PgPool, user_id: Uuid, session_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let revoked = sqlx::query!(
+        "UPDATE user_sessions SET revoked_at = NOW() WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        session_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE family_id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        session_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(revoked.rows_affected() > 0)
+}
+
+/// Log out all of the user's devices except the one making the request. Returns how many were logged out.
+pub async fn revoke_other_sessions(pool: &PgPool, user_id: Uuid, keep: Option<Uuid>) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let revoked = sqlx::query!(
+        r#"
+        UPDATE user_sessions SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND id IS DISTINCT FROM $2
+        "#,
+        user_id,
+        keep
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND family_id IS DISTINCT FROM $2
+        "#,
+        user_id,
+        keep
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(revoked.rows_affected())
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Whether another account uses the username, compared regardless of case
+pub async fn is_username_taken(pool: &PgPool, username: &str, except_user_id: Option<Uuid>) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM users
+            WHERE LOWER(username) = LOWER($1) AND ($2::uuid IS NULL OR id <> $2)
+        ) as "exists!"
+        "#,
+        username,
+        except_user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// When the user last changed their username, None if they never did
+pub async fn last_username_change(conn: &mut PgConnection, user_id: Uuid) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT MAX(changed_at) as "changed_at" FROM username_history WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Rename the user and record the change. Names copied into score events and game summaries
+/// are renamed too, so games keep showing who scored under their current name.
+pub async fn change_username(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    old_username: &str,
+    new_username: &str,
+) -> Result<DateTime<Utc>, sqlx::Error> {
+    sqlx::query!(
+        "UPDATE users SET username = $2, updated_at = NOW() WHERE id = $1",
+        user_id,
+        new_username
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let changed_at = sqlx::query_scalar!(
+        r#"
+        INSERT INTO username_history (user_id, old_username, new_username)
+        VALUES ($1, $2, $3)
+        RETURNING changed_at
+        "#,
+        user_id,
+        old_username,
+        new_username
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE live_score_events SET username = $2 WHERE user_id = $1",
+        user_id,
+        new_username
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE game_summaries SET
+            mvp_username = CASE WHEN mvp_user_id = $1 THEN $2 ELSE mvp_username END,
+            lvp_username = CASE WHEN lvp_user_id = $1 THEN $2 ELSE lvp_username END,
+            home_team_top_scorer_username = CASE WHEN home_team_top_scorer_id = $1 THEN $2 ELSE home_team_top_scorer_username END,
+            home_team_lowest_performer_username = CASE WHEN home_team_lowest_performer_id = $1 THEN $2 ELSE home_team_lowest_performer_username END,
+            away_team_top_scorer_username = CASE WHEN away_team_top_scorer_id = $1 THEN $2 ELSE away_team_top_scorer_username END,
+            away_team_lowest_performer_username = CASE WHEN away_team_lowest_performer_id = $1 THEN $2 ELSE away_team_lowest_performer_username END
+        WHERE $1 IN (
+            mvp_user_id, lvp_user_id, home_team_top_scorer_id, home_team_lowest_performer_id,
+            away_team_top_scorer_id, away_team_lowest_performer_id
+        )
+        "#,
+        user_id,
+        new_username
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(changed_at)
+}
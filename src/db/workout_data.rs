@@ -1,10 +1,12 @@
-use sqlx::{Pool, Postgres};
+use sqlx::{PgConnection, Pool, Postgres};
 use uuid::Uuid;
 use chrono::{Duration, DateTime, Utc};
 
 use crate::{
     services::ml_client::ClassifyResponse,
-    models::workout_data::{HeartRateData, WorkoutDataUploadRequest, WorkoutStats, ZoneBreakdown}
+    models::workout_data::{HeartRateData, StoredWorkoutFingerprint, WorkoutDataUploadRequest, WorkoutStats, ZoneBreakdown},
+    models::post::{PostVisibility, WorkoutPostingSettings},
+    workout::intervals::IntervalAnalysis,
 };
 
 /// Calculate duration in minutes from start/end times
@@ -39,7 +41,7 @@ fn calculate_min_heart_rate(heart_rate_data: &[HeartRateData]) -> Option<i32> {
 
 #[tracing::instrument(
     name = "Insert workout data into database",
-    skip(pool, data),
+    skip(conn, data),
     fields(
         user_id = %user_id,
         workout_uuid = ?data.workout_uuid,
@@ -47,7 +49,7 @@ fn calculate_min_heart_rate(heart_rate_data: &[HeartRateData]) -> Option<i32> {
     )
 )]
 pub async fn insert_workout_data(
-    pool: &Pool<Postgres>,
+    conn: &mut PgConnection,
     user_id: Uuid,
     data: &WorkoutDataUploadRequest,
     workout_stats: &WorkoutStats,
@@ -111,7 +113,7 @@ pub async fn insert_workout_data(
         data.activity_name.as_deref(),
         "public"  // Default visibility for all workouts
     )
-    .fetch_one(pool)
+    .fetch_one(conn)
     .await?;
     
     tracing::info!("Successfully inserted workout data with id: {}", record.id);
@@ -163,11 +165,11 @@ pub async fn check_workout_exists_by_time(
     Ok(record.is_some())
 }
 
-/// Check if a workout overlaps with any existing workouts for the user
+/// Find an existing workout of the user that overlaps the given interval
 /// Two workouts overlap if their time intervals intersect (including exact duplicates)
 /// Uses a 1-second tolerance to avoid false positives from rounding/precision issues
 #[tracing::instrument(
-    name = "Check workout overlap",
+    name = "Find overlapping workout",
     skip(pool),
     fields(
         user_id = %user_id,
@@ -175,15 +177,21 @@ pub async fn check_workout_exists_by_time(
         workout_end = %workout_end
     )
 )]
-pub async fn check_workout_overlap(
+pub async fn find_overlapping_workout(
     pool: &Pool<Postgres>,
     user_id: Uuid,
     workout_start: &DateTime<Utc>,
     workout_end: &DateTime<Utc>,
-) -> Result<bool, sqlx::Error> {
-    let record = sqlx::query!(
+) -> Result<Option<StoredWorkoutFingerprint>, sqlx::Error> {
+    sqlx::query_as!(
+        StoredWorkoutFingerprint,
         r#"
-        SELECT id, workout_start, workout_end
+        SELECT
+            id,
+            workout_start,
+            workout_end,
+            calories_burned,
+            jsonb_array_length(heart_rate_data)::bigint as "samples_count!"
         FROM workout_data
         WHERE user_id = $1
         AND workout_start IS NOT NULL
@@ -194,6 +202,7 @@ pub async fn check_workout_overlap(
             workout_start <= ($3::timestamptz + INTERVAL '1 second')
             AND workout_end >= ($2::timestamptz - INTERVAL '1 second')
         )
+        ORDER BY created_at ASC
         LIMIT 1
         "#,
         user_id,
@@ -201,41 +210,87 @@ pub async fn check_workout_overlap(
         workout_end
     )
     .fetch_optional(pool)
+    .await
+}
+
+/// The owner and comparable fields of a stored workout
+pub async fn get_workout_fingerprint(
+    pool: &Pool<Postgres>,
+    workout_id: Uuid,
+) -> Result<Option<(Uuid, StoredWorkoutFingerprint)>, sqlx::Error> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            id,
+            user_id,
+            workout_start,
+            workout_end,
+            calories_burned,
+            jsonb_array_length(heart_rate_data)::bigint as "samples_count!"
+        FROM workout_data
+        WHERE id = $1
+        "#,
+        workout_id
+    )
+    .fetch_optional(pool)
     .await?;
 
-    Ok(record.is_some())
+    Ok(record.map(|r| (r.user_id, StoredWorkoutFingerprint {
+        id: r.id,
+        workout_start: r.workout_start,
+        workout_end: r.workout_end,
+        calories_burned: r.calories_burned,
+        samples_count: r.samples_count,
+    })))
+}
+
+/// The user's choices for the posts of their workout uploads, defaults if they made none
+pub async fn get_workout_posting_settings(conn: &mut PgConnection, user_id: Uuid) -> Result<WorkoutPostingSettings, sqlx::Error> {
+    let settings = sqlx::query_as!(
+        WorkoutPostingSettings,
+        r#"
+        SELECT workout_auto_post as "auto_post: _", caption_template
+        FROM user_posting_settings
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(settings.unwrap_or_default())
 }
 
 #[tracing::instrument(
     name = "Create post for workout",
-    skip(pool, user_id, workout_id, image_urls, video_urls, workout_start),
+    skip(conn, user_id, workout_id, data, caption),
     fields(
         user_id = %user_id,
         workout_id = %workout_id,
-        image_urls = ?image_urls,
-        video_urls = ?video_urls,
-        workout_start = %workout_start
+        image_urls = ?data.image_urls,
+        video_urls = ?data.video_urls,
+        workout_start = %data.workout_start,
+        visibility = ?visibility
     )
 )]
 pub async fn create_post_for_workout(
-    pool: &Pool<Postgres>,
+    conn: &mut PgConnection,
     user_id: Uuid,
     workout_id: Uuid,
-    image_urls: &Option<Vec<String>>,
-    video_urls: &Option<Vec<String>>,
-    workout_start: DateTime<Utc>,
+    data: &WorkoutDataUploadRequest,
+    caption: Option<&str>,
+    visibility: PostVisibility,
 ) -> Result<Uuid, sqlx::Error> {
 
     // Build media_urls JSONB array from image_urls and video_urls
     let mut media_items = Vec::new();
 
-    if let Some(images) = image_urls {
+    if let Some(images) = &data.image_urls {
         for url in images {
             media_items.push(serde_json::json!({"type": "image", "url": url}));
         }
     }
 
-    if let Some(videos) = video_urls {
+    if let Some(videos) = &data.video_urls {
         for url in videos {
             media_items.push(serde_json::json!({"type": "video", "url": url}));
         }
@@ -250,16 +305,18 @@ pub async fn create_post_for_workout(
     // Create a post for this workout with media files
     let record = sqlx::query!(
         r#"
-        INSERT INTO posts (id, user_id, post_type, workout_id, media_urls, visibility, is_editable, created_at, updated_at)
-        VALUES (gen_random_uuid(), $1, 'workout'::post_type, $2, $3, 'public'::post_visibility, true, $4, $4)
+        INSERT INTO posts (id, user_id, post_type, workout_id, content, media_urls, visibility, is_editable, created_at, updated_at)
+        VALUES (gen_random_uuid(), $1, 'workout'::post_type, $2, $3, $4, $5, true, $6, $6)
         RETURNING id
         "#,
         user_id,
         workout_id,
+        caption,
         media_urls_json as Option<serde_json::Value>,
-        workout_start
+        visibility as PostVisibility,
+        data.workout_start
     )
-    .fetch_one(pool)
+    .fetch_one(conn)
     .await?;
 
     Ok(record.id)
@@ -267,7 +324,7 @@ pub async fn create_post_for_workout(
 
 #[tracing::instrument(
     name = "Update workout data with classification and score",
-    skip(pool, workout_id, workout_stats, zone_breakdown, ml_classification),
+    skip(conn, workout_id, workout_stats, zone_breakdown, ml_classification),
     fields(
         workout_id = %workout_id,
         workout_stats = ?workout_stats,
@@ -276,11 +333,12 @@ pub async fn create_post_for_workout(
     )
 )]
 pub async fn update_workout_data_with_classification_and_score(
-    pool: &Pool<Postgres>,
+    conn: &mut PgConnection,
     workout_id: Uuid,
     workout_stats: &WorkoutStats,
     zone_breakdown: &Vec<ZoneBreakdown>,
     ml_classification: &ClassifyResponse,
+    scoring_calibration: f32,
 ) -> Result<(), sqlx::Error> {
     let ml_prediction = &ml_classification.prediction;
     let ml_confidence = ml_classification.confidence as f32;
@@ -295,7 +353,8 @@ pub async fn update_workout_data_with_classification_and_score(
                 heart_rate_zones = $4,
                 ml_prediction = $5,
                 ml_confidence = $6,
-                ml_classified_at = $7
+                ml_classified_at = $7,
+                scoring_calibration = $9
             WHERE id = $8
             "#,
             workout_stats.changes.stamina_change,
@@ -305,9 +364,27 @@ pub async fn update_workout_data_with_classification_and_score(
             ml_prediction,
             ml_confidence,
             ml_classified_at,
-            workout_id
-        ).execute(pool)
+            workout_id,
+            scoring_calibration
+        ).execute(conn)
         .await?;
 
+    Ok(())
+}
+
+/// Store the work/rest intervals detected in a workout
+pub async fn update_workout_intervals(
+    conn: &mut PgConnection,
+    workout_id: Uuid,
+    intervals: &IntervalAnalysis,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE workout_data SET intervals = $1 WHERE id = $2",
+        serde_json::to_value(intervals).unwrap_or(serde_json::Value::Null),
+        workout_id
+    )
+    .execute(conn)
+    .await?;
+
     Ok(())
 }
\ No newline at end of file
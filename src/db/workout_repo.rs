@@ -0,0 +1,33 @@
+use std::future::Future;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Read access to workouts, implemented by `WorkoutRepo` and by mocks in tests
+pub trait WorkoutRepository: Sync {
+    /// Owner of a workout, `None` if the workout does not exist
+    fn find_owner(&self, workout_id: Uuid) -> impl Future<Output = Result<Option<Uuid>, sqlx::Error>> + Send;
+
+    fn is_owned_by(&self, workout_id: Uuid, user_id: Uuid) -> impl Future<Output = Result<bool, sqlx::Error>> + Send {
+        async move { Ok(self.find_owner(workout_id).await? == Some(user_id)) }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkoutRepo {
+    pool: PgPool,
+}
+
+impl WorkoutRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl WorkoutRepository for WorkoutRepo {
+    async fn find_owner(&self, workout_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!("SELECT user_id FROM workout_data WHERE id = $1", workout_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+}
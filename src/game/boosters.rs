@@ -0,0 +1,112 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Unused boosters a team can hold of each kind; further awards are forfeited
+pub const MAX_HELD_PER_TYPE: i64 = 2;
+
+/// Booster kinds, stored as `team_boosters.booster_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoosterType {
+    /// Doubles the points of the team's next workout in the game
+    DoubleNextWorkout,
+    /// Fills the team's weakest day of the game up to its daily average
+    Shield,
+}
+
+impl BoosterType {
+    pub const ALL: [BoosterType; 2] = [BoosterType::DoubleNextWorkout, BoosterType::Shield];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoosterType::DoubleNextWorkout => "double_next_workout",
+            BoosterType::Shield => "shield",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "double_next_workout" => Some(BoosterType::DoubleNextWorkout),
+            "shield" => Some(BoosterType::Shield),
+            _ => None,
+        }
+    }
+}
+
+/// Achievements that earn a team a booster, stored as `team_boosters.earned_for`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoosterAchievement {
+    /// Won a game
+    GameWin,
+    /// Every member of the team scored in a game
+    FullSquad,
+}
+
+impl BoosterAchievement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoosterAchievement::GameWin => "game_win",
+            BoosterAchievement::FullSquad => "full_squad",
+        }
+    }
+
+    pub fn booster(&self) -> BoosterType {
+        match self {
+            BoosterAchievement::GameWin => BoosterType::DoubleNextWorkout,
+            BoosterAchievement::FullSquad => BoosterType::Shield,
+        }
+    }
+}
+
+/// Achievements of a team in an evaluated game
+pub fn earned_achievements(won: bool, squad_size: usize, scorers: usize) -> Vec<BoosterAchievement> {
+    let mut achievements = Vec::new();
+    if won {
+        achievements.push(BoosterAchievement::GameWin);
+    }
+    if squad_size > 0 && scorers >= squad_size {
+        achievements.push(BoosterAchievement::FullSquad);
+    }
+    achievements
+}
+
+/// Points a double booster adds to a workout: the workout's own points once more
+pub fn double_bonus(workout_points: f32) -> f32 {
+    workout_points.max(0.0)
+}
+
+/// Day of a game `at` falls on, counted in 24 hour periods from the start and starting at 0
+pub fn game_day(game_start: DateTime<Utc>, at: DateTime<Utc>) -> usize {
+    ((at - game_start).num_seconds().max(0) / Duration::days(1).num_seconds()) as usize
+}
+
+/// Number of days of a game; a started day counts
+pub fn game_days(game_start: DateTime<Utc>, game_end: DateTime<Utc>) -> usize {
+    let seconds = (game_end - game_start).num_seconds().max(0);
+    let day = Duration::days(1).num_seconds();
+    ((seconds + day - 1) / day) as usize
+}
+
+/// What a shield adds for the team's weakest day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShieldFill {
+    pub day: usize,
+    pub day_points: f64,
+    pub bonus_points: f64,
+}
+
+/// Fill the weakest day up to the average of the other days. None if no day falls short of it.
+pub fn shield_fill(daily_points: &[f64]) -> Option<ShieldFill> {
+    if daily_points.len() < 2 {
+        return None;
+    }
+    let (day, day_points) = daily_points
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    let others_average = (daily_points.iter().sum::<f64>() - day_points) / (daily_points.len() - 1) as f64;
+
+    let bonus_points = ((others_average - day_points) * 10.0).round() / 10.0;
+    (bonus_points > 0.0).then_some(ShieldFill { day, day_points, bonus_points })
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+
+/// Largest comeback multiplier a league can configure; the bonus is meant to keep games close,
+/// not to decide them
+pub const MAX_COMEBACK_MULTIPLIER: f32 = 1.5;
+
+/// Share of the game at its end in which the trailing team earns the comeback bonus
+pub const COMEBACK_WINDOW_SHARE: f64 = 0.25;
+
+/// Comeback bonus a score event earned on top of the workout's own points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComebackBonus {
+    pub multiplier: f32,
+    pub bonus_points: f32,
+}
+
+/// Whether `at` lies in the final quarter of the game window
+pub fn is_in_comeback_window(game_start: DateTime<Utc>, game_end: DateTime<Utc>, at: DateTime<Utc>) -> bool {
+    if at < game_start || at > game_end {
+        return false;
+    }
+    let total_seconds = (game_end - game_start).num_seconds() as f64;
+    let window_start = game_start + chrono::Duration::seconds((total_seconds * (1.0 - COMEBACK_WINDOW_SHARE)) as i64);
+    at >= window_start
+}
+
+/// Bonus for a workout finished at `workout_end`, if the league has the comeback rule enabled,
+/// the workout falls into the final quarter of the game and the scoring team trails
+pub fn comeback_bonus(
+    multiplier: Option<f32>,
+    game_start: DateTime<Utc>,
+    game_end: DateTime<Utc>,
+    workout_end: DateTime<Utc>,
+    team_score: i32,
+    opponent_score: i32,
+    base_points: f32,
+) -> Option<ComebackBonus> {
+    let multiplier = multiplier.filter(|m| *m > 1.0)?.min(MAX_COMEBACK_MULTIPLIER);
+    if team_score >= opponent_score || base_points <= 0.0 || !is_in_comeback_window(game_start, game_end, workout_end) {
+        return None;
+    }
+
+    let bonus_points = (base_points * (multiplier - 1.0) * 10.0).round() / 10.0;
+    (bonus_points > 0.0).then_some(ComebackBonus { multiplier, bonus_points })
+}
+
+/// Score event description, explaining the comeback bonus if there is one
+pub fn score_event_description(stamina_gained: f32, strength_gained: f32, bonus: Option<ComebackBonus>) -> String {
+    let description = format!("Workout completed: +{} stamina, +{} strength", stamina_gained, strength_gained);
+    match bonus {
+        Some(bonus) => format!(
+            "{} (comeback bonus ×{}: +{} points for the trailing team)",
+            description, bonus.multiplier, bonus.bonus_points
+        ),
+        None => description,
+    }
+}
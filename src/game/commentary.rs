@@ -0,0 +1,51 @@
+use serde_json::{Map, Value};
+
+use crate::models::commentary::CommentaryMilestone;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales the commentator has templates for
+pub const SUPPORTED_LOCALES: [&str; 2] = ["en", "de"];
+
+/// How long before the end of a game the final minutes are called
+pub const FINAL_MINUTES_WINDOW: i64 = 10;
+
+pub fn is_supported_locale(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale)
+}
+
+/// Template for a milestone; unknown locales fall back to English.
+/// Placeholders are `{name}` and are filled from the commentary params.
+pub fn template(milestone: CommentaryMilestone, locale: &str) -> &'static str {
+    match (locale, milestone) {
+        ("de", CommentaryMilestone::FirstScore) =>
+            "{player} eröffnet das Spiel und bringt {team} mit {points} Punkten in Führung!",
+        ("de", CommentaryMilestone::LeadChange) =>
+            "Führungswechsel! {team} liegt jetzt mit {leading_score} zu {trailing_score} vorne.",
+        ("de", CommentaryMilestone::FinalMinutes) =>
+            "Die letzten {minutes} Minuten laufen! {home_team} {home_score} - {away_score} {away_team}.",
+        ("de", CommentaryMilestone::MvpCandidate) =>
+            "{player} ({team}) ist mit {points} Punkten der neue MVP-Kandidat.",
+        (_, CommentaryMilestone::FirstScore) =>
+            "{player} opens the scoring for {team} with {points} points!",
+        (_, CommentaryMilestone::LeadChange) =>
+            "Lead change! {team} pull ahead {leading_score} to {trailing_score}.",
+        (_, CommentaryMilestone::FinalMinutes) =>
+            "Final {minutes} minutes! {home_team} {home_score} - {away_score} {away_team}.",
+        (_, CommentaryMilestone::MvpCandidate) =>
+            "{player} ({team}) is the new MVP candidate with {points} points.",
+    }
+}
+
+/// Render a commentary line; placeholders without a param are left as they are
+pub fn render(milestone: CommentaryMilestone, locale: &str, params: &Map<String, Value>) -> String {
+    let mut message = template(milestone, locale).to_string();
+    for (key, value) in params {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        message = message.replace(&format!("{{{key}}}"), &value);
+    }
+    message
+}
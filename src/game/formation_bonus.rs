@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::models::workout_data::WorkoutType;
+
+/// Workout archetypes a roster can cover, in the order they're listed
+pub const FORMATION_ARCHETYPES: [WorkoutType; 3] = [WorkoutType::Cardio, WorkoutType::Strength, WorkoutType::Hiit];
+
+/// Share of the team's workout points awarded for covering two or all three archetypes; small
+/// enough to reward variety without deciding games
+pub fn formation_bonus_share(archetypes_covered: usize) -> f32 {
+    match archetypes_covered {
+        0 | 1 => 0.0,
+        2 => 0.02,
+        _ => 0.05,
+    }
+}
+
+/// A classified workout a player scored in a game
+#[derive(Debug, Clone)]
+pub struct FormationWorkout {
+    pub user_id: Uuid,
+    pub workout_type: WorkoutType,
+    pub points: f32,
+}
+
+/// Formation bonus of a team: the archetypes its players covered and the points each of them earns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formation {
+    pub archetypes: Vec<WorkoutType>,
+    pub bonus_points: f32,
+    /// Players that make up the formation with their share of the bonus
+    pub shares: Vec<(Uuid, f32)>,
+}
+
+/// Each player's archetype: the classified type they scored the most points with. Unclassified
+/// workouts don't count.
+pub fn player_archetypes(workouts: &[FormationWorkout]) -> HashMap<Uuid, WorkoutType> {
+    let mut points: HashMap<Uuid, HashMap<WorkoutType, f32>> = HashMap::new();
+    for workout in workouts.iter().filter(|w| FORMATION_ARCHETYPES.contains(&w.workout_type)) {
+        *points.entry(workout.user_id).or_default().entry(workout.workout_type).or_default() += workout.points;
+    }
+
+    points
+        .into_iter()
+        .filter_map(|(user_id, by_type)| {
+            // Ties go to the archetype listed first, so the result doesn't depend on map order
+            FORMATION_ARCHETYPES
+                .iter()
+                .filter_map(|archetype| by_type.get(archetype).map(|points| (*archetype, *points)))
+                .fold(None, |best: Option<(WorkoutType, f32)>, (archetype, points)| match best {
+                    Some((_, best_points)) if best_points >= points => best,
+                    _ => Some((archetype, points)),
+                })
+                .map(|(archetype, _)| (user_id, archetype))
+        })
+        .collect()
+}
+
+/// Formation bonus of a team that scored `team_points` with its workouts, None unless the
+/// players cover at least two archetypes. The bonus is split evenly between those players.
+pub fn team_formation(workouts: &[FormationWorkout], team_points: f32) -> Option<Formation> {
+    let archetypes_by_player = player_archetypes(workouts);
+    let archetypes: Vec<WorkoutType> = FORMATION_ARCHETYPES
+        .into_iter()
+        .filter(|archetype| archetypes_by_player.values().any(|a| a == archetype))
+        .collect();
+
+    let mut players: Vec<Uuid> = archetypes_by_player.into_keys().collect();
+    players.sort();
+    let bonus = team_points.max(0.0) * formation_bonus_share(archetypes.len());
+    let share = (bonus / players.len().max(1) as f32 * 10.0).round() / 10.0;
+    if share <= 0.0 {
+        return None;
+    }
+
+    Some(Formation {
+        archetypes,
+        bonus_points: (share * players.len() as f32 * 10.0).round() / 10.0,
+        shares: players.into_iter().map(|user_id| (user_id, share)).collect(),
+    })
+}
+
+/// Explanation of the bonus for score events and the game summary
+pub fn formation_description(formation: &Formation) -> String {
+    let names: Vec<&str> = formation
+        .archetypes
+        .iter()
+        .map(|archetype| match archetype {
+            WorkoutType::Hiit => "HIIT",
+            other => other.as_str(),
+        })
+        .collect();
+    let covered = match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    };
+    format!("Formation bonus: {} workouts covered (+{} points)", covered, formation.bonus_points)
+}
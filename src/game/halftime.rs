@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::halftime::{HalftimeContributor, Pace, TeamPace};
+
+/// How far a team's projected score may be off its average in previous meetings and still be on pace
+const PACE_MARGIN: f32 = 0.1;
+
+/// Midpoint of a game window
+pub fn halftime_at(game_start: DateTime<Utc>, game_end: DateTime<Utc>) -> DateTime<Utc> {
+    game_start + (game_end - game_start) / 2
+}
+
+/// Pace of a team at halftime: its score doubled, against its average final score in previous
+/// meetings with the same opponent. No pace without previous meetings.
+pub fn team_pace(halftime_score: i32, previous_scores: &[i32]) -> TeamPace {
+    let projected_score = halftime_score * 2;
+    let previous_average = (!previous_scores.is_empty())
+        .then(|| previous_scores.iter().sum::<i32>() as f32 / previous_scores.len() as f32);
+    let pace = previous_average.map(|average| {
+        let projected = projected_score as f32;
+        if projected > average * (1.0 + PACE_MARGIN) {
+            Pace::Ahead
+        } else if projected < average * (1.0 - PACE_MARGIN) {
+            Pace::Behind
+        } else {
+            Pace::OnPace
+        }
+    });
+
+    TeamPace {
+        projected_score,
+        previous_meetings: previous_scores.len(),
+        previous_average: previous_average.map(|average| (average * 10.0).round() / 10.0),
+        pace,
+    }
+}
+
+/// One-line halftime report for the notification center and the game stream
+pub fn halftime_message(
+    home_team: &str,
+    away_team: &str,
+    home_score: i32,
+    away_score: i32,
+    top_contributors: &[HalftimeContributor],
+) -> String {
+    let mut message = format!("Halftime: {} {} – {} {}", home_team, home_score, away_score, away_team);
+    if let Some(top) = top_contributors.first() {
+        message.push_str(&format!(". Top contributor: {} with {} points", top.username, top.points));
+    }
+    message
+}
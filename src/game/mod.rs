@@ -1,2 +1,11 @@
 pub mod stats_calculator;
-pub mod game_evaluator;
\ No newline at end of file
+pub mod game_evaluator;
+pub mod commentary;
+pub mod workout_credit;
+pub mod comeback_bonus;
+pub mod boosters;
+pub mod stat_decay;
+pub mod player_card;
+pub mod formation_bonus;
+pub mod halftime;
+pub mod review_lock;
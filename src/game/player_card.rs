@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Workouts in the archetype window needed before a player gets an archetype other than rookie
+pub const MIN_ARCHETYPE_WORKOUTS: usize = 3;
+/// Share of the stats gained that has to come from one stat to specialise in it
+const SPECIALIST_SHARE: f32 = 0.65;
+/// How far the last week may be off the weekly average before form changes
+const FORM_MARGIN: f32 = 0.2;
+
+/// Position-like role of a player on the roster, from their workout mix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Archetype {
+    Rookie,
+    Endurance,
+    Power,
+    AllRounder,
+}
+
+/// Whether a player is training more or less than they used to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Form {
+    Rising,
+    Steady,
+    Falling,
+    Inactive,
+}
+
+/// Archetype from the stamina and strength gained by `workouts` recent workouts
+pub fn archetype(workouts: usize, stamina_gained: f32, strength_gained: f32) -> Archetype {
+    let total = stamina_gained + strength_gained;
+    if workouts < MIN_ARCHETYPE_WORKOUTS || total <= 0.0 {
+        return Archetype::Rookie;
+    }
+    if stamina_gained / total >= SPECIALIST_SHARE {
+        Archetype::Endurance
+    } else if strength_gained / total >= SPECIALIST_SHARE {
+        Archetype::Power
+    } else {
+        Archetype::AllRounder
+    }
+}
+
+/// Form from the points of the last 7 days against the weekly average of the 21 days before
+pub fn form(last_week_points: f32, previous_three_weeks_points: f32) -> Form {
+    if last_week_points <= 0.0 {
+        return Form::Inactive;
+    }
+    let weekly_average = previous_three_weeks_points / 3.0;
+    if last_week_points > weekly_average * (1.0 + FORM_MARGIN) {
+        Form::Rising
+    } else if last_week_points < weekly_average * (1.0 - FORM_MARGIN) {
+        Form::Falling
+    } else {
+        Form::Steady
+    }
+}
+
+/// Consecutive days with a workout, ending today or, if there was none yet today, yesterday
+pub fn workout_streak_days(workout_dates: &[NaiveDate], today: NaiveDate) -> u32 {
+    let dates: HashSet<NaiveDate> = workout_dates.iter().copied().collect();
+    let mut day = if dates.contains(&today) { today } else { today - Duration::days(1) };
+    let mut streak = 0;
+    while dates.contains(&day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Longest review lock a league can configure; a result shouldn't wait more than a week
+pub const MAX_REVIEW_LOCK_HOURS: i32 = 168;
+
+/// Pending suspicious-workout report on a workout that scored in a game
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReviewFlag {
+    pub report_id: Uuid,
+    pub workout_data_id: Uuid,
+    pub workout_owner_id: Uuid,
+    pub reason: String,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// Finished game whose evaluation waits until its flags are resolved, or at most until `proceeds_at`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReviewBlockedGame {
+    pub game_id: Uuid,
+    pub proceeds_at: DateTime<Utc>,
+    pub flags: Vec<ReviewFlag>,
+}
+
+/// When a game ending at `game_end` is evaluated even with flags still pending
+pub fn lock_ends_at(game_end: DateTime<Utc>, lock_hours: i32) -> DateTime<Utc> {
+    game_end + Duration::hours(lock_hours as i64)
+}
+
+/// Group the flags of locked games by game, keeping the games in order of their first flag.
+/// Games whose lock has ended by `now` are left out, they proceed with their flags pending.
+pub fn blocked_games(
+    flags: impl IntoIterator<Item = (Uuid, DateTime<Utc>, ReviewFlag)>,
+    now: DateTime<Utc>,
+) -> Vec<ReviewBlockedGame> {
+    let mut games: Vec<ReviewBlockedGame> = Vec::new();
+    for (game_id, proceeds_at, flag) in flags {
+        if proceeds_at <= now {
+            continue;
+        }
+        match games.iter_mut().find(|game| game.game_id == game_id) {
+            Some(game) => game.flags.push(flag),
+            None => games.push(ReviewBlockedGame { game_id, proceeds_at, flags: vec![flag] }),
+        }
+    }
+    games
+}
@@ -0,0 +1,16 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// A stat after one week of decay: it loses `weekly_percent` of its value but never drops below
+/// the floor. Stats already at or below the floor are left alone.
+pub fn decayed_stat(value: f32, weekly_percent: f32, floor: f32) -> f32 {
+    if value <= floor || weekly_percent <= 0.0 {
+        return value;
+    }
+    let decayed = value * (1.0 - weekly_percent.min(100.0) / 100.0);
+    ((decayed * 10.0).round() / 10.0).max(floor)
+}
+
+/// Monday of the week `date` falls in; decay is applied at most once per user and week
+pub fn decay_week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
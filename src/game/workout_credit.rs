@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::team_repo::TeamRepository;
+use crate::models::league::LeagueGame;
+
+/// Team of `user_id` that a workout scores for in `game`, if it counts for the game at all.
+/// It counts when the user is an active member of either team, joined before the workout
+/// started, and the workout lies entirely within the game's live window.
+pub async fn credited_team_for_workout(
+    teams: &impl TeamRepository,
+    user_id: Uuid,
+    game: &LeagueGame,
+    workout_start: DateTime<Utc>,
+    workout_end: DateTime<Utc>,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let Some(membership) = teams.find_active_membership(user_id, [game.home_team_id, game.away_team_id]).await? else {
+        tracing::debug!("User {} is not a member of teams playing in game {}", user_id, game.id);
+        return Ok(None);
+    };
+
+    if workout_start < membership.joined_at {
+        tracing::debug!("❌ Workout time ({}) is before player joined team ({}) for user {} in game {}",
+                       workout_start, membership.joined_at, user_id, game.id);
+        return Ok(None);
+    }
+
+    let (Some(game_start), Some(game_end)) = (game.game_start_time, game.game_end_time) else {
+        tracing::debug!("❌ Game {} does not have live scoring times set", game.id);
+        return Ok(None);
+    };
+
+    if workout_start < game_start || workout_end > game_end {
+        tracing::debug!("❌ Workout time ({} to {}) is outside live game period ({} to {}) for user {} in game {}",
+                       workout_start, workout_end, game_start, game_end, user_id, game.id);
+        return Ok(None);
+    }
+
+    Ok(Some(membership.team_id))
+}
@@ -0,0 +1,181 @@
+use async_graphql::dataloader::{DataLoader, Loader};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::types::{TeamMemberNode, TeamNode, UserNode};
+
+/// Per-request dataloaders; relations resolved across a query are batched into one
+/// `= ANY($1)` query per loader instead of one query per parent object
+pub struct Loaders {
+    pub users: DataLoader<UserLoader>,
+    pub teams: DataLoader<TeamLoader>,
+    pub team_members: DataLoader<TeamMembersLoader>,
+    pub user_teams: DataLoader<UserTeamLoader>,
+    pub workout_social: DataLoader<WorkoutSocialLoader>,
+}
+
+impl Loaders {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            users: DataLoader::new(UserLoader { pool: pool.clone() }, tokio::spawn),
+            teams: DataLoader::new(TeamLoader { pool: pool.clone() }, tokio::spawn),
+            team_members: DataLoader::new(TeamMembersLoader { pool: pool.clone() }, tokio::spawn),
+            user_teams: DataLoader::new(UserTeamLoader { pool: pool.clone() }, tokio::spawn),
+            workout_social: DataLoader::new(WorkoutSocialLoader { pool }, tokio::spawn),
+        }
+    }
+}
+
+pub struct UserLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for UserLoader {
+    type Value = UserNode;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, username, email, role, status, profile_picture_url, created_at
+            FROM users
+            WHERE id = ANY($1)
+            "#,
+            keys
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.id, UserNode {
+            id: row.id,
+            username: row.username,
+            profile_picture_url: row.profile_picture_url,
+            role: row.role,
+            status: row.status,
+            created_at: row.created_at,
+            email: row.email,
+        })).collect())
+    }
+}
+
+pub struct TeamLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for TeamLoader {
+    type Value = TeamNode;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id, team_name, team_description, team_color, league_id, created_at
+            FROM teams
+            WHERE id = ANY($1)
+            "#,
+            keys
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.id, TeamNode {
+            id: row.id,
+            team_name: row.team_name,
+            team_description: row.team_description,
+            team_color: row.team_color,
+            league_id: row.league_id,
+            created_at: row.created_at,
+            owner_id: row.user_id,
+        })).collect())
+    }
+}
+
+/// Active members of each team, keyed by team id
+pub struct TeamMembersLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for TeamMembersLoader {
+    type Value = Vec<TeamMemberNode>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT team_id, user_id, role, status, joined_at
+            FROM team_members
+            WHERE team_id = ANY($1) AND status = 'active'
+            ORDER BY joined_at
+            "#,
+            keys
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut members: HashMap<Uuid, Vec<TeamMemberNode>> = HashMap::new();
+        for row in rows {
+            members.entry(row.team_id).or_default().push(TeamMemberNode {
+                role: row.role,
+                status: row.status,
+                joined_at: row.joined_at,
+                user_id: row.user_id,
+            });
+        }
+        Ok(members)
+    }
+}
+
+/// The team each user is an active member of, keyed by user id
+pub struct UserTeamLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for UserTeamLoader {
+    type Value = Uuid;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT ON (user_id) user_id, team_id
+            FROM team_members
+            WHERE user_id = ANY($1) AND status = 'active'
+            ORDER BY user_id, joined_at DESC
+            "#,
+            keys
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.user_id, row.team_id)).collect())
+    }
+}
+
+/// Reaction and comment counts of workouts, keyed by workout id
+pub struct WorkoutSocialLoader {
+    pool: PgPool,
+}
+
+impl Loader<Uuid> for WorkoutSocialLoader {
+    type Value = (i64, i64);
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                w.id as "workout_id!",
+                (SELECT COUNT(*) FROM reactions r WHERE r.target_type = 'workout' AND r.target_id = w.id) as "reaction_count!",
+                (SELECT COUNT(*) FROM post_comments pc WHERE pc.workout_id = w.id) as "comment_count!"
+            FROM UNNEST($1::uuid[]) AS w(id)
+            "#,
+            keys
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.workout_id, (row.reaction_count, row.comment_count))).collect())
+    }
+}
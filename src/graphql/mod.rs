@@ -0,0 +1,193 @@
+//! GraphQL facade over the existing db layer, served at `/graphql`.
+//!
+//! Lets the web dashboard fetch users, teams, games, standings and the feed in one
+//! round trip. Relations are resolved through per-request dataloaders (see `loaders`).
+
+pub mod loaders;
+pub mod types;
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::league::standings::StandingsService;
+use crate::models::league::{GameStatus, LeagueGame};
+use crate::models::user::UserRole;
+use loaders::Loaders;
+use types::{game_status_str, FeedPostNode, GameNode, StandingNode, TeamNode, UserNode};
+
+pub type RiinaSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+const MAX_QUERY_DEPTH: usize = 10;
+const MAX_QUERY_COMPLEXITY: usize = 1000;
+const DEFAULT_FEED_LIMIT: i32 = 20;
+const MAX_FEED_LIMIT: i32 = 50;
+
+/// The authenticated caller, attached to every GraphQL request
+pub struct Viewer {
+    pub user_id: Uuid,
+    pub role: UserRole,
+}
+
+impl Viewer {
+    pub fn is_admin(&self) -> bool {
+        matches!(self.role, UserRole::Admin | UserRole::SuperAdmin)
+    }
+
+    /// Whether private fields (e.g. email) of the given user are visible
+    pub fn can_see_private(&self, user_id: Uuid) -> bool {
+        self.user_id == user_id || self.is_admin()
+    }
+}
+
+pub fn build_schema(pool: PgPool) -> RiinaSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(pool)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The authenticated user
+    async fn me(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let viewer = ctx.data_unchecked::<Viewer>();
+        Ok(ctx.data_unchecked::<Loaders>().users.load_one(viewer.user_id).await?)
+    }
+
+    async fn user(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<UserNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().users.load_one(id).await?)
+    }
+
+    async fn team(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<TeamNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().teams.load_one(id).await?)
+    }
+
+    /// All teams, optionally limited to one league
+    async fn teams(&self, ctx: &Context<'_>, league_id: Option<Uuid>) -> Result<Vec<TeamNode>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id, team_name, team_description, team_color, league_id, created_at
+            FROM teams
+            WHERE ($1::uuid IS NULL OR league_id = $1)
+            ORDER BY team_name
+            "#,
+            league_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| TeamNode {
+            id: row.id,
+            team_name: row.team_name,
+            team_description: row.team_description,
+            team_color: row.team_color,
+            league_id: row.league_id,
+            created_at: row.created_at,
+            owner_id: row.user_id,
+        }).collect())
+    }
+
+    /// Games of a season, optionally limited to one week
+    async fn games(&self, ctx: &Context<'_>, season_id: Uuid, week_number: Option<i32>) -> Result<Vec<GameNode>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let games = sqlx::query_as!(
+            LeagueGame,
+            r#"
+            SELECT
+                id, season_id, home_team_id, away_team_id,
+                week_number, is_first_leg, status as "status: GameStatus",
+                winner_team_id,
+                created_at, updated_at,
+                home_score, away_score, game_start_time, game_end_time,
+                last_score_time, last_scorer_id, last_scorer_name, last_scorer_team
+            FROM games
+            WHERE season_id = $1 AND ($2::int IS NULL OR week_number = $2)
+            ORDER BY week_number, game_start_time, id
+            "#,
+            season_id,
+            week_number
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(games.into_iter().map(|game| GameNode {
+            id: game.id,
+            season_id: game.season_id,
+            week_number: game.week_number,
+            is_first_leg: game.is_first_leg,
+            status: game_status_str(&game.status).to_string(),
+            home_score: game.home_score,
+            away_score: game.away_score,
+            winner_team_id: game.winner_team_id,
+            game_start_time: game.game_start_time,
+            game_end_time: game.game_end_time,
+            home_team_id: game.home_team_id,
+            away_team_id: game.away_team_id,
+        }).collect())
+    }
+
+    /// Standings of a season, ordered by position
+    async fn standings(&self, ctx: &Context<'_>, season_id: Uuid) -> Result<Vec<StandingNode>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let response = match StandingsService::new(pool.clone()).get_league_standings(season_id).await {
+            Ok(response) => response,
+            Err(sqlx::Error::RowNotFound) => return Err("Season not found".into()),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(response.standings.into_iter().map(|entry| StandingNode {
+            position: entry.standing.position,
+            games_played: entry.standing.games_played,
+            wins: entry.standing.wins,
+            draws: entry.standing.draws,
+            losses: entry.standing.losses,
+            points: entry.standing.points.unwrap_or(0),
+            total_points_scored: entry.standing.total_points_scored,
+            team_power: entry.team_power,
+            recent_form: entry.recent_form.iter().map(|c| c.to_string()).collect(),
+            team_id: entry.standing.team_id,
+        }).collect())
+    }
+
+    /// Public posts, newest first. Pass the `createdAt` of the last post as `cursor` for the next page.
+    async fn feed(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<DateTime<Utc>>,
+        limit: Option<i32>,
+    ) -> Result<Vec<FeedPostNode>> {
+        let pool = ctx.data_unchecked::<PgPool>();
+        let limit = limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_FEED_LIMIT);
+        let rows = sqlx::query!(
+            r#"
+            SELECT p.id, p.user_id, p.post_type as "post_type: String", p.content, p.workout_id,
+                   p.created_at, p.edited_at
+            FROM posts p
+            WHERE p.visibility = 'public'
+            AND ($1::timestamptz IS NULL OR p.created_at < $1)
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $2
+            "#,
+            cursor,
+            limit as i64
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| FeedPostNode {
+            id: row.id,
+            post_type: row.post_type,
+            content: row.content,
+            workout_id: row.workout_id,
+            created_at: row.created_at,
+            edited_at: row.edited_at,
+            author_id: row.user_id,
+        }).collect())
+    }
+}
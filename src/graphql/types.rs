@@ -0,0 +1,180 @@
+use async_graphql::{ComplexObject, Context, Result, SimpleObject};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::models::league::GameStatus;
+use super::{loaders::Loaders, Viewer};
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "User", complex)]
+pub struct UserNode {
+    pub id: Uuid,
+    pub username: String,
+    pub profile_picture_url: Option<String>,
+    pub role: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    #[graphql(skip)]
+    pub email: String,
+}
+
+#[ComplexObject]
+impl UserNode {
+    /// Only visible to the user themselves and to admins
+    async fn email(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.data_unchecked::<Viewer>().can_see_private(self.id).then(|| self.email.clone())
+    }
+
+    /// The team the user is an active member of
+    async fn team(&self, ctx: &Context<'_>) -> Result<Option<TeamNode>> {
+        let loaders = ctx.data_unchecked::<Loaders>();
+        let Some(team_id) = loaders.user_teams.load_one(self.id).await? else {
+            return Ok(None);
+        };
+        Ok(loaders.teams.load_one(team_id).await?)
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Team", complex)]
+pub struct TeamNode {
+    pub id: Uuid,
+    pub team_name: String,
+    pub team_description: Option<String>,
+    pub team_color: String,
+    pub league_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[graphql(skip)]
+    pub owner_id: Uuid,
+}
+
+#[ComplexObject]
+impl TeamNode {
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().users.load_one(self.owner_id).await?)
+    }
+
+    /// Active members, in order of joining
+    async fn members(&self, ctx: &Context<'_>) -> Result<Vec<TeamMemberNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().team_members.load_one(self.id).await?.unwrap_or_default())
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "TeamMember", complex)]
+pub struct TeamMemberNode {
+    pub role: String,
+    pub status: String,
+    pub joined_at: DateTime<Utc>,
+    #[graphql(skip)]
+    pub user_id: Uuid,
+}
+
+#[ComplexObject]
+impl TeamMemberNode {
+    async fn user(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().users.load_one(self.user_id).await?)
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Game", complex)]
+pub struct GameNode {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub week_number: i32,
+    pub is_first_leg: bool,
+    /// scheduled, in_progress, finished, evaluated or postponed
+    pub status: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub winner_team_id: Option<Uuid>,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+    #[graphql(skip)]
+    pub home_team_id: Uuid,
+    #[graphql(skip)]
+    pub away_team_id: Uuid,
+}
+
+#[ComplexObject]
+impl GameNode {
+    async fn home_team(&self, ctx: &Context<'_>) -> Result<Option<TeamNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().teams.load_one(self.home_team_id).await?)
+    }
+
+    async fn away_team(&self, ctx: &Context<'_>) -> Result<Option<TeamNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().teams.load_one(self.away_team_id).await?)
+    }
+}
+
+pub fn game_status_str(status: &GameStatus) -> &'static str {
+    match status {
+        GameStatus::Scheduled => "scheduled",
+        GameStatus::InProgress => "in_progress",
+        GameStatus::Finished => "finished",
+        GameStatus::Evaluated => "evaluated",
+        GameStatus::Postponed => "postponed",
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Standing", complex)]
+pub struct StandingNode {
+    pub position: i32,
+    pub games_played: i32,
+    pub wins: i32,
+    pub draws: i32,
+    pub losses: i32,
+    pub points: i32,
+    pub total_points_scored: i32,
+    pub team_power: f32,
+    /// W, D or L for the last five games, most recent first
+    pub recent_form: Vec<String>,
+    #[graphql(skip)]
+    pub team_id: Uuid,
+}
+
+#[ComplexObject]
+impl StandingNode {
+    async fn team(&self, ctx: &Context<'_>) -> Result<Option<TeamNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().teams.load_one(self.team_id).await?)
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "FeedPost", complex)]
+pub struct FeedPostNode {
+    pub id: Uuid,
+    pub post_type: String,
+    pub content: Option<String>,
+    pub workout_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    #[graphql(skip)]
+    pub author_id: Uuid,
+}
+
+#[ComplexObject]
+impl FeedPostNode {
+    async fn author(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        Ok(ctx.data_unchecked::<Loaders>().users.load_one(self.author_id).await?)
+    }
+
+    async fn reaction_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        Ok(self.social_counts(ctx).await?.0)
+    }
+
+    async fn comment_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        Ok(self.social_counts(ctx).await?.1)
+    }
+}
+
+impl FeedPostNode {
+    async fn social_counts(&self, ctx: &Context<'_>) -> Result<(i64, i64)> {
+        let Some(workout_id) = self.workout_id else {
+            return Ok((0, 0));
+        };
+        Ok(ctx.data_unchecked::<Loaders>().workout_social.load_one(workout_id).await?.unwrap_or_default())
+    }
+}
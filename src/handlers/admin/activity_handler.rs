@@ -0,0 +1,139 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::{info, error};
+
+use crate::db::activities::list_activities;
+use crate::models::activity::{Activity, CreateActivityRequest, UpdateActivityRequest};
+use crate::models::common::ApiResponse;
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505"))
+}
+
+/// GET /admin/activities - List the activity catalog, including inactive entries
+pub async fn get_activities(
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let activities = list_activities(pool.get_ref(), true).await.map_err(|e| {
+        error!("Failed to fetch activities: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Activities retrieved successfully", activities)))
+}
+
+/// POST /admin/activities - Add an activity to the catalog
+pub async fn create_activity(
+    pool: web::Data<PgPool>,
+    body: web::Json<CreateActivityRequest>,
+) -> Result<HttpResponse> {
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<Activity>::error(message)));
+    }
+
+    let result = sqlx::query_as!(
+        Activity,
+        r#"
+        INSERT INTO activities (key, name, met_value, default_scoring_profile, icon_key, is_active)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, key, name, met_value, default_scoring_profile, icon_key, is_active, created_at, updated_at
+        "#,
+        body.key,
+        body.name.trim(),
+        body.met_value,
+        body.default_scoring_profile.as_deref().unwrap_or("other"),
+        body.icon_key.as_deref().unwrap_or(&body.key),
+        body.is_active.unwrap_or(true)
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(activity) => {
+            info!("Created activity {} ({})", activity.key, activity.id);
+            Ok(HttpResponse::Created().json(ApiResponse::success("Activity created successfully", activity)))
+        }
+        Err(e) if is_unique_violation(&e) => Ok(HttpResponse::Conflict().json(
+            ApiResponse::<Activity>::error("An activity with this key or name already exists")
+        )),
+        Err(e) => {
+            error!("Failed to create activity: {}", e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// PATCH /admin/activities/{id} - Update an activity's metadata or deactivate it
+pub async fn update_activity(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateActivityRequest>,
+) -> Result<HttpResponse> {
+    let activity_id = path.into_inner();
+
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<Activity>::error(message)));
+    }
+
+    let result = sqlx::query_as!(
+        Activity,
+        r#"
+        UPDATE activities
+        SET name = COALESCE($2, name),
+            met_value = COALESCE($3, met_value),
+            default_scoring_profile = COALESCE($4, default_scoring_profile),
+            icon_key = COALESCE($5, icon_key),
+            is_active = COALESCE($6, is_active)
+        WHERE id = $1
+        RETURNING id, key, name, met_value, default_scoring_profile, icon_key, is_active, created_at, updated_at
+        "#,
+        activity_id,
+        body.name.as_deref().map(str::trim),
+        body.met_value,
+        body.default_scoring_profile.as_deref(),
+        body.icon_key.as_deref(),
+        body.is_active
+    )
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some(activity)) => {
+            info!("Updated activity {} ({})", activity.key, activity.id);
+            Ok(HttpResponse::Ok().json(ApiResponse::success("Activity updated successfully", activity)))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<Activity>::error("Activity not found"))),
+        Err(e) if is_unique_violation(&e) => Ok(HttpResponse::Conflict().json(
+            ApiResponse::<Activity>::error("An activity with this name already exists")
+        )),
+        Err(e) => {
+            error!("Failed to update activity {}: {}", activity_id, e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// DELETE /admin/activities/{id} - Remove an activity from the catalog.
+/// Workouts already labelled with it keep their label.
+pub async fn delete_activity(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let activity_id = path.into_inner();
+
+    let result = sqlx::query!("DELETE FROM activities WHERE id = $1", activity_id)
+        .execute(pool.get_ref())
+        .await
+        .map_err(|e| {
+            error!("Failed to delete activity {}: {}", activity_id, e);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Activity not found")));
+    }
+
+    info!("Deleted activity {}", activity_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Activity deleted successfully")))
+}
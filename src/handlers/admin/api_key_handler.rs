@@ -0,0 +1,74 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::db::api_keys::{issue_api_key, list_api_keys, revoke_api_key};
+use crate::middleware::auth::Claims;
+use crate::models::api_key::{ApiKeyListQuery, IssueApiKeyRequest};
+use crate::models::common::ApiResponse;
+
+/// GET /admin/api-keys - Issued API keys, optionally only those of ?user_id=
+pub async fn get_api_keys(
+    pool: web::Data<PgPool>,
+    query: web::Query<ApiKeyListQuery>,
+) -> Result<HttpResponse> {
+    match list_api_keys(pool.get_ref(), query.user_id).await {
+        Ok(api_keys) => Ok(HttpResponse::Ok().json(ApiResponse::success("API keys retrieved", api_keys))),
+        Err(e) => {
+            error!("Failed to list API keys: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
+
+/// POST /admin/api-keys - Issue a key acting as a user within its scopes. The key is only
+/// shown in this response.
+pub async fn create_api_key(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    body: web::Json<IssueApiKeyRequest>,
+) -> Result<HttpResponse> {
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+    let scopes = match body.validate(chrono::Utc::now()) {
+        Ok(scopes) => scopes,
+        Err(message) => return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(message))),
+    };
+
+    match issue_api_key(pool.get_ref(), body.user_id, body.name.trim(), &scopes, body.expires_at, admin_id).await {
+        Ok(Some(issued)) => {
+            info!(
+                "Admin {} issued API key {} ({}) for user {} with scopes {:?}",
+                admin_id, issued.api_key.id, issued.api_key.name, body.user_id, scopes
+            );
+            Ok(HttpResponse::Created().json(ApiResponse::success("API key issued", issued)))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("User not found"))),
+        Err(e) => {
+            error!("Failed to issue API key for user {}: {}", body.user_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
+
+/// DELETE /admin/api-keys/{id} - Revoke a key; requests with it are refused from now on
+pub async fn delete_api_key(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let key_id = path.into_inner();
+    match revoke_api_key(pool.get_ref(), key_id).await {
+        Ok(true) => {
+            info!("Admin {} revoked API key {}", claims.username, key_id);
+            Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("API key revoked")))
+        }
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("API key not found or already revoked"))),
+        Err(e) => {
+            error!("Failed to revoke API key {}: {}", key_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
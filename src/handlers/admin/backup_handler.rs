@@ -0,0 +1,68 @@
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use uuid::Uuid;
+use tracing::{info, error};
+
+use crate::models::common::ApiResponse;
+use crate::services::backup_verification_service::{BackupVerificationService, BackupVerificationRun};
+
+#[derive(Debug, Deserialize)]
+pub struct BackupVerificationQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TriggerBackupVerificationQuery {
+    /// Also verify the large tables, which nightly runs leave to Sundays
+    #[serde(default)]
+    pub full: bool,
+}
+
+/// GET /admin/backups/verifications - List recent backup verification runs
+pub async fn get_backup_verifications(
+    service: web::Data<BackupVerificationService>,
+    query: web::Query<BackupVerificationQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    let runs = service.get_recent_runs(limit).await.map_err(|e| {
+        error!("Failed to fetch backup verification runs: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Backup verification runs retrieved successfully", runs)))
+}
+
+/// GET /admin/backups/verifications/{id} - Get a single backup verification run
+pub async fn get_backup_verification(
+    service: web::Data<BackupVerificationService>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let run_id = path.into_inner();
+
+    let run = service.get_run(run_id).await.map_err(|e| {
+        error!("Failed to fetch backup verification run {}: {}", run_id, e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    match run {
+        Some(run) => Ok(HttpResponse::Ok().json(ApiResponse::success("Backup verification run retrieved successfully", run))),
+        None => Ok(HttpResponse::NotFound().json(ApiResponse::<BackupVerificationRun>::error("Backup verification run not found"))),
+    }
+}
+
+/// POST /admin/backups/verify?full=true - Run a backup verification immediately
+pub async fn trigger_backup_verification(
+    service: web::Data<BackupVerificationService>,
+    query: web::Query<TriggerBackupVerificationQuery>,
+) -> Result<HttpResponse> {
+    info!("Admin triggered {} backup verification", if query.full { "full" } else { "nightly" });
+
+    let run = service.run_verification("admin", query.full).await.map_err(|e| {
+        error!("Failed to run backup verification: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to run backup verification")
+    })?;
+
+    let message = format!("Backup verification finished with status '{}'", run.status);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(message, run)))
+}
@@ -0,0 +1,110 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+use tracing::{info, error};
+
+use crate::middleware::auth::Claims;
+use crate::models::broadcast::{AdminBroadcastWithStats, BroadcastListQuery, CreateBroadcastRequest};
+use crate::models::common::ApiResponse;
+use crate::services::BroadcastService;
+
+const BROADCAST_STATUSES: &[&str] = &["scheduled", "sending", "sent", "cancelled", "failed"];
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("Broadcast database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// POST /admin/broadcast - Send an announcement to all users, a league or a team,
+/// right away or at `scheduled_for`
+pub async fn create_broadcast(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    redis_client: Option<web::Data<Arc<redis::Client>>>,
+    body: web::Json<CreateBroadcastRequest>,
+) -> Result<HttpResponse> {
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<AdminBroadcastWithStats>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<AdminBroadcastWithStats>::error(message)));
+    }
+
+    let service = BroadcastService::new(pool.get_ref().clone(), redis_client.map(|c| c.get_ref().clone()));
+    if !service.target_exists(&body.target_type, body.target_id).await.map_err(database_error)? {
+        let message = if body.target_type == "league" { "League not found" } else { "Team not found" };
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<AdminBroadcastWithStats>::error(message)));
+    }
+
+    let broadcast = service.create(admin_id, &body).await.map_err(database_error)?;
+    info!("Broadcast {} to {} created by {}", broadcast.id, broadcast.target_type, claims.username);
+
+    let message = if body.scheduled_for.is_some() {
+        "Broadcast scheduled successfully"
+    } else {
+        service.send(broadcast.id).await.map_err(database_error)?;
+        "Broadcast sent successfully"
+    };
+
+    let broadcast = service.get(broadcast.id).await.map_err(database_error)?
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("Broadcast disappeared"))?;
+    Ok(HttpResponse::Created().json(ApiResponse::success(message, broadcast)))
+}
+
+/// GET /admin/broadcast - List announcements with their delivery stats, newest first
+pub async fn get_broadcasts(
+    pool: web::Data<PgPool>,
+    query: web::Query<BroadcastListQuery>,
+) -> Result<HttpResponse> {
+    if let Some(status) = &query.status {
+        if !BROADCAST_STATUSES.contains(&status.as_str()) {
+            return Ok(HttpResponse::BadRequest().json(ApiResponse::<Vec<AdminBroadcastWithStats>>::error(
+                format!("Status must be one of: {}", BROADCAST_STATUSES.join(", "))
+            )));
+        }
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let broadcasts = BroadcastService::new(pool.get_ref().clone(), None)
+        .list(query.status.as_deref(), limit)
+        .await
+        .map_err(database_error)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Broadcasts retrieved successfully", broadcasts)))
+}
+
+/// GET /admin/broadcast/{id} - An announcement with its delivery stats
+pub async fn get_broadcast(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let broadcast_id = path.into_inner();
+    match BroadcastService::new(pool.get_ref().clone(), None).get(broadcast_id).await.map_err(database_error)? {
+        Some(broadcast) => Ok(HttpResponse::Ok().json(ApiResponse::success("Broadcast retrieved successfully", broadcast))),
+        None => Ok(HttpResponse::NotFound().json(ApiResponse::<AdminBroadcastWithStats>::error("Broadcast not found"))),
+    }
+}
+
+/// DELETE /admin/broadcast/{id} - Cancel a scheduled announcement before it goes out
+pub async fn cancel_broadcast(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let broadcast_id = path.into_inner();
+    let service = BroadcastService::new(pool.get_ref().clone(), None);
+
+    if let Some(broadcast) = service.cancel(broadcast_id).await.map_err(database_error)? {
+        info!("Broadcast {} cancelled by {}", broadcast.id, claims.username);
+        let broadcast = AdminBroadcastWithStats { broadcast, read_count: 0 };
+        return Ok(HttpResponse::Ok().json(ApiResponse::success("Broadcast cancelled successfully", broadcast)));
+    }
+
+    match service.get(broadcast_id).await.map_err(database_error)? {
+        Some(broadcast) => Ok(HttpResponse::Conflict().json(ApiResponse::<AdminBroadcastWithStats>::error(
+            format!("Broadcast is already {}", broadcast.broadcast.status)
+        ))),
+        None => Ok(HttpResponse::NotFound().json(ApiResponse::<AdminBroadcastWithStats>::error("Broadcast not found"))),
+    }
+}
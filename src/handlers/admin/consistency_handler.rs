@@ -0,0 +1,49 @@
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::error;
+
+use crate::models::common::ApiResponse;
+use crate::services::score_consistency_service::ScoreConsistencyService;
+
+#[derive(Debug, Deserialize)]
+pub struct ScoreConsistencyQuery {
+    pub season_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScoreConsistencyRunsQuery {
+    pub limit: Option<i64>,
+}
+
+/// GET /admin/consistency - Recompute game scores now and report divergences
+pub async fn get_score_consistency(
+    pool: web::Data<PgPool>,
+    query: web::Query<ScoreConsistencyQuery>,
+) -> Result<HttpResponse> {
+    let service = ScoreConsistencyService::new(pool.get_ref().clone());
+    let report = service.check_games(query.season_id).await.map_err(|e| {
+        error!("Failed to run score consistency check: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    let message = format!("{} of {} games have score issues", report.games_with_issues, report.games_checked);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(message, report)))
+}
+
+/// GET /admin/consistency/runs - List recent periodic consistency check runs
+pub async fn get_score_consistency_runs(
+    pool: web::Data<PgPool>,
+    query: web::Query<ScoreConsistencyRunsQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    let service = ScoreConsistencyService::new(pool.get_ref().clone());
+    let runs = service.get_recent_runs(limit).await.map_err(|e| {
+        error!("Failed to fetch score consistency runs: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Score consistency runs retrieved successfully", runs)))
+}
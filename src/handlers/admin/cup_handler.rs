@@ -0,0 +1,29 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::handlers::league::cup_handler::cup_error_response;
+use crate::league::cups::CupService;
+use crate::models::common::ApiResponse;
+use crate::models::cup::{CreateCupRequest, SeasonCup};
+
+/// POST /admin/seasons/{season_id}/cups - Start a knockout cup alongside the season and draw its first round
+pub async fn create_cup(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<CreateCupRequest>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<SeasonCup>::error(message)));
+    }
+
+    match CupService::new(pool.get_ref().clone()).create_cup(season_id, &body).await {
+        Ok(cup) => {
+            info!("Created cup {} in season {}", cup.id, season_id);
+            Ok(HttpResponse::Created().json(ApiResponse::success("Cup created", cup)))
+        }
+        Err(e) => Ok(cup_error_response(e)),
+    }
+}
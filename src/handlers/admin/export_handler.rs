@@ -0,0 +1,223 @@
+use actix_web::{http::header, web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use sqlx::postgres::PgPoolCopyExt;
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::{error, info};
+
+const USER_STATUSES: &[&str] = &["active", "inactive", "suspended", "banned"];
+const GAME_STATUSES: &[&str] = &["scheduled", "in_progress", "finished", "evaluated", "postponed"];
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEntity {
+    Workouts,
+    Users,
+    Games,
+}
+
+impl ExportEntity {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExportEntity::Workouts => "workouts",
+            ExportEntity::Users => "users",
+            ExportEntity::Games => "games",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub entity: ExportEntity,
+    pub format: Option<String>,
+    /// Lower bound (inclusive) on workout_start / users.created_at / game_start_time
+    pub from: Option<DateTime<Utc>>,
+    /// Upper bound (exclusive) on workout_start / users.created_at / game_start_time
+    pub to: Option<DateTime<Utc>>,
+    pub user_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub season_id: Option<Uuid>,
+    pub status: Option<String>,
+}
+
+/// Build the `COPY ... TO STDOUT` statement for an export.
+/// COPY cannot take bind parameters, so only typed values (UUIDs, timestamps) and
+/// whitelisted statuses are ever interpolated.
+fn build_copy_statement(query: &ExportQuery) -> std::result::Result<String, String> {
+    let mut conditions: Vec<String> = Vec::new();
+    let unsupported = |filter: &str| Err(format!("Filter '{}' is not supported for {}", filter, query.entity.as_str()));
+
+    let select = match query.entity {
+        ExportEntity::Workouts => {
+            if query.season_id.is_some() {
+                return unsupported("season_id");
+            }
+            if query.status.is_some() {
+                return unsupported("status");
+            }
+            if let Some(from) = query.from {
+                conditions.push(format!("wd.workout_start >= '{}'::timestamptz", from.to_rfc3339()));
+            }
+            if let Some(to) = query.to {
+                conditions.push(format!("wd.workout_start < '{}'::timestamptz", to.to_rfc3339()));
+            }
+            if let Some(user_id) = query.user_id {
+                conditions.push(format!("wd.user_id = '{user_id}'"));
+            }
+            if let Some(team_id) = query.team_id {
+                conditions.push(format!(
+                    "wd.user_id IN (SELECT tm.user_id FROM team_members tm WHERE tm.team_id = '{team_id}')"
+                ));
+            }
+            r#"
+            SELECT
+                wd.id AS workout_id,
+                wd.user_id,
+                u.username,
+                wd.workout_start,
+                wd.workout_end,
+                wd.duration_minutes,
+                COALESCE(wd.user_activity, wd.activity_name) AS activity,
+                wd.avg_heart_rate,
+                wd.max_heart_rate,
+                wd.min_heart_rate,
+                wd.calories_burned,
+                wd.stamina_gained,
+                wd.strength_gained,
+                wd.total_points_gained,
+                wd.created_at AS uploaded_at
+            FROM workout_data wd
+            JOIN users u ON u.id = wd.user_id
+            "#
+        }
+        ExportEntity::Users => {
+            if query.season_id.is_some() {
+                return unsupported("season_id");
+            }
+            if let Some(from) = query.from {
+                conditions.push(format!("u.created_at >= '{}'::timestamptz", from.to_rfc3339()));
+            }
+            if let Some(to) = query.to {
+                conditions.push(format!("u.created_at < '{}'::timestamptz", to.to_rfc3339()));
+            }
+            if let Some(user_id) = query.user_id {
+                conditions.push(format!("u.id = '{user_id}'"));
+            }
+            if let Some(team_id) = query.team_id {
+                conditions.push(format!("t.id = '{team_id}'"));
+            }
+            if let Some(status) = &query.status {
+                let status = USER_STATUSES.iter().find(|s| **s == status.as_str())
+                    .ok_or_else(|| format!("Invalid user status '{status}'"))?;
+                conditions.push(format!("u.status = '{status}'"));
+            }
+            r#"
+            SELECT
+                u.id AS user_id,
+                u.username,
+                u.email,
+                u.role,
+                u.status,
+                u.created_at,
+                t.id AS team_id,
+                t.team_name,
+                tm.role AS team_role,
+                (SELECT COUNT(*) FROM workout_data w WHERE w.user_id = u.id) AS workout_count,
+                (SELECT MAX(w.workout_start) FROM workout_data w WHERE w.user_id = u.id) AS last_workout_at
+            FROM users u
+            LEFT JOIN team_members tm ON tm.user_id = u.id AND tm.status = 'active'
+            LEFT JOIN teams t ON t.id = tm.team_id
+            "#
+        }
+        ExportEntity::Games => {
+            if query.user_id.is_some() {
+                return unsupported("user_id");
+            }
+            if let Some(from) = query.from {
+                conditions.push(format!("g.game_start_time >= '{}'::timestamptz", from.to_rfc3339()));
+            }
+            if let Some(to) = query.to {
+                conditions.push(format!("g.game_start_time < '{}'::timestamptz", to.to_rfc3339()));
+            }
+            if let Some(team_id) = query.team_id {
+                conditions.push(format!("(g.home_team_id = '{team_id}' OR g.away_team_id = '{team_id}')"));
+            }
+            if let Some(season_id) = query.season_id {
+                conditions.push(format!("g.season_id = '{season_id}'"));
+            }
+            if let Some(status) = &query.status {
+                let status = GAME_STATUSES.iter().find(|s| **s == status.as_str())
+                    .ok_or_else(|| format!("Invalid game status '{status}'"))?;
+                conditions.push(format!("g.status = '{status}'"));
+            }
+            r#"
+            SELECT
+                g.id AS game_id,
+                g.season_id,
+                s.name AS season_name,
+                g.week_number,
+                g.home_team_id,
+                home_team.team_name AS home_team_name,
+                g.away_team_id,
+                away_team.team_name AS away_team_name,
+                g.status,
+                g.home_score,
+                g.away_score,
+                g.winner_team_id,
+                g.game_start_time,
+                g.game_end_time
+            FROM games g
+            JOIN league_seasons s ON s.id = g.season_id
+            JOIN teams home_team ON home_team.id = g.home_team_id
+            JOIN teams away_team ON away_team.id = g.away_team_id
+            "#
+        }
+    };
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let order_by = match query.entity {
+        ExportEntity::Workouts => "ORDER BY wd.workout_start, wd.id",
+        ExportEntity::Users => "ORDER BY u.created_at, u.id",
+        ExportEntity::Games => "ORDER BY g.season_id, g.week_number, g.id",
+    };
+
+    Ok(format!("COPY ({select} {where_clause} {order_by}) TO STDOUT WITH (FORMAT csv, HEADER)"))
+}
+
+/// GET /admin/export?entity=workouts|users|games&format=csv - Stream a CSV export
+/// straight from Postgres COPY, optionally filtered by date range, user, team, season and status
+pub async fn export_csv(
+    pool: web::Data<PgPool>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse> {
+    let format = query.format.as_deref().unwrap_or("csv");
+    if format != "csv" {
+        return Err(actix_web::error::ErrorBadRequest(format!("Unsupported export format '{format}'")));
+    }
+
+    let statement = build_copy_statement(&query).map_err(actix_web::error::ErrorBadRequest)?;
+
+    let stream = pool.copy_out_raw(&statement).await.map_err(|e| {
+        error!("Failed to start {} export: {}", query.entity.as_str(), e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    let filename = format!("{}_{}.csv", query.entity.as_str(), Utc::now().format("%Y%m%d_%H%M%S"));
+    info!("Streaming {} export as {}", query.entity.as_str(), filename);
+
+    let entity = query.entity.as_str();
+    let body = stream.inspect_err(move |e| {
+        error!("Export of {} aborted mid-stream: {}", entity, e);
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")))
+        .streaming(body))
+}
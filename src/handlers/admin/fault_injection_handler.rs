@@ -0,0 +1,30 @@
+use actix_web::{web, HttpResponse, Result};
+use tracing::warn;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::services::fault_injection::{faults, set_faults, FaultInjectionSettings};
+
+/// GET /admin/fault-injection - Dependency failures injected right now
+pub async fn get_fault_injection() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Fault injection retrieved successfully", faults())))
+}
+
+/// PUT /admin/fault-injection - Replace the injected Redis, MinIO and ML failures; all zero turns
+/// injection off. Applies to this instance only, until it restarts.
+pub async fn update_fault_injection(
+    claims: web::ReqData<Claims>,
+    body: web::Json<FaultInjectionSettings>,
+) -> Result<HttpResponse> {
+    let settings = body.into_inner();
+    if let Err(e) = settings.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(e)));
+    }
+    set_faults(settings.clone());
+
+    warn!(
+        "Fault injection updated by {}: Redis publish failure rate {}, MinIO delay {}ms, ML drop rate {}",
+        claims.username, settings.redis_publish_failure_rate, settings.minio_delay_ms, settings.ml_drop_rate
+    );
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Fault injection updated successfully", settings)))
+}
@@ -0,0 +1,96 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::db::fixture_flavor;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::league::{FixtureFlavor, GameWeekName, SetFixtureFlavorRequest, SetGameWeekNameRequest};
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("Fixture flavor database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// PUT /admin/seasons/{season_id}/weeks/{week_number}/name - Name a game week, e.g. "Derby Week"
+pub async fn set_game_week_name(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<(Uuid, i32)>,
+    body: web::Json<SetGameWeekNameRequest>,
+) -> Result<HttpResponse> {
+    let (season_id, week_number) = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<GameWeekName>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<GameWeekName>::error(message)));
+    }
+
+    let Some(week_name) = fixture_flavor::set_week_name(pool.get_ref(), season_id, week_number, &body, admin_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<GameWeekName>::error("The season has no games that week")));
+    };
+
+    info!("Admin {} named week {} of season {} '{}'", admin_id, week_number, season_id, week_name.name);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Game week named successfully", week_name)))
+}
+
+/// DELETE /admin/seasons/{season_id}/weeks/{week_number}/name - Go back to the plain week number
+pub async fn remove_game_week_name(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, i32)>,
+) -> Result<HttpResponse> {
+    let (season_id, week_number) = path.into_inner();
+
+    if !fixture_flavor::remove_week_name(pool.get_ref(), season_id, week_number).await.map_err(database_error)? {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Week has no name")));
+    }
+
+    info!("Removed the name of week {} of season {}", week_number, season_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Game week name removed successfully")))
+}
+
+/// PUT /admin/games/{game_id}/flavor - Set a fixture's title, venue, description and banner
+pub async fn set_fixture_flavor(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetFixtureFlavorRequest>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<FixtureFlavor>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<FixtureFlavor>::error(message)));
+    }
+
+    let Some(flavor) = fixture_flavor::set_fixture_flavor(pool.get_ref(), game_id, &body, admin_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<FixtureFlavor>::error("Game not found")));
+    };
+
+    info!("Admin {} set the flavor of game {}", admin_id, game_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Fixture flavor updated successfully", flavor)))
+}
+
+/// DELETE /admin/games/{game_id}/flavor - Remove a fixture's flavor
+pub async fn remove_fixture_flavor(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+
+    if !fixture_flavor::remove_fixture_flavor(pool.get_ref(), game_id).await.map_err(database_error)? {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Fixture has no flavor")));
+    }
+
+    info!("Removed the flavor of game {}", game_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Fixture flavor removed successfully")))
+}
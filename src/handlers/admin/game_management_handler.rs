@@ -6,8 +6,10 @@ use chrono::{Utc, Duration};
 use tracing::{info, error};
 use std::sync::Arc;
 
+use crate::db::game_repo::{GameRepo, GameRepository};
+use crate::game::review_lock::ReviewBlockedGame;
 use crate::models::common::ApiResponse;
-use crate::services::GameEvaluationService;
+use crate::services::{GameEvaluationService, GameWatchdogService, ReviewLockService, SchedulerService};
 
 #[derive(Debug, Deserialize)]
 pub struct StartGamesRequest {
@@ -315,20 +317,7 @@ pub async fn adjust_live_game_score(
     }
 
     // Validate that the game exists and is active
-    let game = sqlx::query!(
-        r#"
-        SELECT g.id, g.home_score, g.away_score, g.status,
-               ht.team_name as home_team_name,
-               at.team_name as away_team_name
-        FROM games g
-        JOIN teams ht ON g.home_team_id = ht.id
-        JOIN teams at ON g.away_team_id = at.id
-        WHERE g.id = $1
-        "#,
-        body.game_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await
+    let game = GameRepo::new(pool.get_ref().clone()).find_live_game_state(body.game_id).await
     .map_err(|e| {
         error!("Failed to fetch live game: {}", e);
         actix_web::error::ErrorInternalServerError("Database error")
@@ -343,7 +332,7 @@ pub async fn adjust_live_game_score(
         }
     };
 
-    if game_data.status != "in_progress" {
+    if !game_data.is_in_progress() {
         return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(
             "Cannot adjust scores for game not in progress"
         )));
@@ -440,6 +429,14 @@ pub async fn adjust_live_game_score(
     Ok(HttpResponse::Ok().json(ApiResponse::success(response.message.clone(), response)))
 }
 
+/// Games among `game_ids` whose evaluation waits for pending workout reports, with the reports
+async fn review_blocked_games(pool: &PgPool, game_ids: &[Uuid]) -> Result<Vec<ReviewBlockedGame>> {
+    ReviewLockService::new(pool.clone()).blocked_games(game_ids).await.map_err(|e| {
+        error!("Failed to check review locks: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to check review locks")
+    })
+}
+
 /// POST /admin/games/trigger-evaluation - Manually trigger game evaluation for all finished games and start upcoming games
 pub async fn trigger_game_evaluation(
     pool: web::Data<PgPool>,
@@ -469,7 +466,7 @@ pub async fn trigger_game_evaluation(
         actix_web::error::ErrorInternalServerError("Failed to fetch games")
     })?;
 
-    let games_evaluated = if !finished_games.is_empty() {
+    let (games_evaluated, blocked_games) = if !finished_games.is_empty() {
         // Initialize game evaluation service
         let redis_client_inner = match redis_client.as_ref() {
             Some(client) => client.get_ref().clone(),
@@ -491,11 +488,14 @@ pub async fn trigger_game_evaluation(
             row.id
         }).collect();
 
+        // Games waiting for workout reports are held back by the evaluation
+        let blocked_games = review_blocked_games(pool.get_ref(), &games_for_evaluation).await?;
+
         // Evaluate the games
         match evaluation_service.evaluate_finished_live_games(&games_for_evaluation).await {
             Ok(evaluation_results) => {
                 info!("Successfully evaluated {} games", evaluation_results.len());
-                evaluation_results.len()
+                (evaluation_results.len(), blocked_games)
             }
             Err(e) => {
                 error!("Failed to evaluate games: {}", e);
@@ -506,7 +506,7 @@ pub async fn trigger_game_evaluation(
         }
     } else {
         info!("No finished games to evaluate");
-        0
+        (0, Vec::new())
     };
 
     // Now run the game cycle to start upcoming games
@@ -536,6 +536,7 @@ pub async fn trigger_game_evaluation(
         "games_finished": finished_games.len(),
         "live_games": live_games.len(),
         "games_ready_to_start": games_ready_to_start.len(),
+        "blocked_games": blocked_games,
         "message": message
     });
 
@@ -831,6 +832,9 @@ pub async fn evaluate_games_for_date(
         row.id
     }).collect();
 
+    // Games waiting for workout reports are held back by the evaluation
+    let blocked_games = review_blocked_games(pool.get_ref(), &games_for_evaluation).await?;
+
     // Evaluate the games
     match evaluation_service.evaluate_finished_live_games(&games_for_evaluation).await {
         Ok(evaluation_results) => {
@@ -844,6 +848,7 @@ pub async fn evaluate_games_for_date(
                 "success": true,
                 "games_evaluated": games_evaluated,
                 "games_updated": games_evaluated,
+                "blocked_games": blocked_games,
                 "message": message
             });
 
@@ -856,4 +861,30 @@ pub async fn evaluate_games_for_date(
             )))
         }
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Deserialize)]
+pub struct GameWatchdogQuery {
+    pub limit: Option<i64>,
+}
+
+/// GET /admin/games/watchdog - Games left unfinalized past their end time, with alert totals
+pub async fn get_game_watchdog_status(
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<redis::Client>>,
+    scheduler: web::Data<Arc<SchedulerService>>,
+    query: web::Query<GameWatchdogQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let watchdog = GameWatchdogService::new(
+        pool.get_ref().clone(),
+        redis_client.get_ref().clone(),
+        scheduler.game_watchdog_settings().clone(),
+    );
+    let status = watchdog.status(limit).await.map_err(|e| {
+        error!("Failed to fetch game watchdog status: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    let message = format!("{} games are overdue for finalization", status.unfinished_games);
+    Ok(HttpResponse::Ok().json(ApiResponse::success(message, status)))
+}
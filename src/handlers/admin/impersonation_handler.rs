@@ -0,0 +1,107 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::config::jwt::JwtSettings;
+use crate::db::impersonations::{list_impersonations, record_impersonation};
+use crate::db::user_sessions::start_session;
+use crate::handlers::auth_handler::{generate_impersonation_token, session_device};
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::impersonation::{
+    ImpersonateUserRequest, Impersonation, ImpersonationTokenResponse, IMPERSONATION_TOKEN_MINUTES,
+};
+
+/// POST /admin/users/{id}/impersonate - Short-lived token to act as the user, e.g. to reproduce
+/// what they report. Every token is recorded in the audit trail with the reason given, and gets
+/// a session of the user's that expires with it.
+pub async fn impersonate_user(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<ImpersonateUserRequest>,
+) -> Result<HttpResponse> {
+    let user_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<ImpersonationTokenResponse>::error("Invalid user ID")));
+    };
+    if admin_id == user_id {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<ImpersonationTokenResponse>::error("You cannot impersonate yourself")));
+    }
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<ImpersonationTokenResponse>::error(message)));
+    }
+
+    let user = match sqlx::query!("SELECT username, role, status FROM users WHERE id = $1", user_id)
+        .fetch_optional(pool.get_ref())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::<ImpersonationTokenResponse>::error("User not found")));
+        }
+        Err(e) => {
+            error!("Database error looking up user {} to impersonate: {}", user_id, e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<ImpersonationTokenResponse>::error("Database error")));
+        }
+    };
+    // Acting as another admin would hand out their privileges
+    if user.role == "admin" || user.role == "superadmin" {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::<ImpersonationTokenResponse>::error("Admins cannot be impersonated")));
+    }
+
+    let expires_at = Utc::now() + Duration::minutes(IMPERSONATION_TOKEN_MINUTES);
+    let reason = body.reason.trim();
+    let impersonation_id = match record_impersonation(pool.get_ref(), admin_id, user_id, reason, expires_at).await {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to record impersonation of user {}: {}", user_id, e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<ImpersonationTokenResponse>::error("Database error")));
+        }
+    };
+
+    let session = async {
+        let mut conn = pool.acquire().await?;
+        start_session(&mut conn, user_id, &session_device(&req), expires_at).await
+    }
+    .await;
+    let session_id = match session {
+        Ok(session_id) => session_id,
+        Err(e) => {
+            error!("Failed to start impersonation session for user {}: {}", user_id, e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<ImpersonationTokenResponse>::error("Database error")));
+        }
+    };
+
+    match generate_impersonation_token(user_id, user.username, &user.role, &user.status, admin_id, session_id, expires_at, &jwt_settings) {
+        Ok(token) => {
+            info!("Admin {} impersonating user {} until {}: {}", admin_id, user_id, expires_at, reason);
+            Ok(HttpResponse::Created().json(ApiResponse::success(
+                "Impersonation token issued",
+                ImpersonationTokenResponse { impersonation_id, token, expires_at },
+            )))
+        }
+        Err(e) => {
+            error!("Error generating impersonation token: {:?}", e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<ImpersonationTokenResponse>::error("Failed to issue token")))
+        }
+    }
+}
+
+/// GET /admin/users/{id}/impersonations - Who impersonated a user, when and why
+pub async fn get_user_impersonations(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    match list_impersonations(pool.get_ref(), path.into_inner()).await {
+        Ok(impersonations) => Ok(HttpResponse::Ok().json(ApiResponse::success("Impersonations retrieved", impersonations))),
+        Err(e) => {
+            error!("Failed to list impersonations: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<Vec<Impersonation>>::error("Database error")))
+        }
+    }
+}
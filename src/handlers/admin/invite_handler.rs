@@ -0,0 +1,43 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::db::invites::{list_invite_codes, revoke_invite_code};
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::team_invitation::InviteCodeListQuery;
+
+/// GET /admin/invites - Team invite codes, optionally only those of ?team_id=
+pub async fn get_invite_codes(
+    pool: web::Data<PgPool>,
+    query: web::Query<InviteCodeListQuery>,
+) -> Result<HttpResponse> {
+    match list_invite_codes(pool.get_ref(), query.team_id).await {
+        Ok(invites) => Ok(HttpResponse::Ok().json(ApiResponse::success("Invite codes retrieved", invites))),
+        Err(e) => {
+            error!("Failed to list invite codes: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
+
+/// POST /admin/invites/{id}/revoke - Revoke an invite code; registrations with it are refused from now on
+pub async fn revoke_invite(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let invite_id = path.into_inner();
+    match revoke_invite_code(pool.get_ref(), invite_id).await {
+        Ok(true) => {
+            info!("Admin {} revoked invite code {}", claims.username, invite_id);
+            Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Invite code revoked")))
+        }
+        Ok(false) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Invite code not found or already revoked"))),
+        Err(e) => {
+            error!("Failed to revoke invite code {}: {}", invite_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
@@ -6,6 +6,13 @@ use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
 use crate::handlers::admin::user_handler::ApiResponse;
+use crate::db::league_waitlist;
+use crate::db::organizations::find_organization;
+use crate::game::commentary;
+use crate::game::comeback_bonus;
+use crate::game::review_lock::MAX_REVIEW_LOCK_HOURS;
+use crate::models::commentary::CommentaryMilestone;
+use crate::models::league::ScheduleFairness;
 
 #[derive(Serialize)]
 pub struct AdminLeagueResponse {
@@ -15,6 +22,14 @@ pub struct AdminLeagueResponse {
     pub max_teams: i32,
     pub current_team_count: i64,
     pub created_at: DateTime<Utc>,
+    pub commentary_enabled: bool,
+    pub commentary_locale: String,
+    pub commentary_milestones: Vec<String>,
+    pub schedule_balance_home_away: bool,
+    pub schedule_spread_strong_teams: bool,
+    pub timezone: String,
+    pub comeback_multiplier: Option<f32>,
+    pub review_lock_hours: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -22,6 +37,7 @@ pub struct CreateLeagueRequest {
     pub name: String,
     pub description: Option<String>,
     pub max_teams: i32,
+    pub timezone: Option<String>, // IANA timezone kickoffs are kept in (defaults to UTC)
 }
 
 #[derive(Deserialize)]
@@ -29,6 +45,15 @@ pub struct UpdateLeagueRequest {
     pub name: Option<String>,
     pub season_start_date: Option<DateTime<Utc>>,
     pub season_end_date: Option<DateTime<Utc>>,
+    pub commentary_enabled: Option<bool>,
+    pub commentary_locale: Option<String>, // One of crate::game::commentary::SUPPORTED_LOCALES
+    pub commentary_milestones: Option<Vec<String>>, // first_score, lead_change, final_minutes, mvp_candidate
+    pub max_teams: Option<i32>, // Raising the cap promotes waitlisted teams; lowering it keeps current teams
+    pub schedule_balance_home_away: Option<bool>, // Alternate home and away games evenly in new schedules
+    pub schedule_spread_strong_teams: Option<bool>, // Keep strong opponents (by previous season) apart in new schedules
+    pub timezone: Option<String>, // Applies to seasons created afterwards; existing schedules keep theirs
+    pub comeback_multiplier: Option<f32>, // Trailing team's multiplier in the final quarter of a game; 1.0 disables
+    pub review_lock_hours: Option<i32>, // Hours finished games wait for pending workout reports before evaluation; 0 disables
 }
 
 #[derive(Deserialize)]
@@ -52,14 +77,87 @@ pub struct CreateSeasonRequest {
     pub evaluation_cron: Option<String>, // Cron expression for game evaluation schedule
     pub evaluation_timezone: Option<String>, // Timezone (defaults to "UTC")
     pub auto_evaluation_enabled: Option<bool>, // Whether to enable automatic evaluation (defaults to true)
-    pub game_duration_seconds: Option<i64>, // Duration of games in seconds (defaults to 518400 = 6 days)
-    pub games_per_matchup: Option<i32>, // Number of games per matchup (defaults to 1 = single round-robin)
+    pub game_duration_seconds: Option<i64>, // Duration of games in seconds (defaults to the organization default, 518400 = 6 days)
+    pub games_per_matchup: Option<i32>, // Number of games per matchup (defaults to the organization default, 1 = single round-robin)
+    pub inactivity_nudge_days: Option<i32>, // Days without an upload before members are nudged (defaults to the organization default, 3; 0 disables)
 }
 
 #[derive(Deserialize)]
 pub struct UpdateSeasonRequest {
     pub name: Option<String>,
     pub start_date: Option<DateTime<Utc>>,
+    pub inactivity_nudge_days: Option<i32>, // 0 disables nudges for the season
+}
+
+/// Days without an upload before season members are nudged, unless configured otherwise
+const DEFAULT_INACTIVITY_NUDGE_DAYS: i32 = 3;
+
+/// Validate a season's nudge threshold; 0 disables nudges and is stored as NULL
+fn parse_commentary_locale(locale: &str) -> Result<&str> {
+    if commentary::is_supported_locale(locale) {
+        Ok(locale)
+    } else {
+        Err(actix_web::error::ErrorBadRequest(
+            format!("Unsupported commentary locale {locale}. Supported: {}", commentary::SUPPORTED_LOCALES.join(", "))
+        ))
+    }
+}
+
+fn parse_league_timezone(timezone: &str) -> Result<&str> {
+    match timezone.parse::<chrono_tz::Tz>() {
+        Ok(tz) => Ok(tz.name()),
+        Err(_) => Err(actix_web::error::ErrorBadRequest(
+            format!("Unknown timezone {timezone}. Use an IANA name like Europe/Berlin")
+        )),
+    }
+}
+
+fn parse_commentary_milestones(milestones: &[String]) -> Result<Vec<String>> {
+    let mut parsed: Vec<String> = Vec::new();
+    for milestone in milestones {
+        let Some(milestone) = CommentaryMilestone::parse(milestone) else {
+            return Err(actix_web::error::ErrorBadRequest(format!("Unknown commentary milestone: {milestone}")));
+        };
+        if !parsed.iter().any(|m| m == milestone.as_str()) {
+            parsed.push(milestone.as_str().to_string());
+        }
+    }
+    Ok(parsed)
+}
+
+/// Validate a league's comeback multiplier; 1.0 disables the rule and is stored as NULL
+fn parse_comeback_multiplier(multiplier: f32) -> Result<Option<f32>> {
+    if multiplier == 1.0 {
+        Ok(None)
+    } else if multiplier > 1.0 && multiplier <= comeback_bonus::MAX_COMEBACK_MULTIPLIER {
+        Ok(Some(multiplier))
+    } else {
+        Err(actix_web::error::ErrorBadRequest(format!(
+            "Comeback multiplier must be above 1.0 and at most {}, or 1.0 to disable. Got: {multiplier}",
+            comeback_bonus::MAX_COMEBACK_MULTIPLIER
+        )))
+    }
+}
+
+/// Validate a league's review lock; 0 disables the lock and is stored as NULL
+fn parse_review_lock_hours(hours: i32) -> Result<Option<i32>> {
+    match hours {
+        0 => Ok(None),
+        1..=MAX_REVIEW_LOCK_HOURS => Ok(Some(hours)),
+        _ => Err(actix_web::error::ErrorBadRequest(format!(
+            "Review lock hours must be between 1 and {MAX_REVIEW_LOCK_HOURS}, or 0 to disable. Got: {hours}"
+        ))),
+    }
+}
+
+fn parse_inactivity_nudge_days(days: i32) -> Result<Option<i32>> {
+    match days {
+        0 => Ok(None),
+        1..=60 => Ok(Some(days)),
+        _ => Err(actix_web::error::ErrorBadRequest(
+            format!("Inactivity nudge days must be between 1 and 60, or 0 to disable. Got: {days}")
+        )),
+    }
 }
 
 #[derive(Serialize)]
@@ -75,7 +173,10 @@ pub struct AdminSeasonResponse {
     pub auto_evaluation_enabled: Option<bool>,
     pub game_duration_seconds: i64,
     pub games_per_matchup: Option<i32>,
+    pub inactivity_nudge_days: Option<i32>,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule_fairness: Option<ScheduleFairness>, // Only on single-season responses
 }
 
 // GET /admin/leagues - Get all leagues
@@ -89,6 +190,14 @@ pub async fn get_leagues(
             l.description,
             l.max_teams,
             l.created_at,
+            l.commentary_enabled,
+            l.commentary_locale,
+            l.commentary_milestones,
+            l.schedule_balance_home_away,
+            l.schedule_spread_strong_teams,
+            l.timezone,
+            l.comeback_multiplier,
+            l.review_lock_hours,
             COUNT(DISTINCT t.id) as current_team_count
         FROM leagues l
         LEFT JOIN teams t ON l.id = t.league_id
@@ -111,6 +220,14 @@ pub async fn get_leagues(
             max_teams: row.get("max_teams"),
             current_team_count: row.get::<i64, _>("current_team_count"),
             created_at: row.get("created_at"),
+            commentary_enabled: row.get("commentary_enabled"),
+            commentary_locale: row.get("commentary_locale"),
+            commentary_milestones: row.get("commentary_milestones"),
+            schedule_balance_home_away: row.get("schedule_balance_home_away"),
+            schedule_spread_strong_teams: row.get("schedule_spread_strong_teams"),
+            timezone: row.get("timezone"),
+            comeback_multiplier: row.get("comeback_multiplier"),
+            review_lock_hours: row.get("review_lock_hours"),
         })
         .collect();
 
@@ -137,6 +254,14 @@ pub async fn get_league_by_id(
             l.description,
             l.max_teams,
             l.created_at,
+            l.commentary_enabled,
+            l.commentary_locale,
+            l.commentary_milestones,
+            l.schedule_balance_home_away,
+            l.schedule_spread_strong_teams,
+            l.timezone,
+            l.comeback_multiplier,
+            l.review_lock_hours,
             ls.start_date as season_start_date,
             ls.end_date as season_end_date,
             COUNT(DISTINCT t.id) as current_team_count
@@ -162,6 +287,14 @@ pub async fn get_league_by_id(
             max_teams: row.get("max_teams"),
             current_team_count: row.get::<i64, _>("current_team_count"),
             created_at: row.get("created_at"),
+            commentary_enabled: row.get("commentary_enabled"),
+            commentary_locale: row.get("commentary_locale"),
+            commentary_milestones: row.get("commentary_milestones"),
+            schedule_balance_home_away: row.get("schedule_balance_home_away"),
+            schedule_spread_strong_teams: row.get("schedule_spread_strong_teams"),
+            timezone: row.get("timezone"),
+            comeback_multiplier: row.get("comeback_multiplier"),
+            review_lock_hours: row.get("review_lock_hours"),
         };
 
         let response = ApiResponse {
@@ -190,19 +323,22 @@ pub async fn create_league(
         })));
     }
 
+    let timezone = body.timezone.as_deref().map(parse_league_timezone).transpose()?.unwrap_or("UTC");
+
     let league_id = Uuid::new_v4();
     let now = chrono::Utc::now();
 
     // Create league only (seasons will be managed separately)
     let league_result = sqlx::query!(
         r#"
-        INSERT INTO leagues (id, name, description, max_teams, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO leagues (id, name, description, max_teams, timezone, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         "#,
         league_id,
         body.name,
         body.description,
         body.max_teams,
+        timezone,
         now,
         now
     )
@@ -218,6 +354,14 @@ pub async fn create_league(
                 max_teams: body.max_teams,
                 current_team_count: 0,
                 created_at: now,
+                commentary_enabled: true,
+                commentary_locale: commentary::DEFAULT_LOCALE.to_string(),
+                commentary_milestones: CommentaryMilestone::ALL.iter().map(|m| m.as_str().to_string()).collect(),
+                schedule_balance_home_away: true,
+                schedule_spread_strong_teams: true,
+                timezone: timezone.to_string(),
+                comeback_multiplier: None,
+                review_lock_hours: None,
             };
 
             let response = ApiResponse {
@@ -245,12 +389,28 @@ pub async fn update_league(
 ) -> Result<HttpResponse> {
     let league_id = path.into_inner();
 
-    if body.name.is_none() && body.season_start_date.is_none() && body.season_end_date.is_none() {
+    if body.name.is_none() && body.season_start_date.is_none() && body.season_end_date.is_none()
+        && body.commentary_enabled.is_none() && body.commentary_locale.is_none() && body.commentary_milestones.is_none()
+        && body.max_teams.is_none() && body.schedule_balance_home_away.is_none()
+        && body.schedule_spread_strong_teams.is_none() && body.timezone.is_none()
+        && body.comeback_multiplier.is_none() && body.review_lock_hours.is_none() {
         return Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "error": "No fields to update"
         })));
     }
 
+    if body.max_teams.is_some_and(|max_teams| max_teams <= 0) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_teams must be greater than 0"
+        })));
+    }
+
+    let commentary_locale = body.commentary_locale.as_deref().map(parse_commentary_locale).transpose()?;
+    let commentary_milestones = body.commentary_milestones.as_deref().map(parse_commentary_milestones).transpose()?;
+    let timezone = body.timezone.as_deref().map(parse_league_timezone).transpose()?;
+    let comeback_multiplier = body.comeback_multiplier.map(parse_comeback_multiplier).transpose()?;
+    let review_lock_hours = body.review_lock_hours.map(parse_review_lock_hours).transpose()?;
+
     let mut tx = pool.begin().await.map_err(|e| {
         eprintln!("Database error starting transaction: {e}");
         actix_web::error::ErrorInternalServerError("Database error")
@@ -267,6 +427,51 @@ pub async fn update_league(
         league_query_builder.push_bind(name);
     }
 
+    if let Some(enabled) = body.commentary_enabled {
+        league_query_builder.push(", commentary_enabled = ");
+        league_query_builder.push_bind(enabled);
+    }
+
+    if let Some(locale) = commentary_locale {
+        league_query_builder.push(", commentary_locale = ");
+        league_query_builder.push_bind(locale);
+    }
+
+    if let Some(milestones) = commentary_milestones {
+        league_query_builder.push(", commentary_milestones = ");
+        league_query_builder.push_bind(milestones);
+    }
+
+    if let Some(max_teams) = body.max_teams {
+        league_query_builder.push(", max_teams = ");
+        league_query_builder.push_bind(max_teams);
+    }
+
+    if let Some(balance_home_away) = body.schedule_balance_home_away {
+        league_query_builder.push(", schedule_balance_home_away = ");
+        league_query_builder.push_bind(balance_home_away);
+    }
+
+    if let Some(spread_strong_teams) = body.schedule_spread_strong_teams {
+        league_query_builder.push(", schedule_spread_strong_teams = ");
+        league_query_builder.push_bind(spread_strong_teams);
+    }
+
+    if let Some(timezone) = timezone {
+        league_query_builder.push(", timezone = ");
+        league_query_builder.push_bind(timezone);
+    }
+
+    if let Some(multiplier) = comeback_multiplier {
+        league_query_builder.push(", comeback_multiplier = ");
+        league_query_builder.push_bind(multiplier);
+    }
+
+    if let Some(hours) = review_lock_hours {
+        league_query_builder.push(", review_lock_hours = ");
+        league_query_builder.push_bind(hours);
+    }
+
     league_query_builder.push(" WHERE id = ");
     league_query_builder.push_bind(league_id);
 
@@ -316,6 +521,15 @@ pub async fn update_league(
         }
     }
 
+    if body.max_teams.is_some() {
+        league_waitlist::promote_waitlisted_teams(&mut tx, league_id)
+            .await
+            .map_err(|e| {
+                eprintln!("Database error promoting waitlisted teams: {e}");
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
+    }
+
     tx.commit().await.map_err(|e| {
         eprintln!("Database error committing transaction: {e}");
         actix_web::error::ErrorInternalServerError("Database error")
@@ -409,22 +623,78 @@ pub async fn assign_team_to_league(
         }
     }
 
+    let mut tx = pool.begin().await.map_err(|e| {
+        eprintln!("Database error starting transaction: {e}");
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    // Full leagues put the team on their waitlist instead
+    let open_slots = league_waitlist::lock_open_slots(&mut tx, league_id)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error checking league capacity: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .unwrap_or(0);
+
+    if open_slots == 0 {
+        let position = league_waitlist::add_to_waitlist(&mut tx, league_id, team_id)
+            .await
+            .map_err(|e| {
+                eprintln!("Database error adding team to waitlist: {e}");
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
+
+        let Some(position) = position else {
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Team is already on a league waitlist"
+            })));
+        };
+
+        tx.commit().await.map_err(|e| {
+            eprintln!("Database error committing transaction: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+        let response = ApiResponse {
+            data: serde_json::json!({
+                "league_id": league_id,
+                "team_id": team_id,
+                "waitlisted": true,
+                "waitlist_position": position
+            }),
+            success: true,
+            message: Some("League is full, team added to the waitlist".to_string()),
+        };
+        return Ok(HttpResponse::Accepted().json(response));
+    }
+
     // Update team to assign it to this league
     let result = sqlx::query!(
         "UPDATE teams SET league_id = $1, updated_at = NOW() WHERE id = $2",
         league_id,
         team_id
     )
-    .execute(pool.get_ref())
+    .execute(&mut *tx)
     .await;
 
+    let result = match result {
+        Ok(_) => league_waitlist::remove_team_from_waitlists(&mut tx, team_id).await,
+        Err(e) => Err(e),
+    };
+
     match result {
         Ok(_) => {
+            tx.commit().await.map_err(|e| {
+                eprintln!("Database error committing transaction: {e}");
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
 
             let response = ApiResponse {
                 data: serde_json::json!({
                     "league_id": league_id,
                     "team_id": team_id,
+                    "waitlisted": false,
                     "message": "Team assigned to league successfully. Team will be added to seasons when they are created/activated."
                 }),
                 success: true,
@@ -517,6 +787,14 @@ pub async fn remove_team_from_league(
     total_rows_affected += team_result.rows_affected();
 
     if total_rows_affected > 0 {
+        // The freed slot goes to the first team on the waitlist
+        let promoted_team_ids = league_waitlist::promote_waitlisted_teams(&mut tx, league_id)
+            .await
+            .map_err(|e| {
+                eprintln!("Database error promoting waitlisted teams: {e}");
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
+
         tx.commit().await.map_err(|e| {
             eprintln!("Database error committing transaction: {e}");
             actix_web::error::ErrorInternalServerError("Database error")
@@ -525,7 +803,8 @@ pub async fn remove_team_from_league(
         let response = ApiResponse {
             data: serde_json::json!({
                 "league_id": league_id,
-                "team_id": team_id
+                "team_id": team_id,
+                "promoted_team_ids": promoted_team_ids
             }),
             success: true,
             message: Some("Team removed from league successfully".to_string()),
@@ -621,12 +900,13 @@ pub async fn get_league_seasons(
             COUNT(DISTINCT lt.team_id) as total_teams,
             COUNT(DISTINCT lg.id) as games_count,
             ls.game_duration_seconds,
-            ls.games_per_matchup
+            ls.games_per_matchup,
+            ls.inactivity_nudge_days
         FROM league_seasons ls
         LEFT JOIN league_teams lt ON ls.id = lt.season_id
         LEFT JOIN games lg ON ls.id = lg.season_id
         WHERE ls.league_id = $1
-        GROUP BY ls.id, ls.league_id, ls.name, ls.start_date, ls.end_date, ls.evaluation_cron, ls.evaluation_timezone, ls.auto_evaluation_enabled, ls.created_at, ls.game_duration_seconds, ls.games_per_matchup
+        GROUP BY ls.id, ls.league_id, ls.name, ls.start_date, ls.end_date, ls.evaluation_cron, ls.evaluation_timezone, ls.auto_evaluation_enabled, ls.created_at, ls.game_duration_seconds, ls.games_per_matchup, ls.inactivity_nudge_days
         ORDER BY ls.created_at DESC
         "#,
         league_id
@@ -653,6 +933,8 @@ pub async fn get_league_seasons(
             created_at: row.created_at,
             game_duration_seconds: row.game_duration_seconds,
             games_per_matchup: row.games_per_matchup,
+            inactivity_nudge_days: row.inactivity_nudge_days,
+            schedule_fairness: None,
         })
         .collect();
 
@@ -713,7 +995,17 @@ pub async fn create_league_season(
     // For double round-robin (games_per_matchup = 2): each team plays every other team twice (home & away)
     // Calculate end date: N/2 games per week, so total weeks = games_per_matchup * (N-1)
     
-    let games_per_matchup = body.games_per_matchup.unwrap_or(1); // Default to single round-robin
+    // Settings the admin leaves out come from the default organization
+    let org_defaults = find_organization(pool.get_ref(), None)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error loading organization defaults: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    let games_per_matchup = body.games_per_matchup
+        .or(org_defaults.as_ref().map(|org| org.default_games_per_matchup))
+        .unwrap_or(1); // Default to single round-robin
     
     // Validate games_per_matchup
     if !(1..=2).contains(&games_per_matchup) {
@@ -755,7 +1047,9 @@ pub async fn create_league_season(
     
     let evaluation_timezone = body.evaluation_timezone.as_deref().unwrap_or("UTC");
     let auto_evaluation_enabled = body.auto_evaluation_enabled.unwrap_or(true);
-    let game_duration_seconds = body.game_duration_seconds.unwrap_or(518400); // Default: 6 days = 518400 seconds
+    let game_duration_seconds = body.game_duration_seconds
+        .or(org_defaults.as_ref().map(|org| org.default_game_duration_seconds))
+        .unwrap_or(518400); // Default: 6 days = 518400 seconds
     
     // Use provided evaluation_cron or default to every minute
     let evaluation_cron = body.evaluation_cron.as_deref().unwrap_or("0 * * * * *");
@@ -767,10 +1061,16 @@ pub async fn create_league_season(
         ));
     }
 
+    let inactivity_nudge_days = parse_inactivity_nudge_days(
+        body.inactivity_nudge_days
+            .or(org_defaults.as_ref().map(|org| org.default_inactivity_nudge_days))
+            .unwrap_or(DEFAULT_INACTIVITY_NUDGE_DAYS)
+    )?;
+
     let result = sqlx::query!(
         r#"
-        INSERT INTO league_seasons (id, league_id, name, start_date, end_date, evaluation_cron, evaluation_timezone, auto_evaluation_enabled, game_duration_seconds, games_per_matchup, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        INSERT INTO league_seasons (id, league_id, name, start_date, end_date, evaluation_cron, evaluation_timezone, auto_evaluation_enabled, game_duration_seconds, games_per_matchup, inactivity_nudge_days, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         "#,
         season_id,
         league_id,
@@ -782,6 +1082,7 @@ pub async fn create_league_season(
         auto_evaluation_enabled,
         game_duration_seconds,
         games_per_matchup,
+        inactivity_nudge_days,
         now,
         now
     )
@@ -846,6 +1147,7 @@ pub async fn create_league_season(
             .await;
 
             let mut games_created = 0;
+            let mut schedule_fairness = None;
             if let Ok(teams) = team_ids_result {
                 let team_ids: Vec<Uuid> = teams.into_iter().map(|t| t.team_id).collect();
                 
@@ -853,8 +1155,9 @@ pub async fn create_league_season(
                     let schedule_service = crate::league::schedule::ScheduleService::new(pool.get_ref().clone());
                     
                     match schedule_service.generate_schedule(season_id, &team_ids, body.start_date).await {
-                        Ok(created) => {
+                        Ok((created, fairness)) => {
                             games_created = created;
+                            schedule_fairness = Some(fairness);
                             tracing::info!("Automatically generated {} games for new season {}", created, season_id);
                             
                             // Update season end date based on the latest game end time
@@ -897,11 +1200,14 @@ pub async fn create_league_season(
                 tracing::info!("🕐 Scheduling automatic game evaluation for season '{}'", body.name);
                 tracing::info!("Using scheduler frequency: {} (game duration: {} seconds)", evaluation_cron, game_duration_seconds);
                 
-                match scheduler.schedule_season_with_frequency(
-                    season_id,
-                    body.name.clone(),
-                    evaluation_cron,
-                ).await {
+                // Seasons without their own cron follow the configured game cycle schedule
+                let scheduled = match body.evaluation_cron.as_deref() {
+                    Some(evaluation_cron) => {
+                        scheduler.schedule_season_with_frequency(season_id, body.name.clone(), evaluation_cron).await
+                    }
+                    None => scheduler.schedule_season(season_id, body.name.clone()).await,
+                };
+                match scheduled {
                     Ok(_) => {
                         tracing::info!("✅ Successfully scheduled evaluation for season '{}'", body.name);
                     }
@@ -925,6 +1231,8 @@ pub async fn create_league_season(
                 created_at: now,
                 game_duration_seconds,
                 games_per_matchup: Some(games_per_matchup),
+                inactivity_nudge_days,
+                schedule_fairness,
             };
 
             let response = ApiResponse {
@@ -966,12 +1274,13 @@ pub async fn get_league_season_by_id(
             COUNT(DISTINCT lt.team_id) as total_teams,
             COUNT(DISTINCT lg.id) as games_count,
             ls.game_duration_seconds,
-            ls.games_per_matchup
+            ls.games_per_matchup,
+            ls.inactivity_nudge_days
         FROM league_seasons ls
         LEFT JOIN league_teams lt ON ls.id = lt.season_id
         LEFT JOIN games lg ON ls.id = lg.season_id
         WHERE ls.league_id = $1 AND ls.id = $2
-        GROUP BY ls.id, ls.league_id, ls.name, ls.start_date, ls.end_date, ls.evaluation_cron, ls.evaluation_timezone, ls.auto_evaluation_enabled, ls.created_at, ls.game_duration_seconds, ls.games_per_matchup
+        GROUP BY ls.id, ls.league_id, ls.name, ls.start_date, ls.end_date, ls.evaluation_cron, ls.evaluation_timezone, ls.auto_evaluation_enabled, ls.created_at, ls.game_duration_seconds, ls.games_per_matchup, ls.inactivity_nudge_days
         "#,
         league_id,
         season_id
@@ -984,6 +1293,18 @@ pub async fn get_league_season_by_id(
     })?;
 
     if let Some(row) = row {
+        let schedule_fairness = sqlx::query_scalar!(
+            "SELECT fairness FROM season_schedule_reports WHERE season_id = $1",
+            season_id
+        )
+        .fetch_optional(pool.get_ref())
+        .await
+        .map_err(|e| {
+            eprintln!("Database error getting schedule report: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .and_then(|fairness| serde_json::from_value::<ScheduleFairness>(fairness).ok());
+
         let season = AdminSeasonResponse {
             id: row.id,
             league_id: row.league_id,
@@ -997,6 +1318,8 @@ pub async fn get_league_season_by_id(
             created_at: row.created_at,
             game_duration_seconds: row.game_duration_seconds,
             games_per_matchup: row.games_per_matchup,
+            inactivity_nudge_days: row.inactivity_nudge_days,
+            schedule_fairness,
         };
 
         let response = ApiResponse {
@@ -1021,7 +1344,7 @@ pub async fn update_league_season(
 ) -> Result<HttpResponse> {
     let (league_id, season_id) = path.into_inner();
 
-    if body.name.is_none() && body.start_date.is_none() {
+    if body.name.is_none() && body.start_date.is_none() && body.inactivity_nudge_days.is_none() {
         return Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "error": "No fields to update"
         })));
@@ -1043,6 +1366,11 @@ pub async fn update_league_season(
         query_builder.push_bind(start_date);
     }
 
+    if let Some(days) = body.inactivity_nudge_days {
+        query_builder.push(", inactivity_nudge_days = ");
+        query_builder.push_bind(parse_inactivity_nudge_days(days)?);
+    }
+
     query_builder.push(" WHERE league_id = ");
     query_builder.push_bind(league_id);
     query_builder.push(" AND id = ");
@@ -0,0 +1,78 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::league::{LeagueRulesPage, SetLeagueRulesRequest};
+use crate::services::LeagueRulesService;
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("League rules database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// GET /admin/leagues/{id}/rules - The league's rules page as players see it
+pub async fn get_league_rules(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    let Some(rules) = LeagueRulesService::new(pool.get_ref().clone())
+        .page(league_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<LeagueRulesPage>::error("League not found")));
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("League rules retrieved successfully", rules)))
+}
+
+/// PUT /admin/leagues/{id}/rules - Replace the league's scoring summary, forfeit policy and transfer windows
+pub async fn set_league_rules(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetLeagueRulesRequest>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueRulesPage>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueRulesPage>::error(message)));
+    }
+
+    let Some(rules) = LeagueRulesService::new(pool.get_ref().clone())
+        .set_rules(league_id, &body, admin_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<LeagueRulesPage>::error("League not found")));
+    };
+
+    info!("Admin {} updated the rules of league {}", admin_id, league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("League rules updated successfully", rules)))
+}
+
+/// DELETE /admin/leagues/{id}/rules - Remove the league's written rules and transfer windows
+pub async fn remove_league_rules(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    if !LeagueRulesService::new(pool.get_ref().clone())
+        .remove_rules(league_id)
+        .await
+        .map_err(database_error)?
+    {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("League has no written rules")));
+    }
+
+    info!("Removed the written rules of league {}", league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("League rules removed successfully")))
+}
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::league::{ChatProvider, LeagueChatWebhook, LeagueChatWebhookSettings, SetLeagueChatWebhookRequest};
+use crate::services::LeagueWebhookService;
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("League webhook database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// GET /admin/leagues/{id}/webhooks - Slack/Teams webhooks of a league with their failing result cards
+pub async fn get_league_webhooks(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+    let service = LeagueWebhookService::new(pool.get_ref().clone());
+
+    let webhooks = service.webhooks(league_id).await.map_err(database_error)?;
+    let failed_deliveries = service.failed_deliveries(league_id).await.map_err(database_error)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        "League webhooks retrieved successfully",
+        LeagueChatWebhookSettings { webhooks, failed_deliveries },
+    )))
+}
+
+/// PUT /admin/leagues/{id}/webhooks - Set the league's Slack or Teams webhook
+pub async fn set_league_webhook(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetLeagueChatWebhookRequest>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueChatWebhook>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueChatWebhook>::error(message)));
+    }
+
+    let Some(webhook) = LeagueWebhookService::new(pool.get_ref().clone())
+        .set_webhook(league_id, &body, admin_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<LeagueChatWebhook>::error("League not found")));
+    };
+
+    info!("Admin {} set the {:?} webhook of league {}", admin_id, webhook.provider, league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("League webhook updated successfully", webhook)))
+}
+
+/// DELETE /admin/leagues/{id}/webhooks/{provider} - Stop posting results to a provider
+pub async fn remove_league_webhook(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, ChatProvider)>,
+) -> Result<HttpResponse> {
+    let (league_id, provider) = path.into_inner();
+
+    if !LeagueWebhookService::new(pool.get_ref().clone())
+        .remove_webhook(league_id, provider)
+        .await
+        .map_err(database_error)?
+    {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("League has no such webhook")));
+    }
+
+    info!("Removed the {:?} webhook of league {}", provider, league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("League webhook removed successfully")))
+}
+
+/// POST /admin/leagues/{id}/webhook-deliveries/{delivery_id}/retry - Post a failed result card again now
+pub async fn retry_webhook_delivery(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (league_id, delivery_id) = path.into_inner();
+
+    let Some(delivered) = LeagueWebhookService::new(pool.get_ref().clone())
+        .retry(league_id, delivery_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("No undelivered result card found")));
+    };
+
+    if !delivered {
+        return Ok(HttpResponse::BadGateway().json(ApiResponse::<()>::error(
+            "Result card could not be posted, it will be retried automatically",
+        )));
+    }
+    info!("Posted result card {} of league {} on retry", delivery_id, league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Result card posted successfully")))
+}
@@ -0,0 +1,76 @@
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{info, warn, error};
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::services::{MediaModerationService, MinIOService};
+use crate::services::media_moderation_service::MediaTakedown;
+use crate::services::social_events;
+
+#[derive(Debug, Deserialize)]
+pub struct MediaTakedownRequest {
+    pub object_key: String,
+    pub reason: String,
+    /// Also reject future uploads that look like this image (default: true)
+    pub block_similar: Option<bool>,
+}
+
+/// POST /admin/media/takedown - Remove media from all posts and workouts, delete it from
+/// storage and tell connected clients to drop it from their caches
+pub async fn take_down_media(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    minio_service: web::Data<MinIOService>,
+    redis_client: Option<web::Data<Arc<redis::Client>>>,
+    body: web::Json<MediaTakedownRequest>,
+) -> Result<HttpResponse> {
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<MediaTakedown>::error("Invalid user ID")));
+    };
+
+    let object_key = body.object_key.trim();
+    if object_key.is_empty() || object_key.contains("..") || object_key.starts_with('/') {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<MediaTakedown>::error("Invalid object key")));
+    }
+    let reason = body.reason.trim();
+    if reason.is_empty() || reason.len() > 500 {
+        return Ok(HttpResponse::BadRequest().json(
+            ApiResponse::<MediaTakedown>::error("Reason must be 1-500 characters")
+        ));
+    }
+
+    let file_url = minio_service.generate_file_url(object_key);
+    let service = MediaModerationService::new(pool.get_ref().clone());
+    let takedown = service
+        .take_down(object_key, &file_url, reason, admin_id, body.block_similar.unwrap_or(true))
+        .await
+        .map_err(|e| {
+            error!("Failed to take down media {}: {}", object_key, e);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    // The references are gone either way; a leftover object is only reachable by its key
+    if let Err(e) = minio_service.delete_file(object_key).await {
+        warn!("Failed to delete taken down media {} from storage: {}", object_key, e);
+    }
+
+    if let Some(redis_client) = redis_client {
+        if let Err(e) = social_events::broadcast_media_taken_down(
+            &redis_client,
+            takedown.file_url.clone(),
+            takedown.post_ids.clone(),
+            takedown.workout_ids.clone(),
+        ).await {
+            warn!("Failed to broadcast takedown of {}: {}", object_key, e);
+        }
+    }
+
+    info!(
+        "Media {} taken down by {}: {} posts, {} workouts updated",
+        object_key, claims.username, takedown.post_ids.len(), takedown.workout_ids.len()
+    );
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Media taken down successfully", takedown)))
+}
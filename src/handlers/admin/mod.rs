@@ -2,4 +2,30 @@ pub mod user_handler;
 pub mod team_handler;
 pub mod league_handler;
 pub mod game_management_handler;
-pub mod workout_handler;
\ No newline at end of file
+pub mod workout_handler;
+pub mod backup_handler;
+pub mod consistency_handler;
+pub mod export_handler;
+pub mod activity_handler;
+pub mod media_handler;
+pub mod broadcast_handler;
+pub mod scheduler_handler;
+pub mod organization_handler;
+pub mod organization_sso_handler;
+pub mod waitlist_handler;
+pub mod research_handler;
+pub mod quest_handler;
+pub mod suspension_handler;
+pub mod fixture_flavor_handler;
+pub mod league_webhook_handler;
+pub mod league_rules_handler;
+#[cfg(feature = "sandbox")]
+pub mod sandbox_handler;
+pub mod telemetry_handler;
+pub mod api_key_handler;
+pub mod security_handler;
+pub mod invite_handler;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection_handler;
+pub mod impersonation_handler;
+pub mod cup_handler;
@@ -0,0 +1,213 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::{info, error};
+
+use crate::db::organizations::{get_organization, list_organizations};
+use crate::league::historical_import::plan_import;
+use crate::models::common::ApiResponse;
+use crate::models::organization::{
+    CreateOrganizationRequest, HistoricalImportReport, HistoricalImportRequest, Organization, UpdateOrganizationRequest,
+};
+use crate::services::HistoricalImportService;
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505"))
+}
+
+/// GET /admin/organizations - List organizations with their branding and defaults
+pub async fn get_organizations(
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let organizations = list_organizations(pool.get_ref()).await.map_err(|e| {
+        error!("Failed to fetch organizations: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Organizations retrieved successfully", organizations)))
+}
+
+/// POST /admin/organizations - Add an organization for a white-labeled app
+pub async fn create_organization(
+    pool: web::Data<PgPool>,
+    body: web::Json<CreateOrganizationRequest>,
+) -> Result<HttpResponse> {
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<Organization>::error(message)));
+    }
+
+    let result = async {
+        let mut tx = pool.begin().await?;
+        if body.is_default == Some(true) {
+            sqlx::query!("UPDATE organizations SET is_default = false WHERE is_default")
+                .execute(&mut *tx)
+                .await?;
+        }
+        let organization = sqlx::query_as!(
+            Organization,
+            r#"
+            INSERT INTO organizations (
+                slug, name, primary_color, secondary_color, logo_key, locale,
+                default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days, is_default
+            )
+            VALUES ($1, $2, COALESCE($3, '#1E88E5'), COALESCE($4, '#43A047'), $5, COALESCE($6, 'en'),
+                    COALESCE($7, 518400::bigint), COALESCE($8, 1), COALESCE($9, 3), $10)
+            RETURNING id, slug, name, primary_color, secondary_color, logo_key, locale,
+                      default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days,
+                      is_default, created_at, updated_at
+            "#,
+            body.slug,
+            body.name.trim(),
+            body.primary_color.as_deref(),
+            body.secondary_color.as_deref(),
+            body.logo_key.as_deref(),
+            body.locale.as_deref(),
+            body.default_game_duration_seconds,
+            body.default_games_per_matchup,
+            body.default_inactivity_nudge_days,
+            body.is_default.unwrap_or(false)
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(organization)
+    }
+    .await;
+
+    match result {
+        Ok(organization) => {
+            info!("Created organization {} ({})", organization.slug, organization.id);
+            Ok(HttpResponse::Created().json(ApiResponse::success("Organization created successfully", organization)))
+        }
+        Err(e) if is_unique_violation(&e) => Ok(HttpResponse::Conflict().json(
+            ApiResponse::<Organization>::error("An organization with this slug already exists")
+        )),
+        Err(e) => {
+            error!("Failed to create organization: {}", e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// PATCH /admin/organizations/{id} - Update an organization's branding, locale or league defaults
+pub async fn update_organization(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateOrganizationRequest>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<Organization>::error(message)));
+    }
+
+    let result = async {
+        let mut tx = pool.begin().await?;
+        if body.is_default == Some(true) {
+            sqlx::query!(
+                "UPDATE organizations SET is_default = false WHERE is_default AND id <> $1",
+                organization_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        let organization = sqlx::query_as!(
+            Organization,
+            r#"
+            UPDATE organizations
+            SET name = COALESCE($2, name),
+                primary_color = COALESCE($3, primary_color),
+                secondary_color = COALESCE($4, secondary_color),
+                logo_key = COALESCE($5, logo_key),
+                locale = COALESCE($6, locale),
+                default_game_duration_seconds = COALESCE($7, default_game_duration_seconds),
+                default_games_per_matchup = COALESCE($8, default_games_per_matchup),
+                default_inactivity_nudge_days = COALESCE($9, default_inactivity_nudge_days),
+                is_default = COALESCE($10, is_default)
+            WHERE id = $1
+            RETURNING id, slug, name, primary_color, secondary_color, logo_key, locale,
+                      default_game_duration_seconds, default_games_per_matchup, default_inactivity_nudge_days,
+                      is_default, created_at, updated_at
+            "#,
+            organization_id,
+            body.name.as_deref().map(str::trim),
+            body.primary_color.as_deref(),
+            body.secondary_color.as_deref(),
+            body.logo_key.as_deref(),
+            body.locale.as_deref(),
+            body.default_game_duration_seconds,
+            body.default_games_per_matchup,
+            body.default_inactivity_nudge_days,
+            body.is_default
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        // Keep the previous default if the organization does not exist
+        if organization.is_some() {
+            tx.commit().await?;
+        }
+        Ok::<_, sqlx::Error>(organization)
+    }
+    .await;
+
+    match result {
+        Ok(Some(organization)) => {
+            info!("Updated organization {} ({})", organization.slug, organization.id);
+            Ok(HttpResponse::Ok().json(ApiResponse::success("Organization updated successfully", organization)))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<Organization>::error("Organization not found"))),
+        Err(e) => {
+            error!("Failed to update organization {}: {}", organization_id, e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// An error response that still carries the report, so admins can fix every row at once
+fn rejected_import(message: &str, report: HistoricalImportReport) -> HttpResponse {
+    HttpResponse::UnprocessableEntity().json(ApiResponse { data: Some(report), ..ApiResponse::error(message) })
+}
+
+/// POST /admin/organizations/{id}/import - Bootstrap a league from the CSV exports of a spreadsheet-run one.
+/// Every problem found is reported and nothing is kept unless all rows import.
+pub async fn import_historical_data(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<HistoricalImportRequest>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+    let dry_run = body.dry_run.unwrap_or(false);
+
+    let organization = get_organization(pool.get_ref(), organization_id).await.map_err(|e| {
+        error!("Failed to fetch organization {}: {}", organization_id, e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+    let Some(organization) = organization else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<HistoricalImportReport>::error("Organization not found")));
+    };
+
+    let plan = match plan_import(&body) {
+        Ok(plan) => plan,
+        Err(issues) => {
+            let report = HistoricalImportReport { dry_run, issues, ..Default::default() };
+            return Ok(rejected_import("Import has invalid rows", report));
+        }
+    };
+
+    let report = HistoricalImportService::new(pool.get_ref().clone())
+        .import(&organization, &body.league_name, body.season_name.as_deref(), &plan, dry_run)
+        .await
+        .map_err(|e| {
+            error!("Failed to import historical data for organization {}: {}", organization_id, e);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    if !report.issues.is_empty() {
+        Ok(rejected_import("Import conflicts with existing data", report))
+    } else if dry_run {
+        Ok(HttpResponse::Ok().json(ApiResponse::success("Dry run passed, nothing was imported", report)))
+    } else {
+        info!("Imported historical data for organization {}", organization.slug);
+        Ok(HttpResponse::Created().json(ApiResponse::success("Historical data imported successfully", report)))
+    }
+}
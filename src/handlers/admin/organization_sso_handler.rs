@@ -0,0 +1,140 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::db::organizations::get_organization;
+use crate::models::common::ApiResponse;
+use crate::models::organization::{
+    ScimTokenResponse, SetSsoGroupMappingRequest, SetSsoProviderRequest, SsoGroupMapping, SsoProvider, SsoSettings,
+};
+use crate::services::OrganizationSsoService;
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("Organization SSO database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// GET /admin/organizations/{id}/sso - OIDC provider and group-to-team mappings of an organization
+pub async fn get_sso_settings(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+    if get_organization(pool.get_ref(), organization_id).await.map_err(database_error)?.is_none() {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<SsoSettings>::error("Organization not found")));
+    }
+
+    let settings = OrganizationSsoService::new(pool.get_ref().clone())
+        .settings(organization_id)
+        .await
+        .map_err(database_error)?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success("SSO settings retrieved successfully", settings)))
+}
+
+/// PUT /admin/organizations/{id}/sso - Set the organization's OIDC provider
+pub async fn set_sso_provider(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetSsoProviderRequest>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<SsoProvider>::error(message)));
+    }
+    if get_organization(pool.get_ref(), organization_id).await.map_err(database_error)?.is_none() {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<SsoProvider>::error("Organization not found")));
+    }
+
+    let provider = OrganizationSsoService::new(pool.get_ref().clone())
+        .set_provider(organization_id, &body)
+        .await
+        .map_err(database_error)?;
+
+    info!("Set the SSO provider of organization {} to {}", organization_id, provider.issuer_url);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("SSO provider updated successfully", provider)))
+}
+
+/// DELETE /admin/organizations/{id}/sso - Turn off single sign-on; users keep their accounts
+pub async fn remove_sso_provider(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+
+    if !OrganizationSsoService::new(pool.get_ref().clone())
+        .remove_provider(organization_id)
+        .await
+        .map_err(database_error)?
+    {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Organization has no SSO provider")));
+    }
+
+    info!("Removed the SSO provider of organization {}", organization_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("SSO provider removed successfully")))
+}
+
+/// PUT /admin/organizations/{id}/sso/groups - Map a directory group to the team its members join
+pub async fn set_sso_group_mapping(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<SetSsoGroupMappingRequest>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<SsoGroupMapping>::error(message)));
+    }
+    if get_organization(pool.get_ref(), organization_id).await.map_err(database_error)?.is_none() {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<SsoGroupMapping>::error("Organization not found")));
+    }
+
+    let Some(mapping) = OrganizationSsoService::new(pool.get_ref().clone())
+        .set_group_mapping(organization_id, &body)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<SsoGroupMapping>::error("Team not found")));
+    };
+
+    info!("Mapped group '{}' of organization {} to team {}", mapping.group_name, organization_id, mapping.team_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Group mapping updated successfully", mapping)))
+}
+
+/// DELETE /admin/organizations/{id}/sso/groups/{mapping_id} - Stop adding a group's members to its team
+pub async fn remove_sso_group_mapping(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (organization_id, mapping_id) = path.into_inner();
+
+    if !OrganizationSsoService::new(pool.get_ref().clone())
+        .remove_group_mapping(organization_id, mapping_id)
+        .await
+        .map_err(database_error)?
+    {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Group mapping not found")));
+    }
+
+    info!("Removed group mapping {} of organization {}", mapping_id, organization_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Group mapping removed successfully")))
+}
+
+/// POST /admin/organizations/{id}/sso/scim-token - Issue the token the company directory deactivates
+/// users with, replacing the previous one. It is only shown once.
+pub async fn rotate_scim_token(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let organization_id = path.into_inner();
+
+    let Some(token) = OrganizationSsoService::new(pool.get_ref().clone())
+        .rotate_scim_token(organization_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<ScimTokenResponse>::error("Organization has no SSO provider")));
+    };
+
+    info!("Issued a new SCIM token for organization {}", organization_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::success("SCIM token issued successfully", ScimTokenResponse { token })))
+}
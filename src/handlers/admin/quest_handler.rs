@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::quest::{CreateLeagueQuestRequest, LeagueQuest};
+use crate::services::LeagueQuestService;
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("League quest database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// POST /admin/leagues/{id}/quests - Start a quest the whole league works toward together
+pub async fn create_league_quest(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<CreateLeagueQuestRequest>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueQuest>::error("Invalid user ID")));
+    };
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<LeagueQuest>::error(message)));
+    }
+
+    let service = LeagueQuestService::new(pool.get_ref().clone());
+    if !service.league_exists(league_id).await.map_err(database_error)? {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<LeagueQuest>::error("League not found")));
+    }
+
+    let quest = service.create(league_id, admin_id, &body).await.map_err(database_error)?;
+    info!("Admin {} started quest {} ({}) in league {}", admin_id, quest.name, quest.id, league_id);
+
+    Ok(HttpResponse::Created().json(ApiResponse::success("League quest created successfully", quest)))
+}
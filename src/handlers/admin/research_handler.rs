@@ -0,0 +1,80 @@
+use actix_web::{web, HttpResponse, Result};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tracing::{error, info};
+
+use crate::db::research_datasets;
+use crate::models::common::ApiResponse;
+use crate::models::research::*;
+
+/// GET /admin/research/datasets - Anonymized aggregates for research partners.
+/// Users who opted out of research data sharing are excluded and rows describing fewer than k users are withheld.
+pub async fn get_research_dataset(
+    pool: web::Data<PgPool>,
+    query: web::Query<ResearchDatasetQuery>,
+) -> Result<HttpResponse> {
+    let k = query.k.unwrap_or(DEFAULT_K_ANONYMITY);
+    if k < MIN_K_ANONYMITY {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(format!(
+            "k must be at least {MIN_K_ANONYMITY}"
+        ))));
+    }
+
+    let weeks = query.weeks.unwrap_or(DEFAULT_ADHERENCE_WEEKS);
+    if !(1..=MAX_ADHERENCE_WEEKS).contains(&weeks) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(format!(
+            "weeks must be between 1 and {MAX_ADHERENCE_WEEKS}"
+        ))));
+    }
+
+    let to = query.to.unwrap_or_else(Utc::now).min(Utc::now());
+    let from = query.from.unwrap_or(to - Duration::days(180));
+    if from >= to {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("from must be before to")));
+    }
+
+    let response = match query.dataset {
+        ResearchDataset::ZoneMinutesByAgeBand => {
+            let rows = research_datasets::zone_minutes_by_age_band(pool.get_ref(), from, to).await;
+            rows.map(|rows| build_response(query.dataset, from, to, k, rows, |row| row.user_count))
+        }
+        ResearchDataset::AdherenceCurves => {
+            let rows = research_datasets::adherence_curves(pool.get_ref(), from, to, weeks).await;
+            rows.map(|rows| build_response(query.dataset, from, to, k, rows, |row| row.cohort_size))
+        }
+    };
+
+    match response {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            error!("Failed to build research dataset {:?}: {}", query.dataset, e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+fn build_response<T: Serialize>(
+    dataset: ResearchDataset,
+    from: chrono::DateTime<Utc>,
+    to: chrono::DateTime<Utc>,
+    k: i64,
+    rows: Vec<T>,
+    group_size: impl Fn(&T) -> i64,
+) -> HttpResponse {
+    let (rows, suppressed_rows) = apply_k_anonymity(rows, k, group_size);
+    info!("Research dataset {:?}: released {} rows, withheld {}", dataset, rows.len(), suppressed_rows);
+
+    HttpResponse::Ok().json(ApiResponse::success(
+        "Research dataset generated successfully",
+        ResearchDatasetResponse {
+            dataset,
+            from,
+            to,
+            k,
+            generated_at: Utc::now(),
+            rows,
+            suppressed_rows,
+        },
+    ))
+}
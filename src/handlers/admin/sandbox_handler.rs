@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::sandbox::{CreateSandboxRequest, Sandbox};
+use crate::services::SandboxService;
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("Sandbox database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// POST /admin/sandbox - Provision an isolated organization with users, a league and a live game
+pub async fn create_sandbox(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    body: web::Json<CreateSandboxRequest>,
+) -> Result<HttpResponse> {
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<Sandbox>::error(message)));
+    }
+
+    let sandbox = SandboxService::new(pool.get_ref().clone())
+        .provision(body.ttl(), claims.user_id())
+        .await
+        .map_err(database_error)?;
+    info!("Admin {} provisioned sandbox {}", claims.username, sandbox.id);
+
+    Ok(HttpResponse::Created().json(ApiResponse::success("Sandbox provisioned successfully", sandbox)))
+}
+
+/// DELETE /admin/sandbox/{id} - Tear a sandbox down before it expires
+pub async fn delete_sandbox(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let sandbox_id = path.into_inner();
+    let removed = SandboxService::new(pool.get_ref().clone())
+        .tear_down(sandbox_id)
+        .await
+        .map_err(database_error)?;
+
+    if removed {
+        Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Sandbox torn down successfully")))
+    } else {
+        Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Sandbox not found")))
+    }
+}
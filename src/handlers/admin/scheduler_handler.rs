@@ -0,0 +1,107 @@
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+use tracing::{info, error};
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::scheduled_job::{ScheduledJobConfig, UpdateJobScheduleRequest};
+use crate::services::SchedulerService;
+use crate::services::job_registry::{JobRegistry, RegisteredJobInfo};
+
+fn database_error(e: sqlx::Error) -> actix_web::Error {
+    error!("Scheduler database error: {}", e);
+    actix_web::error::ErrorInternalServerError("Database error")
+}
+
+/// GET /admin/scheduler/jobs - Registered jobs with their last and next run and success/failure counts
+pub async fn get_scheduler_jobs(
+    scheduler: web::Data<Arc<SchedulerService>>,
+) -> Result<HttpResponse> {
+    let jobs = scheduler.list_jobs().await;
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Scheduler jobs retrieved successfully", jobs)))
+}
+
+/// GET /admin/scheduler/jobs/{name} - A single registered job
+pub async fn get_scheduler_job(
+    scheduler: web::Data<Arc<SchedulerService>>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let name = path.into_inner();
+    match scheduler.list_jobs().await.into_iter().find(|job| job.name == name) {
+        Some(job) => Ok(HttpResponse::Ok().json(ApiResponse::success("Scheduler job retrieved successfully", job))),
+        None => Ok(HttpResponse::NotFound().json(ApiResponse::<RegisteredJobInfo>::error("Scheduler job not found"))),
+    }
+}
+
+/// POST /admin/scheduler/jobs/{name}/run - Run a job now, outside its schedule.
+/// The run happens in the background; its outcome shows up in the job's stats.
+pub async fn run_scheduler_job(
+    scheduler: web::Data<Arc<SchedulerService>>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let name = path.into_inner();
+    if !scheduler.trigger_job(&name) {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<RegisteredJobInfo>::error("Scheduler job not found")));
+    }
+
+    info!("Scheduler job '{}' triggered manually by {}", name, claims.username);
+    Ok(HttpResponse::Accepted().json(ApiResponse::<()>::success_message(format!("Job '{}' started", name))))
+}
+
+/// GET /admin/scheduler/schedules - Configured cron schedules of the background jobs
+pub async fn get_job_schedules(
+    scheduler: web::Data<Arc<SchedulerService>>,
+) -> Result<HttpResponse> {
+    let schedules = scheduler.job_schedules().await.map_err(database_error)?;
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Job schedules retrieved successfully", schedules)))
+}
+
+/// PUT /admin/scheduler/schedules/{name} - Change a job's cron schedule.
+/// Running jobs move to the new schedule right away.
+pub async fn update_job_schedule(
+    scheduler: web::Data<Arc<SchedulerService>>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<String>,
+    body: web::Json<UpdateJobScheduleRequest>,
+) -> Result<HttpResponse> {
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<ScheduledJobConfig>::error("Invalid user ID")));
+    };
+    let name = path.into_inner();
+    let cron_expression = body.cron_expression.trim();
+    if let Err(message) = JobRegistry::validate_schedule(cron_expression) {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<ScheduledJobConfig>::error(message)));
+    }
+
+    let Some(schedule) = scheduler
+        .update_job_schedule(&name, cron_expression, admin_id)
+        .await
+        .map_err(database_error)?
+    else {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<ScheduledJobConfig>::error("Job schedule not found")));
+    };
+    info!("Schedule of job '{}' set to '{}' by {}", name, cron_expression, claims.username);
+
+    if let Err(e) = scheduler.reload_job_schedules().await {
+        error!("Failed to reload job schedules: {}", e);
+        return Err(actix_web::error::ErrorInternalServerError("Failed to reload job schedules"));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Job schedule updated successfully", schedule)))
+}
+
+/// POST /admin/scheduler/schedules/reload - Apply the configured schedules, e.g. after
+/// editing the `scheduled_jobs` table directly
+pub async fn reload_job_schedules(
+    scheduler: web::Data<Arc<SchedulerService>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let summary = scheduler.reload_job_schedules().await.map_err(|e| {
+        error!("Failed to reload job schedules: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to reload job schedules")
+    })?;
+
+    info!("Job schedules reloaded by {}: {} jobs moved", claims.username, summary.rescheduled.len());
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Job schedules reloaded successfully", summary)))
+}
@@ -0,0 +1,69 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::db::login_activity::list_security_events;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::login_activity::SecurityEventQuery;
+use crate::services::LoginProtectionService;
+
+/// GET /admin/security-events - Lockouts, unlocks and suspicious logins, newest first,
+/// optionally only of ?user_id= or ?event_type=
+pub async fn get_security_events(
+    pool: web::Data<PgPool>,
+    query: web::Query<SecurityEventQuery>,
+) -> Result<HttpResponse> {
+    let event_type = match query.event_type() {
+        Ok(event_type) => event_type,
+        Err(message) => return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(message))),
+    };
+
+    match list_security_events(pool.get_ref(), query.user_id, event_type, query.limit()).await {
+        Ok(events) => Ok(HttpResponse::Ok().json(ApiResponse::success("Security events retrieved", events))),
+        Err(e) => {
+            error!("Failed to list security events: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")))
+        }
+    }
+}
+
+/// DELETE /admin/users/{id}/login-lockout - Let a user locked out by failed logins try again now
+pub async fn unlock_user_login(
+    pool: web::Data<PgPool>,
+    login_protection: web::Data<LoginProtectionService>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let user_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    match sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)", user_id)
+        .fetch_one(pool.get_ref())
+        .await
+    {
+        Ok(Some(true)) => {}
+        Ok(_) => return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("User not found"))),
+        Err(e) => {
+            error!("Failed to look up user {}: {}", user_id, e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error")));
+        }
+    }
+
+    match login_protection.unlock_account(user_id, admin_id).await {
+        Ok(was_locked) => {
+            info!("Admin {} unlocked logins of user {} (was locked: {})", admin_id, user_id, was_locked);
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                "Login lockout lifted",
+                serde_json::json!({ "user_id": user_id, "was_locked": was_locked }),
+            )))
+        }
+        Err(e) => {
+            error!("Failed to unlock logins of user {}: {}", user_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to lift login lockout")))
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::suspension::{CreateSuspensionRequest, LiftSuspensionRequest, UserSuspension};
+use crate::services::suspension_service::SuspensionError;
+use crate::services::SuspensionService;
+
+fn suspension_error_response(e: SuspensionError) -> HttpResponse {
+    match e {
+        e @ (SuspensionError::UserNotFound | SuspensionError::NotFound) => {
+            HttpResponse::NotFound().json(ApiResponse::<UserSuspension>::error(e.to_string()))
+        }
+        e @ (SuspensionError::AlreadySuspended | SuspensionError::NotActive) => {
+            HttpResponse::Conflict().json(ApiResponse::<UserSuspension>::error(e.to_string()))
+        }
+        SuspensionError::Database(e) => {
+            error!("Suspension database error: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<UserSuspension>::error("Database error"))
+        }
+    }
+}
+
+/// POST /admin/users/{id}/suspensions - Suspend a user for a while, or until lifted without a duration
+pub async fn suspend_user(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<CreateSuspensionRequest>,
+) -> Result<HttpResponse> {
+    let user_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<UserSuspension>::error("Invalid user ID")));
+    };
+    if admin_id == user_id {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<UserSuspension>::error("You cannot suspend yourself")));
+    }
+    if let Err(message) = body.validate() {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<UserSuspension>::error(message)));
+    }
+
+    match SuspensionService::new(pool.get_ref().clone()).suspend(user_id, admin_id, &body).await {
+        Ok(suspension) => {
+            info!("Admin {} suspended user {} until {:?}: {}", admin_id, user_id, suspension.ends_at, suspension.reason);
+            Ok(HttpResponse::Created().json(ApiResponse::success("User suspended", suspension)))
+        }
+        Err(e) => Ok(suspension_error_response(e)),
+    }
+}
+
+/// GET /admin/users/{id}/suspensions - Suspension history of a user
+pub async fn get_user_suspensions(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    match SuspensionService::new(pool.get_ref().clone()).list(path.into_inner()).await {
+        Ok(suspensions) => Ok(HttpResponse::Ok().json(ApiResponse::success("Suspensions retrieved", suspensions))),
+        Err(e) => Ok(suspension_error_response(e.into())),
+    }
+}
+
+/// POST /admin/suspensions/{id}/lift - End a suspension early
+pub async fn lift_suspension(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<Uuid>,
+    body: web::Json<LiftSuspensionRequest>,
+) -> Result<HttpResponse> {
+    let suspension_id = path.into_inner();
+    let Some(admin_id) = claims.user_id() else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<UserSuspension>::error("Invalid user ID")));
+    };
+
+    match SuspensionService::new(pool.get_ref().clone()).lift(suspension_id, admin_id, body.reason.as_deref()).await {
+        Ok(suspension) => {
+            info!("Admin {} lifted suspension {} of user {}", admin_id, suspension_id, suspension.user_id);
+            Ok(HttpResponse::Ok().json(ApiResponse::success("Suspension lifted", suspension)))
+        }
+        Err(e) => Ok(suspension_error_response(e)),
+    }
+}
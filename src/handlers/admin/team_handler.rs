@@ -1,11 +1,14 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Result};
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
+use crate::db::league_waitlist;
 use crate::handlers::admin::user_handler::{PaginatedResponse, PaginationInfo, ApiResponse};
 use crate::handlers::league::team_member_helper::{remove_member_and_return_to_pool, remove_from_player_pool};
+use crate::middleware::etag::{if_match_version, version_etag};
+use crate::services::BoosterService;
 
 #[derive(Serialize)]
 pub struct AdminTeamResponse {
@@ -18,6 +21,8 @@ pub struct AdminTeamResponse {
     pub created_at: DateTime<Utc>,
     pub owner_id: Uuid,
     pub league_id: Option<Uuid>,
+    /// Send back in If-Match when updating the team
+    pub version: i32,
 }
 
 #[derive(Serialize)]
@@ -91,6 +96,7 @@ pub async fn get_teams(
             t.team_color as color,
             t.created_at,
             t.user_id as owner_id,
+            t.version,
             COUNT(tm.user_id) as member_count,
             COALESCE(SUM(ua.stamina + ua.strength), 0.0) as total_power
         FROM teams t
@@ -119,7 +125,7 @@ pub async fn get_teams(
         }
     }
 
-    sql.push_str(" GROUP BY t.id, t.team_name, t.team_color, t.created_at, t.user_id");
+    sql.push_str(" GROUP BY t.id, t.team_name, t.team_color, t.created_at, t.user_id, t.version");
     sql.push_str(&format!(
         " ORDER BY t.created_at DESC LIMIT {limit} OFFSET {offset}"
     ));
@@ -154,6 +160,7 @@ pub async fn get_teams(
             created_at: row.get("created_at"),
             owner_id: row.get("owner_id"),
             league_id: None, // TODO: Add league association
+            version: row.get("version"),
         })
         .collect();
 
@@ -179,49 +186,22 @@ pub async fn get_team_by_id(
 ) -> Result<HttpResponse> {
     let team_id = path.into_inner();
 
-    let row = sqlx::query(r#"
-        SELECT 
-            t.id,
-            t.team_name as name,
-            t.team_color as color,
-            t.created_at,
-            t.user_id as owner_id,
-            COUNT(tm.user_id) as member_count,
-            COALESCE(SUM(ua.stamina + ua.strength), 0.0) as total_power
-        FROM teams t
-        LEFT JOIN team_members tm ON t.id = tm.team_id
-        LEFT JOIN user_avatars ua ON tm.user_id = ua.user_id
-        WHERE t.id = $1
-        GROUP BY t.id, t.team_name, t.team_color, t.created_at, t.user_id
-    "#)
-    .bind(team_id)
-    .fetch_optional(pool.get_ref())
-    .await
-    .map_err(|e| {
+    let team = fetch_admin_team(pool.get_ref(), team_id).await.map_err(|e| {
         eprintln!("Database error getting team: {e}");
         actix_web::error::ErrorInternalServerError("Database error")
     })?;
 
-    if let Some(row) = row {
-        let team = AdminTeamResponse {
-            id: row.get("id"),
-            name: row.get("name"),
-            color: row.get("color"),
-            member_count: row.get("member_count"),
-            max_members: 5,
-            total_power: row.get("total_power"),
-            created_at: row.get("created_at"),
-            owner_id: row.get("owner_id"),
-            league_id: None,
-        };
-
+    if let Some(team) = team {
+        let etag = version_etag(team.version);
         let response = ApiResponse {
             data: team,
             success: true,
             message: None,
         };
 
-        Ok(HttpResponse::Ok().json(response))
+        Ok(HttpResponse::Ok()
+            .insert_header((header::ETAG, etag))
+            .json(response))
     } else {
         Ok(HttpResponse::NotFound().json(serde_json::json!({
             "error": "Team not found"
@@ -229,6 +209,41 @@ pub async fn get_team_by_id(
     }
 }
 
+async fn fetch_admin_team(pool: &PgPool, team_id: Uuid) -> Result<Option<AdminTeamResponse>, sqlx::Error> {
+    let row = sqlx::query(r#"
+        SELECT 
+            t.id,
+            t.team_name as name,
+            t.team_color as color,
+            t.created_at,
+            t.user_id as owner_id,
+            t.version,
+            COUNT(tm.user_id) as member_count,
+            COALESCE(SUM(ua.stamina + ua.strength), 0.0) as total_power
+        FROM teams t
+        LEFT JOIN team_members tm ON t.id = tm.team_id
+        LEFT JOIN user_avatars ua ON tm.user_id = ua.user_id
+        WHERE t.id = $1
+        GROUP BY t.id, t.team_name, t.team_color, t.created_at, t.user_id, t.version
+    "#)
+    .bind(team_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| AdminTeamResponse {
+        id: row.get("id"),
+        name: row.get("name"),
+        color: row.get("color"),
+        member_count: row.get("member_count"),
+        max_members: 5,
+        total_power: row.get("total_power"),
+        created_at: row.get("created_at"),
+        owner_id: row.get("owner_id"),
+        league_id: None,
+        version: row.get("version"),
+    }))
+}
+
 // POST /admin/teams - Create new team
 pub async fn create_team(
     pool: web::Data<PgPool>,
@@ -319,6 +334,7 @@ pub async fn create_team(
         created_at: now,
         owner_id,
         league_id: body.league_id,
+        version: 1,
     };
 
     let response = ApiResponse {
@@ -331,13 +347,17 @@ pub async fn create_team(
 }
 
 // PATCH /admin/teams/{id} - Update team
+// With `If-Match: "<version>"` the update is rejected with 409 and the current team
+// if someone else saved the team in the meantime
 pub async fn update_team(
     pool: web::Data<PgPool>,
     path: web::Path<Uuid>,
     body: web::Json<CreateTeamRequest>,
+    req: HttpRequest,
 ) -> Result<HttpResponse> {
     let team_id = path.into_inner();
     let now = chrono::Utc::now();
+    let expected_version = if_match_version(&req).map_err(actix_web::error::ErrorBadRequest)?;
 
     // Start a transaction to handle owner change
     let mut tx = match pool.begin().await {
@@ -350,9 +370,9 @@ pub async fn update_team(
         }
     };
 
-    // Get current team owner
+    // Get current team owner, locking the row so the version check holds until commit
     let current_team = sqlx::query!(
-        "SELECT user_id FROM teams WHERE id = $1",
+        "SELECT user_id, version FROM teams WHERE id = $1 FOR UPDATE",
         team_id
     )
     .fetch_optional(&mut *tx)
@@ -363,6 +383,17 @@ pub async fn update_team(
     })?;
 
     let old_owner_id = match current_team {
+        Some(team) if expected_version.is_some_and(|v| v != team.version) => {
+            let _ = tx.rollback().await;
+            let current = fetch_admin_team(pool.get_ref(), team_id).await.ok().flatten();
+            return Ok(HttpResponse::Conflict()
+                .insert_header((header::ETAG, version_etag(team.version)))
+                .json(serde_json::json!({
+                    "success": false,
+                    "error": "Team was changed by someone else. Review the current version and try again.",
+                    "data": current
+                })));
+        }
         Some(team) => team.user_id,
         None => {
             let _ = tx.rollback().await;
@@ -398,7 +429,7 @@ pub async fn update_team(
     let result = sqlx::query!(
         r#"
         UPDATE teams
-        SET team_name = $1, team_color = $2, user_id = $3, updated_at = $4
+        SET team_name = $1, team_color = $2, user_id = $3, updated_at = $4, version = version + 1
         WHERE id = $5
         "#,
         body.name,
@@ -477,17 +508,27 @@ pub async fn delete_team(
 ) -> Result<HttpResponse> {
     let team_id = path.into_inner();
 
-    // Delete the team - all related data will be cascade deleted
-    let result = sqlx::query!(
-        "DELETE FROM teams WHERE id = $1",
-        team_id
-    )
-    .execute(pool.get_ref())
+    // Delete the team - all related data will be cascade deleted.
+    // Its slot in the league goes to the first team on the waitlist.
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let deleted = sqlx::query!(
+            "DELETE FROM teams WHERE id = $1 RETURNING league_id",
+            team_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(league_id) = deleted.as_ref().and_then(|team| team.league_id) {
+            league_waitlist::promote_waitlisted_teams(&mut tx, league_id).await?;
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(deleted.is_some())
+    }
     .await;
 
     match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
+        Ok(deleted) => {
+            if deleted {
                 let response = ApiResponse {
                     data: serde_json::json!({
                         "id": team_id,
@@ -807,4 +848,27 @@ pub async fn remove_team_member(
             })))
         }
     }
-}
\ No newline at end of file
+}
+// GET /admin/teams/{id}/boosters/audit - Audit trail of a team's boosters
+pub async fn get_team_booster_audit(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let team_id = path.into_inner();
+
+    let entries = BoosterService::new(pool.get_ref().clone(), None)
+        .audit_trail(team_id)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error getting booster audit: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    let response = ApiResponse {
+        data: entries,
+        success: true,
+        message: None,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,34 @@
+use actix_web::{web, HttpResponse, Result};
+use tracing::info;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::services::telemetry::{log_controls, LogControls, LogControlsSettings};
+
+/// GET /admin/telemetry/log-controls - Per-route log levels and request sampling in effect
+pub async fn get_log_controls() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        "Log controls retrieved successfully",
+        log_controls().get().settings(),
+    )))
+}
+
+/// PUT /admin/telemetry/log-controls - Replace the per-route log levels and request sampling,
+/// e.g. to quiet noisy upload logs during an incident. Applies to requests starting afterwards.
+pub async fn update_log_controls(
+    claims: web::ReqData<Claims>,
+    body: web::Json<LogControlsSettings>,
+) -> Result<HttpResponse> {
+    let controls = match LogControls::try_from(body.into_inner()) {
+        Ok(controls) => controls,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(e))),
+    };
+    let settings = controls.settings();
+    log_controls().set(controls);
+
+    info!(
+        "Log controls updated by {}: sample rate {}, route levels {:?}",
+        claims.username, settings.sample_rate, settings.route_levels
+    );
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Log controls updated successfully", settings)))
+}
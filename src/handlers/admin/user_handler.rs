@@ -4,6 +4,9 @@ use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
+use crate::handlers::profile::diagnostics::diagnostics_response;
+use crate::models::diagnostics::DiagnosticsQuery;
+
 #[derive(Serialize)]
 pub struct AdminUserResponse {
     pub id: Uuid,
@@ -247,6 +250,15 @@ pub async fn get_user_by_id(
     }
 }
 
+// GET /admin/users/{id}/diagnostics - Upload attempts and device syncs of a user
+pub async fn get_user_diagnostics(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<DiagnosticsQuery>,
+) -> Result<HttpResponse> {
+    Ok(diagnostics_response(pool.get_ref(), path.into_inner(), &query).await)
+}
+
 // PATCH /admin/users/{id}/status - Update user status
 pub async fn update_user_status(
     _pool: web::Data<PgPool>,
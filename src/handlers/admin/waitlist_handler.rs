@@ -0,0 +1,87 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::{info, error};
+
+use crate::db::league_waitlist;
+use crate::models::common::ApiResponse;
+use crate::models::league::{LeagueWaitlistEntry, ReorderWaitlistRequest};
+
+/// GET /admin/leagues/{id}/waitlist - Teams waiting for a slot, in promotion order
+pub async fn get_league_waitlist(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    let waitlist = league_waitlist::list_waitlist(pool.get_ref(), league_id).await.map_err(|e| {
+        error!("Failed to fetch waitlist of league {}: {}", league_id, e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success("Waitlist retrieved successfully", waitlist)))
+}
+
+/// PUT /admin/leagues/{id}/waitlist - Reorder the waitlist. The body lists all waiting teams.
+pub async fn reorder_league_waitlist(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<ReorderWaitlistRequest>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let reordered = league_waitlist::reorder_waitlist(&mut tx, league_id, &body.team_ids).await?;
+        if reordered {
+            tx.commit().await?;
+        }
+        Ok::<_, sqlx::Error>(reordered)
+    }
+    .await;
+
+    match result {
+        Ok(true) => {
+            info!("Reordered waitlist of league {}", league_id);
+            let waitlist = league_waitlist::list_waitlist(pool.get_ref(), league_id).await.map_err(|e| {
+                error!("Failed to fetch waitlist of league {}: {}", league_id, e);
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
+            Ok(HttpResponse::Ok().json(ApiResponse::success("Waitlist reordered successfully", waitlist)))
+        }
+        Ok(false) => Ok(HttpResponse::BadRequest().json(ApiResponse::<Vec<LeagueWaitlistEntry>>::error(
+            "team_ids must list every waitlisted team exactly once"
+        ))),
+        Err(e) => {
+            error!("Failed to reorder waitlist of league {}: {}", league_id, e);
+            Err(actix_web::error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// DELETE /admin/leagues/{id}/waitlist/{team_id} - Take a team off the waitlist
+pub async fn remove_from_league_waitlist(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (league_id, team_id) = path.into_inner();
+
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let removed = league_waitlist::remove_from_waitlist(&mut tx, league_id, team_id).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(removed)
+    }
+    .await
+    .map_err(|e| {
+        error!("Failed to remove team {} from waitlist of league {}: {}", team_id, league_id, e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    if !result {
+        return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Team is not on this waitlist")));
+    }
+
+    info!("Removed team {} from waitlist of league {}", team_id, league_id);
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("Team removed from waitlist successfully")))
+}
@@ -2,10 +2,12 @@ use actix_web::{web, HttpResponse};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Row};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::models::workout_data::HeartRateData;
 use crate::models::common::ApiResponse;
+use crate::services::UserStatsCache;
 
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct AdminWorkoutData {
@@ -223,6 +225,7 @@ pub async fn get_workout_detail(
 
 pub async fn delete_workout(
     pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
     workout_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, actix_web::Error> {
     let workout_id = workout_id.into_inner();
@@ -298,6 +301,10 @@ pub async fn delete_workout(
             actix_web::error::ErrorInternalServerError("Failed to delete workout")
         })?;
 
+    UserStatsCache::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()))
+        .invalidate(&[workout_user_id])
+        .await;
+
     tracing::info!("Admin deleted workout: {}", workout_id);
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
@@ -313,6 +320,7 @@ pub struct BulkDeleteRequest {
 
 pub async fn bulk_delete_workouts(
     pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
     body: web::Json<BulkDeleteRequest>,
 ) -> Result<HttpResponse, actix_web::Error> {
     if body.workout_ids.is_empty() {
@@ -341,6 +349,9 @@ pub async fn bulk_delete_workouts(
 
     // Group and reverse stat changes by user
     use std::collections::HashMap;
+    let mut affected_users: Vec<Uuid> = workout_infos.iter().map(|row| row.get("workout_user_id")).collect();
+    affected_users.sort();
+    affected_users.dedup();
     let mut user_stat_changes: HashMap<Uuid, (f32, f32)> = HashMap::new(); // user_id -> (stamina, strength)
     
     for row in &workout_infos {
@@ -402,6 +413,10 @@ pub async fn bulk_delete_workouts(
     })?;
 
     let deleted_count = result.rows_affected();
+
+    UserStatsCache::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()))
+        .invalidate(&affected_users)
+        .await;
     
     tracing::info!("Admin bulk deleted {} workouts", deleted_count);
 
@@ -1,31 +1,213 @@
 // src/handlers/auth_handler.rs
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use secrecy::ExposeSecret;
 use sqlx::PgPool;
-use chrono::{Utc, Duration};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use chrono::{DateTime, Utc, Duration};
+use uuid::Uuid;
 
-use crate::models::auth::{LoginRequest, LoginResponse, BiometricRefreshRequest, ResetPasswordRequest};
+use crate::db::account_deletions::cancel_deletion;
+use crate::db::login_activity::LoginContext;
+use crate::db::magic_link_tokens::{consume_magic_link_token, issue_magic_link_token};
+use crate::db::password_reset_tokens::{consume_password_reset_token, issue_password_reset_token, password_reset_token_user};
+use crate::db::refresh_tokens::{issue_refresh_token, lock_refresh_token, mark_rotated, revoke_all_for_user, revoke_family};
+use crate::db::user_sessions::{check_session, refresh_session, start_session};
+use crate::models::auth::{
+    LoginRequest, LoginResponse, BiometricRefreshRequest, RefreshTokenRequest,
+    PasswordResetRequest, PasswordResetConfirmRequest, MagicLinkRequest, MagicLinkConsumeRequest, SessionDevice,
+};
+use crate::models::login_activity::LoginFailure;
 use crate::models::user::{UserRole, UserStatus};
 use crate::utils::password::{verify_password, hash_password};
 use crate::config::jwt::JwtSettings;
 use crate::middleware::auth::Claims;
+use crate::services::{EmailService, LoginProtectionService, PasswordPolicyService};
+
+/// How long the link of a password reset email can be used
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 60;
+
+/// How long the link of a sign-in email can be used
+const MAGIC_LINK_TOKEN_MINUTES: i64 = 15;
+
+/// Signed access token for a user's session, valid for 24 hours
+pub(crate) fn generate_access_token(
+    user_id: Uuid,
+    username: String,
+    role: &str,
+    status: &str,
+    session_id: Option<Uuid>,
+    jwt_settings: &JwtSettings,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let expires_at = Utc::now()
+        .checked_add_signed(Duration::hours(24))
+        .expect("Valid timestamp");
+
+    let claims = Claims {
+        sid: session_id,
+        ..access_claims(user_id, username, role, status, expires_at, jwt_settings)
+    };
+
+    jwt_settings.sign(&claims)
+}
+
+/// Signed access token letting an admin act as the user until `expires_at`. It has a session of
+/// its own, so logging that out revokes it, and it can't be refreshed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn generate_impersonation_token(
+    user_id: Uuid,
+    username: String,
+    role: &str,
+    status: &str,
+    admin_id: Uuid,
+    session_id: Uuid,
+    expires_at: DateTime<Utc>,
+    jwt_settings: &JwtSettings,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = Claims {
+        sid: Some(session_id),
+        impersonated_by: Some(admin_id),
+        ..access_claims(user_id, username, role, status, expires_at, jwt_settings)
+    };
+
+    jwt_settings.sign(&claims)
+}
+
+fn access_claims(
+    user_id: Uuid,
+    username: String,
+    role: &str,
+    status: &str,
+    expires_at: DateTime<Utc>,
+    jwt_settings: &JwtSettings,
+) -> Claims {
+    let role = match role {
+        "superadmin" => UserRole::SuperAdmin,
+        "admin" => UserRole::Admin,
+        "moderator" => UserRole::Moderator,
+        _ => UserRole::User,
+    };
+
+    let status = match status {
+        "inactive" => UserStatus::Inactive,
+        "suspended" => UserStatus::Suspended,
+        "banned" => UserStatus::Banned,
+        _ => UserStatus::Active,
+    };
+
+    Claims {
+        sub: user_id.to_string(),
+        username,
+        role,
+        status,
+        exp: expires_at.timestamp() as usize,
+        iss: Some(jwt_settings.issuer.clone()),
+        aud: Some(jwt_settings.audience.clone()),
+        sid: None,
+        impersonated_by: None,
+    }
+}
+
+/// Device a request comes from, as told by the app's `X-Device-Id` and `User-Agent` headers
+pub(crate) fn session_device(req: &HttpRequest) -> SessionDevice {
+    let header = |name: &str| req.headers().get(name).and_then(|value| value.to_str().ok());
+    SessionDevice::new(header("X-Device-Id"), header("User-Agent"))
+}
+
+/// Session of a newly logged in device and the first refresh token of its family
+struct LoginSession {
+    session_id: Uuid,
+    refresh_token: String,
+    username: String,
+    role: String,
+}
+
+/// Start the session of a newly logged in device. Logging in cancels a pending deletion of the
+/// account, restoring the status it had before; accounts that are not active otherwise, e.g.
+/// deactivated by their organization's directory, get None.
+async fn start_refresh_family(
+    pool: &PgPool,
+    user_id: Uuid,
+    device: &SessionDevice,
+    jwt_settings: &JwtSettings,
+) -> Result<Option<LoginSession>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let restored_status = cancel_deletion(&mut tx, user_id).await?;
+    let user = sqlx::query!("SELECT username, role, status FROM users WHERE id = $1", user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if user.status != "active" {
+        // Rolled back, so a pending deletion stays pending
+        tracing::info!("User {} with status {} refused login", user_id, user.status);
+        return Ok(None);
+    }
+    if restored_status.is_some() {
+        tracing::info!("User {} logged in again, account deletion cancelled", user_id);
+    }
+    let expires_at = Utc::now() + Duration::days(jwt_settings.refresh_token_days);
+    let session_id = start_session(&mut tx, user_id, device, expires_at).await?;
+    let (_, refresh_token) = issue_refresh_token(&mut tx, user_id, session_id, expires_at).await?;
+    tx.commit().await?;
+    Ok(Some(LoginSession { session_id, refresh_token, username: user.username, role: user.role }))
+}
+
+/// Tokens for a user who just proved who they are, by password, sign-in link or provider login.
+/// Accounts that are not active get none. Successful logins are recorded with the login
+/// protection, which notices logins from new addresses.
+pub(crate) async fn issue_login(
+    req: &HttpRequest,
+    pool: &PgPool,
+    user_id: Uuid,
+    jwt_settings: &JwtSettings,
+) -> HttpResponse {
+    let login = match start_refresh_family(pool, user_id, &session_device(req), jwt_settings).await {
+        Ok(Some(login)) => login,
+        Ok(None) => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Account is not active"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Error issuing refresh token: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let token = match generate_access_token(user_id, login.username.clone(), &login.role, "active", Some(login.session_id), jwt_settings) {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Error generating JWT token: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    if let Some(login_protection) = req.app_data::<web::Data<LoginProtectionService>>() {
+        login_protection.login_succeeded(&LoginContext {
+            user_id: Some(user_id),
+            username: login.username,
+            ip_address: login_protection.client_address(req),
+            user_agent: req.headers().get("User-Agent").and_then(|value| value.to_str().ok()).map(str::to_string),
+        }).await;
+    }
+
+    HttpResponse::Ok().json(LoginResponse { token, refresh_token: Some(login.refresh_token) })
+}
 
 #[tracing::instrument(
     name = "Login user attempt",
-    skip(login_form, pool, jwt_settings),
+    skip(req, login_form, pool, jwt_settings, login_protection),
     fields(
         username = %login_form.username
     )
 )]
 pub async fn login_user(
+    req: HttpRequest,
     login_form: web::Json<LoginRequest>,
     pool: web::Data<PgPool>,
-    jwt_settings: web::Data<JwtSettings>
+    jwt_settings: web::Data<JwtSettings>,
+    login_protection: web::Data<LoginProtectionService>,
 ) -> HttpResponse {
     let user_result = sqlx::query!(
         r#"
-        SELECT id, username, password_hash, role, status
+        SELECT id, password_hash
         FROM users
         WHERE username = $1
         "#,
@@ -36,93 +218,55 @@ pub async fn login_user(
 
     // Return database error to user as 500
     let user = match user_result {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            tracing::info!("User not found or invalid credentials");
-            return HttpResponse::Unauthorized().finish();
-        }
+        Ok(user) => user,
         Err(e) => {
             tracing::error!("Database error occurred: {:?}", e);
             return HttpResponse::InternalServerError().finish();
         }
     };
 
-    // Verify password
-    if !verify_password(
-        login_form.password.expose_secret(),
-        &user.password_hash
-    ) {
-        tracing::info!("Invalid password");
-        return HttpResponse::Unauthorized().finish();
-    }
-
-    // Generate JWT token
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
-        .expect("Valid timestamp")
-        .timestamp() as usize;
-
-    let role = match user.role.as_str() {
-        "superadmin" => UserRole::SuperAdmin,
-        "admin" => UserRole::Admin,
-        "moderator" => UserRole::Moderator,
-        _ => UserRole::User,
-    };
-    
-    let status = match user.status.as_str() {
-        "inactive" => UserStatus::Inactive,
-        "suspended" => UserStatus::Suspended,
-        "banned" => UserStatus::Banned,
-        _ => UserStatus::Active,
-    };
-    
-    let claims = Claims {
-        sub: user.id.to_string(),
-        username: user.username,
-        role,
-        status,
-        exp: expiration,
+    let login = LoginContext {
+        user_id: user.as_ref().map(|user| user.id),
+        username: login_form.username.clone(),
+        ip_address: login_protection.client_address(&req),
+        user_agent: req.headers().get("User-Agent").and_then(|value| value.to_str().ok()).map(str::to_string),
     };
 
-    let token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(jwt_settings.secret.expose_secret().as_bytes()),
-    ) {
-        Ok(t) => t,
-        Err(e) => {
-            tracing::error!("Error generating JWT token: {:?}", e);
-            return HttpResponse::InternalServerError().finish();
-        }
-    };
+    // Checked before the password, so a locked account can't be guessed at meanwhile
+    if let Some(retry_after) = login_protection.locked_for(&login).await {
+        tracing::warn!("Login to locked account or from blocked address");
+        login_protection.login_failed(&login, LoginFailure::Locked).await;
+        return HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.as_secs().to_string()))
+            .json(serde_json::json!({
+                "error": "Too many failed logins, try again later",
+                "retry_after_seconds": retry_after.as_secs()
+            }));
+    }
 
-    // Return token
-    HttpResponse::Ok().json(LoginResponse { token })
+    // Verify password
+    let Some(user) = user.filter(|user| verify_password(login_form.password.expose_secret(), &user.password_hash)) else {
+        tracing::info!("User not found or invalid credentials");
+        login_protection.login_failed(&login, LoginFailure::InvalidCredentials).await;
+        return HttpResponse::Unauthorized().finish();
+    };
+    issue_login(&req, pool.get_ref(), user.id, &jwt_settings).await
 }
 
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
-
 #[tracing::instrument(
     name = "Refresh biometric token",
-    skip(refresh_request, pool, jwt_settings),
+    skip(req, refresh_request, pool, jwt_settings),
 )]
 pub async fn refresh_biometric_token(
+    req: HttpRequest,
     refresh_request: web::Json<BiometricRefreshRequest>,
     pool: web::Data<PgPool>,
     jwt_settings: web::Data<JwtSettings>,
 ) -> HttpResponse {
     let expired_token = &refresh_request.token;
     // Decode the expired token with validation disabled for expiry
-    let mut validation = Validation::new(Algorithm::HS256);
-    validation.validate_exp = false; // Don't validate expiry
-    validation.validate_nbf = false;
-
-    let token_data = match decode::<Claims>(
-        expired_token,
-        &DecodingKey::from_secret(jwt_settings.secret.expose_secret().as_bytes()),
-        &validation,
-    ) {
-        Ok(data) => data,
+    let claims = match jwt_settings.verify_ignoring_expiry::<Claims>(expired_token) {
+        Ok(claims) => claims,
         Err(e) => {
             tracing::warn!("Invalid token provided for refresh: {:?}", e);
             return HttpResponse::Unauthorized().json(serde_json::json!({
@@ -131,8 +275,6 @@ pub async fn refresh_biometric_token(
         }
     };
 
-    let claims = token_data.claims;
-
     // Check if token is not too old (e.g., expired less than 30 days ago)
     let now = Utc::now().timestamp() as usize;
     let thirty_days_in_seconds = 30 * 24 * 60 * 60;
@@ -144,6 +286,14 @@ pub async fn refresh_biometric_token(
         }));
     }
 
+    // Impersonation ends when its token expires
+    if claims.impersonated_by.is_some() {
+        tracing::warn!("Impersonation token presented for refresh");
+        return HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Impersonation tokens can't be refreshed"
+        }));
+    }
+
     let Some(user_id) = claims.user_id() else {
         tracing::error!("Invalid user ID in claims");
         return HttpResponse::BadRequest().json(serde_json::json!({
@@ -151,6 +301,23 @@ pub async fn refresh_biometric_token(
         }));
     };
 
+    // A logged out device can't bring its old token back to life
+    if let Some(session_id) = claims.sid {
+        match check_session(pool.get_ref(), session_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::warn!("Token of logged out session {} presented for refresh", session_id);
+                return HttpResponse::Unauthorized().json(serde_json::json!({
+                    "error": "Session has been logged out"
+                }));
+            }
+            Err(e) => {
+                tracing::error!("Database error during token refresh: {:?}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        }
+    }
+
     // Verify user still exists and is active
     let user_result = sqlx::query!(
         r#"
@@ -177,114 +344,382 @@ pub async fn refresh_biometric_token(
         }
     };
 
+    // Tokens from before sessions existed get one now, so the device is listed and can be logged out
+    let session_id = match claims.sid {
+        Some(session_id) => session_id,
+        None => {
+            let expires_at = Utc::now() + Duration::days(jwt_settings.refresh_token_days);
+            let started = async {
+                let mut conn = pool.acquire().await?;
+                start_session(&mut conn, user.id, &session_device(&req), expires_at).await
+            }
+            .await;
+            match started {
+                Ok(session_id) => {
+                    tracing::info!("Started session {} for a token of user {} without one", session_id, user.id);
+                    session_id
+                }
+                Err(e) => {
+                    tracing::error!("Failed to start session during token refresh: {:?}", e);
+                    return HttpResponse::InternalServerError().finish();
+                }
+            }
+        }
+    };
+
     // Generate new JWT token with fresh expiry
-    let new_expiration = Utc::now()
-        .checked_add_signed(Duration::hours(24))
-        .expect("Valid timestamp")
-        .timestamp() as usize;
+    let new_token = match generate_access_token(user.id, user.username, &user.role, &user.status, Some(session_id), &jwt_settings) {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Error generating new JWT token: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
 
-    let role = match user.role.as_str() {
-        "superadmin" => UserRole::SuperAdmin,
-        "admin" => UserRole::Admin,
-        "moderator" => UserRole::Moderator,
-        _ => UserRole::User,
+    tracing::info!("Successfully refreshed token for user {}", user.id);
+    HttpResponse::Ok().json(LoginResponse { token: new_token, refresh_token: None })
+}
+
+#[tracing::instrument(
+    name = "Refresh access token",
+    skip(req, refresh_request, pool, jwt_settings),
+)]
+pub async fn refresh_access_token(
+    req: HttpRequest,
+    refresh_request: web::Json<RefreshTokenRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> HttpResponse {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            tracing::error!("Failed to start transaction: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
     };
 
-    let status = match user.status.as_str() {
-        "inactive" => UserStatus::Inactive,
-        "suspended" => UserStatus::Suspended,
-        "banned" => UserStatus::Banned,
-        _ => UserStatus::Active,
+    let stored = match lock_refresh_token(&mut tx, &refresh_request.refresh_token).await {
+        Ok(Some(stored)) => stored,
+        Ok(None) => {
+            tracing::warn!("Unknown refresh token presented");
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid refresh token"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Database error looking up refresh token: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
     };
 
-    let new_claims = Claims {
-        sub: user.id.to_string(),
-        username: user.username,
-        role,
-        status,
-        exp: new_expiration,
+    let user = match sqlx::query!(
+        "SELECT id, username, role, status FROM users WHERE id = $1 AND status = 'active'",
+        stored.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(user) => user,
+        Err(e) => {
+            tracing::error!("Database error during token refresh: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    // A token that was already rotated is being replayed, so it may have leaked: log out the device
+    let rejection = if stored.revoked_at.is_some() {
+        tracing::warn!("Revoked refresh token reused for user {}, revoking its family", stored.user_id);
+        Some("Refresh token revoked")
+    } else if stored.expires_at <= Utc::now() {
+        Some("Refresh token expired")
+    } else if user.is_none() {
+        Some("User not found or inactive")
+    } else {
+        None
+    };
+    let Some(user) = user.filter(|_| rejection.is_none()) else {
+        if let Err(e) = revoke_family(&mut tx, stored.family_id).await {
+            tracing::error!("Failed to revoke refresh token family {}: {:?}", stored.family_id, e);
+        }
+        if let Err(e) = tx.commit().await {
+            tracing::error!("Failed to commit refresh token revocation: {:?}", e);
+        }
+        return HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": rejection.unwrap_or("Invalid refresh token")
+        }));
     };
 
-    let new_token = match encode(
-        &Header::default(),
-        &new_claims,
-        &EncodingKey::from_secret(jwt_settings.secret.expose_secret().as_bytes()),
-    ) {
+    let expires_at = Utc::now() + Duration::days(jwt_settings.refresh_token_days);
+    let rotated = async {
+        let (new_id, new_token) = issue_refresh_token(&mut tx, user.id, stored.family_id, expires_at).await?;
+        mark_rotated(&mut tx, stored.id, new_id).await?;
+        refresh_session(&mut tx, stored.family_id, user.id, &session_device(&req), expires_at).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(new_token)
+    }
+    .await;
+    let refresh_token = match rotated {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to rotate refresh token: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let token = match generate_access_token(user.id, user.username, &user.role, &user.status, Some(stored.family_id), &jwt_settings) {
         Ok(t) => t,
         Err(e) => {
-            tracing::error!("Error generating new JWT token: {:?}", e);
+            tracing::error!("Error generating JWT token: {:?}", e);
             return HttpResponse::InternalServerError().finish();
         }
     };
 
-    tracing::info!("Successfully refreshed token for user {}", new_claims.sub);
-    HttpResponse::Ok().json(LoginResponse { token: new_token })
+    tracing::info!("Rotated refresh token for user {}", user.id);
+    HttpResponse::Ok().json(LoginResponse { token, refresh_token: Some(refresh_token) })
 }
 
 #[tracing::instrument(
-    name = "Reset password",
-    skip(reset_request, pool),
-    fields(
-        username = %reset_request.username
-    )
+    name = "Revoke refresh token",
+    skip(revoke_request, pool),
 )]
-pub async fn reset_password(
-    reset_request: web::Json<ResetPasswordRequest>,
+pub async fn revoke_refresh_token(
+    revoke_request: web::Json<RefreshTokenRequest>,
     pool: web::Data<PgPool>,
 ) -> HttpResponse {
-    // Check if user exists
-    let user_result = sqlx::query!(
-        r#"
-        SELECT id
-        FROM users
-        WHERE username = $1
-        "#,
-        reset_request.username,
-    )
-    .fetch_optional(pool.get_ref())
+    let result = async {
+        let mut tx = pool.begin().await?;
+        if let Some(stored) = lock_refresh_token(&mut tx, &revoke_request.refresh_token).await? {
+            revoke_family(&mut tx, stored.family_id).await?;
+            tracing::info!("Revoked refresh tokens of family {} for user {}", stored.family_id, stored.user_id);
+        }
+        tx.commit().await
+    }
     .await;
 
-    let user = match user_result {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            tracing::info!("User not found for password reset");
-            return HttpResponse::NotFound().json(serde_json::json!({
-                "error": "User not found"
-            }));
+    match result {
+        // Unknown tokens are fine too: either way the token can't be used anymore
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Logged out"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to revoke refresh token: {:?}", e);
+            HttpResponse::InternalServerError().finish()
         }
+    }
+}
+
+#[tracing::instrument(
+    name = "Request password reset",
+    skip(req, reset_request, pool, email_service, login_protection),
+)]
+pub async fn request_password_reset(
+    req: HttpRequest,
+    reset_request: web::Json<PasswordResetRequest>,
+    pool: web::Data<PgPool>,
+    email_service: web::Data<EmailService>,
+    login_protection: web::Data<LoginProtectionService>,
+) -> HttpResponse {
+    let email = reset_request.email.trim();
+    let ip_address = login_protection.client_address(&req);
+    if !login_protection.allow_email_request("password_reset", email, ip_address.as_deref()).await {
+        tracing::warn!("Too many password reset requests for an email or from {:?}", ip_address);
+    } else if let Err(e) = send_password_reset(pool.get_ref(), &email_service, email).await {
+        tracing::error!("Failed to handle password reset request: {}", e);
+    }
+
+    // Same answer whatever happened, so it can't be used to probe for users
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "message": "If an account with this email exists, a password reset link has been sent"
+    }))
+}
+
+async fn send_password_reset(pool: &PgPool, email_service: &EmailService, email: &str) -> Result<(), String> {
+    let user = sqlx::query!(
+        "SELECT id, username, email FROM users WHERE LOWER(email) = LOWER($1) AND status = 'active'",
+        email
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Database error: {e:?}"))?;
+    let Some(user) = user else {
+        tracing::info!("No active user for password reset request");
+        return Ok(());
+    };
+
+    let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES);
+    let token = issue_password_reset_token(pool, user.id, expires_at)
+        .await
+        .map_err(|e| format!("Failed to issue token for user {}: {e:?}", user.id))?;
+
+    let text = format!(
+        "Hi {},\n\nUse this link within {} minutes to choose a new password:\n{}\n\nIf you didn't ask to reset your password, you can ignore this email.",
+        user.username,
+        PASSWORD_RESET_TOKEN_MINUTES,
+        email_service.password_reset_link(&token)
+    );
+    email_service
+        .send(&user.email, "Reset your Riina password", &text)
+        .await
+        .map_err(|e| format!("Failed to send email to user {}: {e}", user.id))?;
+
+    tracing::info!("Password reset link sent to user {}", user.id);
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "Confirm password reset",
+    skip(confirm_request, pool, password_policy),
+)]
+pub async fn confirm_password_reset(
+    confirm_request: web::Json<PasswordResetConfirmRequest>,
+    pool: web::Data<PgPool>,
+    password_policy: web::Data<PasswordPolicyService>,
+) -> HttpResponse {
+    let invalid_token = || HttpResponse::BadRequest().json(serde_json::json!({
+        "error": "Invalid or expired password reset token"
+    }));
+    let (username, email) = match password_reset_token_user(pool.get_ref(), &confirm_request.token).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return invalid_token(),
         Err(e) => {
-            tracing::error!("Database error during password reset: {:?}", e);
+            tracing::error!("Error looking up password reset token: {:?}", e);
             return HttpResponse::InternalServerError().finish();
         }
     };
 
-    // Hash the new password
-    let password_hash = hash_password(reset_request.new_password.expose_secret());
+    // The same rules as at registration; the token stays valid for another try
+    let errors = password_policy
+        .check(confirm_request.new_password.expose_secret(), &username, &email)
+        .await;
+    if let Some(first) = errors.first() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": first.message,
+            "errors": errors
+        }));
+    }
 
-    // Update the password in the database
-    let update_result = sqlx::query!(
-        r#"
-        UPDATE users
-        SET password_hash = $1
-        WHERE id = $2
-        "#,
-        password_hash,
-        user.id,
-    )
-    .execute(pool.get_ref())
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let Some(user_id) = consume_password_reset_token(&mut tx, &confirm_request.token).await? else {
+            return Ok(None);
+        };
+        sqlx::query!(
+            // The reset link went to the user's email, so they own it
+            "UPDATE users SET password_hash = $1, email_verified_at = COALESCE(email_verified_at, NOW()) WHERE id = $2",
+            hash_password(confirm_request.new_password.expose_secret()),
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Some(user_id))
+    }
     .await;
 
-    match update_result {
-        Ok(_) => {
-            tracing::info!("Password reset successful for user {}", reset_request.username);
+    match result {
+        Ok(Some(user_id)) => {
+            tracing::info!("Password reset confirmed for user {}", user_id);
+            // Devices logged in with the old password have to log in again
+            if let Err(e) = revoke_all_for_user(pool.get_ref(), user_id).await {
+                tracing::error!("Failed to revoke refresh tokens after password reset: {:?}", e);
+            }
             HttpResponse::Ok().json(serde_json::json!({
                 "success": true,
                 "message": "Password reset successful"
             }))
         }
+        Ok(None) => invalid_token(),
         Err(e) => {
-            tracing::error!("Error updating password: {:?}", e);
+            tracing::error!("Error confirming password reset: {:?}", e);
             HttpResponse::InternalServerError().finish()
         }
     }
-}
\ No newline at end of file
+}
+#[tracing::instrument(
+    name = "Request magic link",
+    skip(req, link_request, pool, email_service, login_protection),
+)]
+pub async fn request_magic_link(
+    req: HttpRequest,
+    link_request: web::Json<MagicLinkRequest>,
+    pool: web::Data<PgPool>,
+    email_service: web::Data<EmailService>,
+    login_protection: web::Data<LoginProtectionService>,
+) -> HttpResponse {
+    let email = link_request.email.trim();
+    let ip_address = login_protection.client_address(&req);
+    if !login_protection.allow_email_request("magic_link", email, ip_address.as_deref()).await {
+        tracing::warn!("Too many magic link requests for an email or from {:?}", ip_address);
+    } else if let Err(e) = send_magic_link(pool.get_ref(), &email_service, email).await {
+        tracing::error!("Failed to handle magic link request: {}", e);
+    }
+
+    // Same answer whatever happened, so it can't be used to probe for users
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "message": "If an account with this email exists, a sign-in link has been sent"
+    }))
+}
+
+async fn send_magic_link(pool: &PgPool, email_service: &EmailService, email: &str) -> Result<(), String> {
+    let user = sqlx::query!(
+        "SELECT id, username, email FROM users WHERE LOWER(email) = LOWER($1) AND status = 'active'",
+        email
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Database error: {e:?}"))?;
+    let Some(user) = user else {
+        tracing::info!("No active user for magic link request");
+        return Ok(());
+    };
+
+    let expires_at = Utc::now() + Duration::minutes(MAGIC_LINK_TOKEN_MINUTES);
+    let token = issue_magic_link_token(pool, user.id, expires_at)
+        .await
+        .map_err(|e| format!("Failed to issue token for user {}: {e:?}", user.id))?;
+
+    let text = format!(
+        "Hi {},\n\nUse this link within {} minutes to sign in to Riina:\n{}\n\nIf you didn't ask to sign in, you can ignore this email.",
+        user.username,
+        MAGIC_LINK_TOKEN_MINUTES,
+        email_service.magic_link(&token)
+    );
+    email_service
+        .send(&user.email, "Sign in to Riina", &text)
+        .await
+        .map_err(|e| format!("Failed to send email to user {}: {e}", user.id))?;
+
+    tracing::info!("Magic link sent to user {}", user.id);
+    Ok(())
+}
+
+/// Log in with the token of a sign-in email, which works once
+#[tracing::instrument(
+    name = "Consume magic link",
+    skip(req, consume_request, pool, jwt_settings),
+)]
+pub async fn consume_magic_link(
+    req: HttpRequest,
+    consume_request: web::Json<MagicLinkConsumeRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> HttpResponse {
+    let user_id = match consume_magic_link_token(pool.get_ref(), &consume_request.token).await {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid or expired sign-in link"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Database error consuming magic link: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    tracing::info!("User {} opened a magic link", user_id);
+    issue_login(&req, pool.get_ref(), user_id, &jwt_settings).await
+}
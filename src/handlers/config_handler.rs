@@ -0,0 +1,32 @@
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::db::organizations::find_organization;
+use crate::models::organization::{BrandingConfig, BrandingQuery};
+
+/// Branding, locale and league defaults of an organization for the white-labeled apps.
+/// Without `org` the default organization is returned.
+#[tracing::instrument(name = "Get branding config", skip(pool))]
+pub async fn get_branding_config(
+    pool: web::Data<PgPool>,
+    query: web::Query<BrandingQuery>,
+) -> HttpResponse {
+    match find_organization(pool.get_ref(), query.org.as_deref()).await {
+        Ok(Some(organization)) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": BrandingConfig::from(organization)
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "success": false,
+            "message": "Organization not found"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to load branding config: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to load branding config"
+            }))
+        }
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
 
 const ENGAGEMENT_WINDOW_HOURS: i64 = 24;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 struct FeedPost {
     id: Uuid,
     user_id: Uuid,
@@ -28,6 +28,9 @@ struct FeedPost {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     edited_at: Option<DateTime<Utc>>,
+    workout: Option<WorkoutData>,
+    social: SocialCounts,
+    effort_rating: Option<i16>,
 }
 
 #[derive(Debug)]
@@ -38,6 +41,7 @@ struct WorkoutData {
     calories_burned: Option<i32>,
     activity_name: Option<String>,
     user_activity: Option<String>,
+    notes: Option<String>,
     avg_heart_rate: Option<i32>,
     max_heart_rate: Option<i32>,
     heart_rate_zones: Option<serde_json::Value>,
@@ -61,11 +65,13 @@ struct SocialCounts {
     user_has_reacted: bool,
 }
 
-/// Fetch base posts with pagination
+/// Fetch a page of posts together with their workout, counts and the user's own reaction and rating.
+/// Counts come from the trigger-maintained feed_workout_summaries, so nothing is aggregated here.
 async fn fetch_feed_posts(
     pool: &PgPool,
     cursor: Option<DateTime<Utc>>,
     limit: i64,
+    user_id: Uuid,
 ) -> Result<Vec<FeedPost>, sqlx::Error> {
     let rows = sqlx::query!(
         r#"
@@ -74,9 +80,24 @@ async fn fetch_feed_posts(
             p.content, p.workout_id, p.media_urls,
             p.ad_metadata, p.visibility as "visibility: String",
             p.is_editable, p.created_at, p.updated_at, p.edited_at,
-            u.username, u.profile_picture_url
+            u.username, u.profile_picture_url,
+            w.workout_start as "workout_start?", w.workout_end as "workout_end?",
+            w.duration_minutes, w.calories_burned, w.activity_name, w.user_activity, w.notes,
+            w.avg_heart_rate, w.max_heart_rate, w.heart_rate_zones,
+            w.stamina_gained as "stamina_gained?", w.strength_gained as "strength_gained?",
+            w.image_url, w.video_url,
+            COALESCE(s.reaction_count, 0) as "reaction_count!",
+            COALESCE(s.comment_count, 0) as "comment_count!",
+            EXISTS (
+                SELECT 1 FROM reactions r
+                WHERE r.target_type = 'workout' AND r.target_id = p.workout_id AND r.user_id = $3
+            ) as "user_has_reacted!",
+            f.effort_rating as "effort_rating?"
         FROM posts p
         JOIN users u ON u.id = p.user_id
+        LEFT JOIN workout_data w ON w.id = p.workout_id
+        LEFT JOIN feed_workout_summaries s ON s.workout_id = p.workout_id
+        LEFT JOIN workout_scoring_feedback f ON f.workout_data_id = p.workout_id AND f.user_id = $3
         WHERE
             p.visibility = 'public'
             AND ($1::timestamptz IS NULL OR p.created_at < $1)
@@ -84,72 +105,57 @@ async fn fetch_feed_posts(
         LIMIT $2
         "#,
         cursor,
-        limit
+        limit,
+        user_id
     )
     .fetch_all(pool)
     .await?;
 
-    Ok(rows.into_iter().map(|row| FeedPost {
-        id: row.id,
-        user_id: row.user_id,
-        username: row.username,
-        profile_picture_url: row.profile_picture_url,
-        post_type: row.post_type,
-        content: row.content,
-        workout_id: row.workout_id,
-        media_urls: row.media_urls,
-        ad_metadata: row.ad_metadata,
-        visibility: row.visibility,
-        is_editable: row.is_editable,
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-        edited_at: row.edited_at,
-    }).collect())
-}
-
-/// Fetch workout data for given workout IDs
-async fn fetch_workout_data(
-    pool: &PgPool,
-    workout_ids: &[Uuid],
-) -> Result<HashMap<Uuid, WorkoutData>, sqlx::Error> {
-    if workout_ids.is_empty() {
-        return Ok(HashMap::new());
-    }
-
-    let rows = sqlx::query!(
-        r#"
-        SELECT
-            id, workout_start, workout_end,
-            duration_minutes, calories_burned, activity_name, user_activity,
-            avg_heart_rate, max_heart_rate, heart_rate_zones,
-            stamina_gained, strength_gained,
-            image_url, video_url
-        FROM workout_data
-        WHERE id = ANY($1)
-        "#,
-        workout_ids
-    )
-    .fetch_all(pool)
-    .await?;
+    Ok(rows.into_iter().map(|row| {
+        let workout = match (row.workout_start, row.workout_end) {
+            (Some(workout_start), Some(workout_end)) => Some(WorkoutData {
+                workout_start,
+                workout_end,
+                duration_minutes: row.duration_minutes,
+                calories_burned: row.calories_burned,
+                activity_name: row.activity_name,
+                user_activity: row.user_activity,
+                notes: row.notes,
+                avg_heart_rate: row.avg_heart_rate,
+                max_heart_rate: row.max_heart_rate,
+                heart_rate_zones: row.heart_rate_zones,
+                stamina_gained: row.stamina_gained.unwrap_or(0.0),
+                strength_gained: row.strength_gained.unwrap_or(0.0),
+                image_url: row.image_url,
+                video_url: row.video_url,
+            }),
+            _ => None,
+        };
 
-    Ok(rows.into_iter().map(|row| (
-        row.id,
-        WorkoutData {
-            workout_start: row.workout_start,
-            workout_end: row.workout_end,
-            duration_minutes: row.duration_minutes,
-            calories_burned: row.calories_burned,
-            activity_name: row.activity_name,
-            user_activity: row.user_activity,
-            avg_heart_rate: row.avg_heart_rate,
-            max_heart_rate: row.max_heart_rate,
-            heart_rate_zones: row.heart_rate_zones,
-            stamina_gained: row.stamina_gained,
-            strength_gained: row.strength_gained,
-            image_url: row.image_url,
-            video_url: row.video_url,
+        FeedPost {
+            id: row.id,
+            user_id: row.user_id,
+            username: row.username,
+            profile_picture_url: row.profile_picture_url,
+            post_type: row.post_type,
+            content: row.content,
+            workout_id: row.workout_id,
+            media_urls: row.media_urls,
+            ad_metadata: row.ad_metadata,
+            visibility: row.visibility,
+            is_editable: row.is_editable,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            edited_at: row.edited_at,
+            workout,
+            social: SocialCounts {
+                reaction_count: row.reaction_count,
+                comment_count: row.comment_count,
+                user_has_reacted: row.user_has_reacted,
+            },
+            effort_rating: row.effort_rating,
         }
-    )).collect())
+    }).collect())
 }
 
 /// Fetch live game info for given workout IDs
@@ -202,107 +208,8 @@ async fn fetch_live_game_info(
     }).collect())
 }
 
-/// Fetch social counts (reactions, comments) for given workout IDs
-async fn fetch_social_counts(
-    pool: &PgPool,
-    workout_ids: &[Uuid],
-    user_id: Uuid,
-) -> Result<HashMap<Uuid, SocialCounts>, sqlx::Error> {
-    if workout_ids.is_empty() {
-        return Ok(HashMap::new());
-    }
-
-    // Fetch reaction counts
-    let reaction_rows = sqlx::query!(
-        r#"
-        SELECT workout_id, COUNT(*) as "count!"
-        FROM post_reactions
-        WHERE workout_id = ANY($1)
-        GROUP BY workout_id
-        "#,
-        workout_ids
-    )
-    .fetch_all(pool)
-    .await?;
-
-    let mut reaction_counts: HashMap<Uuid, i64> = reaction_rows.into_iter()
-        .map(|row| (row.workout_id, row.count))
-        .collect();
-
-    // Fetch comment counts
-    let comment_rows = sqlx::query!(
-        r#"
-        SELECT workout_id, COUNT(*) as "count!"
-        FROM post_comments
-        WHERE workout_id = ANY($1)
-        GROUP BY workout_id
-        "#,
-        workout_ids
-    )
-    .fetch_all(pool)
-    .await?;
-
-    let mut comment_counts: HashMap<Uuid, i64> = comment_rows.into_iter()
-        .map(|row| (row.workout_id, row.count))
-        .collect();
-
-    // Fetch user reactions
-    let user_reaction_rows = sqlx::query!(
-        r#"
-        SELECT workout_id
-        FROM post_reactions
-        WHERE workout_id = ANY($1) AND user_id = $2
-        "#,
-        workout_ids,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
-
-    let user_reactions: std::collections::HashSet<Uuid> = user_reaction_rows.into_iter()
-        .map(|row| row.workout_id)
-        .collect();
-
-    // Combine into SocialCounts
-    Ok(workout_ids.iter().map(|&workout_id| {
-        (workout_id, SocialCounts {
-            reaction_count: reaction_counts.remove(&workout_id).unwrap_or(0),
-            comment_count: comment_counts.remove(&workout_id).unwrap_or(0),
-            user_has_reacted: user_reactions.contains(&workout_id),
-        })
-    }).collect())
-}
-
-/// Fetch effort ratings for given workout IDs for a user
-async fn fetch_effort_ratings(
-    pool: &PgPool,
-    workout_ids: &[Uuid],
-    user_id: Uuid,
-) -> Result<HashMap<Uuid, i16>, sqlx::Error> {
-    if workout_ids.is_empty() {
-        return Ok(HashMap::new());
-    }
-
-    let rows = sqlx::query!(
-        r#"
-        SELECT workout_data_id, effort_rating
-        FROM workout_scoring_feedback
-        WHERE workout_data_id = ANY($1) AND user_id = $2
-        "#,
-        workout_ids,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
-
-    Ok(rows.into_iter().map(|row| (row.workout_data_id, row.effort_rating)).collect())
-}
-
 /// Calculate engagement score for sorting
-fn calculate_engagement_score(
-    post: &FeedPost,
-    social: Option<&SocialCounts>,
-) -> i32 {
+fn calculate_engagement_score(post: &FeedPost) -> i32 {
     // Only apply engagement ranking for posts within last ENGAGEMENT_WINDOW_HOURS hours
     let now = Utc::now();
     if now.signed_duration_since(post.created_at).num_hours() > ENGAGEMENT_WINDOW_HOURS {
@@ -319,10 +226,8 @@ fn calculate_engagement_score(
     }
 
     // Social engagement
-    if let Some(s) = social {
-        score += (s.reaction_count * 2) as i32; // Reactions: 2 points each
-        score += (s.comment_count * 3) as i32;  // Comments: 3 points each
-    }
+    score += (post.social.reaction_count * 2) as i32; // Reactions: 2 points each
+    score += (post.social.comment_count * 3) as i32;  // Comments: 3 points each
 
     score
 }
@@ -375,7 +280,7 @@ pub async fn get_unified_feed(
     let mut posts = if show_ranked_section {
         // FIRST REQUEST ONLY: Fetch and rank posts from last ENGAGEMENT_WINDOW_HOURS hours
         // This is a one-time snapshot, never paginated or re-calculated
-        let all_recent = match fetch_feed_posts(&pool, None, 1000).await {
+        let all_recent = match fetch_feed_posts(&pool, None, 1000, current_user_id).await {
             Ok(p) => p,
             Err(e) => {
                 tracing::error!("Failed to fetch feed posts: {}", e);
@@ -391,7 +296,7 @@ pub async fn get_unified_feed(
             .collect::<Vec<_>>()
     } else {
         // ALL SUBSEQUENT REQUESTS: Pure chronological feed
-        match fetch_feed_posts(&pool, cursor_datetime, limit as i64).await {
+        match fetch_feed_posts(&pool, cursor_datetime, limit as i64, current_user_id).await {
             Ok(p) => p,
             Err(e) => {
                 tracing::error!("Failed to fetch chronological posts: {}", e);
@@ -421,23 +326,8 @@ pub async fn get_unified_feed(
         .filter_map(|p| p.workout_id)
         .collect();
 
-    // Step 3: Fetch all related data in parallel
-    let (workout_data_result, live_game_result, social_counts_result, effort_ratings_result) = tokio::join!(
-        fetch_workout_data(&pool, &workout_ids),
-        fetch_live_game_info(&pool, &workout_ids),
-        fetch_social_counts(&pool, &workout_ids, current_user_id),
-        fetch_effort_ratings(&pool, &workout_ids, current_user_id),
-    );
-
-    let workout_data = match workout_data_result {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Failed to fetch workout data: {}", e);
-            return HttpResponse::InternalServerError().json(json!({"error": "Failed to fetch workout data"}));
-        }
-    };
-
-    let live_game_info = match live_game_result {
+    // Step 3: Fetch live game info; workout data and social counts came with the posts
+    let live_game_info = match fetch_live_game_info(&pool, &workout_ids).await {
         Ok(d) => d,
         Err(e) => {
             tracing::error!("Failed to fetch live game info: {}", e);
@@ -445,31 +335,12 @@ pub async fn get_unified_feed(
         }
     };
 
-    let social_counts = match social_counts_result {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Failed to fetch social counts: {}", e);
-            return HttpResponse::InternalServerError().json(json!({"error": "Failed to fetch social counts"}));
-        }
-    };
-
-    let effort_ratings = match effort_ratings_result {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Failed to fetch effort ratings: {}", e);
-            return HttpResponse::InternalServerError().json(json!({"error": "Failed to fetch effort ratings"}));
-        }
-    };
-
     // Step 4: Sort and limit
     if show_ranked_section {
         // Sort by engagement score for the ranked snapshot
         posts.sort_by(|a, b| {
-            let a_social = a.workout_id.and_then(|id| social_counts.get(&id));
-            let b_social = b.workout_id.and_then(|id| social_counts.get(&id));
-
-            let a_score = calculate_engagement_score(a, a_social);
-            let b_score = calculate_engagement_score(b, b_social);
+            let a_score = calculate_engagement_score(a);
+            let b_score = calculate_engagement_score(b);
 
             b_score.cmp(&a_score)
                 .then_with(|| b.created_at.cmp(&a.created_at))
@@ -487,10 +358,8 @@ pub async fn get_unified_feed(
 
     // Step 6: Build response JSON
     let response_posts: Vec<serde_json::Value> = posts.iter().map(|post| {
-        let workout_info = post.workout_id.and_then(|id| workout_data.get(&id));
         let game_info = post.workout_id.and_then(|id| live_game_info.get(&id));
-        let social = post.workout_id.and_then(|id| social_counts.get(&id));
-        let effort = post.workout_id.and_then(|id| effort_ratings.get(&id));
+        let effort = post.effort_rating;
 
         json!({
             "id": post.id,
@@ -508,13 +377,15 @@ pub async fn get_unified_feed(
             "updated_at": post.updated_at,
             "edited_at": post.edited_at,
 
-            "workout_data": workout_info.map(|wd| json!({
+            "workout_data": post.workout.as_ref().map(|wd| json!({
                 "workout_start": wd.workout_start,
                 "workout_end": wd.workout_end,
                 "duration_minutes": wd.duration_minutes,
                 "calories_burned": wd.calories_burned,
                 "activity_name": wd.activity_name,
                 "user_activity": wd.user_activity,
+                // The feed only carries public posts, so notes are visible to everyone
+                "notes": wd.notes,
                 "avg_heart_rate": wd.avg_heart_rate,
                 "max_heart_rate": wd.max_heart_rate,
                 "heart_rate_zones": wd.heart_rate_zones,
@@ -531,9 +402,9 @@ pub async fn get_unified_feed(
                 "opponent_team_name": gi.opponent_team_name,
             })),
 
-            "reaction_count": social.map(|s| s.reaction_count).unwrap_or(0),
-            "comment_count": social.map(|s| s.comment_count).unwrap_or(0),
-            "user_has_reacted": social.map(|s| s.user_has_reacted).unwrap_or(false),
+            "reaction_count": post.social.reaction_count,
+            "comment_count": post.social.comment_count,
+            "user_has_reacted": post.social.user_has_reacted,
 
             "effort_rating": effort,
             "needs_effort_rating": post.workout_id.is_some() && post.user_id == current_user_id && effort.is_none(),
@@ -0,0 +1,42 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+
+use crate::graphql::{loaders::Loaders, RiinaSchema, Viewer};
+use crate::middleware::auth::Claims;
+
+/// POST /graphql - Execute a GraphQL query as the authenticated user
+#[tracing::instrument(
+    name = "GraphQL query",
+    skip(schema, pool, claims, request),
+    fields(username = %claims.username, operation = ?request.operation_name)
+)]
+pub async fn graphql(
+    schema: web::Data<RiinaSchema>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<async_graphql::Request>,
+) -> Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    let request = request
+        .into_inner()
+        .data(Viewer { user_id, role: claims.role.clone() })
+        .data(Loaders::new(pool.get_ref().clone()));
+
+    let response = schema.execute(request).await;
+    for error in &response.errors {
+        tracing::warn!("GraphQL error: {}", error.message);
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// GET /graphql/schema - The schema in SDL, for client code generation
+pub async fn graphql_schema(schema: web::Data<RiinaSchema>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(schema.sdl())
+}
@@ -0,0 +1,70 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::game::boosters::BoosterType;
+use crate::middleware::auth::Claims;
+use crate::models::booster::ActivateBoosterRequest;
+use crate::models::common::ApiResponse;
+use crate::services::booster_service::BoosterError;
+use crate::services::BoosterService;
+
+/// GET /league/games/{game_id}/boosters - Boosters used in the game and the caller's team inventory
+pub async fn get_game_boosters(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = BoosterService::new(pool.get_ref().clone(), None);
+    match service.game_boosters(game_id, user_id).await {
+        Ok(Some(boosters)) => Ok(HttpResponse::Ok().json(ApiResponse::success("Game boosters retrieved successfully", boosters))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Game not found"))),
+        Err(e) => {
+            tracing::error!("Failed to fetch boosters of game {}: {}", game_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to fetch game boosters")))
+        }
+    }
+}
+
+/// POST /league/games/{game_id}/boosters - Activate one of the team's boosters in a live game
+pub async fn activate_booster(
+    pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
+    path: web::Path<Uuid>,
+    body: web::Json<ActivateBoosterRequest>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let Some(booster_type) = BoosterType::parse(&body.booster_type) else {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(format!(
+            "Unknown booster type {}. Supported: double_next_workout, shield",
+            body.booster_type
+        ))));
+    };
+
+    let service = BoosterService::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()));
+    match service.activate(game_id, user_id, booster_type).await {
+        Ok(booster) => Ok(HttpResponse::Created().json(ApiResponse::success("Booster activated", booster))),
+        Err(e @ BoosterError::GameNotFound) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error(e.to_string()))),
+        Err(e @ BoosterError::NotAPlayer) => Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error(e.to_string()))),
+        Err(e @ (BoosterError::GameNotLive | BoosterError::NoneAvailable | BoosterError::AlreadyActive)) => {
+            Ok(HttpResponse::Conflict().json(ApiResponse::<()>::error(e.to_string())))
+        }
+        Err(BoosterError::Database(e)) => {
+            tracing::error!("Failed to activate booster in game {} for user {}: {}", game_id, user_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to activate booster")))
+        }
+    }
+}
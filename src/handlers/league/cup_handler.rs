@@ -0,0 +1,43 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::league::cups::{CupError, CupService};
+use crate::models::common::ApiResponse;
+
+pub(crate) fn cup_error_response(e: CupError) -> HttpResponse {
+    match e {
+        e @ (CupError::SeasonNotFound | CupError::NotFound) => {
+            HttpResponse::NotFound().json(ApiResponse::<()>::error(e.to_string()))
+        }
+        e @ CupError::InvalidTeams(_) => HttpResponse::BadRequest().json(ApiResponse::<()>::error(e.to_string())),
+        CupError::Database(e) => {
+            tracing::error!("Cup database error: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error"))
+        }
+    }
+}
+
+/// Cups played alongside the season's league
+pub async fn get_season_cups(pool: web::Data<PgPool>, season_id: web::Path<Uuid>) -> HttpResponse {
+    match CupService::new(pool.get_ref().clone()).list_cups(season_id.into_inner()).await {
+        Ok(cups) => HttpResponse::Ok().json(ApiResponse::success("Cups retrieved", cups)),
+        Err(e) => cup_error_response(e.into()),
+    }
+}
+
+/// A cup's seeds and the games of every round drawn so far
+pub async fn get_cup_bracket(pool: web::Data<PgPool>, cup_id: web::Path<Uuid>) -> HttpResponse {
+    match CupService::new(pool.get_ref().clone()).get_bracket(cup_id.into_inner()).await {
+        Ok(bracket) => HttpResponse::Ok().json(ApiResponse::success("Cup retrieved", bracket)),
+        Err(e) => cup_error_response(e),
+    }
+}
+
+/// League and cup games of the season together, in kickoff order
+pub async fn get_season_calendar(pool: web::Data<PgPool>, season_id: web::Path<Uuid>) -> HttpResponse {
+    match CupService::new(pool.get_ref().clone()).season_calendar(season_id.into_inner()).await {
+        Ok(games) => HttpResponse::Ok().json(ApiResponse::success("Calendar retrieved", games)),
+        Err(e) => cup_error_response(e),
+    }
+}
@@ -3,10 +3,12 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use serde_json::json;
 
+use crate::db::game_repo::{GameRepo, GameRepository};
 use crate::league::league::LeagueService;
 use crate::middleware::auth::Claims;
 use crate::models::league::*;
 use crate::services::game_summary_service::GameSummaryService;
+use crate::services::FormationBonusService;
 
 /// Update game result
 #[tracing::instrument(
@@ -123,6 +125,41 @@ pub async fn get_league_live_games(
     }
 }
 
+/// Get the UTC window in which the current user's workouts score
+#[tracing::instrument(
+    name = "Get next game window",
+    skip(pool, claims),
+    fields(username = %claims.username)
+)]
+pub async fn get_next_game_window(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "message": "Invalid user ID"
+        })));
+    };
+
+    let league_service = LeagueService::new(pool.get_ref().clone());
+
+    match league_service.get_next_game_window(user_id).await {
+        Ok(window) => Ok(HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": window
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get next game window for user {}: {}", user_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to retrieve game window"
+            })))
+        }
+    }
+}
+
 #[tracing::instrument(
     name = "Get game countdown",
     skip(query, pool),
@@ -173,28 +210,22 @@ pub async fn get_game_summary(
     match summary_service.get_game_summary(*game_id).await {
         Ok(Some(summary)) => {
             // Get team names from the database
-            let game = sqlx::query!(
-                r#"
-                SELECT
-                    g.id,
-                    ht.team_name as home_team_name,
-                    at.team_name as away_team_name
-                FROM games g
-                JOIN teams ht ON g.home_team_id = ht.id
-                JOIN teams at ON g.away_team_id = at.id
-                WHERE g.id = $1
-                "#,
-                *game_id
-            )
-            .fetch_optional(pool.get_ref())
-            .await;
+            let game = GameRepo::new(pool.get_ref().clone()).find_live_game_state(*game_id).await;
 
             match game {
                 Ok(Some(game_data)) => {
+                    let formation_bonuses = FormationBonusService::new(pool.get_ref().clone())
+                        .game_bonuses(*game_id)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("Failed to fetch formation bonuses for game {}: {}", game_id, e);
+                            Vec::new()
+                        });
                     let response = GameSummaryResponse {
                         summary,
                         home_team_name: game_data.home_team_name,
                         away_team_name: game_data.away_team_name,
+                        formation_bonuses,
                     };
 
                     Ok(HttpResponse::Ok().json(json!({
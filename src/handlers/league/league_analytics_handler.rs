@@ -0,0 +1,56 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::league_analytics::LeagueAnalyticsQuery;
+use crate::models::user::UserRole;
+use crate::services::LeagueAnalyticsService;
+
+/// GET /league/{league_id}/analytics - Participation, scores and drop-off of a league season.
+/// Only admins and owners of the league's teams may see it.
+pub async fn get_league_analytics(
+    pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
+    path: web::Path<Uuid>,
+    query: web::Query<LeagueAnalyticsQuery>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = LeagueAnalyticsService::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()));
+
+    if !matches!(claims.role, UserRole::Admin) {
+        match service.is_captain(league_id, user_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                    "Only admins and team captains of this league can view its analytics",
+                )));
+            }
+            Err(e) => {
+                tracing::error!("Failed to check captaincy of user {} in league {}: {}", user_id, league_id, e);
+                return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify permissions")));
+            }
+        }
+    }
+
+    match service.get(league_id, query.season_id).await {
+        Ok(Some(analytics)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            "League analytics retrieved successfully",
+            analytics,
+        ))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("No season found for this league"))),
+        Err(e) => {
+            tracing::error!("Failed to compute analytics of league {}: {}", league_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to compute league analytics")))
+        }
+    }
+}
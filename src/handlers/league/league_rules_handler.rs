@@ -0,0 +1,23 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::common::ApiResponse;
+use crate::services::LeagueRulesService;
+
+/// GET /league/{league_id}/rules - The league's rules page: scoring, caps, forfeits and transfer windows
+pub async fn get_league_rules(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    match LeagueRulesService::new(pool.get_ref().clone()).page(league_id).await {
+        Ok(Some(rules)) => Ok(HttpResponse::Ok().json(ApiResponse::success("League rules retrieved successfully", rules))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("League not found"))),
+        Err(e) => {
+            tracing::error!("Failed to load rules of league {}: {}", league_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to load league rules")))
+        }
+    }
+}
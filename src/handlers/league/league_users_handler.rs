@@ -5,14 +5,19 @@ use serde_json::json;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
+use std::sync::Arc;
+
 use crate::middleware::auth::Claims;
 use crate::models::team::TeamRole;
 use crate::models::common::PlayerStats;
-use crate::utils::trailing_average;
+use crate::services::UserStatsCache;
 
 /// Fetch ALL eligible users for the leaderboard (team members + free agents)
 /// This function ensures mutual exclusivity: users in teams are NOT included as free agents
-pub async fn fetch_all_leaderboard_users(pool: &PgPool) -> Result<Vec<LeagueUserWithStats>, sqlx::Error> {
+pub async fn fetch_all_leaderboard_users(
+    pool: &PgPool,
+    stats_cache: &UserStatsCache,
+) -> Result<Vec<LeagueUserWithStats>, sqlx::Error> {
     // Use a UNION query to get both team members and free agents
     // Free agents are excluded if they have ANY team membership (to prevent duplicates)
     let all_users = sqlx::query!(
@@ -67,8 +72,8 @@ pub async fn fetch_all_leaderboard_users(pool: &PgPool) -> Result<Vec<LeagueUser
     // Extract user IDs for batch trailing average calculation
     let user_ids: Vec<Uuid> = all_users.iter().filter_map(|row| row.user_id).collect();
 
-    // Calculate trailing averages for all users in batch
-    let trailing_averages = trailing_average::calculate_trailing_averages_batch(pool, &user_ids)
+    // Trailing averages for all users in batch, from the stats cache where possible
+    let activity_stats = stats_cache.get_many(&user_ids)
         .await
         .unwrap_or_default();
 
@@ -80,7 +85,7 @@ pub async fn fetch_all_leaderboard_users(pool: &PgPool) -> Result<Vec<LeagueUser
         let username = row.username?;
         let email = row.email?;
 
-        let trailing_avg = trailing_averages.get(&user_id).copied().unwrap_or(0.0);
+        let trailing_avg = activity_stats.get(&user_id).map(|stats| stats.trailing_average).unwrap_or(0.0);
 
         Some(LeagueUserWithStats {
             user_id,
@@ -168,13 +173,14 @@ pub struct PaginationParams {
 /// This endpoint returns all users who are members of teams in active leagues
 #[tracing::instrument(
     name = "Get league users with stats",
-    skip(pool, claims),
+    skip(pool, redis, claims),
     fields(
         username = %claims.username
     )
 )]
 pub async fn get_league_users_with_stats(
     pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
     claims: web::ReqData<Claims>,
     query: web::Query<PaginationParams>,
 ) -> Result<HttpResponse> {
@@ -215,7 +221,8 @@ pub async fn get_league_users_with_stats(
     let page_size = query.page_size.unwrap_or(20).clamp(1, 200); // Default 20, max 200
 
     // Fetch ALL leaderboard users (team members + free agents, ensuring no duplicates)
-    let all_users = match fetch_all_leaderboard_users(pool.get_ref()).await {
+    let stats_cache = UserStatsCache::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()));
+    let all_users = match fetch_all_leaderboard_users(pool.get_ref(), &stats_cache).await {
         Ok(users) => users,
         Err(e) => {
             tracing::error!("Failed to fetch league users with stats: {}", e);
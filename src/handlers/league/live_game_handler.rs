@@ -1,10 +1,16 @@
 use actix_web::{web, HttpResponse, Result};
 use sqlx::PgPool;
 use uuid::Uuid;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
 
-use crate::services::ManageGameService;
+use crate::db::fixture_flavor;
+use crate::db::game_repo::{GameRepo, GameRepository};
+use crate::game::commentary;
+use crate::models::commentary::CommentaryMilestone;
+use crate::league::team_kits::GameKit;
+use crate::services::{GameCommentaryService, ManageGameService, TeamKitService};
 use crate::middleware::auth::Claims;
 use crate::models::league::PaginationQuery;
 // Removed unused import: use crate::db::game_queries::GameQueries;
@@ -91,23 +97,7 @@ pub async fn get_game_live_score(
     let offset = (page - 1) * limit;
     
     // Get game info with live scoring data from unified games table
-    let game = sqlx::query!(
-        r#"
-        SELECT 
-            g.id, g.week_number, g.status,
-            g.home_score, g.away_score,
-            g.game_start_time, g.game_end_time,
-            ht.team_name as home_team_name,
-            at.team_name as away_team_name
-        FROM games g
-        JOIN teams ht ON g.home_team_id = ht.id
-        JOIN teams at ON g.away_team_id = at.id
-        WHERE g.id = $1
-        "#,
-        game_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await;
+    let game = GameRepo::new(pool.get_ref().clone()).find_live_game_state(game_id).await;
 
     match game {
         Ok(Some(game_data)) => {
@@ -136,6 +126,7 @@ pub async fn get_game_live_score(
                     lse.occurred_at, lse.event_type::text as "event_type!", lse.description,
                     lse.username, lse.team_id, lse.team_side, lse.workout_data_id,
                     lse.stamina_gained, lse.strength_gained,
+                    lse.comeback_multiplier, lse.comeback_bonus, lse.booster_bonus,
                     u.profile_picture_url as "profile_picture_url?",
                     wd.id as "workout_id?", wd.created_at as "workout_date?",
                     wd.workout_start as "workout_start?", wd.workout_end as "workout_end?",
@@ -176,7 +167,10 @@ pub async fn get_game_live_score(
                         "score_points": event.score_points,
                         "occurred_at": event.occurred_at,
                         "event_type": event.event_type.to_string(),
-                        "description": event.description
+                        "description": event.description,
+                        "comeback_multiplier": event.comeback_multiplier,
+                        "comeback_bonus": event.comeback_bonus,
+                        "booster_bonus": event.booster_bonus
                     });
                     
                     // Add workout details if available
@@ -206,11 +200,20 @@ pub async fn get_game_live_score(
                 .collect();
 
             let total_pages = ((total_count as f64) / (limit as f64)).ceil() as i64;
+            let (home_kit, away_kit) = load_game_kits(pool.get_ref(), game_id).await;
+            let (week_name, fixture) = fixture_flavor::game_flavor(pool.get_ref(), game_id).await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to load the flavor of game {}: {}", game_id, e);
+                (None, None)
+            });
             
             let mut game_info = serde_json::json!({
                 "game_id": game_id,
                 "home_team_name": game_data.home_team_name,
                 "away_team_name": game_data.away_team_name,
+                "home_kit": home_kit,
+                "away_kit": away_kit,
+                "week_name": week_name,
+                "fixture": fixture,
                 "home_score": home_score,
                 "away_score": away_score,
                 "week_number": game_data.week_number,
@@ -255,6 +258,19 @@ pub async fn get_game_live_score(
     }
 }
 
+/// Kits the teams wear in the game, so clients color the ticker consistently. Missing kits
+/// leave the client's default colors instead of failing the payload.
+async fn load_game_kits(pool: &PgPool, game_id: Uuid) -> (Option<GameKit>, Option<GameKit>) {
+    match TeamKitService::new(pool.clone()).game_kits(game_id).await {
+        Ok(Some((home_kit, away_kit))) => (Some(home_kit), Some(away_kit)),
+        Ok(None) => (None, None),
+        Err(e) => {
+            tracing::warn!("Failed to load the kits of game {}: {}", game_id, e);
+            (None, None)
+        }
+    }
+}
+
 /// Admin endpoint to manually trigger game management cycle
 pub async fn manage_games(
     pool: web::Data<PgPool>,
@@ -339,23 +355,7 @@ pub async fn get_game_player_scores(
     let game_id = path.into_inner();
 
     // Get game info to verify it exists and get team names
-    let game = sqlx::query!(
-        r#"
-        SELECT
-            g.id,
-            ht.id as home_team_id,
-            ht.team_name as home_team_name,
-            at.id as away_team_id,
-            at.team_name as away_team_name
-        FROM games g
-        JOIN teams ht ON g.home_team_id = ht.id
-        JOIN teams at ON g.away_team_id = at.id
-        WHERE g.id = $1
-        "#,
-        game_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await;
+    let game = GameRepo::new(pool.get_ref().clone()).find_live_game_state(game_id).await;
 
     match game {
         Ok(Some(game_data)) => {
@@ -428,4 +428,223 @@ pub async fn get_game_player_scores(
             })))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Width of a timeline bucket in minutes
+const TIMELINE_BUCKET_MINUTES: i32 = 5;
+
+#[derive(Serialize)]
+pub struct TimelineScorer {
+    pub user_id: Uuid,
+    pub username: String,
+    pub team_side: String,
+    pub points: f64,
+    pub event_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct TimelineBucket {
+    pub bucket_start: DateTime<Utc>,
+    pub bucket_end: DateTime<Utc>,
+    pub home_points: f64,
+    pub away_points: f64,
+    pub event_count: i64,
+    /// Team scores at the end of the bucket, computed the same way as the game score
+    pub home_score: i32,
+    pub away_score: i32,
+    pub scorers: Vec<TimelineScorer>,
+}
+
+/// GET /league/games/{game_id}/timeline - Get the game's score progression
+/// Scoring events of both teams are merged into 5-minute buckets (empty buckets are omitted)
+/// with the cumulative score after each bucket, so charts don't need every raw event
+pub async fn get_game_timeline(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    _claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+
+    let game = GameRepo::new(pool.get_ref().clone()).find_live_game_state(game_id).await;
+
+    let game_data = match game {
+        Ok(Some(game_data)) => game_data,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "success": false,
+                "error": "Game not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get game {} for timeline: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to get game timeline"
+            })));
+        }
+    };
+
+    // Buckets are aligned to the game start so the first bucket starts with the game
+    let origin = game_data.game_start_time.unwrap_or(DateTime::UNIX_EPOCH);
+
+    let rows = match sqlx::query!(
+        r#"
+        SELECT
+            date_bin(make_interval(mins => $3), lse.occurred_at, $2) as "bucket_start!",
+            lse.user_id,
+            lse.username,
+            lse.team_side,
+            SUM(lse.score_points)::float8 as "points!",
+            COUNT(*) as "event_count!"
+        FROM live_score_events lse
+        WHERE lse.game_id = $1
+        GROUP BY 1, lse.user_id, lse.username, lse.team_side
+        ORDER BY 1, 5 DESC
+        "#,
+        game_id,
+        origin,
+        TIMELINE_BUCKET_MINUTES
+    )
+    .fetch_all(pool.get_ref())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("Failed to get timeline events for game {}: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to get game timeline"
+            })));
+        }
+    };
+
+    let mut buckets: Vec<TimelineBucket> = Vec::new();
+    let mut home_total = 0.0_f64;
+    let mut away_total = 0.0_f64;
+
+    for row in rows {
+        if buckets.last().map(|b| b.bucket_start) != Some(row.bucket_start) {
+            buckets.push(TimelineBucket {
+                bucket_start: row.bucket_start,
+                bucket_end: row.bucket_start + Duration::minutes(TIMELINE_BUCKET_MINUTES as i64),
+                home_points: 0.0,
+                away_points: 0.0,
+                event_count: 0,
+                home_score: home_total as i32,
+                away_score: away_total as i32,
+                scorers: Vec::new(),
+            });
+        }
+        let bucket = buckets.last_mut().expect("bucket was just pushed");
+
+        match row.team_side.as_str() {
+            "home" => {
+                bucket.home_points += row.points;
+                home_total += row.points;
+            }
+            "away" => {
+                bucket.away_points += row.points;
+                away_total += row.points;
+            }
+            _ => {}
+        }
+        bucket.event_count += row.event_count;
+        bucket.home_score = home_total as i32;
+        bucket.away_score = away_total as i32;
+        bucket.scorers.push(TimelineScorer {
+            user_id: row.user_id,
+            username: row.username,
+            team_side: row.team_side,
+            points: row.points,
+            event_count: row.event_count,
+        });
+    }
+
+    let (home_kit, away_kit) = load_game_kits(pool.get_ref(), game_id).await;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "data": {
+            "game_id": game_id,
+            "home_team_name": game_data.home_team_name,
+            "away_team_name": game_data.away_team_name,
+            "home_kit": home_kit,
+            "away_kit": away_kit,
+            "home_score": game_data.home_score,
+            "away_score": game_data.away_score,
+            "status": game_data.status,
+            "game_start_time": game_data.game_start_time,
+            "game_end_time": game_data.game_end_time,
+            "bucket_minutes": TIMELINE_BUCKET_MINUTES,
+            "buckets": buckets
+        }
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct GameCommentaryQuery {
+    /// Render the lines in this locale instead of the league's
+    pub locale: Option<String>,
+}
+
+/// GET /league/games/{game_id}/commentary - Automated commentary of a game, oldest first
+pub async fn get_game_commentary(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<GameCommentaryQuery>,
+    _claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+
+    if let Some(locale) = &query.locale {
+        if !commentary::is_supported_locale(locale) {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": format!("Unsupported locale. Supported: {}", commentary::SUPPORTED_LOCALES.join(", "))
+            })));
+        }
+    }
+
+    match GameRepo::new(pool.get_ref().clone()).game_exists(game_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "success": false,
+                "error": "Game not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get game {} for commentary: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to get game commentary"
+            })));
+        }
+    }
+
+    let service = GameCommentaryService::new(pool.get_ref().clone(), None);
+    let mut lines = match service.get_game_commentary(game_id).await {
+        Ok(lines) => lines,
+        Err(e) => {
+            tracing::error!("Failed to get commentary for game {}: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to get game commentary"
+            })));
+        }
+    };
+
+    if let Some(locale) = &query.locale {
+        for line in lines.iter_mut().filter(|line| &line.locale != locale) {
+            if let (Some(milestone), Some(params)) = (CommentaryMilestone::parse(&line.milestone), line.params.as_object()) {
+                line.message = commentary::render(milestone, locale, params);
+                line.locale = locale.clone();
+            }
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "data": lines
+    })))
+}
@@ -9,4 +9,13 @@ pub mod live_game_handler;
 pub mod player_pool_handler;
 pub mod team_invitation_handler;
 pub mod team_poll_handler;
-pub mod chat_handler;
\ No newline at end of file
+pub mod chat_handler;
+pub mod zone_stats_handler;
+pub mod team_activity_handler;
+pub mod league_analytics_handler;
+pub mod booster_handler;
+pub mod quest_handler;
+pub mod team_notification_handler;
+pub mod league_rules_handler;
+pub mod spectator_handler;
+pub mod cup_handler;
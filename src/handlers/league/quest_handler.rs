@@ -0,0 +1,48 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::user::UserRole;
+use crate::services::LeagueQuestService;
+
+/// GET /league/{league_id}/quests - The league's quests with collective progress and milestones.
+/// Visible to players of the league and admins.
+pub async fn get_league_quests(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let league_id = path.into_inner();
+
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = LeagueQuestService::new(pool.get_ref().clone());
+
+    if !matches!(claims.role, UserRole::Admin) {
+        match service.is_member(league_id, user_id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                    "Only players of this league can view its quests",
+                )));
+            }
+            Err(e) => {
+                tracing::error!("Failed to check membership of user {} in league {}: {}", user_id, league_id, e);
+                return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify permissions")));
+            }
+        }
+    }
+
+    match service.list(league_id).await {
+        Ok(quests) => Ok(HttpResponse::Ok().json(ApiResponse::success("League quests retrieved successfully", quests))),
+        Err(e) => {
+            tracing::error!("Failed to load quests of league {}: {}", league_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to load league quests")))
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use actix_web::{web, HttpResponse, Result};
 use sqlx::PgPool;
 use uuid::Uuid;
+use serde::Deserialize;
 use serde_json::json;
 use crate::league::league::LeagueService;
 use crate::models::league::{LeagueSeason, PaginationQuery};
+use crate::league::seasons::SeasonService;
+use crate::services::{MinIOService, SeasonRecapService, SeasonStatsService};
 
 /// Get active league season
 pub async fn get_active_league_season(
@@ -140,4 +143,161 @@ pub async fn get_league_standings(
             })))
         }
     }
-}
\ No newline at end of file
+}
+#[derive(Deserialize)]
+pub struct RecapCardQuery {
+    /// Team whose card to fetch; the league card without it
+    pub team_id: Option<Uuid>,
+}
+
+/// Get the season recap: the league-wide recap and one per team in standings order
+pub async fn get_season_recap(
+    season_id: Uuid,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let recap_service = SeasonRecapService::new(pool.get_ref().clone());
+
+    match recap_service.get_recaps(season_id).await {
+        Ok(recaps) if recaps.is_empty() => {
+            Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "message": "No recap for this season yet. Recaps are written once every game has been evaluated."
+            })))
+        }
+        Ok(recaps) => {
+            let (league, teams): (Vec<_>, Vec<_>) = recaps.into_iter().partition(|r| r.team_id.is_none());
+            Ok(HttpResponse::Ok().json(json!({
+                "success": true,
+                "data": {
+                    "league": league.into_iter().next(),
+                    "teams": teams
+                }
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to get recap for season {}: {}", season_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to retrieve season recap"
+            })))
+        }
+    }
+}
+
+/// Get league-wide season stats for media and recap pages, kept up to date as games are evaluated
+pub async fn get_season_stats(
+    season_id: Uuid,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    match SeasonService::new(pool.get_ref().clone()).get_season(season_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "message": "Season not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get season {}: {}", season_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to retrieve season stats"
+            })));
+        }
+    }
+
+    match SeasonStatsService::new(pool.get_ref().clone()).get_stats(season_id).await {
+        Ok(stats) => {
+            Ok(HttpResponse::Ok().json(json!({
+                "success": true,
+                "data": stats
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to get stats for season {}: {}", season_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to retrieve season stats"
+            })))
+        }
+    }
+}
+
+/// Queue shareable cards for a season's recaps; the image-render job picks them up
+pub async fn request_season_recap_cards(
+    season_id: Uuid,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let recap_service = SeasonRecapService::new(pool.get_ref().clone());
+
+    match recap_service.get_recaps(season_id).await {
+        Ok(recaps) if recaps.is_empty() => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "message": "No recap for this season yet"
+            })));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to get recap for season {}: {}", season_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to queue recap cards"
+            })));
+        }
+    }
+
+    match recap_service.request_cards(season_id).await {
+        Ok(queued) => {
+            Ok(HttpResponse::Accepted().json(json!({
+                "success": true,
+                "data": { "cards_queued": queued }
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to queue recap cards for season {}: {}", season_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to queue recap cards"
+            })))
+        }
+    }
+}
+
+/// Download a rendered recap card
+pub async fn get_season_recap_card(
+    season_id: Uuid,
+    query: web::Query<RecapCardQuery>,
+    pool: web::Data<PgPool>,
+    minio_service: web::Data<MinIOService>,
+) -> Result<HttpResponse> {
+    let recap_service = SeasonRecapService::new(pool.get_ref().clone());
+
+    let object_key = match recap_service.get_card_object_key(season_id, query.team_id).await {
+        Ok(Some(object_key)) => object_key,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "message": "Card not rendered"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get recap card for season {}: {}", season_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "message": "Failed to retrieve recap card"
+            })));
+        }
+    };
+
+    match minio_service.get_file(&object_key).await {
+        Ok((bytes, content_type)) => Ok(HttpResponse::Ok().content_type(content_type).body(bytes)),
+        Err(e) => {
+            tracing::error!("Failed to download recap card {}: {}", object_key, e);
+            Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "message": "Card not found"
+            })))
+        }
+    }
+}
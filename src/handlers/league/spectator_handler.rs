@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use redis::{AsyncCommands, RedisResult};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::jwt::JwtSettings;
+use crate::db::game_repo::{GameRepo, GameRepository};
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::game_events::StateSyncReason;
+use crate::services::spectator_token_service::{issue_spectator_token, verify_spectator_token, SPECTATOR_TOKENS_PER_HOUR};
+use crate::services::LiveStateSyncService;
+
+#[derive(Debug, Serialize)]
+pub struct SpectatorTokenResponse {
+    pub token: String,
+    pub game_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpectatorQuery {
+    pub token: String,
+}
+
+/// Hand out a short-lived, read-only token for following a game that hasn't finished, e.g. to put
+/// in a shared live game link. It opens `/game-ws` and the game's public live score, nothing else.
+/// Only users create them, at most `SPECTATOR_TOKENS_PER_HOUR` each.
+#[tracing::instrument(name = "Create spectator token", skip(pool, redis_client, jwt_settings, claims), fields(game_id = %game_id, username = %claims.username))]
+pub async fn create_spectator_token(
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<redis::Client>>,
+    jwt_settings: web::Data<JwtSettings>,
+    game_id: web::Path<Uuid>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let game_id = game_id.into_inner();
+
+    if !allow_token_request(&redis_client, &claims.sub).await {
+        tracing::warn!("User {} hit the spectator token limit", claims.sub);
+        return HttpResponse::TooManyRequests()
+            .json(ApiResponse::<()>::error("Too many spectator tokens created, try again later"));
+    }
+
+    let game = match GameRepo::new(pool.get_ref().clone()).find_live_game_state(game_id).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return HttpResponse::NotFound().json(ApiResponse::<()>::error("Game not found")),
+        Err(e) => {
+            tracing::error!("Failed to fetch game {}: {}", game_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create spectator token"));
+        }
+    };
+    if !matches!(game.status.as_str(), "scheduled" | "in_progress") {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Only upcoming and live games can be spectated"));
+    }
+
+    match issue_spectator_token(&jwt_settings, game_id, Utc::now()) {
+        Ok((token, expires_at)) => HttpResponse::Ok().json(ApiResponse::success(
+            "Spectator token created",
+            SpectatorTokenResponse { token, game_id, expires_at },
+        )),
+        Err(e) => {
+            tracing::error!("Failed to sign spectator token for game {}: {:?}", game_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create spectator token"))
+        }
+    }
+}
+
+/// Count a spectator token request of the user in the current hour and tell whether it may be
+/// served. Lets requests through while Redis is down.
+async fn allow_token_request(redis_client: &redis::Client, user_id: &str) -> bool {
+    let key = format!("spectator_tokens:{user_id}");
+    let allowed = async {
+        let mut conn = redis_client.get_multiplexed_async_connection().await?;
+        let requests: u32 = conn.incr(&key, 1).await?;
+        if requests == 1 {
+            conn.expire::<_, ()>(&key, 3600).await?;
+        }
+        RedisResult::Ok(requests <= SPECTATOR_TOKENS_PER_HOUR)
+    }
+    .await;
+
+    allowed.unwrap_or_else(|e| {
+        tracing::error!("Failed to count spectator token request, letting it through: {}", e);
+        true
+    })
+}
+
+/// Live score and recent scoring events of the game a spectator token was issued for
+#[tracing::instrument(name = "Get spectated game", skip(pool, jwt_settings, query), fields(game_id = %game_id))]
+pub async fn get_spectated_game(
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+    game_id: web::Path<Uuid>,
+    query: web::Query<SpectatorQuery>,
+) -> HttpResponse {
+    let game_id = game_id.into_inner();
+
+    let claims = match verify_spectator_token(&jwt_settings, &query.token) {
+        Ok(claims) => claims,
+        Err(e) => {
+            tracing::warn!("Invalid spectator token: {:?}", e);
+            return HttpResponse::Unauthorized().json(ApiResponse::<()>::error("Invalid spectator token"));
+        }
+    };
+    if claims.game_id != game_id {
+        return HttpResponse::Forbidden().json(ApiResponse::<()>::error("The token is for another game"));
+    }
+
+    match LiveStateSyncService::new(pool.get_ref().clone())
+        .game_state_sync(game_id, StateSyncReason::Requested)
+        .await
+    {
+        Ok(state_sync) => HttpResponse::Ok().json(ApiResponse::success("Live game", state_sync)),
+        Err(e) => {
+            tracing::error!("Failed to load live state of game {}: {}", game_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to load game"))
+        }
+    }
+}
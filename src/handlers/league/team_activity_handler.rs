@@ -0,0 +1,239 @@
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::middleware::auth::Claims;
+use crate::models::user::UserRole;
+
+/// Days shown when the team has no game in progress
+const DEFAULT_WINDOW_DAYS: i64 = 7;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HeatmapRange {
+    /// The window of the team's game in progress, or the last 7 days
+    #[default]
+    GameWeek,
+    /// The team's current season up to today
+    Season,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityHeatmapQuery {
+    #[serde(default)]
+    pub range: HeatmapRange,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemberActivityRow {
+    pub user_id: Uuid,
+    pub username: String,
+    pub role: String,
+    /// Workouts per day, aligned with the response's `days`
+    pub workouts: Vec<i64>,
+    /// Training minutes per day, aligned with the response's `days`
+    pub minutes: Vec<i64>,
+    pub total_workouts: i64,
+    pub active_days: usize,
+    pub last_workout_at: Option<DateTime<Utc>>,
+}
+
+/// GET /league/teams/{team_id}/activity-heatmap - Per-member × per-day workout matrix
+/// for the current game week (default) or season, visible to team members and admins
+pub async fn get_team_activity_heatmap(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<ActivityHeatmapQuery>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let team_id = path.into_inner();
+
+    let Some(requester_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "error": "Invalid user ID"
+        })));
+    };
+
+    let team = match sqlx::query!(
+        r#"
+        SELECT
+            t.id,
+            t.team_name,
+            EXISTS(SELECT 1 FROM team_members tm WHERE tm.team_id = t.id AND tm.user_id = $2 AND tm.status = 'active') as "is_member!"
+        FROM teams t
+        WHERE t.id = $1
+        "#,
+        team_id,
+        requester_id
+    )
+    .fetch_optional(pool.get_ref())
+    .await
+    {
+        Ok(Some(team)) => team,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "error": "Team not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get team {} for activity heatmap: {}", team_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get activity heatmap"
+            })));
+        }
+    };
+
+    if !team.is_member && !matches!(claims.role, UserRole::Admin) {
+        return Ok(HttpResponse::Forbidden().json(json!({
+            "success": false,
+            "error": "You must be a team member to view team activity"
+        })));
+    }
+
+    let today = Utc::now().date_naive();
+    let window = match query.range {
+        HeatmapRange::GameWeek => current_game_window(pool.get_ref(), team_id).await,
+        HeatmapRange::Season => current_season_window(pool.get_ref(), team_id, today).await,
+    };
+    let (game_id, from, to) = match window {
+        Ok(Some(window)) => window,
+        Ok(None) => (None, today - Duration::days(DEFAULT_WINDOW_DAYS - 1), today),
+        Err(e) => {
+            tracing::error!("Failed to get heatmap window for team {}: {}", team_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get activity heatmap"
+            })));
+        }
+    };
+
+    let cells = match sqlx::query!(
+        r#"
+        SELECT
+            tm.user_id,
+            u.username,
+            tm.role,
+            COUNT(wd.id) as "workouts!",
+            COALESCE(SUM(wd.duration_minutes), 0)::bigint as "minutes!",
+            (SELECT MAX(w.workout_start) FROM workout_data w WHERE w.user_id = tm.user_id) as last_workout_at
+        FROM team_members tm
+        JOIN users u ON u.id = tm.user_id
+        CROSS JOIN generate_series($2::date, $3::date, INTERVAL '1 day') d(day)
+        LEFT JOIN workout_data wd
+            ON wd.user_id = tm.user_id
+            AND wd.workout_start >= d.day AT TIME ZONE 'UTC'
+            AND wd.workout_start < (d.day + INTERVAL '1 day') AT TIME ZONE 'UTC'
+        WHERE tm.team_id = $1 AND tm.status = 'active'
+        GROUP BY tm.user_id, u.username, tm.role, d.day
+        ORDER BY u.username, tm.user_id, d.day
+        "#,
+        team_id,
+        from,
+        to
+    )
+    .fetch_all(pool.get_ref())
+    .await
+    {
+        Ok(cells) => cells,
+        Err(e) => {
+            tracing::error!("Failed to build activity heatmap for team {}: {}", team_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get activity heatmap"
+            })));
+        }
+    };
+
+    let mut members: Vec<MemberActivityRow> = Vec::new();
+    for cell in cells {
+        if members.last().map(|m| m.user_id) != Some(cell.user_id) {
+            members.push(MemberActivityRow {
+                user_id: cell.user_id,
+                username: cell.username,
+                role: cell.role,
+                workouts: Vec::new(),
+                minutes: Vec::new(),
+                total_workouts: 0,
+                active_days: 0,
+                last_workout_at: cell.last_workout_at,
+            });
+        }
+        let member = members.last_mut().expect("member was just pushed");
+        member.workouts.push(cell.workouts);
+        member.minutes.push(cell.minutes);
+        member.total_workouts += cell.workouts;
+        if cell.workouts > 0 {
+            member.active_days += 1;
+        }
+    }
+
+    let days: Vec<NaiveDate> = from.iter_days().take_while(|day| *day <= to).collect();
+
+    Ok(HttpResponse::Ok().json(json!({
+        "success": true,
+        "data": {
+            "team_id": team_id,
+            "team_name": team.team_name,
+            "range": match query.range {
+                HeatmapRange::GameWeek => "game_week",
+                HeatmapRange::Season => "season",
+            },
+            "game_id": game_id,
+            "days": days,
+            "members": members
+        }
+    })))
+}
+
+/// Days covered by the team's game in progress
+async fn current_game_window(
+    pool: &PgPool,
+    team_id: Uuid,
+) -> Result<Option<(Option<Uuid>, NaiveDate, NaiveDate)>, sqlx::Error> {
+    let game = sqlx::query!(
+        r#"
+        SELECT id, game_start_time as "game_start_time!", game_end_time as "game_end_time!"
+        FROM games
+        WHERE (home_team_id = $1 OR away_team_id = $1)
+        AND status = 'in_progress'
+        AND game_start_time IS NOT NULL AND game_end_time IS NOT NULL
+        ORDER BY game_start_time DESC
+        LIMIT 1
+        "#,
+        team_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(game.map(|g| (Some(g.id), g.game_start_time.date_naive(), g.game_end_time.date_naive())))
+}
+
+/// Days of the team's current (or most recent) started season, up to today
+async fn current_season_window(
+    pool: &PgPool,
+    team_id: Uuid,
+    today: NaiveDate,
+) -> Result<Option<(Option<Uuid>, NaiveDate, NaiveDate)>, sqlx::Error> {
+    let season = sqlx::query!(
+        r#"
+        SELECT s.start_date, s.end_date
+        FROM league_seasons s
+        JOIN league_teams lt ON lt.season_id = s.id
+        WHERE lt.team_id = $1 AND s.start_date <= NOW()
+        ORDER BY s.start_date DESC
+        LIMIT 1
+        "#,
+        team_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(season.map(|s| (None, s.start_date.date_naive(), s.end_date.date_naive().min(today))))
+}
@@ -1,4 +1,4 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{http::header, web, HttpResponse, Result};
 use sqlx::PgPool;
 use uuid::Uuid;
 use serde_json::json;
@@ -6,6 +6,8 @@ use chrono::Utc;
 use std::sync::Arc;
 
 use crate::middleware::auth::Claims;
+use crate::middleware::etag::version_etag;
+use crate::handlers::league::team_member_helper::get_team_info;
 use crate::models::league::*;
 use crate::models::team::{TeamRegistrationRequest, TeamUpdateRequest, TeamInfo, TeamInfoWithPower};
 use crate::utils::team_power;
@@ -84,14 +86,16 @@ pub async fn register_new_team(
     // Create the team
     match sqlx::query!(
         r#"
-        INSERT INTO teams (id, user_id, team_name, team_description, team_color, league_id, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO teams (id, user_id, team_name, team_description, team_color, away_color, kit_emoji, league_id, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         "#,
         team_id,
         user_id,
         sanitized_team_name,
         team_request.team_description,
         team_request.team_color.as_deref().unwrap_or("#4F46E5"),
+        team_request.away_color,
+        team_request.kit_emoji,
         team_request.league_id,
         now,
         now
@@ -226,10 +230,13 @@ pub async fn get_team_information(
             t.team_name,
             t.team_description,
             t.team_color,
+            t.away_color,
+            t.kit_emoji,
             t.league_id,
             t.created_at,
             t.updated_at,
-            u.username as owner_username
+            u.username as owner_username,
+            t.version
         FROM teams t
         JOIN users u ON t.user_id = u.id
         WHERE t.id = $1
@@ -271,17 +278,22 @@ pub async fn get_team_information(
         team_name: team.team_name,
         team_description: team.team_description,
         team_color: team.team_color,
+        away_color: team.away_color,
+        kit_emoji: team.kit_emoji,
         league_id: team.league_id,
         created_at: team.created_at,
         updated_at: team.updated_at,
         owner_username: team.owner_username,
         total_power: team_power,
+        version: team.version,
     };
 
-    Ok(HttpResponse::Ok().json(json!({
-        "success": true,
-        "data": team_with_power
-    })))
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, version_etag(team_with_power.version)))
+        .json(json!({
+            "success": true,
+            "data": team_with_power
+        })))
 }
 
 /// Get all registered teams
@@ -301,10 +313,13 @@ pub async fn get_all_registered_teams(
             t.team_name,
             t.team_description,
             t.team_color,
+            t.away_color,
+            t.kit_emoji,
             t.league_id,
             t.created_at,
             t.updated_at,
-            u.username as owner_username
+            u.username as owner_username,
+            t.version
         FROM teams t
         JOIN users u ON t.user_id = u.id
         ORDER BY t.created_at DESC
@@ -348,10 +363,13 @@ pub async fn get_all_registered_teams(
             team_name: team.team_name,
             team_description: team.team_description,
             team_color: team.team_color,
+            away_color: team.away_color,
+            kit_emoji: team.kit_emoji,
             league_id: team.league_id,
             created_at: team.created_at,
             updated_at: team.updated_at,
             owner_username: team.owner_username,
+            version: team.version,
         })
         .collect();
 
@@ -365,12 +383,14 @@ pub async fn get_all_registered_teams(
     })))
 }
 
-/// Update team information
+/// Update team information. With `If-Match: "<version>"` the update only applies if nobody
+/// else changed the team since; otherwise 409 with the current team.
 pub async fn update_team_information(
     team_id: Uuid,
     team_update: web::Json<TeamUpdateRequest>,
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
+    expected_version: Option<i32>,
 ) -> Result<HttpResponse> {
     // Validate the update request
     if let Err(validation_error) = team_update.validate() {
@@ -404,29 +424,52 @@ pub async fn update_team_information(
         })));
     }
 
-    // Update team information
-    match sqlx::query!(
+    // Update team information; the version check and bump happen in the same statement
+    match sqlx::query_scalar!(
         r#"
         UPDATE teams 
         SET team_name = COALESCE($1, team_name),
             team_description = COALESCE($2, team_description),
             team_color = COALESCE($3, team_color),
-            updated_at = NOW()
-        WHERE id = $4
+            away_color = COALESCE($6, away_color),
+            kit_emoji = COALESCE($7, kit_emoji),
+            updated_at = NOW(),
+            version = version + 1
+        WHERE id = $4 AND ($5::int IS NULL OR version = $5)
+        RETURNING version
         "#,
         team_update.team_name.as_deref(),
         team_update.team_description.as_deref(),
         team_update.team_color.as_deref(),
-        team_id
+        team_id,
+        expected_version,
+        team_update.away_color.as_deref(),
+        team_update.kit_emoji.as_deref()
     )
-    .execute(pool.get_ref())
+    .fetch_optional(pool.get_ref())
     .await
     {
-        Ok(_) => {
-            tracing::info!("Successfully updated team {}", team_id);
-            Ok(HttpResponse::Ok().json(json!({
-                "success": true,
-                "message": "Team updated successfully"
+        Ok(Some(version)) => {
+            tracing::info!("Successfully updated team {} to version {}", team_id, version);
+            Ok(HttpResponse::Ok()
+                .insert_header((header::ETAG, version_etag(version)))
+                .json(json!({
+                    "success": true,
+                    "message": "Team updated successfully",
+                    "version": version
+                })))
+        }
+        Ok(None) => {
+            tracing::warn!("Team {} update rejected: version {:?} is stale", team_id, expected_version);
+            let current = get_team_info(&team_id, pool.get_ref()).await.ok().flatten();
+            let mut response = HttpResponse::Conflict();
+            if let Some(team) = &current {
+                response.insert_header((header::ETAG, version_etag(team.version)));
+            }
+            Ok(response.json(json!({
+                "success": false,
+                "message": "Team was changed by someone else. Review the current version and try again.",
+                "data": current
             })))
         }
         Err(e) => {
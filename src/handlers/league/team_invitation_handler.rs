@@ -3,10 +3,12 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use std::sync::Arc;
 
+use crate::db::invites::create_invite_code;
+use crate::handlers::league::team_member_helper::check_team_member_role;
 use crate::middleware::auth::Claims;
 use crate::models::common::ApiResponse;
 use crate::models::team_invitation::{
-    InvitationStatus, SendInvitationRequest, RespondToInvitationRequest,
+    CreateInviteCodeRequest, InvitationStatus, SendInvitationRequest, RespondToInvitationRequest,
     TeamInvitationWithDetails,
 };
 use crate::models::team::TeamRole;
@@ -557,3 +559,60 @@ pub async fn respond_to_invitation(
         }
     }
 }
+
+/// Create an invite code for the team. New users registering with it join the team directly.
+#[tracing::instrument(
+    name = "Create team invite code",
+    skip(pool, claims, team_id, request),
+    fields(username = %claims.username, team_id = %team_id)
+)]
+pub async fn create_team_invite_code(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    team_id: web::Path<Uuid>,
+    request: web::Json<CreateInviteCodeRequest>,
+) -> HttpResponse {
+    let team_id = team_id.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match check_team_member_role(&team_id, &user_id, pool.get_ref()).await {
+        Ok(Some(TeamRole::Owner | TeamRole::Admin)) => {}
+        Ok(Some(_)) => {
+            return HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                "Only team owners and admins can create invite codes"
+            ));
+        }
+        Ok(None) => {
+            return HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                "You are not a member of this team"
+            ));
+        }
+        Err(e) => {
+            tracing::error!("Database error checking team membership: {}", e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                "Failed to verify team membership"
+            ));
+        }
+    }
+
+    let (max_uses, expires_in_days) = match request.validate() {
+        Ok(limits) => limits,
+        Err(message) => return HttpResponse::BadRequest().json(ApiResponse::<()>::error(message)),
+    };
+    let expires_at = chrono::Utc::now() + chrono::Duration::days(expires_in_days);
+
+    match create_invite_code(pool.get_ref(), team_id, user_id, max_uses, expires_at).await {
+        Ok(invite) => {
+            tracing::info!("User {} created invite code {} for team {}", user_id, invite.id, team_id);
+            HttpResponse::Created().json(ApiResponse::success("Invite code created", invite))
+        }
+        Err(e) => {
+            tracing::error!("Failed to create invite code for team {}: {}", team_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
+                "Failed to create invite code"
+            ))
+        }
+    }
+}
@@ -71,11 +71,13 @@ pub async fn add_team_member(
     };
 
     let mut added_members = Vec::new();
+    let mut existing_members = Vec::new();
+    let mut conflicts = Vec::new();
     let mut errors = Vec::new();
 
     for member in &request.member_request {
         match add_member(team_id, member, &pool, &requester_role).await {
-            Ok(member_info) => {
+            Ok(AddMemberOutcome::Added(member_info)) => {
                 // Remove member from player pool using common helper
                 let _ = remove_from_player_pool(&member_info.user_id, pool.get_ref()).await;
 
@@ -106,6 +108,14 @@ pub async fn add_team_member(
 
                 added_members.push(member_info);
             }
+            Ok(AddMemberOutcome::AlreadyMember(member_info)) => {
+                existing_members.push(member_info);
+            }
+            Err(TeamMemberError::InOtherLeagueTeam(conflict)) => {
+                tracing::warn!("User {} is already in team {} of the same league", conflict.user_id, conflict.team_id);
+                errors.push(TeamMemberError::InOtherLeagueTeam(conflict.clone()).to_string());
+                conflicts.push(conflict);
+            }
             Err(e) => {
                 tracing::error!("Failed to add member: {}", e);
                 errors.push(e.to_string());
@@ -113,24 +123,39 @@ pub async fn add_team_member(
         }
     }
 
-    // Return appropriate response based on results
-    if added_members.is_empty() && !errors.is_empty() {
-        // If we have errors and no successful additions
-        Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(errors.join(", "))))
-    } else if !added_members.is_empty() {
-        // If we have at least one successful addition
-        Ok(HttpResponse::Created().json(ApiResponse::success(
-            if errors.is_empty() {
-                "All users added to team successfully".to_string()
-            } else {
-                format!("Some users added successfully. Errors: {}", errors.join(", "))
-            },
-            json!({"members": added_members})
-        )))
-    } else {
-        // If we somehow got here with no additions and no errors
-        Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("No members were added")))
+    if added_members.is_empty() && existing_members.is_empty() {
+        if !conflicts.is_empty() {
+            let message = errors.join(", ");
+            return Ok(HttpResponse::Conflict().json(ApiResponse {
+                success: false,
+                message: message.clone(),
+                data: Some(json!({"conflicts": conflicts})),
+                error: Some(message),
+            }));
+        }
+        let message = if errors.is_empty() { "No members were added".to_string() } else { errors.join(", ") };
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error(message)));
     }
+
+    // Repeating a request succeeds without changes for users who are already members
+    let message = match (added_members.is_empty(), errors.is_empty()) {
+        (false, true) => "All users added to team successfully".to_string(),
+        (false, false) => format!("Some users added successfully. Errors: {}", errors.join(", ")),
+        (true, true) => "All users are already members of this team".to_string(),
+        (true, false) => format!("No users added. Errors: {}", errors.join(", ")),
+    };
+    let mut response = if added_members.is_empty() { HttpResponse::Ok() } else { HttpResponse::Created() };
+    let already_members: Vec<Uuid> = existing_members.iter().map(|member| member.user_id).collect();
+    added_members.extend(existing_members);
+
+    Ok(response.json(ApiResponse::success(
+        message,
+        json!({
+            "members": added_members,
+            "already_members": already_members,
+            "conflicts": conflicts,
+        })
+    )))
 }
 
 /// Get all members of a team
@@ -263,7 +288,17 @@ pub async fn remove_team_member(
     let target_role = match check_team_member_role(&team_id, &target_user_id, &pool).await {
         Ok(Some(role)) => role,
         Ok(None) => {
-            return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("User is not a member of this team")));
+            // Removing someone who is not in the team is a no-op for those allowed to remove them
+            if requester_id != target_user_id
+                && requester_role != TeamRole::Owner
+                && requester_role != TeamRole::Admin
+            {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error("Only team owners and admins can remove members")));
+            }
+            return Ok(HttpResponse::Ok().json(ApiResponse::success(
+                "User is not a member of this team",
+                json!({"removed": false}),
+            )));
         }
         Err(e) => {
             tracing::error!("Failed to check target user role: {}", e);
@@ -343,7 +378,11 @@ pub async fn remove_team_member(
 
     // Use common function to remove member and return to pool
     match remove_member_and_return_to_pool(&team_id, &target_user_id, pool.get_ref()).await {
-        Ok(_) => {
+        Ok(false) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            "User is not a member of this team",
+            json!({"removed": false}),
+        ))),
+        Ok(true) => {
             // Publish player_left_team event (left the team)
             if let Err(e) = player_pool_events::publish_player_left_team(
                 &redis_client,
@@ -368,7 +407,10 @@ pub async fn remove_team_member(
                 tracing::warn!("Failed to publish player_joined event: {}", e);
             }
 
-            Ok(HttpResponse::Ok().json(ApiResponse::<()>::success_message("User removed from team successfully")))
+            Ok(HttpResponse::Ok().json(ApiResponse::success(
+                "User removed from team successfully",
+                json!({"removed": true}),
+            )))
         }
         Err(e) => {
             tracing::error!("Failed to remove user {} from team {}: {}", target_user_id, team_id, e);
@@ -377,6 +419,148 @@ pub async fn remove_team_member(
     }
 }
 
+/// Move a user from one team to another atomically.
+/// The requester must be an owner or admin of both teams.
+pub async fn transfer_team_member(
+    path: web::Path<(Uuid, Uuid)>, // (team_id, user_id)
+    request: web::Json<TransferTeamMemberRequest>,
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<redis::Client>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let (team_id, target_user_id) = path.into_inner();
+    let to_team_id = request.to_team_id;
+
+    let Some(requester_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    if to_team_id == team_id {
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("User is already in this team")));
+    }
+
+    let mut requester_roles = Vec::new();
+    for id in [team_id, to_team_id] {
+        match check_team_member_role(&id, &requester_id, &pool).await {
+            Ok(Some(role)) if role == TeamRole::Owner || role == TeamRole::Admin => requester_roles.push(role),
+            Ok(_) => {
+                return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                    "Only owners and admins of both teams can transfer members",
+                )));
+            }
+            Err(e) => {
+                tracing::error!("Failed to check requester role: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify permissions")));
+            }
+        }
+    }
+    let (source_requester_role, target_requester_role) = (&requester_roles[0], &requester_roles[1]);
+
+    let target_role = match check_team_member_role(&team_id, &target_user_id, &pool).await {
+        Ok(Some(role)) => role,
+        Ok(None) => {
+            // A repeated transfer finds the user already in the new team
+            return match get_team_member_info(&to_team_id, &target_user_id, &pool).await {
+                Ok(Some(member_info)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+                    "User is already a member of the new team",
+                    member_info,
+                ))),
+                Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("User is not a member of this team"))),
+                Err(e) => {
+                    tracing::error!("Failed to get member info: {}", e);
+                    Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify target user")))
+                }
+            };
+        }
+        Err(e) => {
+            tracing::error!("Failed to check target user role: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify target user")));
+        }
+    };
+
+    // Admins can't move owners
+    if source_requester_role == &TeamRole::Admin && target_role == TeamRole::Owner {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error("Admins cannot transfer team owners")));
+    }
+
+    // Only owners can make someone an owner
+    let new_role = request.role.clone().unwrap_or(target_role.clone());
+    if new_role == TeamRole::Owner && target_requester_role != &TeamRole::Owner {
+        return Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error("Only team owners can add other owners")));
+    }
+
+    if target_role == TeamRole::Owner {
+        match count_team_owners(&team_id, &pool).await {
+            Ok(count) if count <= 1 => {
+                return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Cannot transfer the last owner of a team")));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Failed to count team owners: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to verify team ownership")));
+            }
+        }
+    }
+
+    let (from_team, to_team) = match (get_team_info(&team_id, &pool).await, get_team_info(&to_team_id, &pool).await) {
+        (Ok(Some(from_team)), Ok(Some(to_team))) => (from_team, to_team),
+        (Ok(_), Ok(_)) => {
+            return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Team not found")));
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to get team info: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to get team information")));
+        }
+    };
+
+    match transfer_member(&team_id, &to_team_id, &target_user_id, Some(new_role), &pool).await {
+        Ok(member_info) => {
+            if let Err(e) = player_pool_events::publish_player_left_team(
+                &redis_client,
+                &pool,
+                target_user_id,
+                member_info.username.clone(),
+                from_team.league_id,
+                team_id,
+                from_team.team_name.clone(),
+            ).await {
+                tracing::warn!("Failed to publish player_left_team event: {}", e);
+            }
+
+            if let Err(e) = player_pool_events::publish_player_assigned(
+                &redis_client,
+                &pool,
+                target_user_id,
+                member_info.username.clone(),
+                to_team.league_id,
+                to_team_id,
+                to_team.team_name.clone(),
+            ).await {
+                tracing::warn!("Failed to publish player_assigned event: {}", e);
+            }
+
+            Ok(HttpResponse::Ok().json(ApiResponse::success("User transferred successfully", member_info)))
+        }
+        Err(TeamMemberError::NotAMember) => {
+            Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("User is not a member of this team")))
+        }
+        Err(TeamMemberError::InOtherLeagueTeam(conflict)) => {
+            let message = TeamMemberError::InOtherLeagueTeam(conflict.clone()).to_string();
+            Ok(HttpResponse::Conflict().json(ApiResponse {
+                success: false,
+                message: message.clone(),
+                data: Some(json!({"conflicts": [conflict]})),
+                error: Some(message),
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to transfer user {} from team {} to team {}: {}", target_user_id, team_id, to_team_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to transfer user")))
+        }
+    }
+}
+
 /// Update current user's status in their team (active/inactive)
 pub async fn update_my_team_status(
     team_id: web::Path<Uuid>,
@@ -1,97 +1,197 @@
 use chrono::Utc;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+use crate::db::team_repo::{TeamRepo, TeamRepository};
 use crate::models::team::*;
 
+/// Result of adding a user to a team. Adding someone who is already a member changes nothing.
+pub enum AddMemberOutcome {
+    Added(TeamMemberInfo),
+    AlreadyMember(TeamMemberInfo),
+}
+
+/// Why a team membership could not be created or moved
+#[derive(Debug)]
+pub enum TeamMemberError {
+    UserNotFound,
+    NotAMember,
+    OwnerRoleRequired,
+    /// The user is active in another team of the same league
+    InOtherLeagueTeam(LeagueMembershipConflict),
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for TeamMemberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UserNotFound => write!(f, "User not found"),
+            Self::NotAMember => write!(f, "User is not a member of this team"),
+            Self::OwnerRoleRequired => write!(f, "Only team owners can add other owners"),
+            Self::InOtherLeagueTeam(conflict) => write!(
+                f,
+                "{} is already a member of {} in the same league",
+                conflict.username, conflict.team_name
+            ),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for TeamMemberError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
 pub async fn add_member(
     team_id: Uuid,
     member: &TeamMemberRequest,
     pool: &PgPool,
     requester_role: &TeamRole,
-) -> Result<TeamMemberInfo, sqlx::Error> {
-    
-    // Find the target user
-    let target_user_id = match find_user_by_request(member, pool).await {
-        Ok(Some(user_id)) => user_id,
-        Ok(None) => {
-            tracing::error!("User not found");
-            return Err(sqlx::Error::TypeNotFound { type_name: "User".to_string() });
-        }
-        Err(e) => {
-            tracing::error!("Failed to find target user: {}", e);
-            return Err(e);
-        }
-    };
+) -> Result<AddMemberOutcome, TeamMemberError> {
+    let target_user_id = find_user_by_request(member, pool)
+        .await?
+        .ok_or(TeamMemberError::UserNotFound)?;
 
-    // Check if user is already a member
-    match check_team_member_role(&team_id, &target_user_id, pool).await {
-        Ok(Some(_)) => {
-            tracing::error!("User is already a member of this team");
-            return Err(sqlx::Error::TypeNotFound { type_name: "User".to_string() });
-        }
-        Ok(None) => {
-            tracing::info!("User is not a member of this team - proceeding");
-        }
-        Err(e) => {
-            tracing::error!("Failed to check existing membership: {}", e);
-            return Err(e);
-        }
+    // Adding an existing member again is a no-op, whatever role was asked for
+    if let Some(existing) = get_team_member_info(&team_id, &target_user_id, pool).await? {
+        tracing::info!("User {} is already a member of team {}", target_user_id, team_id);
+        return Ok(AddMemberOutcome::AlreadyMember(existing));
     }
 
-    // Determine the role for the new member
     let member_role = member.role.clone().unwrap_or(TeamRole::Member);
 
     // Only owners can add other owners
     if member_role == TeamRole::Owner && requester_role != &TeamRole::Owner {
-        tracing::error!("Only team owners can add other owners");
-        return Err(sqlx::Error::TypeNotFound { type_name: "User".to_string() });
+        return Err(TeamMemberError::OwnerRoleRequired);
     }
 
-    // Add the user to the team
-    let member_id = Uuid::new_v4();
-    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+    lock_user_memberships(&mut tx, &target_user_id).await?;
 
-    match sqlx::query!(
+    if let Some(conflict) = find_league_membership_conflict(&mut tx, &team_id, &target_user_id).await? {
+        return Err(TeamMemberError::InOtherLeagueTeam(conflict));
+    }
+
+    let inserted = insert_member(&mut tx, &team_id, &target_user_id, &member_role).await?;
+    tx.commit().await?;
+
+    let member_info = get_team_member_info(&team_id, &target_user_id, pool)
+        .await?
+        .ok_or(TeamMemberError::NotAMember)?;
+
+    if inserted {
+        tracing::info!("Successfully added user {} to team {} as {}", target_user_id, team_id, member_role);
+        Ok(AddMemberOutcome::Added(member_info))
+    } else {
+        Ok(AddMemberOutcome::AlreadyMember(member_info))
+    }
+}
+
+/// Move a user from one team to another in a single transaction.
+/// The user keeps their role unless a new one is given; they are never left without a team.
+pub async fn transfer_member(
+    from_team_id: &Uuid,
+    to_team_id: &Uuid,
+    user_id: &Uuid,
+    role: Option<TeamRole>,
+    pool: &PgPool,
+) -> Result<TeamMemberInfo, TeamMemberError> {
+    let mut tx = pool.begin().await?;
+    lock_user_memberships(&mut tx, user_id).await?;
+
+    let current_role = sqlx::query_scalar!(
+        r#"
+        DELETE FROM team_members
+        WHERE team_id = $1 AND user_id = $2
+        RETURNING role as "role: TeamRole"
+        "#,
+        from_team_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(TeamMemberError::NotAMember)?;
+
+    // The source membership is gone, so only other teams of the league can conflict
+    if let Some(conflict) = find_league_membership_conflict(&mut tx, to_team_id, user_id).await? {
+        return Err(TeamMemberError::InOtherLeagueTeam(conflict));
+    }
+
+    insert_member(&mut tx, to_team_id, user_id, &role.unwrap_or(current_role)).await?;
+    tx.commit().await?;
+
+    tracing::info!("Transferred user {} from team {} to team {}", user_id, from_team_id, to_team_id);
+
+    get_team_member_info(to_team_id, user_id, pool)
+        .await?
+        .ok_or(TeamMemberError::NotAMember)
+}
+
+/// Serialize membership changes of one user, so two requests cannot put them in two teams of a league
+async fn lock_user_memberships(conn: &mut PgConnection, user_id: &Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+        .fetch_optional(conn)
+        .await?;
+    Ok(())
+}
+
+/// An active membership of the user in another team of the team's league
+async fn find_league_membership_conflict(
+    conn: &mut PgConnection,
+    team_id: &Uuid,
+    user_id: &Uuid,
+) -> Result<Option<LeagueMembershipConflict>, sqlx::Error> {
+    sqlx::query_as!(
+        LeagueMembershipConflict,
+        r#"
+        SELECT
+            tm.user_id,
+            u.username,
+            other.league_id as "league_id!",
+            other.id as team_id,
+            other.team_name
+        FROM teams t
+        JOIN teams other ON other.league_id = t.league_id AND other.id <> t.id
+        JOIN team_members tm ON tm.team_id = other.id AND tm.user_id = $2 AND tm.status = 'active'
+        JOIN users u ON u.id = tm.user_id
+        WHERE t.id = $1
+        LIMIT 1
+        "#,
+        team_id,
+        user_id
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// Insert an active membership. Returns false if the user was already in the team.
+async fn insert_member(
+    conn: &mut PgConnection,
+    team_id: &Uuid,
+    user_id: &Uuid,
+    role: &TeamRole,
+) -> Result<bool, sqlx::Error> {
+    let now = Utc::now();
+    let result = sqlx::query!(
         r#"
         INSERT INTO team_members (id, team_id, user_id, role, status, joined_at, updated_at)
         VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (team_id, user_id) DO NOTHING
         "#,
-        member_id,
+        Uuid::new_v4(),
         team_id,
-        target_user_id,
-        member_role.to_string(),
+        user_id,
+        role.to_string(),
         MemberStatus::Active.to_string(),
         now,
         now
     )
-    .execute(pool)
-    .await
-    {
-        Ok(_) => {
-            tracing::info!("Successfully added user {} to team {} as {}", 
-                target_user_id, team_id, member_role);
-
-            // Get the member info for response
-            match get_team_member_info(&team_id, &target_user_id, pool).await {
-                Ok(Some(member_info)) => {
-                    Ok(member_info)
-                }
-                Ok(None) => {
-                    tracing::error!("Failed to get member info after adding");
-                    Err(sqlx::Error::TypeNotFound { type_name: "TeamMemberInfo".to_string() })
-                }
-                Err(e) => {
-                    tracing::error!("Failed to get member info after adding");
-                    Err(e)
-                }
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to add user {} to team {}", target_user_id, team_id);
-            Err(e)
-        }
-    }
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
 }
 
 async fn find_user_by_request(request: &TeamMemberRequest, pool: &PgPool) -> Result<Option<Uuid>, sqlx::Error> {
@@ -133,18 +233,7 @@ async fn find_user_by_request(request: &TeamMemberRequest, pool: &PgPool) -> Res
 }
 
 pub async fn check_team_member_role(team_id: &Uuid, user_id: &Uuid, pool: &PgPool) -> Result<Option<TeamRole>, sqlx::Error> {
-    let result = sqlx::query!(
-        "SELECT role FROM team_members WHERE team_id = $1 AND user_id = $2 AND status = 'active'",
-        team_id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
-
-    match result {
-        Some(row) => Ok(Some(row.role.parse().map_err(|_| sqlx::Error::TypeNotFound { type_name: "TeamRole".to_string() })?)),
-        None => Ok(None),
-    }
+    TeamRepo::new(pool.clone()).find_active_role(*team_id, *user_id).await
 }
 
 pub async fn get_team_member_info(team_id: &Uuid, user_id: &Uuid, pool: &PgPool) -> Result<Option<TeamMemberInfo>, sqlx::Error> {
@@ -182,10 +271,13 @@ pub async fn get_team_info(team_id: &Uuid, pool: &PgPool) -> Result<Option<TeamI
             t.team_name,
             t.team_description,
             t.team_color,
+            t.away_color,
+            t.kit_emoji,
             t.league_id,
             t.created_at,
             t.updated_at,
-            u.username as owner_username
+            u.username as owner_username,
+            t.version
         FROM teams t
         JOIN users u ON t.user_id = u.id
         WHERE t.id = $1
@@ -207,12 +299,13 @@ pub async fn count_team_owners(team_id: &Uuid, pool: &PgPool) -> Result<i64, sql
     Ok(result.count.unwrap_or(0))
 }
 
-/// Remove a user from a team and add them back to player pool if they're active
+/// Remove a user from a team and add them back to player pool if they're active.
+/// Returns false if the user was not in the team, which is not an error.
 pub async fn remove_member_and_return_to_pool(
     team_id: &Uuid,
     user_id: &Uuid,
     pool: &PgPool,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<bool, Box<dyn std::error::Error>> {
     // Get user status before removal
     let user_info = sqlx::query!(
         r#"
@@ -235,7 +328,8 @@ pub async fn remove_member_and_return_to_pool(
     .await?;
 
     if result.rows_affected() == 0 {
-        return Err("User not found in team".into());
+        tracing::info!("User {} was not in team {} - nothing to remove", user_id, team_id);
+        return Ok(false);
     }
 
     tracing::info!("Successfully removed user {} from team {}", user_id, team_id);
@@ -267,7 +361,7 @@ pub async fn remove_member_and_return_to_pool(
         }
     }
 
-    Ok(())
+    Ok(true)
 }
 
 /// Remove a user from player pool after joining a team
@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::notification::{TeamNotificationSettings, UpdateTeamNotificationSettingsRequest};
+use crate::services::team_notification_service::TeamNotificationError;
+use crate::services::TeamNotificationService;
+
+/// GET /league/teams/{team_id}/notification-settings - Team events the members are notified about
+pub async fn get_team_notification_settings(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let team_id = path.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = TeamNotificationService::new(pool.get_ref().clone());
+    let result = service.settings_for_member(team_id, user_id).await;
+    Ok(settings_response(result, "Team notification settings retrieved successfully"))
+}
+
+/// PUT /league/teams/{team_id}/notification-settings - Captains choose the team events members are notified about
+pub async fn update_team_notification_settings(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateTeamNotificationSettingsRequest>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let team_id = path.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = TeamNotificationService::new(pool.get_ref().clone());
+    let result = service.update(team_id, user_id, &body).await;
+    Ok(settings_response(result, "Team notification settings updated"))
+}
+
+fn settings_response(result: Result<TeamNotificationSettings, TeamNotificationError>, message: &str) -> HttpResponse {
+    match result {
+        Ok(settings) => HttpResponse::Ok().json(ApiResponse::success(message, settings)),
+        Err(e @ TeamNotificationError::TeamNotFound) => HttpResponse::NotFound().json(ApiResponse::<()>::error(e.to_string())),
+        Err(e @ (TeamNotificationError::NotAMember | TeamNotificationError::NotCaptain)) => {
+            HttpResponse::Forbidden().json(ApiResponse::<()>::error(e.to_string()))
+        }
+        Err(TeamNotificationError::Database(e)) => {
+            tracing::error!("Failed to handle team notification settings: {}", e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to handle team notification settings"))
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use actix_web::{web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::db::game_repo::{GameRepo, GameRepository};
+use crate::middleware::auth::Claims;
+use crate::models::health::TrainingZoneName;
+
+#[derive(Debug, Deserialize)]
+pub struct SeasonZoneQuery {
+    pub team_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ZoneMinutes {
+    pub zone: String,
+    pub minutes: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamZoneBreakdown {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub total_minutes: f64,
+    pub workout_count: i64,
+    pub zones: Vec<ZoneMinutes>,
+}
+
+/// Row shape shared by the game and season aggregates.
+/// Teams without credited workouts come back as a single row with no zone.
+struct TeamZoneRow {
+    team_id: Uuid,
+    team_name: String,
+    zone: Option<String>,
+    minutes: Option<f64>,
+    workout_count: i64,
+}
+
+/// Position of a zone in the display order (Off → Hard), unknown zones last
+fn zone_order(zone: &str) -> usize {
+    [
+        TrainingZoneName::OFF,
+        TrainingZoneName::REST,
+        TrainingZoneName::EASY,
+        TrainingZoneName::MODERATE,
+        TrainingZoneName::HARD,
+    ]
+    .iter()
+    .position(|z| z.to_string() == zone)
+    .unwrap_or(usize::MAX)
+}
+
+/// Group per-zone rows (ordered by team) into one breakdown per team
+fn group_by_team(rows: Vec<TeamZoneRow>) -> Vec<TeamZoneBreakdown> {
+    let mut teams: Vec<TeamZoneBreakdown> = Vec::new();
+
+    for row in rows {
+        if teams.last().map(|t| t.team_id) != Some(row.team_id) {
+            teams.push(TeamZoneBreakdown {
+                team_id: row.team_id,
+                team_name: row.team_name,
+                total_minutes: 0.0,
+                workout_count: row.workout_count,
+                zones: Vec::new(),
+            });
+        }
+        let team = teams.last_mut().expect("team was just pushed");
+        if let Some(zone) = row.zone {
+            let minutes = row.minutes.unwrap_or(0.0);
+            team.total_minutes += minutes;
+            team.zones.push(ZoneMinutes { zone, minutes });
+        }
+    }
+
+    for team in &mut teams {
+        team.zones.sort_by(|a, b| zone_order(&a.zone).cmp(&zone_order(&b.zone)).then_with(|| a.zone.cmp(&b.zone)));
+    }
+
+    teams
+}
+
+/// GET /league/games/{game_id}/zones - Minutes per heart rate zone for both teams of a game
+/// Only workouts credited to the game (via live_score_events) are counted, each once per team
+pub async fn get_game_zone_breakdown(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    _claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let game_id = path.into_inner();
+
+    let game = match GameRepo::new(pool.get_ref().clone()).find_live_game_state(game_id).await {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "error": "Game not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get game {} for zone breakdown: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get zone breakdown"
+            })));
+        }
+    };
+
+    let rows = sqlx::query!(
+        r#"
+        WITH credited AS (
+            SELECT DISTINCT lse.team_id, lse.workout_data_id
+            FROM live_score_events lse
+            WHERE lse.game_id = $1 AND lse.workout_data_id IS NOT NULL
+        ),
+        zone_rows AS (
+            SELECT c.team_id, z.value->>'zone' AS zone, (z.value->>'minutes')::float8 AS minutes
+            FROM credited c
+            JOIN workout_data wd ON wd.id = c.workout_data_id
+            CROSS JOIN LATERAL jsonb_array_elements(
+                CASE WHEN jsonb_typeof(wd.heart_rate_zones) = 'array' THEN wd.heart_rate_zones ELSE '[]'::jsonb END
+            ) z
+            WHERE z.value ? 'zone'
+        )
+        SELECT
+            t.id as team_id,
+            t.team_name,
+            zr.zone as "zone?",
+            SUM(zr.minutes) as "minutes?",
+            (SELECT COUNT(*) FROM credited c WHERE c.team_id = t.id) as "workout_count!"
+        FROM teams t
+        LEFT JOIN zone_rows zr ON zr.team_id = t.id
+        WHERE t.id IN ($2, $3)
+        GROUP BY t.id, t.team_name, zr.zone
+        ORDER BY (t.id = $2) DESC
+        "#,
+        game_id,
+        game.home_team_id,
+        game.away_team_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("Failed to aggregate zone minutes for game {}: {}", game_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get zone breakdown"
+            })));
+        }
+    };
+
+    let teams = group_by_team(rows.into_iter().map(|r| TeamZoneRow {
+        team_id: r.team_id,
+        team_name: r.team_name,
+        zone: r.zone,
+        minutes: r.minutes,
+        workout_count: r.workout_count,
+    }).collect());
+    let (home, away): (Vec<_>, Vec<_>) = teams.into_iter().partition(|t| t.team_id == game.home_team_id);
+
+    Ok(HttpResponse::Ok().json(json!({
+        "success": true,
+        "data": {
+            "game_id": game_id,
+            "status": game.status,
+            "home_team": home.into_iter().next(),
+            "away_team": away.into_iter().next()
+        }
+    })))
+}
+
+/// GET /league/seasons/{season_id}/zones - Minutes per heart rate zone for each team over a season
+/// Optionally restricted to a single team with `?team_id=`
+pub async fn get_season_zone_breakdown(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    query: web::Query<SeasonZoneQuery>,
+    _claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+
+    match sqlx::query_scalar!("SELECT id FROM league_seasons WHERE id = $1", season_id)
+        .fetch_optional(pool.get_ref())
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "success": false,
+                "error": "Season not found"
+            })));
+        }
+        Err(e) => {
+            tracing::error!("Failed to get season {} for zone breakdown: {}", season_id, e);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get zone breakdown"
+            })));
+        }
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        WITH season_teams AS (
+            SELECT home_team_id AS team_id FROM games WHERE season_id = $1
+            UNION
+            SELECT away_team_id AS team_id FROM games WHERE season_id = $1
+        ),
+        credited AS (
+            SELECT DISTINCT lse.team_id, lse.workout_data_id
+            FROM live_score_events lse
+            JOIN games g ON g.id = lse.game_id
+            WHERE g.season_id = $1 AND lse.workout_data_id IS NOT NULL
+        ),
+        zone_rows AS (
+            SELECT c.team_id, z.value->>'zone' AS zone, (z.value->>'minutes')::float8 AS minutes
+            FROM credited c
+            JOIN workout_data wd ON wd.id = c.workout_data_id
+            CROSS JOIN LATERAL jsonb_array_elements(
+                CASE WHEN jsonb_typeof(wd.heart_rate_zones) = 'array' THEN wd.heart_rate_zones ELSE '[]'::jsonb END
+            ) z
+            WHERE z.value ? 'zone'
+        )
+        SELECT
+            t.id as team_id,
+            t.team_name,
+            zr.zone as "zone?",
+            SUM(zr.minutes) as "minutes?",
+            (SELECT COUNT(*) FROM credited c WHERE c.team_id = t.id) as "workout_count!"
+        FROM season_teams st
+        JOIN teams t ON t.id = st.team_id
+        LEFT JOIN zone_rows zr ON zr.team_id = t.id
+        WHERE ($2::uuid IS NULL OR t.id = $2)
+        GROUP BY t.id, t.team_name, zr.zone
+        ORDER BY t.team_name, t.id
+        "#,
+        season_id,
+        query.team_id
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match rows {
+        Ok(rows) => {
+            let teams = group_by_team(rows.into_iter().map(|r| TeamZoneRow {
+                team_id: r.team_id,
+                team_name: r.team_name,
+                zone: r.zone,
+                minutes: r.minutes,
+                workout_count: r.workout_count,
+            }).collect());
+
+            Ok(HttpResponse::Ok().json(json!({
+                "success": true,
+                "data": {
+                    "season_id": season_id,
+                    "teams": teams
+                }
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to aggregate zone minutes for season {}: {}", season_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get zone breakdown"
+            })))
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use actix_web::{http::header, web, HttpResponse};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::link_preview::{LinkPreview, LinkPreviewQuery, PreviewTarget};
+use crate::models::share_card::ShareCardContent;
+use crate::models::workout_data::OpenGraphSummary;
+use crate::services::share_card_service::share_card_path;
+
+/// Open Graph metadata for a shared app link (game, team, shared workout or share card),
+/// so the marketing site and chat apps can unfurl it
+#[tracing::instrument(name = "Get link preview", skip(pool, query), fields(path = %query.path))]
+pub async fn get_link_preview(
+    pool: web::Data<PgPool>,
+    query: web::Query<LinkPreviewQuery>,
+) -> HttpResponse {
+    let Some(target) = PreviewTarget::parse(&query.path) else {
+        return HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "error": "Unsupported path"
+        }));
+    };
+
+    let og = match &target {
+        PreviewTarget::Game(game_id) => game_preview(&pool, *game_id).await,
+        PreviewTarget::Team(team_id) => team_preview(&pool, *team_id).await,
+        PreviewTarget::SharedWorkout(token) => workout_preview(&pool, token).await,
+        PreviewTarget::ShareCard(card_id) => share_card_preview(&pool, *card_id).await,
+    };
+
+    match og {
+        Ok(Some(og)) => HttpResponse::Ok()
+            .insert_header((header::CACHE_CONTROL, "public, max-age=300"))
+            .json(json!({
+                "success": true,
+                "data": LinkPreview {
+                    kind: target.kind(),
+                    path: target.canonical_path(),
+                    og,
+                }
+            })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "success": false,
+            "error": "Nothing to preview at this path"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to build link preview for {}: {}", query.path, e);
+            HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }))
+        }
+    }
+}
+
+async fn game_preview(pool: &PgPool, game_id: Uuid) -> Result<Option<OpenGraphSummary>, sqlx::Error> {
+    let Some(game) = sqlx::query!(
+        r#"
+        SELECT ht.team_name as home_team_name, at.team_name as away_team_name,
+               g.home_score, g.away_score, g.status, g.week_number, g.game_start_time,
+               l.name as league_name, s.name as season_name,
+               (SELECT sc.id FROM share_cards sc
+                WHERE sc.card_type = 'game_result' AND sc.subject_id = g.id AND sc.status = 'rendered'
+                ORDER BY sc.rendered_at DESC LIMIT 1) as card_id
+        FROM games g
+        JOIN teams ht ON ht.id = g.home_team_id
+        JOIN teams at ON at.id = g.away_team_id
+        JOIN league_seasons s ON s.id = g.season_id
+        JOIN leagues l ON l.id = s.league_id
+        WHERE g.id = $1
+        "#,
+        game_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let state = match game.status.as_str() {
+        "in_progress" => format!("Live: {} - {}", game.home_score, game.away_score),
+        "finished" | "evaluated" => format!("Final: {} - {}", game.home_score, game.away_score),
+        _ => match game.game_start_time {
+            Some(start) => format!("Kicks off {}", start.format("%b %-d, %H:%M UTC")),
+            None => "Upcoming".to_string(),
+        },
+    };
+
+    Ok(Some(OpenGraphSummary {
+        title: format!("{} vs {}", game.home_team_name, game.away_team_name),
+        description: format!("{} · {} · {} week {}", state, game.league_name, game.season_name, game.week_number),
+        image_url: game.card_id.map(share_card_path),
+    }))
+}
+
+async fn team_preview(pool: &PgPool, team_id: Uuid) -> Result<Option<OpenGraphSummary>, sqlx::Error> {
+    let Some(team) = sqlx::query!(
+        r#"
+        SELECT t.team_name, t.team_description, l.name as "league_name?",
+               (SELECT COUNT(*) FROM team_members tm WHERE tm.team_id = t.id AND tm.status = 'active') as "members!"
+        FROM teams t
+        LEFT JOIN leagues l ON l.id = t.league_id
+        WHERE t.id = $1
+        "#,
+        team_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let players = if team.members == 1 { "1 player".to_string() } else { format!("{} players", team.members) };
+    let description = match (team.team_description.filter(|d| !d.trim().is_empty()), team.league_name) {
+        (Some(description), _) => description,
+        (None, Some(league_name)) => format!("{players} · {league_name}"),
+        (None, None) => players,
+    };
+
+    Ok(Some(OpenGraphSummary {
+        title: team.team_name,
+        description,
+        image_url: None,
+    }))
+}
+
+/// Same visibility rules as `get_shared_workout`, without counting the preview as a view
+async fn workout_preview(pool: &PgPool, token: &str) -> Result<Option<OpenGraphSummary>, sqlx::Error> {
+    let Some(workout) = sqlx::query!(
+        r#"
+        SELECT u.username,
+               COALESCE(wd.user_activity, wd.activity_name, 'Workout') as "activity!",
+               wd.duration_minutes, wd.calories_burned, wd.total_points_gained,
+               COALESCE(wd.image_url, (SELECT m->>'url' FROM jsonb_array_elements(p.media_urls) m
+                                       WHERE m->>'type' = 'image' LIMIT 1)) as image_url
+        FROM workout_share_links sl
+        JOIN workout_data wd ON wd.id = sl.workout_data_id
+        JOIN users u ON u.id = wd.user_id
+        LEFT JOIN posts p ON p.workout_id = wd.id AND p.user_id = wd.user_id
+        WHERE sl.token = $1 AND sl.revoked_at IS NULL
+        AND p.visibility IS DISTINCT FROM 'private'
+        "#,
+        token
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut highlights = Vec::new();
+    if let Some(minutes) = workout.duration_minutes {
+        highlights.push(format!("{} min", minutes));
+    }
+    if let Some(calories) = workout.calories_burned {
+        highlights.push(format!("{} kcal", calories));
+    }
+    highlights.push(format!("{} points", workout.total_points_gained));
+
+    Ok(Some(OpenGraphSummary {
+        title: format!("{}'s {}", workout.username, workout.activity),
+        description: highlights.join(" · "),
+        image_url: workout.image_url,
+    }))
+}
+
+async fn share_card_preview(pool: &PgPool, card_id: Uuid) -> Result<Option<OpenGraphSummary>, sqlx::Error> {
+    let Some(card) = sqlx::query!("SELECT content, status FROM share_cards WHERE id = $1", card_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let Ok(content) = serde_json::from_value::<ShareCardContent>(card.content) else {
+        return Ok(None);
+    };
+
+    let mut description = vec![content.eyebrow];
+    description.extend(content.lines.into_iter().map(|line| line.value));
+
+    Ok(Some(OpenGraphSummary {
+        title: content.title,
+        description: description.join(" · "),
+        image_url: (card.status == "rendered").then(|| share_card_path(card_id)),
+    }))
+}
@@ -1,10 +1,12 @@
 use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
 use uuid::Uuid;
 use sha2::{Sha256, Digest};
 
 use crate::middleware::auth::Claims;
 use crate::models::common::ApiResponse;
-use crate::services::MinIOService;
+use crate::services::{MediaModerationService, MinIOService};
+use crate::utils::perceptual_hash::perceptual_hash;
 
 // Response types for signed URL operations
 
@@ -159,6 +161,7 @@ pub async fn confirm_upload(
     request: web::Json<ConfirmUploadRequest>,
     claims: web::ReqData<Claims>,
     minio_service: web::Data<MinIOService>,
+    pool: web::Data<PgPool>,
 ) -> HttpResponse {
     tracing::info!("✅ User {} confirming upload: {}", claims.username, request.object_key);
 
@@ -179,6 +182,13 @@ pub async fn confirm_upload(
                 );
             }
 
+            // Images are compared against the moderation blocklist by perceptual hash
+            if is_image_extension(&get_file_extension(&request.object_key)) {
+                if let Some(response) = moderate_image(&pool, &minio_service, &claims, &request.object_key, &actual_hash, &contents).await {
+                    return response;
+                }
+            }
+
             let file_url = minio_service.generate_file_url(&request.object_key);
             
             tracing::info!("✅ Upload confirmed and verified: {} (hash: {})", 
@@ -205,11 +215,54 @@ pub async fn confirm_upload(
     }
 }
 
+// Hash an uploaded image and reject it if it resembles blocked media.
+// Returns the response to send when the upload is rejected.
+async fn moderate_image(
+    pool: &PgPool,
+    minio_service: &MinIOService,
+    claims: &Claims,
+    object_key: &str,
+    sha256: &str,
+    contents: &[u8],
+) -> Option<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        return Some(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+    let Some(phash) = perceptual_hash(contents) else {
+        // e.g. HEIC, which we cannot decode
+        tracing::debug!("Skipping perceptual hash of {}: format not decodable", object_key);
+        return None;
+    };
+
+    let service = MediaModerationService::new(pool.clone());
+    match service.find_blocked_match(phash).await {
+        Ok(Some(blocked)) => {
+            tracing::warn!("🚫 Upload {} by {} matches blocked media {} (distance {})",
+                object_key, claims.username, blocked.blocklist_id, blocked.distance);
+            if let Err(e) = minio_service.delete_file(object_key).await {
+                tracing::warn!("Failed to delete blocked upload {}: {}", object_key, e);
+            }
+            return Some(HttpResponse::UnprocessableEntity().json(
+                ApiResponse::<()>::error("This image was removed by moderation and cannot be uploaded")
+            ));
+        }
+        Ok(None) => {}
+        // Moderation must not block uploads when the database hiccups
+        Err(e) => tracing::error!("Failed to check {} against the media blocklist: {}", object_key, e),
+    }
+
+    if let Err(e) = service.record_hash(user_id, object_key, sha256, phash).await {
+        tracing::error!("Failed to record perceptual hash of {}: {}", object_key, e);
+    }
+    None
+}
+
 // Get download signed URL for existing file
 pub async fn get_download_signed_url(
     path: web::Path<(String, String)>, // (user_id, filename)
     claims: web::ReqData<Claims>,
     minio_service: web::Data<MinIOService>,
+    pool: web::Data<PgPool>,
 ) -> HttpResponse {
     let (user_id_str, filename) = path.into_inner();
     tracing::info!("🔗 User {} requesting download URL for: {}/{}", 
@@ -237,6 +290,15 @@ pub async fn get_download_signed_url(
     let new_object_key = format!("media/{user_id_str}/{filename}");
     let legacy_object_key = format!("users/{user_id_str}/{filename}");
 
+    let service = MediaModerationService::new(pool.get_ref().clone());
+    match service.is_taken_down(&new_object_key).await {
+        Ok(true) => {
+            return HttpResponse::Gone().json(ApiResponse::<()>::error("This media was removed by moderation"));
+        }
+        Ok(false) => {}
+        Err(e) => tracing::error!("Failed to check takedown status of {}: {}", new_object_key, e),
+    }
+
     // Try to get file from new location first, then legacy location
     let (object_key, get_result) = match minio_service.get_file(&new_object_key).await {
         Ok(result) => (new_object_key, Ok(result)),
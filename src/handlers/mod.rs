@@ -11,3 +11,9 @@ pub mod posts;
 pub mod media;
 pub mod analytics_handler;
 pub mod notification_handler;
+pub mod sync_handler;
+pub mod config_handler;
+pub mod share_card_handler;
+pub mod link_preview_handler;
+#[cfg(feature = "graphql")]
+pub mod graphql_handler;
@@ -8,7 +8,10 @@ use crate::middleware::auth::Claims;
 use crate::models::notification::{
     RegisterPushTokenRequest, UnregisterPushTokenRequest, SendNotificationRequest,
     PushToken, PushTokenResponse, ExpoPushMessage, SendNotificationResponse,
+    NotificationPreferences, UpdateNotificationPreferencesRequest, DigestListQuery, SetQuietHoursRequest,
 };
+use crate::services::{quiet_hours_service, WeeklyDigestService};
+use crate::utils::quiet_hours::QuietHours;
 
 /// Register a push notification token for the authenticated user
 pub async fn register_push_token(
@@ -297,6 +300,16 @@ pub async fn send_notification_to_user(
 ) -> Result<(), String> {
     info!("Sending notification to single user {}: {}", user_id, title);
 
+    // Quiet hours hold back pushes that can wait; the notification center already has them
+    match quiet_hours_service::defer_if_quiet(pool, user_id, &title, &body, data.as_ref(), notification_type.as_deref()).await {
+        Ok(Some(deliver_at)) => {
+            info!("🌙 User {} is in quiet hours, push deferred until {}", user_id, deliver_at);
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(e) => error!("Failed to check quiet hours of user {}, pushing now: {}", user_id, e),
+    }
+
     let req = SendNotificationRequest {
         user_ids: vec![user_id],
         title,
@@ -456,3 +469,198 @@ pub async fn get_badge_count(
         "message_count": message_count,
     })))
 }
+
+/// Get the authenticated user's notification channel preferences
+pub async fn get_notification_preferences(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> actix_web::Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        r#"
+        SELECT push_enabled, in_app_enabled, email_enabled, weekly_digest_enabled,
+               inactivity_nudges_enabled, team_inactivity_alerts_enabled,
+               quiet_hours_start, quiet_hours_end, quiet_hours_timezone
+        FROM notification_preferences
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool.as_ref())
+    .await
+    .map_err(|e| {
+        error!("Database error fetching notification preferences: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?
+    .unwrap_or_default();
+
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Update the authenticated user's notification channel preferences; omitted fields keep their value
+pub async fn update_notification_preferences(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<UpdateNotificationPreferencesRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    let defaults = NotificationPreferences::default();
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        r#"
+        INSERT INTO notification_preferences (
+            user_id, push_enabled, in_app_enabled, email_enabled, weekly_digest_enabled,
+            inactivity_nudges_enabled, team_inactivity_alerts_enabled
+        )
+        VALUES (
+            $1,
+            COALESCE($2::boolean, $8),
+            COALESCE($3::boolean, $9),
+            COALESCE($4::boolean, $10),
+            COALESCE($5::boolean, $11),
+            COALESCE($6::boolean, $12),
+            COALESCE($7::boolean, $13)
+        )
+        ON CONFLICT (user_id) DO UPDATE SET
+            push_enabled = COALESCE($2, notification_preferences.push_enabled),
+            in_app_enabled = COALESCE($3, notification_preferences.in_app_enabled),
+            email_enabled = COALESCE($4, notification_preferences.email_enabled),
+            weekly_digest_enabled = COALESCE($5, notification_preferences.weekly_digest_enabled),
+            inactivity_nudges_enabled = COALESCE($6, notification_preferences.inactivity_nudges_enabled),
+            team_inactivity_alerts_enabled = COALESCE($7, notification_preferences.team_inactivity_alerts_enabled),
+            updated_at = NOW()
+        RETURNING push_enabled, in_app_enabled, email_enabled, weekly_digest_enabled,
+                  inactivity_nudges_enabled, team_inactivity_alerts_enabled,
+                  quiet_hours_start, quiet_hours_end, quiet_hours_timezone
+        "#,
+        user_id,
+        req.push_enabled,
+        req.in_app_enabled,
+        req.email_enabled,
+        req.weekly_digest_enabled,
+        req.inactivity_nudges_enabled,
+        req.team_inactivity_alerts_enabled,
+        defaults.push_enabled,
+        defaults.in_app_enabled,
+        defaults.email_enabled,
+        defaults.weekly_digest_enabled,
+        defaults.inactivity_nudges_enabled,
+        defaults.team_inactivity_alerts_enabled
+    )
+    .fetch_one(pool.as_ref())
+    .await
+    .map_err(|e| {
+        error!("Database error updating notification preferences: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    info!("Updated notification preferences for user_id={}", user_id);
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Set the authenticated user's quiet hours, during which non-critical push notifications wait
+pub async fn set_quiet_hours(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<SetQuietHoursRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    let quiet_hours = match QuietHours::parse(&req.start, &req.end, &req.timezone) {
+        Ok(quiet_hours) => quiet_hours,
+        Err(message) => return Ok(HttpResponse::BadRequest().json(json!({ "error": message }))),
+    };
+
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        r#"
+        INSERT INTO notification_preferences (user_id, quiet_hours_start, quiet_hours_end, quiet_hours_timezone)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id) DO UPDATE SET
+            quiet_hours_start = $2,
+            quiet_hours_end = $3,
+            quiet_hours_timezone = $4,
+            updated_at = NOW()
+        RETURNING push_enabled, in_app_enabled, email_enabled, weekly_digest_enabled,
+                  inactivity_nudges_enabled, team_inactivity_alerts_enabled,
+                  quiet_hours_start, quiet_hours_end, quiet_hours_timezone
+        "#,
+        user_id,
+        quiet_hours.start,
+        quiet_hours.end,
+        quiet_hours.timezone.name()
+    )
+    .fetch_one(pool.as_ref())
+    .await
+    .map_err(|e| {
+        error!("Database error setting quiet hours: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    info!("Set quiet hours for user_id={}", user_id);
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Turn off the authenticated user's quiet hours. Pushes already held back still wait for their window's end.
+pub async fn clear_quiet_hours(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> actix_web::Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE notification_preferences
+        SET quiet_hours_start = NULL, quiet_hours_end = NULL, updated_at = NOW()
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(pool.as_ref())
+    .await
+    .map_err(|e| {
+        error!("Database error clearing quiet hours: {}", e);
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+
+    info!("Cleared quiet hours for user_id={}", user_id);
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get the authenticated user's most recent weekly digests
+pub async fn get_weekly_digests(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<DigestListQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        error!("Invalid user ID in claims");
+        return Err(actix_web::error::ErrorBadRequest("Invalid user ID"));
+    };
+
+    let limit = query.limit.unwrap_or(4).clamp(1, 52);
+    let digests = WeeklyDigestService::new(pool.get_ref().clone())
+        .get_recent_digests(user_id, limit)
+        .await
+        .map_err(|e| {
+            error!("Database error fetching weekly digests: {}", e);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(digests))
+}
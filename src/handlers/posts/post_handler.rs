@@ -7,12 +7,15 @@ use std::sync::Arc;
 
 use crate::{
     middleware::auth::Claims,
-    models::post::{CreatePostRequest, UpdatePostRequest, PostType, PostVisibility},
+    models::post::{CreatePostRequest, UpdatePostRequest, PublishPostRequest, PostType, PostVisibility},
     models::common::ApiResponse,
     models::social::NotificationType,
     utils::mention_parser::extract_unique_mentions,
     services::social_events,
     db::social::create_notification,
+    db::activities::find_active_activity,
+    db::workout_repo::{WorkoutRepo, WorkoutRepository},
+    services::post_publishing_service::{create_draft, validate_publish_at, PostPublishingService},
 };
 
 /// Create a new post
@@ -41,15 +44,9 @@ pub async fn create_post(
 
     // Verify workout belongs to user if workout_id provided
     if let Some(workout_id) = body.workout_id {
-        match sqlx::query!(
-            "SELECT user_id FROM workout_data WHERE id = $1",
-            workout_id
-        )
-        .fetch_optional(&**pool)
-        .await
-        {
-            Ok(Some(workout)) => {
-                if workout.user_id != user_id {
+        match WorkoutRepo::new(pool.get_ref().clone()).find_owner(workout_id).await {
+            Ok(Some(owner_id)) => {
+                if owner_id != user_id {
                     return HttpResponse::Forbidden().json(
                         ApiResponse::<()>::error("You can only create posts for your own workouts")
                     );
@@ -71,6 +68,16 @@ pub async fn create_post(
 
     let visibility = body.visibility.clone().unwrap_or(PostVisibility::Public);
 
+    // Drafts stay private until they are published, by their author or at publish_at
+    let now = Utc::now();
+    if let Some(publish_at) = body.publish_at {
+        if let Err(e) = validate_publish_at(publish_at, now) {
+            return HttpResponse::BadRequest().json(ApiResponse::<()>::error(e));
+        }
+    }
+    let is_draft = body.draft || body.publish_at.is_some_and(|publish_at| publish_at > now);
+    let post_visibility = if is_draft { PostVisibility::Private } else { visibility.clone() };
+
     // Convert media_urls to JSON
     let media_urls_json = body.media_urls.as_ref().map(|media| {
         serde_json::to_value(media).unwrap_or(serde_json::Value::Null)
@@ -79,34 +86,43 @@ pub async fn create_post(
     // Insert post
     let post_id = Uuid::new_v4();
     let post_type_str = body.post_type.as_str();
-    let visibility_str = visibility.as_str();
+    let visibility_str = post_visibility.as_str();
 
-    let result = sqlx::query(
-        r#"
-        INSERT INTO posts (
-            id, user_id, post_type, content, workout_id,
-            media_urls, visibility
+    let result = async {
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO posts (
+                id, user_id, post_type, content, workout_id,
+                media_urls, visibility
+            )
+            VALUES ($1, $2, $3::post_type, $4, $5, $6, $7::post_visibility)
+            RETURNING id
+            "#
         )
-        VALUES ($1, $2, $3::post_type, $4, $5, $6, $7::post_visibility)
-        RETURNING id
-        "#
-    )
-    .bind(post_id)
-    .bind(user_id)
-    .bind(post_type_str)
-    .bind(body.content.as_ref())
-    .bind(body.workout_id)
-    .bind(media_urls_json)
-    .bind(visibility_str)
-    .fetch_one(&**pool)
+        .bind(post_id)
+        .bind(user_id)
+        .bind(post_type_str)
+        .bind(body.content.as_ref())
+        .bind(body.workout_id)
+        .bind(media_urls_json)
+        .bind(visibility_str)
+        .fetch_one(&mut *tx)
+        .await?;
+        if is_draft {
+            create_draft(&mut tx, post_id, visibility, body.publish_at).await?;
+        }
+        tx.commit().await
+    }
     .await;
 
     match result {
         Ok(_) => {
-            tracing::info!("Created post {} for user {}", post_id, claims.username);
+            tracing::info!("Created {} {} for user {}", if is_draft { "draft post" } else { "post" }, post_id, claims.username);
 
-            // Extract mentions from post content and create notifications
-            if let Some(ref content) = body.content {
+            // Extract mentions from post content and create notifications; nobody is told
+            // about a draft they can't see yet
+            if let Some(content) = body.content.as_ref().filter(|_| !is_draft) {
                 let mentions = extract_unique_mentions(content);
                 if !mentions.is_empty() {
                     tracing::info!("Found {} mentions in post {}: {:?}", mentions.len(), post_id, mentions);
@@ -187,7 +203,8 @@ pub async fn create_post(
             HttpResponse::Ok().json(json!({
                 "success": true,
                 "data": {
-                    "id": post_id
+                    "id": post_id,
+                    "draft": is_draft
                 }
             }))
         }
@@ -263,9 +280,54 @@ pub async fn update_post(
         );
     }
 
+    // The edited activity must be in the catalog; store its canonical name
+    let user_activity = match body.activity_name.as_deref() {
+        Some(activity_name) if post.2 == "workout" => match find_active_activity(&pool, activity_name).await {
+            Ok(Some(activity)) => Some(activity.name),
+            Ok(None) => {
+                return HttpResponse::BadRequest().json(
+                    ApiResponse::<()>::error(format!("Unknown activity: {}", activity_name))
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to look up activity {}: {}", activity_name, e);
+                return HttpResponse::InternalServerError().json(
+                    ApiResponse::<()>::error("Database error")
+                );
+            }
+        },
+        _ => None,
+    };
+
     // Build update query dynamically based on provided fields
     let now = Utc::now();
-    let visibility_str = body.visibility.as_ref().map(|v| v.as_str());
+    // A draft stays private; the requested visibility applies once it is published
+    let visibility_str = match &body.visibility {
+        Some(visibility) => {
+            let publishing = PostPublishingService::new(pool.get_ref().clone());
+            let is_draft = match publishing.is_draft_of(post_id, user_id).await {
+                Ok(is_draft) => is_draft,
+                Err(e) => {
+                    tracing::error!("Database error: {}", e);
+                    return HttpResponse::InternalServerError().json(
+                        ApiResponse::<()>::error("Database error")
+                    );
+                }
+            };
+            if is_draft {
+                if let Err(e) = publishing.set_publish_visibility(post_id, visibility.clone()).await {
+                    tracing::error!("Failed to update visibility of draft post {}: {}", post_id, e);
+                    return HttpResponse::InternalServerError().json(
+                        ApiResponse::<()>::error("Failed to update post")
+                    );
+                }
+                None
+            } else {
+                Some(visibility.as_str())
+            }
+        }
+        None => None,
+    };
 
     // Convert media_urls to JSON
     // Important: We need to distinguish between "not updating media" (None) and "clearing media" (Some(empty array))
@@ -317,7 +379,7 @@ pub async fn update_post(
 
     // If it's a workout post and activity_name is provided, update the workout
     // Store user-edited activity in user_activity field (not activity_name which is read-only)
-    if post.2 == "workout" && body.activity_name.is_some() {
+    if let Some(user_activity) = user_activity {
         if let Some(workout_id) = post.3 {
             let _ = sqlx::query(
                 r#"
@@ -327,7 +389,7 @@ pub async fn update_post(
                 WHERE id = $2
                 "#
             )
-            .bind(body.activity_name.as_ref())
+            .bind(user_activity)
             .bind(workout_id)
             .execute(&**pool)
             .await;
@@ -584,7 +646,7 @@ pub async fn get_post(
     claims: web::ReqData<Claims>,
     post_id: web::Path<Uuid>,
 ) -> HttpResponse {
-    let Some(_current_user_id) = claims.user_id() else {
+    let Some(current_user_id) = claims.user_id() else {
         tracing::error!("Invalid user ID in claims");
         return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
     };
@@ -603,7 +665,8 @@ pub async fn get_post(
             wd.calories_burned, wd.activity_name, wd.user_activity, wd.avg_heart_rate,
             wd.max_heart_rate, wd.heart_rate_zones, wd.stamina_gained,
             wd.strength_gained, wd.total_points_gained,
-            wd.image_url as workout_image_url, wd.video_url as workout_video_url
+            wd.image_url as workout_image_url, wd.video_url as workout_video_url,
+            wd.notes as workout_notes
         FROM posts p
         JOIN users u ON u.id = p.user_id
         LEFT JOIN workout_data wd ON wd.id = p.workout_id
@@ -617,6 +680,10 @@ pub async fn get_post(
     match result {
         Ok(Some(row)) => {
             // Build workout_data if this is a workout post
+            // Workout notes are shown to the owner, and to everyone on public posts
+            let show_notes = row.try_get::<Uuid, _>("user_id").ok() == Some(current_user_id)
+                || row.try_get::<String, _>("visibility").ok().as_deref() == Some("public");
+
             let workout_data = if row.try_get::<Option<Uuid>, _>("workout_id").ok().flatten().is_some() {
                 Some(json!({
                     "workout_start": row.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("workout_start").ok().flatten(),
@@ -633,6 +700,7 @@ pub async fn get_post(
                     "total_points_gained": row.try_get::<i32, _>("total_points_gained").ok(),
                     "image_url": row.try_get::<Option<String>, _>("workout_image_url").ok().flatten(),
                     "video_url": row.try_get::<Option<String>, _>("workout_video_url").ok().flatten(),
+                    "notes": row.try_get::<Option<String>, _>("workout_notes").ok().flatten().filter(|_| show_notes),
                 }))
             } else {
                 None
@@ -707,7 +775,8 @@ pub async fn get_post_by_workout_id(
             wd.calories_burned, wd.activity_name, wd.avg_heart_rate,
             wd.max_heart_rate, wd.heart_rate_zones, wd.stamina_gained,
             wd.strength_gained, wd.total_points_gained,
-            wd.image_url as workout_image_url, wd.video_url as workout_video_url
+            wd.image_url as workout_image_url, wd.video_url as workout_video_url,
+            wd.notes as workout_notes
         FROM posts p
         JOIN users u ON u.id = p.user_id
         LEFT JOIN workout_data wd ON wd.id = p.workout_id
@@ -735,7 +804,8 @@ pub async fn get_post_by_workout_id(
                     "stamina_gained": row.try_get::<Option<f64>, _>("stamina_gained").ok().flatten(),
                     "strength_gained": row.try_get::<Option<f64>, _>("strength_gained").ok().flatten(),
                     "image_url": row.try_get::<Option<String>, _>("workout_image_url").ok().flatten(),
-                    "video_url": row.try_get::<Option<String>, _>("workout_video_url").ok().flatten()
+                    "video_url": row.try_get::<Option<String>, _>("workout_video_url").ok().flatten(),
+                    "notes": row.try_get::<Option<String>, _>("workout_notes").ok().flatten()
                 }))
             } else {
                 None
@@ -779,3 +849,78 @@ pub async fn get_post_by_workout_id(
         }
     }
 }
+
+/// List the authenticated user's draft posts
+#[tracing::instrument(
+    name = "List draft posts",
+    skip(pool, claims),
+    fields(username = %claims.username)
+)]
+pub async fn list_drafts(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match PostPublishingService::new(pool.get_ref().clone()).drafts(user_id).await {
+        Ok(drafts) => HttpResponse::Ok().json(ApiResponse::success("Draft posts retrieved successfully", drafts)),
+        Err(e) => {
+            tracing::error!("Failed to fetch draft posts: {}", e);
+            HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to fetch draft posts")
+            )
+        }
+    }
+}
+
+/// Publish a draft post now, or schedule it with `publish_at`
+#[tracing::instrument(
+    name = "Publish post",
+    skip(pool, claims, body),
+    fields(username = %claims.username, post_id = %post_id)
+)]
+pub async fn publish_post(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    post_id: web::Path<Uuid>,
+    body: Option<web::Json<PublishPostRequest>>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let post_id = post_id.into_inner();
+    let now = Utc::now();
+    let publish_at = body.and_then(|body| body.publish_at).filter(|publish_at| *publish_at > now);
+    if let Some(publish_at) = publish_at {
+        if let Err(e) = validate_publish_at(publish_at, now) {
+            return HttpResponse::BadRequest().json(ApiResponse::<()>::error(e));
+        }
+    }
+
+    let publishing = PostPublishingService::new(pool.get_ref().clone());
+    let result = match publish_at {
+        Some(publish_at) => publishing.schedule(post_id, user_id, publish_at).await,
+        None => publishing.publish(post_id, user_id).await,
+    };
+
+    match result {
+        Ok(true) => HttpResponse::Ok().json(ApiResponse::success(
+            if publish_at.is_some() { "Post scheduled successfully" } else { "Post published successfully" },
+            json!({ "id": post_id, "publish_at": publish_at }),
+        )),
+        Ok(false) => HttpResponse::NotFound().json(
+            ApiResponse::<()>::error("Draft post not found")
+        ),
+        Err(e) => {
+            tracing::error!("Failed to publish post {}: {}", post_id, e);
+            HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to publish post")
+            )
+        }
+    }
+}
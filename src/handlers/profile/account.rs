@@ -0,0 +1,85 @@
+use actix_web::{http::header, web, HttpResponse};
+use futures_util::TryStreamExt;
+use sqlx::PgPool;
+
+use crate::db::account_deletions::request_deletion;
+use crate::db::api_keys::revoke_all_api_keys_for_user;
+use crate::db::refresh_tokens::revoke_all_for_user;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::services::AccountDataService;
+
+/// Request deletion of the authenticated user's account. The account is deactivated, logged out
+/// everywhere and its API keys revoked right away, and purged after the grace period unless the
+/// user logs in again.
+pub async fn delete_account(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    // Purging an account deletes the teams it owns along with their games
+    let owned_teams = sqlx::query_scalar!("SELECT team_name FROM teams WHERE user_id = $1 ORDER BY team_name", user_id)
+        .fetch_all(pool.get_ref())
+        .await;
+    match owned_teams {
+        Ok(teams) if !teams.is_empty() => {
+            return HttpResponse::Conflict().json(ApiResponse::<()>::error(format!(
+                "Hand over or delete your teams before deleting your account: {}",
+                teams.join(", ")
+            )));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to check teams owned by user {}: {}", user_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to delete account"));
+        }
+    }
+
+    let deletion = match request_deletion(pool.get_ref(), user_id).await {
+        Ok(Some(deletion)) => deletion,
+        Ok(None) => return HttpResponse::NotFound().json(ApiResponse::<()>::error("User not found")),
+        Err(e) => {
+            tracing::error!("Failed to request deletion of account {}: {}", user_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to delete account"));
+        }
+    };
+
+    if let Err(e) = revoke_all_for_user(pool.get_ref(), user_id).await {
+        tracing::error!("Failed to log out account {} pending deletion: {}", user_id, e);
+    }
+    if let Err(e) = revoke_all_api_keys_for_user(pool.get_ref(), user_id).await {
+        tracing::error!("Failed to revoke API keys of account {} pending deletion: {}", user_id, e);
+    }
+    tracing::info!("User {} requested account deletion, purging after {}", user_id, deletion.purge_after);
+    HttpResponse::Ok().json(ApiResponse::success(
+        "Account scheduled for deletion. Log in again before it is purged to keep it",
+        deletion,
+    ))
+}
+
+/// Download everything stored about the authenticated user as one JSON document: account,
+/// health profile, workouts with heart rate samples, social activity and uploaded media references
+pub async fn export_account_data(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    tracing::info!("Streaming data export of user {}", user_id);
+    let body = AccountDataService::new(pool.get_ref().clone())
+        .export(user_id)
+        .inspect_err(move |e| tracing::error!("Data export of user {} aborted mid-stream: {}", user_id, e));
+
+    let filename = format!("riina_export_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header((header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")))
+        .streaming(body)
+}
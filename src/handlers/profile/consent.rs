@@ -0,0 +1,77 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::research::{ConsentSettings, UpdateConsentSettingsRequest};
+
+/// Get the authenticated user's data sharing consent
+pub async fn get_consent_settings(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let settings = sqlx::query_as!(
+        ConsentSettings,
+        "SELECT research_data_sharing FROM user_consent_settings WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match settings {
+        Ok(settings) => HttpResponse::Ok().json(ApiResponse::success(
+            "Consent settings retrieved successfully",
+            settings.unwrap_or_default(),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to fetch consent settings for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to fetch consent settings"))
+        }
+    }
+}
+
+/// Update the authenticated user's data sharing consent; omitted fields keep their value
+pub async fn update_consent_settings(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateConsentSettingsRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let defaults = ConsentSettings::default();
+    let settings = sqlx::query_as!(
+        ConsentSettings,
+        r#"
+        INSERT INTO user_consent_settings (user_id, research_data_sharing)
+        VALUES ($1, COALESCE($2::boolean, $3))
+        ON CONFLICT (user_id) DO UPDATE SET
+            research_data_sharing = COALESCE($2, user_consent_settings.research_data_sharing),
+            updated_at = NOW()
+        RETURNING research_data_sharing
+        "#,
+        user_id,
+        request.research_data_sharing,
+        defaults.research_data_sharing
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match settings {
+        Ok(settings) => {
+            tracing::info!("User {} set research data sharing to {}", user_id, settings.research_data_sharing);
+            HttpResponse::Ok().json(ApiResponse::success("Consent settings updated successfully", settings))
+        }
+        Err(e) => {
+            tracing::error!("Failed to update consent settings for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to update consent settings"))
+        }
+    }
+}
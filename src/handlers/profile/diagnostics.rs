@@ -0,0 +1,32 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::diagnostics::DiagnosticsQuery;
+use crate::services::SyncDiagnosticsService;
+
+/// Get the authenticated user's recent upload attempts and device syncs
+pub async fn get_sync_diagnostics(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<DiagnosticsQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+    diagnostics_response(pool.get_ref(), user_id, &query).await
+}
+
+/// Sync diagnostics of any user, shared with the admin endpoint support uses
+pub(crate) async fn diagnostics_response(pool: &PgPool, user_id: Uuid, query: &DiagnosticsQuery) -> HttpResponse {
+    match SyncDiagnosticsService::new(pool.clone()).diagnostics(user_id, query).await {
+        Ok(diagnostics) => HttpResponse::Ok().json(ApiResponse::success("Sync diagnostics retrieved", diagnostics)),
+        Err(e) => {
+            tracing::error!("Failed to fetch sync diagnostics of user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to fetch sync diagnostics"))
+        }
+    }
+}
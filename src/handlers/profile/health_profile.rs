@@ -1,16 +1,21 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use sqlx::PgPool;
 
 use crate::middleware::auth::Claims;
+use crate::middleware::etag::{if_match_version, version_etag};
 use crate::models::{
-    profile::{HealthProfileResponse, UpdateHealthProfileRequest},
+    profile::{DeviceHealthProfile, HealthProfileHistoryQuery, HealthProfileResponse, UpdateHealthProfileRequest},
     health::Gender,
 };
 use crate::utils::health_calculations::calc_max_heart_rate;
-use crate::db::health_data::update_max_heart_rate_and_vt_thresholds;
+use crate::services::threshold_detection_service::{ThresholdDetectionService, ThresholdProposalError};
+use crate::db::health_data::{get_health_profile_history, import_device_health_profile, update_max_heart_rate_and_vt_thresholds};
+
+const DEFAULT_HISTORY_LIMIT: i64 = 100;
+const MAX_HISTORY_LIMIT: i64 = 500;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct HealthProfileQuery {
@@ -57,10 +62,15 @@ pub async fn get_health_profile(
     match sqlx::query_as!(
         HealthProfileResponse,
         r#"
-        SELECT id, user_id, age, gender, resting_heart_rate, max_heart_rate,
-               vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, weight, height, last_updated
-        FROM user_health_profiles
-        WHERE user_id = $1
+        SELECT p.id, p.user_id, p.age, p.gender, p.resting_heart_rate, p.max_heart_rate,
+               p.vt_off_threshold, p.vt0_threshold, p.vt1_threshold, p.vt2_threshold, p.vt1_source, p.vt2_source,
+               p.weight, p.height,
+               p.last_updated, p.version,
+               COALESCE(c.factor, 1.0) as "scoring_calibration!",
+               COALESCE(c.rated_workouts, 0) as "calibration_rated_workouts!"
+        FROM user_health_profiles p
+        LEFT JOIN user_scoring_calibrations c ON c.user_id = p.user_id
+        WHERE p.user_id = $1
         "#,
         target_user_id
     )
@@ -77,10 +87,12 @@ pub async fn get_health_profile(
 
             tracing::info!("Successfully retrieved health profile for user: {} (own profile: {})",
                           target_user_id, is_own_profile);
-            HttpResponse::Ok().json(json!({
-                "success": true,
-                "data": profile
-            }))
+            HttpResponse::Ok()
+                .insert_header((header::ETAG, version_etag(profile.version)))
+                .json(json!({
+                    "success": true,
+                    "data": profile
+                }))
         }
         Ok(None) => {
             tracing::info!("No health profile found for user: {}", target_user_id);
@@ -99,13 +111,14 @@ pub async fn get_health_profile(
 
 #[tracing::instrument(
     name = "Update health profile",
-    skip(pool, claims, profile_data),
+    skip(pool, claims, profile_data, req),
     fields(username = %claims.username)
 )]
 pub async fn update_health_profile(
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
-    profile_data: web::Json<UpdateHealthProfileRequest>
+    profile_data: web::Json<UpdateHealthProfileRequest>,
+    req: HttpRequest,
 ) -> HttpResponse {
     let Some(user_id) = claims.user_id() else {
         tracing::error!("Invalid user ID in claims");
@@ -113,6 +126,14 @@ pub async fn update_health_profile(
             "error": "Invalid user ID"
         }));
     };
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(json!({
+                "error": message
+            }));
+        }
+    };
     tracing::info!("Updating health profile for user: {}", user_id);
 
     // Validate input data
@@ -148,6 +169,8 @@ pub async fn update_health_profile(
         }
     }
 
+    // With If-Match the existing row is only updated if it is still at that version;
+    // otherwise no row comes back and the client gets 409 with the current profile
     let result = sqlx::query!(
         r#"
         INSERT INTO user_health_profiles (user_id, age, gender, resting_heart_rate, weight, height, last_updated)
@@ -159,7 +182,9 @@ pub async fn update_health_profile(
             resting_heart_rate = COALESCE($4, user_health_profiles.resting_heart_rate),
             weight = COALESCE($5, user_health_profiles.weight),
             height = COALESCE($6, user_health_profiles.height),
-            last_updated = NOW()
+            last_updated = NOW(),
+            version = user_health_profiles.version + 1
+        WHERE $7::int IS NULL OR user_health_profiles.version = $7
         RETURNING id, age, resting_heart_rate
         "#,
         user_id,
@@ -167,13 +192,32 @@ pub async fn update_health_profile(
         profile_data.gender.as_deref(),
         profile_data.resting_heart_rate,
         profile_data.weight,
-        profile_data.height
+        profile_data.height,
+        expected_version
     )
-    .fetch_one(&**pool)
+    .fetch_optional(&**pool)
     .await;
 
     match result {
-        Ok(profile_record) => {
+        Ok(None) => {
+            tracing::info!("Health profile update for user {} rejected: version mismatch", user_id);
+            match fetch_health_profile(&pool, user_id).await {
+                Ok(current) => HttpResponse::Conflict()
+                    .insert_header((header::ETAG, version_etag(current.version)))
+                    .json(json!({
+                        "success": false,
+                        "error": "Health profile was changed elsewhere. Review the current version and try again.",
+                        "data": current
+                    })),
+                Err(e) => {
+                    tracing::error!("Failed to fetch current profile after conflict: {}", e);
+                    HttpResponse::InternalServerError().json(json!({
+                        "error": "Failed to update health profile"
+                    }))
+                }
+            }
+        }
+        Ok(Some(profile_record)) => {
             tracing::info!("Successfully updated health profile for user: {}", claims.username);
             
             // Calculate and store heart rate zones if we have age and resting heart rate
@@ -202,24 +246,14 @@ pub async fn update_health_profile(
             }
             
             // Fetch and return the updated profile
-            match sqlx::query_as!(
-                HealthProfileResponse,
-                r#"
-                SELECT id, user_id, age, gender, resting_heart_rate, max_heart_rate,
-                       vt_off_threshold, vt0_threshold, vt1_threshold, vt2_threshold, weight, height, last_updated
-                FROM user_health_profiles
-                WHERE user_id = $1
-                "#,
-                user_id
-            )
-            .fetch_one(&**pool)
-            .await
-            {
-                Ok(profile) => HttpResponse::Ok().json(json!({
-                    "success": true,
-                    "data": profile,
-                    "message": "Health profile updated successfully"
-                })),
+            match fetch_health_profile(&pool, user_id).await {
+                Ok(profile) => HttpResponse::Ok()
+                    .insert_header((header::ETAG, version_etag(profile.version)))
+                    .json(json!({
+                        "success": true,
+                        "data": profile,
+                        "message": "Health profile updated successfully"
+                    })),
                 Err(e) => {
                     tracing::error!("Failed to fetch updated profile: {}", e);
                     HttpResponse::InternalServerError().json(json!({
@@ -235,4 +269,202 @@ pub async fn update_health_profile(
             }))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Create the user's health profile from values their device provided, on the first sync of
+/// users who registered without them. Profiles that exist already are not touched.
+#[tracing::instrument(
+    name = "Import device health profile",
+    skip(pool, claims, device_profile),
+    fields(username = %claims.username)
+)]
+pub async fn import_health_profile(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    device_profile: web::Json<DeviceHealthProfile>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    if let Err(message) = device_profile.validate() {
+        return HttpResponse::BadRequest().json(json!({
+            "error": message
+        }));
+    }
+
+    let imported = match pool.acquire().await {
+        Ok(mut conn) => import_device_health_profile(&mut conn, user_id, &device_profile).await,
+        Err(e) => Err(e),
+    };
+    match imported {
+        Ok(false) => HttpResponse::Conflict().json(json!({
+            "success": false,
+            "error": "Health profile is already set up, update it instead"
+        })),
+        Ok(true) => match fetch_health_profile(&pool, user_id).await {
+            Ok(profile) => {
+                tracing::info!("Imported device health profile for user: {}", user_id);
+                HttpResponse::Created()
+                    .insert_header((header::ETAG, version_etag(profile.version)))
+                    .json(json!({
+                        "success": true,
+                        "data": profile,
+                        "message": "Health profile imported successfully"
+                    }))
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch imported profile: {}", e);
+                HttpResponse::InternalServerError().json(json!({
+                    "error": "Profile imported but failed to retrieve it"
+                }))
+            }
+        },
+        Err(e) => {
+            tracing::error!("Database error importing health profile: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to import health profile"
+            }))
+        }
+    }
+}
+
+/// The user's health profile snapshots over time, oldest first, for charting trends
+#[tracing::instrument(
+    name = "Get health profile history",
+    skip(pool, claims, query),
+    fields(username = %claims.username)
+)]
+pub async fn get_health_profile_history_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<HealthProfileHistoryQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, MAX_HISTORY_LIMIT);
+
+    match get_health_profile_history(&pool, user_id, query.from, query.to, limit).await {
+        Ok(history) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": history
+        })),
+        Err(e) => {
+            tracing::error!("Database error fetching health profile history: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch health profile history"
+            }))
+        }
+    }
+}
+
+async fn fetch_health_profile(pool: &PgPool, user_id: Uuid) -> Result<HealthProfileResponse, sqlx::Error> {
+    sqlx::query_as!(
+        HealthProfileResponse,
+        r#"
+        SELECT p.id, p.user_id, p.age, p.gender, p.resting_heart_rate, p.max_heart_rate,
+               p.vt_off_threshold, p.vt0_threshold, p.vt1_threshold, p.vt2_threshold, p.vt1_source, p.vt2_source,
+               p.weight, p.height,
+               p.last_updated, p.version,
+               COALESCE(c.factor, 1.0) as "scoring_calibration!",
+               COALESCE(c.rated_workouts, 0) as "calibration_rated_workouts!"
+        FROM user_health_profiles p
+        LEFT JOIN user_scoring_calibrations c ON c.user_id = p.user_id
+        WHERE p.user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+#[derive(Debug, Deserialize)]
+pub struct ThresholdProposalsQuery {
+    #[serde(default)]
+    pub pending_only: bool,
+}
+
+/// VT1/VT2 proposals detected from the user's workouts, newest first
+#[tracing::instrument(
+    name = "Get threshold proposals",
+    skip(pool, claims, query),
+    fields(username = %claims.username)
+)]
+pub async fn get_threshold_proposals(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ThresholdProposalsQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    match ThresholdDetectionService::new(pool.get_ref().clone()).list_proposals(user_id, query.pending_only).await {
+        Ok(proposals) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": proposals
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch threshold proposals: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch threshold proposals"
+            }))
+        }
+    }
+}
+
+/// Accept or decline a pending threshold proposal
+#[tracing::instrument(
+    name = "Decide threshold proposal",
+    skip(pool, claims),
+    fields(username = %claims.username)
+)]
+pub async fn decide_threshold_proposal(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    proposal_id: Uuid,
+    accept: bool,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    let service = ThresholdDetectionService::new(pool.get_ref().clone());
+    let decided = if accept {
+        service.accept(user_id, proposal_id).await
+    } else {
+        service.decline(user_id, proposal_id).await
+    };
+    match decided {
+        Ok(proposal) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": proposal,
+            "message": if accept { "Thresholds updated" } else { "Threshold proposal declined" }
+        })),
+        Err(ThresholdProposalError::NotFound) => HttpResponse::NotFound().json(json!({
+            "error": ThresholdProposalError::NotFound.to_string()
+        })),
+        Err(e @ (ThresholdProposalError::NotPending | ThresholdProposalError::NoLongerFits)) => {
+            HttpResponse::Conflict().json(json!({
+                "error": e.to_string()
+            }))
+        }
+        Err(ThresholdProposalError::Database(e)) => {
+            tracing::error!("Database error deciding threshold proposal: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to update threshold proposal"
+            }))
+        }
+    }
+}
@@ -2,4 +2,10 @@
 pub mod profile;
 pub mod health_profile;
 pub mod profile_picture;
-pub mod user_status;
\ No newline at end of file
+pub mod user_status;
+pub mod consent;
+pub mod player_card;
+pub mod posting_settings;
+pub mod account;
+pub mod username;
+pub mod diagnostics;
@@ -0,0 +1,29 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::services::PlayerCardService;
+
+/// Player card of the requesting user
+pub async fn get_own_player_card(pool: web::Data<PgPool>, claims: web::ReqData<Claims>) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+    get_player_card(pool, user_id).await
+}
+
+/// Player card of any user, for the team roster screen
+#[tracing::instrument(name = "Get player card", skip(pool))]
+pub async fn get_player_card(pool: web::Data<PgPool>, user_id: Uuid) -> HttpResponse {
+    match PlayerCardService::new(pool.get_ref().clone()).player_card(user_id).await {
+        Ok(Some(card)) => HttpResponse::Ok().json(ApiResponse::success("Player card retrieved", card)),
+        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<()>::error("User not found")),
+        Err(e) => {
+            tracing::error!("Failed to build player card for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to fetch player card"))
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::db::workout_data::get_workout_posting_settings;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::post::{AutoPostMode, UpdateWorkoutPostingSettingsRequest, WorkoutPostingSettings};
+use crate::workout::post_caption::validate_caption_template;
+
+/// Get the authenticated user's settings for the posts of their workout uploads
+pub async fn get_posting_settings(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let settings = match pool.acquire().await {
+        Ok(mut conn) => get_workout_posting_settings(&mut conn, user_id).await,
+        Err(e) => Err(e),
+    };
+
+    match settings {
+        Ok(settings) => HttpResponse::Ok().json(ApiResponse::success(
+            "Posting settings retrieved successfully",
+            settings,
+        )),
+        Err(e) => {
+            tracing::error!("Failed to fetch posting settings for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to fetch posting settings"))
+        }
+    }
+}
+
+/// Update the authenticated user's posting settings; omitted fields keep their value
+pub async fn update_posting_settings(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateWorkoutPostingSettingsRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let caption_template = request.caption_template.as_deref().map(str::trim);
+    if let Some(template) = caption_template {
+        if let Err(e) = validate_caption_template(template) {
+            return HttpResponse::BadRequest().json(ApiResponse::<()>::error(e));
+        }
+    }
+
+    let defaults = WorkoutPostingSettings::default();
+    let settings = sqlx::query_as!(
+        WorkoutPostingSettings,
+        r#"
+        INSERT INTO user_posting_settings (user_id, workout_auto_post, caption_template)
+        VALUES ($1, COALESCE($2, $3), NULLIF($4, ''))
+        ON CONFLICT (user_id) DO UPDATE SET
+            workout_auto_post = COALESCE($2, user_posting_settings.workout_auto_post),
+            caption_template = CASE WHEN $4::text IS NULL THEN user_posting_settings.caption_template ELSE NULLIF($4, '') END,
+            updated_at = NOW()
+        RETURNING workout_auto_post as "auto_post: _", caption_template
+        "#,
+        user_id,
+        request.auto_post as Option<AutoPostMode>,
+        defaults.auto_post as AutoPostMode,
+        caption_template
+    )
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match settings {
+        Ok(settings) => {
+            tracing::info!("User {} set workout auto-posting to {:?}", user_id, settings.auto_post);
+            HttpResponse::Ok().json(ApiResponse::success("Posting settings updated successfully", settings))
+        }
+        Err(e) => {
+            tracing::error!("Failed to update posting settings for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to update posting settings"))
+        }
+    }
+}
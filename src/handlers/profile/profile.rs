@@ -2,11 +2,12 @@ use actix_web::{web, HttpResponse};
 use serde_json::json;
 use uuid::Uuid;
 use sqlx::PgPool;
+use std::sync::Arc;
 
 use crate::middleware::auth::Claims;
-use crate::models::profile::{UserProfileResponse, GameStats};
+use crate::models::profile::{UserProfileResponse, GameStats, StatDecayEntry};
 use crate::models::common::ApiResponse;
-use crate::utils::trailing_average;
+use crate::services::UserStatsCache;
 use crate::handlers::league::league_users_handler::fetch_all_leaderboard_users;
 
 use serde::Deserialize;
@@ -18,11 +19,12 @@ pub struct UserProfileQuery {
 
 #[tracing::instrument(
     name = "Get user profile",
-    skip(pool, claims, query),
+    skip(pool, redis, claims, query),
     fields(username = %claims.username)
 )]
 pub async fn get_user_profile(
     pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
     claims: web::ReqData<Claims>,
     query: web::Query<UserProfileQuery>
 ) -> HttpResponse {
@@ -114,7 +116,8 @@ pub async fn get_user_profile(
 
     tracing::info!("Getting stats for user: {}", user_id);
     // Get user rank from leaderboard
-    let rank = get_user_rank(&pool, user_id).await.unwrap_or(999);
+    let stats_cache = UserStatsCache::new(pool.get_ref().clone(), redis.map(|r| r.get_ref().clone()));
+    let rank = get_user_rank(&pool, &stats_cache, user_id).await.unwrap_or(999);
 
     // Get avatar style
     let avatar_style = match sqlx::query!(
@@ -135,12 +138,12 @@ pub async fn get_user_profile(
 
     let total_stats = game_stats.stamina + game_stats.strength;
 
-    // Calculate trailing average
-    let trailing_avg = match trailing_average::calculate_trailing_average(&pool, user_id).await {
-        Ok(avg) => avg,
+    // Trailing average and exercise minutes, usually from the stats cache
+    let activity_stats = match stats_cache.get(user_id).await {
+        Ok(stats) => stats,
         Err(e) => {
-            tracing::warn!("Failed to calculate trailing average for user {}: {}", user_id, e);
-            0.0
+            tracing::warn!("Failed to calculate activity stats for user {}: {}", user_id, e);
+            Default::default()
         }
     };
 
@@ -182,44 +185,45 @@ pub async fn get_user_profile(
         }
     };
 
-    // Calculate average exercise minutes per day (last 7 days)
-    let avg_exercise_minutes = match sqlx::query!(
+    // Get user's team_id, status, and role if they're in a team
+    let (team_id, team_status, team_role) = match sqlx::query!(
         r#"
-        SELECT COALESCE(AVG(duration_minutes)::FLOAT, 0.0) as avg_minutes
-        FROM workout_data
+        SELECT team_id, status, role
+        FROM team_members
         WHERE user_id = $1
-        AND workout_start >= NOW() - INTERVAL '7 days'
-        AND duration_minutes IS NOT NULL
         "#,
         user_id
     )
-    .fetch_one(&**pool)
+    .fetch_optional(&**pool)
     .await
     {
-        Ok(row) => row.avg_minutes.unwrap_or(0.0) as f32,
+        Ok(Some(row)) => (Some(row.team_id), Some(row.status), Some(row.role)),
+        Ok(None) => (None, None, None),
         Err(e) => {
-            tracing::warn!("Failed to calculate average exercise minutes for user {}: {}", user_id, e);
-            0.0
+            tracing::warn!("Failed to fetch team info for user {}: {}", user_id, e);
+            (None, None, None)
         }
     };
 
-    // Get user's team_id, status, and role if they're in a team
-    let (team_id, team_status, team_role) = match sqlx::query!(
+    // Recent weekly decay of the stats
+    let stat_decay = match sqlx::query_as!(
+        StatDecayEntry,
         r#"
-        SELECT team_id, status, role
-        FROM team_members
+        SELECT week_start, weekly_percent, stamina_before, stamina_after, strength_before, strength_after, applied_at
+        FROM stat_decay_history
         WHERE user_id = $1
+        ORDER BY week_start DESC
+        LIMIT 8
         "#,
         user_id
     )
-    .fetch_optional(&**pool)
+    .fetch_all(&**pool)
     .await
     {
-        Ok(Some(row)) => (Some(row.team_id), Some(row.status), Some(row.role)),
-        Ok(None) => (None, None, None),
+        Ok(entries) => entries,
         Err(e) => {
-            tracing::warn!("Failed to fetch team info for user {}: {}", user_id, e);
-            (None, None, None)
+            tracing::warn!("Failed to fetch stat decay history for user {}: {}", user_id, e);
+            Vec::new()
         }
     };
 
@@ -230,16 +234,17 @@ pub async fn get_user_profile(
         rank,
         avatar_style,
         total_stats,
-        trailing_average: trailing_avg,
+        trailing_average: activity_stats.trailing_average,
         profile_picture_url: user_info.profile_picture_url,
         created_at: user_info.created_at,
         last_login: None,
         mvp_count,
         lvp_count,
-        avg_exercise_minutes_per_day: avg_exercise_minutes,
+        avg_exercise_minutes_per_day: activity_stats.avg_exercise_minutes_per_day,
         team_id,
         team_status,
         team_role,
+        stat_decay,
     };
 
     tracing::info!("Successfully retrieved profile for user: {}", claims.username);
@@ -267,9 +272,9 @@ async fn create_default_avatar(pool: &PgPool, user_id: Uuid) -> Result<GameStats
     })
 }
 
-async fn get_user_rank(pool: &PgPool, user_id: Uuid) -> Result<i32, sqlx::Error> {
+async fn get_user_rank(pool: &PgPool, stats_cache: &UserStatsCache, user_id: Uuid) -> Result<i32, sqlx::Error> {
     // Use the same leaderboard logic to ensure consistency and prevent duplicates
-    let all_users = fetch_all_leaderboard_users(pool).await?;
+    let all_users = fetch_all_leaderboard_users(pool, stats_cache).await?;
 
     // Find the rank of the target user (ranks are already assigned in fetch_all_leaderboard_users)
     let rank = all_users.iter()
@@ -6,7 +6,9 @@ use uuid::Uuid;
 
 use crate::middleware::auth::Claims;
 use crate::models::common::ApiResponse;
+use crate::models::suspension::UserSuspension;
 use crate::models::user::UserStatus;
+use crate::services::suspension_service::active_suspension;
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateUserStatusRequest {
@@ -18,6 +20,8 @@ pub struct UserStatusResponse {
     pub user_id: Uuid,
     pub status: UserStatus,
     pub in_player_pool: bool,
+    /// Suspension in effect, if an admin suspended the account
+    pub suspension: Option<UserSuspension>,
 }
 
 #[tracing::instrument(
@@ -200,10 +204,12 @@ pub async fn update_user_status(
             .json(ApiResponse::<()>::error("Failed to update status"));
     }
 
+    // Suspended users can't reach this endpoint, see `AuthMiddleware`
     let response = UserStatusResponse {
         user_id,
         status: updated_status,
         in_player_pool,
+        suspension: None,
     };
 
     HttpResponse::Ok().json(ApiResponse::success("Status updated successfully", response))
@@ -237,12 +243,22 @@ pub async fn get_user_status(
     .fetch_optional(&**pool)
     .await;
 
+    let suspension = match active_suspension(&**pool, user_id).await {
+        Ok(suspension) => suspension,
+        Err(e) => {
+            tracing::error!("Failed to fetch suspension status: {}", e);
+            return HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::error("Failed to fetch user status"));
+        }
+    };
+
     match result {
         Ok(Some(record)) => {
             let response = UserStatusResponse {
                 user_id,
                 status: record.status,
                 in_player_pool: record.in_player_pool,
+                suspension,
             };
             HttpResponse::Ok().json(ApiResponse::success("User status retrieved successfully", response))
         }
@@ -0,0 +1,105 @@
+use actix_web::{web, HttpResponse};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+use crate::config::jwt::JwtSettings;
+use crate::db::usernames::{change_username, is_username_taken, last_username_change};
+use crate::handlers::auth_handler::generate_access_token;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::user::{validate_username, ChangeUsernameRequest, ChangeUsernameResponse, USERNAME_CHANGE_COOLDOWN_DAYS};
+
+/// Change the authenticated user's username, at most once per cooldown period. Comes back with
+/// an access token carrying the new name, since handlers take the username from the token.
+#[tracing::instrument(
+    name = "Change username",
+    skip(pool, claims, jwt_settings, request),
+    fields(username = %claims.username)
+)]
+pub async fn update_username(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    jwt_settings: web::Data<JwtSettings>,
+    request: web::Json<ChangeUsernameRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    let new_username = request.username.trim().to_string();
+    if let Err(validation_error) = validate_username(&new_username) {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error(validation_error));
+    }
+
+    match is_username_taken(pool.get_ref(), &new_username, Some(user_id)).await {
+        Ok(true) => return HttpResponse::Conflict().json(ApiResponse::<()>::error("Username already exists")),
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!("Failed to check availability of username {}: {}", new_username, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to change username"));
+        }
+    }
+
+    let changed = async {
+        let mut tx = pool.begin().await?;
+        let user = sqlx::query!(
+            "SELECT username, role, status FROM users WHERE id = $1 FOR UPDATE",
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(user) = user else {
+            return Ok(Err(HttpResponse::NotFound().json(ApiResponse::<()>::error("User not found"))));
+        };
+        if user.username == new_username {
+            return Ok(Err(HttpResponse::BadRequest().json(ApiResponse::<()>::error("That is already your username"))));
+        }
+
+        if let Some(last_change) = last_username_change(&mut tx, user_id).await? {
+            let next_change_at = last_change + Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS);
+            if next_change_at > Utc::now() {
+                return Ok(Err(HttpResponse::TooManyRequests().json(ApiResponse::<()>::error(format!(
+                    "You can change your username again on {}",
+                    next_change_at.format("%Y-%m-%d")
+                )))));
+            }
+        }
+
+        let changed_at = change_username(&mut tx, user_id, &user.username, &new_username).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Ok((user.username, user.role, user.status, changed_at)))
+    }
+    .await;
+
+    let (old_username, role, status, changed_at) = match changed {
+        Ok(Ok(changed)) => changed,
+        Ok(Err(response)) => return response,
+        // Someone else took the name since the check
+        Err(e) if e.as_database_error().is_some_and(|db_error| db_error.is_unique_violation()) => {
+            return HttpResponse::Conflict().json(ApiResponse::<()>::error("Username already exists"));
+        }
+        Err(e) => {
+            tracing::error!("Failed to change username of user {}: {}", user_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to change username"));
+        }
+    };
+
+    let token = match generate_access_token(user_id, new_username.clone(), &role, &status, claims.sid, &jwt_settings) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Error generating JWT token: {:?}", e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to change username"));
+        }
+    };
+
+    tracing::info!("User {} changed username from {} to {}", user_id, old_username, new_username);
+    HttpResponse::Ok().json(ApiResponse::success(
+        "Username changed",
+        ChangeUsernameResponse {
+            username: new_username,
+            token,
+            next_change_at: changed_at + Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS),
+        },
+    ))
+}
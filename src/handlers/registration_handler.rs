@@ -1,18 +1,26 @@
 use actix_web::{web, HttpResponse};
-use secrecy::ExposeSecret;
+use secrecy::{ExposeSecret, SecretString};
 use sqlx::PgPool;
 use chrono::Utc;
+use rand::Rng;
 use uuid::Uuid;
 use std::sync::Arc;
 
-use crate::models::user::{RegistrationRequest, UserRole, UserStatus};
+use crate::db::health_data::import_device_health_profile;
+use crate::db::invites::{find_invite_code, redeem_invite_code};
+use crate::db::usernames::is_username_taken;
+use crate::league::constants::MAX_TEAM_SIZE;
+use crate::models::common::ApiResponse;
+use crate::models::user::{username_from_email, validate_username, CheckUsernameQuery, RegistrationRequest, UserRole, UserStatus, UsernameAvailability};
+use crate::utils::opaque_token::generate_opaque_token;
 use crate::utils::password::hash_password;
 use crate::services::player_pool_events;
+use crate::services::PasswordPolicyService;
 
 #[tracing::instrument(
     name = "Adding a new user",
     // Don't show arguments
-    skip(user_form, pool, redis_client),
+    skip(user_form, pool, redis_client, password_policy),
     fields(
         username = %user_form.username,
         email = %user_form
@@ -22,18 +30,68 @@ pub async fn register_user(
     user_form: web::Json<RegistrationRequest>,
     pool: web::Data<PgPool>,
     redis_client: web::Data<Arc<redis::Client>>,
+    password_policy: web::Data<PasswordPolicyService>,
 ) -> HttpResponse {
-    // Validate the registration request
-    if let Err(validation_error) = user_form.validate() {
-        tracing::warn!("Registration validation failed: {}", validation_error);
+    // Validate the registration request, reporting every problem by field
+    let mut errors = user_form.field_errors();
+    errors.extend(
+        password_policy
+            .check(user_form.password.expose_secret(), &user_form.username, &user_form.email)
+            .await,
+    );
+    if let Some(first) = errors.first() {
+        tracing::warn!("Registration validation failed: {}", first.message);
         return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": validation_error
+            "error": first.message,
+            "errors": errors
         }));
     }
 
+    // The unique constraint only catches exact matches
+    match is_username_taken(&pool, user_form.username.trim(), None).await {
+        Ok(true) => {
+            tracing::warn!("Registration failed, username {} is taken", user_form.username);
+            return HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Username already exists"
+            }));
+        }
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!("Failed to check username availability: {:?}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create user"
+            }));
+        }
+    }
+
+    if let Some(code) = &user_form.invite_code {
+        match check_invite_code(&pool, code).await {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+                tracing::warn!("Registration failed, invite code {} is unusable: {}", code, reason);
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": reason
+                }));
+            }
+            Err(e) => {
+                tracing::error!("Failed to check invite code: {:?}", e);
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to create user"
+                }));
+            }
+        }
+    }
+
     match insert_user(&user_form, &pool, &redis_client).await
     {
         Ok(_) => HttpResponse::Ok().finish(),
+        // The invite code was used up or revoked since it was checked
+        Err(sqlx::Error::RowNotFound) if user_form.invite_code.is_some() => {
+            tracing::warn!("Registration failed, invite code became unusable");
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "This invite code is no longer valid"
+            }))
+        }
         Err(e) => {
             // Check if error is due to unique constraint violation
             if let Some(db_error) = e.as_database_error() {
@@ -62,6 +120,53 @@ pub async fn register_user(
     }
 }
 
+/// Why an invite code can't be registered with, if it can't
+async fn check_invite_code(pool: &PgPool, code: &str) -> Result<Option<&'static str>, sqlx::Error> {
+    let Some(invite) = find_invite_code(pool, code).await? else {
+        return Ok(Some("Invalid invite code"));
+    };
+    if let Some(reason) = invite.unusable_reason(Utc::now()) {
+        return Ok(Some(reason));
+    }
+
+    let member_count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM team_members WHERE team_id = $1 AND status = 'active'",
+        invite.team_id
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
+    if member_count >= MAX_TEAM_SIZE {
+        return Ok(Some("The team of this invite code is already full"));
+    }
+    Ok(None)
+}
+
+/// Whether a username could be registered right now, for validating it while the user types
+pub async fn check_username_availability(
+    query: web::Query<CheckUsernameQuery>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let username = query.username.trim().to_string();
+    let reason = match validate_username(&username) {
+        Err(validation_error) => Some(validation_error),
+        Ok(()) => match is_username_taken(&pool, &username, None).await {
+            Ok(true) => Some("Username already exists".to_string()),
+            Ok(false) => None,
+            Err(e) => {
+                tracing::error!("Failed to check availability of username {}: {:?}", username, e);
+                return HttpResponse::InternalServerError()
+                    .json(ApiResponse::<()>::error("Failed to check username"));
+            }
+        },
+    };
+
+    HttpResponse::Ok().json(ApiResponse::success(
+        "Username checked",
+        UsernameAvailability { username, available: reason.is_none(), reason },
+    ))
+}
+
 pub async fn insert_user(
     user_form: &web::Json<RegistrationRequest>,
     pool: &PgPool,
@@ -110,6 +215,36 @@ pub async fn insert_user(
         e
     })?;
 
+    if let Some(device_profile) = &user_form.device_profile {
+        import_device_health_profile(&mut tx, user_id, device_profile).await.map_err(|e| {
+            tracing::error!("Failed to import the device health profile: {:?}", e);
+            e
+        })?;
+    }
+
+    // Users with an invite code join its team; everyone else starts in the player pool
+    if let Some(code) = &user_form.invite_code {
+        let team_id = redeem_invite_code(&mut tx, code).await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO team_members (team_id, user_id, role, status)
+            VALUES ($1, $2, 'member', 'active')
+            "#,
+            team_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to add user to the team of their invite code: {:?}", e);
+            e
+        })?;
+
+        tx.commit().await?;
+        tracing::info!("User {} successfully registered and joined team {} with an invite code", user_id, team_id);
+        return Ok(());
+    }
+
     // Add user to player pool (since they're active and not on any team)
     sqlx::query!(
         r#"
@@ -143,4 +278,41 @@ pub async fn insert_user(
     }
 
     Ok(())
+}
+
+/// Register a user who signs in through an identity provider (Apple, Google or an
+/// organization's SSO). The username comes from the email and the password is random; a
+/// password reset sets one for logging in without the provider.
+pub async fn insert_external_user(
+    pool: &PgPool,
+    redis_client: &Arc<redis::Client>,
+    email: &str,
+) -> Result<Uuid, sqlx::Error> {
+    let base = username_from_email(email);
+    let mut username = base.clone();
+    for _ in 0..5 {
+        let taken = sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(username) = LOWER($1))", username)
+            .fetch_one(pool)
+            .await?
+            .unwrap_or(false);
+        if !taken {
+            break;
+        }
+        username = format!("{}{}", base, rand::thread_rng().gen_range(1000..10000));
+    }
+
+    let registration = web::Json(RegistrationRequest {
+        username: username.clone(),
+        email: email.to_string(),
+        password: SecretString::new(generate_opaque_token().into_boxed_str()),
+        invite_code: None,
+        device_profile: None,
+    });
+    insert_user(&registration, pool, redis_client).await?;
+
+    let user_id = sqlx::query_scalar!("SELECT id FROM users WHERE username = $1", username)
+        .fetch_one(pool)
+        .await?;
+    tracing::info!("Created user {} for a login through an identity provider", user_id);
+    Ok(user_id)
 }
\ No newline at end of file
@@ -0,0 +1,79 @@
+use actix_web::{http::header, web, HttpResponse, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::share_card::CreateShareCardRequest;
+use crate::services::share_card_service::{ShareCardError, ShareCardService};
+use crate::services::MinIOService;
+
+/// POST /share/cards - Request a shareable card of a badge, game result or personal record.
+/// The image is rendered in the background and served at the returned path.
+pub async fn create_share_card(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    body: web::Json<CreateShareCardRequest>,
+) -> Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID")));
+    };
+
+    let service = ShareCardService::new(pool.get_ref().clone());
+    match service.request(user_id, body.card_type, body.subject_id).await {
+        Ok((card, true)) => {
+            tracing::info!("Queued {} share card {} for user {}", card.card_type, card.id, user_id);
+            Ok(HttpResponse::Created().json(ApiResponse::success("Share card queued", card)))
+        }
+        Ok((card, false)) => Ok(HttpResponse::Ok().json(ApiResponse::success("Share card retrieved", card))),
+        Err(e @ (ShareCardError::GameNotFound | ShareCardError::WorkoutNotFound)) => {
+            Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error(e.to_string())))
+        }
+        Err(e @ (ShareCardError::NotAPlayer | ShareCardError::PrivateWorkout)) => {
+            Ok(HttpResponse::Forbidden().json(ApiResponse::<()>::error(e.to_string())))
+        }
+        Err(e @ (ShareCardError::GameNotFinished | ShareCardError::NoBadge | ShareCardError::NotAPersonalRecord)) => {
+            Ok(HttpResponse::Conflict().json(ApiResponse::<()>::error(e.to_string())))
+        }
+        Err(ShareCardError::Database(e)) => {
+            tracing::error!("Failed to request share card for user {}: {}", user_id, e);
+            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create share card")))
+        }
+    }
+}
+
+/// GET /share/cards/{id} - The card image, public so it can be posted anywhere.
+/// Answers 202 while the card is still rendering.
+pub async fn get_share_card_image(
+    pool: web::Data<PgPool>,
+    minio_service: web::Data<MinIOService>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let card_id = path.into_inner();
+    let service = ShareCardService::new(pool.get_ref().clone());
+
+    let object_key = match service.get_image(card_id).await {
+        Ok(Some((status, Some(object_key)))) if status == "rendered" => object_key,
+        Ok(Some((status, _))) if status == "pending" => {
+            return Ok(HttpResponse::Accepted().json(ApiResponse::<()>::error("Card is still rendering")));
+        }
+        Ok(_) => return Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Card not found"))),
+        Err(e) => {
+            tracing::error!("Failed to get share card {}: {}", card_id, e);
+            return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to retrieve card")));
+        }
+    };
+
+    match minio_service.get_file(&object_key).await {
+        // Rendered cards never change, so clients and link previews may keep them
+        Ok((bytes, content_type)) => Ok(HttpResponse::Ok()
+            .content_type(content_type)
+            .insert_header((header::CACHE_CONTROL, "public, max-age=86400, immutable"))
+            .body(bytes)),
+        Err(e) => {
+            tracing::error!("Failed to download share card {}: {}", object_key, e);
+            Ok(HttpResponse::NotFound().json(ApiResponse::<()>::error("Card not found")))
+        }
+    }
+}
@@ -4,14 +4,11 @@ use uuid::Uuid;
 use std::sync::Arc;
 
 use crate::{
-    db::social::{
-        create_comment_reaction, delete_comment_reaction, get_comment_reactions, get_comment_reaction_users,
-        get_comment_by_id, create_notification,
-    },
+    db::social::{get_comment_reactions, get_comment_reaction_users},
+    handlers::social::reaction_handler::{react_to_target, unreact_to_target},
     middleware::auth::Claims,
-    models::social::{CreateCommentReactionRequest, ReactionType, NotificationType},
+    models::social::{CommentReaction, CreateCommentReactionRequest, ReactionTarget},
     models::common::ApiResponse,
-    services::social_events,
 };
 
 pub async fn add_comment_reaction(
@@ -21,96 +18,17 @@ pub async fn add_comment_reaction(
     claims: web::ReqData<Claims>,
     redis_client: web::Data<Arc<redis::Client>>,
 ) -> HttpResponse {
-    let user_id = match Uuid::parse_str(&claims.sub.clone()) {
-        Ok(id) => id,
-        Err(e) => {
-            tracing::error!("Failed to parse user ID: {}", e);
-            return HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Invalid user ID")
-            );
-        }
-    };
     let comment_id = comment_id.into_inner();
 
-    if ReactionType::parse(&body.reaction_type).is_none() {
-        return HttpResponse::BadRequest().json(
-            ApiResponse::<()>::error("Invalid reaction type")
-        );
-    }
-
-    match create_comment_reaction(&pool, user_id, comment_id, &body.reaction_type).await {
-        Ok(reaction) => {
-            // Get comment author to send notification
-            if let Ok(Some(comment)) = get_comment_by_id(&pool, comment_id).await {
-                let message = format!("{} reacted to your comment", claims.username);
-                match create_notification(
-                    &pool,
-                    comment.user_id,
-                    user_id,
-                    NotificationType::Reaction.as_str(),
-                    "comment",
-                    comment_id,
-                    &message,
-                ).await {
-                    Ok(Some(notification_id)) => {
-                        // Broadcast notification via WebSocket
-                        if let Err(e) = social_events::send_websocket_notification_to_user(
-                            &redis_client,
-                            comment.user_id,
-                            notification_id,
-                            claims.username.clone(),
-                            NotificationType::Reaction.as_str().to_string(),
-                            message.clone(),
-                        ).await {
-                            tracing::warn!("Failed to broadcast notification: {}", e);
-                        }
-
-                        // Send push notification
-                        let notification_data = serde_json::json!({
-                            "type": "comment_reaction",
-                            "comment_id": comment_id.to_string(),
-                            "notification_id": notification_id.to_string(),
-                        });
-
-                        if let Err(e) = crate::handlers::notification_handler::send_notification_to_user(
-                            &pool,
-                            comment.user_id,
-                            format!("{} reacted to your comment", claims.username),
-                            "🔥".to_string(),
-                            Some(notification_data),
-                            Some("comment_reaction".to_string()),
-                        ).await {
-                            tracing::warn!("Failed to send push notification: {}", e);
-                        }
-                    }
-                    Ok(None) => {
-                        // Notification not created (user reacted to their own comment)
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to create notification: {}", e);
-                    }
-                }
-            }
-
-            // Broadcast WebSocket event for UI updates (fire and forget)
-            if let Err(e) = social_events::broadcast_comment_reaction_added(
-                &redis_client,
-                comment_id,
-                user_id,
-                claims.username.clone(),
-                body.reaction_type.clone(),
-            ).await {
-                tracing::warn!("Failed to broadcast comment reaction added event: {}", e);
-            }
-
-            HttpResponse::Ok().json(reaction)
-        }
-        Err(e) => {
-            tracing::error!("Failed to create comment reaction: {}", e);
-            HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Failed to create comment reaction")
-            )
-        }
+    match react_to_target(&pool, &redis_client, &claims, ReactionTarget::Comment, comment_id, &body.reaction_type).await {
+        Ok(reaction) => HttpResponse::Ok().json(CommentReaction {
+            id: reaction.id,
+            user_id: reaction.user_id,
+            comment_id: reaction.target_id,
+            reaction_type: reaction.reaction_type,
+            created_at: reaction.created_at,
+        }),
+        Err(response) => response,
     }
 }
 
@@ -120,45 +38,13 @@ pub async fn remove_comment_reaction(
     claims: web::ReqData<Claims>,
     redis_client: web::Data<Arc<redis::Client>>,
 ) -> HttpResponse {
-    let user_id = match Uuid::parse_str(&claims.sub.clone()) {
-        Ok(id) => id,
-        Err(e) => {
-            tracing::error!("Failed to parse user ID: {}", e);
-            return HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Invalid user ID")
-            );
-        }
-    };
     let comment_id = comment_id.into_inner();
 
-    match delete_comment_reaction(&pool, user_id, comment_id).await {
-        Ok(deleted) => {
-            if deleted {
-                // Broadcast WebSocket event (fire and forget)
-                if let Err(e) = social_events::broadcast_comment_reaction_removed(
-                    &redis_client,
-                    comment_id,
-                    user_id,
-                    claims.username.clone(),
-                ).await {
-                    tracing::warn!("Failed to broadcast comment reaction removed event: {}", e);
-                }
-
-                HttpResponse::Ok().json(serde_json::json!({
-                    "message": "Reaction removed successfully"
-                }))
-            } else {
-                HttpResponse::NotFound().json(
-                    ApiResponse::<()>::error("Reaction not found")
-                )
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to delete comment reaction: {}", e);
-            HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Failed to delete comment reaction")
-            )
-        }
+    match unreact_to_target(&pool, &redis_client, &claims, ReactionTarget::Comment, comment_id).await {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Reaction removed successfully"
+        })),
+        Err(response) => response,
     }
 }
 
@@ -4,118 +4,220 @@ use uuid::Uuid;
 use std::sync::Arc;
 
 use crate::{
-    db::social::{create_reaction, delete_reaction, get_workout_reactions, get_reaction_users, create_notification, get_workout_owner},
+    db::social::{
+        create_target_reaction, delete_target_reaction, get_target_reactions, get_target_reaction_users,
+        get_reaction_target_owner, get_workout_reactions, get_reaction_users, create_notification,
+    },
     middleware::auth::Claims,
-    models::social::{CreateReactionRequest, ReactionType, NotificationType},
+    models::social::{CreateReactionRequest, Reaction, ReactionTarget, ReactionType, NotificationType, WorkoutReaction},
     models::common::ApiResponse,
     services::social_events,
 };
 
+fn invalid_user_id(e: uuid::Error) -> HttpResponse {
+    tracing::error!("Failed to parse user ID: {}", e);
+    HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Invalid user ID"))
+}
 
-pub async fn add_reaction(
-    pool: web::Data<PgPool>,
-    workout_id: web::Path<Uuid>,
-    body: web::Json<CreateReactionRequest>,
-    claims: web::ReqData<Claims>,
-    redis_client: web::Data<Arc<redis::Client>>,
-) -> HttpResponse {
-    let user_id = match Uuid::parse_str(&claims.sub.clone()) {
-        Ok(id) => id,
+/// Resolve the owner of a target the user can see, 404 if there is none
+async fn find_target_owner(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, HttpResponse> {
+    match get_reaction_target_owner(pool, target, target_id, user_id).await {
+        Ok(Some(owner_id)) => Ok(owner_id),
+        Ok(None) => Err(HttpResponse::NotFound().json(
+            ApiResponse::<()>::error("Reaction target not found")
+        )),
         Err(e) => {
-            tracing::error!("Failed to parse user ID: {}", e);
-            return HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Invalid user ID")
-            );
+            tracing::error!("Failed to look up {} {}: {}", target.as_str(), target_id, e);
+            Err(HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to look up reaction target")
+            ))
         }
-    };
-    let workout_id = workout_id.into_inner();
+    }
+}
+
+/// Add or replace the user's reaction on a target, notify its owner and broadcast the change.
+/// Shared by the workout, comment and generic target endpoints.
+pub(crate) async fn react_to_target(
+    pool: &PgPool,
+    redis_client: &web::Data<Arc<redis::Client>>,
+    claims: &Claims,
+    target: ReactionTarget,
+    target_id: Uuid,
+    reaction_type: &str,
+) -> Result<Reaction, HttpResponse> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(invalid_user_id)?;
 
-    if ReactionType::parse(&body.reaction_type).is_none() {
-        return HttpResponse::BadRequest().json(
+    if ReactionType::parse(reaction_type).is_none() {
+        return Err(HttpResponse::BadRequest().json(
             ApiResponse::<()>::error("Invalid reaction type")
-        );
-    }
-
-    match create_reaction(&pool, user_id, workout_id, &body.reaction_type).await {
-        Ok(reaction) => {
-            // Get workout owner to send notification
-            if let Ok(Some(workout_owner_id)) = get_workout_owner(&pool, workout_id).await {
-                // Don't send notification if user reacted to their own workout
-                if workout_owner_id == user_id {
-                    // User reacted to their own workout, skip notification
-                } else {
-                    let message = format!("{} reacted to your workout", claims.username);
-
-                    // Create in-app notification FIRST
-                    match create_notification(
-                        &pool,
-                        workout_owner_id,
-                        user_id,
-                        NotificationType::Reaction.as_str(),
-                        "workout",
-                        workout_id,
-                        &message,
-                    ).await {
-                        Ok(Some(notification_id)) => {
-                            // Broadcast notification via WebSocket
-                            if let Err(e) = social_events::send_websocket_notification_to_user(
-                                &redis_client,
-                                workout_owner_id,
-                                notification_id,
-                                claims.username.clone(),
-                                NotificationType::Reaction.as_str().to_string(),
-                                message.clone(),
-                            ).await {
-                                tracing::warn!("Failed to broadcast notification: {}", e);
-                            }
-
-                            // Now send push notification with accurate badge count
-                            let notification_data = serde_json::json!({
-                                "type": "reaction",
-                                "workout_id": workout_id.to_string(),
-                                "notification_id": notification_id.to_string(),
-                            });
-
-                            if let Err(e) = crate::handlers::notification_handler::send_notification_to_user(
-                                &pool,
-                                workout_owner_id,
-                                format!("{} reacted to your workout", claims.username),
-                                "🔥".to_string(),
-                                Some(notification_data),
-                                Some("reaction".to_string())
-                            ).await {
-                                tracing::warn!("Failed to send push notification: {}", e);
-                            }
-                        }
-                        Ok(None) => {
-                            // User reacted to their own workout, no notification needed
-                        }
-                        Err(e) => {
-                            tracing::warn!("Failed to create notification: {}", e);
-                        }
-                    }
-                }
-            }
+        ));
+    }
 
+    let owner_id = find_target_owner(pool, target, target_id, user_id).await?;
+
+    let reaction = create_target_reaction(pool, user_id, target, target_id, reaction_type)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to create reaction on {} {}: {}", target.as_str(), target_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create reaction"))
+        })?;
+
+    // Don't notify users about reactions to their own content
+    if let Some(owner_id) = owner_id.filter(|&owner_id| owner_id != user_id) {
+        notify_target_owner(pool, redis_client, claims, user_id, owner_id, target, target_id).await;
+    }
+
+    // Broadcast WebSocket event (fire and forget)
+    if let Err(e) = social_events::broadcast_target_reaction_added(
+        redis_client,
+        target,
+        target_id,
+        user_id,
+        claims.username.clone(),
+        reaction_type.to_string(),
+    ).await {
+        tracing::warn!("Failed to broadcast reaction added event: {}", e);
+    }
+
+    Ok(reaction)
+}
+
+/// Remove the user's reaction from a target and broadcast the change, 404 if there was none
+pub(crate) async fn unreact_to_target(
+    pool: &PgPool,
+    redis_client: &web::Data<Arc<redis::Client>>,
+    claims: &Claims,
+    target: ReactionTarget,
+    target_id: Uuid,
+) -> Result<(), HttpResponse> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(invalid_user_id)?;
+
+    match delete_target_reaction(pool, user_id, target, target_id).await {
+        Ok(true) => {
             // Broadcast WebSocket event (fire and forget)
-            if let Err(e) = social_events::broadcast_reaction_added(
-                &redis_client,
-                workout_id,
+            if let Err(e) = social_events::broadcast_target_reaction_removed(
+                redis_client,
+                target,
+                target_id,
                 user_id,
                 claims.username.clone(),
-                body.reaction_type.clone(),
             ).await {
-                tracing::warn!("Failed to broadcast reaction added event: {}", e);
+                tracing::warn!("Failed to broadcast reaction removed event: {}", e);
             }
-
-            HttpResponse::Ok().json(reaction)
+            Ok(())
         }
+        Ok(false) => Err(HttpResponse::NotFound().json(
+            ApiResponse::<()>::error("Reaction not found")
+        )),
         Err(e) => {
-            tracing::error!("Failed to create reaction: {}", e);
-            HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Failed to create reaction")
-            )
+            tracing::error!("Failed to delete reaction: {}", e);
+            Err(HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to delete reaction")
+            ))
+        }
+    }
+}
+
+async fn notify_target_owner(
+    pool: &PgPool,
+    redis_client: &web::Data<Arc<redis::Client>>,
+    claims: &Claims,
+    user_id: Uuid,
+    owner_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
+) {
+    let message = format!("{} reacted to your {}", claims.username, target.noun());
+
+    // Create in-app notification FIRST
+    let notification_id = match create_notification(
+        pool,
+        owner_id,
+        user_id,
+        NotificationType::Reaction.as_str(),
+        target.as_str(),
+        target_id,
+        &message,
+    ).await {
+        Ok(Some(notification_id)) => notification_id,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to create notification: {}", e);
+            return;
         }
+    };
+
+    // Broadcast notification via WebSocket
+    if let Err(e) = social_events::send_websocket_notification_to_user(
+        redis_client,
+        owner_id,
+        notification_id,
+        claims.username.clone(),
+        NotificationType::Reaction.as_str().to_string(),
+        message.clone(),
+    ).await {
+        tracing::warn!("Failed to broadcast notification: {}", e);
+    }
+
+    // Now send push notification with accurate badge count
+    let (push_type, notification_data) = match target {
+        ReactionTarget::Workout => ("reaction", serde_json::json!({
+            "type": "reaction",
+            "workout_id": target_id.to_string(),
+            "notification_id": notification_id.to_string(),
+        })),
+        ReactionTarget::Comment => ("comment_reaction", serde_json::json!({
+            "type": "comment_reaction",
+            "comment_id": target_id.to_string(),
+            "notification_id": notification_id.to_string(),
+        })),
+        _ => ("reaction", serde_json::json!({
+            "type": "reaction",
+            "target_type": target.as_str(),
+            "target_id": target_id.to_string(),
+            "notification_id": notification_id.to_string(),
+        })),
+    };
+
+    if let Err(e) = crate::handlers::notification_handler::send_notification_to_user(
+        pool,
+        owner_id,
+        message,
+        "🔥".to_string(),
+        Some(notification_data),
+        Some(push_type.to_string()),
+    ).await {
+        tracing::warn!("Failed to send push notification: {}", e);
+    }
+}
+
+fn invalid_target() -> HttpResponse {
+    HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid reaction target"))
+}
+
+pub async fn add_reaction(
+    pool: web::Data<PgPool>,
+    workout_id: web::Path<Uuid>,
+    body: web::Json<CreateReactionRequest>,
+    claims: web::ReqData<Claims>,
+    redis_client: web::Data<Arc<redis::Client>>,
+) -> HttpResponse {
+    let workout_id = workout_id.into_inner();
+
+    match react_to_target(&pool, &redis_client, &claims, ReactionTarget::Workout, workout_id, &body.reaction_type).await {
+        Ok(reaction) => HttpResponse::Ok().json(WorkoutReaction {
+            id: reaction.id,
+            user_id: reaction.user_id,
+            workout_id: reaction.target_id,
+            reaction_type: reaction.reaction_type,
+            created_at: reaction.created_at,
+        }),
+        Err(response) => response,
     }
 }
 
@@ -125,43 +227,11 @@ pub async fn remove_reaction(
     claims: web::ReqData<Claims>,
     redis_client: web::Data<Arc<redis::Client>>,
 ) -> HttpResponse {
-    let user_id = match Uuid::parse_str(&claims.sub.clone()) {
-        Ok(id) => id,
-        Err(e) => {
-            tracing::error!("Failed to parse user ID: {}", e);
-            return HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Invalid user ID")
-            );
-        }
-    };
     let workout_id = workout_id.into_inner();
 
-    match delete_reaction(&pool, user_id, workout_id).await {
-        Ok(deleted) => {
-            if deleted {
-                // Broadcast WebSocket event (fire and forget)
-                if let Err(e) = social_events::broadcast_reaction_removed(
-                    &redis_client,
-                    workout_id,
-                    user_id,
-                    claims.username.clone(),
-                ).await {
-                    tracing::warn!("Failed to broadcast reaction removed event: {}", e);
-                }
-
-                HttpResponse::Ok().json(ApiResponse::<()>::success("Reaction removed successfully", ()))
-            } else {
-                HttpResponse::NotFound().json(
-                    ApiResponse::<()>::error("Reaction not found")
-                )
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to delete reaction: {}", e);
-            HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Failed to delete reaction")
-            )
-        }
+    match unreact_to_target(&pool, &redis_client, &claims, ReactionTarget::Workout, workout_id).await {
+        Ok(()) => HttpResponse::Ok().json(ApiResponse::<()>::success("Reaction removed successfully", ())),
+        Err(response) => response,
     }
 }
 
@@ -202,4 +272,101 @@ pub async fn get_reaction_details(
             )
         }
     }
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// TARGET REACTIONS (posts, game summaries, score events, ...)
+// ============================================================================
+
+pub async fn add_target_reaction(
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, Uuid)>,
+    body: web::Json<CreateReactionRequest>,
+    claims: web::ReqData<Claims>,
+    redis_client: web::Data<Arc<redis::Client>>,
+) -> HttpResponse {
+    let (target_type, target_id) = path.into_inner();
+    let Some(target) = ReactionTarget::parse(&target_type) else {
+        return invalid_target();
+    };
+
+    match react_to_target(&pool, &redis_client, &claims, target, target_id, &body.reaction_type).await {
+        Ok(reaction) => HttpResponse::Ok().json(reaction),
+        Err(response) => response,
+    }
+}
+
+pub async fn remove_target_reaction(
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, Uuid)>,
+    claims: web::ReqData<Claims>,
+    redis_client: web::Data<Arc<redis::Client>>,
+) -> HttpResponse {
+    let (target_type, target_id) = path.into_inner();
+    let Some(target) = ReactionTarget::parse(&target_type) else {
+        return invalid_target();
+    };
+
+    match unreact_to_target(&pool, &redis_client, &claims, target, target_id).await {
+        Ok(()) => HttpResponse::Ok().json(ApiResponse::<()>::success("Reaction removed successfully", ())),
+        Err(response) => response,
+    }
+}
+
+pub async fn get_target_reactions_handler(
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, Uuid)>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let (target_type, target_id) = path.into_inner();
+    let Some(target) = ReactionTarget::parse(&target_type) else {
+        return invalid_target();
+    };
+    let user_id = match Uuid::parse_str(&claims.sub) {
+        Ok(id) => id,
+        Err(e) => return invalid_user_id(e),
+    };
+    if let Err(response) = find_target_owner(&pool, target, target_id, user_id).await {
+        return response;
+    }
+
+    match get_target_reactions(&pool, target, target_id, Some(user_id)).await {
+        Ok(reactions) => HttpResponse::Ok().json(reactions),
+        Err(e) => {
+            tracing::error!("Failed to get reactions: {}", e);
+            HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to get reactions")
+            )
+        }
+    }
+}
+
+pub async fn get_target_reaction_details(
+    pool: web::Data<PgPool>,
+    path: web::Path<(String, Uuid)>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let (target_type, target_id) = path.into_inner();
+    let Some(target) = ReactionTarget::parse(&target_type) else {
+        return invalid_target();
+    };
+    let user_id = match Uuid::parse_str(&claims.sub) {
+        Ok(id) => id,
+        Err(e) => return invalid_user_id(e),
+    };
+    if let Err(response) = find_target_owner(&pool, target, target_id, user_id).await {
+        return response;
+    }
+    let reaction_type = query.get("type").map(|s| s.as_str());
+
+    match get_target_reaction_users(&pool, target, target_id, reaction_type).await {
+        Ok(users) => HttpResponse::Ok().json(users),
+        Err(e) => {
+            tracing::error!("Failed to get reaction users: {}", e);
+            HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to get reaction users")
+            )
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::middleware::auth::Claims;
+use crate::services::SyncService;
+
+#[derive(Debug, Deserialize)]
+pub struct SyncChangesQuery {
+    /// `next_since` of the previous sync; omit for a full snapshot
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// GET /sync/changes?since= - Workouts, games, standings and notifications changed since
+/// the last sync, plus tombstones for deletions, for the app's offline cache
+#[tracing::instrument(
+    name = "Get sync changes",
+    skip(pool, claims, query),
+    fields(username = %claims.username, since = ?query.since)
+)]
+pub async fn get_sync_changes(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<SyncChangesQuery>,
+) -> Result<HttpResponse> {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return Ok(HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "error": "Invalid user ID"
+        })));
+    };
+
+    let sync_service = SyncService::new(pool.get_ref().clone());
+    match sync_service.get_changes(user_id, query.since).await {
+        Ok(changes) => Ok(HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": changes
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get sync changes for user {}: {}", user_id, e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Failed to get changes"
+            })))
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::db::activities::list_activities;
+
+/// Active entries of the activity catalog, for the apps' activity picker
+#[tracing::instrument(name = "Get activity catalog", skip(pool))]
+pub async fn get_activity_catalog(pool: web::Data<PgPool>) -> HttpResponse {
+    match list_activities(pool.get_ref(), false).await {
+        Ok(activities) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": activities
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch activity catalog: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch activities"
+            }))
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::body_metrics::{BodyMetricsQuery, RecordBodyMetricsRequest};
+use crate::services::BodyMetricsService;
+
+const DEFAULT_METRICS_LIMIT: i64 = 500;
+const MAX_METRICS_LIMIT: i64 = 5000;
+
+#[derive(Debug, Deserialize)]
+pub struct BodyMetricsAnalyticsQuery {
+    pub days: Option<i32>,
+}
+
+#[tracing::instrument(
+    name = "Record body metrics",
+    skip(pool, claims, request),
+    fields(username = %claims.username, entries = %request.entries.len())
+)]
+pub async fn record_body_metrics(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<RecordBodyMetricsRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    if let Err(e) = request.validate() {
+        return HttpResponse::BadRequest().json(json!({
+            "error": e
+        }));
+    }
+
+    let service = BodyMetricsService::new(pool.get_ref().clone());
+    match service.record(user_id, &request.entries).await {
+        Ok(metrics) => HttpResponse::Created().json(json!({
+            "success": true,
+            "data": metrics
+        })),
+        Err(e) => {
+            tracing::error!("Failed to record body metrics for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to record body metrics"
+            }))
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "Get body metrics",
+    skip(pool, claims, query),
+    fields(username = %claims.username)
+)]
+pub async fn get_body_metrics(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BodyMetricsQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_METRICS_LIMIT).clamp(1, MAX_METRICS_LIMIT);
+
+    let service = BodyMetricsService::new(pool.get_ref().clone());
+    match service.list(user_id, &query, limit).await {
+        Ok(metrics) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": metrics
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch body metrics for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch body metrics"
+            }))
+        }
+    }
+}
+
+#[tracing::instrument(
+    name = "Delete body metric",
+    skip(pool, claims),
+    fields(username = %claims.username, metric_id = %metric_id)
+)]
+pub async fn delete_body_metric(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    metric_id: web::Path<Uuid>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    let service = BodyMetricsService::new(pool.get_ref().clone());
+    match service.delete(user_id, metric_id.into_inner()).await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(json!({
+            "error": "Body metric not found"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to delete body metric for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to delete body metric"
+            }))
+        }
+    }
+}
+
+/// Daily training load with weight, HRV and resting HR, for charting them together
+#[tracing::instrument(
+    name = "Get body metrics analytics",
+    skip(pool, claims),
+    fields(username = %claims.username)
+)]
+pub async fn get_body_metrics_analytics(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BodyMetricsAnalyticsQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    let days = query.days.unwrap_or(90).clamp(7, 365);
+
+    let service = BodyMetricsService::new(pool.get_ref().clone());
+    match service.daily_overview(user_id, days).await {
+        Ok(points) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": {
+                "days": days,
+                "points": points
+            }
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch body metrics analytics for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch body metrics analytics"
+            }))
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::db::health_connect::{clear_change_token, get_change_token, store_change_token};
+use crate::middleware::auth::Claims;
+
+const MAX_DEVICE_ID_LENGTH: usize = 255;
+const MAX_CHANGE_TOKEN_LENGTH: usize = 4096;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeTokenQuery {
+    pub device_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoreChangeTokenRequest {
+    pub device_id: String,
+    pub change_token: String,
+}
+
+/// The stored Health Connect change token of a device, and whether the app has to start
+/// with a full sync instead of a delta
+#[tracing::instrument(
+    name = "Get change token",
+    skip(pool, claims, query),
+    fields(username = %claims.username, device_id = %query.device_id)
+)]
+pub async fn get_device_change_token(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ChangeTokenQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    match get_change_token(&pool, user_id, &query.device_id).await {
+        Ok(token) => {
+            let expired = token.as_ref().is_some_and(|token| token.expires_at <= Utc::now());
+            HttpResponse::Ok().json(json!({
+                "success": true,
+                "data": {
+                    "device_id": query.device_id,
+                    "change_token": token.as_ref().filter(|_| !expired).map(|token| &token.change_token),
+                    "expires_at": token.as_ref().map(|token| token.expires_at),
+                    "full_resync_required": token.is_none() || expired
+                }
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch change token for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch change token"
+            }))
+        }
+    }
+}
+
+/// Store the token Health Connect returned once the device finished uploading a sync
+#[tracing::instrument(
+    name = "Store change token",
+    skip(pool, claims, request),
+    fields(username = %claims.username, device_id = %request.device_id)
+)]
+pub async fn store_device_change_token(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<StoreChangeTokenRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let device_id = request.device_id.trim();
+    let change_token = request.change_token.trim();
+    if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LENGTH {
+        return HttpResponse::BadRequest().json(json!({
+            "error": format!("device_id must be 1-{} characters", MAX_DEVICE_ID_LENGTH)
+        }));
+    }
+    if change_token.is_empty() || change_token.len() > MAX_CHANGE_TOKEN_LENGTH {
+        return HttpResponse::BadRequest().json(json!({
+            "error": format!("change_token must be 1-{} characters", MAX_CHANGE_TOKEN_LENGTH)
+        }));
+    }
+
+    match store_change_token(&pool, user_id, device_id, change_token).await {
+        Ok(token) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": token
+        })),
+        Err(e) => {
+            tracing::error!("Failed to store change token for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to store change token"
+            }))
+        }
+    }
+}
+
+/// Forget a device's token, e.g. when Health Connect reports it as expired on the device
+#[tracing::instrument(
+    name = "Clear change token",
+    skip(pool, claims, query),
+    fields(username = %claims.username, device_id = %query.device_id)
+)]
+pub async fn clear_device_change_token(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ChangeTokenQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    match clear_change_token(&pool, user_id, &query.device_id).await {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(e) => {
+            tracing::error!("Failed to clear change token for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to clear change token"
+            }))
+        }
+    }
+}
@@ -6,7 +6,11 @@ use std::cmp::Ordering;
 
 use crate::middleware::auth::Claims;
 use crate::models::common::ApiResponse;
-use crate::db::workout_data::check_workout_overlap;
+use crate::db::health_connect::check_change_token;
+use crate::db::upload_attempts::{record_upload_attempt, NewUploadAttempt};
+use crate::db::workout_data::find_overlapping_workout;
+use crate::models::diagnostics::UploadOutcome;
+use crate::models::workout_data::{ChangeTokenStatus, WorkoutReuploadDiff};
 use crate::utils::workout_approval::WorkoutApprovalToken;
 use crate::config::jwt::JwtSettings;
 
@@ -18,12 +22,17 @@ pub struct WorkoutSyncRequest {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     pub calories: Option<i32>,
+    #[serde(default)]
+    pub samples_count: Option<i64>,
     pub id: String,  // Keep original ID for frontend reference
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CheckSyncStatusRequest {
     pub workouts: Vec<WorkoutSyncRequest>,
+    // Health Connect delta sync: the syncing device and the change token the delta is based on
+    pub device_id: Option<String>,
+    pub change_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -33,10 +42,20 @@ pub struct WorkoutApproval {
     pub expires_at: DateTime<Utc>,
 }
 
+/// A workout rejected as already synced, with how it differs from the stored workout
+#[derive(Debug, Serialize)]
+pub struct DuplicateWorkout {
+    pub workout_id: String,
+    pub diff: WorkoutReuploadDiff,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SyncStatusResponse {
     pub unsynced_workouts: Vec<String>,
     pub approved_workouts: Vec<WorkoutApproval>,  // New field with approval tokens
+    pub duplicate_workouts: Vec<DuplicateWorkout>,
+    pub change_token_status: Option<ChangeTokenStatus>,  // Only for syncs that name their device
+    pub full_resync_required: bool,
 }
 
 #[tracing::instrument(
@@ -61,19 +80,59 @@ pub async fn check_workout_sync(
         return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
     };
 
+    let change_token_status = match &request.device_id {
+        Some(device_id) => match check_change_token(pool.get_ref(), user_id, device_id, request.change_token.as_deref()).await {
+            Ok(status) => Some(status),
+            Err(e) => {
+                tracing::error!("Error checking change token of device {}: {}", device_id, e);
+                None
+            }
+        },
+        None => None,
+    };
+    let full_resync_required = change_token_status.is_some_and(|status| status.requires_full_resync());
+    if full_resync_required {
+        tracing::info!("🔄 Change token of device {:?} is {:?}, asking for a full resync",
+            request.device_id, change_token_status);
+    }
+
     let mut synced_workouts = Vec::new();
     let mut unsynced_workouts = Vec::new();
     let mut approved_workouts = Vec::new();
+    let mut duplicate_workouts = Vec::new();
 
     let unique_workouts = remove_duplicates(request.workouts.clone(), WORKOUT_TIME_TOLERANCE);
 
     // Check each workout for overlaps with existing workouts in the database
     for workout in &unique_workouts {
-        match check_workout_overlap(pool.get_ref(), user_id, &workout.start, &workout.end).await {
-            Ok(overlaps) => {
-                if overlaps {
-                    tracing::debug!("Workout {} overlaps with existing workout", workout.id);
+        match find_overlapping_workout(pool.get_ref(), user_id, &workout.start, &workout.end).await {
+            Ok(overlapping) => {
+                if let Some(existing) = overlapping {
+                    tracing::debug!("Workout {} overlaps with existing workout {}", workout.id, existing.id);
+                    let attempt = NewUploadAttempt {
+                        device_id: request.device_id.as_deref(),
+                        workout_uuid: &workout.id,
+                        outcome: UploadOutcome::Duplicate,
+                        detail: Some("Overlaps a workout that was already synced"),
+                        workout_data_id: Some(existing.id),
+                        workout_start: Some(workout.start),
+                        workout_end: Some(workout.end),
+                    };
+                    if let Err(e) = record_upload_attempt(pool.get_ref(), user_id, &attempt).await {
+                        tracing::warn!("Failed to record duplicate upload of workout {}: {}", workout.id, e);
+                    }
                     synced_workouts.push(workout.id.clone());
+                    duplicate_workouts.push(DuplicateWorkout {
+                        workout_id: workout.id.clone(),
+                        diff: WorkoutReuploadDiff::compare(
+                            &existing,
+                            workout.start,
+                            workout.end,
+                            workout.calories,
+                            workout.samples_count,
+                            WORKOUT_TIME_TOLERANCE.num_seconds(),
+                        ),
+                    });
                 } else {
                     tracing::debug!("Workout {} does not overlap, approved for upload", workout.id);
                     unsynced_workouts.push(workout.id.clone());
@@ -135,6 +194,9 @@ pub async fn check_workout_sync(
     let response = SyncStatusResponse {
         unsynced_workouts,
         approved_workouts,
+        duplicate_workouts,
+        change_token_status,
+        full_resync_required,
     };
 
     tracing::info!("✅ Sync status check completed: {} synced, {} unsynced, {} approved", 
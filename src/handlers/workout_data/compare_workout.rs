@@ -0,0 +1,71 @@
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::workout_data::get_workout_fingerprint;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+use crate::models::user::UserRole;
+use crate::models::workout_data::{CompareWorkoutRequest, WorkoutReuploadDiff};
+use crate::utils::heart_rate_filters::filter_heart_rate_data;
+
+/// Same tolerance the sync check uses to call two workouts the same
+const WORKOUT_TIME_TOLERANCE_SECONDS: i64 = 1;
+
+/// Diff a re-sent workout against a stored one, so support can tell an identical re-send
+/// from amended data. Available to the workout's owner and admins.
+#[tracing::instrument(
+    name = "Compare re-uploaded workout",
+    skip(pool, claims, request),
+    fields(username = %claims.username, workout_id = %workout_id)
+)]
+pub async fn compare_workout(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<Uuid>,
+    request: web::Json<CompareWorkoutRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+    let workout_id = workout_id.into_inner();
+
+    if request.workout_end <= request.workout_start {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("workout_end must be after workout_start"));
+    }
+
+    let existing = match get_workout_fingerprint(&pool, workout_id).await {
+        Ok(Some((owner_id, existing)))
+            if owner_id == user_id || matches!(claims.role, UserRole::Admin | UserRole::SuperAdmin) =>
+        {
+            existing
+        }
+        Ok(_) => {
+            return HttpResponse::NotFound().json(ApiResponse::<()>::error("Workout not found"));
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch workout {}: {}", workout_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error"));
+        }
+    };
+
+    // Count samples the way the upload stores them
+    let samples_count = request.heart_rate.as_ref().map(|heart_rate| {
+        let mut heart_rate = heart_rate.clone();
+        heart_rate.sort_by_key(|sample| sample.timestamp);
+        filter_heart_rate_data(&mut heart_rate, &request.workout_start, &request.workout_end);
+        heart_rate.len() as i64
+    });
+
+    let diff = WorkoutReuploadDiff::compare(
+        &existing,
+        request.workout_start,
+        request.workout_end,
+        request.calories_burned,
+        samples_count,
+        WORKOUT_TIME_TOLERANCE_SECONDS,
+    );
+
+    HttpResponse::Ok().json(ApiResponse::success("Workout compared successfully", diff))
+}
@@ -0,0 +1,46 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::middleware::auth::Claims;
+use crate::services::hr_trend_service::HrTrendService;
+
+#[derive(Debug, Deserialize)]
+pub struct HrTrendsQuery {
+    pub days: Option<i32>,
+}
+
+#[tracing::instrument(
+    name = "Get heart rate trends",
+    skip(pool, claims),
+    fields(username = %claims.username)
+)]
+pub async fn get_hr_trends(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<HrTrendsQuery>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    let days = query.days.unwrap_or(90).clamp(7, 365);
+
+    let service = HrTrendService::new(pool.get_ref().clone());
+    match service.get_trends(user_id, days).await {
+        Ok(trends) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": trends
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch heart rate trends for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to fetch heart rate trends"
+            }))
+        }
+    }
+}
@@ -1,6 +1,16 @@
 pub mod upload_workout_data;
+pub mod upload_payload;
 pub mod workout_history;
 pub mod workout_detail;
 pub mod check_workout_sync;
 pub mod scoring_feedback;
-pub mod workout_reports;
\ No newline at end of file
+pub mod workout_reports;
+pub mod hr_trends;
+pub mod activities;
+pub mod share_links;
+pub mod compare_workout;
+pub mod zone_recalculation;
+pub mod body_metrics;
+pub mod change_tokens;
+
+pub mod score_breakdown;
\ No newline at end of file
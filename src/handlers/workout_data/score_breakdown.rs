@@ -0,0 +1,108 @@
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::auth::Claims;
+use crate::models::workout_data::{HeartRateData, WorkoutType, ZoneBreakdown};
+use crate::services::suspension_service;
+use crate::workout::score_breakdown::{explain_score, CreditedGame, ScoredWorkout};
+
+/// Step-by-step explanation of how one of the user's workouts was scored: samples used and
+/// discarded, minutes and weight per zone, multipliers, caps and the live games credited
+#[tracing::instrument(
+    name = "Get workout score breakdown",
+    skip(pool, claims),
+    fields(username = %claims.username, workout_id = %workout_id)
+)]
+pub async fn get_score_breakdown(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<Uuid>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "error": "Invalid user ID"
+        }));
+    };
+    let workout_id = workout_id.into_inner();
+
+    match load_scored_workout(&pool, workout_id, user_id).await {
+        Ok(Some(workout)) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": explain_score(&workout)
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "success": false,
+            "error": "Workout not found"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to load workout {} for its score breakdown: {}", workout_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }))
+        }
+    }
+}
+
+async fn load_scored_workout(pool: &PgPool, workout_id: Uuid, user_id: Uuid) -> Result<Option<ScoredWorkout>, sqlx::Error> {
+    let Some(row) = sqlx::query!(
+        r#"
+        SELECT id, workout_start, workout_end, heart_rate_data, heart_rate_zones, ml_prediction,
+               scoring_calibration, total_points_gained
+        FROM workout_data
+        WHERE id = $1 AND user_id = $2
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let games = sqlx::query_as!(
+        CreditedGame,
+        r#"
+        SELECT lse.game_id as "game_id!", t.team_name, o.team_name as opponent_name,
+               lse.score_points, lse.comeback_multiplier, lse.comeback_bonus, lse.booster_bonus
+        FROM live_score_events lse
+        JOIN games g ON g.id = lse.game_id
+        JOIN teams t ON t.id = lse.team_id
+        JOIN teams o ON o.id = CASE WHEN g.home_team_id = lse.team_id THEN g.away_team_id ELSE g.home_team_id END
+        WHERE lse.workout_data_id = $1 AND lse.user_id = $2
+        ORDER BY lse.occurred_at
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let suspended = suspension_service::overlaps_suspension(
+        &mut *pool.acquire().await?,
+        user_id,
+        row.workout_start,
+        row.workout_end,
+    )
+    .await?;
+
+    Ok(Some(ScoredWorkout {
+        workout_id: row.id,
+        workout_start: row.workout_start,
+        workout_end: row.workout_end,
+        heart_rate_data: serde_json::from_value::<Vec<HeartRateData>>(row.heart_rate_data).unwrap_or_default(),
+        zones: row
+            .heart_rate_zones
+            .and_then(|zones| serde_json::from_value::<Vec<ZoneBreakdown>>(zones).ok())
+            .unwrap_or_default(),
+        workout_type: WorkoutType::parse(&row.ml_prediction.unwrap_or_default().to_lowercase()),
+        scoring_calibration: row.scoring_calibration,
+        total_points: row.total_points_gained,
+        suspended,
+        games,
+    }))
+}
@@ -3,6 +3,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::models::workout_data::{SubmitScoringFeedbackRequest, WorkoutScoringFeedback};
+use crate::db::workout_repo::{WorkoutRepo, WorkoutRepository};
 use crate::middleware::auth::Claims;
 
 /// Submit scoring feedback for a workout
@@ -30,22 +31,13 @@ pub async fn submit_scoring_feedback(
     let effort_rating = request.effort_rating;
 
     // Verify the workout exists and belongs to this user
-    let workout_exists = sqlx::query!(
-        r#"
-        SELECT id FROM workout_data
-        WHERE id = $1 AND user_id = $2
-        "#,
-        workout_id,
-        user_id
-    )
-    .fetch_optional(pool.as_ref())
-    .await
+    let owns_workout = WorkoutRepo::new(pool.get_ref().clone()).is_owned_by(workout_id, user_id).await
     .map_err(|e| {
         tracing::error!("Database error checking workout: {}", e);
         actix_web::error::ErrorInternalServerError("Failed to verify workout")
     })?;
 
-    if workout_exists.is_none() {
+    if !owns_workout {
         return Ok(HttpResponse::NotFound().json(serde_json::json!({
             "error": "Workout not found or you don't have permission to rate it"
         })));
@@ -97,22 +89,13 @@ pub async fn get_scoring_feedback(
     };
 
     // Verify the workout exists and belongs to this user
-    let workout_exists = sqlx::query!(
-        r#"
-        SELECT id FROM workout_data
-        WHERE id = $1 AND user_id = $2
-        "#,
-        workout_id,
-        user_id
-    )
-    .fetch_optional(pool.as_ref())
-    .await
+    let owns_workout = WorkoutRepo::new(pool.get_ref().clone()).is_owned_by(workout_id, user_id).await
     .map_err(|e| {
         tracing::error!("Database error checking workout: {}", e);
         actix_web::error::ErrorInternalServerError("Failed to verify workout")
     })?;
 
-    if workout_exists.is_none() {
+    if !owns_workout {
         return Ok(HttpResponse::NotFound().json(serde_json::json!({
             "error": "Workout not found or you don't have permission to view it"
         })));
@@ -0,0 +1,280 @@
+use actix_web::{web, HttpResponse};
+use rand::{distributions::Alphanumeric, Rng};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    middleware::auth::Claims,
+    models::workout_data::{OpenGraphSummary, PublicWorkoutSummary, WorkoutShareLink},
+};
+
+const SHARE_TOKEN_LENGTH: usize = 32;
+
+fn generate_share_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SHARE_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Create a public share link for one of the user's workouts, or return the live one.
+/// Workouts whose post is private cannot be shared.
+#[tracing::instrument(
+    name = "Create workout share link",
+    skip(pool, claims),
+    fields(username = %claims.username, workout_id = %workout_id)
+)]
+pub async fn create_share_link(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<Uuid>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let workout_id = workout_id.into_inner();
+
+    let visibility = match sqlx::query_scalar!(
+        r#"
+        SELECT p.visibility::text as "visibility?"
+        FROM workout_data wd
+        LEFT JOIN posts p ON p.workout_id = wd.id AND p.user_id = wd.user_id
+        WHERE wd.id = $1 AND wd.user_id = $2
+        "#,
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&**pool)
+    .await
+    {
+        Ok(Some(visibility)) => visibility,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(json!({
+                "success": false,
+                "error": "Workout not found"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch workout {}: {}", workout_id, e);
+            return HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }));
+        }
+    };
+
+    if visibility.as_deref() == Some("private") {
+        return HttpResponse::Forbidden().json(json!({
+            "success": false,
+            "error": "Private workouts cannot be shared"
+        }));
+    }
+
+    let created = sqlx::query_as!(
+        WorkoutShareLink,
+        r#"
+        INSERT INTO workout_share_links (token, workout_data_id, user_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (workout_data_id) WHERE revoked_at IS NULL DO NOTHING
+        RETURNING token, workout_data_id, view_count, created_at
+        "#,
+        generate_share_token(),
+        workout_id,
+        user_id
+    )
+    .fetch_optional(&**pool)
+    .await;
+
+    let (link, is_new) = match created {
+        Ok(Some(link)) => (link, true),
+        Ok(None) => match sqlx::query_as!(
+            WorkoutShareLink,
+            r#"
+            SELECT token, workout_data_id, view_count, created_at
+            FROM workout_share_links
+            WHERE workout_data_id = $1 AND revoked_at IS NULL
+            "#,
+            workout_id
+        )
+        .fetch_one(&**pool)
+        .await
+        {
+            Ok(link) => (link, false),
+            Err(e) => {
+                tracing::error!("Failed to fetch share link of workout {}: {}", workout_id, e);
+                return HttpResponse::InternalServerError().json(json!({
+                    "success": false,
+                    "error": "Database error"
+                }));
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to create share link for workout {}: {}", workout_id, e);
+            return HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }));
+        }
+    };
+
+    let path = format!("/public/workouts/{}", link.token);
+    let body = json!({
+        "success": true,
+        "data": {
+            "link": link,
+            "path": path
+        }
+    });
+    if is_new {
+        tracing::info!("Created share link for workout {}", workout_id);
+        HttpResponse::Created().json(body)
+    } else {
+        HttpResponse::Ok().json(body)
+    }
+}
+
+/// Revoke the live share link of one of the user's workouts
+#[tracing::instrument(
+    name = "Revoke workout share link",
+    skip(pool, claims),
+    fields(username = %claims.username, workout_id = %workout_id)
+)]
+pub async fn revoke_share_link(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<Uuid>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let workout_id = workout_id.into_inner();
+
+    match sqlx::query!(
+        r#"
+        UPDATE workout_share_links
+        SET revoked_at = NOW()
+        WHERE workout_data_id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        workout_id,
+        user_id
+    )
+    .execute(&**pool)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 => HttpResponse::NotFound().json(json!({
+            "success": false,
+            "error": "No active share link for this workout"
+        })),
+        Ok(_) => {
+            tracing::info!("Revoked share link for workout {}", workout_id);
+            HttpResponse::Ok().json(json!({
+                "success": true,
+                "message": "Share link revoked"
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to revoke share link for workout {}: {}", workout_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }))
+        }
+    }
+}
+
+/// Public summary behind a share link. Revoked links, and links to workouts
+/// whose post has since been made private, are not found.
+#[tracing::instrument(name = "Get shared workout", skip(pool, token))]
+pub async fn get_shared_workout(
+    pool: web::Data<PgPool>,
+    token: web::Path<String>,
+) -> HttpResponse {
+    let token = token.into_inner();
+
+    let row = match sqlx::query!(
+        r#"
+        WITH link AS (
+            UPDATE workout_share_links sl
+            SET view_count = sl.view_count + 1, last_viewed_at = NOW()
+            FROM workout_data wd
+            LEFT JOIN posts p ON p.workout_id = wd.id AND p.user_id = wd.user_id
+            WHERE sl.token = $1 AND sl.revoked_at IS NULL
+            AND wd.id = sl.workout_data_id
+            AND p.visibility IS DISTINCT FROM 'private'
+            RETURNING sl.workout_data_id, p.visibility::text as visibility,
+                      (SELECT m->>'url' FROM jsonb_array_elements(p.media_urls) m
+                       WHERE m->>'type' = 'image' LIMIT 1) as post_image_url
+        )
+        SELECT u.username, u.profile_picture_url,
+               COALESCE(wd.user_activity, wd.activity_name, 'Workout') as "activity!",
+               wd.workout_start, wd.duration_minutes, wd.calories_burned,
+               wd.avg_heart_rate, wd.max_heart_rate, wd.total_points_gained,
+               COALESCE(wd.image_url, link.post_image_url) as image_url,
+               wd.notes, link.visibility
+        FROM link
+        JOIN workout_data wd ON wd.id = link.workout_data_id
+        JOIN users u ON u.id = wd.user_id
+        "#,
+        token
+    )
+    .fetch_optional(&**pool)
+    .await
+    {
+        Ok(Some(row)) => row,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(json!({
+                "success": false,
+                "error": "Shared workout not found"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch shared workout: {}", e);
+            return HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }));
+        }
+    };
+
+    let mut highlights = Vec::new();
+    if let Some(minutes) = row.duration_minutes {
+        highlights.push(format!("{} min", minutes));
+    }
+    if let Some(calories) = row.calories_burned {
+        highlights.push(format!("{} kcal", calories));
+    }
+    highlights.push(format!("{} points", row.total_points_gained));
+
+    let summary = PublicWorkoutSummary {
+        og: OpenGraphSummary {
+            title: format!("{}'s {}", row.username, row.activity),
+            description: highlights.join(" · "),
+            image_url: row.image_url.clone(),
+        },
+        username: row.username,
+        profile_picture_url: row.profile_picture_url,
+        activity: row.activity,
+        workout_start: row.workout_start,
+        duration_minutes: row.duration_minutes,
+        calories_burned: row.calories_burned,
+        avg_heart_rate: row.avg_heart_rate,
+        max_heart_rate: row.max_heart_rate,
+        total_points_gained: row.total_points_gained,
+        image_url: row.image_url,
+        // Notes are only public on public posts
+        notes: row.notes.filter(|_| row.visibility.as_deref() == Some("public")),
+    };
+
+    HttpResponse::Ok().json(json!({
+        "success": true,
+        "data": summary
+    }))
+}
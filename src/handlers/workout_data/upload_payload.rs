@@ -0,0 +1,178 @@
+use std::cell::Cell;
+use std::fmt;
+
+use actix_web::{dev::Decompress, http::header, web, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+
+use crate::config::upload_limits::UploadLimitsSettings;
+use crate::models::common::ApiResponse;
+use crate::models::workout_data::WorkoutDataUploadRequest;
+
+/// Bodies above this size are parsed on the blocking thread pool
+const BLOCKING_PARSE_THRESHOLD: usize = 1024 * 1024;
+
+/// Which upload limit a request exceeded, returned with the 413
+#[derive(Debug, Serialize)]
+pub struct UploadLimitExceeded {
+    pub limit: &'static str, // "payload_bytes" or "heart_rate_samples"
+    pub max: usize,
+    pub received: Option<usize>, // Announced body size; unknown when reading or counting stopped at the cap
+}
+
+/// Read a workout upload chunk by chunk, stopping as soon as it exceeds the configured caps.
+/// Compressed bodies (Content-Encoding gzip, br, ...) are inflated while reading, and the size cap
+/// applies to the inflated bytes. Heart rate samples are counted in a first pass that allocates
+/// nothing and stops at the cap, so oversized series are rejected before the workout is materialized.
+pub async fn read_workout_upload(
+    req: &HttpRequest,
+    payload: web::Payload,
+    limits: &UploadLimitsSettings,
+) -> Result<WorkoutDataUploadRequest, HttpResponse> {
+    let announced_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if let Some(length) = announced_length.filter(|&length| length > limits.max_payload_bytes) {
+        return Err(limit_exceeded("payload_bytes", limits.max_payload_bytes, Some(length)));
+    }
+
+    let mut payload = Decompress::from_headers(payload.into_inner(), req.headers());
+    let mut body = web::BytesMut::with_capacity(announced_length.unwrap_or(0));
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| {
+            tracing::warn!("Failed to read workout upload: {}", e);
+            HttpResponse::BadRequest().json(ApiResponse::<()>::error("Failed to read request body"))
+        })?;
+        if body.len() + chunk.len() > limits.max_payload_bytes {
+            tracing::warn!("Workout upload exceeds {} bytes", limits.max_payload_bytes);
+            return Err(limit_exceeded("payload_bytes", limits.max_payload_bytes, None));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let max_samples = limits.max_heart_rate_samples;
+    let parsed = if body.len() > BLOCKING_PARSE_THRESHOLD {
+        web::block(move || parse_workout_upload(&body, max_samples))
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to parse workout upload: {}", e);
+                HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to parse workout upload"))
+            })?
+    } else {
+        parse_workout_upload(&body, max_samples)
+    };
+
+    parsed.map_err(|e| match e {
+        UploadParseError::TooManySamples => {
+            tracing::warn!("Workout upload has more than {} heart rate samples", max_samples);
+            limit_exceeded("heart_rate_samples", max_samples, None)
+        }
+        UploadParseError::Invalid(e) => HttpResponse::BadRequest().json(
+            ApiResponse::<()>::error(format!("Invalid workout data: {e}"))
+        ),
+    })
+}
+
+fn limit_exceeded(limit: &'static str, max: usize, received: Option<usize>) -> HttpResponse {
+    let message = match limit {
+        "heart_rate_samples" => format!("Workout has too many heart rate samples (max {max})"),
+        _ => format!("Upload is too large (max {max} bytes)"),
+    };
+    HttpResponse::PayloadTooLarge().json(ApiResponse {
+        success: false,
+        message: message.clone(),
+        data: Some(UploadLimitExceeded { limit, max, received }),
+        error: Some(message),
+    })
+}
+
+enum UploadParseError {
+    TooManySamples,
+    Invalid(serde_json::Error),
+}
+
+fn parse_workout_upload(body: &[u8], max_samples: usize) -> Result<WorkoutDataUploadRequest, UploadParseError> {
+    let counted = Cell::new(0);
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    // Malformed bodies are reported by the full parse below
+    let _ = HeartRateSampleCount { max_samples, counted: &counted }.deserialize(&mut deserializer);
+    if counted.get() > max_samples {
+        return Err(UploadParseError::TooManySamples);
+    }
+    serde_json::from_slice(body).map_err(UploadParseError::Invalid)
+}
+
+/// Counts the entries of the top-level `heart_rate` array into `counted`, skipping everything else.
+/// Fails as soon as the count passes the limit, leaving the rest of the body unread.
+#[derive(Clone, Copy)]
+struct HeartRateSampleCount<'a> {
+    max_samples: usize,
+    counted: &'a Cell<usize>,
+}
+
+impl<'de> DeserializeSeed<'de> for HeartRateSampleCount<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for HeartRateSampleCount<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a workout upload")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "heart_rate" {
+                map.next_value_seed(HeartRateSamples(self))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct HeartRateSamples<'a>(HeartRateSampleCount<'a>);
+
+impl<'de> DeserializeSeed<'de> for HeartRateSamples<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for HeartRateSamples<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of heart rate samples")
+    }
+
+    fn visit_none<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let HeartRateSamples(count) = self;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            count.counted.set(count.counted.get() + 1);
+            if count.counted.get() > count.max_samples {
+                return Err(de::Error::custom("heart rate sample limit exceeded"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,28 +2,47 @@ use actix_web::{web, HttpResponse};
 use chrono::{DateTime, Utc};
 use serde_json::json;
 use uuid::Uuid;
-use redis::AsyncCommands;
+use sqlx::{Acquire, PgConnection, Postgres, Transaction};
 use std::sync::Arc;
+use redis::AsyncCommands;
 use crate::middleware::auth::Claims;
 use crate::db::{
-    workout_data::{insert_workout_data, create_post_for_workout, update_workout_data_with_classification_and_score},
+    workout_data::{insert_workout_data, create_post_for_workout, get_workout_posting_settings, update_workout_data_with_classification_and_score, update_workout_intervals},
     game_queries::GameQueries,
-    health_data::{get_user_health_profile_details, update_max_heart_rate_and_vt_thresholds},
+    game_repo::{GameRepo, GameRepository},
+    team_repo::TeamRepo,
+    health_data::{get_user_health_profile_at, get_user_health_profile_details, update_max_heart_rate_and_vt_thresholds},
+    upload_attempts::{record_upload_attempt, NewUploadAttempt},
 };
 use crate::models::{
     workout_data::{WorkoutDataUploadRequest, WorkoutUploadResponse, StatChanges, WorkoutStats, HeartRateData, WorkoutType},
     health::{UserHealthProfile},
     common::ApiResponse,
+    diagnostics::UploadOutcome,
     league::{LeagueGame, LiveGameScoreUpdate},
     game_events::GameEvent,
+    post::{AutoPostMode, PostVisibility},
 };
+use crate::game::boosters;
+use crate::game::comeback_bonus::{self, ComebackBonus};
 use crate::game::stats_calculator::WorkoutStatsCalculator;
+use crate::game::workout_credit::credited_team_for_workout;
+use crate::workout::effort_calibration::apply_calibration;
+use crate::workout::intervals::detect_intervals;
+use crate::workout::post_caption::{render_caption, CaptionContext};
+use crate::services::post_publishing_service::create_draft;
 use crate::utils::{
     workout_approval::WorkoutApprovalToken,
     heart_rate_filters::filter_heart_rate_data,
 };
 use crate::config::jwt::JwtSettings;
 use crate::services::ml_client::{ClassifyResponse, MLClient};
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::live_state_sync_service::season_scores_channel;
+use crate::services::telemetry::traced_message;
+use crate::services::{
+    booster_service, event_outbox, league_quest_service, scoring_calibration_service, suspension_service, EventOutbox, GameCommentaryService, LeagueQuestService, TeamNotificationService, UserStatsCache,
+};
 
 #[tracing::instrument(
     name = "Upload workout data with game stats",
@@ -53,6 +72,7 @@ pub async fn upload_workout_data(
         Some(token) => token,
         None => {
             tracing::error!("❌ No approval token provided for workout {}", data.workout_uuid);
+            record_rejection(&pool, user_id, &data, "No approval token").await;
             return HttpResponse::BadRequest().json(
                 ApiResponse::<()>::error("Approval token is required. Please sync workouts first to get approval tokens.")
             );
@@ -66,6 +86,7 @@ pub async fn upload_workout_data(
             if approved_workout.workout_id != data.workout_uuid {
                 tracing::error!("❌ Workout ID mismatch: expected {}, got {}", 
                     approved_workout.workout_id, data.workout_uuid);
+                record_rejection(&pool, user_id, &data, "Workout ID does not match approval token").await;
                 return HttpResponse::BadRequest().json(
                     ApiResponse::<()>::error("Workout ID does not match approval token")
                 );
@@ -77,6 +98,7 @@ pub async fn upload_workout_data(
             
             if time_diff_start > 60 || time_diff_end > 60 {
                 tracing::error!("❌ Workout timestamps do not match approval token");
+                record_rejection(&pool, user_id, &data, "Workout timestamps do not match approval").await;
                 return HttpResponse::BadRequest().json(
                     ApiResponse::<()>::error("Workout timestamps do not match approval")
                 );
@@ -86,6 +108,7 @@ pub async fn upload_workout_data(
         },
         Err(e) => {
             tracing::error!("❌ Invalid approval token for workout {}: {}", data.workout_uuid, e);
+            record_rejection(&pool, user_id, &data, &format!("Invalid or expired approval token: {e}")).await;
             return HttpResponse::Unauthorized().json(
                 ApiResponse::<()>::error(format!("Invalid or expired approval token: {e}"))
             );
@@ -96,6 +119,7 @@ pub async fn upload_workout_data(
         Some(data) => data,
         None => {
             tracing::warn!("⚠️ No heart rate data provided - returning zero stats");
+            record_rejection(&pool, user_id, &data, "No heart rate data provided").await;
             return HttpResponse::BadRequest().json(
                 ApiResponse::<()>::error("No heart rate data provided")
             );
@@ -104,6 +128,7 @@ pub async fn upload_workout_data(
     
     if heart_rate_data.is_empty() {
         tracing::warn!("⚠️ No heart rate data provided - returning zero stats");
+        record_rejection(&pool, user_id, &data, "No heart rate data provided").await;
         return HttpResponse::BadRequest().json(
             ApiResponse::<()>::error("No heart rate data provided")
         );
@@ -123,37 +148,8 @@ pub async fn upload_workout_data(
         tracing::info!("✅ Heart rate data filtered successfully - removed {} samples", removed_heart_rate_samples);
     }
     
-    // Insert workout data into database FIRST (with temporary/placeholder stats)
-    tracing::info!("💾 Inserting workout data into database for user: {} with workout_uuid: {:?}",
-    claims.username, data.workout_uuid);
-
-    // Create placeholder stats for initial insertion
-    let placeholder_stats = WorkoutStats {
-        changes: StatChanges::new(),
-        zone_breakdown: None,
-    };
-    let sync_id = match insert_workout_data(&pool, user_id, &data, &placeholder_stats).await {
-        Ok(id) => id,
-        Err(e) => {
-            tracing::error!("❌ Error inserting workout data: {}", e);
-            return HttpResponse::InternalServerError().json(
-                ApiResponse::<()>::error("Error inserting workout data")
-            );
-        }
-    };
-
-    // Create a post for this workout with media files (mandatory)
-    match create_post_for_workout(&pool, user_id, sync_id, &data.image_urls, &data.video_urls, data.workout_start).await {
-        Ok(_post_id) => tracing::info!("✅ Successfully created post for workout {} with media", sync_id),
-        Err(e) => {
-            tracing::error!("❌ Failed to create post for workout {}: {}", sync_id, e);
-            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create post for workout")
-            );
-        }
-    };
-
-    // Get user health profile
-    let mut user_health_profile = get_user_health_profile_details(&pool, user_id).await.unwrap();
+    // Get the health profile that was current when the workout happened
+    let mut user_health_profile = get_user_health_profile_at(&pool, user_id, data.workout_start).await.unwrap();
 
     // Check and update max heart rate if needed
     update_max_heart_rate_if_needed(&mut user_health_profile, &heart_rate_data, user_id, &pool).await;
@@ -179,7 +175,7 @@ pub async fn upload_workout_data(
     // 🎲 NOW CALCULATE GAME STATS
     let workout_type = WorkoutType::parse(&ml_classification.prediction.to_lowercase());
     let calculator = WorkoutStatsCalculator::with_universal_hr_based();
-    let workout_stats = match calculator.calculate_stat_changes(user_health_profile, heart_rate_data.clone(), workout_type).await {
+    let mut workout_stats = match calculator.calculate_stat_changes(user_health_profile, heart_rate_data.clone(), workout_type).await {
         Ok(stats) => stats,
         Err(e) => {
             tracing::error!("❌ Error calculating workout stats: {}", e);
@@ -189,6 +185,16 @@ pub async fn upload_workout_data(
         }
     };
 
+    // 🎚️ WEIGHT THE ZONES BY THE USER'S CALIBRATION FROM EFFORT RATINGS
+    let scoring_calibration = match scoring_calibration_service::current_factor(pool.get_ref(), user_id).await {
+        Ok(factor) => factor,
+        Err(e) => {
+            tracing::warn!("⚠️ Failed to load scoring calibration for {}: {}. Scoring uncalibrated.", claims.username, e);
+            1.0
+        }
+    };
+    apply_calibration(&mut workout_stats, scoring_calibration);
+
     tracing::info!("📊 Calculated stat changes for {}: +{} stamina, +{} strength",
         claims.username, workout_stats.changes.stamina_change, workout_stats.changes.strength_change,
     );
@@ -196,7 +202,81 @@ pub async fn upload_workout_data(
     // Heart rate zone breakdown - always use the scoring system's zone breakdown
     let zone_breakdown = workout_stats.zone_breakdown.clone().unwrap_or_default();
 
-    match update_workout_data_with_classification_and_score(&pool, sync_id, &workout_stats, &zone_breakdown, &ml_classification).await {
+    // Work/rest intervals, for HIIT breakdowns in the workout detail
+    let intervals = detect_intervals(&heart_rate_data);
+
+    // 💾 STORE, SCORE AND ANNOUNCE THE WORKOUT IN ONE TRANSACTION
+    // Either all of it is stored or nothing is, so a failed upload can simply be retried
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            tracing::error!("❌ Failed to start workout upload transaction: {}", e);
+            return HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Error inserting workout data")
+            );
+        }
+    };
+
+    tracing::info!("💾 Inserting workout data into database for user: {} with workout_uuid: {:?}",
+    claims.username, data.workout_uuid);
+
+    let sync_id = match insert_workout_data(&mut tx, user_id, &data, &workout_stats).await {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("❌ Error inserting workout data: {}", e);
+            return HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Error inserting workout data")
+            );
+        }
+    };
+
+    // Create a post for this workout with media files (mandatory); the user's posting settings
+    // decide whether it is published right away and what its caption is
+    let posting_settings = match get_workout_posting_settings(&mut tx, user_id).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::error!("❌ Failed to load posting settings of {}: {}", claims.username, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create post for workout")
+            );
+        }
+    };
+    let caption = posting_settings.caption_template.as_deref().map(|template| {
+        render_caption(template, &CaptionContext {
+            activity: data.activity_name.as_deref().unwrap_or(workout_type.as_str()),
+            minutes: (data.workout_end - data.workout_start).num_minutes(),
+            points: (workout_stats.changes.stamina_change + workout_stats.changes.strength_change).round() as i32,
+            calories: data.calories_burned,
+        })
+    });
+    let post_id = match create_post_for_workout(
+        &mut tx,
+        user_id,
+        sync_id,
+        &data,
+        caption.as_deref(),
+        posting_settings.auto_post.post_visibility(),
+    ).await {
+        Ok(post_id) => {
+            tracing::info!("✅ Successfully created post for workout {} with media ({:?})", sync_id, posting_settings.auto_post);
+            post_id
+        }
+        Err(e) => {
+            tracing::error!("❌ Failed to create post for workout {}: {}", sync_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create post for workout")
+            );
+        }
+    };
+
+    // Posts the user is asked about are drafts, so the app's prompt can publish them
+    if posting_settings.auto_post == AutoPostMode::Ask {
+        if let Err(e) = create_draft(&mut tx, post_id, PostVisibility::Public, None).await {
+            tracing::error!("❌ Failed to keep the post of workout {} as a draft: {}", sync_id, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to create post for workout")
+            );
+        }
+    }
+
+    match update_workout_data_with_classification_and_score(&mut tx, sync_id, &workout_stats, &zone_breakdown, &ml_classification, scoring_calibration).await {
         Ok(_) => tracing::debug!("Successfully updated workout stats"),
         Err(e) => {
             tracing::error!("Failed to update workout stats: {}", e);
@@ -205,8 +285,16 @@ pub async fn upload_workout_data(
         }
     };
 
+    if let Some(intervals) = &intervals {
+        tracing::info!("⏱️ Detected intervals in workout {}: {}", sync_id, intervals.summary);
+        if let Err(e) = update_workout_intervals(&mut tx, sync_id, intervals).await {
+            tracing::error!("Failed to store workout intervals: {}", e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to update workout stats"));
+        }
+    }
+
     // Update user avatar stats
-    let update_result = update_user_stats(user_id, &workout_stats.changes, &pool).await;
+    let update_result = update_user_stats(user_id, &workout_stats.changes, &mut tx).await;
     match update_result {
         Ok(_) => {
             tracing::info!("✅ Successfully updated user stats for {}", claims.username);
@@ -219,24 +307,61 @@ pub async fn upload_workout_data(
         }
     }
 
+    // 🚫 WORKOUTS RECORDED WHILE SUSPENDED ARE KEPT BUT DON'T COUNT TOWARD GAMES OR QUESTS
+    let excluded = match suspension_service::overlaps_suspension(&mut tx, user_id, data.workout_start, data.workout_end).await {
+        Ok(excluded) => excluded,
+        Err(e) => {
+            tracing::error!("❌ Failed to check suspensions of user {}: {}", claims.username, e);
+            return HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to update game scores")
+            );
+        }
+    };
+    if excluded {
+        tracing::info!("🚫 Workout {} of {} overlaps a suspension, not counting it", sync_id, claims.username);
+    }
+
     // 🏆 CHECK FOR ACTIVE GAMES AND UPDATE SCORES
-    match check_and_update_active_games(
-        user_id, 
-        &claims.username,
-        sync_id,
-        &workout_stats,
-        &data.workout_start,
-        &data.workout_end,
-        &pool,
-    ).await {
-        Ok(_) => {
-            tracing::info!("✅ Successfully updated game scores for user {}", claims.username);
+    let scored_games = if excluded {
+        Vec::new()
+    } else {
+        match check_and_update_active_games(
+            user_id, 
+            &claims.username,
+            sync_id,
+            &workout_stats,
+            &data.workout_start,
+            &data.workout_end,
+            &pool,
+            &mut tx,
+        ).await {
+            Ok(scored_games) => {
+                tracing::info!("✅ Successfully updated game scores for user {}", claims.username);
+                scored_games
+            }
+            Err(e) => {
+                tracing::error!("❌ Failed to update game scores for user {}: {}", claims.username, e);
+                return HttpResponse::InternalServerError().json(
+                    ApiResponse::<()>::error("Failed to update game scores")
+                );
+            }
         }
+    };
+
+    // 🏔️ COUNT THE WORKOUT TOWARD THE LEAGUE'S QUESTS
+    let quest_points = if excluded { 0.0 } else { workout_stats.changes.stamina_change + workout_stats.changes.strength_change };
+    let reached_milestones = match league_quest_service::record_workout(&mut tx, user_id, sync_id, data.workout_start, quest_points).await {
+        Ok(reached) => reached,
         Err(e) => {
-            tracing::error!("❌ Failed to update game scores for user {}: {}", claims.username, e);
+            tracing::error!("❌ Failed to update league quests for user {}: {}", claims.username, e);
+            return HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Failed to update league quests")
+            );
         }
-    }
+    };
+
     // 🎯 PREPARE GAME EVENT FOR REAL-TIME NOTIFICATION
+    // It goes out through the outbox, so it is only published for a committed workout
     let game_event = json!({
         "event_type": "workout_data_processed",
         "user_id": user_id.to_string(),
@@ -248,57 +373,88 @@ pub async fn upload_workout_data(
         },
         "timestamp": Utc::now().to_rfc3339()
     });
+    let channels = [format!("game:events:user:{user_id}"), "game:events:global".to_string()];
+    let outbox_event_id = match event_outbox::enqueue(&mut tx, &channels, &game_event).await {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("❌ Failed to queue game event for {}: {}", claims.username, e);
+            return HttpResponse::InternalServerError().json(
+                ApiResponse::<()>::error("Error inserting workout data")
+            );
+        }
+    };
+
+    if let Err(e) = tx.commit().await {
+        tracing::error!("❌ Failed to commit workout {} for {}: {}", sync_id, claims.username, e);
+        return HttpResponse::InternalServerError().json(
+            ApiResponse::<()>::error("Error inserting workout data")
+        );
+    }
+
+    UserStatsCache::new(pool.get_ref().clone(), redis.as_ref().map(|r| r.get_ref().clone()))
+        .invalidate(&[user_id])
+        .await;
+
+    let (outcome, detail) = if excluded {
+        (UploadOutcome::Flagged, Some("Overlaps a suspension, doesn't count toward games or quests"))
+    } else if scored_games.is_empty() {
+        (UploadOutcome::OutOfWindow, Some("No running game of your team covered the workout"))
+    } else {
+        (UploadOutcome::Accepted, None)
+    };
+    record_attempt(pool.get_ref(), user_id, &data, outcome, detail, Some(sync_id)).await;
+
+    announce_scored_games(&scored_games, pool.get_ref(), redis.as_ref().map(|r| r.get_ref().clone())).await;
 
     // 📡 PUBLISH TO REDIS FOR REAL-TIME NOTIFICATION
-    if let Some(redis_client) = &redis {
-        let user_channel = format!("game:events:user:{user_id}");
-        let global_channel = "game:events:global".to_string();
-        let event_str = serde_json::to_string(&game_event)
-            .unwrap_or_else(|e| {
-                tracing::error!("Failed to serialize game event: {}", e);
-                "{}".to_string()
-            });
-
-        let redis_client = redis_client.clone();
-        let event_str_clone = event_str.clone();
-        let username_clone = claims.username.clone();
-        
-        tokio::spawn(async move {
-            match redis_client.get_async_connection().await {
-                Ok(mut conn) => {
-                    // Publish to user-specific channel
-                    let user_result: Result<i32, redis::RedisError> = 
-                        conn.publish(&user_channel, &event_str).await;
-                    
-                    // Also publish to global channel for leaderboards/social features
-                    let global_result: Result<i32, redis::RedisError> = 
-                        conn.publish(&global_channel, &event_str_clone).await;
-                    
-                    match (user_result, global_result) {
-                        (Ok(user_receivers), Ok(global_receivers)) => {
-                            tracing::info!("🎮 Published game event for {} to {} user subscribers and {} global subscribers", 
-                                username_clone, user_receivers, global_receivers);
-                        }
-                        (Err(e), _) | (_, Err(e)) => {
-                            tracing::error!("❌ Failed to publish game event for {}: {}", username_clone, e);
-                        }
-                    }
-                },
-                Err(e) => {
-                    tracing::error!("❌ Redis connection failed during game event publishing: {}", e);
-                }
+    // Events that fail to go out now are published by the scheduler's outbox job
+    let event_outbox = EventOutbox::new(pool.get_ref().clone(), redis.as_ref().map(|r| r.get_ref().clone()));
+    let username = claims.username.clone();
+    let mut outbox_event_ids = vec![outbox_event_id];
+    outbox_event_ids.extend(scored_games.iter().filter_map(|g| g.booster_event_id));
+    outbox_event_ids.extend(reached_milestones.iter().map(|(_, event_id)| *event_id));
+    tokio::spawn(async move {
+        match event_outbox.publish(&outbox_event_ids).await {
+            Ok(published) if published > 0 => {
+                tracing::info!("🎮 Published game event for {}", username);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("❌ Failed to publish game event for {}: {}", username, e);
             }
+        }
+    });
+
+    if !reached_milestones.is_empty() {
+        let quest_service = LeagueQuestService::new(pool.get_ref().clone());
+        let milestones: Vec<_> = reached_milestones.into_iter().map(|(milestone, _)| milestone).collect();
+        tokio::spawn(async move {
+            quest_service.notify_members(&milestones).await;
         });
-    } else {
-        tracing::warn!("⚠️  Redis not available - game events will not be published in real-time");
     }
 
+    // 🔔 TEAM NOTIFICATIONS: OPPONENTS THAT FELL BEHIND, TEAMMATES OF A MILESTONE
+    let team_notifications = TeamNotificationService::new(pool.get_ref().clone());
+    let score_event_ids: Vec<Uuid> = scored_games.iter().map(|g| g.score_event_id).collect();
+    tokio::spawn(async move {
+        for score_event_id in score_event_ids {
+            if let Err(e) = team_notifications.notify_if_fell_behind(score_event_id).await {
+                tracing::error!("Failed to notify the team behind after score event {}: {}", score_event_id, e);
+            }
+        }
+        if let Err(e) = team_notifications.notify_if_member_milestone(user_id, sync_id).await {
+            tracing::error!("Failed to notify the teammates of {} about a milestone: {}", user_id, e);
+        }
+    });
+
     // 🎉 RESPONSE WITH GAME STATS
     let message = "Workout data synced and game stats calculated!";
     let response = WorkoutUploadResponse {
         sync_id,
         timestamp: Utc::now(),
         game_stats: workout_stats.changes,
+        post_id,
+        post_awaiting_confirmation: posting_settings.auto_post == AutoPostMode::Ask,
     };
 
     tracing::info!("✅ Workout data processed successfully for {}: {}", 
@@ -308,7 +464,47 @@ pub async fn upload_workout_data(
     )
 }
 
-/// Check if user is in any active games and update scores using consolidated architecture
+/// Keep what became of the upload for the user's sync diagnostics; failing to is only logged
+async fn record_attempt(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+    data: &WorkoutDataUploadRequest,
+    outcome: UploadOutcome,
+    detail: Option<&str>,
+    workout_data_id: Option<Uuid>,
+) {
+    let attempt = NewUploadAttempt {
+        device_id: Some(&data.device_id),
+        workout_uuid: &data.workout_uuid,
+        outcome,
+        detail,
+        workout_data_id,
+        workout_start: Some(data.workout_start),
+        workout_end: Some(data.workout_end),
+    };
+    if let Err(e) = record_upload_attempt(pool, user_id, &attempt).await {
+        tracing::warn!("Failed to record upload attempt of workout {}: {}", data.workout_uuid, e);
+    }
+}
+
+async fn record_rejection(pool: &sqlx::PgPool, user_id: Uuid, data: &WorkoutDataUploadRequest, reason: &str) {
+    record_attempt(pool, user_id, data, UploadOutcome::Rejected, Some(reason), None).await;
+}
+
+/// A game a workout scored in, with the score event it produced
+struct ScoredGame {
+    game_id: Uuid,
+    score_event_id: Uuid,
+    team_side: &'static str,
+    points: f32,
+    /// Outbox event announcing a booster the workout used up
+    booster_event_id: Option<Uuid>,
+}
+
+/// Check if user is in any active games and update scores using consolidated architecture.
+/// Each game is scored within its own savepoint of the upload transaction.
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "Score workout in active games", skip_all, fields(user_id = %user_id, workout_data_id = %workout_data_id))]
 async fn check_and_update_active_games(
     user_id: Uuid,
     username: &str,
@@ -317,63 +513,67 @@ async fn check_and_update_active_games(
     workout_start_time: &DateTime<Utc>,
     workout_end_time: &DateTime<Utc>,
     pool: &sqlx::PgPool,
-) -> Result<(), Box<dyn std::error::Error>> {
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<ScoredGame>, Box<dyn std::error::Error>> {
     tracing::info!("🎮 Checking for active games for user {}", username);
 
     let game_queries = GameQueries::new(pool.clone());
-    let active_games = game_queries.get_active_games().await?;
+    let active_games = game_queries.get_games_accepting_workouts().await?;
     
     if active_games.is_empty() {
         tracing::debug!("No active games found for user {}", username);
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     tracing::info!("🏆 Found {} active game(s) to check for user {}", active_games.len(), username);
 
+    let team_repo = TeamRepo::new(pool.clone());
+    let mut scored_games = Vec::new();
     for game in active_games {
-        // Check if user is a member of either team in this game and get join date
-        let (user_team_id, joined_at) = match get_user_team_for_game(user_id, &game, pool).await {
-            Ok(result) => result,
-            Err(_) => {
-                tracing::debug!("User {} is not a member of teams playing in game {}", username, game.id);
-                continue;
-            }
+        // Credit the workout to the user's team if it counts for this game
+        let Some(user_team_id) = credited_team_for_workout(
+            &team_repo,
+            user_id,
+            &game,
+            *workout_start_time,
+            *workout_end_time,
+        ).await? else {
+            continue;
         };
 
-        // Check if the workout was performed after the player joined the team
-        if workout_start_time < &joined_at {
-            tracing::debug!("❌ Workout time ({}) is before player joined team ({}) for user {} in game {}",
-                           workout_start_time, joined_at, username, game.id);
-            continue;
-        }
+        tracing::info!("🏆 Workout time is within live game period for user {} in game {} ({} to {})",
+                      username, game.id, workout_start_time, workout_end_time);
 
-        // Check if the workout time falls within the game's live scoring period
-        if let (Some(game_start), Some(game_end)) = (game.game_start_time, game.game_end_time) {
-            if workout_start_time >= &game_start && workout_end_time <= &game_end {
-                tracing::info!("🏆 Workout time is within live game period for user {} in game {} ({} to {})",
-                              username, game.id, workout_start_time, workout_end_time);
-                update_game_score_from_workout(
-                    user_id,
-                    username,
-                    user_team_id,
-                    &game,
-                    stat_changes,
-                    workout_data_id,
-                    pool,
-                ).await?;
-            } else {
-                tracing::debug!("❌ Workout time ({} to {}) is outside live game period ({} to {}) for user {} in game {}",
-                               workout_start_time, workout_end_time, game_start, game_end, username, game.id);
+        // A workout that already scored in this game violates the unique score event per
+        // game and workout; rolling back to the savepoint keeps the rest of the upload intact
+        let mut savepoint = tx.begin().await?;
+        match update_game_score_from_workout(
+            user_id,
+            username,
+            user_team_id,
+            &game,
+            stat_changes,
+            workout_data_id,
+            *workout_end_time,
+            &mut savepoint,
+        ).await {
+            Ok(scored_game) => {
+                savepoint.commit().await?;
+                scored_games.push(scored_game);
             }
-        } else {
-            tracing::debug!("❌ Game {} does not have live scoring times set", game.id);
+            Err(e) if e.as_database_error().is_some_and(|db_error| db_error.is_unique_violation()) => {
+                savepoint.rollback().await?;
+                tracing::info!("Workout {} already scored in game {}, skipping", workout_data_id, game.id);
+            }
+            Err(e) => return Err(e.into()),
         }
     }
 
-    Ok(())
+    Ok(scored_games)
 }
 
 /// Update game score based on workout stats using consolidated games table
+#[allow(clippy::too_many_arguments)]
 async fn update_game_score_from_workout(
     user_id: Uuid,
     username: &str,
@@ -381,15 +581,13 @@ async fn update_game_score_from_workout(
     game: &LeagueGame,
     workout_stats: &WorkoutStats,
     workout_data_id: Uuid,
-    pool: &sqlx::PgPool,
-) -> Result<(), Box<dyn std::error::Error>> {
+    workout_end_time: DateTime<Utc>,
+    conn: &mut PgConnection,
+) -> Result<ScoredGame, sqlx::Error> {
     tracing::info!("🏆 Updating game score for user {} in game {}", username, game.id);
 
     // Simple scoring: just add up stamina and strength gains
-    let score_increase = workout_stats.changes.stamina_change + workout_stats.changes.strength_change;
-    
-    tracing::info!("📊 Score calculation for {}: stamina={}, strength={}, score_increase={}", 
-        username, workout_stats.changes.stamina_change, workout_stats.changes.strength_change, score_increase);
+    let workout_points = workout_stats.changes.stamina_change + workout_stats.changes.strength_change;
 
     // Determine which team side (home or away)
     let team_side = if user_team_id == game.home_team_id {
@@ -398,172 +596,201 @@ async fn update_game_score_from_workout(
         "away"
     };
 
+    let bonus = find_comeback_bonus(game, team_side, workout_end_time, workout_points, &mut *conn).await?;
+    // An active double booster of the team goes to this workout
+    let booster_id = if workout_points > 0.0 {
+        booster_service::take_active_double_booster(&mut *conn, game.id, user_team_id).await?
+    } else {
+        None
+    };
+    let booster_bonus = booster_id.map_or(0.0, |_| boosters::double_bonus(workout_points));
+    let score_increase = workout_points + bonus.map_or(0.0, |b| b.bonus_points) + booster_bonus;
+
+    tracing::info!("📊 Score calculation for {}: stamina={}, strength={}, comeback_bonus={:?}, booster_bonus={}, score_increase={}", 
+        username, workout_stats.changes.stamina_change, workout_stats.changes.strength_change, bonus, booster_bonus, score_increase);
+
     // IMPORTANT: Record the scoring event FIRST before updating game scores
     // The game score calculation depends on reading from live_score_events
-    record_score_event(
+    let score_event_id = record_score_event(
         game.id,
         user_id,
-        username,
         user_team_id,
         team_side,
         score_increase,
         workout_stats.changes.stamina_change,
         workout_stats.changes.strength_change,
+        bonus,
+        booster_bonus,
         workout_data_id,
-        pool
+        &mut *conn
     ).await?;
 
+    let booster_event_id = match booster_id {
+        Some(booster_id) => Some(booster_service::mark_applied(&mut *conn, booster_id, game.id, score_event_id, booster_bonus).await?),
+        None => None,
+    };
+
     // Now update the game score using GameQueries (which reads from live_score_events)
     let score_update = LiveGameScoreUpdate {
         user_id,
         username: username.to_string(),
         score_increase,
     };
-    let game_queries = GameQueries::new(pool.clone());
-    game_queries.update_game_score(game.id, &score_update).await?;
-
-    // Broadcast score update via WebSocket
-    broadcast_score_update(game.id, pool).await.unwrap_or_else(|e| {
-        tracing::error!("Failed to broadcast score update: {}", e);
-    });
+    GameQueries::update_game_score(&mut *conn, game.id, &score_update).await?;
 
     tracing::info!("✅ Successfully updated score for game {} by {} points from user {}", 
         game.id, score_increase, username);
 
-    Ok(())
+    Ok(ScoredGame { game_id: game.id, score_event_id, team_side, points: score_increase, booster_event_id })
 }
 
-/// Helper function to determine which team a user belongs to in a game
-/// Returns the team_id and joined_at timestamp
-async fn get_user_team_for_game(
-    user_id: Uuid,
+/// Comeback bonus of a workout under the league's comeback rule, judged on the current score
+async fn find_comeback_bonus(
     game: &LeagueGame,
-    pool: &sqlx::PgPool
-) -> Result<(Uuid, DateTime<Utc>), Box<dyn std::error::Error>> {
-    let membership = sqlx::query!(
+    team_side: &str,
+    workout_end_time: DateTime<Utc>,
+    workout_points: f32,
+    conn: &mut PgConnection,
+) -> Result<Option<ComebackBonus>, sqlx::Error> {
+    let (Some(game_start), Some(game_end)) = (game.game_start_time, game.game_end_time) else {
+        return Ok(None);
+    };
+
+    let current = sqlx::query!(
         r#"
-        SELECT team_id, joined_at
-        FROM team_members
-        WHERE user_id = $1
-        AND status = 'active'
-        AND (team_id = $2 OR team_id = $3)
+        SELECT g.home_score, g.away_score, l.comeback_multiplier
+        FROM games g
+        JOIN league_seasons ls ON ls.id = g.season_id
+        JOIN leagues l ON l.id = ls.league_id
+        WHERE g.id = $1
         "#,
-        user_id,
-        game.home_team_id,
-        game.away_team_id
+        game.id
     )
-    .fetch_optional(pool)
+    .fetch_optional(&mut *conn)
     .await?;
 
-    match membership {
-        Some(m) => Ok((m.team_id, m.joined_at)),
-        None => Err("User does not belong to either team in this game".into())
+    let Some(current) = current else {
+        return Ok(None);
+    };
+    let (team_score, opponent_score) = if team_side == "home" {
+        (current.home_score, current.away_score)
+    } else {
+        (current.away_score, current.home_score)
+    };
+
+    Ok(comeback_bonus::comeback_bonus(
+        current.comeback_multiplier,
+        game_start,
+        game_end,
+        workout_end_time,
+        team_score,
+        opponent_score,
+        workout_points,
+    ))
+}
+
+/// Broadcast the new scores and let the commentator react, once the scores are committed
+#[tracing::instrument(name = "Announce scored games", skip_all, fields(games = scored_games.len()))]
+async fn announce_scored_games(
+    scored_games: &[ScoredGame],
+    pool: &sqlx::PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+) {
+    for scored_game in scored_games {
+        // Broadcast score update via WebSocket
+        broadcast_score_update(scored_game, pool, redis_client.clone()).await.unwrap_or_else(|e| {
+            tracing::error!(tags.game_id = %scored_game.game_id, "Failed to broadcast score update: {}", e);
+        });
+
+        // Let the commentator call first scores, lead changes and new MVP candidates
+        let commentary_service = GameCommentaryService::new(pool.clone(), redis_client.clone());
+        if let Err(e) = commentary_service.comment_on_score(scored_game.game_id, scored_game.score_event_id).await {
+            tracing::error!(tags.game_id = %scored_game.game_id, "Failed to post commentary for game {}: {}", scored_game.game_id, e);
+        }
     }
 }
 
-/// Record a scoring event in the live_score_events table
+/// Record a scoring event in the live_score_events table, under the user's current username
 #[allow(clippy::too_many_arguments)]
 async fn record_score_event(
     game_id: Uuid,
     user_id: Uuid,
-    username: &str,
     team_id: Uuid,
     team_side: &str,
     score_increase: f32,
     stamina_gained: f32,
     strength_gained: f32,
+    bonus: Option<ComebackBonus>,
+    booster_bonus: f32,
     workout_data_id: Uuid,
-    pool: &sqlx::PgPool,
-) -> Result<(), sqlx::Error> {
+    conn: &mut PgConnection,
+) -> Result<Uuid, sqlx::Error> {
+    let score_event_id = Uuid::new_v4();
+    let mut description = comeback_bonus::score_event_description(stamina_gained, strength_gained, bonus);
+    if booster_bonus > 0.0 {
+        description.push_str(&format!(" (2x booster: +{} points)", booster_bonus));
+    }
     sqlx::query!(
         r#"
         INSERT INTO live_score_events (
             id, game_id, user_id, username, team_id, team_side,
             score_points, power_contribution, stamina_gained, strength_gained,
-            event_type, description, workout_data_id, occurred_at
+            event_type, description, workout_data_id, comeback_multiplier, comeback_bonus, booster_bonus, occurred_at
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'workout_upload', $11, $12, NOW())
+        SELECT $1, $2, u.id, u.username, $4, $5, $6, $7, $8, $9, 'workout_upload', $10, $11, $12, $13, $14, NOW()
+        FROM users u WHERE u.id = $3
         "#,
-        Uuid::new_v4(),
+        score_event_id,
         game_id,
         user_id,
-        username,
         team_id,
         team_side,
         score_increase, // score_points
         0i32, // power_contribution (no longer used, set to 0)
         stamina_gained,
         strength_gained,
-        format!("Workout completed: +{} stamina, +{} strength", stamina_gained, strength_gained),
-        workout_data_id
+        description,
+        workout_data_id,
+        bonus.map(|b| b.multiplier),
+        bonus.map_or(0.0, |b| b.bonus_points),
+        booster_bonus
     )
-    .execute(pool)
+    .execute(conn)
     .await?;
 
-    Ok(())
+    Ok(score_event_id)
 }
 
-/// Broadcast game score update via WebSocket
+/// Broadcast game score update via WebSocket, to the clients spectating the game's season
 async fn broadcast_score_update(
-    game_id: Uuid,
+    scored_game: &ScoredGame,
     pool: &sqlx::PgPool,
+    redis_client: Option<Arc<redis::Client>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Get updated game information with team names
-    let game_data = sqlx::query!(
-        r#"
-        SELECT 
-            g.id, g.home_team_id, g.away_team_id, 
-            g.home_score, g.away_score, g.status,
-            g.game_start_time, g.game_end_time,
-            ht.team_name as home_team_name,
-            at.team_name as away_team_name
-        FROM games g
-        JOIN teams ht ON g.home_team_id = ht.id
-        JOIN teams at ON g.away_team_id = at.id
-        WHERE g.id = $1
-        "#,
-        game_id
-    )
-    .fetch_optional(pool)
-    .await?;
+    let game_data = GameRepo::new(pool.clone()).find_live_game_state(scored_game.game_id).await?;
 
     if let Some(game) = game_data {
-        // Calculate game progress (simplified)
-        let game_progress = if let (Some(start), Some(end)) = (game.game_start_time, game.game_end_time) {
-            let now = chrono::Utc::now();
-            let total_duration = end - start;
-            let elapsed = now - start;
-            
-            if elapsed.num_seconds() < 0 {
-                0.0
-            } else if elapsed > total_duration {
-                100.0
-            } else {
-                (elapsed.num_seconds() as f32 / total_duration.num_seconds() as f32) * 100.0
-            }
-        } else {
-            0.0
-        };
+        tracing::info!("Broadcasting score update for game {}: {} {} - {} {}",
+            game.id, game.home_team_name, game.home_score, game.away_score, game.away_team_name);
 
-        let game_event = GameEvent::LiveScoreUpdate {
+        let Some(redis_client) = redis_client else {
+            return Ok(());
+        };
+        let game_event = GameEvent::ScoreDelta {
+            season_id: game.season_id,
             game_id: game.id,
-            home_team_id: game.home_team_id,
-            home_team_name: game.home_team_name,
-            away_team_id: game.away_team_id,
-            away_team_name: game.away_team_name,
-            home_score: game.home_score as u32,
-            away_score: game.away_score as u32,
-            game_progress,
-            game_time_remaining: None, // TODO: Calculate remaining time
-            is_active: game.status == "in_progress",
-            last_updated: chrono::Utc::now(),
+            team_side: scored_game.team_side.to_string(),
+            points: scored_game.points,
+            home_score: game.home_score.max(0) as u32,
+            away_score: game.away_score.max(0) as u32,
+            game_progress: game.progress_percent(Utc::now()),
+            timestamp: Utc::now(),
         };
 
-        // TODO: Implement actual WebSocket broadcasting via Redis
-        if let GameEvent::LiveScoreUpdate { home_team_name, home_score, away_score, away_team_name, .. } = &game_event {
-            tracing::info!("Broadcasting score update for game {}: {} {} - {} {}", 
-                game.id, home_team_name, home_score, away_score, away_team_name);
-        }
+        redis_publish_fault()?;
+        let mut conn = redis_client.get_async_connection().await?;
+        let _: i32 = conn.publish(season_scores_channel(game.season_id), traced_message(&game_event)?).await?;
     }
 
     Ok(())
@@ -572,8 +799,8 @@ async fn broadcast_score_update(
 async fn update_user_stats(
     user_id: Uuid,
     stat_changes: &StatChanges,
-    pool: &sqlx::PgPool,
-) -> Result<(), Box<dyn std::error::Error>> {
+    conn: &mut PgConnection,
+) -> Result<(), sqlx::Error> {
     sqlx::query!(
         r#"
         UPDATE user_avatars
@@ -585,7 +812,7 @@ async fn update_user_stats(
         stat_changes.strength_change,
         user_id
     )
-    .execute(pool)
+    .execute(conn)
     .await?;
 
     Ok(())
@@ -604,7 +831,21 @@ async fn update_max_heart_rate_if_needed(
         .max()
         .unwrap_or(0);
 
-    let stored_max_hr = user_health_profile.max_heart_rate;
+    if workout_max_hr <= user_health_profile.max_heart_rate {
+        return;
+    }
+    // The workout is scored against a measured max even if it is an older workout
+    user_health_profile.max_heart_rate = workout_max_hr;
+
+    // Only raise the stored max: the profile passed in may be an older version than the current one
+    let current_profile = match get_user_health_profile_details(pool, user_id).await {
+        Ok(profile) => profile,
+        Err(e) => {
+            tracing::error!("❌ Failed to fetch current health profile: {}", e);
+            return;
+        }
+    };
+    let stored_max_hr = current_profile.max_heart_rate;
 
     if workout_max_hr > stored_max_hr {
         tracing::info!("🔄 Workout max HR ({}) exceeds stored max HR ({}), updating max heart rate",
@@ -612,7 +853,7 @@ async fn update_max_heart_rate_if_needed(
 
         // Update max heart rate to measured max
         let new_max_hr = workout_max_hr;
-        let resting_hr = user_health_profile.resting_heart_rate;
+        let resting_hr = current_profile.resting_heart_rate;
 
         // Use the centralized function to update max HR and VT thresholds
         match update_max_heart_rate_and_vt_thresholds(
@@ -624,7 +865,6 @@ async fn update_max_heart_rate_if_needed(
             Ok(_) => {
                 tracing::info!("✅ Updated max heart rate from {} to {} and recalculated VT thresholds",
                     stored_max_hr, new_max_hr);
-                user_health_profile.max_heart_rate = new_max_hr;
             }
             Err(e) => {
                 tracing::error!("❌ Failed to update max heart rate: {}", e);
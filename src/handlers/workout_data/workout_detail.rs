@@ -1,11 +1,16 @@
 use actix_web::{web, HttpResponse};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 use sqlx::PgPool;
 use chrono::{DateTime, Utc};
 
-use crate::{middleware::auth::Claims, models::workout_data::HeartRateData};
+use crate::{
+    middleware::auth::Claims,
+    models::workout_data::{HeartRateData, UpdateWorkoutNotesRequest, normalize_tags},
+    workout::hr_downsampling::{downsample, HrResolution},
+    workout::intervals::IntervalAnalysis,
+};
 
 #[derive(Debug, Serialize)]
 pub struct WorkoutDetail {
@@ -20,7 +25,14 @@ pub struct WorkoutDetail {
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
     pub heart_rate_zones: Option<serde_json::Value>,
+    // Zones recalculated after a threshold change; heart_rate_zones keeps the scored original
+    pub recalculated_heart_rate_zones: Option<serde_json::Value>,
+    pub zones_recalculated_at: Option<DateTime<Utc>>,
     pub heart_rate_data: Option<Vec<HeartRateData>>,
+    // Resolution heart_rate_data was downsampled to
+    pub heart_rate_resolution: HrResolution,
+    // Work/rest intervals of interval sessions, e.g. "8 × 2min high intensity"
+    pub intervals: Option<IntervalAnalysis>,
     // Game stats gained from this workout
     pub stamina_gained: Option<f32>,
     pub strength_gained: Option<f32>,
@@ -33,6 +45,22 @@ pub struct WorkoutDetail {
     pub post_updated_at: Option<DateTime<Utc>>,
     pub post_edited_at: Option<DateTime<Utc>>,
     pub post_media_urls: Option<serde_json::Value>,
+    // Notes and tags, for the owner or when the post is public
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkoutDetailQuery {
+    /// `raw` (default), `10s` or `60s`
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkoutNotes {
+    pub id: Uuid,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
 }
 
 fn calculate_duration_minutes(start: DateTime<Utc>, end: DateTime<Utc>) -> Option<i32> {
@@ -60,15 +88,26 @@ fn calculate_max_heart_rate(heart_rate_data: &[HeartRateData]) -> Option<i32> {
 
 #[tracing::instrument(
     name = "Get user workout detail",
-    skip(pool, claims),
+    skip(pool, claims, query),
     fields(username = %claims.username, workout_id = %workout_id)
 )]
 pub async fn get_workout_detail(
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
     workout_id: web::Path<Uuid>,
+    query: web::Query<WorkoutDetailQuery>,
 ) -> HttpResponse {
     let workout_id = workout_id.into_inner();
+    let resolution = match query.resolution.as_deref().map(HrResolution::parse) {
+        None => HrResolution::Raw,
+        Some(Some(resolution)) => resolution,
+        Some(None) => {
+            return HttpResponse::BadRequest().json(json!({
+                "success": false,
+                "error": "resolution must be one of raw, 10s or 60s"
+            }));
+        }
+    };
 
     // Fetch specific workout with all stats from workout_data and post info
     let workout = match sqlx::query!(
@@ -87,6 +126,9 @@ pub async fn get_workout_detail(
             wd.max_heart_rate,
             wd.heart_rate_data,
             wd.heart_rate_zones,
+            wd.recalculated_heart_rate_zones,
+            wd.zones_recalculated_at,
+            wd.intervals,
             COALESCE(wd.stamina_gained, 0.0) as stamina_gained,
             COALESCE(wd.strength_gained, 0.0) as strength_gained,
             p.id as "post_id?",
@@ -96,7 +138,9 @@ pub async fn get_workout_detail(
             p.created_at as "post_created_at?",
             COALESCE(p.updated_at, p.created_at) as "post_updated_at?",
             COALESCE(p.edited_at, p.created_at) as "post_edited_at?",
-            p.media_urls as "post_media_urls?"
+            p.media_urls as "post_media_urls?",
+            wd.notes,
+            wd.tags
         FROM workout_data wd
         LEFT JOIN posts p ON p.workout_id = wd.id
         WHERE wd.id = $1
@@ -124,13 +168,18 @@ pub async fn get_workout_detail(
                 )
             };
 
-            // Parse heart rate data from JSON
+            // Parse heart rate data from JSON, downsampled to the requested resolution
             let heart_rate_data = if !row.heart_rate_data.is_null() {
-                serde_json::from_value(row.heart_rate_data.clone()).ok()
+                serde_json::from_value::<Vec<HeartRateData>>(row.heart_rate_data.clone())
+                    .ok()
+                    .map(|samples| downsample(&samples, resolution))
             } else {
                 None
             };
 
+            let show_notes = claims.user_id() == Some(row.user_id)
+                || row.post_visibility.as_deref() == Some("public");
+
             WorkoutDetail {
                 id: row.id,
                 user_id: row.user_id,
@@ -143,7 +192,11 @@ pub async fn get_workout_detail(
                 avg_heart_rate,
                 max_heart_rate,
                 heart_rate_zones: row.heart_rate_zones,
+                recalculated_heart_rate_zones: row.recalculated_heart_rate_zones,
+                zones_recalculated_at: row.zones_recalculated_at,
                 heart_rate_data,
+                heart_rate_resolution: resolution,
+                intervals: row.intervals.and_then(|intervals| serde_json::from_value(intervals).ok()),
                 stamina_gained: row.stamina_gained,
                 strength_gained: row.strength_gained,
                 // Post information
@@ -155,6 +208,8 @@ pub async fn get_workout_detail(
                 post_updated_at: row.post_updated_at,
                 post_edited_at: row.post_edited_at,
                 post_media_urls: row.post_media_urls,
+                notes: row.notes.filter(|_| show_notes),
+                tags: if show_notes { row.tags } else { Vec::new() },
             }
         }
         Ok(None) => {
@@ -177,3 +232,71 @@ pub async fn get_workout_detail(
         "data": workout
     }))
 }
+
+/// Set the notes and/or tags of one of the user's own workouts
+#[tracing::instrument(
+    name = "Update workout notes",
+    skip(pool, claims, request),
+    fields(username = %claims.username, workout_id = %workout_id)
+)]
+pub async fn update_workout_notes(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<Uuid>,
+    request: web::Json<UpdateWorkoutNotesRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+    let workout_id = workout_id.into_inner();
+
+    if let Err(message) = request.validate() {
+        return HttpResponse::BadRequest().json(json!({
+            "success": false,
+            "error": message
+        }));
+    }
+
+    let request = request.into_inner();
+    // Blank notes clear them
+    let notes = request.notes.as_ref().map(|notes| notes.trim());
+    let tags = request.tags.as_deref().map(normalize_tags);
+
+    match sqlx::query_as!(
+        WorkoutNotes,
+        r#"
+        UPDATE workout_data
+        SET notes = CASE WHEN $3::text IS NULL THEN notes ELSE NULLIF($3, '') END,
+            tags = COALESCE($4, tags),
+            updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, notes, tags
+        "#,
+        workout_id,
+        user_id,
+        notes,
+        tags.as_deref()
+    )
+    .fetch_optional(&**pool)
+    .await
+    {
+        Ok(Some(workout)) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": workout
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "success": false,
+            "error": "Workout not found"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to update notes of workout {}: {}", workout_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "success": false,
+                "error": "Database error"
+            }))
+        }
+    }
+}
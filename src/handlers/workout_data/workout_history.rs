@@ -17,6 +17,8 @@ pub struct WorkoutHistoryItem {
     pub calories_burned: Option<i32>,
     pub activity_name: Option<String>,
     pub user_activity: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
     pub heart_rate_zones: Option<serde_json::Value>,
@@ -44,6 +46,8 @@ pub struct WorkoutHistoryQuery {
     pub offset: Option<i32>,
     pub include_heart_rate_data: Option<bool>,
     pub user_id: Option<String>,
+    pub tag: Option<String>,    // Only workouts with this tag
+    pub search: Option<String>, // Matches notes, tags and activity
 }
 
 fn calculate_duration_minutes(start: DateTime<Utc>, end: DateTime<Utc>) -> Option<i32> {
@@ -104,6 +108,13 @@ pub async fn get_workout_history(
 
     let limit = query.limit.unwrap_or(20).min(100); // Max 100 items
     let offset = query.offset.unwrap_or(0);
+    let tag = query.tag.as_ref().map(|tag| tag.trim().trim_start_matches('#').to_lowercase());
+    // Notes are private unless the workout's post is public
+    let is_own_history = claims.user_id() == Some(user_id);
+    let search = query.search.as_deref()
+        .map(str::trim)
+        .filter(|search| !search.is_empty())
+        .map(|search| format!("%{}%", search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")));
 
     // Fetch workout history as posts that wrap workouts
     let workouts: Vec<WorkoutHistoryItem> = match sqlx::query!(
@@ -118,6 +129,8 @@ pub async fn get_workout_history(
             wd.duration_minutes,
             wd.activity_name,
             wd.user_activity,
+            wd.notes,
+            wd.tags,
             wd.avg_heart_rate,
             wd.max_heart_rate,
             wd.heart_rate_data,
@@ -138,12 +151,20 @@ pub async fn get_workout_history(
         LEFT JOIN workout_scoring_feedback wsf ON wsf.workout_data_id = wd.id AND wsf.user_id = wd.user_id
         WHERE wd.user_id = $1
         AND (wd.calories_burned > 100 OR wd.heart_rate_data IS NOT NULL)
+        AND ($4::text IS NULL OR (($6 OR p.visibility = 'public') AND $4 = ANY(wd.tags)))
+        AND ($5::text IS NULL
+            OR COALESCE(wd.user_activity, wd.activity_name) ILIKE $5
+            OR (($6 OR p.visibility = 'public')
+                AND (wd.notes ILIKE $5 OR EXISTS (SELECT 1 FROM unnest(wd.tags) t WHERE t ILIKE $5))))
         ORDER BY COALESCE(wd.workout_start, wd.created_at) DESC
         LIMIT $2 OFFSET $3
         "#,
         user_id,
         limit as i64,
-        offset as i64
+        offset as i64,
+        tag,
+        search,
+        is_own_history
     )
     .fetch_all(&**pool)
     .await
@@ -178,6 +199,8 @@ pub async fn get_workout_history(
                     None
                 };
 
+                let show_notes = is_own_history || row.post_visibility.as_deref() == Some("public");
+
                 WorkoutHistoryItem {
                     id: row.id,
                     workout_date: row.workout_date.unwrap_or(row.created_at),
@@ -187,6 +210,8 @@ pub async fn get_workout_history(
                     calories_burned: row.calories_burned,
                     activity_name: row.activity_name,
                     user_activity: row.user_activity,
+                    notes: row.notes.filter(|_| show_notes),
+                    tags: if show_notes { row.tags } else { Vec::new() },
                     avg_heart_rate,
                     max_heart_rate,
                     heart_rate_zones: row.heart_rate_zones,
@@ -220,11 +245,20 @@ pub async fn get_workout_history(
     let total_count = match sqlx::query!(
         r#"
         SELECT COUNT(*) as count
-        FROM workout_data
-        WHERE user_id = $1
-        AND (calories_burned > 100 OR heart_rate_data IS NOT NULL)
+        FROM workout_data wd
+        LEFT JOIN posts p ON p.workout_id = wd.id AND p.user_id = wd.user_id
+        WHERE wd.user_id = $1
+        AND (wd.calories_burned > 100 OR wd.heart_rate_data IS NOT NULL)
+        AND ($2::text IS NULL OR (($4 OR p.visibility = 'public') AND $2 = ANY(wd.tags)))
+        AND ($3::text IS NULL
+            OR COALESCE(wd.user_activity, wd.activity_name) ILIKE $3
+            OR (($4 OR p.visibility = 'public')
+                AND (wd.notes ILIKE $3 OR EXISTS (SELECT 1 FROM unnest(wd.tags) t WHERE t ILIKE $3))))
         "#,
-        user_id
+        user_id,
+        tag,
+        search,
+        is_own_history
     )
     .fetch_one(&**pool)
     .await
@@ -3,6 +3,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use std::sync::Arc;
 
+use crate::db::workout_repo::{WorkoutRepo, WorkoutRepository};
 use crate::models::workout_data::{SubmitWorkoutReportRequest, UpdateWorkoutReportRequest, WorkoutReport};
 use crate::middleware::auth::Claims;
 use crate::handlers::notification_handler::send_notification_to_user;
@@ -31,32 +32,18 @@ pub async fn submit_workout_report(
     }
 
     // Get the workout and verify it exists
-    let workout = sqlx::query!(
-        r#"
-        SELECT id, user_id
-        FROM workout_data
-        WHERE id = $1
-        "#,
-        workout_id
-    )
-    .fetch_optional(pool.as_ref())
-    .await
+    let workout_owner_id = WorkoutRepo::new(pool.get_ref().clone()).find_owner(workout_id).await
     .map_err(|e| {
         tracing::error!("Database error checking workout: {}", e);
         actix_web::error::ErrorInternalServerError("Failed to verify workout")
     })?;
 
-    let workout = match workout {
-        Some(w) => w,
-        None => {
-            return Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Workout not found"
-            })));
-        }
+    let Some(workout_owner_id) = workout_owner_id else {
+        return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Workout not found"
+        })));
     };
 
-    let workout_owner_id = workout.user_id;
-
     // Check if this workout has already been reported
     let existing_report = sqlx::query!(
         r#"
@@ -0,0 +1,56 @@
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::middleware::auth::Claims;
+use crate::services::ZoneRecalculationService;
+
+#[derive(Debug, Deserialize)]
+pub struct RecalculateZonesRequest {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Opt-in recalculation of past workouts' zone distributions with the current max HR and
+/// VT thresholds. Game scores settled from these workouts are not changed.
+#[tracing::instrument(
+    name = "Recalculate workout zones",
+    skip(pool, claims, request),
+    fields(username = %claims.username)
+)]
+pub async fn recalculate_workout_zones(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<RecalculateZonesRequest>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        tracing::error!("Invalid user ID in claims");
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid user ID"
+        }));
+    };
+
+    if let (Some(from), Some(to)) = (request.from, request.to) {
+        if from > to {
+            return HttpResponse::BadRequest().json(json!({
+                "error": "from must not be after to"
+            }));
+        }
+    }
+
+    let service = ZoneRecalculationService::new(pool.get_ref().clone());
+    match service.recalculate_for_user(user_id, request.from, request.to).await {
+        Ok(summary) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "data": summary
+        })),
+        Err(e) => {
+            tracing::error!("Failed to recalculate zones for user {}: {}", user_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to recalculate workout zones"
+            }))
+        }
+    }
+}
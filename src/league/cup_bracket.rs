@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// A game of a cup round; `slot` is its place in the round's bracket, counted from the top
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CupPairing {
+    pub slot: usize,
+    pub home_team_id: Uuid,
+    pub away_team_id: Uuid,
+}
+
+/// Seeds (1-based) in bracket order, so the top seeds can only meet in the late rounds:
+/// 1 v 8, 4 v 5, 2 v 7, 3 v 6 for 8 places. `size` must be a power of two.
+pub fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let places = order.len() * 2;
+        order = order.iter().flat_map(|&seed| [seed, places + 1 - seed]).collect();
+    }
+    order
+}
+
+/// Number of rounds of a cup for `team_count` teams
+pub fn round_count(team_count: usize) -> u32 {
+    team_count.next_power_of_two().trailing_zeros()
+}
+
+/// Entrants of the first round in bracket order, from teams in seed order. Places beyond the
+/// number of teams are byes, which fall to the top seeds.
+pub fn first_round_entrants(seeded_teams: &[Uuid]) -> Vec<Option<Uuid>> {
+    bracket_order(seeded_teams.len().next_power_of_two())
+        .into_iter()
+        .map(|seed| seeded_teams.get(seed - 1).copied())
+        .collect()
+}
+
+/// Games of a round between neighbouring entrants; an entrant without an opponent has a bye
+pub fn round_pairings(entrants: &[Option<Uuid>]) -> Vec<CupPairing> {
+    entrants
+        .chunks(2)
+        .enumerate()
+        .filter_map(|(slot, pair)| match pair {
+            [Some(home_team_id), Some(away_team_id)] => Some(CupPairing {
+                slot,
+                home_team_id: *home_team_id,
+                away_team_id: *away_team_id,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Entrants of the next round, in bracket order, from the winners of the round's games by slot
+/// (None for a draw). Byes advance without playing and draws go to the higher seed. None while
+/// a game of the round has no result yet.
+pub fn advancing(
+    entrants: &[Option<Uuid>],
+    winners: &HashMap<usize, Option<Uuid>>,
+    seeds: &HashMap<Uuid, i32>,
+) -> Option<Vec<Option<Uuid>>> {
+    let seed = |team_id: &Uuid| seeds.get(team_id).copied().unwrap_or(i32::MAX);
+    entrants
+        .chunks(2)
+        .enumerate()
+        .map(|(slot, pair)| match pair {
+            [Some(home_team_id), Some(away_team_id)] => {
+                let winner = (*winners.get(&slot)?).unwrap_or_else(|| {
+                    if seed(away_team_id) < seed(home_team_id) { *away_team_id } else { *home_team_id }
+                });
+                Some(Some(winner))
+            }
+            [Some(team_id), None] | [None, Some(team_id)] => Some(Some(*team_id)),
+            _ => Some(None),
+        })
+        .collect()
+}
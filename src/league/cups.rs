@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::league::cup_bracket::{advancing, first_round_entrants, round_count, round_pairings, CupPairing};
+use crate::models::cup::{CalendarGame, CreateCupRequest, CupBracket, CupGame, CupRound, CupTeam, SeasonCup};
+
+#[derive(Debug)]
+pub enum CupError {
+    SeasonNotFound,
+    NotFound,
+    /// Entered teams that don't play in the season, or too few of them
+    InvalidTeams(String),
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for CupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SeasonNotFound => write!(f, "Season not found"),
+            Self::NotFound => write!(f, "Cup not found"),
+            Self::InvalidTeams(message) => write!(f, "{message}"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for CupError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// Knockout cups run alongside a season's league. Cup games are ordinary games marked with
+/// their cup, so the live game engine plays them; each round is drawn once the previous one
+/// is evaluated.
+pub struct CupService {
+    pool: PgPool,
+}
+
+impl CupService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a cup in the season and draw its first round
+    pub async fn create_cup(&self, season_id: Uuid, request: &CreateCupRequest) -> Result<SeasonCup, CupError> {
+        let mut tx = self.pool.begin().await?;
+        let game_duration_seconds = sqlx::query_scalar!(
+            "SELECT game_duration_seconds FROM league_seasons WHERE id = $1",
+            season_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(CupError::SeasonNotFound)?;
+
+        // The season's teams, strongest in the standings first
+        let season_teams = sqlx::query_scalar!(
+            r#"
+            SELECT lt.team_id
+            FROM league_teams lt
+            JOIN teams t ON t.id = lt.team_id
+            LEFT JOIN league_standings s ON s.season_id = lt.season_id AND s.team_id = lt.team_id
+            WHERE lt.season_id = $1
+            ORDER BY s.position NULLS LAST, t.team_name
+            "#,
+            season_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let seeded_teams = match &request.team_ids {
+            Some(team_ids) => {
+                if let Some(team_id) = team_ids.iter().find(|team_id| !season_teams.contains(team_id)) {
+                    return Err(CupError::InvalidTeams(format!("Team {team_id} doesn't play in this season")));
+                }
+                team_ids.clone()
+            }
+            None => season_teams,
+        };
+        if seeded_teams.len() < 2 {
+            return Err(CupError::InvalidTeams("A cup needs at least 2 teams".to_string()));
+        }
+
+        let cup = sqlx::query_as!(
+            SeasonCup,
+            r#"
+            INSERT INTO season_cups (season_id, name, start_date, round_interval_hours)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, season_id, name, start_date, round_interval_hours, status, winner_team_id, created_at, finished_at
+            "#,
+            season_id,
+            request.name.trim(),
+            request.start_date,
+            request.round_interval_hours()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (index, team_id) in seeded_teams.iter().enumerate() {
+            sqlx::query!(
+                "INSERT INTO season_cup_teams (cup_id, team_id, seed) VALUES ($1, $2, $3)",
+                cup.id,
+                team_id,
+                index as i32 + 1
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let pairings = round_pairings(&first_round_entrants(&seeded_teams));
+        insert_round(&mut tx, &cup, 1, &pairings, game_duration_seconds).await?;
+
+        tx.commit().await?;
+        tracing::info!("🏆 Created cup {} '{}' in season {} with {} teams", cup.id, cup.name, season_id, seeded_teams.len());
+        Ok(cup)
+    }
+
+    /// Draw the next round once every game of the latest one is evaluated, or crown the winner
+    /// after the final. Returns whether the cup moved on.
+    pub async fn advance(&self, cup_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let Some(cup) = sqlx::query_as!(
+            SeasonCup,
+            r#"
+            SELECT id, season_id, name, start_date, round_interval_hours, status, winner_team_id, created_at, finished_at
+            FROM season_cups
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            cup_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+        if cup.status != "active" {
+            return Ok(false);
+        }
+
+        let seeds: HashMap<Uuid, i32> = sqlx::query!("SELECT team_id, seed FROM season_cup_teams WHERE cup_id = $1 ORDER BY seed", cup_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .map(|row| (row.team_id, row.seed))
+            .collect();
+        let mut seeded_teams: Vec<Uuid> = seeds.keys().copied().collect();
+        seeded_teams.sort_by_key(|team_id| seeds[team_id]);
+
+        let games = sqlx::query!(
+            r#"
+            SELECT cup_round AS "cup_round!", cup_slot AS "cup_slot!", status, winner_team_id
+            FROM games
+            WHERE cup_id = $1
+            "#,
+            cup_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let latest_round = games.iter().map(|game| game.cup_round).max().unwrap_or(0);
+
+        // Replay the bracket up to the latest round drawn
+        let mut entrants = first_round_entrants(&seeded_teams);
+        for round in 1..=latest_round {
+            let winners: HashMap<usize, Option<Uuid>> = games
+                .iter()
+                .filter(|game| game.cup_round == round && game.status == "evaluated")
+                .map(|game| (game.cup_slot as usize, game.winner_team_id))
+                .collect();
+            match advancing(&entrants, &winners, &seeds) {
+                Some(next) => entrants = next,
+                None => return Ok(false),
+            }
+        }
+
+        if let [Some(winner_team_id)] = entrants[..] {
+            sqlx::query!(
+                "UPDATE season_cups SET status = 'finished', winner_team_id = $2, finished_at = NOW() WHERE id = $1",
+                cup_id,
+                winner_team_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            tracing::info!("🏆 Cup {} '{}' won by team {}", cup_id, cup.name, winner_team_id);
+            return Ok(true);
+        }
+
+        let game_duration_seconds = sqlx::query_scalar!(
+            "SELECT game_duration_seconds FROM league_seasons WHERE id = $1",
+            cup.season_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_round(&mut tx, &cup, latest_round + 1, &round_pairings(&entrants), game_duration_seconds).await?;
+        tx.commit().await?;
+        tracing::info!("🏆 Drew round {} of cup {} '{}'", latest_round + 1, cup_id, cup.name);
+        Ok(true)
+    }
+
+    /// Cups of the season, oldest first
+    pub async fn list_cups(&self, season_id: Uuid) -> Result<Vec<SeasonCup>, sqlx::Error> {
+        sqlx::query_as!(
+            SeasonCup,
+            r#"
+            SELECT id, season_id, name, start_date, round_interval_hours, status, winner_team_id, created_at, finished_at
+            FROM season_cups
+            WHERE season_id = $1
+            ORDER BY created_at
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// The cup with its seeded teams and the games of every round drawn so far
+    pub async fn get_bracket(&self, cup_id: Uuid) -> Result<CupBracket, CupError> {
+        let cup = sqlx::query_as!(
+            SeasonCup,
+            r#"
+            SELECT id, season_id, name, start_date, round_interval_hours, status, winner_team_id, created_at, finished_at
+            FROM season_cups
+            WHERE id = $1
+            "#,
+            cup_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(CupError::NotFound)?;
+
+        let teams = sqlx::query_as!(
+            CupTeam,
+            r#"
+            SELECT ct.team_id, t.team_name, ct.seed
+            FROM season_cup_teams ct
+            JOIN teams t ON t.id = ct.team_id
+            WHERE ct.cup_id = $1
+            ORDER BY ct.seed
+            "#,
+            cup_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let games = sqlx::query!(
+            r#"
+            SELECT g.id, g.cup_round AS "cup_round!", g.cup_slot AS "cup_slot!",
+                   g.home_team_id, ht.team_name AS home_team_name, g.away_team_id, at.team_name AS away_team_name,
+                   g.status, g.home_score, g.away_score, g.winner_team_id, g.game_start_time, g.game_end_time
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE g.cup_id = $1
+            ORDER BY g.cup_round, g.cup_slot
+            "#,
+            cup_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut rounds: Vec<CupRound> = Vec::new();
+        for game in games {
+            if rounds.last().is_none_or(|round| round.round != game.cup_round) {
+                rounds.push(CupRound { round: game.cup_round, games: Vec::new() });
+            }
+            if let Some(round) = rounds.last_mut() {
+                round.games.push(CupGame {
+                    game_id: game.id,
+                    slot: game.cup_slot,
+                    home_team_id: game.home_team_id,
+                    home_team_name: game.home_team_name,
+                    away_team_id: game.away_team_id,
+                    away_team_name: game.away_team_name,
+                    status: game.status,
+                    home_score: game.home_score,
+                    away_score: game.away_score,
+                    winner_team_id: game.winner_team_id,
+                    game_start_time: game.game_start_time,
+                    game_end_time: game.game_end_time,
+                });
+            }
+        }
+
+        Ok(CupBracket {
+            total_rounds: round_count(teams.len()) as i32,
+            cup,
+            teams,
+            rounds,
+        })
+    }
+
+    /// League and cup games of the season in kickoff order
+    pub async fn season_calendar(&self, season_id: Uuid) -> Result<Vec<CalendarGame>, CupError> {
+        let season_exists = sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM league_seasons WHERE id = $1) AS \"exists!\"", season_id)
+            .fetch_one(&self.pool)
+            .await?;
+        if !season_exists {
+            return Err(CupError::SeasonNotFound);
+        }
+
+        let games = sqlx::query_as!(
+            CalendarGame,
+            r#"
+            SELECT g.id AS game_id,
+                   CASE WHEN g.cup_id IS NULL THEN 'league' ELSE 'cup' END AS "competition!",
+                   g.cup_id, c.name AS "cup_name?",
+                   COALESCE(g.cup_round, g.week_number) AS "round!",
+                   g.home_team_id, ht.team_name AS home_team_name, g.away_team_id, at.team_name AS away_team_name,
+                   g.status, g.home_score, g.away_score, g.winner_team_id, g.game_start_time, g.game_end_time
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            LEFT JOIN season_cups c ON c.id = g.cup_id
+            WHERE g.season_id = $1
+            ORDER BY g.game_start_time NULLS LAST, g.cup_id NULLS FIRST, g.week_number, g.cup_slot
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(games)
+    }
+}
+
+/// Kickoff of a cup round: on the cup's rhythm, but never in the past, as a round can only be
+/// drawn once the previous one is evaluated
+fn round_start(cup: &SeasonCup, round: i32, now: DateTime<Utc>) -> DateTime<Utc> {
+    let planned = cup.start_date + Duration::hours(cup.round_interval_hours as i64 * (round as i64 - 1));
+    if round == 1 { planned } else { planned.max(now) }
+}
+
+async fn insert_round(
+    conn: &mut PgConnection,
+    cup: &SeasonCup,
+    round: i32,
+    pairings: &[CupPairing],
+    game_duration_seconds: i64,
+) -> Result<(), sqlx::Error> {
+    let game_start_time = round_start(cup, round, Utc::now());
+    let game_end_time = game_start_time + Duration::seconds(game_duration_seconds);
+    for pairing in pairings {
+        sqlx::query!(
+            r#"
+            INSERT INTO games (
+                season_id, home_team_id, away_team_id, week_number, status,
+                game_start_time, game_end_time, cup_id, cup_round, cup_slot
+            )
+            VALUES ($1, $2, $3, $4, 'scheduled', $5, $6, $7, $4, $8)
+            "#,
+            cup.season_id,
+            pairing.home_team_id,
+            pairing.away_team_id,
+            round,
+            game_start_time,
+            game_end_time,
+            cup.id,
+            pairing.slot as i32
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use crate::models::league::ScheduleFairness;
+
+/// A game as (home team index, away team index)
+pub type Pairing = (usize, usize);
+
+/// Fixture generator parameters, configured per league
+#[derive(Debug, Clone, Copy)]
+pub struct FixtureSettings {
+    pub balance_home_away: bool,
+    pub spread_strong_teams: bool,
+}
+
+/// Build all rounds of a round-robin schedule for `ratings.len()` teams.
+/// The second leg (games_per_matchup = 2) replays the first leg's rounds with home and away swapped.
+pub fn build_rounds(ratings: &[f64], games_per_matchup: i32, settings: FixtureSettings) -> Vec<Vec<Pairing>> {
+    let mut first_leg = circle_rounds(ratings.len());
+    let legs = if games_per_matchup == 2 { 2 } else { 1 };
+
+    if settings.spread_strong_teams {
+        first_leg = spread_strong_teams(first_leg, ratings, legs);
+    }
+    if settings.balance_home_away {
+        first_leg = balance_home_away(first_leg, ratings.len());
+    }
+
+    let mut rounds = first_leg.clone();
+    if legs == 2 {
+        rounds.extend(
+            first_leg.iter().map(|round| round.iter().map(|&(home, away)| (away, home)).collect::<Vec<_>>())
+        );
+    }
+    rounds
+}
+
+/// Circle method: team 0 stays fixed while the others rotate, giving N-1 conflict-free rounds
+fn circle_rounds(team_count: usize) -> Vec<Vec<Pairing>> {
+    let mut teams: Vec<usize> = (0..team_count).collect();
+    let mut rounds = Vec::with_capacity(team_count.saturating_sub(1));
+
+    for _ in 0..team_count.saturating_sub(1) {
+        let round = (0..team_count / 2)
+            .map(|i| (teams[i], teams[team_count - 1 - i]))
+            .collect();
+        rounds.push(round);
+
+        let last = teams.pop().unwrap();
+        teams.insert(1, last);
+    }
+    rounds
+}
+
+/// Reorder rounds so no team faces strong opponents week after week.
+/// Swaps pairs of rounds while that lowers the summed strength of consecutive opponents.
+fn spread_strong_teams(mut rounds: Vec<Vec<Pairing>>, ratings: &[f64], legs: usize) -> Vec<Vec<Pairing>> {
+    let strengths = normalized_strengths(ratings);
+    if strengths.iter().all(|&s| s == 0.0) {
+        return rounds; // Nothing to spread when all teams are rated the same
+    }
+
+    let mut cost = clustering_cost(&rounds, &strengths, legs);
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for i in 0..rounds.len() {
+            for j in (i + 1)..rounds.len() {
+                rounds.swap(i, j);
+                let swapped_cost = clustering_cost(&rounds, &strengths, legs);
+                rounds.swap(i, j);
+                if swapped_cost < best.map_or(cost, |(_, _, c)| c) - 1e-9 {
+                    best = Some((i, j, swapped_cost));
+                }
+            }
+        }
+        match best {
+            Some((i, j, swapped_cost)) => {
+                rounds.swap(i, j);
+                cost = swapped_cost;
+            }
+            None => return rounds,
+        }
+    }
+}
+
+/// Ratings scaled to 0 (weakest) ..= 1 (strongest); all 0 when the ratings are equal
+fn normalized_strengths(ratings: &[f64]) -> Vec<f64> {
+    let min = ratings.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ratings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max - min <= f64::EPSILON {
+        return vec![0.0; ratings.len()];
+    }
+    ratings.iter().map(|r| (r - min) / (max - min)).collect()
+}
+
+fn clustering_cost(rounds: &[Vec<Pairing>], strengths: &[f64], legs: usize) -> f64 {
+    let opponents = opponent_sequences(rounds, strengths.len(), legs);
+    opponents
+        .iter()
+        .map(|sequence| sequence.windows(2).map(|w| strengths[w[0]] * strengths[w[1]]).sum::<f64>())
+        .sum()
+}
+
+/// Opponent of every team in every round, with the rounds repeated once per leg
+fn opponent_sequences(rounds: &[Vec<Pairing>], team_count: usize, legs: usize) -> Vec<Vec<usize>> {
+    let mut opponents = vec![Vec::with_capacity(rounds.len() * legs); team_count];
+    for _ in 0..legs {
+        for round in rounds {
+            for &(home, away) in round {
+                opponents[home].push(away);
+                opponents[away].push(home);
+            }
+        }
+    }
+    opponents
+}
+
+/// Orient games so every team alternates home and away and ends up with as many home as away games.
+/// Each game goes home to the team with fewer home games so far, then to the team that was away last round.
+/// A final pass flips games while that evens out a team that is still more than one game off.
+fn balance_home_away(rounds: Vec<Vec<Pairing>>, team_count: usize) -> Vec<Vec<Pairing>> {
+    let mut balance = vec![0i32; team_count]; // home games - away games
+    let mut last_home: Vec<Option<bool>> = vec![None; team_count];
+
+    let mut rounds: Vec<Vec<Pairing>> = rounds
+        .into_iter()
+        .map(|round| {
+            round
+                .into_iter()
+                .map(|(a, b)| {
+                    let (home, away) = if balance[a] != balance[b] {
+                        if balance[a] < balance[b] { (a, b) } else { (b, a) }
+                    } else if last_home[a] == Some(true) && last_home[b] != Some(true) {
+                        (b, a)
+                    } else {
+                        (a, b)
+                    };
+                    balance[home] += 1;
+                    balance[away] -= 1;
+                    last_home[home] = Some(true);
+                    last_home[away] = Some(false);
+                    (home, away)
+                })
+                .collect()
+        })
+        .collect();
+
+    // Each flip lowers the sum of squared balances, so this terminates
+    'repair: loop {
+        for round in rounds.iter_mut() {
+            for game in round.iter_mut() {
+                let (home, away) = *game;
+                if balance[home] - balance[away] > 2 {
+                    *game = (away, home);
+                    balance[home] -= 2;
+                    balance[away] += 2;
+                    continue 'repair;
+                }
+            }
+        }
+        return rounds;
+    }
+}
+
+/// Fairness metrics of a complete schedule
+pub fn fairness(rounds: &[Vec<Pairing>], ratings: &[f64], rated_teams: usize, settings: FixtureSettings) -> ScheduleFairness {
+    let team_count = ratings.len();
+    let mut venues: Vec<Vec<bool>> = vec![Vec::with_capacity(rounds.len()); team_count]; // true = home
+    for round in rounds {
+        for &(home, away) in round {
+            venues[home].push(true);
+            venues[away].push(false);
+        }
+    }
+
+    let max_home_away_imbalance = venues
+        .iter()
+        .map(|v| {
+            let home = v.iter().filter(|&&is_home| is_home).count() as i32;
+            (2 * home - v.len() as i32).abs()
+        })
+        .max()
+        .unwrap_or(0);
+    let total_breaks = venues
+        .iter()
+        .map(|v| v.windows(2).filter(|w| w[0] == w[1]).count() as i32)
+        .sum();
+
+    // Strong teams are rated above the median; with equal ratings there are none
+    let mut sorted = ratings.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = if sorted.is_empty() {
+        0.0
+    } else {
+        (sorted[(team_count - 1) / 2] + sorted[team_count / 2]) / 2.0
+    };
+    let strong: Vec<bool> = ratings.iter().map(|&r| r > median + 1e-9).collect();
+
+    let mut strong_opponent_back_to_backs = 0;
+    let mut max_strong_opponent_streak = 0;
+    for sequence in opponent_sequences(rounds, team_count, 1) {
+        let mut streak = 0;
+        for opponent in sequence {
+            if strong[opponent] {
+                streak += 1;
+                if streak > 1 {
+                    strong_opponent_back_to_backs += 1;
+                }
+                max_strong_opponent_streak = max_strong_opponent_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+    }
+
+    ScheduleFairness {
+        balance_home_away: settings.balance_home_away,
+        spread_strong_teams: settings.spread_strong_teams,
+        rated_teams: rated_teams as i32,
+        max_home_away_imbalance,
+        total_breaks,
+        strong_opponent_back_to_backs,
+        max_strong_opponent_streak,
+    }
+}
@@ -3,6 +3,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use crate::models::league::*;
 use crate::utils::team_power;
+use crate::db::game_queries::WORKOUT_UPLOAD_GRACE_MINUTES;
 
 /// Service responsible for individual game operations
 pub struct GameService {
@@ -47,7 +48,11 @@ impl GameService {
                     END,
                     updated_at = NOW()
                 WHERE id = $4
-                RETURNING *
+                RETURNING id, season_id, home_team_id, away_team_id,
+                    week_number, is_first_leg, status as "status: GameStatus",
+                    winner_team_id, created_at, updated_at,
+                    home_score, away_score, game_start_time, game_end_time,
+                    last_score_time, last_scorer_id, last_scorer_name, last_scorer_team
                 "#,
                 home_score,
                 away_score,
@@ -68,7 +73,11 @@ impl GameService {
                     winner_team_id = NULL,
                     updated_at = NOW()
                 WHERE id = $3
-                RETURNING *
+                RETURNING id, season_id, home_team_id, away_team_id,
+                    week_number, is_first_leg, status as "status: GameStatus",
+                    winner_team_id, created_at, updated_at,
+                    home_score, away_score, game_start_time, game_end_time,
+                    last_score_time, last_scorer_id, last_scorer_name, last_scorer_team
                 "#,
                 home_score,
                 away_score,
@@ -95,7 +104,14 @@ impl GameService {
     pub async fn get_game(&self, game_id: Uuid) -> Result<Option<LeagueGame>, sqlx::Error> {
         sqlx::query_as!(
             LeagueGame,
-            "SELECT * FROM games WHERE id = $1",
+            r#"
+            SELECT id, season_id, home_team_id, away_team_id,
+                week_number, is_first_leg, status as "status: GameStatus",
+                winner_team_id, created_at, updated_at,
+                home_score, away_score, game_start_time, game_end_time,
+                last_score_time, last_scorer_id, last_scorer_name, last_scorer_team
+            FROM games WHERE id = $1
+            "#,
             game_id
         )
         .fetch_optional(&self.pool)
@@ -168,6 +184,110 @@ impl GameService {
         }
     }
 
+    /// Database time and the next game start or end in the season. The game scheduler
+    /// compares against the database clock, so this is the time clients should count down by.
+    pub async fn get_game_clock(&self, season_id: Option<Uuid>) -> Result<GameClock, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT clock.now as "server_time!",
+                   t.game_id as "game_id?", t.week_number as "week_number?",
+                   t.kind as "kind?", t.at as "at?"
+            FROM (SELECT NOW() as now) clock
+            LEFT JOIN LATERAL (
+                SELECT id as game_id, week_number, 'game_start' as kind, game_start_time as at
+                FROM games
+                WHERE season_id = $1 AND status = 'scheduled' AND game_start_time IS NOT NULL
+                UNION ALL
+                SELECT id, week_number, 'game_end', game_end_time
+                FROM games
+                WHERE season_id = $1 AND status = 'in_progress' AND game_end_time IS NOT NULL
+                ORDER BY at, game_id
+                LIMIT 1
+            ) t ON true
+            "#,
+            season_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let next_transition = match (row.game_id, row.week_number, row.kind, row.at) {
+            (Some(game_id), Some(week_number), Some(kind), Some(at)) => Some(GameTransition {
+                game_id,
+                week_number,
+                kind: if kind == "game_end" { GameTransitionKind::GameEnd } else { GameTransitionKind::GameStart },
+                at,
+            }),
+            _ => None,
+        };
+
+        Ok(GameClock {
+            server_time: row.server_time,
+            next_transition,
+        })
+    }
+
+    /// The window in which the user's workouts score for their team: the running game if
+    /// there is one, otherwise the next scheduled one, otherwise a finished game still
+    /// accepting late uploads
+    pub async fn get_next_window(&self, user_id: Uuid) -> Result<NextGameWindowResponse, sqlx::Error> {
+        let server_time = sqlx::query_scalar!(r#"SELECT NOW() as "now!""#)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT g.id, g.season_id, g.week_number, g.status as "status: GameStatus",
+                   g.game_start_time as "game_start_time!", g.game_end_time as "game_end_time!",
+                   tm.team_id, tm.joined_at, own.team_name,
+                   opp.id as opponent_team_id, opp.team_name as opponent_team_name
+            FROM team_members tm
+            JOIN games g ON tm.team_id IN (g.home_team_id, g.away_team_id)
+            JOIN teams own ON own.id = tm.team_id
+            JOIN teams opp ON opp.id = CASE WHEN g.home_team_id = tm.team_id THEN g.away_team_id ELSE g.home_team_id END
+            WHERE tm.user_id = $1 AND tm.status = 'active'
+            AND g.game_start_time IS NOT NULL AND g.game_end_time IS NOT NULL
+            AND g.status IN ('scheduled', 'in_progress', 'finished')
+            AND g.game_end_time > $3::timestamptz - make_interval(mins => $2)
+            ORDER BY (g.game_end_time <= $3::timestamptz), g.game_start_time, g.id
+            LIMIT 1
+            "#,
+            user_id,
+            WORKOUT_UPLOAD_GRACE_MINUTES,
+            server_time
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let window = row.map(|row| {
+            let record_from = row.game_start_time.max(row.joined_at);
+            let record_until = row.game_end_time;
+            let upload_until = record_until + chrono::Duration::minutes(WORKOUT_UPLOAD_GRACE_MINUTES as i64);
+            // A scheduled game past its start is only waiting for the scheduler to pick it up
+            let is_recording_open = record_from <= server_time && server_time < record_until;
+            let is_upload_open = server_time < upload_until && !matches!(row.status, GameStatus::Scheduled);
+            GameWindow {
+                game_id: row.id,
+                season_id: row.season_id,
+                week_number: row.week_number,
+                status: row.status,
+                team_id: row.team_id,
+                team_name: row.team_name,
+                opponent_team_id: row.opponent_team_id,
+                opponent_team_name: row.opponent_team_name,
+                record_from,
+                record_until,
+                upload_until,
+                is_recording_open,
+                is_upload_open,
+            }
+        });
+
+        Ok(NextGameWindowResponse {
+            server_time,
+            upload_grace_minutes: WORKOUT_UPLOAD_GRACE_MINUTES,
+            window,
+        })
+    }
 
     /// Get next upcoming game for a season
     pub async fn get_next_game(&self, season_id: Uuid) -> Result<Option<GameWithTeams>, sqlx::Error> {
@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+use secrecy::SecretString;
+
+use crate::league::team_kits::validate_hex_color;
+use crate::models::organization::{HistoricalImportRequest, ImportIssue};
+use crate::models::user::RegistrationRequest;
+use crate::utils::csv_reader::{parse_csv, CsvRecord};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedUser {
+    pub line: usize,
+    pub username: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTeam {
+    pub line: usize,
+    pub name: String,
+    /// Username of an imported or existing user
+    pub owner: String,
+    pub color: Option<String>,
+    /// Usernames of the other members
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedResult {
+    pub line: usize,
+    pub week: i32,
+    pub home_team: String,
+    pub away_team: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub played_on: NaiveDate,
+}
+
+/// Rows of an import that passed validation on their own and against each other. Conflicts
+/// with existing data are only known once applied to the database.
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub users: Vec<ImportedUser>,
+    pub teams: Vec<ImportedTeam>,
+    pub results: Vec<ImportedResult>,
+}
+
+fn issue(file: &str, line: usize, message: impl Into<String>) -> ImportIssue {
+    ImportIssue { file: file.to_string(), line, message: message.into() }
+}
+
+/// Parse and validate the CSVs of an import. Returns every issue found, not just the first.
+pub fn plan_import(request: &HistoricalImportRequest) -> Result<ImportPlan, Vec<ImportIssue>> {
+    let mut issues = Vec::new();
+    if request.league_name.trim().is_empty() {
+        issues.push(issue("request", 0, "league_name cannot be empty"));
+    }
+    if request.users_csv.is_none() && request.teams_csv.is_none() && request.results_csv.is_none() {
+        issues.push(issue("request", 0, "Provide at least one of users_csv, teams_csv or results_csv"));
+    }
+
+    let mut records = |file: &str, csv: &Option<String>| match csv.as_deref().map(parse_csv) {
+        None => Vec::new(),
+        Some(Ok(records)) => records,
+        Some(Err(message)) => {
+            issues.push(issue(file, 0, message));
+            Vec::new()
+        }
+    };
+    let (user_records, team_records, result_records) =
+        (records("users", &request.users_csv), records("teams", &request.teams_csv), records("results", &request.results_csv));
+
+    let users = plan_users(&user_records, &mut issues);
+    let teams = plan_teams(&team_records, &mut issues);
+    let results = plan_results(&result_records, &teams, &mut issues);
+
+    if issues.is_empty() {
+        Ok(ImportPlan { users, teams, results })
+    } else {
+        Err(issues)
+    }
+}
+
+fn plan_users(records: &[CsvRecord], issues: &mut Vec<ImportIssue>) -> Vec<ImportedUser> {
+    let (mut usernames, mut emails) = (HashSet::new(), HashSet::new());
+    let mut users = Vec::new();
+
+    for record in records {
+        let (Some(username), Some(email)) = (record.get("username"), record.get("email")) else {
+            issues.push(issue("users", record.line, "username and email are required"));
+            continue;
+        };
+        let registration = RegistrationRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            password: SecretString::from(""),
+            invite_code: None,
+            device_profile: None,
+        };
+        if let Err(message) = registration.validate() {
+            issues.push(issue("users", record.line, message));
+            continue;
+        }
+        if !usernames.insert(username.to_lowercase()) {
+            issues.push(issue("users", record.line, format!("Username {username} is listed twice")));
+            continue;
+        }
+        if !emails.insert(email.to_lowercase()) {
+            issues.push(issue("users", record.line, format!("Email {email} is listed twice")));
+            continue;
+        }
+        users.push(ImportedUser { line: record.line, username: username.to_string(), email: email.to_string() });
+    }
+    users
+}
+
+fn plan_teams(records: &[CsvRecord], issues: &mut Vec<ImportIssue>) -> Vec<ImportedTeam> {
+    let mut names = HashSet::new();
+    // Players can only be on one team
+    let mut players: HashMap<String, String> = HashMap::new();
+    let mut teams = Vec::new();
+
+    for record in records {
+        let (Some(name), Some(owner)) = (record.get("team_name"), record.get("owner")) else {
+            issues.push(issue("teams", record.line, "team_name and owner are required"));
+            continue;
+        };
+        if !(2..=50).contains(&name.chars().count()) {
+            issues.push(issue("teams", record.line, "Team name must be 2 to 50 characters"));
+            continue;
+        }
+        if !names.insert(name.to_lowercase()) {
+            issues.push(issue("teams", record.line, format!("Team {name} is listed twice")));
+            continue;
+        }
+        let color = record.get("color").map(str::to_string);
+        if let Some(Err(message)) = color.as_deref().map(validate_hex_color) {
+            issues.push(issue("teams", record.line, message));
+            continue;
+        }
+
+        let members: Vec<String> = record
+            .get("members")
+            .map(|members| members.split(';').map(str::trim).filter(|m| !m.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default();
+        let mut roster_ok = true;
+        for player in std::iter::once(owner).chain(members.iter().map(String::as_str)) {
+            if let Some(other_team) = players.insert(player.to_lowercase(), name.to_string()) {
+                let message = if other_team == name {
+                    format!("{player} is listed twice on the team")
+                } else {
+                    format!("{player} is already on team {other_team}")
+                };
+                issues.push(issue("teams", record.line, message));
+                roster_ok = false;
+            }
+        }
+        if roster_ok {
+            teams.push(ImportedTeam { line: record.line, name: name.to_string(), owner: owner.to_string(), color, members });
+        }
+    }
+    teams
+}
+
+fn plan_results(records: &[CsvRecord], teams: &[ImportedTeam], issues: &mut Vec<ImportIssue>) -> Vec<ImportedResult> {
+    let team_names: HashMap<String, &str> = teams.iter().map(|t| (t.name.to_lowercase(), t.name.as_str())).collect();
+    let mut results = Vec::new();
+
+    for record in records {
+        let line = record.line;
+        let number = |column: &str| record.get(column).and_then(|value| value.parse::<i32>().ok()).filter(|n| *n >= 0);
+        let (Some(week), Some(home_score), Some(away_score)) = (number("week"), number("home_score"), number("away_score")) else {
+            issues.push(issue("results", line, "week, home_score and away_score must be whole numbers, 0 or more"));
+            continue;
+        };
+        if week == 0 {
+            issues.push(issue("results", line, "Weeks start at 1"));
+            continue;
+        }
+        let Some(played_on) = record.get("played_on").and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) else {
+            issues.push(issue("results", line, "played_on must be a date like 2025-09-14"));
+            continue;
+        };
+
+        let team = |column: &str| record.get(column).and_then(|name| team_names.get(&name.to_lowercase()).copied());
+        let (Some(home_team), Some(away_team)) = (team("home_team"), team("away_team")) else {
+            issues.push(issue("results", line, "home_team and away_team must be teams of teams_csv"));
+            continue;
+        };
+        if home_team == away_team {
+            issues.push(issue("results", line, "A team can't play itself"));
+            continue;
+        }
+
+        results.push(ImportedResult {
+            line,
+            week,
+            home_team: home_team.to_string(),
+            away_team: away_team.to_string(),
+            home_score,
+            away_score,
+            played_on,
+        });
+    }
+    results
+}
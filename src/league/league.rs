@@ -59,36 +59,55 @@ impl LeagueService {
 
         match active_season {
             Some(season) => {
+                let clock = self.games.get_game_clock(Some(season.id)).await?;
                 let next_game = self.games.get_next_game(season.id).await?;
                 let games_this_week = self.games.get_games_this_week(season.id).await?;
                 let week_number = next_game.as_ref().map(|g| g.game.week_number);
                 
-                // Only calculate countdown if there's actually a next game
-                let countdown_seconds = if next_game.is_some() {
-                    Some(self.timing.seconds_until_next_game())
-                } else {
-                    None
-                };
+                // Count down to the game's actual start, on the server clock
+                let countdown_seconds = next_game
+                    .as_ref()
+                    .and_then(|g| g.game.game_start_time)
+                    .map(|start| (start - clock.server_time).num_seconds().max(0));
 
                 Ok(NextGameInfo {
                     next_game,
                     countdown_seconds,
                     week_number,
                     games_this_week,
+                    server_time: clock.server_time,
+                    next_transition: clock.next_transition,
                 })
             }
             None => {
                 // No active season - no countdown
+                let clock = self.games.get_game_clock(None).await?;
                 Ok(NextGameInfo {
                     next_game: None,
                     countdown_seconds: None,
                     week_number: None,
                     games_this_week: vec![],
+                    server_time: clock.server_time,
+                    next_transition: None,
                 })
             }
         }
     }
 
+    /// The window in which the user's workouts count for their current or next game
+    pub async fn get_next_game_window(&self, user_id: Uuid) -> Result<NextGameWindowResponse, sqlx::Error> {
+        self.games.get_next_window(user_id).await
+    }
+
+    /// Server time and next game transition for a season, defaulting to the active season
+    pub async fn get_game_clock(&self, season_id: Option<Uuid>) -> Result<GameClock, sqlx::Error> {
+        let season_id = match season_id {
+            Some(id) => Some(id),
+            None => self.seasons.get_active_season().await?.map(|season| season.id),
+        };
+        self.games.get_game_clock(season_id).await
+    }
+
     /// Get league standings
     pub async fn get_standings(&self, season_id: Uuid) -> Result<LeagueStandingsResponse, sqlx::Error> {
         self.standings.get_league_standings(season_id).await
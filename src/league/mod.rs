@@ -2,8 +2,15 @@
 pub mod league;
 pub mod timing;
 pub mod schedule;
+pub mod fixture_balancing;
 pub mod validation;
 pub mod games;
 pub mod standings;
 pub mod seasons;
-pub mod constants;
\ No newline at end of file
+pub mod constants;
+pub mod quests;
+pub mod team_alerts;
+pub mod team_kits;
+pub mod historical_import;
+pub mod result_card;pub mod cup_bracket;
+pub mod cups;
@@ -0,0 +1,52 @@
+/// Milestones a quest announces unless configured otherwise, in percent of the target
+pub const DEFAULT_MILESTONE_PERCENTS: [i32; 4] = [25, 50, 75, 100];
+
+/// Share of the target reached, in percent and capped at 100
+pub fn progress_percent(target_points: f64, progress_points: f64) -> f64 {
+    if target_points <= 0.0 {
+        return 0.0;
+    }
+    ((progress_points / target_points * 1000.0).round() / 10.0).min(100.0)
+}
+
+/// Milestones crossed when progress went from `before` to `after`, in ascending order
+pub fn crossed_milestones(milestone_percents: &[i32], target_points: f64, before: f64, after: f64) -> Vec<i32> {
+    let mut crossed: Vec<i32> = milestone_percents
+        .iter()
+        .copied()
+        .filter(|percent| {
+            let threshold = target_points * *percent as f64 / 100.0;
+            before < threshold && after >= threshold
+        })
+        .collect();
+    crossed.sort_unstable();
+    crossed.dedup();
+    crossed
+}
+
+/// Validate milestone percents: each between 1 and 100. Returns them sorted without duplicates.
+pub fn normalize_milestones(milestone_percents: &[i32]) -> Option<Vec<i32>> {
+    if milestone_percents.iter().any(|percent| !(1..=100).contains(percent)) {
+        return None;
+    }
+    let mut milestones = milestone_percents.to_vec();
+    milestones.sort_unstable();
+    milestones.dedup();
+    Some(milestones)
+}
+
+/// Feed line announcing a milestone
+pub fn milestone_message(quest_name: &str, percent: i32, progress_points: f64, target_points: f64, unit_label: &str) -> String {
+    if percent >= 100 {
+        format!(
+            "Quest complete! The league reached {} {unit_label} in \"{quest_name}\" together.",
+            target_points.round()
+        )
+    } else {
+        format!(
+            "\"{quest_name}\" is {percent}% done: {} of {} {unit_label}.",
+            progress_points.round(),
+            target_points.round()
+        )
+    }
+}
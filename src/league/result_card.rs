@@ -0,0 +1,87 @@
+use serde_json::{json, Value};
+
+use crate::models::league::ChatProvider;
+
+/// Final result of a game as posted to a league's chat channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultCard {
+    pub league_name: String,
+    pub week_number: i32,
+    pub home_team: String,
+    pub away_team: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    /// MVP name and score contribution, once the game summary is written
+    pub mvp: Option<(String, i32)>,
+}
+
+impl ResultCard {
+    /// "Lions 42 – 37 Tigers"
+    pub fn scoreline(&self) -> String {
+        format!("{} {} – {} {}", self.home_team, self.home_score, self.away_score, self.away_team)
+    }
+
+    pub fn outcome(&self) -> String {
+        match self.home_score.cmp(&self.away_score) {
+            std::cmp::Ordering::Greater => format!("{} win", self.home_team),
+            std::cmp::Ordering::Less => format!("{} win", self.away_team),
+            std::cmp::Ordering::Equal => "Draw".to_string(),
+        }
+    }
+
+    fn title(&self) -> String {
+        format!("{} · Week {}", self.league_name, self.week_number)
+    }
+
+    fn mvp_line(&self) -> Option<String> {
+        self.mvp.as_ref().map(|(name, points)| format!("{name} ({points} points)"))
+    }
+
+    /// Plain text version, used as notification fallback
+    pub fn text(&self) -> String {
+        let mut text = format!("{}: {} – {}", self.title(), self.scoreline(), self.outcome());
+        if let Some(mvp) = self.mvp_line() {
+            text.push_str(&format!(", MVP {mvp}"));
+        }
+        text
+    }
+
+    /// Webhook body in the format the provider expects
+    pub fn payload(&self, provider: ChatProvider) -> Value {
+        match provider {
+            ChatProvider::Slack => self.slack_payload(),
+            ChatProvider::Teams => self.teams_payload(),
+        }
+    }
+
+    /// Slack Block Kit message
+    fn slack_payload(&self) -> Value {
+        let mut fields = vec![json!({ "type": "mrkdwn", "text": format!("*Result*\n{}", self.outcome()) })];
+        if let Some(mvp) = self.mvp_line() {
+            fields.push(json!({ "type": "mrkdwn", "text": format!("*MVP*\n{mvp}") }));
+        }
+        json!({
+            "text": self.text(),
+            "blocks": [
+                { "type": "header", "text": { "type": "plain_text", "text": self.title() } },
+                { "type": "section", "text": { "type": "mrkdwn", "text": format!("*{}*", self.scoreline()) }, "fields": fields }
+            ]
+        })
+    }
+
+    /// Microsoft Teams message card
+    fn teams_payload(&self) -> Value {
+        let mut facts = vec![json!({ "name": "Result", "value": self.outcome() })];
+        if let Some(mvp) = self.mvp_line() {
+            facts.push(json!({ "name": "MVP", "value": mvp }));
+        }
+        json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": self.text(),
+            "themeColor": "4F46E5",
+            "title": self.title(),
+            "sections": [{ "activityTitle": self.scoreline(), "facts": facts }]
+        })
+    }
+}
@@ -3,7 +3,10 @@ use sqlx::{PgPool, FromRow};
 use uuid::Uuid;
 use crate::models::league::*;
 use crate::utils::team_power;
+use crate::db::fixture_flavor;
 use super::timing::TimingService;
+use super::fixture_balancing::{self, FixtureSettings};
+use chrono_tz::Tz;
 
 #[derive(Debug, FromRow)]
 struct GameQueryRow {
@@ -40,21 +43,30 @@ impl ScheduleService {
     /// Generate complete league schedule using round-robin algorithm
     /// Teams play each other once (single round-robin) or twice (double round-robin)
     /// N/2 games happen simultaneously each week
+    /// Home/away balancing and spreading of strong teams follow the league's schedule settings;
+    /// the fairness of the result is stored with the season and returned with the game count
     pub async fn generate_schedule(
         &self,
         season_id: Uuid,
         team_ids: &[Uuid],
         season_start_date: DateTime<Utc>,
-    ) -> Result<i32, sqlx::Error> {
+    ) -> Result<(i32, ScheduleFairness), sqlx::Error> {
         let team_count = team_ids.len();
         if team_count < 2 {
             tracing::error!("Cannot create schedule with less than 2 teams");
             return Err(sqlx::Error::RowNotFound);
         }
 
-        // Get the season's game duration and games_per_matchup to calculate game end times
+        // Get the season's game duration and games_per_matchup to calculate game end times,
+        // and the league's generator settings and timezone
         let season = sqlx::query!(
-            "SELECT game_duration_seconds, games_per_matchup FROM league_seasons WHERE id = $1",
+            r#"
+            SELECT ls.game_duration_seconds, ls.games_per_matchup,
+                   l.schedule_balance_home_away, l.schedule_spread_strong_teams, l.timezone
+            FROM league_seasons ls
+            JOIN leagues l ON l.id = ls.league_id
+            WHERE ls.id = $1
+            "#,
             season_id
         )
         .fetch_one(&self.pool)
@@ -63,47 +75,67 @@ impl ScheduleService {
         let game_duration_seconds = season.game_duration_seconds;
         let games_per_matchup = season.games_per_matchup.unwrap_or(1); // Default to single round-robin
         let game_duration = Duration::seconds(game_duration_seconds);
+        let timezone = season.timezone.parse::<Tz>().unwrap_or_else(|_| {
+            tracing::warn!("Unknown league timezone {}, scheduling season {} in UTC", season.timezone, season_id);
+            Tz::UTC
+        });
+        // Kickoffs are kept on the league's wall clock and materialized to UTC per round
+        let local_start = season_start_date.with_timezone(&timezone).naive_local();
+        let settings = FixtureSettings {
+            balance_home_away: season.schedule_balance_home_away,
+            spread_strong_teams: season.schedule_spread_strong_teams,
+        };
 
+        let (ratings, rated_teams) = self.get_previous_season_ratings(season_id, team_ids, season_start_date).await?;
 
         let games_per_round = team_count / 2;
         let schedule_type = if games_per_matchup == 1 { "single round-robin" } else { "double round-robin" };
-        tracing::info!("Generating {} schedule for {} teams, {} games per round", schedule_type, team_count, games_per_round);
+        tracing::info!(
+            "Generating {} schedule for {} teams, {} games per round ({} rated from previous seasons)",
+            schedule_type, team_count, games_per_round, rated_teams
+        );
+
+        let rounds = fixture_balancing::build_rounds(&ratings, games_per_matchup, settings);
+        let fairness = fixture_balancing::fairness(&rounds, &ratings, rated_teams, settings);
+        let first_leg_rounds = team_count - 1;
 
         let mut tx = self.pool.begin().await?;
         let mut games_created = 0;
 
-        // Use the circle method for round-robin scheduling
-        // This guarantees perfect scheduling with no conflicts
-        let mut teams: Vec<usize> = (0..team_count).collect();
-        
-        // FIRST LEG: Generate N-1 rounds (always generated)
-        for round in 0..(team_count - 1) {
+        sqlx::query!(
+            r#"
+            INSERT INTO season_schedule_rules (season_id, timezone, local_start, game_duration_seconds)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (season_id) DO UPDATE
+            SET timezone = EXCLUDED.timezone,
+                local_start = EXCLUDED.local_start,
+                game_duration_seconds = EXCLUDED.game_duration_seconds,
+                updated_at = NOW()
+            "#,
+            season_id,
+            timezone.name(),
+            local_start,
+            game_duration_seconds
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (round, pairings) in rounds.iter().enumerate() {
             let round_counter_for_readability = round + 1;
-            tracing::info!("🏗️ FIRST LEG - Round {} (round index={})", round_counter_for_readability, round);
-            let game_start_time = self.timing.calculate_game_start_time(season_start_date, round, game_duration)?;
-            
-            // Generate pairings for this round
-            for i in 0..games_per_round {
-                let home_idx = if i == 0 {
-                    // First team stays fixed
-                    0
-                } else {
-                    teams[i]
-                };
-                
-                let away_idx = teams[team_count - 1 - i];
-                
+            // Round starts at the local kickoff time and ends when the next round kicks off
+            let (game_start_time, game_end_time) = self.timing.materialize_round(timezone, local_start, round, game_duration);
+            // Only double round-robin schedules have a first leg
+            let is_first_leg = games_per_matchup == 2 && round < first_leg_rounds;
+
+            for &(home_idx, away_idx) in pairings {
                 let home_team = team_ids[home_idx];
                 let away_team = team_ids[away_idx];
-                
+
                 tracing::debug!(
-                    "First leg - Round {}: {} (home) vs {} (away)",
+                    "Round {}: {} (home) vs {} (away)",
                     round_counter_for_readability, home_team, away_team
                 );
 
-                // Round starts at the scheduled time, ends after game duration
-                let game_end_time = game_start_time + game_duration;
-
                 sqlx::query!(
                     r#"
                     INSERT INTO games (
@@ -115,7 +147,7 @@ impl ScheduleService {
                     home_team,
                     away_team,
                     round_counter_for_readability as i32,
-                    games_per_matchup == 2, // true for double round-robin (this is first leg), false for single round-robin
+                    is_first_leg,
                     game_start_time,
                     game_end_time
                 )
@@ -124,93 +156,148 @@ impl ScheduleService {
                 
                 games_created += 1;
             }
-            
-            // Rotate teams for next round (except first team which stays fixed)
-            let last = teams.pop().unwrap();
-            teams.insert(1, last);
         }
-        
-        tracing::info!("Completed first leg: {} games in {} weeks", games_created, team_count - 1);
-        
-        // SECOND LEG: Only generate if games_per_matchup is 2 (double round-robin)
-        if games_per_matchup == 2 {
-            // Reset teams array for second leg
-            teams = (0..team_count).collect();
-            
-            // Generate N-1 rounds with home/away swapped
-        for round in 0..(team_count - 1) {
-            let game_round = (team_count - 1) + round;
-            let round_counter_for_readability = game_round + 1;
-            tracing::info!("🏗️ SECOND LEG - Round {} (round index={}, game_round={})", round_counter_for_readability, round, game_round);
-            let game_start_time = self.timing.calculate_game_start_time(season_start_date, game_round, game_duration)?;
-            
-            // Generate pairings for this round (with home/away swapped)
-            for i in 0..games_per_round {
-                let home_idx = if i == 0 {
-                    // First team stays fixed but now plays away
-                    teams[team_count - 1 - i]
-                } else {
-                    teams[team_count - 1 - i]
-                };
-                
-                let away_idx = if i == 0 {
-                    0
-                } else {
-                    teams[i]
-                };
-                
-                let home_team = team_ids[home_idx];
-                let away_team = team_ids[away_idx];
-                
-                tracing::debug!(
-                    "Second leg - Round {}: {} (home) vs {} (away)",
-                    game_round + 1, home_team, away_team
-                );
-
-                // Week starts at the scheduled time, ends after game duration
-                let game_end_time = game_start_time + game_duration;
 
-                sqlx::query!(
-                    r#"
-                    INSERT INTO games (
-                        season_id, home_team_id, away_team_id,
-                        week_number, is_first_leg, status, game_start_time, game_end_time
-                    ) VALUES ($1, $2, $3, $4, FALSE, 'scheduled', $5, $6)
-                    "#,
-                    season_id,
-                    home_team,
-                    away_team,
-                    round_counter_for_readability as i32,
-                    game_start_time,
-                    game_end_time
-                )
-                .execute(&mut *tx)
-                .await?;
-                
-                games_created += 1;
-            }
-            
-            // Rotate teams for next round (except first team which stays fixed)
-            let last = teams.pop().unwrap();
-            teams.insert(1, last);
-        }
-        
-        tracing::info!("Completed second leg: {} total games in {} rounds", games_created, 2 * (team_count - 1));
-        } else {
-            tracing::info!("Skipping second leg for single round-robin schedule");
-        }
+        sqlx::query!(
+            r#"
+            INSERT INTO season_schedule_reports (season_id, balance_home_away, spread_strong_teams, fairness)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (season_id) DO UPDATE
+            SET balance_home_away = EXCLUDED.balance_home_away,
+                spread_strong_teams = EXCLUDED.spread_strong_teams,
+                fairness = EXCLUDED.fairness,
+                generated_at = NOW()
+            "#,
+            season_id,
+            settings.balance_home_away,
+            settings.spread_strong_teams,
+            serde_json::to_value(&fairness).unwrap_or_default()
+        )
+        .execute(&mut *tx)
+        .await?;
 
         tx.commit().await?;
 
-        let total_weeks = games_per_matchup as usize * (team_count - 1);
         tracing::info!(
-            "Schedule generation complete: {} total games over {} rounds ({} games per round)",
+            "Schedule generation complete: {} total games over {} rounds ({} games per round), fairness: {:?}",
             games_created,
-            total_weeks,
-            games_per_round
+            rounds.len(),
+            games_per_round,
+            fairness
         );
 
-        Ok(games_created)
+        Ok((games_created, fairness))
+    }
+
+    /// Points per game of each team in its most recent earlier season, in `team_ids` order.
+    /// Teams without a previous season are rated as the average; also returns how many were rated.
+    async fn get_previous_season_ratings(
+        &self,
+        season_id: Uuid,
+        team_ids: &[Uuid],
+        season_start_date: DateTime<Utc>,
+    ) -> Result<(Vec<f64>, usize), sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT ON (s.team_id)
+                s.team_id,
+                COALESCE(s.points, 0)::float8 / s.games_played AS "rating!"
+            FROM league_standings s
+            JOIN league_seasons ls ON ls.id = s.season_id
+            WHERE s.team_id = ANY($1)
+              AND s.season_id <> $2
+              AND ls.start_date < $3
+              AND s.games_played > 0
+            ORDER BY s.team_id, ls.start_date DESC
+            "#,
+            team_ids,
+            season_id,
+            season_start_date
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let rated: std::collections::HashMap<Uuid, f64> = rows.into_iter().map(|row| (row.team_id, row.rating)).collect();
+        let average = if rated.is_empty() { 0.0 } else { rated.values().sum::<f64>() / rated.len() as f64 };
+        let ratings = team_ids.iter().map(|id| rated.get(id).copied().unwrap_or(average)).collect();
+
+        Ok((ratings, rated.len()))
+    }
+
+    /// Re-materialize upcoming games from their season's schedule rules so kickoffs stay on the
+    /// league's wall clock when timezone (DST) rules change. Games that started already keep their times.
+    /// Returns the number of games that moved.
+    pub async fn rematerialize_upcoming_games(&self) -> Result<usize, sqlx::Error> {
+        let games = sqlx::query!(
+            r#"
+            SELECT g.id, g.season_id, g.week_number, g.game_start_time, g.game_end_time,
+                   r.timezone, r.local_start, r.game_duration_seconds
+            FROM games g
+            JOIN season_schedule_rules r ON r.season_id = g.season_id
+            WHERE g.status = 'scheduled' AND g.game_start_time > NOW() AND g.cup_id IS NULL
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx = self.pool.begin().await?;
+        let mut moved = 0;
+        let mut moved_seasons = std::collections::HashSet::new();
+
+        for game in games {
+            let Ok(timezone) = game.timezone.parse::<Tz>() else {
+                tracing::warn!("Skipping game {}: unknown schedule timezone {}", game.id, game.timezone);
+                continue;
+            };
+            let round = (game.week_number - 1).max(0) as usize;
+            let (game_start_time, game_end_time) = self.timing.materialize_round(
+                timezone,
+                game.local_start,
+                round,
+                Duration::seconds(game.game_duration_seconds),
+            );
+            if game.game_start_time == Some(game_start_time) && game.game_end_time == Some(game_end_time) {
+                continue;
+            }
+
+            tracing::info!(
+                "Moving game {} (week {}) from {:?} to {} to keep its {} kickoff",
+                game.id, game.week_number, game.game_start_time, game_start_time, game.timezone
+            );
+            sqlx::query!(
+                r#"
+                UPDATE games
+                SET game_start_time = $2, game_end_time = $3, updated_at = NOW()
+                WHERE id = $1 AND status = 'scheduled'
+                "#,
+                game.id,
+                game_start_time,
+                game_end_time
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            moved += 1;
+            moved_seasons.insert(game.season_id);
+        }
+
+        // Seasons end with their last game
+        for season_id in moved_seasons {
+            sqlx::query!(
+                r#"
+                UPDATE league_seasons
+                SET end_date = COALESCE((SELECT MAX(game_end_time) FROM games WHERE season_id = $1), end_date),
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+                season_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(moved)
     }
 
     /// Get complete season schedule with team details
@@ -237,12 +324,16 @@ impl ScheduleService {
         tracing::info!("Found {} games for season {}, total weeks: {}", games_with_teams.len(), season_id, total_weeks);
         
         let next_game_time = self.timing.get_next_game_time();
+        let week_names = fixture_flavor::season_week_names(&self.pool, season_id).await?;
+        let fixture_flavors = fixture_flavor::season_fixture_flavors(&self.pool, season_id).await?;
 
         Ok(LeagueScheduleResponse {
             season,
             games: games_with_teams,
             next_game_time,
             total_weeks,
+            week_names,
+            fixture_flavors,
         })
     }
 
@@ -263,7 +354,7 @@ impl ScheduleService {
             FROM games lg
             JOIN teams ht ON lg.home_team_id = ht.id
             JOIN teams at ON lg.away_team_id = at.id
-            WHERE lg.season_id = $1 AND lg.week_number = $2
+            WHERE lg.season_id = $1 AND lg.week_number = $2 AND lg.cup_id IS NULL
             ORDER BY lg.game_start_time ASC
             "#,
             season_id,
@@ -300,7 +391,7 @@ impl ScheduleService {
         let team_powers = team_power::calculate_multiple_team_powers(&team_ids, &self.pool).await?;
 
         // Convert query results to GameWithTeams with team powers
-        let games_with_teams = games_query.into_iter().map(|row| {
+        let games_with_teams: Vec<GameWithTeams> = games_query.into_iter().map(|row| {
             let status = match row.status.as_str() {
                 "in_progress" => GameStatus::InProgress,
                 "live" => GameStatus::InProgress,
@@ -332,12 +423,22 @@ impl ScheduleService {
             }
         }).collect();
 
+        let week_name = fixture_flavor::season_week_names(&self.pool, season_id).await?
+            .into_iter()
+            .find(|w| w.week_number == week_number);
+        let fixture_flavors = fixture_flavor::season_fixture_flavors(&self.pool, season_id).await?
+            .into_iter()
+            .filter(|f| games_with_teams.iter().any(|g| g.game.id == f.game_id))
+            .collect();
+
         Ok(GameWeekResponse {
             week_number,
             game_time,
             games: games_with_teams,
             is_current_week,
             countdown_seconds,
+            week_name,
+            fixture_flavors,
         })
     }
 
@@ -601,7 +702,7 @@ impl ScheduleService {
             FROM games lg
             JOIN teams ht ON lg.home_team_id = ht.id
             JOIN teams at ON lg.away_team_id = at.id
-            WHERE lg.season_id = $1
+            WHERE lg.season_id = $1 AND lg.cup_id IS NULL
             ORDER BY lg.game_start_time, lg.week_number
             "#,
             season_id
@@ -677,7 +778,7 @@ impl ScheduleService {
                 MIN(week_number) as first_week,
                 MAX(week_number) as last_week
             FROM games
-            WHERE season_id = $1
+            WHERE season_id = $1 AND cup_id IS NULL
             "#,
             season_id
         )
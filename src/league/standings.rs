@@ -51,6 +51,15 @@ impl StandingsService {
         home_score: i32,
         away_score: i32,
     ) -> Result<(), sqlx::Error> {
+        // Cup games are played alongside the league without counting towards it
+        let is_cup_game = sqlx::query_scalar!("SELECT cup_id IS NOT NULL AS \"is_cup!\" FROM games WHERE id = $1", game.id)
+            .fetch_optional(&self.pool)
+            .await?
+            .unwrap_or(false);
+        if is_cup_game {
+            return Ok(());
+        }
+
         tracing::info!("🏆 Updating standings for game {}: {} - {} (home team: {}, away team: {})", 
             game.id, home_score, away_score, game.home_team_id, game.away_team_id);
 
@@ -164,7 +173,7 @@ impl StandingsService {
                 home_score,
                 away_score
             FROM games
-            WHERE season_id = $1 AND status = 'evaluated'
+            WHERE season_id = $1 AND status = 'evaluated' AND cup_id IS NULL
             "#,
             season_id
         )
@@ -228,7 +237,7 @@ impl StandingsService {
     }
 
     /// Recalculate all positions based on current points with tie-breaker logic
-    async fn recalculate_positions_in_tx(
+    pub(crate) async fn recalculate_positions_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         season_id: Uuid,
@@ -306,7 +315,7 @@ impl StandingsService {
                 home_score,
                 away_score
             FROM games
-            WHERE season_id = $1 AND status = 'evaluated'
+            WHERE season_id = $1 AND status = 'evaluated' AND cup_id IS NULL
             "#,
             season_id
         )
@@ -456,10 +465,10 @@ impl StandingsService {
             let total_points = sqlx::query_scalar!(
                 r#"
                 SELECT COALESCE(
-                    (SELECT SUM(home_score) FROM games WHERE season_id = $1 AND home_team_id = $2 AND status = 'evaluated'),
+                    (SELECT SUM(home_score) FROM games WHERE season_id = $1 AND home_team_id = $2 AND status = 'evaluated' AND cup_id IS NULL),
                     0
                 ) + COALESCE(
-                    (SELECT SUM(away_score) FROM games WHERE season_id = $1 AND away_team_id = $2 AND status = 'evaluated'),
+                    (SELECT SUM(away_score) FROM games WHERE season_id = $1 AND away_team_id = $2 AND status = 'evaluated' AND cup_id IS NULL),
                     0
                 ) as "total!"
                 "#,
@@ -0,0 +1,48 @@
+/// Workout counts at which a member's teammates hear about it
+pub const MEMBER_WORKOUT_MILESTONES: [i64; 6] = [10, 25, 50, 100, 250, 500];
+
+/// Team events a captain can turn notifications on or off for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeamAlert {
+    /// One of the team's games started
+    GameStart,
+    /// The team lost the lead or the tie in a live game
+    FellBehind,
+    /// A member reached a workout milestone
+    MemberMilestone,
+}
+
+impl TeamAlert {
+    pub const ALL: [TeamAlert; 3] = [TeamAlert::GameStart, TeamAlert::FellBehind, TeamAlert::MemberMilestone];
+
+    /// Stored as `notifications.notification_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TeamAlert::GameStart => "team_game_start",
+            TeamAlert::FellBehind => "team_fell_behind",
+            TeamAlert::MemberMilestone => "team_member_milestone",
+        }
+    }
+}
+
+/// Whether `count` workouts is a milestone worth telling the team
+pub fn is_workout_milestone(count: i64) -> bool {
+    MEMBER_WORKOUT_MILESTONES.contains(&count)
+}
+
+/// Whether a team that was level or ahead is now behind
+pub fn fell_behind(team_before: i32, opponent_before: i32, team_after: i32, opponent_after: i32) -> bool {
+    team_before >= opponent_before && team_after < opponent_after
+}
+
+pub fn game_start_message(team_name: &str, opponent_name: &str) -> String {
+    format!("{} vs {} has started. Every workout counts from now on!", team_name, opponent_name)
+}
+
+pub fn fell_behind_message(opponent_name: &str, team_score: i32, opponent_score: i32) -> String {
+    format!("{} took the lead, {} - {}. Time for a workout to win it back!", opponent_name, opponent_score, team_score)
+}
+
+pub fn member_milestone_message(username: &str, workouts: i64) -> String {
+    format!("{} just logged their {}th workout!", username, workouts)
+}
@@ -0,0 +1,73 @@
+use serde::Serialize;
+
+/// Colors an away team falls back to when neither of its own colors can be told apart from
+/// the home team's, in order of preference
+pub const ALTERNATE_KIT_COLORS: [&str; 6] = ["#FFFFFF", "#111827", "#F59E0B", "#10B981", "#EF4444", "#3B82F6"];
+
+/// Colors closer than this (redmean distance, 0 to ~765) are too alike to tell apart on the ticker
+const CLASH_DISTANCE: f64 = 120.0;
+
+/// A team's colors and emoji as set by its owner
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamKit {
+    pub home_color: String,
+    pub away_color: Option<String>,
+    pub emoji: Option<String>,
+}
+
+/// The colors and emoji a team wears in one game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameKit {
+    pub color: String,
+    pub emoji: Option<String>,
+}
+
+pub fn validate_hex_color(color: &str) -> Result<(), String> {
+    if color.len() != 7 || !color.starts_with('#') || !color[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{color} is not a valid hex color (e.g., #FF0000)"));
+    }
+    Ok(())
+}
+
+/// Kit emoji are a few emoji characters, no text
+pub fn validate_kit_emoji(emoji: &str) -> Result<(), String> {
+    if emoji.is_empty() || emoji.chars().count() > 8 || emoji.chars().any(|c| c.is_ascii()) {
+        return Err("Kit emoji must be 1 to 8 emoji characters".to_string());
+    }
+    Ok(())
+}
+
+fn rgb(color: &str) -> Option<(f64, f64, f64)> {
+    validate_hex_color(color).ok()?;
+    let channel = |i: usize| u8::from_str_radix(&color[i..i + 2], 16).map(f64::from).ok();
+    Some((channel(1)?, channel(3)?, channel(5)?))
+}
+
+/// Whether two hex colors are too alike to tell the teams apart. Invalid colors never clash.
+pub fn colors_clash(a: &str, b: &str) -> bool {
+    let (Some((r1, g1, b1)), Some((r2, g2, b2))) = (rgb(a), rgb(b)) else {
+        return false;
+    };
+    // Redmean approximation of perceived distance
+    let mean_red = (r1 + r2) / 2.0;
+    let (dr, dg, db) = (r1 - r2, g1 - g2, b1 - b2);
+    let distance = ((2.0 + mean_red / 256.0) * dr * dr + 4.0 * dg * dg + (2.0 + (255.0 - mean_red) / 256.0) * db * db).sqrt();
+    distance < CLASH_DISTANCE
+}
+
+/// Kits of a game: the home team wears its home color, the away team its home color unless it
+/// clashes, then its away color, then the first alternate that doesn't clash
+pub fn assign_game_kits(home: &TeamKit, away: &TeamKit) -> (GameKit, GameKit) {
+    let home_color = home.home_color.clone();
+    let away_color = std::iter::once(away.home_color.as_str())
+        .chain(away.away_color.as_deref())
+        .chain(ALTERNATE_KIT_COLORS)
+        .find(|color| !colors_clash(color, &home_color))
+        .unwrap_or(ALTERNATE_KIT_COLORS[0])
+        .to_string();
+
+    (
+        GameKit { color: home_color, emoji: home.emoji.clone() },
+        GameKit { color: away_color, emoji: away.emoji.clone() },
+    )
+}
@@ -1,6 +1,7 @@
 use std::io::Error;
 
-use chrono::{DateTime, Utc, Datelike, Duration, Weekday, Timelike};
+use chrono::{DateTime, Utc, Datelike, Duration, Weekday, Timelike, NaiveDateTime, LocalResult, Offset, TimeZone};
+use chrono_tz::Tz;
 pub struct TimingService;
 
 impl Default for TimingService {
@@ -65,6 +66,38 @@ impl TimingService {
         Ok(game_start_time)
     }
 
+    /// UTC start and end of a round kept on the wall clock of `timezone`.
+    /// Rounds follow each other by the game duration in local time, so kickoffs stay at the
+    /// same local time across DST changes and the round spanning a change is an hour shorter or longer.
+    pub fn materialize_round(
+        &self,
+        timezone: Tz,
+        local_start: NaiveDateTime,
+        round: usize,
+        game_duration: Duration,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let local_round_start = local_start + game_duration * round as i32;
+        (
+            self.materialize_local_time(timezone, local_round_start),
+            self.materialize_local_time(timezone, local_round_start + game_duration),
+        )
+    }
+
+    /// Convert a wall-clock time in `timezone` to UTC.
+    /// Times skipped when clocks go forward move forward by the length of the gap;
+    /// times repeated when clocks go back resolve to their first occurrence.
+    pub fn materialize_local_time(&self, timezone: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+        match timezone.from_local_datetime(&local) {
+            LocalResult::Single(time) => time.with_timezone(&Utc),
+            LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+            LocalResult::None => {
+                // Read the skipped time with the offset in effect before the gap
+                let offset_before = timezone.offset_from_utc_datetime(&(local - Duration::days(1))).fix();
+                Utc.from_utc_datetime(&(local - Duration::seconds(offset_before.local_minus_utc() as i64)))
+            }
+        }
+    }
+
     /// Check if we're currently within game time (Saturday evening)
     pub fn is_game_time(&self) -> bool {
         let now = Utc::now();
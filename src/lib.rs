@@ -16,11 +16,20 @@ pub mod game;
 pub mod league;
 pub mod workout;
 pub mod services;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 use crate::routes::init_routes;
 use crate::config::jwt::JwtSettings;
-use crate::services::{SchedulerService, MinIOService, MLClient};
+use crate::config::upload_limits::UploadLimitsSettings;
+use crate::config::cors::CorsSettings;
+use crate::config::login_protection::LoginProtectionSettings;
+use crate::config::password_policy::PasswordPolicySettings;
+use crate::services::{SchedulerService, MinIOService, MLClient, LiveMetrics, EmailService, OAuthService, LoginProtectionService, PasswordPolicyService, BackupVerificationService, GameClockCache};
+use secrecy::SecretString;
+use actix_web::dev::Service;
 use std::sync::Arc;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     listener: TcpListener,
     db_pool: PgPool,
@@ -28,12 +37,20 @@ pub fn run(
     redis_client: Arc<redis::Client>,
     scheduler_service: Arc<SchedulerService>,
     minio_service: MinIOService,
-    ml_client: MLClient
+    ml_client: MLClient,
+    upload_limits: UploadLimitsSettings,
+    email_service: EmailService,
+    oauth_service: OAuthService,
+    cors_settings: CorsSettings,
+    login_protection: LoginProtectionSettings,
+    password_policy: PasswordPolicySettings,
+    database_url: SecretString
 ) -> Result<Server, std::io::Error> {
     // Wrap using web::Data, which boils down to an Arc smart pointer
     let db_pool_data = web::Data::new(db_pool.clone());
     let jwt_settings = web::Data::new(jwt_settings);
     let scheduler_service = web::Data::new(scheduler_service);
+    let upload_limits = web::Data::new(upload_limits);
     let redis_client_data = web::Data::new(redis_client.clone());
 
     // Wrap ML Client
@@ -42,15 +59,41 @@ pub fn run(
     // Wrap MinIOService
     let minio_service_data = web::Data::new(minio_service);
 
+    let email_service_data = web::Data::new(email_service);
+    let oauth_service_data = web::Data::new(oauth_service);
+    let trusted_proxies = login_protection
+        .trusted_proxies()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let login_protection_data = web::Data::new(LoginProtectionService::new(
+        db_pool.clone(),
+        redis_client.clone(),
+        login_protection,
+        trusted_proxies,
+    ));
+    let password_policy_data = web::Data::new(PasswordPolicyService::new(password_policy));
+    let backup_verification_data = web::Data::new(BackupVerificationService::new(db_pool.clone()).with_database_url(database_url));
+
+    // Server errors of this instance, streamed to the admin monitor
+    let live_metrics = Arc::new(LiveMetrics::new());
+    let live_metrics_data = web::Data::new(live_metrics.clone());
+
+    // Quotas of the expensive admin endpoints, one set for all workers
+    let admin_quotas = web::Data::new(crate::middleware::quota::AdminQuotas::new());
+
+    // Game clock sent with WebSocket heartbeats, loaded once for all connections
+    let game_clock_cache = web::Data::new(GameClockCache::new());
+
+    // GraphQL schema is built once and shared across workers
+    #[cfg(feature = "graphql")]
+    let graphql_schema = web::Data::new(crate::graphql::build_schema(db_pool.clone()));
+
 
     let server = HttpServer::new( move || {
+        let cors_settings = cors_settings.clone();
         let cors = Cors::default()
-            .allowed_origin("http://localhost:3000")
-            .allowed_origin("http://localhost:3001")
-            .allowed_origin("https://riina.fly.dev")
-            .allowed_origin("https://evolveme-admin.fly.dev")
-            .allowed_origin("https://riina-dev.fly.dev")
-            .allowed_origin("https://evolveme-admin-dev.fly.dev")
+            .allowed_origin_fn(move |origin, _req| {
+                origin.to_str().is_ok_and(|origin| cors_settings.allows_origin(origin))
+            })
             .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"])
             .allowed_headers(vec![
                 http::header::AUTHORIZATION,
@@ -58,20 +101,52 @@ pub fn run(
                 http::header::CONTENT_TYPE,
                 http::header::UPGRADE,
                 http::header::CONNECTION,
+                http::header::IF_NONE_MATCH,
+                http::header::IF_MATCH,
             ])
+            .expose_headers(vec![http::header::ETAG])
             .supports_credentials()
             .max_age(3600);
 
+        let live_metrics = live_metrics.clone();
         let app = App::new()
             .wrap(TracingLogger::default())
+            // Reports server errors with the request; handlers add the user through the auth middleware
+            .wrap(sentry::integrations::actix::Sentry::new())
             .wrap(cors)
+            .wrap_fn(move |req, srv| {
+                let live_metrics = live_metrics.clone();
+                let response = srv.call(req);
+                async move {
+                    let response = response.await;
+                    let is_server_error = match &response {
+                        Ok(res) => res.status().is_server_error(),
+                        Err(e) => e.as_response_error().status_code().is_server_error(),
+                    };
+                    if is_server_error {
+                        live_metrics.record_server_error();
+                    }
+                    response
+                }
+            })
             // Get a pointer copy and attach it to the application state
             .app_data(db_pool_data.clone())
             .app_data(jwt_settings.clone())
             .app_data(scheduler_service.clone())
             .app_data(minio_service_data.clone())
             .app_data(redis_client_data.clone())
-            .app_data(ml_client_data.clone());
+            .app_data(ml_client_data.clone())
+            .app_data(email_service_data.clone())
+            .app_data(oauth_service_data.clone())
+            .app_data(login_protection_data.clone())
+            .app_data(password_policy_data.clone())
+            .app_data(backup_verification_data.clone())
+            .app_data(upload_limits.clone())
+            .app_data(live_metrics_data.clone())
+            .app_data(admin_quotas.clone())
+            .app_data(game_clock_cache.clone());
+        #[cfg(feature = "graphql")]
+        let app = app.app_data(graphql_schema.clone());
 
         app.configure(init_routes)
     })
@@ -8,15 +8,20 @@ use riina_backend::run;
 use riina_backend::config::settings::{get_config, get_jwt_settings};
 use riina_backend::services::{
     SchedulerService, MinIOService,
-    telemetry::{get_subscriber, init_subscriber},
+    telemetry::{get_subscriber, init_subscriber, shutdown_trace_export},
+    error_reporting::init_error_reporting,
     redis_service::RedisService,
-    ml_client::MLClient
+    ml_client::MLClient,
+    EmailService,
+    OAuthService
 };
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     // Panic if we can't read the config
     let config = get_config().expect("Failed to read the config.");
+    // Error reporting, kept running until the server stops
+    let _error_reporting = init_error_reporting(&config.error_reporting);
     // Telemetry
     let subscriber = get_subscriber(
         "riina-backend".into(), 
@@ -48,6 +53,12 @@ async fn main() -> std::io::Result<()> {
     // Scheduler service
     let scheduler_service = match SchedulerService::new_with_redis(conection_pool.clone(), redis_service.client.clone()).await {
         Ok(scheduler) => {
+            let scheduler = scheduler
+                .with_minio(minio_service.clone())
+                .with_game_watchdog(config.game_watchdog.clone())
+                .with_stat_decay(config.stat_decay.clone())
+                .with_backup_database_url(config.database.connection_string())
+                .with_email(EmailService::new(config.email.clone()));
             match scheduler.start().await {
                 Ok(_) => {
                     tracing::info!("✅ Scheduler service started successfully");
@@ -71,13 +82,24 @@ async fn main() -> std::io::Result<()> {
         config.ml.api_key.expose_secret().to_string()
     );
 
-    run(
+    let result = run(
         listener,
         conection_pool,
         jwt_settings,
         redis_service.client,
         scheduler_service,
         minio_service,
-        ml_client
-    )?.await
+        ml_client,
+        config.upload_limits.clone(),
+        EmailService::new(config.email.clone()),
+        OAuthService::new(config.oauth.clone()),
+        config.cors.clone(),
+        config.login_protection.clone(),
+        config.password_policy.clone(),
+        config.database.connection_string()
+    )?.await;
+
+    // Traces still buffered would be lost on exit
+    shutdown_trace_export();
+    result
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpMessage,
+    web, Error, HttpMessage,
     error::{ErrorUnauthorized, ErrorForbidden},
 };
 use futures_util::future::LocalBoxFuture;
@@ -9,7 +9,9 @@ use std::{
     rc::Rc,
 };
 
-use crate::middleware::auth::validate_jwt_from_request;
+use sqlx::PgPool;
+
+use crate::middleware::auth::{ensure_session_active, validate_jwt_from_request};
 use crate::models::user::{UserRole, UserStatus};
 
 pub struct AdminMiddleware;
@@ -76,10 +78,15 @@ where
             }
         }
 
+        let pool = req.app_data::<web::Data<PgPool>>().cloned();
+        let session_claims = claims.clone();
+
         // Store the claims in the request extensions for handlers to access
         req.extensions_mut().insert(claims);
 
         Box::pin(async move {
+            ensure_session_active(pool, &session_claims).await?;
+
             let res = service.call(req).await?;
             Ok(res)
         })
@@ -1,17 +1,21 @@
 // src/middleware/auth.rs
-use std::{future::{ready, Ready}};
+use std::{future::{ready, Ready}, rc::Rc};
 use actix_web::{
-    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform}, error::ErrorUnauthorized, http::header, web, Error, HttpMessage
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform}, error::{ErrorForbidden, ErrorInternalServerError, ErrorUnauthorized}, http::{header, Method}, web, Error, HttpMessage
 };
 use futures_util::future::LocalBoxFuture;
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
 use serde::{Deserialize, Serialize};
-use secrecy::ExposeSecret;
+use sqlx::PgPool;
 
 use uuid::Uuid;
 
 use crate::config::jwt::JwtSettings;
+use crate::db::api_keys::authenticate_api_key;
+use crate::db::user_sessions::check_session;
+use crate::models::api_key::scopes_allow;
 use crate::models::user::{UserRole, UserStatus};
+use crate::services::error_reporting::set_reporting_user;
+use crate::services::suspension_service::active_suspension;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -20,6 +24,17 @@ pub struct Claims {
     pub role: UserRole,
     pub status: UserStatus,
     pub exp: usize,   // Expiration time (as UTC timestamp)
+    /// Who issued the token and who it is for; required in JWTs, absent for API keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    /// Session (logged in device) the token was issued to; absent in tokens from before sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    /// Admin acting as the user through a support impersonation token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<Uuid>,
 }
 
 impl Claims {
@@ -51,16 +66,77 @@ pub fn validate_jwt_from_request(req: &ServiceRequest) -> Result<Claims, Error>
 
     // Extract and decode the token
     let token = &auth_header[7..]; // Skip "Bearer "
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(jwt_settings.secret.expose_secret().as_bytes()),
-        &Validation::new(Algorithm::HS256),
-    ).map_err(|e| {
+    jwt_settings.verify::<Claims>(token).map_err(|e| {
         tracing::error!("Failed to decode token: {:?}", e);
         ErrorUnauthorized("Invalid token")
-    })?;
+    })
+}
+
+/// Refuse tokens whose session was logged out, so a stolen token stops working before it expires
+pub async fn ensure_session_active(pool: Option<web::Data<PgPool>>, claims: &Claims) -> Result<(), Error> {
+    let (Some(session_id), Some(pool)) = (claims.sid, pool) else {
+        return Ok(());
+    };
+    match check_session(pool.get_ref(), session_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(ErrorUnauthorized("Session has been logged out")),
+        Err(e) => {
+            tracing::error!("Failed to check session {}: {}", session_id, e);
+            Err(ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+/// Header server-to-server integrations send their API key in, instead of a JWT
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/// Claims of the user an API key acts as, if the key is valid and its scopes cover the request
+async fn validate_api_key(pool: Option<web::Data<PgPool>>, key: &str, method: &Method, path: &str) -> Result<Claims, Error> {
+    let pool = pool.ok_or_else(|| ErrorInternalServerError("Database not available"))?;
+    let api_key_user = match authenticate_api_key(pool.get_ref(), key).await {
+        Ok(Some(api_key_user)) => api_key_user,
+        Ok(None) => return Err(ErrorUnauthorized("Invalid API key")),
+        Err(e) => {
+            tracing::error!("Failed to authenticate API key: {}", e);
+            return Err(ErrorInternalServerError("Database error"));
+        }
+    };
+    if !scopes_allow(&api_key_user.scopes, method, path) {
+        return Err(ErrorForbidden("API key is not allowed to do this"));
+    }
+    Ok(Claims {
+        sub: api_key_user.user_id.to_string(),
+        username: api_key_user.username,
+        role: api_key_user.role,
+        status: api_key_user.status,
+        exp: api_key_user.expires_at.map_or(0, |expires_at| expires_at.timestamp() as usize),
+        iss: None,
+        aud: None,
+        sid: None,
+        impersonated_by: None,
+    })
+}
 
-    Ok(token_data.claims)
+/// Refuse requests that change data from suspended users
+async fn ensure_not_suspended(pool: web::Data<PgPool>, user_id: Uuid) -> Result<(), Error> {
+    match active_suspension(pool.get_ref(), user_id).await {
+        Ok(Some(suspension)) => {
+            let message = match suspension.ends_at {
+                Some(ends_at) => format!(
+                    "Your account is suspended until {}: {}",
+                    ends_at.format("%Y-%m-%d %H:%M UTC"),
+                    suspension.reason
+                ),
+                None => format!("Your account is suspended: {}", suspension.reason),
+            };
+            Err(ErrorForbidden(message))
+        }
+        Ok(None) => Ok(()),
+        Err(e) => {
+            tracing::error!("Failed to check suspension of user {}: {}", user_id, e);
+            Err(ErrorInternalServerError("Database error"))
+        }
+    }
 }
 
 // Create the middleware
@@ -69,7 +145,7 @@ pub struct AuthMiddleware;
 // Middleware factory
 impl<S, B> Transform<S, ServiceRequest> for AuthMiddleware
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -80,17 +156,17 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ready(Ok(AuthMiddlewareService { service }))
+        ready(Ok(AuthMiddlewareService { service: Rc::new(service) }))
     }
 }
 
 pub struct AuthMiddlewareService<S> {
-    service: S,
+    service: Rc<S>,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -101,20 +177,120 @@ where
     forward_ready!(service);
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let pool = req.app_data::<web::Data<PgPool>>().cloned();
+
+        // Integrations authenticate with an API key, checked against the database
+        let api_key = if req.headers().contains_key(header::AUTHORIZATION) {
+            None
+        } else {
+            req.headers().get(API_KEY_HEADER).map(|key| key.to_str().unwrap_or_default().to_string())
+        };
+        if let Some(api_key) = api_key {
+            return Box::pin(async move {
+                let claims = validate_api_key(pool.clone(), &api_key, req.method(), req.path()).await?;
+                set_reporting_user(&claims.sub, &claims.username);
+                if !is_read_only(req.method()) {
+                    if let (Some(user_id), Some(pool)) = (claims.user_id(), pool) {
+                        ensure_not_suspended(pool, user_id).await?;
+                    }
+                }
+                req.extensions_mut().insert(claims);
+                service.call(req).await
+            });
+        }
+
         // Validate JWT and extract claims using shared function
         let claims = match validate_jwt_from_request(&req) {
             Ok(claims) => claims,
             Err(e) => return Box::pin(async move { Err(e) }),
         };
 
+        // Tokens issued while the account was not active give no access
+        if !matches!(claims.status, UserStatus::Active) {
+            tracing::warn!("Token of inactive user {} refused", claims.username);
+            return Box::pin(async move { Err(ErrorUnauthorized("Account is not active")) });
+        }
+
+        // Suspended users keep read access; anything that changes data is refused
+        let suspension_check = if is_read_only(req.method()) {
+            None
+        } else {
+            claims.user_id().zip(pool.clone())
+        };
+        let session_claims = claims.clone();
+        set_reporting_user(&claims.sub, &claims.username);
+        if let Some(admin_id) = claims.impersonated_by {
+            tracing::info!("Admin {} impersonating user {}: {} {}", admin_id, claims.sub, req.method(), req.path());
+        }
+
         // Store the claims in the request extensions for handlers to access
         req.extensions_mut().insert(claims);
 
-        let fut = self.service.call(req);
-
         Box::pin(async move {
-            let res = fut.await?;
+            ensure_session_active(pool, &session_claims).await?;
+
+            if let Some((user_id, pool)) = suspension_check {
+                ensure_not_suspended(pool, user_id).await?;
+            }
+
+            let res = service.call(req).await?;
             Ok(res)
         })
     }
-}
\ No newline at end of file
+}
+
+fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+/// Refuses impersonation tokens on routes that change how the account is secured (linked logins,
+/// sessions, deletion, username), so support access can't be turned into lasting access.
+/// Used per route inside `AuthMiddleware`: `#[post("/path", wrap = "AccountSecurity")]`
+pub struct AccountSecurity;
+
+impl<S, B> Transform<S, ServiceRequest> for AccountSecurity
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AccountSecurityService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AccountSecurityService { service }))
+    }
+}
+
+pub struct AccountSecurityService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AccountSecurityService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let impersonation = req
+            .extensions()
+            .get::<Claims>()
+            .and_then(|claims| claims.impersonated_by.map(|admin_id| (admin_id, claims.sub.clone())));
+        if let Some((admin_id, user_id)) = impersonation {
+            tracing::warn!("Admin {} impersonating user {} refused: {} {}", admin_id, user_id, req.method(), req.path());
+            return Box::pin(async move { Err(ErrorForbidden("Not allowed while impersonating")) });
+        }
+
+        Box::pin(self.service.call(req))
+    }
+}
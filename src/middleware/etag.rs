@@ -0,0 +1,134 @@
+// src/middleware/etag.rs
+use std::future::{ready, Ready};
+use actix_web::{
+    body::{to_bytes, BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{header, Method, StatusCode},
+    Error, HttpRequest, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+use sha2::{Digest, Sha256};
+
+/// Adds an `ETag` to successful GET responses and answers `If-None-Match` with
+/// `304 Not Modified` when the client already has the current representation.
+///
+/// The tag is a hash of the response body, so it changes exactly when the data the
+/// client would see changes (standings recalculated, profile updated, games rescheduled)
+/// without each endpoint tracking its own version. The body is still computed, but
+/// clients polling unchanged data no longer download it.
+///
+/// Used per route: `#[get("/path", wrap = "ConditionalGet")]`
+pub struct ConditionalGet;
+
+impl<S, B> Transform<S, ServiceRequest> for ConditionalGet
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ConditionalGetService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ConditionalGetService { service }))
+    }
+}
+
+pub struct ConditionalGetService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for ConditionalGetService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let is_get = req.method() == Method::GET;
+        let if_none_match = req
+            .headers()
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+            if !is_get || res.status() != StatusCode::OK {
+                return Ok(res.map_into_boxed_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let bytes = to_bytes(body).await.map_err(|e| {
+                let e = e.into();
+                tracing::error!("Failed to buffer response body for ETag: {}", e);
+                actix_web::error::ErrorInternalServerError("Failed to build response")
+            })?;
+
+            let etag = compute_etag(&bytes);
+
+            if if_none_match.as_deref().is_some_and(|value| etag_matches(value, &etag)) {
+                let not_modified = HttpResponse::NotModified()
+                    .insert_header((header::ETAG, etag))
+                    .finish();
+                return Ok(ServiceResponse::new(req, not_modified));
+            }
+
+            let mut res = res.set_body(bytes).map_into_boxed_body();
+            if let Ok(value) = header::HeaderValue::from_str(&etag) {
+                res.headers_mut().insert(header::ETAG, value);
+            }
+            Ok(ServiceResponse::new(req, res))
+        })
+    }
+}
+
+/// Strong ETag from the first 128 bits of the body's SHA-256
+fn compute_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// `If-None-Match` uses weak comparison: `*` or any listed tag, ignoring a `W/` prefix
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// ETag carrying a row version, for optimistic concurrency via `If-Match`
+pub fn version_etag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// The version the client last saw, from an `If-Match` header holding a version ETag.
+/// `Ok(None)` when the header is absent or `*`, i.e. the update is unconditional.
+pub fn if_match_version(req: &HttpRequest) -> Result<Option<i32>, &'static str> {
+    let Some(value) = req.headers().get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().map_err(|_| "Invalid If-Match header")?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i32>()
+        .map(Some)
+        .map_err(|_| "If-Match must be the version ETag returned by the server")
+}
@@ -1,2 +1,4 @@
 pub mod auth;
-pub mod admin;
\ No newline at end of file
+pub mod admin;
+pub mod etag;
+pub mod quota;
\ No newline at end of file
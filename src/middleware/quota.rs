@@ -0,0 +1,237 @@
+// src/middleware/quota.rs
+use std::collections::{HashMap, VecDeque};
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header,
+    web, Error, HttpMessage, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::middleware::auth::Claims;
+
+/// Limits for an expensive admin endpoint
+#[derive(Debug, Clone, Copy)]
+pub struct QuotaPolicy {
+    /// Requests each admin may make per window
+    pub max_per_window: usize,
+    pub window: Duration,
+    /// Requests that may wait while one runs; more are refused
+    pub max_queued: usize,
+    /// How long a queued request waits before giving up
+    pub queue_timeout: Duration,
+}
+
+impl QuotaPolicy {
+    /// CSV and research exports, which scan whole tables
+    pub const EXPORT: Self = Self {
+        max_per_window: 10,
+        window: Duration::from_secs(600),
+        max_queued: 3,
+        queue_timeout: Duration::from_secs(60),
+    };
+
+    /// Recalculations and evaluations, which rewrite standings and scores
+    pub const RECALCULATION: Self = Self {
+        max_per_window: 5,
+        window: Duration::from_secs(60),
+        max_queued: 1,
+        queue_timeout: Duration::from_secs(120),
+    };
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum QuotaError {
+    /// The admin used up the window; retry after the given time
+    RateLimited(Duration),
+    /// Too many requests are already waiting
+    QueueFull,
+    /// The request waited `queue_timeout` without its turn coming
+    QueueTimeout,
+}
+
+impl std::fmt::Display for QuotaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RateLimited(retry_after) => {
+                write!(f, "Request quota exceeded, retry in {} seconds", retry_after.as_secs().max(1))
+            }
+            Self::QueueFull => write!(f, "This operation is already running with requests waiting, try again later"),
+            Self::QueueTimeout => write!(f, "Timed out waiting for an earlier run of this operation to finish"),
+        }
+    }
+}
+
+/// One at a time per endpoint and path, with a count of requests waiting their turn
+struct Lane {
+    semaphore: Arc<Semaphore>,
+    waiting: Arc<AtomicUsize>,
+}
+
+/// Quota state of the expensive admin endpoints, shared by all workers of an instance
+#[derive(Default)]
+pub struct AdminQuotas {
+    /// Recent request times per endpoint and admin
+    requests: Mutex<HashMap<(String, String), VecDeque<Instant>>>,
+    lanes: Mutex<HashMap<String, Lane>>,
+}
+
+impl AdminQuotas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request of `admin` against the endpoint's window
+    pub fn check_rate(&self, endpoint: &str, admin: &str, policy: &QuotaPolicy, now: Instant) -> Result<(), QuotaError> {
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        let recent = requests.entry((endpoint.to_string(), admin.to_string())).or_default();
+        while recent.front().is_some_and(|&at| now.duration_since(at) >= policy.window) {
+            recent.pop_front();
+        }
+        if recent.len() >= policy.max_per_window {
+            let oldest = recent.front().copied().unwrap_or(now);
+            return Err(QuotaError::RateLimited(policy.window.saturating_sub(now.duration_since(oldest))));
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+
+    /// Wait for the lane `key` to be free. The lane stays taken until the permit is dropped.
+    pub async fn enter(&self, key: &str, policy: &QuotaPolicy) -> Result<OwnedSemaphorePermit, QuotaError> {
+        let (semaphore, waiting) = {
+            let mut lanes = self.lanes.lock().unwrap_or_else(|e| e.into_inner());
+            let lane = lanes.entry(key.to_string()).or_insert_with(|| Lane {
+                semaphore: Arc::new(Semaphore::new(1)),
+                waiting: Arc::new(AtomicUsize::new(0)),
+            });
+            (lane.semaphore.clone(), lane.waiting.clone())
+        };
+
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+        if waiting.fetch_add(1, Ordering::SeqCst) >= policy.max_queued {
+            waiting.fetch_sub(1, Ordering::SeqCst);
+            return Err(QuotaError::QueueFull);
+        }
+
+        let permit = tokio::time::timeout(policy.queue_timeout, semaphore.acquire_owned()).await;
+        waiting.fetch_sub(1, Ordering::SeqCst);
+        match permit {
+            Ok(Ok(permit)) => Ok(permit),
+            _ => Err(QuotaError::QueueTimeout),
+        }
+    }
+}
+
+/// Applies a `QuotaPolicy` to an admin resource: a per-admin request quota, and runs of the
+/// same endpoint and path queued one after another instead of executing concurrently, so a
+/// double-clicked "recalculate" waits for the first run instead of doubling the load.
+///
+/// Used per resource, inside the admin scope: `.wrap(AdminQuota::new("export", QuotaPolicy::EXPORT))`.
+/// Without `AdminQuotas` in the app data requests pass through unchecked.
+pub struct AdminQuota {
+    endpoint: &'static str,
+    policy: QuotaPolicy,
+}
+
+impl AdminQuota {
+    pub fn new(endpoint: &'static str, policy: QuotaPolicy) -> Self {
+        Self { endpoint, policy }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for AdminQuota
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AdminQuotaService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AdminQuotaService {
+            service: Rc::new(service),
+            endpoint: self.endpoint,
+            policy: self.policy,
+        }))
+    }
+}
+
+pub struct AdminQuotaService<S> {
+    service: Rc<S>,
+    endpoint: &'static str,
+    policy: QuotaPolicy,
+}
+
+impl<S, B> Service<ServiceRequest> for AdminQuotaService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let endpoint = self.endpoint;
+        let policy = self.policy;
+
+        let Some(quotas) = req.app_data::<web::Data<AdminQuotas>>().cloned() else {
+            return Box::pin(service.call(req));
+        };
+        let admin = req
+            .extensions()
+            .get::<Claims>()
+            .map(|claims| claims.sub.clone())
+            .unwrap_or_default();
+        let lane = format!("{} {}", endpoint, req.path());
+
+        Box::pin(async move {
+            if let Err(e) = quotas.check_rate(endpoint, &admin, &policy, Instant::now()) {
+                tracing::warn!("Admin {} hit the {} quota: {}", admin, endpoint, e);
+                return Err(quota_error_response(e));
+            }
+
+            let _permit = match quotas.enter(&lane, &policy).await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    tracing::warn!("Admin {} could not run {}: {}", admin, lane, e);
+                    return Err(quota_error_response(e));
+                }
+            };
+
+            service.call(req).await
+        })
+    }
+}
+
+fn quota_error_response(e: QuotaError) -> Error {
+    let mut response = match e {
+        QuotaError::RateLimited(retry_after) => {
+            let mut response = HttpResponse::TooManyRequests();
+            response.insert_header((header::RETRY_AFTER, retry_after.as_secs().max(1).to_string()));
+            response
+        }
+        QuotaError::QueueFull | QuotaError::QueueTimeout => HttpResponse::TooManyRequests(),
+    };
+    let message = e.to_string();
+    let response = response.json(json!({ "success": false, "error": message }));
+    InternalError::from_response(message, response).into()
+}
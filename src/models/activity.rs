@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Scoring profiles an activity can default to; the same names `WorkoutType` parses
+pub const SCORING_PROFILES: &[&str] = &["strength", "cardio", "hiit", "other"];
+
+/// Entry of the activity catalog
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: Uuid,
+    pub key: String,
+    pub name: String,
+    pub met_value: f32,
+    pub default_scoring_profile: String,
+    pub icon_key: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateActivityRequest {
+    pub key: String,
+    pub name: String,
+    pub met_value: f32,
+    pub default_scoring_profile: Option<String>, // Defaults to "other"
+    pub icon_key: Option<String>,                // Defaults to the key
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateActivityRequest {
+    pub name: Option<String>,
+    pub met_value: Option<f32>,
+    pub default_scoring_profile: Option<String>,
+    pub icon_key: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl CreateActivityRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.key.is_empty()
+            || self.key.len() > 50
+            || !self.key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err("Key must be 1-50 characters of a-z, 0-9 and _".to_string());
+        }
+        validate_name(&self.name)?;
+        validate_met_value(self.met_value)?;
+        if let Some(profile) = &self.default_scoring_profile {
+            validate_scoring_profile(profile)?;
+        }
+        if let Some(icon_key) = &self.icon_key {
+            validate_icon_key(icon_key)?;
+        }
+        Ok(())
+    }
+}
+
+impl UpdateActivityRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = &self.name {
+            validate_name(name)?;
+        }
+        if let Some(met_value) = self.met_value {
+            validate_met_value(met_value)?;
+        }
+        if let Some(profile) = &self.default_scoring_profile {
+            validate_scoring_profile(profile)?;
+        }
+        if let Some(icon_key) = &self.icon_key {
+            validate_icon_key(icon_key)?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err("Name must be 1-100 characters".to_string());
+    }
+    Ok(())
+}
+
+fn validate_met_value(met_value: f32) -> Result<(), String> {
+    if !(met_value > 0.0 && met_value <= 25.0) {
+        return Err("MET value must be greater than 0 and at most 25".to_string());
+    }
+    Ok(())
+}
+
+fn validate_scoring_profile(profile: &str) -> Result<(), String> {
+    if !SCORING_PROFILES.contains(&profile) {
+        return Err(format!("Scoring profile must be one of: {}", SCORING_PROFILES.join(", ")));
+    }
+    Ok(())
+}
+
+fn validate_icon_key(icon_key: &str) -> Result<(), String> {
+    if icon_key.is_empty() || icon_key.len() > 50 {
+        return Err("Icon key must be 1-50 characters".to_string());
+    }
+    Ok(())
+}
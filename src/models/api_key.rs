@@ -0,0 +1,127 @@
+use actix_web::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 100;
+
+/// What an API key may do on behalf of its user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    /// Read workouts and health data
+    WorkoutsRead,
+    /// Push, edit and delete workouts and health data
+    WorkoutsWrite,
+    /// Read the user's profile
+    ProfileRead,
+    /// Update the user's health profile
+    HealthProfileWrite,
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 4] = [
+        ApiKeyScope::WorkoutsRead,
+        ApiKeyScope::WorkoutsWrite,
+        ApiKeyScope::ProfileRead,
+        ApiKeyScope::HealthProfileWrite,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::WorkoutsRead => "workouts:read",
+            ApiKeyScope::WorkoutsWrite => "workouts:write",
+            ApiKeyScope::ProfileRead => "profile:read",
+            ApiKeyScope::HealthProfileWrite => "health_profile:write",
+        }
+    }
+
+    pub fn parse(scope: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == scope)
+    }
+
+    /// Whether the scope covers a request with this method to this path
+    pub fn allows(&self, method: &Method, path: &str) -> bool {
+        let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+        match self {
+            ApiKeyScope::WorkoutsRead => read && path.starts_with("/health/"),
+            ApiKeyScope::WorkoutsWrite => !read && path.starts_with("/health/"),
+            ApiKeyScope::ProfileRead => read && path.starts_with("/profile/"),
+            ApiKeyScope::HealthProfileWrite => !read && path == "/profile/health_profile",
+        }
+    }
+}
+
+/// Whether any of the scopes covers the request
+pub fn scopes_allow(scopes: &[String], method: &Method, path: &str) -> bool {
+    scopes
+        .iter()
+        .filter_map(|scope| ApiKeyScope::parse(scope))
+        .any(|scope| scope.allows(method, path))
+}
+
+/// An issued key as admins see it; the key itself is only shown once, when it's issued
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// A newly issued key, with the key to hand to the integration
+#[derive(Debug, Serialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueApiKeyRequest {
+    /// User the key acts as
+    pub user_id: Uuid,
+    /// Tells keys apart, e.g. the gym's name
+    pub name: String,
+    /// See `ApiKeyScope`, e.g. ["workouts:write"]
+    pub scopes: Vec<String>,
+    /// Omit for a key that works until it's revoked
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl IssueApiKeyRequest {
+    /// The requested scopes without duplicates, or why the request can't be issued
+    pub fn validate(&self, now: DateTime<Utc>) -> Result<Vec<String>, String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+            return Err(format!("Name must be 1-{MAX_NAME_LENGTH} characters"));
+        }
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err("expires_at must be in the future".to_string());
+        }
+        let mut scopes: Vec<String> = Vec::new();
+        for scope in &self.scopes {
+            let Some(scope) = ApiKeyScope::parse(scope) else {
+                let supported: Vec<&str> = ApiKeyScope::ALL.iter().map(|s| s.as_str()).collect();
+                return Err(format!("Unknown scope {scope}. Supported: {}", supported.join(", ")));
+            };
+            if !scopes.iter().any(|s| s == scope.as_str()) {
+                scopes.push(scope.as_str().to_string());
+            }
+        }
+        if scopes.is_empty() {
+            return Err("A key needs at least one scope".to_string());
+        }
+        Ok(scopes)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyListQuery {
+    pub user_id: Option<Uuid>,
+}
@@ -1,6 +1,8 @@
 // src/models/auth.rs
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use secrecy::SecretString;
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize)]
 pub struct LoginRequest {
@@ -13,6 +15,14 @@ pub struct LoginRequest {
 #[derive(Serialize, Deserialize)]
 pub struct LoginResponse {
     pub token: String,
+    /// Exchange at /refresh for a new token once this one expires; rotated on every use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -21,9 +31,87 @@ pub struct BiometricRefreshRequest {
 }
 
 #[derive(Serialize, Deserialize)]
-pub struct ResetPasswordRequest {
-    pub username: String,
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PasswordResetConfirmRequest {
+    /// Token from the link of the password reset email
+    pub token: String,
     #[serde(serialize_with = "crate::models::user::serialize_secret_string",
             deserialize_with = "crate::models::user::deserialize_secret_string")]
     pub new_password: SecretString,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MagicLinkRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MagicLinkConsumeRequest {
+    /// Token from the link of the sign-in email
+    pub token: String,
+}
+
+/// Identity providers users can sign in with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    Apple,
+    Google,
+}
+
+impl OAuthProvider {
+    /// Issuers the provider signs its ID tokens as
+    pub fn issuers(&self) -> &'static [&'static str] {
+        match self {
+            OAuthProvider::Apple => &["https://appleid.apple.com"],
+            OAuthProvider::Google => &["https://accounts.google.com", "accounts.google.com"],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OAuthLoginRequest {
+    /// ID token the app got from Sign in with Apple / Google
+    pub id_token: String,
+}
+
+/// Device a session was started on, as the app described it when logging in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionDevice {
+    pub device_id: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl SessionDevice {
+    /// Blank values are dropped and long ones cut short, since both come straight from request headers
+    pub fn new(device_id: Option<&str>, user_agent: Option<&str>) -> Self {
+        let clean = |value: Option<&str>, max_chars: usize| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| value.chars().take(max_chars).collect::<String>())
+        };
+        Self {
+            device_id: clean(device_id, 128),
+            user_agent: clean(user_agent, 512),
+        }
+    }
+}
+
+/// Logged in device of a user
+#[derive(Debug, Serialize)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub device_id: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Whether the request listing the sessions was made with this one
+    pub current: bool,
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Tracked body metrics with the range a value must fall in: weight (kg), HRV (ms), resting HR (bpm)
+pub const BODY_METRIC_TYPES: &[(&str, f32, f32)] = &[
+    ("weight", 20.0, 300.0),
+    ("hrv", 1.0, 300.0),
+    ("resting_hr", 30.0, 120.0),
+];
+
+pub const BODY_METRIC_SOURCES: &[&str] = &["manual", "device"];
+
+/// Entries accepted per request, enough for a device backfill
+pub const MAX_BODY_METRIC_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct BodyMetric {
+    pub id: Uuid,
+    pub metric_type: String,
+    pub value: f32,
+    pub recorded_at: DateTime<Utc>,
+    pub source: String,
+    pub device_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BodyMetricEntry {
+    pub metric_type: String,
+    pub value: f32,
+    pub recorded_at: Option<DateTime<Utc>>, // Defaults to now
+    pub source: Option<String>,             // Defaults to manual
+    pub device_id: Option<String>,
+    pub external_id: Option<String>,        // Device's id for the sample; re-syncs update the entry
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordBodyMetricsRequest {
+    pub entries: Vec<BodyMetricEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BodyMetricsQuery {
+    pub metric_type: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// One day of the analytics chart: training load next to the body metrics of that day
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyBodyMetrics {
+    pub date: NaiveDate,
+    /// Stamina plus strength gained from the day's workouts
+    pub training_load: f64,
+    pub workouts: i64,
+    pub weight: Option<f64>,
+    pub hrv: Option<f64>,
+    pub resting_hr: Option<f64>,
+}
+
+impl RecordBodyMetricsRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.entries.is_empty() {
+            return Err("At least one entry is required".to_string());
+        }
+        if self.entries.len() > MAX_BODY_METRIC_ENTRIES {
+            return Err(format!("At most {} entries are allowed per request", MAX_BODY_METRIC_ENTRIES));
+        }
+        for (idx, entry) in self.entries.iter().enumerate() {
+            entry.validate().map_err(|e| format!("Invalid entry at index {}: {}", idx, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl BodyMetricEntry {
+    pub fn validate(&self) -> Result<(), String> {
+        let Some((_, min, max)) = BODY_METRIC_TYPES.iter().find(|(name, _, _)| *name == self.metric_type) else {
+            let names: Vec<&str> = BODY_METRIC_TYPES.iter().map(|(name, _, _)| *name).collect();
+            return Err(format!("Metric type must be one of: {}", names.join(", ")));
+        };
+        if !(*min..=*max).contains(&self.value) {
+            return Err(format!("{} must be between {} and {}", self.metric_type, min, max));
+        }
+        if let Some(source) = &self.source {
+            if !BODY_METRIC_SOURCES.contains(&source.as_str()) {
+                return Err(format!("Source must be one of: {}", BODY_METRIC_SOURCES.join(", ")));
+            }
+        }
+        if let Some(recorded_at) = self.recorded_at {
+            if recorded_at > Utc::now() + chrono::Duration::minutes(5) {
+                return Err("recorded_at cannot be in the future".to_string());
+            }
+        }
+        Ok(())
+    }
+}
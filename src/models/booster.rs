@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A team booster as stored in `team_boosters`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TeamBooster {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub booster_type: String,
+    pub status: String,
+    pub earned_for: String,
+    pub earned_in_game_id: Option<Uuid>,
+    pub earned_at: DateTime<Utc>,
+    pub game_id: Option<Uuid>,
+    pub activated_by: Option<Uuid>,
+    pub activated_at: Option<DateTime<Utc>>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub bonus_points: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivateBoosterRequest {
+    /// double_next_workout or shield
+    pub booster_type: String,
+}
+
+/// Unused boosters of one kind
+#[derive(Debug, Clone, Serialize)]
+pub struct BoosterInventory {
+    pub booster_type: String,
+    pub available: i64,
+}
+
+/// Boosters of a game, as returned by `/league/games/{game_id}/boosters`
+#[derive(Debug, Clone, Serialize)]
+pub struct GameBoosters {
+    pub game_id: Uuid,
+    /// Team of the requesting user in the game, if they play in it
+    pub team_id: Option<Uuid>,
+    /// Boosters the requesting user's team can still activate
+    pub inventory: Vec<BoosterInventory>,
+    /// Boosters either team activated in this game
+    pub boosters: Vec<TeamBooster>,
+}
+
+/// An entry of a team's booster audit trail
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BoosterAuditEntry {
+    pub id: Uuid,
+    pub booster_id: Uuid,
+    pub team_id: Uuid,
+    pub game_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub action: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
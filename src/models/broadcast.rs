@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Audiences an announcement can be sent to
+pub const BROADCAST_TARGET_TYPES: &[&str] = &["all", "league", "team"];
+
+/// Channels an announcement can be delivered through
+pub const BROADCAST_CHANNELS: &[&str] = &["websocket", "push", "in_app"];
+
+/// Announcement sent by an admin, with its delivery stats once sent
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AdminBroadcast {
+    pub id: Uuid,
+    pub created_by: Uuid,
+    pub title: String,
+    pub message: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub channels: Vec<String>,
+    pub status: String,
+    pub scheduled_for: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub recipient_count: i32,
+    pub websocket_count: i32,
+    pub push_count: i32,
+    pub in_app_count: i32,
+    pub failed_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Broadcast with how many recipients have read it in the notification center
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminBroadcastWithStats {
+    #[serde(flatten)]
+    pub broadcast: AdminBroadcast,
+    pub read_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBroadcastRequest {
+    pub title: String,
+    pub message: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub channels: Option<Vec<String>>,          // Defaults to all channels
+    pub scheduled_for: Option<DateTime<Utc>>,   // Sent right away when omitted
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastListQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl CreateBroadcastRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let title = self.title.trim();
+        if title.is_empty() || title.len() > 100 {
+            return Err("Title must be 1-100 characters".to_string());
+        }
+        let message = self.message.trim();
+        if message.is_empty() || message.len() > 1000 {
+            return Err("Message must be 1-1000 characters".to_string());
+        }
+        if !BROADCAST_TARGET_TYPES.contains(&self.target_type.as_str()) {
+            return Err(format!("Target type must be one of: {}", BROADCAST_TARGET_TYPES.join(", ")));
+        }
+        match (self.target_type.as_str(), self.target_id) {
+            ("all", Some(_)) => return Err("target_id must be omitted when sending to all users".to_string()),
+            ("league" | "team", None) => return Err(format!("target_id is required for a {} announcement", self.target_type)),
+            _ => {}
+        }
+        if let Some(channels) = &self.channels {
+            if channels.is_empty() {
+                return Err("At least one channel is required".to_string());
+            }
+            if let Some(channel) = channels.iter().find(|c| !BROADCAST_CHANNELS.contains(&c.as_str())) {
+                return Err(format!("Unknown channel '{}', expected one of: {}", channel, BROADCAST_CHANNELS.join(", ")));
+            }
+        }
+        if let Some(scheduled_for) = self.scheduled_for {
+            if scheduled_for <= Utc::now() {
+                return Err("scheduled_for must be in the future".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Requested channels without duplicates, defaulting to all of them
+    pub fn channels(&self) -> Vec<String> {
+        let requested = self.channels.as_deref().unwrap_or_default();
+        BROADCAST_CHANNELS
+            .iter()
+            .filter(|channel| requested.is_empty() || requested.iter().any(|c| c == *channel))
+            .map(|channel| channel.to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Game moments the commentator calls out, stored as `game_commentary.milestone`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentaryMilestone {
+    FirstScore,
+    LeadChange,
+    FinalMinutes,
+    MvpCandidate,
+}
+
+impl CommentaryMilestone {
+    pub const ALL: [CommentaryMilestone; 4] = [
+        CommentaryMilestone::FirstScore,
+        CommentaryMilestone::LeadChange,
+        CommentaryMilestone::FinalMinutes,
+        CommentaryMilestone::MvpCandidate,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentaryMilestone::FirstScore => "first_score",
+            CommentaryMilestone::LeadChange => "lead_change",
+            CommentaryMilestone::FinalMinutes => "final_minutes",
+            CommentaryMilestone::MvpCandidate => "mvp_candidate",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "first_score" => Some(CommentaryMilestone::FirstScore),
+            "lead_change" => Some(CommentaryMilestone::LeadChange),
+            "final_minutes" => Some(CommentaryMilestone::FinalMinutes),
+            "mvp_candidate" => Some(CommentaryMilestone::MvpCandidate),
+            _ => None,
+        }
+    }
+}
+
+/// A commentary line as stored and returned by `/league/games/{game_id}/commentary`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GameCommentary {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub milestone: String,
+    pub locale: String,
+    pub message: String,
+    pub params: serde_json::Value,
+    pub user_id: Option<Uuid>,
+    pub team_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Per-league commentator settings
+#[derive(Debug, Clone)]
+pub struct CommentarySettings {
+    pub enabled: bool,
+    pub locale: String,
+    pub milestones: Vec<String>,
+}
+
+impl CommentarySettings {
+    pub fn covers(&self, milestone: CommentaryMilestone) -> bool {
+        self.enabled && self.milestones.iter().any(|m| m == milestone.as_str())
+    }
+}
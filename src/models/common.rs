@@ -56,6 +56,25 @@ impl<T> ApiResponse<T> {
     }
 }
 
+/// A validation problem with one field of a request, so apps can show it next to that field
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    /// Stable identifier of the rule that failed, e.g. "too_short"
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Common player statistics used across different contexts
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PlayerStats {
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Hours between cup rounds unless the admin picks otherwise
+pub const DEFAULT_ROUND_INTERVAL_HOURS: i32 = 168;
+const MAX_ROUND_INTERVAL_HOURS: i32 = 720;
+const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCupRequest {
+    pub name: String,
+    /// Kickoff of the first round
+    pub start_date: DateTime<Utc>,
+    pub round_interval_hours: Option<i32>,
+    /// Teams of the season in seed order; all of them, seeded by the standings, if omitted
+    pub team_ids: Option<Vec<Uuid>>,
+}
+
+impl CreateCupRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+            return Err(format!("Name must be 1-{MAX_NAME_LENGTH} characters"));
+        }
+        if !(1..=MAX_ROUND_INTERVAL_HOURS).contains(&self.round_interval_hours()) {
+            return Err(format!("round_interval_hours must be between 1 and {MAX_ROUND_INTERVAL_HOURS}"));
+        }
+        if let Some(team_ids) = &self.team_ids {
+            if team_ids.len() < 2 {
+                return Err("A cup needs at least 2 teams".to_string());
+            }
+            let mut unique = team_ids.clone();
+            unique.sort();
+            unique.dedup();
+            if unique.len() != team_ids.len() {
+                return Err("Teams can only be entered once".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn round_interval_hours(&self) -> i32 {
+        self.round_interval_hours.unwrap_or(DEFAULT_ROUND_INTERVAL_HOURS)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SeasonCup {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub name: String,
+    pub start_date: DateTime<Utc>,
+    pub round_interval_hours: i32,
+    /// "active" or "finished"
+    pub status: String,
+    pub winner_team_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CupTeam {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub seed: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CupGame {
+    pub game_id: Uuid,
+    pub slot: i32,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub status: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub winner_team_id: Option<Uuid>,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CupRound {
+    pub round: i32,
+    pub games: Vec<CupGame>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CupBracket {
+    pub cup: SeasonCup,
+    pub teams: Vec<CupTeam>,
+    /// Rounds a knockout of this many teams takes, including the ones not drawn yet
+    pub total_rounds: i32,
+    pub rounds: Vec<CupRound>,
+}
+
+/// A game of the season's combined calendar, from the league or one of its cups
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarGame {
+    pub game_id: Uuid,
+    /// "league" or "cup"
+    pub competition: String,
+    pub cup_id: Option<Uuid>,
+    pub cup_name: Option<String>,
+    /// League week, or cup round
+    pub round: i32,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub status: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub winner_team_id: Option<Uuid>,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+}
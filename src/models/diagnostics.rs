@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Days upload attempts are kept for diagnostics
+pub const UPLOAD_ATTEMPT_RETENTION_DAYS: i64 = 30;
+const DEFAULT_DAYS: i64 = 14;
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+/// What became of a workout the app tried to upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadOutcome {
+    /// Stored and scored in a running game
+    Accepted,
+    /// Not uploaded, it overlaps a workout that was already synced
+    Duplicate,
+    /// Stored, but no running game of the user's team covered it
+    OutOfWindow,
+    /// Stored, but it doesn't count because it overlaps a suspension
+    Flagged,
+    /// Refused, e.g. for a missing or expired approval token
+    Rejected,
+}
+
+impl UploadOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadOutcome::Accepted => "accepted",
+            UploadOutcome::Duplicate => "duplicate",
+            UploadOutcome::OutOfWindow => "out_of_window",
+            UploadOutcome::Flagged => "flagged",
+            UploadOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiagnosticsQuery {
+    /// How far back to look, up to the retention
+    pub days: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl DiagnosticsQuery {
+    pub fn days(&self) -> i64 {
+        self.days.unwrap_or(DEFAULT_DAYS).clamp(1, UPLOAD_ATTEMPT_RETENTION_DAYS)
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+/// A workout's upload attempts with one outcome
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadAttempt {
+    pub id: Uuid,
+    pub device_id: Option<String>,
+    pub workout_uuid: String,
+    pub outcome: String,
+    pub detail: Option<String>,
+    /// The stored workout, for accepted uploads or the one a duplicate overlaps
+    pub workout_data_id: Option<Uuid>,
+    pub workout_start: Option<DateTime<Utc>>,
+    pub workout_end: Option<DateTime<Utc>>,
+    pub attempt_count: i32,
+    pub first_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadOutcomeCount {
+    pub outcome: String,
+    pub attempts: i64,
+}
+
+/// When a device last synced, by what it did
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceSync {
+    pub device_id: String,
+    /// Latest workout stored from the device
+    pub last_upload_at: Option<DateTime<Utc>>,
+    /// Latest upload attempt, whatever its outcome
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// Latest Health Connect delta sync
+    pub last_delta_sync_at: Option<DateTime<Utc>>,
+    pub change_token_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncDiagnostics {
+    pub user_id: Uuid,
+    pub days: i64,
+    pub outcome_counts: Vec<UploadOutcomeCount>,
+    /// Newest first
+    pub attempts: Vec<UploadAttempt>,
+    pub devices: Vec<DeviceSync>,
+}
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::models::common::{MatchResult, PlayerStats, TeamStandings};
+use crate::models::league::GameTransition;
 
 /// Game-specific WebSocket message types
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -189,6 +190,25 @@ pub enum GameEvent {
         timestamp: DateTime<Utc>,
     },
 
+    #[serde(rename = "reaction_added")]
+    ReactionAdded {
+        target_type: String,
+        target_id: Uuid,
+        user_id: Uuid,
+        username: String,
+        reaction_type: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    #[serde(rename = "reaction_removed")]
+    ReactionRemoved {
+        target_type: String,
+        target_id: Uuid,
+        user_id: Uuid,
+        username: String,
+        timestamp: DateTime<Utc>,
+    },
+
     #[serde(rename = "notification_received")]
     NotificationReceived {
         recipient_id: Uuid,
@@ -255,6 +275,191 @@ pub enum GameEvent {
         team_name: String,
         timestamp: DateTime<Utc>,
     },
+
+    // Media removed by moderation; clients drop it from cached feeds and workouts
+    #[serde(rename = "media_taken_down")]
+    MediaTakenDown {
+        file_url: String,
+        post_ids: Vec<Uuid>,
+        workout_ids: Vec<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+
+    // Automated commentary at game milestones; params let clients re-render the line in their own language
+    #[serde(rename = "game_commentary")]
+    GameCommentary {
+        commentary_id: Uuid,
+        game_id: Uuid,
+        milestone: String,
+        locale: String,
+        message: String,
+        params: serde_json::Value,
+        timestamp: DateTime<Utc>,
+    },
+
+    // Scores, top contributors and pace against previous meetings at the midpoint of a game
+    #[serde(rename = "halftime_report")]
+    HalftimeReport {
+        report_id: Uuid,
+        game_id: Uuid,
+        home_score: i32,
+        away_score: i32,
+        top_contributors: serde_json::Value,
+        home_pace: serde_json::Value,
+        away_pace: serde_json::Value,
+        message: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    // Announcement from the admins, sent to each recipient's user channel
+    #[serde(rename = "admin_broadcast")]
+    AdminBroadcast {
+        broadcast_id: Uuid,
+        title: String,
+        message: String,
+        target_type: String,
+        target_id: Option<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+
+    // Team boosters: earned from achievements, activated during a live game and applied by the scoring engine
+    #[serde(rename = "booster_earned")]
+    BoosterEarned {
+        booster_id: Uuid,
+        team_id: Uuid,
+        booster_type: String,
+        earned_for: String,
+        game_id: Option<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+
+    #[serde(rename = "booster_activated")]
+    BoosterActivated {
+        booster_id: Uuid,
+        game_id: Uuid,
+        team_id: Uuid,
+        booster_type: String,
+        user_id: Uuid,
+        username: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    #[serde(rename = "booster_applied")]
+    BoosterApplied {
+        booster_id: Uuid,
+        game_id: Uuid,
+        team_id: Uuid,
+        booster_type: String,
+        bonus_points: f32,
+        score_event_id: Option<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+
+    #[serde(rename = "booster_expired")]
+    BoosterExpired {
+        booster_id: Uuid,
+        game_id: Uuid,
+        team_id: Uuid,
+        booster_type: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    // League quest progress passed one of its milestones
+    #[serde(rename = "quest_milestone_reached")]
+    QuestMilestoneReached {
+        quest_id: Uuid,
+        league_id: Uuid,
+        quest_name: String,
+        percent: i32,
+        progress_points: f64,
+        target_points: f64,
+        message: String,
+        reached_by: Uuid,
+        timestamp: DateTime<Utc>,
+    },
+
+    // Sent with every WebSocket heartbeat so clients can correct for clock skew
+    #[serde(rename = "heartbeat")]
+    Heartbeat {
+        server_time: DateTime<Utc>,
+        next_transition: Option<GameTransition>,
+    },
+
+    // Authoritative state of the live games a client shows, replacing whatever it pieced
+    // together from single events. Sent on request and whenever events may have been missed.
+    #[serde(rename = "state_sync")]
+    StateSync {
+        reason: StateSyncReason,
+        server_time: DateTime<Utc>,
+        games: Vec<LiveGameSnapshot>,
+    },
+
+    // Live games of a season a client spectates, sent when it starts spectating and whenever
+    // it may have missed score deltas. Scoreboards leave out the games' scoring events.
+    #[serde(rename = "season_scoreboard")]
+    SeasonScoreboard {
+        season_id: Uuid,
+        server_time: DateTime<Utc>,
+        games: Vec<LiveGameSnapshot>,
+    },
+
+    // A score of one game changed, published to the season's channel for spectating clients
+    #[serde(rename = "score_delta")]
+    ScoreDelta {
+        season_id: Uuid,
+        game_id: Uuid,
+        team_side: String,
+        points: f32,
+        home_score: u32,
+        away_score: u32,
+        game_progress: f32,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Why a state sync was sent
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StateSyncReason {
+    /// The client asked for it
+    Requested,
+    /// The connection's event subscriptions are set up, after connecting or reconnecting
+    Subscribed,
+    /// The server's event subscription dropped and was set up again
+    Resubscribed,
+    /// The client went quiet for longer than a heartbeat and is back
+    Gap,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiveGameSnapshot {
+    pub game_id: Uuid,
+    pub week_number: i32,
+    pub status: String,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub home_score: u32,
+    pub away_score: u32,
+    pub game_progress: f32,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+    /// Latest scoring events, newest first
+    pub recent_events: Vec<ScoreEventSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScoreEventSummary {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub team_id: Uuid,
+    pub team_side: String,
+    pub score_points: f32,
+    pub description: String,
+    pub occurred_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
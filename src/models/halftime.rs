@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+/// Whether a team is set to score more or less than in previous meetings with the same opponent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pace {
+    Ahead,
+    OnPace,
+    Behind,
+}
+
+/// A team's halftime score projected to the end of the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamPace {
+    pub projected_score: i32,
+    pub previous_meetings: usize,
+    /// Average final score in previous meetings
+    pub previous_average: Option<f32>,
+    pub pace: Option<Pace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HalftimeContributor {
+    pub user_id: Uuid,
+    pub username: String,
+    pub team_side: String,
+    pub points: i32,
+}
+
+/// Report written at the midpoint of a game
+#[derive(Debug, Clone, sqlx::FromRow, Serialize)]
+pub struct HalftimeReport {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub top_contributors: Json<Vec<HalftimeContributor>>,
+    pub home_pace: Json<TeamPace>,
+    pub away_pace: Json<TeamPace>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
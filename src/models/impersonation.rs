@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How long an impersonation token can be used
+pub const IMPERSONATION_TOKEN_MINUTES: i64 = 30;
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ImpersonateUserRequest {
+    /// Why support needs to act as the user, e.g. the ticket being investigated
+    pub reason: String,
+}
+
+impl ImpersonateUserRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let reason = self.reason.trim();
+        if reason.is_empty() || reason.len() > MAX_REASON_LENGTH {
+            return Err(format!("Reason must be 1-{MAX_REASON_LENGTH} characters"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImpersonationTokenResponse {
+    pub impersonation_id: Uuid,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Audit entry of an impersonation token issued to an admin
+#[derive(Debug, Clone, Serialize)]
+pub struct Impersonation {
+    pub id: Uuid,
+    pub admin_id: Option<Uuid>,
+    pub admin_username: Option<String>,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -27,6 +27,7 @@ pub struct LeagueSeason {
     pub auto_evaluation_enabled: Option<bool>, // Whether automatic evaluation is enabled
     pub game_duration_seconds: i64, // Duration of each game in seconds (default: 518400 = 6 days)
     pub games_per_matchup: Option<i32>, // Number of games per team matchup: 1 = single round-robin, 2 = double round-robin (default: 2)
+    pub inactivity_nudge_days: Option<i32>, // Days without an upload before members are nudged (NULL = disabled)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -203,6 +204,8 @@ pub struct LeagueScheduleResponse {
     pub games: Vec<GameWithTeams>,
     pub next_game_time: DateTime<Utc>,
     pub total_weeks: i32,
+    pub week_names: Vec<GameWeekName>,
+    pub fixture_flavors: Vec<FixtureFlavor>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -235,9 +238,63 @@ pub struct StandingWithTeam {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NextGameInfo {
     pub next_game: Option<GameWithTeams>,
-    pub countdown_seconds: Option<i64>,
+    pub countdown_seconds: Option<i64>, // Seconds from server_time until next_game starts
     pub week_number: Option<i32>,
     pub games_this_week: Vec<GameWithTeams>,
+    pub server_time: DateTime<Utc>,
+    pub next_transition: Option<GameTransition>,
+}
+
+/// The server's clock plus the next game status change, so clients can count down against
+/// the backend instead of their own (possibly skewed) clock
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameClock {
+    pub server_time: DateTime<Utc>,
+    pub next_transition: Option<GameTransition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameTransition {
+    pub game_id: Uuid,
+    pub week_number: i32,
+    pub kind: GameTransitionKind,
+    /// When the game scheduler will switch the game; may already be past if its next run is pending
+    pub at: DateTime<Utc>,
+}
+
+/// When a user's workouts count towards their current or next game
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameWindow {
+    pub game_id: Uuid,
+    pub season_id: Uuid,
+    pub week_number: i32,
+    pub status: GameStatus,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub opponent_team_id: Uuid,
+    pub opponent_team_name: String,
+    /// Workouts must start at or after this (game start, or when the user joined the team)
+    pub record_from: DateTime<Utc>,
+    /// ... and end at or before this
+    pub record_until: DateTime<Utc>,
+    /// Uploads after this no longer score, and earlier if the game is evaluated first
+    pub upload_until: DateTime<Utc>,
+    pub is_recording_open: bool,
+    pub is_upload_open: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextGameWindowResponse {
+    pub server_time: DateTime<Utc>,
+    pub upload_grace_minutes: i32,
+    pub window: Option<GameWindow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameTransitionKind {
+    GameStart, // scheduled -> in_progress, workouts start counting
+    GameEnd,   // in_progress -> finished
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -247,6 +304,8 @@ pub struct GameWeekResponse {
     pub games: Vec<GameWithTeams>,
     pub is_current_week: bool,
     pub countdown_seconds: Option<i64>, // Only for current week
+    pub week_name: Option<GameWeekName>,
+    pub fixture_flavors: Vec<FixtureFlavor>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -382,4 +441,249 @@ pub struct GameSummaryResponse {
     pub summary: GameSummary,
     pub home_team_name: String,
     pub away_team_name: String,
-}
\ No newline at end of file
+    /// Bonuses the teams earned for their mix of workout archetypes
+    #[serde(default)]
+    pub formation_bonuses: Vec<GameFormationBonus>,
+}
+
+/// Bonus a team earned in a game for covering several workout archetypes
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct GameFormationBonus {
+    pub team_id: Uuid,
+    pub archetypes: Vec<String>,
+    pub bonus_points: f32,
+    pub description: String,
+}
+/// Team waiting for a slot in a full league
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct LeagueWaitlistEntry {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderWaitlistRequest {
+    pub team_ids: Vec<Uuid>, // All waitlisted teams, in their new order
+}
+
+/// Generator settings and fairness metrics of a generated season schedule
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduleFairness {
+    pub balance_home_away: bool,
+    pub spread_strong_teams: bool,
+    pub rated_teams: i32, // Teams with a rating from a previous season; the rest count as average
+    pub max_home_away_imbalance: i32, // Largest |home games - away games| of any team
+    pub total_breaks: i32, // Back-to-back home or back-to-back away games, summed over teams
+    pub strong_opponent_back_to_backs: i32, // Consecutive weeks against above-median opponents, summed over teams
+    pub max_strong_opponent_streak: i32, // Longest run of weeks against above-median opponents
+}
+
+/// Name league admins gave a game week, e.g. "Derby Week"
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct GameWeekName {
+    pub season_id: Uuid,
+    pub week_number: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Title, venue, description and banner league admins gave a fixture
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct FixtureFlavor {
+    pub game_id: Uuid,
+    pub title: Option<String>,
+    pub venue_name: Option<String>,
+    pub description: Option<String>,
+    pub banner_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetGameWeekNameRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl SetGameWeekNameRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > 60 {
+            return Err("Week name must be 1 to 60 characters".to_string());
+        }
+        validate_flavor_description(self.description.as_deref())
+    }
+}
+
+/// Replaces the fixture's flavor; omitted fields are cleared
+#[derive(Debug, Deserialize)]
+pub struct SetFixtureFlavorRequest {
+    pub title: Option<String>,
+    pub venue_name: Option<String>,
+    pub description: Option<String>,
+    pub banner_url: Option<String>,
+}
+
+impl SetFixtureFlavorRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.is_none() && self.venue_name.is_none() && self.description.is_none() && self.banner_url.is_none() {
+            return Err("At least one of title, venue_name, description or banner_url must be provided".to_string());
+        }
+        if self.title.as_deref().is_some_and(|t| t.trim().is_empty() || t.chars().count() > 80) {
+            return Err("Title must be 1 to 80 characters".to_string());
+        }
+        if self.venue_name.as_deref().is_some_and(|v| v.trim().is_empty() || v.chars().count() > 80) {
+            return Err("Venue name must be 1 to 80 characters".to_string());
+        }
+        if let Some(banner_url) = &self.banner_url {
+            if !url::Url::parse(banner_url).is_ok_and(|url| url.scheme() == "https") {
+                return Err("Banner URL must be an https URL".to_string());
+            }
+        }
+        validate_flavor_description(self.description.as_deref())
+    }
+}
+
+fn validate_flavor_description(description: Option<&str>) -> Result<(), String> {
+    if description.is_some_and(|d| d.chars().count() > 500) {
+        return Err("Description cannot exceed 500 characters".to_string());
+    }
+    Ok(())
+}
+
+/// Chat tool a league cross-posts its game results to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ChatProvider {
+    Slack,
+    Teams,
+}
+
+/// Incoming webhook of a league's Slack or Microsoft Teams channel
+#[derive(Debug, Serialize)]
+pub struct LeagueChatWebhook {
+    pub id: Uuid,
+    pub league_id: Uuid,
+    pub provider: ChatProvider,
+    pub webhook_url: String,
+    pub enabled: bool,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Result card that could not be posted yet. `next_attempt_at` is None once it ran out of attempts.
+#[derive(Debug, Serialize)]
+pub struct FailedWebhookDelivery {
+    pub id: Uuid,
+    pub provider: ChatProvider,
+    pub game_id: Uuid,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeagueChatWebhookSettings {
+    pub webhooks: Vec<LeagueChatWebhook>,
+    pub failed_deliveries: Vec<FailedWebhookDelivery>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetLeagueChatWebhookRequest {
+    pub provider: ChatProvider,
+    pub webhook_url: String,
+    pub enabled: Option<bool>,
+}
+
+impl SetLeagueChatWebhookRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if !url::Url::parse(&self.webhook_url).is_ok_and(|url| url.scheme() == "https") {
+            return Err("Webhook URL must be an https URL".to_string());
+        }
+        if self.webhook_url.len() > 2000 {
+            return Err("Webhook URL cannot exceed 2000 characters".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Period in which players may move between the league's teams
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransferWindow {
+    pub opens_at: DateTime<Utc>,
+    pub closes_at: DateTime<Utc>,
+    pub note: Option<String>,
+}
+
+impl TransferWindow {
+    pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+        self.opens_at <= at && at < self.closes_at
+    }
+}
+
+/// Limits the league enforces, read from its configuration
+#[derive(Debug, Serialize)]
+pub struct LeagueCaps {
+    pub max_teams: i32,
+    pub max_team_size: i64,
+    pub max_held_boosters_per_type: i64,
+}
+
+/// Everything the app shows on a league's rules page. The written parts are None until an
+/// admin sets them; caps and the comeback rule are always current.
+#[derive(Debug, Serialize)]
+pub struct LeagueRulesPage {
+    pub league_id: Uuid,
+    pub league_name: String,
+    pub scoring_summary: Option<String>,
+    pub comeback_multiplier: Option<f32>,
+    pub caps: LeagueCaps,
+    pub forfeit_policy: Option<String>,
+    pub transfer_windows: Vec<TransferWindow>,
+    pub transfer_window_open: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Replaces the league's written rules; omitted transfer windows are removed
+#[derive(Debug, Deserialize)]
+pub struct SetLeagueRulesRequest {
+    pub scoring_summary: String,
+    pub forfeit_policy: Option<String>,
+    #[serde(default)]
+    pub transfer_windows: Vec<TransferWindow>,
+}
+
+impl SetLeagueRulesRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let scoring_summary = self.scoring_summary.trim();
+        if scoring_summary.is_empty() || scoring_summary.chars().count() > 4000 {
+            return Err("Scoring summary must be 1 to 4000 characters".to_string());
+        }
+        if self.forfeit_policy.as_deref().is_some_and(|p| p.trim().is_empty() || p.chars().count() > 2000) {
+            return Err("Forfeit policy must be 1 to 2000 characters".to_string());
+        }
+        if self.transfer_windows.len() > 20 {
+            return Err("A league can have at most 20 transfer windows".to_string());
+        }
+
+        let mut windows: Vec<&TransferWindow> = self.transfer_windows.iter().collect();
+        windows.sort_by_key(|window| window.opens_at);
+        for window in &windows {
+            if window.closes_at <= window.opens_at {
+                return Err("Transfer windows must close after they open".to_string());
+            }
+            if window.note.as_deref().is_some_and(|n| n.chars().count() > 200) {
+                return Err("Transfer window notes cannot exceed 200 characters".to_string());
+            }
+        }
+        if windows.windows(2).any(|pair| pair[1].opens_at < pair[0].closes_at) {
+            return Err("Transfer windows cannot overlap".to_string());
+        }
+        Ok(())
+    }
+}
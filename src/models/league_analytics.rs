@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct LeagueAnalyticsQuery {
+    /// Defaults to the running season, or the most recent one
+    pub season_id: Option<Uuid>,
+}
+
+/// Engagement of a league's members over one season
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeagueAnalytics {
+    pub league_id: Uuid,
+    pub season_id: Uuid,
+    pub season_name: String,
+    pub generated_at: DateTime<Utc>,
+    /// Active members of the season's teams
+    pub member_count: usize,
+    /// Season weeks started so far, the current one included
+    pub weeks_elapsed: u32,
+    /// Share of members with at least one workout this season
+    pub participation_rate: f64,
+    pub avg_workouts_per_user_per_week: f64,
+    /// Members without a single workout this season
+    pub never_active: usize,
+    pub weekly: Vec<WeeklyParticipation>,
+    /// Points of single workouts
+    pub workout_score_distribution: ScoreDistribution,
+    /// Season points of each member who worked out
+    pub member_score_distribution: ScoreDistribution,
+    pub drop_off_cohorts: Vec<DropOffCohort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyParticipation {
+    /// Season week, starting at 1
+    pub week: u32,
+    pub week_start: DateTime<Utc>,
+    pub active_members: usize,
+    pub participation_rate: f64,
+    pub workouts: usize,
+    pub avg_workouts_per_user: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    pub count: usize,
+    pub min: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub buckets: Vec<ScoreBucket>,
+}
+
+/// Values in [from, to); the last bucket also holds the maximum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreBucket {
+    pub from: f64,
+    pub to: f64,
+    pub count: usize,
+}
+
+/// Members grouped by the season week of their first workout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropOffCohort {
+    pub first_active_week: u32,
+    pub members: usize,
+    /// Members of the cohort who worked out in each week, starting with `first_active_week`
+    pub active_by_week: Vec<usize>,
+    /// Members of the cohort without a workout in the last 14 days
+    pub dropped_off: usize,
+}
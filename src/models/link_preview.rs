@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::workout_data::OpenGraphSummary;
+
+/// Share link tokens are alphanumeric (see `share_links::generate_share_token`)
+const MAX_SHARE_TOKEN_LENGTH: usize = 64;
+
+/// What a shared app link points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreviewTarget {
+    Game(Uuid),
+    Team(Uuid),
+    /// Workouts are only previewed through their public share link
+    SharedWorkout(String),
+    ShareCard(Uuid),
+}
+
+impl PreviewTarget {
+    /// Parse an app or API path such as `/games/{id}`, `/league/teams/{id}` or
+    /// `/public/workouts/{token}`. Full URLs, query strings and fragments are tolerated.
+    pub fn parse(path: &str) -> Option<Self> {
+        let path = path.trim();
+        let path = match path.split_once("://") {
+            Some((_, rest)) => rest.find('/').map_or("", |i| &rest[i..]),
+            None => path,
+        };
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match segments.as_slice() {
+            ["games", id] | ["league", "games", id] => Uuid::parse_str(id).ok().map(Self::Game),
+            ["teams", id] | ["league", "teams", id] => Uuid::parse_str(id).ok().map(Self::Team),
+            ["workouts", token] | ["public", "workouts", token] => {
+                let valid = !token.is_empty()
+                    && token.len() <= MAX_SHARE_TOKEN_LENGTH
+                    && token.chars().all(|c| c.is_ascii_alphanumeric());
+                valid.then(|| Self::SharedWorkout(token.to_string()))
+            }
+            ["share", "cards", id] => Uuid::parse_str(id).ok().map(Self::ShareCard),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Game(_) => "game",
+            Self::Team(_) => "team",
+            Self::SharedWorkout(_) => "workout",
+            Self::ShareCard(_) => "share_card",
+        }
+    }
+
+    /// Canonical path of the target in the apps
+    pub fn canonical_path(&self) -> String {
+        match self {
+            Self::Game(id) => format!("/games/{id}"),
+            Self::Team(id) => format!("/teams/{id}"),
+            Self::SharedWorkout(token) => format!("/public/workouts/{token}"),
+            Self::ShareCard(id) => format!("/share/cards/{id}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LinkPreviewQuery {
+    pub path: String,
+}
+
+/// Open Graph metadata of a shared link, for unfurling in chat apps and on the marketing site
+#[derive(Debug, Serialize)]
+pub struct LinkPreview {
+    pub kind: &'static str,
+    pub path: String,
+    #[serde(flatten)]
+    pub og: OpenGraphSummary,
+}
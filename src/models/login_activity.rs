@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const DEFAULT_EVENT_LIMIT: i64 = 100;
+const MAX_EVENT_LIMIT: i64 = 500;
+
+/// Why a login was turned down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginFailure {
+    /// Unknown username or wrong password
+    InvalidCredentials,
+    /// The account or the address it came from is locked after too many failed logins
+    Locked,
+}
+
+impl LoginFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginFailure::InvalidCredentials => "invalid_credentials",
+            LoginFailure::Locked => "locked",
+        }
+    }
+}
+
+/// A login to the user's account, as listed in their login activity
+#[derive(Debug, Serialize)]
+pub struct LoginAttempt {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub succeeded: bool,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityEventType {
+    /// An account was locked after too many failed logins
+    AccountLocked,
+    /// An IP address was blocked after too many failed logins to any account
+    AddressBlocked,
+    /// An admin lifted the lockout of an account
+    AccountUnlocked,
+    /// A login went through from a new address or right after a run of failed ones
+    SuspiciousLogin,
+}
+
+impl SecurityEventType {
+    pub const ALL: [SecurityEventType; 4] = [
+        SecurityEventType::AccountLocked,
+        SecurityEventType::AddressBlocked,
+        SecurityEventType::AccountUnlocked,
+        SecurityEventType::SuspiciousLogin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityEventType::AccountLocked => "account_locked",
+            SecurityEventType::AddressBlocked => "address_blocked",
+            SecurityEventType::AccountUnlocked => "account_unlocked",
+            SecurityEventType::SuspiciousLogin => "suspicious_login",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event_type| event_type.as_str() == value)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SecurityEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub user_id: Option<Uuid>,
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SecurityEventQuery {
+    pub user_id: Option<Uuid>,
+    pub event_type: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl SecurityEventQuery {
+    /// The requested event type, if any. Errs on unknown types.
+    pub fn event_type(&self) -> Result<Option<SecurityEventType>, String> {
+        match &self.event_type {
+            None => Ok(None),
+            Some(value) => SecurityEventType::parse(value)
+                .map(Some)
+                .ok_or_else(|| format!("Unknown event type '{}'", value)),
+        }
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_EVENT_LIMIT).clamp(1, MAX_EVENT_LIMIT)
+    }
+}
@@ -14,3 +14,24 @@ pub mod team_invitation;
 pub mod analytics;
 pub mod chat;
 pub mod notification;
+pub mod activity;
+pub mod commentary;
+pub mod broadcast;
+pub mod scheduled_job;
+pub mod body_metrics;
+pub mod organization;
+pub mod research;
+pub mod league_analytics;
+pub mod booster;
+pub mod quest;
+pub mod share_card;
+pub mod link_preview;
+pub mod suspension;
+#[cfg(feature = "sandbox")]
+pub mod sandbox;
+pub mod halftime;
+pub mod api_key;
+pub mod login_activity;
+pub mod impersonation;
+pub mod cup;
+pub mod diagnostics;
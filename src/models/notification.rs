@@ -1,8 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::league::team_alerts::TeamAlert;
+
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct PushToken {
     pub id: Uuid,
@@ -106,3 +108,94 @@ impl ExpoPushMessage {
         self
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationPreferences {
+    pub push_enabled: bool,
+    pub in_app_enabled: bool,
+    pub email_enabled: bool,
+    pub weekly_digest_enabled: bool,
+    pub inactivity_nudges_enabled: bool,
+    pub team_inactivity_alerts_enabled: bool,
+    /// Do-not-disturb window in `quiet_hours_timezone`; push notifications wait for its end
+    pub quiet_hours_start: Option<NaiveTime>,
+    pub quiet_hours_end: Option<NaiveTime>,
+    pub quiet_hours_timezone: String,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            push_enabled: true,
+            in_app_enabled: true,
+            email_enabled: true,
+            weekly_digest_enabled: true,
+            inactivity_nudges_enabled: true,
+            team_inactivity_alerts_enabled: true,
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            quiet_hours_timezone: "UTC".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub push_enabled: Option<bool>,
+    pub in_app_enabled: Option<bool>,
+    pub email_enabled: Option<bool>,
+    pub weekly_digest_enabled: Option<bool>,
+    pub inactivity_nudges_enabled: Option<bool>,
+    pub team_inactivity_alerts_enabled: Option<bool>,
+}
+
+/// Quiet hours as "HH:MM" in an IANA timezone; a start after the end spans midnight
+#[derive(Debug, Deserialize)]
+pub struct SetQuietHoursRequest {
+    pub start: String,
+    pub end: String,
+    pub timezone: String,
+}
+
+/// Team events a team's members are notified about, set by the team's captains
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TeamNotificationSettings {
+    pub team_id: Uuid,
+    pub game_start_enabled: bool,
+    pub fell_behind_enabled: bool,
+    pub member_milestone_enabled: bool,
+    /// None until a captain changed the defaults
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl TeamNotificationSettings {
+    pub fn defaults(team_id: Uuid) -> Self {
+        Self {
+            team_id,
+            game_start_enabled: true,
+            fell_behind_enabled: true,
+            member_milestone_enabled: true,
+            updated_at: None,
+        }
+    }
+
+    pub fn allows(&self, alert: TeamAlert) -> bool {
+        match alert {
+            TeamAlert::GameStart => self.game_start_enabled,
+            TeamAlert::FellBehind => self.fell_behind_enabled,
+            TeamAlert::MemberMilestone => self.member_milestone_enabled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTeamNotificationSettingsRequest {
+    pub game_start_enabled: Option<bool>,
+    pub fell_behind_enabled: Option<bool>,
+    pub member_milestone_enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DigestListQuery {
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,404 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::game::commentary;
+
+/// Branding and defaults of a white-labeled app
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Organization {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub primary_color: String,
+    pub secondary_color: String,
+    pub logo_key: Option<String>,
+    pub locale: String,
+    pub default_game_duration_seconds: i64,
+    pub default_games_per_matchup: i32,
+    pub default_inactivity_nudge_days: i32,
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What `/config/branding` returns to the apps
+#[derive(Debug, Serialize)]
+pub struct BrandingConfig {
+    pub slug: String,
+    pub name: String,
+    pub primary_color: String,
+    pub secondary_color: String,
+    pub logo_key: Option<String>,
+    pub locale: String,
+    pub league_defaults: LeagueDefaults,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeagueDefaults {
+    pub game_duration_seconds: i64,
+    pub games_per_matchup: i32,
+    pub inactivity_nudge_days: i32,
+}
+
+impl From<Organization> for BrandingConfig {
+    fn from(org: Organization) -> Self {
+        Self {
+            slug: org.slug,
+            name: org.name,
+            primary_color: org.primary_color,
+            secondary_color: org.secondary_color,
+            logo_key: org.logo_key,
+            locale: org.locale,
+            league_defaults: LeagueDefaults {
+                game_duration_seconds: org.default_game_duration_seconds,
+                games_per_matchup: org.default_games_per_matchup,
+                inactivity_nudge_days: org.default_inactivity_nudge_days,
+            },
+            updated_at: org.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BrandingQuery {
+    pub org: Option<String>, // Slug; defaults to the default organization
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub slug: String,
+    pub name: String,
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub logo_key: Option<String>,
+    pub locale: Option<String>, // One of crate::game::commentary::SUPPORTED_LOCALES
+    pub default_game_duration_seconds: Option<i64>,
+    pub default_games_per_matchup: Option<i32>,
+    pub default_inactivity_nudge_days: Option<i32>,
+    pub is_default: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub logo_key: Option<String>,
+    pub locale: Option<String>,
+    pub default_game_duration_seconds: Option<i64>,
+    pub default_games_per_matchup: Option<i32>,
+    pub default_inactivity_nudge_days: Option<i32>,
+    pub is_default: Option<bool>, // Only true is accepted; the previous default is unset
+}
+
+impl CreateOrganizationRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.slug.is_empty()
+            || self.slug.len() > 50
+            || !self.slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err("Slug must be 1-50 characters of a-z, 0-9 and -".to_string());
+        }
+        validate_name(&self.name)?;
+        validate_settings(
+            self.primary_color.as_deref(),
+            self.secondary_color.as_deref(),
+            self.logo_key.as_deref(),
+            self.locale.as_deref(),
+            self.default_game_duration_seconds,
+            self.default_games_per_matchup,
+            self.default_inactivity_nudge_days,
+        )
+    }
+}
+
+impl UpdateOrganizationRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = &self.name {
+            validate_name(name)?;
+        }
+        if self.is_default == Some(false) {
+            return Err("Make another organization the default instead".to_string());
+        }
+        validate_settings(
+            self.primary_color.as_deref(),
+            self.secondary_color.as_deref(),
+            self.logo_key.as_deref(),
+            self.locale.as_deref(),
+            self.default_game_duration_seconds,
+            self.default_games_per_matchup,
+            self.default_inactivity_nudge_days,
+        )
+    }
+}
+
+fn validate_settings(
+    primary_color: Option<&str>,
+    secondary_color: Option<&str>,
+    logo_key: Option<&str>,
+    locale: Option<&str>,
+    game_duration_seconds: Option<i64>,
+    games_per_matchup: Option<i32>,
+    inactivity_nudge_days: Option<i32>,
+) -> Result<(), String> {
+    for color in [primary_color, secondary_color].into_iter().flatten() {
+        validate_color(color)?;
+    }
+    if let Some(logo_key) = logo_key {
+        if logo_key.is_empty() || logo_key.len() > 500 {
+            return Err("Logo key must be 1-500 characters".to_string());
+        }
+    }
+    if let Some(locale) = locale {
+        if !commentary::is_supported_locale(locale) {
+            return Err(format!("Locale must be one of: {}", commentary::SUPPORTED_LOCALES.join(", ")));
+        }
+    }
+    if let Some(seconds) = game_duration_seconds {
+        if !(1..=2592000).contains(&seconds) {
+            return Err("Default game duration must be between 1 second and 2592000 seconds (30 days)".to_string());
+        }
+    }
+    if let Some(games) = games_per_matchup {
+        if !(1..=2).contains(&games) {
+            return Err("Default games per matchup must be 1 or 2".to_string());
+        }
+    }
+    if let Some(days) = inactivity_nudge_days {
+        if !(0..=60).contains(&days) {
+            return Err("Default inactivity nudge days must be between 0 and 60".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err("Name must be 1-100 characters".to_string());
+    }
+    Ok(())
+}
+
+fn validate_color(color: &str) -> Result<(), String> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(format!("Color {color} must be a hex color like #1E88E5"));
+    }
+    Ok(())
+}
+
+/// Data of a league that was run in spreadsheets, as CSV exports of its sheets
+#[derive(Debug, Deserialize)]
+pub struct HistoricalImportRequest {
+    /// League the imported teams and results go into
+    pub league_name: String,
+    /// Season the results are filed under; defaults to "<league name> (imported)"
+    pub season_name: Option<String>,
+    /// Columns: username, email
+    pub users_csv: Option<String>,
+    /// Columns: team_name, owner, color (optional), members (optional, usernames separated by ';')
+    pub teams_csv: Option<String>,
+    /// Columns: week, home_team, away_team, home_score, away_score, played_on (YYYY-MM-DD)
+    pub results_csv: Option<String>,
+    /// Validate against the database without keeping anything
+    pub dry_run: Option<bool>,
+}
+
+/// A row that can't be imported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportIssue {
+    /// users, teams or results
+    pub file: String,
+    /// CSV line, 0 for problems with the whole file
+    pub line: usize,
+    pub message: String,
+}
+
+/// What an import created, or would create in a dry run. Nothing is kept when there are issues.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HistoricalImportReport {
+    pub dry_run: bool,
+    pub league_id: Option<Uuid>,
+    pub season_id: Option<Uuid>,
+    pub users_created: usize,
+    pub teams_created: usize,
+    pub members_added: usize,
+    pub games_imported: usize,
+    pub issues: Vec<ImportIssue>,
+}
+
+/// OIDC provider an organization's members sign in with. The SCIM token is only shown when created.
+#[derive(Debug, Clone, Serialize)]
+pub struct SsoProvider {
+    pub organization_id: Uuid,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub groups_claim: String,
+    pub allowed_email_domains: Vec<String>,
+    pub jit_provisioning: bool,
+    pub enabled: bool,
+    pub has_scim_token: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Directory group whose members join a team on login
+#[derive(Debug, Serialize)]
+pub struct SsoGroupMapping {
+    pub id: Uuid,
+    pub group_name: String,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SsoSettings {
+    pub provider: Option<SsoProvider>,
+    pub group_mappings: Vec<SsoGroupMapping>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSsoProviderRequest {
+    pub issuer_url: String,
+    pub client_id: String,
+    pub groups_claim: Option<String>,
+    pub allowed_email_domains: Option<Vec<String>>,
+    pub jit_provisioning: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+impl SetSsoProviderRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if !url::Url::parse(&self.issuer_url).is_ok_and(|url| url.scheme() == "https") {
+            return Err("Issuer URL must be an https URL".to_string());
+        }
+        if self.client_id.trim().is_empty() || self.client_id.len() > 255 {
+            return Err("Client ID must be 1-255 characters".to_string());
+        }
+        if self.groups_claim.as_deref().is_some_and(|claim| claim.is_empty() || claim.len() > 100) {
+            return Err("Groups claim must be 1-100 characters".to_string());
+        }
+        for domain in self.allowed_email_domains.iter().flatten() {
+            if !domain.contains('.') || domain.contains('@') || domain.len() > 255 {
+                return Err(format!("{domain} is not an email domain like example.com"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSsoGroupMappingRequest {
+    pub group_name: String,
+    pub team_id: Uuid,
+}
+
+impl SetSsoGroupMappingRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.group_name.trim().is_empty() || self.group_name.len() > 255 {
+            return Err("Group name must be 1-255 characters".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Bearer token the company directory calls the SCIM endpoints with
+#[derive(Debug, Serialize)]
+pub struct ScimTokenResponse {
+    pub token: String,
+}
+
+/// Directory account of a valid SSO ID token
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsoIdentity {
+    pub subject: String,
+    /// Lowercased email, unless the provider flagged it unverified
+    pub email: Option<String>,
+    /// Whether the provider sent `email_verified: true`, rather than leaving it out
+    pub email_verified: bool,
+    pub groups: Vec<String>,
+}
+
+impl SsoIdentity {
+    /// Read the account from the claims of an ID token. Groups may be a list or a single name.
+    pub fn from_claims(claims: &serde_json::Map<String, serde_json::Value>, groups_claim: &str) -> Result<Self, String> {
+        let subject = claims
+            .get("sub")
+            .and_then(|sub| sub.as_str())
+            .ok_or("ID token has no subject")?
+            .to_string();
+        // Directories often leave out `email_verified`; that is enough for new accounts, only an
+        // explicit false is distrusted. Linking existing accounts needs an explicit true.
+        let email_verified = match claims.get("email_verified") {
+            Some(serde_json::Value::Bool(verified)) => Some(*verified),
+            Some(serde_json::Value::String(verified)) => Some(verified == "true"),
+            _ => None,
+        };
+        let email = claims
+            .get("email")
+            .and_then(|email| email.as_str())
+            .filter(|_| email_verified != Some(false))
+            .map(|email| email.to_lowercase());
+        let groups = match claims.get(groups_claim) {
+            Some(serde_json::Value::Array(groups)) => groups.iter().filter_map(|g| g.as_str().map(str::to_string)).collect(),
+            Some(serde_json::Value::String(group)) => vec![group.clone()],
+            _ => Vec::new(),
+        };
+        Ok(Self { subject, email, email_verified: email_verified == Some(true), groups })
+    }
+
+    /// Whether the directory account may take over the existing user with its email: only for
+    /// verified emails of a domain the organization owns, so no directory can claim accounts of
+    /// addresses it doesn't control
+    pub fn may_link_existing_user(&self, allowed_domains: &[String]) -> bool {
+        self.email_verified
+            && !allowed_domains.is_empty()
+            && self.email.as_deref().is_some_and(|email| email_domain_allowed(email, allowed_domains))
+    }
+}
+
+/// Whether new accounts can be created for the email. No domains allow every email.
+pub fn email_domain_allowed(email: &str, allowed_domains: &[String]) -> bool {
+    allowed_domains.is_empty()
+        || email
+            .rsplit_once('@')
+            .is_some_and(|(_, domain)| allowed_domains.iter().any(|allowed| allowed.eq_ignore_ascii_case(domain)))
+}
+
+/// SCIM PATCH of a user: `{"active": false}` or a SCIM PatchOp replacing `active`
+#[derive(Debug, Deserialize)]
+pub struct ScimUserPatch {
+    pub active: Option<bool>,
+    #[serde(rename = "Operations", default)]
+    pub operations: Vec<ScimPatchOperation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScimPatchOperation {
+    pub op: String,
+    pub path: Option<String>,
+    pub value: serde_json::Value,
+}
+
+impl ScimUserPatch {
+    /// The account's new active state, if the patch sets it
+    pub fn active(&self) -> Option<bool> {
+        self.active.or_else(|| {
+            self.operations
+                .iter()
+                .filter(|operation| operation.op.eq_ignore_ascii_case("replace"))
+                .find_map(|operation| match operation.path.as_deref() {
+                    Some("active") => operation.value.as_bool(),
+                    None => operation.value.get("active").and_then(|active| active.as_bool()),
+                    _ => None,
+                })
+        })
+    }
+}
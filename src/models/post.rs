@@ -102,6 +102,11 @@ pub struct CreatePostRequest {
     pub workout_id: Option<Uuid>, // For workout posts
     pub media_urls: Option<Vec<MediaItem>>,
     pub visibility: Option<PostVisibility>,
+    /// Keep the post as a draft until it is published
+    #[serde(default)]
+    pub draft: bool,
+    /// Keep the post as a draft until this time
+    pub publish_at: Option<DateTime<Utc>>,
 }
 
 // Update post request
@@ -142,3 +147,61 @@ pub struct FeedPagination {
     pub has_more: bool,
     pub limit: i32,
 }
+
+/// Whether a workout upload publishes the post it creates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Default)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AutoPostMode {
+    #[default]
+    Always,
+    /// The post stays private until the user publishes it from the upload prompt
+    Ask,
+    /// The post stays private
+    Never,
+}
+
+impl AutoPostMode {
+    /// Visibility of the post a workout upload creates
+    pub fn post_visibility(&self) -> PostVisibility {
+        match self {
+            AutoPostMode::Always => PostVisibility::Public,
+            AutoPostMode::Ask | AutoPostMode::Never => PostVisibility::Private,
+        }
+    }
+}
+
+/// The authenticated user's choices for the posts of their workout uploads
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WorkoutPostingSettings {
+    pub auto_post: AutoPostMode,
+    /// Default caption, see `workout::post_caption` for the placeholders
+    pub caption_template: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWorkoutPostingSettingsRequest {
+    pub auto_post: Option<AutoPostMode>,
+    /// An empty template removes it
+    pub caption_template: Option<String>,
+}
+
+/// A post of the authenticated user that is not published yet
+#[derive(Debug, Clone, Serialize)]
+pub struct PostDraft {
+    pub post_id: Uuid,
+    pub post_type: PostType,
+    pub content: Option<String>,
+    pub workout_id: Option<Uuid>,
+    pub media_urls: Option<serde_json::Value>,
+    pub publish_visibility: PostVisibility,
+    /// Publishes automatically at this time, or waits for the author if not set
+    pub publish_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublishPostRequest {
+    /// Schedule the post instead of publishing it right away
+    pub publish_at: Option<DateTime<Utc>>,
+}
@@ -1,5 +1,6 @@
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use crate::game::player_card::{Archetype, Form};
 
 #[derive(sqlx::FromRow, serde::Serialize)]
 pub struct UserProfileResponse {
@@ -25,6 +26,9 @@ pub struct UserProfileResponse {
     pub team_status: Option<String>,
     #[sqlx(skip)]
     pub team_role: Option<String>,
+    /// Most recent weekly stat decays, newest first
+    #[sqlx(skip)]
+    pub stat_decay: Vec<StatDecayEntry>,
 }
 
 #[derive(serde::Serialize)]
@@ -33,6 +37,18 @@ pub struct GameStats {
     pub strength: f32,
 }
 
+/// One week of stat decay of a user
+#[derive(Debug, sqlx::FromRow, serde::Serialize, serde::Deserialize)]
+pub struct StatDecayEntry {
+    pub week_start: chrono::NaiveDate,
+    pub weekly_percent: f32,
+    pub stamina_before: f32,
+    pub stamina_after: f32,
+    pub strength_before: f32,
+    pub strength_after: f32,
+    pub applied_at: DateTime<Utc>,
+}
+
 #[derive(sqlx::FromRow, serde::Serialize)]
 pub struct HealthProfileResponse {
     pub id: Uuid,
@@ -45,9 +61,18 @@ pub struct HealthProfileResponse {
     pub vt0_threshold: Option<i32>,
     pub vt1_threshold: Option<i32>,
     pub vt2_threshold: Option<i32>,
+    /// Where VT1 and VT2 come from: `hr_reserve` or `detected` from the user's workouts
+    pub vt1_source: String,
+    pub vt2_source: String,
     pub weight: Option<f32>,
     pub height: Option<f32>,
     pub last_updated: DateTime<Utc>,
+    /// Send back in If-Match when updating the profile
+    pub version: i32,
+    /// Personal factor points are multiplied by, from the user's effort ratings
+    pub scoring_calibration: f32,
+    /// Recently rated workouts the calibration is based on
+    pub calibration_rated_workouts: i32,
 }
 
 #[derive(serde::Deserialize)]
@@ -57,4 +82,111 @@ pub struct UpdateHealthProfileRequest {
     pub resting_heart_rate: Option<i32>,
     pub weight: Option<f32>,
     pub height: Option<f32>,
-}
\ No newline at end of file
+}
+
+/// Health values a device knows about its user (e.g. from HealthKit), sent at registration or on
+/// the first sync so scoring starts from real values instead of the defaults
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct DeviceHealthProfile {
+    pub age: Option<i32>,
+    pub gender: Option<String>,
+    pub weight: Option<f32>,
+    pub height: Option<f32>,
+    pub resting_heart_rate: Option<i32>,
+    /// Highest heart rate the device measured; estimated from age and gender if absent
+    pub max_heart_rate: Option<i32>,
+}
+
+impl DeviceHealthProfile {
+    /// Same limits as updating the health profile by hand
+    pub fn validate(&self) -> Result<(), String> {
+        if self.age.is_some_and(|age| !(10..=120).contains(&age)) {
+            return Err("Age must be between 10 and 120".to_string());
+        }
+        if self.resting_heart_rate.is_some_and(|rhr| !(30..=120).contains(&rhr)) {
+            return Err("Resting heart rate must be between 30 and 120 BPM".to_string());
+        }
+        if self.max_heart_rate.is_some_and(|max_hr| !(100..=250).contains(&max_hr)) {
+            return Err("Max heart rate must be between 100 and 250 BPM".to_string());
+        }
+        if let (Some(rhr), Some(max_hr)) = (self.resting_heart_rate, self.max_heart_rate) {
+            if max_hr <= rhr {
+                return Err("Max heart rate must be above the resting heart rate".to_string());
+            }
+        }
+        if self.weight.is_some_and(|weight| !(20.0..=300.0).contains(&weight)) {
+            return Err("Weight must be between 20 and 300 kg".to_string());
+        }
+        if self.height.is_some_and(|height| !(100.0..=250.0).contains(&height)) {
+            return Err("Height must be between 100 and 250 cm".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// VT1/VT2 thresholds estimated from the user's recent workouts, for them to accept or decline
+#[derive(Debug, sqlx::FromRow, serde::Serialize)]
+pub struct ThresholdProposal {
+    pub id: Uuid,
+    pub vt1_threshold: Option<i32>,
+    pub vt2_threshold: Option<i32>,
+    /// The profile's values when the proposal was made
+    pub current_vt1_threshold: Option<i32>,
+    pub current_vt2_threshold: Option<i32>,
+    pub aerobic_workouts: i32,
+    pub sustained_workouts: i32,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+/// Health profile values as they were from `valid_from` until the next snapshot
+#[derive(sqlx::FromRow, serde::Serialize)]
+pub struct HealthProfileSnapshot {
+    pub profile_version: i32,
+    pub age: Option<i32>,
+    pub gender: Option<String>,
+    pub resting_heart_rate: i32,
+    pub max_heart_rate: i32,
+    pub weight: Option<f32>,
+    pub height: Option<f32>,
+    pub vt_off_threshold: Option<i32>,
+    pub vt0_threshold: Option<i32>,
+    pub vt1_threshold: Option<i32>,
+    pub vt2_threshold: Option<i32>,
+    pub valid_from: DateTime<Utc>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct HealthProfileHistoryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// Game-facing attributes of a player, as shown on the team roster screen
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PlayerCard {
+    pub user_id: Uuid,
+    pub username: String,
+    pub profile_picture_url: Option<String>,
+    pub avatar_style: String,
+    pub stamina: f32,
+    pub strength: f32,
+    pub form: Form,
+    /// Consecutive days with a workout, counted within the last 28 days
+    pub streak_days: u32,
+    pub archetype: Archetype,
+    pub badges: PlayerBadges,
+    pub team_id: Option<Uuid>,
+    pub team_name: Option<String>,
+    pub team_role: Option<String>,
+    pub workouts_last_28_days: i64,
+}
+
+/// How often a player was the most and least valuable player of a game
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PlayerBadges {
+    pub mvp_count: i64,
+    pub lvp_count: i64,
+}
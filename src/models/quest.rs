@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::league::quests;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLeagueQuestRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub target_points: f32,
+    /// How progress is shown, e.g. "m"; defaults to "points"
+    pub unit_label: Option<String>,
+    /// Defaults to 25, 50, 75 and 100 percent
+    pub milestone_percents: Option<Vec<i32>>,
+    /// Defaults to now
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl CreateLeagueQuestRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err("Name must be 1-100 characters".to_string());
+        }
+        if !self.target_points.is_finite() || self.target_points <= 0.0 {
+            return Err("target_points must be greater than 0".to_string());
+        }
+        if let Some(unit_label) = &self.unit_label {
+            let unit_label = unit_label.trim();
+            if unit_label.is_empty() || unit_label.len() > 20 {
+                return Err("unit_label must be 1-20 characters".to_string());
+            }
+        }
+        if let Some(percents) = &self.milestone_percents {
+            if quests::normalize_milestones(percents).is_none() {
+                return Err("Milestone percents must be between 1 and 100".to_string());
+            }
+        }
+        if let (Some(starts_at), Some(ends_at)) = (self.starts_at, self.ends_at) {
+            if ends_at <= starts_at {
+                return Err("ends_at must be after starts_at".to_string());
+            }
+        } else if self.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+            return Err("ends_at must be in the future".to_string());
+        }
+        Ok(())
+    }
+
+    /// Milestones to announce, sorted and without duplicates
+    pub fn milestones(&self) -> Vec<i32> {
+        self.milestone_percents
+            .as_deref()
+            .and_then(quests::normalize_milestones)
+            .unwrap_or_else(|| quests::DEFAULT_MILESTONE_PERCENTS.to_vec())
+    }
+}
+
+/// A league quest with the league's progress
+#[derive(Debug, Clone, Serialize)]
+pub struct LeagueQuest {
+    pub id: Uuid,
+    pub league_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_points: f32,
+    pub unit_label: String,
+    pub milestone_percents: Vec<i32>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub progress_points: f64,
+    pub progress_percent: f64,
+    pub contributor_count: i64,
+    pub top_contributors: Vec<QuestContributor>,
+    /// Milestones reached so far, most recent first
+    pub milestones: Vec<QuestMilestone>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuestContributor {
+    pub user_id: Uuid,
+    pub username: String,
+    pub points: f64,
+    pub workouts: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuestMilestone {
+    pub percent: i32,
+    pub progress_points: f32,
+    pub reached_by: Option<Uuid>,
+    pub reached_by_username: Option<String>,
+    pub reached_at: DateTime<Utc>,
+    pub message: String,
+}
+
+/// A milestone a workout upload crossed, announced once the upload committed
+#[derive(Debug, Clone)]
+pub struct ReachedQuestMilestone {
+    pub quest_id: Uuid,
+    pub league_id: Uuid,
+    pub quest_name: String,
+    pub percent: i32,
+    pub message: String,
+    pub reached_by: Uuid,
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Groups smaller than this are never released, whatever the request asks for
+pub const MIN_K_ANONYMITY: i64 = 5;
+pub const DEFAULT_K_ANONYMITY: i64 = 10;
+pub const DEFAULT_ADHERENCE_WEEKS: i32 = 12;
+pub const MAX_ADHERENCE_WEEKS: i32 = 52;
+
+/// The authenticated user's data sharing choices
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConsentSettings {
+    pub research_data_sharing: bool,
+}
+
+impl Default for ConsentSettings {
+    fn default() -> Self {
+        Self {
+            research_data_sharing: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateConsentSettingsRequest {
+    pub research_data_sharing: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResearchDataset {
+    /// Minutes per heart rate zone, by age band
+    ZoneMinutesByAgeBand,
+    /// Share of each age band's cohort still working out N weeks after their first workout
+    AdherenceCurves,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResearchDatasetQuery {
+    pub dataset: ResearchDataset,
+    /// Lower bound (inclusive) on workout_start, defaults to 180 days before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Upper bound (exclusive) on workout_start, defaults to now
+    pub to: Option<DateTime<Utc>>,
+    /// Minimum number of distinct users behind every released row
+    pub k: Option<i64>,
+    /// Number of weeks in the adherence curves
+    pub weeks: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ZoneMinutesRow {
+    pub age_band: String,
+    pub zone: String,
+    pub user_count: i64,
+    pub workout_count: i64,
+    pub total_minutes: f64,
+    pub avg_minutes_per_user: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AdherenceRow {
+    pub age_band: String,
+    /// Weeks since the user's first workout in the period, starting at 0
+    pub week: i32,
+    /// Users whose first workout was long enough ago to observe this week
+    pub cohort_size: i64,
+    pub active_users: i64,
+    pub adherence_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResearchDatasetResponse<T> {
+    pub dataset: ResearchDataset,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub k: i64,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<T>,
+    /// Rows withheld because fewer than k users were behind them
+    pub suppressed_rows: usize,
+}
+
+/// Drop every row that describes fewer than `k` distinct users.
+/// Returns the released rows and how many were withheld.
+pub fn apply_k_anonymity<T>(rows: Vec<T>, k: i64, group_size: impl Fn(&T) -> i64) -> (Vec<T>, usize) {
+    let total = rows.len();
+    let released: Vec<T> = rows.into_iter().filter(|row| group_size(row) >= k).collect();
+    let suppressed = total - released.len();
+    (released, suppressed)
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sandboxes live 1 hour unless asked otherwise, and at most a day
+pub const DEFAULT_SANDBOX_TTL_MINUTES: i64 = 60;
+pub const MAX_SANDBOX_TTL_MINUTES: i64 = 24 * 60;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSandboxRequest {
+    /// How long the sandbox lives before it is torn down
+    pub ttl_minutes: Option<i64>,
+}
+
+impl CreateSandboxRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.ttl_minutes {
+            Some(ttl) if !(5..=MAX_SANDBOX_TTL_MINUTES).contains(&ttl) => {
+                Err(format!("ttl_minutes must be between 5 and {MAX_SANDBOX_TTL_MINUTES}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn ttl(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.ttl_minutes.unwrap_or(DEFAULT_SANDBOX_TTL_MINUTES))
+    }
+}
+
+/// A user of a sandbox, who signs in with the sandbox password
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SandboxUser {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub team_name: String,
+}
+
+/// An isolated organization with a league whose only game is live
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sandbox {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub organization_slug: String,
+    pub league_id: Uuid,
+    pub season_id: Uuid,
+    pub live_game_id: Uuid,
+    /// Shared by all sandbox users; only returned when the sandbox is created
+    pub password: String,
+    pub users: Vec<SandboxUser>,
+    pub expires_at: DateTime<Utc>,
+}
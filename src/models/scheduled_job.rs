@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Cron schedule of a background job as configured by admins
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ScheduledJobConfig {
+    pub job_name: String,
+    pub cron_expression: String,
+    pub description: String,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateJobScheduleRequest {
+    pub cron_expression: String,
+}
+
+/// Outcome of applying the configured schedules to the running jobs
+#[derive(Debug, Default, Serialize)]
+pub struct ScheduleReloadSummary {
+    pub rescheduled: Vec<String>,
+    /// Jobs whose configured expression could not be applied, with the reason
+    pub failed: Vec<String>,
+}
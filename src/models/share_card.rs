@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a card shows, stored as `share_cards.card_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareCardType {
+    /// MVP or LVP badge of a game; the subject is the game
+    Badge,
+    /// Final score of a game the user played in; the subject is the game
+    GameResult,
+    /// A workout that beat all the user's earlier ones; the subject is the workout
+    PersonalRecord,
+}
+
+impl ShareCardType {
+    pub const ALL: [ShareCardType; 3] = [
+        ShareCardType::Badge,
+        ShareCardType::GameResult,
+        ShareCardType::PersonalRecord,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareCardType::Badge => "badge",
+            ShareCardType::GameResult => "game_result",
+            ShareCardType::PersonalRecord => "personal_record",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "badge" => Some(ShareCardType::Badge),
+            "game_result" => Some(ShareCardType::GameResult),
+            "personal_record" => Some(ShareCardType::PersonalRecord),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShareCardRequest {
+    pub card_type: ShareCardType,
+    /// Game for badge and game result cards, workout for personal record cards
+    pub subject_id: Uuid,
+}
+
+/// Text drawn on a card
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShareCardContent {
+    /// Small heading above the title, e.g. "MVP BADGE"
+    pub eyebrow: String,
+    pub title: String,
+    pub lines: Vec<ShareCardLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShareCardLine {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareCard {
+    pub id: Uuid,
+    pub card_type: String,
+    pub subject_id: Uuid,
+    /// pending, rendered or failed
+    pub status: String,
+    /// Public URL of the image, relative to the API
+    pub path: String,
+    pub content: ShareCardContent,
+    pub created_at: DateTime<Utc>,
+    pub rendered_at: Option<DateTime<Utc>>,
+}
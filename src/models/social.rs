@@ -25,17 +25,63 @@ impl ReactionType {
     }
 }
 
+/// Kind of row a reaction points at, stored as `reactions.target_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionTarget {
+    Workout,
+    Comment,
+    Post,
+    GameSummary,
+    ScoreEvent,
+}
+
+impl ReactionTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionTarget::Workout => "workout",
+            ReactionTarget::Comment => "comment",
+            ReactionTarget::Post => "post",
+            ReactionTarget::GameSummary => "game_summary",
+            ReactionTarget::ScoreEvent => "score_event",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "workout" => Some(ReactionTarget::Workout),
+            "comment" => Some(ReactionTarget::Comment),
+            "post" => Some(ReactionTarget::Post),
+            "game_summary" => Some(ReactionTarget::GameSummary),
+            "score_event" => Some(ReactionTarget::ScoreEvent),
+            _ => None,
+        }
+    }
+
+    /// How the target is called in notification messages
+    pub fn noun(&self) -> &'static str {
+        match self {
+            ReactionTarget::Workout => "workout",
+            ReactionTarget::Comment => "comment",
+            ReactionTarget::Post => "post",
+            ReactionTarget::GameSummary => "game summary",
+            ReactionTarget::ScoreEvent => "score",
+        }
+    }
+}
+
 #[derive(Debug, FromRow, Serialize, Deserialize)]
-pub struct WorkoutReaction {
+pub struct Reaction {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub workout_id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
     pub reaction_type: String,
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct WorkoutReactionWithUser {
+pub struct ReactionWithUser {
     pub id: Uuid,
     pub user_id: Uuid,
     pub username: String,
@@ -43,6 +89,25 @@ pub struct WorkoutReactionWithUser {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TargetReactionSummary {
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub fire_count: i64,
+    pub user_reacted: bool,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct WorkoutReaction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub workout_id: Uuid,
+    pub reaction_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type WorkoutReactionWithUser = ReactionWithUser;
+
 #[derive(Debug, Deserialize)]
 pub struct CreateReactionRequest {
     pub reaction_type: String,
@@ -125,14 +190,7 @@ pub struct CommentReaction {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct CommentReactionWithUser {
-    pub id: Uuid,
-    pub user_id: Uuid,
-    pub username: String,
-    pub reaction_type: String,
-    pub created_at: DateTime<Utc>,
-}
+pub type CommentReactionWithUser = ReactionWithUser;
 
 #[derive(Debug, Serialize)]
 pub struct CommentReactionSummary {
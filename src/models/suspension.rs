@@ -0,0 +1,64 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest time-boxed suspension; anything longer is a soft ban without an end
+pub const MAX_SUSPENSION_HOURS: i64 = 24 * 365;
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSuspensionRequest {
+    /// Shown to the user
+    pub reason: String,
+    /// Omit for a soft ban that lasts until an admin lifts it
+    pub duration_hours: Option<i64>,
+}
+
+impl CreateSuspensionRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        let reason = self.reason.trim();
+        if reason.is_empty() || reason.len() > MAX_REASON_LENGTH {
+            return Err(format!("Reason must be 1-{MAX_REASON_LENGTH} characters"));
+        }
+        if let Some(hours) = self.duration_hours {
+            if !(1..=MAX_SUSPENSION_HOURS).contains(&hours) {
+                return Err(format!("duration_hours must be between 1 and {MAX_SUSPENSION_HOURS}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// When a suspension starting at `starts_at` expires on its own, if it does
+    pub fn ends_at(&self, starts_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.duration_hours.map(|hours| starts_at + Duration::hours(hours))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LiftSuspensionRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserSuspension {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub starts_at: DateTime<Utc>,
+    /// None for a soft ban
+    pub ends_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<Uuid>,
+    pub lift_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserSuspension {
+    /// In effect at `at`: started, not yet expired and not lifted
+    pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at
+            && self.ends_at.is_none_or(|ends_at| at < ends_at)
+            && self.lifted_at.is_none_or(|lifted_at| at < lifted_at)
+    }
+}
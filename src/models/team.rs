@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::league::team_kits;
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Team {
     pub id: Uuid,
@@ -23,10 +25,15 @@ pub struct TeamInfo {
     pub team_name: String,
     pub team_description: Option<String>,
     pub team_color: String,
+    /// Worn when the team color clashes with the opponent's
+    pub away_color: Option<String>,
+    pub kit_emoji: Option<String>,
     pub league_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub owner_username: String,
+    /// Send back in If-Match when updating the team
+    pub version: i32,
 }
 
 /// Request to register a new team
@@ -35,6 +42,8 @@ pub struct TeamRegistrationRequest {
     pub team_name: String,
     pub team_description: Option<String>,
     pub team_color: Option<String>,
+    pub away_color: Option<String>,
+    pub kit_emoji: Option<String>,
     pub league_id: Option<Uuid>,
 }
 
@@ -44,6 +53,8 @@ pub struct TeamUpdateRequest {
     pub team_name: Option<String>,
     pub team_description: Option<String>,
     pub team_color: Option<String>,
+    pub away_color: Option<String>,
+    pub kit_emoji: Option<String>,
 }
 
 /// Response for team registration
@@ -217,6 +228,24 @@ pub struct UpdateTeamMemberRequest {
     pub status: Option<MemberStatus>,
 }
 
+/// Request to move a member to another team in one step
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferTeamMemberRequest {
+    pub to_team_id: Uuid,
+    /// Role in the new team; the current role is kept when omitted
+    pub role: Option<TeamRole>,
+}
+
+/// Active membership in another team of the same league, which blocks joining a team
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeagueMembershipConflict {
+    pub user_id: Uuid,
+    pub username: String,
+    pub league_id: Uuid,
+    pub team_id: Uuid,
+    pub team_name: String,
+}
+
 /// Response for team member operations
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TeamMemberResponse {
@@ -241,11 +270,15 @@ pub struct TeamInfoWithPower {
     pub team_name: String,
     pub team_description: Option<String>,
     pub team_color: String,
+    /// Worn when the team color clashes with the opponent's
+    pub away_color: Option<String>,
+    pub kit_emoji: Option<String>,
     pub league_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub owner_username: String,
     pub total_power: f32,
+    pub version: i32,
 }
 
 impl TeamRegistrationRequest {
@@ -298,7 +331,7 @@ impl TeamRegistrationRequest {
             }
         }
 
-        Ok(())
+        validate_kit(self.team_color.as_deref(), self.away_color.as_deref(), self.kit_emoji.as_deref())
     }
 
     /// Get sanitized team name
@@ -319,7 +352,9 @@ impl TeamUpdateRequest {
         // Check if at least one field is being updated
         if self.team_name.is_none() 
             && self.team_description.is_none() 
-            && self.team_color.is_none() {
+            && self.team_color.is_none()
+            && self.away_color.is_none()
+            && self.kit_emoji.is_none() {
             return Err("At least one field must be provided for update".to_string());
         }
 
@@ -361,8 +396,22 @@ impl TeamUpdateRequest {
             }
         }
 
-        Ok(())
+        validate_kit(self.team_color.as_deref(), self.away_color.as_deref(), self.kit_emoji.as_deref())
+    }
+}
+
+/// The away color must be valid and tell apart from the team color it's given with
+fn validate_kit(team_color: Option<&str>, away_color: Option<&str>, kit_emoji: Option<&str>) -> Result<(), String> {
+    if let Some(away_color) = away_color {
+        team_kits::validate_hex_color(away_color)?;
+        if team_color.is_some_and(|team_color| team_kits::colors_clash(team_color, away_color)) {
+            return Err("Away color must be clearly different from the team color".to_string());
+        }
+    }
+    if let Some(emoji) = kit_emoji {
+        team_kits::validate_kit_emoji(emoji)?;
     }
+    Ok(())
 }
 
 impl AddTeamMemberRequest {
@@ -55,3 +55,66 @@ pub struct SendInvitationRequest {
 pub struct RespondToInvitationRequest {
     pub accept: bool,
 }
+
+/// Longest an invite code can stay valid
+pub const MAX_INVITE_CODE_DAYS: i64 = 30;
+
+/// Most registrations a single invite code can be used for
+pub const MAX_INVITE_CODE_USES: i32 = 50;
+
+/// A code that lets new users register straight into a team
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InviteCode {
+    pub id: Uuid,
+    pub code: String,
+    pub team_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub max_uses: i32,
+    pub use_count: i32,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InviteCode {
+    /// Why the code can't be registered with right now, if it can't
+    pub fn unusable_reason(&self, now: DateTime<Utc>) -> Option<&'static str> {
+        if self.revoked_at.is_some() {
+            Some("This invite code has been revoked")
+        } else if self.expires_at <= now {
+            Some("This invite code has expired")
+        } else if self.use_count >= self.max_uses {
+            Some("This invite code has been used up")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInviteCodeRequest {
+    /// Defaults to a single use
+    pub max_uses: Option<i32>,
+    /// Defaults to a week
+    pub expires_in_days: Option<i64>,
+}
+
+impl CreateInviteCodeRequest {
+    /// The number of uses and days the code is valid for, or why it can't be created
+    pub fn validate(&self) -> Result<(i32, i64), String> {
+        let max_uses = self.max_uses.unwrap_or(1);
+        if !(1..=MAX_INVITE_CODE_USES).contains(&max_uses) {
+            return Err(format!("max_uses must be between 1 and {MAX_INVITE_CODE_USES}"));
+        }
+        let expires_in_days = self.expires_in_days.unwrap_or(7);
+        if !(1..=MAX_INVITE_CODE_DAYS).contains(&expires_in_days) {
+            return Err(format!("expires_in_days must be between 1 and {MAX_INVITE_CODE_DAYS}"));
+        }
+        Ok((max_uses, expires_in_days))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InviteCodeListQuery {
+    pub team_id: Option<Uuid>,
+}
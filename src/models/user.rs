@@ -5,6 +5,9 @@ use uuid::Uuid;
 use secrecy::SecretString;
 use sqlx::Type;
 
+use crate::models::common::FieldError;
+use crate::models::profile::DeviceHealthProfile;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[sqlx(type_name = "varchar", rename_all = "lowercase")]
 #[derive(Default)]
@@ -80,65 +83,138 @@ pub struct RegistrationRequest {
     pub email: String,
     #[serde(serialize_with = "serialize_secret_string", deserialize_with = "deserialize_secret_string")]
     pub password: SecretString,
+    /// Code from a team's invite; registering with it joins that team
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invite_code: Option<String>,
+    /// Health values from the device, imported as the new user's health profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_profile: Option<DeviceHealthProfile>,
 }
 
 impl RegistrationRequest {
     /// Validate the registration request
     pub fn validate(&self) -> Result<(), String> {
-        // Validate username
-        self.validate_username()?;
+        match self.field_errors().into_iter().next() {
+            Some(error) => Err(error.message),
+            None => Ok(()),
+        }
+    }
 
-        // Validate email
-        if self.email.is_empty() {
-            return Err("Email cannot be empty".to_string());
+    /// Problems with the username, email and device profile, by field. The password is checked
+    /// against the password policy separately.
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if let Err(message) = self.validate_username() {
+            errors.push(FieldError::new("username", "invalid_username", message));
         }
 
-        // Basic email format validation
-        if !self.email.contains('@') {
-            return Err("Invalid email format".to_string());
+        if self.email.is_empty() {
+            errors.push(FieldError::new("email", "required", "Email cannot be empty"));
+        } else if !self.email.contains('@') {
+            // Basic email format validation
+            errors.push(FieldError::new("email", "invalid_email", "Invalid email format"));
         }
 
-        Ok(())
+        if let Some(device_profile) = &self.device_profile {
+            if let Err(message) = device_profile.validate() {
+                errors.push(FieldError::new("device_profile", "invalid_device_profile", message));
+            }
+        }
+        errors
     }
 
     /// Validate username format
-    /// - Only alphanumeric characters, underscores, and hyphens allowed
-    /// - No spaces, no special characters (é, õ, etc.)
-    /// - Length between 3 and 30 characters
-    /// - Cannot start or end with underscore or hyphen
     pub fn validate_username(&self) -> Result<(), String> {
-        let username = self.username.trim();
+        validate_username(&self.username)
+    }
+}
 
-        // Check length
-        if username.len() < 3 {
-            return Err("Username must be at least 3 characters long".to_string());
-        }
+/// Validate username format
+/// - Only alphanumeric characters, underscores, and hyphens allowed
+/// - No spaces, no special characters (é, õ, etc.)
+/// - Length between 3 and 30 characters
+/// - Cannot start or end with underscore or hyphen
+pub fn validate_username(username: &str) -> Result<(), String> {
+    let username = username.trim();
+
+    // Check length
+    if username.len() < 3 {
+        return Err("Username must be at least 3 characters long".to_string());
+    }
 
-        if username.len() > 30 {
-            return Err("Username cannot exceed 30 characters".to_string());
-        }
+    if username.len() > 30 {
+        return Err("Username cannot exceed 30 characters".to_string());
+    }
 
-        // Check for invalid characters (only allow a-z, A-Z, 0-9, _, -)
-        if !username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
-            return Err("Username can only contain letters, numbers, underscores, and hyphens (no spaces or special characters like é, õ)".to_string());
-        }
+    // Check for invalid characters (only allow a-z, A-Z, 0-9, _, -)
+    if !username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("Username can only contain letters, numbers, underscores, and hyphens (no spaces or special characters like é, õ)".to_string());
+    }
 
-        // Cannot start or end with underscore or hyphen
-        if username.starts_with('_') || username.starts_with('-') {
-            return Err("Username cannot start with an underscore or hyphen".to_string());
-        }
+    // Cannot start or end with underscore or hyphen
+    if username.starts_with('_') || username.starts_with('-') {
+        return Err("Username cannot start with an underscore or hyphen".to_string());
+    }
 
-        if username.ends_with('_') || username.ends_with('-') {
-            return Err("Username cannot end with an underscore or hyphen".to_string());
-        }
+    if username.ends_with('_') || username.ends_with('-') {
+        return Err("Username cannot end with an underscore or hyphen".to_string());
+    }
 
-        // Check for consecutive underscores or hyphens
-        if username.contains("__") || username.contains("--") {
-            return Err("Username cannot contain consecutive underscores or hyphens".to_string());
-        }
+    // Check for consecutive underscores or hyphens
+    if username.contains("__") || username.contains("--") {
+        return Err("Username cannot contain consecutive underscores or hyphens".to_string());
+    }
+
+    Ok(())
+}
 
-        Ok(())
+/// Days a user has to wait between two username changes
+pub const USERNAME_CHANGE_COOLDOWN_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeUsernameRequest {
+    pub username: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangeUsernameResponse {
+    pub username: String,
+    /// Access token carrying the new username; tokens issued before keep the old one until refreshed
+    pub token: String,
+    pub next_change_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CheckUsernameQuery {
+    pub username: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsernameAvailability {
+    pub username: String,
+    pub available: bool,
+    /// Why the username can't be used, if it can't
+    pub reason: Option<String>,
+}
+
+/// Username for an account registered by signing in with Apple or Google, from the local part
+/// of its email. Follows the username rules and leaves room for a numeric suffix.
+pub fn username_from_email(email: &str) -> String {
+    let local_part = email.split('@').next().unwrap_or_default();
+    let mut username = String::new();
+    for c in local_part.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-') {
+        let is_separator = c == '_' || c == '-';
+        if is_separator && (username.is_empty() || username.ends_with(['_', '-'])) {
+            continue;
+        }
+        username.push(c);
     }
+    username.truncate(24);
+    let mut username = username.trim_end_matches(['_', '-']).to_string();
+    while username.len() < 3 {
+        username.push('0');
+    }
+    username
 }
 
 impl std::fmt::Display for RegistrationRequest{
@@ -147,6 +223,22 @@ impl std::fmt::Display for RegistrationRequest{
     }
 }
 
+/// Days between an account deletion request and the purge of the account's data
+pub const ACCOUNT_DELETION_GRACE_DAYS: i64 = 30;
+
+/// Pending deletion of the user's account; logging in before `purge_after` cancels it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AccountDeletion {
+    pub requested_at: DateTime<Utc>,
+    pub purge_after: DateTime<Utc>,
+}
+
+impl AccountDeletion {
+    pub fn requested(requested_at: DateTime<Utc>) -> Self {
+        Self { requested_at, purge_after: requested_at + chrono::Duration::days(ACCOUNT_DELETION_GRACE_DAYS) }
+    }
+}
+
 pub fn serialize_secret_string<S>(_: &SecretString, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -49,6 +49,9 @@ pub struct WorkoutUploadResponse {
     pub sync_id: Uuid,
     pub timestamp: DateTime<Utc>,
     pub game_stats: StatChanges,
+    pub post_id: Uuid,
+    /// The post is a draft and the app should ask whether to publish it
+    pub post_awaiting_confirmation: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -107,7 +110,7 @@ impl WorkoutStats {
     }
 }
 
-#[derive(PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum WorkoutType {
     Strength,
     Cardio,
@@ -209,4 +212,195 @@ impl UpdateWorkoutReportRequest {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+pub const MAX_WORKOUT_TAGS: usize = 10;
+pub const MAX_WORKOUT_TAG_LENGTH: usize = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWorkoutNotesRequest {
+    pub notes: Option<String>,     // Empty string clears the notes
+    pub tags: Option<Vec<String>>, // Replaces all tags
+}
+
+impl UpdateWorkoutNotesRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.notes.is_none() && self.tags.is_none() {
+            return Err("Provide notes or tags".to_string());
+        }
+        if let Some(notes) = &self.notes {
+            if notes.len() > 2000 {
+                return Err("Notes must be 2000 characters or less".to_string());
+            }
+        }
+        if let Some(tags) = &self.tags {
+            let tags = normalize_tags(tags);
+            if tags.len() > MAX_WORKOUT_TAGS {
+                return Err(format!("At most {} tags are allowed", MAX_WORKOUT_TAGS));
+            }
+            if tags.iter().any(|tag| tag.chars().count() > MAX_WORKOUT_TAG_LENGTH) {
+                return Err(format!("Tags must be {} characters or less", MAX_WORKOUT_TAG_LENGTH));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tags as stored: trimmed, lowercase, without a leading `#`, no empties or duplicates
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct WorkoutShareLink {
+    pub token: String,
+    pub workout_data_id: Uuid,
+    pub view_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a share link shows: a summary without location data, ready for link previews
+#[derive(Debug, Serialize)]
+pub struct PublicWorkoutSummary {
+    pub username: String,
+    pub profile_picture_url: Option<String>,
+    pub activity: String,
+    pub workout_start: DateTime<Utc>,
+    pub duration_minutes: Option<i32>,
+    pub calories_burned: Option<i32>,
+    pub avg_heart_rate: Option<i32>,
+    pub max_heart_rate: Option<i32>,
+    pub total_points_gained: i32,
+    pub image_url: Option<String>,
+    pub notes: Option<String>,
+    pub og: OpenGraphSummary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenGraphSummary {
+    pub title: String,
+    pub description: String,
+    pub image_url: Option<String>,
+}
+
+/// The fields of a stored workout that a re-upload is compared against
+#[derive(Debug, Clone)]
+pub struct StoredWorkoutFingerprint {
+    pub id: Uuid,
+    pub workout_start: DateTime<Utc>,
+    pub workout_end: DateTime<Utc>,
+    pub calories_burned: Option<i32>,
+    pub samples_count: i64,
+}
+
+/// A re-sent workout to compare against the stored one it conflicts with
+#[derive(Debug, Deserialize)]
+pub struct CompareWorkoutRequest {
+    pub workout_start: DateTime<Utc>,
+    pub workout_end: DateTime<Utc>,
+    pub calories_burned: Option<i32>,
+    pub heart_rate: Option<Vec<HeartRateData>>,
+}
+
+/// One compared field. `incoming` is None when the device did not send the field,
+/// which never counts as a change.
+#[derive(Debug, Serialize, Clone)]
+pub struct WorkoutFieldDiff<T> {
+    pub existing: Option<T>,
+    pub incoming: Option<T>,
+    pub changed: bool,
+}
+
+impl<T: PartialEq> WorkoutFieldDiff<T> {
+    pub fn new(existing: Option<T>, incoming: Option<T>) -> Self {
+        let changed = incoming.is_some() && existing != incoming;
+        Self { existing, incoming, changed }
+    }
+}
+
+/// Field-by-field diff of a re-uploaded workout against the stored workout it overlaps,
+/// telling an identical re-send apart from amended data
+#[derive(Debug, Serialize, Clone)]
+pub struct WorkoutReuploadDiff {
+    pub conflicting_workout_id: Uuid,
+    pub identical: bool,
+    pub start_offset_seconds: i64,
+    pub end_offset_seconds: i64,
+    pub duration_seconds: WorkoutFieldDiff<i64>,
+    pub samples_count: WorkoutFieldDiff<i64>,
+    pub calories: WorkoutFieldDiff<i32>,
+}
+
+impl WorkoutReuploadDiff {
+    /// Start/end shifts up to `time_tolerance_seconds` are treated as the same workout times
+    pub fn compare(
+        existing: &StoredWorkoutFingerprint,
+        workout_start: DateTime<Utc>,
+        workout_end: DateTime<Utc>,
+        calories: Option<i32>,
+        samples_count: Option<i64>,
+        time_tolerance_seconds: i64,
+    ) -> Self {
+        let start_offset_seconds = (workout_start - existing.workout_start).num_seconds();
+        let end_offset_seconds = (workout_end - existing.workout_end).num_seconds();
+        let duration_seconds = WorkoutFieldDiff::new(
+            Some((existing.workout_end - existing.workout_start).num_seconds()),
+            Some((workout_end - workout_start).num_seconds()),
+        );
+        let samples_count = WorkoutFieldDiff::new(Some(existing.samples_count), samples_count);
+        let calories = WorkoutFieldDiff::new(existing.calories_burned, calories);
+
+        let identical = start_offset_seconds.abs() <= time_tolerance_seconds
+            && end_offset_seconds.abs() <= time_tolerance_seconds
+            && !samples_count.changed
+            && !calories.changed;
+
+        Self {
+            conflicting_workout_id: existing.id,
+            identical,
+            start_offset_seconds,
+            end_offset_seconds,
+            duration_seconds,
+            samples_count,
+            calories,
+        }
+    }
+}
+
+/// Health Connect drops change tokens that were not used for this many days
+pub const CHANGE_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct HealthConnectChangeToken {
+    pub device_id: String,
+    pub change_token: String,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// How a sync relates to the stored change token of its device
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeTokenStatus {
+    /// No token was sent, the sync covers the full history
+    FullSync,
+    /// The delta is based on the stored token
+    Valid,
+    /// The stored token was not used for too long
+    Expired,
+    /// No token is stored for the device or the delta is based on an older one
+    Unknown,
+}
+
+impl ChangeTokenStatus {
+    pub fn requires_full_resync(&self) -> bool {
+        matches!(self, ChangeTokenStatus::Expired | ChangeTokenStatus::Unknown)
+    }
+}
@@ -6,10 +6,33 @@ use crate::handlers::admin::{
     league_handler,
     game_management_handler,
     workout_handler,
+    backup_handler,
+    consistency_handler,
+    export_handler,
+    activity_handler,
+    media_handler,
+    broadcast_handler,
+    scheduler_handler,
+    organization_handler,
+    organization_sso_handler,
+    waitlist_handler,
+    research_handler,
+    quest_handler,
+    suspension_handler,
+    fixture_flavor_handler,
+    league_webhook_handler,
+    league_rules_handler,
+    telemetry_handler,
+    api_key_handler,
+    security_handler,
+    invite_handler,
+    impersonation_handler,
+    cup_handler,
 };
 use crate::handlers::notification_handler;
 use crate::handlers::workout_data::workout_reports;
 use crate::middleware::admin::AdminMiddleware;
+use crate::middleware::quota::{AdminQuota, QuotaPolicy};
 
 pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -33,6 +56,27 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/users/{id}/status")
                     .route(web::patch().to(user_handler::update_user_status))
             )
+            .service(
+                web::resource("/users/{id}/suspensions")
+                    .route(web::get().to(suspension_handler::get_user_suspensions))
+                    .route(web::post().to(suspension_handler::suspend_user))
+            )
+            .service(
+                web::resource("/users/{id}/impersonate")
+                    .route(web::post().to(impersonation_handler::impersonate_user))
+            )
+            .service(
+                web::resource("/users/{id}/impersonations")
+                    .route(web::get().to(impersonation_handler::get_user_impersonations))
+            )
+            .service(
+                web::resource("/users/{id}/diagnostics")
+                    .route(web::get().to(user_handler::get_user_diagnostics))
+            )
+            .service(
+                web::resource("/suspensions/{id}/lift")
+                    .route(web::post().to(suspension_handler::lift_suspension))
+            )
             
             // Team management routes
             .service(
@@ -51,11 +95,23 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
                     .route(web::get().to(team_handler::get_team_members))
                     .route(web::post().to(team_handler::add_team_member))
             )
+            .service(
+                web::resource("/teams/{id}/boosters/audit")
+                    .route(web::get().to(team_handler::get_team_booster_audit))
+            )
             .service(
                 web::resource("/teams/{team_id}/members/{member_id}")
                     .route(web::patch().to(team_handler::update_team_member))
                     .route(web::delete().to(team_handler::remove_team_member))
             )
+            .service(
+                web::resource("/invites")
+                    .route(web::get().to(invite_handler::get_invite_codes))
+            )
+            .service(
+                web::resource("/invites/{id}/revoke")
+                    .route(web::post().to(invite_handler::revoke_invite))
+            )
             
             // League management routes
             .service(
@@ -74,6 +130,38 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
                     .route(web::post().to(league_handler::assign_team_to_league))
                     .route(web::delete().to(league_handler::remove_team_from_league))
             )
+            .service(
+                web::resource("/leagues/{id}/waitlist")
+                    .route(web::get().to(waitlist_handler::get_league_waitlist))
+                    .route(web::put().to(waitlist_handler::reorder_league_waitlist))
+            )
+            .service(
+                web::resource("/leagues/{id}/waitlist/{team_id}")
+                    .route(web::delete().to(waitlist_handler::remove_from_league_waitlist))
+            )
+            .service(
+                web::resource("/leagues/{id}/quests")
+                    .route(web::post().to(quest_handler::create_league_quest))
+            )
+            .service(
+                web::resource("/leagues/{id}/webhooks")
+                    .route(web::get().to(league_webhook_handler::get_league_webhooks))
+                    .route(web::put().to(league_webhook_handler::set_league_webhook))
+            )
+            .service(
+                web::resource("/leagues/{id}/webhooks/{provider}")
+                    .route(web::delete().to(league_webhook_handler::remove_league_webhook))
+            )
+            .service(
+                web::resource("/leagues/{id}/webhook-deliveries/{delivery_id}/retry")
+                    .route(web::post().to(league_webhook_handler::retry_webhook_delivery))
+            )
+            .service(
+                web::resource("/leagues/{id}/rules")
+                    .route(web::get().to(league_rules_handler::get_league_rules))
+                    .route(web::put().to(league_rules_handler::set_league_rules))
+                    .route(web::delete().to(league_rules_handler::remove_league_rules))
+            )
             // Season management routes
             .service(
                 web::resource("/leagues/{id}/seasons")
@@ -88,8 +176,18 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
             )
             .service(
                 web::resource("/seasons/{season_id}/recalculate-standings")
+                    .wrap(AdminQuota::new("recalculate_standings", QuotaPolicy::RECALCULATION))
                     .route(web::post().to(league_handler::recalculate_standings_positions))
             )
+            .service(
+                web::resource("/seasons/{season_id}/cups")
+                    .route(web::post().to(cup_handler::create_cup))
+            )
+            .service(
+                web::resource("/seasons/{season_id}/weeks/{week_number}/name")
+                    .route(web::put().to(fixture_flavor_handler::set_game_week_name))
+                    .route(web::delete().to(fixture_flavor_handler::remove_game_week_name))
+            )
             // Game management routes
             .service(
                 web::resource("/games/start-now")
@@ -105,10 +203,12 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
             )
             .service(
                 web::resource("/games/evaluate")
+                    .wrap(AdminQuota::new("evaluate_games", QuotaPolicy::RECALCULATION))
                     .route(web::post().to(game_management_handler::evaluate_games_for_date))
             )
             .service(
                 web::resource("/games/trigger-evaluation")
+                    .wrap(AdminQuota::new("trigger_evaluation", QuotaPolicy::RECALCULATION))
                     .route(web::post().to(game_management_handler::trigger_game_evaluation))
             )
             .service(
@@ -117,8 +217,18 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
             )
             .service(
                 web::resource("/games/create-summaries")
+                    .wrap(AdminQuota::new("create_summaries", QuotaPolicy::RECALCULATION))
                     .route(web::post().to(game_management_handler::create_missing_game_summaries))
             )
+            .service(
+                web::resource("/games/watchdog")
+                    .route(web::get().to(game_management_handler::get_game_watchdog_status))
+            )
+            .service(
+                web::resource("/games/{game_id}/flavor")
+                    .route(web::put().to(fixture_flavor_handler::set_fixture_flavor))
+                    .route(web::delete().to(fixture_flavor_handler::remove_fixture_flavor))
+            )
 
             // Workout management routes
             .service(
@@ -154,5 +264,154 @@ pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
                 web::resource("/notifications/send")
                     .route(web::post().to(notification_handler::send_notification))
             )
+
+            // Announcements to all users, a league or a team
+            .service(
+                web::resource("/broadcast")
+                    .route(web::get().to(broadcast_handler::get_broadcasts))
+                    .route(web::post().to(broadcast_handler::create_broadcast))
+            )
+            .service(
+                web::resource("/broadcast/{id}")
+                    .route(web::get().to(broadcast_handler::get_broadcast))
+                    .route(web::delete().to(broadcast_handler::cancel_broadcast))
+            )
+
+            // Scheduler job observability
+            .service(
+                web::resource("/scheduler/jobs")
+                    .route(web::get().to(scheduler_handler::get_scheduler_jobs))
+            )
+            .service(
+                web::resource("/scheduler/jobs/{name}")
+                    .route(web::get().to(scheduler_handler::get_scheduler_job))
+            )
+            .service(
+                web::resource("/scheduler/jobs/{name}/run")
+                    .route(web::post().to(scheduler_handler::run_scheduler_job))
+            )
+            .service(
+                web::resource("/scheduler/schedules")
+                    .route(web::get().to(scheduler_handler::get_job_schedules))
+            )
+            .service(
+                web::resource("/scheduler/schedules/reload")
+                    .route(web::post().to(scheduler_handler::reload_job_schedules))
+            )
+            .service(
+                web::resource("/scheduler/schedules/{name}")
+                    .route(web::put().to(scheduler_handler::update_job_schedule))
+            )
+
+            // Runtime log level and sampling routes
+            .service(
+                web::resource("/telemetry/log-controls")
+                    .route(web::get().to(telemetry_handler::get_log_controls))
+                    .route(web::put().to(telemetry_handler::update_log_controls))
+            )
+
+            // Server-to-server API key routes
+            .service(
+                web::resource("/api-keys")
+                    .route(web::get().to(api_key_handler::get_api_keys))
+                    .route(web::post().to(api_key_handler::create_api_key))
+            )
+            .service(
+                web::resource("/api-keys/{id}")
+                    .route(web::delete().to(api_key_handler::delete_api_key))
+            )
+
+            // Login lockout and security event routes
+            .service(
+                web::resource("/security-events")
+                    .route(web::get().to(security_handler::get_security_events))
+            )
+            .service(
+                web::resource("/users/{id}/login-lockout")
+                    .route(web::delete().to(security_handler::unlock_user_login))
+            )
+
+            // Backup verification routes
+            .service(
+                web::resource("/backups/verifications")
+                    .route(web::get().to(backup_handler::get_backup_verifications))
+            )
+            .service(
+                web::resource("/backups/verifications/{id}")
+                    .route(web::get().to(backup_handler::get_backup_verification))
+            )
+            .service(
+                web::resource("/backups/verify")
+                    .route(web::post().to(backup_handler::trigger_backup_verification))
+            )
+            // Score consistency routes
+            .service(
+                web::resource("/consistency")
+                    .wrap(AdminQuota::new("consistency_check", QuotaPolicy::RECALCULATION))
+                    .route(web::get().to(consistency_handler::get_score_consistency))
+            )
+            .service(
+                web::resource("/consistency/runs")
+                    .route(web::get().to(consistency_handler::get_score_consistency_runs))
+            )
+            // Reporting exports
+            .service(
+                web::resource("/export")
+                    .wrap(AdminQuota::new("export", QuotaPolicy::EXPORT))
+                    .route(web::get().to(export_handler::export_csv))
+            )
+            .service(
+                web::resource("/research/datasets")
+                    .wrap(AdminQuota::new("research_dataset", QuotaPolicy::EXPORT))
+                    .route(web::get().to(research_handler::get_research_dataset))
+            )
+            // Photo moderation
+            .service(
+                web::resource("/media/takedown")
+                    .route(web::post().to(media_handler::take_down_media))
+            )
+            // Activity catalog
+            .service(
+                web::resource("/activities")
+                    .route(web::get().to(activity_handler::get_activities))
+                    .route(web::post().to(activity_handler::create_activity))
+            )
+            .service(
+                web::resource("/activities/{id}")
+                    .route(web::patch().to(activity_handler::update_activity))
+                    .route(web::delete().to(activity_handler::delete_activity))
+            )
+            // Organization branding and defaults
+            .service(
+                web::resource("/organizations")
+                    .route(web::get().to(organization_handler::get_organizations))
+                    .route(web::post().to(organization_handler::create_organization))
+            )
+            .service(
+                web::resource("/organizations/{id}")
+                    .route(web::patch().to(organization_handler::update_organization))
+            )
+            .service(
+                web::resource("/organizations/{id}/import")
+                    .route(web::post().to(organization_handler::import_historical_data))
+            )
+            .service(
+                web::resource("/organizations/{id}/sso")
+                    .route(web::get().to(organization_sso_handler::get_sso_settings))
+                    .route(web::put().to(organization_sso_handler::set_sso_provider))
+                    .route(web::delete().to(organization_sso_handler::remove_sso_provider))
+            )
+            .service(
+                web::resource("/organizations/{id}/sso/groups")
+                    .route(web::put().to(organization_sso_handler::set_sso_group_mapping))
+            )
+            .service(
+                web::resource("/organizations/{id}/sso/groups/{mapping_id}")
+                    .route(web::delete().to(organization_sso_handler::remove_sso_group_mapping))
+            )
+            .service(
+                web::resource("/organizations/{id}/sso/scim-token")
+                    .route(web::post().to(organization_sso_handler::rotate_scim_token))
+            )
     );
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::db::login_activity::recent_login_attempts;
+use crate::middleware::auth::Claims;
+use crate::models::common::ApiResponse;
+
+/// Logins to the account listed in the login activity
+const LOGIN_ACTIVITY_LIMIT: i64 = 50;
+
+/// Recent logins to the user's account, failed ones included, so they can spot access they
+/// don't recognise
+#[get("/login-activity")]
+async fn get_login_activity(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match recent_login_attempts(pool.get_ref(), user_id, LOGIN_ACTIVITY_LIMIT).await {
+        Ok(attempts) => HttpResponse::Ok().json(ApiResponse::success("Login activity retrieved successfully", attempts)),
+        Err(e) => {
+            tracing::error!("Failed to load login activity of user {}: {:?}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to load login activity"))
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// src/routes/auth/mod.rs
+pub mod login_activity;
+pub mod oauth;
+pub mod sessions;
+pub mod sso;
+
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use sqlx::PgPool;
+
+use crate::handlers::auth_handler::{
+    login_user, refresh_access_token, refresh_biometric_token, revoke_refresh_token,
+    request_password_reset, confirm_password_reset, request_magic_link, consume_magic_link,
+};
+use crate::models::auth::{
+    LoginRequest, BiometricRefreshRequest, RefreshTokenRequest,
+    PasswordResetRequest, PasswordResetConfirmRequest, MagicLinkRequest, MagicLinkConsumeRequest,
+};
+use crate::services::{EmailService, LoginProtectionService, PasswordPolicyService};
+use crate::config::jwt::JwtSettings;
+
+#[post("/login")]
+async fn login(
+    req: HttpRequest,
+    login_form: web::Json<LoginRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+    login_protection: web::Data<LoginProtectionService>,
+) -> HttpResponse {
+    login_user(req, login_form, pool, jwt_settings, login_protection).await
+}
+
+#[post("/biometric-refresh")]
+async fn biometric_refresh(
+    req: HttpRequest,
+    refresh_form: web::Json<BiometricRefreshRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>
+) -> HttpResponse {
+    refresh_biometric_token(req, refresh_form, pool, jwt_settings).await
+}
+
+#[post("/refresh")]
+async fn refresh(
+    req: HttpRequest,
+    refresh_form: web::Json<RefreshTokenRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>
+) -> HttpResponse {
+    refresh_access_token(req, refresh_form, pool, jwt_settings).await
+}
+
+#[post("/logout")]
+async fn logout(
+    logout_form: web::Json<RefreshTokenRequest>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    revoke_refresh_token(logout_form, pool).await
+}
+
+#[post("/password-reset/request")]
+async fn password_reset_request(
+    req: HttpRequest,
+    reset_form: web::Json<PasswordResetRequest>,
+    pool: web::Data<PgPool>,
+    email_service: web::Data<EmailService>,
+    login_protection: web::Data<LoginProtectionService>,
+) -> HttpResponse {
+    request_password_reset(req, reset_form, pool, email_service, login_protection).await
+}
+
+#[post("/password-reset/confirm")]
+async fn password_reset_confirm(
+    confirm_form: web::Json<PasswordResetConfirmRequest>,
+    pool: web::Data<PgPool>,
+    password_policy: web::Data<PasswordPolicyService>,
+) -> HttpResponse {
+    confirm_password_reset(confirm_form, pool, password_policy).await
+}
+
+// Passwordless login; registered before the authenticated /auth scope, which would otherwise
+// claim the paths
+#[post("/auth/magic-link/request")]
+async fn magic_link_request(
+    req: HttpRequest,
+    link_form: web::Json<MagicLinkRequest>,
+    pool: web::Data<PgPool>,
+    email_service: web::Data<EmailService>,
+    login_protection: web::Data<LoginProtectionService>,
+) -> HttpResponse {
+    request_magic_link(req, link_form, pool, email_service, login_protection).await
+}
+
+#[post("/auth/magic-link/consume")]
+async fn magic_link_consume(
+    req: HttpRequest,
+    consume_form: web::Json<MagicLinkConsumeRequest>,
+    pool: web::Data<PgPool>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> HttpResponse {
+    consume_magic_link(req, consume_form, pool, jwt_settings).await
+}
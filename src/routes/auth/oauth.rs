@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::jwt::JwtSettings;
+use crate::db::oauth_identities::{link_identity, linked_user, login_linked_user};
+use crate::handlers::auth_handler::issue_login;
+use crate::handlers::registration_handler::insert_external_user;
+use crate::middleware::auth::{AccountSecurity, Claims};
+use crate::models::auth::{OAuthLoginRequest, OAuthProvider};
+use crate::models::common::ApiResponse;
+use crate::services::oauth_service::{OAuthError, VerifiedIdentity};
+use crate::services::OAuthService;
+
+/// User a provider login belongs to
+enum OAuthUser {
+    Found(Uuid),
+    /// Unknown provider account without a verified email to create a user with
+    NoVerifiedEmail,
+    /// A user with the email exists but never verified it, so they have to link the login themselves
+    LinkRequired,
+}
+
+/// Response for an ID token that could not be verified
+fn verification_failed(provider: OAuthProvider, error: OAuthError) -> HttpResponse {
+    match error {
+        OAuthError::NotConfigured => HttpResponse::NotFound().json(serde_json::json!({
+            "error": OAuthError::NotConfigured.to_string()
+        })),
+        OAuthError::InvalidToken(reason) => {
+            tracing::info!("Rejected {:?} ID token: {}", provider, reason);
+            HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid ID token"
+            }))
+        }
+        e @ OAuthError::KeysUnavailable(_) => {
+            tracing::error!("Could not verify {:?} ID token: {}", provider, e);
+            HttpResponse::ServiceUnavailable().finish()
+        }
+    }
+}
+
+#[post("/oauth/{provider}")]
+async fn oauth_login(
+    req: HttpRequest,
+    provider: web::Path<OAuthProvider>,
+    login_form: web::Json<OAuthLoginRequest>,
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<redis::Client>>,
+    jwt_settings: web::Data<JwtSettings>,
+    oauth_service: web::Data<OAuthService>,
+) -> HttpResponse {
+    let provider = provider.into_inner();
+    let identity = match oauth_service.verify(provider, &login_form.id_token).await {
+        Ok(identity) => identity,
+        Err(e) => return verification_failed(provider, e),
+    };
+
+    let user_id = match find_or_create_user(&pool, &redis_client, provider, &identity).await {
+        Ok(OAuthUser::Found(user_id)) => user_id,
+        Ok(OAuthUser::NoVerifiedEmail) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "No verified email was shared to create an account with"
+            }));
+        }
+        Ok(OAuthUser::LinkRequired) => {
+            return HttpResponse::Conflict().json(serde_json::json!({
+                "error": "An account with this email already exists. Log in to it and link this login from your account settings"
+            }));
+        }
+        Err(e) if e.as_database_error().is_some_and(|db_error| db_error.is_unique_violation()) => {
+            return HttpResponse::Conflict().json(serde_json::json!({
+                "error": "The account with this email is already linked to another login of this provider"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Database error during {:?} login: {:?}", provider, e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    issue_login(&req, pool.get_ref(), user_id, &jwt_settings).await
+}
+
+/// User of a provider account: the linked one, else the one with its email if they verified it,
+/// else a new account
+async fn find_or_create_user(
+    pool: &PgPool,
+    redis_client: &Arc<redis::Client>,
+    provider: OAuthProvider,
+    identity: &VerifiedIdentity,
+) -> Result<OAuthUser, sqlx::Error> {
+    if let Some(user_id) = login_linked_user(pool, provider, &identity.subject).await? {
+        return Ok(OAuthUser::Found(user_id));
+    }
+    let Some(email) = identity.email.as_deref() else {
+        return Ok(OAuthUser::NoVerifiedEmail);
+    };
+
+    let existing_user = sqlx::query!(
+        r#"SELECT id, email_verified_at IS NOT NULL as "email_verified!" FROM users WHERE LOWER(email) = $1"#,
+        email
+    )
+    .fetch_optional(pool)
+    .await?;
+    let user_id = match existing_user {
+        // Whoever registered the email may not own it, so the provider account can't take it over
+        Some(user) if !user.email_verified => {
+            tracing::info!("Not linking {:?} login to user {} with an unverified email", provider, user.id);
+            return Ok(OAuthUser::LinkRequired);
+        }
+        Some(user) => {
+            tracing::info!("Linking {:?} login to existing user {}", provider, user.id);
+            user.id
+        }
+        None => {
+            let user_id = insert_external_user(pool, redis_client, email).await?;
+            sqlx::query!("UPDATE users SET email_verified_at = NOW() WHERE id = $1", user_id)
+                .execute(pool)
+                .await?;
+            user_id
+        }
+    };
+
+    link_identity(pool, user_id, provider, &identity.subject, Some(email)).await?;
+    Ok(OAuthUser::Found(user_id))
+}
+
+/// Link an Apple or Google login to the logged in user, so it signs in to this account
+#[post("/oauth/{provider}/link", wrap = "AccountSecurity")]
+async fn link_oauth_login(
+    provider: web::Path<OAuthProvider>,
+    link_form: web::Json<OAuthLoginRequest>,
+    pool: web::Data<PgPool>,
+    oauth_service: web::Data<OAuthService>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let provider = provider.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+    let identity = match oauth_service.verify(provider, &link_form.id_token).await {
+        Ok(identity) => identity,
+        Err(e) => return verification_failed(provider, e),
+    };
+
+    match linked_user(pool.get_ref(), provider, &identity.subject).await {
+        Ok(Some(linked_user_id)) if linked_user_id == user_id => {
+            return HttpResponse::Ok().json(ApiResponse::<()>::success_message("Login already linked"));
+        }
+        Ok(Some(_)) => {
+            return HttpResponse::Conflict().json(ApiResponse::<()>::error("This login is linked to another account"));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!("Failed to look up {:?} login: {:?}", provider, e);
+            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to link login"));
+        }
+    }
+
+    match link_identity(pool.get_ref(), user_id, provider, &identity.subject, identity.email.as_deref()).await {
+        Ok(()) => {
+            tracing::info!("User {} linked a {:?} login", user_id, provider);
+            HttpResponse::Ok().json(ApiResponse::<()>::success_message("Login linked"))
+        }
+        Err(e) if e.as_database_error().is_some_and(|db_error| db_error.is_unique_violation()) => {
+            HttpResponse::Conflict().json(ApiResponse::<()>::error("Another login of this provider is already linked to your account"))
+        }
+        Err(e) => {
+            tracing::error!("Failed to link {:?} login to user {}: {:?}", provider, user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to link login"))
+        }
+    }
+}
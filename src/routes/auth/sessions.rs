@@ -0,0 +1,76 @@
+use actix_web::{delete, get, web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::user_sessions::{list_sessions, revoke_other_sessions, revoke_session};
+use crate::middleware::auth::{AccountSecurity, Claims};
+use crate::models::common::ApiResponse;
+
+/// Devices the user is logged in on, marking the one making the request
+#[get("/sessions")]
+async fn list_user_sessions(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match list_sessions(pool.get_ref(), user_id, claims.sid).await {
+        Ok(sessions) => HttpResponse::Ok().json(ApiResponse::success("Sessions retrieved successfully", sessions)),
+        Err(e) => {
+            tracing::error!("Failed to list sessions of user {}: {:?}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to load sessions"))
+        }
+    }
+}
+
+/// Log out one device; its refresh and access tokens stop working right away
+#[delete("/sessions/{session_id}", wrap = "AccountSecurity")]
+async fn revoke_user_session(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let session_id = path.into_inner();
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match revoke_session(pool.get_ref(), user_id, session_id).await {
+        Ok(true) => {
+            tracing::info!("User {} logged out session {}", user_id, session_id);
+            HttpResponse::Ok().json(ApiResponse::<()>::success_message("Session logged out"))
+        }
+        Ok(false) => HttpResponse::NotFound().json(ApiResponse::<()>::error("Session not found")),
+        Err(e) => {
+            tracing::error!("Failed to revoke session {} of user {}: {:?}", session_id, user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to log out session"))
+        }
+    }
+}
+
+/// Log out every device except the one making the request
+#[delete("/sessions", wrap = "AccountSecurity")]
+async fn revoke_other_user_sessions(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    let Some(user_id) = claims.user_id() else {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Invalid user ID"));
+    };
+
+    match revoke_other_sessions(pool.get_ref(), user_id, claims.sid).await {
+        Ok(revoked) => {
+            tracing::info!("User {} logged out {} other sessions", user_id, revoked);
+            HttpResponse::Ok().json(ApiResponse::success(
+                "Other sessions logged out",
+                serde_json::json!({ "revoked": revoked }),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Failed to revoke other sessions of user {}: {:?}", user_id, e);
+            HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Failed to log out sessions"))
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use actix_web::{delete, patch, post, web, HttpRequest, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::jwt::JwtSettings;
+use crate::handlers::auth_handler::issue_login;
+use crate::models::auth::OAuthLoginRequest;
+use crate::models::organization::{ScimUserPatch, SsoIdentity};
+use crate::services::oauth_service::OAuthError;
+use crate::services::organization_sso_service::SsoLogin;
+use crate::services::{OAuthService, OrganizationSsoService};
+
+#[post("/sso/{org_slug}")]
+async fn sso_login(
+    request: HttpRequest,
+    org_slug: web::Path<String>,
+    login_form: web::Json<OAuthLoginRequest>,
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<redis::Client>>,
+    jwt_settings: web::Data<JwtSettings>,
+    oauth_service: web::Data<OAuthService>,
+) -> HttpResponse {
+    let sso_service = OrganizationSsoService::new(pool.get_ref().clone());
+    let provider = match sso_service.provider_for_login(&org_slug).await {
+        Ok(Some(provider)) => provider,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Organization has no single sign-on"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Database error occurred: {:?}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let claims = match oauth_service.verify_oidc(&provider.issuer_url, &provider.client_id, &login_form.id_token).await {
+        Ok(claims) => claims,
+        Err(e @ OAuthError::KeysUnavailable(_)) => {
+            tracing::error!("Could not verify SSO ID token of {}: {}", org_slug, e);
+            return HttpResponse::ServiceUnavailable().finish();
+        }
+        Err(e) => {
+            tracing::info!("Rejected SSO ID token of {}: {}", org_slug, e);
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid ID token"
+            }));
+        }
+    };
+    let identity = match SsoIdentity::from_claims(&claims, &provider.groups_claim) {
+        Ok(identity) => identity,
+        Err(reason) => {
+            tracing::info!("Rejected SSO ID token of {}: {}", org_slug, reason);
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "Invalid ID token"
+            }));
+        }
+    };
+
+    let user_id = match sso_service.login(&provider, &identity, &redis_client).await {
+        Ok(SsoLogin::User(user_id)) => user_id,
+        Ok(SsoLogin::Deactivated) => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Your account was deactivated by your organization"
+            }));
+        }
+        Ok(SsoLogin::NotProvisioned) => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "Your organization has not given you access yet"
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Database error during SSO login of {}: {:?}", org_slug, e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    issue_login(&request, pool.get_ref(), user_id, &jwt_settings).await
+}
+
+/// Organization whose SCIM token the request carries
+async fn scim_organization(request: &HttpRequest, sso_service: &OrganizationSsoService) -> Result<Uuid, HttpResponse> {
+    let token = request
+        .headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .ok_or_else(|| HttpResponse::Unauthorized().finish())?;
+
+    match sso_service.organization_for_scim_token(token).await {
+        Ok(Some(organization_id)) => Ok(organization_id),
+        Ok(None) => Err(HttpResponse::Unauthorized().finish()),
+        Err(e) => {
+            tracing::error!("Database error occurred: {:?}", e);
+            Err(HttpResponse::InternalServerError().finish())
+        }
+    }
+}
+
+async fn set_directory_user_active(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    subject: &str,
+    active: bool,
+) -> HttpResponse {
+    let sso_service = OrganizationSsoService::new(pool.get_ref().clone());
+    let organization_id = match scim_organization(&request, &sso_service).await {
+        Ok(organization_id) => organization_id,
+        Err(response) => return response,
+    };
+
+    match sso_service.set_active(organization_id, subject, active).await {
+        Ok(Some(_)) => HttpResponse::Ok().json(serde_json::json!({ "id": subject, "active": active })),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(e) => {
+            tracing::error!("Database error occurred: {:?}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[patch("/scim/v2/Users/{subject}")]
+async fn scim_patch_user(
+    request: HttpRequest,
+    subject: web::Path<String>,
+    patch: web::Json<ScimUserPatch>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    let Some(active) = patch.active() else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Only the active attribute can be changed"
+        }));
+    };
+    set_directory_user_active(request, pool, &subject, active).await
+}
+
+#[delete("/scim/v2/Users/{subject}")]
+async fn scim_delete_user(
+    request: HttpRequest,
+    subject: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    set_directory_user_active(request, pool, &subject, false).await
+}
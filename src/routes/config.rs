@@ -0,0 +1,18 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::handlers::config_handler;
+use crate::middleware::etag::ConditionalGet;
+use crate::models::organization::BrandingQuery;
+
+#[get("/branding", wrap = "ConditionalGet")]
+async fn get_branding(
+    pool: web::Data<PgPool>,
+    query: web::Query<BrandingQuery>,
+) -> HttpResponse {
+    config_handler::get_branding_config(pool, query).await
+}
+
+pub fn init_config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_branding);
+}
@@ -0,0 +1,11 @@
+use actix_web::web;
+
+use crate::handlers::admin::fault_injection_handler;
+
+pub fn init_fault_injection_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(fault_injection_handler::get_fault_injection))
+            .route(web::put().to(fault_injection_handler::update_fault_injection))
+    );
+}
@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::graphql_handler;
+
+pub fn init_graphql_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::post().to(graphql_handler::graphql))
+    )
+    .service(
+        web::resource("/schema")
+            .route(web::get().to(graphql_handler::graphql_schema))
+    );
+}
@@ -1,19 +1,27 @@
-use actix_web::{post, web, HttpResponse};
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use crate::handlers::workout_data::upload_payload::read_workout_upload;
 use crate::handlers::workout_data::upload_workout_data::upload_workout_data;
 use crate::middleware::auth::Claims;
-use crate::models::workout_data::WorkoutDataUploadRequest;
 use crate::config::jwt::JwtSettings;
+use crate::config::upload_limits::UploadLimitsSettings;
 use crate::services::ml_client::MLClient;
 use std::sync::Arc;
 
 #[post("/upload_health")]
+#[allow(clippy::too_many_arguments)]
 async fn upload_health(
-    data: web::Json<WorkoutDataUploadRequest>,
+    req: HttpRequest,
+    payload: web::Payload,
+    limits: web::Data<UploadLimitsSettings>,
     pool: web::Data<sqlx::PgPool>,
     redis: Option<web::Data<Arc<redis::Client>>>,
     claims: web::ReqData<Claims>,
     jwt_settings: web::Data<JwtSettings>,
     ml_client: web::Data<MLClient>,
 ) -> HttpResponse {
-    upload_workout_data(data, pool, redis, claims, jwt_settings, ml_client).await
+    let data = match read_workout_upload(&req, payload, &limits).await {
+        Ok(data) => data,
+        Err(response) => return response,
+    };
+    upload_workout_data(web::Json(data), pool, redis, claims, jwt_settings, ml_client).await
 }
@@ -1,5 +1,5 @@
 // src/routes/league.rs
-use actix_web::{delete, get, post, put, web, HttpResponse, Result};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Result};
 use sqlx::PgPool;
 use uuid::Uuid;
 use std::sync::Arc;
@@ -16,10 +16,22 @@ use crate::handlers::league::{
     team_poll_handler,
     team_invitation_handler,
     player_pool_handler,
-    live_game_handler
+    live_game_handler,
+    zone_stats_handler,
+    team_activity_handler,
+    league_analytics_handler,
+    booster_handler,
+    quest_handler,
+    team_notification_handler,
+    league_rules_handler,
+    cup_handler,
+    spectator_handler
 };
+use crate::config::jwt::JwtSettings;
 use crate::handlers::league::league_users_handler::PaginationParams;
+use crate::handlers::profile::player_card;
 use crate::middleware::auth::Claims;
+use crate::middleware::etag::{if_match_version, ConditionalGet};
 use crate::models::{league::*, team_invitation::*, team::*, chat::*};
 
 /// Create a new league season
@@ -60,7 +72,7 @@ async fn get_all_seasons(
 }
 
 /// Get season schedule
-#[get("/seasons/{season_id}/schedule")]
+#[get("/seasons/{season_id}/schedule", wrap = "ConditionalGet")]
 async fn get_season_schedule(
     path: web::Path<Uuid>,
     pool: web::Data<PgPool>,
@@ -69,8 +81,50 @@ async fn get_season_schedule(
     season_handler::get_league_schedule(season_id, pool).await
 }
 
+/// Get participation and score analytics of a league season
+#[get("/{league_id}/analytics")]
+async fn get_league_analytics(
+    path: web::Path<Uuid>,
+    query: web::Query<crate::models::league_analytics::LeagueAnalyticsQuery>,
+    pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<RedisClient>>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    league_analytics_handler::get_league_analytics(pool, redis, path, query, claims).await
+}
+
+/// Get the league's cooperative quests and their progress
+#[get("/{league_id}/quests")]
+async fn get_league_quests(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    quest_handler::get_league_quests(pool, path, claims).await
+}
+
+/// Get the league's rules page
+#[get("/{league_id}/rules", wrap = "ConditionalGet")]
+async fn get_league_rules(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    league_rules_handler::get_league_rules(pool, path).await
+}
+
+/// Get heart rate zone minutes per team for a season
+#[get("/seasons/{season_id}/zones")]
+async fn get_season_zone_breakdown(
+    path: web::Path<Uuid>,
+    query: web::Query<zone_stats_handler::SeasonZoneQuery>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    zone_stats_handler::get_season_zone_breakdown(pool, path, query, claims).await
+}
+
 /// Get season standings
-#[get("/seasons/{season_id}/standings")]
+#[get("/seasons/{season_id}/standings", wrap = "ConditionalGet")]
 async fn get_season_standings(
     path: web::Path<Uuid>,
     pool: web::Data<PgPool>,
@@ -79,6 +133,48 @@ async fn get_season_standings(
     season_handler::get_league_standings(season_id, pool).await
 }
 
+/// Get the season recap for the league and every team
+#[get("/seasons/{season_id}/recap")]
+async fn get_season_recap(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+    season_handler::get_season_recap(season_id, pool).await
+}
+
+/// Get league-wide stats of a season
+#[get("/seasons/{season_id}/stats")]
+async fn get_season_stats(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+    season_handler::get_season_stats(season_id, pool).await
+}
+
+/// Queue shareable recap cards for rendering
+#[post("/seasons/{season_id}/recap/cards")]
+async fn request_season_recap_cards(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+    season_handler::request_season_recap_cards(season_id, pool).await
+}
+
+/// Download a rendered recap card
+#[get("/seasons/{season_id}/recap/card")]
+async fn get_season_recap_card(
+    path: web::Path<Uuid>,
+    query: web::Query<season_handler::RecapCardQuery>,
+    pool: web::Data<PgPool>,
+    minio_service: web::Data<crate::services::MinIOService>,
+) -> Result<HttpResponse> {
+    let season_id = path.into_inner();
+    season_handler::get_season_recap_card(season_id, query, pool, minio_service).await
+}
+
 /// Update game result
 #[put("/games/{game_id}/result")]
 async fn update_game_result(
@@ -118,6 +214,15 @@ async fn get_live_active_games(
     game_handler::get_league_live_games(query, pool).await
 }
 
+/// Get the window in which the current user's workouts count for their next game
+#[get("/games/next-window")]
+async fn get_next_game_window(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    game_handler::get_next_game_window(pool, claims).await
+}
+
 /// Get recent results
 #[get("/games/results")]
 async fn get_recent_results(
@@ -127,6 +232,33 @@ async fn get_recent_results(
     league_handler::get_league_recent_results(query, pool).await
 }
 
+/// Get league and cup games of a season in one calendar
+#[get("/seasons/{season_id}/calendar")]
+async fn get_season_calendar(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    cup_handler::get_season_calendar(pool, path).await
+}
+
+/// Get the cups of a season
+#[get("/seasons/{season_id}/cups")]
+async fn get_season_cups(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    cup_handler::get_season_cups(pool, path).await
+}
+
+/// Get a cup's bracket
+#[get("/cups/{cup_id}")]
+async fn get_cup(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    cup_handler::get_cup_bracket(pool, path).await
+}
+
 /// Get games for specific week
 #[get("/seasons/{season_id}/weeks/{week_number}")]
 async fn get_game_week(
@@ -183,9 +315,19 @@ async fn update_team(
     team_update: web::Json<TeamUpdateRequest>,
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
+    req: HttpRequest,
 ) -> Result<HttpResponse> {
     let team_id = path.into_inner();
-    team_handler::update_team_information(team_id, team_update, pool, claims).await
+    let expected_version = match if_match_version(&req) {
+        Ok(version) => version,
+        Err(message) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "message": message
+            })));
+        }
+    };
+    team_handler::update_team_information(team_id, team_update, pool, claims, expected_version).await
 }
 
 /// Get team's league history
@@ -198,6 +340,17 @@ async fn get_team_history(
     team_handler::get_team_league_history(team_id, pool).await
 }
 
+/// Get a per-member × per-day activity matrix for the current game week or season
+#[get("/teams/{team_id}/activity-heatmap")]
+async fn get_team_activity_heatmap(
+    path: web::Path<Uuid>,
+    query: web::Query<team_activity_handler::ActivityHeatmapQuery>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    team_activity_handler::get_team_activity_heatmap(pool, path, query, claims).await
+}
+
 /// Add a user to a team
 #[post("/teams/{team_id}/members")]
 async fn add_team_member(
@@ -231,6 +384,18 @@ async fn remove_team_member(
     team_member_handler::remove_team_member(path, pool, redis_client, claims).await
 }
 
+/// Move a user to another team in the same step
+#[post("/teams/{team_id}/members/{user_id}/transfer")]
+async fn transfer_team_member(
+    path: web::Path<(Uuid, Uuid)>,
+    request: web::Json<TransferTeamMemberRequest>,
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<RedisClient>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    team_member_handler::transfer_team_member(path, request, pool, redis_client, claims).await
+}
+
 /// Update current user's team status (active/inactive)
 #[actix_web::patch("/teams/{team_id}/my-status")]
 pub async fn update_my_team_status(
@@ -257,10 +422,11 @@ async fn update_team_member(
 #[get("/users/stats")]
 async fn get_league_users_with_stats(
     pool: web::Data<PgPool>,
+    redis_client: Option<web::Data<Arc<RedisClient>>>,
     claims: web::ReqData<Claims>,
     query: web::Query<PaginationParams>
 ) -> Result<HttpResponse> {
-    league_users_handler::get_league_users_with_stats(pool, claims, query).await
+    league_users_handler::get_league_users_with_stats(pool, redis_client, claims, query).await
 }
 
 /// Search users for mentions/tagging
@@ -273,6 +439,15 @@ async fn search_users(
     league_users_handler::search_users(pool, claims, query).await
 }
 
+/// Player card of a user for the team roster screen
+#[get("/users/{user_id}/player-card")]
+async fn get_player_card(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> Result<HttpResponse> {
+    Ok(player_card::get_player_card(pool, path.into_inner()).await)
+}
+
 /// Get live scores for all active games
 #[get("/games/live")]
 async fn get_live_scores(
@@ -294,6 +469,18 @@ async fn get_game_live_score(
     live_game_handler::get_game_live_score(pool, path, query, claims).await
 }
 
+/// Create a spectator token to share a game that hasn't finished with guests
+#[post("/games/{game_id}/spectator-token")]
+async fn create_spectator_token(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<Arc<RedisClient>>,
+    jwt_settings: web::Data<JwtSettings>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    spectator_handler::create_spectator_token(pool, redis_client, jwt_settings, path, claims).await
+}
+
 /// Get aggregated player scores for a game
 #[get("/games/{game_id}/player-scores")]
 async fn get_game_player_scores(
@@ -304,6 +491,59 @@ async fn get_game_player_scores(
     live_game_handler::get_game_player_scores(pool, path, claims).await
 }
 
+/// Get bucketed score progression for a game
+#[get("/games/{game_id}/timeline")]
+async fn get_game_timeline(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    live_game_handler::get_game_timeline(pool, path, claims).await
+}
+
+/// Get the automated commentary of a game
+#[get("/games/{game_id}/commentary")]
+async fn get_game_commentary(
+    path: web::Path<Uuid>,
+    query: web::Query<live_game_handler::GameCommentaryQuery>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    live_game_handler::get_game_commentary(pool, path, query, claims).await
+}
+
+/// Get heart rate zone minutes per team for a game
+#[get("/games/{game_id}/zones")]
+async fn get_game_zone_breakdown(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    zone_stats_handler::get_game_zone_breakdown(pool, path, claims).await
+}
+
+/// Get the boosters used in a game and the caller's team inventory
+#[get("/games/{game_id}/boosters")]
+async fn get_game_boosters(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    booster_handler::get_game_boosters(pool, path, claims).await
+}
+
+/// Activate one of the team's boosters in a live game
+#[post("/games/{game_id}/boosters")]
+async fn activate_booster(
+    path: web::Path<Uuid>,
+    body: web::Json<crate::models::booster::ActivateBoosterRequest>,
+    pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<RedisClient>>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    booster_handler::activate_booster(pool, redis, path, body, claims).await
+}
+
 /// Get all currently active games
 #[get("/games/active")]
 async fn get_active_games(
@@ -353,6 +593,17 @@ async fn send_team_invitation(
     Ok(team_invitation_handler::send_invitation(pool, redis_client, claims, team_id, request).await)
 }
 
+/// Create an invite code new users can register into the team with
+#[post("/teams/{team_id}/invites")]
+async fn create_team_invite_code(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    team_id: web::Path<Uuid>,
+    request: web::Json<CreateInviteCodeRequest>,
+) -> Result<HttpResponse> {
+    Ok(team_invitation_handler::create_team_invite_code(pool, claims, team_id, request).await)
+}
+
 /// Get user's team invitations
 #[get("/invitations")]
 async fn get_user_invitations(
@@ -419,6 +670,27 @@ async fn delete_poll(
     Ok(team_poll_handler::delete_poll(pool, path, claims).await)
 }
 
+/// Get the team events the team's members are notified about
+#[get("/teams/{team_id}/notification-settings")]
+async fn get_team_notification_settings(
+    path: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    team_notification_handler::get_team_notification_settings(pool, path, claims).await
+}
+
+/// Choose the team events the team's members are notified about (captains only)
+#[put("/teams/{team_id}/notification-settings")]
+async fn update_team_notification_settings(
+    path: web::Path<Uuid>,
+    body: web::Json<crate::models::notification::UpdateTeamNotificationSettingsRequest>,
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    team_notification_handler::update_team_notification_settings(pool, path, body, claims).await
+}
+
 /// Send a chat message to a team
 #[post("/teams/{team_id}/chat")]
 async fn send_team_chat(
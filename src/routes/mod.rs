@@ -1,4 +1,4 @@
-use actix_web::web;
+use actix_web::{middleware::Compress, web};
 
 pub mod registration;
 pub mod backend_health;
@@ -15,43 +15,106 @@ pub mod posts;
 pub mod media;
 pub mod analytics;
 pub mod notifications;
+pub mod sync;
+pub mod public;
+pub mod config;
+pub mod share;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "sandbox")]
+pub mod sandbox;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 
 use crate::middleware::auth::AuthMiddleware;
+#[cfg(any(feature = "sandbox", feature = "fault-injection"))]
+use crate::middleware::admin::AdminMiddleware;
 
 pub fn init_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(registration::register)
+        .service(registration::check_username)
         .service(backend_health::backend_health)
         .service(auth::login)
         .service(auth::biometric_refresh)
-        .service(auth::reset_password_route);
-    // Health routes (require authentication)
+        .service(auth::refresh)
+        .service(auth::logout)
+        .service(auth::password_reset_request)
+        .service(auth::password_reset_confirm)
+        .service(auth::magic_link_request)
+        .service(auth::magic_link_consume)
+        .service(auth::oauth::oauth_login)
+        .service(auth::sso::sso_login)
+        .service(auth::sso::scim_patch_user)
+        .service(auth::sso::scim_delete_user);
+    // Sessions and linked logins of the logged in user (require authentication)
+    cfg.service(
+        web::scope("/auth")
+            .wrap(AuthMiddleware)
+            .service(auth::sessions::list_user_sessions)
+            .service(auth::sessions::revoke_other_user_sessions)
+            .service(auth::sessions::revoke_user_session)
+            .service(auth::login_activity::get_login_activity)
+            .service(auth::oauth::link_oauth_login)
+    );
+    // Health routes (require authentication); workout history and details carry long
+    // heart rate series, so responses are compressed when the client accepts it
     cfg.service(
         web::scope("/health")
             .wrap(AuthMiddleware)
+            .wrap(Compress::default())
             .service(health_data::upload_health)
             .service(workout_sync::get_workout_hist)
             .service(workout_sync::get_workout_detail_handler)
+            .service(workout_sync::update_workout_notes_handler)
+            .service(workout_sync::create_share_link_handler)
+            .service(workout_sync::revoke_share_link_handler)
+            .service(workout_sync::compare_workout_handler)
+            .service(workout_sync::get_score_breakdown_handler)
             .service(workout_sync::check_workout_sync_handler)
+            .service(workout_sync::get_change_token_handler)
+            .service(workout_sync::store_change_token_handler)
+            .service(workout_sync::clear_change_token_handler)
             .service(workout_sync::submit_scoring_feedback_handler)
             .service(workout_sync::get_scoring_feedback_handler)
             .service(workout_sync::submit_workout_report_handler)
             .service(workout_sync::get_my_report_for_workout_handler)
             .service(workout_sync::get_my_reports_handler)
             .service(workout_sync::delete_workout_report_handler)
+            .service(workout_sync::get_hr_trends_handler)
+            .service(workout_sync::recalculate_workout_zones_handler)
+            .service(workout_sync::record_body_metrics_handler)
+            .service(workout_sync::get_body_metrics_handler)
+            .service(workout_sync::delete_body_metric_handler)
+            .service(workout_sync::get_body_metrics_analytics_handler)
+            .service(workout_sync::get_activity_catalog_handler)
     );
     // Profile routes (require authentication)
     cfg.service(
         web::scope("/profile")
             .wrap(AuthMiddleware)
             .service(profile::get_user)
+            .service(profile::get_player_card)
             .service(profile::get_health_prof)
             .service(profile::update_health_prof)
+            .service(profile::get_health_prof_history)
+            .service(profile::import_health_prof)
+            .service(profile::get_threshold_props)
+            .service(profile::accept_threshold_prop)
+            .service(profile::decline_threshold_prop)
             .service(profile::request_profile_picture_upload_url_handler)
             .service(profile::confirm_profile_picture_upload_handler)
             .service(profile::get_profile_picture_download_url_handler)
             .service(profile::serve_profile_picture)
             .service(profile::get_status)
             .service(profile::update_status)
+            .service(profile::get_consent)
+            .service(profile::update_consent)
+            .service(profile::get_posting)
+            .service(profile::update_posting)
+            .service(profile::get_diagnostics)
+            .service(profile::request_account_deletion)
+            .service(profile::export_account)
+            .service(profile::change_username)
     );
     // League routes (require authentication)
     cfg.service(
@@ -63,52 +126,100 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
             .service(league::get_all_seasons)
             .service(league::get_season_schedule)
             .service(league::get_season_standings)
+            .service(league::get_season_recap)
+            .service(league::get_season_stats)
+            .service(league::request_season_recap_cards)
+            .service(league::get_season_recap_card)
+            .service(league::get_season_zone_breakdown)
             .service(league::update_game_result)
             .service(league::get_countdown_info)
             .service(league::get_upcoming_games)
             .service(league::get_live_active_games)
             .service(league::get_recent_results)
+            .service(league::get_next_game_window)
             .service(league::get_game_week)
+            .service(league::get_season_calendar)
+            .service(league::get_season_cups)
+            .service(league::get_cup)
             .service(league::register_team)
             .service(league::get_user_team)
             .service(league::get_team_info)
             .service(league::get_all_teams)
             .service(league::update_team)
             .service(league::get_team_history)
+            .service(league::get_team_activity_heatmap)
             .service(league::add_team_member)
             .service(league::get_team_members)
             .service(league::remove_team_member)
+            .service(league::transfer_team_member)
             .service(league::update_my_team_status)
             .service(league::update_team_member)
             .service(league::get_league_users_with_stats)
             .service(league::search_users)
+            .service(league::get_player_card)
             .service(league::get_live_scores)
             .service(league::get_game_live_score)
+            .service(league::create_spectator_token)
             .service(league::get_game_player_scores)
+            .service(league::get_game_timeline)
+            .service(league::get_game_commentary)
+            .service(league::get_game_zone_breakdown)
+            .service(league::get_game_boosters)
+            .service(league::activate_booster)
             .service(league::get_active_games)
             .service(league::manage_games)
             .service(league::get_game_summary)
             .service(league::get_player_pool)
             .service(league::send_team_invitation)
+            .service(league::create_team_invite_code)
             .service(league::get_user_invitations)
             .service(league::respond_to_invitation)
             .service(league::create_team_poll)
             .service(league::get_team_polls)
             .service(league::cast_poll_vote)
             .service(league::delete_poll)
+            .service(league::get_team_notification_settings)
+            .service(league::update_team_notification_settings)
             .service(league::send_team_chat)
             .service(league::get_team_chat)
             .service(league::get_unread_chat_count)
             .service(league::mark_team_chat_read)  // Must come before edit/delete to avoid UUID parsing conflict
             .service(league::edit_team_chat)
             .service(league::delete_team_chat)
+            // Catch-all shapes, keep last
+            .service(league::get_league_quests)
+            .service(league::get_league_rules)
+            .service(league::get_league_analytics)
     );
     // WebSocket routes (authentication handled in route)
     cfg.service(
         web::resource("/game-ws")
             .route(web::get().to(websocket::game_ws_route))
     );
+    // Ops dashboard live metrics; registered before the admin scope because the admin
+    // check happens in the route (the JWT comes as query parameter)
+    cfg.service(
+        web::resource("/admin/monitor-ws")
+            .route(web::get().to(websocket::admin_monitor_ws_route))
+    );
     
+    // Sandbox organizations for QA and app store review (require admin authentication);
+    // registered before the admin scope, which would otherwise claim the path
+    #[cfg(feature = "sandbox")]
+    cfg.service(
+        web::scope("/admin/sandbox")
+            .wrap(AdminMiddleware)
+            .configure(sandbox::init_sandbox_routes)
+    );
+
+    // Simulated dependency failures for resilience testing (require admin authentication)
+    #[cfg(feature = "fault-injection")]
+    cfg.service(
+        web::scope("/admin/fault-injection")
+            .wrap(AdminMiddleware)
+            .configure(fault_injection::init_fault_injection_routes)
+    );
+
     // Admin routes (require admin authentication)
     admin::init_admin_routes(cfg);
 
@@ -123,6 +234,7 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/feed")
             .wrap(AuthMiddleware)
+            .wrap(Compress::default())
             .configure(feed::init_feed_routes)
     );
 
@@ -153,4 +265,37 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
             .wrap(AuthMiddleware)
             .configure(notifications::init_notification_routes)
     );
+
+    // Delta sync routes (require authentication)
+    cfg.service(
+        web::scope("/sync")
+            .wrap(AuthMiddleware)
+            .configure(sync::init_sync_routes)
+    );
+
+    // Public share links (no authentication)
+    cfg.service(
+        web::scope("/public")
+            .configure(public::init_public_routes)
+    );
+
+    // Share cards: requesting one needs authentication, the images are public
+    cfg.service(
+        web::scope("/share")
+            .configure(share::init_share_routes)
+    );
+
+    // App configuration for the white-labeled apps (no authentication, needed before login)
+    cfg.service(
+        web::scope("/config")
+            .configure(config::init_config_routes)
+    );
+
+    // GraphQL facade (requires authentication)
+    #[cfg(feature = "graphql")]
+    cfg.service(
+        web::scope("/graphql")
+            .wrap(AuthMiddleware)
+            .configure(graphql::init_graphql_routes)
+    );
 }
\ No newline at end of file
@@ -22,5 +22,19 @@ pub fn init_notification_routes(cfg: &mut web::ServiceConfig) {
     .service(
         web::resource("/badge-count")
             .route(web::get().to(notification_handler::get_badge_count))
+    )
+    .service(
+        web::resource("/preferences")
+            .route(web::get().to(notification_handler::get_notification_preferences))
+            .route(web::put().to(notification_handler::update_notification_preferences))
+    )
+    .service(
+        web::resource("/preferences/quiet-hours")
+            .route(web::put().to(notification_handler::set_quiet_hours))
+            .route(web::delete().to(notification_handler::clear_quiet_hours))
+    )
+    .service(
+        web::resource("/digests")
+            .route(web::get().to(notification_handler::get_weekly_digests))
     );
 }
@@ -1,6 +1,6 @@
 use actix_web::web;
 use crate::handlers::posts::post_handler::{
-    create_post, update_post, delete_post, get_post, get_post_by_workout_id
+    create_post, update_post, delete_post, get_post, get_post_by_workout_id, list_drafts, publish_post
 };
 
 pub fn init_posts_routes(cfg: &mut web::ServiceConfig) {
@@ -9,6 +9,12 @@ pub fn init_posts_routes(cfg: &mut web::ServiceConfig) {
             .route(web::post().to(create_post))
     );
 
+    // Registered before /{post_id} so "drafts" isn't taken for a post id
+    cfg.service(
+        web::resource("/drafts")
+            .route(web::get().to(list_drafts))
+    );
+
     cfg.service(
         web::resource("/{post_id}")
             .route(web::get().to(get_post))
@@ -16,6 +22,11 @@ pub fn init_posts_routes(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(delete_post))
     );
 
+    cfg.service(
+        web::resource("/{post_id}/publish")
+            .route(web::post().to(publish_post))
+    );
+
     cfg.service(
         web::resource("/workout/{workout_id}")
             .route(web::get().to(get_post_by_workout_id))
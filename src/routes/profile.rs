@@ -1,24 +1,49 @@
-use actix_web::{web, get, put, post, patch, HttpResponse};
+use actix_web::{web, get, put, post, patch, HttpRequest, HttpResponse};
 use sqlx::PgPool;
+use std::sync::Arc;
 use crate::handlers::profile::profile::{get_user_profile, UserProfileQuery};
-use crate::handlers::profile::health_profile::{get_health_profile, update_health_profile, HealthProfileQuery};
+use crate::handlers::profile::health_profile::{
+    get_health_profile, get_health_profile_history_handler, import_health_profile, update_health_profile, HealthProfileQuery,
+    get_threshold_proposals, decide_threshold_proposal, ThresholdProposalsQuery
+};
 use crate::handlers::profile::profile_picture::{
     request_profile_picture_upload_url,
     confirm_profile_picture_upload,
     get_profile_picture_download_url
 };
+use crate::handlers::profile::player_card::get_own_player_card;
+use crate::handlers::profile::consent::{get_consent_settings, update_consent_settings};
+use crate::handlers::profile::posting_settings::{get_posting_settings, update_posting_settings};
+use crate::handlers::profile::account::{delete_account, export_account_data};
 use crate::handlers::profile::user_status::{update_user_status, get_user_status, UpdateUserStatusRequest};
-use crate::middleware::auth::Claims;
+use crate::handlers::profile::username::update_username;
+use crate::handlers::profile::diagnostics::get_sync_diagnostics;
+use crate::config::jwt::JwtSettings;
+use crate::middleware::auth::{AccountSecurity, Claims};
+use crate::middleware::etag::ConditionalGet;
 use crate::models::profile::UpdateHealthProfileRequest;
+use crate::models::research::UpdateConsentSettingsRequest;
+use crate::models::user::ChangeUsernameRequest;
+use crate::models::post::UpdateWorkoutPostingSettingsRequest;
+use crate::models::diagnostics::DiagnosticsQuery;
 use crate::services::MinIOService;
 
-#[get("/user")]
+#[get("/user", wrap = "ConditionalGet")]
 async fn get_user(
     pool: web::Data<PgPool>,
+    redis: Option<web::Data<Arc<redis::Client>>>,
     claims: web::ReqData<Claims>,
     query: web::Query<UserProfileQuery>
 ) -> HttpResponse {
-    get_user_profile(pool, claims, query).await
+    get_user_profile(pool, redis, claims, query).await
+}
+
+#[get("/player-card")]
+async fn get_player_card(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    get_own_player_card(pool, claims).await
 }
 
 #[get("/health_profile")]
@@ -35,8 +60,54 @@ async fn update_health_prof(
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
     data: web::Json<UpdateHealthProfileRequest>,
+    req: HttpRequest,
+) -> HttpResponse {
+    update_health_profile(pool, claims, data, req).await
+}
+
+#[post("/health_profile/import")]
+async fn import_health_prof(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    data: web::Json<crate::models::profile::DeviceHealthProfile>,
+) -> HttpResponse {
+    import_health_profile(pool, claims, data).await
+}
+
+#[get("/health_profile/history")]
+async fn get_health_prof_history(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<crate::models::profile::HealthProfileHistoryQuery>
+) -> HttpResponse {
+    get_health_profile_history_handler(pool, claims, query).await
+}
+
+#[get("/health_profile/threshold-proposals")]
+async fn get_threshold_props(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ThresholdProposalsQuery>
+) -> HttpResponse {
+    get_threshold_proposals(pool, claims, query).await
+}
+
+#[post("/health_profile/threshold-proposals/{proposal_id}/accept")]
+async fn accept_threshold_prop(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<uuid::Uuid>
+) -> HttpResponse {
+    decide_threshold_proposal(pool, claims, path.into_inner(), true).await
+}
+
+#[post("/health_profile/threshold-proposals/{proposal_id}/decline")]
+async fn decline_threshold_prop(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<uuid::Uuid>
 ) -> HttpResponse {
-    update_health_profile(pool, claims, data).await
+    decide_threshold_proposal(pool, claims, path.into_inner(), false).await
 }
 
 // Profile picture upload routes
@@ -114,4 +185,76 @@ async fn update_status(
     request: web::Json<UpdateUserStatusRequest>,
 ) -> HttpResponse {
     update_user_status(pool, claims, request).await
-}
\ No newline at end of file
+}
+// Data sharing consent routes
+#[get("/consent")]
+async fn get_consent(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    get_consent_settings(pool, claims).await
+}
+
+#[patch("/consent")]
+async fn update_consent(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateConsentSettingsRequest>,
+) -> HttpResponse {
+    update_consent_settings(pool, claims, request).await
+}
+
+// Workout auto-posting routes
+#[get("/posting-settings")]
+async fn get_posting(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    get_posting_settings(pool, claims).await
+}
+
+#[patch("/posting-settings")]
+async fn update_posting(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateWorkoutPostingSettingsRequest>,
+) -> HttpResponse {
+    update_posting_settings(pool, claims, request).await
+}
+
+// Upload and device sync diagnostics, for support
+#[get("/diagnostics")]
+async fn get_diagnostics(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<DiagnosticsQuery>,
+) -> HttpResponse {
+    get_sync_diagnostics(pool, claims, query).await
+}
+
+// Account deletion and data export routes
+#[post("/delete-account", wrap = "AccountSecurity")]
+async fn request_account_deletion(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    delete_account(pool, claims).await
+}
+
+#[get("/export")]
+async fn export_account(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+) -> HttpResponse {
+    export_account_data(pool, claims).await
+}
+
+#[patch("/username", wrap = "AccountSecurity")]
+async fn change_username(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    jwt_settings: web::Data<JwtSettings>,
+    request: web::Json<ChangeUsernameRequest>,
+) -> HttpResponse {
+    update_username(pool, claims, jwt_settings, request).await
+}
@@ -0,0 +1,21 @@
+use actix_web::web;
+
+use crate::handlers::league::spectator_handler;
+use crate::handlers::link_preview_handler;
+use crate::handlers::workout_data::share_links;
+
+pub fn init_public_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/workouts/{token}")
+            .route(web::get().to(share_links::get_shared_workout))
+    )
+    .service(
+        web::resource("/link-preview")
+            .route(web::get().to(link_preview_handler::get_link_preview))
+    )
+    // Guest access to live games with a spectator token from a shared link
+    .service(
+        web::resource("/games/{game_id}/live")
+            .route(web::get().to(spectator_handler::get_spectated_game))
+    );
+}
@@ -1,16 +1,26 @@
-use actix_web::{post, web, HttpResponse};
+use actix_web::{get, post, web, HttpResponse};
 use sqlx::PgPool;
 use std::sync::Arc;
 use redis::Client as RedisClient;
 
-use crate::handlers::registration_handler::register_user;
-use crate::models::user::RegistrationRequest;
+use crate::handlers::registration_handler::{check_username_availability, register_user};
+use crate::models::user::{CheckUsernameQuery, RegistrationRequest};
+use crate::services::PasswordPolicyService;
 
 #[post("/register_user")]
 async fn register(
     user_form: web::Json<RegistrationRequest>,
     pool: web::Data<PgPool>,
     redis_client: web::Data<Arc<RedisClient>>,
+    password_policy: web::Data<PasswordPolicyService>,
 ) -> HttpResponse {
-    register_user(user_form, pool, redis_client).await
-}
\ No newline at end of file
+    register_user(user_form, pool, redis_client, password_policy).await
+}
+
+#[get("/registration/check-username")]
+async fn check_username(
+    query: web::Query<CheckUsernameQuery>,
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    check_username_availability(query, pool).await
+}
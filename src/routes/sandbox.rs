@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::admin::sandbox_handler;
+
+pub fn init_sandbox_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::post().to(sandbox_handler::create_sandbox))
+    )
+    .service(
+        web::resource("/{id}")
+            .route(web::delete().to(sandbox_handler::delete_sandbox))
+    );
+}
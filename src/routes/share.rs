@@ -0,0 +1,16 @@
+use actix_web::web;
+
+use crate::handlers::share_card_handler;
+use crate::middleware::auth::AuthMiddleware;
+
+pub fn init_share_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/cards")
+            .wrap(AuthMiddleware)
+            .route(web::post().to(share_card_handler::create_share_card))
+    )
+    .service(
+        web::resource("/cards/{id}")
+            .route(web::get().to(share_card_handler::get_share_card_image))
+    );
+}
@@ -1,7 +1,10 @@
 use actix_web::web;
 
 use crate::handlers::social::{
-    reaction_handler::{add_reaction, remove_reaction, get_reactions, get_reaction_details},
+    reaction_handler::{
+        add_reaction, remove_reaction, get_reactions, get_reaction_details,
+        add_target_reaction, remove_target_reaction, get_target_reactions_handler, get_target_reaction_details,
+    },
     comment_handler::{add_comment, edit_comment, remove_comment, get_comments, get_single_comment},
     comment_reaction_handler::{add_comment_reaction, remove_comment_reaction, get_comment_reactions_handler, get_comment_reaction_details},
     notification_handler::{get_user_notifications, mark_notification_as_read, mark_all_as_read, get_unread_notification_count},
@@ -50,6 +53,19 @@ pub fn init_social_routes(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(get_comment_reaction_details))
     );
 
+    // Reactions on any target: post, game_summary, score_event, workout, comment
+    cfg.service(
+        web::resource("/reactions/{target_type}/{target_id}")
+            .route(web::post().to(add_target_reaction))
+            .route(web::delete().to(remove_target_reaction))
+            .route(web::get().to(get_target_reactions_handler))
+    );
+
+    cfg.service(
+        web::resource("/reactions/{target_type}/{target_id}/users")
+            .route(web::get().to(get_target_reaction_details))
+    );
+
     // Notification endpoints
     cfg.service(
         web::resource("/notifications")
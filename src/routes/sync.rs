@@ -0,0 +1,10 @@
+use actix_web::web;
+
+use crate::handlers::sync_handler;
+
+pub fn init_sync_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/changes")
+            .route(web::get().to(sync_handler::get_sync_changes))
+    );
+}
@@ -0,0 +1,117 @@
+use actix::{Actor, ActorContext, AsyncContext, Handler, StreamHandler};
+use actix_web::web;
+use actix_web_actors::ws;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing;
+
+use crate::services::{LiveMetrics, LiveMetricsService};
+
+// How often a metrics snapshot is pushed to the dashboard
+const METRICS_INTERVAL: Duration = Duration::from_secs(2);
+// How often heartbeat pings are sent
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+// How long before lack of client response causes a timeout
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Admin-only WebSocket connection that streams aggregate live metrics for the ops dashboard
+pub struct AdminMonitorConnection {
+    heartbeat: Instant,
+    admin_id: Uuid,
+    db_pool: web::Data<PgPool>,
+    metrics: web::Data<Arc<LiveMetrics>>,
+    window: Duration,
+}
+
+impl Actor for AdminMonitorConnection {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        tracing::info!("📊 Admin monitor started for {} (window: {}s)", self.admin_id, self.window.as_secs());
+
+        // Send the first snapshot right away instead of after the first interval
+        self.send_snapshot(ctx);
+        ctx.run_interval(METRICS_INTERVAL, |act, ctx| {
+            act.send_snapshot(ctx);
+        });
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.heartbeat) > CLIENT_TIMEOUT {
+                tracing::warn!("💔 Admin monitor heartbeat missed, disconnecting {}", act.admin_id);
+                ctx.stop();
+                return;
+            }
+            ctx.ping(b"ping");
+        });
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        tracing::info!("❌ Admin monitor stopped for {}", self.admin_id);
+    }
+}
+
+impl AdminMonitorConnection {
+    pub fn new(
+        admin_id: Uuid,
+        db_pool: web::Data<PgPool>,
+        metrics: web::Data<Arc<LiveMetrics>>,
+        window: Duration,
+    ) -> Self {
+        Self {
+            heartbeat: Instant::now(),
+            admin_id,
+            db_pool,
+            metrics,
+            window,
+        }
+    }
+
+    fn send_snapshot(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        let service = LiveMetricsService::new(self.db_pool.get_ref().clone(), self.metrics.get_ref().clone());
+        let window = self.window;
+        let addr = ctx.address();
+        tokio::spawn(async move {
+            match service.snapshot(window).await {
+                Ok(snapshot) => {
+                    if let Ok(message) = serde_json::to_string(&snapshot) {
+                        addr.do_send(MetricsSnapshotMessage(message));
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to collect live metrics: {}", e),
+            }
+        });
+    }
+}
+
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+struct MetricsSnapshotMessage(String);
+
+impl Handler<MetricsSnapshotMessage> for AdminMonitorConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: MetricsSnapshotMessage, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for AdminMonitorConnection {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => {
+                self.heartbeat = Instant::now();
+                ctx.pong(&msg);
+            }
+            Ok(ws::Message::Pong(_)) | Ok(ws::Message::Text(_)) => {
+                self.heartbeat = Instant::now();
+            }
+            Ok(ws::Message::Binary(_)) => {}
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => ctx.stop(),
+        }
+    }
+}
@@ -3,20 +3,40 @@
 use actix::{Actor, ActorContext, AsyncContext, StreamHandler, Handler};
 use actix_web_actors::ws;
 use futures::StreamExt;
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use actix_web::web;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tracing;
 use std::sync::Arc;
 use sqlx::PgPool;
 
-use crate::models::game_events::GameEvent;
+use crate::config::jwt::JwtSettings;
+use crate::middleware::auth::ensure_session_active;
+use crate::models::game_events::{GameEvent, StateSyncReason};
+use crate::models::user::UserStatus;
+use crate::services::{GameClockCache, LiveStateSyncService};
+use crate::services::live_state_sync_service::season_scores_channel;
+use crate::services::telemetry::{continue_trace, trace_context_of};
+use crate::utils::leaky_bucket::LeakyBucket;
+use super::auth::{decode_token, token_expiry};
 
 // How often heartbeat pings are sent
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
 // How long before lack of client response causes a timeout
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(120);
+// Client silence longer than a heartbeat round trip, after which it may have missed events
+const GAP_THRESHOLD: Duration = Duration::from_secs(45);
+// How long to wait before subscribing to Redis again after the subscription dropped
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+// How long before the token expires clients are asked to send a fresh one
+const TOKEN_RENEWAL_WARNING: chrono::Duration = chrono::Duration::minutes(5);
+// Client messages a connection may send in a burst, and per second after that
+const MESSAGE_BURST: u32 = 20;
+const MESSAGES_PER_SECOND: f64 = 5.0;
+// Seasons a connection may spectate at the same time
+const MAX_SPECTATED_SEASONS: usize = 5;
 
 /// Game-focused WebSocket connection actor
 pub struct GameConnection {
@@ -26,6 +46,16 @@ pub struct GameConnection {
     redis: Option<web::Data<Arc<redis::Client>>>,
     db_pool: Option<web::Data<PgPool>>,
     session_id: Uuid,
+    jwt_settings: web::Data<JwtSettings>,
+    game_clock: Option<web::Data<GameClockCache>>,
+    /// Expiry of the JWT the connection is currently authenticated with
+    token_expires_at: DateTime<Utc>,
+    renewal_requested: bool,
+    message_limiter: LeakyBucket,
+    /// Whether the client was told it is rate limited since its last accepted message
+    rate_limit_notified: bool,
+    /// Score delta subscriptions of the seasons the client spectates
+    spectated_seasons: HashMap<Uuid, tokio::task::JoinHandle<()>>,
 }
 
 impl Actor for GameConnection {
@@ -36,12 +66,15 @@ impl Actor for GameConnection {
             self.user_id, self.username, self.session_id);
 
         self.heartbeat(ctx);
-        self.setup_game_event_subscription(ctx);
+        self.setup_game_event_subscription(ctx, false);
     }
 
     fn stopped(&mut self, _ctx: &mut Self::Context) {
         tracing::info!("❌ GameConnection stopped for user {} ({}) - session: {}",
             self.user_id, self.username, self.session_id);
+        for (_, subscription) in self.spectated_seasons.drain() {
+            subscription.abort();
+        }
     }
 }
 
@@ -51,6 +84,9 @@ impl GameConnection {
         username: String,
         redis: Option<web::Data<Arc<redis::Client>>>,
         db_pool: Option<web::Data<PgPool>>,
+        jwt_settings: web::Data<JwtSettings>,
+        game_clock: Option<web::Data<GameClockCache>>,
+        token_expires_at: DateTime<Utc>,
     ) -> Self {
         let session_id = Uuid::new_v4();
         tracing::info!("🆕 Creating new GameConnection for user {} ({}) - session: {}",
@@ -63,6 +99,13 @@ impl GameConnection {
             redis,
             db_pool,
             session_id,
+            jwt_settings,
+            game_clock,
+            token_expires_at,
+            renewal_requested: false,
+            message_limiter: LeakyBucket::new(MESSAGE_BURST, MESSAGES_PER_SECOND, Instant::now()),
+            rate_limit_notified: false,
+            spectated_seasons: HashMap::new(),
         }
     }
     
@@ -75,13 +118,328 @@ impl GameConnection {
                 return;
             }
             
+            if !act.check_token_expiry(ctx) {
+                return;
+            }
+
             tracing::debug!("💓 Sending game client heartbeat ping for user: {} ({}) - session: {}", 
                 act.user_id, act.username, act.session_id);
             ctx.ping(b"ping");
+            act.send_game_clock(ctx);
+        });
+    }
+
+    /// Close the connection once its token expired, and ask the client for a fresh token shortly
+    /// before. Returns whether the connection is still authenticated.
+    fn check_token_expiry(&mut self, ctx: &mut ws::WebsocketContext<Self>) -> bool {
+        let now = Utc::now();
+        if now >= self.token_expires_at {
+            tracing::info!("🔑 Token expired, disconnecting user: {} ({}) - session: {}",
+                self.user_id, self.username, self.session_id);
+            self.send_json(ctx, serde_json::json!({
+                "event_type": "token_expired",
+                "session_id": self.session_id.to_string(),
+                "timestamp": now.to_rfc3339()
+            }));
+            ctx.close(Some(ws::CloseReason {
+                code: ws::CloseCode::Policy,
+                description: Some("Token expired".to_string()),
+            }));
+            ctx.stop();
+            return false;
+        }
+
+        if !self.renewal_requested && self.token_expires_at - now <= TOKEN_RENEWAL_WARNING {
+            self.renewal_requested = true;
+            self.send_json(ctx, serde_json::json!({
+                "event_type": "token_expiring",
+                "session_id": self.session_id.to_string(),
+                "expires_at": self.token_expires_at.to_rfc3339(),
+                "message": "Send a refresh_token message with a new token to stay connected",
+                "timestamp": now.to_rfc3339()
+            }));
+        }
+        true
+    }
+
+    /// Swap the connection's token for a fresh one of the same user, without reconnecting.
+    /// Like tokens at connect, it must belong to a session that is still logged in.
+    fn handle_token_refresh(&mut self, token: &str, ctx: &mut ws::WebsocketContext<Self>) {
+        let claims = match decode_token(token, &self.jwt_settings) {
+            Ok(claims) if claims.user_id() != Some(self.user_id) => Err("Token belongs to another user"),
+            Ok(claims) if !matches!(claims.status, UserStatus::Active) => Err("Account is not active"),
+            Ok(claims) => Ok(claims),
+            Err(e) => {
+                tracing::warn!("Invalid refresh token from {} ({}) session {}: {}",
+                    self.user_id, self.username, self.session_id, e);
+                Err("Invalid token")
+            }
+        };
+        let claims = match claims {
+            Ok(claims) => claims,
+            Err(error) => {
+                self.finish_token_refresh(Err(error), ctx);
+                return;
+            }
+        };
+
+        let addr = ctx.address();
+        let pool = self.db_pool.clone();
+        tokio::spawn(async move {
+            let result = match ensure_session_active(pool, &claims).await {
+                Ok(()) => Ok(token_expiry(&claims)),
+                Err(e) => {
+                    tracing::info!("Refresh token of {} refused: {}", claims.sub, e);
+                    Err("Session has been logged out")
+                }
+            };
+            addr.do_send(TokenRefreshChecked(result));
         });
     }
 
-    fn setup_game_event_subscription(&self, ctx: &mut ws::WebsocketContext<Self>) {
+    fn finish_token_refresh(&mut self, result: Result<DateTime<Utc>, &'static str>, ctx: &mut ws::WebsocketContext<Self>) {
+        match result {
+            Ok(expires_at) => {
+                self.token_expires_at = expires_at;
+                self.renewal_requested = false;
+                tracing::info!("🔑 Token renewed for {} ({}) session {} until {}",
+                    self.user_id, self.username, self.session_id, expires_at);
+                self.send_json(ctx, serde_json::json!({
+                    "event_type": "token_refreshed",
+                    "session_id": self.session_id.to_string(),
+                    "expires_at": expires_at.to_rfc3339(),
+                    "timestamp": Utc::now().to_rfc3339()
+                }));
+            }
+            Err(error) => {
+                // The current token stays valid until it expires, so the client can retry
+                self.send_json(ctx, serde_json::json!({
+                    "event_type": "token_refresh_failed",
+                    "session_id": self.session_id.to_string(),
+                    "error": error,
+                    "expires_at": self.token_expires_at.to_rfc3339(),
+                    "timestamp": Utc::now().to_rfc3339()
+                }));
+            }
+        }
+    }
+
+    fn send_json(&self, ctx: &mut ws::WebsocketContext<Self>, message: serde_json::Value) {
+        if let Ok(msg_str) = serde_json::to_string(&message) {
+            ctx.text(msg_str);
+        }
+    }
+
+    /// Take a client message from the connection's budget. Over budget, the message is dropped
+    /// and the client gets one `rate_limited` frame until a message is accepted again.
+    fn within_rate_limit(&mut self, ctx: &mut ws::WebsocketContext<Self>) -> bool {
+        match self.message_limiter.try_acquire(Instant::now()) {
+            Ok(()) => {
+                self.rate_limit_notified = false;
+                true
+            }
+            Err(retry_after) => {
+                if !self.rate_limit_notified {
+                    self.rate_limit_notified = true;
+                    tracing::warn!("🚦 Rate limiting game messages from {} ({}) session: {}",
+                        self.user_id, self.username, self.session_id);
+                    self.send_json(ctx, serde_json::json!({
+                        "event_type": "rate_limited",
+                        "session_id": self.session_id.to_string(),
+                        "error": "Too many messages, slow down",
+                        "retry_after_ms": retry_after.as_millis() as u64,
+                        "timestamp": Utc::now().to_rfc3339()
+                    }));
+                }
+                false
+            }
+        }
+    }
+
+    /// Send a heartbeat event with the server time and the active season's next game
+    /// transition, so clients keep their countdowns aligned with the backend
+    fn send_game_clock(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        let (Some(pool), Some(game_clock)) = (self.db_pool.clone(), self.game_clock.clone()) else {
+            let heartbeat = GameEvent::Heartbeat {
+                server_time: Utc::now(),
+                next_transition: None,
+            };
+            if let Ok(message) = serde_json::to_string(&heartbeat) {
+                ctx.text(message);
+            }
+            return;
+        };
+
+        let addr = ctx.address();
+        let user_id = self.user_id;
+        tokio::spawn(async move {
+            let next_transition = match game_clock.next_transition(pool.get_ref()).await {
+                Ok(next_transition) => next_transition,
+                Err(e) => {
+                    tracing::warn!("Failed to load game clock for heartbeat of user {}: {}", user_id, e);
+                    None
+                }
+            };
+            let heartbeat = GameEvent::Heartbeat {
+                server_time: Utc::now(),
+                next_transition,
+            };
+            if let Ok(message) = serde_json::to_string(&heartbeat) {
+                addr.do_send(GameEventMessage(message));
+            }
+        });
+    }
+
+    /// Send the authoritative state of the user's live games, or of the requested game
+    fn send_state_sync(&self, ctx: &mut ws::WebsocketContext<Self>, game_id: Option<Uuid>, reason: StateSyncReason) {
+        let Some(pool) = self.db_pool.clone() else {
+            tracing::debug!("No database for state sync of user {} session: {}", self.user_id, self.session_id);
+            return;
+        };
+
+        let addr = ctx.address();
+        let user_id = self.user_id;
+        let session_id = self.session_id;
+        tokio::spawn(async move {
+            match LiveStateSyncService::new(pool.get_ref().clone()).state_sync(user_id, game_id, reason).await {
+                Ok(state_sync) => {
+                    if let Ok(message) = serde_json::to_string(&state_sync) {
+                        addr.do_send(GameEventMessage(message));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load state sync for user {} session {}: {}", user_id, session_id, e);
+                }
+            }
+        });
+    }
+
+    /// Note that the client is alive. Back after a longer silence it may have missed events,
+    /// so it gets the current state without having to ask.
+    fn client_seen(&mut self, ctx: &mut ws::WebsocketContext<Self>) {
+        let now = Instant::now();
+        if now.duration_since(self.heartbeat) > GAP_THRESHOLD {
+            tracing::info!("🔄 Game client back after {}s, syncing state for {} ({}) session: {}",
+                now.duration_since(self.heartbeat).as_secs(), self.user_id, self.username, self.session_id);
+            self.send_state_sync(ctx, None, StateSyncReason::Gap);
+            let season_ids: Vec<Uuid> = self.spectated_seasons.keys().copied().collect();
+            for season_id in season_ids {
+                self.send_season_scoreboard(ctx, season_id);
+            }
+        }
+        self.heartbeat = now;
+    }
+
+    /// Send the scores of a spectated season's live games
+    fn send_season_scoreboard(&self, ctx: &mut ws::WebsocketContext<Self>, season_id: Uuid) {
+        let Some(pool) = self.db_pool.clone() else {
+            return;
+        };
+
+        let addr = ctx.address();
+        let session_id = self.session_id;
+        tokio::spawn(async move {
+            match LiveStateSyncService::new(pool.get_ref().clone()).season_scoreboard(season_id).await {
+                Ok(Some(scoreboard)) => {
+                    if let Ok(message) = serde_json::to_string(&scoreboard) {
+                        addr.do_send(GameEventMessage(message));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to load scoreboard of season {} for session {}: {}", season_id, session_id, e);
+                }
+            }
+        });
+    }
+
+    /// Start forwarding the score deltas of all live games of a season, for a league-wide
+    /// scores page. The client gets the season's scoreboard once the deltas are flowing.
+    fn start_spectating(&mut self, season_id: Uuid, ctx: &mut ws::WebsocketContext<Self>) {
+        if self.spectated_seasons.contains_key(&season_id) {
+            self.send_season_scoreboard(ctx, season_id);
+            return;
+        }
+        if self.spectated_seasons.len() >= MAX_SPECTATED_SEASONS {
+            self.send_spectate_failed(ctx, season_id, "Spectating too many seasons, stop spectating one first");
+            return;
+        }
+        let (Some(redis_client), Some(pool)) = (self.redis.clone(), self.db_pool.clone()) else {
+            self.send_spectate_failed(ctx, season_id, "Live scores are not available");
+            return;
+        };
+
+        let addr = ctx.address();
+        let session_id = self.session_id;
+        let subscription = tokio::spawn(async move {
+            let mut pubsub = match redis_client.get_async_connection().await {
+                Ok(conn) => conn.into_pubsub(),
+                Err(e) => {
+                    tracing::error!("❌ Failed to connect to Redis for season {} session {}: {}", season_id, session_id, e);
+                    addr.do_send(SeasonSubscriptionLost(season_id));
+                    return;
+                }
+            };
+            if let Err(e) = pubsub.subscribe(season_scores_channel(season_id)).await {
+                tracing::error!("❌ Failed to subscribe to season {} session {}: {}", season_id, session_id, e);
+                addr.do_send(SeasonSubscriptionLost(season_id));
+                return;
+            }
+
+            // Deltas published from here on are forwarded, so the scoreboard is the base they apply to
+            match LiveStateSyncService::new(pool.get_ref().clone()).season_scoreboard(season_id).await {
+                Ok(Some(scoreboard)) => {
+                    if let Ok(message) = serde_json::to_string(&scoreboard) {
+                        addr.do_send(GameEventMessage(message));
+                    }
+                }
+                Ok(None) => {
+                    addr.do_send(SpectateRejected { season_id, error: "Season not found" });
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load scoreboard of season {} for session {}: {}", season_id, session_id, e);
+                }
+            }
+
+            let mut stream = pubsub.on_message();
+            while let Some(msg) = stream.next().await {
+                if let Ok(payload) = msg.get_payload::<String>() {
+                    addr.do_send(GameEventMessage(payload));
+                }
+            }
+            tracing::warn!("🔌 Score deltas of season {} ended for session: {}", season_id, session_id);
+            addr.do_send(SeasonSubscriptionLost(season_id));
+        });
+
+        tracing::info!("👀 {} ({}) session {} spectates season {}",
+            self.user_id, self.username, self.session_id, season_id);
+        self.spectated_seasons.insert(season_id, subscription);
+    }
+
+    fn stop_spectating(&mut self, season_id: Uuid, ctx: &mut ws::WebsocketContext<Self>) {
+        if let Some(subscription) = self.spectated_seasons.remove(&season_id) {
+            subscription.abort();
+        }
+        self.send_json(ctx, serde_json::json!({
+            "event_type": "spectating_stopped",
+            "session_id": self.session_id.to_string(),
+            "season_id": season_id.to_string(),
+            "timestamp": Utc::now().to_rfc3339()
+        }));
+    }
+
+    fn send_spectate_failed(&self, ctx: &mut ws::WebsocketContext<Self>, season_id: Uuid, error: &str) {
+        self.send_json(ctx, serde_json::json!({
+            "event_type": "spectate_failed",
+            "session_id": self.session_id.to_string(),
+            "season_id": season_id.to_string(),
+            "error": error,
+            "timestamp": Utc::now().to_rfc3339()
+        }));
+    }
+
+    fn setup_game_event_subscription(&self, ctx: &mut ws::WebsocketContext<Self>, resubscribe: bool) {
         let user_id = self.user_id;
         let session_id = self.session_id;
         let username = self.username.clone();
@@ -151,6 +509,12 @@ impl GameConnection {
                             if let Ok(msg_str) = serde_json::to_string(&confirmation_msg) {
                                 addr.do_send(GameEventMessage(msg_str));
                             }
+                            // Events published before the subscriptions were up are missing
+                            addr.do_send(SyncStateMessage(if resubscribe {
+                                StateSyncReason::Resubscribed
+                            } else {
+                                StateSyncReason::Subscribed
+                            }));
                             
                             tracing::info!("📡 Redis subscriptions confirmed for {} ({}) session: {} - listening for events", 
                                 user_id, username, session_id);
@@ -167,8 +531,15 @@ impl GameConnection {
                         while let Some(msg) = stream.next().await {
                             match msg.get_payload::<String>() {
                                 Ok(payload) => {
-                                    tracing::debug!("📥 Received Redis event for {} ({}) session {}: {}", 
-                                        user_id, username, session_id, payload);
+                                    // Forwarded in the trace the event was published in
+                                    let span = tracing::info_span!("Forward Redis event", channel = %msg.get_channel_name(), %session_id);
+                                    if let Ok(event) = serde_json::from_str::<serde_json::Value>(&payload) {
+                                        continue_trace(&span, &trace_context_of(&event));
+                                    }
+                                    span.in_scope(|| {
+                                        tracing::debug!("📥 Received Redis event for {} ({}) session {}: {}", 
+                                            user_id, username, session_id, payload);
+                                    });
                                     addr.do_send(GameEventMessage(payload));
                                 },
                                 Err(e) => {
@@ -180,6 +551,7 @@ impl GameConnection {
                         
                         tracing::warn!("🔌 Redis message stream ended for {} ({}) session: {}", 
                             user_id, username, session_id);
+                        addr.do_send(RedisSubscriptionLost);
                     },
                     Err(e) => {
                         tracing::error!("❌ Failed to connect to Redis for game events for {} ({}) session {}: {}", 
@@ -197,6 +569,7 @@ impl GameConnection {
                         if let Ok(msg_str) = serde_json::to_string(&error_msg) {
                             addr.do_send(GameEventMessage(msg_str));
                         }
+                        addr.do_send(RedisSubscriptionLost);
                     }
                 }
             });
@@ -234,27 +607,108 @@ impl Handler<GameEventMessage> for GameConnection {
     }
 }
 
+/// Send the client a state sync, from tasks that noticed it may have missed events
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct SyncStateMessage(pub StateSyncReason);
+
+impl Handler<SyncStateMessage> for GameConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: SyncStateMessage, ctx: &mut Self::Context) {
+        self.send_state_sync(ctx, None, msg.0);
+    }
+}
+
+/// Outcome of the session check of a token the client sent to stay connected
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct TokenRefreshChecked(pub Result<DateTime<Utc>, &'static str>);
+
+impl Handler<TokenRefreshChecked> for GameConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: TokenRefreshChecked, ctx: &mut Self::Context) {
+        self.finish_token_refresh(msg.0, ctx);
+    }
+}
+
+/// The Redis subscription of the connection dropped or couldn't be set up
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct RedisSubscriptionLost;
+
+impl Handler<RedisSubscriptionLost> for GameConnection {
+    type Result = ();
+
+    fn handle(&mut self, _msg: RedisSubscriptionLost, ctx: &mut Self::Context) {
+        tracing::info!("🔁 Subscribing to Redis again in {}s for {} ({}) session: {}",
+            RESUBSCRIBE_DELAY.as_secs(), self.user_id, self.username, self.session_id);
+        ctx.run_later(RESUBSCRIBE_DELAY, |act, ctx| {
+            act.setup_game_event_subscription(ctx, true);
+        });
+    }
+}
+
+/// The score delta subscription of a spectated season dropped or couldn't be set up
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct SeasonSubscriptionLost(pub Uuid);
+
+impl Handler<SeasonSubscriptionLost> for GameConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: SeasonSubscriptionLost, ctx: &mut Self::Context) {
+        let season_id = msg.0;
+        if self.spectated_seasons.remove(&season_id).is_none() {
+            return;
+        }
+        ctx.run_later(RESUBSCRIBE_DELAY, move |act, ctx| {
+            act.start_spectating(season_id, ctx);
+        });
+    }
+}
+
+/// A season the client asked to spectate can't be spectated
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct SpectateRejected {
+    pub season_id: Uuid,
+    pub error: &'static str,
+}
+
+impl Handler<SpectateRejected> for GameConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: SpectateRejected, ctx: &mut Self::Context) {
+        self.spectated_seasons.remove(&msg.season_id);
+        self.send_spectate_failed(ctx, msg.season_id, msg.error);
+    }
+}
+
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameConnection {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
         match msg {
             Ok(ws::Message::Ping(msg)) => {
-                self.heartbeat = Instant::now();
+                self.client_seen(ctx);
                 ctx.pong(&msg);
                 tracing::debug!("🏓 Pong sent for {} ({}) session: {}", 
                     self.user_id, self.username, self.session_id);
             }
             Ok(ws::Message::Pong(_)) => {
-                self.heartbeat = Instant::now();
+                self.client_seen(ctx);
                 tracing::debug!("🏓 Pong received for {} ({}) session: {}", 
                     self.user_id, self.username, self.session_id);
             }
             Ok(ws::Message::Text(text)) => {
                 tracing::debug!("📨 Received game message from {} ({}) session {}: {}", 
                     self.user_id, self.username, self.session_id, text);
-                self.heartbeat = Instant::now();
-                
-                // Handle incoming game commands
-                self.handle_game_message(&text, ctx);
+                self.client_seen(ctx);
+
+                if self.within_rate_limit(ctx) {
+                    // Handle incoming game commands
+                    self.handle_game_message(&text, ctx);
+                }
             }
             Ok(ws::Message::Binary(_)) => {
                 tracing::warn!("⚠️  Received unexpected binary message from {} ({}) session: {}", 
@@ -272,7 +726,7 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameConnection {
 }
 
 impl GameConnection {
-    fn handle_game_message(&self, message: &str, ctx: &mut ws::WebsocketContext<Self>) {
+    fn handle_game_message(&mut self, message: &str, ctx: &mut ws::WebsocketContext<Self>) {
         // Parse incoming game commands from client
         if let Ok(command) = serde_json::from_str::<serde_json::Value>(message) {
             match command.get("type").and_then(|t| t.as_str()) {
@@ -295,6 +749,35 @@ impl GameConnection {
                     // Handle leaderboard requests
                     self.handle_leaderboard_request(ctx);
                 }
+                Some("refresh_token") => {
+                    // Clients get the fresh token from the regular auth endpoints
+                    let token = command.get("token").and_then(|t| t.as_str()).unwrap_or_default();
+                    self.handle_token_refresh(token, ctx);
+                }
+                Some("request_game_clock") => {
+                    // Answer right away instead of waiting for the next heartbeat
+                    self.send_game_clock(ctx);
+                }
+                Some("state_sync") => {
+                    // Clients reconcile their scores with this, e.g. after coming back to the foreground
+                    let game_id = command.get("game_id")
+                        .and_then(|id| id.as_str())
+                        .and_then(|id| Uuid::parse_str(id).ok());
+                    self.send_state_sync(ctx, game_id, StateSyncReason::Requested);
+                }
+                Some("spectate_season") => {
+                    // All live games of a season in one connection, for league-wide scores pages
+                    match season_id_of(&command) {
+                        Some(season_id) => self.start_spectating(season_id, ctx),
+                        None => self.send_json(ctx, missing_season_id(self.session_id)),
+                    }
+                }
+                Some("stop_spectating_season") => {
+                    match season_id_of(&command) {
+                        Some(season_id) => self.stop_spectating(season_id, ctx),
+                        None => self.send_json(ctx, missing_season_id(self.session_id)),
+                    }
+                }
                 _ => {
                     tracing::debug!("❓ Unknown game command from {} ({}) session {}: {}", 
                         self.user_id, self.username, self.session_id, message);
@@ -368,4 +851,19 @@ impl GameConnection {
             ctx.text(message);
         }
     }
-}
\ No newline at end of file
+}
+
+fn season_id_of(command: &serde_json::Value) -> Option<Uuid> {
+    command.get("season_id")
+        .and_then(|id| id.as_str())
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+fn missing_season_id(session_id: Uuid) -> serde_json::Value {
+    serde_json::json!({
+        "event_type": "spectate_failed",
+        "session_id": session_id.to_string(),
+        "error": "A valid season_id is required",
+        "timestamp": Utc::now().to_rfc3339()
+    })
+}
@@ -4,4 +4,12 @@ use serde::Deserialize;
 #[derive(Deserialize)]
 pub struct TokenQuery {
     pub token: String,
-} 
\ No newline at end of file
+}
+
+// Query parameters of the admin monitor socket
+#[derive(Deserialize)]
+pub struct MonitorQuery {
+    pub token: String,
+    /// Window the rates are averaged over, in seconds
+    pub window_secs: Option<u64>,
+}
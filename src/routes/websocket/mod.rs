@@ -1,21 +1,32 @@
 mod connection;
 mod messages;
 mod auth;
+mod admin_monitor;
+mod spectator;
 
 use actix_web::{web, Error, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
-use crate::middleware::auth::Claims;
+use crate::middleware::auth::{ensure_session_active, Claims};
+use crate::models::user::{UserRole, UserStatus};
+use crate::services::{GameClockCache, LiveMetrics};
+use crate::services::spectator_token_service::verify_spectator_token;
 use crate::config::jwt::JwtSettings;
 use uuid::Uuid;
 use tracing;
 use std::sync::Arc;
 use sqlx::PgPool;
+use std::time::Duration;
 
 pub use connection::GameConnection;
-pub use messages::TokenQuery;
-pub use auth::decode_token;
+pub use admin_monitor::AdminMonitorConnection;
+pub use spectator::SpectatorConnection;
+pub use messages::{TokenQuery, MonitorQuery};
+pub use auth::{decode_token, token_expiry};
 
-/// Game-focused WebSocket route handler with connection deduplication
+/// Game-focused WebSocket route handler with connection deduplication.
+/// The connection closes when its token expires unless the client sends a fresh one in a
+/// `{"type": "refresh_token", "token": ...}` message.
+/// A guest spectator token in the query opens a read-only connection following its game instead.
 pub async fn game_ws_route(
     req: HttpRequest,
     stream: web::Payload,
@@ -28,22 +39,39 @@ pub async fn game_ws_route(
     tracing::info!("🔗 New game WebSocket connection request");
     
     // Try to get user info from different sources
-    let (user_id, username) = if let Some(claims) = claims {
+    let (user_id, username, token_expires_at) = if let Some(claims) = claims {
         // JWT from Authorization header via middleware
         tracing::info!("Using JWT from Authorization header for user: {}", claims.username);
-        (claims.sub.clone(), claims.username.clone())
+        (claims.sub.clone(), claims.username.clone(), token_expiry(&claims))
     } else if let Some(query) = query {
         // JWT from query parameter
         tracing::info!("Using JWT from query parameter");
         match decode_token(&query.token, &jwt_settings) {
             Ok(token_claims) => {
+                ensure_session_active(db_pool.clone(), &token_claims).await?;
                 tracing::info!("JWT from query parameter verified for user: {}", token_claims.username);
-                (token_claims.sub, token_claims.username)
+                let expires_at = token_expiry(&token_claims);
+                (token_claims.sub, token_claims.username, expires_at)
+            },
+            Err(e) => match (verify_spectator_token(&jwt_settings, &query.token), db_pool) {
+                (Ok(spectator), Some(db_pool)) => {
+                    let expires_at = spectator.expires_at();
+                    let resp = ws::start(
+                        SpectatorConnection::new(spectator.sub, spectator.game_id, redis, db_pool, expires_at),
+                        &req,
+                        stream,
+                    )?;
+                    tracing::info!("✅ Spectator WebSocket connection initiated for game: {}", spectator.game_id);
+                    return Ok(resp);
+                }
+                (Ok(_), None) => {
+                    return Err(actix_web::error::ErrorServiceUnavailable("Live games are not available"));
+                }
+                (Err(_), _) => {
+                    tracing::error!("Invalid JWT in query parameter: {}", e);
+                    return Err(actix_web::error::ErrorUnauthorized("Invalid token"));
+                }
             },
-            Err(e) => {
-                tracing::error!("Invalid JWT in query parameter: {}", e);
-                return Err(actix_web::error::ErrorUnauthorized("Invalid token"));
-            }
         }
     } else {
         // No authentication provided
@@ -61,12 +89,51 @@ pub async fn game_ws_route(
     };
     
     // Start game WebSocket connection - the registry will handle duplicates
+    let game_clock = req.app_data::<web::Data<GameClockCache>>().cloned();
     let resp = ws::start(
-        GameConnection::new(user_uuid, username.clone(), redis, db_pool),
+        GameConnection::new(user_uuid, username.clone(), redis, db_pool, jwt_settings, game_clock, token_expires_at),
         &req,
         stream,
     )?;
 
     tracing::info!("✅ Game WebSocket connection initiated for user: {} ({})", user_uuid, username);
     Ok(resp)
-}
\ No newline at end of file
+}
+
+/// Admin-only WebSocket route streaming aggregate live metrics for the ops dashboard.
+/// Browsers cannot set headers on WebSocket requests, so the JWT comes as query parameter.
+pub async fn admin_monitor_ws_route(
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<MonitorQuery>,
+    db_pool: web::Data<PgPool>,
+    metrics: web::Data<Arc<LiveMetrics>>,
+    jwt_settings: web::Data<JwtSettings>,
+) -> Result<HttpResponse, Error> {
+    let claims = decode_token(&query.token, &jwt_settings).map_err(|e| {
+        tracing::error!("Invalid JWT for admin monitor WebSocket: {}", e);
+        actix_web::error::ErrorUnauthorized("Invalid token")
+    })?;
+    ensure_session_active(Some(db_pool.clone()), &claims).await?;
+
+    if !matches!(claims.status, UserStatus::Active) {
+        return Err(actix_web::error::ErrorUnauthorized("Account is not active"));
+    }
+    if !matches!(claims.role, UserRole::Admin | UserRole::SuperAdmin) {
+        tracing::warn!("Non-admin user attempted to open the admin monitor: {}", claims.username);
+        return Err(actix_web::error::ErrorForbidden("Insufficient privileges"));
+    }
+
+    let admin_id = claims.user_id()
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("Invalid user ID"))?;
+    let window = Duration::from_secs(query.window_secs.unwrap_or(10));
+
+    let resp = ws::start(
+        AdminMonitorConnection::new(admin_id, db_pool, metrics, window),
+        &req,
+        stream,
+    )?;
+
+    tracing::info!("✅ Admin monitor WebSocket initiated for {} ({})", admin_id, claims.username);
+    Ok(resp)
+}
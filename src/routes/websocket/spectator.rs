@@ -0,0 +1,211 @@
+use actix::{Actor, ActorContext, AsyncContext, Handler, StreamHandler};
+use actix_web::web;
+use actix_web_actors::ws;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use tracing;
+
+use super::connection::GameEventMessage;
+use crate::db::game_repo::{GameRepo, GameRepository};
+use crate::models::game_events::StateSyncReason;
+use crate::services::live_state_sync_service::season_scores_channel;
+use crate::services::LiveStateSyncService;
+
+// How often heartbeat pings are sent
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+// How long before lack of client response causes a timeout
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Read-only WebSocket connection of a guest holding a spectator token. It follows the one game
+/// the token names and only answers `state_sync` requests, whatever else the client sends.
+pub struct SpectatorConnection {
+    heartbeat: Instant,
+    spectator_id: String,
+    game_id: Uuid,
+    redis: Option<web::Data<Arc<redis::Client>>>,
+    db_pool: web::Data<PgPool>,
+    token_expires_at: DateTime<Utc>,
+    subscription: Option<JoinHandle<()>>,
+}
+
+impl Actor for SpectatorConnection {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        tracing::info!("👀 Spectator {} started following game {}", self.spectator_id, self.game_id);
+
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.heartbeat) > CLIENT_TIMEOUT {
+                tracing::warn!("💔 Spectator heartbeat missed, disconnecting {}", act.spectator_id);
+                ctx.stop();
+                return;
+            }
+            ctx.ping(b"ping");
+        });
+
+        // Spectator tokens can't be refreshed in the connection, the client reconnects with a new one
+        let remaining = (self.token_expires_at - Utc::now()).to_std().unwrap_or_default();
+        ctx.run_later(remaining, |act, ctx| {
+            tracing::info!("🔑 Spectator token expired, disconnecting {}", act.spectator_id);
+            ctx.text(serde_json::json!({
+                "event_type": "token_expired",
+                "timestamp": Utc::now().to_rfc3339()
+            }).to_string());
+            ctx.close(Some(ws::CloseReason {
+                code: ws::CloseCode::Policy,
+                description: Some("Token expired".to_string()),
+            }));
+            ctx.stop();
+        });
+
+        self.subscribe(ctx);
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        if let Some(subscription) = self.subscription.take() {
+            subscription.abort();
+        }
+        tracing::info!("❌ Spectator {} stopped following game {}", self.spectator_id, self.game_id);
+    }
+}
+
+impl SpectatorConnection {
+    pub fn new(
+        spectator_id: String,
+        game_id: Uuid,
+        redis: Option<web::Data<Arc<redis::Client>>>,
+        db_pool: web::Data<PgPool>,
+        token_expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            heartbeat: Instant::now(),
+            spectator_id,
+            game_id,
+            redis,
+            db_pool,
+            token_expires_at,
+            subscription: None,
+        }
+    }
+
+    /// Forward the score deltas of the game from its season's channel, after a state sync they
+    /// apply to. Without Redis the client only gets the state syncs it asks for.
+    fn subscribe(&mut self, ctx: &mut ws::WebsocketContext<Self>) {
+        let Some(redis_client) = self.redis.clone() else {
+            self.send_state_sync(ctx, StateSyncReason::Subscribed);
+            return;
+        };
+
+        let addr = ctx.address();
+        let pool = self.db_pool.get_ref().clone();
+        let game_id = self.game_id;
+        self.subscription = Some(tokio::spawn(async move {
+            let season_id = match GameRepo::new(pool.clone()).find_live_game_state(game_id).await {
+                Ok(Some(game)) => game.season_id,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::error!("Failed to load game {} for spectator: {}", game_id, e);
+                    return;
+                }
+            };
+
+            let mut pubsub = match redis_client.get_async_connection().await {
+                Ok(conn) => conn.into_pubsub(),
+                Err(e) => {
+                    tracing::error!("❌ Failed to connect to Redis for spectator of game {}: {}", game_id, e);
+                    return;
+                }
+            };
+            if let Err(e) = pubsub.subscribe(season_scores_channel(season_id)).await {
+                tracing::error!("❌ Failed to subscribe spectator of game {}: {}", game_id, e);
+                return;
+            }
+
+            match LiveStateSyncService::new(pool).game_state_sync(game_id, StateSyncReason::Subscribed).await {
+                Ok(state_sync) => {
+                    if let Ok(message) = serde_json::to_string(&state_sync) {
+                        addr.do_send(GameEventMessage(message));
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load state of game {} for spectator: {}", game_id, e),
+            }
+
+            let mut stream = pubsub.on_message();
+            while let Some(msg) = stream.next().await {
+                let Ok(payload) = msg.get_payload::<String>() else {
+                    continue;
+                };
+                if is_delta_of_game(&payload, game_id) {
+                    addr.do_send(GameEventMessage(payload));
+                }
+            }
+            tracing::warn!("🔌 Score deltas of game {} ended for spectator", game_id);
+        }));
+    }
+
+    fn send_state_sync(&self, ctx: &mut ws::WebsocketContext<Self>, reason: StateSyncReason) {
+        let service = LiveStateSyncService::new(self.db_pool.get_ref().clone());
+        let game_id = self.game_id;
+        let addr = ctx.address();
+        tokio::spawn(async move {
+            match service.game_state_sync(game_id, reason).await {
+                Ok(state_sync) => {
+                    if let Ok(message) = serde_json::to_string(&state_sync) {
+                        addr.do_send(GameEventMessage(message));
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load state of game {} for spectator: {}", game_id, e),
+            }
+        });
+    }
+}
+
+/// Whether a message of the season channel is a score delta of the game
+fn is_delta_of_game(payload: &str, game_id: Uuid) -> bool {
+    let Ok(event) = serde_json::from_str::<serde_json::Value>(payload) else {
+        return false;
+    };
+    event.get("event_type").and_then(|t| t.as_str()) == Some("score_delta")
+        && event.get("game_id").and_then(|id| id.as_str()) == Some(game_id.to_string().as_str())
+}
+
+impl Handler<GameEventMessage> for SpectatorConnection {
+    type Result = ();
+
+    fn handle(&mut self, msg: GameEventMessage, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for SpectatorConnection {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => {
+                self.heartbeat = Instant::now();
+                ctx.pong(&msg);
+            }
+            Ok(ws::Message::Pong(_)) => {
+                self.heartbeat = Instant::now();
+            }
+            Ok(ws::Message::Text(text)) => {
+                self.heartbeat = Instant::now();
+                let command = serde_json::from_str::<serde_json::Value>(&text).ok();
+                match command.as_ref().and_then(|command| command.get("type")).and_then(|t| t.as_str()) {
+                    Some("state_sync") => self.send_state_sync(ctx, StateSyncReason::Requested),
+                    _ => tracing::debug!("❓ Ignoring command from spectator {}: {}", self.spectator_id, text),
+                }
+            }
+            Ok(ws::Message::Binary(_)) => {}
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => ctx.stop(),
+        }
+    }
+}
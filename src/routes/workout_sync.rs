@@ -1,13 +1,26 @@
-use actix_web::{web, get, post, delete, HttpResponse};
+use actix_web::{web, get, post, put, patch, delete, HttpResponse};
 use sqlx::PgPool;
 use crate::middleware::auth::Claims;
 use crate::handlers::workout_data::workout_history::get_workout_history;
-use crate::handlers::workout_data::workout_detail::get_workout_detail;
+use crate::handlers::workout_data::workout_detail::{get_workout_detail, update_workout_notes};
 use crate::handlers::workout_data::check_workout_sync::{check_workout_sync, CheckSyncStatusRequest};
 use crate::handlers::workout_data::scoring_feedback::{submit_scoring_feedback, get_scoring_feedback};
 use crate::handlers::workout_data::workout_reports::{
     submit_workout_report, get_my_report_for_workout, get_my_reports, delete_workout_report
 };
+use crate::handlers::workout_data::hr_trends::{get_hr_trends, HrTrendsQuery};
+use crate::handlers::workout_data::activities::get_activity_catalog;
+use crate::handlers::workout_data::share_links::{create_share_link, revoke_share_link};
+use crate::handlers::workout_data::compare_workout::compare_workout;
+use crate::handlers::workout_data::score_breakdown::get_score_breakdown;
+use crate::handlers::workout_data::zone_recalculation::{recalculate_workout_zones, RecalculateZonesRequest};
+use crate::handlers::workout_data::body_metrics::{
+    record_body_metrics, get_body_metrics, delete_body_metric, get_body_metrics_analytics, BodyMetricsAnalyticsQuery
+};
+use crate::handlers::workout_data::change_tokens::{
+    get_device_change_token, store_device_change_token, clear_device_change_token, ChangeTokenQuery, StoreChangeTokenRequest
+};
+use crate::models::body_metrics::{BodyMetricsQuery, RecordBodyMetricsRequest};
 use crate::config::jwt::JwtSettings;
 
 #[get("/history")]
@@ -24,8 +37,56 @@ async fn get_workout_detail_handler(
     pool: web::Data<PgPool>,
     claims: web::ReqData<Claims>,
     workout_id: web::Path<uuid::Uuid>,
+    query: web::Query<crate::handlers::workout_data::workout_detail::WorkoutDetailQuery>,
 ) -> HttpResponse {
-    get_workout_detail(pool, claims, workout_id).await
+    get_workout_detail(pool, claims, workout_id, query).await
+}
+
+#[patch("/workout/{id}")]
+async fn update_workout_notes_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<uuid::Uuid>,
+    request: web::Json<crate::models::workout_data::UpdateWorkoutNotesRequest>,
+) -> HttpResponse {
+    update_workout_notes(pool, claims, workout_id, request).await
+}
+
+#[post("/workout/{id}/share")]
+async fn create_share_link_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<uuid::Uuid>,
+) -> HttpResponse {
+    create_share_link(pool, claims, workout_id).await
+}
+
+#[delete("/workout/{id}/share")]
+async fn revoke_share_link_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<uuid::Uuid>,
+) -> HttpResponse {
+    revoke_share_link(pool, claims, workout_id).await
+}
+
+#[post("/workout/{id}/compare")]
+async fn compare_workout_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<uuid::Uuid>,
+    request: web::Json<crate::models::workout_data::CompareWorkoutRequest>,
+) -> HttpResponse {
+    compare_workout(pool, claims, workout_id, request).await
+}
+
+#[get("/workout/{id}/score-breakdown")]
+async fn get_score_breakdown_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    workout_id: web::Path<uuid::Uuid>,
+) -> HttpResponse {
+    get_score_breakdown(pool, claims, workout_id).await
 }
 
 #[post("/check_sync_status")]
@@ -38,6 +99,33 @@ async fn check_workout_sync_handler(
     check_workout_sync(pool, claims, request, jwt_settings).await
 }
 
+#[get("/sync/change-token")]
+async fn get_change_token_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ChangeTokenQuery>,
+) -> HttpResponse {
+    get_device_change_token(pool, claims, query).await
+}
+
+#[put("/sync/change-token")]
+async fn store_change_token_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<StoreChangeTokenRequest>,
+) -> HttpResponse {
+    store_device_change_token(pool, claims, request).await
+}
+
+#[delete("/sync/change-token")]
+async fn clear_change_token_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<ChangeTokenQuery>,
+) -> HttpResponse {
+    clear_device_change_token(pool, claims, query).await
+}
+
 #[post("/workout/{workout_id}/scoring-feedback")]
 async fn submit_scoring_feedback_handler(
     pool: web::Data<PgPool>,
@@ -91,4 +179,65 @@ async fn delete_workout_report_handler(
     claims: web::ReqData<Claims>,
 ) -> actix_web::Result<HttpResponse> {
     delete_workout_report(pool, report_id, claims).await
-}
\ No newline at end of file
+}
+
+#[get("/analytics/hr-trends")]
+async fn get_hr_trends_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<HrTrendsQuery>,
+) -> HttpResponse {
+    get_hr_trends(pool, claims, query).await
+}
+
+#[post("/workouts/recalculate-zones")]
+async fn recalculate_workout_zones_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<RecalculateZonesRequest>,
+) -> HttpResponse {
+    recalculate_workout_zones(pool, claims, request).await
+}
+
+#[post("/metrics")]
+async fn record_body_metrics_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<RecordBodyMetricsRequest>,
+) -> HttpResponse {
+    record_body_metrics(pool, claims, request).await
+}
+
+#[get("/metrics")]
+async fn get_body_metrics_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BodyMetricsQuery>,
+) -> HttpResponse {
+    get_body_metrics(pool, claims, query).await
+}
+
+#[delete("/metrics/{metric_id}")]
+async fn delete_body_metric_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    metric_id: web::Path<uuid::Uuid>,
+) -> HttpResponse {
+    delete_body_metric(pool, claims, metric_id).await
+}
+
+#[get("/analytics/body-metrics")]
+async fn get_body_metrics_analytics_handler(
+    pool: web::Data<PgPool>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<BodyMetricsAnalyticsQuery>,
+) -> HttpResponse {
+    get_body_metrics_analytics(pool, claims, query).await
+}
+
+#[get("/activities")]
+async fn get_activity_catalog_handler(
+    pool: web::Data<PgPool>,
+) -> HttpResponse {
+    get_activity_catalog(pool).await
+}
@@ -0,0 +1,222 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::account_deletions::due_deletions;
+use crate::services::MinIOService;
+
+/// Workouts carry their heart rate samples, so they're streamed in pages of this many
+const EXPORT_WORKOUT_PAGE_SIZE: i64 = 50;
+
+/// Accounts purged per scheduler run
+const PURGE_BATCH_SIZE: i64 = 50;
+
+/// Sections of the data export between the account and the workouts, each read as one JSON value.
+/// Every query takes the user id as its only parameter.
+const EXPORT_SECTIONS: &[(&str, &str)] = &[
+    ("health_profile", "SELECT to_jsonb(p) - 'user_id' FROM user_health_profiles p WHERE p.user_id = $1"),
+    ("health_profile_history", "SELECT COALESCE(jsonb_agg(to_jsonb(h) - 'user_id' ORDER BY h.valid_from), '[]') FROM user_health_profile_history h WHERE h.user_id = $1"),
+    ("body_metrics", "SELECT COALESCE(jsonb_agg(to_jsonb(m) - 'user_id' ORDER BY m.recorded_at), '[]') FROM body_metrics m WHERE m.user_id = $1"),
+    ("username_history", "SELECT COALESCE(jsonb_agg(jsonb_build_object('old_username', h.old_username, 'new_username', h.new_username, 'changed_at', h.changed_at) ORDER BY h.changed_at), '[]') FROM username_history h WHERE h.user_id = $1"),
+    ("consent_settings", "SELECT to_jsonb(c) - 'user_id' FROM user_consent_settings c WHERE c.user_id = $1"),
+    ("team_memberships", "SELECT COALESCE(jsonb_agg(jsonb_build_object('team_id', t.id, 'team_name', t.team_name, 'role', tm.role, 'status', tm.status, 'joined_at', tm.joined_at) ORDER BY tm.joined_at), '[]') FROM team_members tm JOIN teams t ON t.id = tm.team_id WHERE tm.user_id = $1"),
+    ("posts", "SELECT COALESCE(jsonb_agg(to_jsonb(p) - 'user_id' ORDER BY p.created_at), '[]') FROM posts p WHERE p.user_id = $1"),
+    ("comments", "SELECT COALESCE(jsonb_agg(to_jsonb(c) - 'user_id' ORDER BY c.created_at), '[]') FROM post_comments c WHERE c.user_id = $1"),
+    ("reactions", "SELECT COALESCE(jsonb_agg(to_jsonb(r) - 'user_id' ORDER BY r.created_at), '[]') FROM reactions r WHERE r.user_id = $1"),
+    ("chat_messages", "SELECT COALESCE(jsonb_agg(to_jsonb(m) - 'user_id' ORDER BY m.created_at), '[]') FROM team_chat_messages m WHERE m.user_id = $1"),
+    ("workout_reports", "SELECT COALESCE(jsonb_agg(jsonb_build_object('workout_data_id', r.workout_data_id, 'reason', r.reason, 'status', r.status, 'created_at', r.created_at) ORDER BY r.created_at), '[]') FROM workout_reports r WHERE r.reported_by_user_id = $1"),
+    ("media", "SELECT COALESCE(jsonb_agg(jsonb_build_object('object_key', m.object_key, 'sha256', m.sha256, 'uploaded_at', m.created_at) ORDER BY m.created_at), '[]') FROM media_hashes m WHERE m.user_id = $1"),
+];
+
+/// Where the export stream is
+enum ExportStep {
+    Account,
+    Section(usize),
+    Workouts { after: Option<(DateTime<Utc>, Uuid)> },
+    Done,
+}
+
+/// Object key of a profile picture stored in MinIO, from the URL saved on the user
+pub fn profile_picture_object_key(url: &str) -> Option<String> {
+    url.strip_prefix("/profile/picture/").map(|path| format!("profile-pictures/{path}"))
+}
+
+/// Exports and purges everything stored about a user, for account deletion and data access requests
+pub struct AccountDataService {
+    pool: PgPool,
+}
+
+impl AccountDataService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's data as one JSON document, streamed section by section so heart rate samples
+    /// of long workout histories are never held in memory at once
+    pub fn export(&self, user_id: Uuid) -> impl Stream<Item = Result<Bytes, sqlx::Error>> + 'static {
+        let pool = self.pool.clone();
+        stream::unfold(ExportStep::Account, move |step| {
+            let pool = pool.clone();
+            async move {
+                let (chunk, next) = match step {
+                    ExportStep::Done => return None,
+                    ExportStep::Account => (export_account(&pool, user_id).await, ExportStep::Section(0)),
+                    ExportStep::Section(index) => match EXPORT_SECTIONS.get(index) {
+                        Some((name, query)) => (export_section(&pool, user_id, name, query).await, ExportStep::Section(index + 1)),
+                        None => (Ok(",\"workouts\":[".to_string()), ExportStep::Workouts { after: None }),
+                    },
+                    ExportStep::Workouts { after } => match export_workouts(&pool, user_id, after).await {
+                        Ok((chunk, Some(last))) => (Ok(chunk), ExportStep::Workouts { after: Some(last) }),
+                        Ok((chunk, None)) => (Ok(chunk + "]}"), ExportStep::Done),
+                        Err(e) => (Err(e), ExportStep::Done),
+                    },
+                };
+                match chunk {
+                    Ok(chunk) => Some((Ok(Bytes::from(chunk)), next)),
+                    Err(e) => Some((Err(e), ExportStep::Done)),
+                }
+            }
+        })
+    }
+
+    /// Purge the accounts whose deletion grace period has passed, along with their stored media
+    /// when MinIO is available. Returns how many accounts were purged.
+    pub async fn purge_due_accounts(&self, minio_service: Option<&MinIOService>) -> Result<usize, sqlx::Error> {
+        let mut purged = 0;
+        for user_id in due_deletions(&self.pool, PURGE_BATCH_SIZE).await? {
+            match self.purge_account(user_id).await {
+                Ok(Some(object_keys)) => {
+                    purged += 1;
+                    tracing::info!("🗑️ Purged account {} and its data", user_id);
+                    let Some(minio_service) = minio_service else {
+                        continue;
+                    };
+                    for object_key in object_keys {
+                        if let Err(e) = minio_service.delete_file(&object_key).await {
+                            tracing::warn!("Failed to delete media {} of purged account {}: {}", object_key, user_id, e);
+                        }
+                    }
+                }
+                Ok(None) => tracing::warn!("Account {} still owns teams, purge postponed", user_id),
+                Err(e) => tracing::error!("Failed to purge account {}: {}", user_id, e),
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Delete the user and everything stored about them. Game results keep their scores but lose
+    /// the user's score events and awards. Returns the MinIO objects to delete, or None while the
+    /// user still owns teams, which would be deleted with them.
+    async fn purge_account(&self, user_id: Uuid) -> Result<Option<Vec<String>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let owns_teams = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM teams WHERE user_id = $1) as "owns_teams!""#,
+            user_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if owns_teams {
+            return Ok(None);
+        }
+
+        let mut object_keys = sqlx::query_scalar!("SELECT object_key FROM media_hashes WHERE user_id = $1", user_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        let profile_picture_url = sqlx::query_scalar!("SELECT profile_picture_url FROM users WHERE id = $1", user_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .flatten();
+        object_keys.extend(profile_picture_url.as_deref().and_then(profile_picture_object_key));
+
+        sqlx::query!(
+            r#"
+            UPDATE game_summaries SET
+                mvp_user_id = NULLIF(mvp_user_id, $1),
+                lvp_user_id = NULLIF(lvp_user_id, $1),
+                home_team_top_scorer_id = NULLIF(home_team_top_scorer_id, $1),
+                home_team_lowest_performer_id = NULLIF(home_team_lowest_performer_id, $1),
+                away_team_top_scorer_id = NULLIF(away_team_top_scorer_id, $1),
+                away_team_lowest_performer_id = NULLIF(away_team_lowest_performer_id, $1)
+            WHERE $1 IN (mvp_user_id, lvp_user_id, home_team_top_scorer_id, home_team_lowest_performer_id,
+                away_team_top_scorer_id, away_team_lowest_performer_id)
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("DELETE FROM live_score_events WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+        // Everything else stored about the user cascades
+        sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some(object_keys))
+    }
+}
+
+/// Opening of the export document with the account itself; the password hash stays out
+async fn export_account(pool: &PgPool, user_id: Uuid) -> Result<String, sqlx::Error> {
+    let account = sqlx::query_scalar!(
+        r#"
+        SELECT jsonb_build_object(
+            'id', id, 'username', username, 'email', email, 'role', role, 'status', status,
+            'profile_picture_url', profile_picture_url, 'created_at', created_at
+        ) as "account!"
+        FROM users WHERE id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(format!(
+        "{{\"exported_at\":{},\"account\":{}",
+        serde_json::Value::String(Utc::now().to_rfc3339()),
+        account
+    ))
+}
+
+async fn export_section(pool: &PgPool, user_id: Uuid, name: &str, query: &str) -> Result<String, sqlx::Error> {
+    let value = sqlx::query_scalar::<_, Option<serde_json::Value>>(query)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .flatten()
+        .unwrap_or(serde_json::Value::Null);
+    Ok(format!(",\"{name}\":{value}"))
+}
+
+/// The next page of workouts after `after`, with the position of its last workout if more may follow
+async fn export_workouts(
+    pool: &PgPool,
+    user_id: Uuid,
+    after: Option<(DateTime<Utc>, Uuid)>,
+) -> Result<(String, Option<(DateTime<Utc>, Uuid)>), sqlx::Error> {
+    let (after_start, after_id) = after.unzip();
+    let rows = sqlx::query!(
+        r#"
+        SELECT w.id, w.workout_start, to_jsonb(w) - 'user_id' as "workout!"
+        FROM workout_data w
+        WHERE w.user_id = $1
+            AND ($2::timestamptz IS NULL OR (w.workout_start, w.id) > ($2, $3))
+        ORDER BY w.workout_start, w.id
+        LIMIT $4
+        "#,
+        user_id,
+        after_start,
+        after_id,
+        EXPORT_WORKOUT_PAGE_SIZE
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let last = (rows.len() as i64 == EXPORT_WORKOUT_PAGE_SIZE)
+        .then(|| rows.last().map(|row| (row.workout_start, row.id)))
+        .flatten();
+    let workouts: Vec<String> = rows.iter().map(|row| row.workout.to_string()).collect();
+    let separator = if after.is_some() && !workouts.is_empty() { "," } else { "" };
+    Ok((format!("{separator}{}", workouts.join(",")), last))
+}
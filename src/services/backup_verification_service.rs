@@ -0,0 +1,390 @@
+use std::fmt;
+use std::path::Path;
+
+use percent_encoding::percent_decode_str;
+use secrecy::{ExposeSecret, SecretString};
+use sqlx::{Connection, PgConnection, PgExecutor, PgPool, Row};
+use tokio::process::Command;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::services::score_consistency_service::{ScoreConsistencyService, ScoreDivergence};
+
+/// Tables whose contents are required to rebuild games, standings and player history.
+/// Order matters only for readability of the report.
+pub const CRITICAL_TABLES: &[&str] = &[
+    "users",
+    "teams",
+    "team_members",
+    "leagues",
+    "league_seasons",
+    "league_standings",
+    "games",
+    "live_score_events",
+];
+
+/// Critical tables too large to dump and restore every night, only verified by full runs
+pub const LARGE_CRITICAL_TABLES: &[&str] = &["workout_data"];
+
+/// Service that dumps the critical tables with `pg_dump`, restores the dump into a scratch
+/// database with `pg_restore` and verifies the restored copy against the live tables (row
+/// counts, checksums, score consistency).
+#[derive(Debug)]
+pub struct BackupVerificationService {
+    pool: PgPool,
+    /// Connection string `pg_dump` and `pg_restore` use; runs error without it
+    database_url: Option<SecretString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TableVerification {
+    pub table_name: String,
+    pub source_rows: i64,
+    pub restored_rows: i64,
+    pub source_checksum: Option<String>,
+    pub restored_checksum: Option<String>,
+    pub matches: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupVerificationRun {
+    pub id: Uuid,
+    pub status: String,
+    pub triggered_by: String,
+    pub table_results: Vec<TableVerification>,
+    pub score_divergences: Vec<ScoreDivergence>,
+    pub error_message: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+struct VerificationOutcome {
+    table_results: Vec<TableVerification>,
+    score_divergences: Vec<ScoreDivergence>,
+}
+
+#[derive(Debug)]
+pub enum BackupVerificationError {
+    /// No connection string to dump and restore with
+    NotConfigured,
+    Database(sqlx::Error),
+    /// `pg_dump` or `pg_restore` couldn't be run or failed
+    Tool(String),
+}
+
+impl fmt::Display for BackupVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupVerificationError::NotConfigured => write!(f, "Backup verification has no database connection string"),
+            BackupVerificationError::Database(e) => write!(f, "Database error: {e}"),
+            BackupVerificationError::Tool(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for BackupVerificationError {}
+
+impl From<sqlx::Error> for BackupVerificationError {
+    fn from(e: sqlx::Error) -> Self {
+        BackupVerificationError::Database(e)
+    }
+}
+
+/// Name of the scratch database a run restores into
+pub fn scratch_database_name(run_id: Uuid) -> String {
+    format!("backup_verify_{}", run_id.simple())
+}
+
+/// The connection string with the database swapped for another one on the same server
+pub fn scratch_database_url(database_url: &str, database: &str) -> Result<String, String> {
+    let mut url = url::Url::parse(database_url).map_err(|e| format!("Invalid database URL: {e}"))?;
+    url.set_path(&format!("/{database}"));
+    Ok(url.to_string())
+}
+
+/// A connection string for `pg_dump` and `pg_restore`, without its password, and the password.
+/// The tools get the password through `PGPASSWORD`, so it doesn't show in the process list.
+pub fn tool_connection(database_url: &str) -> Result<(String, Option<String>), String> {
+    let mut url = url::Url::parse(database_url).map_err(|e| format!("Invalid database URL: {e}"))?;
+    let password = url
+        .password()
+        .map(|password| percent_decode_str(password).decode_utf8_lossy().into_owned());
+    url.set_password(None)
+        .map_err(|_| "Invalid database URL: it can't have a password".to_string())?;
+    Ok((url.to_string(), password))
+}
+
+impl BackupVerificationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, database_url: None }
+    }
+
+    /// Connect `pg_dump` and `pg_restore` to the database with this connection string
+    pub fn with_database_url(mut self, database_url: SecretString) -> Self {
+        self.database_url = Some(database_url);
+        self
+    }
+
+    /// Run a backup verification and persist the result. `triggered_by` is either "scheduler"
+    /// or "admin"; `full` also verifies the large tables.
+    pub async fn run_verification(&self, triggered_by: &str, full: bool) -> Result<BackupVerificationRun, sqlx::Error> {
+        let run_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO backup_verification_runs (triggered_by)
+            VALUES ($1)
+            RETURNING id
+            "#,
+            triggered_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        tracing::info!("💾 [BACKUP] Starting {} backup verification run {}", if full { "full" } else { "nightly" }, run_id);
+
+        let tables: Vec<&str> = CRITICAL_TABLES
+            .iter()
+            .chain(if full { LARGE_CRITICAL_TABLES } else { &[] })
+            .copied()
+            .collect();
+        let (status, table_results, score_divergences, error_message) = match self.verify_snapshot(run_id, &tables).await {
+            Ok(outcome) => {
+                let tables_ok = outcome.table_results.iter().all(|t| t.matches);
+                let status = if tables_ok && outcome.score_divergences.is_empty() { "passed" } else { "failed" };
+                (status, outcome.table_results, outcome.score_divergences, None)
+            }
+            Err(e) => {
+                tracing::error!("❌ [BACKUP] Verification run {} errored: {}", run_id, e);
+                ("error", Vec::new(), Vec::new(), Some(e.to_string()))
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE backup_verification_runs
+            SET status = $2,
+                table_results = $3,
+                score_divergences = $4,
+                error_message = $5,
+                finished_at = NOW()
+            WHERE id = $1
+            "#,
+            run_id,
+            status,
+            serde_json::to_value(&table_results).unwrap_or(serde_json::Value::Null),
+            serde_json::to_value(&score_divergences).unwrap_or(serde_json::Value::Null),
+            error_message
+        )
+        .execute(&self.pool)
+        .await?;
+
+        tracing::info!("✅ [BACKUP] Verification run {} finished with status '{}' ({} tables, {} score divergences)",
+            run_id, status, table_results.len(), score_divergences.len());
+
+        self.get_run(run_id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Dump the tables, restore the dump into a scratch database and compare it with the live
+    /// tables. The dump is taken from a snapshot exported by a REPEATABLE READ transaction that
+    /// the live checksums are computed in too, so writes meanwhile don't count as differences.
+    /// The scratch database and dump files are removed afterwards, whatever the outcome.
+    async fn verify_snapshot(&self, run_id: Uuid, tables: &[&str]) -> Result<VerificationOutcome, BackupVerificationError> {
+        let database_url = self.database_url.as_ref().ok_or(BackupVerificationError::NotConfigured)?;
+        let scratch_database = scratch_database_name(run_id);
+        let scratch_url = scratch_database_url(database_url.expose_secret(), &scratch_database)
+            .map_err(BackupVerificationError::Tool)?;
+        let dump_dir = std::env::temp_dir().join(&scratch_database);
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(&mut *tx)
+            .await?;
+        let snapshot: String = sqlx::query_scalar("SELECT pg_export_snapshot()")
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query(&format!(r#"CREATE DATABASE "{scratch_database}""#))
+            .execute(&self.pool)
+            .await?;
+
+        let outcome = async {
+            tokio::fs::create_dir_all(&dump_dir)
+                .await
+                .map_err(|e| BackupVerificationError::Tool(format!("Failed to create dump directory: {e}")))?;
+            Self::dump_and_restore(database_url.expose_secret(), &scratch_url, &snapshot, tables, &dump_dir).await?;
+
+            let mut restored = PgConnection::connect(&scratch_url).await?;
+            let mut table_results = Vec::with_capacity(tables.len());
+            for table in tables {
+                let (source_rows, source_checksum) = Self::table_fingerprint(&mut *tx, table).await?;
+                let (restored_rows, restored_checksum) = Self::table_fingerprint(&mut restored, table).await?;
+                let matches = source_rows == restored_rows && source_checksum == restored_checksum;
+                if !matches {
+                    tracing::warn!("⚠️ [BACKUP] Restored table {} differs from source: {} vs {} rows",
+                        table, source_rows, restored_rows);
+                }
+                table_results.push(TableVerification {
+                    table_name: table.to_string(),
+                    source_rows,
+                    restored_rows,
+                    source_checksum,
+                    restored_checksum,
+                    matches,
+                });
+            }
+            let score_divergences = ScoreConsistencyService::find_score_divergences(&mut restored, "public", None).await?;
+            restored.close().await?;
+            Ok(VerificationOutcome { table_results, score_divergences })
+        }
+        .await;
+
+        tx.rollback().await?;
+        if let Err(e) = tokio::fs::remove_dir_all(&dump_dir).await {
+            tracing::warn!("⚠️ [BACKUP] Failed to remove dump directory {}: {}", dump_dir.display(), e);
+        }
+        sqlx::query(&format!(r#"DROP DATABASE IF EXISTS "{scratch_database}" WITH (FORCE)"#))
+            .execute(&self.pool)
+            .await?;
+
+        outcome
+    }
+
+    /// Dump the schema and the tables' data with `pg_dump` and restore them into the scratch
+    /// database. Only the schema's pre-data section is restored, tables without constraints or
+    /// indexes, so the data loads regardless of the tables left out.
+    async fn dump_and_restore(
+        database_url: &str,
+        scratch_url: &str,
+        snapshot: &str,
+        tables: &[&str],
+        dump_dir: &Path,
+    ) -> Result<(), BackupVerificationError> {
+        let schema_dump = dump_dir.join("schema.dump");
+        let data_dump = dump_dir.join("data.dump");
+        let (database_url, password) = tool_connection(database_url).map_err(BackupVerificationError::Tool)?;
+        let (scratch_url, scratch_password) = tool_connection(scratch_url).map_err(BackupVerificationError::Tool)?;
+        let dump_args = [
+            format!("--dbname={database_url}"),
+            format!("--snapshot={snapshot}"),
+            "--format=custom".to_string(),
+            "--no-owner".to_string(),
+            "--no-privileges".to_string(),
+        ];
+
+        let mut schema_args = dump_args.to_vec();
+        schema_args.extend(["--schema-only".to_string(), format!("--file={}", schema_dump.display())]);
+        run_tool("pg_dump", &schema_args, password.as_deref(), true).await?;
+
+        let mut data_args = dump_args.to_vec();
+        data_args.push("--data-only".to_string());
+        data_args.extend(tables.iter().map(|table| format!("--table=public.{table}")));
+        data_args.push(format!("--file={}", data_dump.display()));
+        run_tool("pg_dump", &data_args, password.as_deref(), true).await?;
+
+        // Objects the restoring role may not create (e.g. extension comments) only warn; a
+        // restore that lost data shows up in the comparison
+        let restore_args = |dump: &Path, extra: &[&str]| {
+            let mut args = vec![format!("--dbname={scratch_url}"), "--no-owner".to_string(), "--no-privileges".to_string()];
+            args.extend(extra.iter().map(|arg| arg.to_string()));
+            args.push(dump.display().to_string());
+            args
+        };
+        run_tool("pg_restore", &restore_args(&schema_dump, &["--section=pre-data"]), scratch_password.as_deref(), false).await?;
+        run_tool("pg_restore", &restore_args(&data_dump, &[]), scratch_password.as_deref(), false).await?;
+        Ok(())
+    }
+
+    /// Row count and order-independent checksum of a table's contents
+    async fn table_fingerprint<'e, E>(executor: E, table: &str) -> Result<(i64, Option<String>), sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT COUNT(*) AS row_count,
+                   md5(string_agg(row_hash, '' ORDER BY row_hash)) AS checksum
+            FROM (SELECT md5(t::text) AS row_hash FROM public.{table} t) hashed
+            "#
+        ))
+        .fetch_one(executor)
+        .await?;
+
+        Ok((row.try_get("row_count")?, row.try_get("checksum")?))
+    }
+
+    /// Get a single verification run
+    pub async fn get_run(&self, run_id: Uuid) -> Result<Option<BackupVerificationRun>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, status, triggered_by, table_results, score_divergences,
+                   error_message, started_at, finished_at
+            FROM backup_verification_runs
+            WHERE id = $1
+            "#,
+            run_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| BackupVerificationRun {
+            id: r.id,
+            status: r.status,
+            triggered_by: r.triggered_by,
+            table_results: serde_json::from_value(r.table_results).unwrap_or_default(),
+            score_divergences: serde_json::from_value(r.score_divergences).unwrap_or_default(),
+            error_message: r.error_message,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        }))
+    }
+
+    /// Get the most recent verification runs, newest first
+    pub async fn get_recent_runs(&self, limit: i64) -> Result<Vec<BackupVerificationRun>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, status, triggered_by, table_results, score_divergences,
+                   error_message, started_at, finished_at
+            FROM backup_verification_runs
+            ORDER BY started_at DESC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| BackupVerificationRun {
+            id: r.id,
+            status: r.status,
+            triggered_by: r.triggered_by,
+            table_results: serde_json::from_value(r.table_results).unwrap_or_default(),
+            score_divergences: serde_json::from_value(r.score_divergences).unwrap_or_default(),
+            error_message: r.error_message,
+            started_at: r.started_at,
+            finished_at: r.finished_at,
+        }).collect())
+    }
+}
+
+/// Run `pg_dump` or `pg_restore`, connecting with `password`. With `strict`, a failed run is an
+/// error, otherwise only logged.
+async fn run_tool(program: &str, args: &[String], password: Option<&str>, strict: bool) -> Result<(), BackupVerificationError> {
+    let mut command = Command::new(program);
+    command.args(args);
+    if let Some(password) = password {
+        command.env("PGPASSWORD", password);
+    }
+    let output = command
+        .output()
+        .await
+        .map_err(|e| BackupVerificationError::Tool(format!("Failed to run {program}: {e}")))?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if strict {
+        return Err(BackupVerificationError::Tool(format!("{program} failed ({}): {}", output.status, stderr.trim())));
+    }
+    tracing::warn!("⚠️ [BACKUP] {} reported errors ({}): {}", program, output.status, stderr.trim());
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::body_metrics::{BodyMetric, BodyMetricEntry, BodyMetricsQuery, DailyBodyMetrics};
+
+/// Stores the weight, HRV and resting HR time series of users and lines them up with
+/// their training load for analytics.
+#[derive(Debug)]
+pub struct BodyMetricsService {
+    pool: PgPool,
+}
+
+impl BodyMetricsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store validated entries. Entries with an `external_id` replace the entry a device
+    /// synced earlier under the same id.
+    pub async fn record(&self, user_id: Uuid, entries: &[BodyMetricEntry]) -> Result<Vec<BodyMetric>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut recorded = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let metric = sqlx::query_as!(
+                BodyMetric,
+                r#"
+                INSERT INTO body_metrics (user_id, metric_type, value, recorded_at, source, device_id, external_id)
+                VALUES ($1, $2, $3, COALESCE($4, NOW()), COALESCE($5, 'manual'), $6, $7)
+                ON CONFLICT (user_id, metric_type, external_id) WHERE external_id IS NOT NULL
+                DO UPDATE SET
+                    value = EXCLUDED.value,
+                    recorded_at = EXCLUDED.recorded_at,
+                    source = EXCLUDED.source,
+                    device_id = EXCLUDED.device_id
+                RETURNING id, metric_type, value, recorded_at, source, device_id, created_at
+                "#,
+                user_id,
+                entry.metric_type,
+                entry.value,
+                entry.recorded_at,
+                entry.source,
+                entry.device_id,
+                entry.external_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            recorded.push(metric);
+        }
+
+        tx.commit().await?;
+        Ok(recorded)
+    }
+
+    /// A user's entries in a time range, oldest first
+    pub async fn list(&self, user_id: Uuid, query: &BodyMetricsQuery, limit: i64) -> Result<Vec<BodyMetric>, sqlx::Error> {
+        sqlx::query_as!(
+            BodyMetric,
+            r#"
+            SELECT id as "id!", metric_type as "metric_type!", value as "value!", recorded_at as "recorded_at!",
+                   source as "source!", device_id, created_at as "created_at!"
+            FROM (
+                SELECT id, metric_type, value, recorded_at, source, device_id, created_at
+                FROM body_metrics
+                WHERE user_id = $1
+                AND ($2::text IS NULL OR metric_type = $2)
+                AND ($3::timestamptz IS NULL OR recorded_at >= $3)
+                AND ($4::timestamptz IS NULL OR recorded_at <= $4)
+                ORDER BY recorded_at DESC
+                LIMIT $5
+            ) recent
+            ORDER BY recorded_at ASC
+            "#,
+            user_id,
+            query.metric_type,
+            query.from,
+            query.to,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Delete one of the user's entries. Returns false if there was no such entry.
+    pub async fn delete(&self, user_id: Uuid, metric_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM body_metrics WHERE id = $1 AND user_id = $2",
+            metric_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Daily training load and body metrics over the last `days` days, for one chart.
+    /// Days with several entries of a metric show their average.
+    pub async fn daily_overview(&self, user_id: Uuid, days: i32) -> Result<Vec<DailyBodyMetrics>, sqlx::Error> {
+        sqlx::query_as!(
+            DailyBodyMetrics,
+            r#"
+            WITH days AS (
+                SELECT generate_series(CURRENT_DATE - ($2::int - 1), CURRENT_DATE, INTERVAL '1 day')::date AS day
+            ),
+            load AS (
+                SELECT workout_start::date AS day,
+                       SUM(stamina_gained + strength_gained)::float8 AS training_load,
+                       COUNT(*) AS workouts
+                FROM workout_data
+                WHERE user_id = $1 AND workout_start >= CURRENT_DATE - ($2::int - 1)
+                GROUP BY 1
+            ),
+            metrics AS (
+                SELECT recorded_at::date AS day,
+                       AVG(value) FILTER (WHERE metric_type = 'weight')::float8 AS weight,
+                       AVG(value) FILTER (WHERE metric_type = 'hrv')::float8 AS hrv,
+                       AVG(value) FILTER (WHERE metric_type = 'resting_hr')::float8 AS resting_hr
+                FROM body_metrics
+                WHERE user_id = $1 AND recorded_at >= CURRENT_DATE - ($2::int - 1)
+                GROUP BY 1
+            )
+            SELECT
+                d.day as "date!",
+                COALESCE(l.training_load, 0) as "training_load!",
+                COALESCE(l.workouts, 0) as "workouts!",
+                m.weight,
+                m.hrv,
+                m.resting_hr
+            FROM days d
+            LEFT JOIN load l ON l.day = d.day
+            LEFT JOIN metrics m ON m.day = d.day
+            ORDER BY d.day
+            "#,
+            user_id,
+            days
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
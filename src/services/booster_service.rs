@@ -0,0 +1,582 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::db::game_queries::GameQueries;
+use crate::game::boosters::{self, BoosterType, MAX_HELD_PER_TYPE};
+use crate::models::booster::{BoosterAuditEntry, BoosterInventory, GameBoosters, TeamBooster};
+use crate::models::game_events::GameEvent;
+use crate::services::{event_outbox, EventOutbox};
+
+/// Booster announcements go to everyone following the games
+const BOOSTER_CHANNEL: &str = "game:events:global";
+
+#[derive(Debug)]
+pub enum BoosterError {
+    GameNotFound,
+    /// Boosters can only be activated while the game is in progress
+    GameNotLive,
+    NotAPlayer,
+    NoneAvailable,
+    /// The team already activated a booster of this kind in the game
+    AlreadyActive,
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for BoosterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameNotFound => write!(f, "Game not found"),
+            Self::GameNotLive => write!(f, "Boosters can only be activated while the game is in progress"),
+            Self::NotAPlayer => write!(f, "You are not a member of a team playing in this game"),
+            Self::NoneAvailable => write!(f, "Your team has no booster of this kind left"),
+            Self::AlreadyActive => write!(f, "Your team already used a booster of this kind in this game"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for BoosterError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+struct BoosterGame {
+    home_team_id: Uuid,
+    away_team_id: Uuid,
+    game_start_time: Option<DateTime<Utc>>,
+    game_end_time: Option<DateTime<Utc>>,
+}
+
+impl BoosterGame {
+    fn team_side(&self, team_id: Uuid) -> &'static str {
+        if team_id == self.home_team_id { "home" } else { "away" }
+    }
+}
+
+/// Awards, activates and settles team boosters. Double boosters are applied by the scoring
+/// engine through `take_active_double_booster` and `mark_applied`.
+pub struct BoosterService {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl BoosterService {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Boosters used in a game and the inventory of the user's team. None if the game does not exist.
+    pub async fn game_boosters(&self, game_id: Uuid, user_id: Uuid) -> Result<Option<GameBoosters>, sqlx::Error> {
+        let Some(game) = find_game(&mut *self.pool.acquire().await?, game_id).await? else {
+            return Ok(None);
+        };
+
+        let team_id = find_player_team(&mut *self.pool.acquire().await?, &game, user_id).await?.map(|(team_id, _)| team_id);
+        let inventory = match team_id {
+            Some(team_id) => self.inventory(team_id).await?,
+            None => Vec::new(),
+        };
+
+        let boosters = sqlx::query_as!(
+            TeamBooster,
+            r#"
+            SELECT id, team_id, booster_type, status, earned_for, earned_in_game_id, earned_at,
+                   game_id, activated_by, activated_at, consumed_at, bonus_points
+            FROM team_boosters
+            WHERE game_id = $1
+            ORDER BY activated_at ASC
+            "#,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(GameBoosters { game_id, team_id, inventory, boosters }))
+    }
+
+    async fn inventory(&self, team_id: Uuid) -> Result<Vec<BoosterInventory>, sqlx::Error> {
+        let counts = sqlx::query!(
+            r#"
+            SELECT booster_type, COUNT(*) as "available!"
+            FROM team_boosters
+            WHERE team_id = $1 AND status = 'available'
+            GROUP BY booster_type
+            "#,
+            team_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(BoosterType::ALL
+            .iter()
+            .map(|booster_type| BoosterInventory {
+                booster_type: booster_type.as_str().to_string(),
+                available: counts
+                    .iter()
+                    .find(|c| c.booster_type == booster_type.as_str())
+                    .map_or(0, |c| c.available),
+            })
+            .collect())
+    }
+
+    /// Activate the oldest available booster of the user's team in a live game
+    pub async fn activate(&self, game_id: Uuid, user_id: Uuid, booster_type: BoosterType) -> Result<TeamBooster, BoosterError> {
+        let mut tx = self.pool.begin().await?;
+
+        let live = sqlx::query_scalar!(
+            r#"
+            SELECT (status = 'in_progress' AND game_end_time > NOW()) as "live!"
+            FROM games WHERE id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(BoosterError::GameNotFound)?;
+        if !live {
+            return Err(BoosterError::GameNotLive);
+        }
+
+        let game = find_game(&mut tx, game_id).await?.ok_or(BoosterError::GameNotFound)?;
+        let (team_id, username) = find_player_team(&mut tx, &game, user_id).await?.ok_or(BoosterError::NotAPlayer)?;
+
+        let already_active = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM team_boosters WHERE game_id = $1 AND team_id = $2 AND booster_type = $3
+            ) as "exists!"
+            "#,
+            game_id,
+            team_id,
+            booster_type.as_str()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if already_active {
+            return Err(BoosterError::AlreadyActive);
+        }
+
+        let booster = sqlx::query_as!(
+            TeamBooster,
+            r#"
+            UPDATE team_boosters
+            SET status = 'active', game_id = $3, activated_by = $4, activated_at = NOW()
+            WHERE id = (
+                SELECT id FROM team_boosters
+                WHERE team_id = $1 AND booster_type = $2 AND status = 'available'
+                ORDER BY earned_at ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, team_id, booster_type, status, earned_for, earned_in_game_id, earned_at,
+                      game_id, activated_by, activated_at, consumed_at, bonus_points
+            "#,
+            team_id,
+            booster_type.as_str(),
+            game_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            // A teammate activated one at the same time
+            Some(db_error) if db_error.is_unique_violation() => BoosterError::AlreadyActive,
+            _ => BoosterError::Database(e),
+        })?
+        .ok_or(BoosterError::NoneAvailable)?;
+
+        audit(&mut tx, &booster, "activated", Some(user_id), json!({})).await?;
+        let event_id = announce(&mut tx, &GameEvent::BoosterActivated {
+            booster_id: booster.id,
+            game_id,
+            team_id,
+            booster_type: booster.booster_type.clone(),
+            user_id,
+            username,
+            timestamp: Utc::now(),
+        }).await?;
+        tx.commit().await?;
+
+        tracing::info!("🚀 User {} activated {} booster {} for team {} in game {}",
+            user_id, booster.booster_type, booster.id, team_id, game_id);
+        self.publish(&[event_id]).await;
+        Ok(booster)
+    }
+
+    /// Settle the boosters of finished games before they are evaluated: shields fill their team's
+    /// weakest day and double boosters that found no workout expire
+    pub async fn settle_games(&self, game_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        for game_id in game_ids {
+            self.settle_game(*game_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn settle_game(&self, game_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let active = sqlx::query_as!(
+            TeamBooster,
+            r#"
+            SELECT id, team_id, booster_type, status, earned_for, earned_in_game_id, earned_at,
+                   game_id, activated_by, activated_at, consumed_at, bonus_points
+            FROM team_boosters
+            WHERE game_id = $1 AND status = 'active'
+            FOR UPDATE
+            "#,
+            game_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if active.is_empty() {
+            return Ok(());
+        }
+        let Some(game) = find_game(&mut tx, game_id).await? else {
+            return Ok(());
+        };
+
+        let mut event_ids = Vec::new();
+        let mut scores_changed = false;
+        for booster in &active {
+            let shield_event = match BoosterType::parse(&booster.booster_type) {
+                Some(BoosterType::Shield) => apply_shield(&mut tx, game_id, &game, booster).await?,
+                _ => None,
+            };
+
+            match shield_event {
+                Some((score_event_id, bonus_points)) => {
+                    event_ids.push(mark_applied(&mut tx, booster.id, game_id, score_event_id, bonus_points).await?);
+                    scores_changed = true;
+                }
+                None => event_ids.push(expire(&mut tx, booster, game_id).await?),
+            }
+        }
+
+        if scores_changed {
+            let (home_score, away_score) = GameQueries::calculate_team_scores_best_4(&mut tx, game_id).await?;
+            sqlx::query!(
+                "UPDATE games SET home_score = $2, away_score = $3, updated_at = NOW() WHERE id = $1",
+                game_id,
+                home_score,
+                away_score
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.publish(&event_ids).await;
+        Ok(())
+    }
+
+    /// Award the boosters both teams earned in an evaluated game. An award is forfeited
+    /// when the team already holds the maximum of that kind.
+    pub async fn award_for_game(&self, game_id: Uuid, winner_team_id: Option<Uuid>) -> Result<Vec<TeamBooster>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let Some(game) = find_game(&mut tx, game_id).await? else {
+            return Ok(Vec::new());
+        };
+
+        let mut awarded = Vec::new();
+        let mut event_ids = Vec::new();
+        for team_id in [game.home_team_id, game.away_team_id] {
+            let squad = sqlx::query!(
+                r#"
+                SELECT
+                    (SELECT COUNT(*) FROM team_members tm
+                     WHERE tm.team_id = $1 AND tm.status = 'active'
+                     AND ($3::timestamptz IS NULL OR tm.joined_at <= $3)) as "size!",
+                    (SELECT COUNT(DISTINCT lse.user_id) FROM live_score_events lse
+                     WHERE lse.game_id = $2 AND lse.team_id = $1 AND lse.event_type = 'workout_upload') as "scorers!"
+                "#,
+                team_id,
+                game_id,
+                game.game_end_time
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            let won = winner_team_id == Some(team_id);
+            for achievement in boosters::earned_achievements(won, squad.size as usize, squad.scorers as usize) {
+                let booster_type = achievement.booster();
+                let held = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "held!" FROM team_boosters WHERE team_id = $1 AND booster_type = $2 AND status = 'available'"#,
+                    team_id,
+                    booster_type.as_str()
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                if held >= MAX_HELD_PER_TYPE {
+                    tracing::info!("Team {} already holds {} {} boosters, forfeiting {} award",
+                        team_id, held, booster_type.as_str(), achievement.as_str());
+                    continue;
+                }
+
+                let booster = sqlx::query_as!(
+                    TeamBooster,
+                    r#"
+                    INSERT INTO team_boosters (team_id, booster_type, earned_for, earned_in_game_id)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (earned_in_game_id, team_id, earned_for) DO NOTHING
+                    RETURNING id, team_id, booster_type, status, earned_for, earned_in_game_id, earned_at,
+                              game_id, activated_by, activated_at, consumed_at, bonus_points
+                    "#,
+                    team_id,
+                    booster_type.as_str(),
+                    achievement.as_str(),
+                    game_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+                // Awarded when the game was evaluated before
+                let Some(booster) = booster else { continue };
+
+                audit(&mut tx, &booster, "earned", None, json!({ "earned_for": booster.earned_for })).await?;
+                event_ids.push(announce(&mut tx, &GameEvent::BoosterEarned {
+                    booster_id: booster.id,
+                    team_id,
+                    booster_type: booster.booster_type.clone(),
+                    earned_for: booster.earned_for.clone(),
+                    game_id: Some(game_id),
+                    timestamp: Utc::now(),
+                }).await?);
+                awarded.push(booster);
+            }
+        }
+        tx.commit().await?;
+
+        self.publish(&event_ids).await;
+        Ok(awarded)
+    }
+
+    /// Audit trail of a team's boosters, newest first
+    pub async fn audit_trail(&self, team_id: Uuid) -> Result<Vec<BoosterAuditEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            BoosterAuditEntry,
+            r#"
+            SELECT id, booster_id, team_id, game_id, user_id, action, details, created_at
+            FROM team_booster_audit
+            WHERE team_id = $1
+            ORDER BY created_at DESC
+            "#,
+            team_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn publish(&self, event_ids: &[Uuid]) {
+        let outbox = EventOutbox::new(self.pool.clone(), self.redis_client.clone());
+        if let Err(e) = outbox.publish(event_ids).await {
+            tracing::error!("Failed to publish booster events: {}", e);
+        }
+    }
+}
+
+/// Active double booster of a team in a game, locked until the scoring transaction ends
+pub async fn take_active_double_booster(
+    conn: &mut PgConnection,
+    game_id: Uuid,
+    team_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT id FROM team_boosters
+        WHERE game_id = $1 AND team_id = $2 AND booster_type = $3 AND status = 'active'
+        FOR UPDATE SKIP LOCKED
+        "#,
+        game_id,
+        team_id,
+        BoosterType::DoubleNextWorkout.as_str()
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// Consume a booster for the score event it added points to. Returns the outbox event announcing it.
+pub async fn mark_applied(
+    conn: &mut PgConnection,
+    booster_id: Uuid,
+    game_id: Uuid,
+    score_event_id: Uuid,
+    bonus_points: f32,
+) -> Result<Uuid, sqlx::Error> {
+    let booster = sqlx::query_as!(
+        TeamBooster,
+        r#"
+        UPDATE team_boosters
+        SET status = 'consumed', consumed_at = NOW(), score_event_id = $2, bonus_points = $3
+        WHERE id = $1
+        RETURNING id, team_id, booster_type, status, earned_for, earned_in_game_id, earned_at,
+                  game_id, activated_by, activated_at, consumed_at, bonus_points
+        "#,
+        booster_id,
+        score_event_id,
+        bonus_points
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    audit(&mut *conn, &booster, "applied", None, json!({
+        "score_event_id": score_event_id,
+        "bonus_points": bonus_points,
+    })).await?;
+    announce(&mut *conn, &GameEvent::BoosterApplied {
+        booster_id,
+        game_id,
+        team_id: booster.team_id,
+        booster_type: booster.booster_type,
+        bonus_points,
+        score_event_id: Some(score_event_id),
+        timestamp: Utc::now(),
+    }).await
+}
+
+async fn expire(conn: &mut PgConnection, booster: &TeamBooster, game_id: Uuid) -> Result<Uuid, sqlx::Error> {
+    sqlx::query!(
+        "UPDATE team_boosters SET status = 'expired', consumed_at = NOW() WHERE id = $1",
+        booster.id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    audit(&mut *conn, booster, "expired", None, json!({})).await?;
+    announce(&mut *conn, &GameEvent::BoosterExpired {
+        booster_id: booster.id,
+        game_id,
+        team_id: booster.team_id,
+        booster_type: booster.booster_type.clone(),
+        timestamp: Utc::now(),
+    }).await
+}
+
+/// Add a team bonus event filling the team's weakest game day. Returns the score event and
+/// its points, or None if no day fell short of the team's average.
+async fn apply_shield(
+    conn: &mut PgConnection,
+    game_id: Uuid,
+    game: &BoosterGame,
+    booster: &TeamBooster,
+) -> Result<Option<(Uuid, f32)>, sqlx::Error> {
+    let (Some(game_start), Some(game_end), Some(activated_by)) = (game.game_start_time, game.game_end_time, booster.activated_by) else {
+        return Ok(None);
+    };
+
+    let workouts = sqlx::query!(
+        r#"
+        SELECT wd.workout_start, lse.score_points
+        FROM live_score_events lse
+        JOIN workout_data wd ON wd.id = lse.workout_data_id
+        WHERE lse.game_id = $1 AND lse.team_id = $2 AND lse.event_type = 'workout_upload'
+        "#,
+        game_id,
+        booster.team_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut daily_points = vec![0.0; boosters::game_days(game_start, game_end)];
+    for workout in &workouts {
+        if let Some(day) = daily_points.get_mut(boosters::game_day(game_start, workout.workout_start)) {
+            *day += workout.score_points as f64;
+        }
+    }
+    let Some(fill) = boosters::shield_fill(&daily_points) else {
+        return Ok(None);
+    };
+
+    let bonus_points = fill.bonus_points as f32;
+    let score_event_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO live_score_events (
+            game_id, user_id, username, team_id, team_side,
+            score_points, power_contribution, booster_bonus, event_type, description
+        )
+        SELECT $1, u.id, u.username, $3, $4, $5, 0, $5, 'team_bonus', $6
+        FROM users u WHERE u.id = $2
+        RETURNING id
+        "#,
+        game_id,
+        activated_by,
+        booster.team_id,
+        game.team_side(booster.team_id),
+        bonus_points,
+        format!(
+            "Shield booster: day {} filled up to the team's daily average (+{} points)",
+            fill.day + 1,
+            bonus_points
+        )
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(score_event_id.map(|id| (id, bonus_points)))
+}
+
+async fn find_game(conn: &mut PgConnection, game_id: Uuid) -> Result<Option<BoosterGame>, sqlx::Error> {
+    sqlx::query_as!(
+        BoosterGame,
+        "SELECT home_team_id, away_team_id, game_start_time, game_end_time FROM games WHERE id = $1",
+        game_id
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// Team and username of a user playing in the game
+async fn find_player_team(
+    conn: &mut PgConnection,
+    game: &BoosterGame,
+    user_id: Uuid,
+) -> Result<Option<(Uuid, String)>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT tm.team_id, u.username
+        FROM team_members tm
+        JOIN users u ON u.id = tm.user_id
+        WHERE tm.user_id = $1 AND tm.team_id IN ($2, $3) AND tm.status = 'active'
+        LIMIT 1
+        "#,
+        user_id,
+        game.home_team_id,
+        game.away_team_id
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(|r| (r.team_id, r.username)))
+}
+
+async fn audit(
+    conn: &mut PgConnection,
+    booster: &TeamBooster,
+    action: &str,
+    user_id: Option<Uuid>,
+    mut details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    details["booster_type"] = json!(booster.booster_type);
+    sqlx::query!(
+        r#"
+        INSERT INTO team_booster_audit (booster_id, team_id, game_id, user_id, action, details)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        booster.id,
+        booster.team_id,
+        booster.game_id,
+        user_id,
+        action,
+        details
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn announce(conn: &mut PgConnection, event: &GameEvent) -> Result<Uuid, sqlx::Error> {
+    let payload = serde_json::to_value(event).unwrap_or_default();
+    event_outbox::enqueue(conn, &[BOOSTER_CHANNEL.to_string()], &payload).await
+}
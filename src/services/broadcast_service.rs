@@ -0,0 +1,332 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::broadcast::{AdminBroadcast, AdminBroadcastWithStats, CreateBroadcastRequest};
+use crate::models::game_events::GameEvent;
+use crate::services::notification_delivery::{deliver_to_channels, ChannelNotification};
+
+/// Sends admin announcements to all users, a league or a team through WebSocket,
+/// push and the notification center, and keeps per-channel delivery stats.
+pub struct BroadcastService {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+#[derive(Debug, Default)]
+struct DeliveryStats {
+    recipients: i32,
+    websocket: i32,
+    push: i32,
+    in_app: i32,
+    failed: i32,
+}
+
+impl BroadcastService {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Whether the league or team an announcement targets exists
+    pub async fn target_exists(&self, target_type: &str, target_id: Option<Uuid>) -> Result<bool, sqlx::Error> {
+        let Some(target_id) = target_id else {
+            return Ok(target_type == "all");
+        };
+        sqlx::query_scalar!(
+            r#"
+            SELECT CASE $1::text
+                WHEN 'league' THEN EXISTS(SELECT 1 FROM leagues WHERE id = $2)
+                WHEN 'team' THEN EXISTS(SELECT 1 FROM teams WHERE id = $2)
+                ELSE FALSE
+            END as "exists!"
+            "#,
+            target_type,
+            target_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Store a validated announcement; it goes out at `scheduled_for`, or right away when unset
+    pub async fn create(&self, created_by: Uuid, request: &CreateBroadcastRequest) -> Result<AdminBroadcast, sqlx::Error> {
+        sqlx::query_as!(
+            AdminBroadcast,
+            r#"
+            INSERT INTO admin_broadcasts (created_by, title, message, target_type, target_id, channels, scheduled_for)
+            VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, NOW()))
+            RETURNING *
+            "#,
+            created_by,
+            request.title.trim(),
+            request.message.trim(),
+            request.target_type,
+            request.target_id,
+            &request.channels(),
+            request.scheduled_for
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Send every scheduled announcement that is due. Returns how many went out.
+    pub async fn send_due(&self) -> Result<usize, sqlx::Error> {
+        let due = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM admin_broadcasts
+            WHERE status = 'scheduled' AND scheduled_for <= NOW()
+            ORDER BY scheduled_for ASC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sent = 0;
+        for broadcast_id in due {
+            if self.send(broadcast_id).await?.is_some() {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Deliver a due announcement to its recipients and record the delivery stats.
+    /// Returns None if it is not due, or was already picked up or cancelled.
+    pub async fn send(&self, broadcast_id: Uuid) -> Result<Option<AdminBroadcast>, sqlx::Error> {
+        // Claim the announcement so the scheduler and an admin request never both send it
+        let Some(broadcast) = sqlx::query_as!(
+            AdminBroadcast,
+            r#"
+            UPDATE admin_broadcasts
+            SET status = 'sending', updated_at = NOW()
+            WHERE id = $1 AND status = 'scheduled' AND scheduled_for <= NOW()
+            RETURNING *
+            "#,
+            broadcast_id
+        )
+        .fetch_optional(&self.pool)
+        .await? else {
+            return Ok(None);
+        };
+
+        let stats = match self.deliver(&broadcast).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                tracing::error!("Failed to send broadcast {}: {}", broadcast.id, e);
+                sqlx::query!(
+                    "UPDATE admin_broadcasts SET status = 'failed', updated_at = NOW() WHERE id = $1",
+                    broadcast.id
+                )
+                .execute(&self.pool)
+                .await?;
+                return Err(e);
+            }
+        };
+
+        let broadcast = sqlx::query_as!(
+            AdminBroadcast,
+            r#"
+            UPDATE admin_broadcasts
+            SET status = 'sent', sent_at = NOW(), updated_at = NOW(),
+                recipient_count = $2, websocket_count = $3, push_count = $4, in_app_count = $5, failed_count = $6
+            WHERE id = $1
+            RETURNING *
+            "#,
+            broadcast.id,
+            stats.recipients,
+            stats.websocket,
+            stats.push,
+            stats.in_app,
+            stats.failed
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        tracing::info!(
+            "📣 Broadcast {} sent to {} recipients ({} websocket, {} push, {} in app, {} failed)",
+            broadcast.id, stats.recipients, stats.websocket, stats.push, stats.in_app, stats.failed
+        );
+        Ok(Some(broadcast))
+    }
+
+    /// Cancel an announcement that has not gone out yet.
+    /// Returns None if it does not exist or is no longer scheduled.
+    pub async fn cancel(&self, broadcast_id: Uuid) -> Result<Option<AdminBroadcast>, sqlx::Error> {
+        sqlx::query_as!(
+            AdminBroadcast,
+            r#"
+            UPDATE admin_broadcasts
+            SET status = 'cancelled', updated_at = NOW()
+            WHERE id = $1 AND status = 'scheduled'
+            RETURNING *
+            "#,
+            broadcast_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn get(&self, broadcast_id: Uuid) -> Result<Option<AdminBroadcastWithStats>, sqlx::Error> {
+        let Some(broadcast) = sqlx::query_as!(
+            AdminBroadcast,
+            "SELECT * FROM admin_broadcasts WHERE id = $1",
+            broadcast_id
+        )
+        .fetch_optional(&self.pool)
+        .await? else {
+            return Ok(None);
+        };
+
+        let read_count = self.read_count(broadcast.id).await?;
+        Ok(Some(AdminBroadcastWithStats { broadcast, read_count }))
+    }
+
+    /// Announcements, newest first, optionally only those with the given status
+    pub async fn list(&self, status: Option<&str>, limit: i64) -> Result<Vec<AdminBroadcastWithStats>, sqlx::Error> {
+        let broadcasts = sqlx::query_as!(
+            AdminBroadcast,
+            r#"
+            SELECT * FROM admin_broadcasts
+            WHERE ($1::text IS NULL OR status = $1)
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+            status,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut with_stats = Vec::with_capacity(broadcasts.len());
+        for broadcast in broadcasts {
+            let read_count = self.read_count(broadcast.id).await?;
+            with_stats.push(AdminBroadcastWithStats { broadcast, read_count });
+        }
+        Ok(with_stats)
+    }
+
+    /// Recipients who opened the announcement in their notification center
+    async fn read_count(&self, broadcast_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM notifications
+            WHERE entity_type = 'admin_broadcast' AND entity_id = $1 AND read
+            "#,
+            broadcast_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Active users the announcement is for: everyone, or the active members of the league's or team's teams
+    async fn get_recipients(&self, broadcast: &AdminBroadcast) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT u.id
+            FROM users u
+            WHERE u.status = 'active'
+            AND (
+                $1::text = 'all'
+                OR ($1 = 'league' AND EXISTS (
+                    SELECT 1 FROM team_members tm
+                    JOIN teams t ON t.id = tm.team_id
+                    WHERE tm.user_id = u.id AND tm.status = 'active' AND t.league_id = $2
+                ))
+                OR ($1 = 'team' AND EXISTS (
+                    SELECT 1 FROM team_members tm
+                    WHERE tm.user_id = u.id AND tm.status = 'active' AND tm.team_id = $2
+                ))
+            )
+            ORDER BY u.created_at
+            "#,
+            broadcast.target_type,
+            broadcast.target_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn deliver(&self, broadcast: &AdminBroadcast) -> Result<DeliveryStats, sqlx::Error> {
+        let recipients = self.get_recipients(broadcast).await?;
+        let mut stats = DeliveryStats {
+            recipients: recipients.len() as i32,
+            ..Default::default()
+        };
+
+        let wants = |channel: &str| broadcast.channels.iter().any(|c| c == channel);
+        let notification_channels: Vec<&str> = ["in_app", "push"].into_iter().filter(|c| wants(c)).collect();
+
+        // A Redis outage skips the WebSocket channel instead of failing every recipient
+        let mut redis_conn = match (&self.redis_client, wants("websocket")) {
+            (Some(redis_client), true) => match redis_client.get_async_connection().await {
+                Ok(conn) => Some(conn),
+                Err(e) => {
+                    tracing::warn!("Broadcast {} skips WebSocket delivery, Redis unavailable: {}", broadcast.id, e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        let event = serde_json::to_string(&GameEvent::AdminBroadcast {
+            broadcast_id: broadcast.id,
+            title: broadcast.title.clone(),
+            message: broadcast.message.clone(),
+            target_type: broadcast.target_type.clone(),
+            target_id: broadcast.target_id,
+            timestamp: Utc::now(),
+        })
+        .unwrap_or_default();
+
+        for recipient_id in recipients {
+            let mut failed = false;
+
+            if let Some(conn) = redis_conn.as_mut() {
+                let user_channel = format!("game:events:user:{recipient_id}");
+                match conn.publish::<_, _, i32>(&user_channel, &event).await {
+                    Ok(subscribers) if subscribers > 0 => stats.websocket += 1,
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to publish broadcast {} to user {}: {}", broadcast.id, recipient_id, e);
+                        failed = true;
+                    }
+                }
+            }
+
+            if !notification_channels.is_empty() {
+                let notification = ChannelNotification {
+                    recipient_id,
+                    actor_id: broadcast.created_by,
+                    notification_type: "admin_broadcast".to_string(),
+                    entity_type: "admin_broadcast".to_string(),
+                    entity_id: broadcast.id,
+                    title: broadcast.title.clone(),
+                    message: broadcast.message.clone(),
+                    push_category: "league_update".to_string(),
+                };
+                match deliver_to_channels(&self.pool, &notification, &notification_channels).await {
+                    Ok(delivered) => {
+                        if delivered.iter().any(|c| c == "in_app") {
+                            stats.in_app += 1;
+                        }
+                        if delivered.iter().any(|c| c == "push") {
+                            stats.push += 1;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to deliver broadcast {} to user {}: {}", broadcast.id, recipient_id, e);
+                        failed = true;
+                    }
+                }
+            }
+
+            if failed {
+                stats.failed += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
@@ -0,0 +1,67 @@
+use reqwest::Client;
+use secrecy::ExposeSecret;
+use serde::Serialize;
+
+use crate::config::email::EmailSettings;
+
+#[derive(Debug, Serialize)]
+struct SendEmailRequest<'a> {
+    from: &'a str,
+    to: [&'a str; 1],
+    subject: &'a str,
+    text: &'a str,
+}
+
+/// Sends transactional emails through the configured email API
+#[derive(Debug)]
+pub struct EmailService {
+    settings: EmailSettings,
+    client: Client,
+}
+
+impl EmailService {
+    pub fn new(settings: EmailSettings) -> Self {
+        Self {
+            settings,
+            client: Client::new(),
+        }
+    }
+
+    /// Link of a password reset email
+    pub fn password_reset_link(&self, token: &str) -> String {
+        format!("{}?token={}", self.settings.password_reset_url, token)
+    }
+
+    /// Link of a sign-in email
+    pub fn magic_link(&self, token: &str) -> String {
+        format!("{}?token={}", self.settings.magic_link_url, token)
+    }
+
+    /// Send a plain text email. Without a configured API the email is logged instead.
+    pub async fn send(&self, to: &str, subject: &str, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(api_url) = &self.settings.api_url else {
+            tracing::info!("📧 Email API not configured, not sending \"{}\"", subject);
+            return Ok(());
+        };
+
+        let mut request = self
+            .client
+            .post(api_url)
+            .json(&SendEmailRequest { from: &self.settings.sender, to: [to], subject, text })
+            .timeout(std::time::Duration::from_secs(10));
+        if let Some(api_key) = &self.settings.api_key {
+            request = request.bearer_auth(api_key.expose_secret());
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            tracing::error!("❌ Email API returned error {}: {}", status, error_text);
+            return Err(format!("Email API error: {status} - {error_text}").into());
+        }
+
+        tracing::info!("📧 Sent \"{}\"", subject);
+        Ok(())
+    }
+}
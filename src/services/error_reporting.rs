@@ -0,0 +1,46 @@
+use std::borrow::Cow;
+
+use secrecy::ExposeSecret;
+use sentry::integrations::tracing::SentryLayer;
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::error_reporting::ErrorReportingSettings;
+
+/// Start reporting errors, if a DSN is configured. Reports are sent until the guard is dropped,
+/// so it has to live as long as the process. Panics are reported from here on.
+pub fn init_error_reporting(settings: &ErrorReportingSettings) -> Option<sentry::ClientInitGuard> {
+    let dsn = settings.dsn.as_ref()?.expose_secret().to_string();
+    let release = settings
+        .release
+        .clone()
+        .map(Cow::Owned)
+        .or_else(|| sentry::release_name!());
+    let guard = sentry::init((dsn, sentry::ClientOptions {
+        release,
+        environment: Some(Cow::Owned(settings.environment.clone())),
+        sample_rate: settings.sample_rate,
+        ..Default::default()
+    }));
+    guard.is_enabled().then_some(guard)
+}
+
+/// Layer reporting error logs, with the logs before them as breadcrumbs. Fields prefixed with
+/// `tags.` become tags of the report, e.g. `tags.game_id`. Spans are left to the trace export.
+pub fn error_reporting_layer<S>() -> SentryLayer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    sentry::integrations::tracing::layer().span_filter(|_| false)
+}
+
+/// Attribute reports of the current request to the user it's made by
+pub fn set_reporting_user(user_id: &str, username: &str) {
+    sentry::configure_scope(|scope| {
+        scope.set_user(Some(sentry::User {
+            id: Some(user_id.to_string()),
+            username: Some(username.to_string()),
+            ..Default::default()
+        }));
+    });
+}
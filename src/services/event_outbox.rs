@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use redis::AsyncCommands;
+use sqlx::{PgConnection, PgPool};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::telemetry::{attach_trace_context, continue_trace, trace_context_of};
+
+/// Unpublished events are handed to Redis at most this many times
+const MAX_PUBLISH_ATTEMPTS: i32 = 10;
+
+/// Published events are kept this long for debugging, then pruned
+pub const PUBLISHED_RETENTION_DAYS: i32 = 7;
+
+/// Store an event in the outbox as part of the caller's transaction.
+/// It is published once the transaction committed, never for a rolled back one,
+/// and carries the trace it was enqueued in.
+pub async fn enqueue(
+    conn: &mut PgConnection,
+    channels: &[String],
+    payload: &serde_json::Value,
+) -> Result<Uuid, sqlx::Error> {
+    let mut payload = payload.clone();
+    attach_trace_context(&mut payload);
+    sqlx::query_scalar!(
+        "INSERT INTO event_outbox (channels, payload) VALUES ($1, $2) RETURNING id",
+        channels,
+        payload
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Publishes committed outbox events to their Redis channels
+pub struct EventOutbox {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl EventOutbox {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Publish every event still waiting in the outbox. Returns how many went out.
+    pub async fn publish_pending(&self) -> Result<usize, sqlx::Error> {
+        let pending = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM event_outbox
+            WHERE published_at IS NULL AND attempts < $1
+            ORDER BY created_at ASC
+            LIMIT 500
+            "#,
+            MAX_PUBLISH_ATTEMPTS
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.publish(&pending).await
+    }
+
+    /// Delete events published longer ago than the retention. Returns how many were deleted.
+    pub async fn prune_published(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM event_outbox WHERE published_at < NOW() - make_interval(days => $1)",
+            PUBLISHED_RETENTION_DAYS
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Publish the given events, right after their transaction committed.
+    /// Events that fail stay in the outbox for `publish_pending`. Returns how many went out.
+    pub async fn publish(&self, event_ids: &[Uuid]) -> Result<usize, sqlx::Error> {
+        if event_ids.is_empty() {
+            return Ok(0);
+        }
+        let Some(redis_client) = &self.redis_client else {
+            tracing::warn!("⚠️  Redis not available - {} outbox events stay unpublished", event_ids.len());
+            return Ok(0);
+        };
+
+        // Lock the events so the upload and the scheduler never both publish one
+        let mut tx = self.pool.begin().await?;
+        let events = sqlx::query!(
+            r#"
+            SELECT id, channels, payload FROM event_outbox
+            WHERE id = ANY($1) AND published_at IS NULL
+            ORDER BY created_at ASC
+            FOR UPDATE SKIP LOCKED
+            "#,
+            event_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let mut published = Vec::new();
+        let mut failed = Vec::new();
+        match redis_client.get_multiplexed_async_connection().await {
+            Ok(mut conn) => {
+                for event in events {
+                    // Published in the trace of the request or job that enqueued the event
+                    let span = tracing::info_span!("Publish outbox event", event_id = %event.id);
+                    continue_trace(&span, &trace_context_of(&event.payload));
+                    let result = publish_to_channels(&mut conn, &event.channels, &event.payload.to_string())
+                        .instrument(span)
+                        .await;
+                    match result {
+                        Ok(()) => published.push(event.id),
+                        Err(e) => {
+                            tracing::error!("❌ Failed to publish outbox event {}: {}", event.id, e);
+                            failed.push(event.id);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("❌ Redis connection failed while publishing outbox events: {}", e);
+                failed.extend(events.iter().map(|event| event.id));
+            }
+        }
+
+        sqlx::query!(
+            "UPDATE event_outbox SET published_at = NOW(), attempts = attempts + 1 WHERE id = ANY($1)",
+            &published
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("UPDATE event_outbox SET attempts = attempts + 1 WHERE id = ANY($1)", &failed)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(published.len())
+    }
+}
+
+async fn publish_to_channels(
+    conn: &mut redis::aio::MultiplexedConnection,
+    channels: &[String],
+    message: &str,
+) -> Result<(), redis::RedisError> {
+    for channel in channels {
+        redis_publish_fault()?;
+        conn.publish::<_, _, i32>(channel, message).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Simulated dependency failures, to check the system degrades gracefully when Redis, MinIO or
+//! the ML service misbehave. Faults are only injected in builds with `--features fault-injection`
+//! and configured at runtime through `/admin/fault-injection`; otherwise the hooks do nothing.
+
+#[cfg(feature = "fault-injection")]
+pub use injected::*;
+
+/// Fail a Redis publish if injected failures hit it
+pub fn redis_publish_fault() -> redis::RedisResult<()> {
+    #[cfg(feature = "fault-injection")]
+    if injected::hits(faults().redis_publish_failure_rate) {
+        return Err(redis::RedisError::from((redis::ErrorKind::IoError, "Injected Redis publish failure")));
+    }
+    Ok(())
+}
+
+/// Hold up a MinIO call by the injected delay
+pub async fn minio_fault() {
+    #[cfg(feature = "fault-injection")]
+    {
+        let delay_ms = faults().minio_delay_ms;
+        if delay_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        }
+    }
+}
+
+/// Drop an ML service call if injected failures hit it
+pub fn ml_call_fault() -> Result<(), String> {
+    #[cfg(feature = "fault-injection")]
+    if injected::hits(faults().ml_drop_rate) {
+        return Err("Injected ML service failure".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "fault-injection")]
+mod injected {
+    use std::sync::{OnceLock, RwLock};
+
+    use serde::{Deserialize, Serialize};
+
+    /// Longest delay injected into MinIO calls
+    pub const MAX_MINIO_DELAY_MS: u64 = 60_000;
+
+    /// Dependency failures injected as configured through the admin API
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct FaultInjectionSettings {
+        /// Share of Redis publishes failing, from 0.0 to 1.0
+        #[serde(default)]
+        pub redis_publish_failure_rate: f64,
+        /// Delay added to every MinIO call
+        #[serde(default)]
+        pub minio_delay_ms: u64,
+        /// Share of ML service calls dropped, from 0.0 to 1.0
+        #[serde(default)]
+        pub ml_drop_rate: f64,
+    }
+
+    impl FaultInjectionSettings {
+        pub fn validate(&self) -> Result<(), String> {
+            for (name, rate) in [
+                ("redis_publish_failure_rate", self.redis_publish_failure_rate),
+                ("ml_drop_rate", self.ml_drop_rate),
+            ] {
+                if !(0.0..=1.0).contains(&rate) {
+                    return Err(format!("{name} must be between 0.0 and 1.0. Got: {rate}"));
+                }
+            }
+            if self.minio_delay_ms > MAX_MINIO_DELAY_MS {
+                return Err(format!("minio_delay_ms must be at most {MAX_MINIO_DELAY_MS}"));
+            }
+            Ok(())
+        }
+    }
+
+    fn current() -> &'static RwLock<FaultInjectionSettings> {
+        static FAULTS: OnceLock<RwLock<FaultInjectionSettings>> = OnceLock::new();
+        FAULTS.get_or_init(Default::default)
+    }
+
+    /// Faults injected right now; none after a restart
+    pub fn faults() -> FaultInjectionSettings {
+        current().read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set_faults(settings: FaultInjectionSettings) {
+        *current().write().unwrap_or_else(|e| e.into_inner()) = settings;
+    }
+
+    pub(super) fn hits(rate: f64) -> bool {
+        rate >= 1.0 || (rate > 0.0 && rand::random::<f64>() < rate)
+    }
+}
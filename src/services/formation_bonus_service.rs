@@ -0,0 +1,149 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::db::game_queries::GameQueries;
+use crate::game::formation_bonus::{formation_description, team_formation, FormationWorkout};
+use crate::models::league::GameFormationBonus;
+use crate::models::workout_data::WorkoutType;
+
+/// Awards teams a small bonus at evaluation when their players covered several workout
+/// archetypes (cardio, strength, HIIT) in the game
+pub struct FormationBonusService {
+    pool: PgPool,
+}
+
+impl FormationBonusService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Award the formation bonuses of finished games; must run before their final scores are read
+    pub async fn settle_games(&self, game_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        for game_id in game_ids {
+            self.settle_game(*game_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn settle_game(&self, game_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let Some(game) = sqlx::query!(
+            "SELECT home_team_id, away_team_id FROM games WHERE id = $1 AND status = 'finished' FOR UPDATE",
+            game_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let mut scores_changed = false;
+        for (team_id, team_side) in [(game.home_team_id, "home"), (game.away_team_id, "away")] {
+            scores_changed |= award_team_bonus(&mut tx, game_id, team_id, team_side).await?;
+        }
+
+        if scores_changed {
+            let (home_score, away_score) = GameQueries::calculate_team_scores_best_4(&mut tx, game_id).await?;
+            sqlx::query!(
+                "UPDATE games SET home_score = $2, away_score = $3, updated_at = NOW() WHERE id = $1",
+                game_id,
+                home_score,
+                away_score
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Formation bonuses awarded in a game, for its summary
+    pub async fn game_bonuses(&self, game_id: Uuid) -> Result<Vec<GameFormationBonus>, sqlx::Error> {
+        sqlx::query_as!(
+            GameFormationBonus,
+            r#"
+            SELECT team_id, archetypes, bonus_points, description
+            FROM game_formation_bonuses
+            WHERE game_id = $1
+            ORDER BY created_at
+            "#,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
+
+/// Award the team its formation bonus, unless it has none or already got it. True if awarded.
+async fn award_team_bonus(conn: &mut PgConnection, game_id: Uuid, team_id: Uuid, team_side: &str) -> Result<bool, sqlx::Error> {
+    let workouts = sqlx::query!(
+        r#"
+        SELECT lse.user_id, LOWER(wd.ml_prediction) as prediction, lse.score_points
+        FROM live_score_events lse
+        JOIN workout_data wd ON wd.id = lse.workout_data_id
+        WHERE lse.game_id = $1 AND lse.team_id = $2 AND lse.event_type = 'workout_upload'
+        "#,
+        game_id,
+        team_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let team_points: f32 = workouts.iter().map(|w| w.score_points).sum();
+    let classified: Vec<FormationWorkout> = workouts
+        .iter()
+        .filter_map(|w| {
+            w.prediction.as_deref().map(|prediction| FormationWorkout {
+                user_id: w.user_id,
+                workout_type: WorkoutType::parse(prediction),
+                points: w.score_points,
+            })
+        })
+        .collect();
+    let Some(formation) = team_formation(&classified, team_points) else {
+        return Ok(false);
+    };
+
+    let description = formation_description(&formation);
+    let archetypes: Vec<String> = formation.archetypes.iter().map(|a| a.as_str().to_string()).collect();
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO game_formation_bonuses (game_id, team_id, archetypes, bonus_points, description)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (game_id, team_id) DO NOTHING
+        "#,
+        game_id,
+        team_id,
+        &archetypes,
+        formation.bonus_points,
+        description
+    )
+    .execute(&mut *conn)
+    .await?;
+    if inserted.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    let (user_ids, shares): (Vec<Uuid>, Vec<f32>) = formation.shares.into_iter().unzip();
+    sqlx::query!(
+        r#"
+        INSERT INTO live_score_events (
+            game_id, user_id, username, team_id, team_side,
+            score_points, power_contribution, event_type, description
+        )
+        SELECT $1, u.id, u.username, $2, $3, share.points, 0, 'team_bonus', $6
+        FROM UNNEST($4::uuid[], $5::real[]) AS share(user_id, points)
+        JOIN users u ON u.id = share.user_id
+        "#,
+        game_id,
+        team_id,
+        team_side,
+        &user_ids,
+        &shares,
+        description
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    tracing::info!("🧩 Team {} earned a formation bonus in game {}: {}", team_id, game_id, description);
+    Ok(true)
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use tokio::sync::RwLock;
+
+use crate::league::league::LeagueService;
+use crate::models::league::GameTransition;
+
+/// How long a loaded game clock is reused, so rescheduled games show up soon enough
+const GAME_CLOCK_MAX_AGE_SECONDS: i64 = 30;
+
+/// The active season's next game transition, shared by the heartbeats of all WebSocket
+/// connections. It is loaded again once it is older than `GAME_CLOCK_MAX_AGE_SECONDS`
+/// or the cached transition came due.
+#[derive(Debug, Default)]
+pub struct GameClockCache {
+    loaded: RwLock<Option<(DateTime<Utc>, Option<GameTransition>)>>,
+}
+
+impl GameClockCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn next_transition(&self, pool: &PgPool) -> Result<Option<GameTransition>, sqlx::Error> {
+        let now = Utc::now();
+        if let Some((loaded_at, next_transition)) = self.loaded.read().await.as_ref() {
+            let came_due = next_transition.as_ref().is_some_and(|t| t.at > *loaded_at && t.at <= now);
+            if now - *loaded_at < Duration::seconds(GAME_CLOCK_MAX_AGE_SECONDS) && !came_due {
+                return Ok(next_transition.clone());
+            }
+        }
+
+        let clock = LeagueService::new(pool.clone()).get_game_clock(None).await?;
+        *self.loaded.write().await = Some((clock.server_time, clock.next_transition.clone()));
+        Ok(clock.next_transition)
+    }
+}
@@ -0,0 +1,376 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use serde_json::{json, Map, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::game::commentary::{self, FINAL_MINUTES_WINDOW};
+use crate::models::commentary::{CommentaryMilestone, CommentarySettings, GameCommentary};
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::telemetry::traced_message;
+use crate::models::game_events::GameEvent;
+
+/// Automated commentator that posts templated lines into the game event stream
+/// at milestones (first score, lead change, final minutes, new MVP candidate),
+/// in the language configured for the game's league.
+pub struct GameCommentaryService {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+#[derive(Debug, Clone)]
+struct CommentaryGame {
+    id: Uuid,
+    home_team_id: Uuid,
+    away_team_id: Uuid,
+    home_team_name: String,
+    away_team_name: String,
+    settings: CommentarySettings,
+}
+
+impl CommentaryGame {
+    fn team_name(&self, team_side: &str) -> &str {
+        if team_side == "home" { &self.home_team_name } else { &self.away_team_name }
+    }
+
+    fn team_id(&self, team_side: &str) -> Uuid {
+        if team_side == "home" { self.home_team_id } else { self.away_team_id }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PlayerTotal {
+    user_id: Uuid,
+    username: String,
+    team_side: String,
+    total: f64,
+    previous_total: Option<f64>,
+}
+
+struct Commentary {
+    milestone: CommentaryMilestone,
+    params: Map<String, Value>,
+    user_id: Option<Uuid>,
+    team_id: Option<Uuid>,
+}
+
+/// Side strictly ahead, if any
+fn leader(home_score: i32, away_score: i32) -> Option<&'static str> {
+    match home_score.cmp(&away_score) {
+        std::cmp::Ordering::Greater => Some("home"),
+        std::cmp::Ordering::Less => Some("away"),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+fn params(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+impl GameCommentaryService {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Comment on a freshly recorded score event: the first score of the game,
+    /// a lead change, or a player taking over the top of the scoring.
+    #[tracing::instrument(name = "Comment on score", skip(self))]
+    pub async fn comment_on_score(&self, game_id: Uuid, score_event_id: Uuid) -> Result<Vec<GameCommentary>, sqlx::Error> {
+        let Some(game) = self.get_game(game_id).await? else {
+            return Ok(Vec::new());
+        };
+        if !game.settings.enabled {
+            return Ok(Vec::new());
+        }
+
+        let Some(event) = sqlx::query!(
+            r#"
+            SELECT user_id, username, team_side, score_points
+            FROM live_score_events
+            WHERE id = $1 AND game_id = $2
+            "#,
+            score_event_id,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await? else {
+            return Ok(Vec::new());
+        };
+
+        let players = self.get_player_totals(game_id, score_event_id).await?;
+        let team_score = |side: &str, previous: bool| -> i32 {
+            players
+                .iter()
+                .filter(|p| p.team_side == side)
+                .map(|p| if previous { p.previous_total.unwrap_or(0.0) } else { p.total })
+                .sum::<f64>() as i32
+        };
+        let (home_before, away_before) = (team_score("home", true), team_score("away", true));
+        let (home_after, away_after) = (team_score("home", false), team_score("away", false));
+
+        let mut lines = Vec::new();
+        let first_score = players.iter().all(|p| p.previous_total.is_none());
+
+        if first_score {
+            lines.push(Commentary {
+                milestone: CommentaryMilestone::FirstScore,
+                params: params(json!({
+                    "player": event.username,
+                    "team": game.team_name(&event.team_side),
+                    "points": event.score_points.round() as i64,
+                })),
+                user_id: Some(event.user_id),
+                team_id: Some(game.team_id(&event.team_side)),
+            });
+        } else {
+            let (before, after) = (leader(home_before, away_before), leader(home_after, away_after));
+            if let (Some(before), Some(after)) = (before, after) {
+                if before != after {
+                    lines.push(Commentary {
+                        milestone: CommentaryMilestone::LeadChange,
+                        params: params(json!({
+                            "team": game.team_name(after),
+                            "leading_score": home_after.max(away_after),
+                            "trailing_score": home_after.min(away_after),
+                            "home_score": home_after,
+                            "away_score": away_after,
+                        })),
+                        user_id: Some(event.user_id),
+                        team_id: Some(game.team_id(after)),
+                    });
+                }
+            }
+
+            // Only the scorer moved, so they are the new MVP candidate if they overtook the top scorer
+            let top_before = players
+                .iter()
+                .filter_map(|p| p.previous_total.map(|total| (p, total)))
+                .fold(None::<(&PlayerTotal, f64)>, |top, (p, total)| match top {
+                    Some((_, best)) if best >= total => top,
+                    _ => Some((p, total)),
+                });
+            let scorer = players.iter().find(|p| p.user_id == event.user_id && p.team_side == event.team_side);
+            if let (Some((top, best)), Some(scorer)) = (top_before, scorer) {
+                if top.user_id != scorer.user_id && scorer.total > best {
+                    lines.push(Commentary {
+                        milestone: CommentaryMilestone::MvpCandidate,
+                        params: params(json!({
+                            "player": scorer.username,
+                            "team": game.team_name(&scorer.team_side),
+                            "points": scorer.total.round() as i64,
+                        })),
+                        user_id: Some(scorer.user_id),
+                        team_id: Some(game.team_id(&scorer.team_side)),
+                    });
+                }
+            }
+        }
+
+        let mut posted = Vec::new();
+        for line in lines {
+            if let Some(commentary) = self.post(&game, line).await? {
+                posted.push(commentary);
+            }
+        }
+        Ok(posted)
+    }
+
+    /// Call the final minutes of every running game that enters its last stretch.
+    /// Meant to run every minute; each game is only called once.
+    pub async fn comment_on_final_minutes(&self) -> Result<usize, sqlx::Error> {
+        let games = sqlx::query!(
+            r#"
+            SELECT g.id, g.home_score, g.away_score, g.game_end_time as "game_end_time!"
+            FROM games g
+            JOIN league_seasons ls ON ls.id = g.season_id
+            JOIN leagues l ON l.id = ls.league_id
+            WHERE g.status = 'in_progress'
+            AND g.game_end_time > NOW()
+            AND g.game_end_time <= NOW() + make_interval(mins => $1)
+            AND l.commentary_enabled
+            AND 'final_minutes' = ANY(l.commentary_milestones)
+            AND NOT EXISTS (
+                SELECT 1 FROM game_commentary gc
+                WHERE gc.game_id = g.id AND gc.milestone = 'final_minutes'
+            )
+            "#,
+            FINAL_MINUTES_WINDOW as i32
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut posted = 0;
+        for row in games {
+            let Some(game) = self.get_game(row.id).await? else {
+                continue;
+            };
+            // Short games can start inside the window, so say how long is actually left
+            let minutes = ((row.game_end_time - Utc::now()).num_seconds() + 59) / 60;
+            let line = Commentary {
+                milestone: CommentaryMilestone::FinalMinutes,
+                params: params(json!({
+                    "minutes": minutes.clamp(1, FINAL_MINUTES_WINDOW),
+                    "home_team": game.home_team_name,
+                    "away_team": game.away_team_name,
+                    "home_score": row.home_score,
+                    "away_score": row.away_score,
+                })),
+                user_id: None,
+                team_id: None,
+            };
+            if self.post(&game, line).await?.is_some() {
+                posted += 1;
+            }
+        }
+        Ok(posted)
+    }
+
+    /// Commentary of a game, oldest first
+    pub async fn get_game_commentary(&self, game_id: Uuid) -> Result<Vec<GameCommentary>, sqlx::Error> {
+        sqlx::query_as!(
+            GameCommentary,
+            r#"
+            SELECT id, game_id, milestone, locale, message, params, user_id, team_id, created_at
+            FROM game_commentary
+            WHERE game_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn get_game(&self, game_id: Uuid) -> Result<Option<CommentaryGame>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                g.id, g.home_team_id, g.away_team_id,
+                ht.team_name as home_team_name,
+                at.team_name as away_team_name,
+                l.commentary_enabled,
+                l.commentary_locale,
+                l.commentary_milestones
+            FROM games g
+            JOIN teams ht ON g.home_team_id = ht.id
+            JOIN teams at ON g.away_team_id = at.id
+            JOIN league_seasons ls ON ls.id = g.season_id
+            JOIN leagues l ON l.id = ls.league_id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| CommentaryGame {
+            id: row.id,
+            home_team_id: row.home_team_id,
+            away_team_id: row.away_team_id,
+            home_team_name: row.home_team_name,
+            away_team_name: row.away_team_name,
+            settings: CommentarySettings {
+                enabled: row.commentary_enabled,
+                locale: row.commentary_locale,
+                milestones: row.commentary_milestones,
+            },
+        }))
+    }
+
+    /// Per-player totals with and without the given score event
+    async fn get_player_totals(&self, game_id: Uuid, score_event_id: Uuid) -> Result<Vec<PlayerTotal>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                user_id,
+                MAX(username) as "username!",
+                team_side,
+                SUM(score_points)::float8 as "total!",
+                (SUM(score_points) FILTER (WHERE id <> $2))::float8 as previous_total
+            FROM live_score_events
+            WHERE game_id = $1
+            GROUP BY user_id, team_side
+            ORDER BY MIN(occurred_at) ASC
+            "#,
+            game_id,
+            score_event_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PlayerTotal {
+                user_id: row.user_id,
+                username: row.username,
+                team_side: row.team_side,
+                total: row.total,
+                previous_total: row.previous_total,
+            })
+            .collect())
+    }
+
+    /// Store and broadcast a line if the league wants this milestone called.
+    /// Returns None when it is disabled or was already called for the game.
+    async fn post(&self, game: &CommentaryGame, line: Commentary) -> Result<Option<GameCommentary>, sqlx::Error> {
+        if !game.settings.covers(line.milestone) {
+            return Ok(None);
+        }
+
+        let locale = &game.settings.locale;
+        let message = commentary::render(line.milestone, locale, &line.params);
+        let commentary = sqlx::query_as!(
+            GameCommentary,
+            r#"
+            INSERT INTO game_commentary (game_id, milestone, locale, message, params, user_id, team_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING
+            RETURNING id, game_id, milestone, locale, message, params, user_id, team_id, created_at
+            "#,
+            game.id,
+            line.milestone.as_str(),
+            locale,
+            message,
+            Value::Object(line.params),
+            line.user_id,
+            line.team_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(commentary) = &commentary {
+            tracing::info!("🎙️ Commentary for game {} ({}): {}", game.id, commentary.milestone, commentary.message);
+            if let Err(e) = self.broadcast(commentary).await {
+                tracing::error!(tags.game_id = %game.id, "Failed to broadcast commentary for game {}: {}", game.id, e);
+            }
+        }
+        Ok(commentary)
+    }
+
+    async fn broadcast(&self, commentary: &GameCommentary) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(redis_client) = &self.redis_client else {
+            return Ok(());
+        };
+
+        let event = GameEvent::GameCommentary {
+            commentary_id: commentary.id,
+            game_id: commentary.game_id,
+            milestone: commentary.milestone.clone(),
+            locale: commentary.locale.clone(),
+            message: commentary.message.clone(),
+            params: commentary.params.clone(),
+            timestamp: commentary.created_at,
+        };
+
+        redis_publish_fault()?;
+        let mut conn = redis_client.get_async_connection().await?;
+        let message = traced_message(&event)?;
+        let _: i32 = conn.publish("game:events:global", message).await?;
+        Ok(())
+    }
+}
@@ -1,16 +1,25 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use redis::AsyncCommands;
 
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::telemetry::traced_message;
 use crate::models::game_events::{GameEvent, GameResult, NotificationType};
 use crate::models::common::MatchResult;
+use crate::league::cups::CupService;
 use crate::league::standings::StandingsService;
 use crate::models::league::{LeagueGame, GameStatus};
 use crate::game::game_evaluator::GameStats;
 use crate::services::game_summary_service::GameSummaryService;
+use crate::services::season_recap_service::SeasonRecapService;
+use crate::services::season_stats_service::SeasonStatsService;
+use crate::services::booster_service::BoosterService;
+use crate::services::formation_bonus_service::FormationBonusService;
+use crate::services::league_webhook_service::LeagueWebhookService;
+use crate::services::review_lock_service::ReviewLockService;
 
 #[derive(Debug)]
 pub struct GameEvaluationService {
@@ -104,7 +113,7 @@ winner_team_id,
                     game_id, game_stats.home_team_score, game_stats.away_team_score);
             }
             Err(e) => {
-                tracing::error!("❌ Failed to update standings for game {}: {}", game_id, e);
+                tracing::error!(tags.game_id = %game_id, "❌ Failed to update standings for game {}: {}", game_id, e);
                 return Err(e);
             }
         }
@@ -136,7 +145,7 @@ winner_team_id,
                 }
             }
             Err(e) => {
-                tracing::error!("❌ Failed to create game summary for game {}: {}", game_id, e);
+                tracing::error!(tags.game_id = %game_id, "❌ Failed to create game summary for game {}: {}", game_id, e);
                 // Don't fail the entire evaluation if summary creation fails
                 // The game is still evaluated and standings are updated
             }
@@ -146,6 +155,7 @@ winner_team_id,
     }
 
     /// Evaluate and update finished live games
+    #[tracing::instrument(name = "Evaluate finished games", skip_all, fields(games = game_ids.len()))]
     pub async fn evaluate_finished_live_games(&self, game_ids: &Vec<Uuid>) -> Result<Vec<GameStats>, sqlx::Error> {
         if game_ids.is_empty() {
             tracing::info!("🎯 [EVALUATOR] No games to evaluate");
@@ -153,11 +163,41 @@ winner_team_id,
         }
         tracing::info!("🎯 [EVALUATOR] Starting evaluation of {} finished live games: {:?}", game_ids.len(), game_ids);
 
+        // Games with reported workouts wait for the review, in leagues with the review lock
+        let review_lock = ReviewLockService::new(self.pool.clone());
+        let blocked = review_lock.blocked_games(game_ids).await?;
+        let unblocked: Vec<Uuid> = game_ids
+            .iter()
+            .filter(|game_id| !blocked.iter().any(|game| game.game_id == **game_id))
+            .copied()
+            .collect();
+        let game_ids = &unblocked;
+        if !blocked.is_empty() {
+            for game in &blocked {
+                tracing::info!("🔒 [EVALUATOR] Holding game {} for {} pending workout reports until {}",
+                    game.game_id, game.flags.len(), game.proceeds_at);
+            }
+            let blocked_ids: Vec<Uuid> = blocked.iter().map(|game| game.game_id).collect();
+            review_lock.hold(&blocked_ids).await?;
+            if game_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+        }
+
+        // Shields and formation bonuses can still add points, so they're settled before the final scores are read
+        let booster_service = BoosterService::new(self.pool.clone(), Some(self.redis_client.clone()));
+        if let Err(e) = booster_service.settle_games(game_ids).await {
+            tracing::error!("❌ [EVALUATOR] Failed to settle boosters: {}", e);
+        }
+        if let Err(e) = FormationBonusService::new(self.pool.clone()).settle_games(game_ids).await {
+            tracing::error!("❌ [EVALUATOR] Failed to award formation bonuses: {}", e);
+        }
+
         // Get the game details
         tracing::info!("🔍 [EVALUATOR] Fetching game data from database for {} games", game_ids.len());
         let games = sqlx::query!(
             r#"
-            SELECT id, home_team_id, away_team_id, home_score, away_score
+            SELECT id, season_id, home_team_id, away_team_id, home_score, away_score, cup_id
             FROM games
             WHERE id = ANY($1) and status = 'finished'
             "#,
@@ -173,6 +213,9 @@ winner_team_id,
         }
 
         let mut results = Vec::new();
+        let mut evaluated_seasons = HashSet::new();
+        let mut evaluated_cups = HashSet::new();
+        let season_stats = SeasonStatsService::new(self.pool.clone());
 
         for game_data in games {
             let game_id = game_data.id;
@@ -223,16 +266,58 @@ winner_team_id,
                 Ok(_) => {
                     tracing::info!("✅ [EVALUATOR] Game {} evaluated and updated: {} - {}",
                         game_id, game_stats.home_team_score, game_stats.away_team_score);
+                    if let Err(e) = booster_service.award_for_game(game_id, game_stats.winner_team_id).await {
+                        tracing::error!(tags.game_id = %game_id, "❌ [EVALUATOR] Failed to award boosters for game {}: {}", game_id, e);
+                    }
+                    match game_data.cup_id {
+                        Some(cup_id) => {
+                            evaluated_cups.insert(cup_id);
+                        }
+                        None => {
+                            if let Err(e) = season_stats.record_game(game_id).await {
+                                tracing::error!(tags.game_id = %game_id, "❌ [EVALUATOR] Failed to add game {} to season stats: {}", game_id, e);
+                            }
+                        }
+                    }
                     results.push(game_stats);
+                    evaluated_seasons.insert(game_data.season_id);
                 }
                 Err(e) => {
-                    tracing::error!("❌ [EVALUATOR] Failed to update game {}: {}", game_id, e);
+                    tracing::error!(tags.game_id = %game_id, "❌ [EVALUATOR] Failed to update game {}: {}", game_id, e);
                 }
             }
         }
 
         tracing::info!("✅ [EVALUATOR] Completed evaluation of {} games", results.len());
 
+        // Draw the next cup rounds before the recaps check whether their seasons are complete
+        let cup_service = CupService::new(self.pool.clone());
+        for cup_id in evaluated_cups {
+            if let Err(e) = cup_service.advance(cup_id).await {
+                tracing::error!("❌ [EVALUATOR] Failed to advance cup {}: {}", cup_id, e);
+            }
+        }
+
+        // Write the season recap once the last game of a season is in
+        let recap_service = SeasonRecapService::new(self.pool.clone());
+        for season_id in evaluated_seasons {
+            match recap_service.generate_if_complete(season_id).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("📖 [EVALUATOR] Season {} is complete, wrote {} recaps", season_id, count),
+                Err(e) => tracing::error!("❌ [EVALUATOR] Failed to generate recap for season {}: {}", season_id, e),
+            }
+        }
+
+        let evaluated_game_ids: Vec<Uuid> = results.iter().map(|stats| stats.game_id).collect();
+        if let Err(e) = review_lock.release(&evaluated_game_ids).await {
+            tracing::error!("❌ [EVALUATOR] Failed to release review holds: {}", e);
+        }
+
+        // Cross-post the results to the leagues' Slack and Teams channels
+        if let Err(e) = LeagueWebhookService::new(self.pool.clone()).post_results(&evaluated_game_ids).await {
+            tracing::error!("❌ [EVALUATOR] Failed to post results to league webhooks: {}", e);
+        }
+
         // Send WebSocket notifications if we have results
         if !results.is_empty() {
             tracing::info!("📡 [EVALUATOR] Broadcasting results for {} evaluated games", results.len());
@@ -310,8 +395,9 @@ winner_team_id,
 
     /// Broadcast event to global game events channel using existing Redis pattern
     async fn broadcast_to_global_channel(&self, event: &GameEvent) -> Result<(), Box<dyn std::error::Error>> {
+            redis_publish_fault()?;
             let mut conn = self.redis_client.get_async_connection().await?;
-            let message = serde_json::to_string(event)?;
+            let message = traced_message(event)?;
             
             let global_channel = "game:events:global";
             let result: Result<i32, redis::RedisError> = conn.publish(global_channel, message).await;
@@ -373,8 +459,9 @@ winner_team_id,
 
     /// Send notification to a specific user using existing Redis pattern
     async fn send_user_notification(&self, user_id: &Uuid, notification: &GameEvent) -> Result<(), Box<dyn std::error::Error>> {
+            redis_publish_fault()?;
             let mut conn = self.redis_client.get_async_connection().await?;
-            let message = serde_json::to_string(notification)?;
+            let message = traced_message(notification)?;
             let user_channel = format!("game:events:user:{user_id}");
             
             let result: Result<i32, redis::RedisError> = conn.publish(&user_channel, message).await;
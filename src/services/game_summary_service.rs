@@ -25,6 +25,7 @@ impl GameSummaryService {
     }
 
     /// Calculate and create a game summary for a finished game
+    #[tracing::instrument(name = "Create game summary", skip_all, fields(game_id = %game.id))]
     pub async fn create_game_summary(&self, game: &LeagueGame) -> Result<GameSummary, sqlx::Error> {
         tracing::info!("📊 Creating game summary for game {}", game.id);
 
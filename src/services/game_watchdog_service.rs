@@ -0,0 +1,290 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::game_watchdog::GameWatchdogSettings;
+use crate::db::game_queries::{GameQueries, WORKOUT_UPLOAD_GRACE_MINUTES};
+use crate::services::game_evaluation_service::GameEvaluationService;
+use crate::services::notification_delivery::{deliver_to_enabled_channels, ChannelNotification};
+
+/// Dead man's switch for the game cycle: finds games that are past their end time but were
+/// never evaluated, alerts admins once per game and, if enabled, finalizes them itself.
+pub struct GameWatchdogService {
+    pool: PgPool,
+    redis_client: Arc<redis::Client>,
+    settings: GameWatchdogSettings,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OverdueGame {
+    pub game_id: Uuid,
+    pub season_id: Uuid,
+    pub status: String,
+    pub home_team_name: String,
+    pub away_team_name: String,
+    pub game_end_time: DateTime<Utc>,
+    pub overdue_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GameWatchdogRunSummary {
+    pub overdue_games: usize,
+    pub new_alerts: usize,
+    pub admins_notified: usize,
+    pub auto_finalized: usize,
+    pub resolved: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GameWatchdogAlert {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub season_id: Uuid,
+    pub game_status: String,
+    pub game_end_time: DateTime<Utc>,
+    pub first_detected_at: DateTime<Utc>,
+    pub last_detected_at: DateTime<Utc>,
+    pub admins_notified: i32,
+    pub auto_finalized_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Watchdog state for admins: the current number of unfinished games and alert totals
+#[derive(Debug, Serialize, Clone)]
+pub struct GameWatchdogStatus {
+    pub overdue_minutes: i64,
+    pub auto_finalize: bool,
+    pub unfinished_games: usize,
+    pub open_alerts: i64,
+    pub total_alerts: i64,
+    pub auto_finalized_total: i64,
+    pub overdue: Vec<OverdueGame>,
+    pub recent_alerts: Vec<GameWatchdogAlert>,
+}
+
+impl GameWatchdogService {
+    pub fn new(pool: PgPool, redis_client: Arc<redis::Client>, settings: GameWatchdogSettings) -> Self {
+        Self { pool, redis_client, settings }
+    }
+
+    /// Games still in progress or finished but not evaluated, more than
+    /// `overdue_minutes` after their end time. Finished games wait for the workout
+    /// upload grace period before they're evaluated, so they get that much longer.
+    pub async fn find_overdue_games(&self) -> Result<Vec<OverdueGame>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                g.id,
+                g.season_id,
+                g.status,
+                ht.team_name as home_team_name,
+                at.team_name as away_team_name,
+                g.game_end_time as "game_end_time!",
+                (EXTRACT(EPOCH FROM (NOW() - g.game_end_time)) / 60)::bigint as "overdue_minutes!"
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE g.status IN ('in_progress', 'finished')
+            AND g.game_end_time IS NOT NULL
+            AND g.game_end_time < NOW() - make_interval(mins => $1::int
+                + CASE WHEN g.status = 'finished' THEN $2::int ELSE 0 END)
+            ORDER BY g.game_end_time ASC
+            "#,
+            self.settings.overdue_minutes as i32,
+            WORKOUT_UPLOAD_GRACE_MINUTES
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| OverdueGame {
+            game_id: r.id,
+            season_id: r.season_id,
+            status: r.status,
+            home_team_name: r.home_team_name,
+            away_team_name: r.away_team_name,
+            game_end_time: r.game_end_time,
+            overdue_minutes: r.overdue_minutes,
+        }).collect())
+    }
+
+    /// Raise alerts for newly overdue games, resolve alerts of games that got evaluated
+    /// and, if enabled, finish and evaluate the overdue games
+    pub async fn run_check(&self) -> Result<GameWatchdogRunSummary, sqlx::Error> {
+        let overdue = self.find_overdue_games().await?;
+        let overdue_ids: Vec<Uuid> = overdue.iter().map(|game| game.game_id).collect();
+        let mut summary = GameWatchdogRunSummary {
+            overdue_games: overdue.len(),
+            ..Default::default()
+        };
+
+        summary.resolved = sqlx::query!(
+            r#"
+            UPDATE game_watchdog_alerts
+            SET resolved_at = NOW()
+            WHERE resolved_at IS NULL AND NOT (game_id = ANY($1))
+            "#,
+            &overdue_ids
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected() as usize;
+
+        for game in &overdue {
+            let is_new = sqlx::query_scalar!(
+                r#"
+                INSERT INTO game_watchdog_alerts (game_id, season_id, game_status, game_end_time)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (game_id) DO UPDATE
+                SET game_status = EXCLUDED.game_status,
+                    last_detected_at = NOW(),
+                    resolved_at = NULL
+                RETURNING (xmax = 0) as "inserted!"
+                "#,
+                game.game_id,
+                game.season_id,
+                game.status,
+                game.game_end_time
+            )
+            .fetch_one(&self.pool)
+            .await?;
+
+            if is_new {
+                summary.new_alerts += 1;
+                let notified = self.notify_admins(game).await?;
+                summary.admins_notified += notified;
+                sqlx::query!(
+                    "UPDATE game_watchdog_alerts SET admins_notified = $2 WHERE game_id = $1",
+                    game.game_id,
+                    notified as i32
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        if !overdue.is_empty() {
+            tracing::warn!(
+                unfinished_games = overdue.len(),
+                "⚠️ [WATCHDOG] {} games are more than {} minutes past their end time without being evaluated",
+                overdue.len(), self.settings.overdue_minutes
+            );
+        }
+
+        if self.settings.auto_finalize && !overdue.is_empty() {
+            summary.auto_finalized = self.finalize(&overdue).await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Current overdue games with alert totals and the most recent alerts
+    pub async fn status(&self, limit: i64) -> Result<GameWatchdogStatus, sqlx::Error> {
+        let overdue = self.find_overdue_games().await?;
+
+        let totals = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE resolved_at IS NULL) as "open_alerts!",
+                COUNT(*) as "total_alerts!",
+                COUNT(auto_finalized_at) as "auto_finalized_total!"
+            FROM game_watchdog_alerts
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let recent_alerts = sqlx::query_as!(
+            GameWatchdogAlert,
+            r#"
+            SELECT id, game_id, season_id, game_status, game_end_time, first_detected_at,
+                   last_detected_at, admins_notified, auto_finalized_at, resolved_at
+            FROM game_watchdog_alerts
+            ORDER BY first_detected_at DESC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(GameWatchdogStatus {
+            overdue_minutes: self.settings.overdue_minutes,
+            auto_finalize: self.settings.auto_finalize,
+            unfinished_games: overdue.len(),
+            open_alerts: totals.open_alerts,
+            total_alerts: totals.total_alerts,
+            auto_finalized_total: totals.auto_finalized_total,
+            overdue,
+            recent_alerts,
+        })
+    }
+
+    async fn notify_admins(&self, game: &OverdueGame) -> Result<usize, sqlx::Error> {
+        let admin_ids = sqlx::query_scalar!(
+            "SELECT id FROM users WHERE role IN ('admin', 'superadmin')"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let message = format!(
+            "{} vs {} ended {} minutes ago but is still {}",
+            game.home_team_name, game.away_team_name, game.overdue_minutes, game.status.replace('_', " ")
+        );
+
+        let mut notified = 0;
+        for admin_id in admin_ids {
+            let notification = ChannelNotification {
+                recipient_id: admin_id,
+                actor_id: admin_id,
+                notification_type: "unfinished_game".to_string(),
+                entity_type: "game".to_string(),
+                entity_id: game.game_id,
+                title: "Game not finalized".to_string(),
+                message: message.clone(),
+                push_category: "league_update".to_string(),
+            };
+            match deliver_to_enabled_channels(&self.pool, &notification).await {
+                Ok(channels) if !channels.is_empty() => notified += 1,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("❌ [WATCHDOG] Failed to alert admin {} about game {}: {}", admin_id, game.game_id, e);
+                }
+            }
+        }
+
+        Ok(notified)
+    }
+
+    /// Finish the overdue games still in progress and evaluate all of them.
+    /// Returns how many games were evaluated.
+    async fn finalize(&self, overdue: &[OverdueGame]) -> Result<usize, sqlx::Error> {
+        let game_queries = GameQueries::new(self.pool.clone());
+        for game in overdue.iter().filter(|game| game.status == "in_progress") {
+            tracing::info!("🏁 [WATCHDOG] Finishing overdue game {}", game.game_id);
+            game_queries.finish_game(game.game_id).await?;
+        }
+
+        let game_ids: Vec<Uuid> = overdue.iter().map(|game| game.game_id).collect();
+        let evaluated = GameEvaluationService::new(self.pool.clone(), self.redis_client.clone())
+            .evaluate_finished_live_games(&game_ids)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE game_watchdog_alerts
+            SET auto_finalized_at = NOW(), resolved_at = NOW()
+            WHERE game_id = ANY($1)
+            AND EXISTS (SELECT 1 FROM games g WHERE g.id = game_watchdog_alerts.game_id AND g.status = 'evaluated')
+            "#,
+            &game_ids
+        )
+        .execute(&self.pool)
+        .await?;
+
+        tracing::info!("✅ [WATCHDOG] Auto-finalized {} overdue games", evaluated.len());
+        Ok(evaluated.len())
+    }
+}
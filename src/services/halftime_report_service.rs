@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use redis::AsyncCommands;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::game::halftime::{halftime_message, team_pace};
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::telemetry::traced_message;
+use crate::models::game_events::GameEvent;
+use crate::models::halftime::{HalftimeContributor, HalftimeReport, TeamPace};
+use crate::services::notification_delivery::{deliver_to_channels, ChannelNotification};
+
+/// Players listed in a halftime report
+const TOP_CONTRIBUTORS: i64 = 3;
+
+/// Writes a halftime report at the midpoint of each live game and sends it to the game stream
+/// and the notification center of both teams
+pub struct HalftimeReportService {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl HalftimeReportService {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Report on every running game past its midpoint that has no report yet. Meant to run
+    /// every minute; returns how many reports were written.
+    pub async fn report_halftimes(&self) -> Result<usize, sqlx::Error> {
+        let game_ids = sqlx::query_scalar!(
+            r#"
+            SELECT g.id
+            FROM games g
+            WHERE g.status = 'in_progress'
+            AND g.game_start_time + (g.game_end_time - g.game_start_time) / 2 <= NOW()
+            AND g.game_end_time > NOW()
+            AND NOT EXISTS (SELECT 1 FROM game_halftime_reports hr WHERE hr.game_id = g.id)
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut written = 0;
+        for game_id in game_ids {
+            if let Some(report) = self.write_report(game_id).await? {
+                self.publish(&report).await;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Write the game's halftime report, None if it has one already
+    async fn write_report(&self, game_id: Uuid) -> Result<Option<HalftimeReport>, sqlx::Error> {
+        let Some(game) = sqlx::query!(
+            r#"
+            SELECT g.home_team_id, g.away_team_id, g.home_score, g.away_score,
+                   ht.team_name as home_team_name, at.team_name as away_team_name
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let top_contributors: Vec<HalftimeContributor> = sqlx::query!(
+            r#"
+            SELECT user_id, MAX(username) as "username!", team_side, SUM(score_points)::int as "points!"
+            FROM live_score_events
+            WHERE game_id = $1
+            GROUP BY user_id, team_side
+            HAVING SUM(score_points) > 0
+            ORDER BY SUM(score_points) DESC, MIN(occurred_at)
+            LIMIT $2
+            "#,
+            game_id,
+            TOP_CONTRIBUTORS
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| HalftimeContributor { user_id: row.user_id, username: row.username, team_side: row.team_side, points: row.points })
+        .collect();
+
+        let home_pace = self.pace(game.home_team_id, game.away_team_id, game.home_score, game_id).await?;
+        let away_pace = self.pace(game.away_team_id, game.home_team_id, game.away_score, game_id).await?;
+        let message = halftime_message(
+            &game.home_team_name,
+            &game.away_team_name,
+            game.home_score,
+            game.away_score,
+            &top_contributors,
+        );
+
+        let report = sqlx::query_as!(
+            HalftimeReport,
+            r#"
+            INSERT INTO game_halftime_reports (game_id, home_score, away_score, top_contributors, home_pace, away_pace, message)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (game_id) DO NOTHING
+            RETURNING id, game_id, home_score, away_score,
+                      top_contributors as "top_contributors: Json<Vec<HalftimeContributor>>",
+                      home_pace as "home_pace: Json<TeamPace>", away_pace as "away_pace: Json<TeamPace>",
+                      message, created_at
+            "#,
+            game_id,
+            game.home_score,
+            game.away_score,
+            Json(&top_contributors) as _,
+            Json(&home_pace) as _,
+            Json(&away_pace) as _,
+            message
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(report) = &report {
+            tracing::info!("⏱️ Halftime report for game {}: {}", game_id, report.message);
+            for team_id in [game.home_team_id, game.away_team_id] {
+                self.notify_team(team_id, report).await?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Pace of the team against its final scores in earlier evaluated games with the opponent
+    async fn pace(&self, team_id: Uuid, opponent_id: Uuid, halftime_score: i32, game_id: Uuid) -> Result<TeamPace, sqlx::Error> {
+        let previous_scores = sqlx::query_scalar!(
+            r#"
+            SELECT CASE WHEN home_team_id = $1 THEN home_score ELSE away_score END as "score!"
+            FROM games
+            WHERE status = 'evaluated' AND id <> $3
+            AND ((home_team_id = $1 AND away_team_id = $2) OR (home_team_id = $2 AND away_team_id = $1))
+            "#,
+            team_id,
+            opponent_id,
+            game_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(team_pace(halftime_score, &previous_scores))
+    }
+
+    /// Put the report into the notification center of the team's active members
+    async fn notify_team(&self, team_id: Uuid, report: &HalftimeReport) -> Result<(), sqlx::Error> {
+        let members = sqlx::query_scalar!(
+            "SELECT user_id FROM team_members WHERE team_id = $1 AND status = 'active'",
+            team_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for member_id in members {
+            let notification = ChannelNotification {
+                recipient_id: member_id,
+                actor_id: member_id,
+                notification_type: "halftime_report".to_string(),
+                entity_type: "game".to_string(),
+                entity_id: report.game_id,
+                title: "Halftime".to_string(),
+                message: report.message.clone(),
+                push_category: "league_update".to_string(),
+            };
+            if let Err(e) = deliver_to_channels(&self.pool, &notification, &["in_app"]).await {
+                tracing::error!("Failed to notify {} about the halftime of game {}: {}", member_id, report.game_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn publish(&self, report: &HalftimeReport) {
+        let Some(redis_client) = &self.redis_client else {
+            return;
+        };
+
+        let event = GameEvent::HalftimeReport {
+            report_id: report.id,
+            game_id: report.game_id,
+            home_score: report.home_score,
+            away_score: report.away_score,
+            top_contributors: serde_json::to_value(&report.top_contributors.0).unwrap_or_default(),
+            home_pace: serde_json::to_value(&report.home_pace.0).unwrap_or_default(),
+            away_pace: serde_json::to_value(&report.away_pace.0).unwrap_or_default(),
+            message: report.message.clone(),
+            timestamp: report.created_at,
+        };
+        let result: Result<(), Box<dyn std::error::Error>> = async {
+            redis_publish_fault()?;
+            let mut conn = redis_client.get_async_connection().await?;
+            let _: i32 = conn.publish("game:events:global", traced_message(&event)?).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::error!("Failed to broadcast the halftime report of game {}: {}", report.game_id, e);
+        }
+    }
+}
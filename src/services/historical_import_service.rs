@@ -0,0 +1,388 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::league::historical_import::ImportPlan;
+use crate::league::standings::StandingsService;
+use crate::models::organization::{HistoricalImportReport, ImportIssue, Organization};
+use crate::models::user::{UserRole, UserStatus};
+use crate::utils::password::hash_password;
+
+/// Applies a planned historical import in one transaction, so a league migrating from
+/// spreadsheets lands completely or not at all
+pub struct HistoricalImportService {
+    pool: PgPool,
+}
+
+fn issue(file: &str, line: usize, message: impl Into<String>) -> ImportIssue {
+    ImportIssue { file: file.to_string(), line, message: message.into() }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).expect("midnight is a valid time").and_utc()
+}
+
+impl HistoricalImportService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Import the plan into a new league of the organization. When the report has issues or it
+    /// is a dry run, nothing is kept.
+    pub async fn import(
+        &self,
+        organization: &Organization,
+        league_name: &str,
+        season_name: Option<&str>,
+        plan: &ImportPlan,
+        dry_run: bool,
+    ) -> Result<HistoricalImportReport, sqlx::Error> {
+        let mut report = HistoricalImportReport { dry_run, ..Default::default() };
+        let mut tx = self.pool.begin().await?;
+
+        let user_ids = self.create_users(&mut tx, plan, &mut report).await?;
+        let team_ids = self.create_teams(&mut tx, plan, &user_ids, &mut report).await?;
+        if !report.issues.is_empty() {
+            tx.rollback().await?;
+            return Ok(report);
+        }
+
+        let league_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"
+            INSERT INTO leagues (id, name, description, max_teams)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            league_id,
+            league_name.trim(),
+            format!("Imported for {}", organization.name),
+            (team_ids.len() as i32).max(16)
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE teams SET league_id = $1 WHERE id = ANY($2)",
+            league_id,
+            &team_ids.values().copied().collect::<Vec<Uuid>>()
+        )
+        .execute(&mut *tx)
+        .await?;
+        report.league_id = Some(league_id);
+
+        if !plan.results.is_empty() {
+            let season_name = season_name.map(str::to_string).unwrap_or_else(|| format!("{} (imported)", league_name.trim()));
+            let season_id = self.import_results(&mut tx, organization, &season_name, plan, &team_ids).await?;
+            report.season_id = Some(season_id);
+            report.games_imported = plan.results.len();
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+            tracing::info!(
+                "Imported league {} for organization {}: {} users, {} teams, {} games",
+                league_id, organization.slug, report.users_created, report.teams_created, report.games_imported
+            );
+        }
+        Ok(report)
+    }
+
+    /// Create the imported users, returning the ids of all of them by lowercased username
+    async fn create_users(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        plan: &ImportPlan,
+        report: &mut HistoricalImportReport,
+    ) -> Result<HashMap<String, Uuid>, sqlx::Error> {
+        let usernames: Vec<String> = plan.users.iter().map(|u| u.username.to_lowercase()).collect();
+        let emails: Vec<String> = plan.users.iter().map(|u| u.email.to_lowercase()).collect();
+        let taken = sqlx::query!(
+            r#"
+            SELECT LOWER(username) as "username!", LOWER(email) as "email!"
+            FROM users
+            WHERE LOWER(username) = ANY($1) OR LOWER(email) = ANY($2)
+            "#,
+            &usernames,
+            &emails
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+        let taken_usernames: HashSet<&str> = taken.iter().map(|row| row.username.as_str()).collect();
+        let taken_emails: HashSet<&str> = taken.iter().map(|row| row.email.as_str()).collect();
+
+        for user in &plan.users {
+            if taken_usernames.contains(user.username.to_lowercase().as_str()) {
+                report.issues.push(issue("users", user.line, format!("Username {} is already taken", user.username)));
+            } else if taken_emails.contains(user.email.to_lowercase().as_str()) {
+                report.issues.push(issue("users", user.line, format!("Email {} is already registered", user.email)));
+            }
+        }
+        if !report.issues.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Imported users get an unknown password and sign in through the password reset flow.
+        // Hashing is slow, so they share one.
+        let password_hash = hash_password(&Uuid::new_v4().to_string());
+        let mut user_ids = HashMap::new();
+        for user in &plan.users {
+            let user_id = Uuid::new_v4();
+            sqlx::query!(
+                r#"
+                INSERT INTO users (id, username, password_hash, email, role, status)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                user_id,
+                user.username,
+                password_hash,
+                user.email,
+                UserRole::User.to_string(),
+                UserStatus::Active.to_string()
+            )
+            .execute(&mut **tx)
+            .await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO user_avatars (user_id, stamina, strength, avatar_style)
+                VALUES ($1, 0, 0, 'warrior')
+                "#,
+                user_id
+            )
+            .execute(&mut **tx)
+            .await?;
+            user_ids.insert(user.username.to_lowercase(), user_id);
+        }
+        report.users_created = user_ids.len();
+        Ok(user_ids)
+    }
+
+    /// Create the teams with their rosters, returning their ids by name. Imported users left
+    /// without a team go to the player pool.
+    async fn create_teams(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        plan: &ImportPlan,
+        imported_users: &HashMap<String, Uuid>,
+        report: &mut HistoricalImportReport,
+    ) -> Result<HashMap<String, Uuid>, sqlx::Error> {
+        let rostered: Vec<String> = plan
+            .teams
+            .iter()
+            .flat_map(|team| std::iter::once(&team.owner).chain(&team.members))
+            .map(|username| username.to_lowercase())
+            .filter(|username| !imported_users.contains_key(username))
+            .collect();
+        let existing_users: HashMap<String, (Uuid, bool)> = sqlx::query!(
+            r#"
+            SELECT u.id, LOWER(u.username) as "username!",
+                   EXISTS (
+                       SELECT 1 FROM team_members tm WHERE tm.user_id = u.id AND tm.status = 'active'
+                   ) OR EXISTS (SELECT 1 FROM teams t WHERE t.user_id = u.id) as "on_team!"
+            FROM users u
+            WHERE LOWER(u.username) = ANY($1)
+            "#,
+            &rostered
+        )
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .map(|row| (row.username, (row.id, row.on_team)))
+        .collect();
+
+        let names: Vec<String> = plan.teams.iter().map(|team| team.name.to_lowercase()).collect();
+        let taken_names: HashSet<String> = sqlx::query_scalar!(
+            r#"SELECT LOWER(team_name) as "team_name!" FROM teams WHERE LOWER(team_name) = ANY($1)"#,
+            &names
+        )
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut rosters = Vec::new();
+        for team in &plan.teams {
+            let line_issues = report.issues.len();
+            if taken_names.contains(&team.name.to_lowercase()) {
+                report.issues.push(issue("teams", team.line, format!("Team name {} is already taken", team.name)));
+            }
+            let mut roster = Vec::new();
+            for username in std::iter::once(&team.owner).chain(&team.members) {
+                let key = username.to_lowercase();
+                match (imported_users.get(&key), existing_users.get(&key)) {
+                    (Some(user_id), _) => roster.push(*user_id),
+                    (None, Some((_, true))) => {
+                        report.issues.push(issue("teams", team.line, format!("{username} is already on a team")));
+                    }
+                    (None, Some((user_id, false))) => roster.push(*user_id),
+                    (None, None) => {
+                        report.issues.push(issue("teams", team.line, format!("{username} is neither in users_csv nor registered")));
+                    }
+                }
+            }
+            if report.issues.len() == line_issues {
+                rosters.push((team, roster));
+            }
+        }
+        if !report.issues.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut team_ids = HashMap::new();
+        let mut placed = HashSet::new();
+        for (team, roster) in rosters {
+            let team_id = Uuid::new_v4();
+            sqlx::query!(
+                r#"
+                INSERT INTO teams (id, user_id, team_name, team_color)
+                VALUES ($1, $2, $3, COALESCE($4, '#4F46E5'))
+                "#,
+                team_id,
+                roster[0],
+                team.name,
+                team.color.as_deref()
+            )
+            .execute(&mut **tx)
+            .await?;
+            for (index, user_id) in roster.iter().enumerate() {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO team_members (team_id, user_id, role, status)
+                    VALUES ($1, $2, $3, 'active')
+                    "#,
+                    team_id,
+                    user_id,
+                    if index == 0 { "owner" } else { "member" }
+                )
+                .execute(&mut **tx)
+                .await?;
+                placed.insert(*user_id);
+            }
+            report.members_added += roster.len();
+            team_ids.insert(team.name.to_lowercase(), team_id);
+        }
+        report.teams_created = team_ids.len();
+
+        let placed: Vec<Uuid> = placed.into_iter().collect();
+        sqlx::query!("DELETE FROM player_pool WHERE user_id = ANY($1)", &placed)
+            .execute(&mut **tx)
+            .await?;
+        let unplaced: Vec<Uuid> = imported_users.values().copied().filter(|id| !placed.contains(id)).collect();
+        sqlx::query!(
+            "INSERT INTO player_pool (user_id, last_active_at) SELECT UNNEST($1::uuid[]), NOW()",
+            &unplaced
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(team_ids)
+    }
+
+    /// File the results under a new season and derive its standings
+    async fn import_results(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        organization: &Organization,
+        season_name: &str,
+        plan: &ImportPlan,
+        team_ids: &HashMap<String, Uuid>,
+    ) -> Result<Uuid, sqlx::Error> {
+        let first_day = plan.results.iter().map(|r| r.played_on).min().expect("results are not empty");
+        let last_day = plan.results.iter().map(|r| r.played_on).max().expect("results are not empty");
+        let game_duration = chrono::Duration::seconds(organization.default_game_duration_seconds);
+
+        let season_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"
+            INSERT INTO league_seasons (
+                id, league_id, name, start_date, end_date, auto_evaluation_enabled,
+                game_duration_seconds, games_per_matchup, inactivity_nudge_days
+            )
+            SELECT $1, t.league_id, $2, $3, $4, false, $5, $6, $7
+            FROM teams t
+            WHERE t.id = $8
+            "#,
+            season_id,
+            season_name,
+            start_of_day(first_day),
+            start_of_day(last_day) + chrono::Duration::days(1),
+            organization.default_game_duration_seconds,
+            organization.default_games_per_matchup,
+            organization.default_inactivity_nudge_days,
+            team_ids.values().next().copied()
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO league_teams (season_id, team_id)
+            SELECT $1, UNNEST($2::uuid[])
+            "#,
+            season_id,
+            &team_ids.values().copied().collect::<Vec<Uuid>>()
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        // The return leg of a pairing is every game after their first meeting
+        let mut met = HashSet::new();
+        for result in &plan.results {
+            let home_team_id = team_ids[&result.home_team.to_lowercase()];
+            let away_team_id = team_ids[&result.away_team.to_lowercase()];
+            let pairing = (home_team_id.min(away_team_id), home_team_id.max(away_team_id));
+            let winner_team_id = match result.home_score.cmp(&result.away_score) {
+                std::cmp::Ordering::Greater => Some(home_team_id),
+                std::cmp::Ordering::Less => Some(away_team_id),
+                std::cmp::Ordering::Equal => None,
+            };
+            let game_start_time = start_of_day(result.played_on);
+            sqlx::query!(
+                r#"
+                INSERT INTO games (
+                    season_id, home_team_id, away_team_id, week_number, is_first_leg, status,
+                    winner_team_id, home_score, away_score, game_start_time, game_end_time
+                )
+                VALUES ($1, $2, $3, $4, $5, 'evaluated', $6, $7, $8, $9, $10)
+                "#,
+                season_id,
+                home_team_id,
+                away_team_id,
+                result.week,
+                met.insert(pairing),
+                winner_team_id,
+                result.home_score,
+                result.away_score,
+                game_start_time,
+                game_start_time + game_duration
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO league_standings (season_id, team_id, games_played, wins, draws, losses, position)
+            SELECT lt.season_id, lt.team_id,
+                   COUNT(g.id)::int,
+                   COUNT(g.id) FILTER (WHERE g.winner_team_id = lt.team_id)::int,
+                   COUNT(g.id) FILTER (WHERE g.winner_team_id IS NULL)::int,
+                   COUNT(g.id) FILTER (WHERE g.winner_team_id <> lt.team_id)::int,
+                   1
+            FROM league_teams lt
+            LEFT JOIN games g ON g.season_id = lt.season_id AND lt.team_id IN (g.home_team_id, g.away_team_id)
+            WHERE lt.season_id = $1
+            GROUP BY lt.season_id, lt.team_id
+            "#,
+            season_id
+        )
+        .execute(&mut **tx)
+        .await?;
+        StandingsService::new(self.pool.clone())
+            .recalculate_positions_in_tx(tx, season_id)
+            .await?;
+
+        Ok(season_id)
+    }
+}
@@ -0,0 +1,236 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::models::workout_data::HeartRateData;
+use crate::workout::hr_trends::calculate_hr_workout_metrics;
+
+/// Number of workouts processed per batch by the nightly job
+const METRICS_BATCH_SIZE: i64 = 200;
+
+/// Service that derives heart rate trends (resting HR, HR at a given activity, cardiac drift)
+/// from health profiles and raw workout samples.
+#[derive(Debug)]
+pub struct HrTrendService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HrTrendRunSummary {
+    pub resting_hr_snapshots: u64,
+    pub workouts_processed: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RestingHrPoint {
+    pub date: NaiveDate,
+    pub resting_heart_rate: i32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WeeklyHrPoint {
+    pub week_start: DateTime<Utc>,
+    pub avg_heart_rate: f64,
+    pub workouts: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ActivityHrTrend {
+    pub activity: String,
+    pub points: Vec<WeeklyHrPoint>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WeeklyDriftPoint {
+    pub week_start: DateTime<Utc>,
+    pub avg_drift_pct: f64,
+    pub workouts: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HrTrends {
+    pub days: i32,
+    pub resting_hr: Vec<RestingHrPoint>,
+    /// Latest minus earliest resting HR in the window (negative means improving)
+    pub resting_hr_change: Option<i32>,
+    /// Weekly steady-state HR per activity; a falling line at the same activity means better fitness
+    pub workload_hr: Vec<ActivityHrTrend>,
+    pub hr_drift: Vec<WeeklyDriftPoint>,
+    pub last_computed_at: Option<DateTime<Utc>>,
+}
+
+impl HrTrendService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Snapshot today's resting HR for every profile and compute metrics for new workouts
+    pub async fn run_nightly(&self) -> Result<HrTrendRunSummary, sqlx::Error> {
+        let resting_hr_snapshots = sqlx::query!(
+            r#"
+            INSERT INTO user_resting_hr_history (user_id, recorded_on, resting_heart_rate)
+            SELECT user_id, CURRENT_DATE, resting_heart_rate
+            FROM user_health_profiles
+            ON CONFLICT (user_id, recorded_on)
+            DO UPDATE SET resting_heart_rate = EXCLUDED.resting_heart_rate
+            "#
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        let mut workouts_processed = 0;
+        loop {
+            let processed = self.process_workout_batch().await?;
+            workouts_processed += processed;
+            if (processed as i64) < METRICS_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(HrTrendRunSummary { resting_hr_snapshots, workouts_processed })
+    }
+
+    /// Compute metrics for the next batch of workouts that have none yet.
+    /// Workouts without usable samples still get a row so they are not picked up again.
+    async fn process_workout_batch(&self) -> Result<usize, sqlx::Error> {
+        let workouts = sqlx::query!(
+            r#"
+            SELECT wd.id, wd.user_id, wd.workout_start, wd.heart_rate_data,
+                   COALESCE(wd.user_activity, wd.activity_name) as activity_name
+            FROM workout_data wd
+            LEFT JOIN workout_hr_metrics m ON m.workout_data_id = wd.id
+            WHERE m.workout_data_id IS NULL
+            ORDER BY wd.workout_start
+            LIMIT $1
+            "#,
+            METRICS_BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for workout in &workouts {
+            let hr_data: Vec<HeartRateData> = serde_json::from_value(workout.heart_rate_data.clone())
+                .unwrap_or_default();
+            let metrics = calculate_hr_workout_metrics(&hr_data);
+
+            sqlx::query!(
+                r#"
+                INSERT INTO workout_hr_metrics (
+                    workout_data_id, user_id, workout_start, activity_name,
+                    steady_state_avg_hr, hr_drift_pct
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (workout_data_id) DO NOTHING
+                "#,
+                workout.id,
+                workout.user_id,
+                workout.workout_start,
+                workout.activity_name,
+                metrics.steady_state_avg_hr,
+                metrics.hr_drift_pct
+            )
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(workouts.len())
+    }
+
+    /// Get a user's heart rate trends over the last `days` days
+    pub async fn get_trends(&self, user_id: Uuid, days: i32) -> Result<HrTrends, sqlx::Error> {
+        let resting_hr: Vec<RestingHrPoint> = sqlx::query!(
+            r#"
+            SELECT recorded_on, resting_heart_rate
+            FROM user_resting_hr_history
+            WHERE user_id = $1 AND recorded_on >= CURRENT_DATE - $2::int
+            ORDER BY recorded_on
+            "#,
+            user_id,
+            days
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|r| RestingHrPoint { date: r.recorded_on, resting_heart_rate: r.resting_heart_rate })
+        .collect();
+
+        let resting_hr_change = match (resting_hr.first(), resting_hr.last()) {
+            (Some(first), Some(last)) if resting_hr.len() > 1 => Some(last.resting_heart_rate - first.resting_heart_rate),
+            _ => None,
+        };
+
+        let workload_rows = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(activity_name, 'Other') as "activity!",
+                date_trunc('week', workout_start) as "week_start!",
+                AVG(steady_state_avg_hr)::float8 as "avg_heart_rate!",
+                COUNT(*) as "workouts!"
+            FROM workout_hr_metrics
+            WHERE user_id = $1
+            AND workout_start >= NOW() - make_interval(days => $2)
+            AND steady_state_avg_hr IS NOT NULL
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+            user_id,
+            days
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut workload_hr: Vec<ActivityHrTrend> = Vec::new();
+        for row in workload_rows {
+            if workload_hr.last().map(|a| a.activity.as_str()) != Some(row.activity.as_str()) {
+                workload_hr.push(ActivityHrTrend { activity: row.activity.clone(), points: Vec::new() });
+            }
+            if let Some(activity) = workload_hr.last_mut() {
+                activity.points.push(WeeklyHrPoint {
+                    week_start: row.week_start,
+                    avg_heart_rate: row.avg_heart_rate,
+                    workouts: row.workouts,
+                });
+            }
+        }
+
+        let hr_drift = sqlx::query!(
+            r#"
+            SELECT
+                date_trunc('week', workout_start) as "week_start!",
+                AVG(hr_drift_pct)::float8 as "avg_drift_pct!",
+                COUNT(*) as "workouts!"
+            FROM workout_hr_metrics
+            WHERE user_id = $1
+            AND workout_start >= NOW() - make_interval(days => $2)
+            AND hr_drift_pct IS NOT NULL
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            user_id,
+            days
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|r| WeeklyDriftPoint { week_start: r.week_start, avg_drift_pct: r.avg_drift_pct, workouts: r.workouts })
+        .collect();
+
+        let last_computed_at = sqlx::query_scalar!(
+            "SELECT MAX(computed_at) FROM workout_hr_metrics WHERE user_id = $1",
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(HrTrends {
+            days,
+            resting_hr,
+            resting_hr_change,
+            workload_hr,
+            hr_drift,
+            last_computed_at,
+        })
+    }
+}
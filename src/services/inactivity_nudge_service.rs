@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::services::EmailService;
+use crate::services::notification_delivery::{deliver_email, deliver_to_enabled_channels, ChannelNotification};
+
+/// Service that finds team members without uploads during a running season, nudges them
+/// and tells their team captain, honoring both users' notification preferences.
+#[derive(Debug)]
+pub struct InactivityNudgeService {
+    pool: PgPool,
+    // Members are also nudged by email when set
+    email_service: Option<Arc<EmailService>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InactivityNudgeRunSummary {
+    pub inactive_members: usize,
+    pub members_nudged: usize,
+    pub captains_notified: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InactiveMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub season_id: Uuid,
+    pub captain_id: Option<Uuid>,
+    pub last_upload_at: Option<DateTime<Utc>>,
+    pub inactive_days: i32,
+    pub nudges_enabled: bool,
+    pub captain_alerts_enabled: bool,
+}
+
+impl InactivityNudgeService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, email_service: None }
+    }
+
+    /// Send the member's nudge by email too, to members who didn't turn email off
+    pub fn with_email(mut self, email_service: Arc<EmailService>) -> Self {
+        self.email_service = Some(email_service);
+        self
+    }
+
+    /// Nudge every member who crossed their season's inactivity threshold since their last nudge
+    pub async fn run(&self) -> Result<InactivityNudgeRunSummary, sqlx::Error> {
+        let members = self.find_inactive_members().await?;
+        let mut summary = InactivityNudgeRunSummary {
+            inactive_members: members.len(),
+            members_nudged: 0,
+            captains_notified: 0,
+        };
+
+        for member in members {
+            match self.nudge(&member).await {
+                Ok((member_channels, captain_channels)) => {
+                    if !member_channels.is_empty() {
+                        summary.members_nudged += 1;
+                    }
+                    if !captain_channels.is_empty() {
+                        summary.captains_notified += 1;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to nudge inactive user {}: {}", member.user_id, e);
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Members of teams in running seasons whose last upload (or season start / join date, if later)
+    /// is older than the season's `inactivity_nudge_days` and who were not nudged since.
+    /// A member in several running seasons is only reported once.
+    pub async fn find_inactive_members(&self) -> Result<Vec<InactiveMember>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            WITH season_members AS (
+                SELECT DISTINCT ON (tm.user_id)
+                    tm.user_id,
+                    u.username,
+                    t.id as team_id,
+                    t.team_name,
+                    t.user_id as captain_id,
+                    s.id as season_id,
+                    s.inactivity_nudge_days,
+                    GREATEST(s.start_date, tm.joined_at) as counting_from
+                FROM league_seasons s
+                JOIN league_teams lt ON lt.season_id = s.id
+                JOIN teams t ON t.id = lt.team_id
+                JOIN team_members tm ON tm.team_id = t.id AND tm.status = 'active'
+                JOIN users u ON u.id = tm.user_id AND u.status = 'active'
+                WHERE s.start_date <= NOW() AND s.end_date >= NOW()
+                AND s.inactivity_nudge_days IS NOT NULL
+                ORDER BY tm.user_id, s.start_date
+            ),
+            last_uploads AS (
+                SELECT sm.*, (SELECT MAX(wd.created_at) FROM workout_data wd WHERE wd.user_id = sm.user_id) as last_upload_at
+                FROM season_members sm
+            )
+            SELECT
+                lu.user_id as "user_id!",
+                lu.username as "username!",
+                lu.team_id as "team_id!",
+                lu.team_name as "team_name!",
+                lu.captain_id as "captain_id?",
+                lu.season_id as "season_id!",
+                lu.last_upload_at,
+                EXTRACT(DAY FROM NOW() - GREATEST(lu.counting_from, lu.last_upload_at))::int as "inactive_days!",
+                COALESCE(member_np.inactivity_nudges_enabled, TRUE) as "nudges_enabled!",
+                COALESCE(captain_np.team_inactivity_alerts_enabled, TRUE) as "captain_alerts_enabled!"
+            FROM last_uploads lu
+            LEFT JOIN notification_preferences member_np ON member_np.user_id = lu.user_id
+            LEFT JOIN notification_preferences captain_np ON captain_np.user_id = lu.captain_id
+            WHERE GREATEST(lu.counting_from, lu.last_upload_at) < NOW() - make_interval(days => lu.inactivity_nudge_days)
+            AND NOT EXISTS (
+                SELECT 1 FROM inactivity_nudges n
+                WHERE n.user_id = lu.user_id
+                AND n.created_at > GREATEST(lu.counting_from, lu.last_upload_at)
+            )
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| InactiveMember {
+                user_id: r.user_id,
+                username: r.username,
+                team_id: r.team_id,
+                team_name: r.team_name,
+                season_id: r.season_id,
+                captain_id: r.captain_id,
+                last_upload_at: r.last_upload_at,
+                inactive_days: r.inactive_days,
+                nudges_enabled: r.nudges_enabled,
+                captain_alerts_enabled: r.captain_alerts_enabled,
+            })
+            .collect())
+    }
+
+    /// Record the nudge and deliver it to the member and their captain.
+    /// Returns the channels used for the member and for the captain.
+    async fn nudge(&self, member: &InactiveMember) -> Result<(Vec<String>, Vec<String>), sqlx::Error> {
+        // Captains who are inactive themselves only get their own nudge
+        let captain_id = member.captain_id
+            .filter(|captain_id| *captain_id != member.user_id && member.captain_alerts_enabled);
+
+        if !member.nudges_enabled && captain_id.is_none() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let nudge_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO inactivity_nudges (user_id, season_id, team_id, last_upload_at, inactive_days, captain_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+            member.user_id,
+            member.season_id,
+            member.team_id,
+            member.last_upload_at,
+            member.inactive_days,
+            captain_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let member_channels = if member.nudges_enabled {
+            let nudge = ChannelNotification {
+                recipient_id: member.user_id,
+                actor_id: member.user_id,
+                notification_type: "inactivity_nudge".to_string(),
+                entity_type: "inactivity_nudge".to_string(),
+                entity_id: nudge_id,
+                title: format!("{} needs you", member.team_name),
+                message: format!(
+                    "It's been {} days since your last workout. Get moving and score some points for {}!",
+                    member.inactive_days, member.team_name
+                ),
+                push_category: "health_reminder".to_string(),
+            };
+            let mut channels = deliver_to_enabled_channels(&self.pool, &nudge).await?;
+            if let Some(email_service) = &self.email_service {
+                if deliver_email(&self.pool, email_service, &nudge).await? {
+                    channels.push("email".to_string());
+                }
+            }
+            channels
+        } else {
+            Vec::new()
+        };
+
+        let captain_channels = match captain_id {
+            Some(captain_id) => {
+                deliver_to_enabled_channels(&self.pool, &ChannelNotification {
+                    recipient_id: captain_id,
+                    actor_id: member.user_id,
+                    notification_type: "team_member_inactive".to_string(),
+                    entity_type: "user".to_string(),
+                    entity_id: member.user_id,
+                    title: "Inactive team member".to_string(),
+                    message: format!(
+                        "{} hasn't uploaded a workout for {} days",
+                        member.username, member.inactive_days
+                    ),
+                    push_category: "league_update".to_string(),
+                }).await?
+            }
+            None => Vec::new(),
+        };
+
+        sqlx::query!(
+            "UPDATE inactivity_nudges SET delivered_channels = $2, captain_channels = $3 WHERE id = $1",
+            nudge_id,
+            &member_channels,
+            &captain_channels
+        )
+        .execute(&self.pool)
+        .await?;
+
+        tracing::info!("Nudged inactive user {} ({} days) via {:?}, captain via {:?}",
+            member.user_id, member.inactive_days, member_channels, captain_channels);
+
+        Ok((member_channels, captain_channels))
+    }
+}
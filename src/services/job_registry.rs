@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio_cron_scheduler::{Job, JobSchedulerError};
+use sentry::{Hub, SentryFutureExt};
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Outcome of a job run: a short summary on success, the error otherwise
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+/// The work a scheduled job does, runnable both from its schedule and by hand
+pub type JobRunner = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+/// Run history of a job since the process started
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JobStats {
+    /// Runs currently in flight; a run that never finishes keeps this above zero
+    pub in_flight: u32,
+    /// Start of the oldest run still in flight
+    pub running_since: Option<DateTime<Utc>>,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<i64>,
+    pub last_status: Option<String>,
+    pub last_message: Option<String>,
+    /// What started the last run: "schedule" or "manual"
+    pub last_trigger: Option<String>,
+    pub success_count: u64,
+    pub failure_count: u64,
+}
+
+/// A registered job as shown to admins
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisteredJobInfo {
+    pub name: String,
+    pub description: String,
+    pub schedule: String,
+    pub job_id: Uuid,
+    pub next_run_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub stats: JobStats,
+}
+
+struct RegisteredJob {
+    description: String,
+    schedule: String,
+    job_id: Uuid,
+    runner: JobRunner,
+    stats: JobStats,
+}
+
+/// Jobs known to the scheduler with their run stats, so they can be inspected and re-run
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    jobs: Arc<Mutex<BTreeMap<String, RegisteredJob>>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a cron job for the runner and track it under `name`.
+    /// The job still has to be added to the scheduler.
+    pub fn register(
+        &self,
+        name: &str,
+        schedule: &str,
+        description: &str,
+        runner: JobRunner,
+    ) -> Result<Job, JobSchedulerError> {
+        let job = self.cron_job(name, schedule)?;
+
+        self.lock().insert(name.to_string(), RegisteredJob {
+            description: description.to_string(),
+            schedule: schedule.to_string(),
+            job_id: job.guid(),
+            runner,
+            stats: JobStats::default(),
+        });
+        Ok(job)
+    }
+
+    /// Move a job to a new schedule, keeping its runner and stats. Returns the id of
+    /// the job it replaces and the new job, which still has to be swapped in the scheduler.
+    /// Returns None for unknown jobs.
+    pub fn reschedule(&self, name: &str, schedule: &str) -> Option<Result<(Uuid, Job), JobSchedulerError>> {
+        if !self.contains(name) {
+            return None;
+        }
+        let job = match self.cron_job(name, schedule) {
+            Ok(job) => job,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut jobs = self.lock();
+        // Unregistered in the meantime
+        let registered = jobs.get_mut(name)?;
+        let replaced_id = registered.job_id;
+        registered.schedule = schedule.to_string();
+        registered.job_id = job.guid();
+        Some(Ok((replaced_id, job)))
+    }
+
+    /// Whether the scheduler accepts a cron expression (six fields, including seconds)
+    pub fn validate_schedule(schedule: &str) -> Result<(), String> {
+        Job::new_async(schedule, |_uuid, _l| Box::pin(async {}))
+            .map(|_| ())
+            .map_err(|_| format!("Invalid cron expression '{}'; expected six fields including seconds", schedule))
+    }
+
+    /// Forget a job, returning its scheduler id
+    pub fn unregister(&self, name: &str) -> Option<Uuid> {
+        self.lock().remove(name).map(|job| job.job_id)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.lock().contains_key(name)
+    }
+
+    /// Run a job now and record the outcome. Returns None for unknown jobs.
+    pub async fn run(&self, name: &str, trigger: &str) -> Option<Result<String, String>> {
+        let started_at = Utc::now();
+        let runner = {
+            let mut jobs = self.lock();
+            let job = jobs.get_mut(name)?;
+            if job.stats.in_flight == 0 {
+                job.stats.running_since = Some(started_at);
+            }
+            job.stats.in_flight += 1;
+            job.stats.last_started_at = Some(started_at);
+            job.stats.last_trigger = Some(trigger.to_string());
+            job.runner.clone()
+        };
+
+        // Manual runs are part of the admin request's trace, scheduled runs start their own
+        let span = tracing::info_span!("Scheduler job", job.name = %name, job.trigger = %trigger);
+        // Errors reported during the run are tagged with the job
+        let hub = Arc::new(Hub::new_from_top(Hub::current()));
+        hub.configure_scope(|scope| {
+            scope.set_tag("job", name);
+            scope.set_tag("job_trigger", trigger);
+        });
+        let result = runner().instrument(span.clone()).bind_hub(hub.clone()).await;
+        if let Err(error) = &result {
+            Hub::run(hub, || span.in_scope(|| tracing::error!("❌ [SCHEDULER] Job {} failed: {}", name, error)));
+        }
+
+        let finished_at = Utc::now();
+        let mut jobs = self.lock();
+        // Unregistered while running, e.g. a deleted season
+        if let Some(job) = jobs.get_mut(name) {
+            job.stats.in_flight = job.stats.in_flight.saturating_sub(1);
+            if job.stats.in_flight == 0 {
+                job.stats.running_since = None;
+            }
+            job.stats.last_finished_at = Some(finished_at);
+            job.stats.last_duration_ms = Some((finished_at - started_at).num_milliseconds());
+            match &result {
+                Ok(message) => {
+                    job.stats.success_count += 1;
+                    job.stats.last_status = Some("succeeded".to_string());
+                    job.stats.last_message = Some(message.clone());
+                }
+                Err(error) => {
+                    job.stats.failure_count += 1;
+                    job.stats.last_status = Some("failed".to_string());
+                    job.stats.last_message = Some(error.clone());
+                }
+            }
+        }
+        Some(result)
+    }
+
+    /// Registered jobs ordered by name; `next_run_at` is left for the scheduler to fill in
+    pub fn list(&self) -> Vec<RegisteredJobInfo> {
+        self.lock()
+            .iter()
+            .map(|(name, job)| RegisteredJobInfo {
+                name: name.clone(),
+                description: job.description.clone(),
+                schedule: job.schedule.clone(),
+                job_id: job.job_id,
+                next_run_at: None,
+                stats: job.stats.clone(),
+            })
+            .collect()
+    }
+
+    fn cron_job(&self, name: &str, schedule: &str) -> Result<Job, JobSchedulerError> {
+        let registry = self.clone();
+        let job_name = name.to_string();
+        Job::new_async(schedule, move |_uuid, _l| {
+            let registry = registry.clone();
+            let job_name = job_name.clone();
+            Box::pin(async move {
+                registry.run(&job_name, "schedule").await;
+            })
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, RegisteredJob>> {
+        self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::league_analytics::*;
+
+/// Analytics trail live data by at most this long
+const LEAGUE_ANALYTICS_TTL_SECONDS: u64 = 900;
+/// Members without a workout for this long count as dropped off
+const DROP_OFF_DAYS: i64 = 14;
+
+fn cache_key(league_id: Uuid, season_id: Uuid) -> String {
+    format!("league_analytics:{league_id}:{season_id}")
+}
+
+/// Season the analytics cover
+#[derive(Debug, Clone)]
+pub struct SeasonWindow {
+    pub id: Uuid,
+    pub name: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberWorkout {
+    pub user_id: Uuid,
+    pub workout_start: DateTime<Utc>,
+    pub points: f64,
+}
+
+/// Read-through Redis cache of league analytics. Entries expire after a while instead of being
+/// invalidated, since every workout upload in the league would change them.
+pub struct LeagueAnalyticsService {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl LeagueAnalyticsService {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    /// Whether the user owns a team of the league
+    pub async fn is_captain(&self, league_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM teams t
+                JOIN team_members tm ON tm.team_id = t.id
+                WHERE t.league_id = $1 AND tm.user_id = $2 AND tm.role = 'owner' AND tm.status = 'active'
+            ) as "exists!"
+            "#,
+            league_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Analytics of the given season, or of the running or most recent one.
+    /// None if the league has no such season.
+    pub async fn get(&self, league_id: Uuid, season_id: Option<Uuid>) -> Result<Option<LeagueAnalytics>, sqlx::Error> {
+        let Some(season) = self.find_season(league_id, season_id).await? else {
+            return Ok(None);
+        };
+
+        let key = cache_key(league_id, season.id);
+        if let Some(analytics) = self.read_cached(&key).await {
+            return Ok(Some(analytics));
+        }
+
+        let members = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT tm.user_id
+            FROM league_teams lt
+            JOIN team_members tm ON tm.team_id = lt.team_id AND tm.status = 'active'
+            WHERE lt.season_id = $1
+            "#,
+            season.id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let workouts = sqlx::query_as!(
+            MemberWorkout,
+            r#"
+            SELECT user_id, workout_start, (stamina_gained + strength_gained)::float8 as "points!"
+            FROM workout_data
+            WHERE user_id = ANY($1) AND workout_start >= $2 AND workout_start < $3
+            "#,
+            &members,
+            season.start_date,
+            season.end_date
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let analytics = build_league_analytics(league_id, &season, Utc::now(), &members, &workouts);
+        self.write_cached(&key, &analytics).await;
+        Ok(Some(analytics))
+    }
+
+    async fn find_season(&self, league_id: Uuid, season_id: Option<Uuid>) -> Result<Option<SeasonWindow>, sqlx::Error> {
+        sqlx::query_as!(
+            SeasonWindow,
+            r#"
+            SELECT id, name, start_date, end_date
+            FROM league_seasons
+            WHERE league_id = $1 AND ($2::uuid IS NULL OR id = $2)
+            ORDER BY (start_date <= NOW() AND end_date >= NOW()) DESC, start_date DESC
+            LIMIT 1
+            "#,
+            league_id,
+            season_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn read_cached(&self, key: &str) -> Option<LeagueAnalytics> {
+        let redis_client = self.redis_client.as_ref()?;
+        let result: Result<Option<String>, redis::RedisError> = async {
+            let mut conn = redis_client.get_multiplexed_async_connection().await?;
+            redis::cmd("GET").arg(key).query_async(&mut conn).await
+        }
+        .await;
+
+        match result {
+            Ok(value) => serde_json::from_str(&value?).ok(),
+            Err(e) => {
+                tracing::warn!("Failed to read cached league analytics: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn write_cached(&self, key: &str, analytics: &LeagueAnalytics) {
+        let Some(redis_client) = &self.redis_client else { return };
+        let Ok(value) = serde_json::to_string(analytics) else { return };
+
+        let result: Result<(), redis::RedisError> = async {
+            let mut conn = redis_client.get_multiplexed_async_connection().await?;
+            redis::cmd("SET").arg(key).arg(value).arg("EX").arg(LEAGUE_ANALYTICS_TTL_SECONDS).query_async(&mut conn).await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache league analytics: {}", e);
+        }
+    }
+}
+
+/// Season week of a point in time, starting at 1
+fn season_week(season_start: DateTime<Utc>, at: DateTime<Utc>) -> u32 {
+    ((at - season_start).num_seconds().max(0) / Duration::weeks(1).num_seconds()) as u32 + 1
+}
+
+/// Compute the analytics of a season as of `now` from its members and their season workouts
+pub fn build_league_analytics(
+    league_id: Uuid,
+    season: &SeasonWindow,
+    now: DateTime<Utc>,
+    members: &[Uuid],
+    workouts: &[MemberWorkout],
+) -> LeagueAnalytics {
+    let as_of = now.min(season.end_date);
+    let weeks_elapsed = if as_of < season.start_date {
+        0
+    } else {
+        // A season ending exactly on a week boundary has no extra week
+        season_week(season.start_date, as_of - Duration::nanoseconds(1))
+    };
+
+    let member_set: HashSet<Uuid> = members.iter().copied().collect();
+    let workouts: Vec<&MemberWorkout> = workouts
+        .iter()
+        .filter(|w| member_set.contains(&w.user_id) && w.workout_start >= season.start_date && w.workout_start <= as_of)
+        .collect();
+
+    // week -> (active members, workout count)
+    let mut weekly_activity: BTreeMap<u32, (HashSet<Uuid>, usize)> = BTreeMap::new();
+    let mut first_week: HashMap<Uuid, u32> = HashMap::new();
+    let mut last_workout: HashMap<Uuid, DateTime<Utc>> = HashMap::new();
+    let mut member_points: HashMap<Uuid, f64> = HashMap::new();
+    for workout in &workouts {
+        let week = season_week(season.start_date, workout.workout_start);
+        let entry = weekly_activity.entry(week).or_default();
+        entry.0.insert(workout.user_id);
+        entry.1 += 1;
+
+        let first = first_week.entry(workout.user_id).or_insert(week);
+        *first = (*first).min(week);
+        let last = last_workout.entry(workout.user_id).or_insert(workout.workout_start);
+        *last = (*last).max(workout.workout_start);
+        *member_points.entry(workout.user_id).or_default() += workout.points;
+    }
+
+    let member_count = member_set.len();
+    let rate = |count: usize| if member_count > 0 { round(count as f64 / member_count as f64, 3) } else { 0.0 };
+
+    let weekly = (1..=weeks_elapsed)
+        .map(|week| {
+            let (active, workout_count) = weekly_activity.get(&week).map(|(a, w)| (a.len(), *w)).unwrap_or_default();
+            WeeklyParticipation {
+                week,
+                week_start: season.start_date + Duration::weeks(week as i64 - 1),
+                active_members: active,
+                participation_rate: rate(active),
+                workouts: workout_count,
+                avg_workouts_per_user: if member_count > 0 { round(workout_count as f64 / member_count as f64, 2) } else { 0.0 },
+            }
+        })
+        .collect();
+
+    let drop_off_cutoff = as_of - Duration::days(DROP_OFF_DAYS);
+    let mut cohorts: BTreeMap<u32, Vec<Uuid>> = BTreeMap::new();
+    for (user_id, week) in &first_week {
+        cohorts.entry(*week).or_default().push(*user_id);
+    }
+    let drop_off_cohorts = cohorts
+        .into_iter()
+        .map(|(first_active_week, cohort)| DropOffCohort {
+            first_active_week,
+            members: cohort.len(),
+            active_by_week: (first_active_week..=weeks_elapsed)
+                .map(|week| {
+                    weekly_activity
+                        .get(&week)
+                        .map(|(active, _)| cohort.iter().filter(|user_id| active.contains(user_id)).count())
+                        .unwrap_or(0)
+                })
+                .collect(),
+            dropped_off: cohort.iter().filter(|user_id| last_workout[user_id] < drop_off_cutoff).count(),
+        })
+        .collect();
+
+    let avg_workouts_per_user_per_week = if member_count > 0 && weeks_elapsed > 0 {
+        round(workouts.len() as f64 / member_count as f64 / weeks_elapsed as f64, 2)
+    } else {
+        0.0
+    };
+
+    LeagueAnalytics {
+        league_id,
+        season_id: season.id,
+        season_name: season.name.clone(),
+        generated_at: now,
+        member_count,
+        weeks_elapsed,
+        participation_rate: rate(first_week.len()),
+        avg_workouts_per_user_per_week,
+        never_active: member_count - first_week.len(),
+        weekly,
+        workout_score_distribution: score_distribution(workouts.iter().map(|w| w.points).collect()),
+        member_score_distribution: score_distribution(member_points.into_values().collect()),
+        drop_off_cohorts,
+    }
+}
+
+const SCORE_BUCKETS: usize = 10;
+
+fn score_distribution(mut values: Vec<f64>) -> ScoreDistribution {
+    if values.is_empty() {
+        return ScoreDistribution::default();
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    // Nearest-rank percentile
+    let percentile = |p: f64| values[((p * values.len() as f64).ceil() as usize).clamp(1, values.len()) - 1];
+    let (min, max) = (values[0], values[values.len() - 1]);
+
+    let width = (max - min) / SCORE_BUCKETS as f64;
+    let buckets = if width > 0.0 {
+        let mut counts = [0usize; SCORE_BUCKETS];
+        for value in &values {
+            counts[(((value - min) / width) as usize).min(SCORE_BUCKETS - 1)] += 1;
+        }
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| ScoreBucket {
+                from: round(min + width * i as f64, 1),
+                to: round(min + width * (i + 1) as f64, 1),
+                count: *count,
+            })
+            .collect()
+    } else {
+        vec![ScoreBucket { from: round(min, 1), to: round(max, 1), count: values.len() }]
+    };
+
+    ScoreDistribution {
+        count: values.len(),
+        min: round(min, 1),
+        p25: round(percentile(0.25), 1),
+        median: round(percentile(0.5), 1),
+        p75: round(percentile(0.75), 1),
+        p90: round(percentile(0.9), 1),
+        max: round(max, 1),
+        mean: round(values.iter().sum::<f64>() / values.len() as f64, 1),
+        buckets,
+    }
+}
+
+fn round(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
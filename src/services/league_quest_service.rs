@@ -0,0 +1,355 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::league::quests;
+use crate::models::game_events::GameEvent;
+use crate::models::quest::{CreateLeagueQuestRequest, LeagueQuest, QuestContributor, QuestMilestone, ReachedQuestMilestone};
+use crate::services::event_outbox;
+use crate::services::notification_delivery::{deliver_to_enabled_channels, ChannelNotification};
+
+/// Contributors listed with each quest
+const TOP_CONTRIBUTORS: i64 = 5;
+
+struct QuestRow {
+    id: Uuid,
+    league_id: Uuid,
+    name: String,
+    description: Option<String>,
+    target_points: f32,
+    unit_label: String,
+    milestone_percents: Vec<i32>,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+/// League quests: collective targets every member's workouts count toward
+pub struct LeagueQuestService {
+    pool: PgPool,
+}
+
+impl LeagueQuestService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn league_exists(&self, league_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM leagues WHERE id = $1) as "exists!""#, league_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Whether the user plays on one of the league's teams
+    pub async fn is_member(&self, league_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM team_members tm
+                JOIN teams t ON t.id = tm.team_id
+                WHERE t.league_id = $1 AND tm.user_id = $2 AND tm.status = 'active'
+            ) as "exists!"
+            "#,
+            league_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn create(
+        &self,
+        league_id: Uuid,
+        created_by: Uuid,
+        request: &CreateLeagueQuestRequest,
+    ) -> Result<LeagueQuest, sqlx::Error> {
+        let row = sqlx::query_as!(
+            QuestRow,
+            r#"
+            INSERT INTO league_quests (
+                league_id, name, description, target_points, unit_label, milestone_percents, starts_at, ends_at, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id, league_id, name, description, target_points, unit_label, milestone_percents,
+                      starts_at, ends_at, completed_at
+            "#,
+            league_id,
+            request.name.trim(),
+            request.description.as_deref(),
+            request.target_points,
+            request.unit_label.as_deref().map(str::trim).unwrap_or("points"),
+            &request.milestones(),
+            request.starts_at.unwrap_or_else(Utc::now),
+            request.ends_at,
+            created_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.with_progress(row).await
+    }
+
+    /// Quests of a league with their progress, newest first
+    pub async fn list(&self, league_id: Uuid) -> Result<Vec<LeagueQuest>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            QuestRow,
+            r#"
+            SELECT id, league_id, name, description, target_points, unit_label, milestone_percents,
+                   starts_at, ends_at, completed_at
+            FROM league_quests
+            WHERE league_id = $1
+            ORDER BY starts_at DESC
+            "#,
+            league_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut result = Vec::with_capacity(rows.len());
+        for row in rows {
+            result.push(self.with_progress(row).await?);
+        }
+        Ok(result)
+    }
+
+    async fn with_progress(&self, quest: QuestRow) -> Result<LeagueQuest, sqlx::Error> {
+        let totals = sqlx::query!(
+            r#"
+            SELECT COALESCE(SUM(points), 0)::float8 as "progress!", COUNT(DISTINCT user_id) as "contributors!"
+            FROM league_quest_contributions
+            WHERE quest_id = $1
+            "#,
+            quest.id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let top_contributors = sqlx::query_as!(
+            QuestContributor,
+            r#"
+            SELECT c.user_id, u.username, SUM(c.points)::float8 as "points!", COUNT(*) as "workouts!"
+            FROM league_quest_contributions c
+            JOIN users u ON u.id = c.user_id
+            WHERE c.quest_id = $1
+            GROUP BY c.user_id, u.username
+            ORDER BY SUM(c.points) DESC
+            LIMIT $2
+            "#,
+            quest.id,
+            TOP_CONTRIBUTORS
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let milestones = sqlx::query!(
+            r#"
+            SELECT m.percent, m.progress_points, m.reached_by, u.username as "reached_by_username?", m.reached_at
+            FROM league_quest_milestones m
+            LEFT JOIN users u ON u.id = m.reached_by
+            WHERE m.quest_id = $1
+            ORDER BY m.reached_at DESC, m.percent DESC
+            "#,
+            quest.id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|m| QuestMilestone {
+            message: quests::milestone_message(
+                &quest.name,
+                m.percent,
+                m.progress_points as f64,
+                quest.target_points as f64,
+                &quest.unit_label,
+            ),
+            percent: m.percent,
+            progress_points: m.progress_points,
+            reached_by: m.reached_by,
+            reached_by_username: m.reached_by_username,
+            reached_at: m.reached_at,
+        })
+        .collect();
+
+        Ok(LeagueQuest {
+            progress_percent: quests::progress_percent(quest.target_points as f64, totals.progress),
+            progress_points: totals.progress,
+            contributor_count: totals.contributors,
+            top_contributors,
+            milestones,
+            id: quest.id,
+            league_id: quest.league_id,
+            name: quest.name,
+            description: quest.description,
+            target_points: quest.target_points,
+            unit_label: quest.unit_label,
+            milestone_percents: quest.milestone_percents,
+            starts_at: quest.starts_at,
+            ends_at: quest.ends_at,
+            completed_at: quest.completed_at,
+        })
+    }
+
+    /// Tell every member of the league about milestones an upload reached
+    pub async fn notify_members(&self, reached: &[ReachedQuestMilestone]) {
+        for milestone in reached {
+            let members = sqlx::query_scalar!(
+                r#"
+                SELECT DISTINCT tm.user_id
+                FROM team_members tm
+                JOIN teams t ON t.id = tm.team_id
+                WHERE t.league_id = $1 AND tm.status = 'active'
+                "#,
+                milestone.league_id
+            )
+            .fetch_all(&self.pool)
+            .await;
+
+            let members = match members {
+                Ok(members) => members,
+                Err(e) => {
+                    tracing::error!("Failed to load members of league {} for quest milestone: {}", milestone.league_id, e);
+                    continue;
+                }
+            };
+
+            for member_id in members {
+                let notification = ChannelNotification {
+                    recipient_id: member_id,
+                    actor_id: milestone.reached_by,
+                    notification_type: "quest_milestone".to_string(),
+                    entity_type: "league_quest".to_string(),
+                    entity_id: milestone.quest_id,
+                    title: milestone.quest_name.clone(),
+                    message: milestone.message.clone(),
+                    push_category: "league_update".to_string(),
+                };
+                if let Err(e) = deliver_to_enabled_channels(&self.pool, &notification).await {
+                    tracing::error!("Failed to notify {} about quest milestone: {}", member_id, e);
+                }
+            }
+        }
+    }
+}
+
+/// Count a workout toward the open quests of the user's leagues, within the upload transaction.
+/// Returns the milestones it reached; their WebSocket events are already in the outbox.
+pub async fn record_workout(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_data_id: Uuid,
+    workout_start: DateTime<Utc>,
+    points: f32,
+) -> Result<Vec<(ReachedQuestMilestone, Uuid)>, sqlx::Error> {
+    if points <= 0.0 {
+        return Ok(Vec::new());
+    }
+
+    // Locking the quests keeps concurrent uploads from both claiming a milestone's crossing
+    let open_quests = sqlx::query_as!(
+        QuestRow,
+        r#"
+        SELECT q.id, q.league_id, q.name, q.description, q.target_points, q.unit_label, q.milestone_percents,
+               q.starts_at, q.ends_at, q.completed_at
+        FROM league_quests q
+        WHERE q.completed_at IS NULL
+        AND q.starts_at <= $2
+        AND (q.ends_at IS NULL OR $2 < q.ends_at)
+        AND q.league_id IN (
+            SELECT t.league_id FROM team_members tm
+            JOIN teams t ON t.id = tm.team_id
+            WHERE tm.user_id = $1 AND tm.status = 'active' AND t.league_id IS NOT NULL
+        )
+        FOR UPDATE OF q
+        "#,
+        user_id,
+        workout_start
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut reached = Vec::new();
+    for quest in open_quests {
+        let counted = sqlx::query!(
+            r#"
+            INSERT INTO league_quest_contributions (quest_id, workout_data_id, user_id, points)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (quest_id, workout_data_id) DO NOTHING
+            "#,
+            quest.id,
+            workout_data_id,
+            user_id,
+            points
+        )
+        .execute(&mut *conn)
+        .await?
+        .rows_affected() > 0;
+        if !counted {
+            continue;
+        }
+
+        let after = sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(points), 0)::float8 as "progress!" FROM league_quest_contributions WHERE quest_id = $1"#,
+            quest.id
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        let before = after - points as f64;
+        let target = quest.target_points as f64;
+
+        for percent in quests::crossed_milestones(&quest.milestone_percents, target, before, after) {
+            let recorded = sqlx::query!(
+                r#"
+                INSERT INTO league_quest_milestones (quest_id, percent, progress_points, reached_by, workout_data_id)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (quest_id, percent) DO NOTHING
+                "#,
+                quest.id,
+                percent,
+                after as f32,
+                user_id,
+                workout_data_id
+            )
+            .execute(&mut *conn)
+            .await?
+            .rows_affected() > 0;
+            if !recorded {
+                continue;
+            }
+
+            let milestone = ReachedQuestMilestone {
+                quest_id: quest.id,
+                league_id: quest.league_id,
+                quest_name: quest.name.clone(),
+                percent,
+                message: quests::milestone_message(&quest.name, percent, after, target, &quest.unit_label),
+                reached_by: user_id,
+            };
+            let event = GameEvent::QuestMilestoneReached {
+                quest_id: quest.id,
+                league_id: quest.league_id,
+                quest_name: quest.name.clone(),
+                percent,
+                progress_points: after,
+                target_points: target,
+                message: milestone.message.clone(),
+                reached_by: user_id,
+                timestamp: Utc::now(),
+            };
+            let payload = serde_json::to_value(&event).unwrap_or_default();
+            let event_id = event_outbox::enqueue(&mut *conn, &["game:events:global".to_string()], &payload).await?;
+            reached.push((milestone, event_id));
+        }
+
+        if after >= target {
+            sqlx::query!(
+                "UPDATE league_quests SET completed_at = NOW(), updated_at = NOW() WHERE id = $1",
+                quest.id
+            )
+            .execute(&mut *conn)
+            .await?;
+            tracing::info!("🏔️ League {} completed quest {}", quest.league_id, quest.name);
+        }
+    }
+
+    Ok(reached)
+}
@@ -0,0 +1,123 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::game::boosters::MAX_HELD_PER_TYPE;
+use crate::league::constants::MAX_TEAM_SIZE;
+use crate::models::league::{LeagueCaps, LeagueRulesPage, SetLeagueRulesRequest, TransferWindow};
+
+/// Written rules of leagues, combined with their configured limits into the rules page
+pub struct LeagueRulesService {
+    pool: PgPool,
+}
+
+impl LeagueRulesService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The league's rules page. Returns None if the league doesn't exist.
+    pub async fn page(&self, league_id: Uuid) -> Result<Option<LeagueRulesPage>, sqlx::Error> {
+        let Some(league) = sqlx::query!(
+            r#"
+            SELECT l.name, l.max_teams, l.comeback_multiplier,
+                r.scoring_summary as "scoring_summary?", r.forfeit_policy, r.updated_at as "updated_at?"
+            FROM leagues l
+            LEFT JOIN league_rules r ON r.league_id = l.id
+            WHERE l.id = $1
+            "#,
+            league_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let transfer_windows = sqlx::query_as!(
+            TransferWindow,
+            "SELECT opens_at, closes_at, note FROM league_transfer_windows WHERE league_id = $1 ORDER BY opens_at",
+            league_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let now = Utc::now();
+
+        Ok(Some(LeagueRulesPage {
+            league_id,
+            league_name: league.name,
+            scoring_summary: league.scoring_summary,
+            comeback_multiplier: league.comeback_multiplier,
+            caps: LeagueCaps {
+                max_teams: league.max_teams,
+                max_team_size: MAX_TEAM_SIZE,
+                max_held_boosters_per_type: MAX_HELD_PER_TYPE,
+            },
+            forfeit_policy: league.forfeit_policy,
+            transfer_window_open: transfer_windows.iter().any(|window| window.is_open_at(now)),
+            transfer_windows,
+            updated_at: league.updated_at,
+        }))
+    }
+
+    /// Replace the league's written rules and transfer windows. Returns None if the league doesn't exist.
+    pub async fn set_rules(
+        &self,
+        league_id: Uuid,
+        request: &SetLeagueRulesRequest,
+        admin_id: Uuid,
+    ) -> Result<Option<LeagueRulesPage>, sqlx::Error> {
+        let league_exists = sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM leagues WHERE id = $1)", league_id)
+            .fetch_one(&self.pool)
+            .await?
+            .unwrap_or(false);
+        if !league_exists {
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO league_rules (league_id, scoring_summary, forfeit_policy, updated_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (league_id) DO UPDATE SET
+                scoring_summary = EXCLUDED.scoring_summary,
+                forfeit_policy = EXCLUDED.forfeit_policy,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            "#,
+            league_id,
+            request.scoring_summary.trim(),
+            request.forfeit_policy.as_deref().map(str::trim),
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!("DELETE FROM league_transfer_windows WHERE league_id = $1", league_id)
+            .execute(&mut *tx)
+            .await?;
+        for window in &request.transfer_windows {
+            sqlx::query!(
+                "INSERT INTO league_transfer_windows (league_id, opens_at, closes_at, note) VALUES ($1, $2, $3, $4)",
+                league_id,
+                window.opens_at,
+                window.closes_at,
+                window.note.as_deref().map(str::trim).filter(|note| !note.is_empty())
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.page(league_id).await
+    }
+
+    /// Remove the league's written rules and transfer windows. Returns whether it had any.
+    pub async fn remove_rules(&self, league_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM league_rules WHERE league_id = $1", league_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,289 @@
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::league::result_card::ResultCard;
+use crate::models::league::{
+    ChatProvider, FailedWebhookDelivery, LeagueChatWebhook, SetLeagueChatWebhookRequest,
+};
+
+/// Result cards are posted at most this many times before they need a manual retry
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 5;
+
+/// Wait before the next attempt after `attempts` failed ones: 2, 4, 8, 16 minutes, at most an hour
+pub fn retry_delay(attempts: i32) -> Duration {
+    Duration::minutes(2i64.pow(attempts.clamp(1, 6) as u32).min(60))
+}
+
+/// Cross-posts game results to the Slack and Microsoft Teams channels of a league
+pub struct LeagueWebhookService {
+    pool: PgPool,
+    client: Client,
+}
+
+impl LeagueWebhookService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, client: Client::new() }
+    }
+
+    pub async fn webhooks(&self, league_id: Uuid) -> Result<Vec<LeagueChatWebhook>, sqlx::Error> {
+        sqlx::query_as!(
+            LeagueChatWebhook,
+            r#"
+            SELECT id, league_id, provider as "provider: ChatProvider", webhook_url, enabled,
+                last_delivered_at, last_error, last_error_at, created_at, updated_at
+            FROM league_chat_webhooks
+            WHERE league_id = $1
+            ORDER BY provider
+            "#,
+            league_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Undelivered result cards of a league that failed at least once, latest first
+    pub async fn failed_deliveries(&self, league_id: Uuid) -> Result<Vec<FailedWebhookDelivery>, sqlx::Error> {
+        sqlx::query_as!(
+            FailedWebhookDelivery,
+            r#"
+            SELECT d.id, w.provider as "provider: ChatProvider", d.game_id, d.attempts,
+                CASE WHEN d.attempts < $2 THEN d.next_attempt_at END as next_attempt_at,
+                d.last_error, d.created_at
+            FROM league_webhook_deliveries d
+            JOIN league_chat_webhooks w ON w.id = d.webhook_id
+            WHERE w.league_id = $1 AND d.delivered_at IS NULL AND d.attempts > 0
+            ORDER BY d.created_at DESC
+            LIMIT 50
+            "#,
+            league_id,
+            MAX_DELIVERY_ATTEMPTS
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Set the league's webhook of a provider. Returns None if the league doesn't exist.
+    pub async fn set_webhook(
+        &self,
+        league_id: Uuid,
+        request: &SetLeagueChatWebhookRequest,
+        admin_id: Uuid,
+    ) -> Result<Option<LeagueChatWebhook>, sqlx::Error> {
+        let league_exists = sqlx::query_scalar!("SELECT EXISTS(SELECT 1 FROM leagues WHERE id = $1)", league_id)
+            .fetch_one(&self.pool)
+            .await?
+            .unwrap_or(false);
+        if !league_exists {
+            return Ok(None);
+        }
+
+        // A new URL starts over without the errors of the old one
+        sqlx::query_as!(
+            LeagueChatWebhook,
+            r#"
+            INSERT INTO league_chat_webhooks (league_id, provider, webhook_url, enabled, created_by)
+            VALUES ($1, $2, $3, COALESCE($4, TRUE), $5)
+            ON CONFLICT (league_id, provider) DO UPDATE SET
+                webhook_url = EXCLUDED.webhook_url,
+                enabled = COALESCE($4, league_chat_webhooks.enabled),
+                last_error = CASE WHEN league_chat_webhooks.webhook_url = EXCLUDED.webhook_url
+                    THEN league_chat_webhooks.last_error END,
+                last_error_at = CASE WHEN league_chat_webhooks.webhook_url = EXCLUDED.webhook_url
+                    THEN league_chat_webhooks.last_error_at END,
+                updated_at = NOW()
+            RETURNING id, league_id, provider as "provider: ChatProvider", webhook_url, enabled,
+                last_delivered_at, last_error, last_error_at, created_at, updated_at
+            "#,
+            league_id,
+            request.provider as ChatProvider,
+            request.webhook_url,
+            request.enabled,
+            admin_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map(Some)
+    }
+
+    pub async fn remove_webhook(&self, league_id: Uuid, provider: ChatProvider) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM league_chat_webhooks WHERE league_id = $1 AND provider = $2",
+            league_id,
+            provider as ChatProvider
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queue and post the result cards of freshly evaluated games. Returns how many went out;
+    /// failed ones are retried by `deliver_pending`.
+    pub async fn post_results(&self, game_ids: &[Uuid]) -> Result<usize, sqlx::Error> {
+        if game_ids.is_empty() {
+            return Ok(0);
+        }
+        let delivery_ids = sqlx::query_scalar!(
+            r#"
+            INSERT INTO league_webhook_deliveries (webhook_id, game_id)
+            SELECT w.id, g.id
+            FROM games g
+            JOIN league_seasons s ON s.id = g.season_id
+            JOIN league_chat_webhooks w ON w.league_id = s.league_id AND w.enabled
+            WHERE g.id = ANY($1)
+            ON CONFLICT (webhook_id, game_id) DO NOTHING
+            RETURNING id
+            "#,
+            game_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.deliver(&delivery_ids).await
+    }
+
+    /// Retry every failed result card whose backoff is over. Returns how many went out.
+    pub async fn deliver_pending(&self) -> Result<usize, sqlx::Error> {
+        let due = sqlx::query_scalar!(
+            r#"
+            SELECT d.id FROM league_webhook_deliveries d
+            JOIN league_chat_webhooks w ON w.id = d.webhook_id
+            WHERE d.delivered_at IS NULL AND d.attempts < $1 AND d.next_attempt_at <= NOW() AND w.enabled
+            ORDER BY d.next_attempt_at ASC
+            LIMIT 100
+            "#,
+            MAX_DELIVERY_ATTEMPTS
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.deliver(&due).await
+    }
+
+    /// Post a failed result card of the league again right away, with fresh attempts.
+    /// Returns None if the league has no such undelivered card, otherwise whether it went out.
+    pub async fn retry(&self, league_id: Uuid, delivery_id: Uuid) -> Result<Option<bool>, sqlx::Error> {
+        let reset = sqlx::query!(
+            r#"
+            UPDATE league_webhook_deliveries d SET attempts = 0, next_attempt_at = NOW()
+            FROM league_chat_webhooks w
+            WHERE d.id = $1 AND w.id = d.webhook_id AND w.league_id = $2 AND d.delivered_at IS NULL
+            "#,
+            delivery_id,
+            league_id
+        )
+        .execute(&self.pool)
+        .await?;
+        if reset.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.deliver(&[delivery_id]).await? > 0))
+    }
+
+    /// Post the given result cards, built from the game as it is now.
+    /// Returns how many went out; failures are recorded on the delivery and its webhook.
+    async fn deliver(&self, delivery_ids: &[Uuid]) -> Result<usize, sqlx::Error> {
+        if delivery_ids.is_empty() {
+            return Ok(0);
+        }
+
+        // Lock the deliveries so the evaluation and the scheduler never both post one
+        let mut tx = self.pool.begin().await?;
+        let deliveries = sqlx::query!(
+            r#"
+            SELECT d.id, d.webhook_id, d.attempts, w.provider as "provider: ChatProvider", w.webhook_url,
+                l.name as league_name, g.week_number, ht.team_name as home_team, at.team_name as away_team,
+                g.home_score, g.away_score,
+                gs.mvp_username as "mvp_username?", gs.mvp_score_contribution as "mvp_score_contribution?"
+            FROM league_webhook_deliveries d
+            JOIN league_chat_webhooks w ON w.id = d.webhook_id
+            JOIN games g ON g.id = d.game_id
+            JOIN league_seasons s ON s.id = g.season_id
+            JOIN leagues l ON l.id = s.league_id
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            LEFT JOIN game_summaries gs ON gs.game_id = g.id
+            WHERE d.id = ANY($1) AND d.delivered_at IS NULL
+            FOR UPDATE OF d SKIP LOCKED
+            "#,
+            delivery_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut delivered = 0;
+        for delivery in deliveries {
+            let card = ResultCard {
+                league_name: delivery.league_name,
+                week_number: delivery.week_number,
+                home_team: delivery.home_team,
+                away_team: delivery.away_team,
+                home_score: delivery.home_score,
+                away_score: delivery.away_score,
+                mvp: delivery.mvp_username.zip(delivery.mvp_score_contribution),
+            };
+
+            match self.post(&delivery.webhook_url, &card.payload(delivery.provider)).await {
+                Ok(()) => {
+                    sqlx::query!(
+                        "UPDATE league_webhook_deliveries SET delivered_at = NOW(), attempts = attempts + 1, last_error = NULL WHERE id = $1",
+                        delivery.id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                    sqlx::query!(
+                        "UPDATE league_chat_webhooks SET last_delivered_at = NOW() WHERE id = $1",
+                        delivery.webhook_id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                    delivered += 1;
+                }
+                Err(error) => {
+                    tracing::warn!("⚠️ Failed to post result card {} to {:?}: {}", delivery.id, delivery.provider, error);
+                    let next_attempt_at = Utc::now() + retry_delay(delivery.attempts + 1);
+                    sqlx::query!(
+                        r#"
+                        UPDATE league_webhook_deliveries
+                        SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3
+                        WHERE id = $1
+                        "#,
+                        delivery.id,
+                        error,
+                        next_attempt_at
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                    sqlx::query!(
+                        "UPDATE league_chat_webhooks SET last_error = $2, last_error_at = NOW() WHERE id = $1",
+                        delivery.webhook_id,
+                        error
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+        tx.commit().await?;
+
+        Ok(delivered)
+    }
+
+    async fn post(&self, webhook_url: &str, payload: &serde_json::Value) -> Result<(), String> {
+        let response = self
+            .client
+            .post(webhook_url)
+            .json(payload)
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("{status}: {}", body.chars().take(500).collect::<String>()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+/// How far back server errors are remembered. Longer monitor windows are capped to this.
+pub const MAX_METRICS_WINDOW: Duration = Duration::from_secs(300);
+
+/// In-process tracker for server errors (5xx responses) of this instance. Uploads and score
+/// events are read from the database instead, so they cover all instances.
+#[derive(Debug, Default)]
+pub struct LiveMetrics {
+    server_errors: Mutex<VecDeque<Instant>>,
+}
+
+impl LiveMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_server_error(&self) {
+        let now = Instant::now();
+        let mut errors = self.server_errors.lock().unwrap_or_else(|e| e.into_inner());
+        errors.push_back(now);
+        Self::prune(&mut errors, now);
+    }
+
+    /// Number of server errors within the last `window`
+    pub fn server_errors_within(&self, window: Duration) -> usize {
+        let now = Instant::now();
+        let mut errors = self.server_errors.lock().unwrap_or_else(|e| e.into_inner());
+        Self::prune(&mut errors, now);
+        errors.iter().filter(|at| now.duration_since(**at) <= window).count()
+    }
+
+    fn prune(errors: &mut VecDeque<Instant>, now: Instant) {
+        while errors.front().is_some_and(|at| now.duration_since(*at) > MAX_METRICS_WINDOW) {
+            errors.pop_front();
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LiveMetricsSnapshot {
+    pub event_type: &'static str,
+    pub window_secs: u64,
+    pub uploads: i64,
+    pub uploads_per_sec: f64,
+    pub score_events: i64,
+    pub score_events_per_sec: f64,
+    pub active_games: i64,
+    pub errors: i64,
+    pub errors_per_sec: f64,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Aggregates the live metrics streamed to the ops dashboard
+pub struct LiveMetricsService {
+    pool: PgPool,
+    metrics: Arc<LiveMetrics>,
+}
+
+impl LiveMetricsService {
+    pub fn new(pool: PgPool, metrics: Arc<LiveMetrics>) -> Self {
+        Self { pool, metrics }
+    }
+
+    /// Counts and rates over the last `window`
+    pub async fn snapshot(&self, window: Duration) -> Result<LiveMetricsSnapshot, sqlx::Error> {
+        let window = window.clamp(Duration::from_secs(1), MAX_METRICS_WINDOW);
+        let window_secs = window.as_secs();
+
+        let counts = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM workout_data
+                 WHERE created_at >= NOW() - make_interval(secs => $1)) as "uploads!",
+                (SELECT COUNT(*) FROM live_score_events
+                 WHERE occurred_at >= NOW() - make_interval(secs => $1)) as "score_events!",
+                (SELECT COUNT(*) FROM games WHERE status = 'in_progress') as "active_games!"
+            "#,
+            window_secs as f64
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let errors = self.metrics.server_errors_within(window) as i64;
+        let per_sec = |count: i64| count as f64 / window_secs as f64;
+
+        Ok(LiveMetricsSnapshot {
+            event_type: "live_metrics",
+            window_secs,
+            uploads: counts.uploads,
+            uploads_per_sec: per_sec(counts.uploads),
+            score_events: counts.score_events,
+            score_events_per_sec: per_sec(counts.score_events),
+            active_games: counts.active_games,
+            errors,
+            errors_per_sec: per_sec(errors),
+            captured_at: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::chat::get_user_team_ids;
+use crate::db::game_repo::{GameRepo, GameRepository, LiveGameState};
+use crate::league::seasons::SeasonService;
+use crate::models::game_events::{GameEvent, LiveGameSnapshot, ScoreEventSummary, StateSyncReason};
+
+/// Scoring events sent along with each game, enough to refill a client's ticker
+const RECENT_EVENTS_PER_GAME: i64 = 10;
+
+/// Redis channel carrying the score deltas of a season's games to spectating clients
+pub fn season_scores_channel(season_id: Uuid) -> String {
+    format!("game:events:season:{season_id}")
+}
+
+/// Builds the `state_sync` frames WebSocket clients reconcile their live scores with
+pub struct LiveStateSyncService {
+    pool: PgPool,
+}
+
+impl LiveStateSyncService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// State of the games in progress of the user's teams, or of one game when the client
+    /// asks for it, e.g. to get the final score of a game that ended while it was offline
+    pub async fn state_sync(
+        &self,
+        user_id: Uuid,
+        game_id: Option<Uuid>,
+        reason: StateSyncReason,
+    ) -> Result<GameEvent, sqlx::Error> {
+        let repo = GameRepo::new(self.pool.clone());
+        let games = match game_id {
+            Some(game_id) => repo.find_live_game_state(game_id).await?.into_iter().collect(),
+            None => {
+                let team_ids = get_user_team_ids(&self.pool, user_id).await?;
+                if team_ids.is_empty() {
+                    Vec::new()
+                } else {
+                    repo.find_live_games_of_teams(&team_ids).await?
+                }
+            }
+        };
+
+        self.sync_games(&repo, games, reason).await
+    }
+
+    /// State of one game, for guests following it with a spectator token
+    pub async fn game_state_sync(&self, game_id: Uuid, reason: StateSyncReason) -> Result<GameEvent, sqlx::Error> {
+        let repo = GameRepo::new(self.pool.clone());
+        let games = repo.find_live_game_state(game_id).await?.into_iter().collect();
+        self.sync_games(&repo, games, reason).await
+    }
+
+    async fn sync_games(
+        &self,
+        repo: &GameRepo,
+        games: Vec<LiveGameState>,
+        reason: StateSyncReason,
+    ) -> Result<GameEvent, sqlx::Error> {
+        let game_ids: Vec<Uuid> = games.iter().map(|game| game.id).collect();
+        let events = if game_ids.is_empty() {
+            Vec::new()
+        } else {
+            repo.find_recent_score_events(&game_ids, RECENT_EVENTS_PER_GAME).await?
+        };
+
+        Ok(build_state_sync(reason, games, events, Utc::now()))
+    }
+
+    /// Scores of all games in progress of a season, `None` if there is no such season
+    pub async fn season_scoreboard(&self, season_id: Uuid) -> Result<Option<GameEvent>, sqlx::Error> {
+        if SeasonService::new(self.pool.clone()).get_season(season_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let games = GameRepo::new(self.pool.clone()).find_live_games_of_season(season_id).await?;
+        let now = Utc::now();
+        Ok(Some(GameEvent::SeasonScoreboard {
+            season_id,
+            server_time: now,
+            games: games.into_iter().map(|game| snapshot(game, Vec::new(), now)).collect(),
+        }))
+    }
+}
+
+/// Pair each game with its scoring events, which must come newest first
+pub fn build_state_sync(
+    reason: StateSyncReason,
+    games: Vec<LiveGameState>,
+    events: Vec<ScoreEventSummary>,
+    now: DateTime<Utc>,
+) -> GameEvent {
+    let games = games
+        .into_iter()
+        .map(|game| {
+            let recent_events = events.iter().filter(|event| event.game_id == game.id).cloned().collect();
+            snapshot(game, recent_events, now)
+        })
+        .collect();
+
+    GameEvent::StateSync {
+        reason,
+        server_time: now,
+        games,
+    }
+}
+
+fn snapshot(game: LiveGameState, recent_events: Vec<ScoreEventSummary>, now: DateTime<Utc>) -> LiveGameSnapshot {
+    LiveGameSnapshot {
+        game_id: game.id,
+        week_number: game.week_number,
+        game_progress: game.progress_percent(now),
+        home_score: game.home_score.max(0) as u32,
+        away_score: game.away_score.max(0) as u32,
+        recent_events,
+        status: game.status,
+        home_team_id: game.home_team_id,
+        home_team_name: game.home_team_name,
+        away_team_id: game.away_team_id,
+        away_team_name: game.away_team_name,
+        game_start_time: game.game_start_time,
+        game_end_time: game.game_end_time,
+    }
+}
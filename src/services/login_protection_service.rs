@@ -0,0 +1,293 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::HttpRequest;
+use redis::{AsyncCommands, RedisResult};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::login_protection::LoginProtectionSettings;
+use crate::db::login_activity::{known_login_address, record_login_attempt, record_security_event, LoginContext};
+use crate::models::login_activity::{LoginFailure, SecurityEventType};
+use crate::utils::client_ip::{client_address, TrustedProxy};
+
+const KEY_PREFIX: &str = "login_protection";
+
+/// How long lockouts in a row keep doubling the next one
+const LOCKOUT_MEMORY_SECS: usize = 24 * 60 * 60;
+
+/// What failed logins are counted against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginSubject {
+    Account(Uuid),
+    Address(String),
+}
+
+impl LoginSubject {
+    fn key(&self, kind: &str) -> String {
+        match self {
+            LoginSubject::Account(user_id) => format!("{KEY_PREFIX}:{kind}:account:{user_id}"),
+            LoginSubject::Address(ip_address) => format!("{KEY_PREFIX}:{kind}:address:{ip_address}"),
+        }
+    }
+}
+
+/// A lockout started by a failed login
+#[derive(Debug)]
+pub struct Lockout {
+    pub subject: LoginSubject,
+    pub duration: Duration,
+    /// Lockouts of the subject in a row, this one included
+    pub lockouts_in_row: u32,
+}
+
+/// Length of the n-th lockout in a row: the base length, doubled for each earlier lockout
+pub fn lockout_duration(settings: &LoginProtectionSettings, lockouts_in_row: u32) -> Duration {
+    let doublings = lockouts_in_row.saturating_sub(1).min(32);
+    let secs = settings.base_lockout_secs.saturating_mul(1u64 << doublings);
+    Duration::from_secs(secs.min(settings.max_lockout_secs))
+}
+
+/// Why a successful login looks suspicious, if it does
+pub fn suspicious_login_reasons(
+    settings: &LoginProtectionSettings,
+    new_address: bool,
+    failures_before: u32,
+) -> Vec<&'static str> {
+    let mut reasons = Vec::new();
+    if new_address {
+        reasons.push("new_address");
+    }
+    // Most of the way to a lockout, so the password may have been guessed
+    if failures_before > 0 && failures_before * 2 >= settings.max_failed_attempts {
+        reasons.push("after_failed_attempts");
+    }
+    reasons
+}
+
+/// Counts failed logins per account and per IP address in Redis, locks either after too
+/// many, and keeps the login history and security events in Postgres. Redis outages don't
+/// stop anyone from logging in; lockouts just aren't enforced meanwhile.
+pub struct LoginProtectionService {
+    pool: PgPool,
+    redis_client: Arc<redis::Client>,
+    settings: LoginProtectionSettings,
+    trusted_proxies: Vec<TrustedProxy>,
+}
+
+impl LoginProtectionService {
+    /// `trusted_proxies` are the parsed `settings.trusted_proxies()`
+    pub fn new(
+        pool: PgPool,
+        redis_client: Arc<redis::Client>,
+        settings: LoginProtectionSettings,
+        trusted_proxies: Vec<TrustedProxy>,
+    ) -> Self {
+        Self { pool, redis_client, settings, trusted_proxies }
+    }
+
+    /// Address failed logins of the request are counted against
+    pub fn client_address(&self, req: &HttpRequest) -> Option<String> {
+        client_address(req, &self.trusted_proxies)
+    }
+
+    fn subjects(login: &LoginContext) -> Vec<LoginSubject> {
+        login.user_id.map(LoginSubject::Account).into_iter()
+            .chain(login.ip_address.clone().map(LoginSubject::Address))
+            .collect()
+    }
+
+    fn threshold(&self, subject: &LoginSubject) -> u32 {
+        match subject {
+            LoginSubject::Account(_) => self.settings.max_failed_attempts,
+            LoginSubject::Address(_) => self.settings.max_failed_attempts_per_ip,
+        }
+    }
+
+    /// How long until the account or address of the login may try again, if it is locked
+    pub async fn locked_for(&self, login: &LoginContext) -> Option<Duration> {
+        let remaining = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            let mut longest: Option<Duration> = None;
+            for subject in Self::subjects(login) {
+                let ttl: i64 = conn.ttl(subject.key("locked")).await?;
+                if ttl > 0 {
+                    longest = longest.max(Some(Duration::from_secs(ttl as u64)));
+                }
+            }
+            RedisResult::Ok(longest)
+        }
+        .await;
+
+        remaining.unwrap_or_else(|e| {
+            tracing::error!("Failed to check login lockout, letting the login through: {}", e);
+            None
+        })
+    }
+
+    /// Record a turned down login, and lock its account or address once they failed too often
+    pub async fn login_failed(&self, login: &LoginContext, failure: LoginFailure) {
+        if let Err(e) = record_login_attempt(&self.pool, login, Some(failure)).await {
+            tracing::error!("Failed to record failed login of {}: {:?}", login.username, e);
+        }
+        // Attempts while locked don't extend the lockout
+        if failure == LoginFailure::Locked {
+            return;
+        }
+
+        let lockouts = match self.count_failure(login).await {
+            Ok(lockouts) => lockouts,
+            Err(e) => {
+                tracing::error!("Failed to count failed login of {}: {}", login.username, e);
+                return;
+            }
+        };
+
+        for lockout in lockouts {
+            let (event_type, user_id) = match &lockout.subject {
+                LoginSubject::Account(user_id) => (SecurityEventType::AccountLocked, Some(*user_id)),
+                LoginSubject::Address(_) => (SecurityEventType::AddressBlocked, None),
+            };
+            tracing::warn!(
+                "🔒 {} after failed logins to {} from {:?}, for {}s",
+                event_type.as_str(), login.username, login.ip_address, lockout.duration.as_secs()
+            );
+            let details = json!({
+                "username": login.username,
+                "locked_for_secs": lockout.duration.as_secs(),
+                "lockouts_in_row": lockout.lockouts_in_row,
+            });
+            if let Err(e) = record_security_event(&self.pool, event_type, user_id, login.ip_address.as_deref(), details).await {
+                tracing::error!("Failed to record {} event: {:?}", event_type.as_str(), e);
+            }
+        }
+    }
+
+    async fn count_failure(&self, login: &LoginContext) -> RedisResult<Vec<Lockout>> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let mut lockouts = Vec::new();
+        for subject in Self::subjects(login) {
+            let failures_key = subject.key("failures");
+            let failures: u32 = conn.incr(&failures_key, 1).await?;
+            if failures == 1 {
+                conn.expire::<_, ()>(&failures_key, self.settings.failure_window_secs as usize).await?;
+            }
+            if failures < self.threshold(&subject) {
+                continue;
+            }
+
+            let lockouts_key = subject.key("lockouts");
+            let lockouts_in_row: u32 = conn.incr(&lockouts_key, 1).await?;
+            conn.expire::<_, ()>(&lockouts_key, LOCKOUT_MEMORY_SECS).await?;
+            let duration = lockout_duration(&self.settings, lockouts_in_row);
+            conn.set_ex::<_, _, ()>(subject.key("locked"), lockouts_in_row, duration.as_secs().max(1) as usize).await?;
+            conn.del::<_, ()>(&failures_key).await?;
+            lockouts.push(Lockout { subject, duration, lockouts_in_row });
+        }
+        Ok(lockouts)
+    }
+
+    /// Record a successful login, flag it when it looks suspicious and reset the failures of
+    /// the account. Failures of the address are kept, or one valid account would be enough
+    /// to keep guessing the passwords of others.
+    pub async fn login_succeeded(&self, login: &LoginContext) {
+        let Some(user_id) = login.user_id else {
+            return;
+        };
+
+        let new_address = match &login.ip_address {
+            Some(ip_address) => match known_login_address(&self.pool, user_id, ip_address).await {
+                Ok((logged_in_before, known_address)) => logged_in_before && !known_address,
+                Err(e) => {
+                    tracing::error!("Failed to look up earlier logins of user {}: {:?}", user_id, e);
+                    false
+                }
+            },
+            None => false,
+        };
+        let failures_before = match self.clear_failures(&LoginSubject::Account(user_id), false).await {
+            Ok(failures) => failures,
+            Err(e) => {
+                tracing::error!("Failed to reset failed logins of user {}: {}", user_id, e);
+                0
+            }
+        };
+
+        let reasons = suspicious_login_reasons(&self.settings, new_address, failures_before);
+        if !reasons.is_empty() {
+            tracing::warn!("🕵️ Suspicious login of user {} from {:?}: {:?}", user_id, login.ip_address, reasons);
+            let details = json!({
+                "username": login.username,
+                "reasons": reasons,
+                "failed_attempts_before": failures_before,
+                "user_agent": login.user_agent,
+            });
+            if let Err(e) = record_security_event(&self.pool, SecurityEventType::SuspiciousLogin, Some(user_id), login.ip_address.as_deref(), details).await {
+                tracing::error!("Failed to record suspicious login event: {:?}", e);
+            }
+        }
+
+        if let Err(e) = record_login_attempt(&self.pool, login, None).await {
+            tracing::error!("Failed to record login of user {}: {:?}", user_id, e);
+        }
+    }
+
+    /// Forget the failed logins of the subject, and with `unlock` its lockout too. Returns
+    /// the failed logins counted before.
+    async fn clear_failures(&self, subject: &LoginSubject, unlock: bool) -> RedisResult<u32> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let failures: Option<u32> = conn.get(subject.key("failures")).await?;
+        let mut keys = vec![subject.key("failures"), subject.key("lockouts")];
+        if unlock {
+            keys.push(subject.key("locked"));
+        }
+        conn.del::<_, ()>(keys).await?;
+        Ok(failures.unwrap_or(0))
+    }
+
+    /// Count a request for an email of the kind (password reset, sign-in link) to the email
+    /// address, and tell whether it may be sent. Limited per email address, so nobody's inbox
+    /// gets flooded, and per IP address, so they can't be tried one after another. Lets
+    /// requests through while Redis is down.
+    pub async fn allow_email_request(&self, kind: &str, email: &str, ip_address: Option<&str>) -> bool {
+        let email_key = format!("{KEY_PREFIX}:{kind}:email:{}", email.trim().to_lowercase());
+        let mut limits = vec![(email_key, self.settings.max_email_requests_per_email)];
+        if let Some(ip_address) = ip_address {
+            limits.push((format!("{KEY_PREFIX}:{kind}:address:{ip_address}"), self.settings.max_email_requests_per_ip));
+        }
+
+        let allowed = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            let mut allowed = true;
+            for (key, limit) in limits {
+                let requests: u32 = conn.incr(&key, 1).await?;
+                if requests == 1 {
+                    conn.expire::<_, ()>(&key, self.settings.email_request_window_secs as usize).await?;
+                }
+                allowed &= requests <= limit;
+            }
+            RedisResult::Ok(allowed)
+        }
+        .await;
+
+        allowed.unwrap_or_else(|e| {
+            tracing::error!("Failed to count {} request, letting it through: {}", kind, e);
+            true
+        })
+    }
+
+    /// Lift the lockout of an account right away. Returns whether it was locked.
+    pub async fn unlock_account(&self, user_id: Uuid, admin_id: Uuid) -> RedisResult<bool> {
+        let subject = LoginSubject::Account(user_id);
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let was_locked: bool = conn.exists(subject.key("locked")).await?;
+        self.clear_failures(&subject, true).await?;
+
+        let details = json!({ "unlocked_by": admin_id, "was_locked": was_locked });
+        if let Err(e) = record_security_event(&self.pool, SecurityEventType::AccountUnlocked, Some(user_id), None, details).await {
+            tracing::error!("Failed to record account unlock event: {:?}", e);
+        }
+        Ok(was_locked)
+    }
+}
@@ -3,16 +3,21 @@ use uuid::Uuid;
 
 use crate::models::league::LeagueGame; // Removed unused import: GameStatus
 use crate::db::game_queries::GameQueries;
+use crate::services::{TeamKitService, TeamNotificationService};
 
 /// Service for managing games in a season
 pub struct ManageGameService {
     game_queries: GameQueries,
+    team_notifications: TeamNotificationService,
+    team_kits: TeamKitService,
 }
 
 impl ManageGameService {
     pub fn new(pool: PgPool) -> Self {
-        let game_queries = GameQueries::new(pool);
-        Self { game_queries }
+        let game_queries = GameQueries::new(pool.clone());
+        let team_notifications = TeamNotificationService::new(pool.clone());
+        let team_kits = TeamKitService::new(pool);
+        Self { game_queries, team_notifications, team_kits }
     }
 
     /// Start games that should be in progress (current time is within their week window)
@@ -32,6 +37,12 @@ impl ManageGameService {
             // Start the game (updates status to 'in_progress' and sets game_start_time)
             tracing::info!("▶️  [GAME_SERVICE] Starting game {}", game.id);
             self.game_queries.start_game(game.id).await?;
+            if let Err(e) = self.team_kits.fix_game_kits(game.id).await {
+                tracing::error!("❌ [GAME_SERVICE] Failed to fix the kits of game {}: {}", game.id, e);
+            }
+            if let Err(e) = self.team_notifications.notify_game_started(game.id).await {
+                tracing::error!("❌ [GAME_SERVICE] Failed to notify the teams of game {}: {}", game.id, e);
+            }
 
             started_game_ids.push(game.id);
             tracing::info!("✅ [GAME_SERVICE] Started game {} with live scoring", game.id);
@@ -83,6 +94,11 @@ impl ManageGameService {
         self.game_queries.get_active_games().await
     }
 
+    /// Finished games past the upload grace period, ready to be evaluated
+    pub async fn get_games_ready_to_evaluate(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        self.game_queries.get_games_ready_to_evaluate().await
+    }
+
     /// Get all games that are ready to start
     async fn get_games_ready_to_start(&self) -> Result<Vec<LeagueGame>, sqlx::Error> {
         self.game_queries.get_games_ready_to_start().await
@@ -0,0 +1,216 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::utils::perceptual_hash::{hamming_distance, similar_band_values, SIMILARITY_THRESHOLD};
+
+/// Service behind photo moderation: remembers perceptual hashes of confirmed uploads,
+/// rejects uploads resembling blocked images and strips taken-down media from posts and workouts.
+#[derive(Debug)]
+pub struct MediaModerationService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BlockedMatch {
+    pub blocklist_id: Uuid,
+    pub reason: String,
+    pub distance: u32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MediaTakedown {
+    pub id: Uuid,
+    pub object_key: String,
+    pub file_url: String,
+    pub reason: String,
+    /// Posts and workouts that referenced the media and no longer do
+    pub post_ids: Vec<Uuid>,
+    pub workout_ids: Vec<Uuid>,
+    /// Whether the image's hash now blocks re-uploads of it
+    pub hash_blocked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MediaModerationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Closest blocklist entry within the similarity threshold, if any.
+    /// Only entries sharing a nearby band with the hash are compared, found through the band indexes.
+    pub async fn find_blocked_match(&self, phash: i64) -> Result<Option<BlockedMatch>, sqlx::Error> {
+        let [band_0, band_1, band_2, band_3] = similar_band_values(phash);
+        let entries = sqlx::query!(
+            r#"
+            SELECT id, phash, reason FROM media_hash_blocklist
+            WHERE phash_band_0 = ANY($1) OR phash_band_1 = ANY($2)
+                OR phash_band_2 = ANY($3) OR phash_band_3 = ANY($4)
+            "#,
+            &band_0,
+            &band_1,
+            &band_2,
+            &band_3
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| BlockedMatch {
+                blocklist_id: entry.id,
+                reason: entry.reason,
+                distance: hamming_distance(entry.phash, phash),
+            })
+            .filter(|entry| entry.distance <= SIMILARITY_THRESHOLD)
+            .min_by_key(|entry| entry.distance))
+    }
+
+    pub async fn record_hash(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        sha256: &str,
+        phash: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO media_hashes (object_key, user_id, sha256, phash)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (object_key) DO UPDATE SET sha256 = EXCLUDED.sha256, phash = EXCLUDED.phash
+            "#,
+            object_key,
+            user_id,
+            sha256,
+            phash
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn is_taken_down(&self, object_key: &str) -> Result<bool, sqlx::Error> {
+        let taken_down = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM media_takedowns WHERE object_key = $1) as "exists!""#,
+            object_key
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(taken_down)
+    }
+
+    /// Remove the media from every post and workout referencing it, by object key or file URL,
+    /// and optionally block its hash. Touched rows get a new `updated_at`, so ETags and
+    /// delta sync pick up the change on the next request.
+    pub async fn take_down(
+        &self,
+        object_key: &str,
+        file_url: &str,
+        reason: &str,
+        admin_id: Uuid,
+        block_hash: bool,
+    ) -> Result<MediaTakedown, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let post_ids = sqlx::query_scalar!(
+            r#"
+            UPDATE posts
+            SET media_urls = (
+                    SELECT jsonb_agg(m)
+                    FROM jsonb_array_elements(media_urls) m
+                    WHERE m->>'url' NOT IN ($1, $2)
+                ),
+                updated_at = NOW()
+            WHERE jsonb_typeof(media_urls) = 'array'
+            AND EXISTS (
+                SELECT 1 FROM jsonb_array_elements(media_urls) m WHERE m->>'url' IN ($1, $2)
+            )
+            RETURNING id
+            "#,
+            object_key,
+            file_url
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let workout_ids = sqlx::query_scalar!(
+            r#"
+            UPDATE workout_data
+            SET image_url = CASE WHEN image_url IN ($1, $2) THEN NULL ELSE image_url END,
+                video_url = CASE WHEN video_url IN ($1, $2) THEN NULL ELSE video_url END,
+                updated_at = NOW()
+            WHERE image_url IN ($1, $2) OR video_url IN ($1, $2)
+            RETURNING id
+            "#,
+            object_key,
+            file_url
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let takedown = sqlx::query!(
+            r#"
+            INSERT INTO media_takedowns (object_key, file_url, reason, taken_down_by, posts_updated, workouts_updated)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (object_key) DO UPDATE
+            SET reason = EXCLUDED.reason,
+                taken_down_by = EXCLUDED.taken_down_by,
+                posts_updated = media_takedowns.posts_updated + EXCLUDED.posts_updated,
+                workouts_updated = media_takedowns.workouts_updated + EXCLUDED.workouts_updated
+            RETURNING id, created_at
+            "#,
+            object_key,
+            file_url,
+            reason,
+            admin_id,
+            post_ids.len() as i32,
+            workout_ids.len() as i32
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let hash_blocked = if block_hash {
+            sqlx::query!(
+                r#"
+                INSERT INTO media_hash_blocklist (phash, reason, source_object_key, created_by)
+                SELECT phash, $2, object_key, $3 FROM media_hashes WHERE object_key = $1
+                ON CONFLICT (phash) DO NOTHING
+                "#,
+                object_key,
+                reason,
+                admin_id
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM media_hashes h
+                    JOIN media_hash_blocklist b ON b.phash = h.phash
+                    WHERE h.object_key = $1
+                ) as "exists!"
+                "#,
+                object_key
+            )
+            .fetch_one(&mut *tx)
+            .await?
+        } else {
+            false
+        };
+
+        tx.commit().await?;
+
+        Ok(MediaTakedown {
+            id: takedown.id,
+            object_key: object_key.to_string(),
+            file_url: file_url.to_string(),
+            reason: reason.to_string(),
+            post_ids,
+            workout_ids,
+            hash_blocked,
+            created_at: takedown.created_at,
+        })
+    }
+}
@@ -7,6 +7,7 @@ use aws_sdk_s3::error::SdkError;
 use aws_sdk_s3::operation::create_bucket::CreateBucketError;
 
 use crate::config::minio::MinIOSettings;
+use crate::services::fault_injection::minio_fault;
 
 #[derive(Clone, Debug)]
 pub struct MinIOService {
@@ -111,6 +112,7 @@ impl MinIOService {
         content_type: &str,
         user_id: Uuid,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        minio_fault().await;
         // Create a unique path with user_id prefix for organization
         let object_key = format!("users/{user_id}/{file_name}");
         
@@ -140,10 +142,38 @@ impl MinIOService {
         }
     }
 
+    /// Store server-generated content (e.g. rendered cards) under a fixed key, replacing any previous version
+    pub async fn upload_object(
+        &self,
+        file_data: Bytes,
+        object_key: &str,
+        content_type: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        minio_fault().await;
+        tracing::info!("📤 Uploading generated object to MinIO: {} (size: {} bytes)", object_key, file_data.len());
+
+        self.internal_client
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(object_key)
+            .body(ByteStream::from(file_data))
+            .content_type(content_type)
+            .metadata("uploaded_at", chrono::Utc::now().to_rfc3339())
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("❌ Failed to upload generated object to MinIO: {}", e);
+                Box::new(e) as Box<dyn std::error::Error + Send + Sync>
+            })?;
+
+        Ok(())
+    }
+
     pub async fn get_file(
         &self,
         object_key: &str,
     ) -> Result<(Bytes, String), Box<dyn std::error::Error + Send + Sync>> {
+        minio_fault().await;
         tracing::info!("📥 Downloading file from MinIO: {}", object_key);
 
         match self.internal_client
@@ -180,6 +210,7 @@ impl MinIOService {
         &self,
         object_key: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        minio_fault().await;
         tracing::info!("🗑️ Deleting file from MinIO: {}", object_key);
 
         match self.internal_client
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 
+use crate::services::fault_injection::ml_call_fault;
+
 #[derive(Debug, Serialize)]
 pub struct HeartRateSample {
     pub timestamp: String,
@@ -69,6 +71,7 @@ impl MLClient {
             activity_type,
         };
 
+        ml_call_fault()?;
         let url = format!("{}/classify", self.base_url);
 
         tracing::debug!("🤖 Calling ML service at {}", url);
@@ -9,6 +9,31 @@ pub mod game_summary_service;
 pub mod player_pool_events;
 pub mod ml_client;
 pub mod chat_events;
+pub mod backup_verification_service;
+pub mod score_consistency_service;
+pub mod hr_trend_service;
+pub mod weekly_digest_service;
+pub mod notification_delivery;
+pub mod inactivity_nudge_service;
+pub mod sync_service;
+pub mod media_moderation_service;
+pub mod game_commentary_service;
+pub mod season_recap_service;
+pub mod broadcast_service;
+pub mod job_registry;
+pub mod game_watchdog_service;
+pub mod zone_recalculation_service;
+pub mod body_metrics_service;
+pub mod live_metrics_service;
+pub mod user_stats_cache;
+pub mod event_outbox;
+pub mod league_analytics_service;
+pub mod booster_service;
+pub mod league_quest_service;
+pub mod share_card_service;
+pub mod suspension_service;
+pub mod scoring_calibration_service;
+pub mod game_clock_cache;
 
 pub use game_evaluation_service::GameEvaluationService;
 pub use scheduler::SchedulerService;
@@ -16,4 +41,77 @@ pub use manage_game_service::ManageGameService;
 pub use minio_service::MinIOService;
 pub use redis_service::RedisService;
 pub use game_summary_service::GameSummaryService;
-pub use ml_client::MLClient;
\ No newline at end of file
+pub use ml_client::MLClient;
+pub use backup_verification_service::BackupVerificationService;
+pub use score_consistency_service::ScoreConsistencyService;
+pub use hr_trend_service::HrTrendService;
+pub use weekly_digest_service::WeeklyDigestService;
+pub use inactivity_nudge_service::InactivityNudgeService;
+pub use sync_service::SyncService;
+pub use media_moderation_service::MediaModerationService;
+pub use game_commentary_service::GameCommentaryService;
+pub use season_recap_service::SeasonRecapService;
+pub use broadcast_service::BroadcastService;
+pub use game_watchdog_service::GameWatchdogService;
+pub use zone_recalculation_service::ZoneRecalculationService;
+pub use body_metrics_service::BodyMetricsService;
+pub use live_metrics_service::{LiveMetrics, LiveMetricsService};
+pub use user_stats_cache::UserStatsCache;
+pub use event_outbox::EventOutbox;
+pub use league_analytics_service::LeagueAnalyticsService;
+pub use booster_service::BoosterService;
+pub use league_quest_service::LeagueQuestService;
+pub use share_card_service::ShareCardService;
+pub use suspension_service::SuspensionService;
+pub use scoring_calibration_service::ScoringCalibrationService;
+pub use game_clock_cache::GameClockCache;
+pub mod threshold_detection_service;
+pub use threshold_detection_service::ThresholdDetectionService;
+pub mod team_notification_service;
+pub use team_notification_service::TeamNotificationService;
+pub mod quiet_hours_service;
+pub mod team_kit_service;
+pub use team_kit_service::TeamKitService;
+pub mod historical_import_service;
+pub use historical_import_service::HistoricalImportService;
+#[cfg(feature = "sandbox")]
+pub mod sandbox_service;
+#[cfg(feature = "sandbox")]
+pub use sandbox_service::SandboxService;
+pub mod stat_decay_service;
+pub use stat_decay_service::StatDecayService;
+pub mod player_card_service;
+pub use player_card_service::PlayerCardService;
+pub mod formation_bonus_service;
+pub use formation_bonus_service::FormationBonusService;
+pub mod halftime_report_service;
+pub use halftime_report_service::HalftimeReportService;
+pub mod email_service;
+pub use email_service::EmailService;
+pub mod post_publishing_service;
+pub use post_publishing_service::PostPublishingService;
+pub mod league_webhook_service;
+pub use league_webhook_service::LeagueWebhookService;
+pub mod oauth_service;
+pub use oauth_service::OAuthService;
+pub mod organization_sso_service;
+pub use organization_sso_service::OrganizationSsoService;
+pub mod league_rules_service;
+pub use league_rules_service::LeagueRulesService;
+pub mod review_lock_service;
+pub use review_lock_service::ReviewLockService;
+pub mod account_data_service;
+pub use account_data_service::AccountDataService;
+pub mod login_protection_service;
+pub use login_protection_service::LoginProtectionService;
+pub mod password_policy_service;
+pub use password_policy_service::PasswordPolicyService;
+pub mod sync_diagnostics_service;
+pub use sync_diagnostics_service::SyncDiagnosticsService;
+pub mod live_state_sync_service;
+pub use live_state_sync_service::LiveStateSyncService;
+pub mod error_reporting;
+pub mod fault_injection;
+pub mod season_stats_service;
+pub use season_stats_service::SeasonStatsService;
+pub mod spectator_token_service;
@@ -0,0 +1,128 @@
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::handlers::notification_handler::send_notification_to_user;
+use crate::services::EmailService;
+
+/// A notification produced by a background job, delivered through the recipient's enabled channels
+#[derive(Debug, Clone)]
+pub struct ChannelNotification {
+    pub recipient_id: Uuid,
+    pub actor_id: Uuid,
+    pub notification_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// Push notification title; the notification center only shows the message
+    pub title: String,
+    pub message: String,
+    /// Expo channel category (see `send_notification_to_user`)
+    pub push_category: String,
+}
+
+/// Deliver a notification to the notification center and/or push, as enabled in the
+/// recipient's notification_preferences. Returns the channels it was delivered through.
+pub async fn deliver_to_enabled_channels(
+    pool: &PgPool,
+    notification: &ChannelNotification,
+) -> Result<Vec<String>, sqlx::Error> {
+    deliver_to_channels(pool, notification, &["in_app", "push"]).await
+}
+
+/// Like `deliver_to_enabled_channels`, limited to the given channels ("in_app", "push")
+pub async fn deliver_to_channels(
+    pool: &PgPool,
+    notification: &ChannelNotification,
+    wanted: &[&str],
+) -> Result<Vec<String>, sqlx::Error> {
+    let (want_in_app, want_push) = (wanted.contains(&"in_app"), wanted.contains(&"push"));
+    let channels = sqlx::query!(
+        r#"
+        SELECT
+            COALESCE(np.push_enabled, TRUE) as "push_enabled!",
+            COALESCE(np.in_app_enabled, TRUE) as "in_app_enabled!",
+            EXISTS(SELECT 1 FROM push_tokens pt WHERE pt.user_id = $1 AND pt.is_active) as "has_push_token!"
+        FROM (SELECT $1::uuid AS user_id) u
+        LEFT JOIN notification_preferences np ON np.user_id = u.user_id
+        "#,
+        notification.recipient_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let mut delivered_channels = Vec::new();
+
+    if want_in_app && channels.in_app_enabled {
+        sqlx::query!(
+            r#"
+            INSERT INTO notifications (recipient_id, actor_id, notification_type, entity_type, entity_id, message)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            notification.recipient_id,
+            notification.actor_id,
+            notification.notification_type,
+            notification.entity_type,
+            notification.entity_id,
+            notification.message
+        )
+        .execute(pool)
+        .await?;
+        delivered_channels.push("in_app".to_string());
+    }
+
+    if want_push && channels.push_enabled && channels.has_push_token {
+        match send_notification_to_user(
+            pool,
+            notification.recipient_id,
+            notification.title.clone(),
+            notification.message.clone(),
+            Some(json!({
+                "type": notification.notification_type,
+                "entity_type": notification.entity_type,
+                "entity_id": notification.entity_id,
+            })),
+            Some(notification.push_category.clone()),
+        ).await {
+            Ok(()) => delivered_channels.push("push".to_string()),
+            Err(e) => {
+                tracing::error!("Failed to push {} to user {}: {}",
+                    notification.notification_type, notification.recipient_id, e);
+            }
+        }
+    }
+
+    Ok(delivered_channels)
+}
+
+/// Email a notification, unless the recipient turned the email channel off in their
+/// notification_preferences. Returns whether it was sent.
+pub async fn deliver_email(
+    pool: &PgPool,
+    email_service: &EmailService,
+    notification: &ChannelNotification,
+) -> Result<bool, sqlx::Error> {
+    let recipient = sqlx::query!(
+        r#"
+        SELECT u.email, COALESCE(np.email_enabled, TRUE) as "email_enabled!"
+        FROM users u
+        LEFT JOIN notification_preferences np ON np.user_id = u.id
+        WHERE u.id = $1
+        "#,
+        notification.recipient_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(recipient) = recipient.filter(|recipient| recipient.email_enabled) else {
+        return Ok(false);
+    };
+
+    match email_service.send(&recipient.email, &notification.title, &notification.message).await {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            tracing::error!("Failed to email {} to user {}: {}",
+                notification.notification_type, notification.recipient_id, e);
+            Ok(false)
+        }
+    }
+}
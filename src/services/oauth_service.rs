@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use tokio::sync::RwLock;
+
+use crate::config::oauth::OAuthSettings;
+use crate::models::auth::OAuthProvider;
+
+/// Signing keys of a provider are fetched again after this long
+const JWKS_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Tokens naming an unknown key refetch the keys at most this often, in case the provider rotated them
+const JWKS_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Account a valid ID token belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedIdentity {
+    /// The provider's stable account id
+    pub subject: String,
+    /// Lowercased email, only if the provider verified it
+    pub email: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum OAuthError {
+    /// The provider has no client ids configured
+    NotConfigured,
+    InvalidToken(String),
+    /// The provider's signing keys could not be fetched
+    KeysUnavailable(String),
+}
+
+impl fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OAuthError::NotConfigured => write!(f, "Sign-in with this provider is not enabled"),
+            OAuthError::InvalidToken(reason) => write!(f, "Invalid ID token: {reason}"),
+            OAuthError::KeysUnavailable(reason) => write!(f, "Signing keys unavailable: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    #[serde(default, deserialize_with = "bool_or_string")]
+    email_verified: bool,
+}
+
+/// Apple sends `email_verified` as the string "true", Google as a boolean
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+    Ok(match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(value) => value,
+        BoolOrString::String(value) => value == "true",
+    })
+}
+
+/// Check an ID token's signature, issuer, audience and expiry, returning its claims
+pub fn decode_id_token<T: DeserializeOwned>(
+    id_token: &str,
+    keys: &JwkSet,
+    issuers: &[&str],
+    audiences: &[String],
+) -> Result<T, OAuthError> {
+    let header = decode_header(id_token).map_err(|e| OAuthError::InvalidToken(e.to_string()))?;
+    let kid = header.kid.ok_or_else(|| OAuthError::InvalidToken("missing key id".to_string()))?;
+    let jwk = keys.find(&kid).ok_or_else(|| OAuthError::InvalidToken("unknown key id".to_string()))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| OAuthError::InvalidToken(e.to_string()))?;
+
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(audiences);
+    validation.set_issuer(issuers);
+    decode::<T>(id_token, &key, &validation)
+        .map(|data| data.claims)
+        .map_err(|e| OAuthError::InvalidToken(e.to_string()))
+}
+
+/// Check an ID token's signature, issuer, audience and expiry against the provider's keys
+pub fn validate_id_token(
+    provider: OAuthProvider,
+    id_token: &str,
+    keys: &JwkSet,
+    client_ids: &[String],
+) -> Result<VerifiedIdentity, OAuthError> {
+    let claims: IdTokenClaims = decode_id_token(id_token, keys, provider.issuers(), client_ids)?;
+
+    Ok(VerifiedIdentity {
+        subject: claims.sub,
+        email: claims.email.filter(|_| claims.email_verified).map(|email| email.to_lowercase()),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenIdConfiguration {
+    jwks_uri: String,
+}
+
+/// Verifies Sign in with Apple / Google ID tokens and those of organizations' OIDC providers
+pub struct OAuthService {
+    settings: OAuthSettings,
+    client: Client,
+    /// Signing keys by JWKS url
+    keys: RwLock<HashMap<String, (Instant, JwkSet)>>,
+    /// JWKS urls of OIDC issuers, from their discovery documents
+    jwks_uris: RwLock<HashMap<String, String>>,
+}
+
+impl OAuthService {
+    pub fn new(settings: OAuthSettings) -> Self {
+        Self {
+            settings,
+            client: Client::new(),
+            keys: RwLock::new(HashMap::new()),
+            jwks_uris: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn client_ids(&self, provider: OAuthProvider) -> &[String] {
+        match provider {
+            OAuthProvider::Apple => &self.settings.apple_client_ids,
+            OAuthProvider::Google => &self.settings.google_client_ids,
+        }
+    }
+
+    fn jwks_url(&self, provider: OAuthProvider) -> &str {
+        match provider {
+            OAuthProvider::Apple => &self.settings.apple_jwks_url,
+            OAuthProvider::Google => &self.settings.google_jwks_url,
+        }
+    }
+
+    pub async fn verify(&self, provider: OAuthProvider, id_token: &str) -> Result<VerifiedIdentity, OAuthError> {
+        let client_ids = self.client_ids(provider);
+        if client_ids.is_empty() {
+            return Err(OAuthError::NotConfigured);
+        }
+        let kid = decode_header(id_token)
+            .map_err(|e| OAuthError::InvalidToken(e.to_string()))?
+            .kid
+            .ok_or_else(|| OAuthError::InvalidToken("missing key id".to_string()))?;
+
+        let keys = self.keys(self.jwks_url(provider), &kid).await?;
+        validate_id_token(provider, id_token, &keys, client_ids)
+    }
+
+    /// Verify an ID token of an OIDC provider, whose keys are found through its discovery
+    /// document. Returns all claims of the token.
+    pub async fn verify_oidc(
+        &self,
+        issuer: &str,
+        client_id: &str,
+        id_token: &str,
+    ) -> Result<serde_json::Map<String, serde_json::Value>, OAuthError> {
+        let kid = decode_header(id_token)
+            .map_err(|e| OAuthError::InvalidToken(e.to_string()))?
+            .kid
+            .ok_or_else(|| OAuthError::InvalidToken("missing key id".to_string()))?;
+
+        let jwks_uri = self.jwks_uri(issuer).await?;
+        let keys = self.keys(&jwks_uri, &kid).await?;
+        decode_id_token(id_token, &keys, &[issuer], &[client_id.to_string()])
+    }
+
+    async fn jwks_uri(&self, issuer: &str) -> Result<String, OAuthError> {
+        if let Some(jwks_uri) = self.jwks_uris.read().await.get(issuer) {
+            return Ok(jwks_uri.clone());
+        }
+
+        let discovery_url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+        let configuration: OpenIdConfiguration = self
+            .client
+            .get(discovery_url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| OAuthError::KeysUnavailable(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| OAuthError::KeysUnavailable(e.to_string()))?;
+        self.jwks_uris.write().await.insert(issuer.to_string(), configuration.jwks_uri.clone());
+        Ok(configuration.jwks_uri)
+    }
+
+    /// Cached signing keys behind a JWKS url, refetched when stale or missing the token's key
+    async fn keys(&self, jwks_url: &str, kid: &str) -> Result<JwkSet, OAuthError> {
+        if let Some((fetched_at, keys)) = self.keys.read().await.get(jwks_url) {
+            let known_key = keys.find(kid).is_some();
+            if fetched_at.elapsed() < JWKS_MIN_REFRESH_INTERVAL || (known_key && fetched_at.elapsed() < JWKS_MAX_AGE) {
+                return Ok(keys.clone());
+            }
+        }
+
+        let keys: JwkSet = self
+            .client
+            .get(jwks_url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| OAuthError::KeysUnavailable(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| OAuthError::KeysUnavailable(e.to_string()))?;
+        self.keys.write().await.insert(jwks_url.to_string(), (Instant::now(), keys.clone()));
+        Ok(keys)
+    }
+}
@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::api_keys::revoke_all_api_keys_for_user;
+use crate::db::refresh_tokens::revoke_all_for_user;
+use crate::handlers::registration_handler::insert_external_user;
+use crate::models::organization::{
+    email_domain_allowed, SetSsoGroupMappingRequest, SetSsoProviderRequest, SsoGroupMapping, SsoIdentity, SsoProvider,
+    SsoSettings,
+};
+use crate::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// How a directory account's login turned out
+#[derive(Debug, PartialEq)]
+pub enum SsoLogin {
+    User(Uuid),
+    /// The account left the company directory
+    Deactivated,
+    /// No user has the account's email and the organization doesn't create users on login
+    NotProvisioned,
+}
+
+/// Single sign-on of organizations through their OIDC provider, with users created on first
+/// login, teams from directory groups and deactivation by the directory
+pub struct OrganizationSsoService {
+    pool: PgPool,
+}
+
+impl OrganizationSsoService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn settings(&self, organization_id: Uuid) -> Result<SsoSettings, sqlx::Error> {
+        let provider = self.provider(organization_id).await?;
+        let group_mappings = sqlx::query_as!(
+            SsoGroupMapping,
+            r#"
+            SELECT m.id, m.group_name, m.team_id, t.team_name, m.created_at
+            FROM organization_sso_group_mappings m
+            JOIN teams t ON t.id = m.team_id
+            WHERE m.organization_id = $1
+            ORDER BY m.group_name
+            "#,
+            organization_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(SsoSettings { provider, group_mappings })
+    }
+
+    pub async fn provider(&self, organization_id: Uuid) -> Result<Option<SsoProvider>, sqlx::Error> {
+        sqlx::query_as!(
+            SsoProvider,
+            r#"
+            SELECT organization_id, issuer_url, client_id, groups_claim, allowed_email_domains, jit_provisioning,
+                   enabled, scim_token_hash IS NOT NULL as "has_scim_token!", created_at, updated_at
+            FROM organization_sso_providers
+            WHERE organization_id = $1
+            "#,
+            organization_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Enabled provider of the organization with the slug
+    pub async fn provider_for_login(&self, slug: &str) -> Result<Option<SsoProvider>, sqlx::Error> {
+        sqlx::query_as!(
+            SsoProvider,
+            r#"
+            SELECT p.organization_id, p.issuer_url, p.client_id, p.groups_claim, p.allowed_email_domains,
+                   p.jit_provisioning, p.enabled, p.scim_token_hash IS NOT NULL as "has_scim_token!",
+                   p.created_at, p.updated_at
+            FROM organization_sso_providers p
+            JOIN organizations o ON o.id = p.organization_id
+            WHERE o.slug = $1 AND p.enabled
+            "#,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn set_provider(
+        &self,
+        organization_id: Uuid,
+        request: &SetSsoProviderRequest,
+    ) -> Result<SsoProvider, sqlx::Error> {
+        let allowed_email_domains: Option<Vec<String>> = request
+            .allowed_email_domains
+            .as_ref()
+            .map(|domains| domains.iter().map(|domain| domain.trim().to_lowercase()).collect());
+
+        sqlx::query_as!(
+            SsoProvider,
+            r#"
+            INSERT INTO organization_sso_providers (
+                organization_id, issuer_url, client_id, groups_claim, allowed_email_domains, jit_provisioning, enabled
+            )
+            VALUES ($1, $2, $3, COALESCE($4, 'groups'), COALESCE($5, '{}'::text[]), COALESCE($6, TRUE), COALESCE($7, TRUE))
+            ON CONFLICT (organization_id) DO UPDATE SET
+                issuer_url = EXCLUDED.issuer_url,
+                client_id = EXCLUDED.client_id,
+                groups_claim = COALESCE($4, organization_sso_providers.groups_claim),
+                allowed_email_domains = COALESCE($5, organization_sso_providers.allowed_email_domains),
+                jit_provisioning = COALESCE($6, organization_sso_providers.jit_provisioning),
+                enabled = COALESCE($7, organization_sso_providers.enabled),
+                updated_at = NOW()
+            RETURNING organization_id, issuer_url, client_id, groups_claim, allowed_email_domains, jit_provisioning,
+                      enabled, scim_token_hash IS NOT NULL as "has_scim_token!", created_at, updated_at
+            "#,
+            organization_id,
+            request.issuer_url.trim_end_matches('/'),
+            request.client_id.trim(),
+            request.groups_claim.as_deref(),
+            allowed_email_domains.as_deref(),
+            request.jit_provisioning,
+            request.enabled
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Turn off SSO for the organization. Users keep their accounts.
+    pub async fn remove_provider(&self, organization_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM organization_sso_providers WHERE organization_id = $1",
+            organization_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Map a directory group to a team. Returns None if the team doesn't exist.
+    pub async fn set_group_mapping(
+        &self,
+        organization_id: Uuid,
+        request: &SetSsoGroupMappingRequest,
+    ) -> Result<Option<SsoGroupMapping>, sqlx::Error> {
+        sqlx::query_as!(
+            SsoGroupMapping,
+            r#"
+            WITH mapping AS (
+                INSERT INTO organization_sso_group_mappings (organization_id, group_name, team_id)
+                SELECT $1, $2, id FROM teams WHERE id = $3
+                ON CONFLICT (organization_id, group_name) DO UPDATE SET team_id = EXCLUDED.team_id
+                RETURNING id, group_name, team_id, created_at
+            )
+            SELECT mapping.id, mapping.group_name, mapping.team_id, t.team_name, mapping.created_at
+            FROM mapping
+            JOIN teams t ON t.id = mapping.team_id
+            "#,
+            organization_id,
+            request.group_name.trim(),
+            request.team_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn remove_group_mapping(&self, organization_id: Uuid, mapping_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM organization_sso_group_mappings WHERE organization_id = $1 AND id = $2",
+            organization_id,
+            mapping_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replace the organization's SCIM token. Returns None if it has no SSO provider.
+    pub async fn rotate_scim_token(&self, organization_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let token = generate_opaque_token();
+        let result = sqlx::query!(
+            "UPDATE organization_sso_providers SET scim_token_hash = $2, updated_at = NOW() WHERE organization_id = $1",
+            organization_id,
+            hash_opaque_token(&token)
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok((result.rows_affected() > 0).then_some(token))
+    }
+
+    /// Organization a SCIM token belongs to
+    pub async fn organization_for_scim_token(&self, token: &str) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            "SELECT organization_id FROM organization_sso_providers WHERE scim_token_hash = $1 AND enabled",
+            hash_opaque_token(token)
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Sign in a directory account: its linked user, else the user with its email if the
+    /// directory may claim it, else a new user if the organization allows it. Users join the teams mapped to their groups.
+    pub async fn login(
+        &self,
+        provider: &SsoProvider,
+        identity: &SsoIdentity,
+        redis_client: &Arc<redis::Client>,
+    ) -> Result<SsoLogin, sqlx::Error> {
+        let organization_id = provider.organization_id;
+        let linked = sqlx::query!(
+            r#"
+            UPDATE organization_sso_users SET last_login_at = NOW(), email = COALESCE($3, email)
+            WHERE organization_id = $1 AND subject = $2
+            RETURNING user_id, deactivated_at
+            "#,
+            organization_id,
+            identity.subject,
+            identity.email
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let user_id = match linked {
+            Some(linked) if linked.deactivated_at.is_some() => return Ok(SsoLogin::Deactivated),
+            Some(linked) => linked.user_id,
+            None => {
+                let Some(email) = identity.email.as_deref() else {
+                    return Ok(SsoLogin::NotProvisioned);
+                };
+                let existing_user = sqlx::query_scalar!("SELECT id FROM users WHERE LOWER(email) = $1", email)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                let user_id = match existing_user {
+                    Some(user_id) if identity.may_link_existing_user(&provider.allowed_email_domains) => user_id,
+                    Some(user_id) => {
+                        tracing::warn!(
+                            "Directory account of organization {} not linked to user {}, its email is unverified or of a foreign domain",
+                            organization_id, user_id
+                        );
+                        return Ok(SsoLogin::NotProvisioned);
+                    }
+                    None if provider.jit_provisioning && email_domain_allowed(email, &provider.allowed_email_domains) => {
+                        insert_external_user(&self.pool, redis_client, email).await?
+                    }
+                    None => return Ok(SsoLogin::NotProvisioned),
+                };
+                sqlx::query!(
+                    r#"
+                    INSERT INTO organization_sso_users (organization_id, subject, user_id, email)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                    organization_id,
+                    identity.subject,
+                    user_id,
+                    email
+                )
+                .execute(&self.pool)
+                .await?;
+                tracing::info!("Linked directory account of organization {} to user {}", organization_id, user_id);
+                user_id
+            }
+        };
+
+        let joined = self.join_mapped_teams(organization_id, user_id, &identity.groups).await?;
+        if joined > 0 {
+            tracing::info!("User {} joined {} teams of their directory groups", user_id, joined);
+        }
+        Ok(SsoLogin::User(user_id))
+    }
+
+    /// Add the user to the teams mapped to their groups, skipping leagues they already play in.
+    /// Returns how many teams they joined.
+    async fn join_mapped_teams(&self, organization_id: Uuid, user_id: Uuid, groups: &[String]) -> Result<usize, sqlx::Error> {
+        if groups.is_empty() {
+            return Ok(0);
+        }
+        let mut tx = self.pool.begin().await?;
+        let joined = sqlx::query_scalar!(
+            r#"
+            INSERT INTO team_members (team_id, user_id, role, status)
+            SELECT DISTINCT ON (COALESCE(t.league_id, t.id)) t.id, $2, 'member', 'active'
+            FROM organization_sso_group_mappings m
+            JOIN teams t ON t.id = m.team_id
+            WHERE m.organization_id = $1 AND m.group_name = ANY($3)
+              AND NOT EXISTS (
+                  SELECT 1 FROM team_members tm
+                  JOIN teams other ON other.id = tm.team_id
+                  WHERE tm.user_id = $2 AND tm.status = 'active'
+                    AND (other.id = t.id OR other.league_id = t.league_id)
+              )
+            ORDER BY COALESCE(t.league_id, t.id), m.group_name
+            ON CONFLICT (team_id, user_id) DO NOTHING
+            RETURNING team_id
+            "#,
+            organization_id,
+            user_id,
+            groups
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !joined.is_empty() {
+            sqlx::query!("DELETE FROM player_pool WHERE user_id = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(joined.len())
+    }
+
+    /// Deactivate or reactivate a directory account's user, as the company directory says.
+    /// Deactivated users are logged out everywhere. Returns None for unknown accounts.
+    pub async fn set_active(&self, organization_id: Uuid, subject: &str, active: bool) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let user_id = sqlx::query_scalar!(
+            r#"
+            UPDATE organization_sso_users
+            SET deactivated_at = CASE WHEN $3 THEN NULL ELSE COALESCE(deactivated_at, NOW()) END
+            WHERE organization_id = $1 AND subject = $2
+            RETURNING user_id
+            "#,
+            organization_id,
+            subject,
+            active
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        // Suspended and banned users stay as they are
+        let (from, to) = if active { ("inactive", "active") } else { ("active", "inactive") };
+        sqlx::query!(
+            "UPDATE users SET status = $3, updated_at = NOW() WHERE id = $1 AND status = $2",
+            user_id,
+            from,
+            to
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        if !active {
+            revoke_all_for_user(&self.pool, user_id).await?;
+            revoke_all_api_keys_for_user(&self.pool, user_id).await?;
+        }
+        tracing::info!("Directory of organization {} set user {} {}", organization_id, user_id, to);
+        Ok(Some(user_id))
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use sha1::{Digest, Sha1};
+
+use crate::config::password_policy::PasswordPolicySettings;
+use crate::models::common::FieldError;
+
+const FIELD: &str = "password";
+
+/// The most common passwords of public breach corpora, lowercase. Passwords are compared
+/// case-insensitively, so "Password1" is caught by "password1".
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "123456789", "12345678", "1234567890", "12345", "1234567", "123123", "111111",
+    "000000", "654321", "666666", "121212", "112233", "123321", "987654321", "1q2w3e4r",
+    "1q2w3e4r5t", "1qaz2wsx", "qwerty", "qwerty123", "qwertyuiop", "qwerty1", "asdfghjkl",
+    "asdfgh", "zxcvbnm", "azerty", "password", "password1", "password12", "password123",
+    "password1234", "passw0rd", "p@ssw0rd", "p@ssword", "pass1234", "abc123", "abcd1234",
+    "abc12345", "a1b2c3d4", "iloveyou", "iloveyou1", "letmein", "letmein1", "welcome",
+    "welcome1", "welcome123", "admin", "admin123", "administrator", "root", "toor", "login",
+    "master", "monkey", "dragon", "football", "baseball", "basketball", "soccer", "hockey",
+    "superman", "batman", "trustno1", "sunshine", "princess", "starwars", "whatever",
+    "shadow", "michael", "jennifer", "jordan23", "charlie", "freedom", "secret", "secret123",
+    "changeme", "default", "guest", "hello123", "hellohello", "loveme", "lovely", "flower",
+    "computer", "internet", "samsung", "google", "ninja", "mustang", "access", "master123",
+    "zaq12wsx", "q1w2e3r4", "aa123456", "11111111", "00000000", "88888888", "12341234",
+    "11223344", "147258369", "159753", "987654", "789456123", "qazwsx", "qweasdzxc",
+    "fitness", "fitness1", "workout", "running", "runner", "marathon", "cycling",
+    "training", "riina", "riina123",
+];
+
+/// Violations of the policy's local rules, i.e. everything but the breach check
+pub fn policy_violations(
+    settings: &PasswordPolicySettings,
+    password: &str,
+    username: &str,
+    email: &str,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let length = password.chars().count();
+    if length < settings.min_length {
+        errors.push(FieldError::new(
+            FIELD,
+            "too_short",
+            format!("Password must be at least {} characters long", settings.min_length),
+        ));
+    }
+    if password.len() > settings.max_length {
+        errors.push(FieldError::new(
+            FIELD,
+            "too_long",
+            format!("Password cannot exceed {} characters", settings.max_length),
+        ));
+    }
+    if password.trim().is_empty() && length > 0 {
+        errors.push(FieldError::new(FIELD, "blank", "Password cannot consist of spaces only"));
+    }
+
+    let lowercase = password.to_lowercase();
+    if settings.reject_common_passwords && COMMON_PASSWORDS.contains(&lowercase.as_str()) {
+        errors.push(FieldError::new(FIELD, "too_common", "This password is too common, please choose another one"));
+    }
+
+    let email_name = email.split('@').next().unwrap_or_default().to_lowercase();
+    let personal = [username.trim().to_lowercase(), email_name];
+    if personal.iter().any(|value| value.len() >= 4 && lowercase.contains(value.as_str())) {
+        errors.push(FieldError::new(FIELD, "contains_personal_info", "Password cannot contain your username or email"));
+    }
+    errors
+}
+
+/// Uppercase hex SHA-1 of the password, split into the 5 character prefix sent to the breach
+/// check and the suffix looked up in its answer
+pub fn breach_check_hash(password: &str) -> (String, String) {
+    let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(5);
+    (prefix.to_string(), suffix.to_string())
+}
+
+/// How often the suffix appears in breaches, from a range answer of "SUFFIX:COUNT" lines.
+/// Padding lines have a count of 0.
+pub fn breach_count(range: &str, suffix: &str) -> u64 {
+    range
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(line_suffix, _)| line_suffix.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Checks new passwords against the password policy
+pub struct PasswordPolicyService {
+    settings: PasswordPolicySettings,
+    client: Client,
+}
+
+impl PasswordPolicyService {
+    pub fn new(settings: PasswordPolicySettings) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(settings.breach_check_timeout_ms))
+            .build()
+            .unwrap_or_default();
+        Self { settings, client }
+    }
+
+    /// Everything wrong with the password; empty if it may be used
+    pub async fn check(&self, password: &str, username: &str, email: &str) -> Vec<FieldError> {
+        let mut errors = policy_violations(&self.settings, password, username, email);
+        // Known weak passwords don't need asking about
+        if errors.is_empty() && self.settings.breach_check {
+            match self.breached(password).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    tracing::info!("Rejected a password found in {} breaches", count);
+                    errors.push(FieldError::new(
+                        FIELD,
+                        "breached",
+                        "This password has appeared in a data breach, please choose another one",
+                    ));
+                }
+                Err(e) => tracing::warn!("Password breach check unavailable, skipping it: {}", e),
+            }
+        }
+        errors
+    }
+
+    async fn breached(&self, password: &str) -> Result<u64, reqwest::Error> {
+        let (prefix, suffix) = breach_check_hash(password);
+        let range = self
+            .client
+            .get(format!("{}{}", self.settings.breach_check_url, prefix))
+            // Hides from onlookers how many hashes share the prefix
+            .header("Add-Padding", "true")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(breach_count(&range, &suffix))
+    }
+}
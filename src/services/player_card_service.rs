@@ -0,0 +1,89 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::game::player_card::{archetype, form, workout_streak_days};
+use crate::models::profile::{PlayerBadges, PlayerCard};
+
+/// Days of workouts the archetype and form are derived from
+const CARD_WINDOW_DAYS: i64 = 28;
+
+/// Builds player cards: stats, form, streak, badges and archetype of a player in one payload
+pub struct PlayerCardService {
+    pool: PgPool,
+}
+
+impl PlayerCardService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Card of the user, None if they don't exist
+    pub async fn player_card(&self, user_id: Uuid) -> Result<Option<PlayerCard>, sqlx::Error> {
+        let Some(player) = sqlx::query!(
+            r#"
+            SELECT u.id, u.username, u.profile_picture_url,
+                   COALESCE(ua.avatar_style, 'warrior') as "avatar_style!",
+                   COALESCE(ua.stamina, 0) as "stamina!", COALESCE(ua.strength, 0) as "strength!",
+                   tm.team_id as "team_id?", t.team_name as "team_name?", tm.role as "team_role?",
+                   (SELECT COUNT(*) FROM game_summaries WHERE mvp_user_id = u.id) as "mvp_count!",
+                   (SELECT COUNT(*) FROM game_summaries WHERE lvp_user_id = u.id) as "lvp_count!"
+            FROM users u
+            LEFT JOIN user_avatars ua ON ua.user_id = u.id
+            LEFT JOIN team_members tm ON tm.user_id = u.id
+            LEFT JOIN teams t ON t.id = tm.team_id
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let workouts = sqlx::query!(
+            r#"
+            SELECT workout_start, stamina_gained, strength_gained, total_points_gained
+            FROM workout_data
+            WHERE user_id = $1 AND workout_start >= $2
+            "#,
+            user_id,
+            now - Duration::days(CARD_WINDOW_DAYS)
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let week_ago = now - Duration::days(7);
+        let (mut last_week_points, mut earlier_points) = (0.0, 0.0);
+        let (mut stamina_gained, mut strength_gained) = (0.0, 0.0);
+        for workout in &workouts {
+            if workout.workout_start >= week_ago {
+                last_week_points += workout.total_points_gained as f32;
+            } else {
+                earlier_points += workout.total_points_gained as f32;
+            }
+            stamina_gained += workout.stamina_gained;
+            strength_gained += workout.strength_gained;
+        }
+        let workout_dates: Vec<NaiveDate> = workouts.iter().map(|w| w.workout_start.date_naive()).collect();
+
+        Ok(Some(PlayerCard {
+            user_id: player.id,
+            username: player.username,
+            profile_picture_url: player.profile_picture_url,
+            avatar_style: player.avatar_style,
+            stamina: player.stamina,
+            strength: player.strength,
+            form: form(last_week_points, earlier_points),
+            streak_days: workout_streak_days(&workout_dates, now.date_naive()),
+            archetype: archetype(workouts.len(), stamina_gained, strength_gained),
+            badges: PlayerBadges { mvp_count: player.mvp_count, lvp_count: player.lvp_count },
+            team_id: player.team_id,
+            team_name: player.team_name,
+            team_role: player.team_role,
+            workouts_last_28_days: workouts.len() as i64,
+        }))
+    }
+}
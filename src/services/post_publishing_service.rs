@@ -0,0 +1,158 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::post::{PostDraft, PostType, PostVisibility};
+
+/// How far ahead a post can be scheduled
+pub const MAX_SCHEDULE_DAYS: i64 = 30;
+
+/// Whether a publish time can be used for scheduling; times that have passed publish right away
+pub fn validate_publish_at(publish_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), String> {
+    if publish_at > now + Duration::days(MAX_SCHEDULE_DAYS) {
+        return Err(format!("Posts can be scheduled at most {} days ahead", MAX_SCHEDULE_DAYS));
+    }
+    Ok(())
+}
+
+/// Keep a just created post as a draft. The post must have been created private.
+pub async fn create_draft(
+    conn: &mut PgConnection,
+    post_id: Uuid,
+    publish_visibility: PostVisibility,
+    publish_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO post_drafts (post_id, publish_visibility, publish_at) VALUES ($1, $2, $3)",
+        post_id,
+        publish_visibility as PostVisibility,
+        publish_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Publishes draft posts, on request of their author or at their scheduled time
+pub struct PostPublishingService {
+    pool: PgPool,
+}
+
+impl PostPublishingService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's drafts, next scheduled first
+    pub async fn drafts(&self, user_id: Uuid) -> Result<Vec<PostDraft>, sqlx::Error> {
+        sqlx::query_as!(
+            PostDraft,
+            r#"
+            SELECT d.post_id, p.post_type as "post_type: PostType", p.content, p.workout_id, p.media_urls,
+                   d.publish_visibility as "publish_visibility: PostVisibility", d.publish_at, d.created_at
+            FROM post_drafts d
+            JOIN posts p ON p.id = d.post_id
+            WHERE p.user_id = $1
+            ORDER BY d.publish_at ASC NULLS LAST, d.created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Whether the post is a draft of the user
+    pub async fn is_draft_of(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM post_drafts d JOIN posts p ON p.id = d.post_id
+                WHERE d.post_id = $1 AND p.user_id = $2
+            ) as "exists!"
+            "#,
+            post_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Set the visibility a draft gets once published
+    pub async fn set_publish_visibility(&self, post_id: Uuid, visibility: PostVisibility) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE post_drafts SET publish_visibility = $2 WHERE post_id = $1",
+            post_id,
+            visibility as PostVisibility
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Schedule a draft of the user, returning false if it isn't one
+    pub async fn schedule(&self, post_id: Uuid, user_id: Uuid, publish_at: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE post_drafts d SET publish_at = $3
+            FROM posts p
+            WHERE d.post_id = $1 AND p.id = d.post_id AND p.user_id = $2
+            "#,
+            post_id,
+            user_id,
+            publish_at
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Publish a draft of the user now, returning false if it isn't one. Published posts are
+    /// dated to their publishing so they show up at the top of the feed.
+    pub async fn publish(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let published = sqlx::query_scalar!(
+            r#"
+            WITH draft AS (
+                DELETE FROM post_drafts d
+                USING posts p
+                WHERE d.post_id = $1 AND p.id = d.post_id AND p.user_id = $2
+                RETURNING d.post_id, d.publish_visibility
+            )
+            UPDATE posts p
+            SET visibility = draft.publish_visibility, created_at = NOW(), updated_at = NOW()
+            FROM draft
+            WHERE p.id = draft.post_id
+            RETURNING p.id
+            "#,
+            post_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        if published.is_some() {
+            tracing::info!("📣 Published draft post {}", post_id);
+        }
+        Ok(published.is_some())
+    }
+
+    /// Publish every draft whose scheduled time has come. Meant to run every minute; returns
+    /// how many posts were published.
+    pub async fn publish_due(&self) -> Result<usize, sqlx::Error> {
+        let published = sqlx::query_scalar!(
+            r#"
+            WITH due AS (
+                DELETE FROM post_drafts
+                WHERE publish_at <= NOW()
+                RETURNING post_id, publish_visibility
+            )
+            UPDATE posts p
+            SET visibility = due.publish_visibility, created_at = NOW(), updated_at = NOW()
+            FROM due
+            WHERE p.id = due.post_id
+            RETURNING p.id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(published.len())
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::handlers::notification_handler::send_notification_to_user;
+use crate::utils::quiet_hours::{self, QuietHours};
+
+/// The user's quiet hours, if they set any
+pub async fn quiet_hours_of<'e, E: PgExecutor<'e>>(executor: E, user_id: Uuid) -> Result<Option<QuietHours>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT quiet_hours_start as "start!", quiet_hours_end as "end!", quiet_hours_timezone
+        FROM notification_preferences
+        WHERE user_id = $1 AND quiet_hours_start IS NOT NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.and_then(|row| match row.quiet_hours_timezone.parse() {
+        Ok(timezone) => Some(QuietHours { start: row.start, end: row.end, timezone }),
+        Err(_) => {
+            tracing::warn!("Ignoring quiet hours of {} in unknown timezone {}", user_id, row.quiet_hours_timezone);
+            None
+        }
+    }))
+}
+
+/// Hold a push notification back until the user's quiet hours end, unless it is critical or
+/// the user isn't in quiet hours. Returns when it will be sent, None to push it right away.
+pub async fn defer_if_quiet(
+    pool: &PgPool,
+    user_id: Uuid,
+    title: &str,
+    body: &str,
+    data: Option<&serde_json::Value>,
+    category: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let notification_type = data.and_then(|d| d.get("type")).and_then(|t| t.as_str());
+    if quiet_hours::is_critical(notification_type) {
+        return Ok(None);
+    }
+    let Some(deliver_at) = quiet_hours_of(pool, user_id).await?.and_then(|q| q.window_end(Utc::now())) else {
+        return Ok(None);
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO deferred_push_notifications (user_id, title, body, data, category, deliver_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        user_id,
+        title,
+        body,
+        data,
+        category,
+        deliver_at
+    )
+    .execute(pool)
+    .await?;
+    Ok(Some(deliver_at))
+}
+
+/// Sends push notifications that waited for the end of their recipient's quiet hours
+pub struct DeferredPushService {
+    pool: PgPool,
+}
+
+impl DeferredPushService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Push every deferred notification whose window is over. Returns how many were sent.
+    pub async fn send_due(&self) -> Result<usize, sqlx::Error> {
+        // Claim the due notifications first, so overlapping runs never push one twice
+        let due = sqlx::query!(
+            r#"
+            UPDATE deferred_push_notifications
+            SET sent_at = NOW()
+            WHERE id IN (
+                SELECT id FROM deferred_push_notifications
+                WHERE sent_at IS NULL AND deliver_at <= NOW()
+                ORDER BY deliver_at
+                LIMIT 500
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING user_id, title, body, data, category
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sent = 0;
+        for push in due {
+            match send_notification_to_user(&self.pool, push.user_id, push.title, push.body, push.data, push.category).await {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::error!("Failed to send deferred push to user {}: {}", push.user_id, e),
+            }
+        }
+        Ok(sent)
+    }
+}
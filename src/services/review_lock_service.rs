@@ -0,0 +1,91 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::game::review_lock::{blocked_games, ReviewBlockedGame, ReviewFlag};
+
+/// Holds back the evaluation of finished games while workouts that scored in them are reported,
+/// in leagues that enabled the review lock
+pub struct ReviewLockService {
+    pool: PgPool,
+}
+
+impl ReviewLockService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The games among `game_ids` that can't be evaluated yet, with the reports they wait for
+    pub async fn blocked_games(&self, game_ids: &[Uuid]) -> Result<Vec<ReviewBlockedGame>, sqlx::Error> {
+        if game_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query!(
+            r#"
+            SELECT g.id as game_id,
+                g.game_end_time + make_interval(hours => l.review_lock_hours) as "proceeds_at!",
+                r.id as report_id, r.workout_data_id, r.workout_owner_id, r.reason, r.created_at as reported_at
+            FROM games g
+            JOIN league_seasons s ON s.id = g.season_id
+            JOIN leagues l ON l.id = s.league_id
+            JOIN live_score_events e ON e.game_id = g.id
+            JOIN workout_reports r ON r.workout_data_id = e.workout_data_id AND r.status = 'pending'
+            WHERE g.id = ANY($1) AND l.review_lock_hours IS NOT NULL AND g.game_end_time IS NOT NULL
+            ORDER BY r.created_at
+            "#,
+            game_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(blocked_games(
+            rows.into_iter().map(|row| {
+                (
+                    row.game_id,
+                    row.proceeds_at,
+                    ReviewFlag {
+                        report_id: row.report_id,
+                        workout_data_id: row.workout_data_id,
+                        workout_owner_id: row.workout_owner_id,
+                        reason: row.reason,
+                        reported_at: row.reported_at,
+                    },
+                )
+            }),
+            Utc::now(),
+        ))
+    }
+
+    /// Remember that evaluation held these games back, keeping when it first did
+    pub async fn hold(&self, game_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO game_review_holds (game_id) SELECT UNNEST($1::uuid[]) ON CONFLICT (game_id) DO NOTHING",
+            game_ids
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Held games that are still waiting to be evaluated
+    pub async fn held_games(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT h.game_id FROM game_review_holds h
+            JOIN games g ON g.id = h.game_id
+            WHERE g.status = 'finished'
+            ORDER BY h.held_since
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Forget the holds of games that were evaluated
+    pub async fn release(&self, game_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM game_review_holds WHERE game_id = ANY($1)", game_ids)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
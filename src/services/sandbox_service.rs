@@ -0,0 +1,248 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::sandbox::{Sandbox, SandboxUser};
+use crate::models::user::{UserRole, UserStatus};
+use crate::utils::password::hash_password;
+
+const SANDBOX_TEAMS: [&str; 2] = ["Rockets", "Comets"];
+const PLAYERS_PER_TEAM: usize = 2;
+
+/// Provisions isolated sandbox organizations with a live game, so QA and app store reviewers
+/// can try live-game flows on demand, and tears them down again
+pub struct SandboxService {
+    pool: PgPool,
+}
+
+impl SandboxService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Provision an organization with two teams of sandbox users in a league whose only game
+    /// runs until the sandbox expires
+    pub async fn provision(&self, ttl: Duration, created_by: Option<Uuid>) -> Result<Sandbox, sqlx::Error> {
+        let tag = Uuid::new_v4().simple().to_string()[..8].to_string();
+        let password = Uuid::new_v4().simple().to_string()[..16].to_string();
+        let password_hash = hash_password(&password);
+        let now = Utc::now();
+        let expires_at = now + ttl;
+
+        let mut tx = self.pool.begin().await?;
+
+        let organization_slug = format!("sandbox-{tag}");
+        let organization_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO organizations (slug, name, is_default)
+            VALUES ($1, $2, false)
+            RETURNING id
+            "#,
+            organization_slug,
+            format!("Sandbox {tag}")
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let league_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO leagues (name, description, max_teams)
+            VALUES ($1, 'Sandbox league, torn down when the sandbox expires', 2)
+            RETURNING id
+            "#,
+            format!("Sandbox League {tag}")
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut users = Vec::new();
+        let mut team_ids = Vec::new();
+        for (team_index, team) in SANDBOX_TEAMS.iter().enumerate() {
+            let team_name = format!("{team} {tag}");
+            let mut member_ids = Vec::new();
+            for player in 1..=PLAYERS_PER_TEAM {
+                let username = format!("sandbox_{tag}_{}", team_index * PLAYERS_PER_TEAM + player);
+                let email = format!("{username}@sandbox.invalid");
+                let user_id = sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO users (username, password_hash, email, role, status)
+                    VALUES ($1, $2, $3, $4, $5)
+                    RETURNING id
+                    "#,
+                    username,
+                    password_hash,
+                    email,
+                    UserRole::User.to_string(),
+                    UserStatus::Active.to_string()
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                sqlx::query!(
+                    r#"
+                    INSERT INTO user_avatars (user_id, stamina, strength, avatar_style)
+                    VALUES ($1, 0, 0, 'warrior')
+                    "#,
+                    user_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                member_ids.push(user_id);
+                users.push(SandboxUser { user_id, username, email, team_name: team_name.clone() });
+            }
+
+            let team_id = sqlx::query_scalar!(
+                r#"
+                INSERT INTO teams (user_id, team_name, league_id)
+                VALUES ($1, $2, $3)
+                RETURNING id
+                "#,
+                member_ids[0],
+                team_name,
+                league_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            for (index, user_id) in member_ids.iter().enumerate() {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO team_members (team_id, user_id, role, status)
+                    VALUES ($1, $2, $3, 'active')
+                    "#,
+                    team_id,
+                    user_id,
+                    if index == 0 { "owner" } else { "member" }
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            team_ids.push(team_id);
+        }
+
+        let game_duration_seconds = ttl.num_seconds().clamp(1, 2592000);
+        let season_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO league_seasons (league_id, name, start_date, end_date, auto_evaluation_enabled, game_duration_seconds)
+            VALUES ($1, 'Sandbox Season', $2, $3, true, $4)
+            RETURNING id
+            "#,
+            league_id,
+            now,
+            expires_at,
+            game_duration_seconds
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO league_teams (season_id, team_id)
+            SELECT $1, UNNEST($2::uuid[])
+            "#,
+            season_id,
+            &team_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO league_standings (season_id, team_id, position)
+            SELECT $1, team_id, ROW_NUMBER() OVER ()::int
+            FROM UNNEST($2::uuid[]) AS team_id
+            "#,
+            season_id,
+            &team_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let live_game_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO games (season_id, home_team_id, away_team_id, week_number, status, game_start_time, game_end_time)
+            VALUES ($1, $2, $3, 1, 'in_progress', $4, $5)
+            RETURNING id
+            "#,
+            season_id,
+            team_ids[0],
+            team_ids[1],
+            now,
+            now + Duration::seconds(game_duration_seconds)
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let user_ids: Vec<Uuid> = users.iter().map(|user| user.user_id).collect();
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO sandboxes (organization_id, league_id, user_ids, created_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            organization_id,
+            league_id,
+            &user_ids,
+            created_by,
+            expires_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        tracing::info!("🧪 Provisioned sandbox {} ({}) until {}", id, organization_slug, expires_at);
+
+        Ok(Sandbox {
+            id,
+            organization_id,
+            organization_slug,
+            league_id,
+            season_id,
+            live_game_id,
+            password,
+            users,
+            expires_at,
+        })
+    }
+
+    /// Remove the sandbox with its organization, league and users. False if it doesn't exist.
+    pub async fn tear_down(&self, sandbox_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let Some(sandbox) = sqlx::query!(
+            "SELECT organization_id, league_id, user_ids FROM sandboxes WHERE id = $1 FOR UPDATE",
+            sandbox_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        // The league goes first: score events and summaries of its games keep users from being deleted.
+        // Teams and memberships go with the users.
+        sqlx::query!("DELETE FROM leagues WHERE id = $1", sandbox.league_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM users WHERE id = ANY($1)", &sandbox.user_ids)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM organizations WHERE id = $1", sandbox.organization_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        tracing::info!("🧹 Tore down sandbox {}", sandbox_id);
+        Ok(true)
+    }
+
+    /// Tear down every expired sandbox, returning how many were removed
+    pub async fn tear_down_expired(&self) -> Result<usize, sqlx::Error> {
+        let expired = sqlx::query_scalar!("SELECT id FROM sandboxes WHERE expires_at <= NOW()")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut removed = 0;
+        for sandbox_id in expired {
+            if self.tear_down(sandbox_id).await? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
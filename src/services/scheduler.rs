@@ -3,15 +3,65 @@ use std::collections::HashMap;
 use tokio::sync::Mutex;
 use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
 use sqlx::PgPool;
+use secrecy::SecretString;
+use chrono::Datelike;
 use uuid::Uuid;
 use std::error::Error;
 use crate::services::game_evaluation_service::GameEvaluationService;
 use crate::services::manage_game_service::ManageGameService;
+use crate::services::backup_verification_service::BackupVerificationService;
+use crate::services::score_consistency_service::ScoreConsistencyService;
+use crate::services::hr_trend_service::HrTrendService;
+use crate::services::scoring_calibration_service::ScoringCalibrationService;
+use crate::services::threshold_detection_service::ThresholdDetectionService;
+use crate::services::weekly_digest_service::WeeklyDigestService;
+use crate::services::inactivity_nudge_service::InactivityNudgeService;
+use crate::services::email_service::EmailService;
+use crate::services::sync_service::SyncService;
+use crate::services::sync_diagnostics_service::SyncDiagnosticsService;
+use crate::services::game_commentary_service::GameCommentaryService;
+use crate::services::halftime_report_service::HalftimeReportService;
+use crate::services::post_publishing_service::PostPublishingService;
+use crate::services::league_webhook_service::LeagueWebhookService;
+use crate::services::review_lock_service::ReviewLockService;
+use crate::services::account_data_service::AccountDataService;
+use crate::services::season_recap_service::SeasonRecapService;
+use crate::services::share_card_service::ShareCardService;
+use crate::services::minio_service::MinIOService;
+use crate::services::broadcast_service::BroadcastService;
+use crate::services::quiet_hours_service::DeferredPushService;
+#[cfg(feature = "sandbox")]
+use crate::services::sandbox_service::SandboxService;
+use crate::services::event_outbox::EventOutbox;
+use crate::services::game_watchdog_service::GameWatchdogService;
+use crate::league::schedule::ScheduleService;
+use crate::config::game_watchdog::GameWatchdogSettings;
+use crate::config::stat_decay::StatDecaySettings;
+use crate::services::stat_decay_service::StatDecayService;
+use crate::services::job_registry::{JobRegistry, JobRunner, RegisteredJobInfo};
+use crate::models::scheduled_job::{ScheduledJobConfig, ScheduleReloadSummary};
+
+/// Schedule config key shared by the game cycles of all seasons
+const GAME_CYCLE_JOB: &str = "game_cycle";
+/// Game cycle schedule used when none is configured
+const DEFAULT_GAME_CYCLE_SCHEDULE: &str = "0 * * * * *";
 
 pub struct SchedulerService {
     scheduler: Arc<Mutex<JobScheduler>>,
     pool: PgPool,
     redis_client: Arc<redis::Client>,
+    // Storage for rendered images; image-render jobs only run when set
+    minio_service: Option<MinIOService>,
+    // Threshold and auto-finalize switch of the unfinished game watchdog
+    game_watchdog: GameWatchdogSettings,
+    // Rate and floors of the weekly stat decay
+    stat_decay: StatDecaySettings,
+    // Connection string backup verification dumps and restores with; the job errors without
+    backup_database_url: Option<SecretString>,
+    // Email channel of jobs that notify users by email
+    email_service: Option<Arc<EmailService>>,
+    // Registered jobs and their run stats, for inspection and manual runs
+    job_registry: JobRegistry,
     // Track active season jobs by season_id -> job_id
     active_jobs: Arc<Mutex<HashMap<Uuid, Uuid>>>,
 }
@@ -24,10 +74,51 @@ impl SchedulerService {
             scheduler: Arc::new(Mutex::new(scheduler)),
             pool,
             redis_client,
+            minio_service: None,
+            game_watchdog: GameWatchdogSettings::default(),
+            stat_decay: StatDecaySettings::default(),
+            backup_database_url: None,
+            email_service: None,
+            job_registry: JobRegistry::new(),
             active_jobs: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
+    /// Enable jobs that render images into MinIO (season recap cards)
+    pub fn with_minio(mut self, minio_service: MinIOService) -> Self {
+        self.minio_service = Some(minio_service);
+        self
+    }
+
+    /// Configure the watchdog for games left unfinalized past their end time
+    pub fn with_game_watchdog(mut self, settings: GameWatchdogSettings) -> Self {
+        self.game_watchdog = settings;
+        self
+    }
+
+    /// Configure the weekly decay of avatar stats
+    pub fn with_stat_decay(mut self, settings: StatDecaySettings) -> Self {
+        self.stat_decay = settings;
+        self
+    }
+
+    /// Let the nightly backup verification dump and restore the database
+    pub fn with_backup_database_url(mut self, database_url: SecretString) -> Self {
+        self.backup_database_url = Some(database_url);
+        self
+    }
+
+    /// Let jobs reach users by email, for those who didn't turn email off
+    pub fn with_email(mut self, email_service: EmailService) -> Self {
+        self.email_service = Some(Arc::new(email_service));
+        self
+    }
+
+    /// Watchdog settings in use, for reporting
+    pub fn game_watchdog_settings(&self) -> &GameWatchdogSettings {
+        &self.game_watchdog
+    }
+
     pub async fn start(&self) -> Result<(), Box<dyn Error>> {
         let scheduler = self.scheduler.lock().await;
 
@@ -35,6 +126,104 @@ impl SchedulerService {
         let poll_job = self.create_poll_expiration_job()?;
         scheduler.add(poll_job).await?;
 
+        // Schedule nightly backup verification job
+        let backup_job = self.create_backup_verification_job()?;
+        scheduler.add(backup_job).await?;
+
+        // Schedule score consistency check job
+        let consistency_job = self.create_score_consistency_job()?;
+        scheduler.add(consistency_job).await?;
+
+        // Schedule nightly heart rate trend job
+        let hr_trend_job = self.create_hr_trend_job()?;
+        scheduler.add(hr_trend_job).await?;
+
+        // Schedule weekly scoring calibration from effort ratings
+        let scoring_calibration_job = self.create_scoring_calibration_job()?;
+        scheduler.add(scoring_calibration_job).await?;
+
+        // Schedule weekly VT1/VT2 detection from recent workouts
+        let threshold_detection_job = self.create_threshold_detection_job()?;
+        scheduler.add(threshold_detection_job).await?;
+
+        // Schedule weekly digest job
+        let weekly_digest_job = self.create_weekly_digest_job()?;
+        scheduler.add(weekly_digest_job).await?;
+
+        // Schedule daily inactivity nudge job
+        let inactivity_nudge_job = self.create_inactivity_nudge_job()?;
+        scheduler.add(inactivity_nudge_job).await?;
+
+        // Schedule nightly sync tombstone pruning
+        let sync_tombstone_prune_job = self.create_sync_tombstone_prune_job()?;
+        scheduler.add(sync_tombstone_prune_job).await?;
+
+        // Schedule nightly pruning of old upload attempts
+        let upload_attempt_prune_job = self.create_upload_attempt_prune_job()?;
+        scheduler.add(upload_attempt_prune_job).await?;
+
+        // Schedule game commentary for the final minutes of running games
+        let game_commentary_job = self.create_game_commentary_job()?;
+        scheduler.add(game_commentary_job).await?;
+
+        // Schedule halftime reports at the midpoint of running games
+        let halftime_report_job = self.create_halftime_report_job()?;
+        scheduler.add(halftime_report_job).await?;
+
+        // Schedule publishing of scheduled draft posts
+        let post_publishing_job = self.create_post_publishing_job()?;
+        scheduler.add(post_publishing_job).await?;
+
+        // Schedule retries of result cards that failed to reach league chat channels
+        let league_webhook_job = self.create_league_webhook_job()?;
+        scheduler.add(league_webhook_job).await?;
+
+        // Schedule evaluation of games held back for workout reports
+        let review_lock_job = self.create_review_lock_job()?;
+        scheduler.add(review_lock_job).await?;
+
+        // Schedule purging of accounts deleted by their users
+        let account_purge_job = self.create_account_purge_job()?;
+        scheduler.add(account_purge_job).await?;
+
+        // Schedule delivery of scheduled admin announcements
+        let broadcast_job = self.create_broadcast_job()?;
+        scheduler.add(broadcast_job).await?;
+
+        // Schedule the weekly decay of avatar stats
+        let stat_decay_job = self.create_stat_decay_job()?;
+        scheduler.add(stat_decay_job).await?;
+
+        // Schedule pushes held back by quiet hours
+        let deferred_push_job = self.create_deferred_push_job()?;
+        scheduler.add(deferred_push_job).await?;
+
+        // Schedule teardown of expired sandbox organizations
+        #[cfg(feature = "sandbox")]
+        scheduler.add(self.create_sandbox_teardown_job()?).await?;
+
+        // Schedule publishing of outbox events left behind by failed publishes
+        let event_outbox_job = self.create_event_outbox_job()?;
+        scheduler.add(event_outbox_job).await?;
+
+        // Schedule re-materialization of upcoming games after timezone rule changes
+        let schedule_rematerialization_job = self.create_schedule_rematerialization_job()?;
+        scheduler.add(schedule_rematerialization_job).await?;
+
+        // Schedule the watchdog for games left unfinalized
+        let game_watchdog_job = self.create_game_watchdog_job()?;
+        scheduler.add(game_watchdog_job).await?;
+
+        // Schedule season recap card rendering
+        if let Some(recap_card_job) = self.create_recap_card_render_job()? {
+            scheduler.add(recap_card_job).await?;
+        }
+
+        // Schedule share card rendering
+        if let Some(share_card_job) = self.create_share_card_render_job()? {
+            scheduler.add(share_card_job).await?;
+        }
+
         scheduler.start().await?;
 
         tracing::info!("✅ [SCHEDULER] Service started successfully");
@@ -42,6 +231,17 @@ impl SchedulerService {
         // Release the lock before scheduling seasons
         drop(scheduler);
 
+        // Move jobs to the schedules configured by admins
+        match self.reload_job_schedules().await {
+            Ok(summary) if !summary.rescheduled.is_empty() => {
+                tracing::info!("✅ [SCHEDULER] Applied configured schedules to {} jobs", summary.rescheduled.len());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("❌ [SCHEDULER] Failed to load configured job schedules, using defaults: {}", e);
+            }
+        }
+
         // Load and schedule all active seasons from the database
         tracing::info!("🔍 [SCHEDULER] Loading active seasons from database...");
         match self.load_active_seasons().await {
@@ -74,14 +274,13 @@ impl SchedulerService {
 
         tracing::info!("🔍 [SCHEDULER] Found {} active seasons with auto-evaluation enabled", active_seasons.len());
 
+        let cron_expr = self.game_cycle_schedule().await;
+
         for season in &active_seasons {
             tracing::info!("📅 [SCHEDULER] Scheduling season '{}' (id: {}, duration: {}s)",
                 season.name, season.id, season.game_duration_seconds);
 
-            // Calculate cron expression based on game duration (default to every minute)
-            let cron_expr = "0 * * * * *"; // Run every minute
-
-            match self.schedule_season_with_frequency(season.id, season.name.clone(), cron_expr).await {
+            match self.schedule_season_with_frequency(season.id, season.name.clone(), &cron_expr).await {
                 Ok(_) => {
                     tracing::info!("✅ [SCHEDULER] Scheduled season '{}'", season.name);
                 }
@@ -99,7 +298,7 @@ impl SchedulerService {
         let pool = self.pool.clone();
         let redis_client = self.redis_client.clone();
 
-        Job::new_async("0 */5 * * * *", move |_uuid, _l| {
+        let runner: JobRunner = Arc::new(move || {
             let pool = pool.clone();
             let redis_client = redis_client.clone();
 
@@ -120,24 +319,730 @@ impl SchedulerService {
                     Ok(polls) => polls,
                     Err(e) => {
                         tracing::error!("❌ [SCHEDULER] Failed to fetch expired polls: {}", e);
-                        return;
+                        return Err(format!("Failed to fetch expired polls: {e}"));
                     }
                 };
 
                 if expired_polls.is_empty() {
                     tracing::debug!("[SCHEDULER] No expired polls found");
-                    return;
+                    return Ok("No expired polls".to_string());
                 }
 
                 tracing::info!("[SCHEDULER] Found {} expired polls to process", expired_polls.len());
 
-                for poll in expired_polls {
+                let mut failed = 0;
+                for poll in &expired_polls {
                     if let Err(e) = Self::process_expired_poll(&pool, &redis_client, poll.id).await {
                         tracing::error!("❌ Failed to process expired poll {}: {}", poll.id, e);
+                        failed += 1;
                     }
                 }
+
+                if failed > 0 {
+                    return Err(format!("Failed to expire {} of {} polls", failed, expired_polls.len()));
+                }
+                Ok(format!("Expired {} polls", expired_polls.len()))
             })
-        })
+        });
+        self.job_registry.register("poll_expiration", "0 */5 * * * *", "Expire team polls past their deadline", runner)
+    }
+
+    /// Create backup verification job that runs every night at 03:30 UTC, verifying the large
+    /// tables too on Sundays
+    fn create_backup_verification_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let database_url = self.backup_database_url.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let database_url = database_url.clone();
+
+            Box::pin(async move {
+                let full = chrono::Utc::now().weekday() == chrono::Weekday::Sun;
+                tracing::info!("💾 [SCHEDULER] Running {} backup verification", if full { "full" } else { "nightly" });
+
+                let mut backup_service = BackupVerificationService::new(pool);
+                if let Some(database_url) = database_url {
+                    backup_service = backup_service.with_database_url(database_url);
+                }
+                match backup_service.run_verification("scheduler", full).await {
+                    Ok(run) if run.status == "passed" => {
+                        tracing::info!("✅ [SCHEDULER] Backup verification {} passed", run.id);
+                        Ok(format!("Backup verification {} passed", run.id))
+                    }
+                    Ok(run) => {
+                        tracing::error!("❌ [SCHEDULER] Backup verification {} finished with status '{}'", run.id, run.status);
+                        Err(format!("Backup verification {} finished with status '{}'", run.id, run.status))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to run backup verification: {}", e);
+                        Err(format!("Failed to run backup verification: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("backup_verification", "0 30 3 * * *", "Restore and verify the latest backup", runner)
+    }
+
+    /// Create score consistency job that runs every 15 minutes
+    fn create_score_consistency_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                tracing::debug!("🔍 [SCHEDULER] Running score consistency check");
+
+                let consistency_service = ScoreConsistencyService::new(pool);
+                match consistency_service.run_check().await {
+                    Ok(run) if run.issues.is_empty() => {
+                        tracing::debug!("✅ [SCHEDULER] Score consistency check {} found no issues", run.id);
+                        Ok(format!("Score consistency check {} found no issues", run.id))
+                    }
+                    Ok(run) => {
+                        tracing::error!("❌ [SCHEDULER] Score consistency check {} flagged {} games", run.id, run.games_with_issues);
+                        Ok(format!("Score consistency check {} flagged {} games", run.id, run.games_with_issues))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to run score consistency check: {}", e);
+                        Err(format!("Failed to run score consistency check: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("score_consistency", "0 */15 * * * *", "Compare live game scores with their score events", runner)
+    }
+
+    /// Create heart rate trend job that runs every night at 02:15 UTC
+    fn create_hr_trend_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                tracing::info!("❤️ [SCHEDULER] Computing nightly heart rate trends");
+
+                let hr_trend_service = HrTrendService::new(pool);
+                match hr_trend_service.run_nightly().await {
+                    Ok(summary) => {
+                        tracing::info!("✅ [SCHEDULER] Heart rate trends updated: {} resting HR snapshots, {} workouts processed",
+                            summary.resting_hr_snapshots, summary.workouts_processed);
+                        Ok(format!("{} resting HR snapshots, {} workouts processed",
+                            summary.resting_hr_snapshots, summary.workouts_processed))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to compute heart rate trends: {}", e);
+                        Err(format!("Failed to compute heart rate trends: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("hr_trends", "0 15 2 * * *", "Compute resting heart rate and HR zone trends", runner)
+    }
+
+    /// Create a job that recalibrates personal scoring from effort ratings every Monday at 03:30 UTC
+    fn create_scoring_calibration_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                tracing::info!("🎚️ [SCHEDULER] Calibrating personal scoring from effort ratings");
+
+                match ScoringCalibrationService::new(pool).run_weekly().await {
+                    Ok(summary) => {
+                        tracing::info!("✅ [SCHEDULER] Scoring calibrated for {} users, {} reset",
+                            summary.users_calibrated, summary.users_reset);
+                        Ok(format!("{} users calibrated, {} reset", summary.users_calibrated, summary.users_reset))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to calibrate scoring: {}", e);
+                        Err(format!("Failed to calibrate scoring: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("scoring_calibration", "0 30 3 * * Mon", "Calibrate personal scoring from effort ratings", runner)
+    }
+
+    /// Create a job that proposes VT1/VT2 thresholds detected from recent workouts every Sunday at 03:45 UTC
+    fn create_threshold_detection_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                tracing::info!("🫀 [SCHEDULER] Detecting VT1/VT2 thresholds from recent workouts");
+
+                match ThresholdDetectionService::new(pool).run_weekly().await {
+                    Ok(summary) => {
+                        tracing::info!("✅ [SCHEDULER] Thresholds analyzed for {} users, {} proposals created",
+                            summary.users_analyzed, summary.proposals_created);
+                        Ok(format!("{} users analyzed, {} proposals created", summary.users_analyzed, summary.proposals_created))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to detect thresholds: {}", e);
+                        Err(format!("Failed to detect thresholds: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("threshold_detection", "0 45 3 * * Sun", "Propose VT1/VT2 thresholds detected from recent workouts", runner)
+    }
+
+    /// Create a job that composes and delivers the weekly digest every Monday morning (UTC)
+    fn create_weekly_digest_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                tracing::info!("📰 [SCHEDULER] Generating weekly digests");
+
+                let weekly_digest_service = WeeklyDigestService::new(pool);
+                match weekly_digest_service.run_weekly().await {
+                    Ok(summary) => {
+                        tracing::info!("✅ [SCHEDULER] Weekly digests for {} generated: {} digests, {} standings snapshotted",
+                            summary.week_start, summary.digests_created, summary.standings_snapshotted);
+                        Ok(format!("Week of {}: {} digests, {} standings snapshotted",
+                            summary.week_start, summary.digests_created, summary.standings_snapshotted))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to generate weekly digests: {}", e);
+                        Err(format!("Failed to generate weekly digests: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("weekly_digest", "0 0 7 * * Mon", "Compose and deliver the weekly digest", runner)
+    }
+
+    /// Create a job that nudges inactive team members every day at 10:00 UTC
+    fn create_inactivity_nudge_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let email_service = self.email_service.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let email_service = email_service.clone();
+
+            Box::pin(async move {
+                tracing::info!("👋 [SCHEDULER] Checking for inactive team members");
+
+                let mut inactivity_nudge_service = InactivityNudgeService::new(pool);
+                if let Some(email_service) = email_service {
+                    inactivity_nudge_service = inactivity_nudge_service.with_email(email_service);
+                }
+                match inactivity_nudge_service.run().await {
+                    Ok(summary) => {
+                        tracing::info!("✅ [SCHEDULER] Inactivity check done: {} inactive members, {} nudged, {} captains notified",
+                            summary.inactive_members, summary.members_nudged, summary.captains_notified);
+                        Ok(format!("{} inactive members, {} nudged, {} captains notified",
+                            summary.inactive_members, summary.members_nudged, summary.captains_notified))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to run inactivity nudges: {}", e);
+                        Err(format!("Failed to run inactivity nudges: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("inactivity_nudges", "0 0 10 * * *", "Nudge inactive team members and their captains", runner)
+    }
+
+    /// Create a job that prunes expired delta sync tombstones every night at 04:00 UTC
+    fn create_sync_tombstone_prune_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let sync_service = SyncService::new(pool);
+                match sync_service.prune_tombstones().await {
+                    Ok(pruned) => {
+                        tracing::info!("🧹 [SCHEDULER] Pruned {} expired sync tombstones", pruned);
+                        Ok(format!("Pruned {} sync tombstones", pruned))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to prune sync tombstones: {}", e);
+                        Err(format!("Failed to prune sync tombstones: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("sync_tombstone_prune", "0 0 4 * * *", "Prune expired delta sync tombstones", runner)
+    }
+
+    /// Create a job that prunes upload attempts past their retention every night at 04:10 UTC
+    fn create_upload_attempt_prune_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let diagnostics_service = SyncDiagnosticsService::new(pool);
+                match diagnostics_service.prune_attempts().await {
+                    Ok(pruned) => {
+                        tracing::info!("🧹 [SCHEDULER] Pruned {} old upload attempts", pruned);
+                        Ok(format!("Pruned {} upload attempts", pruned))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to prune upload attempts: {}", e);
+                        Err(format!("Failed to prune upload attempts: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("upload_attempt_prune", "0 10 4 * * *", "Prune upload attempts kept for sync diagnostics", runner)
+    }
+
+    /// Create a job that moves upcoming games back to their local kickoff time, checked every hour.
+    /// Timezone rules ship with the binary, so a deploy with new DST rules is picked up within the hour.
+    fn create_schedule_rematerialization_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let schedule_service = ScheduleService::new(pool);
+                match schedule_service.rematerialize_upcoming_games().await {
+                    Ok(moved) => {
+                        if moved > 0 {
+                            tracing::info!("🕐 [SCHEDULER] Moved {} upcoming games to their local kickoff time", moved);
+                        }
+                        Ok(format!("Moved {} upcoming games", moved))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to re-materialize upcoming games: {}", e);
+                        Err(format!("Failed to re-materialize upcoming games: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register(
+            "schedule_rematerialization",
+            "0 5 * * * *",
+            "Keep upcoming games at their local kickoff time when DST rules change",
+            runner,
+        )
+    }
+
+    /// Create a job that calls the final minutes of running games, checked every minute
+    fn create_game_commentary_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+
+            Box::pin(async move {
+                let commentary_service = GameCommentaryService::new(pool, Some(redis_client));
+                match commentary_service.comment_on_final_minutes().await {
+                    Ok(posted) => {
+                        if posted > 0 {
+                            tracing::info!("🎙️ [SCHEDULER] Called the final minutes of {} games", posted);
+                        }
+                        Ok(format!("Called the final minutes of {} games", posted))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to post final minutes commentary: {}", e);
+                        Err(format!("Failed to post final minutes commentary: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("game_commentary", "0 * * * * *", "Call the final minutes of running games", runner)
+    }
+
+    /// Create a job that reports on running games reaching their midpoint, checked every minute
+    fn create_halftime_report_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+
+            Box::pin(async move {
+                let halftime_service = HalftimeReportService::new(pool, Some(redis_client));
+                match halftime_service.report_halftimes().await {
+                    Ok(written) => {
+                        if written > 0 {
+                            tracing::info!("⏱️ [SCHEDULER] Wrote halftime reports for {} games", written);
+                        }
+                        Ok(format!("Wrote halftime reports for {} games", written))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to write halftime reports: {}", e);
+                        Err(format!("Failed to write halftime reports: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("halftime_report", "15 * * * * *", "Report on running games at their midpoint", runner)
+    }
+
+    /// Create a job that publishes draft posts whose scheduled time has come, every minute
+    fn create_post_publishing_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                match PostPublishingService::new(pool).publish_due().await {
+                    Ok(published) => {
+                        if published > 0 {
+                            tracing::info!("📣 [SCHEDULER] Published {} scheduled posts", published);
+                        }
+                        Ok(format!("Published {} scheduled posts", published))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to publish scheduled posts: {}", e);
+                        Err(format!("Failed to publish scheduled posts: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("post_publishing", "30 * * * * *", "Publish scheduled draft posts", runner)
+    }
+
+    /// Create a job that retries posting result cards to league chat channels, every minute
+    fn create_league_webhook_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                match LeagueWebhookService::new(pool).deliver_pending().await {
+                    Ok(delivered) => {
+                        if delivered > 0 {
+                            tracing::info!("💬 [SCHEDULER] Posted {} result cards to league chat channels", delivered);
+                        }
+                        Ok(format!("Posted {} result cards", delivered))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to post result cards: {}", e);
+                        Err(format!("Failed to post result cards: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("league_webhooks", "45 * * * * *", "Retry result cards for league Slack/Teams channels", runner)
+    }
+
+    /// Create a job that evaluates games held for workout reports once the reports are resolved
+    /// or the league's review lock ends, every 5 minutes
+    fn create_review_lock_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+
+            Box::pin(async move {
+                let held_games = ReviewLockService::new(pool.clone()).held_games().await
+                    .map_err(|e| format!("Failed to load held games: {e}"))?;
+                if held_games.is_empty() {
+                    return Ok("No held games".to_string());
+                }
+
+                match GameEvaluationService::new(pool, redis_client).evaluate_finished_live_games(&held_games).await {
+                    Ok(evaluated) => {
+                        if !evaluated.is_empty() {
+                            tracing::info!("🔓 [SCHEDULER] Evaluated {} games released from review", evaluated.len());
+                        }
+                        Ok(format!("Evaluated {} of {} held games", evaluated.len(), held_games.len()))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to evaluate held games: {}", e);
+                        Err(format!("Failed to evaluate held games: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("review_lock", "15 */5 * * * *", "Evaluate games held for workout reports once released", runner)
+    }
+
+    /// Create a job that purges accounts whose deletion grace period has passed, every hour.
+    /// Their stored media is deleted too when MinIO is available.
+    fn create_account_purge_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let minio_service = self.minio_service.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let minio_service = minio_service.clone();
+
+            Box::pin(async move {
+                match AccountDataService::new(pool).purge_due_accounts(minio_service.as_ref()).await {
+                    Ok(purged) => {
+                        if purged > 0 {
+                            tracing::info!("🗑️ [SCHEDULER] Purged {} deleted accounts", purged);
+                        }
+                        Ok(format!("Purged {} deleted accounts", purged))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to purge deleted accounts: {}", e);
+                        Err(format!("Failed to purge deleted accounts: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("account_purge", "0 20 * * * *", "Purge accounts whose deletion grace period has passed", runner)
+    }
+
+    /// Create a job that alerts on games left unfinalized past their end time, every 5 minutes
+    fn create_game_watchdog_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+        let settings = self.game_watchdog.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+            let settings = settings.clone();
+
+            Box::pin(async move {
+                let watchdog = GameWatchdogService::new(pool, redis_client, settings);
+                match watchdog.run_check().await {
+                    Ok(summary) => {
+                        if summary.new_alerts > 0 || summary.auto_finalized > 0 {
+                            tracing::warn!("🐕 [SCHEDULER] Game watchdog: {} overdue games, {} new alerts, {} auto-finalized",
+                                summary.overdue_games, summary.new_alerts, summary.auto_finalized);
+                        }
+                        Ok(format!("{} overdue games, {} new alerts, {} admins notified, {} auto-finalized, {} resolved",
+                            summary.overdue_games, summary.new_alerts, summary.admins_notified,
+                            summary.auto_finalized, summary.resolved))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to run game watchdog: {}", e);
+                        Err(format!("Failed to run game watchdog: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("game_watchdog", "45 */5 * * * *", "Alert on games past their end time that were never finalized", runner)
+    }
+
+    /// Create a job that sends scheduled admin announcements once they are due, every minute
+    fn create_broadcast_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+
+            Box::pin(async move {
+                let broadcast_service = BroadcastService::new(pool, Some(redis_client));
+                match broadcast_service.send_due().await {
+                    Ok(sent) => {
+                        if sent > 0 {
+                            tracing::info!("📣 [SCHEDULER] Sent {} scheduled broadcasts", sent);
+                        }
+                        Ok(format!("Sent {} scheduled broadcasts", sent))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to send scheduled broadcasts: {}", e);
+                        Err(format!("Failed to send scheduled broadcasts: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("broadcasts", "15 * * * * *", "Send scheduled admin announcements that are due", runner)
+    }
+
+    /// Create a job that decays avatar stats, every Monday
+    fn create_stat_decay_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let settings = self.stat_decay.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let settings = settings.clone();
+
+            Box::pin(async move {
+                match StatDecayService::new(pool, settings).apply_weekly_decay(chrono::Utc::now().date_naive()).await {
+                    Ok(decayed) => {
+                        tracing::info!("📉 [SCHEDULER] Decayed the stats of {} users", decayed);
+                        Ok(format!("Decayed the stats of {} users", decayed))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to decay stats: {}", e);
+                        Err(format!("Failed to decay stats: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("stat_decay", "0 0 2 * * Mon", "Decay avatar stamina and strength toward their floors", runner)
+    }
+
+    /// Create a job that sends push notifications whose recipient's quiet hours are over, every minute
+    fn create_deferred_push_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                match DeferredPushService::new(pool).send_due().await {
+                    Ok(sent) => {
+                        if sent > 0 {
+                            tracing::info!("🌅 [SCHEDULER] Sent {} pushes held back by quiet hours", sent);
+                        }
+                        Ok(format!("Sent {} deferred pushes", sent))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to send deferred pushes: {}", e);
+                        Err(format!("Failed to send deferred pushes: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("deferred_push", "45 * * * * *", "Send push notifications held back by quiet hours", runner)
+    }
+
+    /// Create a job that tears down expired sandbox organizations, every 5 minutes
+    #[cfg(feature = "sandbox")]
+    fn create_sandbox_teardown_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                match SandboxService::new(pool).tear_down_expired().await {
+                    Ok(removed) => {
+                        if removed > 0 {
+                            tracing::info!("🧹 [SCHEDULER] Tore down {} expired sandboxes", removed);
+                        }
+                        Ok(format!("Tore down {} expired sandboxes", removed))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to tear down expired sandboxes: {}", e);
+                        Err(format!("Failed to tear down expired sandboxes: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("sandbox_teardown", "30 */5 * * * *", "Tear down expired sandbox organizations", runner)
+    }
+
+    /// Create a job that publishes outbox events that were not published after their commit, every minute,
+    /// and prunes events published longer ago than the retention
+    fn create_event_outbox_job(&self) -> Result<Job, JobSchedulerError> {
+        let pool = self.pool.clone();
+        let redis_client = self.redis_client.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let redis_client = redis_client.clone();
+
+            Box::pin(async move {
+                let event_outbox = EventOutbox::new(pool, Some(redis_client));
+                let published = match event_outbox.publish_pending().await {
+                    Ok(published) => {
+                        if published > 0 {
+                            tracing::info!("📬 [SCHEDULER] Published {} pending outbox events", published);
+                        }
+                        published
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to publish pending outbox events: {}", e);
+                        return Err(format!("Failed to publish pending outbox events: {e}"));
+                    }
+                };
+                match event_outbox.prune_published().await {
+                    Ok(pruned) => {
+                        if pruned > 0 {
+                            tracing::info!("🧹 [SCHEDULER] Pruned {} published outbox events", pruned);
+                        }
+                        Ok(format!("Published {} pending outbox events, pruned {}", published, pruned))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to prune published outbox events: {}", e);
+                        Err(format!("Failed to prune published outbox events: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry.register("event_outbox", "40 * * * * *", "Publish outbox events that were not published after their commit", runner)
+    }
+
+    /// Create a job that renders requested season recap cards every minute, if MinIO is available
+    fn create_recap_card_render_job(&self) -> Result<Option<Job>, JobSchedulerError> {
+        let Some(minio_service) = self.minio_service.clone() else {
+            return Ok(None);
+        };
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let minio_service = minio_service.clone();
+
+            Box::pin(async move {
+                let recap_service = SeasonRecapService::new(pool);
+                match recap_service.render_pending_cards(&minio_service).await {
+                    Ok(rendered) => {
+                        if rendered > 0 {
+                            tracing::info!("🖼️ [SCHEDULER] Rendered {} season recap cards", rendered);
+                        }
+                        Ok(format!("Rendered {} season recap cards", rendered))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to render season recap cards: {}", e);
+                        Err(format!("Failed to render season recap cards: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry
+            .register("recap_card_render", "30 * * * * *", "Render requested season recap cards", runner)
+            .map(Some)
+    }
+
+    /// Create a job that renders requested share cards every 15 seconds, if MinIO is available
+    fn create_share_card_render_job(&self) -> Result<Option<Job>, JobSchedulerError> {
+        let Some(minio_service) = self.minio_service.clone() else {
+            return Ok(None);
+        };
+        let pool = self.pool.clone();
+
+        let runner: JobRunner = Arc::new(move || {
+            let pool = pool.clone();
+            let minio_service = minio_service.clone();
+
+            Box::pin(async move {
+                let share_card_service = ShareCardService::new(pool);
+                match share_card_service.render_pending_cards(&minio_service).await {
+                    Ok(rendered) => {
+                        if rendered > 0 {
+                            tracing::info!("🖼️ [SCHEDULER] Rendered {} share cards", rendered);
+                        }
+                        Ok(format!("Rendered {} share cards", rendered))
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ [SCHEDULER] Failed to render share cards: {}", e);
+                        Err(format!("Failed to render share cards: {e}"))
+                    }
+                }
+            })
+        });
+        self.job_registry
+            .register("share_card_render", "*/15 * * * * *", "Render requested badge, game result and personal record cards", runner)
+            .map(Some)
     }
 
     /// Process an expired poll - just mark it as expired
@@ -168,9 +1073,33 @@ impl SchedulerService {
     }
 
     /// Schedule complete game management cycle for a new season
-    /// Uses every-minute schedule to handle all game durations efficiently
+    /// Uses the configured game cycle schedule, every minute by default
     pub async fn schedule_season(&self, season_id: Uuid, season_name: String) -> Result<(), JobSchedulerError> {
-        self.schedule_season_with_frequency(season_id, season_name, "0 * * * * *").await
+        let cron_expr = self.game_cycle_schedule().await;
+        self.schedule_season_with_frequency(season_id, season_name, &cron_expr).await
+    }
+
+    /// Configured schedule of the season game cycles, falling back to every minute
+    async fn game_cycle_schedule(&self) -> String {
+        let configured = sqlx::query_scalar!(
+            "SELECT cron_expression FROM scheduled_jobs WHERE job_name = $1",
+            GAME_CYCLE_JOB
+        )
+        .fetch_optional(&self.pool)
+        .await;
+
+        match configured {
+            Ok(Some(cron_expr)) if JobRegistry::validate_schedule(&cron_expr).is_ok() => cron_expr,
+            Ok(Some(cron_expr)) => {
+                tracing::error!("❌ [SCHEDULER] Ignoring invalid game cycle schedule '{}'", cron_expr);
+                DEFAULT_GAME_CYCLE_SCHEDULE.to_string()
+            }
+            Ok(None) => DEFAULT_GAME_CYCLE_SCHEDULE.to_string(),
+            Err(e) => {
+                tracing::error!("❌ [SCHEDULER] Failed to load game cycle schedule: {}", e);
+                DEFAULT_GAME_CYCLE_SCHEDULE.to_string()
+            }
+        }
     }
 
     /// Schedule complete game management cycle for a new season with custom frequency
@@ -185,7 +1114,7 @@ impl SchedulerService {
         // Clone season_name before moving into closure
         let season_name_for_logging = season_name.clone();
         
-        let game_cycle_job = Job::new_async(&cron_expr, move |_uuid, _l| {
+        let runner: JobRunner = Arc::new(move || {
             let pool = pool.clone();
             let redis_client = redis_client.clone();
             let season_name = season_name.clone();
@@ -208,35 +1137,52 @@ impl SchedulerService {
                         tracing::info!("   ▶️  Games just started: {:?}", started_games);
                         tracing::info!("   🏁 Games just finished: {:?}", finished_games);
 
-                        if !finished_games.is_empty() {
-                            // Step 2: Evaluate any finished games
-                            tracing::info!("⏰ [SCHEDULER] Step 2: Evaluating {} finished games", finished_games.len());
-                            match evaluate_games.evaluate_finished_live_games(&finished_games).await {
+                        // Step 2: Evaluate finished games once late uploads can't score in them anymore
+                        let games_to_evaluate = match manage_games.get_games_ready_to_evaluate().await {
+                            Ok(games) => games,
+                            Err(e) => {
+                                tracing::error!("❌ [SCHEDULER] Failed to load games ready for evaluation: {}", e);
+                                return Err(format!("Failed to load games ready for evaluation: {e}"));
+                            }
+                        };
+                        if !games_to_evaluate.is_empty() {
+                            tracing::info!("⏰ [SCHEDULER] Step 2: Evaluating {} finished games past the upload grace period", games_to_evaluate.len());
+                            match evaluate_games.evaluate_finished_live_games(&games_to_evaluate).await {
                                 Ok(result) => {
                                     tracing::info!("✅ [SCHEDULER] Game evaluation completed. Calculated final scores for {} games", result.len());
                                 }
                                 Err(e) => {
                                     let error_msg = e.to_string();
-                                    tracing::error!("❌ [SCHEDULER] Game evaluation failed for games: {:?} - {}", finished_games, error_msg);
+                                    tracing::error!("❌ [SCHEDULER] Game evaluation failed for games: {:?} - {}", games_to_evaluate, error_msg);
+                                    return Err(format!("Game evaluation failed for {} finished games: {}", games_to_evaluate.len(), error_msg));
                                 }
                             }
                         } else {
-                            tracing::info!("ℹ️  [SCHEDULER] No finished games to evaluate");
+                            tracing::info!("ℹ️  [SCHEDULER] No finished games past the upload grace period to evaluate");
                         }
 
                         if started_games.is_empty() && finished_games.is_empty() {
                             tracing::info!("ℹ️  [SCHEDULER] No state changes this cycle (no games started or finished)");
                         }
+
+                        tracing::info!("🏁 [SCHEDULER] Completed cycle for season '{}' at {}", season_name, chrono::Utc::now().to_rfc3339());
+                        Ok(format!("{} games started, {} games finished, {} live",
+                            started_games.len(), finished_games.len(), live_games.len()))
                     }
                     Err(e) => {
                         let error_msg = e.to_string();
                         tracing::error!("❌ [SCHEDULER] Season '{}' game cycle failed: {}", season_name, error_msg);
+                        Err(format!("Game cycle failed: {}", error_msg))
                     }
                 }
-
-                tracing::info!("🏁 [SCHEDULER] Completed cycle for season '{}' at {}", season_name, chrono::Utc::now().to_rfc3339());
             })
-        })?;
+        });
+        let game_cycle_job = self.job_registry.register(
+            &Self::game_cycle_job_name(season_id),
+            &cron_expr,
+            &format!("Start, finish and evaluate the games of season '{}'", season_name_for_logging),
+            runner,
+        )?;
         
         let job_id = game_cycle_job.guid();
         scheduler.add(game_cycle_job).await?;
@@ -248,8 +1194,8 @@ impl SchedulerService {
         let now = chrono::Utc::now();
         tracing::info!("✅ [SCHEDULER] Scheduled complete game management cycle for season '{}' (job_id: {})",
             season_name_for_logging, job_id);
-        tracing::info!("   📅 Cron expression: {} (runs every minute)", cron_expr);
-        tracing::info!("   ⏰ Next run: within 1 minute from {}", now.to_rfc3339());
+        tracing::info!("   📅 Cron expression: {}", cron_expr);
+        tracing::info!("   ⏰ Scheduled at {}", now.to_rfc3339());
         
         Ok(())
     }
@@ -259,6 +1205,8 @@ impl SchedulerService {
         let mut active_jobs = self.active_jobs.lock().await;
 
         if let Some(job_id) = active_jobs.remove(&season_id) {
+            // The registry knows the current id if the job was moved to another schedule meanwhile
+            let job_id = self.job_registry.unregister(&Self::game_cycle_job_name(season_id)).unwrap_or(job_id);
             let scheduler = self.scheduler.lock().await;
             scheduler.remove(&job_id).await?;
             tracing::info!("✅ Removed scheduling for season {}", season_id);
@@ -267,6 +1215,129 @@ impl SchedulerService {
         Ok(())
     }
 
+    /// Registry name of a season's game management cycle
+    pub fn game_cycle_job_name(season_id: Uuid) -> String {
+        format!("{GAME_CYCLE_JOB}:{season_id}")
+    }
+
+    /// Registered jobs with their run stats and next scheduled run
+    pub async fn list_jobs(&self) -> Vec<RegisteredJobInfo> {
+        let mut scheduler = self.scheduler.lock().await.clone();
+        let mut jobs = self.job_registry.list();
+        for job in &mut jobs {
+            job.next_run_at = scheduler.next_tick_for_job(job.job_id).await.ok().flatten();
+        }
+        jobs
+    }
+
+    /// Job schedules as configured by admins
+    pub async fn job_schedules(&self) -> Result<Vec<ScheduledJobConfig>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledJobConfig,
+            "SELECT * FROM scheduled_jobs ORDER BY job_name"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Change the configured schedule of a job. Returns None for unknown jobs.
+    /// Takes effect on the next `reload_job_schedules`.
+    pub async fn update_job_schedule(
+        &self,
+        job_name: &str,
+        cron_expression: &str,
+        updated_by: Uuid,
+    ) -> Result<Option<ScheduledJobConfig>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledJobConfig,
+            r#"
+            UPDATE scheduled_jobs
+            SET cron_expression = $2, updated_by = $3, updated_at = NOW()
+            WHERE job_name = $1
+            RETURNING *
+            "#,
+            job_name,
+            cron_expression,
+            updated_by
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Move running jobs whose configured schedule differs from their current one onto
+    /// the configured schedule, keeping their run stats. The `game_cycle` schedule
+    /// applies to the game cycle of every season.
+    pub async fn reload_job_schedules(&self) -> Result<ScheduleReloadSummary, Box<dyn Error>> {
+        let configured: HashMap<String, String> = self.job_schedules().await?
+            .into_iter()
+            .map(|config| (config.job_name, config.cron_expression))
+            .collect();
+
+        let scheduler = self.scheduler.lock().await;
+        let mut summary = ScheduleReloadSummary::default();
+        let mut moved_season_jobs = Vec::new();
+
+        for job in self.job_registry.list() {
+            let (config_key, season_id) = match job.name.split_once(':') {
+                Some((key, season_id)) => (key, Uuid::parse_str(season_id).ok()),
+                None => (job.name.as_str(), None),
+            };
+            let Some(cron_expr) = configured.get(config_key) else {
+                continue;
+            };
+            if *cron_expr == job.schedule {
+                continue;
+            }
+
+            let (replaced_id, new_job) = match self.job_registry.reschedule(&job.name, cron_expr) {
+                Some(Ok(rescheduled)) => rescheduled,
+                Some(Err(e)) => {
+                    tracing::error!("❌ [SCHEDULER] Cannot move job '{}' to schedule '{}': {}", job.name, cron_expr, e);
+                    summary.failed.push(format!("{}: invalid cron expression '{}'", job.name, cron_expr));
+                    continue;
+                }
+                None => continue,
+            };
+
+            let new_id = new_job.guid();
+            scheduler.remove(&replaced_id).await?;
+            scheduler.add(new_job).await?;
+            if let Some(season_id) = season_id {
+                moved_season_jobs.push((season_id, new_id));
+            }
+
+            tracing::info!("🔄 [SCHEDULER] Moved job '{}' from '{}' to '{}'", job.name, job.schedule, cron_expr);
+            summary.rescheduled.push(job.name);
+        }
+        drop(scheduler);
+
+        let mut active_jobs = self.active_jobs.lock().await;
+        for (season_id, job_id) in moved_season_jobs {
+            if let Some(active_job_id) = active_jobs.get_mut(&season_id) {
+                *active_job_id = job_id;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Run a registered job now, in the background. Returns false for unknown jobs.
+    pub fn trigger_job(&self, name: &str) -> bool {
+        if !self.job_registry.contains(name) {
+            return false;
+        }
+
+        let registry = self.job_registry.clone();
+        let name = name.to_string();
+        tokio::spawn(async move {
+            tracing::info!("▶️ [SCHEDULER] Manually running job '{}'", name);
+            if let Some(Err(e)) = registry.run(&name, "manual").await {
+                tracing::error!("❌ [SCHEDULER] Manual run of job '{}' failed: {}", name, e);
+            }
+        });
+        true
+    }
+
     /// Public test helper to process a specific expired poll
     /// Only available in test and debug builds
     #[cfg(any(test, debug_assertions))]
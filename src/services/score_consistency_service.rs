@@ -0,0 +1,456 @@
+use sqlx::{PgExecutor, PgPool, Row};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Game statuses whose stored score is expected to match its scoring events
+pub const CHECKED_GAME_STATUSES: &[&str] = &["in_progress", "finished", "evaluated"];
+
+/// Tolerance when comparing a score event's points with its workout's current stat gains
+const POINTS_TOLERANCE: f32 = 0.01;
+
+/// Service that recomputes game scores from live_score_events and workouts and flags
+/// games whose stored score or scoring events have drifted (e.g. after re-uploads at game end).
+#[derive(Debug)]
+pub struct ScoreConsistencyService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScoreDivergence {
+    pub game_id: Uuid,
+    pub season_id: Uuid,
+    pub status: String,
+    pub stored_home_score: i32,
+    pub stored_away_score: i32,
+    pub event_home_score: i32,
+    pub event_away_score: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "issue_type", rename_all = "snake_case")]
+pub enum ConsistencyIssueKind {
+    /// Stored game score differs from the score recomputed from live_score_events
+    ScoreMismatch {
+        stored_home_score: i32,
+        stored_away_score: i32,
+        event_home_score: i32,
+        event_away_score: i32,
+    },
+    /// The same workout has been credited to the game more than once
+    DuplicateWorkoutEvents {
+        user_id: Uuid,
+        workout_data_id: Uuid,
+        event_count: i64,
+    },
+    /// Two credited workouts of the same player overlap in time (usually a re-upload)
+    OverlappingWorkouts {
+        user_id: Uuid,
+        workout_data_id: Uuid,
+        overlapping_workout_data_id: Uuid,
+    },
+    /// A score event no longer matches the stamina + strength of its workout
+    StaleEventPoints {
+        user_id: Uuid,
+        workout_data_id: Uuid,
+        event_points: f32,
+        workout_points: f32,
+    },
+    /// A workout inside the game window was never credited to the game
+    UncreditedWorkout {
+        user_id: Uuid,
+        workout_data_id: Uuid,
+        workout_points: f32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameConsistencyIssue {
+    pub game_id: Uuid,
+    pub season_id: Uuid,
+    pub status: String,
+    #[serde(flatten)]
+    pub kind: ConsistencyIssueKind,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConsistencyReport {
+    pub checked_at: DateTime<Utc>,
+    pub games_checked: i64,
+    pub games_with_issues: usize,
+    pub issues: Vec<GameConsistencyIssue>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConsistencyRun {
+    pub id: Uuid,
+    pub games_checked: i64,
+    pub games_with_issues: i32,
+    pub issues: Vec<GameConsistencyIssue>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ScoreConsistencyService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Run every consistency check, optionally restricted to a single season
+    pub async fn check_games(&self, season_id: Option<Uuid>) -> Result<ConsistencyReport, sqlx::Error> {
+        let statuses: Vec<String> = CHECKED_GAME_STATUSES.iter().map(|s| s.to_string()).collect();
+
+        let games_checked = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM games
+            WHERE status = ANY($1)
+            AND ($2::uuid IS NULL OR season_id = $2)
+            "#,
+            &statuses,
+            season_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let mut issues = Vec::new();
+        issues.extend(self.find_score_mismatches(season_id).await?);
+        issues.extend(self.find_duplicate_workout_events(&statuses, season_id).await?);
+        issues.extend(self.find_overlapping_workouts(&statuses, season_id).await?);
+        issues.extend(self.find_stale_event_points(&statuses, season_id).await?);
+        issues.extend(self.find_uncredited_workouts(&statuses, season_id).await?);
+
+        let games_with_issues = issues.iter().map(|i| i.game_id).collect::<HashSet<_>>().len();
+
+        Ok(ConsistencyReport {
+            checked_at: Utc::now(),
+            games_checked,
+            games_with_issues,
+            issues,
+        })
+    }
+
+    /// Run the checks across all seasons and persist the result
+    pub async fn run_check(&self) -> Result<ConsistencyRun, sqlx::Error> {
+        let report = self.check_games(None).await?;
+
+        if report.issues.is_empty() {
+            tracing::info!("✅ [CONSISTENCY] {} games checked, no issues found", report.games_checked);
+        } else {
+            tracing::warn!("⚠️ [CONSISTENCY] {} of {} games have score issues ({} total)",
+                report.games_with_issues, report.games_checked, report.issues.len());
+        }
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO score_consistency_runs (games_checked, games_with_issues, issues, checked_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+            report.games_checked,
+            report.games_with_issues as i32,
+            serde_json::to_value(&report.issues).unwrap_or(serde_json::Value::Null),
+            report.checked_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(ConsistencyRun {
+            id: row.id,
+            games_checked: report.games_checked,
+            games_with_issues: report.games_with_issues as i32,
+            issues: report.issues,
+            checked_at: report.checked_at,
+        })
+    }
+
+    /// Get the most recent persisted runs, newest first
+    pub async fn get_recent_runs(&self, limit: i64) -> Result<Vec<ConsistencyRun>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, games_checked, games_with_issues, issues, checked_at
+            FROM score_consistency_runs
+            ORDER BY checked_at DESC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| ConsistencyRun {
+            id: r.id,
+            games_checked: r.games_checked,
+            games_with_issues: r.games_with_issues,
+            issues: serde_json::from_value(r.issues).unwrap_or_default(),
+            checked_at: r.checked_at,
+        }).collect())
+    }
+
+    /// Compare stored game scores with the score recomputed from live_score_events
+    /// (summed per player first, then per team, mirroring GameQueries::calculate_team_scores_best_4).
+    /// The schema is a parameter so backup verification can run it against a restored copy.
+    pub async fn find_score_divergences<'e, E>(
+        executor: E,
+        schema: &str,
+        season_id: Option<Uuid>,
+    ) -> Result<Vec<ScoreDivergence>, sqlx::Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH player_totals AS (
+                SELECT game_id, team_side, user_id, SUM(score_points)::float8 AS total
+                FROM {schema}.live_score_events
+                WHERE game_id IS NOT NULL
+                GROUP BY game_id, team_side, user_id
+            ),
+            event_scores AS (
+                SELECT
+                    g.id,
+                    g.season_id,
+                    g.status::text AS status,
+                    g.home_score,
+                    g.away_score,
+                    COALESCE(TRUNC(SUM(pt.total) FILTER (WHERE pt.team_side = 'home')), 0)::int AS event_home_score,
+                    COALESCE(TRUNC(SUM(pt.total) FILTER (WHERE pt.team_side = 'away')), 0)::int AS event_away_score
+                FROM {schema}.games g
+                LEFT JOIN player_totals pt ON pt.game_id = g.id
+                WHERE g.status IN ('in_progress', 'finished', 'evaluated')
+                AND ($1::uuid IS NULL OR g.season_id = $1)
+                GROUP BY g.id, g.season_id, g.status, g.home_score, g.away_score
+            )
+            SELECT * FROM event_scores
+            WHERE home_score <> event_home_score OR away_score <> event_away_score
+            ORDER BY id
+            "#
+        ))
+        .bind(season_id)
+        .fetch_all(executor)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ScoreDivergence {
+                    game_id: row.try_get("id")?,
+                    season_id: row.try_get("season_id")?,
+                    status: row.try_get("status")?,
+                    stored_home_score: row.try_get("home_score")?,
+                    stored_away_score: row.try_get("away_score")?,
+                    event_home_score: row.try_get("event_home_score")?,
+                    event_away_score: row.try_get("event_away_score")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn find_score_mismatches(&self, season_id: Option<Uuid>) -> Result<Vec<GameConsistencyIssue>, sqlx::Error> {
+        let divergences = Self::find_score_divergences(&self.pool, "public", season_id).await?;
+
+        Ok(divergences.into_iter().map(|d| GameConsistencyIssue {
+            game_id: d.game_id,
+            season_id: d.season_id,
+            status: d.status,
+            kind: ConsistencyIssueKind::ScoreMismatch {
+                stored_home_score: d.stored_home_score,
+                stored_away_score: d.stored_away_score,
+                event_home_score: d.event_home_score,
+                event_away_score: d.event_away_score,
+            },
+        }).collect())
+    }
+
+    /// Workouts that have more than one score event in the same game
+    async fn find_duplicate_workout_events(
+        &self,
+        statuses: &[String],
+        season_id: Option<Uuid>,
+    ) -> Result<Vec<GameConsistencyIssue>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                g.id as game_id,
+                g.season_id,
+                g.status,
+                lse.user_id,
+                lse.workout_data_id as "workout_data_id!",
+                COUNT(*) as "event_count!"
+            FROM live_score_events lse
+            JOIN games g ON g.id = lse.game_id
+            WHERE lse.workout_data_id IS NOT NULL
+            AND g.status = ANY($1)
+            AND ($2::uuid IS NULL OR g.season_id = $2)
+            GROUP BY g.id, g.season_id, g.status, lse.user_id, lse.workout_data_id
+            HAVING COUNT(*) > 1
+            ORDER BY g.id
+            "#,
+            statuses,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| GameConsistencyIssue {
+            game_id: r.game_id,
+            season_id: r.season_id,
+            status: r.status,
+            kind: ConsistencyIssueKind::DuplicateWorkoutEvents {
+                user_id: r.user_id,
+                workout_data_id: r.workout_data_id,
+                event_count: r.event_count,
+            },
+        }).collect())
+    }
+
+    /// Pairs of credited workouts from the same player whose time ranges overlap
+    async fn find_overlapping_workouts(
+        &self,
+        statuses: &[String],
+        season_id: Option<Uuid>,
+    ) -> Result<Vec<GameConsistencyIssue>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                g.id as game_id,
+                g.season_id,
+                g.status,
+                a.user_id,
+                wa.id as workout_data_id,
+                wb.id as overlapping_workout_data_id
+            FROM live_score_events a
+            JOIN live_score_events b
+                ON b.game_id = a.game_id
+                AND b.user_id = a.user_id
+                AND a.workout_data_id < b.workout_data_id
+            JOIN workout_data wa ON wa.id = a.workout_data_id
+            JOIN workout_data wb ON wb.id = b.workout_data_id
+            JOIN games g ON g.id = a.game_id
+            WHERE wa.workout_start < wb.workout_end
+            AND wb.workout_start < wa.workout_end
+            AND g.status = ANY($1)
+            AND ($2::uuid IS NULL OR g.season_id = $2)
+            ORDER BY g.id
+            "#,
+            statuses,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| GameConsistencyIssue {
+            game_id: r.game_id,
+            season_id: r.season_id,
+            status: r.status,
+            kind: ConsistencyIssueKind::OverlappingWorkouts {
+                user_id: r.user_id,
+                workout_data_id: r.workout_data_id,
+                overlapping_workout_data_id: r.overlapping_workout_data_id,
+            },
+        }).collect())
+    }
+
+    /// Workout score events whose points, less any comeback or booster bonus, no longer match the workout's stamina + strength
+    async fn find_stale_event_points(
+        &self,
+        statuses: &[String],
+        season_id: Option<Uuid>,
+    ) -> Result<Vec<GameConsistencyIssue>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                g.id as game_id,
+                g.season_id,
+                g.status,
+                lse.user_id,
+                wd.id as workout_data_id,
+                lse.score_points as event_points,
+                (wd.stamina_gained + wd.strength_gained) as "workout_points!"
+            FROM live_score_events lse
+            JOIN workout_data wd ON wd.id = lse.workout_data_id
+            JOIN games g ON g.id = lse.game_id
+            WHERE lse.event_type = 'workout_upload'
+            AND ABS(lse.score_points - lse.comeback_bonus - lse.booster_bonus - (wd.stamina_gained + wd.strength_gained)) > $3
+            AND g.status = ANY($1)
+            AND ($2::uuid IS NULL OR g.season_id = $2)
+            ORDER BY g.id
+            "#,
+            statuses,
+            season_id,
+            POINTS_TOLERANCE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| GameConsistencyIssue {
+            game_id: r.game_id,
+            season_id: r.season_id,
+            status: r.status,
+            kind: ConsistencyIssueKind::StaleEventPoints {
+                user_id: r.user_id,
+                workout_data_id: r.workout_data_id,
+                event_points: r.event_points,
+                workout_points: r.workout_points,
+            },
+        }).collect())
+    }
+
+    /// Scoring workouts by active team members that fall inside the game window
+    /// but have no score event for the game (e.g. uploaded after the game ended)
+    async fn find_uncredited_workouts(
+        &self,
+        statuses: &[String],
+        season_id: Option<Uuid>,
+    ) -> Result<Vec<GameConsistencyIssue>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                g.id as game_id,
+                g.season_id,
+                g.status,
+                wd.user_id,
+                wd.id as workout_data_id,
+                (wd.stamina_gained + wd.strength_gained) as "workout_points!"
+            FROM games g
+            JOIN team_members tm
+                ON tm.team_id IN (g.home_team_id, g.away_team_id)
+                AND tm.status = 'active'
+            JOIN workout_data wd
+                ON wd.user_id = tm.user_id
+                AND wd.workout_start >= g.game_start_time
+                AND wd.workout_end <= g.game_end_time
+                AND wd.workout_start >= tm.joined_at
+            WHERE g.status = ANY($1)
+            AND ($2::uuid IS NULL OR g.season_id = $2)
+            AND (wd.stamina_gained + wd.strength_gained) > 0
+            AND NOT EXISTS (
+                SELECT 1 FROM live_score_events lse
+                WHERE lse.game_id = g.id AND lse.workout_data_id = wd.id
+            )
+            -- Workouts recorded while suspended are left out on purpose
+            AND NOT EXISTS (
+                SELECT 1 FROM user_suspensions us
+                WHERE us.user_id = wd.user_id
+                AND us.starts_at < wd.workout_end
+                AND COALESCE(LEAST(us.ends_at, us.lifted_at), 'infinity') > wd.workout_start
+            )
+            ORDER BY g.id
+            "#,
+            statuses,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| GameConsistencyIssue {
+            game_id: r.game_id,
+            season_id: r.season_id,
+            status: r.status,
+            kind: ConsistencyIssueKind::UncreditedWorkout {
+                user_id: r.user_id,
+                workout_data_id: r.workout_data_id,
+                workout_points: r.workout_points,
+            },
+        }).collect())
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::workout::effort_calibration::{
+    calibration_factor, mean_effort_gap, RatedWorkout, CALIBRATION_WINDOW_DAYS,
+};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CalibrationRunSummary {
+    pub users_calibrated: usize,
+    pub users_reset: u64,
+}
+
+/// Turns the effort ratings of scoring feedback into personal scoring factors
+pub struct ScoringCalibrationService {
+    pool: PgPool,
+}
+
+impl ScoringCalibrationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Recalculate every user's factor from the workouts they rated recently
+    pub async fn run_weekly(&self) -> Result<CalibrationRunSummary, sqlx::Error> {
+        // Points are divided by the factor they were scored with, so the calibration doesn't feed on itself
+        let rows = sqlx::query!(
+            r#"
+            SELECT f.user_id, f.effort_rating,
+                   ((COALESCE(wd.stamina_gained, 0) + COALESCE(wd.strength_gained, 0)) / wd.scoring_calibration
+                    / (EXTRACT(EPOCH FROM (wd.workout_end - wd.workout_start)) / 60.0))::real as "points_per_minute!"
+            FROM workout_scoring_feedback f
+            JOIN workout_data wd ON wd.id = f.workout_data_id AND wd.user_id = f.user_id
+            WHERE wd.workout_start >= $1
+            AND wd.workout_end - wd.workout_start >= INTERVAL '1 minute'
+            "#,
+            Utc::now() - Duration::days(CALIBRATION_WINDOW_DAYS)
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut ratings: HashMap<Uuid, Vec<RatedWorkout>> = HashMap::new();
+        for row in rows {
+            ratings.entry(row.user_id).or_default().push(RatedWorkout {
+                effort_rating: row.effort_rating,
+                points_per_minute: row.points_per_minute,
+            });
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let calibrated: Vec<Uuid> = ratings.keys().copied().collect();
+        for (user_id, user_ratings) in &ratings {
+            sqlx::query!(
+                r#"
+                INSERT INTO user_scoring_calibrations (user_id, factor, rated_workouts, mean_effort_gap, calculated_at)
+                VALUES ($1, $2, $3, $4, NOW())
+                ON CONFLICT (user_id) DO UPDATE SET
+                    factor = EXCLUDED.factor,
+                    rated_workouts = EXCLUDED.rated_workouts,
+                    mean_effort_gap = EXCLUDED.mean_effort_gap,
+                    calculated_at = EXCLUDED.calculated_at
+                "#,
+                user_id,
+                calibration_factor(user_ratings),
+                user_ratings.len() as i32,
+                mean_effort_gap(user_ratings)
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Users without recent ratings go back to uncalibrated scoring
+        let users_reset = sqlx::query!(
+            r#"
+            UPDATE user_scoring_calibrations
+            SET factor = 1.0, rated_workouts = 0, mean_effort_gap = NULL, calculated_at = NOW()
+            WHERE NOT (user_id = ANY($1)) AND (factor <> 1.0 OR rated_workouts > 0)
+            "#,
+            &calibrated
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(CalibrationRunSummary { users_calibrated: calibrated.len(), users_reset })
+    }
+}
+
+/// The factor the user's points are multiplied by; 1.0 until the weekly job calibrated them
+pub async fn current_factor<'e, E: PgExecutor<'e>>(executor: E, user_id: Uuid) -> Result<f32, sqlx::Error> {
+    let factor = sqlx::query_scalar!("SELECT factor FROM user_scoring_calibrations WHERE user_id = $1", user_id)
+        .fetch_optional(executor)
+        .await?;
+    Ok(factor.unwrap_or(1.0))
+}
@@ -0,0 +1,667 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::services::MinIOService;
+
+/// Service that writes the season narrative recap (champion, biggest upset, longest streak,
+/// top scorer arc) for the league and each team once every game of a season is evaluated,
+/// and renders recaps into shareable cards on request.
+#[derive(Debug)]
+pub struct SeasonRecapService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecapStanding {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub position: i32,
+    pub games_played: i32,
+    pub wins: i32,
+    pub draws: i32,
+    pub losses: i32,
+    pub points: i32,
+}
+
+/// A win by the team that finished lower in the table; the bigger the gap, the bigger the upset
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecapUpset {
+    pub game_id: Uuid,
+    pub week_number: i32,
+    pub winner_team_id: Uuid,
+    pub winner_team_name: String,
+    pub winner_position: i32,
+    pub winner_score: i32,
+    pub loser_team_id: Uuid,
+    pub loser_team_name: String,
+    pub loser_position: i32,
+    pub loser_score: i32,
+}
+
+/// Consecutive wins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecapStreak {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub length: i32,
+    pub from_week: i32,
+    pub to_week: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecapScorerWeek {
+    pub week_number: i32,
+    pub points: f64,
+    pub cumulative_points: f64,
+}
+
+/// The season's top scorer and how their points built up week by week
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecapScorerArc {
+    pub user_id: Uuid,
+    pub username: String,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub total_points: f64,
+    pub weeks: Vec<RecapScorerWeek>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SeasonRecap {
+    pub season_id: Uuid,
+    pub season_name: String,
+    pub league_name: String,
+    /// Set for team recaps; league recaps cover every team
+    pub team_id: Option<Uuid>,
+    pub team_name: Option<String>,
+    pub games_played: i32,
+    pub champion: Option<RecapStanding>,
+    /// The team's final standing (team recaps only)
+    pub standing: Option<RecapStanding>,
+    pub biggest_upset: Option<RecapUpset>,
+    pub longest_streak: Option<RecapStreak>,
+    pub top_scorer: Option<RecapScorerArc>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SeasonRecapRecord {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub team_id: Option<Uuid>,
+    pub content: serde_json::Value,
+    /// "none", "pending", "rendered" or "failed"
+    pub card_status: String,
+    pub card_rendered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+struct SeasonGame {
+    id: Uuid,
+    week_number: i32,
+    home_team_id: Uuid,
+    away_team_id: Uuid,
+    home_score: i32,
+    away_score: i32,
+    winner_team_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+struct ScorerWeek {
+    user_id: Uuid,
+    username: String,
+    team_id: Uuid,
+    week_number: i32,
+    points: f64,
+}
+
+/// Object key of a recap card in MinIO
+fn card_object_key(season_id: Uuid, team_id: Option<Uuid>) -> String {
+    match team_id {
+        Some(team_id) => format!("season-recaps/{season_id}/{team_id}.svg"),
+        None => format!("season-recaps/{season_id}/league.svg"),
+    }
+}
+
+impl SeasonRecapService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Generate the recaps if every game of the season has been evaluated.
+    /// Returns the number of recaps written; 0 while games are still outstanding.
+    pub async fn generate_if_complete(&self, season_id: Uuid) -> Result<usize, sqlx::Error> {
+        let progress = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'evaluated') as "evaluated!",
+                COUNT(*) FILTER (WHERE status NOT IN ('evaluated', 'postponed')) as "outstanding!"
+            FROM games
+            WHERE season_id = $1
+            "#,
+            season_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if progress.evaluated == 0 || progress.outstanding > 0 {
+            return Ok(0);
+        }
+
+        Ok(self.generate(season_id).await?.len())
+    }
+
+    /// Compose and store the league recap and one recap per team. Regenerating replaces the
+    /// content and re-renders cards that were already requested.
+    pub async fn generate(&self, season_id: Uuid) -> Result<Vec<SeasonRecapRecord>, sqlx::Error> {
+        let Some(season) = sqlx::query!(
+            r#"
+            SELECT ls.name, l.name as league_name
+            FROM league_seasons ls
+            JOIN leagues l ON l.id = ls.league_id
+            WHERE ls.id = $1
+            "#,
+            season_id
+        )
+        .fetch_optional(&self.pool)
+        .await? else {
+            return Ok(Vec::new());
+        };
+
+        let standings = self.get_standings(season_id).await?;
+        let games = self.get_evaluated_games(season_id).await?;
+        let scorer_weeks = self.get_scorer_weeks(season_id).await?;
+        let team_names: HashMap<Uuid, String> = standings
+            .iter()
+            .map(|s| (s.team_id, s.team_name.clone()))
+            .collect();
+        let weeks: Vec<i32> = {
+            let mut weeks: Vec<i32> = games.iter().map(|g| g.week_number).collect();
+            weeks.sort_unstable();
+            weeks.dedup();
+            weeks
+        };
+
+        let league_recap = SeasonRecap {
+            season_id,
+            season_name: season.name.clone(),
+            league_name: season.league_name.clone(),
+            team_id: None,
+            team_name: None,
+            games_played: games.len() as i32,
+            champion: standings.first().cloned(),
+            standing: None,
+            biggest_upset: biggest_upset(&games, &standings, None),
+            longest_streak: standings
+                .iter()
+                .filter_map(|s| longest_streak(&games, s.team_id, &s.team_name))
+                .fold(None, |best: Option<RecapStreak>, streak| match best {
+                    Some(best) if best.length >= streak.length => Some(best),
+                    _ => Some(streak),
+                }),
+            top_scorer: top_scorer_arc(&scorer_weeks, &weeks, &team_names, None),
+            generated_at: Utc::now(),
+        };
+
+        let mut recaps = vec![league_recap];
+        for standing in &standings {
+            recaps.push(SeasonRecap {
+                season_id,
+                season_name: season.name.clone(),
+                league_name: season.league_name.clone(),
+                team_id: Some(standing.team_id),
+                team_name: Some(standing.team_name.clone()),
+                games_played: standing.games_played,
+                champion: standings.first().cloned(),
+                standing: Some(standing.clone()),
+                biggest_upset: biggest_upset(&games, &standings, Some(standing.team_id)),
+                longest_streak: longest_streak(&games, standing.team_id, &standing.team_name),
+                top_scorer: top_scorer_arc(&scorer_weeks, &weeks, &team_names, Some(standing.team_id)),
+                generated_at: Utc::now(),
+            });
+        }
+
+        let mut records = Vec::with_capacity(recaps.len());
+        for recap in recaps {
+            records.push(self.store(&recap).await?);
+        }
+
+        tracing::info!("📖 Generated {} recaps for season {}", records.len(), season_id);
+        Ok(records)
+    }
+
+    async fn store(&self, recap: &SeasonRecap) -> Result<SeasonRecapRecord, sqlx::Error> {
+        let content = json!(recap);
+        // Cards that were asked for before are rendered again with the new content
+        let record = if let Some(team_id) = recap.team_id {
+            sqlx::query_as!(
+                SeasonRecapRecord,
+                r#"
+                INSERT INTO season_recaps (season_id, team_id, content)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (season_id, team_id) WHERE team_id IS NOT NULL DO UPDATE
+                SET content = EXCLUDED.content,
+                    card_status = CASE WHEN season_recaps.card_status = 'none' THEN 'none' ELSE 'pending' END,
+                    updated_at = NOW()
+                RETURNING id, season_id, team_id, content, card_status, card_rendered_at, created_at, updated_at
+                "#,
+                recap.season_id,
+                team_id,
+                content
+            )
+            .fetch_one(&self.pool)
+            .await?
+        } else {
+            sqlx::query_as!(
+                SeasonRecapRecord,
+                r#"
+                INSERT INTO season_recaps (season_id, content)
+                VALUES ($1, $2)
+                ON CONFLICT (season_id) WHERE team_id IS NULL DO UPDATE
+                SET content = EXCLUDED.content,
+                    card_status = CASE WHEN season_recaps.card_status = 'none' THEN 'none' ELSE 'pending' END,
+                    updated_at = NOW()
+                RETURNING id, season_id, team_id, content, card_status, card_rendered_at, created_at, updated_at
+                "#,
+                recap.season_id,
+                content
+            )
+            .fetch_one(&self.pool)
+            .await?
+        };
+        Ok(record)
+    }
+
+    /// League recap first, then the teams in standings order
+    pub async fn get_recaps(&self, season_id: Uuid) -> Result<Vec<SeasonRecapRecord>, sqlx::Error> {
+        sqlx::query_as!(
+            SeasonRecapRecord,
+            r#"
+            SELECT sr.id, sr.season_id, sr.team_id, sr.content, sr.card_status,
+                   sr.card_rendered_at, sr.created_at, sr.updated_at
+            FROM season_recaps sr
+            LEFT JOIN league_standings st ON st.season_id = sr.season_id AND st.team_id = sr.team_id
+            WHERE sr.season_id = $1
+            ORDER BY sr.team_id IS NOT NULL, st.position ASC NULLS LAST
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Queue card rendering for all recaps of a season that have none yet (or failed).
+    /// Returns the number of cards queued.
+    pub async fn request_cards(&self, season_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE season_recaps
+            SET card_status = 'pending', updated_at = NOW()
+            WHERE season_id = $1 AND card_status IN ('none', 'failed')
+            "#,
+            season_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Image-render job: render pending recap cards and store them in MinIO.
+    /// Returns the number of cards rendered.
+    pub async fn render_pending_cards(&self, minio_service: &MinIOService) -> Result<usize, sqlx::Error> {
+        let pending = sqlx::query!(
+            r#"
+            SELECT id, season_id, team_id, content, updated_at
+            FROM season_recaps
+            WHERE card_status = 'pending'
+            ORDER BY updated_at ASC
+            LIMIT 50
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut rendered = 0;
+        for row in pending {
+            let svg = match serde_json::from_value::<SeasonRecap>(row.content) {
+                Ok(recap) => render_card_svg(&recap),
+                Err(e) => {
+                    tracing::error!("Recap {} has unreadable content: {}", row.id, e);
+                    self.set_card_status(row.id, row.updated_at, "failed", None).await?;
+                    continue;
+                }
+            };
+
+            let object_key = card_object_key(row.season_id, row.team_id);
+            match minio_service.upload_object(Bytes::from(svg), &object_key, "image/svg+xml").await {
+                Ok(_) => {
+                    self.set_card_status(row.id, row.updated_at, "rendered", Some(&object_key)).await?;
+                    rendered += 1;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to store recap card {}: {}", object_key, e);
+                    self.set_card_status(row.id, row.updated_at, "failed", None).await?;
+                }
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    /// Only touches the row if it was not regenerated while the card was rendering
+    async fn set_card_status(
+        &self,
+        recap_id: Uuid,
+        seen_updated_at: DateTime<Utc>,
+        status: &str,
+        object_key: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE season_recaps
+            SET card_status = $3::text,
+                card_object_key = COALESCE($4, card_object_key),
+                card_rendered_at = CASE WHEN $3::text = 'rendered' THEN NOW() ELSE card_rendered_at END
+            WHERE id = $1 AND updated_at = $2
+            "#,
+            recap_id,
+            seen_updated_at,
+            status,
+            object_key
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// MinIO key of a rendered card; None until the card has been rendered
+    pub async fn get_card_object_key(&self, season_id: Uuid, team_id: Option<Uuid>) -> Result<Option<String>, sqlx::Error> {
+        let key = sqlx::query_scalar!(
+            r#"
+            SELECT card_object_key
+            FROM season_recaps
+            WHERE season_id = $1 AND team_id IS NOT DISTINCT FROM $2 AND card_status = 'rendered'
+            "#,
+            season_id,
+            team_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(key.flatten())
+    }
+
+    async fn get_standings(&self, season_id: Uuid) -> Result<Vec<RecapStanding>, sqlx::Error> {
+        sqlx::query_as!(
+            RecapStanding,
+            r#"
+            SELECT st.team_id, t.team_name, st.position, st.games_played, st.wins, st.draws, st.losses,
+                   st.points as "points!"
+            FROM league_standings st
+            JOIN teams t ON t.id = st.team_id
+            WHERE st.season_id = $1
+            ORDER BY st.position ASC, st.points DESC
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn get_evaluated_games(&self, season_id: Uuid) -> Result<Vec<SeasonGame>, sqlx::Error> {
+        sqlx::query_as!(
+            SeasonGame,
+            r#"
+            SELECT id, week_number, home_team_id, away_team_id, home_score, away_score, winner_team_id
+            FROM games
+            WHERE season_id = $1 AND status = 'evaluated'
+            ORDER BY week_number ASC, game_start_time ASC NULLS LAST
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn get_scorer_weeks(&self, season_id: Uuid) -> Result<Vec<ScorerWeek>, sqlx::Error> {
+        sqlx::query_as!(
+            ScorerWeek,
+            r#"
+            SELECT
+                lse.user_id,
+                MAX(lse.username) as "username!",
+                lse.team_id,
+                g.week_number,
+                SUM(lse.score_points)::float8 as "points!"
+            FROM live_score_events lse
+            JOIN games g ON g.id = lse.game_id
+            WHERE g.season_id = $1 AND g.status = 'evaluated'
+            GROUP BY lse.user_id, lse.team_id, g.week_number
+            "#,
+            season_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
+
+/// Biggest win by the lower-placed team, by places between the teams and then by margin.
+/// With `team_id` only upsets that team pulled off count.
+fn biggest_upset(games: &[SeasonGame], standings: &[RecapStanding], team_id: Option<Uuid>) -> Option<RecapUpset> {
+    let standing_of = |team: Uuid| standings.iter().find(|s| s.team_id == team);
+
+    games
+        .iter()
+        .filter_map(|game| {
+            let winner = game.winner_team_id?;
+            if team_id.is_some_and(|team| team != winner) {
+                return None;
+            }
+            let (loser, winner_score, loser_score) = if winner == game.home_team_id {
+                (game.away_team_id, game.home_score, game.away_score)
+            } else {
+                (game.home_team_id, game.away_score, game.home_score)
+            };
+            let (winner, loser) = (standing_of(winner)?, standing_of(loser)?);
+            if winner.position <= loser.position {
+                return None;
+            }
+            Some(RecapUpset {
+                game_id: game.id,
+                week_number: game.week_number,
+                winner_team_id: winner.team_id,
+                winner_team_name: winner.team_name.clone(),
+                winner_position: winner.position,
+                winner_score,
+                loser_team_id: loser.team_id,
+                loser_team_name: loser.team_name.clone(),
+                loser_position: loser.position,
+                loser_score,
+            })
+        })
+        .fold(None, |best: Option<RecapUpset>, upset| {
+            let key = |u: &RecapUpset| (u.winner_position - u.loser_position, u.winner_score - u.loser_score);
+            match best {
+                Some(best) if key(&best) >= key(&upset) => Some(best),
+                _ => Some(upset),
+            }
+        })
+}
+
+/// Longest run of consecutive wins of a team; the earliest run wins ties
+fn longest_streak(games: &[SeasonGame], team_id: Uuid, team_name: &str) -> Option<RecapStreak> {
+    let mut best: Option<RecapStreak> = None;
+    let mut current: Option<(i32, i32, i32)> = None; // (length, from_week, to_week)
+
+    for game in games.iter().filter(|g| g.home_team_id == team_id || g.away_team_id == team_id) {
+        if game.winner_team_id == Some(team_id) {
+            current = Some(match current {
+                Some((length, from_week, _)) => (length + 1, from_week, game.week_number),
+                None => (1, game.week_number, game.week_number),
+            });
+            let (length, from_week, to_week) = current.expect("streak was just extended");
+            if best.as_ref().is_none_or(|b| length > b.length) {
+                best = Some(RecapStreak {
+                    team_id,
+                    team_name: team_name.to_string(),
+                    length,
+                    from_week,
+                    to_week,
+                });
+            }
+        } else {
+            current = None;
+        }
+    }
+
+    best
+}
+
+/// Top scorer (of a team, or of the league) with their points for every week of the season
+fn top_scorer_arc(
+    scorer_weeks: &[ScorerWeek],
+    weeks: &[i32],
+    team_names: &HashMap<Uuid, String>,
+    team_id: Option<Uuid>,
+) -> Option<RecapScorerArc> {
+    let mut totals: BTreeMap<(Uuid, Uuid), f64> = BTreeMap::new();
+    for row in scorer_weeks.iter().filter(|r| team_id.is_none_or(|team| r.team_id == team)) {
+        *totals.entry((row.user_id, row.team_id)).or_default() += row.points;
+    }
+
+    let (&(user_id, scorer_team_id), &total_points) = totals
+        .iter()
+        .fold(None, |best: Option<(&(Uuid, Uuid), &f64)>, entry| match best {
+            Some(best) if best.1 >= entry.1 => Some(best),
+            _ => Some(entry),
+        })?;
+
+    let rows: Vec<&ScorerWeek> = scorer_weeks
+        .iter()
+        .filter(|r| r.user_id == user_id && r.team_id == scorer_team_id)
+        .collect();
+    let mut cumulative_points = 0.0;
+    let arc = weeks
+        .iter()
+        .map(|&week_number| {
+            let points: f64 = rows.iter().filter(|r| r.week_number == week_number).map(|r| r.points).sum();
+            cumulative_points += points;
+            RecapScorerWeek { week_number, points, cumulative_points }
+        })
+        .collect();
+
+    Some(RecapScorerArc {
+        user_id,
+        username: rows.first().map(|r| r.username.clone()).unwrap_or_default(),
+        team_id: scorer_team_id,
+        team_name: team_names.get(&scorer_team_id).cloned().unwrap_or_default(),
+        total_points,
+        weeks: arc,
+    })
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render a recap as a 1200x630 SVG card, the size social networks use for link previews
+pub fn render_card_svg(recap: &SeasonRecap) -> String {
+    const WIDTH: f64 = 1200.0;
+    const HEIGHT: f64 = 630.0;
+
+    let title = match &recap.team_name {
+        Some(team_name) => format!("{} · {}", team_name, recap.season_name),
+        None => format!("{} · {}", recap.league_name, recap.season_name),
+    };
+
+    let mut lines: Vec<(String, String)> = Vec::new();
+    match (&recap.standing, &recap.champion) {
+        (Some(standing), _) => lines.push((
+            "Final position".to_string(),
+            format!("#{} · {}W {}D {}L · {} pts", standing.position, standing.wins, standing.draws, standing.losses, standing.points),
+        )),
+        (None, Some(champion)) => lines.push((
+            "Champion".to_string(),
+            format!("{} · {} pts", champion.team_name, champion.points),
+        )),
+        (None, None) => {}
+    }
+    if let Some(streak) = &recap.longest_streak {
+        lines.push((
+            "Longest streak".to_string(),
+            format!("{} · {} wins (weeks {}-{})", streak.team_name, streak.length, streak.from_week, streak.to_week),
+        ));
+    }
+    if let Some(upset) = &recap.biggest_upset {
+        lines.push((
+            "Biggest upset".to_string(),
+            format!(
+                "#{} {} beat #{} {} {}-{}",
+                upset.winner_position, upset.winner_team_name, upset.loser_position, upset.loser_team_name,
+                upset.winner_score, upset.loser_score
+            ),
+        ));
+    }
+    if let Some(scorer) = &recap.top_scorer {
+        lines.push((
+            "Top scorer".to_string(),
+            format!("{} · {:.0} pts", scorer.username, scorer.total_points),
+        ));
+    }
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}">
+<rect width="100%" height="100%" fill="#101828"/>
+<text x="60" y="90" font-family="Helvetica, Arial, sans-serif" font-size="28" fill="#98A2B3">SEASON RECAP</text>
+<text x="60" y="150" font-family="Helvetica, Arial, sans-serif" font-size="48" font-weight="bold" fill="#FFFFFF">{}</text>
+"##,
+        escape_xml(&title)
+    );
+
+    for (i, (label, value)) in lines.iter().enumerate() {
+        let y = 240.0 + i as f64 * 80.0;
+        svg.push_str(&format!(
+            r##"<text x="60" y="{y}" font-family="Helvetica, Arial, sans-serif" font-size="22" fill="#98A2B3">{}</text>
+<text x="60" y="{}" font-family="Helvetica, Arial, sans-serif" font-size="32" fill="#FFFFFF">{}</text>
+"##,
+            escape_xml(label),
+            y + 38.0,
+            escape_xml(value)
+        ));
+    }
+
+    // Top scorer arc as a sparkline of cumulative points
+    if let Some(scorer) = recap.top_scorer.as_ref().filter(|s| s.weeks.len() > 1 && s.total_points > 0.0) {
+        let (left, top, width, height) = (780.0, 380.0, 360.0, 180.0);
+        let step = width / (scorer.weeks.len() - 1) as f64;
+        let points: Vec<String> = scorer
+            .weeks
+            .iter()
+            .enumerate()
+            .map(|(i, week)| {
+                let x = left + i as f64 * step;
+                let y = top + height - week.cumulative_points / scorer.total_points * height;
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        svg.push_str(&format!(
+            r##"<polyline points="{}" fill="none" stroke="#F97316" stroke-width="6" stroke-linejoin="round"/>
+"##,
+            points.join(" ")
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
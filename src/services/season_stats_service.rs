@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Service that keeps league-wide season aggregates (workouts, zone minutes, average score,
+/// biggest blowout, closest game) for media and recap pages. Games are added one at a time as
+/// they are evaluated, so reading the stats never scans a season's games or workouts.
+#[derive(Debug)]
+pub struct SeasonStatsService {
+    pool: PgPool,
+}
+
+/// A game standing out in the season stats
+#[derive(Debug, Serialize)]
+pub struct SeasonStatsGame {
+    pub game_id: Uuid,
+    pub week_number: i32,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub margin: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeasonStats {
+    pub season_id: Uuid,
+    pub games_counted: i32,
+    pub total_workouts: i32,
+    pub total_zone_minutes: f64,
+    /// Points a team scores in a game, on average
+    pub average_game_score: f64,
+    pub biggest_blowout: Option<SeasonStatsGame>,
+    pub closest_game: Option<SeasonStatsGame>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl SeasonStats {
+    /// Stats of a season without evaluated games yet
+    fn empty(season_id: Uuid) -> Self {
+        Self {
+            season_id,
+            games_counted: 0,
+            total_workouts: 0,
+            total_zone_minutes: 0.0,
+            average_game_score: 0.0,
+            biggest_blowout: None,
+            closest_game: None,
+            updated_at: None,
+        }
+    }
+}
+
+impl SeasonStatsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add an evaluated game to its season's stats. Returns false if the game was already
+    /// counted or isn't evaluated.
+    pub async fn record_game(&self, game_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(game) = sqlx::query!(
+            r#"
+            WITH game AS (
+                SELECT id, season_id, home_score, away_score
+                FROM games
+                WHERE id = $1 AND status = 'evaluated'
+            ),
+            counted AS (
+                INSERT INTO season_stats_games (game_id, season_id)
+                SELECT id, season_id FROM game
+                ON CONFLICT (game_id) DO NOTHING
+                RETURNING game_id
+            )
+            SELECT game.season_id, game.home_score, game.away_score
+            FROM game
+            JOIN counted ON counted.game_id = game.id
+            "#,
+            game_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        // The workouts credited to the game and their zone minutes
+        let workouts = sqlx::query!(
+            r#"
+            WITH credited AS (
+                SELECT DISTINCT workout_data_id
+                FROM live_score_events
+                WHERE game_id = $1 AND workout_data_id IS NOT NULL
+            )
+            SELECT
+                (SELECT COUNT(*) FROM credited) as "workouts!",
+                (
+                    SELECT COALESCE(SUM((z.value->>'minutes')::float8), 0)
+                    FROM credited c
+                    JOIN workout_data wd ON wd.id = c.workout_data_id
+                    CROSS JOIN LATERAL jsonb_array_elements(
+                        CASE WHEN jsonb_typeof(wd.heart_rate_zones) = 'array' THEN wd.heart_rate_zones ELSE '[]'::jsonb END
+                    ) z
+                    WHERE z.value ? 'minutes'
+                ) as "zone_minutes!"
+            "#,
+            game_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let margin = (game.home_score - game.away_score).abs();
+        sqlx::query!(
+            r#"
+            INSERT INTO season_stats (
+                season_id, games_counted, total_workouts, total_zone_minutes, total_points,
+                biggest_blowout_game_id, biggest_blowout_margin, closest_game_id, closest_game_margin
+            )
+            VALUES ($1, 1, $2, $3, $4, $5, $6, $5, $6)
+            ON CONFLICT (season_id) DO UPDATE SET
+                games_counted = season_stats.games_counted + 1,
+                total_workouts = season_stats.total_workouts + EXCLUDED.total_workouts,
+                total_zone_minutes = season_stats.total_zone_minutes + EXCLUDED.total_zone_minutes,
+                total_points = season_stats.total_points + EXCLUDED.total_points,
+                biggest_blowout_game_id = CASE WHEN EXCLUDED.biggest_blowout_margin > season_stats.biggest_blowout_margin
+                    THEN EXCLUDED.biggest_blowout_game_id ELSE season_stats.biggest_blowout_game_id END,
+                biggest_blowout_margin = GREATEST(season_stats.biggest_blowout_margin, EXCLUDED.biggest_blowout_margin),
+                closest_game_id = CASE WHEN EXCLUDED.closest_game_margin < season_stats.closest_game_margin
+                    THEN EXCLUDED.closest_game_id ELSE season_stats.closest_game_id END,
+                closest_game_margin = LEAST(season_stats.closest_game_margin, EXCLUDED.closest_game_margin),
+                updated_at = NOW()
+            "#,
+            game.season_id,
+            workouts.workouts as i32,
+            workouts.zone_minutes,
+            (game.home_score + game.away_score) as i64,
+            game_id,
+            margin
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// The season's stats so far; all zero before its first game is evaluated
+    pub async fn get_stats(&self, season_id: Uuid) -> Result<SeasonStats, sqlx::Error> {
+        let Some(row) = sqlx::query!(
+            r#"
+            SELECT games_counted, total_workouts, total_zone_minutes, total_points,
+                biggest_blowout_game_id, closest_game_id, updated_at
+            FROM season_stats
+            WHERE season_id = $1
+            "#,
+            season_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(SeasonStats::empty(season_id));
+        };
+
+        let average_game_score = if row.games_counted > 0 {
+            row.total_points as f64 / (2 * row.games_counted) as f64
+        } else {
+            0.0
+        };
+
+        Ok(SeasonStats {
+            season_id,
+            games_counted: row.games_counted,
+            total_workouts: row.total_workouts,
+            total_zone_minutes: row.total_zone_minutes,
+            average_game_score,
+            biggest_blowout: self.get_game(row.biggest_blowout_game_id).await?,
+            closest_game: self.get_game(row.closest_game_id).await?,
+            updated_at: Some(row.updated_at),
+        })
+    }
+
+    async fn get_game(&self, game_id: Option<Uuid>) -> Result<Option<SeasonStatsGame>, sqlx::Error> {
+        let Some(game_id) = game_id else {
+            return Ok(None);
+        };
+        sqlx::query_as!(
+            SeasonStatsGame,
+            r#"
+            SELECT
+                g.id as game_id,
+                g.week_number,
+                g.home_team_id,
+                home_team.team_name as home_team_name,
+                g.away_team_id,
+                away_team.team_name as away_team_name,
+                g.home_score,
+                g.away_score,
+                ABS(g.home_score - g.away_score) as "margin!"
+            FROM games g
+            JOIN teams home_team ON home_team.id = g.home_team_id
+            JOIN teams away_team ON away_team.id = g.away_team_id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,413 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::share_card::{ShareCard, ShareCardContent, ShareCardLine, ShareCardType};
+use crate::services::MinIOService;
+use crate::utils::card_image::render_share_card_png;
+
+#[derive(Debug)]
+pub enum ShareCardError {
+    GameNotFound,
+    /// Results and badges exist once the game is over
+    GameNotFinished,
+    NotAPlayer,
+    NoBadge,
+    WorkoutNotFound,
+    PrivateWorkout,
+    NotAPersonalRecord,
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for ShareCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameNotFound => write!(f, "Game not found"),
+            Self::GameNotFinished => write!(f, "Game results can be shared once the game is over"),
+            Self::NotAPlayer => write!(f, "You did not play in this game"),
+            Self::NoBadge => write!(f, "You did not earn a badge in this game"),
+            Self::WorkoutNotFound => write!(f, "Workout not found"),
+            Self::PrivateWorkout => write!(f, "Private workouts cannot be shared"),
+            Self::NotAPersonalRecord => write!(f, "This workout did not beat your earlier workouts"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ShareCardError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+struct ShareCardRow {
+    id: Uuid,
+    card_type: String,
+    subject_id: Uuid,
+    status: String,
+    content: serde_json::Value,
+    created_at: DateTime<Utc>,
+    rendered_at: Option<DateTime<Utc>>,
+}
+
+impl ShareCardRow {
+    fn into_card(self) -> Result<ShareCard, sqlx::Error> {
+        let content = serde_json::from_value(self.content).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+        Ok(ShareCard {
+            path: share_card_path(self.id),
+            id: self.id,
+            card_type: self.card_type,
+            subject_id: self.subject_id,
+            status: self.status,
+            content,
+            created_at: self.created_at,
+            rendered_at: self.rendered_at,
+        })
+    }
+}
+
+/// A finished game as shown on result and badge cards
+struct CardGame {
+    home_team_id: Uuid,
+    home_team_name: String,
+    away_team_name: String,
+    home_score: i32,
+    away_score: i32,
+    week_number: i32,
+    status: String,
+    league_name: String,
+    season_name: String,
+}
+
+impl CardGame {
+    fn is_over(&self) -> bool {
+        matches!(self.status.as_str(), "finished" | "evaluated")
+    }
+
+    fn scoreline(&self) -> String {
+        format!("{} {} - {} {}", self.home_team_name, self.home_score, self.away_score, self.away_team_name)
+    }
+
+    fn league_line(&self) -> ShareCardLine {
+        ShareCardLine {
+            label: "League".to_string(),
+            value: format!("{} - {} - Week {}", self.league_name, self.season_name, self.week_number),
+        }
+    }
+}
+
+/// Public path of a card's image
+pub fn share_card_path(card_id: Uuid) -> String {
+    format!("/share/cards/{card_id}")
+}
+
+/// Object key of a card image in MinIO
+fn card_object_key(card_id: Uuid) -> String {
+    format!("share-cards/{card_id}.png")
+}
+
+/// Shareable PNG cards of badges, game results and personal records
+pub struct ShareCardService {
+    pool: PgPool,
+}
+
+impl ShareCardService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue a card for rendering. Requesting a card again returns the existing one,
+    /// re-queued if it failed to render. The bool tells whether the card is new.
+    pub async fn request(
+        &self,
+        user_id: Uuid,
+        card_type: ShareCardType,
+        subject_id: Uuid,
+    ) -> Result<(ShareCard, bool), ShareCardError> {
+        let content = match card_type {
+            ShareCardType::Badge => self.badge_content(user_id, subject_id).await?,
+            ShareCardType::GameResult => self.game_result_content(user_id, subject_id).await?,
+            ShareCardType::PersonalRecord => self.personal_record_content(user_id, subject_id).await?,
+        };
+        let content = serde_json::to_value(&content).unwrap_or_default();
+
+        let created = sqlx::query_as!(
+            ShareCardRow,
+            r#"
+            INSERT INTO share_cards (user_id, card_type, subject_id, content)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, card_type, subject_id) DO NOTHING
+            RETURNING id, card_type, subject_id, status, content, created_at, rendered_at
+            "#,
+            user_id,
+            card_type.as_str(),
+            subject_id,
+            content
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(created) = created {
+            return Ok((created.into_card()?, true));
+        }
+
+        let existing = sqlx::query_as!(
+            ShareCardRow,
+            r#"
+            UPDATE share_cards
+            SET status = CASE WHEN status = 'failed' THEN 'pending' ELSE status END,
+                content = CASE WHEN status = 'failed' THEN $4 ELSE content END,
+                updated_at = CASE WHEN status = 'failed' THEN NOW() ELSE updated_at END
+            WHERE user_id = $1 AND card_type = $2 AND subject_id = $3
+            RETURNING id, card_type, subject_id, status, content, created_at, rendered_at
+            "#,
+            user_id,
+            card_type.as_str(),
+            subject_id,
+            content
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((existing.into_card()?, false))
+    }
+
+    async fn card_game(&self, game_id: Uuid) -> Result<CardGame, ShareCardError> {
+        let game = sqlx::query_as!(
+            CardGame,
+            r#"
+            SELECT g.home_team_id, ht.team_name as home_team_name, at.team_name as away_team_name,
+                   g.home_score, g.away_score, g.week_number, g.status,
+                   l.name as league_name, s.name as season_name
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            JOIN league_seasons s ON s.id = g.season_id
+            JOIN leagues l ON l.id = s.league_id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ShareCardError::GameNotFound)?;
+
+        if !game.is_over() {
+            return Err(ShareCardError::GameNotFinished);
+        }
+        Ok(game)
+    }
+
+    async fn badge_content(&self, user_id: Uuid, game_id: Uuid) -> Result<ShareCardContent, ShareCardError> {
+        let game = self.card_game(game_id).await?;
+        let summary = sqlx::query!(
+            r#"
+            SELECT mvp_user_id, mvp_username, mvp_score_contribution,
+                   lvp_user_id, lvp_username, lvp_score_contribution
+            FROM game_summaries
+            WHERE game_id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ShareCardError::NoBadge)?;
+
+        let (badge, username, points) = if summary.mvp_user_id == Some(user_id) {
+            ("MVP", summary.mvp_username, summary.mvp_score_contribution)
+        } else if summary.lvp_user_id == Some(user_id) {
+            ("LVP", summary.lvp_username, summary.lvp_score_contribution)
+        } else {
+            return Err(ShareCardError::NoBadge);
+        };
+
+        Ok(ShareCardContent {
+            eyebrow: format!("{badge} badge"),
+            title: username.unwrap_or_default(),
+            lines: vec![
+                ShareCardLine { label: "Game".to_string(), value: game.scoreline() },
+                ShareCardLine { label: "Contribution".to_string(), value: format!("{} points", points.unwrap_or(0)) },
+                game.league_line(),
+            ],
+        })
+    }
+
+    async fn game_result_content(&self, user_id: Uuid, game_id: Uuid) -> Result<ShareCardContent, ShareCardError> {
+        let game = self.card_game(game_id).await?;
+        let player = sqlx::query!(
+            r#"
+            SELECT tm.team_id, u.username,
+                   (SELECT COALESCE(SUM(score_points), 0)::float8 FROM live_score_events
+                    WHERE game_id = $1 AND user_id = $2) as "points!"
+            FROM team_members tm
+            JOIN games g ON g.id = $1 AND tm.team_id IN (g.home_team_id, g.away_team_id)
+            JOIN users u ON u.id = tm.user_id
+            WHERE tm.user_id = $2
+            LIMIT 1
+            "#,
+            game_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ShareCardError::NotAPlayer)?;
+
+        let (own_score, other_score) = if player.team_id == game.home_team_id {
+            (game.home_score, game.away_score)
+        } else {
+            (game.away_score, game.home_score)
+        };
+        let result = match own_score.cmp(&other_score) {
+            std::cmp::Ordering::Greater => "Won",
+            std::cmp::Ordering::Less => "Lost",
+            std::cmp::Ordering::Equal => "Draw",
+        };
+
+        Ok(ShareCardContent {
+            eyebrow: format!("Game result - {result}"),
+            title: game.scoreline(),
+            lines: vec![
+                ShareCardLine { label: "Player".to_string(), value: player.username },
+                ShareCardLine { label: "Points scored".to_string(), value: format!("{:.0}", player.points) },
+                game.league_line(),
+            ],
+        })
+    }
+
+    async fn personal_record_content(&self, user_id: Uuid, workout_id: Uuid) -> Result<ShareCardContent, ShareCardError> {
+        let workout = sqlx::query!(
+            r#"
+            SELECT u.username, wd.workout_start, wd.duration_minutes, wd.avg_heart_rate, wd.activity_name,
+                   (wd.stamina_gained + wd.strength_gained)::float8 as "points!",
+                   p.visibility::text as "visibility?",
+                   (SELECT MAX(earlier.stamina_gained + earlier.strength_gained)::float8
+                    FROM workout_data earlier
+                    WHERE earlier.user_id = wd.user_id AND earlier.workout_start < wd.workout_start) as "previous_best?"
+            FROM workout_data wd
+            JOIN users u ON u.id = wd.user_id
+            LEFT JOIN posts p ON p.workout_id = wd.id AND p.user_id = wd.user_id
+            WHERE wd.id = $1 AND wd.user_id = $2
+            "#,
+            workout_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(ShareCardError::WorkoutNotFound)?;
+
+        if workout.visibility.as_deref() == Some("private") {
+            return Err(ShareCardError::PrivateWorkout);
+        }
+        if workout.points <= 0.0 || workout.previous_best.is_some_and(|best| workout.points <= best) {
+            return Err(ShareCardError::NotAPersonalRecord);
+        }
+
+        let points = match workout.previous_best {
+            Some(best) => format!("{:.0} points (+{:.0} on previous best)", workout.points, workout.points - best),
+            None => format!("{:.0} points", workout.points),
+        };
+        let mut lines = vec![
+            ShareCardLine { label: "Score".to_string(), value: points },
+            ShareCardLine {
+                label: "Workout".to_string(),
+                value: match workout.duration_minutes {
+                    Some(minutes) => format!("{} - {} min", workout.activity_name.as_deref().unwrap_or("Workout"), minutes),
+                    None => workout.activity_name.unwrap_or_else(|| "Workout".to_string()),
+                },
+            },
+        ];
+        if let Some(avg_heart_rate) = workout.avg_heart_rate {
+            lines.push(ShareCardLine { label: "Average heart rate".to_string(), value: format!("{avg_heart_rate} bpm") });
+        }
+        lines.push(ShareCardLine { label: "Date".to_string(), value: workout.workout_start.format("%b %-d, %Y").to_string() });
+
+        Ok(ShareCardContent {
+            eyebrow: "Personal record".to_string(),
+            title: workout.username,
+            lines,
+        })
+    }
+
+    /// Image-render job: render pending share cards and store them in MinIO.
+    /// Returns the number of cards rendered.
+    pub async fn render_pending_cards(&self, minio_service: &MinIOService) -> Result<usize, sqlx::Error> {
+        let pending = sqlx::query!(
+            r#"
+            SELECT id, content, updated_at
+            FROM share_cards
+            WHERE status = 'pending'
+            ORDER BY updated_at ASC
+            LIMIT 50
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut rendered = 0;
+        for row in pending {
+            let png = match serde_json::from_value::<ShareCardContent>(row.content) {
+                Ok(content) => render_share_card_png(&content),
+                Err(e) => {
+                    tracing::error!("Share card {} has unreadable content: {}", row.id, e);
+                    self.set_status(row.id, row.updated_at, "failed", None).await?;
+                    continue;
+                }
+            };
+            let png = match png {
+                Ok(png) => png,
+                Err(e) => {
+                    tracing::error!("Failed to render share card {}: {}", row.id, e);
+                    self.set_status(row.id, row.updated_at, "failed", None).await?;
+                    continue;
+                }
+            };
+
+            let object_key = card_object_key(row.id);
+            match minio_service.upload_object(Bytes::from(png), &object_key, "image/png").await {
+                Ok(_) => {
+                    self.set_status(row.id, row.updated_at, "rendered", Some(&object_key)).await?;
+                    rendered += 1;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to store share card {}: {}", object_key, e);
+                    self.set_status(row.id, row.updated_at, "failed", None).await?;
+                }
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    /// Only touches the row if it was not re-queued while the card was rendering
+    async fn set_status(
+        &self,
+        card_id: Uuid,
+        seen_updated_at: DateTime<Utc>,
+        status: &str,
+        object_key: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE share_cards
+            SET status = $3::text,
+                object_key = COALESCE($4, object_key),
+                rendered_at = CASE WHEN $3::text = 'rendered' THEN NOW() ELSE rendered_at END
+            WHERE id = $1 AND updated_at = $2
+            "#,
+            card_id,
+            seen_updated_at,
+            status,
+            object_key
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Status of a card and the MinIO key of its image once rendered; None for unknown cards
+    pub async fn get_image(&self, card_id: Uuid) -> Result<Option<(String, Option<String>)>, sqlx::Error> {
+        let card = sqlx::query!("SELECT status, object_key FROM share_cards WHERE id = $1", card_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(card.map(|card| (card.status, card.object_key)))
+    }
+}
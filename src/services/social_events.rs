@@ -5,7 +5,10 @@ use tracing;
 use actix_web::web;
 use std::sync::Arc;
 
+use crate::services::fault_injection::redis_publish_fault;
+use crate::services::telemetry::traced_message;
 use crate::models::game_events::GameEvent;
+use crate::models::social::ReactionTarget;
 
 /// Broadcast workout reaction added event via Redis
 pub async fn broadcast_reaction_added(
@@ -157,6 +160,90 @@ pub async fn broadcast_comment_reaction_removed(
     Ok(())
 }
 
+/// Broadcast reaction added to any target via Redis.
+/// Workout and comment reactions also emit their dedicated events for existing clients.
+pub async fn broadcast_target_reaction_added(
+    redis_client: &web::Data<Arc<redis::Client>>,
+    target: ReactionTarget,
+    target_id: Uuid,
+    user_id: Uuid,
+    username: String,
+    reaction_type: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match target {
+        ReactionTarget::Workout => {
+            broadcast_reaction_added(redis_client, target_id, user_id, username.clone(), reaction_type.clone()).await?
+        }
+        ReactionTarget::Comment => {
+            broadcast_comment_reaction_added(redis_client, target_id, user_id, username.clone(), reaction_type.clone()).await?
+        }
+        _ => {}
+    }
+
+    let event = GameEvent::ReactionAdded {
+        target_type: target.as_str().to_string(),
+        target_id,
+        user_id,
+        username: username.clone(),
+        reaction_type: reaction_type.clone(),
+        timestamp: Utc::now(),
+    };
+
+    broadcast_event(redis_client, &event).await?;
+    tracing::info!("📢 Broadcasted reaction added: {} reacted with {} to {} {}", username, reaction_type, target.as_str(), target_id);
+    Ok(())
+}
+
+/// Broadcast reaction removed from any target via Redis
+pub async fn broadcast_target_reaction_removed(
+    redis_client: &web::Data<Arc<redis::Client>>,
+    target: ReactionTarget,
+    target_id: Uuid,
+    user_id: Uuid,
+    username: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match target {
+        ReactionTarget::Workout => {
+            broadcast_reaction_removed(redis_client, target_id, user_id, username.clone()).await?
+        }
+        ReactionTarget::Comment => {
+            broadcast_comment_reaction_removed(redis_client, target_id, user_id, username.clone()).await?
+        }
+        _ => {}
+    }
+
+    let event = GameEvent::ReactionRemoved {
+        target_type: target.as_str().to_string(),
+        target_id,
+        user_id,
+        username: username.clone(),
+        timestamp: Utc::now(),
+    };
+
+    broadcast_event(redis_client, &event).await?;
+    tracing::info!("📢 Broadcasted reaction removed: {} removed reaction from {} {}", username, target.as_str(), target_id);
+    Ok(())
+}
+
+/// Broadcast media taken down by moderation via Redis
+pub async fn broadcast_media_taken_down(
+    redis_client: &web::Data<Arc<redis::Client>>,
+    file_url: String,
+    post_ids: Vec<Uuid>,
+    workout_ids: Vec<Uuid>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let event = GameEvent::MediaTakenDown {
+        file_url: file_url.clone(),
+        post_ids,
+        workout_ids,
+        timestamp: Utc::now(),
+    };
+
+    broadcast_event(redis_client, &event).await?;
+    tracing::info!("📢 Broadcasted media taken down: {}", file_url);
+    Ok(())
+}
+
 /// Send notification event via Redis to a specific user's channel
 /// Send WebSocket notification event to a specific user
 pub async fn send_websocket_notification_to_user(
@@ -176,8 +263,9 @@ pub async fn send_websocket_notification_to_user(
         timestamp: Utc::now(),
     };
 
+    redis_publish_fault()?;
     let mut conn = redis_client.get_async_connection().await?;
-    let event_message = serde_json::to_string(&event)?;
+    let event_message = traced_message(&event)?;
 
     // Send to user-specific channel only
     let user_channel = format!("game:events:user:{recipient_id}");
@@ -200,8 +288,9 @@ async fn broadcast_event(
     redis_client: &web::Data<Arc<redis::Client>>,
     event: &GameEvent,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    redis_publish_fault()?;
     let mut conn = redis_client.get_async_connection().await?;
-    let message = serde_json::to_string(event)?;
+    let message = traced_message(event)?;
 
     let global_channel = "game:events:global";
     let result: Result<i32, redis::RedisError> = conn.publish(global_channel, message).await;
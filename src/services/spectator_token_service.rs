@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::errors::{Error, ErrorKind};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::jwt::JwtSettings;
+
+/// How long a guest can follow a game before the frontend has to ask for a new token
+pub const SPECTATOR_TOKEN_MINUTES: i64 = 120;
+
+/// Spectator tokens a user can create per hour, so shared links can't be minted in bulk
+pub const SPECTATOR_TOKENS_PER_HOUR: u32 = 20;
+
+/// The only thing a spectator token allows
+pub const SPECTATE_SCOPE: &str = "spectate";
+
+/// Claims of a guest spectator token. They name one game and carry no user, and the token is
+/// issued to its own audience, so neither the auth middleware nor the user WebSocket accept it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpectatorClaims {
+    /// Random id per token, spectators have no account
+    pub sub: String,
+    pub game_id: Uuid,
+    pub scope: String,
+    pub exp: usize,
+    pub iss: String,
+    pub aud: String,
+}
+
+impl SpectatorClaims {
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.exp as i64, 0).unwrap_or_else(Utc::now)
+    }
+}
+
+/// Sign a read-only token for following one game, returning it with its expiry
+pub fn issue_spectator_token(
+    jwt_settings: &JwtSettings,
+    game_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<(String, DateTime<Utc>), Error> {
+    let expires_at = now + Duration::minutes(SPECTATOR_TOKEN_MINUTES);
+    let claims = SpectatorClaims {
+        sub: format!("spectator:{}", Uuid::new_v4()),
+        game_id,
+        scope: SPECTATE_SCOPE.to_string(),
+        exp: expires_at.timestamp() as usize,
+        iss: jwt_settings.issuer.clone(),
+        aud: jwt_settings.spectator_audience(),
+    };
+    Ok((jwt_settings.sign(&claims)?, expires_at))
+}
+
+/// Decode a spectator token, refusing user tokens and tokens with another scope
+pub fn verify_spectator_token(jwt_settings: &JwtSettings, token: &str) -> Result<SpectatorClaims, Error> {
+    let claims: SpectatorClaims = jwt_settings.verify_for_audience(token, &jwt_settings.spectator_audience())?;
+    if claims.scope != SPECTATE_SCOPE {
+        return Err(ErrorKind::InvalidToken.into());
+    }
+    Ok(claims)
+}
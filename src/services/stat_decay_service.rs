@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use crate::config::stat_decay::StatDecaySettings;
+use crate::game::stat_decay::{decay_week_start, decayed_stat};
+
+/// Decays avatar stamina and strength weekly, so stats reflect current fitness instead of
+/// everything ever done
+pub struct StatDecayService {
+    pool: PgPool,
+    settings: StatDecaySettings,
+}
+
+impl StatDecayService {
+    pub fn new(pool: PgPool, settings: StatDecaySettings) -> Self {
+        Self { pool, settings }
+    }
+
+    /// Decay the stats of every user not yet decayed in the week of `today`, returning how many
+    /// users were decayed
+    pub async fn apply_weekly_decay(&self, today: NaiveDate) -> Result<usize, sqlx::Error> {
+        if !self.settings.enabled {
+            return Ok(0);
+        }
+        let week_start = decay_week_start(today);
+        let settings = &self.settings;
+
+        let mut tx = self.pool.begin().await?;
+        let avatars = sqlx::query!(
+            r#"
+            SELECT ua.user_id, ua.stamina, ua.strength
+            FROM user_avatars ua
+            WHERE (ua.stamina > $1 OR ua.strength > $2)
+              AND NOT EXISTS (
+                  SELECT 1 FROM stat_decay_history h WHERE h.user_id = ua.user_id AND h.week_start = $3
+              )
+            FOR UPDATE OF ua
+            "#,
+            settings.stamina_floor,
+            settings.strength_floor,
+            week_start
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let (mut user_ids, mut stamina_before, mut stamina_after, mut strength_before, mut strength_after) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for avatar in avatars {
+            let stamina = decayed_stat(avatar.stamina, settings.weekly_percent, settings.stamina_floor);
+            let strength = decayed_stat(avatar.strength, settings.weekly_percent, settings.strength_floor);
+            if stamina == avatar.stamina && strength == avatar.strength {
+                continue;
+            }
+            user_ids.push(avatar.user_id);
+            stamina_before.push(avatar.stamina);
+            stamina_after.push(stamina);
+            strength_before.push(avatar.strength);
+            strength_after.push(strength);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE user_avatars ua
+            SET stamina = d.stamina, strength = d.strength, updated_at = NOW()
+            FROM UNNEST($1::uuid[], $2::real[], $3::real[]) AS d(user_id, stamina, strength)
+            WHERE ua.user_id = d.user_id
+            "#,
+            &user_ids,
+            &stamina_after,
+            &strength_after
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO stat_decay_history (
+                user_id, week_start, weekly_percent, stamina_before, stamina_after, strength_before, strength_after
+            )
+            SELECT d.user_id, $1, $2, d.stamina_before, d.stamina_after, d.strength_before, d.strength_after
+            FROM UNNEST($3::uuid[], $4::real[], $5::real[], $6::real[], $7::real[])
+                AS d(user_id, stamina_before, stamina_after, strength_before, strength_after)
+            "#,
+            week_start,
+            settings.weekly_percent,
+            &user_ids,
+            &stamina_before,
+            &stamina_after,
+            &strength_before,
+            &strength_after
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(user_ids.len())
+    }
+
+}
@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::models::suspension::{CreateSuspensionRequest, UserSuspension};
+
+#[derive(Debug)]
+pub enum SuspensionError {
+    UserNotFound,
+    /// The user already has a suspension in effect
+    AlreadySuspended,
+    NotFound,
+    /// The suspension has already expired or been lifted
+    NotActive,
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for SuspensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UserNotFound => write!(f, "User not found"),
+            Self::AlreadySuspended => write!(f, "User is already suspended"),
+            Self::NotFound => write!(f, "Suspension not found"),
+            Self::NotActive => write!(f, "Suspension has already ended"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for SuspensionError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// Admin suspensions. They end on their own once `ends_at` passes, so nothing has to run
+/// to expire them; enforcement reads `active_suspension` and `overlaps_suspension`.
+pub struct SuspensionService {
+    pool: PgPool,
+}
+
+impl SuspensionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn suspend(
+        &self,
+        user_id: Uuid,
+        admin_id: Uuid,
+        request: &CreateSuspensionRequest,
+    ) -> Result<UserSuspension, SuspensionError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locking the user keeps two admins from suspending them at the same time
+        let user = sqlx::query_scalar!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if user.is_none() {
+            return Err(SuspensionError::UserNotFound);
+        }
+        if active_suspension(&mut *tx, user_id).await?.is_some() {
+            return Err(SuspensionError::AlreadySuspended);
+        }
+
+        let starts_at = Utc::now();
+        let suspension = sqlx::query_as!(
+            UserSuspension,
+            r#"
+            INSERT INTO user_suspensions (user_id, reason, starts_at, ends_at, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, reason, starts_at, ends_at, created_by, lifted_at, lifted_by, lift_reason, created_at
+            "#,
+            user_id,
+            request.reason.trim(),
+            starts_at,
+            request.ends_at(starts_at),
+            admin_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(suspension)
+    }
+
+    /// All suspensions of a user, newest first
+    pub async fn list(&self, user_id: Uuid) -> Result<Vec<UserSuspension>, sqlx::Error> {
+        sqlx::query_as!(
+            UserSuspension,
+            r#"
+            SELECT id, user_id, reason, starts_at, ends_at, created_by, lifted_at, lifted_by, lift_reason, created_at
+            FROM user_suspensions
+            WHERE user_id = $1
+            ORDER BY starts_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// End a suspension early
+    pub async fn lift(
+        &self,
+        suspension_id: Uuid,
+        admin_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<UserSuspension, SuspensionError> {
+        let lifted = sqlx::query_as!(
+            UserSuspension,
+            r#"
+            UPDATE user_suspensions
+            SET lifted_at = NOW(), lifted_by = $2, lift_reason = $3
+            WHERE id = $1
+            AND lifted_at IS NULL
+            AND (ends_at IS NULL OR ends_at > NOW())
+            RETURNING id, user_id, reason, starts_at, ends_at, created_by, lifted_at, lifted_by, lift_reason, created_at
+            "#,
+            suspension_id,
+            admin_id,
+            reason.map(str::trim).filter(|r| !r.is_empty())
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(lifted) = lifted {
+            return Ok(lifted);
+        }
+
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM user_suspensions WHERE id = $1) as "exists!""#,
+            suspension_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Err(if exists { SuspensionError::NotActive } else { SuspensionError::NotFound })
+    }
+}
+
+/// The suspension currently in effect for a user, if any
+pub async fn active_suspension<'e, E: PgExecutor<'e>>(
+    executor: E,
+    user_id: Uuid,
+) -> Result<Option<UserSuspension>, sqlx::Error> {
+    sqlx::query_as!(
+        UserSuspension,
+        r#"
+        SELECT id, user_id, reason, starts_at, ends_at, created_by, lifted_at, lifted_by, lift_reason, created_at
+        FROM user_suspensions
+        WHERE user_id = $1
+        AND lifted_at IS NULL
+        AND starts_at <= NOW()
+        AND (ends_at IS NULL OR ends_at > NOW())
+        ORDER BY starts_at DESC
+        LIMIT 1
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Whether a workout overlaps a time the user was suspended. Such workouts are stored but don't
+/// count toward games or quests, even when uploaded after the suspension ended.
+pub async fn overlaps_suspension(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    workout_start: DateTime<Utc>,
+    workout_end: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM user_suspensions
+            WHERE user_id = $1
+            AND starts_at < $3
+            AND COALESCE(LEAST(ends_at, lifted_at), 'infinity') > $2
+        ) as "exists!"
+        "#,
+        user_id,
+        workout_start,
+        workout_end
+    )
+    .fetch_one(conn)
+    .await
+}
@@ -0,0 +1,36 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::upload_attempts::{count_upload_outcomes, device_syncs, list_upload_attempts, prune_upload_attempts};
+use crate::models::diagnostics::{DiagnosticsQuery, SyncDiagnostics, UPLOAD_ATTEMPT_RETENTION_DAYS};
+
+/// Service behind `/profile/diagnostics`: what became of a user's recent workout uploads and when
+/// each of their devices last synced, for answering "my workout didn't count".
+#[derive(Debug)]
+pub struct SyncDiagnosticsService {
+    pool: PgPool,
+}
+
+impl SyncDiagnosticsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn diagnostics(&self, user_id: Uuid, query: &DiagnosticsQuery) -> Result<SyncDiagnostics, sqlx::Error> {
+        let days = query.days();
+        let since = Utc::now() - Duration::days(days);
+        Ok(SyncDiagnostics {
+            user_id,
+            days,
+            outcome_counts: count_upload_outcomes(&self.pool, user_id, since).await?,
+            attempts: list_upload_attempts(&self.pool, user_id, since, query.limit()).await?,
+            devices: device_syncs(&self.pool, user_id).await?,
+        })
+    }
+
+    /// Delete upload attempts past the retention
+    pub async fn prune_attempts(&self) -> Result<u64, sqlx::Error> {
+        prune_upload_attempts(&self.pool, Utc::now() - Duration::days(UPLOAD_ATTEMPT_RETENTION_DAYS)).await
+    }
+}
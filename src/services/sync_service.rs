@@ -0,0 +1,256 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// Days tombstones are kept; clients syncing from further back get a full snapshot instead
+pub const SYNC_RETENTION_DAYS: i64 = 30;
+
+/// How far `next_since` trails the sync. Writes still in flight during a sync commit with
+/// `updated_at` stamps from before it, so the next sync repeats this window to catch them.
+pub const SYNC_CURSOR_OVERLAP_SECONDS: i64 = 60;
+
+/// Service behind `/sync/changes`: everything relevant to a user's offline cache that
+/// changed after a point in time, plus tombstones for what was deleted since then.
+#[derive(Debug)]
+pub struct SyncService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncWorkout {
+    pub id: Uuid,
+    pub workout_start: DateTime<Utc>,
+    pub workout_end: DateTime<Utc>,
+    pub duration_minutes: Option<i32>,
+    pub activity_name: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+    pub avg_heart_rate: Option<i32>,
+    pub max_heart_rate: Option<i32>,
+    pub calories_burned: Option<i32>,
+    pub stamina_gained: f32,
+    pub strength_gained: f32,
+    pub total_points_gained: i32,
+    pub image_url: Option<String>,
+    pub video_url: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncGame {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub week_number: i32,
+    pub home_team_id: Uuid,
+    pub home_team_name: String,
+    pub away_team_id: Uuid,
+    pub away_team_name: String,
+    pub status: String,
+    pub home_score: i32,
+    pub away_score: i32,
+    pub winner_team_id: Option<Uuid>,
+    pub game_start_time: Option<DateTime<Utc>>,
+    pub game_end_time: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncStanding {
+    pub id: Uuid,
+    pub season_id: Uuid,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub position: i32,
+    pub games_played: i32,
+    pub wins: i32,
+    pub draws: i32,
+    pub losses: i32,
+    pub points: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncNotification {
+    pub id: Uuid,
+    pub actor_id: Uuid,
+    pub notification_type: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub message: String,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncTombstone {
+    /// "workout", "game", "standing" or "notification"
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncChanges {
+    /// Pass as `since` on the next sync. The next sync repeats changes from shortly before
+    /// it, so clients should upsert by id.
+    pub next_since: DateTime<Utc>,
+    /// True when this is a full snapshot and the client should replace its cache
+    pub full_resync: bool,
+    pub workouts: Vec<SyncWorkout>,
+    pub games: Vec<SyncGame>,
+    pub standings: Vec<SyncStanding>,
+    pub notifications: Vec<SyncNotification>,
+    pub deleted: Vec<SyncTombstone>,
+}
+
+impl SyncService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Changes for the user after `since`. Without `since`, or with one older than the
+    /// tombstone retention, a full snapshot is returned instead.
+    pub async fn get_changes(&self, user_id: Uuid, since: Option<DateTime<Utc>>) -> Result<SyncChanges, sqlx::Error> {
+        // All entities are read from one snapshot, so a write can't land between two of the queries
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(&mut *tx)
+            .await?;
+
+        // Database clock, so the cursor compares against the same clock that stamps updated_at
+        let server_time = sqlx::query_scalar!(r#"SELECT NOW() as "now!""#)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let since = since.filter(|since| *since > server_time - Duration::days(SYNC_RETENTION_DAYS));
+        let full_resync = since.is_none();
+
+        let season_ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT lt.season_id
+            FROM league_teams lt
+            JOIN team_members tm ON tm.team_id = lt.team_id
+            WHERE tm.user_id = $1 AND tm.status = 'active'
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let workouts = sqlx::query_as!(
+            SyncWorkout,
+            r#"
+            SELECT id, workout_start, workout_end, duration_minutes,
+                   COALESCE(user_activity, activity_name) as activity_name, notes, tags,
+                   avg_heart_rate, max_heart_rate, calories_burned,
+                   stamina_gained, strength_gained, total_points_gained,
+                   image_url, video_url, updated_at
+            FROM workout_data
+            WHERE user_id = $1 AND ($2::timestamptz IS NULL OR updated_at > $2)
+            ORDER BY updated_at, id
+            "#,
+            user_id,
+            since
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // A snapshot only carries notifications from the retention window
+        let notifications = sqlx::query_as!(
+            SyncNotification,
+            r#"
+            SELECT id, actor_id, notification_type, entity_type, entity_id, message, read, created_at, updated_at
+            FROM notifications
+            WHERE recipient_id = $1
+            AND CASE WHEN $2::timestamptz IS NULL THEN created_at > $3 ELSE updated_at > $2 END
+            ORDER BY updated_at, id
+            "#,
+            user_id,
+            since,
+            server_time - Duration::days(SYNC_RETENTION_DAYS)
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let games = sqlx::query_as!(
+            SyncGame,
+            r#"
+            SELECT g.id, g.season_id, g.week_number,
+                   g.home_team_id, ht.team_name as home_team_name,
+                   g.away_team_id, at.team_name as away_team_name,
+                   g.status, g.home_score, g.away_score, g.winner_team_id,
+                   g.game_start_time, g.game_end_time, g.updated_at
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE g.season_id = ANY($1) AND ($2::timestamptz IS NULL OR g.updated_at > $2)
+            ORDER BY g.updated_at, g.id
+            "#,
+            &season_ids,
+            since
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let standings = sqlx::query_as!(
+            SyncStanding,
+            r#"
+            SELECT ls.id, ls.season_id, ls.team_id, t.team_name, ls.position,
+                   ls.games_played, ls.wins, ls.draws, ls.losses, ls.points,
+                   ls.last_updated as updated_at
+            FROM league_standings ls
+            JOIN teams t ON t.id = ls.team_id
+            WHERE ls.season_id = ANY($1) AND ($2::timestamptz IS NULL OR ls.last_updated > $2)
+            ORDER BY ls.season_id, ls.position
+            "#,
+            &season_ids,
+            since
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let deleted = match since {
+            Some(since) => sqlx::query_as!(
+                SyncTombstone,
+                r#"
+                SELECT entity_type, entity_id, deleted_at
+                FROM sync_tombstones
+                WHERE deleted_at > $2
+                AND (user_id = $1 OR season_id = ANY($3))
+                ORDER BY deleted_at, id
+                "#,
+                user_id,
+                since,
+                &season_ids
+            )
+            .fetch_all(&mut *tx)
+            .await?,
+            None => Vec::new(),
+        };
+        tx.commit().await?;
+
+        Ok(SyncChanges {
+            next_since: server_time - Duration::seconds(SYNC_CURSOR_OVERLAP_SECONDS),
+            full_resync,
+            workouts,
+            games,
+            standings,
+            notifications,
+            deleted,
+        })
+    }
+
+    /// Drop tombstones older than the retention window
+    pub async fn prune_tombstones(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM sync_tombstones WHERE deleted_at < NOW() - make_interval(days => $1)",
+            SYNC_RETENTION_DAYS as i32
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
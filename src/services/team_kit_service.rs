@@ -0,0 +1,68 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::league::team_kits::{assign_game_kits, GameKit, TeamKit};
+
+/// Kits the teams of a game wear: assigned from their colors so they don't clash, and fixed
+/// once the game starts
+pub struct TeamKitService {
+    pool: PgPool,
+}
+
+impl TeamKitService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Home and away kits of the game, None if it doesn't exist
+    pub async fn game_kits(&self, game_id: Uuid) -> Result<Option<(GameKit, GameKit)>, sqlx::Error> {
+        let Some(game) = sqlx::query!(
+            r#"
+            SELECT gk.home_color as "home_kit_color?", gk.away_color as "away_kit_color?",
+                   ht.team_color as home_team_color, ht.away_color as home_away_color, ht.kit_emoji as home_emoji,
+                   at.team_color as away_team_color, at.away_color as away_away_color, at.kit_emoji as away_emoji
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            LEFT JOIN game_kits gk ON gk.game_id = g.id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let home = TeamKit { home_color: game.home_team_color, away_color: game.home_away_color, emoji: game.home_emoji };
+        let away = TeamKit { home_color: game.away_team_color, away_color: game.away_away_color, emoji: game.away_emoji };
+        let (mut home_kit, mut away_kit) = assign_game_kits(&home, &away);
+        if let (Some(home_color), Some(away_color)) = (game.home_kit_color, game.away_kit_color) {
+            home_kit.color = home_color;
+            away_kit.color = away_color;
+        }
+        Ok(Some((home_kit, away_kit)))
+    }
+
+    /// Fix the game's kit colors, unless they already are
+    pub async fn fix_game_kits(&self, game_id: Uuid) -> Result<(), sqlx::Error> {
+        let Some((home_kit, away_kit)) = self.game_kits(game_id).await? else {
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO game_kits (game_id, home_color, away_color)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (game_id) DO NOTHING
+            "#,
+            game_id,
+            home_kit.color,
+            away_kit.color
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
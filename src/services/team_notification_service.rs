@@ -0,0 +1,288 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::league::team_alerts::{self, TeamAlert};
+use crate::models::notification::{TeamNotificationSettings, UpdateTeamNotificationSettingsRequest};
+use crate::services::notification_delivery::{deliver_to_enabled_channels, ChannelNotification};
+
+#[derive(Debug)]
+pub enum TeamNotificationError {
+    TeamNotFound,
+    NotAMember,
+    /// Only the team's captains change its notification settings
+    NotCaptain,
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for TeamNotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TeamNotFound => write!(f, "Team not found"),
+            Self::NotAMember => write!(f, "You are not a member of this team"),
+            Self::NotCaptain => write!(f, "Only team captains can change team notifications"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for TeamNotificationError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+/// Team notifications: the events each team's captains enabled, and delivering those events
+/// to the team's members through their own enabled channels
+pub struct TeamNotificationService {
+    pool: PgPool,
+}
+
+impl TeamNotificationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The team's settings, for its active members
+    pub async fn settings_for_member(&self, team_id: Uuid, user_id: Uuid) -> Result<TeamNotificationSettings, TeamNotificationError> {
+        self.member_role(team_id, user_id).await?;
+        Ok(self.settings(team_id).await?)
+    }
+
+    /// Change the team's settings; omitted fields keep their value
+    pub async fn update(
+        &self,
+        team_id: Uuid,
+        user_id: Uuid,
+        request: &UpdateTeamNotificationSettingsRequest,
+    ) -> Result<TeamNotificationSettings, TeamNotificationError> {
+        if self.member_role(team_id, user_id).await? != "owner" {
+            return Err(TeamNotificationError::NotCaptain);
+        }
+
+        let settings = sqlx::query_as!(
+            TeamNotificationSettings,
+            r#"
+            INSERT INTO team_notification_settings (
+                team_id, game_start_enabled, fell_behind_enabled, member_milestone_enabled, updated_by
+            )
+            VALUES ($1, COALESCE($2, TRUE), COALESCE($3, TRUE), COALESCE($4, TRUE), $5)
+            ON CONFLICT (team_id) DO UPDATE SET
+                game_start_enabled = COALESCE($2, team_notification_settings.game_start_enabled),
+                fell_behind_enabled = COALESCE($3, team_notification_settings.fell_behind_enabled),
+                member_milestone_enabled = COALESCE($4, team_notification_settings.member_milestone_enabled),
+                updated_by = $5,
+                updated_at = NOW()
+            RETURNING team_id, game_start_enabled, fell_behind_enabled, member_milestone_enabled,
+                      updated_at as "updated_at?"
+            "#,
+            team_id,
+            request.game_start_enabled,
+            request.fell_behind_enabled,
+            request.member_milestone_enabled,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        tracing::info!("🔔 Team {} notification settings updated by {}", team_id, user_id);
+        Ok(settings)
+    }
+
+    /// Role of an active member, `NotAMember` otherwise
+    async fn member_role(&self, team_id: Uuid, user_id: Uuid) -> Result<String, TeamNotificationError> {
+        let membership = sqlx::query!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM teams WHERE id = $1) as "team_exists!",
+                   (SELECT role FROM team_members
+                    WHERE team_id = $1 AND user_id = $2 AND status = 'active') as role
+            "#,
+            team_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if !membership.team_exists {
+            return Err(TeamNotificationError::TeamNotFound);
+        }
+        membership.role.ok_or(TeamNotificationError::NotAMember)
+    }
+
+    pub async fn settings(&self, team_id: Uuid) -> Result<TeamNotificationSettings, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            TeamNotificationSettings,
+            r#"
+            SELECT team_id, game_start_enabled, fell_behind_enabled, member_milestone_enabled,
+                   updated_at as "updated_at?"
+            FROM team_notification_settings
+            WHERE team_id = $1
+            "#,
+            team_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(settings.unwrap_or_else(|| TeamNotificationSettings::defaults(team_id)))
+    }
+
+    /// Deliver a team event to the team's active members, unless its captains turned it off.
+    /// The member whose action it was, if any, is not notified about it. Returns how many
+    /// members were reached.
+    pub async fn notify_team(
+        &self,
+        team_id: Uuid,
+        alert: TeamAlert,
+        actor_id: Option<Uuid>,
+        entity: (&str, Uuid),
+        title: &str,
+        message: &str,
+    ) -> Result<usize, sqlx::Error> {
+        if !self.settings(team_id).await?.allows(alert) {
+            tracing::debug!("Team {} has {} notifications turned off", team_id, alert.as_str());
+            return Ok(0);
+        }
+
+        let members = sqlx::query_scalar!(
+            "SELECT user_id FROM team_members WHERE team_id = $1 AND status = 'active' AND user_id IS DISTINCT FROM $2",
+            team_id,
+            actor_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut reached = 0;
+        for member_id in members {
+            let notification = ChannelNotification {
+                recipient_id: member_id,
+                actor_id: actor_id.unwrap_or(member_id),
+                notification_type: alert.as_str().to_string(),
+                entity_type: entity.0.to_string(),
+                entity_id: entity.1,
+                title: title.to_string(),
+                message: message.to_string(),
+                push_category: "league_update".to_string(),
+            };
+            match deliver_to_enabled_channels(&self.pool, &notification).await {
+                Ok(channels) if !channels.is_empty() => reached += 1,
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to notify {} about {}: {}", member_id, alert.as_str(), e),
+            }
+        }
+        Ok(reached)
+    }
+
+    /// Tell both teams of a game that just started
+    pub async fn notify_game_started(&self, game_id: Uuid) -> Result<(), sqlx::Error> {
+        let Some(game) = sqlx::query!(
+            r#"
+            SELECT g.home_team_id, g.away_team_id, ht.team_name as home_team_name, at.team_name as away_team_name
+            FROM games g
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE g.id = $1
+            "#,
+            game_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let sides = [
+            (game.home_team_id, &game.home_team_name, &game.away_team_name),
+            (game.away_team_id, &game.away_team_name, &game.home_team_name),
+        ];
+        for (team_id, team_name, opponent_name) in sides {
+            self.notify_team(
+                team_id,
+                TeamAlert::GameStart,
+                None,
+                ("game", game_id),
+                "Game on!",
+                &team_alerts::game_start_message(team_name, opponent_name),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Tell the opponent of the scoring team if the score event took away its lead or tie
+    pub async fn notify_if_fell_behind(&self, score_event_id: Uuid) -> Result<bool, sqlx::Error> {
+        let Some(event) = sqlx::query!(
+            r#"
+            SELECT lse.game_id as "game_id!", lse.user_id, lse.team_side, lse.score_points,
+                   g.home_score, g.away_score, g.home_team_id, g.away_team_id,
+                   ht.team_name as home_team_name, at.team_name as away_team_name
+            FROM live_score_events lse
+            JOIN games g ON g.id = lse.game_id
+            JOIN teams ht ON ht.id = g.home_team_id
+            JOIN teams at ON at.id = g.away_team_id
+            WHERE lse.id = $1
+            "#,
+            score_event_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        let (scorer_after, opponent_score, opponent_id, scorer_name) = if event.team_side == "home" {
+            (event.home_score, event.away_score, event.away_team_id, event.home_team_name)
+        } else {
+            (event.away_score, event.home_score, event.home_team_id, event.away_team_name)
+        };
+        let scorer_before = scorer_after - event.score_points.round() as i32;
+        if !team_alerts::fell_behind(opponent_score, scorer_before, opponent_score, scorer_after) {
+            return Ok(false);
+        }
+
+        self.notify_team(
+            opponent_id,
+            TeamAlert::FellBehind,
+            Some(event.user_id),
+            ("game", event.game_id),
+            "You fell behind",
+            &team_alerts::fell_behind_message(&scorer_name, opponent_score, scorer_after),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Tell a member's teammates when their workout count reached a milestone
+    pub async fn notify_if_member_milestone(&self, user_id: Uuid, workout_id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+        let member = sqlx::query!(
+            r#"
+            SELECT u.username,
+                   (SELECT COUNT(*) FROM workout_data wd WHERE wd.user_id = u.id) as "workouts!"
+            FROM users u
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if !team_alerts::is_workout_milestone(member.workouts) {
+            return Ok(None);
+        }
+
+        let teams = sqlx::query_scalar!(
+            "SELECT team_id FROM team_members WHERE user_id = $1 AND status = 'active'",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for team_id in teams {
+            self.notify_team(
+                team_id,
+                TeamAlert::MemberMilestone,
+                Some(user_id),
+                ("workout", workout_id),
+                "Team milestone",
+                &team_alerts::member_milestone_message(&member.username, member.workouts),
+            )
+            .await?;
+        }
+        Ok(Some(member.workouts))
+    }
+}
@@ -1,6 +1,23 @@
-use tracing::{subscriber::set_global_default, Subscriber};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{subscriber::set_global_default, Event, Subscriber};
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_log::LogTracer;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+
+use crate::services::error_reporting::error_reporting_layer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Registry};
 
 /// Compose multiple layers into a `tracing`'s subscriber.
@@ -24,6 +41,7 @@ pub fn get_subscriber<Sink>(
     // if the RUST_LOG environment variable has not been set.
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(env_filter));
+    let trace_export_layer = trace_export_layer(&name);
     let formatting_layer = BunyanFormattingLayer::new(
         name,
         // Output the formatted spans to stdout.
@@ -31,6 +49,9 @@ pub fn get_subscriber<Sink>(
     );
     Registry::default()
         .with(env_filter)
+        .with(trace_export_layer)
+        .with(error_reporting_layer())
+        .with(RequestLogLayer::new(log_controls().clone()))
         .with(JsonStorageLayer)
         .with(formatting_layer)
 }
@@ -38,4 +59,241 @@ pub fn get_subscriber<Sink>(
 pub fn init_subscriber(subscriber: impl Subscriber + Send + Sync) {
     LogTracer::init().expect("Failed to set logger");
     set_global_default(subscriber).expect("Failed to set subscriber");
-}
\ No newline at end of file
+}
+
+/// OTLP/HTTP collector traces are exported to, e.g. http://otel-collector:4318.
+/// Traces are only exported when it's set.
+const OTLP_ENDPOINT_VAR: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Field of events published through Redis carrying the W3C trace context they were published in
+pub const TRACE_CONTEXT_FIELD: &str = "trace_context";
+
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Layer exporting spans as OTLP traces, if a collector is configured
+fn trace_export_layer<S>(service_name: &str) -> Option<OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    std::env::var(OTLP_ENDPOINT_VAR).ok().filter(|endpoint| !endpoint.is_empty())?;
+    let exporter = match opentelemetry_otlp::SpanExporter::builder().with_http().build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("Failed to create the OTLP trace exporter, traces are not exported: {e}");
+            return None;
+        }
+    };
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name.to_string()).build())
+        .build();
+    // Incoming requests continue the trace of their traceparent header
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let tracer = provider.tracer(service_name.to_string());
+    let _ = TRACER_PROVIDER.set(provider);
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Export the traces still buffered before the process exits
+pub fn shutdown_trace_export() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to flush traces: {e}");
+        }
+    }
+}
+
+/// W3C trace context of the current span, empty when traces aren't exported
+pub fn current_trace_context() -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&tracing::Span::current().context(), &mut carrier);
+    carrier
+}
+
+/// Make `span` part of the trace `carrier` was taken from
+pub fn continue_trace(span: &tracing::Span, carrier: &HashMap<String, String>) {
+    if !carrier.is_empty() {
+        span.set_parent(TraceContextPropagator::new().extract(carrier));
+    }
+}
+
+/// Add the current trace context to an event about to be published through Redis,
+/// so whoever handles it continues the trace
+pub fn attach_trace_context(event: &mut serde_json::Value) {
+    let trace_context = current_trace_context();
+    if let (false, Some(event)) = (trace_context.is_empty(), event.as_object_mut()) {
+        event.insert(TRACE_CONTEXT_FIELD.to_string(), serde_json::json!(trace_context));
+    }
+}
+
+/// An event serialized for publishing through Redis, carrying the current trace context
+pub fn traced_message<T: Serialize>(event: &T) -> serde_json::Result<String> {
+    let mut event = serde_json::to_value(event)?;
+    attach_trace_context(&mut event);
+    serde_json::to_string(&event)
+}
+
+/// Trace context a published event carries, empty for events published outside of a trace
+pub fn trace_context_of(event: &serde_json::Value) -> HashMap<String, String> {
+    event
+        .get(TRACE_CONTEXT_FIELD)
+        .and_then(|trace_context| serde_json::from_value(trace_context.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Log levels and sampling of request logs as configured through the admin API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogControlsSettings {
+    /// Most verbose level logged by requests to routes starting with the key, e.g. "/health" => "warn"
+    #[serde(default)]
+    pub route_levels: BTreeMap<String, String>,
+    /// Share of requests, from 0.0 to 1.0, that log below warnings; the others only log warnings and errors
+    pub sample_rate: f64,
+}
+
+/// Log levels and sampling applied to request logs
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogControls {
+    route_levels: Vec<(String, LevelFilter)>,
+    sample_rate: f64,
+}
+
+impl Default for LogControls {
+    fn default() -> Self {
+        Self { route_levels: Vec::new(), sample_rate: 1.0 }
+    }
+}
+
+impl TryFrom<LogControlsSettings> for LogControls {
+    type Error = String;
+
+    fn try_from(settings: LogControlsSettings) -> Result<Self, Self::Error> {
+        if !(0.0..=1.0).contains(&settings.sample_rate) {
+            return Err(format!("Sample rate must be between 0.0 and 1.0. Got: {}", settings.sample_rate));
+        }
+        let mut route_levels = Vec::new();
+        for (route, level) in settings.route_levels {
+            if !route.starts_with('/') {
+                return Err(format!("Route {route} must start with /"));
+            }
+            let level = level
+                .parse::<LevelFilter>()
+                .map_err(|_| format!("Unknown log level {level} for route {route}. Use off, error, warn, info, debug or trace"))?;
+            route_levels.push((route, level));
+        }
+        // Longest prefixes first, so the most specific route wins
+        route_levels.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        Ok(Self { route_levels, sample_rate: settings.sample_rate })
+    }
+}
+
+impl LogControls {
+    pub fn settings(&self) -> LogControlsSettings {
+        LogControlsSettings {
+            route_levels: self.route_levels.iter().map(|(route, level)| (route.clone(), level.to_string().to_lowercase())).collect(),
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// Level override of the most specific route prefix matching `route`
+    pub fn route_level(&self, route: &str) -> Option<LevelFilter> {
+        self.route_levels
+            .iter()
+            .find(|(prefix, _)| route.starts_with(prefix.as_str()))
+            .map(|(_, level)| *level)
+    }
+
+    /// Most verbose level a request to `route` logs; requests left out of the sample only log warnings and errors
+    pub fn request_level(&self, route: &str, sampled: bool) -> LevelFilter {
+        let level = self.route_level(route).unwrap_or(LevelFilter::TRACE);
+        if sampled { level } else { level.min(LevelFilter::WARN) }
+    }
+
+    pub fn sample(&self) -> bool {
+        self.sample_rate >= 1.0 || rand::random::<f64>() < self.sample_rate
+    }
+}
+
+/// Shared log controls, changed at runtime without restarting. Each instance keeps its own,
+/// and they're back to logging everything after a restart.
+#[derive(Debug, Clone, Default)]
+pub struct LogControlsHandle(Arc<RwLock<LogControls>>);
+
+impl LogControlsHandle {
+    pub fn get(&self) -> LogControls {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, controls: LogControls) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = controls;
+    }
+}
+
+/// Log controls of the subscriber built by `get_subscriber`
+pub fn log_controls() -> &'static LogControlsHandle {
+    static LOG_CONTROLS: OnceLock<LogControlsHandle> = OnceLock::new();
+    LOG_CONTROLS.get_or_init(LogControlsHandle::default)
+}
+
+/// Level a request span allows its events, decided when the request starts
+struct RequestLogLevel(LevelFilter);
+
+#[derive(Default)]
+struct RouteVisitor(Option<String>);
+
+impl Visit for RouteVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "http.route" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "http.route" {
+            self.0 = Some(value.to_string());
+        }
+    }
+}
+
+/// Drops events of requests beyond the level their route and sampling allow.
+/// Requests are recognized by the `http.route` field of the request spans of `TracingLogger`.
+pub struct RequestLogLayer {
+    controls: LogControlsHandle,
+}
+
+impl RequestLogLayer {
+    pub fn new(controls: LogControlsHandle) -> Self {
+        Self { controls }
+    }
+}
+
+impl<S> Layer<S> for RequestLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut route = RouteVisitor::default();
+        attrs.record(&mut route);
+        let (Some(route), Some(span)) = (route.0, ctx.span(id)) else {
+            return;
+        };
+        let controls = self.controls.get();
+        let level = controls.request_level(&route, controls.sample());
+        if level < LevelFilter::TRACE {
+            span.extensions_mut().insert(RequestLogLevel(level));
+        }
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        let Some(scope) = ctx.event_scope(event) else {
+            return true;
+        };
+        for span in scope {
+            if let Some(RequestLogLevel(level)) = span.extensions().get::<RequestLogLevel>() {
+                return *level >= *event.metadata().level();
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,317 @@
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::health_data::THRESHOLD_SOURCE_DETECTED;
+use crate::models::profile::ThresholdProposal;
+use crate::models::workout_data::HeartRateData;
+use crate::workout::threshold_detection::{
+    best_sustained_hr, estimate_thresholds, WorkoutThresholdEvidence, DETECTION_WINDOW_DAYS, SUSTAINED_EFFORT_MINUTES,
+};
+
+/// Users who declined a proposal aren't asked again for this long
+const DECLINE_COOLDOWN_DAYS: i64 = 28;
+
+#[derive(Debug)]
+pub enum ThresholdProposalError {
+    NotFound,
+    /// The proposal was already accepted, declined or superseded
+    NotPending,
+    /// The profile changed since, e.g. a new max heart rate, and the proposed values don't fit it anymore
+    NoLongerFits,
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for ThresholdProposalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Threshold proposal not found"),
+            Self::NotPending => write!(f, "Threshold proposal was already decided"),
+            Self::NoLongerFits => write!(f, "Threshold proposal no longer fits your health profile"),
+            Self::Database(e) => write!(f, "Database error: {e}"),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ThresholdProposalError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ThresholdDetectionRunSummary {
+    pub users_analyzed: usize,
+    pub proposals_created: usize,
+}
+
+/// Estimates VT1/VT2 from the heart rate of users' recent workouts and proposes them to the
+/// users. Nothing changes until a user accepts; the profile then records the thresholds as
+/// detected, along with the proposal they came from.
+pub struct ThresholdDetectionService {
+    pool: PgPool,
+}
+
+impl ThresholdDetectionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Propose thresholds to every user whose recent workouts put them notably elsewhere
+    pub async fn run_weekly(&self) -> Result<ThresholdDetectionRunSummary, sqlx::Error> {
+        let since = Utc::now() - Duration::days(DETECTION_WINDOW_DAYS);
+        let users = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT wd.user_id
+            FROM workout_data wd
+            JOIN user_health_profiles p ON p.user_id = wd.user_id
+            WHERE wd.workout_start >= $1
+            AND NOT EXISTS (
+                SELECT 1 FROM threshold_proposals tp
+                WHERE tp.user_id = wd.user_id AND tp.status = 'declined' AND tp.decided_at > $2
+            )
+            "#,
+            since,
+            Utc::now() - Duration::days(DECLINE_COOLDOWN_DAYS)
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut proposals_created = 0;
+        for user_id in &users {
+            if self.propose_for_user(*user_id).await? {
+                proposals_created += 1;
+            }
+        }
+
+        Ok(ThresholdDetectionRunSummary { users_analyzed: users.len(), proposals_created })
+    }
+
+    /// Analyze the user's recent workouts and propose the thresholds they point to. Returns
+    /// false if there isn't enough evidence or the thresholds would barely move.
+    pub async fn propose_for_user(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let Some(profile) = sqlx::query!(
+            r#"
+            SELECT resting_heart_rate, max_heart_rate, vt0_threshold, vt1_threshold, vt2_threshold
+            FROM user_health_profiles
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        let workouts = sqlx::query!(
+            r#"
+            SELECT wd.heart_rate_data, m.steady_state_avg_hr, m.hr_drift_pct
+            FROM workout_data wd
+            LEFT JOIN workout_hr_metrics m ON m.workout_data_id = wd.id
+            WHERE wd.user_id = $1 AND wd.workout_start >= $2
+            "#,
+            user_id,
+            Utc::now() - Duration::days(DETECTION_WINDOW_DAYS)
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let evidence: Vec<WorkoutThresholdEvidence> = workouts
+            .into_iter()
+            .map(|workout| {
+                let hr_data: Vec<HeartRateData> = serde_json::from_value(workout.heart_rate_data).unwrap_or_default();
+                WorkoutThresholdEvidence {
+                    steady_state_avg_hr: workout.steady_state_avg_hr,
+                    hr_drift_pct: workout.hr_drift_pct,
+                    best_sustained_hr: best_sustained_hr(&hr_data, SUSTAINED_EFFORT_MINUTES),
+                }
+            })
+            .collect();
+
+        let floor_hr = profile.vt0_threshold.unwrap_or(profile.resting_heart_rate);
+        let estimate = estimate_thresholds(&evidence, floor_hr, profile.max_heart_rate);
+        if !estimate.differs_from(profile.vt1_threshold, profile.vt2_threshold) {
+            return Ok(false);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let pending = sqlx::query!(
+            "SELECT id, vt1_threshold, vt2_threshold FROM threshold_proposals WHERE user_id = $1 AND status = 'pending' FOR UPDATE",
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(pending) = pending {
+            // Already waiting for the user's answer
+            if pending.vt1_threshold == estimate.vt1 && pending.vt2_threshold == estimate.vt2 {
+                return Ok(false);
+            }
+            sqlx::query!(
+                "UPDATE threshold_proposals SET status = 'superseded', decided_at = NOW() WHERE id = $1",
+                pending.id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO threshold_proposals (
+                user_id, vt1_threshold, vt2_threshold, current_vt1_threshold, current_vt2_threshold,
+                aerobic_workouts, sustained_workouts
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            user_id,
+            estimate.vt1,
+            estimate.vt2,
+            profile.vt1_threshold,
+            profile.vt2_threshold,
+            estimate.aerobic_workouts as i32,
+            estimate.sustained_workouts as i32
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        tracing::info!("Proposed thresholds VT1 {:?} / VT2 {:?} to user {}", estimate.vt1, estimate.vt2, user_id);
+        Ok(true)
+    }
+
+    /// The user's proposals, newest first
+    pub async fn list_proposals(&self, user_id: Uuid, pending_only: bool) -> Result<Vec<ThresholdProposal>, sqlx::Error> {
+        sqlx::query_as!(
+            ThresholdProposal,
+            r#"
+            SELECT id, vt1_threshold, vt2_threshold, current_vt1_threshold, current_vt2_threshold,
+                   aerobic_workouts, sustained_workouts, status, created_at, decided_at
+            FROM threshold_proposals
+            WHERE user_id = $1 AND (NOT $2 OR status = 'pending')
+            ORDER BY created_at DESC
+            LIMIT 20
+            "#,
+            user_id,
+            pending_only
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Apply a pending proposal to the user's profile, recording the thresholds as detected
+    pub async fn accept(&self, user_id: Uuid, proposal_id: Uuid) -> Result<ThresholdProposal, ThresholdProposalError> {
+        let mut tx = self.pool.begin().await?;
+        let proposal = self.lock_pending(&mut tx, user_id, proposal_id).await?;
+
+        let profile = sqlx::query!(
+            r#"
+            SELECT max_heart_rate, vt0_threshold, vt1_threshold, vt2_threshold
+            FROM user_health_profiles
+            WHERE user_id = $1
+            FOR UPDATE
+            "#,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ThresholdProposalError::NoLongerFits)?;
+
+        let vt1 = proposal.vt1_threshold.or(profile.vt1_threshold);
+        let vt2 = proposal.vt2_threshold.or(profile.vt2_threshold);
+        let in_order = match (profile.vt0_threshold, vt1, vt2) {
+            (vt0, Some(vt1), Some(vt2)) => vt0.is_none_or(|vt0| vt0 < vt1) && vt1 < vt2 && vt2 < profile.max_heart_rate,
+            _ => false,
+        };
+        if !in_order {
+            return Err(ThresholdProposalError::NoLongerFits);
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE user_health_profiles
+            SET vt1_threshold = $2,
+                vt2_threshold = $3,
+                vt1_source = CASE WHEN $4 THEN $6 ELSE vt1_source END,
+                vt2_source = CASE WHEN $5 THEN $6 ELSE vt2_source END,
+                threshold_proposal_id = $7,
+                last_updated = NOW(),
+                version = version + 1
+            WHERE user_id = $1
+            "#,
+            user_id,
+            vt1,
+            vt2,
+            proposal.vt1_threshold.is_some(),
+            proposal.vt2_threshold.is_some(),
+            THRESHOLD_SOURCE_DETECTED,
+            proposal_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let accepted = self.decide(&mut tx, proposal_id, "accepted").await?;
+        tx.commit().await?;
+        tracing::info!("User {} accepted threshold proposal {}", user_id, proposal_id);
+        Ok(accepted)
+    }
+
+    /// Decline a pending proposal; the profile stays as it is
+    pub async fn decline(&self, user_id: Uuid, proposal_id: Uuid) -> Result<ThresholdProposal, ThresholdProposalError> {
+        let mut tx = self.pool.begin().await?;
+        self.lock_pending(&mut tx, user_id, proposal_id).await?;
+        let declined = self.decide(&mut tx, proposal_id, "declined").await?;
+        tx.commit().await?;
+        Ok(declined)
+    }
+
+    async fn lock_pending(
+        &self,
+        tx: &mut sqlx::PgConnection,
+        user_id: Uuid,
+        proposal_id: Uuid,
+    ) -> Result<ThresholdProposal, ThresholdProposalError> {
+        let proposal = sqlx::query_as!(
+            ThresholdProposal,
+            r#"
+            SELECT id, vt1_threshold, vt2_threshold, current_vt1_threshold, current_vt2_threshold,
+                   aerobic_workouts, sustained_workouts, status, created_at, decided_at
+            FROM threshold_proposals
+            WHERE id = $1 AND user_id = $2
+            FOR UPDATE
+            "#,
+            proposal_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ThresholdProposalError::NotFound)?;
+
+        if proposal.status != "pending" {
+            return Err(ThresholdProposalError::NotPending);
+        }
+        Ok(proposal)
+    }
+
+    async fn decide(
+        &self,
+        tx: &mut sqlx::PgConnection,
+        proposal_id: Uuid,
+        status: &str,
+    ) -> Result<ThresholdProposal, sqlx::Error> {
+        sqlx::query_as!(
+            ThresholdProposal,
+            r#"
+            UPDATE threshold_proposals
+            SET status = $2, decided_at = NOW()
+            WHERE id = $1
+            RETURNING id, vt1_threshold, vt2_threshold, current_vt1_threshold, current_vt2_threshold,
+                      aerobic_workouts, sustained_workouts, status, created_at, decided_at
+            "#,
+            proposal_id,
+            status
+        )
+        .fetch_one(&mut *tx)
+        .await
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::trailing_average::TRAILING_AVERAGE_DAYS;
+
+/// Entries also expire on their own, since workouts leave the trailing window as time passes
+const USER_STATS_TTL_SECONDS: u64 = 600;
+
+fn cache_key(user_id: Uuid) -> String {
+    format!("user_activity_stats:{user_id}")
+}
+
+/// Workout-derived stats shown on profiles and the leaderboard
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct UserActivityStats {
+    pub trailing_average: f32,
+    pub avg_exercise_minutes_per_day: f32,
+}
+
+/// Read-through Redis cache of per-user activity stats.
+/// Entries are dropped when the user's workouts change (upload, rescoring, deletion);
+/// without Redis, or when it fails, the stats are computed from the database.
+pub struct UserStatsCache {
+    pool: PgPool,
+    redis_client: Option<Arc<redis::Client>>,
+}
+
+impl UserStatsCache {
+    pub fn new(pool: PgPool, redis_client: Option<Arc<redis::Client>>) -> Self {
+        Self { pool, redis_client }
+    }
+
+    pub async fn get(&self, user_id: Uuid) -> Result<UserActivityStats, sqlx::Error> {
+        let stats = self.get_many(&[user_id]).await?;
+        Ok(stats.get(&user_id).copied().unwrap_or_default())
+    }
+
+    /// Stats of every given user; users without workouts get zeros
+    pub async fn get_many(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, UserActivityStats>, sqlx::Error> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut stats = self.read_cached(user_ids).await;
+        let missing: Vec<Uuid> = user_ids.iter().filter(|id| !stats.contains_key(id)).copied().collect();
+        if !missing.is_empty() {
+            tracing::debug!("Computing activity stats for {} of {} users", missing.len(), user_ids.len());
+            let computed = compute_activity_stats(&self.pool, &missing).await?;
+            self.write_cached(&computed).await;
+            stats.extend(computed);
+        }
+        Ok(stats)
+    }
+
+    /// Drop the cached stats of users whose workouts changed
+    pub async fn invalidate(&self, user_ids: &[Uuid]) {
+        let Some(redis_client) = &self.redis_client else { return };
+        if user_ids.is_empty() {
+            return;
+        }
+
+        let keys: Vec<String> = user_ids.iter().map(|id| cache_key(*id)).collect();
+        let result: Result<(), redis::RedisError> = async {
+            let mut conn = redis_client.get_multiplexed_async_connection().await?;
+            redis::cmd("DEL").arg(&keys).query_async(&mut conn).await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to invalidate activity stats of {} users: {}", user_ids.len(), e);
+        }
+    }
+
+    async fn read_cached(&self, user_ids: &[Uuid]) -> HashMap<Uuid, UserActivityStats> {
+        let Some(redis_client) = &self.redis_client else { return HashMap::new() };
+
+        let keys: Vec<String> = user_ids.iter().map(|id| cache_key(*id)).collect();
+        let result: Result<Vec<Option<String>>, redis::RedisError> = async {
+            let mut conn = redis_client.get_multiplexed_async_connection().await?;
+            redis::cmd("MGET").arg(&keys).query_async(&mut conn).await
+        }
+        .await;
+
+        match result {
+            Ok(values) => user_ids
+                .iter()
+                .zip(values)
+                .filter_map(|(user_id, value)| {
+                    let stats = serde_json::from_str(value.as_deref()?).ok()?;
+                    Some((*user_id, stats))
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Failed to read cached activity stats: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    async fn write_cached(&self, stats: &HashMap<Uuid, UserActivityStats>) {
+        let Some(redis_client) = &self.redis_client else { return };
+
+        let mut pipe = redis::pipe();
+        for (user_id, user_stats) in stats {
+            let Ok(value) = serde_json::to_string(user_stats) else { continue };
+            pipe.cmd("SET").arg(cache_key(*user_id)).arg(value).arg("EX").arg(USER_STATS_TTL_SECONDS).ignore();
+        }
+
+        let result: Result<(), redis::RedisError> = async {
+            let mut conn = redis_client.get_multiplexed_async_connection().await?;
+            pipe.query_async(&mut conn).await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to cache activity stats: {}", e);
+        }
+    }
+}
+
+/// Trailing average of workout points and average workout minutes over the trailing window
+async fn compute_activity_stats(pool: &PgPool, user_ids: &[Uuid]) -> Result<HashMap<Uuid, UserActivityStats>, sqlx::Error> {
+    let cutoff_date = Utc::now() - Duration::days(TRAILING_AVERAGE_DAYS);
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT user_id, stamina_gained, strength_gained, duration_minutes
+        FROM workout_data
+        WHERE user_id = ANY($1)
+        AND workout_start >= $2
+        "#,
+        user_ids,
+        cutoff_date
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // user_id -> (points, summed minutes, workouts with a duration)
+    let mut totals: HashMap<Uuid, (f32, f64, u32)> = HashMap::new();
+    for row in rows {
+        let entry = totals.entry(row.user_id).or_default();
+        entry.0 += row.stamina_gained + row.strength_gained;
+        if let Some(minutes) = row.duration_minutes {
+            entry.1 += minutes as f64;
+            entry.2 += 1;
+        }
+    }
+
+    Ok(user_ids
+        .iter()
+        .map(|user_id| {
+            let (points, minutes, timed_workouts) = totals.get(user_id).copied().unwrap_or_default();
+            let stats = UserActivityStats {
+                trailing_average: points / TRAILING_AVERAGE_DAYS as f32,
+                avg_exercise_minutes_per_day: if timed_workouts > 0 { (minutes / timed_workouts as f64) as f32 } else { 0.0 },
+            };
+            (*user_id, stats)
+        })
+        .collect())
+}
@@ -0,0 +1,476 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::services::notification_delivery::{deliver_to_enabled_channels, ChannelNotification};
+
+/// Maximum number of league movers listed in a digest
+const MAX_LEAGUE_MOVERS: usize = 3;
+
+/// Service that composes the weekly digest (own stats, team result, league movers, next fixture)
+/// for every user and delivers it through the notification channels the user enabled.
+#[derive(Debug)]
+pub struct WeeklyDigestService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WeeklyDigestRunSummary {
+    pub week_start: NaiveDate,
+    pub standings_snapshotted: u64,
+    pub digests_created: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DigestStats {
+    pub workouts: i64,
+    pub active_minutes: i64,
+    pub stamina_gained: f64,
+    pub strength_gained: f64,
+    pub points_scored: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DigestTeamResult {
+    pub game_id: Uuid,
+    pub opponent_team_name: String,
+    pub team_score: i32,
+    pub opponent_score: i32,
+    /// "win", "loss" or "draw"
+    pub outcome: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DigestLeagueMover {
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub previous_position: i32,
+    pub position: i32,
+    /// Places gained (positive) or lost (negative) since the previous digest
+    pub change: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DigestFixture {
+    pub game_id: Uuid,
+    pub opponent_team_name: String,
+    pub is_home: bool,
+    pub week_number: i32,
+    pub game_start_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WeeklyDigest {
+    pub week_start: NaiveDate,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub stats: DigestStats,
+    pub team_id: Option<Uuid>,
+    pub team_name: Option<String>,
+    pub team_result: Option<DigestTeamResult>,
+    pub league_movers: Vec<DigestLeagueMover>,
+    pub next_fixture: Option<DigestFixture>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WeeklyDigestRecord {
+    pub id: Uuid,
+    pub week_start: NaiveDate,
+    pub content: serde_json::Value,
+    pub delivered_channels: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WeeklyDigest {
+    /// One-line summary used as the notification text
+    pub fn summary_line(&self) -> String {
+        let mut parts = vec![format!(
+            "{} workout{}, {} active minutes",
+            self.stats.workouts,
+            if self.stats.workouts == 1 { "" } else { "s" },
+            self.stats.active_minutes
+        )];
+
+        if let (Some(team_name), Some(result)) = (&self.team_name, &self.team_result) {
+            let verb = match result.outcome.as_str() {
+                "win" => "beat",
+                "loss" => "lost to",
+                _ => "drew with",
+            };
+            parts.push(format!(
+                "{} {} {} {}-{}",
+                team_name, verb, result.opponent_team_name, result.team_score, result.opponent_score
+            ));
+        }
+
+        if let Some(fixture) = &self.next_fixture {
+            parts.push(format!("Next up: {}", fixture.opponent_team_name));
+        }
+
+        format!("Your week: {}.", parts.join(". "))
+    }
+}
+
+/// Monday of the week containing `date`
+pub fn week_start_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+impl WeeklyDigestService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Snapshot standings and generate this week's digest for every user who has none yet
+    pub async fn run_weekly(&self) -> Result<WeeklyDigestRunSummary, sqlx::Error> {
+        let week_start = week_start_of(Utc::now().date_naive());
+        let standings_snapshotted = self.snapshot_standings(week_start).await?;
+
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT u.id
+            FROM users u
+            LEFT JOIN notification_preferences np ON np.user_id = u.id
+            LEFT JOIN weekly_digests wd ON wd.user_id = u.id AND wd.week_start = $1
+            WHERE u.status = 'active'
+            AND COALESCE(np.weekly_digest_enabled, TRUE)
+            AND wd.id IS NULL
+            "#,
+            week_start
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut digests_created = 0;
+        for user_id in user_ids {
+            match self.generate_for_user(user_id, week_start).await {
+                Ok(Some(_)) => digests_created += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("Failed to generate weekly digest for user {}: {}", user_id, e);
+                }
+            }
+        }
+
+        Ok(WeeklyDigestRunSummary { week_start, standings_snapshotted, digests_created })
+    }
+
+    /// Copy the current standings of running seasons so next week's digest can show movers
+    async fn snapshot_standings(&self, week_start: NaiveDate) -> Result<u64, sqlx::Error> {
+        let period_start = week_start - Duration::days(7);
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO league_standing_snapshots (season_id, team_id, week_start, position, points)
+            SELECT ls.season_id, ls.team_id, $1, ls.position, COALESCE(ls.points, 0)
+            FROM league_standings ls
+            JOIN league_seasons s ON s.id = ls.season_id
+            WHERE s.start_date <= NOW() AND s.end_date >= $2::date
+            ON CONFLICT (season_id, team_id, week_start)
+            DO UPDATE SET position = EXCLUDED.position, points = EXCLUDED.points
+            "#,
+            week_start,
+            period_start
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Compose and deliver the digest for one user. Returns `None` if the user opted out
+    /// or already has a digest for this week.
+    pub async fn generate_for_user(
+        &self,
+        user_id: Uuid,
+        week_start: NaiveDate,
+    ) -> Result<Option<WeeklyDigestRecord>, sqlx::Error> {
+        let digest_enabled = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(np.weekly_digest_enabled, TRUE) as "enabled!"
+            FROM users u
+            LEFT JOIN notification_preferences np ON np.user_id = u.id
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if digest_enabled != Some(true) {
+            return Ok(None);
+        }
+
+        let digest = self.compose_digest(user_id, week_start).await?;
+        let content = json!(digest);
+
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO weekly_digests (user_id, week_start, content)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, week_start) DO NOTHING
+            RETURNING id, created_at
+            "#,
+            user_id,
+            week_start,
+            content
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(inserted) = inserted else {
+            return Ok(None);
+        };
+
+        let delivered_channels = deliver_to_enabled_channels(&self.pool, &ChannelNotification {
+            recipient_id: user_id,
+            actor_id: user_id,
+            notification_type: "weekly_digest".to_string(),
+            entity_type: "weekly_digest".to_string(),
+            entity_id: inserted.id,
+            title: "Your weekly digest".to_string(),
+            message: digest.summary_line(),
+            push_category: "league_update".to_string(),
+        }).await?;
+
+        sqlx::query!(
+            "UPDATE weekly_digests SET delivered_channels = $2 WHERE id = $1",
+            inserted.id,
+            &delivered_channels
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(Some(WeeklyDigestRecord {
+            id: inserted.id,
+            week_start,
+            content,
+            delivered_channels,
+            created_at: inserted.created_at,
+        }))
+    }
+
+    /// Build the digest covering the seven days before `week_start`
+    pub async fn compose_digest(&self, user_id: Uuid, week_start: NaiveDate) -> Result<WeeklyDigest, sqlx::Error> {
+        let period_end = week_start.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc();
+        let period_start = period_end - Duration::days(7);
+
+        let stats = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) as "workouts!",
+                COALESCE(SUM(duration_minutes), 0)::bigint as "active_minutes!",
+                COALESCE(SUM(stamina_gained), 0)::float8 as "stamina_gained!",
+                COALESCE(SUM(strength_gained), 0)::float8 as "strength_gained!"
+            FROM workout_data
+            WHERE user_id = $1 AND workout_start >= $2 AND workout_start < $3
+            "#,
+            user_id,
+            period_start,
+            period_end
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let points_scored = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(score_points), 0)::float8 as "points!"
+            FROM live_score_events
+            WHERE user_id = $1 AND occurred_at >= $2 AND occurred_at < $3
+            "#,
+            user_id,
+            period_start,
+            period_end
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let mut digest = WeeklyDigest {
+            week_start,
+            period_start,
+            period_end,
+            stats: DigestStats {
+                workouts: stats.workouts,
+                active_minutes: stats.active_minutes,
+                stamina_gained: stats.stamina_gained,
+                strength_gained: stats.strength_gained,
+                points_scored,
+            },
+            team_id: None,
+            team_name: None,
+            team_result: None,
+            league_movers: Vec::new(),
+            next_fixture: None,
+        };
+
+        let team = sqlx::query!(
+            r#"
+            SELECT t.id, t.team_name, t.league_id
+            FROM team_members tm
+            JOIN teams t ON t.id = tm.team_id
+            WHERE tm.user_id = $1 AND tm.status = 'active'
+            ORDER BY tm.joined_at DESC
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(team) = team else {
+            return Ok(digest);
+        };
+        digest.team_id = Some(team.id);
+        digest.team_name = Some(team.team_name);
+
+        digest.team_result = sqlx::query!(
+            r#"
+            SELECT
+                g.id,
+                g.home_team_id,
+                g.home_score,
+                g.away_score,
+                opp.team_name as opponent_team_name
+            FROM games g
+            JOIN teams opp ON opp.id = CASE WHEN g.home_team_id = $1 THEN g.away_team_id ELSE g.home_team_id END
+            WHERE (g.home_team_id = $1 OR g.away_team_id = $1)
+            AND g.status IN ('finished', 'evaluated')
+            AND g.game_end_time >= $2 AND g.game_end_time < $3
+            ORDER BY g.game_end_time DESC
+            LIMIT 1
+            "#,
+            team.id,
+            period_start,
+            period_end
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|g| {
+            let (team_score, opponent_score) = if g.home_team_id == team.id {
+                (g.home_score, g.away_score)
+            } else {
+                (g.away_score, g.home_score)
+            };
+            let outcome = match team_score.cmp(&opponent_score) {
+                std::cmp::Ordering::Greater => "win",
+                std::cmp::Ordering::Less => "loss",
+                std::cmp::Ordering::Equal => "draw",
+            };
+            DigestTeamResult {
+                game_id: g.id,
+                opponent_team_name: g.opponent_team_name,
+                team_score,
+                opponent_score,
+                outcome: outcome.to_string(),
+            }
+        });
+
+        digest.next_fixture = sqlx::query!(
+            r#"
+            SELECT
+                g.id,
+                g.home_team_id,
+                g.week_number,
+                g.game_start_time,
+                opp.team_name as opponent_team_name
+            FROM games g
+            JOIN teams opp ON opp.id = CASE WHEN g.home_team_id = $1 THEN g.away_team_id ELSE g.home_team_id END
+            WHERE (g.home_team_id = $1 OR g.away_team_id = $1)
+            AND g.status = 'scheduled'
+            ORDER BY g.game_start_time NULLS LAST, g.week_number
+            LIMIT 1
+            "#,
+            team.id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|g| DigestFixture {
+            game_id: g.id,
+            opponent_team_name: g.opponent_team_name,
+            is_home: g.home_team_id == team.id,
+            week_number: g.week_number,
+            game_start_time: g.game_start_time,
+        });
+
+        // Movers compare live standings with the latest snapshot from an earlier digest week
+        if let Some(league_id) = team.league_id {
+            let movers = sqlx::query!(
+                r#"
+                WITH current_season AS (
+                    SELECT id FROM league_seasons
+                    WHERE league_id = $1 AND end_date >= $2
+                    ORDER BY start_date
+                    LIMIT 1
+                ),
+                previous AS (
+                    SELECT DISTINCT ON (lss.team_id) lss.team_id, lss.position
+                    FROM league_standing_snapshots lss
+                    WHERE lss.season_id = (SELECT id FROM current_season)
+                    AND lss.week_start < $3
+                    ORDER BY lss.team_id, lss.week_start DESC
+                )
+                SELECT
+                    ls.team_id,
+                    t.team_name,
+                    p.position as previous_position,
+                    ls.position
+                FROM league_standings ls
+                JOIN previous p ON p.team_id = ls.team_id
+                JOIN teams t ON t.id = ls.team_id
+                WHERE ls.season_id = (SELECT id FROM current_season)
+                AND ls.position <> p.position
+                ORDER BY ABS(ls.position - p.position) DESC, ls.position
+                "#,
+                league_id,
+                period_start,
+                week_start
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+            digest.league_movers = movers
+                .into_iter()
+                .take(MAX_LEAGUE_MOVERS)
+                .map(|m| DigestLeagueMover {
+                    team_id: m.team_id,
+                    team_name: m.team_name,
+                    previous_position: m.previous_position,
+                    position: m.position,
+                    change: m.previous_position - m.position,
+                })
+                .collect();
+        }
+
+        Ok(digest)
+    }
+
+    /// Get a user's most recent digests, newest first
+    pub async fn get_recent_digests(&self, user_id: Uuid, limit: i64) -> Result<Vec<WeeklyDigestRecord>, sqlx::Error> {
+        let digests = sqlx::query!(
+            r#"
+            SELECT id, week_start, content, delivered_channels, created_at
+            FROM weekly_digests
+            WHERE user_id = $1
+            ORDER BY week_start DESC
+            LIMIT $2
+            "#,
+            user_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|d| WeeklyDigestRecord {
+            id: d.id,
+            week_start: d.week_start,
+            content: d.content,
+            delivered_channels: d.delivered_channels,
+            created_at: d.created_at,
+        })
+        .collect();
+
+        Ok(digests)
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::health_data::get_user_health_profile_details;
+use crate::game::stats_calculator::WorkoutStatsCalculator;
+use crate::models::workout_data::{HeartRateData, WorkoutType};
+
+const RECALCULATION_BATCH_SIZE: i64 = 100;
+
+/// Recalculates the zone distribution of past workouts with the user's current max HR and
+/// VT thresholds. Only the recalculated view is written; the original zones and the stats
+/// the workout was scored with stay untouched.
+pub struct ZoneRecalculationService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ZoneRecalculationSummary {
+    pub workouts_recalculated: usize,
+    pub workouts_skipped: usize,
+    pub max_heart_rate: i32,
+    pub resting_heart_rate: i32,
+}
+
+impl ZoneRecalculationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Recalculate zones for the user's workouts that started within `from..=to`.
+    /// Workouts without usable heart rate samples are skipped.
+    pub async fn recalculate_for_user(
+        &self,
+        user_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<ZoneRecalculationSummary, sqlx::Error> {
+        let profile = get_user_health_profile_details(&self.pool, user_id).await?;
+        let calculator = WorkoutStatsCalculator::with_universal_hr_based();
+        let mut summary = ZoneRecalculationSummary {
+            max_heart_rate: profile.max_heart_rate,
+            resting_heart_rate: profile.resting_heart_rate,
+            ..Default::default()
+        };
+
+        let mut cursor: Option<(DateTime<Utc>, Uuid)> = None;
+        loop {
+            let workouts = sqlx::query!(
+                r#"
+                SELECT id, workout_start, heart_rate_data
+                FROM workout_data
+                WHERE user_id = $1
+                AND ($2::timestamptz IS NULL OR workout_start >= $2)
+                AND ($3::timestamptz IS NULL OR workout_start <= $3)
+                AND ($4::timestamptz IS NULL OR (workout_start, id) > ($4, $5))
+                ORDER BY workout_start, id
+                LIMIT $6
+                "#,
+                user_id,
+                from,
+                to,
+                cursor.map(|(start, _)| start),
+                cursor.map(|(_, id)| id),
+                RECALCULATION_BATCH_SIZE
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+            for workout in &workouts {
+                let hr_data: Vec<HeartRateData> = serde_json::from_value(workout.heart_rate_data.clone())
+                    .unwrap_or_default();
+
+                // The workout type only scales the totals, not the zone minutes
+                let zone_breakdown = match calculator.calculate_stat_changes(profile.clone(), hr_data, WorkoutType::Other).await {
+                    Ok(stats) => match stats.zone_breakdown {
+                        Some(zone_breakdown) => zone_breakdown,
+                        None => {
+                            summary.workouts_skipped += 1;
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        tracing::warn!("⚠️ Could not recalculate zones for workout {}: {}", workout.id, e);
+                        summary.workouts_skipped += 1;
+                        continue;
+                    }
+                };
+
+                sqlx::query!(
+                    r#"
+                    UPDATE workout_data
+                    SET recalculated_heart_rate_zones = $2,
+                        zones_recalculated_at = NOW(),
+                        zones_recalculated_max_heart_rate = $3,
+                        zones_recalculated_resting_heart_rate = $4
+                    WHERE id = $1
+                    "#,
+                    workout.id,
+                    serde_json::to_value(&zone_breakdown).unwrap_or_default(),
+                    profile.max_heart_rate,
+                    profile.resting_heart_rate
+                )
+                .execute(&self.pool)
+                .await?;
+                summary.workouts_recalculated += 1;
+            }
+
+            match workouts.last() {
+                Some(last) if (workouts.len() as i64) == RECALCULATION_BATCH_SIZE => {
+                    cursor = Some((last.workout_start, last.id));
+                }
+                _ => break,
+            }
+        }
+
+        tracing::info!(
+            "✅ Recalculated zones for {} workouts of user {} ({} skipped)",
+            summary.workouts_recalculated, user_id, summary.workouts_skipped
+        );
+        Ok(summary)
+    }
+}
@@ -0,0 +1,145 @@
+use std::io::Cursor;
+
+use image::{ImageOutputFormat, Rgb, RgbImage};
+
+use crate::models::share_card::ShareCardContent;
+
+/// Cards are 1200x630, the size social networks use for link previews
+pub const CARD_WIDTH: u32 = 1200;
+pub const CARD_HEIGHT: u32 = 630;
+
+const MARGIN: u32 = 60;
+/// Lines beyond this don't fit below the title
+pub const MAX_CARD_LINES: usize = 4;
+
+const BACKGROUND: Rgb<u8> = Rgb([0x10, 0x18, 0x28]);
+const MUTED: Rgb<u8> = Rgb([0x98, 0xA2, 0xB3]);
+const TEXT: Rgb<u8> = Rgb([0xFF, 0xFF, 0xFF]);
+const ACCENT: Rgb<u8> = Rgb([0xF9, 0x73, 0x16]);
+
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+/// 5x7 bitmap glyphs; text is drawn upper case and unknown characters as '?'
+fn glyph(c: char) -> [&'static str; 7] {
+    match c {
+        'A' => [".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#"],
+        'B' => ["####.", "#...#", "#...#", "####.", "#...#", "#...#", "####."],
+        'C' => [".###.", "#...#", "#....", "#....", "#....", "#...#", ".###."],
+        'D' => ["####.", "#...#", "#...#", "#...#", "#...#", "#...#", "####."],
+        'E' => ["#####", "#....", "#....", "####.", "#....", "#....", "#####"],
+        'F' => ["#####", "#....", "#....", "####.", "#....", "#....", "#...."],
+        'G' => [".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".####"],
+        'H' => ["#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#"],
+        'I' => [".###.", "..#..", "..#..", "..#..", "..#..", "..#..", ".###."],
+        'J' => ["..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##.."],
+        'K' => ["#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#"],
+        'L' => ["#....", "#....", "#....", "#....", "#....", "#....", "#####"],
+        'M' => ["#...#", "##.##", "#.#.#", "#.#.#", "#...#", "#...#", "#...#"],
+        'N' => ["#...#", "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#"],
+        'O' => [".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###."],
+        'P' => ["####.", "#...#", "#...#", "####.", "#....", "#....", "#...."],
+        'Q' => [".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#"],
+        'R' => ["####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#"],
+        'S' => [".####", "#....", "#....", ".###.", "....#", "....#", "####."],
+        'T' => ["#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#.."],
+        'U' => ["#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###."],
+        'V' => ["#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#.."],
+        'W' => ["#...#", "#...#", "#...#", "#.#.#", "#.#.#", "#.#.#", ".#.#."],
+        'X' => ["#...#", "#...#", ".#.#.", "..#..", ".#.#.", "#...#", "#...#"],
+        'Y' => ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#.."],
+        'Z' => ["#####", "....#", "...#.", "..#..", ".#...", "#....", "#####"],
+        '0' => [".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###."],
+        '1' => ["..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###."],
+        '2' => [".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####"],
+        '3' => ["####.", "....#", "....#", ".###.", "....#", "....#", "####."],
+        '4' => ["...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#."],
+        '5' => ["#####", "#....", "####.", "....#", "....#", "#...#", ".###."],
+        '6' => ["..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###."],
+        '7' => ["#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#..."],
+        '8' => [".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###."],
+        '9' => [".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##.."],
+        ' ' => [".....", ".....", ".....", ".....", ".....", ".....", "....."],
+        '.' => [".....", ".....", ".....", ".....", ".....", ".##..", ".##.."],
+        ',' => [".....", ".....", ".....", ".....", ".##..", "..#..", ".#..."],
+        ':' => [".....", ".##..", ".##..", ".....", ".##..", ".##..", "....."],
+        '!' => ["..#..", "..#..", "..#..", "..#..", "..#..", ".....", "..#.."],
+        '-' => [".....", ".....", ".....", ".###.", ".....", ".....", "....."],
+        '+' => [".....", "..#..", "..#..", "#####", "..#..", "..#..", "....."],
+        '%' => ["##..#", "##..#", "...#.", "..#..", ".#...", "#..##", "#..##"],
+        '/' => ["....#", "....#", "...#.", "..#..", ".#...", "#....", "#...."],
+        '\'' => ["..#..", "..#..", ".#...", ".....", ".....", ".....", "....."],
+        '"' => [".#.#.", ".#.#.", ".....", ".....", ".....", ".....", "....."],
+        '#' => [".#.#.", ".#.#.", "#####", ".#.#.", "#####", ".#.#.", ".#.#."],
+        '&' => [".##..", "#..#.", "#.#..", ".#...", "#.#.#", "#..#.", ".##.#"],
+        '(' => ["...#.", "..#..", ".#...", ".#...", ".#...", "..#..", "...#."],
+        ')' => [".#...", "..#..", "...#.", "...#.", "...#.", "..#..", ".#..."],
+        '_' => [".....", ".....", ".....", ".....", ".....", ".....", "#####"],
+        _ => [".###.", "#...#", "....#", "...#.", "..#..", ".....", "..#.."],
+    }
+}
+
+/// Width in pixels of `chars` characters at `scale`, one blank column between characters
+fn text_width(chars: u32, scale: u32) -> u32 {
+    if chars == 0 {
+        return 0;
+    }
+    chars * (GLYPH_WIDTH + 1) * scale - scale
+}
+
+/// Shorten text to at most `max_chars` characters, ending in "..." when cut
+pub fn fit_text(text: &str, max_chars: usize) -> String {
+    let text: Vec<char> = text.trim().chars().collect();
+    if text.len() <= max_chars {
+        return text.into_iter().collect();
+    }
+    let mut fitted: String = text[..max_chars.saturating_sub(3)].iter().collect();
+    fitted.push_str("...");
+    fitted
+}
+
+fn draw_text(image: &mut RgbImage, text: &str, x: u32, y: u32, scale: u32, color: Rgb<u8>) {
+    let max_chars = ((CARD_WIDTH - MARGIN - x) / ((GLYPH_WIDTH + 1) * scale)) as usize;
+    for (i, c) in fit_text(text, max_chars).chars().enumerate() {
+        let left = x + i as u32 * (GLYPH_WIDTH + 1) * scale;
+        for (row, pattern) in glyph(c.to_ascii_uppercase()).iter().enumerate() {
+            for (col, pixel) in pattern.chars().enumerate() {
+                if pixel != '#' {
+                    continue;
+                }
+                fill_rect(image, left + col as u32 * scale, y + row as u32 * scale, scale, scale, color);
+            }
+        }
+    }
+}
+
+fn fill_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// Render a share card as a PNG
+pub fn render_share_card_png(content: &ShareCardContent) -> Result<Vec<u8>, image::ImageError> {
+    let mut image = RgbImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, BACKGROUND);
+    fill_rect(&mut image, 0, 0, 16, CARD_HEIGHT, ACCENT);
+
+    draw_text(&mut image, &content.eyebrow, MARGIN, MARGIN, 4, MUTED);
+    draw_text(&mut image, &content.title, MARGIN, 110, 8, TEXT);
+
+    for (i, line) in content.lines.iter().take(MAX_CARD_LINES).enumerate() {
+        let y = 220 + i as u32 * 90;
+        draw_text(&mut image, &line.label, MARGIN, y, 3, MUTED);
+        draw_text(&mut image, &line.value, MARGIN, y + 32, 5, TEXT);
+    }
+
+    let brand = "RIINA";
+    let brand_x = CARD_WIDTH - MARGIN - text_width(brand.len() as u32, 4);
+    draw_text(&mut image, brand, brand_x, CARD_HEIGHT - MARGIN - GLYPH_HEIGHT * 4, 4, ACCENT);
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)?;
+    Ok(png)
+}
@@ -0,0 +1,87 @@
+use std::net::{IpAddr, SocketAddr};
+
+use actix_web::HttpRequest;
+
+/// A proxy, or network of proxies, whose `X-Forwarded-For` entries are believed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedProxy {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl TrustedProxy {
+    /// Parse an address (`10.0.0.1`) or a network in CIDR notation (`fdaa::/16`)
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (address, prefix_len) = match value.trim().split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (value.trim(), None),
+        };
+        let network: IpAddr = address.parse().map_err(|_| format!("Invalid trusted proxy address '{value}'"))?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|len| *len <= max_len)
+                .ok_or_else(|| format!("Invalid prefix length in trusted proxy '{value}'"))?,
+            None => max_len,
+        };
+        Ok(Self { network, prefix_len })
+    }
+
+    pub fn contains(&self, address: IpAddr) -> bool {
+        match (self.network, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                prefix_matches(u32::from(network).into(), u32::from(address).into(), 32, self.prefix_len)
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                prefix_matches(u128::from(network), u128::from(address), 128, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: u128, address: u128, bits: u8, prefix_len: u8) -> bool {
+    let host_bits = u32::from(bits - prefix_len);
+    network.checked_shr(host_bits).unwrap_or(0) == address.checked_shr(host_bits).unwrap_or(0)
+}
+
+/// The client behind a connection. `X-Forwarded-For` is only believed when the connection comes
+/// from a trusted proxy, and then read from the right, so entries the client put in the header
+/// itself are never taken for its address.
+pub fn resolve_client_address(
+    peer: Option<IpAddr>,
+    forwarded_for: Option<&str>,
+    trusted_proxies: &[TrustedProxy],
+) -> Option<IpAddr> {
+    let peer = peer?;
+    let is_trusted = |address: IpAddr| trusted_proxies.iter().any(|proxy| proxy.contains(address));
+    if !is_trusted(peer) {
+        return Some(peer);
+    }
+
+    let mut client = peer;
+    for entry in forwarded_for.unwrap_or_default().rsplit(',') {
+        let entry = entry.trim();
+        let Some(address) = entry
+            .parse::<IpAddr>()
+            .ok()
+            .or_else(|| entry.parse::<SocketAddr>().ok().map(|socket| socket.ip()))
+        else {
+            break;
+        };
+        client = address;
+        if !is_trusted(address) {
+            break;
+        }
+    }
+    Some(client)
+}
+
+/// Address of the client that sent the request, see [`resolve_client_address`]
+pub fn client_address(req: &HttpRequest, trusted_proxies: &[TrustedProxy]) -> Option<String> {
+    let forwarded_for = req.headers().get("X-Forwarded-For").and_then(|value| value.to_str().ok());
+    resolve_client_address(req.peer_addr().map(|peer| peer.ip()), forwarded_for, trusted_proxies)
+        .map(|address| address.to_string())
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+/// A CSV data row, with its fields by lowercased header
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    /// Line the row starts on, counting the header as line 1
+    pub line: usize,
+    fields: HashMap<String, String>,
+}
+
+impl CsvRecord {
+    /// The trimmed field, None when missing or empty
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.fields.get(column).map(|value| value.trim()).filter(|value| !value.is_empty())
+    }
+}
+
+/// Parse RFC 4180 CSV with a header row: comma separated, fields optionally double-quoted, quotes
+/// inside quoted fields doubled. Blank lines are skipped.
+pub fn parse_csv(text: &str) -> Result<Vec<CsvRecord>, String> {
+    let mut rows = parse_rows(text.strip_prefix('\u{feff}').unwrap_or(text))?.into_iter();
+    let Some((_, headers)) = rows.next() else {
+        return Err("CSV is empty".to_string());
+    };
+    let headers: Vec<String> = headers.iter().map(|h| h.trim().to_lowercase()).collect();
+
+    rows.map(|(line, values)| {
+        if values.len() != headers.len() {
+            return Err(format!("Line {line} has {} fields, the header has {}", values.len(), headers.len()));
+        }
+        Ok(CsvRecord { line, fields: headers.iter().cloned().zip(values).collect() })
+    })
+    .collect()
+}
+
+fn parse_rows(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut row_line) = (1, 1);
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                if !(row.len() == 1 && row[0].trim().is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                }
+                row.clear();
+                line += 1;
+                row_line = line;
+            }
+            (c, _) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if in_quotes {
+        return Err(format!("Unterminated quoted field starting on line {row_line}"));
+    }
+    row.push(field);
+    if !(row.len() == 1 && row[0].trim().is_empty()) {
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
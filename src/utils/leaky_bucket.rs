@@ -0,0 +1,36 @@
+use std::time::{Duration, Instant};
+
+/// Leaky-bucket limiter: each accepted message fills the bucket by one, which drains at a
+/// steady rate. Bursts up to the capacity pass, sustained traffic above the rate doesn't.
+#[derive(Debug, Clone)]
+pub struct LeakyBucket {
+    capacity: f64,
+    leak_per_sec: f64,
+    level: f64,
+    last_leak: Instant,
+}
+
+impl LeakyBucket {
+    pub fn new(capacity: u32, leak_per_sec: f64, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            leak_per_sec,
+            level: 0.0,
+            last_leak: now,
+        }
+    }
+
+    /// Take one message. When the bucket is full, returns how long until there is room.
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_leak).as_secs_f64();
+        self.level = (self.level - elapsed * self.leak_per_sec).max(0.0);
+        self.last_leak = now;
+
+        if self.level + 1.0 <= self.capacity {
+            self.level += 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((self.level + 1.0 - self.capacity) / self.leak_per_sec))
+        }
+    }
+}
@@ -4,4 +4,11 @@ pub mod workout_approval;
 pub mod health_calculations;
 pub mod trailing_average;
 pub mod heart_rate_filters;
-pub mod mention_parser;
\ No newline at end of file
+pub mod mention_parser;
+pub mod perceptual_hash;
+pub mod card_image;
+pub mod quiet_hours;
+pub mod leaky_bucket;
+pub mod csv_reader;
+pub mod opaque_token;
+pub mod client_ip;
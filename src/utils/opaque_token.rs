@@ -0,0 +1,18 @@
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+
+const TOKEN_LENGTH: usize = 64;
+
+/// New random token for refresh tokens and password reset links, handed out once
+pub fn generate_opaque_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Hash a token is stored and looked up by; the token itself is never stored
+pub fn hash_opaque_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
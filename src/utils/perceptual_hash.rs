@@ -0,0 +1,91 @@
+use image::imageops::FilterType;
+
+/// Side of the grayscale thumbnail the DCT runs on
+const HASH_IMAGE_SIZE: usize = 32;
+/// Side of the low-frequency block that makes up the hash
+const HASH_BLOCK_SIZE: usize = 8;
+
+/// Hashes closer than this many bits are treated as the same picture
+/// (re-encoded, resized, slightly cropped or recolored)
+pub const SIMILARITY_THRESHOLD: u32 = 10;
+
+/// 64-bit perceptual hash (pHash) of an encoded image, or `None` if it cannot be decoded.
+///
+/// The image is reduced to a 32x32 grayscale thumbnail and transformed with a 2D DCT;
+/// each bit of the hash says whether one of the 8x8 lowest frequencies lies above their
+/// median. Unlike a cryptographic hash it barely changes when the image is re-encoded.
+pub fn perceptual_hash(bytes: &[u8]) -> Option<i64> {
+    let image = image::load_from_memory(bytes).ok()?;
+    let thumbnail = image
+        .resize_exact(HASH_IMAGE_SIZE as u32, HASH_IMAGE_SIZE as u32, FilterType::Triangle)
+        .to_luma8();
+
+    let pixels: Vec<f64> = thumbnail.pixels().map(|p| p.0[0] as f64).collect();
+    let coefficients = dct_low_frequencies(&pixels);
+
+    // Skip the DC term, it only carries the average brightness
+    let mut sorted: Vec<f64> = coefficients[1..].to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted[sorted.len() / 2];
+
+    let hash = coefficients
+        .iter()
+        .enumerate()
+        .fold(0u64, |hash, (i, &c)| if c > median { hash | (1 << i) } else { hash });
+    Some(hash as i64)
+}
+
+/// Number of differing bits between two hashes
+pub fn hamming_distance(a: i64, b: i64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Number of 16-bit bands a hash is indexed by
+pub const HASH_BANDS: usize = 4;
+/// Hashes within `SIMILARITY_THRESHOLD` bits differ in at most this many bits of some band
+const BAND_RADIUS: u32 = SIMILARITY_THRESHOLD / HASH_BANDS as u32;
+
+/// The hash's 16-bit bands, lowest bits first, as stored in the blocklist's band columns
+pub fn hash_bands(hash: i64) -> [i32; HASH_BANDS] {
+    std::array::from_fn(|i| ((hash >> (16 * i)) & 0xFFFF) as i32)
+}
+
+/// For each band of the hash, the band values within `BAND_RADIUS` bits of it.
+/// Every hash within `SIMILARITY_THRESHOLD` bits has at least one band among them.
+pub fn similar_band_values(hash: i64) -> [Vec<i32>; HASH_BANDS] {
+    hash_bands(hash).map(|band| {
+        (0..=u16::MAX as i32)
+            .filter(|flipped| flipped.count_ones() <= BAND_RADIUS)
+            .map(|flipped| band ^ flipped)
+            .collect()
+    })
+}
+
+/// The 8x8 lowest-frequency coefficients of the 2D DCT-II of a 32x32 image, row-major
+fn dct_low_frequencies(pixels: &[f64]) -> Vec<f64> {
+    let n = HASH_IMAGE_SIZE;
+    let cosines: Vec<Vec<f64>> = (0..HASH_BLOCK_SIZE)
+        .map(|k| {
+            (0..n)
+                .map(|x| (std::f64::consts::PI * (2 * x + 1) as f64 * k as f64 / (2 * n) as f64).cos())
+                .collect()
+        })
+        .collect();
+
+    // Rows first, then columns, only for the frequencies we keep
+    let rows: Vec<Vec<f64>> = (0..n)
+        .map(|y| {
+            (0..HASH_BLOCK_SIZE)
+                .map(|u| (0..n).map(|x| pixels[y * n + x] * cosines[u][x]).sum())
+                .collect()
+        })
+        .collect();
+
+    let mut coefficients = Vec::with_capacity(HASH_BLOCK_SIZE * HASH_BLOCK_SIZE);
+    for cosine in &cosines {
+        for u in 0..HASH_BLOCK_SIZE {
+            coefficients.push(rows.iter().zip(cosine).map(|(row, c)| row[u] * c).sum());
+        }
+    }
+    coefficients
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Notification types pushed even during quiet hours: alerts that need acting on right away
+pub const CRITICAL_NOTIFICATION_TYPES: [&str; 2] = ["admin_broadcast", "unfinished_game"];
+
+/// A user's daily do-not-disturb window in their timezone. A start later than the end spans
+/// midnight, e.g. 22:00 to 07:00.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub timezone: Tz,
+}
+
+impl QuietHours {
+    /// From "HH:MM" times and an IANA timezone name
+    pub fn parse(start: &str, end: &str, timezone: &str) -> Result<Self, String> {
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| format!("Invalid time {time}. Use HH:MM, e.g. 22:00"))
+        };
+        let (start, end) = (parse_time(start)?, parse_time(end)?);
+        if start == end {
+            return Err("Quiet hours must start and end at different times".to_string());
+        }
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|_| format!("Unknown timezone {timezone}. Use an IANA name like Europe/Berlin"))?;
+        Ok(Self { start, end, timezone })
+    }
+
+    /// When the window `now` falls in ends, or None outside quiet hours
+    pub fn window_end(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local = now.with_timezone(&self.timezone);
+        let (today, time) = (local.date_naive(), local.time());
+
+        let end_date = if self.start < self.end {
+            (self.start <= time && time < self.end).then_some(today)
+        } else if time >= self.start {
+            today.succ_opt()
+        } else if time < self.end {
+            Some(today)
+        } else {
+            None
+        }?;
+        Some(self.at(end_date, self.end))
+    }
+
+    /// `time` on `date` in the user's timezone; a time skipped by a DST change is taken an hour later
+    fn at(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        let local = date.and_time(time);
+        self.timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| self.timezone.from_local_datetime(&(local + Duration::hours(1))).earliest())
+            .map(|at| at.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+    }
+}
+
+/// Whether a notification of this type is pushed regardless of quiet hours
+pub fn is_critical(notification_type: Option<&str>) -> bool {
+    notification_type.is_some_and(|t| CRITICAL_NOTIFICATION_TYPES.contains(&t))
+}
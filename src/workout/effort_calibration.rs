@@ -0,0 +1,61 @@
+use crate::models::workout_data::WorkoutStats;
+
+/// The largest change calibration makes to a user's points, either way
+pub const MAX_ADJUSTMENT: f32 = 0.15;
+/// Rated workouts needed before a user is calibrated at all
+pub const MIN_RATED_WORKOUTS: usize = 5;
+/// Ratings are trusted more as they add up: with this many the adjustment reaches half its full size
+pub const CONFIDENCE_RATINGS: f32 = 5.0;
+/// Points per minute of a workout spent entirely in the hard zone, rated 10
+pub const FULL_EFFORT_POINTS_PER_MINUTE: f32 = 8.0;
+/// How many days of effort ratings the weekly calibration looks at
+pub const CALIBRATION_WINDOW_DAYS: i64 = 90;
+
+/// A workout the user rated in their scoring feedback
+#[derive(Debug, Clone, Copy)]
+pub struct RatedWorkout {
+    /// 0-10, how hard the workout felt
+    pub effort_rating: i16,
+    /// Points per minute the workout scored before calibration
+    pub points_per_minute: f32,
+}
+
+/// The effort rating, 0-10, that the heart rate scoring implies for a workout
+pub fn implied_effort(points_per_minute: f32) -> f32 {
+    (points_per_minute / FULL_EFFORT_POINTS_PER_MINUTE * 10.0).clamp(0.0, 10.0)
+}
+
+/// Average of how much harder (positive) or easier (negative) the workouts felt than the
+/// heart rate scoring implies, on the 0-10 scale
+pub fn mean_effort_gap(ratings: &[RatedWorkout]) -> Option<f32> {
+    if ratings.is_empty() {
+        return None;
+    }
+    let total: f32 = ratings
+        .iter()
+        .map(|r| r.effort_rating as f32 - implied_effort(r.points_per_minute))
+        .sum();
+    Some(total / ratings.len() as f32)
+}
+
+/// Personal scoring factor from a user's effort ratings. Workouts that felt harder than their
+/// heart rate suggests raise it, easier ones lower it, by at most `MAX_ADJUSTMENT` and
+/// only partly while there are few ratings.
+pub fn calibration_factor(ratings: &[RatedWorkout]) -> f32 {
+    let Some(mean_gap) = mean_effort_gap(ratings).filter(|_| ratings.len() >= MIN_RATED_WORKOUTS) else {
+        return 1.0;
+    };
+    let confidence = ratings.len() as f32 / (ratings.len() as f32 + CONFIDENCE_RATINGS);
+    1.0 + (mean_gap / 10.0).clamp(-MAX_ADJUSTMENT, MAX_ADJUSTMENT) * confidence
+}
+
+/// Weight every zone of a scored workout by the user's calibration factor
+pub fn apply_calibration(stats: &mut WorkoutStats, factor: f32) {
+    let factor = factor.clamp(1.0 - MAX_ADJUSTMENT, 1.0 + MAX_ADJUSTMENT);
+    stats.changes.stamina_change *= factor;
+    stats.changes.strength_change *= factor;
+    for zone in stats.zone_breakdown.iter_mut().flatten() {
+        zone.stamina_gained *= factor;
+        zone.strength_gained *= factor;
+    }
+}
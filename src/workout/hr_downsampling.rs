@@ -0,0 +1,77 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::workout_data::HeartRateData;
+
+/// How finely a workout's heart rate series is returned, so charts of long workouts don't
+/// download every sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HrResolution {
+    /// Every recorded sample
+    #[default]
+    #[serde(rename = "raw")]
+    Raw,
+    #[serde(rename = "10s")]
+    TenSeconds,
+    #[serde(rename = "60s")]
+    SixtySeconds,
+}
+
+impl HrResolution {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "raw" => Some(Self::Raw),
+            "10s" => Some(Self::TenSeconds),
+            "60s" => Some(Self::SixtySeconds),
+            _ => None,
+        }
+    }
+
+    /// Width of the buckets samples are averaged over; None keeps every sample
+    pub fn bucket(&self) -> Option<Duration> {
+        match self {
+            Self::Raw => None,
+            Self::TenSeconds => Some(Duration::seconds(10)),
+            Self::SixtySeconds => Some(Duration::seconds(60)),
+        }
+    }
+}
+
+/// Average the samples into one point per bucket, stamped with the bucket's start. Buckets are
+/// counted from the first sample and empty ones are skipped, so gaps in the recording stay gaps.
+pub fn downsample(samples: &[HeartRateData], resolution: HrResolution) -> Vec<HeartRateData> {
+    let Some(bucket) = resolution.bucket() else {
+        return samples.to_vec();
+    };
+    let mut sorted: Vec<&HeartRateData> = samples.iter().collect();
+    sorted.sort_by_key(|sample| sample.timestamp);
+    let Some(first) = sorted.first() else {
+        return Vec::new();
+    };
+
+    let start = first.timestamp;
+    let bucket_ms = bucket.num_milliseconds();
+    let mut points: Vec<HeartRateData> = Vec::new();
+    let (mut current, mut sum, mut count) = (0_i64, 0_i64, 0_i64);
+    for sample in sorted {
+        let index = (sample.timestamp - start).num_milliseconds() / bucket_ms;
+        if index != current && count > 0 {
+            points.push(bucket_point(start, bucket_ms, current, sum, count));
+            (sum, count) = (0, 0);
+        }
+        current = index;
+        sum += sample.heart_rate as i64;
+        count += 1;
+    }
+    if count > 0 {
+        points.push(bucket_point(start, bucket_ms, current, sum, count));
+    }
+    points
+}
+
+fn bucket_point(start: DateTime<Utc>, bucket_ms: i64, index: i64, sum: i64, count: i64) -> HeartRateData {
+    HeartRateData {
+        timestamp: start + Duration::milliseconds(index * bucket_ms),
+        heart_rate: ((sum as f64) / (count as f64)).round() as i32,
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::workout_data::HeartRateData;
+
+/// Minutes at the start of a workout that are ignored while the heart rate settles
+pub const WARMUP_MINUTES: i64 = 10;
+/// Minimum post-warmup duration for a steady-state average to be meaningful
+pub const MIN_STEADY_STATE_MINUTES: i64 = 10;
+/// Minimum post-warmup duration before cardiac drift is measured (long workouts only)
+pub const MIN_DRIFT_MINUTES: i64 = 30;
+
+/// Heart rate metrics derived from a single workout, used to track fitness over time
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HrWorkoutMetrics {
+    /// Average heart rate after the warm-up
+    pub steady_state_avg_hr: Option<f32>,
+    /// Increase of the average heart rate from the first to the second half
+    /// of the post-warmup period, in percent of the first half
+    pub hr_drift_pct: Option<f32>,
+}
+
+/// Calculate steady-state heart rate and cardiac drift for a workout.
+/// Samples may arrive unordered; workouts that are too short yield `None` for the affected metric.
+pub fn calculate_hr_workout_metrics(hr_data: &[HeartRateData]) -> HrWorkoutMetrics {
+    let mut samples: Vec<&HeartRateData> = hr_data.iter().filter(|s| s.heart_rate > 0).collect();
+    samples.sort_by_key(|s| s.timestamp);
+
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return HrWorkoutMetrics::default();
+    };
+    let end = last.timestamp;
+
+    let steady_start = first.timestamp + chrono::Duration::minutes(WARMUP_MINUTES);
+    let steady_minutes = (end - steady_start).num_minutes();
+    if steady_minutes < MIN_STEADY_STATE_MINUTES {
+        return HrWorkoutMetrics::default();
+    }
+
+    let steady: Vec<&HeartRateData> = samples.into_iter().filter(|s| s.timestamp >= steady_start).collect();
+    let steady_state_avg_hr = mean_hr(&steady);
+
+    let hr_drift_pct = if steady_minutes >= MIN_DRIFT_MINUTES {
+        let midpoint = steady_start + (end - steady_start) / 2;
+        let (first_half, second_half): (Vec<&HeartRateData>, Vec<&HeartRateData>) =
+            steady.iter().partition(|s| s.timestamp < midpoint);
+        match (mean_hr(&first_half), mean_hr(&second_half)) {
+            (Some(first_avg), Some(second_avg)) => Some((second_avg - first_avg) / first_avg * 100.0),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    HrWorkoutMetrics { steady_state_avg_hr, hr_drift_pct }
+}
+
+fn mean_hr(samples: &[&HeartRateData]) -> Option<f32> {
+    if samples.is_empty() {
+        return None;
+    }
+    Some(samples.iter().map(|s| s.heart_rate as f32).sum::<f32>() / samples.len() as f32)
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::workout_data::HeartRateData;
+
+/// Heart rate is averaged over this many seconds either side of a sample before it is labelled,
+/// so single noisy samples don't split an interval
+pub const SMOOTHING_SECONDS: i64 = 10;
+/// Segments shorter than this are merged into their neighbours
+pub const MIN_SEGMENT_SECONDS: i64 = 30;
+/// Heart rates varying less than this (standard deviation, bpm) are a steady workout
+pub const MIN_HR_STDDEV: f32 = 5.0;
+/// Work and rest heart rates must be at least this far apart (bpm)
+pub const MIN_HR_RANGE: f32 = 12.0;
+/// Fewer work segments than this isn't interval training
+pub const MIN_WORK_INTERVALS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentKind {
+    Work,
+    Rest,
+}
+
+/// A stretch of a workout at high (work) or low (rest) intensity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutSegment {
+    pub kind: SegmentKind,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub duration_seconds: i64,
+    pub avg_heart_rate: i32,
+    pub max_heart_rate: i32,
+}
+
+/// Work/rest intervals detected in a workout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntervalAnalysis {
+    /// e.g. "8 × 2min high intensity"
+    pub summary: String,
+    pub work_intervals: usize,
+    /// Median duration of the work segments
+    pub typical_work_seconds: i64,
+    pub segments: Vec<WorkoutSegment>,
+}
+
+struct Run {
+    work: bool,
+    /// Index of the first sample and one past the last
+    from: usize,
+    to: usize,
+}
+
+/// Split a workout into work and rest segments from the variation of its heart rate.
+/// Returns `None` for steady workouts and for workouts with fewer than `MIN_WORK_INTERVALS`
+/// work segments. Samples may arrive unordered.
+pub fn detect_intervals(hr_data: &[HeartRateData]) -> Option<IntervalAnalysis> {
+    let mut samples: Vec<&HeartRateData> = hr_data.iter().filter(|s| s.heart_rate > 0).collect();
+    samples.sort_by_key(|s| s.timestamp);
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let smoothed = smooth(&samples);
+    let mean = smoothed.iter().sum::<f32>() / smoothed.len() as f32;
+    let variance = smoothed.iter().map(|hr| (hr - mean).powi(2)).sum::<f32>() / smoothed.len() as f32;
+    if variance.sqrt() < MIN_HR_STDDEV {
+        return None;
+    }
+
+    let mut sorted = smoothed.clone();
+    sorted.sort_by(f32::total_cmp);
+    let (low, high) = (percentile(&sorted, 0.2), percentile(&sorted, 0.8));
+    if high - low < MIN_HR_RANGE {
+        return None;
+    }
+    let threshold = (low + high) / 2.0;
+
+    let mut runs: Vec<Run> = Vec::new();
+    for (i, hr) in smoothed.iter().enumerate() {
+        let work = *hr >= threshold;
+        match runs.last_mut() {
+            Some(run) if run.work == work => run.to = i + 1,
+            _ => runs.push(Run { work, from: i, to: i + 1 }),
+        }
+    }
+    merge_short_runs(&mut runs, &samples);
+
+    let segments: Vec<WorkoutSegment> = runs.iter().map(|run| segment(run, &samples)).collect();
+    let mut work_seconds: Vec<i64> = segments
+        .iter()
+        .filter(|s| s.kind == SegmentKind::Work)
+        .map(|s| s.duration_seconds)
+        .collect();
+    if work_seconds.len() < MIN_WORK_INTERVALS {
+        return None;
+    }
+    work_seconds.sort_unstable();
+    let typical_work_seconds = work_seconds[work_seconds.len() / 2];
+
+    Some(IntervalAnalysis {
+        summary: format!("{} × {} high intensity", work_seconds.len(), duration_label(typical_work_seconds)),
+        work_intervals: work_seconds.len(),
+        typical_work_seconds,
+        segments,
+    })
+}
+
+/// Mean heart rate within `SMOOTHING_SECONDS` of each sample
+fn smooth(samples: &[&HeartRateData]) -> Vec<f32> {
+    let window = chrono::Duration::seconds(SMOOTHING_SECONDS);
+    let (mut from, mut to, mut sum) = (0, 0, 0i64);
+    samples
+        .iter()
+        .map(|sample| {
+            while to < samples.len() && samples[to].timestamp <= sample.timestamp + window {
+                sum += samples[to].heart_rate as i64;
+                to += 1;
+            }
+            while samples[from].timestamp < sample.timestamp - window {
+                sum -= samples[from].heart_rate as i64;
+                from += 1;
+            }
+            sum as f32 / (to - from) as f32
+        })
+        .collect()
+}
+
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    sorted[((sorted.len() - 1) as f32 * p).round() as usize]
+}
+
+/// A run lasts until the next one starts; the last one until the last sample
+fn run_seconds(run: &Run, samples: &[&HeartRateData]) -> i64 {
+    let end = samples.get(run.to).unwrap_or(&samples[samples.len() - 1]).timestamp;
+    (end - samples[run.from].timestamp).num_seconds()
+}
+
+/// Flip the shortest run below `MIN_SEGMENT_SECONDS` into its neighbours until none is left
+fn merge_short_runs(runs: &mut Vec<Run>, samples: &[&HeartRateData]) {
+    while runs.len() > 1 {
+        let Some(shortest) = (0..runs.len())
+            .filter(|&i| run_seconds(&runs[i], samples) < MIN_SEGMENT_SECONDS)
+            .min_by_key(|&i| run_seconds(&runs[i], samples))
+        else {
+            break;
+        };
+        runs[shortest].work = !runs[shortest].work;
+
+        let mut merged: Vec<Run> = Vec::with_capacity(runs.len());
+        for run in runs.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.work == run.work => last.to = run.to,
+                _ => merged.push(run),
+            }
+        }
+        *runs = merged;
+    }
+}
+
+fn segment(run: &Run, samples: &[&HeartRateData]) -> WorkoutSegment {
+    let in_run = &samples[run.from..run.to];
+    let start = samples[run.from].timestamp;
+    let end = samples.get(run.to).unwrap_or(&samples[samples.len() - 1]).timestamp;
+    WorkoutSegment {
+        kind: if run.work { SegmentKind::Work } else { SegmentKind::Rest },
+        start,
+        end,
+        duration_seconds: (end - start).num_seconds(),
+        avg_heart_rate: (in_run.iter().map(|s| s.heart_rate as i64).sum::<i64>() / in_run.len() as i64) as i32,
+        max_heart_rate: in_run.iter().map(|s| s.heart_rate).max().unwrap_or_default(),
+    }
+}
+
+/// "45s" below 90 seconds, whole minutes above
+fn duration_label(seconds: i64) -> String {
+    if seconds < 90 {
+        format!("{}s", (seconds + 2) / 5 * 5)
+    } else {
+        format!("{}min", (seconds + 30) / 60)
+    }
+}
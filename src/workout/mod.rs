@@ -1,2 +1,9 @@
 pub mod workout_analyzer;
-pub mod universal_hr_based_scoring;
\ No newline at end of file
+pub mod universal_hr_based_scoring;
+pub mod hr_trends;
+pub mod intervals;
+pub mod effort_calibration;
+pub mod score_breakdown;
+pub mod post_caption;
+pub mod hr_downsampling;
+pub mod threshold_detection;
@@ -0,0 +1,48 @@
+/// Longest caption template a user can save
+pub const MAX_CAPTION_TEMPLATE_LENGTH: usize = 280;
+
+/// Placeholders a caption template can use
+pub const CAPTION_PLACEHOLDERS: [&str; 4] = ["activity", "minutes", "points", "calories"];
+
+/// Values of the placeholders for one workout
+#[derive(Debug, Clone)]
+pub struct CaptionContext<'a> {
+    pub activity: &'a str,
+    pub minutes: i64,
+    pub points: i32,
+    pub calories: Option<i32>,
+}
+
+/// Check a caption template is short enough and only uses known placeholders
+pub fn validate_caption_template(template: &str) -> Result<(), String> {
+    if template.chars().count() > MAX_CAPTION_TEMPLATE_LENGTH {
+        return Err(format!("Caption template cannot exceed {} characters", MAX_CAPTION_TEMPLATE_LENGTH));
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err("Caption template has an unclosed placeholder".to_string());
+        };
+        let placeholder = &rest[start + 1..start + end];
+        if !CAPTION_PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "Unknown placeholder {{{}}}, use one of {}",
+                placeholder,
+                CAPTION_PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+            ));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+/// Caption of a workout post from the user's template. Unknown calories render as a dash.
+pub fn render_caption(template: &str, context: &CaptionContext) -> String {
+    let calories = context.calories.map_or_else(|| "–".to_string(), |calories| calories.to_string());
+    template
+        .replace("{activity}", context.activity)
+        .replace("{minutes}", &context.minutes.to_string())
+        .replace("{points}", &context.points.to_string())
+        .replace("{calories}", &calories)
+}
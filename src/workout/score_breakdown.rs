@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::game::comeback_bonus::MAX_COMEBACK_MULTIPLIER;
+use crate::models::health::TrainingZones;
+use crate::models::workout_data::{HeartRateData, WorkoutType, ZoneBreakdown};
+use crate::utils::heart_rate_filters::filter_heart_rate_data;
+use crate::workout::effort_calibration::MAX_ADJUSTMENT;
+use crate::workout::universal_hr_based_scoring::{INTENSITY_WORKOUT_MULTIPLIER, P_VT0, P_VT1, P_VT2, P_VT_OFF};
+
+/// A scored workout as stored, everything needed to explain its points
+#[derive(Debug, Clone)]
+pub struct ScoredWorkout {
+    pub workout_id: Uuid,
+    pub workout_start: DateTime<Utc>,
+    pub workout_end: DateTime<Utc>,
+    /// Heart rate samples as uploaded, before filtering
+    pub heart_rate_data: Vec<HeartRateData>,
+    /// Zones as scored, including the calibration
+    pub zones: Vec<ZoneBreakdown>,
+    pub workout_type: WorkoutType,
+    pub scoring_calibration: f32,
+    pub total_points: i32,
+    /// Recorded while the user was suspended, so not credited to games or quests
+    pub suspended: bool,
+    pub games: Vec<CreditedGame>,
+}
+
+/// A live game the workout scored in
+#[derive(Debug, Clone, Serialize)]
+pub struct CreditedGame {
+    pub game_id: Uuid,
+    pub team_name: String,
+    pub opponent_name: String,
+    pub score_points: f32,
+    pub comeback_multiplier: Option<f32>,
+    pub comeback_bonus: f32,
+    pub booster_bonus: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleUsage {
+    pub received: usize,
+    pub used: usize,
+    /// Outside the workout's time range, duplicated or out of order
+    pub discarded: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ZoneContribution {
+    pub zone: String,
+    pub minutes: f32,
+    /// Points per minute spent in the zone
+    pub weight: f32,
+    pub points: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMultiplier {
+    pub name: String,
+    pub factor: f32,
+    /// Points after applying this multiplier
+    pub points: f32,
+}
+
+/// Step-by-step explanation of a workout's points
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreBreakdown {
+    pub workout_id: Uuid,
+    pub samples: SampleUsage,
+    pub zones: Vec<ZoneContribution>,
+    /// Sum of the zone points, before multipliers
+    pub base_points: f32,
+    pub multipliers: Vec<AppliedMultiplier>,
+    /// Limits that held the points back
+    pub caps: Vec<String>,
+    pub total_points: i32,
+    pub games: Vec<CreditedGame>,
+    /// The explanation in plain sentences, in scoring order
+    pub steps: Vec<String>,
+}
+
+/// Points per minute of a zone, by its name in the zone breakdown
+pub fn zone_weight(zone: &str) -> Option<f32> {
+    TrainingZones::new(60, 120, P_VT_OFF, P_VT0, P_VT1, P_VT2)
+        .zones
+        .into_iter()
+        .find(|(name, _)| name.to_string() == zone)
+        .map(|(_, zone)| zone.intensity_multiplier)
+}
+
+/// Walk through the scoring of a workout: filtering the samples, points per zone, multipliers,
+/// caps and the live games credited
+pub fn explain_score(workout: &ScoredWorkout) -> ScoreBreakdown {
+    let mut steps = Vec::new();
+    let mut caps = Vec::new();
+
+    // Uploads are reversed when the first samples are descending, then filtered
+    let mut samples = workout.heart_rate_data.clone();
+    if samples.len() > 1 && samples[0].timestamp > samples[1].timestamp {
+        samples.reverse();
+    }
+    let discarded = filter_heart_rate_data(&mut samples, &workout.workout_start, &workout.workout_end);
+    let usage = SampleUsage { received: workout.heart_rate_data.len(), used: samples.len(), discarded };
+    steps.push(if discarded > 0 {
+        format!(
+            "{} of {} heart rate samples were used; {} were outside the workout's time or duplicated and were discarded",
+            usage.used, usage.received, discarded
+        )
+    } else {
+        format!("All {} heart rate samples were used", usage.received)
+    });
+
+    let calibration = if workout.scoring_calibration > 0.0 { workout.scoring_calibration } else { 1.0 };
+    let zones: Vec<ZoneContribution> = workout
+        .zones
+        .iter()
+        .map(|zone| ZoneContribution {
+            zone: zone.zone.clone(),
+            minutes: zone.minutes,
+            weight: zone_weight(&zone.zone).unwrap_or_default(),
+            points: (zone.stamina_gained + zone.strength_gained) / calibration,
+        })
+        .collect();
+    for zone in zones.iter().filter(|z| z.minutes > 0.0) {
+        steps.push(if zone.weight > 0.0 {
+            format!("{}: {:.1} min × {} points/min = {:.1} points", zone.zone, zone.minutes, zone.weight, zone.points)
+        } else {
+            format!("{}: {:.1} min with heart rate too low to count, 0 points", zone.zone, zone.minutes)
+        });
+    }
+    let base_points: f32 = zones.iter().map(|z| z.points).sum();
+    steps.push(format!("Heart rate zones: {:.1} points", base_points));
+
+    let mut points = base_points;
+    let mut multipliers = Vec::new();
+    if matches!(workout.workout_type, WorkoutType::Strength | WorkoutType::Hiit) {
+        points *= INTENSITY_WORKOUT_MULTIPLIER;
+        steps.push(format!("Strength and HIIT workouts earn ×{}: {:.1} points", INTENSITY_WORKOUT_MULTIPLIER, points));
+        multipliers.push(AppliedMultiplier { name: "Strength/HIIT workout".to_string(), factor: INTENSITY_WORKOUT_MULTIPLIER, points });
+    }
+    if (calibration - 1.0).abs() > f32::EPSILON {
+        points *= calibration;
+        steps.push(format!("Personal calibration from your effort ratings ×{:.2}: {:.1} points", calibration, points));
+        multipliers.push(AppliedMultiplier { name: "Personal calibration".to_string(), factor: calibration, points });
+        if (calibration - 1.0).abs() >= MAX_ADJUSTMENT - 1e-4 {
+            caps.push(format!("Personal calibration is limited to ±{:.0}%", MAX_ADJUSTMENT * 100.0));
+        }
+    }
+    if points.fract() > 0.05 && workout.total_points == points.trunc() as i32 {
+        caps.push(format!("Workout points are rounded down to whole points ({:.1} → {})", points, workout.total_points));
+    }
+    steps.push(format!("Workout total: {} points", workout.total_points));
+
+    if workout.suspended {
+        caps.push("Recorded during a suspension, so not credited to games or quests".to_string());
+        steps.push("Not credited to any game: the workout was recorded during a suspension".to_string());
+    } else if workout.games.is_empty() {
+        steps.push("No game of your team was live, so no game was credited".to_string());
+    }
+    for game in &workout.games {
+        let mut step = format!("Credited {:.1} points to {} vs {}", game.score_points, game.team_name, game.opponent_name);
+        if game.comeback_bonus > 0.0 {
+            let multiplier = game.comeback_multiplier.unwrap_or(1.0);
+            step.push_str(&format!(", including a ×{} comeback bonus of {:.1}", multiplier, game.comeback_bonus));
+            if multiplier >= MAX_COMEBACK_MULTIPLIER {
+                caps.push(format!("Comeback bonuses are limited to ×{}", MAX_COMEBACK_MULTIPLIER));
+            }
+        }
+        if game.booster_bonus > 0.0 {
+            step.push_str(&format!(", including a 2x booster bonus of {:.1}", game.booster_bonus));
+        }
+        steps.push(step);
+    }
+    caps.dedup();
+
+    ScoreBreakdown {
+        workout_id: workout.workout_id,
+        samples: usage,
+        zones,
+        base_points,
+        multipliers,
+        caps,
+        total_points: workout.total_points,
+        games: workout.games.clone(),
+        steps,
+    }
+}
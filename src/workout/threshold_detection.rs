@@ -0,0 +1,111 @@
+use chrono::Duration;
+
+use crate::models::workout_data::HeartRateData;
+
+/// Workouts of this many past days are analyzed
+pub const DETECTION_WINDOW_DAYS: i64 = 60;
+/// Steady efforts whose heart rate drifted less than this, in percent, stayed below VT1
+pub const MAX_AEROBIC_DRIFT_PCT: f32 = 5.0;
+/// Low-drift workouts needed before VT1 is estimated
+pub const MIN_AEROBIC_WORKOUTS: usize = 3;
+/// The VT1 estimate averages this many of the hardest low-drift workouts, so one outlier can't set it
+pub const AEROBIC_WORKOUTS_AVERAGED: usize = 3;
+/// Length of the hardest sustained effort whose average heart rate approximates VT2
+pub const SUSTAINED_EFFORT_MINUTES: i64 = 20;
+/// Proposals changing neither threshold by at least this much aren't worth the user's attention
+pub const MIN_CHANGE_BPM: i32 = 3;
+
+/// What one workout tells about the thresholds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkoutThresholdEvidence {
+    /// Average heart rate after the warm-up
+    pub steady_state_avg_hr: Option<f32>,
+    /// Cardiac drift of long workouts, see `hr_trends`
+    pub hr_drift_pct: Option<f32>,
+    /// Highest average heart rate held for `SUSTAINED_EFFORT_MINUTES`
+    pub best_sustained_hr: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThresholdEstimate {
+    pub vt1: Option<i32>,
+    pub vt2: Option<i32>,
+    pub aerobic_workouts: usize,
+    pub sustained_workouts: usize,
+}
+
+impl ThresholdEstimate {
+    /// Whether a threshold would move by at least `MIN_CHANGE_BPM`, or get a value at all
+    pub fn differs_from(&self, vt1: Option<i32>, vt2: Option<i32>) -> bool {
+        let moves = |estimate: Option<i32>, current: Option<i32>| match (estimate, current) {
+            (Some(estimate), Some(current)) => (estimate - current).abs() >= MIN_CHANGE_BPM,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        moves(self.vt1, vt1) || moves(self.vt2, vt2)
+    }
+}
+
+/// Highest average heart rate over any `minutes` long stretch of the workout, None if it is shorter.
+/// Samples may arrive unordered.
+pub fn best_sustained_hr(hr_data: &[HeartRateData], minutes: i64) -> Option<f32> {
+    let mut samples: Vec<&HeartRateData> = hr_data.iter().filter(|s| s.heart_rate > 0).collect();
+    samples.sort_by_key(|s| s.timestamp);
+    let window = Duration::minutes(minutes);
+
+    let mut best: Option<f32> = None;
+    let (mut end, mut sum) = (0, 0_i64);
+    for start in 0..samples.len() {
+        let window_end = samples[start].timestamp + window;
+        while end < samples.len() && samples[end].timestamp < window_end {
+            sum += samples[end].heart_rate as i64;
+            end += 1;
+        }
+        // Only stretches the recording covers completely
+        if end == samples.len() {
+            break;
+        }
+        let average = sum as f32 / (end - start) as f32;
+        best = Some(best.map_or(average, |best| best.max(average)));
+        sum -= samples[start].heart_rate as i64;
+    }
+    best
+}
+
+/// Estimate VT1 from the hardest steady efforts whose heart rate didn't drift (aerobic decoupling
+/// stays low below VT1) and VT2 from the hardest sustained effort. Estimates at or below `floor_hr`
+/// (the user's VT0) or at or above `max_hr` are dropped, as is a VT2 not above VT1.
+pub fn estimate_thresholds(evidence: &[WorkoutThresholdEvidence], floor_hr: i32, max_hr: i32) -> ThresholdEstimate {
+    let plausible = |hr: f32| hr > floor_hr as f32 && hr < max_hr as f32;
+
+    let mut aerobic: Vec<f32> = evidence
+        .iter()
+        .filter(|workout| workout.hr_drift_pct.is_some_and(|drift| drift.abs() < MAX_AEROBIC_DRIFT_PCT))
+        .filter_map(|workout| workout.steady_state_avg_hr)
+        .filter(|hr| plausible(*hr))
+        .collect();
+    aerobic.sort_by(|a, b| b.total_cmp(a));
+    let vt1 = (aerobic.len() >= MIN_AEROBIC_WORKOUTS).then(|| {
+        let hardest = &aerobic[..AEROBIC_WORKOUTS_AVERAGED.min(aerobic.len())];
+        (hardest.iter().sum::<f32>() / hardest.len() as f32).round() as i32
+    });
+
+    let sustained: Vec<f32> = evidence
+        .iter()
+        .filter_map(|workout| workout.best_sustained_hr)
+        .filter(|hr| plausible(*hr))
+        .collect();
+    let vt2 = sustained
+        .iter()
+        .copied()
+        .reduce(f32::max)
+        .map(|hr| hr.round() as i32)
+        .filter(|vt2| vt1.is_none_or(|vt1| *vt2 > vt1));
+
+    ThresholdEstimate {
+        vt1,
+        vt2,
+        aerobic_workouts: aerobic.len(),
+        sustained_workouts: sustained.len(),
+    }
+}
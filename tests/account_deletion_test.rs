@@ -0,0 +1,102 @@
+//! Account deletion and data export tests
+//!
+//! - Deleted accounts are purged after the grace period
+//! - Profile pictures are found in MinIO by the URL saved on the user
+//! - The export is one JSON document with the account and its workouts, without the password hash
+//! - Deleting an account logs it out; logging in again cancels the deletion, otherwise the purge removes it
+
+use chrono::{Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+
+use riina_backend::models::user::{AccountDeletion, ACCOUNT_DELETION_GRACE_DAYS};
+use riina_backend::services::account_data_service::profile_picture_object_key;
+use riina_backend::services::AccountDataService;
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{create_health_profile_for_user, upload_workout_data_for_user, WorkoutData, WorkoutIntensity};
+
+#[test]
+fn accounts_are_purged_after_the_grace_period() {
+    let requested_at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+    let deletion = AccountDeletion::requested(requested_at);
+    assert_eq!(deletion.purge_after - deletion.requested_at, Duration::days(ACCOUNT_DELETION_GRACE_DAYS));
+}
+
+#[test]
+fn profile_pictures_map_to_their_object_keys() {
+    assert_eq!(
+        profile_picture_object_key("/profile/picture/42/1700000000_me.jpg").as_deref(),
+        Some("profile-pictures/42/1700000000_me.jpg")
+    );
+    assert_eq!(profile_picture_object_key("https://example.com/me.jpg"), None);
+}
+
+#[tokio::test]
+async fn deleted_accounts_export_and_purge_their_data() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+
+    let response = make_authenticated_request(&client, Method::GET, &format!("{}/profile/export", test_app.address), &user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let export: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(export["account"]["username"], user.username.as_str());
+    assert!(export["account"].get("password_hash").is_none());
+    assert!(export["health_profile"].is_object());
+    assert_eq!(export["workouts"].as_array().unwrap().len(), 1);
+    assert!(export["workouts"][0]["heart_rate_data"].is_array());
+
+    let delete_url = format!("{}/profile/delete-account", test_app.address);
+    let response = make_authenticated_request(&client, Method::POST, &delete_url, &user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["purge_after"].is_string());
+    let response = make_authenticated_request(&client, Method::GET, &format!("{}/profile/user", test_app.address), &user.token, None).await;
+    assert_eq!(response.status(), 401, "Deleting the account logs it out");
+
+    // Logging in again keeps the account
+    let session: serde_json::Value = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": user.username, "password": "password123" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token = session["token"].as_str().unwrap();
+    let status: String = sqlx::query_scalar("SELECT status FROM users WHERE id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "active");
+
+    // Without logging in again the account is purged once the grace period has passed
+    let response = make_authenticated_request(&client, Method::POST, &delete_url, token, None).await;
+    assert_eq!(response.status(), 200);
+    sqlx::query("UPDATE account_deletions SET purge_after = NOW() - INTERVAL '1 minute' WHERE user_id = $1")
+        .bind(user.user_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let purged = AccountDataService::new(test_app.db_pool.clone()).purge_due_accounts(None).await.unwrap();
+    assert!(purged >= 1);
+    let workouts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM workout_data WHERE user_id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(workouts, 0);
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 0);
+}
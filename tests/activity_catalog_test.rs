@@ -0,0 +1,144 @@
+//! Activity catalog tests
+//!
+//! Covers the `activities` reference table:
+//! - `/health/activities` lists the active catalog
+//! - Admins can add, update, deactivate and delete activities
+//! - Editing a workout post's activity only accepts catalog entries, stored by canonical name
+
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::admin_helpers::create_admin_user_and_login;
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_health_profile_for_user};
+
+async fn catalog_keys(client: &Client, address: &str, token: &str) -> Vec<String> {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/health/activities", address), token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|activity| activity["key"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn admins_manage_the_activity_catalog() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let admin_url = format!("{}/admin/activities", test_app.address);
+
+    // Seeded catalog
+    let keys = catalog_keys(&client, &test_app.address, &user.token).await;
+    assert!(keys.contains(&"run".to_string()));
+    assert!(keys.contains(&"yoga".to_string()));
+
+    let key = format!("padel_{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &admin_url, &admin.token,
+        Some(json!({ "key": key, "name": format!("Padel {}", key), "met_value": 6.0, "default_scoring_profile": "cardio" })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let activity_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["icon_key"], key);
+    assert_eq!(body["data"]["default_scoring_profile"], "cardio");
+
+    // Duplicate key, invalid profile, regular users
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &admin_url, &admin.token,
+        Some(json!({ "key": key, "name": "Another Padel", "met_value": 6.0 })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &admin_url, &admin.token,
+        Some(json!({ "key": "squash_x", "name": "Squash X", "met_value": 7.0, "default_scoring_profile": "racket" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &admin_url, &user.token,
+        Some(json!({ "key": "squash_y", "name": "Squash Y", "met_value": 7.0 })),
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+
+    // Deactivated entries disappear from the public catalog, not from the admin list
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &format!("{}/{}", admin_url, activity_id), &admin.token,
+        Some(json!({ "met_value": 6.5, "is_active": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["met_value"].as_f64().unwrap(), 6.5);
+    assert!(!catalog_keys(&client, &test_app.address, &user.token).await.contains(&key));
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &admin_url, &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"].as_array().unwrap().iter().any(|activity| activity["key"] == key.as_str()));
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/{}", admin_url, activity_id), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/{}", admin_url, activity_id), &admin.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn workout_activity_edits_are_validated_against_the_catalog() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now(), 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+    let workout_id = upload["data"]["sync_id"].as_str().unwrap();
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/posts/workout/{}", test_app.address, workout_id), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let post_url = format!("{}/posts/{}", test_app.address, body["data"]["id"].as_str().unwrap());
+
+    // Free text is rejected and nothing is changed
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &post_url, &user.token,
+        Some(json!({ "content": "Should not be saved", "activity_name": "Underwater basket weaving" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    // Keys and differently cased names resolve to the catalog name
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &post_url, &user.token,
+        Some(json!({ "activity_name": "swim" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &post_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workout_data"]["user_activity"], "Swimming");
+    assert_ne!(body["data"]["content"], "Should not be saved");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &post_url, &user.token,
+        Some(json!({ "activity_name": "strength training" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let user_activity: Option<String> = sqlx::query_scalar("SELECT user_activity FROM workout_data WHERE id = $1")
+        .bind(Uuid::parse_str(workout_id).unwrap())
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(user_activity.as_deref(), Some("Strength Training"));
+}
@@ -0,0 +1,213 @@
+//! Admin broadcast tests
+//!
+//! Covers `/admin/broadcast`:
+//! - Announcements reach the targeted team or league through the requested channels
+//! - Delivery and read stats are reported per announcement
+//! - Scheduled announcements go out when due and can be cancelled before that
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::services::BroadcastService;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+
+#[tokio::test]
+async fn broadcast_reaches_the_targeted_audience() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let home_owner = create_test_user_and_login(&test_app.address).await;
+    let away_owner = create_test_user_and_login(&test_app.address).await;
+    let home_member = create_test_user_and_login(&test_app.address).await;
+    let outsider = create_test_user_and_login(&test_app.address).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![home_owner.user_id, away_owner.user_id]), true, None, None,
+    ).await;
+    let home_team_id = &league.team_ids[0];
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/admin/teams/{}/members", test_app.address, home_team_id),
+        &admin.token, Some(json!({ "user_id": home_member.user_id, "role": "member" })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+
+    let broadcast_url = format!("{}/admin/broadcast", test_app.address);
+
+    // Team announcement through every channel
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &broadcast_url, &admin.token,
+        Some(json!({
+            "title": "Training moved",
+            "message": "Thursday's session starts at 7pm",
+            "target_type": "team",
+            "target_id": home_team_id,
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let team_broadcast = &body["data"];
+    assert_eq!(team_broadcast["status"], "sent");
+    assert_eq!(team_broadcast["channels"], json!(["websocket", "push", "in_app"]));
+    assert_eq!(team_broadcast["recipient_count"], 2);
+    assert_eq!(team_broadcast["in_app_count"], 2);
+    assert_eq!(team_broadcast["push_count"], 0, "Nobody registered a push token");
+    assert_eq!(team_broadcast["read_count"], 0);
+
+    let notifications_for = |token: String| {
+        let client = client.clone();
+        let url = format!("{}/social/notifications", test_app.address);
+        async move {
+            let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &token, None).await;
+            let body: serde_json::Value = response.json().await.unwrap();
+            body["notifications"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|n| n["notification_type"] == "admin_broadcast")
+                .cloned()
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let member_notifications = notifications_for(home_member.token.clone()).await;
+    assert_eq!(member_notifications.len(), 1);
+    assert_eq!(member_notifications[0]["message"], "Thursday's session starts at 7pm");
+    assert_eq!(member_notifications[0]["entity_id"], team_broadcast["id"]);
+    assert!(notifications_for(away_owner.token.clone()).await.is_empty());
+    assert!(notifications_for(outsider.token.clone()).await.is_empty());
+
+    // Reads show up in the stats
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT,
+        &format!("{}/social/notifications/{}/read", test_app.address, member_notifications[0]["id"].as_str().unwrap()),
+        &home_member.token, None,
+    ).await;
+    assert!(response.status().is_success());
+    let team_broadcast_url = format!("{}/{}", broadcast_url, team_broadcast["id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &team_broadcast_url, &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["read_count"], 1);
+
+    // League announcement, push only
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &broadcast_url, &admin.token,
+        Some(json!({
+            "title": "Season kickoff",
+            "message": "The new season starts on Monday",
+            "target_type": "league",
+            "target_id": league.league_id,
+            "channels": ["push"],
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["recipient_count"], 3);
+    assert_eq!(body["data"]["in_app_count"], 0);
+    assert_eq!(notifications_for(away_owner.token.clone()).await.len(), 0, "Push only skips the notification center");
+
+    // Invalid requests
+    for (request, status) in [
+        (json!({ "title": "", "message": "Hi", "target_type": "all" }), 400),
+        (json!({ "title": "Hi", "message": "Hi", "target_type": "galaxy" }), 400),
+        (json!({ "title": "Hi", "message": "Hi", "target_type": "team" }), 400),
+        (json!({ "title": "Hi", "message": "Hi", "target_type": "all", "channels": ["carrier_pigeon"] }), 400),
+        (json!({ "title": "Hi", "message": "Hi", "target_type": "all", "scheduled_for": Utc::now() - Duration::hours(1) }), 400),
+        (json!({ "title": "Hi", "message": "Hi", "target_type": "team", "target_id": Uuid::new_v4() }), 404),
+    ] {
+        let response = make_authenticated_request(&client, reqwest::Method::POST, &broadcast_url, &admin.token, Some(request)).await;
+        assert_eq!(status, response.status().as_u16());
+    }
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &broadcast_url, &outsider.token,
+        Some(json!({ "title": "Hi", "message": "Hi", "target_type": "all" })),
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn scheduled_broadcast_is_sent_when_due() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let broadcast_url = format!("{}/admin/broadcast", test_app.address);
+
+    let schedule = |title: &str| {
+        json!({
+            "title": title,
+            "message": "Maintenance tonight from 2am to 3am",
+            "target_type": "all",
+            "channels": ["websocket", "in_app"],
+            "scheduled_for": Utc::now() + Duration::hours(2),
+        })
+    };
+
+    // Cancelled before it is due
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &broadcast_url, &admin.token, Some(schedule("Cancelled maintenance")),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "scheduled");
+    assert_eq!(body["data"]["recipient_count"], 0);
+    let cancelled_url = format!("{}/{}", broadcast_url, body["data"]["id"].as_str().unwrap());
+
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &cancelled_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "cancelled");
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &cancelled_url, &admin.token, None).await;
+    assert_eq!(409, response.status().as_u16());
+
+    // Sent once due
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &broadcast_url, &admin.token, Some(schedule("Maintenance")),
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let broadcast_id = Uuid::parse_str(body["data"]["id"].as_str().unwrap()).unwrap();
+
+    let service = BroadcastService::new(test_app.db_pool.clone(), None);
+    assert_eq!(service.send_due().await.unwrap(), 0, "Not due yet");
+    sqlx::query("UPDATE admin_broadcasts SET scheduled_for = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(broadcast_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    service.send_due().await.unwrap();
+    assert!(service.send(broadcast_id).await.unwrap().is_none(), "Sent only once");
+
+    let active_users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE status = 'active'")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/{}", broadcast_url, broadcast_id), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "sent");
+    assert!(body["data"]["sent_at"].is_string());
+    assert_eq!(body["data"]["recipient_count"], active_users);
+    assert_eq!(body["data"]["in_app_count"], active_users);
+    assert_eq!(body["data"]["push_count"], 0);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/social/notifications", test_app.address), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["notifications"].as_array().unwrap().iter().any(|n| n["message"] == "Maintenance tonight from 2am to 3am"));
+
+    // Listing by status
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?status=cancelled", broadcast_url), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let cancelled = body["data"].as_array().unwrap();
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0]["title"], "Cancelled maintenance");
+}
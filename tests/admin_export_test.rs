@@ -0,0 +1,122 @@
+//! Admin CSV export tests
+//!
+//! Covers `/admin/export`:
+//! - Workouts, users and games stream as CSV with a header row
+//! - Filters narrow the export; invalid or unsupported filters are rejected
+//! - Only admins can export
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn export(client: &Client, address: &str, token: &str, query: &str) -> reqwest::Response {
+    make_authenticated_request(
+        client,
+        reqwest::Method::GET,
+        &format!("{}/admin/export?{}", address, query),
+        token,
+        None,
+    ).await
+}
+
+#[tokio::test]
+async fn admin_can_export_workouts_users_and_games_as_csv() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let other = create_test_user_and_login(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&user.token);
+    let other_id = parse_user_id_from_jwt_token(&other.token);
+
+    let now = Utc::now();
+    for (offset_days, activity) in [(3, "Running"), (1, "Cycling, indoor")] {
+        let start = now - Duration::days(offset_days);
+        sqlx::query(
+            r#"
+            INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                      workout_start, workout_end, duration_minutes, activity_name)
+            VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 40, $5)
+            "#
+        )
+        .bind(user_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(start)
+        .bind(start + Duration::minutes(40))
+        .bind(activity)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to insert workout");
+    }
+
+    // Workouts of one user, limited to the last two days
+    let from = (now - Duration::days(2)).format("%Y-%m-%dT%H:%M:%SZ");
+    let response = export(&client, &test_app.address, &admin.token,
+        &format!("entity=workouts&format=csv&user_id={}&from={}", user_id, from)).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/csv"));
+    assert!(response.headers()["content-disposition"].to_str().unwrap().contains("workouts_"));
+
+    let body = response.text().await.unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    assert!(lines[0].starts_with("workout_id,user_id,username,workout_start"));
+    assert_eq!(lines.len(), 2, "Header plus the one workout inside the range");
+    assert!(lines[1].contains("\"Cycling, indoor\""), "Values containing commas are quoted");
+
+    // Users with a status filter
+    let response = export(&client, &test_app.address, &admin.token,
+        &format!("entity=users&user_id={}&status=active", other_id)).await;
+    assert_eq!(200, response.status().as_u16());
+    let body = response.text().await.unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    assert!(lines[0].starts_with("user_id,username,email"));
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with(&other_id.to_string()));
+
+    // Games of one season
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![user_id, other_id]), true, None, None,
+    ).await;
+    let start_date = (now + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Export Season", &start_date,
+    ).await;
+    let game_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM games WHERE season_id = $1::uuid")
+        .bind(&season_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let response = export(&client, &test_app.address, &admin.token,
+        &format!("entity=games&season_id={}&status=scheduled", season_id)).await;
+    assert_eq!(200, response.status().as_u16());
+    let body = response.text().await.unwrap();
+    assert!(body.starts_with("game_id,season_id,season_name"));
+    assert_eq!(body.lines().count() as i64, game_count + 1);
+}
+
+#[tokio::test]
+async fn export_rejects_invalid_requests_and_non_admins() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    for query in [
+        "entity=teams",
+        "entity=workouts&format=xlsx",
+        "entity=workouts&status=active",
+        "entity=games&status=active'; DROP TABLE games; --",
+        "entity=users&user_id=not-a-uuid",
+    ] {
+        let response = export(&client, &test_app.address, &admin.token, query).await;
+        assert_eq!(400, response.status().as_u16(), "Expected 400 for {}", query);
+    }
+
+    let response = export(&client, &test_app.address, &user.token, "entity=users").await;
+    assert_eq!(403, response.status().as_u16());
+}
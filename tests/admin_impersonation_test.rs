@@ -0,0 +1,121 @@
+//! Admin impersonation tests
+//!
+//! - Impersonation needs a reason and lasts `IMPERSONATION_TOKEN_MINUTES`
+//! - The token acts as the user, is marked with the admin and shows up in the audit trail
+//! - Admins can't be impersonated, and the token can neither be refreshed nor rename the user
+//! - The token can't change how the account is secured, and logging out its session revokes it
+
+use reqwest::{Client, Method};
+use serde_json::json;
+
+use riina_backend::config::settings::{get_config, get_jwt_settings};
+use riina_backend::middleware::auth::Claims;
+use riina_backend::models::impersonation::{ImpersonateUserRequest, IMPERSONATION_TOKEN_MINUTES};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn impersonation_needs_a_reason() {
+    let request = |reason: &str| ImpersonateUserRequest { reason: reason.to_string() };
+    assert!(request("Ticket 1234: heart rate zones look off").validate().is_ok());
+    assert!(request("   ").validate().is_err());
+    assert!(request(&"x".repeat(501)).validate().is_err());
+}
+
+#[tokio::test]
+async fn admins_act_as_users_with_an_audited_token() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let impersonate_url = format!("{}/admin/users/{}/impersonate", test_app.address, user.user_id);
+
+    let response = make_authenticated_request(&client, Method::POST, &impersonate_url, &admin.token, Some(json!({ "reason": "" }))).await;
+    assert_eq!(response.status(), 400);
+
+    let response = make_authenticated_request(
+        &client, Method::POST, &impersonate_url, &admin.token,
+        Some(json!({ "reason": "Reproduce scoring of yesterday's run" })),
+    ).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let jwt_settings = get_jwt_settings(&get_config().unwrap());
+    let claims: Claims = jwt_settings.verify(&token).unwrap();
+    assert_eq!(claims.user_id(), Some(user.user_id));
+    assert_eq!(claims.impersonated_by, Some(admin.user_id));
+    let session_id = claims.sid.expect("Impersonation tokens have a session");
+    let lifetime = claims.exp as i64 - chrono::Utc::now().timestamp();
+    assert!(lifetime <= IMPERSONATION_TOKEN_MINUTES * 60);
+
+    let response = make_authenticated_request(&client, Method::GET, &format!("{}/profile/user", test_app.address), &token, None).await;
+    assert_eq!(response.status(), 200);
+    let profile: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(profile["data"]["username"], user.username.as_str());
+
+    let response = make_authenticated_request(
+        &client, Method::GET, &format!("{}/admin/users/{}/impersonations", test_app.address, user.user_id), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let trail = body["data"].as_array().unwrap();
+    assert_eq!(trail.len(), 1);
+    assert_eq!(trail[0]["admin_id"], admin.user_id.to_string());
+    assert_eq!(trail[0]["reason"], "Reproduce scoring of yesterday's run");
+
+    // The token doesn't outlive itself or lose its mark
+    let response = client
+        .post(format!("{}/biometric-refresh", test_app.address))
+        .json(&json!({ "token": token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+    let response = make_authenticated_request(
+        &client, Method::PATCH, &format!("{}/profile/username", test_app.address), &token,
+        Some(json!({ "username": "renamed_by_support" })),
+    ).await;
+    assert_eq!(response.status(), 403);
+
+    // Nor take over or lock out the account
+    for (method, path, body) in [
+        (Method::POST, "/auth/oauth/google/link", Some(json!({ "id_token": "x.y.z" }))),
+        (Method::POST, "/profile/delete-account", None),
+        (Method::DELETE, "/auth/sessions", None),
+    ] {
+        let response = make_authenticated_request(&client, method, &format!("{}{}", test_app.address, path), &token, body).await;
+        assert_eq!(response.status(), 403, "{} should be refused while impersonating", path);
+    }
+
+    // The user sees the support session and can end it
+    let response = make_authenticated_request(
+        &client, Method::DELETE, &format!("{}/auth/sessions/{}", test_app.address, session_id), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, Method::GET, &format!("{}/profile/user", test_app.address), &token, None).await;
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn admins_and_non_admins_cannot_impersonate() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let other_admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let reason = Some(json!({ "reason": "Support ticket" }));
+
+    let response = make_authenticated_request(
+        &client, Method::POST, &format!("{}/admin/users/{}/impersonate", test_app.address, other_admin.user_id),
+        &admin.token, reason.clone(),
+    ).await;
+    assert_eq!(response.status(), 403);
+
+    let response = make_authenticated_request(
+        &client, Method::POST, &format!("{}/admin/users/{}/impersonate", test_app.address, admin.user_id),
+        &user.token, reason,
+    ).await;
+    assert!(response.status() == 401 || response.status() == 403);
+}
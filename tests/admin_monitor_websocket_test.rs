@@ -0,0 +1,84 @@
+//! Admin live metrics WebSocket tests
+//!
+//! Covers `/admin/monitor-ws` for the ops dashboard:
+//! - Admins receive periodic snapshots with uploads, score events, active games and errors
+//! - Uploads show up in the snapshot's window
+//! - Regular users and invalid tokens are rejected
+
+use chrono::Utc;
+use futures_util::StreamExt;
+use reqwest::Client;
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use uuid::Uuid;
+
+mod common;
+use common::utils::spawn_app;
+use common::admin_helpers::create_admin_user_and_login;
+use common::workout_data_helpers::{
+    WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_test_user_with_health_profile
+};
+
+async fn next_snapshot<S>(ws_stream: &mut S) -> serde_json::Value
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .expect("No metrics snapshot received")
+            .expect("WebSocket closed")
+            .expect("WebSocket error");
+        if let Message::Text(text) = message {
+            let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if event["event_type"] == "live_metrics" {
+                return event;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn admins_receive_live_metrics_snapshots() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let ws_url = format!("{}/admin/monitor-ws?token={}&window_secs=60", test_app.address.replace("http", "ws"), admin.token);
+    let (mut ws_stream, _) = connect_async(ws_url.into_client_request().unwrap())
+        .await
+        .expect("Admin should be able to connect");
+
+    let snapshot = next_snapshot(&mut ws_stream).await;
+    assert_eq!(snapshot["window_secs"], 60);
+    for field in ["uploads_per_sec", "score_events_per_sec", "errors_per_sec"] {
+        assert!(snapshot[field].as_f64().unwrap() >= 0.0, "{} should be a rate", field);
+    }
+    assert!(snapshot["active_games"].as_i64().unwrap() >= 0);
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now(), 30);
+    workout_data.workout_uuid = Uuid::new_v4().to_string();
+    upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Workout upload should succeed");
+
+    let snapshot = next_snapshot(&mut ws_stream).await;
+    assert!(snapshot["uploads"].as_i64().unwrap() >= 1);
+    assert!(snapshot["uploads_per_sec"].as_f64().unwrap() > 0.0);
+}
+
+#[tokio::test]
+async fn monitor_socket_is_admin_only() {
+    let test_app = spawn_app().await;
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+    let ws_base = test_app.address.replace("http", "ws");
+
+    for token in [user.token.as_str(), "not-a-token"] {
+        let ws_url = format!("{}/admin/monitor-ws?token={}", ws_base, token);
+        let result = connect_async(ws_url.into_client_request().unwrap()).await;
+        assert!(result.is_err(), "Only admins should be able to open the monitor");
+    }
+}
@@ -0,0 +1,86 @@
+//! Admin endpoint quota tests
+//!
+//! - Each admin gets a limited number of expensive requests per window
+//! - Runs of the same endpoint and path queue one after another; extra waiters are refused
+//! - A double-clicked recalculation is answered after the first one instead of alongside it
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Method};
+
+use riina_backend::middleware::quota::{AdminQuotas, QuotaError, QuotaPolicy};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app};
+
+const POLICY: QuotaPolicy = QuotaPolicy {
+    max_per_window: 2,
+    window: Duration::from_secs(60),
+    max_queued: 1,
+    queue_timeout: Duration::from_millis(200),
+};
+
+#[test]
+fn requests_beyond_the_window_quota_are_refused() {
+    let quotas = AdminQuotas::new();
+    let start = Instant::now();
+
+    assert!(quotas.check_rate("export", "admin-a", &POLICY, start).is_ok());
+    assert!(quotas.check_rate("export", "admin-a", &POLICY, start + Duration::from_secs(10)).is_ok());
+    assert_eq!(
+        quotas.check_rate("export", "admin-a", &POLICY, start + Duration::from_secs(20)),
+        Err(QuotaError::RateLimited(Duration::from_secs(40)))
+    );
+
+    // Other admins and endpoints have their own quota
+    assert!(quotas.check_rate("export", "admin-b", &POLICY, start).is_ok());
+    assert!(quotas.check_rate("recalculate_standings", "admin-a", &POLICY, start).is_ok());
+
+    // The window slides
+    assert!(quotas.check_rate("export", "admin-a", &POLICY, start + Duration::from_secs(61)).is_ok());
+}
+
+#[tokio::test]
+async fn runs_of_the_same_lane_are_queued() {
+    let quotas = Arc::new(AdminQuotas::new());
+
+    let running = quotas.enter("recalculate /seasons/1", &POLICY).await.unwrap();
+    // Another lane isn't affected
+    let other = quotas.enter("recalculate /seasons/2", &POLICY).await.unwrap();
+    drop(other);
+
+    let waiter = {
+        let quotas = quotas.clone();
+        tokio::spawn(async move { quotas.enter("recalculate /seasons/1", &POLICY).await.map(|_| ()) })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    // The queue holds one waiter
+    assert_eq!(quotas.enter("recalculate /seasons/1", &POLICY).await.err(), Some(QuotaError::QueueFull));
+
+    drop(running);
+    assert_eq!(waiter.await.unwrap(), Ok(()));
+
+    let running = quotas.enter("recalculate /seasons/1", &POLICY).await.unwrap();
+    assert_eq!(quotas.enter("recalculate /seasons/1", &POLICY).await.err(), Some(QuotaError::QueueTimeout));
+    drop(running);
+}
+
+#[tokio::test]
+async fn admins_are_rate_limited_on_consistency_checks() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let url = format!("{}/admin/consistency", test_app.address);
+
+    for _ in 0..QuotaPolicy::RECALCULATION.max_per_window {
+        let response = make_authenticated_request(&client, Method::GET, &url, &admin.token, None).await;
+        assert_eq!(response.status(), 200);
+    }
+
+    let limited = make_authenticated_request(&client, Method::GET, &url, &admin.token, None).await;
+    assert_eq!(limited.status(), 429);
+    assert!(limited.headers().contains_key("retry-after"));
+}
@@ -0,0 +1,192 @@
+//! Scheduler job observability tests
+//!
+//! Covers `/admin/scheduler/jobs`:
+//! - Registered jobs are listed with their schedule and next run
+//! - Jobs can be run by hand and report duration and success counts
+//! - Season game cycles come and go with their season
+//! - Job schedules can be changed by admins without a restart
+
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn get_job(client: &Client, address: &str, token: &str, name: &str) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/admin/scheduler/jobs/{}", address, name), token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+/// Wait for a manually triggered job to finish its first run
+async fn wait_for_run(client: &Client, address: &str, token: &str, name: &str) -> serde_json::Value {
+    for _ in 0..50 {
+        let job = get_job(client, address, token, name).await;
+        if job["last_trigger"] == "manual" && job["in_flight"] == 0 {
+            return job;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    panic!("Job {} did not finish", name);
+}
+
+#[tokio::test]
+async fn scheduler_jobs_can_be_listed_and_run() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let jobs_url = format!("{}/admin/scheduler/jobs", test_app.address);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &jobs_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let jobs = body["data"].as_array().unwrap();
+    for name in ["poll_expiration", "score_consistency", "sync_tombstone_prune", "game_commentary", "broadcasts"] {
+        let job = jobs.iter().find(|job| job["name"] == name).unwrap_or_else(|| panic!("{} is not listed", name));
+        assert!(job["schedule"].is_string());
+        assert!(job["next_run_at"].is_string(), "{} has no next run", name);
+    }
+    let job = jobs.iter().find(|job| job["name"] == "sync_tombstone_prune").unwrap();
+    assert_eq!(job["schedule"], "0 0 4 * * *");
+    assert_eq!(job["success_count"], 0);
+    assert!(job["last_started_at"].is_null());
+
+    // Manual run
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/sync_tombstone_prune/run", jobs_url), &admin.token, None,
+    ).await;
+    assert_eq!(202, response.status().as_u16());
+    let job = wait_for_run(&client, &test_app.address, &admin.token, "sync_tombstone_prune").await;
+    assert_eq!(job["success_count"], 1);
+    assert_eq!(job["failure_count"], 0);
+    assert_eq!(job["last_status"], "succeeded");
+    assert_eq!(job["last_message"], "Pruned 0 sync tombstones");
+    assert!(job["last_duration_ms"].is_number());
+    assert!(job["last_finished_at"].is_string());
+    assert!(job["running_since"].is_null());
+
+    // Unknown jobs and non-admins
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/no_such_job/run", jobs_url), &admin.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/no_such_job", jobs_url), &admin.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let user = create_test_user_and_login(&test_app.address).await;
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &jobs_url, &user.token, None).await;
+    assert_eq!(403, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn season_game_cycle_is_registered_with_its_season() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 2, 2, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Observed Season", &start_date,
+    ).await;
+    let job_name = format!("game_cycle:{}", season_id);
+
+    let job = get_job(&client, &test_app.address, &admin.token, &job_name).await;
+    assert!(job["description"].as_str().unwrap().contains("Observed Season"));
+
+    // A stuck finisher can be re-run by hand
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST,
+        &format!("{}/admin/scheduler/jobs/{}/run", test_app.address, job_name), &admin.token, None,
+    ).await;
+    assert_eq!(202, response.status().as_u16());
+    let job = wait_for_run(&client, &test_app.address, &admin.token, &job_name).await;
+    assert_eq!(job["last_status"], "succeeded");
+    assert!(job["success_count"].as_u64().unwrap() >= 1);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/admin/leagues/{}/seasons/{}", test_app.address, league.league_id, season_id), &admin.token, None,
+    ).await;
+    assert!(response.status().is_success());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET,
+        &format!("{}/admin/scheduler/jobs/{}", test_app.address, job_name), &admin.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn job_schedules_can_be_changed_without_restart() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let schedules_url = format!("{}/admin/scheduler/schedules", test_app.address);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &schedules_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let schedules = body["data"].as_array().unwrap();
+    assert!(schedules.iter().any(|schedule| schedule["job_name"] == "game_cycle"));
+    let original = schedules.iter()
+        .find(|schedule| schedule["job_name"] == "hr_trends")
+        .unwrap()["cron_expression"].as_str().unwrap().to_string();
+
+    // Invalid expressions and unknown jobs are rejected
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &format!("{}/hr_trends", schedules_url), &admin.token,
+        Some(json!({ "cron_expression": "*/5 * * * *" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &format!("{}/no_such_job", schedules_url), &admin.token,
+        Some(json!({ "cron_expression": "0 0 5 * * *" })),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+
+    // The running job moves to the new schedule and keeps its stats
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST,
+        &format!("{}/admin/scheduler/jobs/hr_trends/run", test_app.address), &admin.token, None,
+    ).await;
+    assert_eq!(202, response.status().as_u16());
+    let before = wait_for_run(&client, &test_app.address, &admin.token, "hr_trends").await;
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &format!("{}/hr_trends", schedules_url), &admin.token,
+        Some(json!({ "cron_expression": "0 45 1 * * *" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["cron_expression"], "0 45 1 * * *");
+    assert_eq!(body["data"]["updated_by"], admin.user_id.to_string());
+
+    let job = get_job(&client, &test_app.address, &admin.token, "hr_trends").await;
+    assert_eq!(job["schedule"], "0 45 1 * * *");
+    assert_ne!(job["job_id"], before["job_id"]);
+    assert_eq!(job["success_count"], before["success_count"]);
+    let next_run: DateTime<Utc> = job["next_run_at"].as_str().unwrap().parse().unwrap();
+    assert_eq!(next_run.format("%H:%M:%S").to_string(), "01:45:00");
+
+    // Restore the shared schedule
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &format!("{}/hr_trends", schedules_url), &admin.token,
+        Some(json!({ "cron_expression": original })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/reload", schedules_url), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["failed"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,151 @@
+//! Server-to-server API key tests
+//!
+//! - Scopes cover workouts under /health and the profile, split into reading and writing
+//! - Keys need a name, known scopes and an expiry in the future
+//! - Integrations push workouts with X-Api-Key, are refused outside their scopes and once the key is revoked
+//! - Admin routes stay JWT only
+//! - Keys of inactive users stop working, and deleting the account revokes them
+
+use actix_web::http::Method as HttpMethod;
+use chrono::{Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::models::api_key::{scopes_allow, ApiKeyScope, IssueApiKeyRequest};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{approve_workout_upload, create_health_profile_for_user, WorkoutData, WorkoutIntensity};
+
+fn scopes(scopes: &[ApiKeyScope]) -> Vec<String> {
+    scopes.iter().map(|scope| scope.as_str().to_string()).collect()
+}
+
+fn request(scopes: &[&str], expires_in: Option<Duration>) -> IssueApiKeyRequest {
+    let now = Utc.with_ymd_and_hms(2026, 3, 27, 9, 0, 0).unwrap();
+    IssueApiKeyRequest {
+        user_id: Uuid::new_v4(),
+        name: "Gym backend".to_string(),
+        scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        expires_at: expires_in.map(|expires_in| now + expires_in),
+    }
+}
+
+#[test]
+fn scopes_split_reading_and_writing() {
+    let write = scopes(&[ApiKeyScope::WorkoutsWrite]);
+    assert!(scopes_allow(&write, &HttpMethod::POST, "/health/upload_health"));
+    assert!(!scopes_allow(&write, &HttpMethod::GET, "/health/workout_history"));
+    assert!(!scopes_allow(&write, &HttpMethod::POST, "/league/teams"));
+
+    let profile = scopes(&[ApiKeyScope::ProfileRead, ApiKeyScope::HealthProfileWrite]);
+    assert!(scopes_allow(&profile, &HttpMethod::GET, "/profile/user"));
+    assert!(scopes_allow(&profile, &HttpMethod::PUT, "/profile/health_profile"));
+    assert!(!scopes_allow(&profile, &HttpMethod::POST, "/profile/delete-account"));
+    assert!(!scopes_allow(&["admin".to_string()], &HttpMethod::GET, "/profile/user"));
+}
+
+#[test]
+fn issue_requests_are_validated() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 27, 9, 0, 0).unwrap();
+    assert_eq!(
+        request(&["workouts:write", "workouts:write", "profile:read"], None).validate(now),
+        Ok(vec!["workouts:write".to_string(), "profile:read".to_string()])
+    );
+    assert!(request(&[], None).validate(now).is_err());
+    assert!(request(&["workouts:delete"], None).validate(now).is_err());
+    assert!(request(&["workouts:write"], Some(Duration::hours(-1))).validate(now).is_err());
+    let mut unnamed = request(&["workouts:write"], None);
+    unnamed.name = "  ".to_string();
+    assert!(unnamed.validate(now).is_err());
+}
+
+#[tokio::test]
+async fn integrations_push_workouts_with_api_keys() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+    let keys_url = format!("{}/admin/api-keys", test_app.address);
+
+    let response = make_authenticated_request(&client, Method::POST, &keys_url, &admin.token, Some(json!({
+        "user_id": user.user_id, "name": "Gym backend", "scopes": ["workouts:fly"]
+    }))).await;
+    assert_eq!(response.status(), 400);
+    let response = make_authenticated_request(&client, Method::POST, &keys_url, &admin.token, Some(json!({
+        "user_id": user.user_id, "name": "Gym backend", "scopes": ["workouts:write"]
+    }))).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let key_id = body["data"]["id"].as_str().unwrap().to_string();
+    let key = body["data"]["key"].as_str().unwrap().to_string();
+
+    let response = make_authenticated_request(&client, Method::GET, &format!("{keys_url}?user_id={}", user.user_id), &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"][0]["id"], key_id.as_str());
+    assert!(body["data"][0].get("key").is_none(), "The key is only shown when issued");
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    approve_workout_upload(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+    let upload_url = format!("{}/health/upload_health", test_app.address);
+    let response = client.post(&upload_url).header("X-Api-Key", &key).json(&workout).send().await.unwrap();
+    assert!(response.status().is_success());
+
+    let response = client.get(format!("{}/profile/user", test_app.address)).header("X-Api-Key", &key).send().await.unwrap();
+    assert_eq!(response.status(), 403, "Outside the key's scopes");
+    let response = client.get(&keys_url).header("X-Api-Key", &key).send().await.unwrap();
+    assert_eq!(response.status(), 401, "Admin routes need a JWT");
+
+    let response = make_authenticated_request(&client, Method::DELETE, &format!("{keys_url}/{key_id}"), &admin.token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = client.post(&upload_url).header("X-Api-Key", &key).json(&workout).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn keys_stop_working_for_deactivated_and_deleted_accounts() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let keys_url = format!("{}/admin/api-keys", test_app.address);
+
+    let response = make_authenticated_request(&client, Method::POST, &keys_url, &admin.token, Some(json!({
+        "user_id": user.user_id, "name": "Profile sync", "scopes": ["profile:read"]
+    }))).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let key = body["data"]["key"].as_str().unwrap().to_string();
+    let profile = || client.get(format!("{}/profile/user", test_app.address)).header("X-Api-Key", &key).send();
+    assert_eq!(profile().await.unwrap().status(), 200);
+
+    let set_status = |status: &'static str| sqlx::query("UPDATE users SET status = $2 WHERE id = $1")
+        .bind(user.user_id)
+        .bind(status)
+        .execute(&test_app.db_pool);
+    set_status("inactive").await.unwrap();
+    assert_eq!(profile().await.unwrap().status(), 401);
+    set_status("active").await.unwrap();
+    assert_eq!(profile().await.unwrap().status(), 200);
+
+    let response = make_authenticated_request(
+        &client, Method::POST, &format!("{}/profile/delete-account", test_app.address), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, Method::GET, &format!("{keys_url}?user_id={}", user.user_id), &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(!body["data"][0]["revoked_at"].is_null());
+
+    // Logging in again keeps the account, but not its keys
+    let response = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": user.username, "password": "password123" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(profile().await.unwrap().status(), 401);
+}
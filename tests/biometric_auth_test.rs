@@ -5,7 +5,7 @@ use chrono::{Utc, Duration};
 use uuid::Uuid;
 
 mod common;
-use common::utils::{spawn_app, create_test_user_and_login, delete_test_user};
+use common::utils::{spawn_app, spawn_app_with, create_test_user_and_login, delete_test_user};
 use common::admin_helpers::create_admin_user_and_login;
 use riina_backend::models::user::{UserRole, UserStatus};
 use riina_backend::middleware::auth::Claims;
@@ -23,6 +23,10 @@ fn create_expired_token(user_id: &str, username: &str, secret: &str) -> String {
         role: UserRole::User,
         status: UserStatus::Active,
         exp: expired_time,
+        iss: Some("riina-backend".to_string()),
+        aud: Some("riina-app".to_string()),
+        sid: None,
+        impersonated_by: None,
     };
 
     encode(
@@ -45,6 +49,10 @@ fn create_very_old_expired_token(user_id: &str, username: &str, secret: &str) ->
         role: UserRole::User,
         status: UserStatus::Active,
         exp: very_old_time,
+        iss: Some("riina-backend".to_string()),
+        aud: Some("riina-app".to_string()),
+        sid: None,
+        impersonated_by: None,
     };
 
     encode(
@@ -94,6 +102,92 @@ async fn biometric_refresh_returns_200_for_valid_expired_token() {
     assert_ne!(expired_token, new_token, "New token should be different from expired token");
 }
 
+#[tokio::test]
+async fn biometric_refresh_accepts_tokens_from_before_issuer_checks() {
+    // Arrange
+    let test_app = spawn_app_with(|configuration| {
+        configuration.jwt.legacy_tokens_until = Some(Utc::now() + Duration::days(1));
+    }).await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    // Signed the way tokens were before they carried an issuer and audience
+    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET environment variable must be set");
+    let legacy_claims = |exp: chrono::DateTime<Utc>| Claims {
+        sub: user.user_id.to_string(),
+        username: user.username.clone(),
+        role: UserRole::User,
+        status: UserStatus::Active,
+        exp: exp.timestamp() as usize,
+        iss: None,
+        aud: None,
+        sid: None,
+        impersonated_by: None,
+    };
+    let sign = |claims: &Claims| encode(&Header::default(), claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap();
+
+    // Act & Assert - a current one still authenticates
+    let current = sign(&legacy_claims(Utc::now() + Duration::hours(1)));
+    let response = client
+        .get(format!("{}/profile/user", &test_app.address))
+        .bearer_auth(&current)
+        .send()
+        .await
+        .expect("Failed to execute profile request.");
+    assert_eq!(200, response.status().as_u16(), "Legacy tokens should authenticate during the transition");
+
+    // An expired one is refreshed into a token with issuer and audience
+    let expired = sign(&legacy_claims(Utc::now() - Duration::hours(2)));
+    let response = client
+        .post(format!("{}/biometric-refresh", &test_app.address))
+        .json(&json!({ "token": expired }))
+        .send()
+        .await
+        .expect("Failed to execute biometric refresh request.");
+    assert_eq!(200, response.status().as_u16(), "Legacy tokens should be refreshed during the transition");
+    let response_body = response.json::<serde_json::Value>().await
+        .expect("Failed to parse refresh response as JSON");
+    let new_token = response_body["token"].as_str().expect("No token in response");
+    let payload = new_token.split('.').nth(1).unwrap();
+    let new_claims: serde_json::Value = serde_json::from_slice(
+        &base64::Engine::decode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, payload).unwrap()
+    ).unwrap();
+    assert_eq!(new_claims["iss"], "riina-backend");
+    assert_eq!(new_claims["aud"], "riina-app");
+}
+
+#[tokio::test]
+async fn biometric_refresh_refuses_tokens_from_before_issuer_checks_after_the_transition() {
+    // Arrange
+    let test_app = spawn_app_with(|configuration| configuration.jwt.legacy_tokens_until = None).await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET environment variable must be set");
+    let claims = Claims {
+        sub: user.user_id.to_string(),
+        username: user.username,
+        role: UserRole::User,
+        status: UserStatus::Active,
+        exp: (Utc::now() - Duration::hours(2)).timestamp() as usize,
+        iss: None,
+        aud: None,
+        sid: None,
+        impersonated_by: None,
+    };
+    let expired = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap();
+
+    // Act
+    let response = client
+        .post(format!("{}/biometric-refresh", &test_app.address))
+        .json(&json!({ "token": expired }))
+        .send()
+        .await
+        .expect("Failed to execute biometric refresh request.");
+
+    // Assert
+    assert_eq!(401, response.status().as_u16());
+}
+
 #[tokio::test]
 async fn biometric_refresh_returns_401_for_invalid_token() {
     // Arrange
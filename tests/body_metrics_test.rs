@@ -0,0 +1,138 @@
+//! Body metrics tests
+//!
+//! Covers `/health/metrics` and `/health/analytics/body-metrics`:
+//! - Manual and device entries are stored and listed oldest first, filtered by type
+//! - Device re-syncs with the same external id update the entry instead of duplicating it
+//! - Out-of-range values and unknown types are rejected
+//! - Daily analytics show training load next to the body metrics
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::workout_data_helpers::{
+    WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_test_user_with_health_profile
+};
+
+#[tokio::test]
+async fn body_metrics_are_recorded_listed_and_deduplicated() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let metrics_url = format!("{}/health/metrics", test_app.address);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &metrics_url, &user.token,
+        Some(json!({
+            "entries": [
+                { "metric_type": "weight", "value": 81.5, "recorded_at": (Utc::now() - Duration::days(2)).to_rfc3339() },
+                { "metric_type": "weight", "value": 80.9 },
+                { "metric_type": "hrv", "value": 62.0, "source": "device", "device_id": "watch", "external_id": "hrv-1" }
+            ]
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 3);
+
+    // The device re-sends its sample with a corrected value
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &metrics_url, &user.token,
+        Some(json!({
+            "entries": [{ "metric_type": "hrv", "value": 64.0, "source": "device", "device_id": "watch", "external_id": "hrv-1" }]
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?metric_type=hrv", metrics_url), &user.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let hrv = body["data"].as_array().unwrap();
+    assert_eq!(hrv.len(), 1, "A re-synced device entry should not duplicate");
+    assert_eq!(hrv[0]["value"], 64.0);
+    assert_eq!(hrv[0]["source"], "device");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?metric_type=weight", metrics_url), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let weight = body["data"].as_array().unwrap();
+    assert_eq!(weight.len(), 2);
+    assert_eq!(weight[0]["value"], 81.5, "Entries are listed oldest first");
+    assert_eq!(weight[1]["source"], "manual");
+
+    // Invalid entries
+    for entry in [
+        json!({ "metric_type": "weight", "value": 5.0 }),
+        json!({ "metric_type": "vo2max", "value": 50.0 }),
+        json!({ "metric_type": "resting_hr", "value": 55.0, "source": "manual-ish" }),
+    ] {
+        let response = make_authenticated_request(
+            &client, reqwest::Method::POST, &metrics_url, &user.token, Some(json!({ "entries": [entry] })),
+        ).await;
+        assert_eq!(400, response.status().as_u16());
+    }
+
+    // Entries can only be deleted by their owner
+    let weight_id = weight[1]["id"].as_str().unwrap();
+    let other_user = create_test_user_and_login(&test_app.address).await;
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/{}", metrics_url, weight_id), &other_user.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/{}", metrics_url, weight_id), &user.token, None,
+    ).await;
+    assert_eq!(204, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/{}", metrics_url, Uuid::new_v4()), &user.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn body_metrics_analytics_line_up_with_training_load() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Intense, Utc::now() - Duration::hours(1), 30);
+    workout_data.workout_uuid = Uuid::new_v4().to_string();
+    upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Workout upload should succeed");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/health/metrics", test_app.address), &user.token,
+        Some(json!({
+            "entries": [
+                { "metric_type": "resting_hr", "value": 50.0 },
+                { "metric_type": "resting_hr", "value": 54.0 }
+            ]
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/health/analytics/body-metrics?days=14", test_app.address), &user.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let points = body["data"]["points"].as_array().unwrap();
+    assert_eq!(points.len(), 14);
+
+    let days_with_workouts: Vec<&serde_json::Value> = points.iter()
+        .filter(|p| p["workouts"].as_i64().unwrap() > 0)
+        .collect();
+    assert!(!days_with_workouts.is_empty());
+    assert!(days_with_workouts.iter().all(|p| p["training_load"].as_f64().unwrap() > 0.0));
+
+    let today = points.last().unwrap();
+    assert_eq!(today["resting_hr"], 52.0, "Several entries on one day are averaged");
+    assert!(today["weight"].is_null());
+}
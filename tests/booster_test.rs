@@ -0,0 +1,195 @@
+//! Team booster tests
+//!
+//! - Game wins and full squads earn boosters, up to a limit per kind
+//! - Shields fill the team's weakest game day up to the average of the other days
+//! - Players activate boosters via `/league/games/{id}/boosters`, once per kind and game
+//! - A double booster doubles the team's next workout and every step is audited
+
+use chrono::{Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::game::boosters::{earned_achievements, game_day, game_days, shield_fill, BoosterAchievement};
+use riina_backend::services::BoosterService;
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league_season, create_league_with_teams};
+use common::utils::{create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token, spawn_app, TestApp};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+#[test]
+fn achievements_earn_boosters() {
+    assert_eq!(earned_achievements(true, 3, 2), vec![BoosterAchievement::GameWin]);
+    assert_eq!(earned_achievements(false, 3, 3), vec![BoosterAchievement::FullSquad]);
+    assert_eq!(earned_achievements(true, 2, 2), vec![BoosterAchievement::GameWin, BoosterAchievement::FullSquad]);
+    assert!(earned_achievements(false, 0, 0).is_empty());
+}
+
+#[test]
+fn shield_fills_weakest_day_up_to_average_of_others() {
+    let fill = shield_fill(&[40.0, 5.0, 50.0, 30.0]).unwrap();
+    assert_eq!((fill.day, fill.day_points, fill.bonus_points), (1, 5.0, 35.0));
+
+    // Nothing to fill on an even game, or with a single day
+    assert_eq!(shield_fill(&[20.0, 20.0, 20.0]), None);
+    assert_eq!(shield_fill(&[0.0]), None);
+
+    let start = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+    assert_eq!(game_days(start, start + Duration::days(6)), 6);
+    assert_eq!(game_days(start, start + Duration::days(6) + Duration::hours(1)), 7);
+    assert_eq!(game_day(start, start + Duration::hours(23)), 0);
+    assert_eq!(game_day(start, start + Duration::hours(25)), 1);
+}
+
+struct RunningGame {
+    game_id: Uuid,
+    team_id: Uuid,
+    token: String,
+}
+
+/// A game in progress with a player on the home team, who can upload workouts
+async fn start_game(test_app: &TestApp, admin_token: &str) -> RunningGame {
+    let player = create_test_user_with_health_profile(&test_app.address).await;
+    let opponent = create_test_user_and_login(&test_app.address).await;
+    let player_id = parse_user_id_from_jwt_token(&player.token);
+    let opponent_id = parse_user_id_from_jwt_token(&opponent.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, admin_token, 2, 2, Some(vec![player_id, opponent_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(&test_app.address, admin_token, &league.league_id, "Booster Season", &start_date).await;
+
+    let game_start = Utc::now() - Duration::hours(3);
+    let game = sqlx::query!(
+        r#"
+        UPDATE games
+        SET status = 'in_progress', game_start_time = $2, game_end_time = $3
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+        RETURNING id
+        "#,
+        Uuid::parse_str(&season_id).unwrap(),
+        game_start,
+        game_start + Duration::days(7)
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to start game");
+
+    // Workouts only count from when a player joined
+    sqlx::query!(
+        "UPDATE team_members SET joined_at = $2 WHERE user_id = ANY($1)",
+        &[player_id, opponent_id],
+        game_start - Duration::days(1)
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    RunningGame {
+        game_id: game.id,
+        team_id: Uuid::parse_str(&league.team_ids[0]).unwrap(),
+        token: player.token,
+    }
+}
+
+async fn grant_booster(test_app: &TestApp, team_id: Uuid, booster_type: &str) {
+    sqlx::query!(
+        "INSERT INTO team_boosters (team_id, booster_type, earned_for) VALUES ($1, $2, 'game_win')",
+        team_id,
+        booster_type
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn activate(test_app: &TestApp, game_id: Uuid, token: &str, booster_type: &str) -> reqwest::Response {
+    make_authenticated_request(
+        &Client::new(),
+        Method::POST,
+        &format!("{}/league/games/{}/boosters", test_app.address, game_id),
+        token,
+        Some(json!({ "booster_type": booster_type })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn double_booster_doubles_next_workout() {
+    let test_app = spawn_app().await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let game = start_game(&test_app, &admin.token).await;
+    grant_booster(&test_app, game.team_id, "double_next_workout").await;
+
+    // Not a player of the game
+    let outsider = create_test_user_and_login(&test_app.address).await;
+    assert_eq!(activate(&test_app, game.game_id, &outsider.token, "double_next_workout").await.status().as_u16(), 403);
+    // None held
+    assert_eq!(activate(&test_app, game.game_id, &game.token, "shield").await.status().as_u16(), 409);
+
+    assert_eq!(activate(&test_app, game.game_id, &game.token, "double_next_workout").await.status().as_u16(), 201);
+    // Once per kind and game
+    grant_booster(&test_app, game.team_id, "double_next_workout").await;
+    assert_eq!(activate(&test_app, game.game_id, &game.token, "double_next_workout").await.status().as_u16(), 409);
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    upload_workout_data_for_user(&Client::new(), &test_app.address, &game.token, &mut workout)
+        .await
+        .expect("Upload failed");
+
+    let event = sqlx::query!(
+        "SELECT score_points, stamina_gained, strength_gained, booster_bonus, description FROM live_score_events WHERE game_id = $1",
+        game.game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    let workout_points = event.stamina_gained + event.strength_gained;
+    assert!(workout_points > 0.0);
+    assert_eq!(event.booster_bonus, workout_points);
+    assert_eq!(event.score_points, workout_points * 2.0);
+    assert!(event.description.contains("2x booster"));
+
+    let response = make_authenticated_request(
+        &Client::new(),
+        Method::GET,
+        &format!("{}/league/games/{}/boosters", test_app.address, game.game_id),
+        &game.token,
+        None,
+    )
+    .await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["boosters"][0]["status"], "consumed");
+    assert_eq!(body["data"]["inventory"][0]["booster_type"], "double_next_workout");
+    assert_eq!(body["data"]["inventory"][0]["available"], 1);
+
+    let audit = BoosterService::new(test_app.db_pool.clone(), None).audit_trail(game.team_id).await.unwrap();
+    let actions: Vec<&str> = audit.iter().rev().map(|entry| entry.action.as_str()).collect();
+    assert_eq!(actions, vec!["activated", "applied"]);
+}
+
+#[tokio::test]
+async fn evaluated_games_award_boosters_once() {
+    let test_app = spawn_app().await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let game = start_game(&test_app, &admin.token).await;
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    upload_workout_data_for_user(&Client::new(), &test_app.address, &game.token, &mut workout)
+        .await
+        .expect("Upload failed");
+
+    // The only member of the winning team scored: a game win and a full squad
+    let service = BoosterService::new(test_app.db_pool.clone(), None);
+    let awarded = service.award_for_game(game.game_id, Some(game.team_id)).await.unwrap();
+    let mut kinds: Vec<&str> = awarded.iter().map(|b| b.booster_type.as_str()).collect();
+    kinds.sort();
+    assert_eq!(kinds, vec!["double_next_workout", "shield"]);
+
+    // Evaluating again awards nothing new
+    assert!(service.award_for_game(game.game_id, Some(game.team_id)).await.unwrap().is_empty());
+}
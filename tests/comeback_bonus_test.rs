@@ -0,0 +1,74 @@
+//! Comeback bonus tests
+//!
+//! - Only the trailing team earns the bonus, and only in the final quarter of the game
+//! - The bonus is explained in the score event description
+//! - Leagues configure the multiplier through the admin API; 1.0 disables it
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+
+use riina_backend::game::comeback_bonus::{comeback_bonus, score_event_description, ComebackBonus};
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league};
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn game_start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap()
+}
+
+fn game_end() -> DateTime<Utc> {
+    game_start() + Duration::days(4)
+}
+
+#[test]
+fn trailing_team_earns_bonus_in_final_quarter() {
+    let final_day = game_start() + Duration::days(3) + Duration::hours(1);
+
+    let bonus = comeback_bonus(Some(1.2), game_start(), game_end(), final_day, 40, 100, 50.0);
+    assert_eq!(bonus, Some(ComebackBonus { multiplier: 1.2, bonus_points: 10.0 }));
+
+    // Leading or level teams get nothing
+    assert_eq!(comeback_bonus(Some(1.2), game_start(), game_end(), final_day, 100, 40, 50.0), None);
+    assert_eq!(comeback_bonus(Some(1.2), game_start(), game_end(), final_day, 40, 40, 50.0), None);
+
+    // Before the final quarter
+    let third_day = game_start() + Duration::days(2) + Duration::hours(23);
+    assert_eq!(comeback_bonus(Some(1.2), game_start(), game_end(), third_day, 40, 100, 50.0), None);
+
+    // Leagues without the rule
+    assert_eq!(comeback_bonus(None, game_start(), game_end(), final_day, 40, 100, 50.0), None);
+}
+
+#[test]
+fn bonus_is_explained_in_the_score_event() {
+    let description = score_event_description(30.0, 20.0, Some(ComebackBonus { multiplier: 1.2, bonus_points: 10.0 }));
+    assert_eq!(
+        description,
+        "Workout completed: +30 stamina, +20 strength (comeback bonus ×1.2: +10 points for the trailing team)"
+    );
+    assert_eq!(score_event_description(30.0, 20.0, None), "Workout completed: +30 stamina, +20 strength");
+}
+
+#[tokio::test]
+async fn admin_configures_comeback_multiplier() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let league_id = create_league(&test_app.address, &admin.token, 4).await;
+    let url = format!("{}/admin/leagues/{}", test_app.address, league_id);
+
+    let response = make_authenticated_request(&client, Method::PATCH, &url, &admin.token, Some(json!({ "comeback_multiplier": 1.25 }))).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["comeback_multiplier"], 1.25);
+
+    let response = make_authenticated_request(&client, Method::PATCH, &url, &admin.token, Some(json!({ "comeback_multiplier": 2.0 }))).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = make_authenticated_request(&client, Method::PATCH, &url, &admin.token, Some(json!({ "comeback_multiplier": 1.0 }))).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["comeback_multiplier"].is_null());
+}
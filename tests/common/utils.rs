@@ -10,8 +10,8 @@ use chrono::{DateTime, Datelike, Duration, Utc, Weekday, NaiveTime};
 use reqwest::Response;
 
 use riina_backend::run;
-use riina_backend::config::settings::{get_config, DatabaseSettings, get_jwt_settings};
-use riina_backend::services::{SchedulerService, MinIOService, telemetry::{get_subscriber, init_subscriber}, MLClient};
+use riina_backend::config::settings::{get_config, DatabaseSettings, Settings, get_jwt_settings};
+use riina_backend::services::{SchedulerService, MinIOService, telemetry::{get_subscriber, init_subscriber}, MLClient, EmailService, OAuthService};
 use riina_backend::config::redis::RedisSettings;
 use std::sync::Arc;
 
@@ -50,6 +50,11 @@ pub struct TestApp{
 }
 
 pub async fn spawn_app() -> TestApp {
+    spawn_app_with(|_| {}).await
+}
+
+/// Spawn an app with its configuration changed, for tests of settings the shared defaults turn off
+pub async fn spawn_app_with(configure: impl FnOnce(&mut Settings)) -> TestApp {
     // The first time `initialize` is invoked the code in `TRACING` is executed.
     // All other invocations will instead skip execution.
     Lazy::force(&TRACING);
@@ -61,6 +66,10 @@ pub async fn spawn_app() -> TestApp {
     let address = format!("http://127.0.0.1:{}", port);
     let mut configuration = get_config().expect("Failed to read configuration.");
     configuration.database.db_name = Uuid::new_v4().to_string();
+    // Every test logs in from localhost, so only limit accounts, never the address
+    configuration.login_protection.max_failed_attempts_per_ip = u32::MAX;
+    configuration.login_protection.max_email_requests_per_ip = u32::MAX;
+    configure(&mut configuration);
     let connection_pool = configure_db(&configuration.database)
         .await;
     let jwt_settings = get_jwt_settings(&configuration);
@@ -88,7 +97,14 @@ pub async fn spawn_app() -> TestApp {
         redis_client_arc,
         scheduler_service,
         minio_service,
-        ml_client
+        ml_client,
+        configuration.upload_limits.clone(),
+        EmailService::new(configuration.email.clone()),
+        OAuthService::new(configuration.oauth.clone()),
+        configuration.cors.clone(),
+        configuration.login_protection.clone(),
+        configuration.password_policy.clone(),
+        configuration.database.connection_string()
     )
         .expect("Failed to bind address");
     // Launch the server as a background task
@@ -228,6 +228,32 @@ pub async fn upload_workout_data_for_user(
     token: &str,
     workout_data: &mut WorkoutData,
 ) -> Result<serde_json::Value, String> {
+    approve_workout_upload(client, app_address, token, workout_data).await?;
+    let response = crate::common::utils::make_authenticated_request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/health/upload_health", app_address),
+        token,
+        Some(json!(workout_data)),
+    ).await;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response.text().await.map_err(|e| e.to_string())?;
+        return Err(format!("Health data upload failed with status {}: {}", status, error_body));
+    }
+
+    let response_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    Ok(response_data)
+}
+
+/// Run the sync check for a workout and store the approval token the upload needs
+pub async fn approve_workout_upload(
+    client: &reqwest::Client,
+    app_address: &str,
+    token: &str,
+    workout_data: &mut WorkoutData,
+) -> Result<(), String> {
     let workout_sync_request = WorkoutSyncRequest {
         start: workout_data.workout_start,
         end: workout_data.workout_end,
@@ -263,22 +289,7 @@ pub async fn upload_workout_data_for_user(
             }
         }
     }
-    let response = crate::common::utils::make_authenticated_request(
-        client,
-        reqwest::Method::POST,
-        &format!("{}/health/upload_health", app_address),
-        token,
-        Some(json!(workout_data)),
-    ).await;
-
-    let status = response.status();
-    if !status.is_success() {
-        let error_body = response.text().await.map_err(|e| e.to_string())?;
-        return Err(format!("Health data upload failed with status {}: {}", status, error_body));
-    }
-
-    let response_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
-    Ok(response_data)
+    Ok(())
 }
 
 pub async fn create_test_user_with_health_profile(app_address: &str) -> UserRegLoginResponse {
@@ -0,0 +1,97 @@
+//! CORS origin settings tests
+//!
+//! Covers the configurable list of allowed browser origins:
+//! - The defaults keep the origins that used to be hard-coded
+//! - Exact entries match only that origin, ignoring case
+//! - Wildcard entries match any subdomain with the same scheme and port, but not the bare domain
+//! - Malformed entries are rejected by validation
+//! - Preflight requests are answered for allowed origins and refused for others
+
+use reqwest::Client;
+
+use riina_backend::config::cors::CorsSettings;
+
+mod common;
+use common::utils::spawn_app;
+
+fn settings(origins: &[&str]) -> CorsSettings {
+    CorsSettings {
+        allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+    }
+}
+
+#[test]
+fn defaults_keep_the_deployed_origins() {
+    let cors = CorsSettings::default();
+
+    assert!(cors.allows_origin("http://localhost:3000"));
+    assert!(cors.allows_origin("https://riina.fly.dev"));
+    assert!(cors.allows_origin("https://evolveme-admin-dev.fly.dev"));
+    assert!(!cors.allows_origin("https://evil.fly.dev"));
+    assert!(cors.validate().is_ok());
+}
+
+#[test]
+fn exact_origins_match_only_themselves() {
+    let cors = settings(&["https://riina.app"]);
+
+    assert!(cors.allows_origin("https://riina.app"));
+    assert!(cors.allows_origin("HTTPS://Riina.App"));
+    assert!(!cors.allows_origin("http://riina.app"));
+    assert!(!cors.allows_origin("https://riina.app:8443"));
+    assert!(!cors.allows_origin("https://staging.riina.app"));
+}
+
+#[test]
+fn wildcard_origins_match_subdomains_only() {
+    let cors = settings(&["https://*.riina.app"]);
+
+    assert!(cors.allows_origin("https://staging.riina.app"));
+    assert!(cors.allows_origin("https://pr-42.preview.riina.app"));
+    assert!(!cors.allows_origin("https://riina.app"));
+    assert!(!cors.allows_origin("https://evilriina.app"));
+    assert!(!cors.allows_origin("http://staging.riina.app"));
+    assert!(!cors.allows_origin("https://staging.riina.app:8443"));
+    assert!(!cors.allows_origin("https://evil.com/.riina.app"));
+}
+
+#[test]
+fn wildcard_origins_keep_the_port() {
+    let cors = settings(&["http://*.localhost:3000"]);
+
+    assert!(cors.allows_origin("http://admin.localhost:3000"));
+    assert!(!cors.allows_origin("http://admin.localhost:3001"));
+}
+
+#[test]
+fn malformed_origins_fail_validation() {
+    assert!(settings(&["riina.app"]).validate().is_err());
+    assert!(settings(&["https://riina.app/"]).validate().is_err());
+    assert!(settings(&["https://*"]).validate().is_err());
+    assert!(settings(&["https://staging.*.riina.app"]).validate().is_err());
+    assert!(settings(&["https://*.riina.app", "http://localhost:3000"]).validate().is_ok());
+}
+
+#[tokio::test]
+async fn preflight_is_answered_for_configured_origins_only() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+
+    let preflight = |origin: &'static str| {
+        client
+            .request(reqwest::Method::OPTIONS, format!("{}/backend_health", test_app.address))
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "GET")
+            .send()
+    };
+
+    let allowed = preflight("http://localhost:3000").await.expect("Failed to execute request");
+    assert!(allowed.status().is_success());
+    assert_eq!(
+        allowed.headers().get("access-control-allow-origin").and_then(|v| v.to_str().ok()),
+        Some("http://localhost:3000")
+    );
+
+    let refused = preflight("https://not-riina.example").await.expect("Failed to execute request");
+    assert!(refused.headers().get("access-control-allow-origin").is_none());
+}
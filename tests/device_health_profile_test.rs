@@ -0,0 +1,129 @@
+//! Device health profile import tests
+//!
+//! Covers onboarding users with the health values their device knows:
+//! - registering with a device profile creates the health profile with computed VT thresholds
+//! - without a measured max heart rate it is estimated from age and gender
+//! - importing on the first sync works once; profiles that exist already are left alone
+//! - values out of range are refused
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::models::profile::DeviceHealthProfile;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, generate_valid_username_suffix, make_authenticated_request};
+
+#[test]
+fn device_profile_validation_uses_profile_limits() {
+    assert!(DeviceHealthProfile::default().validate().is_ok());
+
+    let valid = DeviceHealthProfile {
+        age: Some(34),
+        resting_heart_rate: Some(52),
+        max_heart_rate: Some(188),
+        weight: Some(70.5),
+        ..Default::default()
+    };
+    assert!(valid.validate().is_ok());
+
+    let max_below_resting = DeviceHealthProfile {
+        resting_heart_rate: Some(110),
+        max_heart_rate: Some(105),
+        ..Default::default()
+    };
+    assert!(max_below_resting.validate().is_err());
+
+    let too_old = DeviceHealthProfile { age: Some(130), ..Default::default() };
+    assert!(too_old.validate().is_err());
+}
+
+#[tokio::test]
+async fn registering_with_device_profile_sets_up_health_profile() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let username = format!("device_user_{}", generate_valid_username_suffix());
+
+    let response = client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({
+            "username": username,
+            "password": "password123",
+            "email": format!("{}@example.com", username),
+            "device_profile": {
+                "age": 34,
+                "gender": "female",
+                "weight": 62.0,
+                "resting_heart_rate": 50,
+                "max_heart_rate": 190
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let (resting, max_hr, vt1, vt2, weight): (i32, i32, Option<i32>, Option<i32>, Option<f32>) = sqlx::query_as(
+        "SELECT p.resting_heart_rate, p.max_heart_rate, p.vt1_threshold, p.vt2_threshold, p.weight
+         FROM user_health_profiles p JOIN users u ON u.id = p.user_id
+         WHERE u.username = $1"
+    )
+    .bind(&username)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(resting, 50);
+    assert_eq!(max_hr, 190);
+    assert_eq!(weight, Some(62.0));
+    let (vt1, vt2) = (vt1.unwrap(), vt2.unwrap());
+    assert!(resting < vt1 && vt1 < vt2 && vt2 < max_hr);
+
+    // Out of range values fail the registration as a whole
+    let other = format!("device_user_{}", generate_valid_username_suffix());
+    let response = client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({
+            "username": other,
+            "password": "password123",
+            "email": format!("{}@example.com", other),
+            "device_profile": { "resting_heart_rate": 5 }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn first_sync_imports_device_profile_once() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let url = format!("{}/profile/health_profile/import", test_app.address);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &url, &user.token,
+        Some(json!({ "age": 30, "gender": "male", "resting_heart_rate": 60 })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["age"], 30);
+    assert_eq!(body["data"]["resting_heart_rate"], 60);
+    // Estimated from age and gender, not the default
+    let max_hr = body["data"]["max_heart_rate"].as_i64().unwrap();
+    assert!(max_hr > 60 && max_hr != 190);
+    assert!(body["data"]["vt0_threshold"].is_number());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &url, &user.token,
+        Some(json!({ "age": 50, "resting_heart_rate": 70 })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+
+    let (age,): (Option<i32>,) = sqlx::query_as("SELECT age FROM user_health_profiles WHERE user_id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(age, Some(30));
+}
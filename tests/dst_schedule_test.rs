@@ -0,0 +1,131 @@
+//! Daylight-saving schedule tests
+//!
+//! Covers schedules of leagues outside UTC:
+//! - Kickoffs stay at the same local time when a season spans a DST change
+//! - Leagues only accept IANA timezones
+//! - The `schedule_rematerialization` job moves upcoming games back to their local kickoff
+
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+
+const LEAGUE_TIMEZONE: Tz = chrono_tz::Europe::Berlin;
+
+/// First day after `from` on which the league timezone changes its UTC offset
+fn next_dst_change(from: NaiveDate) -> NaiveDate {
+    let offset_at_noon = |date: NaiveDate| {
+        LEAGUE_TIMEZONE
+            .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .naive_utc()
+    };
+    let mut date = from;
+    loop {
+        let next = date.succ_opt().unwrap();
+        if offset_at_noon(next) - offset_at_noon(date) != Duration::days(1) {
+            return next;
+        }
+        date = next;
+    }
+}
+
+async fn season_games(pool: &sqlx::PgPool, season_id: Uuid) -> Vec<(i32, DateTime<Utc>, DateTime<Utc>)> {
+    sqlx::query!(
+        r#"
+        SELECT week_number, game_start_time AS "game_start_time!", game_end_time AS "game_end_time!"
+        FROM games WHERE season_id = $1 ORDER BY week_number
+        "#,
+        season_id
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|game| (game.week_number, game.game_start_time, game.game_end_time))
+    .collect()
+}
+
+#[tokio::test]
+async fn kickoffs_keep_their_local_time_across_dst_changes() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 4, 4, None, true, None, None).await;
+    let league_url = format!("{}/admin/leagues/{}", test_app.address, league.league_id);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token, Some(json!({ "timezone": "Mars/Olympus" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token, Some(json!({ "timezone": "Europe/Berlin" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["timezone"], "Europe/Berlin");
+
+    // Weekly games at 19:00 Berlin time, with the clocks changing during week 1
+    let dst_change = next_dst_change(Utc::now().date_naive() + Duration::days(4));
+    let local_start = (dst_change - Duration::days(3)).and_hms_opt(19, 0, 0).unwrap();
+    let start_date = LEAGUE_TIMEZONE.from_local_datetime(&local_start).unwrap().with_timezone(&Utc);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/seasons", league_url), &admin.token,
+        Some(json!({
+            "name": "DST Season",
+            "start_date": start_date.to_rfc3339(),
+            "game_duration_seconds": 604800,
+            "auto_evaluation_enabled": false
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let season_id = Uuid::parse_str(body["data"]["id"].as_str().unwrap()).unwrap();
+
+    let games = season_games(&test_app.db_pool, season_id).await;
+    assert_eq!(games.len(), 6);
+    for (week, start, end) in &games {
+        assert_eq!(start.with_timezone(&LEAGUE_TIMEZONE).hour(), 19, "Week {} kicks off at {}", week, start);
+        assert_eq!(end.with_timezone(&LEAGUE_TIMEZONE).hour(), 19, "Week {} ends at {}", week, end);
+    }
+    let week_starts: Vec<DateTime<Utc>> = games.iter().map(|(_, start, _)| *start).collect();
+    let second_week = week_starts.iter().find(|start| **start > week_starts[0]).unwrap();
+    assert_ne!(*second_week - week_starts[0], Duration::days(7), "The week with the DST change is an hour shorter or longer");
+
+    // Games materialized under outdated timezone rules move back to their local kickoff
+    sqlx::query!(
+        r#"
+        UPDATE games
+        SET game_start_time = game_start_time + INTERVAL '1 hour', game_end_time = game_end_time + INTERVAL '1 hour'
+        WHERE season_id = $1 AND week_number = 3
+        "#,
+        season_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST,
+        &format!("{}/admin/scheduler/jobs/schedule_rematerialization/run", test_app.address), &admin.token, None,
+    ).await;
+    assert_eq!(202, response.status().as_u16());
+
+    for _ in 0..50 {
+        if season_games(&test_app.db_pool, season_id).await == games {
+            return;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    panic!("Week 3 was not moved back to its local kickoff");
+}
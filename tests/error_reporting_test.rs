@@ -0,0 +1,69 @@
+//! Error reporting tests
+//!
+//! - Nothing is reported without a DSN
+//! - Error logs are reported with their `tags.` fields as tags, other logs become breadcrumbs
+//! - Failed scheduler jobs are reported, tagged with the job
+//! - Reports of a request carry the user making it
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+use riina_backend::config::error_reporting::ErrorReportingSettings;
+use riina_backend::services::error_reporting::{error_reporting_layer, init_error_reporting, set_reporting_user};
+use riina_backend::services::job_registry::{JobRegistry, JobRunner};
+
+fn captured<F: FnOnce()>(f: F) -> Vec<sentry::protocol::Event<'static>> {
+    sentry::test::with_captured_events(|| {
+        let subscriber = Registry::default().with(error_reporting_layer());
+        tracing::subscriber::with_default(subscriber, f);
+    })
+}
+
+#[test]
+fn nothing_is_reported_without_a_dsn() {
+    assert!(init_error_reporting(&ErrorReportingSettings::default()).is_none());
+}
+
+#[test]
+fn error_logs_are_reported_with_their_tags() {
+    let events = captured(|| {
+        tracing::info!("Evaluating games");
+        tracing::error!(tags.game_id = "42", "Failed to update game 42");
+    });
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tags.get("game_id").map(String::as_str), Some("42"));
+    assert_eq!(events[0].breadcrumbs.len(), 1);
+}
+
+#[test]
+fn failed_jobs_are_reported_with_the_job() {
+    let registry = JobRegistry::new();
+    let runner: JobRunner = Arc::new(|| Box::pin(async { Err("Redis unavailable".to_string()) }));
+    registry.register("event_outbox", "0 * * * * *", "Publish outbox events", runner).unwrap();
+
+    let events = captured(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let result = runtime.block_on(registry.run("event_outbox", "manual"));
+        assert_eq!(result, Some(Err("Redis unavailable".to_string())));
+    });
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tags.get("job").map(String::as_str), Some("event_outbox"));
+    assert_eq!(events[0].tags.get("job_trigger").map(String::as_str), Some("manual"));
+    assert!(events[0].message.as_deref().unwrap_or_default().contains("Redis unavailable"));
+}
+
+#[test]
+fn reports_carry_the_user() {
+    let events = captured(|| {
+        set_reporting_user("4969b8b9-d07b-4215-b4d2-23ac237262c5", "ann");
+        tracing::error!("Failed to upload workout at {}", Utc::now());
+    });
+
+    let user = events[0].user.as_ref().unwrap();
+    assert_eq!(user.username.as_deref(), Some("ann"));
+}
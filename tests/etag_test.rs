@@ -0,0 +1,117 @@
+//! Conditional GET tests
+//!
+//! Covers ETag / If-None-Match on standings, schedule and profile:
+//! - Responses carry an ETag
+//! - A matching If-None-Match gets 304 with no body
+//! - The ETag changes once the underlying data changes
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn conditional_get(client: &Client, url: &str, token: &str, etag: Option<&str>) -> reqwest::Response {
+    let mut request = client.get(url).header("Authorization", format!("Bearer {}", token));
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+    request.send().await.expect("Failed to execute request")
+}
+
+fn etag_of(response: &reqwest::Response) -> String {
+    response.headers().get("etag").expect("Response should carry an ETag").to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn standings_and_schedule_honor_if_none_match() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner_a = create_test_user_and_login(&test_app.address).await;
+    let owner_b = create_test_user_and_login(&test_app.address).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![parse_user_id_from_jwt_token(&owner_a.token), parse_user_id_from_jwt_token(&owner_b.token)]),
+        true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "ETag Season", &start_date,
+    ).await;
+
+    let standings_url = format!("{}/league/seasons/{}/standings", test_app.address, season_id);
+    let response = conditional_get(&client, &standings_url, &owner_a.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let etag = etag_of(&response);
+
+    // Unchanged standings: 304 without a body
+    let response = conditional_get(&client, &standings_url, &owner_a.token, Some(&etag)).await;
+    assert_eq!(304, response.status().as_u16());
+    assert_eq!(etag_of(&response), etag);
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    // Weak and listed tags match too
+    let response = conditional_get(&client, &standings_url, &owner_a.token, Some(&format!("\"other\", W/{}", etag))).await;
+    assert_eq!(304, response.status().as_u16());
+
+    // Standings change: full response with a new tag
+    sqlx::query("UPDATE league_standings SET wins = wins + 1, games_played = games_played + 1 WHERE season_id = $1 AND team_id = $2")
+        .bind(Uuid::parse_str(&season_id).unwrap())
+        .bind(Uuid::parse_str(&league.team_ids[0]).unwrap())
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to update standings");
+
+    let response = conditional_get(&client, &standings_url, &owner_a.token, Some(&etag)).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_ne!(etag_of(&response), etag);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["success"], true);
+
+    // Schedule
+    let schedule_url = format!("{}/league/seasons/{}/schedule", test_app.address, season_id);
+    let response = conditional_get(&client, &schedule_url, &owner_a.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let etag = etag_of(&response);
+    let response = conditional_get(&client, &schedule_url, &owner_a.token, Some(&etag)).await;
+    assert_eq!(304, response.status().as_u16());
+
+    // Errors are passed through untouched
+    let response = conditional_get(
+        &client,
+        &format!("{}/league/seasons/{}/schedule", test_app.address, Uuid::new_v4()),
+        &owner_a.token,
+        Some("*"),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    assert!(response.headers().get("etag").is_none());
+}
+
+#[tokio::test]
+async fn profile_honors_if_none_match() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let profile_url = format!("{}/profile/user", test_app.address);
+
+    let response = conditional_get(&client, &profile_url, &user.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let etag = etag_of(&response);
+
+    let response = conditional_get(&client, &profile_url, &user.token, Some(&etag)).await;
+    assert_eq!(304, response.status().as_u16());
+
+    sqlx::query("UPDATE users SET profile_picture_url = 'https://example.com/new.png', updated_at = NOW() WHERE id = $1")
+        .bind(parse_user_id_from_jwt_token(&user.token))
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to update user");
+
+    let response = conditional_get(&client, &profile_url, &user.token, Some(&etag)).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_ne!(etag_of(&response), etag);
+}
@@ -0,0 +1,85 @@
+//! Fault injection tests, built with `--features fault-injection`
+//!
+//! Covers `/admin/fault-injection`:
+//! - Failure rates and delays are validated
+//! - Uploads still score with Redis publishes failing and the ML service down; their events wait
+//!   in the outbox and go out once Redis is back
+
+#![cfg(feature = "fault-injection")]
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use secrecy::ExposeSecret;
+use serde_json::json;
+
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use riina_backend::services::event_outbox::EventOutbox;
+use riina_backend::services::fault_injection::FaultInjectionSettings;
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{create_health_profile_for_user, upload_workout_data_for_user, WorkoutData, WorkoutIntensity};
+
+#[test]
+fn injected_faults_are_bounded() {
+    let faults = |redis_publish_failure_rate: f64, minio_delay_ms: u64, ml_drop_rate: f64| FaultInjectionSettings {
+        redis_publish_failure_rate,
+        minio_delay_ms,
+        ml_drop_rate,
+    };
+    assert!(faults(0.0, 0, 0.0).validate().is_ok());
+    assert!(faults(1.0, 60_000, 0.5).validate().is_ok());
+    assert!(faults(1.5, 0, 0.0).validate().is_err());
+    assert!(faults(0.0, 0, -0.1).validate().is_err());
+    assert!(faults(0.0, 60_001, 0.0).validate().is_err());
+}
+
+#[tokio::test]
+async fn score_events_survive_dependency_failures() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+    let url = format!("{}/admin/fault-injection", test_app.address);
+
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(json!({ "ml_drop_rate": 2.0 }))).await;
+    assert_eq!(response.status(), 400);
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(json!({
+        "redis_publish_failure_rate": 1.0,
+        "ml_drop_rate": 1.0
+    }))).await;
+    assert_eq!(response.status(), 200);
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Intense, Utc::now() - Duration::hours(1), 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await;
+    assert!(upload.is_ok(), "Uploads degrade gracefully: {upload:?}");
+
+    let unpublished: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM event_outbox WHERE published_at IS NULL AND payload->>'user_id' = $1"
+    )
+    .bind(user.user_id.to_string())
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(unpublished, 1, "The event waits in the outbox instead of getting lost");
+
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(json!({}))).await;
+    assert_eq!(response.status(), 200);
+    let configuration = get_config().expect("Failed to read configuration.");
+    let redis_client = Arc::new(redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret()).unwrap());
+    let outbox = EventOutbox::new(test_app.db_pool.clone(), Some(redis_client));
+    outbox.publish_pending().await.unwrap();
+    let unpublished: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM event_outbox WHERE published_at IS NULL AND payload->>'user_id' = $1"
+    )
+    .bind(user.user_id.to_string())
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(unpublished, 0);
+}
@@ -0,0 +1,122 @@
+//! Feed summary tests
+//!
+//! Covers the trigger-maintained workout summaries the feed reads its counts from:
+//! - Reactions and comments, including replies, are counted as they are added and removed
+//! - Reacting twice does not count the reaction twice
+//! - The feed reports the summary counts and the user's own reaction
+
+use reqwest::Client;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, UserRegLoginResponse};
+use common::social_helpers::create_user_with_workout;
+
+async fn summary_counts(pool: &PgPool, workout_id: Uuid) -> Option<(i64, i64)> {
+    sqlx::query!(
+        "SELECT reaction_count, comment_count FROM feed_workout_summaries WHERE workout_id = $1",
+        workout_id
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap()
+    .map(|row| (row.reaction_count, row.comment_count))
+}
+
+async fn feed_post(client: &Client, address: &str, user: &UserRegLoginResponse, workout_id: Uuid) -> serde_json::Value {
+    let response = client
+        .get(format!("{}/feed/?limit=50&sort_by=chronological", address))
+        .header("Authorization", format!("Bearer {}", user.token))
+        .send()
+        .await
+        .expect("Failed to get newsfeed");
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]["posts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["workout_id"] == workout_id.to_string())
+        .cloned()
+        .expect("Workout post should be in the feed")
+}
+
+async fn react(client: &Client, address: &str, user: &UserRegLoginResponse, workout_id: Uuid) {
+    let response = client
+        .post(format!("{}/social/workouts/{}/reactions", address, workout_id))
+        .header("Authorization", format!("Bearer {}", user.token))
+        .json(&json!({ "reaction_type": "fire" }))
+        .send()
+        .await
+        .expect("Failed to add reaction");
+    assert!(response.status().is_success());
+}
+
+async fn comment(client: &Client, address: &str, user: &UserRegLoginResponse, workout_id: Uuid, parent_id: Option<&str>) -> String {
+    let response = client
+        .post(format!("{}/social/workouts/{}/comments", address, workout_id))
+        .header("Authorization", format!("Bearer {}", user.token))
+        .json(&json!({ "content": "Strong finish!", "parent_id": parent_id }))
+        .send()
+        .await
+        .expect("Failed to add comment");
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn feed_counts_follow_reactions_and_comments() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (owner, workout_id) = create_user_with_workout(&test_app.address).await;
+    let fan = create_test_user_and_login(&test_app.address).await;
+    let other_fan = create_test_user_and_login(&test_app.address).await;
+
+    assert_eq!(summary_counts(&test_app.db_pool, workout_id).await, None);
+
+    react(&client, &test_app.address, &fan, workout_id).await;
+    react(&client, &test_app.address, &other_fan, workout_id).await;
+    // Reacting again keeps a single reaction
+    react(&client, &test_app.address, &fan, workout_id).await;
+    let parent_id = comment(&client, &test_app.address, &fan, workout_id, None).await;
+    comment(&client, &test_app.address, &owner, workout_id, Some(&parent_id)).await;
+    assert_eq!(summary_counts(&test_app.db_pool, workout_id).await, Some((2, 2)));
+
+    let post = feed_post(&client, &test_app.address, &fan, workout_id).await;
+    assert_eq!(post["reaction_count"], 2);
+    assert_eq!(post["comment_count"], 2);
+    assert_eq!(post["user_has_reacted"], true);
+    assert!(post["workout_data"]["workout_start"].is_string());
+
+    // Removing the reaction and the parent comment (with its reply) counts them down again
+    let response = client
+        .delete(format!("{}/social/workouts/{}/reactions", test_app.address, workout_id))
+        .header("Authorization", format!("Bearer {}", fan.token))
+        .send()
+        .await
+        .expect("Failed to remove reaction");
+    assert!(response.status().is_success());
+    let response = client
+        .delete(format!("{}/social/comments/{}", test_app.address, parent_id))
+        .header("Authorization", format!("Bearer {}", fan.token))
+        .send()
+        .await
+        .expect("Failed to delete comment");
+    assert!(response.status().is_success());
+    assert_eq!(summary_counts(&test_app.db_pool, workout_id).await, Some((1, 0)));
+
+    let post = feed_post(&client, &test_app.address, &fan, workout_id).await;
+    assert_eq!(post["reaction_count"], 1);
+    assert_eq!(post["comment_count"], 0);
+    assert_eq!(post["user_has_reacted"], false);
+
+    // The summary goes with its workout
+    sqlx::query!("DELETE FROM workout_data WHERE id = $1", workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(summary_counts(&test_app.db_pool, workout_id).await, None);
+}
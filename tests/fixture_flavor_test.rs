@@ -0,0 +1,105 @@
+//! Game week names and fixture flavor tests
+//!
+//! - Week names, titles, venues and banners are validated
+//! - League admins name weeks and dress up fixtures; schedule and live payloads return them
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::models::league::{SetFixtureFlavorRequest, SetGameWeekNameRequest};
+
+mod common;
+use common::live_game_helpers::setup_live_game_environment;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn fixture(title: Option<&str>, banner_url: Option<&str>) -> SetFixtureFlavorRequest {
+    SetFixtureFlavorRequest {
+        title: title.map(str::to_string),
+        venue_name: None,
+        description: None,
+        banner_url: banner_url.map(str::to_string),
+    }
+}
+
+#[test]
+fn week_names_need_a_short_name() {
+    let week = |name: &str| SetGameWeekNameRequest { name: name.to_string(), description: None };
+    assert!(week("Derby Week").validate().is_ok());
+    assert!(week("   ").validate().is_err());
+    assert!(week(&"x".repeat(61)).validate().is_err());
+}
+
+#[test]
+fn fixture_banners_must_be_https() {
+    assert!(fixture(Some("The Classic"), Some("https://cdn.example.com/banner.png")).validate().is_ok());
+    assert!(fixture(None, Some("http://cdn.example.com/banner.png")).validate().is_err());
+    assert!(fixture(None, Some("not a url")).validate().is_err());
+    assert!(fixture(None, None).validate().is_err(), "Nothing to set");
+}
+
+#[tokio::test]
+async fn admins_name_weeks_and_fixtures_shown_in_schedule_and_live() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let environment = setup_live_game_environment(&test_app).await;
+    let admin_token = &environment.admin_session.token;
+    let game_id = environment.first_game_id;
+    let week_number: i32 = sqlx::query_scalar("SELECT week_number FROM games WHERE id = $1")
+        .bind(game_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let week_url = format!(
+        "{}/admin/seasons/{}/weeks/{}/name",
+        test_app.address, environment.season_id, week_number
+    );
+    let week_name = json!({ "name": "Derby Week", "description": "Local rivals face off" });
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &week_url, &environment.home_user.token, Some(week_name.clone()),
+    ).await;
+    assert_eq!(response.status(), 403, "Only admins name weeks");
+
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &week_url, admin_token, Some(week_name)).await;
+    assert_eq!(response.status(), 200);
+
+    let missing_week_url = format!("{}/admin/seasons/{}/weeks/999/name", test_app.address, environment.season_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &missing_week_url, admin_token, Some(json!({ "name": "Nowhere Week" })),
+    ).await;
+    assert_eq!(response.status(), 404);
+
+    let flavor_url = format!("{}/admin/games/{}/flavor", test_app.address, game_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &flavor_url, admin_token, Some(json!({ "banner_url": "ftp://banner" })),
+    ).await;
+    assert_eq!(response.status(), 400);
+
+    let flavor = json!({
+        "title": "The Classic",
+        "venue_name": "Riverside Arena",
+        "banner_url": "https://cdn.example.com/classic.png"
+    });
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &flavor_url, admin_token, Some(flavor)).await;
+    assert_eq!(response.status(), 200);
+
+    let schedule_url = format!("{}/league/seasons/{}/schedule", test_app.address, environment.season_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &schedule_url, &environment.home_user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["week_names"][0]["name"], "Derby Week");
+    assert_eq!(body["data"]["week_names"][0]["week_number"], week_number);
+    assert_eq!(body["data"]["fixture_flavors"][0]["game_id"], game_id.to_string());
+    assert_eq!(body["data"]["fixture_flavors"][0]["venue_name"], "Riverside Arena");
+
+    let live_url = format!("{}/league/games/{}/live", test_app.address, game_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &live_url, &environment.home_user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["week_name"]["name"], "Derby Week");
+    assert_eq!(body["data"]["fixture"]["title"], "The Classic");
+
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &flavor_url, admin_token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &live_url, &environment.home_user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["fixture"].is_null());
+}
@@ -0,0 +1,192 @@
+//! Team formation bonus tests
+//!
+//! - Each player counts for the classified workout type they scored the most with
+//! - Rosters covering two or all three archetypes earn a small bonus, split between their players
+//! - Evaluation awards the bonus once, adds it to the score and explains it for the summary
+
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use riina_backend::game::formation_bonus::{formation_description, player_archetypes, team_formation, FormationWorkout};
+use riina_backend::models::workout_data::WorkoutType;
+use riina_backend::services::FormationBonusService;
+
+mod common;
+use common::utils::spawn_app;
+
+fn workout(user_id: Uuid, workout_type: WorkoutType, points: f32) -> FormationWorkout {
+    FormationWorkout { user_id, workout_type, points }
+}
+
+#[test]
+fn players_count_for_their_main_workout_type() {
+    let (ann, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let archetypes = player_archetypes(&[
+        workout(ann, WorkoutType::Cardio, 10.0),
+        workout(ann, WorkoutType::Strength, 15.0),
+        workout(bob, WorkoutType::Hiit, 5.0),
+        workout(bob, WorkoutType::Cardio, 5.0),
+        workout(bob, WorkoutType::Other, 50.0),
+    ]);
+    assert_eq!(archetypes[&ann], WorkoutType::Strength);
+    assert_eq!(archetypes[&bob], WorkoutType::Cardio, "Ties go to the archetype listed first");
+}
+
+#[test]
+fn balanced_rosters_earn_a_bonus() {
+    let (ann, bob, cid) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+    let one_archetype = [workout(ann, WorkoutType::Cardio, 60.0), workout(bob, WorkoutType::Cardio, 40.0)];
+    assert_eq!(team_formation(&one_archetype, 100.0), None);
+
+    let two_archetypes = [workout(ann, WorkoutType::Cardio, 60.0), workout(bob, WorkoutType::Strength, 40.0)];
+    assert_eq!(team_formation(&two_archetypes, 100.0).unwrap().bonus_points, 2.0);
+
+    let full = [
+        workout(ann, WorkoutType::Cardio, 50.0),
+        workout(bob, WorkoutType::Strength, 40.0),
+        workout(cid, WorkoutType::Hiit, 30.0),
+    ];
+    let formation = team_formation(&full, 120.0).unwrap();
+    assert_eq!(formation.bonus_points, 6.0);
+    assert!(formation.shares.iter().all(|(_, share)| *share == 2.0));
+    assert_eq!(
+        formation_description(&formation),
+        "Formation bonus: cardio, strength and HIIT workouts covered (+6 points)"
+    );
+}
+
+async fn insert_user(pool: &PgPool) -> Uuid {
+    let username = format!("formation_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    sqlx::query_scalar("INSERT INTO users (username, password_hash, email) VALUES ($1, 'x', $2) RETURNING id")
+        .bind(&username)
+        .bind(format!("{username}@example.com"))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn insert_team(pool: &PgPool, league_id: Uuid, members: &[Uuid]) -> Uuid {
+    let team_id: Uuid = sqlx::query_scalar("INSERT INTO teams (user_id, team_name, league_id) VALUES ($1, $2, $3) RETURNING id")
+        .bind(members[0])
+        .bind(format!("Formation {}", &Uuid::new_v4().simple().to_string()[..8]))
+        .bind(league_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    for user_id in members {
+        sqlx::query("INSERT INTO team_members (team_id, user_id, role, status) VALUES ($1, $2, 'member', 'active')")
+            .bind(team_id)
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+    team_id
+}
+
+async fn score_workout(pool: &PgPool, game_id: Uuid, team_id: Uuid, team_side: &str, user_id: Uuid, prediction: &str, points: f32) {
+    let start = Utc::now() - Duration::hours(1);
+    let workout_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid, workout_start, workout_end, ml_prediction)
+        VALUES ($1, 'test-device', '[]', $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(user_id)
+    .bind(Uuid::new_v4().to_string())
+    .bind(start)
+    .bind(start + Duration::minutes(30))
+    .bind(prediction)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side, score_points, power_contribution,
+                                       description, workout_data_id)
+        SELECT $1, id, username, $2, $3, $4, 0, 'Workout completed', $5 FROM users WHERE id = $6
+        "#,
+    )
+    .bind(game_id)
+    .bind(team_id)
+    .bind(team_side)
+    .bind(points)
+    .bind(workout_id)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn evaluation_awards_formation_bonuses_once() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+
+    let league_id: Uuid = sqlx::query_scalar("INSERT INTO leagues (name, max_teams) VALUES ($1, 2) RETURNING id")
+        .bind(format!("Formation League {}", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let home_players = [insert_user(pool).await, insert_user(pool).await, insert_user(pool).await];
+    let away_player = insert_user(pool).await;
+    let home_team = insert_team(pool, league_id, &home_players).await;
+    let away_team = insert_team(pool, league_id, &[away_player]).await;
+
+    let now = Utc::now();
+    let season_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO league_seasons (league_id, name, start_date, end_date) VALUES ($1, 'Formation Season', $2, $3) RETURNING id",
+    )
+    .bind(league_id)
+    .bind(now - Duration::days(7))
+    .bind(now + Duration::days(7))
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO league_teams (season_id, team_id) VALUES ($1, $2), ($1, $3)")
+        .bind(season_id)
+        .bind(home_team)
+        .bind(away_team)
+        .execute(pool)
+        .await
+        .unwrap();
+    let game_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO games (season_id, home_team_id, away_team_id, week_number, status, game_start_time, game_end_time)
+        VALUES ($1, $2, $3, 1, 'finished', $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(season_id)
+    .bind(home_team)
+    .bind(away_team)
+    .bind(now - Duration::days(2))
+    .bind(now)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    score_workout(pool, game_id, home_team, "home", home_players[0], "Cardio", 50.0).await;
+    score_workout(pool, game_id, home_team, "home", home_players[1], "strength", 40.0).await;
+    score_workout(pool, game_id, home_team, "home", home_players[2], "hiit", 30.0).await;
+    score_workout(pool, game_id, away_team, "away", away_player, "cardio", 50.0).await;
+
+    let service = FormationBonusService::new(pool.clone());
+    service.settle_games(&[game_id]).await.unwrap();
+    service.settle_games(&[game_id]).await.unwrap();
+
+    let scores: (i32, i32) = sqlx::query_as("SELECT home_score, away_score FROM games WHERE id = $1")
+        .bind(game_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(scores, (126, 50), "Only the home roster covered several archetypes, and only once");
+
+    let bonuses = service.game_bonuses(game_id).await.unwrap();
+    assert_eq!(bonuses.len(), 1);
+    assert_eq!(bonuses[0].team_id, home_team);
+    assert_eq!(bonuses[0].archetypes, vec!["cardio", "strength", "hiit"]);
+}
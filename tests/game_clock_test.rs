@@ -0,0 +1,123 @@
+//! Game clock tests
+//!
+//! Covers the server time and next game transition exposed to clients:
+//! - `/league/game_countdown` returns server_time and the next start/end of a game
+//! - countdown_seconds is measured against server_time
+//! - WebSocket clients get a `heartbeat` event carrying the server time
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use serde_json::json;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+fn timestamp(value: &serde_json::Value) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value.as_str().expect("Expected a timestamp"))
+        .expect("Expected an RFC 3339 timestamp")
+        .with_timezone(&Utc)
+}
+
+#[tokio::test]
+async fn countdown_exposes_server_time_and_next_transition() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner_a = create_test_user_and_login(&test_app.address).await;
+    let owner_b = create_test_user_and_login(&test_app.address).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![parse_user_id_from_jwt_token(&owner_a.token), parse_user_id_from_jwt_token(&owner_b.token)]),
+        true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Clock Season", &start_date,
+    ).await;
+    let season_uuid = Uuid::parse_str(&season_id).unwrap();
+    let countdown_url = format!("{}/league/game_countdown?season_id={}", test_app.address, season_id);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &countdown_url, &owner_a.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let data = &body["data"];
+
+    let server_time = timestamp(&data["server_time"]);
+    assert!((server_time - Utc::now()).num_seconds().abs() < 60);
+
+    // The first scheduled game starts next
+    let first_start: DateTime<Utc> = sqlx::query_scalar(
+        "SELECT MIN(game_start_time) FROM games WHERE season_id = $1 AND status = 'scheduled'"
+    )
+    .bind(season_uuid)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    let transition = &data["next_transition"];
+    assert_eq!(transition["kind"], "game_start");
+    assert_eq!(timestamp(&transition["at"]), first_start);
+
+    // The countdown runs on the server clock, to the game's actual start
+    let countdown = data["countdown_seconds"].as_i64().unwrap();
+    assert!((countdown - (first_start - server_time).num_seconds()).abs() <= 1);
+
+    // Once the first week is running, the next transition is its end
+    let week_end = Utc::now() + Duration::hours(1);
+    sqlx::query(
+        "UPDATE games SET status = 'in_progress', game_start_time = NOW() - INTERVAL '1 hour', game_end_time = $2
+         WHERE season_id = $1 AND week_number = 1"
+    )
+    .bind(season_uuid)
+    .bind(week_end)
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &countdown_url, &owner_a.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let transition = &body["data"]["next_transition"];
+    assert_eq!(transition["kind"], "game_end");
+    assert_eq!(transition["week_number"], 1);
+    assert_eq!(timestamp(&transition["at"]).timestamp(), week_end.timestamp());
+}
+
+#[tokio::test]
+async fn websocket_heartbeat_carries_server_time() {
+    let test_app = spawn_app().await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let ws_url = format!("{}/game-ws?token={}", test_app.address.replace("http", "ws"), user.token);
+    let request = ws_url.into_client_request().expect("Failed to create request");
+    let (mut ws_stream, _) = connect_async(request)
+        .await
+        .expect("Failed to connect to WebSocket server");
+
+    ws_stream
+        .send(Message::Text(json!({ "type": "request_game_clock" }).to_string()))
+        .await
+        .unwrap();
+
+    let heartbeat = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while let Some(Ok(message)) = ws_stream.next().await {
+            if let Message::Text(text) = message {
+                let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if event["event_type"] == "heartbeat" {
+                    return event;
+                }
+            }
+        }
+        panic!("WebSocket closed before a heartbeat arrived");
+    })
+    .await
+    .expect("No heartbeat received");
+
+    let server_time = timestamp(&heartbeat["server_time"]);
+    assert!((server_time - Utc::now()).num_seconds().abs() < 60);
+    assert!(heartbeat.get("next_transition").is_some());
+}
@@ -0,0 +1,215 @@
+//! Live game commentary tests
+//!
+//! Covers the automated commentator and `/league/games/{id}/commentary`:
+//! - First score, lead changes, new MVP candidates and the final minutes are called once
+//! - Lines are rendered in the league's locale and can be re-rendered per request
+//! - Leagues can switch milestones or the whole commentator off
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::services::GameCommentaryService;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token, TestApp};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+struct RunningGame {
+    league_id: String,
+    game_id: Uuid,
+    home_team_id: Uuid,
+    away_team_id: Uuid,
+    home_user: (Uuid, String),
+    away_user: (Uuid, String),
+    token: String,
+}
+
+async fn start_game(test_app: &TestApp, admin_token: &str) -> RunningGame {
+    let home_player = create_test_user_and_login(&test_app.address).await;
+    let away_player = create_test_user_and_login(&test_app.address).await;
+    let home_player_id = parse_user_id_from_jwt_token(&home_player.token);
+    let away_player_id = parse_user_id_from_jwt_token(&away_player.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, admin_token, 2, 2, Some(vec![home_player_id, away_player_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, admin_token, &league.league_id, "Commentary Season", &start_date,
+    ).await;
+
+    let game_start = Utc::now() - Duration::hours(1);
+    let game = sqlx::query!(
+        r#"
+        UPDATE games
+        SET status = 'in_progress', game_start_time = $2, game_end_time = $3
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+        RETURNING id, home_team_id, away_team_id
+        "#,
+        Uuid::parse_str(&season_id).unwrap(),
+        game_start,
+        game_start + Duration::days(7)
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to start game");
+
+    let home = (home_player_id, home_player.username.clone());
+    let away = (away_player_id, away_player.username.clone());
+    let (home_user, away_user) = if game.home_team_id == Uuid::parse_str(&league.team_ids[0]).unwrap() {
+        (home, away)
+    } else {
+        (away, home)
+    };
+
+    RunningGame {
+        league_id: league.league_id,
+        game_id: game.id,
+        home_team_id: game.home_team_id,
+        away_team_id: game.away_team_id,
+        home_user,
+        away_user,
+        token: home_player.token,
+    }
+}
+
+/// Record a score event and let the commentator look at it, returning the milestones called
+async fn score(test_app: &TestApp, game: &RunningGame, home: bool, points: f32) -> Vec<String> {
+    let ((user_id, username), team_id, team_side) = if home {
+        (&game.home_user, game.home_team_id, "home")
+    } else {
+        (&game.away_user, game.away_team_id, "away")
+    };
+
+    let score_event_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side,
+                                       score_points, power_contribution, description)
+        VALUES ($1, $2, $3, $4, $5, $6, 0, 'Commentary test event')
+        RETURNING id
+        "#,
+    )
+    .bind(game.game_id)
+    .bind(user_id)
+    .bind(username)
+    .bind(team_id)
+    .bind(team_side)
+    .bind(points)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to insert score event");
+
+    GameCommentaryService::new(test_app.db_pool.clone(), None)
+        .comment_on_score(game.game_id, score_event_id)
+        .await
+        .expect("Commentary should not fail")
+        .into_iter()
+        .map(|line| line.milestone)
+        .collect()
+}
+
+#[tokio::test]
+async fn commentator_calls_game_milestones() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let game = start_game(&test_app, &admin.token).await;
+    let (home_name, away_name) = (&game.home_user.1, &game.away_user.1);
+
+    assert_eq!(score(&test_app, &game, true, 10.0).await, vec!["first_score"]);
+    // Extending the lead is not news
+    assert!(score(&test_app, &game, true, 2.0).await.is_empty());
+    assert_eq!(score(&test_app, &game, false, 15.0).await, vec!["lead_change", "mvp_candidate"]);
+
+    let commentary_url = format!("{}/league/games/{}/commentary", test_app.address, game.game_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &commentary_url, &game.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let lines = body["data"].as_array().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["locale"], "en");
+    assert!(lines[0]["message"].as_str().unwrap().starts_with(&format!("{} opens the scoring", home_name)));
+    assert_eq!(lines[1]["params"]["leading_score"], 15);
+    assert_eq!(lines[1]["params"]["trailing_score"], 12);
+    assert_eq!(lines[2]["user_id"], game.away_user.0.to_string());
+    assert_eq!(lines[2]["params"]["points"], 15);
+
+    // Clients can ask for another language
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?locale=de", commentary_url), &game.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"][2]["locale"], "de");
+    assert!(body["data"][2]["message"].as_str().unwrap().starts_with(&format!("{} (", away_name)));
+    assert!(body["data"][2]["message"].as_str().unwrap().contains("MVP-Kandidat"));
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?locale=xx", commentary_url), &game.token, None,
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET,
+        &format!("{}/league/games/{}/commentary", test_app.address, Uuid::new_v4()), &game.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+
+    // The final minutes are called once, with the time actually left
+    sqlx::query("UPDATE games SET game_end_time = NOW() + INTERVAL '5 minutes' WHERE id = $1")
+        .bind(game.game_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let service = GameCommentaryService::new(test_app.db_pool.clone(), None);
+    for _ in 0..2 {
+        service.comment_on_final_minutes().await.expect("Final minutes check should not fail");
+    }
+    let lines = service.get_game_commentary(game.game_id).await.unwrap();
+    let final_minutes: Vec<_> = lines.iter().filter(|line| line.milestone == "final_minutes").collect();
+    assert_eq!(final_minutes.len(), 1);
+    assert_eq!(final_minutes[0].params["minutes"], 5);
+    assert_eq!(final_minutes[0].params["home_score"], 0, "Scores come from the game row");
+}
+
+#[tokio::test]
+async fn commentary_follows_league_settings() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let game = start_game(&test_app, &admin.token).await;
+    let league_url = format!("{}/admin/leagues/{}", test_app.address, game.league_id);
+
+    // Invalid settings are rejected
+    for body in [json!({ "commentary_locale": "xx" }), json!({ "commentary_milestones": ["own_goal"] })] {
+        let response = make_authenticated_request(&client, reqwest::Method::PATCH, &league_url, &admin.token, Some(body)).await;
+        assert_eq!(400, response.status().as_u16());
+    }
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token,
+        Some(json!({ "commentary_locale": "de", "commentary_milestones": ["lead_change"] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["commentary_enabled"], true);
+    assert_eq!(body["data"]["commentary_locale"], "de");
+    assert_eq!(body["data"]["commentary_milestones"], json!(["lead_change"]));
+
+    assert!(score(&test_app, &game, true, 8.0).await.is_empty(), "First score is switched off");
+    assert_eq!(score(&test_app, &game, false, 9.0).await, vec!["lead_change"]);
+
+    let lines = GameCommentaryService::new(test_app.db_pool.clone(), None)
+        .get_game_commentary(game.game_id)
+        .await
+        .unwrap();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].locale, "de");
+    assert!(lines[0].message.starts_with("Führungswechsel!"));
+
+    // Switched off entirely
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token, Some(json!({ "commentary_enabled": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(score(&test_app, &game, true, 5.0).await.is_empty());
+}
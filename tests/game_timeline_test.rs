@@ -0,0 +1,134 @@
+//! Game timeline tests
+//!
+//! Covers `/league/games/{id}/timeline`:
+//! - Events of both teams are merged into 5-minute buckets
+//! - Each bucket carries the cumulative score after it
+//! - Unknown games return 404
+
+use reqwest::Client;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn insert_score_event(
+    pool: &sqlx::PgPool,
+    game_id: Uuid,
+    user_id: Uuid,
+    team_id: Uuid,
+    team_side: &str,
+    points: f32,
+    occurred_at: DateTime<Utc>,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side,
+                                       score_points, power_contribution, description, occurred_at)
+        VALUES ($1, $2, 'timeline_player', $3, $4, $5, 0, 'Timeline test event', $6)
+        "#
+    )
+    .bind(game_id)
+    .bind(user_id)
+    .bind(team_id)
+    .bind(team_side)
+    .bind(points)
+    .bind(occurred_at)
+    .execute(pool)
+    .await
+    .expect("Failed to insert score event");
+}
+
+#[tokio::test]
+async fn game_timeline_buckets_events_with_cumulative_scores() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let home_player = create_test_user_and_login(&test_app.address).await;
+    let away_player = create_test_user_and_login(&test_app.address).await;
+    let home_player_id = parse_user_id_from_jwt_token(&home_player.token);
+    let away_player_id = parse_user_id_from_jwt_token(&away_player.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![home_player_id, away_player_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Timeline Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let game_start = Utc::now() - Duration::hours(1);
+    let game = sqlx::query!(
+        r#"
+        UPDATE games
+        SET status = 'in_progress', game_start_time = $2, game_end_time = $3
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+        RETURNING id, home_team_id, away_team_id
+        "#,
+        season_id,
+        game_start,
+        game_start + Duration::days(7)
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to start game");
+
+    let team_a = Uuid::parse_str(&league.team_ids[0]).unwrap();
+    let (home_user, away_user) = if game.home_team_id == team_a {
+        (home_player_id, away_player_id)
+    } else {
+        (away_player_id, home_player_id)
+    };
+
+    insert_score_event(&test_app.db_pool, game.id, home_user, game.home_team_id, "home", 10.0, game_start + Duration::minutes(1)).await;
+    insert_score_event(&test_app.db_pool, game.id, away_user, game.away_team_id, "away", 4.5, game_start + Duration::minutes(3)).await;
+    insert_score_event(&test_app.db_pool, game.id, home_user, game.home_team_id, "home", 2.7, game_start + Duration::minutes(12)).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/games/{}/timeline", test_app.address, game.id),
+        &home_player.token,
+        None,
+    ).await;
+
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["bucket_minutes"], 5);
+
+    let buckets = body["data"]["buckets"].as_array().unwrap();
+    assert_eq!(buckets.len(), 2, "Empty buckets should be omitted");
+
+    // First bucket merges events from both teams
+    assert_eq!(buckets[0]["event_count"], 2);
+    assert_eq!(buckets[0]["home_score"], 10);
+    assert_eq!(buckets[0]["away_score"], 4);
+    assert_eq!(buckets[0]["scorers"].as_array().unwrap().len(), 2);
+    let first_start: DateTime<Utc> = buckets[0]["bucket_start"].as_str().unwrap().parse().unwrap();
+    assert!((first_start - game_start).num_seconds().abs() < 1, "Buckets should be aligned to the game start");
+
+    // Second bucket is 10-15 minutes in and carries the running totals
+    let second_start: DateTime<Utc> = buckets[1]["bucket_start"].as_str().unwrap().parse().unwrap();
+    assert_eq!((second_start - first_start).num_minutes(), 10);
+    assert_eq!(buckets[1]["home_score"], 12);
+    assert_eq!(buckets[1]["away_score"], 4);
+}
+
+#[tokio::test]
+async fn game_timeline_returns_404_for_unknown_game() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/games/{}/timeline", test_app.address, Uuid::new_v4()),
+        &user.token,
+        None,
+    ).await;
+
+    assert_eq!(404, response.status().as_u16());
+}
@@ -0,0 +1,152 @@
+//! Unfinished game watchdog tests
+//!
+//! Covers the game watchdog job and `/admin/games/watchdog`:
+//! - Games past their end time without evaluation raise one alert and notify admins once
+//! - Games within the grace period are left alone
+//! - Auto-finalize evaluates the overdue games and resolves their alerts
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use uuid::Uuid;
+
+use riina_backend::config::game_watchdog::GameWatchdogSettings;
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use riina_backend::services::GameWatchdogService;
+use secrecy::ExposeSecret;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, TestApp};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+/// Mark a game of the season as finished but never evaluated, ended `ended_minutes_ago`
+async fn leave_game_unevaluated(test_app: &TestApp, season_id: &str, week_number: i32, ended_minutes_ago: i64) -> Uuid {
+    let game_end = Utc::now() - Duration::minutes(ended_minutes_ago);
+    sqlx::query_scalar!(
+        r#"
+        UPDATE games
+        SET status = 'finished', game_start_time = $3, game_end_time = $4
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 AND week_number = $2 LIMIT 1)
+        RETURNING id
+        "#,
+        Uuid::parse_str(season_id).unwrap(),
+        week_number,
+        game_end - Duration::days(7),
+        game_end
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to update game")
+}
+
+fn watchdog(test_app: &TestApp, auto_finalize: bool) -> GameWatchdogService {
+    let configuration = get_config().expect("Failed to read configuration.");
+    let redis_client = redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret())
+        .expect("Failed to create Redis client");
+    GameWatchdogService::new(
+        test_app.db_pool.clone(),
+        Arc::new(redis_client),
+        GameWatchdogSettings { overdue_minutes: 15, auto_finalize },
+    )
+}
+
+#[tokio::test]
+async fn overdue_games_alert_admins_once_and_can_be_auto_finalized() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 4, 4, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Watchdog Season", &start_date,
+    ).await;
+    // Finished games get the 60 minute upload grace period on top of the 15 overdue minutes
+    let overdue_game_id = leave_game_unevaluated(&test_app, &season_id, 1, 90).await;
+    let recent_game_id = leave_game_unevaluated(&test_app, &season_id, 2, 30).await;
+
+    let summary = watchdog(&test_app, false).run_check().await.expect("Watchdog should run");
+    assert!(summary.overdue_games >= 1);
+    assert!(summary.new_alerts >= 1);
+
+    let alert = sqlx::query!(
+        "SELECT admins_notified, first_detected_at, auto_finalized_at, resolved_at FROM game_watchdog_alerts WHERE game_id = $1",
+        overdue_game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Overdue game should raise an alert");
+    assert!(alert.admins_notified >= 1);
+    assert!(alert.auto_finalized_at.is_none());
+    assert!(alert.resolved_at.is_none());
+
+    let recent_alerts = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM game_watchdog_alerts WHERE game_id = $1"#,
+        recent_game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(recent_alerts, 0, "Games within the grace period should not alert");
+
+    // A second run keeps the alert and does not notify again
+    watchdog(&test_app, false).run_check().await.expect("Watchdog should run");
+    let first_detected_at = sqlx::query_scalar!(
+        "SELECT first_detected_at FROM game_watchdog_alerts WHERE game_id = $1",
+        overdue_game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(first_detected_at, alert.first_detected_at);
+    let notifications = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!" FROM notifications
+        WHERE recipient_id = $1 AND notification_type = 'unfinished_game' AND entity_id = $2
+        "#,
+        admin.user_id,
+        overdue_game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(notifications, 1);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/admin/games/watchdog", test_app.address), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["overdue_minutes"], 15);
+    assert!(body["data"]["unfinished_games"].as_u64().unwrap() >= 1);
+    assert!(body["data"]["open_alerts"].as_i64().unwrap() >= 1);
+    assert!(body["data"]["overdue"].as_array().unwrap().iter()
+        .any(|game| game["game_id"] == overdue_game_id.to_string()));
+
+    // Auto-finalize evaluates the game and resolves its alert
+    let summary = watchdog(&test_app, true).run_check().await.expect("Watchdog should run");
+    assert!(summary.auto_finalized >= 1);
+
+    let status = sqlx::query_scalar!("SELECT status FROM games WHERE id = $1", overdue_game_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "evaluated");
+    let alert = sqlx::query!(
+        "SELECT auto_finalized_at, resolved_at FROM game_watchdog_alerts WHERE game_id = $1",
+        overdue_game_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert!(alert.auto_finalized_at.is_some());
+    assert!(alert.resolved_at.is_some());
+
+    let status = sqlx::query_scalar!("SELECT status FROM games WHERE id = $1", recent_game_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "finished");
+}
@@ -0,0 +1,176 @@
+//! Game window tests
+//!
+//! Covers `/league/games/next-window` and the upload grace period:
+//! - The window of the user's next scheduled game, then of the running game
+//! - A finished game stays visible while it accepts late uploads
+//! - Workouts recorded during a finished game still score within the grace period
+//! - The game cycle evaluates a finished game only once its grace period is over
+//! - Uploads after the grace period no longer score
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user};
+
+fn timestamp(value: &serde_json::Value) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value.as_str().expect("Expected a timestamp"))
+        .expect("Expected an RFC 3339 timestamp")
+        .with_timezone(&Utc)
+}
+
+async fn get_window(client: &Client, address: &str, token: &str) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/league/games/next-window", address), token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+async fn set_game_times(pool: &sqlx::PgPool, game_id: Uuid, status: &str, start: DateTime<Utc>, end: DateTime<Utc>) {
+    sqlx::query("UPDATE games SET status = $2, game_start_time = $3, game_end_time = $4 WHERE id = $1")
+        .bind(game_id)
+        .bind(status)
+        .bind(start)
+        .bind(end)
+        .execute(pool)
+        .await
+        .expect("Failed to update game");
+}
+
+async fn game_status(pool: &sqlx::PgPool, game_id: Uuid) -> String {
+    sqlx::query_scalar("SELECT status FROM games WHERE id = $1")
+        .bind(game_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn get_job(client: &Client, job_url: &str, token: &str) -> serde_json::Value {
+    let response = make_authenticated_request(client, reqwest::Method::GET, job_url, token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+/// Run the season's game cycle by hand and wait until that run succeeded
+async fn run_game_cycle(client: &Client, address: &str, token: &str, season_id: Uuid) {
+    let job_url = format!("{}/admin/scheduler/jobs/game_cycle:{}", address, season_id);
+    let runs_before = get_job(client, &job_url, token).await["success_count"].as_u64().unwrap();
+
+    let response = make_authenticated_request(client, reqwest::Method::POST, &format!("{}/run", job_url), token, None).await;
+    assert_eq!(202, response.status().as_u16());
+    for _ in 0..50 {
+        let job = get_job(client, &job_url, token).await;
+        if job["last_trigger"] == "manual" && job["in_flight"] == 0 && job["success_count"].as_u64().unwrap() > runs_before {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("Game cycle of season {} did not finish", season_id);
+}
+
+async fn score_events(pool: &sqlx::PgPool, game_id: Uuid, user_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM live_score_events WHERE game_id = $1 AND user_id = $2")
+        .bind(game_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn next_window_follows_the_users_games_and_grace_period() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner_a = create_test_user_and_login(&test_app.address).await;
+    let owner_b = create_test_user_and_login(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&owner_a.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![user_id, parse_user_id_from_jwt_token(&owner_b.token)]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Window Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let (game_id, game_start, game_end): (Uuid, DateTime<Utc>, DateTime<Utc>) = sqlx::query_as(
+        "SELECT id, game_start_time, game_end_time FROM games WHERE season_id = $1 AND week_number = 1"
+    )
+    .bind(season_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    // Next scheduled game
+    let data = get_window(&client, &test_app.address, &owner_a.token).await;
+    let grace = data["upload_grace_minutes"].as_i64().unwrap();
+    let window = &data["window"];
+    assert_eq!(window["game_id"], game_id.to_string());
+    assert_eq!(window["status"], "Scheduled");
+    assert_eq!(window["team_id"], league.team_ids[0]);
+    assert_eq!(window["opponent_team_id"], league.team_ids[1]);
+    assert_eq!(timestamp(&window["record_from"]), game_start);
+    assert_eq!(timestamp(&window["record_until"]), game_end);
+    assert_eq!(timestamp(&window["upload_until"]), game_end + Duration::minutes(grace));
+    assert_eq!(window["is_recording_open"], false);
+    assert_eq!(window["is_upload_open"], false);
+
+    // Running game: the window opens when the user joined the team, if that was later
+    sqlx::query("UPDATE team_members SET joined_at = NOW() - INTERVAL '1 day' WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let now = Utc::now();
+    set_game_times(&test_app.db_pool, game_id, "in_progress", now - Duration::hours(3), now + Duration::hours(1)).await;
+
+    let window = get_window(&client, &test_app.address, &owner_a.token).await["window"].clone();
+    assert_eq!(window["game_id"], game_id.to_string());
+    assert_eq!(window["is_recording_open"], true);
+    assert_eq!(window["is_upload_open"], true);
+
+    // The game cycle finishes the ended game but leaves it open for late uploads
+    set_game_times(&test_app.db_pool, game_id, "in_progress", now - Duration::hours(3), now - Duration::minutes(10)).await;
+    run_game_cycle(&client, &test_app.address, &admin.token, season_id).await;
+    assert_eq!(game_status(&test_app.db_pool, game_id).await, "finished");
+
+    // Finished, but within the grace period: recording is closed, late uploads still score
+    let window = get_window(&client, &test_app.address, &owner_a.token).await["window"].clone();
+    assert_eq!(window["game_id"], game_id.to_string());
+    assert_eq!(window["is_recording_open"], false);
+    assert_eq!(window["is_upload_open"], true);
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, now - Duration::minutes(90), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &owner_a.token, &mut workout).await.unwrap();
+    assert_eq!(score_events(&test_app.db_pool, game_id, user_id).await, 1);
+
+    // Past the grace period the game cycle evaluates it and uploads no longer score
+    set_game_times(&test_app.db_pool, game_id, "finished", now - Duration::hours(5), now - Duration::minutes(grace + 30)).await;
+    run_game_cycle(&client, &test_app.address, &admin.token, season_id).await;
+    assert_eq!(game_status(&test_app.db_pool, game_id).await, "evaluated");
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, now - Duration::hours(4), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &owner_a.token, &mut workout).await.unwrap();
+    assert_eq!(score_events(&test_app.db_pool, game_id, user_id).await, 1);
+    assert!(get_window(&client, &test_app.address, &owner_a.token).await["window"].is_null());
+}
+
+#[tokio::test]
+async fn next_window_is_empty_without_games() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let data = get_window(&client, &test_app.address, &user.token).await;
+    assert!(data["window"].is_null());
+    assert!((timestamp(&data["server_time"]) - Utc::now()).num_seconds().abs() < 60);
+}
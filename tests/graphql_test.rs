@@ -0,0 +1,144 @@
+//! GraphQL facade tests
+//!
+//! Covers `/graphql`:
+//! - Users, teams, games and standings resolve with their relations in one request
+//! - Private fields are only visible to the user themselves
+//! - The feed pages by cursor
+//! - Requests require authentication
+#![cfg(feature = "graphql")]
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn graphql(client: &Client, address: &str, token: &str, query: &str, variables: serde_json::Value) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/graphql", address),
+        token,
+        Some(json!({ "query": query, "variables": variables })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn graphql_resolves_teams_games_and_standings_in_one_request() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let rival = create_test_user_and_login(&test_app.address).await;
+    let captain_id = parse_user_id_from_jwt_token(&captain.token);
+    let rival_id = parse_user_id_from_jwt_token(&rival.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![captain_id, rival_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "GraphQL Season", &start_date,
+    ).await;
+
+    let query = r#"
+        query Dashboard($seasonId: UUID!, $rivalId: UUID!) {
+            me { id username email team { id members { role user { id username } } } }
+            rival: user(id: $rivalId) { id email }
+            games(seasonId: $seasonId, weekNumber: 1) {
+                weekNumber status homeTeam { id teamName } awayTeam { id teamName }
+            }
+            standings(seasonId: $seasonId) { position points team { id owner { id } } }
+        }
+    "#;
+    let body = graphql(&client, &test_app.address, &captain.token, query,
+        json!({ "seasonId": season_id, "rivalId": rival_id })).await;
+    assert!(body["errors"].is_null(), "Unexpected errors: {}", body["errors"]);
+    let data = &body["data"];
+
+    assert_eq!(data["me"]["id"], captain_id.to_string());
+    assert!(data["me"]["email"].is_string(), "Own email is visible");
+    assert_eq!(data["me"]["team"]["id"], league.team_ids[0].as_str());
+    let members = data["me"]["team"]["members"].as_array().unwrap();
+    assert!(members.iter().any(|m| m["user"]["id"] == captain_id.to_string().as_str()));
+
+    assert_eq!(data["rival"]["id"], rival_id.to_string());
+    assert!(data["rival"]["email"].is_null(), "Other users' email is hidden");
+
+    let games = data["games"].as_array().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["weekNumber"], 1);
+    assert_eq!(games[0]["status"], "scheduled");
+    let game_team_ids = [games[0]["homeTeam"]["id"].as_str().unwrap(), games[0]["awayTeam"]["id"].as_str().unwrap()];
+    for team_id in &league.team_ids {
+        assert!(game_team_ids.contains(&team_id.as_str()));
+    }
+
+    let standings = data["standings"].as_array().unwrap();
+    assert_eq!(standings.len(), 2);
+    assert_eq!(standings[0]["position"], 1);
+    let owners: Vec<&str> = standings.iter().map(|s| s["team"]["owner"]["id"].as_str().unwrap()).collect();
+    assert!(owners.contains(&captain_id.to_string().as_str()));
+    assert!(owners.contains(&rival_id.to_string().as_str()));
+
+    // Unknown seasons surface as GraphQL errors
+    let body = graphql(&client, &test_app.address, &captain.token,
+        "query($id: UUID!) { standings(seasonId: $id) { position } }", json!({ "id": Uuid::new_v4() })).await;
+    assert_eq!(body["errors"][0]["message"], "Season not found");
+}
+
+#[tokio::test]
+async fn graphql_feed_pages_by_cursor_and_requires_auth() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let author = create_test_user_and_login(&test_app.address).await;
+    let author_id = parse_user_id_from_jwt_token(&author.token);
+
+    // Backdate the post so the cursor below isolates it from posts created by other tests
+    let created_at = Utc::now() - Duration::days(1000) + Duration::microseconds(rand_offset());
+    let post_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO posts (user_id, post_type, content, visibility, created_at, updated_at)
+        VALUES ($1, 'universal', 'Hello from GraphQL', 'public', $2, $2)
+        RETURNING id
+        "#
+    )
+    .bind(author_id)
+    .bind(created_at)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to insert post");
+
+    let query = r#"
+        query($cursor: DateTime) {
+            feed(cursor: $cursor, limit: 1) { id content reactionCount commentCount author { id username } }
+        }
+    "#;
+    let cursor = (created_at + Duration::microseconds(1)).to_rfc3339();
+    let body = graphql(&client, &test_app.address, &author.token, query, json!({ "cursor": cursor })).await;
+    assert!(body["errors"].is_null(), "Unexpected errors: {}", body["errors"]);
+    let feed = body["data"]["feed"].as_array().unwrap();
+    assert_eq!(feed.len(), 1);
+    assert_eq!(feed[0]["id"], post_id.to_string());
+    assert_eq!(feed[0]["content"], "Hello from GraphQL");
+    assert_eq!(feed[0]["reactionCount"], 0);
+    assert_eq!(feed[0]["author"]["id"], author_id.to_string());
+
+    let response = client
+        .post(format!("{}/graphql", test_app.address))
+        .json(&json!({ "query": "{ me { id } }" }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(401, response.status().as_u16());
+}
+
+/// Sub-second offset so concurrent runs don't pick the same timestamp
+fn rand_offset() -> i64 {
+    (Uuid::new_v4().as_u128() % 1_000_000) as i64
+}
@@ -0,0 +1,171 @@
+//! Halftime report tests
+//!
+//! - Pace doubles the halftime score and compares it with previous meetings of the teams
+//! - The job reports on each running game once it passes its midpoint, into the notification center
+
+use chrono::{Duration, TimeZone, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use riina_backend::game::halftime::{halftime_at, halftime_message, team_pace};
+use riina_backend::models::halftime::{HalftimeContributor, Pace};
+use riina_backend::services::HalftimeReportService;
+
+mod common;
+use common::utils::spawn_app;
+
+#[test]
+fn halftime_is_the_middle_of_the_game_window() {
+    let start = Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap();
+    assert_eq!(halftime_at(start, start + Duration::days(7)), start + Duration::hours(84));
+}
+
+#[test]
+fn pace_compares_the_projection_with_previous_meetings() {
+    let pace = team_pace(60, &[100, 120]);
+    assert_eq!(pace.projected_score, 120);
+    assert_eq!(pace.previous_average, Some(110.0));
+    assert_eq!(pace.pace, Some(Pace::OnPace));
+
+    assert_eq!(team_pace(70, &[100]).pace, Some(Pace::Ahead));
+    assert_eq!(team_pace(40, &[100]).pace, Some(Pace::Behind));
+    assert_eq!(team_pace(40, &[]).pace, None, "First meeting");
+}
+
+#[test]
+fn halftime_message_names_the_top_contributor() {
+    let top = HalftimeContributor { user_id: Uuid::new_v4(), username: "ann".to_string(), team_side: "home".to_string(), points: 25 };
+    assert_eq!(halftime_message("Rockets", "Comets", 40, 32, &[]), "Halftime: Rockets 40 – 32 Comets");
+    assert_eq!(
+        halftime_message("Rockets", "Comets", 40, 32, &[top]),
+        "Halftime: Rockets 40 – 32 Comets. Top contributor: ann with 25 points"
+    );
+}
+
+async fn insert_user(pool: &PgPool) -> (Uuid, String) {
+    let username = format!("halftime_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let id = sqlx::query_scalar("INSERT INTO users (username, password_hash, email) VALUES ($1, 'x', $2) RETURNING id")
+        .bind(&username)
+        .bind(format!("{username}@example.com"))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    (id, username)
+}
+
+async fn insert_team(pool: &PgPool, league_id: Uuid, owner_id: Uuid, name: &str) -> Uuid {
+    let team_id = sqlx::query_scalar("INSERT INTO teams (user_id, team_name, league_id) VALUES ($1, $2, $3) RETURNING id")
+        .bind(owner_id)
+        .bind(name)
+        .bind(league_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO team_members (team_id, user_id, role, status) VALUES ($1, $2, 'owner', 'active')")
+        .bind(team_id)
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    team_id
+}
+
+#[tokio::test]
+async fn running_games_get_one_halftime_report() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+
+    let league_id: Uuid = sqlx::query_scalar("INSERT INTO leagues (name, max_teams) VALUES ($1, 2) RETURNING id")
+        .bind(format!("Halftime League {tag}"))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let (home_owner, home_username) = insert_user(pool).await;
+    let (away_owner, _) = insert_user(pool).await;
+    let home_team = insert_team(pool, league_id, home_owner, &format!("Rockets {tag}")).await;
+    let away_team = insert_team(pool, league_id, away_owner, &format!("Comets {tag}")).await;
+
+    let now = Utc::now();
+    let season_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO league_seasons (league_id, name, start_date, end_date) VALUES ($1, 'Halftime Season', $2, $3) RETURNING id",
+    )
+    .bind(league_id)
+    .bind(now - Duration::days(14))
+    .bind(now + Duration::days(7))
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO league_teams (season_id, team_id) VALUES ($1, $2), ($1, $3)")
+        .bind(season_id)
+        .bind(home_team)
+        .bind(away_team)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    // Last week the home team scored 40 as the away side
+    let insert_game = |week: i32, home: Uuid, away: Uuid, status: &'static str, start: chrono::DateTime<Utc>, end: chrono::DateTime<Utc>, scores: (i32, i32)| {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO games (season_id, home_team_id, away_team_id, week_number, status, game_start_time, game_end_time,
+                               home_score, away_score)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(season_id)
+        .bind(home)
+        .bind(away)
+        .bind(week)
+        .bind(status)
+        .bind(start)
+        .bind(end)
+        .bind(scores.0)
+        .bind(scores.1)
+        .fetch_one(pool)
+    };
+    insert_game(1, away_team, home_team, "evaluated", now - Duration::days(8), now - Duration::days(7), (30, 40)).await.unwrap();
+    let game_id = insert_game(2, home_team, away_team, "in_progress", now - Duration::hours(2), now + Duration::hours(1), (25, 10))
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side, score_points, power_contribution, description)
+        VALUES ($1, $2, $3, $4, 'home', 25, 0, 'Workout completed')
+        "#,
+    )
+    .bind(game_id)
+    .bind(home_owner)
+    .bind(&home_username)
+    .bind(home_team)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let service = HalftimeReportService::new(pool.clone(), None);
+    assert!(service.report_halftimes().await.unwrap() >= 1);
+    service.report_halftimes().await.unwrap();
+
+    let reports: Vec<(String, serde_json::Value, serde_json::Value)> =
+        sqlx::query_as("SELECT message, home_pace, top_contributors FROM game_halftime_reports WHERE game_id = $1")
+            .bind(game_id)
+            .fetch_all(pool)
+            .await
+            .unwrap();
+    assert_eq!(reports.len(), 1, "One report per game");
+    let (message, home_pace, top_contributors) = &reports[0];
+    assert_eq!(message, &format!("Halftime: Rockets {tag} 25 – 10 Comets {tag}. Top contributor: {home_username} with 25 points"));
+    assert_eq!(home_pace["projected_score"], 50);
+    assert_eq!(home_pace["pace"], "ahead");
+    assert_eq!(top_contributors[0]["team_side"], "home");
+
+    let notified: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE entity_id = $1 AND notification_type = 'halftime_report'",
+    )
+    .bind(game_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    assert_eq!(notified, 2, "Both teams' members find it in the notification center");
+}
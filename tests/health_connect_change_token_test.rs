@@ -0,0 +1,117 @@
+//! Health Connect change token tests
+//!
+//! Covers incremental syncs based on stored change tokens:
+//! - A device without a stored token has to do a full sync
+//! - Delta syncs based on the stored token are accepted
+//! - Stale or expired tokens ask for a full resync and are dropped
+//! - Tokens can be stored, read and cleared per device
+
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, TestApp};
+
+async fn check_sync(test_app: &TestApp, client: &Client, token: &str, body: serde_json::Value) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/health/check_sync_status", test_app.address),
+        token,
+        Some(body),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+async fn store_token(test_app: &TestApp, client: &Client, token: &str, change_token: &str) {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::PUT,
+        &format!("{}/health/sync/change-token", test_app.address),
+        token,
+        Some(json!({ "device_id": "pixel-8", "change_token": change_token })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+}
+
+async fn get_token(test_app: &TestApp, client: &Client, token: &str) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::GET,
+        &format!("{}/health/sync/change-token?device_id=pixel-8", test_app.address),
+        token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+#[tokio::test]
+async fn delta_syncs_are_checked_against_the_stored_change_token() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let stored = get_token(&test_app, &client, &user.token).await;
+    assert_eq!(stored["full_resync_required"], true);
+    assert!(stored["change_token"].is_null());
+
+    // A full sync names the device but no token
+    let data = check_sync(&test_app, &client, &user.token, json!({ "workouts": [], "device_id": "pixel-8" })).await;
+    assert_eq!(data["change_token_status"], "full_sync");
+    assert_eq!(data["full_resync_required"], false);
+
+    store_token(&test_app, &client, &user.token, "token-1").await;
+    let stored = get_token(&test_app, &client, &user.token).await;
+    assert_eq!(stored["change_token"], "token-1");
+    assert_eq!(stored["full_resync_required"], false);
+
+    let data = check_sync(&test_app, &client, &user.token, json!({
+        "workouts": [], "device_id": "pixel-8", "change_token": "token-1"
+    })).await;
+    assert_eq!(data["change_token_status"], "valid");
+    assert_eq!(data["full_resync_required"], false);
+
+    // A delta based on an older token asks for a full resync and drops the stored token
+    let data = check_sync(&test_app, &client, &user.token, json!({
+        "workouts": [], "device_id": "pixel-8", "change_token": "token-0"
+    })).await;
+    assert_eq!(data["change_token_status"], "unknown");
+    assert_eq!(data["full_resync_required"], true);
+    assert_eq!(get_token(&test_app, &client, &user.token).await["full_resync_required"], true);
+
+    // Tokens not used for too long expire
+    store_token(&test_app, &client, &user.token, "token-2").await;
+    sqlx::query!(
+        "UPDATE health_connect_change_tokens SET expires_at = NOW() - INTERVAL '1 day' WHERE user_id = $1",
+        user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(get_token(&test_app, &client, &user.token).await["full_resync_required"], true);
+    let data = check_sync(&test_app, &client, &user.token, json!({
+        "workouts": [], "device_id": "pixel-8", "change_token": "token-2"
+    })).await;
+    assert_eq!(data["change_token_status"], "expired");
+    assert_eq!(data["full_resync_required"], true);
+
+    // Syncs without a device keep working as before
+    let data = check_sync(&test_app, &client, &user.token, json!({ "workouts": [] })).await;
+    assert!(data["change_token_status"].is_null());
+    assert_eq!(data["full_resync_required"], false);
+
+    store_token(&test_app, &client, &user.token, "token-3").await;
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::DELETE,
+        &format!("{}/health/sync/change-token?device_id=pixel-8", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    assert_eq!(204, response.status().as_u16());
+    assert_eq!(get_token(&test_app, &client, &user.token).await["full_resync_required"], true);
+}
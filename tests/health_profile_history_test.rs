@@ -0,0 +1,108 @@
+//! Health profile history tests
+//!
+//! Covers snapshots of health profile values over time:
+//! - Every profile change adds a snapshot, listed oldest first by `/profile/health_profile/history`
+//! - Saving unchanged values does not add a snapshot
+//! - The profile used for a workout is the one that was current at the workout's start
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::db::health_data::get_user_health_profile_at;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request};
+use common::workout_data_helpers::create_test_user_with_health_profile;
+
+#[tokio::test]
+async fn health_profile_changes_are_kept_as_history() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    for resting_heart_rate in [52, 52] {
+        let response = make_authenticated_request(
+            &client,
+            reqwest::Method::PUT,
+            &format!("{}/profile/health_profile", test_app.address),
+            &user.token,
+            Some(json!({ "resting_heart_rate": resting_heart_rate })),
+        ).await;
+        assert_eq!(200, response.status().as_u16());
+    }
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/profile/health_profile/history", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let history = body["data"].as_array().unwrap();
+
+    assert_eq!(history.first().unwrap()["resting_heart_rate"], 60);
+    assert_eq!(history.last().unwrap()["resting_heart_rate"], 52);
+    let resting_hr_changes = history.windows(2)
+        .filter(|pair| pair[0]["resting_heart_rate"] != pair[1]["resting_heart_rate"])
+        .count();
+    assert_eq!(resting_hr_changes, 1, "Saving the same value twice should not add a snapshot");
+    let valid_from: Vec<&str> = history.iter().map(|s| s["valid_from"].as_str().unwrap()).collect();
+    let mut sorted = valid_from.clone();
+    sorted.sort();
+    assert_eq!(valid_from, sorted, "History should be ordered oldest first");
+
+    let limited = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/profile/health_profile/history?limit=1", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    let body: serde_json::Value = limited.json().await.unwrap();
+    let limited = body["data"].as_array().unwrap();
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0]["resting_heart_rate"], 52, "The limit keeps the most recent snapshots");
+}
+
+#[tokio::test]
+async fn workouts_use_the_profile_that_was_current_at_their_start() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    // Move the initial snapshot ten days back, then change the resting heart rate today
+    sqlx::query!(
+        "UPDATE user_health_profile_history SET valid_from = NOW() - INTERVAL '10 days' WHERE user_id = $1",
+        user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/profile/health_profile", test_app.address),
+        &user.token,
+        Some(json!({ "resting_heart_rate": 48 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let five_days_ago = get_user_health_profile_at(&test_app.db_pool, user.user_id, Utc::now() - Duration::days(5))
+        .await
+        .unwrap();
+    assert_eq!(five_days_ago.resting_heart_rate, 60);
+
+    let now = get_user_health_profile_at(&test_app.db_pool, user.user_id, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(now.resting_heart_rate, 48);
+
+    // Workouts from before the first snapshot fall back to the earliest profile
+    let before_history = get_user_health_profile_at(&test_app.db_pool, user.user_id, Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+    assert_eq!(before_history.resting_heart_rate, 60);
+}
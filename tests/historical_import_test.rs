@@ -0,0 +1,155 @@
+//! Historical data import tests
+//!
+//! Covers `/admin/organizations/{id}/import`:
+//! - CSVs are parsed with quoting, and every invalid row is reported at once
+//! - Dry runs and imports with issues keep nothing; imports create users, teams, games and standings
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::league::historical_import::plan_import;
+use riina_backend::models::organization::HistoricalImportRequest;
+use riina_backend::utils::csv_reader::parse_csv;
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn request(users_csv: &str, teams_csv: &str, results_csv: Option<&str>) -> HistoricalImportRequest {
+    HistoricalImportRequest {
+        league_name: "Office League".to_string(),
+        season_name: None,
+        users_csv: Some(users_csv.to_string()),
+        teams_csv: Some(teams_csv.to_string()),
+        results_csv: results_csv.map(str::to_string),
+        dry_run: None,
+    }
+}
+
+#[test]
+fn csv_fields_can_be_quoted() {
+    let records = parse_csv("\u{feff}Name,Notes\r\n\"Smith, Jo\",\"Said \"\"hi\"\"\"\n\nAnn,\n").unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].get("name"), Some("Smith, Jo"));
+    assert_eq!(records[0].get("notes"), Some("Said \"hi\""));
+    assert_eq!(records[1].line, 4, "Lines count from the header, blank ones included");
+    assert_eq!(records[1].get("notes"), None);
+
+    assert!(parse_csv("name,notes\nAnn\n").is_err(), "Field count must match the header");
+    assert!(parse_csv("name\n\"unterminated\n").is_err());
+    assert!(parse_csv("").is_err());
+}
+
+#[test]
+fn valid_imports_are_planned() {
+    let plan = plan_import(&request(
+        "username,email\nann,ann@example.com\nbob,bob@example.com\ncid,cid@example.com\n",
+        "team_name,owner,color,members\nRed Rockets,ann,#FF0000,bob\nBlue Jays,cid,,\n",
+        Some("week,home_team,away_team,home_score,away_score,played_on\n1,Red Rockets,blue jays,3,1,2025-09-14\n"),
+    ))
+    .unwrap();
+
+    assert_eq!(plan.users.len(), 3);
+    assert_eq!(plan.teams[0].members, vec!["bob"]);
+    assert_eq!(plan.teams[1].color, None);
+    assert_eq!(plan.results[0].away_team, "Blue Jays", "Team names match case-insensitively");
+}
+
+#[test]
+fn every_invalid_row_is_reported() {
+    let issues = plan_import(&request(
+        "username,email\nann,ann@example.com\nann,other@example.com\nb,b@example.com\n",
+        "team_name,owner,color,members\nRed Rockets,ann,red,\nBlue Jays,cid,,ann\n",
+        Some("week,home_team,away_team,home_score,away_score,played_on\n0,Red Rockets,Blue Jays,3,1,2025-09-14\n1,Blue Jays,Green Men,1,1,2025-09-21\n1,Blue Jays,Blue Jays,1,-1,14.09.2025\n"),
+    ))
+    .unwrap_err();
+
+    let lines: Vec<(&str, usize)> = issues.iter().map(|i| (i.file.as_str(), i.line)).collect();
+    assert_eq!(
+        lines,
+        vec![("users", 3), ("users", 4), ("teams", 2), ("results", 2), ("results", 3), ("results", 4)]
+    );
+}
+
+#[tokio::test]
+async fn admins_import_a_league_from_csv() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/admin/organizations", test_app.address), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let organization_id = body["data"][0]["id"].as_str().unwrap().to_string();
+    let import_url = format!("{}/admin/organizations/{}/import", test_app.address, organization_id);
+
+    let users_csv = format!(
+        "username,email\nann{tag},ann{tag}@example.com\nbob{tag},bob{tag}@example.com\ncid{tag},cid{tag}@example.com\ndee{tag},dee{tag}@example.com\n"
+    );
+    let teams_csv = format!("team_name,owner,members\nRockets {tag},ann{tag},bob{tag}\nJays {tag},cid{tag},\n");
+    let results_csv = format!(
+        "week,home_team,away_team,home_score,away_score,played_on\n1,Rockets {tag},Jays {tag},3,1,2025-09-14\n2,Jays {tag},Rockets {tag},2,2,2025-09-21\n"
+    );
+    let body = json!({
+        "league_name": format!("Office League {tag}"),
+        "users_csv": users_csv,
+        "teams_csv": teams_csv,
+        "results_csv": results_csv,
+    });
+    let user_count = |username: String| {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_one(&test_app.db_pool)
+    };
+
+    let mut dry_run = body.clone();
+    dry_run["dry_run"] = json!(true);
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &import_url, &admin.token, Some(dry_run)).await;
+    assert_eq!(response.status(), 200);
+    let report: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(report["data"]["games_imported"], 2);
+    assert_eq!(user_count(format!("ann{tag}")).await.unwrap(), 0, "Dry runs keep nothing");
+
+    // A conflict with existing data rolls back the rows that did import
+    let mut conflicting = body.clone();
+    conflicting["users_csv"] = json!(format!("{users_csv}{},x{tag}@example.com\n", admin.username));
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &import_url, &admin.token, Some(conflicting)).await;
+    assert_eq!(response.status(), 422);
+    let report: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(report["data"]["issues"][0]["line"], 6);
+    assert_eq!(user_count(format!("ann{tag}")).await.unwrap(), 0);
+
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &import_url, &admin.token, Some(body)).await;
+    assert_eq!(response.status(), 201);
+    let report: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(report["data"]["users_created"], 4);
+    assert_eq!(report["data"]["teams_created"], 2);
+    assert_eq!(report["data"]["members_added"], 3);
+    let season_id = Uuid::parse_str(report["data"]["season_id"].as_str().unwrap()).unwrap();
+
+    let standings = sqlx::query_as::<_, (String, i32, i32, i32)>(
+        r#"
+        SELECT t.team_name, ls.wins, ls.draws, ls.position
+        FROM league_standings ls JOIN teams t ON t.id = ls.team_id
+        WHERE ls.season_id = $1
+        ORDER BY ls.position
+        "#,
+    )
+    .bind(season_id)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(standings, vec![(format!("Rockets {tag}"), 1, 1, 1), (format!("Jays {tag}"), 0, 1, 2)]);
+
+    let in_pool: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM player_pool pp JOIN users u ON u.id = pp.user_id WHERE u.username = $1",
+    )
+    .bind(format!("dee{tag}"))
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(in_pool, 1, "Imported users without a team join the player pool");
+}
@@ -0,0 +1,64 @@
+//! Heart rate downsampling tests
+//!
+//! Covers the `?resolution=` of the workout detail endpoint:
+//! - `raw` keeps every sample, `10s` and `60s` average samples into buckets
+//! - gaps in the recording stay gaps and unordered samples are sorted
+
+use chrono::{DateTime, Duration, Utc};
+
+use riina_backend::models::workout_data::HeartRateData;
+use riina_backend::workout::hr_downsampling::{downsample, HrResolution};
+
+/// One sample per second; `hr_at` gives the heart rate for each second offset
+fn samples(start: DateTime<Utc>, seconds: i64, hr_at: impl Fn(i64) -> i32) -> Vec<HeartRateData> {
+    (0..seconds)
+        .map(|s| HeartRateData { timestamp: start + Duration::seconds(s), heart_rate: hr_at(s) })
+        .collect()
+}
+
+#[test]
+fn three_hour_ride_is_reduced_to_one_point_a_minute() {
+    let start = Utc::now();
+    let ride = samples(start, 3 * 3600, |s| 120 + (s % 7) as i32);
+
+    assert_eq!(downsample(&ride, HrResolution::Raw).len(), ride.len());
+    assert_eq!(downsample(&ride, HrResolution::TenSeconds).len(), 3 * 360);
+
+    let minutes = downsample(&ride, HrResolution::SixtySeconds);
+    assert_eq!(minutes.len(), 3 * 60);
+    assert_eq!(minutes[0].timestamp, start);
+    assert_eq!(minutes[1].timestamp, start + Duration::seconds(60));
+}
+
+#[test]
+fn buckets_hold_the_average_heart_rate() {
+    let start = Utc::now();
+    // 10 seconds at 100 bpm, then 10 seconds alternating 150 and 161
+    let data = samples(start, 20, |s| if s < 10 { 100 } else if s % 2 == 0 { 150 } else { 161 });
+
+    let points = downsample(&data, HrResolution::TenSeconds);
+    assert_eq!(points.iter().map(|p| p.heart_rate).collect::<Vec<_>>(), vec![100, 156]);
+}
+
+#[test]
+fn gaps_in_the_recording_stay_gaps() {
+    let start = Utc::now();
+    let mut data = samples(start, 60, |_| 130);
+    // The watch lost contact for five minutes
+    data.extend(samples(start + Duration::seconds(360), 60, |_| 140));
+    data.reverse();
+
+    let points = downsample(&data, HrResolution::SixtySeconds);
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[1].timestamp, start + Duration::seconds(360));
+    assert_eq!(points[1].heart_rate, 140);
+}
+
+#[test]
+fn resolutions_are_parsed_from_the_query() {
+    assert_eq!(HrResolution::parse("raw"), Some(HrResolution::Raw));
+    assert_eq!(HrResolution::parse("10s"), Some(HrResolution::TenSeconds));
+    assert_eq!(HrResolution::parse("60s"), Some(HrResolution::SixtySeconds));
+    assert_eq!(HrResolution::parse("5s"), None);
+    assert!(downsample(&[], HrResolution::SixtySeconds).is_empty());
+}
@@ -0,0 +1,116 @@
+//! Heart rate trend tests
+//!
+//! Covers the per-workout HR metrics (steady-state HR, cardiac drift) and the
+//! `/health/analytics/hr-trends` endpoint fed by the nightly job.
+
+use reqwest::Client;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::workout_data_helpers::create_test_user_with_health_profile;
+use riina_backend::models::workout_data::HeartRateData;
+use riina_backend::services::HrTrendService;
+use riina_backend::workout::hr_trends::calculate_hr_workout_metrics;
+
+/// One sample per minute; `hr_at` gives the heart rate for each minute offset
+fn samples(start: DateTime<Utc>, minutes: i64, hr_at: impl Fn(i64) -> i32) -> Vec<HeartRateData> {
+    (0..=minutes)
+        .map(|m| HeartRateData { timestamp: start + Duration::minutes(m), heart_rate: hr_at(m) })
+        .collect()
+}
+
+#[test]
+fn short_workout_has_no_metrics() {
+    let metrics = calculate_hr_workout_metrics(&samples(Utc::now(), 15, |_| 140));
+
+    assert_eq!(metrics.steady_state_avg_hr, None);
+    assert_eq!(metrics.hr_drift_pct, None);
+}
+
+#[test]
+fn steady_state_ignores_warmup() {
+    // 10 minutes of warm-up at 100 bpm, then 20 minutes at 150 bpm
+    let metrics = calculate_hr_workout_metrics(&samples(Utc::now(), 30, |m| if m < 10 { 100 } else { 150 }));
+
+    assert_eq!(metrics.steady_state_avg_hr, Some(150.0));
+    assert_eq!(metrics.hr_drift_pct, None, "Drift is only measured on long workouts");
+}
+
+#[test]
+fn drift_compares_second_half_with_first_half() {
+    // After warm-up: 140 bpm for the first 20 minutes, 147 bpm for the last 20 minutes
+    let metrics = calculate_hr_workout_metrics(&samples(Utc::now(), 50, |m| match m {
+        0..=9 => 110,
+        10..=29 => 140,
+        _ => 147,
+    }));
+
+    let drift = metrics.hr_drift_pct.expect("Long workout should have drift");
+    assert!((drift - 5.0).abs() < 0.01, "Expected 5% drift, got {}", drift);
+}
+
+#[test]
+fn unordered_samples_are_sorted() {
+    let mut data = samples(Utc::now(), 30, |m| if m < 10 { 100 } else { 150 });
+    data.reverse();
+
+    assert_eq!(calculate_hr_workout_metrics(&data).steady_state_avg_hr, Some(150.0));
+}
+
+#[tokio::test]
+async fn hr_trends_endpoint_returns_nightly_metrics() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&user.token);
+
+    // A long run with some drift, inserted directly so no scoring is involved
+    let start = Utc::now() - Duration::days(2);
+    let hr_data = samples(start, 50, |m| if m < 30 { 140 } else { 147 });
+    sqlx::query(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                  workout_start, workout_end, activity_name)
+        VALUES ($1, 'test-device', $2, $3, $4, $5, 'Running')
+        "#
+    )
+    .bind(user_id)
+    .bind(json!(hr_data))
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(start)
+    .bind(start + Duration::minutes(50))
+    .execute(&test_app.db_pool)
+    .await
+    .expect("Failed to insert workout");
+
+    let summary = HrTrendService::new(test_app.db_pool.clone())
+        .run_nightly()
+        .await
+        .expect("Nightly HR trend job failed");
+    assert!(summary.workouts_processed >= 1);
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/health/analytics/hr-trends?days=30", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let data = &body["data"];
+
+    assert_eq!(data["days"], 30);
+    assert_eq!(data["resting_hr"].as_array().unwrap().len(), 1, "Today's resting HR should be snapshotted");
+
+    let running = data["workload_hr"].as_array().unwrap().iter()
+        .find(|a| a["activity"] == "Running")
+        .expect("Running trend should be present");
+    assert_eq!(running["points"][0]["workouts"], 1);
+
+    let drift = data["hr_drift"].as_array().unwrap();
+    assert_eq!(drift.len(), 1);
+    assert!(drift[0]["avg_drift_pct"].as_f64().unwrap() > 0.0);
+}
@@ -0,0 +1,220 @@
+//! Inactivity nudge tests
+//!
+//! Covers the daily re-engagement job:
+//! - Members without uploads for the season's threshold are nudged once per inactivity period
+//! - Members are nudged by email too, unless they turned the email channel off
+//! - The team captain is told about inactive members
+//! - Opt-outs from `/notifications/preferences` and the per-season threshold are honored
+
+use std::sync::Arc;
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+use riina_backend::config::email::EmailSettings;
+use riina_backend::services::{EmailService, InactivityNudgeService};
+
+async fn notification_count(pool: &sqlx::PgPool, recipient_id: Uuid, notification_type: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE recipient_id = $1 AND notification_type = $2"
+    )
+    .bind(recipient_id)
+    .bind(notification_type)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to count notifications")
+}
+
+async fn nudge_channels(pool: &sqlx::PgPool, user_id: Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT delivered_channels FROM inactivity_nudges WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to load nudge")
+}
+
+/// League with one team captained by `captain_id`, started 10 days ago with a 3-day threshold
+async fn setup_running_season(
+    test_app: &common::utils::TestApp,
+    admin_token: &str,
+    captain_id: Uuid,
+    other_owner_id: Uuid,
+    member_ids: &[Uuid],
+) -> (String, Uuid, Uuid) {
+    let league = create_league_with_teams(
+        &test_app.address, admin_token, 2, 2, Some(vec![captain_id, other_owner_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, admin_token, &league.league_id, "Nudge Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+    let team_id = Uuid::parse_str(&league.team_ids[0]).unwrap();
+
+    for member_id in member_ids {
+        sqlx::query("INSERT INTO team_members (team_id, user_id, role, status) VALUES ($1, $2, 'member', 'active')")
+            .bind(team_id)
+            .bind(member_id)
+            .execute(&test_app.db_pool)
+            .await
+            .expect("Failed to add team member");
+    }
+
+    sqlx::query(
+        "UPDATE league_seasons SET start_date = NOW() - INTERVAL '10 days', end_date = NOW() + INTERVAL '30 days' WHERE id = $1"
+    )
+    .bind(season_id)
+    .execute(&test_app.db_pool)
+    .await
+    .expect("Failed to start season");
+    sqlx::query(
+        "UPDATE team_members SET joined_at = NOW() - INTERVAL '10 days' WHERE team_id IN (SELECT team_id FROM league_teams WHERE season_id = $1)"
+    )
+    .bind(season_id)
+    .execute(&test_app.db_pool)
+    .await
+    .expect("Failed to backdate memberships");
+
+    (league.league_id, season_id, team_id)
+}
+
+#[tokio::test]
+async fn inactive_members_are_nudged_and_captain_is_notified() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let other_owner = create_test_user_and_login(&test_app.address).await;
+    let inactive = create_test_user_and_login(&test_app.address).await;
+    let opted_out = create_test_user_and_login(&test_app.address).await;
+    let no_email = create_test_user_and_login(&test_app.address).await;
+    let active = create_test_user_and_login(&test_app.address).await;
+    let captain_id = parse_user_id_from_jwt_token(&captain.token);
+    let inactive_id = parse_user_id_from_jwt_token(&inactive.token);
+    let opted_out_id = parse_user_id_from_jwt_token(&opted_out.token);
+    let no_email_id = parse_user_id_from_jwt_token(&no_email.token);
+    let active_id = parse_user_id_from_jwt_token(&active.token);
+
+    setup_running_season(
+        &test_app,
+        &admin.token,
+        captain_id,
+        parse_user_id_from_jwt_token(&other_owner.token),
+        &[inactive_id, opted_out_id, no_email_id, active_id],
+    ).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/notifications/preferences", test_app.address),
+        &opted_out.token,
+        Some(json!({ "inactivity_nudges_enabled": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/notifications/preferences", test_app.address),
+        &no_email.token,
+        Some(json!({ "email_enabled": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["email_enabled"], false);
+
+    // One member uploaded yesterday
+    sqlx::query(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid, workout_start, workout_end, created_at)
+        VALUES ($1, 'test-device', '[]'::jsonb, $2, NOW() - INTERVAL '1 day', NOW() - INTERVAL '23 hours', NOW() - INTERVAL '1 day')
+        "#
+    )
+    .bind(active_id)
+    .bind(Uuid::new_v4().to_string())
+    .execute(&test_app.db_pool)
+    .await
+    .expect("Failed to insert workout");
+
+    let service = InactivityNudgeService::new(test_app.db_pool.clone())
+        .with_email(Arc::new(EmailService::new(EmailSettings::default())));
+    service.run().await.expect("Inactivity nudge job failed");
+
+    assert_eq!(notification_count(&test_app.db_pool, inactive_id, "inactivity_nudge").await, 1);
+    assert!(nudge_channels(&test_app.db_pool, inactive_id).await.contains(&"email".to_string()));
+    assert_eq!(notification_count(&test_app.db_pool, no_email_id, "inactivity_nudge").await, 1);
+    assert!(!nudge_channels(&test_app.db_pool, no_email_id).await.contains(&"email".to_string()), "Email opt-out is honored");
+    assert_eq!(notification_count(&test_app.db_pool, opted_out_id, "inactivity_nudge").await, 0, "Opt-out is honored");
+    assert_eq!(notification_count(&test_app.db_pool, active_id, "inactivity_nudge").await, 0, "Active member is left alone");
+    assert_eq!(notification_count(&test_app.db_pool, captain_id, "inactivity_nudge").await, 1, "Inactive captain is nudged too");
+
+    // The captain hears about both inactive members, even the one who muted their own nudges
+    let captain_alerts: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT entity_id FROM notifications WHERE recipient_id = $1 AND notification_type = 'team_member_inactive'"
+    )
+    .bind(captain_id)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(captain_alerts.len(), 3);
+    assert!(captain_alerts.contains(&inactive_id));
+    assert!(captain_alerts.contains(&opted_out_id));
+
+    // Nobody is nudged twice for the same inactivity period
+    service.run().await.expect("Inactivity nudge job failed");
+    assert_eq!(notification_count(&test_app.db_pool, inactive_id, "inactivity_nudge").await, 1);
+    assert_eq!(notification_count(&test_app.db_pool, captain_id, "team_member_inactive").await, 3);
+}
+
+#[tokio::test]
+async fn seasons_can_disable_inactivity_nudges() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let other_owner = create_test_user_and_login(&test_app.address).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let captain_id = parse_user_id_from_jwt_token(&captain.token);
+    let member_id = parse_user_id_from_jwt_token(&member.token);
+
+    let (league_id, season_id, _) = setup_running_season(
+        &test_app,
+        &admin.token,
+        captain_id,
+        parse_user_id_from_jwt_token(&other_owner.token),
+        &[member_id],
+    ).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PATCH,
+        &format!("{}/admin/leagues/{}/seasons/{}", test_app.address, league_id, season_id),
+        &admin.token,
+        Some(json!({ "inactivity_nudge_days": 0 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"].get("inactivity_nudge_days"), Some(&serde_json::Value::Null));
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PATCH,
+        &format!("{}/admin/leagues/{}/seasons/{}", test_app.address, league_id, season_id),
+        &admin.token,
+        Some(json!({ "inactivity_nudge_days": 365 })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    InactivityNudgeService::new(test_app.db_pool.clone())
+        .run()
+        .await
+        .expect("Inactivity nudge job failed");
+
+    assert_eq!(notification_count(&test_app.db_pool, member_id, "inactivity_nudge").await, 0);
+    assert_eq!(notification_count(&test_app.db_pool, captain_id, "team_member_inactive").await, 0);
+}
@@ -0,0 +1,132 @@
+//! JWT key rotation tests
+//!
+//! Covers signing and verifying access tokens with `JwtSettings`:
+//! - tokens carry the current key's id and verify with it
+//! - after a rotation, tokens signed with a previous key keep working
+//! - tokens from another issuer or for another audience, or signed with unknown keys, are refused
+//! - tokens without issuer and audience are accepted until the legacy window closes
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use uuid::Uuid;
+
+use riina_backend::config::jwt::{JwtKey, JwtSettings};
+use riina_backend::middleware::auth::Claims;
+use riina_backend::models::user::{UserRole, UserStatus};
+
+fn claims(iss: &str, aud: &str) -> Claims {
+    Claims {
+        sub: Uuid::new_v4().to_string(),
+        username: "rotation_user".to_string(),
+        role: UserRole::User,
+        status: UserStatus::Active,
+        exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+        iss: Some(iss.to_string()),
+        aud: Some(aud.to_string()),
+        sid: None,
+        impersonated_by: None,
+    }
+}
+
+fn settings(key_id: &str, secret: &str, previous_keys: Vec<JwtKey>) -> JwtSettings {
+    JwtSettings {
+        key_id: key_id.to_string(),
+        previous_keys,
+        ..JwtSettings::new(secret.to_string(), 24, 30)
+    }
+}
+
+#[test]
+fn tokens_signed_before_a_rotation_keep_working() {
+    let before = settings("2026-01", "old_secret", Vec::new());
+    let token = before.sign(&claims(&before.issuer, &before.audience)).unwrap();
+    assert_eq!(jsonwebtoken::decode_header(&token).unwrap().kid.as_deref(), Some("2026-01"));
+
+    let after = settings("2026-07", "new_secret", vec![JwtKey::new("2026-01".to_string(), "old_secret".to_string())]);
+    let verified: Claims = after.verify(&token).unwrap();
+    assert_eq!(verified.username, "rotation_user");
+
+    let new_token = after.sign(&claims(&after.issuer, &after.audience)).unwrap();
+    assert!(after.verify::<Claims>(&new_token).is_ok());
+
+    // Once the old key is dropped, its tokens stop working
+    let later = settings("2026-07", "new_secret", Vec::new());
+    assert!(later.verify::<Claims>(&token).is_err());
+    assert!(later.verify::<Claims>(&new_token).is_ok());
+}
+
+#[test]
+fn tokens_for_another_issuer_or_audience_are_refused() {
+    let jwt = settings("primary", "secret", Vec::new());
+
+    let wrong_issuer = jwt.sign(&claims("someone-else", &jwt.audience)).unwrap();
+    assert!(jwt.verify::<Claims>(&wrong_issuer).is_err());
+
+    let wrong_audience = jwt.sign(&claims(&jwt.issuer, "another-app")).unwrap();
+    assert!(jwt.verify::<Claims>(&wrong_audience).is_err());
+
+    let mut without_claims = claims(&jwt.issuer, &jwt.audience);
+    without_claims.iss = None;
+    without_claims.aud = None;
+    let token = jwt.sign(&without_claims).unwrap();
+    assert!(jwt.verify::<Claims>(&token).is_err());
+}
+
+#[test]
+fn tokens_without_issuer_and_audience_work_during_the_legacy_window() {
+    let mut jwt = settings("primary", "secret", Vec::new());
+    jwt.legacy_tokens_until = Some(Utc::now() + Duration::days(1));
+
+    // Signed the way tokens were before issuer and audience were added
+    let mut legacy = claims(&jwt.issuer, &jwt.audience);
+    legacy.iss = None;
+    legacy.aud = None;
+    let token = encode(&Header::default(), &legacy, &EncodingKey::from_secret(b"secret")).unwrap();
+    let verified: Claims = jwt.verify(&token).unwrap();
+    assert_eq!(verified.username, "rotation_user");
+
+    // Present claims are still checked
+    let mut wrong_issuer = claims("someone-else", &jwt.audience);
+    wrong_issuer.aud = None;
+    assert!(jwt.verify::<Claims>(&jwt.sign(&wrong_issuer).unwrap()).is_err());
+    let mut wrong_audience = claims(&jwt.issuer, "another-app");
+    wrong_audience.iss = None;
+    assert!(jwt.verify::<Claims>(&jwt.sign(&wrong_audience).unwrap()).is_err());
+
+    jwt.legacy_tokens_until = Some(Utc::now() - Duration::seconds(1));
+    assert!(jwt.verify::<Claims>(&token).is_err());
+}
+
+#[test]
+fn tokens_with_unknown_key_ids_are_refused() {
+    let jwt = settings("primary", "secret", Vec::new());
+    let header = Header { kid: Some("unknown".to_string()), ..Header::default() };
+    let token = encode(&header, &claims(&jwt.issuer, &jwt.audience), &EncodingKey::from_secret(b"secret")).unwrap();
+    assert!(jwt.verify::<Claims>(&token).is_err());
+
+    // Tokens without a key id predate key ids and are checked against the current key
+    let legacy = encode(&Header::default(), &claims(&jwt.issuer, &jwt.audience), &EncodingKey::from_secret(b"secret")).unwrap();
+    assert!(jwt.verify::<Claims>(&legacy).is_ok());
+}
+
+#[test]
+fn expired_tokens_only_verify_when_expiry_is_ignored() {
+    let jwt = settings("primary", "secret", Vec::new());
+    let mut expired = claims(&jwt.issuer, &jwt.audience);
+    expired.exp = (Utc::now() - Duration::hours(2)).timestamp() as usize;
+    let token = jwt.sign(&expired).unwrap();
+
+    assert!(jwt.verify::<Claims>(&token).is_err());
+    assert!(jwt.verify_ignoring_expiry::<Claims>(&token).is_ok());
+}
+
+#[test]
+fn previous_keys_are_parsed_from_pairs() {
+    let keys = JwtKey::parse_list("2025-12=first, 2026-01=second").unwrap();
+    assert_eq!(keys.iter().map(|key| key.id.as_str()).collect::<Vec<_>>(), vec!["2025-12", "2026-01"]);
+    assert!(JwtKey::parse_list("no-secret").is_err());
+    assert!(JwtKey::parse_list("").unwrap().is_empty());
+
+    let duplicate = settings("2026-01", "secret", JwtKey::parse_list("2026-01=older").unwrap());
+    assert!(duplicate.validate().is_err());
+}
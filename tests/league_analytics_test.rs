@@ -0,0 +1,137 @@
+//! League analytics tests
+//!
+//! - Weekly participation, workouts per user and drop-off cohorts are computed per season week
+//! - Score distributions cover single workouts and season totals per member
+//! - Only admins and captains of the league's teams can fetch the analytics
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::services::league_analytics_service::{build_league_analytics, MemberWorkout, SeasonWindow};
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league, create_league_season};
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+fn season_start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap()
+}
+
+fn workout(user_id: Uuid, days_into_season: i64, points: f64) -> MemberWorkout {
+    MemberWorkout {
+        user_id,
+        workout_start: season_start() + Duration::days(days_into_season) + Duration::hours(8),
+        points,
+    }
+}
+
+#[test]
+fn analytics_follow_members_through_season_weeks() {
+    let season = SeasonWindow {
+        id: Uuid::new_v4(),
+        name: "Winter".to_string(),
+        start_date: season_start(),
+        end_date: season_start() + Duration::weeks(8),
+    };
+    let (early, late, quitter, idle) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let workouts = vec![
+        workout(early, 0, 10.0),
+        workout(early, 1, 20.0),
+        workout(early, 15, 30.0),
+        workout(quitter, 2, 40.0),
+        workout(late, 8, 50.0),
+        workout(late, 16, 60.0),
+        // Not a member of the league
+        workout(Uuid::new_v4(), 3, 1000.0),
+    ];
+
+    // Three weeks into the season
+    let now = season_start() + Duration::days(20);
+    let analytics = build_league_analytics(Uuid::new_v4(), &season, now, &[early, late, quitter, idle], &workouts);
+
+    assert_eq!(analytics.member_count, 4);
+    assert_eq!(analytics.weeks_elapsed, 3);
+    assert_eq!(analytics.never_active, 1);
+    assert_eq!(analytics.participation_rate, 0.75);
+    assert_eq!(analytics.avg_workouts_per_user_per_week, 0.5);
+
+    let active: Vec<usize> = analytics.weekly.iter().map(|w| w.active_members).collect();
+    assert_eq!(active, vec![2, 1, 2]);
+    assert_eq!(analytics.weekly[0].workouts, 3);
+    assert_eq!(analytics.weekly[0].participation_rate, 0.5);
+
+    let first_cohort = &analytics.drop_off_cohorts[0];
+    assert_eq!(first_cohort.first_active_week, 1);
+    assert_eq!(first_cohort.members, 2);
+    assert_eq!(first_cohort.active_by_week, vec![2, 0, 1]);
+    assert_eq!(first_cohort.dropped_off, 1);
+    let second_cohort = &analytics.drop_off_cohorts[1];
+    assert_eq!((second_cohort.first_active_week, second_cohort.active_by_week.clone()), (2, vec![1, 1]));
+
+    let workouts = &analytics.workout_score_distribution;
+    assert_eq!((workouts.count, workouts.min, workouts.median, workouts.max), (6, 10.0, 30.0, 60.0));
+    assert_eq!(workouts.buckets.iter().map(|b| b.count).sum::<usize>(), 6);
+    let members = &analytics.member_score_distribution;
+    assert_eq!((members.count, members.min, members.max), (3, 40.0, 110.0));
+}
+
+#[test]
+fn analytics_of_an_empty_season_are_zero() {
+    let season = SeasonWindow {
+        id: Uuid::new_v4(),
+        name: "Upcoming".to_string(),
+        start_date: season_start(),
+        end_date: season_start() + Duration::weeks(4),
+    };
+    let analytics = build_league_analytics(Uuid::new_v4(), &season, season_start() - Duration::days(1), &[], &[]);
+
+    assert_eq!(analytics.weeks_elapsed, 0);
+    assert_eq!(analytics.participation_rate, 0.0);
+    assert!(analytics.weekly.is_empty());
+    assert_eq!(analytics.workout_score_distribution.count, 0);
+}
+
+#[tokio::test]
+async fn league_analytics_are_limited_to_admins_and_captains() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let league_id = create_league(&test_app.address, &admin.token, 4).await;
+
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let other_captain = create_test_user_and_login(&test_app.address).await;
+    for owner in [&captain, &other_captain] {
+        let response = make_authenticated_request(
+            &client,
+            Method::POST,
+            &format!("{}/league/teams/register", test_app.address),
+            &owner.token,
+            Some(json!({ "team_name": format!("Team_{}", &Uuid::new_v4().to_string()[..8]), "league_id": league_id })),
+        )
+        .await;
+        assert_eq!(response.status().as_u16(), 201);
+    }
+
+    let other_user = create_test_user_and_login(&test_app.address).await;
+    let url = format!("{}/league/{}/analytics", test_app.address, league_id);
+
+    // No season yet
+    let response = make_authenticated_request(&client, Method::GET, &url, &admin.token, None).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let start_date = (Utc::now() - Duration::days(1)).to_rfc3339();
+    create_league_season(&test_app.address, &admin.token, &league_id, "Analytics Season", &start_date).await;
+
+    let response = make_authenticated_request(&client, Method::GET, &url, &other_user.token, None).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    for token in [&admin.token, &captain.token] {
+        let response = make_authenticated_request(&client, Method::GET, &url, token, None).await;
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["data"]["member_count"], 2);
+        assert_eq!(body["data"]["league_id"], league_id);
+    }
+}
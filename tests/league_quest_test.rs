@@ -0,0 +1,111 @@
+//! League quest tests
+//!
+//! - Milestones are crossed once, in order, when progress passes their share of the target
+//! - Admins start quests via `/admin/leagues/{id}/quests`
+//! - Every player's workouts count toward the league's open quests, visible via `/league/{id}/quests`
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::league::quests::{crossed_milestones, milestone_message, normalize_milestones, progress_percent};
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+use common::utils::{create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token, spawn_app};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+#[test]
+fn milestones_are_crossed_once() {
+    let percents = [25, 50, 75, 100];
+    assert_eq!(crossed_milestones(&percents, 8848.0, 0.0, 2212.0), vec![25]);
+    assert_eq!(crossed_milestones(&percents, 8848.0, 2212.0, 6636.0), vec![50, 75]);
+    assert!(crossed_milestones(&percents, 8848.0, 2212.0, 3000.0).is_empty());
+    assert_eq!(crossed_milestones(&percents, 8848.0, 8000.0, 9500.0), vec![100]);
+
+    assert_eq!(progress_percent(8848.0, 4424.0), 50.0);
+    assert_eq!(progress_percent(8848.0, 10000.0), 100.0);
+    assert_eq!(progress_percent(0.0, 10.0), 0.0);
+}
+
+#[test]
+fn milestone_percents_are_validated() {
+    assert_eq!(normalize_milestones(&[75, 25, 25, 100]), Some(vec![25, 75, 100]));
+    assert_eq!(normalize_milestones(&[0, 50]), None);
+    assert_eq!(normalize_milestones(&[50, 101]), None);
+
+    assert_eq!(
+        milestone_message("Climb Everest", 50, 4424.4, 8848.0, "m"),
+        "\"Climb Everest\" is 50% done: 4424 of 8848 m."
+    );
+    assert!(milestone_message("Climb Everest", 100, 8848.0, 8848.0, "m").starts_with("Quest complete!"));
+}
+
+#[tokio::test]
+async fn workouts_advance_league_quest() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let player = create_test_user_with_health_profile(&test_app.address).await;
+    let player_id = parse_user_id_from_jwt_token(&player.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 1, Some(vec![player_id]), true, None, None,
+    ).await;
+    let quests_url = format!("{}/admin/leagues/{}/quests", test_app.address, league.league_id);
+
+    let invalid = make_authenticated_request(
+        &client, Method::POST, &quests_url, &admin.token,
+        Some(json!({ "name": "Climb Everest", "target_points": 8848, "milestone_percents": [50, 150] })),
+    ).await;
+    assert_eq!(invalid.status().as_u16(), 400);
+
+    let created = make_authenticated_request(
+        &client, Method::POST, &quests_url, &admin.token,
+        Some(json!({
+            "name": "Climb Everest",
+            "target_points": 1,
+            "unit_label": "m",
+            "starts_at": (Utc::now() - Duration::days(1)).to_rfc3339(),
+        })),
+    ).await;
+    assert_eq!(created.status().as_u16(), 201);
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &player.token, &mut workout)
+        .await
+        .expect("Upload failed");
+
+    let league_quests_url = format!("{}/league/{}/quests", test_app.address, league.league_id);
+    let outsider = create_test_user_and_login(&test_app.address).await;
+    let forbidden = make_authenticated_request(&client, Method::GET, &league_quests_url, &outsider.token, None).await;
+    assert_eq!(forbidden.status().as_u16(), 403);
+
+    let response = make_authenticated_request(&client, Method::GET, &league_quests_url, &player.token, None).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let quest = &body["data"][0];
+    assert_eq!(quest["progress_percent"], 100.0);
+    assert!(quest["completed_at"].is_string());
+    assert_eq!(quest["contributor_count"], 1);
+    assert_eq!(quest["top_contributors"][0]["user_id"], player_id.to_string());
+    assert_eq!(quest["milestones"].as_array().unwrap().len(), 4);
+
+    // A completed quest takes no further workouts
+    let mut second = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(1), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &player.token, &mut second)
+        .await
+        .expect("Upload failed");
+    let quest_id = Uuid::parse_str(quest["id"].as_str().unwrap()).unwrap();
+    let contributions = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM league_quest_contributions WHERE quest_id = $1"#,
+        quest_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(contributions, 1);
+}
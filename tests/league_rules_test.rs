@@ -0,0 +1,120 @@
+//! League rules page tests
+//!
+//! - Rules need a scoring summary; transfer windows must close after they open and not overlap
+//! - Players see the written rules together with the caps the league enforces
+//! - Removing the written rules keeps the caps on the page
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::league::constants::MAX_TEAM_SIZE;
+use riina_backend::models::league::{SetLeagueRulesRequest, TransferWindow};
+
+mod common;
+use common::live_game_helpers::setup_live_game_environment;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn window(opens_in_days: i64, closes_in_days: i64) -> TransferWindow {
+    let now = Utc::now();
+    TransferWindow {
+        opens_at: now + Duration::days(opens_in_days),
+        closes_at: now + Duration::days(closes_in_days),
+        note: None,
+    }
+}
+
+fn rules(transfer_windows: Vec<TransferWindow>) -> SetLeagueRulesRequest {
+    SetLeagueRulesRequest {
+        scoring_summary: "Every workout scores stamina and strength points for your team.".to_string(),
+        forfeit_policy: None,
+        transfer_windows,
+    }
+}
+
+#[test]
+fn rules_need_a_scoring_summary() {
+    assert!(rules(vec![]).validate().is_ok());
+
+    let mut blank = rules(vec![]);
+    blank.scoring_summary = "  ".to_string();
+    assert!(blank.validate().is_err());
+
+    let mut blank_forfeits = rules(vec![]);
+    blank_forfeits.forfeit_policy = Some(String::new());
+    assert!(blank_forfeits.validate().is_err());
+}
+
+#[test]
+fn transfer_windows_must_be_ordered_and_apart() {
+    assert!(rules(vec![window(10, 14), window(0, 7)]).validate().is_ok());
+    assert!(rules(vec![window(7, 7)]).validate().is_err(), "A window must close after it opens");
+    assert!(rules(vec![window(0, 7), window(6, 10)]).validate().is_err(), "Windows cannot overlap");
+    assert!(rules(vec![window(0, 7), window(7, 10)]).validate().is_ok(), "A window may open as another closes");
+}
+
+#[test]
+fn transfer_windows_are_open_from_opening_until_closing() {
+    let window = window(0, 7);
+    assert!(window.is_open_at(window.opens_at));
+    assert!(window.is_open_at(window.opens_at + Duration::days(3)));
+    assert!(!window.is_open_at(window.closes_at));
+    assert!(!window.is_open_at(window.opens_at - Duration::seconds(1)));
+}
+
+#[tokio::test]
+async fn players_see_the_rules_admins_write() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let environment = setup_live_game_environment(&test_app).await;
+    let admin_token = &environment.admin_session.token;
+    let admin_rules_url = format!("{}/admin/leagues/{}/rules", test_app.address, environment.league_id);
+    let rules_url = format!("{}/league/{}/rules", test_app.address, environment.league_id);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &rules_url, &environment.home_user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["scoring_summary"].is_null());
+    assert_eq!(body["data"]["caps"]["max_team_size"], MAX_TEAM_SIZE);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &admin_rules_url, admin_token,
+        Some(json!({ "scoring_summary": "Points for every workout", "transfer_windows": [
+            { "opens_at": Utc::now() + Duration::days(2), "closes_at": Utc::now() + Duration::days(1) }
+        ] })),
+    ).await;
+    assert_eq!(response.status(), 400);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &admin_rules_url, admin_token,
+        Some(json!({
+            "scoring_summary": "Points for every workout",
+            "forfeit_policy": "Teams without a workout in a game week forfeit",
+            "transfer_windows": [
+                { "opens_at": Utc::now() - Duration::days(1), "closes_at": Utc::now() + Duration::days(1), "note": "Mid-season" }
+            ]
+        })),
+    ).await;
+    assert_eq!(response.status(), 200);
+
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &admin_rules_url, &environment.home_user.token, Some(json!({ "scoring_summary": "Mine" }))).await;
+    assert_eq!(response.status(), 403);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &rules_url, &environment.home_user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["scoring_summary"], "Points for every workout");
+    assert_eq!(body["data"]["forfeit_policy"], "Teams without a workout in a game week forfeit");
+    assert_eq!(body["data"]["transfer_windows"][0]["note"], "Mid-season");
+    assert_eq!(body["data"]["transfer_window_open"], true);
+
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &admin_rules_url, admin_token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &admin_rules_url, admin_token, None).await;
+    assert_eq!(response.status(), 404);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &rules_url, &environment.home_user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["scoring_summary"].is_null());
+    assert!(body["data"]["transfer_windows"].as_array().unwrap().is_empty());
+    assert_eq!(body["data"]["caps"]["max_team_size"], MAX_TEAM_SIZE);
+}
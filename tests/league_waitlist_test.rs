@@ -0,0 +1,162 @@
+//! League waitlist tests
+//!
+//! Covers the team cap of leagues and `/admin/leagues/{id}/waitlist`:
+//! - Teams assigned to a full league are waitlisted in request order
+//! - Admins can reorder the waitlist and take teams off it
+//! - Removing or deleting a team promotes the next waiting team
+//! - Raising max_teams promotes waiting teams into the new slots
+
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, TestApp};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_teams_for_test};
+
+async fn assign_team(test_app: &TestApp, client: &Client, token: &str, league_id: &str, team_id: &str) -> (u16, serde_json::Value) {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/admin/leagues/{}/teams", test_app.address, league_id),
+        token,
+        Some(json!({ "team_id": team_id })),
+    ).await;
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+async fn waitlist(test_app: &TestApp, client: &Client, token: &str, league_id: &str) -> Vec<String> {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::GET,
+        &format!("{}/admin/leagues/{}/waitlist", test_app.address, league_id),
+        token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].as_array().unwrap().iter()
+        .map(|entry| entry["team_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn league_team_ids(test_app: &TestApp, client: &Client, token: &str, league_id: &str) -> Vec<String> {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::GET,
+        &format!("{}/admin/leagues/{}/teams", test_app.address, league_id),
+        token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].as_array().unwrap().iter()
+        .map(|team| team["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn full_leagues_waitlist_teams_and_promote_them_when_slots_open() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 2, 2, None, true, None, None).await;
+    let waiting = create_teams_for_test(&test_app.address, &admin.token, 3).await;
+
+    for (i, team_id) in waiting.iter().enumerate() {
+        let (status, body) = assign_team(&test_app, &client, &admin.token, &league.league_id, team_id).await;
+        assert_eq!(202, status);
+        assert_eq!(body["data"]["waitlisted"], true);
+        assert_eq!(body["data"]["waitlist_position"], i as i64 + 1);
+    }
+    let (status, _) = assign_team(&test_app, &client, &admin.token, &league.league_id, &waiting[0]).await;
+    assert_eq!(409, status, "A team can only wait once");
+    assert_eq!(waitlist(&test_app, &client, &admin.token, &league.league_id).await, waiting);
+
+    // Reordering needs every waiting team exactly once
+    let reorder_url = format!("{}/admin/leagues/{}/waitlist", test_app.address, league.league_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &reorder_url, &admin.token,
+        Some(json!({ "team_ids": [waiting[2], waiting[0]] })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &reorder_url, &admin.token,
+        Some(json!({ "team_ids": [waiting[2], waiting[0], waiting[1]] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(
+        waitlist(&test_app, &client, &admin.token, &league.league_id).await,
+        vec![waiting[2].clone(), waiting[0].clone(), waiting[1].clone()]
+    );
+
+    // A team leaving the league makes room for the first waiting team
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/admin/leagues/{}/teams", test_app.address, league.league_id), &admin.token,
+        Some(json!({ "team_id": league.team_ids[0] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["promoted_team_ids"], json!([waiting[2]]));
+    assert!(league_team_ids(&test_app, &client, &admin.token, &league.league_id).await.contains(&waiting[2]));
+    assert_eq!(
+        waitlist(&test_app, &client, &admin.token, &league.league_id).await,
+        vec![waiting[0].clone(), waiting[1].clone()]
+    );
+
+    // Deleting a team frees its slot as well
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/admin/teams/{}", test_app.address, league.team_ids[1]), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(league_team_ids(&test_app, &client, &admin.token, &league.league_id).await.contains(&waiting[0]));
+    assert_eq!(waitlist(&test_app, &client, &admin.token, &league.league_id).await, vec![waiting[1].clone()]);
+
+    // Taking the last team off the waitlist
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/admin/leagues/{}/waitlist/{}", test_app.address, league.league_id, waiting[1]), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(waitlist(&test_app, &client, &admin.token, &league.league_id).await.is_empty());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/admin/leagues/{}/waitlist/{}", test_app.address, league.league_id, waiting[1]), &admin.token, None,
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn raising_max_teams_promotes_waiting_teams() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 2, 2, None, true, None, None).await;
+    let waiting = create_teams_for_test(&test_app.address, &admin.token, 3).await;
+    for team_id in &waiting {
+        let (status, _) = assign_team(&test_app, &client, &admin.token, &league.league_id, team_id).await;
+        assert_eq!(202, status);
+    }
+
+    let league_url = format!("{}/admin/leagues/{}", test_app.address, league.league_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token, Some(json!({ "max_teams": 0 })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &league_url, &admin.token, Some(json!({ "max_teams": 4 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["max_teams"], 4);
+
+    let teams = league_team_ids(&test_app, &client, &admin.token, &league.league_id).await;
+    assert_eq!(teams.len(), 4);
+    assert!(teams.contains(&waiting[0]) && teams.contains(&waiting[1]));
+    assert_eq!(waitlist(&test_app, &client, &admin.token, &league.league_id).await, vec![waiting[2].clone()]);
+}
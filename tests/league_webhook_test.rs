@@ -0,0 +1,114 @@
+//! League Slack/Teams webhook tests
+//!
+//! - Result cards carry the scoreline, outcome and MVP in each provider's format
+//! - Failed cards back off exponentially; webhook URLs must be https
+//! - Failing deliveries show up in the league's admin settings and can be retried
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::league::result_card::ResultCard;
+use riina_backend::models::league::{ChatProvider, SetLeagueChatWebhookRequest};
+use riina_backend::services::league_webhook_service::retry_delay;
+use riina_backend::services::LeagueWebhookService;
+
+mod common;
+use common::live_game_helpers::setup_live_game_environment;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn card(mvp: Option<(&str, i32)>) -> ResultCard {
+    ResultCard {
+        league_name: "Office League".to_string(),
+        week_number: 3,
+        home_team: "Lions".to_string(),
+        away_team: "Tigers".to_string(),
+        home_score: 42,
+        away_score: 37,
+        mvp: mvp.map(|(name, points)| (name.to_string(), points)),
+    }
+}
+
+#[test]
+fn result_cards_show_scoreline_outcome_and_mvp() {
+    let card = card(Some(("ann", 18)));
+    assert_eq!(card.text(), "Office League · Week 3: Lions 42 – 37 Tigers – Lions win, MVP ann (18 points)");
+
+    let slack = card.payload(ChatProvider::Slack);
+    assert_eq!(slack["blocks"][0]["text"]["text"], "Office League · Week 3");
+    assert_eq!(slack["blocks"][1]["fields"][1]["text"], "*MVP*\nann (18 points)");
+
+    let teams = card.payload(ChatProvider::Teams);
+    assert_eq!(teams["@type"], "MessageCard");
+    assert_eq!(teams["sections"][0]["activityTitle"], "Lions 42 – 37 Tigers");
+    assert_eq!(teams["sections"][0]["facts"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn draws_without_summary_have_no_mvp() {
+    let draw = ResultCard { away_score: 42, ..card(None) };
+    assert_eq!(draw.outcome(), "Draw");
+    assert_eq!(draw.payload(ChatProvider::Teams)["sections"][0]["facts"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn failed_cards_back_off_up_to_an_hour() {
+    assert_eq!(retry_delay(1).num_minutes(), 2);
+    assert_eq!(retry_delay(4).num_minutes(), 16);
+    assert_eq!(retry_delay(9).num_minutes(), 60);
+}
+
+#[test]
+fn webhook_urls_must_be_https() {
+    let request = |url: &str| SetLeagueChatWebhookRequest { provider: ChatProvider::Slack, webhook_url: url.to_string(), enabled: None };
+    assert!(request("https://hooks.slack.com/services/T000/B000/XXX").validate().is_ok());
+    assert!(request("http://hooks.slack.com/services/T000/B000/XXX").validate().is_err());
+    assert!(request("not a url").validate().is_err());
+}
+
+#[tokio::test]
+async fn failing_result_cards_are_visible_to_admins() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let environment = setup_live_game_environment(&test_app).await;
+    let admin_token = &environment.admin_session.token;
+    let webhooks_url = format!("{}/admin/leagues/{}/webhooks", test_app.address, environment.league_id);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &webhooks_url, admin_token,
+        Some(json!({ "provider": "teams", "webhook_url": "http://example.com/hook" })),
+    ).await;
+    assert_eq!(response.status(), 400);
+
+    // Nothing listens on port 1, so every post fails
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &webhooks_url, admin_token,
+        Some(json!({ "provider": "teams", "webhook_url": "https://127.0.0.1:1/hook" })),
+    ).await;
+    assert_eq!(response.status(), 200);
+
+    let service = LeagueWebhookService::new(test_app.db_pool.clone());
+    assert_eq!(service.post_results(&[environment.first_game_id]).await.unwrap(), 0);
+    assert_eq!(service.post_results(&[environment.first_game_id]).await.unwrap(), 0, "A game is queued once per webhook");
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &webhooks_url, admin_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["webhooks"][0]["last_error"].is_string());
+    let failed = &body["data"]["failed_deliveries"];
+    assert_eq!(failed.as_array().unwrap().len(), 1);
+    assert_eq!(failed[0]["game_id"], environment.first_game_id.to_string());
+    assert_eq!(failed[0]["attempts"], 1);
+    assert!(failed[0]["next_attempt_at"].is_string());
+
+    let retry_url = format!(
+        "{}/admin/leagues/{}/webhook-deliveries/{}/retry",
+        test_app.address, environment.league_id, failed[0]["id"].as_str().unwrap()
+    );
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &retry_url, admin_token, None).await;
+    assert_eq!(response.status(), 502);
+
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &format!("{webhooks_url}/teams"), admin_token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &webhooks_url, admin_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["failed_deliveries"].as_array().unwrap().is_empty());
+}
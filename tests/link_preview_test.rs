@@ -0,0 +1,87 @@
+//! Link preview tests
+//!
+//! - App paths and full URLs resolve to games, teams, shared workouts and share cards
+//! - `/public/link-preview` needs no auth and returns the Open Graph title and description
+
+use reqwest::Client;
+use uuid::Uuid;
+
+use riina_backend::models::link_preview::PreviewTarget;
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_team, TeamConfig};
+use common::utils::spawn_app;
+
+#[test]
+fn preview_paths_are_parsed() {
+    let id = Uuid::new_v4();
+
+    assert_eq!(PreviewTarget::parse(&format!("/games/{id}")), Some(PreviewTarget::Game(id)));
+    assert_eq!(PreviewTarget::parse(&format!("league/games/{id}?tab=live")), Some(PreviewTarget::Game(id)));
+    assert_eq!(
+        PreviewTarget::parse(&format!("https://riina.app/teams/{id}#roster")),
+        Some(PreviewTarget::Team(id))
+    );
+    assert_eq!(
+        PreviewTarget::parse("/public/workouts/AbC123"),
+        Some(PreviewTarget::SharedWorkout("AbC123".to_string()))
+    );
+    assert_eq!(PreviewTarget::parse(&format!("/share/cards/{id}/")), Some(PreviewTarget::ShareCard(id)));
+
+    assert_eq!(PreviewTarget::parse("/games/not-a-uuid"), None);
+    assert_eq!(PreviewTarget::parse("/workouts/abc$def"), None);
+    assert_eq!(PreviewTarget::parse(&format!("/admin/teams/{id}")), None);
+    assert_eq!(PreviewTarget::parse("https://riina.app"), None);
+
+    let target = PreviewTarget::parse(&format!("/league/teams/{id}")).unwrap();
+    assert_eq!(target.kind(), "team");
+    assert_eq!(target.canonical_path(), format!("/teams/{id}"));
+}
+
+#[tokio::test]
+async fn team_links_unfurl_without_auth() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let team_name = format!("Preview Lions {}", &Uuid::new_v4().to_string()[..8]);
+    let team_id = create_team(
+        &test_app.address,
+        &admin.token,
+        TeamConfig {
+            name: Some(team_name.clone()),
+            description: Some("Early risers".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let response = client
+        .get(format!("{}/public/link-preview", test_app.address))
+        .query(&[("path", format!("https://riina.app/teams/{team_id}"))])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["kind"], "team");
+    assert_eq!(body["data"]["path"], format!("/teams/{team_id}"));
+    assert_eq!(body["data"]["title"], team_name);
+    assert_eq!(body["data"]["description"], "Early risers");
+
+    let missing = client
+        .get(format!("{}/public/link-preview", test_app.address))
+        .query(&[("path", format!("/games/{}", Uuid::new_v4()))])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(missing.status(), 404);
+
+    let unsupported = client
+        .get(format!("{}/public/link-preview", test_app.address))
+        .query(&[("path", "/admin/users")])
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(unsupported.status(), 400);
+}
@@ -0,0 +1,231 @@
+//! Live state sync tests
+//!
+//! Covers the authoritative live game state WebSocket clients reconcile with:
+//! - a `state_sync` request returns scores, recent events and server time in one frame
+//! - a `state_sync` for a single game returns just that game
+//! - spectating a season sends its scoreboard and forwards the score deltas of its games
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::{SinkExt, StreamExt};
+use futures_util::stream::{SplitSink, SplitStream};
+use redis::AsyncCommands;
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use secrecy::ExposeSecret;
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, parse_user_id_from_jwt_token, TestApp, UserRegLoginResponse};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Two teams with a running week 1 game, returning its season, its id and the owner of the home team
+async fn setup_live_game(test_app: &TestApp) -> (Uuid, Uuid, UserRegLoginResponse) {
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner_a = create_test_user_and_login(&test_app.address).await;
+    let owner_b = create_test_user_and_login(&test_app.address).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![parse_user_id_from_jwt_token(&owner_a.token), parse_user_id_from_jwt_token(&owner_b.token)]),
+        true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Sync Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let game_id: Uuid = sqlx::query_scalar(
+        "UPDATE games SET status = 'in_progress', home_score = 120, away_score = 80,
+             game_start_time = NOW() - INTERVAL '1 hour', game_end_time = NOW() + INTERVAL '1 hour'
+         WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+         RETURNING id"
+    )
+    .bind(season_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    // Whoever plays at home in the first game
+    let home_team_id: Uuid = sqlx::query_scalar("SELECT home_team_id FROM games WHERE id = $1")
+        .bind(game_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    let home_owner = if league.team_ids[0] == home_team_id.to_string() { owner_a } else { owner_b };
+
+    sqlx::query(
+        "INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side, score_points, power_contribution, stamina_gained, strength_gained, description, occurred_at)
+         VALUES ($1, $2, $3, $4, 'home', 40, 40, 20, 20, 'Evening run', NOW())"
+    )
+    .bind(game_id)
+    .bind(home_owner.user_id)
+    .bind(&home_owner.username)
+    .bind(home_team_id)
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    (season_id, game_id, home_owner)
+}
+
+async fn connect(test_app: &TestApp, token: &str) -> (SplitSink<WsStream, Message>, SplitStream<WsStream>) {
+    let ws_url = format!("{}/game-ws?token={}", test_app.address.replace("http", "ws"), token);
+    let request = ws_url.into_client_request().expect("Failed to create request");
+    let (ws_stream, _) = connect_async(request)
+        .await
+        .expect("Failed to connect to WebSocket server");
+    ws_stream.split()
+}
+
+/// Wait for the next event of the given type, skipping anything else
+async fn next_event(read: &mut SplitStream<WsStream>, event_type: &str) -> serde_json::Value {
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while let Some(Ok(message)) = read.next().await {
+            if let Message::Text(text) = message {
+                let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if event["event_type"] == event_type {
+                    return event;
+                }
+            }
+        }
+        panic!("WebSocket closed before a {} event arrived", event_type);
+    })
+    .await
+    .unwrap_or_else(|_| panic!("No {} event received", event_type))
+}
+
+/// Wait for the state sync sent for the given reason, skipping anything else
+async fn next_state_sync(read: &mut SplitStream<WsStream>, reason: &str) -> serde_json::Value {
+    loop {
+        let state_sync = next_event(read, "state_sync").await;
+        if state_sync["reason"] == reason {
+            return state_sync;
+        }
+    }
+}
+
+#[tokio::test]
+async fn state_sync_request_returns_live_games_of_the_user() {
+    let test_app = spawn_app().await;
+    let (_, game_id, home_owner) = setup_live_game(&test_app).await;
+    let (mut write, mut read) = connect(&test_app, &home_owner.token).await;
+
+    write
+        .send(Message::Text(json!({ "type": "state_sync" }).to_string()))
+        .await
+        .unwrap();
+    let state_sync = next_state_sync(&mut read, "requested").await;
+
+    let server_time = DateTime::parse_from_rfc3339(state_sync["server_time"].as_str().unwrap()).unwrap();
+    assert!((server_time.with_timezone(&Utc) - Utc::now()).num_seconds().abs() < 60);
+
+    let games = state_sync["games"].as_array().unwrap();
+    assert_eq!(games.len(), 1, "Only the game of the user's team is running");
+    let game = &games[0];
+    assert_eq!(game["game_id"], game_id.to_string());
+    assert_eq!(game["home_score"], 120);
+    assert_eq!(game["away_score"], 80);
+    assert!(game["game_progress"].as_f64().unwrap() > 0.0);
+
+    let events = game["recent_events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["description"], "Evening run");
+    assert_eq!(events[0]["user_id"], home_owner.user_id.to_string());
+}
+
+#[tokio::test]
+async fn state_sync_for_one_game_returns_only_that_game() {
+    let test_app = spawn_app().await;
+    let (_, game_id, _) = setup_live_game(&test_app).await;
+    let spectator = create_test_user_and_login(&test_app.address).await;
+    let (mut write, mut read) = connect(&test_app, &spectator.token).await;
+
+    // Without a team the user has no live games of their own
+    write
+        .send(Message::Text(json!({ "type": "state_sync" }).to_string()))
+        .await
+        .unwrap();
+    let state_sync = next_state_sync(&mut read, "requested").await;
+    assert!(state_sync["games"].as_array().unwrap().is_empty());
+
+    write
+        .send(Message::Text(json!({ "type": "state_sync", "game_id": game_id }).to_string()))
+        .await
+        .unwrap();
+    let state_sync = next_state_sync(&mut read, "requested").await;
+    let games = state_sync["games"].as_array().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["game_id"], game_id.to_string());
+    assert_eq!(games[0]["recent_events"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn spectating_a_season_forwards_its_score_deltas() {
+    let test_app = spawn_app().await;
+    let (season_id, game_id, _) = setup_live_game(&test_app).await;
+    let spectator = create_test_user_and_login(&test_app.address).await;
+    let (mut write, mut read) = connect(&test_app, &spectator.token).await;
+
+    write
+        .send(Message::Text(json!({ "type": "spectate_season", "season_id": season_id }).to_string()))
+        .await
+        .unwrap();
+    let scoreboard = next_event(&mut read, "season_scoreboard").await;
+    assert_eq!(scoreboard["season_id"], season_id.to_string());
+    let games = scoreboard["games"].as_array().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["game_id"], game_id.to_string());
+    assert_eq!(games[0]["home_score"], 120);
+
+    // Deltas published for the season reach the spectator
+    let delta = json!({
+        "event_type": "score_delta",
+        "season_id": season_id,
+        "game_id": game_id,
+        "team_side": "away",
+        "points": 25.0,
+        "home_score": 120,
+        "away_score": 105,
+        "game_progress": 50.0,
+        "timestamp": Utc::now()
+    });
+    let configuration = get_config().expect("Failed to read configuration");
+    let redis_client = redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret()).unwrap();
+    let mut conn = redis_client.get_async_connection().await.unwrap();
+    let _: i32 = conn.publish(format!("game:events:season:{season_id}"), delta.to_string()).await.unwrap();
+
+    let received = next_event(&mut read, "score_delta").await;
+    assert_eq!(received["game_id"], game_id.to_string());
+    assert_eq!(received["away_score"], 105);
+
+    write
+        .send(Message::Text(json!({ "type": "stop_spectating_season", "season_id": season_id }).to_string()))
+        .await
+        .unwrap();
+    let stopped = next_event(&mut read, "spectating_stopped").await;
+    assert_eq!(stopped["season_id"], season_id.to_string());
+}
+
+#[tokio::test]
+async fn spectating_an_unknown_season_fails() {
+    let test_app = spawn_app().await;
+    let spectator = create_test_user_and_login(&test_app.address).await;
+    let (mut write, mut read) = connect(&test_app, &spectator.token).await;
+
+    let season_id = Uuid::new_v4();
+    write
+        .send(Message::Text(json!({ "type": "spectate_season", "season_id": season_id }).to_string()))
+        .await
+        .unwrap();
+    let failed = next_event(&mut read, "spectate_failed").await;
+    assert_eq!(failed["season_id"], season_id.to_string());
+    assert_eq!(failed["error"], "Season not found");
+}
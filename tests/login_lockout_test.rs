@@ -0,0 +1,202 @@
+//! Account lockout and login activity tests
+//!
+//! - Lockouts double in length for each one in a row, up to the configured maximum
+//! - Successful logins from a new address, or right after a run of failures, are suspicious
+//! - Security event filters only accept known types and keep the limit in bounds
+//! - The client address comes from `X-Forwarded-For` only behind trusted proxies, read from the right
+//! - Too many failed logins from one address block it, whichever accounts they were for
+//! - Too many failed logins lock the account, even for the right password, until an admin unlocks it
+//! - Users see their own login attempts, and admins see the lockout as a security event
+
+use reqwest::{Client, Method, StatusCode};
+use serde_json::json;
+use std::time::Duration;
+
+use riina_backend::config::login_protection::LoginProtectionSettings;
+use riina_backend::models::login_activity::{SecurityEventQuery, SecurityEventType};
+use riina_backend::services::login_protection_service::{lockout_duration, suspicious_login_reasons};
+use riina_backend::utils::client_ip::{resolve_client_address, TrustedProxy};
+use uuid::Uuid;
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app, spawn_app_with};
+
+#[test]
+fn lockouts_double_up_to_the_maximum() {
+    let settings = LoginProtectionSettings {
+        base_lockout_secs: 60,
+        max_lockout_secs: 600,
+        ..LoginProtectionSettings::default()
+    };
+
+    assert_eq!(lockout_duration(&settings, 1), Duration::from_secs(60));
+    assert_eq!(lockout_duration(&settings, 2), Duration::from_secs(120));
+    assert_eq!(lockout_duration(&settings, 4), Duration::from_secs(480));
+    assert_eq!(lockout_duration(&settings, 5), Duration::from_secs(600));
+    assert_eq!(lockout_duration(&settings, 200), Duration::from_secs(600));
+}
+
+#[test]
+fn suspicious_logins_are_flagged_with_their_reasons() {
+    let settings = LoginProtectionSettings {
+        max_failed_attempts: 5,
+        ..LoginProtectionSettings::default()
+    };
+
+    assert!(suspicious_login_reasons(&settings, false, 0).is_empty());
+    assert!(suspicious_login_reasons(&settings, false, 2).is_empty());
+    assert_eq!(suspicious_login_reasons(&settings, true, 0), vec!["new_address"]);
+    assert_eq!(suspicious_login_reasons(&settings, false, 3), vec!["after_failed_attempts"]);
+    assert_eq!(suspicious_login_reasons(&settings, true, 4), vec!["new_address", "after_failed_attempts"]);
+}
+
+#[test]
+fn security_event_filters_are_validated() {
+    let query = |event_type: Option<&str>, limit: Option<i64>| SecurityEventQuery {
+        user_id: None,
+        event_type: event_type.map(str::to_string),
+        limit,
+    };
+
+    assert_eq!(query(Some("account_locked"), None).event_type(), Ok(Some(SecurityEventType::AccountLocked)));
+    assert_eq!(query(None, None).event_type(), Ok(None));
+    assert!(query(Some("brute_force"), None).event_type().is_err());
+    assert_eq!(query(None, None).limit(), 100);
+    assert_eq!(query(None, Some(0)).limit(), 1);
+    assert_eq!(query(None, Some(10_000)).limit(), 500);
+}
+
+#[test]
+fn forwarded_addresses_are_only_believed_from_trusted_proxies() {
+    let ip = |address: &str| address.parse().unwrap();
+    let proxies = vec![TrustedProxy::parse("10.0.0.0/8").unwrap(), TrustedProxy::parse("fdaa::/16").unwrap()];
+
+    // Straight from the client, whatever it claims
+    assert_eq!(resolve_client_address(Some(ip("203.0.113.7")), Some("198.51.100.1"), &proxies), Some(ip("203.0.113.7")));
+    // Behind the proxies, the rightmost address they didn't add; the client's own entries are ignored
+    assert_eq!(
+        resolve_client_address(Some(ip("10.1.2.3")), Some("198.51.100.1, 203.0.113.7, 10.0.0.5"), &proxies),
+        Some(ip("203.0.113.7"))
+    );
+    assert_eq!(resolve_client_address(Some(ip("fdaa:0:1::3")), Some("203.0.113.7"), &proxies), Some(ip("203.0.113.7")));
+    // A proxy without the header is its own client
+    assert_eq!(resolve_client_address(Some(ip("10.1.2.3")), None, &proxies), Some(ip("10.1.2.3")));
+    assert_eq!(resolve_client_address(Some(ip("10.1.2.3")), Some("garbage"), &proxies), Some(ip("10.1.2.3")));
+    assert_eq!(resolve_client_address(None, Some("203.0.113.7"), &proxies), None);
+
+    assert!(TrustedProxy::parse("10.0.0.0/33").is_err());
+    assert!(TrustedProxy::parse("proxy.internal").is_err());
+    assert!(TrustedProxy::parse("0.0.0.0/0").unwrap().contains(ip("203.0.113.7")));
+}
+
+#[tokio::test]
+async fn failed_logins_from_one_address_block_it() {
+    let max_failed_attempts_per_ip = 3;
+    let test_app = spawn_app_with(|configuration| {
+        configuration.login_protection.max_failed_attempts_per_ip = max_failed_attempts_per_ip;
+        // The test client stands in for the proxy, so each test run gets an address of its own
+        configuration.login_protection.trusted_proxies = vec!["127.0.0.1".to_string()];
+    })
+    .await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let bytes = Uuid::new_v4().into_bytes();
+    let blocked_address = format!("198.18.{}.{}", bytes[0], bytes[1]);
+
+    let login = |username: String, password: &'static str, forwarded_for: String| {
+        client
+            .post(format!("{}/login", test_app.address))
+            .header("X-Forwarded-For", forwarded_for)
+            .json(&json!({ "username": username, "password": password }))
+            .send()
+    };
+
+    // Guessing at different accounts from one address
+    for _ in 0..max_failed_attempts_per_ip {
+        let response = login(format!("nobody{}", Uuid::new_v4().simple()), "not-the-password", blocked_address.clone())
+            .await
+            .expect("Failed to execute request");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let blocked = login(user.username.clone(), "password123", blocked_address.clone()).await.unwrap();
+    assert_eq!(blocked.status(), StatusCode::TOO_MANY_REQUESTS, "The address is blocked, even for valid logins");
+    let spoofed = login(user.username.clone(), "password123", format!("203.0.113.9, {blocked_address}")).await.unwrap();
+    assert_eq!(spoofed.status(), StatusCode::TOO_MANY_REQUESTS, "Addresses the client adds are ignored");
+
+    let other_address = format!("198.19.{}.{}", bytes[0], bytes[1]);
+    let response = login(user.username.clone(), "password123", other_address).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "The account itself isn't locked");
+}
+
+#[tokio::test]
+async fn failed_logins_lock_the_account_until_an_admin_unlocks_it() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let login = |password: &'static str| {
+        client
+            .post(format!("{}/login", test_app.address))
+            .json(&json!({ "username": user.username, "password": password }))
+            .send()
+    };
+
+    let max_failed_attempts = LoginProtectionSettings::default().max_failed_attempts;
+    for _ in 0..max_failed_attempts {
+        let response = login("not-the-password").await.expect("Failed to execute request");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Locked now, so even the right password is turned down
+    let locked = login("password123").await.expect("Failed to execute request");
+    assert_eq!(locked.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(locked.headers().get("retry-after").is_some());
+
+    let events = make_authenticated_request(
+        &client,
+        Method::GET,
+        &format!("{}/admin/security-events?user_id={}&event_type=account_locked", test_app.address, user.user_id),
+        &admin.token,
+        None,
+    )
+    .await;
+    assert_eq!(events.status(), StatusCode::OK);
+    let events: serde_json::Value = events.json().await.unwrap();
+    assert_eq!(events["data"].as_array().unwrap().len(), 1);
+    assert_eq!(events["data"][0]["username"], user.username);
+
+    let unlocked = make_authenticated_request(
+        &client,
+        Method::DELETE,
+        &format!("{}/admin/users/{}/login-lockout", test_app.address, user.user_id),
+        &admin.token,
+        None,
+    )
+    .await;
+    assert_eq!(unlocked.status(), StatusCode::OK);
+    let unlocked: serde_json::Value = unlocked.json().await.unwrap();
+    assert_eq!(unlocked["data"]["was_locked"], true);
+
+    let response = login("password123").await.expect("Failed to execute request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let activity = make_authenticated_request(
+        &client,
+        Method::GET,
+        &format!("{}/auth/login-activity", test_app.address),
+        &user.token,
+        None,
+    )
+    .await;
+    assert_eq!(activity.status(), StatusCode::OK);
+    let activity: serde_json::Value = activity.json().await.unwrap();
+    let attempts = activity["data"].as_array().unwrap();
+    // Registration login, the failures, the locked attempt and the login after the unlock
+    assert_eq!(attempts.len(), max_failed_attempts as usize + 3);
+    assert_eq!(attempts[0]["succeeded"], true);
+    assert_eq!(attempts[1]["failure_reason"], "locked");
+    assert_eq!(attempts[2]["failure_reason"], "invalid_credentials");
+}
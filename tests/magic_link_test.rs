@@ -0,0 +1,152 @@
+//! Magic link login tests
+//!
+//! - Sign-in emails link to the configured page with the token attached
+//! - Requesting a link answers the same for unknown emails and stores a token for known ones
+//! - Sign-in links are limited per email address, apart from reset emails
+//! - A link logs in once, expired ones and ones replaced by a newer link don't
+//! - Opening a link verifies the user's email
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::config::email::EmailSettings;
+use riina_backend::db::magic_link_tokens::issue_magic_link_token;
+use riina_backend::services::EmailService;
+
+mod common;
+use common::utils::{spawn_app, spawn_app_with};
+
+#[test]
+fn magic_links_point_to_the_configured_page() {
+    let settings = EmailSettings {
+        magic_link_url: "https://example.com/sign-in".to_string(),
+        ..EmailSettings::default()
+    };
+    assert_eq!(EmailService::new(settings).magic_link("abc"), "https://example.com/sign-in?token=abc");
+}
+
+async fn consume(client: &Client, address: &str, token: &str) -> reqwest::Response {
+    client
+        .post(format!("{}/auth/magic-link/consume", address))
+        .json(&json!({ "token": token }))
+        .send()
+        .await
+        .expect("Failed to execute consume request")
+}
+
+#[tokio::test]
+async fn magic_links_log_in_once() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+    let client = Client::new();
+    let username = format!("magic_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let email = format!("{username}@example.com");
+
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": email }))
+        .send()
+        .await
+        .expect("Failed to register user");
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+    for requested_email in [email.to_uppercase(), "nobody@example.com".to_string()] {
+        let response = client
+            .post(format!("{}/auth/magic-link/request", test_app.address))
+            .json(&json!({ "email": requested_email }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200, "Unknown emails get the same answer");
+    }
+    let emailed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM magic_link_tokens WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(emailed, 1);
+
+    // The emailed token is replaced by a newer one
+    let expires_at = Utc::now() + Duration::minutes(15);
+    let older = issue_magic_link_token(pool, user_id, expires_at).await.unwrap();
+    let token = issue_magic_link_token(pool, user_id, expires_at).await.unwrap();
+    assert_eq!(consume(&client, &test_app.address, &older).await.status().as_u16(), 401);
+
+    let response = consume(&client, &test_app.address, &token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["refresh_token"].is_string());
+    let email_verified: bool = sqlx::query_scalar("SELECT email_verified_at IS NOT NULL FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert!(email_verified, "Opening the link proves the email is the user's");
+
+    // The access token works like one from a password login
+    let profile = client
+        .get(format!("{}/profile/user", test_app.address))
+        .bearer_auth(body["token"].as_str().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(profile.status().as_u16(), 200);
+
+    assert_eq!(consume(&client, &test_app.address, &token).await.status().as_u16(), 401, "Links are single-use");
+
+    let expired = issue_magic_link_token(pool, user_id, Utc::now() - Duration::minutes(1)).await.unwrap();
+    assert_eq!(consume(&client, &test_app.address, &expired).await.status().as_u16(), 401);
+
+    // Links give no access to accounts that are not active
+    let token = issue_magic_link_token(pool, user_id, expires_at).await.unwrap();
+    sqlx::query("UPDATE users SET status = 'suspended' WHERE id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    assert_eq!(consume(&client, &test_app.address, &token).await.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn sign_in_links_are_rate_limited() {
+    let test_app = spawn_app_with(|configuration| configuration.login_protection.max_email_requests_per_email = 1).await;
+    let pool = &test_app.db_pool;
+    let client = Client::new();
+    let username = format!("magic_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let email = format!("{username}@example.com");
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": email }))
+        .send()
+        .await
+        .expect("Failed to register user");
+    let request = |path: &'static str| {
+        client
+            .post(format!("{}{}", test_app.address, path))
+            .json(&json!({ "email": email }))
+            .send()
+    };
+    let count = |table: &'static str| async move {
+        sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    };
+
+    for _ in 0..2 {
+        let response = request("/auth/magic-link/request").await.unwrap();
+        assert_eq!(response.status().as_u16(), 200, "Requests over the limit get the same answer");
+    }
+    assert_eq!(count("magic_link_tokens").await, 1);
+
+    // Password resets are counted on their own
+    let response = request("/password-reset/request").await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(count("password_reset_tokens").await, 1);
+}
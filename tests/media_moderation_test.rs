@@ -0,0 +1,142 @@
+//! Photo moderation tests
+//!
+//! Covers perceptual hashing of uploads and admin takedowns:
+//! - Confirmed images are hashed; re-encoded copies of blocked images are rejected
+//! - A takedown strips the media from posts and refuses its download URL
+//! - Unrelated images still upload, only admins can take media down
+//! - Every hash within the similarity threshold is found through the band index
+
+use std::io::Cursor;
+
+use chrono::Utc;
+use image::{ImageOutputFormat, Rgb, RgbImage};
+use reqwest::Client;
+use serde_json::json;
+use riina_backend::utils::perceptual_hash::{hamming_distance, hash_bands, similar_band_values, SIMILARITY_THRESHOLD};
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::admin_helpers::create_admin_user_and_login;
+use common::media_helpers::upload_test_media_file;
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_health_profile_for_user};
+
+/// A sky with a sun over a mountain ridge
+fn landscape(width: u32, height: u32) -> RgbImage {
+    RgbImage::from_fn(width, height, |x, y| {
+        let (fx, fy) = (x as f64 / width as f64, y as f64 / height as f64);
+        let sun = if (fx - 0.7).powi(2) + (fy - 0.3).powi(2) < 0.02 { 120.0 } else { 0.0 };
+        let ridge = if fy > 0.55 + 0.1 * (fx * 6.0).sin() { -90.0 } else { 0.0 };
+        let value = (100.0 + 80.0 * fy + sun + ridge).clamp(0.0, 255.0) as u8;
+        Rgb([value, value / 2 + 60, 255 - value])
+    })
+}
+
+fn checkerboard(width: u32, height: u32) -> RgbImage {
+    RgbImage::from_fn(width, height, |x, y| {
+        if (x / 16 + y / 16) % 2 == 0 { Rgb([250, 250, 250]) } else { Rgb([10, 10, 10]) }
+    })
+}
+
+fn encode(image: &RgbImage, format: ImageOutputFormat) -> Vec<u8> {
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, format).expect("Failed to encode test image");
+    bytes.into_inner()
+}
+
+#[tokio::test]
+async fn taken_down_images_are_stripped_and_cannot_be_reuploaded() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+
+    let original = landscape(256, 192);
+    let upload = upload_test_media_file(
+        &client, &test_app.address, &user.token, "summit.png", "image/png", &encode(&original, ImageOutputFormat::Png),
+    ).await.expect("Image upload should succeed");
+
+    let phash: Option<i64> = sqlx::query_scalar("SELECT phash FROM media_hashes WHERE object_key = $1")
+        .bind(&upload.object_key)
+        .fetch_optional(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert!(phash.is_some(), "Confirmed images should be hashed");
+
+    // Attach the image to a workout post
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now(), 30);
+    workout.image_urls = Some(vec![upload.file_url.clone()]);
+    let uploaded = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+    let workout_id = uploaded["data"]["sync_id"].as_str().unwrap().to_string();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/posts/workout/{}", test_app.address, workout_id), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let post_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["media_urls"][0]["url"], upload.file_url.as_str());
+
+    // Only admins take media down
+    let takedown_url = format!("{}/admin/media/takedown", test_app.address);
+    let takedown = json!({ "object_key": upload.object_key, "reason": "Contains personal data" });
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &takedown_url, &user.token, Some(takedown.clone()),
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &takedown_url, &admin.token, Some(takedown),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["post_ids"], json!([post_id]));
+    assert_eq!(body["data"]["hash_blocked"], true);
+
+    // Gone from the post right away
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/posts/{}", test_app.address, post_id), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["media_urls"].is_null());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/media/download-url/{}", test_app.address, upload.object_key.trim_start_matches("media/")),
+        &user.token, None,
+    ).await;
+    assert_eq!(410, response.status().as_u16());
+
+    // The same picture, resized and re-encoded, is rejected
+    let copy = image::imageops::resize(&original, 200, 150, image::imageops::FilterType::Triangle);
+    let result = upload_test_media_file(
+        &client, &test_app.address, &user.token, "summit-again.jpg", "image/jpeg", &encode(&copy, ImageOutputFormat::Jpeg(80)),
+    ).await;
+    match result {
+        Err(error) => assert!(error.contains("422"), "Unexpected error: {}", error),
+        Ok(_) => panic!("A copy of a blocked image should be rejected"),
+    }
+
+    // Something else is fine
+    upload_test_media_file(
+        &client, &test_app.address, &user.token, "board.png", "image/png", &encode(&checkerboard(256, 192), ImageOutputFormat::Png),
+    ).await.expect("Unrelated images should still upload");
+}
+
+#[test]
+fn band_lookup_finds_every_hash_within_the_threshold() {
+    let hash: i64 = 0x5A3C_F00F_1234_ABCD;
+    let candidates = similar_band_values(hash);
+    let is_candidate = |other: i64| {
+        hash_bands(other).iter().zip(&candidates).any(|(band, values)| values.contains(band))
+    };
+
+    // The threshold's bits flipped as evenly across the bands as possible, and all in one band
+    let spread = [0, 1, 2, 16, 17, 18, 32, 33, 48, 49];
+    let clustered = [40, 41, 42, 43, 44, 45, 46, 47, 36, 37];
+    for flipped in [&spread, &clustered] {
+        let similar = flipped.iter().fold(hash, |h, bit| h ^ (1i64 << bit));
+        assert_eq!(hamming_distance(hash, similar), SIMILARITY_THRESHOLD);
+        assert!(is_candidate(similar), "Hash {:x} within the threshold was not found", similar);
+    }
+
+    assert!(is_candidate(hash));
+    assert!(!is_candidate(!hash));
+}
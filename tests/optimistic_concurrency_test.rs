@@ -0,0 +1,164 @@
+//! Optimistic concurrency tests
+//!
+//! Covers `If-Match` on team and health profile updates:
+//! - Reads return the row version as ETag
+//! - An update with the current version succeeds and bumps it
+//! - An update with a stale version gets 409 with the current state
+//! - Updates without If-Match stay unconditional
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+
+async fn send(
+    client: &Client,
+    method: reqwest::Method,
+    url: &str,
+    token: &str,
+    if_match: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> reqwest::Response {
+    let mut request = client.request(method, url).header("Authorization", format!("Bearer {}", token));
+    if let Some(if_match) = if_match {
+        request = request.header("If-Match", if_match);
+    }
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    request.send().await.expect("Failed to execute request")
+}
+
+fn etag_of(response: &reqwest::Response) -> String {
+    response.headers().get("etag").expect("Response should carry an ETag").to_str().unwrap().to_string()
+}
+
+fn unique_name(prefix: &str) -> String {
+    format!("{} {}", prefix, &Uuid::new_v4().to_string()[..8])
+}
+
+#[tokio::test]
+async fn team_updates_with_stale_if_match_are_rejected() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner = create_test_user_and_login(&test_app.address).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 1,
+        Some(vec![parse_user_id_from_jwt_token(&owner.token)]), true, None, None,
+    ).await;
+    let team_id = &league.team_ids[0];
+    let team_url = format!("{}/league/teams/{}", test_app.address, team_id);
+
+    let response = send(&client, reqwest::Method::GET, &team_url, &owner.token, None, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let etag = etag_of(&response);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let version = body["data"]["version"].as_i64().unwrap();
+    assert_eq!(etag, format!("\"{}\"", version));
+
+    // Current version: accepted and bumped
+    let renamed = unique_name("Renamed");
+    let response = send(
+        &client, reqwest::Method::PUT, &team_url, &owner.token, Some(&etag),
+        Some(json!({ "team_name": renamed })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let new_etag = etag_of(&response);
+    assert_eq!(new_etag, format!("\"{}\"", version + 1));
+
+    // Stale version: 409 with the current team
+    let response = send(
+        &client, reqwest::Method::PUT, &team_url, &owner.token, Some(&etag),
+        Some(json!({ "team_name": unique_name("Lost") })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+    assert_eq!(etag_of(&response), new_etag);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["team_name"], renamed);
+    assert_eq!(body["data"]["version"].as_i64().unwrap(), version + 1);
+
+    // Admin editing the same team with the stale version is rejected too
+    let admin_url = format!("{}/admin/teams/{}", test_app.address, team_id);
+    let admin_update = json!({
+        "name": unique_name("Admin"),
+        "color": "#123456",
+        "owner_id": parse_user_id_from_jwt_token(&owner.token),
+    });
+    let response = send(
+        &client, reqwest::Method::PATCH, &admin_url, &admin.token, Some(&etag), Some(admin_update.clone()),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["name"], renamed);
+
+    let response = send(
+        &client, reqwest::Method::PATCH, &admin_url, &admin.token, Some(&new_etag), Some(admin_update),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(etag_of(&response), format!("\"{}\"", version + 2));
+
+    // Without If-Match the update is unconditional
+    let response = send(
+        &client, reqwest::Method::PUT, &team_url, &owner.token, None,
+        Some(json!({ "team_name": unique_name("Forced") })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    // Something that is not a version ETag
+    let response = send(
+        &client, reqwest::Method::PUT, &team_url, &owner.token, Some("\"abc\""),
+        Some(json!({ "team_name": unique_name("Bad") })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn health_profile_updates_with_stale_if_match_are_rejected() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let profile_url = format!("{}/profile/health_profile", test_app.address);
+
+    let response = send(
+        &client, reqwest::Method::PUT, &profile_url, &user.token, None,
+        Some(json!({ "age": 30, "gender": "female", "resting_heart_rate": 55 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let etag = etag_of(&response);
+
+    let response = send(&client, reqwest::Method::GET, &profile_url, &user.token, None, None).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(etag_of(&response), etag);
+
+    // Another device saves first
+    let response = send(
+        &client, reqwest::Method::PUT, &profile_url, &user.token, Some(&etag),
+        Some(json!({ "age": 30, "gender": "female", "resting_heart_rate": 55, "weight": 62.0 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let new_etag = etag_of(&response);
+    assert_ne!(new_etag, etag);
+
+    // The stale edit does not overwrite it
+    let response = send(
+        &client, reqwest::Method::PUT, &profile_url, &user.token, Some(&etag),
+        Some(json!({ "age": 30, "gender": "female", "resting_heart_rate": 55, "weight": 70.0 })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+    assert_eq!(etag_of(&response), new_etag);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["weight"].as_f64().unwrap(), 62.0);
+
+    let weight: Option<f32> = sqlx::query_scalar("SELECT weight FROM user_health_profiles WHERE user_id = $1")
+        .bind(parse_user_id_from_jwt_token(&user.token))
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(weight, Some(62.0));
+}
@@ -0,0 +1,155 @@
+//! Organization branding tests
+//!
+//! Covers `/admin/organizations` and `/config/branding`:
+//! - The default organization is served without authentication
+//! - Admins create and update organizations; apps pick theirs by slug
+//! - Invalid colors and locales are rejected, and only admins manage organizations
+//! - New league seasons fall back to the default organization's league defaults
+//!
+//! Tests share the database, so they leave the default organization as they found it.
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+
+async fn get_branding(client: &Client, address: &str, org: Option<&str>) -> reqwest::Response {
+    let url = match org {
+        Some(slug) => format!("{}/config/branding?org={}", address, slug),
+        None => format!("{}/config/branding", address),
+    };
+    client.get(url).send().await.unwrap()
+}
+
+#[tokio::test]
+async fn branding_is_managed_by_admins_and_served_per_organization() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let organizations_url = format!("{}/admin/organizations", test_app.address);
+    let slug = format!("acme-{}", &Uuid::new_v4().to_string()[..8]);
+
+    // The default organization is available before login
+    let response = get_branding(&client, &test_app.address, None).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(response.headers().get("etag").is_some());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let default_slug = body["data"]["slug"].as_str().unwrap().to_string();
+    assert!(body["data"]["league_defaults"]["games_per_matchup"].is_i64());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &organizations_url, &user.token,
+        Some(json!({ "slug": slug, "name": "Acme Fitness" })),
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &organizations_url, &admin.token,
+        Some(json!({
+            "slug": slug,
+            "name": "Acme Fitness",
+            "primary_color": "#FF5722",
+            "logo_key": "branding/acme/logo.png",
+            "locale": "de"
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let acme_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["secondary_color"], "#43A047");
+    assert_eq!(body["data"]["is_default"], false);
+
+    for invalid in [
+        json!({ "slug": "Acme Two", "name": "Acme Two" }),
+        json!({ "slug": "acme-two", "name": "Acme Two", "primary_color": "red" }),
+        json!({ "slug": "acme-two", "name": "Acme Two", "locale": "xx" }),
+        json!({ "slug": "acme-two", "name": "Acme Two", "default_games_per_matchup": 3 }),
+    ] {
+        let response = make_authenticated_request(
+            &client, reqwest::Method::POST, &organizations_url, &admin.token, Some(invalid),
+        ).await;
+        assert_eq!(400, response.status().as_u16());
+    }
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &organizations_url, &admin.token,
+        Some(json!({ "slug": slug, "name": "Acme Again" })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+
+    let response = get_branding(&client, &test_app.address, Some(&slug)).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["name"], "Acme Fitness");
+    assert_eq!(body["data"]["primary_color"], "#FF5722");
+    assert_eq!(body["data"]["logo_key"], "branding/acme/logo.png");
+    assert_eq!(body["data"]["locale"], "de");
+
+    let response = get_branding(&client, &test_app.address, Some("unknown-org")).await;
+    assert_eq!(404, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &format!("{}/{}", organizations_url, acme_id), &admin.token,
+        Some(json!({ "is_default": false })),
+    ).await;
+    assert_eq!(400, response.status().as_u16(), "The default moves by making another organization the default");
+
+    // Making acme the default unsets the previous default
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &format!("{}/{}", organizations_url, acme_id), &admin.token,
+        Some(json!({ "is_default": true, "name": "Acme Fitness Club" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &organizations_url, &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let organizations = body["data"].as_array().unwrap();
+    let defaults: Vec<&serde_json::Value> = organizations.iter().filter(|o| o["is_default"] == true).collect();
+    assert_eq!(defaults.len(), 1);
+    assert_eq!(defaults[0]["slug"], slug.as_str());
+    assert_eq!(defaults[0]["name"], "Acme Fitness Club");
+
+    // Restore the previous default
+    let previous_default = organizations.iter().find(|o| o["slug"] == default_slug.as_str()).unwrap();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH,
+        &format!("{}/{}", organizations_url, previous_default["id"].as_str().unwrap()), &admin.token,
+        Some(json!({ "is_default": true })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = get_branding(&client, &test_app.address, None).await.json().await.unwrap();
+    assert_eq!(body["data"]["slug"], default_slug.as_str());
+}
+
+#[tokio::test]
+async fn new_seasons_use_the_default_organization_league_defaults() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let body: serde_json::Value = get_branding(&client, &test_app.address, None).await.json().await.unwrap();
+    let defaults = body["data"]["league_defaults"].clone();
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 2, 2, None, true, None, None).await;
+    let seasons_url = format!("{}/admin/leagues/{}/seasons", test_app.address, league.league_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &seasons_url, &admin.token,
+        Some(json!({
+            "name": "Defaults Season",
+            "start_date": (Utc::now() + Duration::days(1)).to_rfc3339(),
+            "inactivity_nudge_days": 0
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["game_duration_seconds"], defaults["game_duration_seconds"]);
+    assert_eq!(body["data"]["games_per_matchup"], defaults["games_per_matchup"]);
+    assert!(body["data"]["inactivity_nudge_days"].is_null(), "Explicit settings win over the defaults");
+}
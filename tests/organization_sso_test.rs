@@ -0,0 +1,146 @@
+//! Organization single sign-on tests
+//!
+//! - ID token claims give the directory account, its email unless flagged unverified, and its groups
+//! - Existing users are only linked for explicitly verified emails of the organization's domains
+//! - New accounts are only created for the organization's email domains
+//! - The directory deactivates users with `{"active": false}` or a SCIM PatchOp
+//! - Admins configure the OIDC provider, group-to-team mappings and the SCIM token
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::models::organization::{email_domain_allowed, ScimUserPatch, SetSsoProviderRequest, SsoIdentity};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn claims(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn claims_give_account_email_and_groups() {
+    let identity = SsoIdentity::from_claims(
+        &claims(json!({ "sub": "00u1", "email": "Ann@Corp.example", "groups": ["runners", "sales"] })),
+        "groups",
+    )
+    .unwrap();
+    assert_eq!(identity.subject, "00u1");
+    assert_eq!(identity.email.as_deref(), Some("ann@corp.example"));
+    assert_eq!(identity.groups, vec!["runners", "sales"]);
+
+    let identity = SsoIdentity::from_claims(
+        &claims(json!({ "sub": "00u2", "email": "bob@corp.example", "email_verified": false, "roles": "runners" })),
+        "roles",
+    )
+    .unwrap();
+    assert_eq!(identity.email, None, "Emails flagged unverified are not used");
+    assert!(!identity.email_verified);
+    assert_eq!(identity.groups, vec!["runners"]);
+
+    assert!(SsoIdentity::from_claims(&claims(json!({ "email": "ann@corp.example" })), "groups").is_err());
+}
+
+#[test]
+fn only_verified_emails_of_own_domains_link_existing_users() {
+    let domains = vec!["corp.example".to_string()];
+    let identity = |email: &str, verified: Option<bool>| {
+        let mut value = json!({ "sub": "00u1", "email": email });
+        if let Some(verified) = verified {
+            value["email_verified"] = json!(verified);
+        }
+        SsoIdentity::from_claims(&claims(value), "groups").unwrap()
+    };
+
+    assert!(identity("ann@corp.example", Some(true)).may_link_existing_user(&domains));
+    assert!(!identity("victim@gmail.com", Some(true)).may_link_existing_user(&domains), "Foreign domain");
+    let unflagged = identity("ann@corp.example", None);
+    assert_eq!(unflagged.email.as_deref(), Some("ann@corp.example"), "Still usable for new accounts");
+    assert!(!unflagged.may_link_existing_user(&domains), "No email_verified claim");
+    assert!(!identity("ann@corp.example", Some(true)).may_link_existing_user(&[]), "Organization without own domains");
+}
+
+#[test]
+fn new_accounts_need_an_allowed_domain() {
+    let domains = vec!["corp.example".to_string()];
+    assert!(email_domain_allowed("ann@Corp.Example", &domains));
+    assert!(!email_domain_allowed("ann@gmail.com", &domains));
+    assert!(!email_domain_allowed("ann@sub.corp.example", &domains));
+    assert!(email_domain_allowed("ann@gmail.com", &[]));
+}
+
+#[test]
+fn directories_deactivate_users_with_plain_or_scim_patches() {
+    let patch = |value: serde_json::Value| serde_json::from_value::<ScimUserPatch>(value).unwrap().active();
+    assert_eq!(patch(json!({ "active": false })), Some(false));
+    assert_eq!(
+        patch(json!({ "Operations": [{ "op": "Replace", "path": "active", "value": false }] })),
+        Some(false)
+    );
+    assert_eq!(patch(json!({ "Operations": [{ "op": "replace", "value": { "active": true } }] })), Some(true));
+    assert_eq!(patch(json!({ "Operations": [{ "op": "replace", "path": "name", "value": "Ann" }] })), None);
+}
+
+#[test]
+fn providers_need_an_https_issuer() {
+    let request = |issuer_url: &str, domains: Vec<&str>| SetSsoProviderRequest {
+        issuer_url: issuer_url.to_string(),
+        client_id: "riina".to_string(),
+        groups_claim: None,
+        allowed_email_domains: Some(domains.into_iter().map(str::to_string).collect()),
+        jit_provisioning: None,
+        enabled: None,
+    };
+    assert!(request("https://corp.okta.com", vec!["corp.example"]).validate().is_ok());
+    assert!(request("http://corp.okta.com", vec![]).validate().is_err());
+    assert!(request("https://corp.okta.com", vec!["@corp.example"]).validate().is_err());
+}
+
+#[tokio::test]
+async fn admins_configure_sso_and_the_directory_authenticates_with_its_token() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let slug = format!("sso-{}", &Uuid::new_v4().simple().to_string()[..8]);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/admin/organizations", test_app.address), &admin.token,
+        Some(json!({ "slug": slug, "name": "Corp" })),
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let organization_id = body["data"]["id"].as_str().unwrap().to_string();
+    let sso_url = format!("{}/admin/organizations/{}/sso", test_app.address, organization_id);
+
+    let login = |slug: String| client.post(format!("{}/sso/{}", test_app.address, slug)).json(&json!({ "id_token": "x.y.z" })).send();
+    assert_eq!(login(slug.clone()).await.unwrap().status().as_u16(), 404, "No SSO configured yet");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &sso_url, &admin.token,
+        Some(json!({ "issuer_url": "https://corp.okta.com", "client_id": "riina", "allowed_email_domains": ["corp.example"] })),
+    ).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(login(slug.clone()).await.unwrap().status().as_u16(), 401, "Malformed ID tokens are rejected");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PUT, &format!("{sso_url}/groups"), &admin.token,
+        Some(json!({ "group_name": "runners", "team_id": Uuid::new_v4() })),
+    ).await;
+    assert_eq!(response.status(), 404, "Groups map to existing teams");
+
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &format!("{sso_url}/scim-token"), &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let scim_token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sso_url, &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["provider"]["has_scim_token"], true);
+
+    let scim_url = format!("{}/scim/v2/Users/unknown-account", test_app.address);
+    let deactivate = json!({ "active": false });
+    let response = client.patch(&scim_url).bearer_auth("wrong-token").json(&deactivate).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+    let response = client.patch(&scim_url).bearer_auth(&scim_token).json(&deactivate).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+}
@@ -0,0 +1,84 @@
+//! Password policy tests
+//!
+//! - Length, common password and personal info rules, each reported as its own field error
+//! - The breach check hashes locally and finds the suffix in the range answer
+//! - Registration rejects weak passwords with errors the app can show field by field
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::config::password_policy::PasswordPolicySettings;
+use riina_backend::services::password_policy_service::{breach_check_hash, breach_count, policy_violations};
+
+mod common;
+use common::utils::{generate_valid_username_suffix, spawn_app};
+
+fn codes(settings: &PasswordPolicySettings, password: &str) -> Vec<String> {
+    policy_violations(settings, password, "runner_kai", "kai@example.com")
+        .into_iter()
+        .map(|error| error.code)
+        .collect()
+}
+
+#[test]
+fn passwords_are_checked_against_the_policy() {
+    let settings = PasswordPolicySettings::default();
+    assert!(codes(&settings, "correct horse battery").is_empty());
+    assert_eq!(codes(&settings, "short1"), vec!["too_short"]);
+    assert_eq!(codes(&settings, &"a".repeat(73)), vec!["too_long"]);
+    assert_eq!(codes(&settings, "          "), vec!["blank"]);
+    assert_eq!(codes(&settings, "Password123"), vec!["too_common"]);
+    assert_eq!(codes(&settings, "Runner_Kai_2024"), vec!["contains_personal_info"]);
+    assert_eq!(codes(&settings, "kai@example"), Vec::<String>::new(), "Short names are not personal info");
+    assert_eq!(codes(&settings, "qwerty"), vec!["too_short", "too_common"]);
+
+    let lenient = PasswordPolicySettings { reject_common_passwords: false, min_length: 4, ..Default::default() };
+    assert!(codes(&lenient, "qwerty").is_empty());
+}
+
+#[test]
+fn breach_check_sends_only_the_hash_prefix() {
+    let (prefix, suffix) = breach_check_hash("password");
+    assert_eq!(prefix, "5BAA6");
+    assert_eq!(suffix, "1E4C9B93F3F0682250B6CF8331B7EE68FD8");
+
+    let range = "003D68EB55068C33ACE09247EE4C639306B:3\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004\r\n01330C689E5D64F660D6947A93AD634EF8F:0";
+    assert_eq!(breach_count(range, &suffix), 10434004);
+    assert_eq!(breach_count(range, "01330C689E5D64F660D6947A93AD634EF8F"), 0, "Padding lines don't count");
+    assert_eq!(breach_count(range, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"), 0);
+}
+
+#[tokio::test]
+async fn registration_reports_every_problem_by_field() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let username = format!("user{}", generate_valid_username_suffix());
+
+    let response = client
+        .post(format!("{}/register_user", &test_app.address))
+        .json(&json!({
+            "username": username,
+            "password": "short",
+            "email": "not-an-email"
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    let errors = body["errors"].as_array().unwrap();
+    let fields: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|error| (error["field"].as_str().unwrap(), error["code"].as_str().unwrap()))
+        .collect();
+    assert_eq!(fields, vec![("email", "invalid_email"), ("password", "too_short")]);
+    assert_eq!(body["error"], errors[0]["message"]);
+
+    let saved = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(saved, 0);
+}
@@ -0,0 +1,206 @@
+//! Password reset tests
+//!
+//! - Reset emails link to the configured page with the token attached
+//! - Requesting a reset answers the same for unknown emails and stores a token for known ones
+//! - Reset emails are limited per email address and per client address, silently
+//! - New passwords have to follow the password policy, a refused one doesn't use up the token
+//! - A reset token sets a new password once, and only the latest token of a user is valid
+//! - Resetting the password verifies the user's email
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::config::email::EmailSettings;
+use riina_backend::db::password_reset_tokens::issue_password_reset_token;
+use riina_backend::services::EmailService;
+
+mod common;
+use common::utils::{spawn_app, spawn_app_with};
+
+#[test]
+fn reset_links_point_to_the_configured_page() {
+    let settings = EmailSettings {
+        password_reset_url: "https://example.com/reset".to_string(),
+        ..EmailSettings::default()
+    };
+    assert_eq!(EmailService::new(settings).password_reset_link("abc"), "https://example.com/reset?token=abc");
+}
+
+#[tokio::test]
+async fn not_configured_email_api_only_logs() {
+    let service = EmailService::new(EmailSettings::default());
+    assert!(service.send("ann@example.com", "Subject", "Text").await.is_ok());
+}
+
+async fn confirm(client: &Client, address: &str, token: &str, new_password: &str) -> u16 {
+    client
+        .post(format!("{}/password-reset/confirm", address))
+        .json(&json!({ "token": token, "new_password": new_password }))
+        .send()
+        .await
+        .expect("Failed to execute confirm request")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn reset_tokens_set_a_new_password_once() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+    let client = Client::new();
+    let username = format!("reset_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let email = format!("{username}@example.com");
+
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": email }))
+        .send()
+        .await
+        .expect("Failed to register user");
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+    for requested_email in [email.to_uppercase(), "nobody@example.com".to_string()] {
+        let response = client
+            .post(format!("{}/password-reset/request", test_app.address))
+            .json(&json!({ "email": requested_email }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200, "Unknown emails get the same answer");
+    }
+    let emailed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM password_reset_tokens WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(emailed, 1);
+
+    // The emailed token is replaced by a newer one
+    let expires_at = Utc::now() + Duration::hours(1);
+    let older = issue_password_reset_token(pool, user_id, expires_at).await.unwrap();
+    let token = issue_password_reset_token(pool, user_id, expires_at).await.unwrap();
+    assert_eq!(confirm(&client, &test_app.address, &older, "new_password").await, 400);
+
+    assert_eq!(confirm(&client, &test_app.address, &token, "new_password").await, 200);
+    assert_eq!(confirm(&client, &test_app.address, &token, "other_password").await, 400, "Tokens are single-use");
+    let email_verified: bool = sqlx::query_scalar("SELECT email_verified_at IS NOT NULL FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert!(email_verified, "The reset link went to the user's email");
+
+    let login = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": username, "password": "new_password" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(login.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn reset_passwords_follow_the_password_policy() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+    let client = Client::new();
+    let username = format!("reset_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": format!("{username}@example.com") }))
+        .send()
+        .await
+        .expect("Failed to register user");
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind(&username)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let token = issue_password_reset_token(pool, user_id, Utc::now() + Duration::hours(1)).await.unwrap();
+
+    for (new_password, code) in [("short".to_string(), "too_short"), (format!("{username}!"), "contains_personal_info")] {
+        let response = client
+            .post(format!("{}/password-reset/confirm", test_app.address))
+            .json(&json!({ "token": token, "new_password": new_password }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["errors"][0]["code"], code);
+    }
+
+    assert_eq!(confirm(&client, &test_app.address, &token, "new_password").await, 200, "The token is still valid");
+}
+
+async fn request_reset(client: &Client, address: &str, email: &str, forwarded_for: &str) -> u16 {
+    client
+        .post(format!("{}/password-reset/request", address))
+        .header("X-Forwarded-For", forwarded_for)
+        .json(&json!({ "email": email }))
+        .send()
+        .await
+        .expect("Failed to execute reset request")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn reset_emails_are_rate_limited() {
+    let test_app = spawn_app_with(|configuration| {
+        configuration.login_protection.max_email_requests_per_email = 2;
+        configuration.login_protection.max_email_requests_per_ip = 3;
+        // The test client stands in for the proxy, so each test run gets an address of its own
+        configuration.login_protection.trusted_proxies = vec!["127.0.0.1".to_string()];
+    })
+    .await;
+    let pool = &test_app.db_pool;
+    let client = Client::new();
+    let username = format!("reset_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let email = format!("{username}@example.com");
+    let other_email = format!("other_{email}");
+    for (username, email) in [(username.clone(), &email), (format!("other_{username}"), &other_email)] {
+        client
+            .post(format!("{}/register_user", test_app.address))
+            .json(&json!({ "username": username, "password": "password123", "email": email }))
+            .send()
+            .await
+            .expect("Failed to register user");
+    }
+    let emailed = || async {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM password_reset_tokens t JOIN users u ON u.id = t.user_id WHERE u.username = $1",
+        )
+        .bind(&username)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    };
+    let bytes = Uuid::new_v4().into_bytes();
+    let address = |n: u8| format!("198.18.{}.{}", bytes[0], bytes[1].wrapping_add(n));
+
+    // Per email, from wherever it is asked
+    for n in 0..3 {
+        assert_eq!(request_reset(&client, &test_app.address, &email, &address(n)).await, 200);
+    }
+    assert_eq!(emailed().await, 2, "Requests over the limit get the same answer, but no email");
+
+    // Per client address, whichever emails it asks for
+    let guessing_address = format!("198.19.{}.{}", bytes[0], bytes[1]);
+    for n in 0..3 {
+        let probe = format!("nobody{n}_{}", email);
+        assert_eq!(request_reset(&client, &test_app.address, &probe, &guessing_address).await, 200);
+    }
+    assert_eq!(request_reset(&client, &test_app.address, &other_email, &guessing_address).await, 200);
+    let limited: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM password_reset_tokens")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(limited, 2, "Nothing more was sent from the guessing address");
+}
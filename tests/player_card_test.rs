@@ -0,0 +1,91 @@
+//! Player card tests
+//!
+//! - Archetype comes from the workout mix, form from the last week against the weeks before
+//! - Streaks count consecutive workout days ending today or yesterday
+//! - `/profile/player-card` and `/league/users/{id}/player-card` return the same card
+
+use chrono::{Duration, NaiveDate, Utc};
+use reqwest::Client;
+use uuid::Uuid;
+
+use riina_backend::game::player_card::{archetype, form, workout_streak_days, Archetype, Form};
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn archetype_follows_the_workout_mix() {
+    assert_eq!(archetype(2, 50.0, 0.0), Archetype::Rookie, "Too few workouts");
+    assert_eq!(archetype(5, 0.0, 0.0), Archetype::Rookie);
+    assert_eq!(archetype(5, 70.0, 30.0), Archetype::Endurance);
+    assert_eq!(archetype(5, 20.0, 80.0), Archetype::Power);
+    assert_eq!(archetype(5, 50.0, 50.0), Archetype::AllRounder);
+}
+
+#[test]
+fn form_compares_the_last_week_to_the_weekly_average() {
+    assert_eq!(form(0.0, 300.0), Form::Inactive);
+    assert_eq!(form(130.0, 300.0), Form::Rising);
+    assert_eq!(form(100.0, 300.0), Form::Steady);
+    assert_eq!(form(70.0, 300.0), Form::Falling);
+    assert_eq!(form(10.0, 0.0), Form::Rising, "Back after three quiet weeks");
+}
+
+#[test]
+fn streaks_end_today_or_yesterday() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 12).unwrap();
+    let days = |offsets: &[i64]| offsets.iter().map(|d| today - Duration::days(*d)).collect::<Vec<_>>();
+
+    assert_eq!(workout_streak_days(&days(&[0, 1, 1, 2, 4]), today), 3);
+    assert_eq!(workout_streak_days(&days(&[1, 2]), today), 2, "Today isn't over yet");
+    assert_eq!(workout_streak_days(&days(&[2, 3]), today), 0);
+    assert_eq!(workout_streak_days(&[], today), 0);
+}
+
+#[tokio::test]
+async fn player_cards_show_derived_attributes() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let viewer = create_test_user_and_login(&test_app.address).await;
+
+    // Mostly stamina workouts on each of the last three days
+    let now = Utc::now();
+    for days_ago in 0..3 {
+        let start = now - Duration::days(days_ago) - Duration::minutes(5);
+        sqlx::query(
+            r#"
+            INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid, workout_start, workout_end,
+                                      stamina_gained, strength_gained, total_points_gained)
+            VALUES ($1, 'test-device', '[]', $2, $3, $4, 8, 2, 10)
+            "#,
+        )
+        .bind(user.user_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(start)
+        .bind(start + Duration::minutes(1))
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let url = format!("{}/profile/player-card", test_app.address);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let own: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(own["data"]["archetype"], "endurance");
+    assert_eq!(own["data"]["form"], "rising");
+    assert_eq!(own["data"]["streak_days"], 3);
+    assert_eq!(own["data"]["workouts_last_28_days"], 3);
+    assert_eq!(own["data"]["badges"]["mvp_count"], 0);
+
+    let url = format!("{}/league/users/{}/player-card", test_app.address, user.user_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &viewer.token, None).await;
+    assert_eq!(response.status(), 200);
+    let seen: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(seen["data"], own["data"]);
+
+    let url = format!("{}/league/users/{}/player-card", test_app.address, Uuid::new_v4());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &viewer.token, None).await;
+    assert_eq!(response.status(), 404);
+}
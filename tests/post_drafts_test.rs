@@ -0,0 +1,83 @@
+//! Draft post tests
+//!
+//! - Posts can be scheduled at most 30 days ahead
+//! - Drafts stay private and are listed for their author until published
+//! - Publishing restores the chosen visibility, right away or at the scheduled time
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::services::post_publishing_service::validate_publish_at;
+use riina_backend::services::PostPublishingService;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+
+#[test]
+fn posts_can_be_scheduled_up_to_30_days_ahead() {
+    let now = Utc::now();
+    assert!(validate_publish_at(now + Duration::days(30), now).is_ok());
+    assert!(validate_publish_at(now + Duration::days(31), now).is_err());
+}
+
+#[tokio::test]
+async fn drafts_stay_private_until_published() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let posts_url = format!("{}/posts/", test_app.address);
+    let create_post = |body: serde_json::Value| {
+        make_authenticated_request(&client, reqwest::Method::POST, &posts_url, &user.token, Some(body))
+    };
+    let draft: serde_json::Value = create_post(json!({ "post_type": "universal", "content": "Later", "draft": true, "visibility": "friends" }))
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(draft["data"]["draft"], true);
+    let draft_id: Uuid = draft["data"]["id"].as_str().unwrap().parse().unwrap();
+    let scheduled: serde_json::Value = create_post(json!({ "post_type": "universal", "content": "Soon", "publish_at": Utc::now() + Duration::hours(1) }))
+        .await
+        .json()
+        .await
+        .unwrap();
+    let scheduled_id: Uuid = scheduled["data"]["id"].as_str().unwrap().parse().unwrap();
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/posts/drafts", test_app.address), &user.token, None,
+    ).await;
+    let drafts: serde_json::Value = response.json().await.unwrap();
+    let drafts = drafts["data"].as_array().unwrap();
+    assert_eq!(drafts.len(), 2);
+    assert_eq!(drafts[0]["post_id"], scheduled_id.to_string(), "Scheduled drafts come first");
+
+    let visibility = |post_id: Uuid| {
+        sqlx::query_scalar::<_, String>("SELECT visibility::text FROM posts WHERE id = $1")
+            .bind(post_id)
+            .fetch_one(&test_app.db_pool)
+    };
+    assert_eq!(visibility(draft_id).await.unwrap(), "private");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/posts/{}/publish", test_app.address, draft_id), &user.token, Some(json!({})),
+    ).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(visibility(draft_id).await.unwrap(), "friends");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/posts/{}/publish", test_app.address, draft_id), &user.token, Some(json!({})),
+    ).await;
+    assert_eq!(response.status().as_u16(), 404, "Published posts are no drafts anymore");
+
+    // The scheduler publishes the scheduled draft once its time has come
+    sqlx::query("UPDATE post_drafts SET publish_at = NOW() - INTERVAL '1 minute' WHERE post_id = $1")
+        .bind(scheduled_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert!(PostPublishingService::new(test_app.db_pool.clone()).publish_due().await.unwrap() >= 1);
+    assert_eq!(visibility(scheduled_id).await.unwrap(), "public");
+}
@@ -0,0 +1,90 @@
+//! Notification quiet hours tests
+//!
+//! - When a user's do-not-disturb window ends, across midnight and in their timezone
+//! - Which notifications are pushed regardless
+//! - Setting and clearing quiet hours through the preferences API
+
+use chrono::{NaiveTime, TimeZone, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::utils::quiet_hours::{is_critical, QuietHours};
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn overnight_window_ends_the_next_morning_in_the_users_timezone() {
+    let quiet_hours = QuietHours::parse("22:00", "07:00", "Europe/Berlin").unwrap();
+
+    // 23:30 in Berlin (CEST, UTC+2) is quiet until 07:00 the next day
+    let late = Utc.with_ymd_and_hms(2026, 6, 10, 21, 30, 0).unwrap();
+    assert_eq!(quiet_hours.window_end(late), Some(Utc.with_ymd_and_hms(2026, 6, 11, 5, 0, 0).unwrap()));
+
+    // 03:00 in Berlin is quiet until 07:00 the same day
+    let early = Utc.with_ymd_and_hms(2026, 6, 11, 1, 0, 0).unwrap();
+    assert_eq!(quiet_hours.window_end(early), Some(Utc.with_ymd_and_hms(2026, 6, 11, 5, 0, 0).unwrap()));
+
+    // Noon and the end itself are outside the window
+    assert_eq!(quiet_hours.window_end(Utc.with_ymd_and_hms(2026, 6, 11, 10, 0, 0).unwrap()), None);
+    assert_eq!(quiet_hours.window_end(Utc.with_ymd_and_hms(2026, 6, 11, 5, 0, 0).unwrap()), None);
+}
+
+#[test]
+fn same_day_window() {
+    let quiet_hours = QuietHours::parse("13:00", "15:00", "UTC").unwrap();
+
+    let nap = Utc.with_ymd_and_hms(2026, 1, 5, 14, 0, 0).unwrap();
+    assert_eq!(quiet_hours.window_end(nap), Some(Utc.with_ymd_and_hms(2026, 1, 5, 15, 0, 0).unwrap()));
+    assert_eq!(quiet_hours.window_end(Utc.with_ymd_and_hms(2026, 1, 5, 16, 0, 0).unwrap()), None);
+    assert_eq!(quiet_hours.window_end(Utc.with_ymd_and_hms(2026, 1, 5, 12, 59, 0).unwrap()), None);
+}
+
+#[test]
+fn invalid_quiet_hours_are_rejected() {
+    assert!(QuietHours::parse("10pm", "07:00", "UTC").is_err());
+    assert!(QuietHours::parse("22:00", "07:00", "Mars/Olympus").is_err());
+    assert!(QuietHours::parse("22:00", "22:00", "UTC").is_err(), "Empty window");
+
+    let quiet_hours = QuietHours::parse("22:00", "07:30", "America/New_York").unwrap();
+    assert_eq!(quiet_hours.end, NaiveTime::from_hms_opt(7, 30, 0).unwrap());
+}
+
+#[test]
+fn only_alerts_that_need_acting_on_break_quiet_hours() {
+    assert!(is_critical(Some("admin_broadcast")));
+    assert!(is_critical(Some("unfinished_game")));
+    assert!(!is_critical(Some("reaction")));
+    assert!(!is_critical(None));
+}
+
+#[tokio::test]
+async fn users_set_and_clear_quiet_hours() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let url = format!("{}/notifications/preferences/quiet-hours", test_app.address);
+
+    let invalid = json!({ "start": "22:00", "end": "07:00", "timezone": "Nowhere/Special" });
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &url, &user.token, Some(invalid)).await;
+    assert_eq!(response.status(), 400);
+
+    let quiet_hours = json!({ "start": "22:00", "end": "07:00", "timezone": "Europe/Berlin" });
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &url, &user.token, Some(quiet_hours)).await;
+    assert_eq!(response.status(), 200);
+
+    let preferences_url = format!("{}/notifications/preferences", test_app.address);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &preferences_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["quiet_hours_start"], "22:00:00");
+    assert_eq!(body["quiet_hours_end"], "07:00:00");
+    assert_eq!(body["quiet_hours_timezone"], "Europe/Berlin");
+    assert_eq!(body["push_enabled"], true, "Other preferences keep their defaults");
+
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &url, &user.token, None).await;
+    assert_eq!(response.status(), 204);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &preferences_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["quiet_hours_start"].is_null());
+}
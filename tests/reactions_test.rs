@@ -0,0 +1,261 @@
+//! Target reaction tests
+//!
+//! Covers `/social/reactions/{target_type}/{target_id}`:
+//! - Posts, game summaries and score events can be reacted to like workouts
+//! - Owners are notified; private posts and unknown targets return 404
+//! - Workouts and their comments are hidden along with a private post of the workout
+//! - Reactions go away with their target
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+use common::social_helpers::create_user_with_workout;
+
+async fn create_post(client: &Client, address: &str, token: &str, visibility: &str) -> String {
+    let response = make_authenticated_request(
+        client, reqwest::Method::POST, &format!("{}/posts/", address), token,
+        Some(json!({ "post_type": "universal", "content": "Rest day stretch", "visibility": visibility })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn count_reactions(pool: &sqlx::PgPool, target_type: &str, target_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM reactions WHERE target_type = $1 AND target_id = $2")
+        .bind(target_type)
+        .bind(target_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn users_react_to_posts() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let author = create_test_user_and_login(&test_app.address).await;
+    let viewer = create_test_user_and_login(&test_app.address).await;
+    let author_id = parse_user_id_from_jwt_token(&author.token);
+
+    let post_id = create_post(&client, &test_app.address, &author.token, "public").await;
+    let reactions_url = format!("{}/social/reactions/post/{}", test_app.address, post_id);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &reactions_url, &viewer.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["target_type"], "post");
+    assert_eq!(body["target_id"], post_id.as_str());
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &reactions_url, &viewer.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["fire_count"], 1);
+    assert_eq!(body["user_reacted"], true);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/users", reactions_url), &author.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body[0]["username"], viewer.username.as_str());
+
+    // The author hears about it
+    let message: String = sqlx::query_scalar(
+        "SELECT message FROM notifications WHERE recipient_id = $1 AND entity_type = 'post' AND entity_id = $2::uuid",
+    )
+    .bind(author_id)
+    .bind(&post_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Post author should be notified");
+    assert_eq!(message, format!("{} reacted to your post", viewer.username));
+
+    // Unknown targets, unsupported types and invalid reactions
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/social/reactions/post/{}", test_app.address, Uuid::new_v4()),
+        &viewer.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/social/reactions/team/{}", test_app.address, post_id),
+        &viewer.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &reactions_url, &viewer.token, Some(json!({ "reaction_type": "meh" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    // Private posts are only visible, and reactable, for their author
+    let private_post = create_post(&client, &test_app.address, &author.token, "private").await;
+    let private_url = format!("{}/social/reactions/post/{}", test_app.address, private_post);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &private_url, &viewer.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &private_url, &viewer.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &private_url, &author.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    // Removing
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &reactions_url, &viewer.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &reactions_url, &viewer.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    // Deleting the post drops its reactions
+    let private_post = Uuid::parse_str(&private_post).unwrap();
+    assert_eq!(count_reactions(&test_app.db_pool, "post", private_post).await, 1);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE, &format!("{}/posts/{}", test_app.address, private_post), &author.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(count_reactions(&test_app.db_pool, "post", private_post).await, 0);
+}
+
+#[tokio::test]
+async fn workouts_and_comments_are_as_visible_as_their_post() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (author, workout_id) = create_user_with_workout(&test_app.address).await;
+    let viewer = create_test_user_and_login(&test_app.address).await;
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/social/workouts/{}/comments", test_app.address, workout_id),
+        &author.token, Some(json!({ "content": "Felt strong today" })),
+    ).await;
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let comment_id = body["id"].as_str().unwrap().to_string();
+
+    let workout_url = format!("{}/social/reactions/workout/{}", test_app.address, workout_id);
+    let comment_url = format!("{}/social/reactions/comment/{}", test_app.address, comment_id);
+    let react = |url: String, token: String| {
+        let client = client.clone();
+        async move {
+            make_authenticated_request(
+                &client, reqwest::Method::POST, &url, &token, Some(json!({ "reaction_type": "fire" })),
+            ).await.status().as_u16()
+        }
+    };
+
+    // Published with a public post
+    assert_eq!(200, react(workout_url.clone(), viewer.token.clone()).await);
+    assert_eq!(200, react(comment_url.clone(), viewer.token.clone()).await);
+
+    sqlx::query("UPDATE posts SET visibility = 'private' WHERE workout_id = $1")
+        .bind(workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(404, react(workout_url.clone(), viewer.token.clone()).await);
+    assert_eq!(404, react(comment_url.clone(), viewer.token.clone()).await);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &workout_url, &viewer.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    // The author still sees their own workout
+    assert_eq!(200, react(workout_url, author.token.clone()).await);
+    assert_eq!(200, react(comment_url, author.token.clone()).await);
+}
+
+#[tokio::test]
+async fn users_react_to_game_summaries_and_score_events() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let scorer = create_test_user_and_login(&test_app.address).await;
+    let fan = create_test_user_and_login(&test_app.address).await;
+    let scorer_id = parse_user_id_from_jwt_token(&scorer.token);
+    let fan_id = parse_user_id_from_jwt_token(&fan.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![scorer_id, fan_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Reactions Season", &start_date,
+    ).await;
+
+    let game = sqlx::query!(
+        "SELECT id, home_team_id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1",
+        Uuid::parse_str(&season_id).unwrap()
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let score_event_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side,
+                                       score_points, power_contribution, description)
+        VALUES ($1, $2, $3, $4, 'home', 12.5, 0, 'Morning run')
+        RETURNING id
+        "#,
+    )
+    .bind(game.id)
+    .bind(scorer_id)
+    .bind(&scorer.username)
+    .bind(game.home_team_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let summary_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO game_summaries (game_id, final_home_score, final_away_score, game_start_date, game_end_date)
+        VALUES ($1, 12, 0, NOW() - INTERVAL '7 days', NOW())
+        RETURNING id
+        "#,
+    )
+    .bind(game.id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    // Score events belong to the scorer, who gets notified
+    let score_url = format!("{}/social/reactions/score_event/{}", test_app.address, score_event_id);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &score_url, &fan.token, Some(json!({ "reaction_type": "fire" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let message: String = sqlx::query_scalar(
+        "SELECT message FROM notifications WHERE recipient_id = $1 AND entity_type = 'score_event'",
+    )
+    .bind(scorer_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Scorer should be notified");
+    assert_eq!(message, format!("{} reacted to your score", fan.username));
+
+    // Game summaries have no owner, everyone can react
+    let summary_url = format!("{}/social/reactions/game_summary/{}", test_app.address, summary_id);
+    for token in [&fan.token, &scorer.token] {
+        let response = make_authenticated_request(
+            &client, reqwest::Method::POST, &summary_url, token, Some(json!({ "reaction_type": "fire" })),
+        ).await;
+        assert_eq!(200, response.status().as_u16());
+    }
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &summary_url, &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["fire_count"], 2);
+    assert_eq!(body["user_reacted"], false);
+
+    // Reactions are removed with their target
+    sqlx::query("DELETE FROM live_score_events WHERE id = $1")
+        .bind(score_event_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(count_reactions(&test_app.db_pool, "score_event", score_event_id).await, 0);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &score_url, &fan.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+}
@@ -0,0 +1,94 @@
+//! Refresh token tests
+//!
+//! - Refresh tokens are random and only stored as hashes
+//! - Login hands out a refresh token; each refresh rotates it within the device's family
+//! - Replaying a rotated token revokes the whole family, and logout revokes it too
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::utils::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+mod common;
+use common::utils::spawn_app;
+
+#[test]
+fn refresh_tokens_are_random() {
+    let token = generate_opaque_token();
+    assert_eq!(token.len(), 64);
+    assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_ne!(token, generate_opaque_token());
+}
+
+#[test]
+fn refresh_tokens_are_stored_as_sha256_hashes() {
+    let hash = hash_opaque_token("token");
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, hash_opaque_token("token"));
+    assert_ne!(hash, hash_opaque_token("token2"));
+}
+
+async fn refresh(client: &Client, address: &str, refresh_token: &str) -> reqwest::Response {
+    client
+        .post(format!("{}/refresh", address))
+        .json(&json!({ "refresh_token": refresh_token }))
+        .send()
+        .await
+        .expect("Failed to execute refresh request")
+}
+
+#[tokio::test]
+async fn refresh_tokens_rotate_and_replays_revoke_the_family() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let username = format!("refresh_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": format!("{username}@example.com") }))
+        .send()
+        .await
+        .expect("Failed to register user");
+    let login: serde_json::Value = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": username, "password": "password123" }))
+        .send()
+        .await
+        .expect("Failed to execute login request")
+        .json()
+        .await
+        .unwrap();
+    let first = login["refresh_token"].as_str().expect("Login returns a refresh token").to_string();
+
+    // Rotation hands out a new refresh token along with the access token
+    let response = refresh(&client, &test_app.address, &first).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let rotated: serde_json::Value = response.json().await.unwrap();
+    assert!(rotated["token"].as_str().is_some());
+    let second = rotated["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(first, second);
+
+    // Replaying the first token revokes its successor as well
+    assert_eq!(refresh(&client, &test_app.address, &first).await.status().as_u16(), 401);
+    assert_eq!(refresh(&client, &test_app.address, &second).await.status().as_u16(), 401);
+
+    // Logout revokes the family of a fresh login
+    let login: serde_json::Value = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": username, "password": "password123" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let third = login["refresh_token"].as_str().unwrap().to_string();
+    let logout = client
+        .post(format!("{}/logout", test_app.address))
+        .json(&json!({ "refresh_token": third }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(logout.status().as_u16(), 200);
+    assert_eq!(refresh(&client, &test_app.address, &third).await.status().as_u16(), 401);
+}
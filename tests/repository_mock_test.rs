@@ -0,0 +1,103 @@
+//! Repository mock tests
+//!
+//! Exercises logic written against the repository traits with in-memory repos, no database:
+//! - Workouts are credited to the user's team only when they fall inside the game's live window
+//! - Workouts from before the user joined the team, or from users outside both teams, are not credited
+//! - Ownership checks are derived from the workout owner
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use riina_backend::db::team_repo::{TeamMembership, TeamRepository};
+use riina_backend::db::workout_repo::WorkoutRepository;
+use riina_backend::game::workout_credit::credited_team_for_workout;
+use riina_backend::models::league::{GameStatus, LeagueGame};
+use riina_backend::models::team::TeamRole;
+
+#[derive(Default)]
+struct MockTeamRepo {
+    memberships: HashMap<(Uuid, Uuid), TeamMembership>,
+}
+
+impl MockTeamRepo {
+    fn with_member(mut self, team_id: Uuid, user_id: Uuid, joined_at: DateTime<Utc>) -> Self {
+        self.memberships.insert((team_id, user_id), TeamMembership { team_id, joined_at });
+        self
+    }
+}
+
+impl TeamRepository for MockTeamRepo {
+    async fn find_active_role(&self, team_id: Uuid, user_id: Uuid) -> Result<Option<TeamRole>, sqlx::Error> {
+        Ok(self.memberships.get(&(team_id, user_id)).map(|_| TeamRole::Member))
+    }
+
+    async fn find_active_membership(&self, user_id: Uuid, team_ids: [Uuid; 2]) -> Result<Option<TeamMembership>, sqlx::Error> {
+        Ok(team_ids.iter().find_map(|team_id| self.memberships.get(&(*team_id, user_id)).copied()))
+    }
+}
+
+struct MockWorkoutRepo {
+    owners: HashMap<Uuid, Uuid>,
+}
+
+impl WorkoutRepository for MockWorkoutRepo {
+    async fn find_owner(&self, workout_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        Ok(self.owners.get(&workout_id).copied())
+    }
+}
+
+fn live_game(home_team_id: Uuid, away_team_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> LeagueGame {
+    let now = Utc::now();
+    let mut game = LeagueGame::with_defaults(
+        Uuid::new_v4(), Uuid::new_v4(), home_team_id, away_team_id, 1, true,
+        GameStatus::InProgress, None, now, now,
+    );
+    game.game_start_time = Some(start);
+    game.game_end_time = Some(end);
+    game
+}
+
+#[tokio::test]
+async fn workouts_are_credited_to_the_users_team_within_the_live_window() {
+    let (home_team, away_team) = (Uuid::new_v4(), Uuid::new_v4());
+    let (home_player, away_player, outsider) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let game_start = Utc::now() - Duration::hours(2);
+    let game_end = game_start + Duration::hours(4);
+    let game = live_game(home_team, away_team, game_start, game_end);
+
+    let teams = MockTeamRepo::default()
+        .with_member(home_team, home_player, game_start - Duration::days(7))
+        .with_member(away_team, away_player, game_start + Duration::hours(1));
+
+    let workout_start = game_start + Duration::minutes(30);
+    let workout_end = workout_start + Duration::minutes(45);
+    let credited = |user_id, start, end| credited_team_for_workout(&teams, user_id, &game, start, end);
+
+    assert_eq!(credited(home_player, workout_start, workout_end).await.unwrap(), Some(home_team));
+    assert_eq!(credited(outsider, workout_start, workout_end).await.unwrap(), None);
+    // The away player joined an hour into the game, after this workout started
+    assert_eq!(credited(away_player, workout_start, workout_end).await.unwrap(), None);
+    let later_start = game_start + Duration::hours(2);
+    assert_eq!(credited(away_player, later_start, later_start + Duration::minutes(30)).await.unwrap(), Some(away_team));
+
+    // Workouts have to lie entirely within the live window
+    assert_eq!(credited(home_player, game_start - Duration::minutes(10), workout_end).await.unwrap(), None);
+    assert_eq!(credited(home_player, workout_start, game_end + Duration::minutes(1)).await.unwrap(), None);
+
+    let mut unscheduled = game.clone();
+    unscheduled.game_start_time = None;
+    let result = credited_team_for_workout(&teams, home_player, &unscheduled, workout_start, workout_end).await;
+    assert_eq!(result.unwrap(), None);
+}
+
+#[tokio::test]
+async fn workout_ownership_follows_the_owner() {
+    let (workout_id, owner, other_user) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let workouts = MockWorkoutRepo { owners: HashMap::from([(workout_id, owner)]) };
+
+    assert!(workouts.is_owned_by(workout_id, owner).await.unwrap());
+    assert!(!workouts.is_owned_by(workout_id, other_user).await.unwrap());
+    assert!(!workouts.is_owned_by(Uuid::new_v4(), owner).await.unwrap());
+}
@@ -0,0 +1,139 @@
+//! Research dataset export tests
+//!
+//! - Zone minutes are aggregated per age band and count distinct users
+//! - Users who opt out of research data sharing are excluded
+//! - Rows describing fewer than k users are withheld
+//! - Only admins can export, and k below the minimum is rejected
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app, UserRegLoginResponse};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+async fn create_users_with_workouts(address: &str, count: usize) -> Vec<UserRegLoginResponse> {
+    let client = Client::new();
+    let mut users = Vec::new();
+    for _ in 0..count {
+        let user = create_test_user_with_health_profile(address).await;
+        let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::days(2), 30);
+        upload_workout_data_for_user(&client, address, &user.token, &mut workout_data)
+            .await
+            .expect("Upload failed");
+        users.push(user);
+    }
+    users
+}
+
+async fn get_dataset(address: &str, token: &str, query: &str) -> reqwest::Response {
+    make_authenticated_request(
+        &Client::new(),
+        Method::GET,
+        &format!("{}/admin/research/datasets?{}", address, query),
+        token,
+        None,
+    )
+    .await
+}
+
+async fn opt_out(address: &str, user: &UserRegLoginResponse) {
+    let response = make_authenticated_request(
+        &Client::new(),
+        Method::PATCH,
+        &format!("{}/profile/consent", address),
+        &user.token,
+        Some(json!({ "research_data_sharing": false })),
+    )
+    .await;
+    assert!(response.status().is_success());
+}
+
+/// Largest number of users behind any released row
+async fn max_user_count(address: &str, token: &str) -> (i64, u64) {
+    let response = get_dataset(address, token, "dataset=zone_minutes_by_age_band&k=5").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let rows = body["data"]["rows"].as_array().unwrap();
+    for row in rows {
+        assert_eq!(row["age_band"], "18-29");
+        assert!(row.get("user_id").is_none());
+    }
+    let max = rows.iter().map(|row| row["user_count"].as_i64().unwrap()).max().unwrap_or(0);
+    (max, body["data"]["suppressed_rows"].as_u64().unwrap())
+}
+
+#[tokio::test]
+async fn zone_minutes_exclude_opted_out_users_and_small_groups() {
+    let test_app = spawn_app().await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let users = create_users_with_workouts(&test_app.address, 6).await;
+
+    let (users_behind_rows, _) = max_user_count(&test_app.address, &admin.token).await;
+    assert_eq!(users_behind_rows, 6);
+
+    opt_out(&test_app.address, &users[0]).await;
+    let (users_behind_rows, _) = max_user_count(&test_app.address, &admin.token).await;
+    assert_eq!(users_behind_rows, 5);
+
+    // Four remaining users are fewer than k, so nothing is released
+    opt_out(&test_app.address, &users[1]).await;
+    let (users_behind_rows, suppressed_rows) = max_user_count(&test_app.address, &admin.token).await;
+    assert_eq!(users_behind_rows, 0);
+    assert!(suppressed_rows > 0);
+}
+
+#[tokio::test]
+async fn adherence_curves_only_cover_fully_observed_weeks() {
+    let test_app = spawn_app().await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    create_users_with_workouts(&test_app.address, 5).await;
+
+    // First workouts 23 days ago: weeks 0 to 2 are over, week 3 is not
+    sqlx::query!(
+        "UPDATE workout_data SET workout_start = workout_start - INTERVAL '21 days', workout_end = workout_end - INTERVAL '21 days'"
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let response = get_dataset(&test_app.address, &admin.token, "dataset=adherence_curves&k=5&weeks=4").await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let rows = body["data"]["rows"].as_array().unwrap();
+    let weeks: Vec<i64> = rows.iter().map(|row| row["week"].as_i64().unwrap()).collect();
+    assert_eq!(weeks, vec![0, 1, 2]);
+    assert_eq!(rows[0]["cohort_size"], 5);
+    assert_eq!(rows[0]["adherence_rate"], 1.0);
+    assert_eq!(rows[1]["active_users"], 0);
+}
+
+#[tokio::test]
+async fn research_datasets_require_admin_and_minimum_k() {
+    let test_app = spawn_app().await;
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let response = get_dataset(&test_app.address, &user.token, "dataset=zone_minutes_by_age_band").await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = get_dataset(&test_app.address, &admin.token, "dataset=zone_minutes_by_age_band&k=2").await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = make_authenticated_request(
+        &Client::new(),
+        Method::GET,
+        &format!("{}/profile/consent", test_app.address),
+        &user.token,
+        None,
+    )
+    .await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["research_data_sharing"], true);
+}
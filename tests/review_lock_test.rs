@@ -0,0 +1,147 @@
+//! Anticheat review lock tests
+//!
+//! - Pending reports are grouped by the game their workouts scored in
+//! - Games proceed once the league's lock has run out, even with reports still pending
+//! - Leagues configure the lock through the admin API; 0 disables it
+//! - Evaluation holds back locked games, reports them with their flags, and evaluates them once the reports are resolved
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::game::review_lock::{blocked_games, lock_ends_at, ReviewFlag};
+use riina_backend::services::ReviewLockService;
+
+mod common;
+use common::live_game_helpers::setup_live_game_environment;
+use common::utils::{make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{upload_workout_data_for_user, WorkoutData, WorkoutIntensity};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 6, 12, 0, 0).unwrap()
+}
+
+fn flag(reason: &str) -> ReviewFlag {
+    ReviewFlag {
+        report_id: Uuid::new_v4(),
+        workout_data_id: Uuid::new_v4(),
+        workout_owner_id: Uuid::new_v4(),
+        reason: reason.to_string(),
+        reported_at: now() - Duration::hours(2),
+    }
+}
+
+#[test]
+fn lock_ends_hours_after_the_game() {
+    assert_eq!(lock_ends_at(now(), 24), now() + Duration::days(1));
+}
+
+#[test]
+fn flags_are_grouped_by_game() {
+    let (first_game, second_game) = (Uuid::new_v4(), Uuid::new_v4());
+    let proceeds_at = now() + Duration::hours(3);
+    let (a, b, c) = (flag("a"), flag("b"), flag("c"));
+
+    let blocked = blocked_games(
+        vec![
+            (first_game, proceeds_at, a.clone()),
+            (second_game, proceeds_at, b.clone()),
+            (first_game, proceeds_at, c.clone()),
+        ],
+        now(),
+    );
+    assert_eq!(blocked.len(), 2);
+    assert_eq!(blocked[0].game_id, first_game);
+    assert_eq!(blocked[0].flags, vec![a, c]);
+    assert_eq!(blocked[1].game_id, second_game);
+    assert_eq!(blocked[1].flags, vec![b]);
+}
+
+#[test]
+fn games_proceed_once_the_lock_runs_out() {
+    let (expired_game, locked_game) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let blocked = blocked_games(
+        vec![
+            (expired_game, now(), flag("expired")),
+            (locked_game, now() + Duration::minutes(1), flag("locked")),
+        ],
+        now(),
+    );
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].game_id, locked_game);
+}
+
+#[tokio::test]
+async fn evaluation_waits_for_reported_workouts() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let environment = setup_live_game_environment(&test_app).await;
+    let admin_token = &environment.admin_session.token;
+    let game_id = environment.first_game_id;
+    let league_url = format!("{}/admin/leagues/{}", test_app.address, environment.league_id);
+
+    let response = make_authenticated_request(&client, Method::PATCH, &league_url, admin_token, Some(json!({ "review_lock_hours": 169 }))).await;
+    assert_eq!(response.status(), 400);
+    let response = make_authenticated_request(&client, Method::PATCH, &league_url, admin_token, Some(json!({ "review_lock_hours": 24 }))).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, Method::GET, &league_url, admin_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["review_lock_hours"], 24);
+
+    // A workout that scored in the game is reported, then the game finishes
+    let mut workout = WorkoutData::new(WorkoutIntensity::Intense, Utc::now() - Duration::hours(3), 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &environment.home_user.token, &mut workout).await.unwrap();
+    let workout_id = Uuid::parse_str(upload["data"]["sync_id"].as_str().unwrap()).unwrap();
+    sqlx::query(
+        "INSERT INTO live_score_events (user_id, username, team_id, team_side, score_points, power_contribution, description, workout_data_id, game_id)
+         VALUES ($1, $2, $3, 'home', 10, 10, 'Workout', $4, $5)"
+    )
+    .bind(environment.home_user.user_id)
+    .bind(&environment.home_user.username)
+    .bind(Uuid::parse_str(&environment.home_team_id).unwrap())
+    .bind(workout_id)
+    .bind(game_id)
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE games SET status = 'finished', game_start_time = NOW() - INTERVAL '1 day', game_end_time = NOW() - INTERVAL '1 hour' WHERE id = $1")
+        .bind(game_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let report_url = format!("{}/health/workout/{}/report", test_app.address, workout_id);
+    let response = make_authenticated_request(&client, Method::POST, &report_url, &environment.away_user_1.token, Some(json!({ "reason": "Heart rate flat for an hour" }))).await;
+    assert!(response.status().is_success());
+    let report: serde_json::Value = response.json().await.unwrap();
+
+    let evaluate_url = format!("{}/admin/games/trigger-evaluation", test_app.address);
+    let response = make_authenticated_request(&client, Method::POST, &evaluate_url, admin_token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let blocked = body["blocked_games"].as_array().unwrap().iter()
+        .find(|game| game["game_id"] == game_id.to_string())
+        .expect("The game with a pending report is blocked");
+    assert_eq!(blocked["flags"][0]["workout_data_id"], workout_id.to_string());
+    assert_eq!(blocked["flags"][0]["reason"], "Heart rate flat for an hour");
+
+    let review_lock = ReviewLockService::new(test_app.db_pool.clone());
+    assert!(review_lock.held_games().await.unwrap().contains(&game_id));
+
+    // Once the report is dismissed the game is evaluated
+    let review_url = format!("{}/admin/workout-reports/{}", test_app.address, report["id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, Method::PATCH, &review_url, admin_token, Some(json!({ "status": "dismissed" }))).await;
+    assert!(response.status().is_success());
+    let response = make_authenticated_request(&client, Method::POST, &evaluate_url, admin_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(!body["blocked_games"].as_array().unwrap().iter().any(|game| game["game_id"] == game_id.to_string()));
+    assert!(!review_lock.held_games().await.unwrap().contains(&game_id));
+    let status: String = sqlx::query_scalar("SELECT status::text FROM games WHERE id = $1")
+        .bind(game_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "evaluated");
+}
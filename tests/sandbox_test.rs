@@ -0,0 +1,76 @@
+//! Sandbox organization tests, built with `--features sandbox`
+//!
+//! Covers `/admin/sandbox`:
+//! - Lifetimes are validated
+//! - Admins provision sandboxes whose users sign in and see a live game, and tear them down
+
+#![cfg(feature = "sandbox")]
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::models::sandbox::CreateSandboxRequest;
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn sandbox_lifetimes_are_bounded() {
+    let request = |ttl_minutes: Option<i64>| CreateSandboxRequest { ttl_minutes };
+    assert!(request(None).validate().is_ok());
+    assert_eq!(request(None).ttl(), chrono::Duration::hours(1));
+    assert!(request(Some(30)).validate().is_ok());
+    assert!(request(Some(1)).validate().is_err());
+    assert!(request(Some(2 * 24 * 60)).validate().is_err());
+}
+
+#[tokio::test]
+async fn admins_provision_and_tear_down_sandboxes() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let sandbox_url = format!("{}/admin/sandbox", test_app.address);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &sandbox_url, &user.token, Some(json!({})),
+    ).await;
+    assert_eq!(response.status(), 403);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &sandbox_url, &admin.token, Some(json!({ "ttl_minutes": 30 })),
+    ).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let sandbox = &body["data"];
+    assert_eq!(sandbox["users"].as_array().unwrap().len(), 4);
+
+    // Sandbox users sign in with the shared password and find their game live
+    let response = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": sandbox["users"][0]["username"], "password": sandbox["password"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let login: serde_json::Value = response.json().await.unwrap();
+    let token = login["token"].as_str().unwrap();
+
+    let live_url = format!("{}/league/games/{}/live", test_app.address, sandbox["live_game_id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &live_url, token, None).await;
+    assert_eq!(response.status(), 200);
+
+    let delete_url = format!("{}/{}", sandbox_url, sandbox["id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &delete_url, &admin.token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &delete_url, &admin.token, None).await;
+    assert_eq!(response.status(), 404);
+
+    let users_left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE username = $1")
+        .bind(sandbox["users"][0]["username"].as_str().unwrap())
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(users_left, 0);
+}
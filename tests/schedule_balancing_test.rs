@@ -0,0 +1,144 @@
+//! Schedule balancing tests
+//!
+//! Covers fixture balancing when seasons are created:
+//! - Every team gets as many home as away games, and the fairness report comes with the season
+//! - Previous season ratings are used to keep strong opponents apart
+//! - Leagues can switch the generator's balancing off
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, TestApp};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams};
+
+async fn create_season(
+    test_app: &TestApp,
+    client: &Client,
+    token: &str,
+    league_id: &str,
+    games_per_matchup: i32,
+    days_from_now: i64,
+) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/admin/leagues/{}/seasons", test_app.address, league_id),
+        token,
+        Some(json!({
+            "name": format!("Balanced Season {}", &Uuid::new_v4().to_string()[..4]),
+            "start_date": (Utc::now() + Duration::days(days_from_now)).to_rfc3339(),
+            "auto_evaluation_enabled": false,
+            "games_per_matchup": games_per_matchup
+        })),
+    ).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+#[tokio::test]
+async fn generated_schedules_balance_home_and_away_games() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 6, 6, None, true, None, None).await;
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET,
+        &format!("{}/admin/leagues/{}", test_app.address, league.league_id), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["schedule_balance_home_away"], true);
+    assert_eq!(body["data"]["schedule_spread_strong_teams"], true);
+
+    let season = create_season(&test_app, &client, &admin.token, &league.league_id, 2, 1).await;
+    assert_eq!(season["games_count"], 30);
+    let fairness = &season["schedule_fairness"];
+    assert_eq!(fairness["balance_home_away"], true);
+    assert_eq!(fairness["rated_teams"], 0);
+    assert_eq!(fairness["max_home_away_imbalance"], 0);
+
+    let season_id = Uuid::parse_str(season["id"].as_str().unwrap()).unwrap();
+    let games = sqlx::query!(
+        "SELECT home_team_id, away_team_id, week_number, is_first_leg FROM games WHERE season_id = $1",
+        season_id
+    )
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    let mut home_games: HashMap<Uuid, i32> = HashMap::new();
+    for game in &games {
+        *home_games.entry(game.home_team_id).or_default() += 1;
+        assert_eq!(game.is_first_leg, game.week_number <= 5);
+    }
+    assert_eq!(home_games.len(), 6);
+    assert!(home_games.values().all(|&home| home == 5), "Every team hosts half of its games: {:?}", home_games);
+
+    // The report is kept with the season
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET,
+        &format!("{}/admin/leagues/{}/seasons/{}", test_app.address, league.league_id, season_id), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(&body["data"]["schedule_fairness"], fairness);
+}
+
+#[tokio::test]
+async fn previous_season_ratings_spread_strong_teams_unless_disabled() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 6, 6, None, true, None, None).await;
+    let first_season = create_season(&test_app, &client, &admin.token, &league.league_id, 1, 1).await;
+
+    // Half of the teams dominated the first season
+    let first_season_id = Uuid::parse_str(first_season["id"].as_str().unwrap()).unwrap();
+    for (i, team_id) in league.team_ids.iter().enumerate() {
+        let wins = if i < 3 { 4 } else { 1 };
+        sqlx::query!(
+            "UPDATE league_standings SET games_played = 5, wins = $1, losses = 5 - $1 WHERE season_id = $2 AND team_id = $3",
+            wins,
+            first_season_id,
+            Uuid::parse_str(team_id).unwrap()
+        )
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let spread = create_season(&test_app, &client, &admin.token, &league.league_id, 1, 60).await;
+    let spread_fairness = &spread["schedule_fairness"];
+    assert_eq!(spread_fairness["rated_teams"], 6);
+    assert_eq!(spread_fairness["spread_strong_teams"], true);
+    assert!(spread_fairness["max_home_away_imbalance"].as_i64().unwrap() <= 1);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH,
+        &format!("{}/admin/leagues/{}", test_app.address, league.league_id), &admin.token,
+        Some(json!({ "schedule_balance_home_away": false, "schedule_spread_strong_teams": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["schedule_balance_home_away"], false);
+
+    let plain = create_season(&test_app, &client, &admin.token, &league.league_id, 1, 120).await;
+    let plain_fairness = &plain["schedule_fairness"];
+    assert_eq!(plain_fairness["rated_teams"], 6);
+    assert_eq!(plain_fairness["spread_strong_teams"], false);
+    assert_eq!(plain_fairness["balance_home_away"], false);
+    assert!(
+        spread_fairness["strong_opponent_back_to_backs"].as_i64().unwrap()
+            <= plain_fairness["strong_opponent_back_to_backs"].as_i64().unwrap(),
+        "Spreading should not cluster strong opponents: {} vs {}", spread_fairness, plain_fairness
+    );
+    assert!(
+        spread_fairness["max_home_away_imbalance"].as_i64().unwrap()
+            < plain_fairness["max_home_away_imbalance"].as_i64().unwrap()
+    );
+}
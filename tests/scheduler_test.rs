@@ -15,6 +15,7 @@ use uuid::Uuid;
 use reqwest::Client;
 use chrono::{Weekday, NaiveTime, Utc, Duration};
 use riina_backend::services::SchedulerService;
+use riina_backend::db::game_queries::WORKOUT_UPLOAD_GRACE_MINUTES;
 use riina_backend::config::redis::RedisSettings;
 use riina_backend::config::settings::get_config;
 use std::sync::Arc;
@@ -592,6 +593,17 @@ async fn test_automated_scheduler_game_lifecycle() {
     while !game_completed && completion_attempts < 30 { // Wait up to 30 seconds
         tokio::time::sleep(TokioDuration::from_secs(1)).await;
         completion_attempts += 1;
+
+        // Finished games wait out the upload grace period before they're evaluated,
+        // so skip it for the next cycle to pick the game up
+        sqlx::query(
+            "UPDATE games SET game_end_time = NOW() - make_interval(mins => $2) WHERE season_id = $1 AND status = 'finished' AND game_end_time > NOW() - make_interval(mins => $2)"
+        )
+        .bind(season_uuid)
+        .bind(WORKOUT_UPLOAD_GRACE_MINUTES)
+        .execute(&app.db_pool)
+        .await
+        .expect("Failed to skip the upload grace period");
         
         // Check standings to see if games have been played
         let standings_response = make_authenticated_request(
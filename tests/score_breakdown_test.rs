@@ -0,0 +1,136 @@
+//! Workout score breakdown tests
+//!
+//! - The explanation walks from the samples through zones, multipliers and caps to the total
+//! - Games credited and suspensions are explained
+//! - `/health/workout/{id}/score-breakdown` is only available to the workout's owner
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use uuid::Uuid;
+
+use riina_backend::models::workout_data::{HeartRateData, WorkoutType, ZoneBreakdown};
+use riina_backend::workout::score_breakdown::{explain_score, zone_weight, CreditedGame, ScoredWorkout};
+
+mod common;
+use common::utils::{make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+fn zone(name: &str, minutes: f32, points: f32) -> ZoneBreakdown {
+    let mut zone = ZoneBreakdown::new(name.to_string());
+    zone.minutes = minutes;
+    zone.stamina_gained = points;
+    zone
+}
+
+fn scored_workout(start: DateTime<Utc>) -> ScoredWorkout {
+    // One sample a minute for 30 minutes, plus one before the start and a duplicate
+    let mut heart_rate_data: Vec<HeartRateData> = (0..=30)
+        .map(|m| HeartRateData { timestamp: start + Duration::minutes(m), heart_rate: 150 })
+        .collect();
+    heart_rate_data.insert(0, HeartRateData { timestamp: start - Duration::minutes(5), heart_rate: 90 });
+    heart_rate_data.push(HeartRateData { timestamp: start + Duration::minutes(30), heart_rate: 150 });
+
+    ScoredWorkout {
+        workout_id: Uuid::new_v4(),
+        workout_start: start,
+        workout_end: start + Duration::minutes(30),
+        heart_rate_data,
+        zones: vec![zone("Off", 2.0, 0.0), zone("Easy", 10.0, 40.0), zone("Moderate", 18.0, 108.0)],
+        workout_type: WorkoutType::Cardio,
+        scoring_calibration: 1.0,
+        total_points: 148,
+        suspended: false,
+        games: Vec::new(),
+    }
+}
+
+#[test]
+fn zone_weights_match_scoring() {
+    assert_eq!(zone_weight("Off"), Some(0.0));
+    assert_eq!(zone_weight("Easy"), Some(4.0));
+    assert_eq!(zone_weight("Hard"), Some(8.0));
+    assert_eq!(zone_weight("Unknown"), None);
+}
+
+#[test]
+fn breakdown_explains_samples_zones_and_total() {
+    let breakdown = explain_score(&scored_workout(Utc::now() - Duration::hours(1)));
+
+    assert_eq!(breakdown.samples.received, 33);
+    assert_eq!(breakdown.samples.used, 31);
+    assert_eq!(breakdown.samples.discarded, 2);
+    assert_eq!(breakdown.zones.len(), 3);
+    assert_eq!(breakdown.zones[2].weight, 6.0);
+    assert!((breakdown.base_points - 148.0).abs() < 1e-3);
+    assert!(breakdown.multipliers.is_empty());
+    assert!(breakdown.caps.is_empty());
+    assert!(breakdown.steps.iter().any(|s| s == "Moderate: 18.0 min × 6 points/min = 108.0 points"));
+    assert!(breakdown.steps.iter().any(|s| s.contains("no game was credited")));
+}
+
+#[test]
+fn breakdown_explains_multipliers_caps_and_games() {
+    let mut workout = scored_workout(Utc::now() - Duration::hours(1));
+    // Zones are stored calibrated: 148 base points × 1.15
+    workout.zones = vec![zone("Easy", 10.0, 46.0), zone("Moderate", 18.0, 124.2)];
+    workout.workout_type = WorkoutType::Hiit;
+    workout.scoring_calibration = 1.15;
+    workout.total_points = 255;
+    workout.games = vec![CreditedGame {
+        game_id: Uuid::new_v4(),
+        team_name: "Lions".to_string(),
+        opponent_name: "Tigers".to_string(),
+        score_points: 510.5,
+        comeback_multiplier: Some(1.5),
+        comeback_bonus: 127.6,
+        booster_bonus: 127.9,
+    }];
+
+    let breakdown = explain_score(&workout);
+    assert!((breakdown.base_points - 148.0).abs() < 1e-3);
+    assert_eq!(breakdown.multipliers.len(), 2);
+    assert!((breakdown.multipliers[1].points - 255.3).abs() < 0.01);
+    assert!(breakdown.caps.iter().any(|c| c.contains("calibration is limited")));
+    assert!(breakdown.caps.iter().any(|c| c.contains("rounded down")));
+    assert!(breakdown.caps.iter().any(|c| c.contains("Comeback bonuses are limited")));
+    let game_step = breakdown.steps.iter().find(|s| s.starts_with("Credited")).unwrap();
+    assert!(game_step.contains("Lions vs Tigers") && game_step.contains("comeback") && game_step.contains("booster"));
+
+    workout.games.clear();
+    workout.suspended = true;
+    let breakdown = explain_score(&workout);
+    assert!(breakdown.caps.iter().any(|c| c.contains("suspension")));
+}
+
+#[tokio::test]
+async fn owner_gets_score_breakdown() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+    let other = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout)
+        .await
+        .expect("Upload should succeed");
+    let workout_id = upload["data"]["sync_id"].as_str().expect("Upload returns the workout id").to_string();
+
+    let url = format!("{}/health/workout/{}/score-breakdown", test_app.address, workout_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let breakdown = &body["data"];
+    assert_eq!(breakdown["samples"]["discarded"], 0);
+    assert!(breakdown["zones"].as_array().is_some_and(|zones| !zones.is_empty()));
+    let total: i64 = breakdown["total_points"].as_i64().unwrap();
+    let stored = sqlx::query_scalar!("SELECT total_points_gained FROM workout_data WHERE id = $1", Uuid::parse_str(&workout_id).unwrap())
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(total, stored as i64);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &other.token, None).await;
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,186 @@
+//! Score consistency tests
+//!
+//! Covers the admin score consistency endpoints:
+//! - Access control
+//! - Detecting games whose stored score diverges from their live score events
+//! - Detecting workouts inside a game window that were never credited to the game
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+#[tokio::test]
+async fn score_consistency_requires_admin() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    for path in ["consistency", "consistency/runs"] {
+        let response = make_authenticated_request(
+            &client,
+            reqwest::Method::GET,
+            &format!("{}/admin/{}", test_app.address, path),
+            &user.token,
+            None,
+        ).await;
+
+        assert_eq!(403, response.status().as_u16());
+    }
+}
+
+#[tokio::test]
+async fn score_consistency_flags_score_mismatch() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, None, true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Consistency Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    // A clean season has nothing to report
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/admin/consistency?season_id={}", test_app.address, season_id),
+        &admin.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["issues"].as_array().unwrap().len(), 0);
+
+    // A game of its own, outside the schedule, with a score no live score event accounts for
+    let now = Utc::now();
+    let game_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO games (season_id, home_team_id, away_team_id, week_number, status, game_start_time, game_end_time, away_score)
+        VALUES ($1, $2, $3, 99, 'finished', $4, $5, 17)
+        RETURNING id
+        "#
+    )
+    .bind(season_id)
+    .bind(Uuid::parse_str(&league.team_ids[0]).unwrap())
+    .bind(Uuid::parse_str(&league.team_ids[1]).unwrap())
+    .bind(now - Duration::days(2))
+    .bind(now - Duration::days(1))
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to insert game with a diverging score");
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/admin/consistency?season_id={}", test_app.address, season_id),
+        &admin.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let issues = body["data"]["issues"].as_array().unwrap();
+    assert!(issues.iter().all(|i| i["game_id"] == game_id.to_string().as_str()), "Only the diverging game is flagged");
+
+    let issue = issues.iter()
+        .find(|i| i["game_id"] == game_id.to_string().as_str())
+        .expect("Diverging game should be flagged");
+    assert_eq!(issue["issue_type"], "score_mismatch");
+    assert_eq!(issue["stored_away_score"], 17);
+    assert_eq!(issue["event_away_score"], 0);
+
+    sqlx::query("DELETE FROM games WHERE id = $1")
+        .bind(game_id)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to remove the diverging game");
+}
+
+#[tokio::test]
+async fn score_consistency_flags_uncredited_workout() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let player = create_test_user_and_login(&test_app.address).await;
+    let player_id = parse_user_id_from_jwt_token(&player.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![player_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Re-upload Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+    let player_team_id = Uuid::parse_str(&league.team_ids[0]).unwrap();
+
+    // Game that ended an hour ago, with the player on one of the teams since before it started
+    let now = Utc::now();
+    let game_id: Uuid = sqlx::query_scalar(
+        r#"
+        UPDATE games
+        SET status = 'finished', game_start_time = $2, game_end_time = $3
+        WHERE id = (
+            SELECT id FROM games
+            WHERE season_id = $1 AND (home_team_id = $4 OR away_team_id = $4)
+            ORDER BY week_number LIMIT 1
+        )
+        RETURNING id
+        "#
+    )
+    .bind(season_id)
+    .bind(now - Duration::hours(4))
+    .bind(now - Duration::hours(1))
+    .bind(player_team_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to prepare game");
+
+    sqlx::query("UPDATE team_members SET joined_at = $2 WHERE user_id = $1")
+        .bind(player_id)
+        .bind(now - Duration::days(2))
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to backdate membership");
+
+    // Workout inside the game window that was uploaded after the game ended
+    let workout_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                  workout_start, workout_end, stamina_gained, strength_gained)
+        VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 6.5, 2.0)
+        RETURNING id
+        "#
+    )
+    .bind(player_id)
+    .bind(Uuid::new_v4().to_string())
+    .bind(now - Duration::hours(3))
+    .bind(now - Duration::hours(2))
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to insert workout");
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/admin/consistency?season_id={}", test_app.address, season_id),
+        &admin.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let issue = body["data"]["issues"].as_array().unwrap().iter()
+        .find(|i| i["issue_type"] == "uncredited_workout")
+        .expect("Uncredited workout should be flagged");
+    assert_eq!(issue["game_id"], game_id.to_string().as_str());
+    assert_eq!(issue["workout_data_id"], workout_id.to_string().as_str());
+    assert_eq!(issue["user_id"], player_id.to_string().as_str());
+}
@@ -0,0 +1,107 @@
+//! Scoring calibration tests
+//!
+//! - Effort ratings move the personal factor only after enough ratings, and never past the bounds
+//! - Calibration weights the totals and every zone of a scored workout
+//! - The health profile shows the current factor, 1.0 for users who were never calibrated
+
+use reqwest::Client;
+
+use riina_backend::models::workout_data::{WorkoutStats, ZoneBreakdown};
+use riina_backend::workout::effort_calibration::{
+    apply_calibration, calibration_factor, mean_effort_gap, RatedWorkout, MAX_ADJUSTMENT, MIN_RATED_WORKOUTS,
+};
+
+mod common;
+use common::utils::{create_test_user_with_health_profile, make_authenticated_request, spawn_app};
+
+fn rated(effort_rating: i16, points_per_minute: f32, count: usize) -> Vec<RatedWorkout> {
+    vec![RatedWorkout { effort_rating, points_per_minute }; count]
+}
+
+#[test]
+fn too_few_ratings_leave_scoring_unchanged() {
+    assert_eq!(calibration_factor(&[]), 1.0);
+    assert_eq!(calibration_factor(&rated(10, 1.0, MIN_RATED_WORKOUTS - 1)), 1.0);
+    assert_eq!(mean_effort_gap(&[]), None);
+}
+
+#[test]
+fn ratings_move_the_factor_within_bounds() {
+    // 4 points per minute implies an effort of 5
+    assert!((mean_effort_gap(&rated(7, 4.0, 3)).unwrap() - 2.0).abs() < 1e-4);
+    assert!((calibration_factor(&rated(5, 4.0, 20)) - 1.0).abs() < 1e-4);
+
+    let harder = calibration_factor(&rated(8, 4.0, 10));
+    assert!(harder > 1.0 && harder <= 1.0 + MAX_ADJUSTMENT);
+    let easier = calibration_factor(&rated(2, 4.0, 10));
+    assert!(easier < 1.0 && easier >= 1.0 - MAX_ADJUSTMENT);
+
+    // More ratings, more of the adjustment
+    assert!(calibration_factor(&rated(8, 4.0, 40)) > harder);
+    assert!(calibration_factor(&rated(10, 0.0, 1000)) <= 1.0 + MAX_ADJUSTMENT);
+    assert!(calibration_factor(&rated(0, 50.0, 1000)) >= 1.0 - MAX_ADJUSTMENT);
+}
+
+#[test]
+fn calibration_weights_totals_and_zones() {
+    let mut zone = ZoneBreakdown::new("Zone4".to_string());
+    zone.stamina_gained = 10.0;
+    zone.strength_gained = 4.0;
+    let mut stats = WorkoutStats::new();
+    stats.changes.stamina_change = 10.0;
+    stats.changes.strength_change = 4.0;
+    stats.zone_breakdown = Some(vec![zone]);
+
+    apply_calibration(&mut stats, 1.1);
+    assert!((stats.changes.stamina_change - 11.0).abs() < 1e-4);
+    assert!((stats.changes.strength_change - 4.4).abs() < 1e-4);
+    let zone = &stats.zone_breakdown.as_ref().unwrap()[0];
+    assert!((zone.stamina_gained - 11.0).abs() < 1e-4);
+    assert!((zone.strength_gained - 4.4).abs() < 1e-4);
+
+    // Out of range factors are clamped
+    let mut stats = WorkoutStats::new();
+    stats.changes.stamina_change = 10.0;
+    apply_calibration(&mut stats, 3.0);
+    assert!((stats.changes.stamina_change - 10.0 * (1.0 + MAX_ADJUSTMENT)).abs() < 1e-4);
+}
+
+#[tokio::test]
+async fn health_profile_shows_calibration() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app, &client).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/profile/health_profile", test_app.address),
+        &user.token,
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["scoring_calibration"], 1.0);
+    assert_eq!(body["data"]["calibration_rated_workouts"], 0);
+
+    sqlx::query!(
+        "INSERT INTO user_scoring_calibrations (user_id, factor, rated_workouts, mean_effort_gap) VALUES ($1, 1.1, 12, 1.5)",
+        user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/profile/health_profile", test_app.address),
+        &user.token,
+        None,
+    )
+    .await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!((body["data"]["scoring_calibration"].as_f64().unwrap() - 1.1).abs() < 1e-4);
+    assert_eq!(body["data"]["calibration_rated_workouts"], 12);
+}
@@ -0,0 +1,177 @@
+//! Season cup tests
+//!
+//! - Brackets seed the top teams apart and give them the byes of an uneven draw
+//! - Winners advance by slot; draws go to the higher seed, missing results hold the round
+//! - Cup games are played and evaluated like league games without touching the standings,
+//!   each evaluated round draws the next one until the final crowns the winner
+//! - The season calendar lists league and cup games together
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use secrecy::ExposeSecret;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use riina_backend::league::cup_bracket::{
+    advancing, bracket_order, first_round_entrants, round_count, round_pairings, CupPairing,
+};
+use riina_backend::models::cup::CreateCupRequest;
+use riina_backend::services::GameEvaluationService;
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league_season, create_league_with_teams};
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn teams(count: usize) -> Vec<Uuid> {
+    (0..count).map(|_| Uuid::new_v4()).collect()
+}
+
+fn seeds(teams: &[Uuid]) -> HashMap<Uuid, i32> {
+    teams.iter().enumerate().map(|(index, team_id)| (*team_id, index as i32 + 1)).collect()
+}
+
+#[test]
+fn top_seeds_meet_late_and_get_the_byes() {
+    assert_eq!(bracket_order(1), vec![1]);
+    assert_eq!(bracket_order(4), vec![1, 4, 2, 3]);
+    assert_eq!(bracket_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+    assert_eq!(round_count(2), 1);
+    assert_eq!(round_count(5), 3);
+
+    let seeded = teams(5);
+    let entrants = first_round_entrants(&seeded);
+    assert_eq!(entrants.len(), 8);
+    assert_eq!(entrants.iter().filter(|entrant| entrant.is_none()).count(), 3);
+    // Seeds 1, 2 and 3 sit out the first round, 4 plays 5
+    assert_eq!(round_pairings(&entrants), vec![CupPairing { slot: 1, home_team_id: seeded[3], away_team_id: seeded[4] }]);
+}
+
+#[test]
+fn winners_advance_by_slot() {
+    let seeded = teams(4);
+    let seeds = seeds(&seeded);
+    let entrants = first_round_entrants(&seeded);
+    let pairings = round_pairings(&entrants);
+    assert_eq!(pairings.len(), 2);
+
+    // One game still open
+    let mut winners = HashMap::from([(0, Some(seeded[3]))]);
+    assert_eq!(advancing(&entrants, &winners, &seeds), None);
+
+    // A draw goes to the higher seed
+    winners.insert(1, None);
+    let finalists = advancing(&entrants, &winners, &seeds).unwrap();
+    assert_eq!(finalists, vec![Some(seeded[3]), Some(seeded[1])]);
+
+    let champion = advancing(&finalists, &HashMap::from([(0, Some(seeded[1]))]), &seeds).unwrap();
+    assert_eq!(champion, vec![Some(seeded[1])]);
+}
+
+#[test]
+fn cups_are_validated() {
+    let request = |name: &str, round_interval_hours, team_ids: Option<Vec<Uuid>>| CreateCupRequest {
+        name: name.to_string(),
+        start_date: Utc::now(),
+        round_interval_hours,
+        team_ids,
+    };
+    assert!(request("Winter Cup", None, None).validate().is_ok());
+    assert!(request(" ", None, None).validate().is_err());
+    assert!(request("Winter Cup", Some(0), None).validate().is_err());
+    assert!(request("Winter Cup", None, Some(teams(1))).validate().is_err());
+    let team_id = Uuid::new_v4();
+    assert!(request("Winter Cup", None, Some(vec![team_id, team_id])).validate().is_err());
+}
+
+#[tokio::test]
+async fn cup_runs_alongside_the_league() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let pool = &test_app.db_pool;
+    let admin = create_admin_user_and_login(&test_app.address, pool).await;
+    let configuration = get_config().expect("Failed to read configuration.");
+    let redis_client = Arc::new(redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret()).unwrap());
+    let evaluator = GameEvaluationService::new(pool.clone(), redis_client);
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 3, 3, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(&test_app.address, &admin.token, &league.league_id, "Cup Season", &start_date).await;
+    let seeded: Vec<Uuid> = league.team_ids.iter().map(|id| Uuid::parse_str(id).unwrap()).collect();
+
+    let cups_url = format!("{}/admin/seasons/{}/cups", test_app.address, season_id);
+    let response = make_authenticated_request(
+        &client, Method::POST, &cups_url, &admin.token,
+        Some(json!({ "name": "Winter Cup", "start_date": Utc::now() + Duration::days(2), "team_ids": [seeded[0], Uuid::new_v4()] })),
+    ).await;
+    assert_eq!(response.status(), 400);
+
+    let response = make_authenticated_request(
+        &client, Method::POST, &cups_url, &admin.token,
+        Some(json!({ "name": "Winter Cup", "start_date": Utc::now() + Duration::days(2), "team_ids": seeded })),
+    ).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let cup_id = Uuid::parse_str(body["data"]["id"].as_str().unwrap()).unwrap();
+    let cup_url = format!("{}/league/cups/{}", test_app.address, cup_id);
+
+    // Seed 1 has a bye, 2 and 3 play
+    let bracket: serde_json::Value = make_authenticated_request(&client, Method::GET, &cup_url, &admin.token, None)
+        .await.json().await.unwrap();
+    assert_eq!(bracket["data"]["total_rounds"], 2);
+    let first_round = &bracket["data"]["rounds"][0]["games"];
+    assert_eq!(first_round.as_array().unwrap().len(), 1);
+    assert_eq!(first_round[0]["home_team_id"], seeded[1].to_string());
+    let semi_final = Uuid::parse_str(first_round[0]["game_id"].as_str().unwrap()).unwrap();
+
+    let evaluator = &evaluator;
+    let finish = |game_id: Uuid, home_score: i32, away_score: i32| async move {
+        sqlx::query("UPDATE games SET status = 'finished', home_score = $2, away_score = $3 WHERE id = $1")
+            .bind(game_id)
+            .bind(home_score)
+            .bind(away_score)
+            .execute(pool)
+            .await
+            .unwrap();
+        evaluator.evaluate_finished_live_games(&vec![game_id]).await.unwrap();
+    };
+
+    // A drawn semi final goes to seed 2 and draws the final against seed 1
+    finish(semi_final, 10, 10).await;
+    let games_played: i64 = sqlx::query_scalar("SELECT COALESCE(SUM(games_played), 0)::BIGINT FROM league_standings WHERE season_id = $1")
+        .bind(Uuid::parse_str(&season_id).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(games_played, 0, "Cup games don't count towards the standings");
+
+    let bracket: serde_json::Value = make_authenticated_request(&client, Method::GET, &cup_url, &admin.token, None)
+        .await.json().await.unwrap();
+    let final_game = &bracket["data"]["rounds"][1]["games"][0];
+    assert_eq!(final_game["home_team_id"], seeded[0].to_string());
+    assert_eq!(final_game["away_team_id"], seeded[1].to_string());
+    let final_id = Uuid::parse_str(final_game["game_id"].as_str().unwrap()).unwrap();
+
+    finish(final_id, 20, 35).await;
+    let bracket: serde_json::Value = make_authenticated_request(&client, Method::GET, &cup_url, &admin.token, None)
+        .await.json().await.unwrap();
+    assert_eq!(bracket["data"]["cup"]["status"], "finished");
+    assert_eq!(bracket["data"]["cup"]["winner_team_id"], seeded[1].to_string());
+
+    // League schedule without cup games, calendar with both
+    let calendar: serde_json::Value = make_authenticated_request(
+        &client, Method::GET, &format!("{}/league/seasons/{}/calendar", test_app.address, season_id), &admin.token, None,
+    ).await.json().await.unwrap();
+    let calendar = calendar["data"].as_array().unwrap();
+    assert_eq!(calendar.iter().filter(|game| game["competition"] == "cup").count(), 2);
+    let league_games = calendar.iter().filter(|game| game["competition"] == "league").count();
+    assert!(league_games > 0);
+    let schedule: serde_json::Value = make_authenticated_request(
+        &client, Method::GET, &format!("{}/league/seasons/{}/schedule", test_app.address, season_id), &admin.token, None,
+    ).await.json().await.unwrap();
+    assert_eq!(schedule["data"]["games"].as_array().unwrap().len(), league_games);
+}
@@ -0,0 +1,206 @@
+//! Season recap tests
+//!
+//! Covers `/league/seasons/{id}/recap`:
+//! - Recaps are written once the last game of a season is evaluated
+//! - League and team recaps carry the biggest upset, longest streak and top scorer arc
+//! - Requested cards are rendered by the image-render job and can be downloaded
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use secrecy::ExposeSecret;
+use uuid::Uuid;
+
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use riina_backend::services::{GameEvaluationService, MinIOService, SeasonRecapService};
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+#[tokio::test]
+async fn season_recap_is_written_when_the_season_ends() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let configuration = get_config().expect("Failed to read configuration.");
+    let redis_client = Arc::new(redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret()).unwrap());
+    let evaluator = GameEvaluationService::new(test_app.db_pool.clone(), redis_client);
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 4, 4, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Recap Season", &start_date,
+    ).await;
+    let season_uuid = Uuid::parse_str(&season_id).unwrap();
+    let teams: Vec<Uuid> = league.team_ids.iter().map(|id| Uuid::parse_str(id).unwrap()).collect();
+    let (a, b, c, d) = (teams[0], teams[1], teams[2], teams[3]);
+
+    // A wins everything but loses to D, D draws its other games: A 6, D 5, B 4, C 1
+    let winner = |x: Uuid, y: Uuid| -> Option<Uuid> {
+        let pairing = |p: Uuid, q: Uuid| (x == p && y == q) || (x == q && y == p);
+        if pairing(a, d) {
+            Some(d)
+        } else if x == d || y == d {
+            None
+        } else if x == a || y == a {
+            Some(a)
+        } else {
+            Some(b)
+        }
+    };
+
+    let games = sqlx::query!(
+        "SELECT id, week_number, home_team_id, away_team_id FROM games WHERE season_id = $1 ORDER BY week_number, id",
+        season_uuid
+    )
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(games.len(), 6, "Four teams play a single round robin");
+
+    let mut members: HashMap<Uuid, (Uuid, String)> = HashMap::new();
+    for team in &teams {
+        let member = sqlx::query!(
+            "SELECT u.id, u.username FROM team_members tm JOIN users u ON u.id = tm.user_id WHERE tm.team_id = $1 LIMIT 1",
+            team
+        )
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+        members.insert(*team, (member.id, member.username));
+    }
+
+    for game in &games {
+        let (home_score, away_score) = match winner(game.home_team_id, game.away_team_id) {
+            Some(w) if w == game.home_team_id => (20, 10),
+            Some(_) => (10, 20),
+            None => (15, 15),
+        };
+        sqlx::query(
+            r#"
+            UPDATE games
+            SET status = 'finished', home_score = $2, away_score = $3,
+                game_start_time = NOW() - make_interval(days => 30 - $4), game_end_time = NOW() - make_interval(days => 29 - $4)
+            WHERE id = $1
+            "#,
+        )
+        .bind(game.id)
+        .bind(home_score)
+        .bind(away_score)
+        .bind(game.week_number)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+        // A's player scores 10 every week, D's player 25 in the upset
+        for (team, side) in [(game.home_team_id, "home"), (game.away_team_id, "away")] {
+            let points = match team {
+                t if t == a => 10.0_f32,
+                t if t == d && winner(game.home_team_id, game.away_team_id) == Some(d) => 25.0,
+                _ => continue,
+            };
+            let (user_id, username) = &members[&team];
+            sqlx::query(
+                r#"
+                INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side,
+                                               score_points, power_contribution, description)
+                VALUES ($1, $2, $3, $4, $5, $6, 0, 'Recap test event')
+                "#,
+            )
+            .bind(game.id)
+            .bind(user_id)
+            .bind(username)
+            .bind(team)
+            .bind(side)
+            .bind(points)
+            .execute(&test_app.db_pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    let recap_url = format!("{}/league/seasons/{}/recap", test_app.address, season_id);
+
+    // Nothing until the last game is in
+    let game_ids: Vec<Uuid> = games.iter().map(|g| g.id).collect();
+    evaluator.evaluate_finished_live_games(&game_ids[..5].to_vec()).await.unwrap();
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &recap_url, &admin.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    evaluator.evaluate_finished_live_games(&game_ids[5..].to_vec()).await.unwrap();
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &recap_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let league_recap = &body["data"]["league"]["content"];
+    assert_eq!(league_recap["games_played"], 6);
+    assert_eq!(league_recap["champion"]["team_id"], a.to_string());
+    assert_eq!(league_recap["biggest_upset"]["winner_team_id"], d.to_string());
+    assert_eq!(league_recap["biggest_upset"]["winner_position"], 2);
+    assert_eq!(league_recap["biggest_upset"]["loser_position"], 1);
+    assert_eq!(league_recap["top_scorer"]["user_id"], members[&a].0.to_string());
+    assert_eq!(league_recap["top_scorer"]["total_points"], 30.0);
+    let arc: Vec<f64> = league_recap["top_scorer"]["weeks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["cumulative_points"].as_f64().unwrap())
+        .collect();
+    assert_eq!(arc, vec![10.0, 20.0, 30.0]);
+
+    // A's wins are a streak unless the loss to D splits them
+    let a_loses_in_week_2 = games.iter().any(|g| {
+        g.week_number == 2 && [g.home_team_id, g.away_team_id].contains(&a) && [g.home_team_id, g.away_team_id].contains(&d)
+    });
+    assert_eq!(league_recap["longest_streak"]["team_id"], a.to_string());
+    assert_eq!(league_recap["longest_streak"]["length"], if a_loses_in_week_2 { 1 } else { 2 });
+
+    // Team recaps follow the standings
+    let team_recaps = body["data"]["teams"].as_array().unwrap();
+    let order: Vec<String> = team_recaps.iter().map(|r| r["team_id"].as_str().unwrap().to_string()).collect();
+    assert_eq!(order, vec![a.to_string(), d.to_string(), b.to_string(), c.to_string()]);
+    let d_recap = &team_recaps[1]["content"];
+    assert_eq!(d_recap["standing"]["position"], 2);
+    assert_eq!(d_recap["standing"]["points"], 5);
+    assert_eq!(d_recap["biggest_upset"]["loser_team_id"], a.to_string());
+    assert_eq!(d_recap["top_scorer"]["total_points"], 25.0);
+    assert!(team_recaps[3]["content"]["biggest_upset"].is_null(), "C pulled off no upset");
+    assert!(team_recaps[3]["content"]["longest_streak"].is_null(), "C never won");
+
+    // Cards are rendered on request
+    let card_url = format!("{}/card", recap_url);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &card_url, &admin.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/cards", recap_url), &admin.token, None,
+    ).await;
+    assert_eq!(202, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["cards_queued"], 5);
+
+    let minio_service = MinIOService::new(&configuration.minio).await.expect("Failed to create MinIO service");
+    let rendered = SeasonRecapService::new(test_app.db_pool.clone())
+        .render_pending_cards(&minio_service)
+        .await
+        .unwrap();
+    assert!(rendered >= 5);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &card_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(response.headers()["content-type"], "image/svg+xml");
+    let svg = response.text().await.unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("Recap Season"));
+    assert!(svg.contains("Biggest upset"));
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}?team_id={}", card_url, d), &admin.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    assert!(response.text().await.unwrap().contains("Final position"));
+}
@@ -971,6 +971,16 @@ async fn test_standings_tiebreaker_three_way_tie() {
 
     println!("✅ Submitted all game results");
 
+    // Tie-breakers only see evaluated games, so recalculate once all games are evaluated
+    let recalculate_response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/admin/seasons/{}/recalculate-standings", &app.address, season_id),
+        &admin_user.token,
+        None,
+    ).await;
+    assert_eq!(recalculate_response.status(), 200);
+
     // Step 8: Get the final standings
     let standings_response = make_authenticated_request(
         &client,
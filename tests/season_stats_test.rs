@@ -0,0 +1,137 @@
+//! Season stats tests
+//!
+//! Covers `/league/seasons/{id}/stats`:
+//! - evaluated games add their workouts, zone minutes and scores to the season's stats
+//! - the biggest blowout and closest game are tracked as games come in
+//! - a game is only counted once
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use secrecy::ExposeSecret;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::config::redis::RedisSettings;
+use riina_backend::config::settings::get_config;
+use riina_backend::services::{GameEvaluationService, SeasonStatsService};
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+#[tokio::test]
+async fn season_stats_add_up_evaluated_games() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let configuration = get_config().expect("Failed to read configuration.");
+    let redis_client = Arc::new(redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret()).unwrap());
+    let evaluator = GameEvaluationService::new(test_app.db_pool.clone(), redis_client);
+
+    let league = create_league_with_teams(&test_app.address, &admin.token, 4, 4, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Stats Season", &start_date,
+    ).await;
+    let season_uuid = Uuid::parse_str(&season_id).unwrap();
+    let stats_url = format!("{}/league/seasons/{}/stats", test_app.address, season_id);
+
+    // Nothing evaluated yet
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &stats_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["games_counted"], 0);
+    assert!(body["data"]["biggest_blowout"].is_null());
+
+    let week_one: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT id, home_team_id FROM games WHERE season_id = $1 AND week_number = 1 ORDER BY id"
+    )
+    .bind(season_uuid)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(week_one.len(), 2);
+    let (blowout, blowout_home) = week_one[0];
+    let (close_game, _) = week_one[1];
+
+    for (game_id, home_score, away_score) in [(blowout, 100, 40), (close_game, 55, 50)] {
+        sqlx::query(
+            "UPDATE games SET status = 'finished', home_score = $2, away_score = $3,
+                 game_start_time = NOW() - INTERVAL '2 days', game_end_time = NOW() - INTERVAL '1 day'
+             WHERE id = $1"
+        )
+        .bind(game_id)
+        .bind(home_score)
+        .bind(away_score)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    // One workout with 30 zone minutes credited to the blowout
+    let (user_id, username): (Uuid, String) = sqlx::query_as(
+        "SELECT u.id, u.username FROM team_members tm JOIN users u ON u.id = tm.user_id WHERE tm.team_id = $1 LIMIT 1"
+    )
+    .bind(blowout_home)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    let zones = json!([{ "zone": "Zone 2", "minutes": 20.0 }, { "zone": "Zone 4", "minutes": 10.0 }]);
+    let workout_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid, workout_start, workout_end, heart_rate_zones)
+         VALUES ($1, 'test-device', '[]', $2, NOW() - INTERVAL '2 days', NOW() - INTERVAL '2 days' + INTERVAL '30 minutes', $3)
+         RETURNING id"
+    )
+    .bind(user_id)
+    .bind(Uuid::new_v4().to_string())
+    .bind(zones)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    for _ in 0..2 {
+        sqlx::query(
+            "INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side, score_points, power_contribution, workout_data_id, description)
+             VALUES ($1, $2, $3, $4, 'home', 50, 0, $5, 'Stats test event')"
+        )
+        .bind(blowout)
+        .bind(user_id)
+        .bind(&username)
+        .bind(blowout_home)
+        .bind(workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    evaluator.evaluate_finished_live_games(&vec![blowout, close_game]).await.unwrap();
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &stats_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let stats = &body["data"];
+    assert_eq!(stats["games_counted"], 2);
+    assert_eq!(stats["total_workouts"], 1, "A workout is counted once however many events it scored");
+    assert_eq!(stats["total_zone_minutes"], 30.0);
+    assert_eq!(stats["average_game_score"], 61.25);
+    assert_eq!(stats["biggest_blowout"]["game_id"], blowout.to_string());
+    assert_eq!(stats["biggest_blowout"]["margin"], 60);
+    assert_eq!(stats["closest_game"]["game_id"], close_game.to_string());
+    assert_eq!(stats["closest_game"]["margin"], 5);
+
+    // Evaluated games are only counted once
+    let recorded = SeasonStatsService::new(test_app.db_pool.clone()).record_game(blowout).await.unwrap();
+    assert!(!recorded);
+}
+
+#[tokio::test]
+async fn season_stats_of_an_unknown_season_are_not_found() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+
+    let url = format!("{}/league/seasons/{}/stats", test_app.address, Uuid::new_v4());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &admin.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+}
@@ -0,0 +1,162 @@
+//! Session (logged in device) tests
+//!
+//! - Device ids and user agents from request headers are trimmed and cut short
+//! - Every login starts a session that the user can list, with the current one marked
+//! - Logging out a session stops its access and refresh tokens before they expire
+//! - Tokens from before sessions existed get a session when they are refreshed
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::middleware::auth::Claims;
+use riina_backend::models::auth::SessionDevice;
+use riina_backend::models::user::{UserRole, UserStatus};
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn session_devices_drop_blank_headers() {
+    assert_eq!(SessionDevice::new(Some("  "), None), SessionDevice::default());
+    assert_eq!(
+        SessionDevice::new(Some(" phone-1 "), Some("Riina/2.3 iOS")),
+        SessionDevice { device_id: Some("phone-1".to_string()), user_agent: Some("Riina/2.3 iOS".to_string()) }
+    );
+}
+
+#[test]
+fn session_devices_cut_long_headers_short() {
+    let device = SessionDevice::new(Some(&"d".repeat(500)), Some(&"ü".repeat(1000)));
+    assert_eq!(device.device_id.unwrap().chars().count(), 128);
+    assert_eq!(device.user_agent.unwrap().chars().count(), 512);
+}
+
+async fn login(client: &Client, address: &str, username: &str, device_id: &str) -> serde_json::Value {
+    client
+        .post(format!("{}/login", address))
+        .header("X-Device-Id", device_id)
+        .header("User-Agent", format!("Riina test on {device_id}"))
+        .json(&json!({ "username": username, "password": "password123" }))
+        .send()
+        .await
+        .expect("Failed to execute login request")
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn logged_out_sessions_lose_their_tokens() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let username = format!("sessions_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    client
+        .post(format!("{}/register_user", test_app.address))
+        .json(&json!({ "username": username, "password": "password123", "email": format!("{username}@example.com") }))
+        .send()
+        .await
+        .expect("Failed to register user");
+
+    let phone = login(&client, &test_app.address, &username, "phone").await;
+    let tablet = login(&client, &test_app.address, &username, "tablet").await;
+    let phone_token = phone["token"].as_str().unwrap();
+    let tablet_token = tablet["token"].as_str().unwrap();
+    let sessions_url = format!("{}/auth/sessions", test_app.address);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, phone_token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let sessions = body["data"].as_array().unwrap();
+    assert_eq!(sessions.len(), 2);
+    let current = sessions.iter().find(|session| session["current"] == true).unwrap();
+    assert_eq!(current["device_id"], "phone");
+    assert_eq!(current["user_agent"], "Riina test on phone");
+    let tablet_session = sessions.iter().find(|session| session["device_id"] == "tablet").unwrap();
+
+    // Log out the tablet from the phone
+    let revoke_url = format!("{}/{}", sessions_url, tablet_session["id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &revoke_url, phone_token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &revoke_url, phone_token, None).await;
+    assert_eq!(response.status(), 404);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, tablet_token, None).await;
+    assert_eq!(response.status(), 401, "The tablet's unexpired access token stops working");
+    let response = client
+        .post(format!("{}/refresh", test_app.address))
+        .json(&json!({ "refresh_token": tablet["refresh_token"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // Logging out everywhere else keeps the phone logged in
+    let laptop = login(&client, &test_app.address, &username, "laptop").await;
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &sessions_url, phone_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["revoked"], 1);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, laptop["token"].as_str().unwrap(), None).await;
+    assert_eq!(response.status(), 401);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, phone_token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn tokens_without_a_session_get_one_when_refreshed() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let sessions_url = format!("{}/auth/sessions", test_app.address);
+
+    // Expired token issued before sessions existed
+    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET environment variable must be set");
+    let claims = Claims {
+        sub: user.user_id.to_string(),
+        username: user.username.clone(),
+        role: UserRole::User,
+        status: UserStatus::Active,
+        exp: (Utc::now() - Duration::hours(2)).timestamp() as usize,
+        iss: Some("riina-backend".to_string()),
+        aud: Some("riina-app".to_string()),
+        sid: None,
+        impersonated_by: None,
+    };
+    let old_token = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap();
+
+    let response = client
+        .post(format!("{}/biometric-refresh", test_app.address))
+        .header("X-Device-Id", "old-phone")
+        .json(&json!({ "token": old_token }))
+        .send()
+        .await
+        .expect("Failed to execute biometric refresh request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let refreshed_token = body["token"].as_str().unwrap().to_string();
+
+    // The device is listed like any other and its refreshed token belongs to that session
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, &refreshed_token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let current = body["data"].as_array().unwrap().iter()
+        .find(|session| session["current"] == true)
+        .expect("The refreshed token should have a session");
+    assert_eq!(current["device_id"], "old-phone");
+
+    // So logging it out from another device works
+    let revoke_url = format!("{}/{}", sessions_url, current["id"].as_str().unwrap());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &revoke_url, &user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &sessions_url, &refreshed_token, None).await;
+    assert_eq!(response.status(), 401);
+    let response = client
+        .post(format!("{}/biometric-refresh", test_app.address))
+        .json(&json!({ "token": refreshed_token }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
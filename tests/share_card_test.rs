@@ -0,0 +1,83 @@
+//! Share card tests
+//!
+//! - Cards render as 1200x630 PNGs, with text cut to fit
+//! - Users request cards via `/share/cards`; requesting one again returns the same card
+//! - Only workouts that beat all earlier ones make personal record cards
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+
+use riina_backend::models::share_card::{ShareCardContent, ShareCardLine, ShareCardType};
+use riina_backend::utils::card_image::{fit_text, render_share_card_png, CARD_HEIGHT, CARD_WIDTH};
+
+mod common;
+use common::utils::{make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+#[test]
+fn cards_render_as_png() {
+    let content = ShareCardContent {
+        eyebrow: "MVP badge".to_string(),
+        title: "a_username_far_too_long_to_fit_on_one_card_line".to_string(),
+        lines: vec![ShareCardLine { label: "Game".to_string(), value: "Lions 120 - 98 Tigers".to_string() }],
+    };
+    let png = render_share_card_png(&content).unwrap();
+    let image = image::load_from_memory(&png).unwrap();
+    assert_eq!((image.width(), image.height()), (CARD_WIDTH, CARD_HEIGHT));
+
+    assert_eq!(fit_text("  Lions  ", 10), "Lions");
+    assert_eq!(fit_text("Lions 120 - 98 Tigers", 10), "Lions 1...");
+
+    for card_type in ShareCardType::ALL {
+        assert_eq!(ShareCardType::parse(card_type.as_str()), Some(card_type));
+    }
+}
+
+#[tokio::test]
+async fn personal_record_cards_are_queued_once() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut record = WorkoutData::new(WorkoutIntensity::Hard, Utc::now() - Duration::hours(4), 45);
+    let uploaded = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut record)
+        .await
+        .expect("Upload failed");
+    let record_id = uploaded["data"]["sync_id"].as_str().unwrap().to_string();
+
+    let mut lighter = WorkoutData::new(WorkoutIntensity::Light, Utc::now() - Duration::hours(2), 10);
+    let uploaded = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut lighter)
+        .await
+        .expect("Upload failed");
+    let lighter_id = uploaded["data"]["sync_id"].as_str().unwrap().to_string();
+
+    let cards_url = format!("{}/share/cards", test_app.address);
+    let request = |subject_id: String| {
+        make_authenticated_request(
+            &client, Method::POST, &cards_url, &user.token,
+            Some(json!({ "card_type": "personal_record", "subject_id": subject_id })),
+        )
+    };
+
+    let created = request(record_id.clone()).await;
+    assert_eq!(created.status().as_u16(), 201);
+    let card: serde_json::Value = created.json().await.unwrap();
+    assert_eq!(card["data"]["status"], "pending");
+    assert_eq!(card["data"]["content"]["eyebrow"], "Personal record");
+
+    let again: serde_json::Value = request(record_id).await.json().await.unwrap();
+    assert_eq!(again["data"]["id"], card["data"]["id"]);
+
+    assert_eq!(request(lighter_id).await.status().as_u16(), 409);
+
+    // Images are public, and not there until rendered
+    let image = client
+        .get(format!("{}{}", test_app.address, card["data"]["path"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(image.status().as_u16(), 202);
+}
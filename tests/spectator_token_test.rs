@@ -0,0 +1,263 @@
+//! Guest spectator token tests
+//!
+//! Covers the read-only tokens for following a live game without an account:
+//! - spectator tokens verify as such, but never as user tokens, and user tokens never as spectator tokens
+//! - a token is issued to users for a running game and fetches its live score, but no other game's
+//! - guests can't create tokens, and users only a limited number per hour
+//! - the token is refused by authenticated routes, e.g. uploads
+//! - `/game-ws` opened with a token sends the game's state sync and ignores other commands
+
+use chrono::{Duration, Utc};
+use futures_util::{SinkExt, StreamExt};
+use futures_util::stream::SplitStream;
+use reqwest::Client;
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::protocol::Message;
+use uuid::Uuid;
+
+use riina_backend::config::jwt::JwtSettings;
+use riina_backend::middleware::auth::Claims;
+use riina_backend::models::user::{UserRole, UserStatus};
+use riina_backend::services::spectator_token_service::{
+    issue_spectator_token, verify_spectator_token, SpectatorClaims, SPECTATOR_TOKENS_PER_HOUR,
+};
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, TestApp};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+fn settings() -> JwtSettings {
+    JwtSettings::new("spectator_test_secret".to_string(), 24, 30)
+}
+
+#[test]
+fn spectator_tokens_are_not_user_tokens() {
+    let settings = settings();
+    let game_id = Uuid::new_v4();
+    let (token, expires_at) = issue_spectator_token(&settings, game_id, Utc::now()).unwrap();
+    assert!(expires_at > Utc::now());
+
+    let claims = verify_spectator_token(&settings, &token).unwrap();
+    assert_eq!(claims.game_id, game_id);
+    assert_eq!(claims.scope, "spectate");
+
+    // What the auth middleware and the user WebSocket do with it
+    assert!(settings.verify::<Claims>(&token).is_err());
+}
+
+#[test]
+fn user_tokens_are_not_spectator_tokens() {
+    let settings = settings();
+    let user_token = settings.sign(&Claims {
+        sub: Uuid::new_v4().to_string(),
+        username: "spectator_user".to_string(),
+        role: UserRole::User,
+        status: UserStatus::Active,
+        exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+        iss: Some(settings.issuer.clone()),
+        aud: Some(settings.audience.clone()),
+        sid: None,
+        impersonated_by: None,
+    }).unwrap();
+    assert!(verify_spectator_token(&settings, &user_token).is_err());
+
+    // Spectator audience, but another scope
+    let other_scope = settings.sign(&SpectatorClaims {
+        sub: "spectator:test".to_string(),
+        game_id: Uuid::new_v4(),
+        scope: "upload".to_string(),
+        exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+        iss: settings.issuer.clone(),
+        aud: settings.spectator_audience(),
+    }).unwrap();
+    assert!(verify_spectator_token(&settings, &other_scope).is_err());
+}
+
+#[test]
+fn expired_spectator_tokens_are_refused() {
+    let settings = settings();
+    let (token, _) = issue_spectator_token(&settings, Uuid::new_v4(), Utc::now() - Duration::hours(3)).unwrap();
+    assert!(verify_spectator_token(&settings, &token).is_err());
+}
+
+/// A season with its first game running, returning that game and another one of the season
+async fn setup_live_game(test_app: &TestApp) -> (Uuid, Uuid) {
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let league = create_league_with_teams(&test_app.address, &admin.token, 2, 2, None, true, None, None).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Spectator Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let game_ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM games WHERE season_id = $1 ORDER BY week_number")
+        .bind(season_id)
+        .fetch_all(&test_app.db_pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE games SET status = 'in_progress', home_score = 30, away_score = 10,
+             game_start_time = NOW() - INTERVAL '1 hour', game_end_time = NOW() + INTERVAL '1 hour'
+         WHERE id = $1"
+    )
+    .bind(game_ids[0])
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    (game_ids[0], game_ids[1])
+}
+
+async fn request_spectator_token(client: &Client, test_app: &TestApp, user_token: &str, game_id: Uuid) -> reqwest::Response {
+    make_authenticated_request(
+        client, reqwest::Method::POST,
+        &format!("{}/league/games/{}/spectator-token", test_app.address, game_id), user_token, None,
+    ).await
+}
+
+#[tokio::test]
+async fn spectator_token_fetches_only_its_game() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (game_id, other_game_id) = setup_live_game(&test_app).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let response = request_spectator_token(&client, &test_app, &user.token, game_id).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let live = client
+        .get(format!("{}/public/games/{}/live", test_app.address, game_id))
+        .query(&[("token", &token)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, live.status().as_u16());
+    let live: serde_json::Value = live.json().await.unwrap();
+    assert_eq!(live["data"]["event_type"], "state_sync");
+    assert_eq!(live["data"]["games"][0]["game_id"], game_id.to_string());
+    assert_eq!(live["data"]["games"][0]["home_score"], 30);
+
+    let other = client
+        .get(format!("{}/public/games/{}/live", test_app.address, other_game_id))
+        .query(&[("token", &token)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(403, other.status().as_u16());
+
+    // Games that aren't running or upcoming can't be spectated, unknown ones aren't found
+    sqlx::query("UPDATE games SET status = 'finished' WHERE id = $1")
+        .bind(other_game_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(400, request_spectator_token(&client, &test_app, &user.token, other_game_id).await.status().as_u16());
+    assert_eq!(404, request_spectator_token(&client, &test_app, &user.token, Uuid::new_v4()).await.status().as_u16());
+}
+
+#[tokio::test]
+async fn spectator_token_cannot_upload_or_post() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (game_id, _) = setup_live_game(&test_app).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let response = request_spectator_token(&client, &test_app, &user.token, game_id).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let upload = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{}/health/upload_health", test_app.address), &token,
+        Some(json!({ "device_id": "spectator", "timestamp": Utc::now() })),
+    ).await;
+    assert_eq!(401, upload.status().as_u16());
+
+    let profile = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/profile/user", test_app.address), &token, None,
+    ).await;
+    assert_eq!(401, profile.status().as_u16());
+
+    // User tokens don't open the public live score
+    let live = client
+        .get(format!("{}/public/games/{}/live", test_app.address, game_id))
+        .query(&[("token", &user.token)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(401, live.status().as_u16());
+}
+
+#[tokio::test]
+async fn spectator_tokens_are_created_by_users_within_a_limit() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (game_id, _) = setup_live_game(&test_app).await;
+
+    let guest = client
+        .post(format!("{}/league/games/{}/spectator-token", test_app.address, game_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(401, guest.status().as_u16());
+
+    let user = create_test_user_and_login(&test_app.address).await;
+    for _ in 0..SPECTATOR_TOKENS_PER_HOUR {
+        assert_eq!(200, request_spectator_token(&client, &test_app, &user.token, game_id).await.status().as_u16());
+    }
+    assert_eq!(429, request_spectator_token(&client, &test_app, &user.token, game_id).await.status().as_u16());
+
+    // The limit is per user
+    let other_user = create_test_user_and_login(&test_app.address).await;
+    assert_eq!(200, request_spectator_token(&client, &test_app, &other_user.token, game_id).await.status().as_u16());
+}
+
+async fn next_event(read: &mut SplitStream<WsStream>, event_type: &str) -> serde_json::Value {
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while let Some(Ok(message)) = read.next().await {
+            if let Message::Text(text) = message {
+                let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if event["event_type"] == event_type {
+                    return event;
+                }
+            }
+        }
+        panic!("WebSocket closed before a {} event arrived", event_type);
+    })
+    .await
+    .unwrap_or_else(|_| panic!("No {} event received", event_type))
+}
+
+#[tokio::test]
+async fn spectator_websocket_follows_its_game() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let (game_id, _) = setup_live_game(&test_app).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let response = request_spectator_token(&client, &test_app, &user.token, game_id).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let token = body["data"]["token"].as_str().unwrap().to_string();
+
+    let ws_url = format!("{}/game-ws?token={}", test_app.address.replace("http", "ws"), token);
+    let (ws_stream, _) = connect_async(ws_url).await.expect("Failed to connect spectator");
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribed = next_event(&mut read, "state_sync").await;
+    assert_eq!(subscribed["event_type"], "state_sync");
+    assert_eq!(subscribed["reason"], "subscribed");
+    assert_eq!(subscribed["games"][0]["game_id"], game_id.to_string());
+
+    // Commands of players are ignored, state syncs answered
+    write.send(Message::Text(json!({ "type": "spectate_season", "season_id": Uuid::new_v4() }).to_string())).await.unwrap();
+    write.send(Message::Text(json!({ "type": "state_sync" }).to_string())).await.unwrap();
+    let requested = next_event(&mut read, "state_sync").await;
+    assert_eq!(requested["event_type"], "state_sync");
+    assert_eq!(requested["reason"], "requested");
+    assert_eq!(requested["games"].as_array().unwrap().len(), 1);
+}
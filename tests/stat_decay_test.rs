@@ -0,0 +1,60 @@
+//! Weekly stat decay tests
+//!
+//! - Stats lose a share each week but never drop below their floor
+//! - The job decays a user at most once per week and the profile shows the history
+
+use chrono::NaiveDate;
+use reqwest::Client;
+
+use riina_backend::config::stat_decay::StatDecaySettings;
+use riina_backend::game::stat_decay::{decay_week_start, decayed_stat};
+use riina_backend::services::StatDecayService;
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn stats_decay_toward_their_floor() {
+    assert_eq!(decayed_stat(100.0, 5.0, 10.0), 95.0);
+    assert_eq!(decayed_stat(10.4, 5.0, 10.0), 10.0, "Never below the floor");
+    assert_eq!(decayed_stat(8.0, 5.0, 10.0), 8.0, "Stats below the floor are left alone");
+    assert_eq!(decayed_stat(100.0, 0.0, 10.0), 100.0);
+}
+
+#[test]
+fn decay_weeks_start_on_monday() {
+    let monday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+    assert_eq!(decay_week_start(monday), monday);
+    assert_eq!(decay_week_start(NaiveDate::from_ymd_opt(2026, 3, 15).unwrap()), monday);
+}
+
+#[tokio::test]
+async fn weekly_decay_runs_once_and_shows_on_the_profile() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    sqlx::query("UPDATE user_avatars SET stamina = 200, strength = 40 WHERE user_id = $1")
+        .bind(user.user_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    // A week far in the future, so other tests' users are decayed that week at most once too
+    let today = NaiveDate::from_ymd_opt(2099, 6, 17).unwrap();
+    let service = StatDecayService::new(test_app.db_pool.clone(), StatDecaySettings::default());
+    assert!(service.apply_weekly_decay(today).await.unwrap() >= 1);
+    service.apply_weekly_decay(today).await.unwrap();
+
+    let (stamina, strength): (f32, f32) = sqlx::query_as("SELECT stamina, strength FROM user_avatars WHERE user_id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!((stamina, strength), (190.0, 38.0), "Decayed once in the week");
+
+    let url = format!("{}/profile/user", test_app.address);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["stat_decay"][0]["stamina_before"], 200.0);
+    assert_eq!(body["data"]["stat_decay"][0]["week_start"], "2099-06-15");
+}
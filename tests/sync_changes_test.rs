@@ -0,0 +1,210 @@
+//! Delta sync tests
+//!
+//! Covers `/sync/changes`:
+//! - Without `since` a full snapshot of the user's workouts, games, standings and notifications
+//! - With `since` only what changed afterwards, plus tombstones for deletions
+//! - The cursor overlaps the previous sync, so writes committed during it aren't missed
+//! - A `since` older than the retention window falls back to a full snapshot
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+async fn get_changes(client: &Client, address: &str, token: &str, since: Option<&str>) -> serde_json::Value {
+    let url = match since {
+        Some(since) => format!("{}/sync/changes?since={}", address, urlencode(since)),
+        None => format!("{}/sync/changes", address),
+    };
+    let response = make_authenticated_request(client, reqwest::Method::GET, &url, token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+fn urlencode(value: &str) -> String {
+    value.replace('+', "%2B").replace(':', "%3A")
+}
+
+fn ids(data: &serde_json::Value, key: &str) -> Vec<String> {
+    data[key].as_array().unwrap().iter().map(|v| v["id"].as_str().unwrap().to_string()).collect()
+}
+
+#[tokio::test]
+async fn sync_changes_returns_snapshot_then_deltas_and_tombstones() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+    let rival = create_test_user_and_login(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&user.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![user_id, parse_user_id_from_jwt_token(&rival.token)]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Sync Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let mut workout_ids = Vec::new();
+    for offset in [2, 1] {
+        let start = Utc::now() - Duration::days(offset);
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                      workout_start, workout_end, duration_minutes)
+            VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 30)
+            RETURNING id
+            "#
+        )
+        .bind(user_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(start)
+        .bind(start + Duration::minutes(30))
+        .fetch_one(&test_app.db_pool)
+        .await
+        .expect("Failed to insert workout");
+        workout_ids.push(id);
+    }
+
+    let notification_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO notifications (recipient_id, actor_id, notification_type, entity_type, entity_id, message)
+        VALUES ($1, $1, 'team_message', 'team', $2, 'Welcome to the season')
+        RETURNING id
+        "#
+    )
+    .bind(user_id)
+    .bind(Uuid::parse_str(&league.team_ids[0]).unwrap())
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to insert notification");
+
+    // Full snapshot
+    let snapshot = get_changes(&client, &test_app.address, &user.token, None).await;
+    assert_eq!(snapshot["full_resync"], true);
+    let snapshot_workouts = ids(&snapshot, "workouts");
+    assert_eq!(snapshot_workouts.len(), 2);
+    assert!(ids(&snapshot, "notifications").contains(&notification_id.to_string()));
+    let season_games: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM games WHERE season_id = $1")
+        .bind(season_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(snapshot["games"].as_array().unwrap().len() as i64, season_games);
+    assert_eq!(snapshot["standings"].as_array().unwrap().len(), 2);
+    assert!(snapshot["deleted"].as_array().unwrap().is_empty());
+    let since = snapshot["next_since"].as_str().unwrap().to_string();
+
+    // Nothing changed yet: the overlap only repeats what the snapshot already had
+    let delta = get_changes(&client, &test_app.address, &user.token, Some(&since)).await;
+    assert_eq!(delta["full_resync"], false);
+    for key in ["workouts", "games", "standings", "notifications"] {
+        let known = ids(&snapshot, key);
+        assert!(ids(&delta, key).iter().all(|id| known.contains(id)), "Expected no new {} changes", key);
+    }
+    assert!(delta["deleted"].as_array().unwrap().is_empty());
+
+    // Mark the notification read, reschedule a game and delete a workout
+    sqlx::query("UPDATE notifications SET read = true WHERE id = $1")
+        .bind(notification_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let game_id: Uuid = sqlx::query_scalar(
+        "UPDATE games SET game_start_time = game_start_time + INTERVAL '1 hour'
+         WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number, id LIMIT 1)
+         RETURNING id"
+    )
+    .bind(season_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM workout_data WHERE id = $1")
+        .bind(workout_ids[0])
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let delta = get_changes(&client, &test_app.address, &user.token, Some(&since)).await;
+    assert!(!ids(&delta, "workouts").contains(&workout_ids[0].to_string()));
+    assert_eq!(ids(&delta, "notifications"), vec![notification_id.to_string()]);
+    assert_eq!(delta["notifications"][0]["read"], true);
+    let game = delta["games"].as_array().unwrap().iter()
+        .find(|game| game["id"] == game_id.to_string())
+        .expect("The rescheduled game should be in the delta");
+    assert_ne!(game["updated_at"], snapshot["games"].as_array().unwrap().iter()
+        .find(|game| game["id"] == game_id.to_string()).unwrap()["updated_at"]);
+
+    let deleted = delta["deleted"].as_array().unwrap();
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0]["entity_type"], "workout");
+    assert_eq!(deleted[0]["entity_id"], workout_ids[0].to_string());
+
+    // The rival does not see this user's workout tombstone
+    let rival_delta = get_changes(&client, &test_app.address, &rival.token, Some(&since)).await;
+    assert!(rival_delta["deleted"].as_array().unwrap().iter().all(|t| t["entity_type"] != "workout"));
+}
+
+#[tokio::test]
+async fn writes_committed_during_a_sync_show_up_in_the_next_one() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&user.token);
+
+    // An upload that started before the sync and commits after it: its updated_at predates the sync
+    let mut upload = test_app.db_pool.begin().await.unwrap();
+    let start = Utc::now() - Duration::hours(1);
+    let workout_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                  workout_start, workout_end, duration_minutes)
+        VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 30)
+        RETURNING id
+        "#
+    )
+    .bind(user_id)
+    .bind(Uuid::new_v4().to_string())
+    .bind(start)
+    .bind(start + Duration::minutes(30))
+    .fetch_one(&mut *upload)
+    .await
+    .expect("Failed to insert workout");
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let snapshot = get_changes(&client, &test_app.address, &user.token, None).await;
+    assert!(ids(&snapshot, "workouts").is_empty());
+    upload.commit().await.unwrap();
+
+    let since = snapshot["next_since"].as_str().unwrap().to_string();
+    let delta = get_changes(&client, &test_app.address, &user.token, Some(&since)).await;
+    assert_eq!(ids(&delta, "workouts"), vec![workout_id.to_string()]);
+}
+
+#[tokio::test]
+async fn sync_changes_with_expired_since_returns_full_snapshot() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let since = (Utc::now() - Duration::days(90)).to_rfc3339();
+    let data = get_changes(&client, &test_app.address, &user.token, Some(&since)).await;
+    assert_eq!(data["full_resync"], true);
+    assert!(data["deleted"].as_array().unwrap().is_empty());
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/sync/changes?since=yesterday", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+}
@@ -0,0 +1,94 @@
+//! Sync diagnostics tests
+//!
+//! - Diagnostics look back 14 days by default, at most as far as attempts are kept
+//! - Uploads are recorded with their outcome: stored outside any game, refused, or already synced
+//! - `/profile/diagnostics` lists them with the device's last syncs, admins see the same per user
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+
+use riina_backend::models::diagnostics::{DiagnosticsQuery, UploadOutcome, UPLOAD_ATTEMPT_RETENTION_DAYS};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{
+    approve_workout_upload, create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+#[test]
+fn diagnostics_window_is_bounded() {
+    let query = DiagnosticsQuery { days: None, limit: None };
+    assert_eq!((query.days(), query.limit()), (14, 50));
+
+    let query = DiagnosticsQuery { days: Some(365), limit: Some(10_000) };
+    assert_eq!((query.days(), query.limit()), (UPLOAD_ATTEMPT_RETENTION_DAYS, 200));
+
+    let query = DiagnosticsQuery { days: Some(0), limit: Some(-1) };
+    assert_eq!((query.days(), query.limit()), (1, 1));
+
+    assert_eq!(UploadOutcome::OutOfWindow.as_str(), "out_of_window");
+    assert_eq!(serde_json::to_value(UploadOutcome::OutOfWindow).unwrap(), "out_of_window");
+}
+
+#[tokio::test]
+async fn diagnostics_explain_what_became_of_uploads() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    // Stored, but the user has no team playing
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(3), 30);
+    upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout)
+        .await
+        .expect("Upload should succeed");
+
+    // Synced again from the device
+    approve_workout_upload(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+
+    // Uploaded without going through the sync check
+    let unapproved = WorkoutData::new(WorkoutIntensity::Light, Utc::now() - Duration::hours(1), 20);
+    let response = make_authenticated_request(
+        &client, Method::POST, &format!("{}/health/upload_health", test_app.address), &user.token,
+        Some(json!(unapproved)),
+    ).await;
+    assert_eq!(response.status(), 400);
+
+    let response = make_authenticated_request(
+        &client, Method::GET, &format!("{}/profile/diagnostics", test_app.address), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let diagnostics = &body["data"];
+
+    let outcome_of = |workout_uuid: &str, outcome: &str| {
+        diagnostics["attempts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|attempt| attempt["workout_uuid"] == workout_uuid && attempt["outcome"] == outcome)
+            .cloned()
+    };
+    let stored = outcome_of(&workout.workout_uuid, "out_of_window").expect("The upload should be recorded");
+    assert!(stored["workout_data_id"].is_string());
+    assert_eq!(stored["device_id"], workout.device_id.as_str());
+    let duplicate = outcome_of(&workout.workout_uuid, "duplicate").expect("The second sync should be recorded");
+    assert_eq!(duplicate["workout_data_id"], stored["workout_data_id"]);
+    let rejected = outcome_of(&unapproved.workout_uuid, "rejected").expect("The refused upload should be recorded");
+    assert_eq!(rejected["detail"], "No approval token");
+    assert_eq!(diagnostics["outcome_counts"].as_array().unwrap().len(), 3);
+
+    let devices = diagnostics["devices"].as_array().unwrap();
+    let device = devices.iter().find(|device| device["device_id"] == workout.device_id.as_str()).unwrap();
+    assert!(device["last_upload_at"].is_string());
+    assert!(device["last_attempt_at"].is_string());
+
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let response = make_authenticated_request(
+        &client, Method::GET, &format!("{}/admin/users/{}/diagnostics", test_app.address, user.user_id), &admin.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let admin_view: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(admin_view["data"]["attempts"].as_array().unwrap().len(), 3);
+}
@@ -0,0 +1,174 @@
+//! Team activity heatmap tests
+//!
+//! Covers `/league/teams/{id}/activity-heatmap`:
+//! - One row per active member and one column per day of the game in progress
+//! - `?range=season` spans the season up to today
+//! - Only active team members (and admins) can see it
+
+use reqwest::Client;
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+#[tokio::test]
+async fn heatmap_shows_daily_activity_per_member() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let other_owner = create_test_user_and_login(&test_app.address).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let outsider = create_test_user_and_login(&test_app.address).await;
+    let captain_id = parse_user_id_from_jwt_token(&captain.token);
+    let member_id = parse_user_id_from_jwt_token(&member.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2,
+        Some(vec![captain_id, parse_user_id_from_jwt_token(&other_owner.token)]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Heatmap Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+    let team_id = Uuid::parse_str(&league.team_ids[0]).unwrap();
+
+    sqlx::query("INSERT INTO team_members (team_id, user_id, role, status) VALUES ($1, $2, 'member', 'active')")
+        .bind(team_id)
+        .bind(member_id)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to add team member");
+
+    // The team's game started two days ago and runs for four more days
+    let game_start = Utc::now() - Duration::days(2);
+    sqlx::query(
+        r#"
+        UPDATE games SET status = 'in_progress', game_start_time = $3, game_end_time = $4
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 AND (home_team_id = $2 OR away_team_id = $2)
+                    ORDER BY week_number LIMIT 1)
+        "#
+    )
+    .bind(season_id)
+    .bind(team_id)
+    .bind(game_start)
+    .bind(game_start + Duration::days(6))
+    .execute(&test_app.db_pool)
+    .await
+    .expect("Failed to start game");
+
+    // The member trained twice on the first day of the game
+    for offset in [1, 3] {
+        let start = game_start.date_naive().and_hms_opt(8 + offset, 0, 0).unwrap().and_utc();
+        sqlx::query(
+            r#"
+            INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                      workout_start, workout_end, duration_minutes)
+            VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 45)
+            "#
+        )
+        .bind(member_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(start)
+        .bind(start + Duration::minutes(45))
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to insert workout");
+    }
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/teams/{}/activity-heatmap", test_app.address, team_id),
+        &captain.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let data = &body["data"];
+
+    assert_eq!(data["range"], "game_week");
+    assert!(data["game_id"].is_string());
+    let days = data["days"].as_array().unwrap();
+    assert_eq!(days.len(), 7, "Game window spans seven calendar days");
+    let first_day: NaiveDate = days[0].as_str().unwrap().parse().unwrap();
+    assert_eq!(first_day, game_start.date_naive());
+
+    let members = data["members"].as_array().unwrap();
+    assert_eq!(members.len(), 2);
+    let member_row = members.iter().find(|m| m["user_id"] == member_id.to_string().as_str()).unwrap();
+    assert_eq!(member_row["workouts"][0], 2);
+    assert_eq!(member_row["minutes"][0], 90);
+    assert_eq!(member_row["total_workouts"], 2);
+    assert_eq!(member_row["active_days"], 1);
+    assert_eq!(member_row["workouts"].as_array().unwrap().len(), days.len());
+
+    let captain_row = members.iter().find(|m| m["user_id"] == captain_id.to_string().as_str()).unwrap();
+    assert_eq!(captain_row["total_workouts"], 0);
+    assert!(captain_row["last_workout_at"].is_null());
+
+    // Season range starts at the season start
+    sqlx::query("UPDATE league_seasons SET start_date = NOW() - INTERVAL '20 days' WHERE id = $1")
+        .bind(season_id)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to backdate season");
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/teams/{}/activity-heatmap?range=season", test_app.address, team_id),
+        &member.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["days"].as_array().unwrap().len(), 21);
+
+    // Outsiders cannot see the team's activity
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/teams/{}/activity-heatmap", test_app.address, team_id),
+        &outsider.token,
+        None,
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+
+    // Neither can members who were removed from the team
+    sqlx::query("UPDATE team_members SET status = 'inactive' WHERE team_id = $1 AND user_id = $2")
+        .bind(team_id)
+        .bind(member_id)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to deactivate team member");
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/teams/{}/activity-heatmap", test_app.address, team_id),
+        &member.token,
+        None,
+    ).await;
+    assert_eq!(403, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn heatmap_returns_404_for_unknown_team() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/teams/{}/activity-heatmap", test_app.address, Uuid::new_v4()),
+        &user.token,
+        None,
+    ).await;
+
+    assert_eq!(404, response.status().as_u16());
+}
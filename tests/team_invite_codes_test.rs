@@ -0,0 +1,137 @@
+//! Team invite code tests
+//!
+//! Covers registering into a team with an invite code:
+//! - team owners create codes, plain members can't
+//! - registering with a code joins the team instead of the player pool and uses up the code
+//! - revoked codes are refused at registration
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, generate_valid_username_suffix, make_authenticated_request};
+use common::admin_helpers::{create_admin_user_and_login, create_team, add_user_to_team, TeamConfig};
+
+async fn create_invite_code(client: &Client, address: &str, token: &str, team_id: &str, body: serde_json::Value) -> reqwest::Response {
+    make_authenticated_request(
+        client, reqwest::Method::POST, &format!("{}/league/teams/{}/invites", address, team_id), token, Some(body),
+    ).await
+}
+
+async fn register_with_code(client: &Client, address: &str, code: &str) -> (reqwest::Response, String) {
+    let username = format!("invited_{}", generate_valid_username_suffix());
+    let response = client
+        .post(format!("{}/register_user", address))
+        .json(&json!({
+            "username": username,
+            "email": format!("{}@example.com", username),
+            "password": "password123",
+            "invite_code": code.to_lowercase()
+        }))
+        .send()
+        .await
+        .expect("Failed to register user.");
+    (response, username)
+}
+
+#[tokio::test]
+async fn registering_with_an_invite_code_joins_the_team() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner = create_test_user_and_login(&test_app.address).await;
+    let team_id = create_team(
+        &test_app.address, &admin.token, TeamConfig { owner_id: Some(owner.user_id), ..Default::default() },
+    ).await;
+
+    let response = create_invite_code(&client, &test_app.address, &owner.token, &team_id, json!({ "max_uses": 1 })).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let code = body["data"]["code"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["max_uses"], 1);
+
+    let (response, username) = register_with_code(&client, &test_app.address, &code).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let (role, in_pool): (String, bool) = sqlx::query_as(
+        "SELECT tm.role, EXISTS(SELECT 1 FROM player_pool pp WHERE pp.user_id = u.id)
+         FROM users u JOIN team_members tm ON tm.user_id = u.id
+         WHERE u.username = $1 AND tm.team_id = $2"
+    )
+    .bind(&username)
+    .bind(Uuid::parse_str(&team_id).unwrap())
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(role, "member");
+    assert!(!in_pool, "Users joining a team are not free agents");
+
+    // The single use is gone
+    let (response, _) = register_with_code(&client, &test_app.address, &code).await;
+    assert_eq!(400, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "This invite code has been used up");
+}
+
+#[tokio::test]
+async fn only_team_owners_and_admins_create_invite_codes() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner = create_test_user_and_login(&test_app.address).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let team_id = create_team(
+        &test_app.address, &admin.token, TeamConfig { owner_id: Some(owner.user_id), ..Default::default() },
+    ).await;
+    add_user_to_team(&test_app.address, &admin.token, &team_id, member.user_id).await;
+
+    let response = create_invite_code(&client, &test_app.address, &member.token, &team_id, json!({})).await;
+    assert_eq!(403, response.status().as_u16());
+
+    let response = create_invite_code(&client, &test_app.address, &owner.token, &team_id, json!({ "expires_in_days": 90 })).await;
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn revoked_invite_codes_are_refused() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let owner = create_test_user_and_login(&test_app.address).await;
+    let team_id = create_team(
+        &test_app.address, &admin.token, TeamConfig { owner_id: Some(owner.user_id), ..Default::default() },
+    ).await;
+
+    let response = create_invite_code(&client, &test_app.address, &owner.token, &team_id, json!({ "max_uses": 5 })).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let invite_id = body["data"]["id"].as_str().unwrap().to_string();
+    let code = body["data"]["code"].as_str().unwrap().to_string();
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/admin/invites?team_id={}", test_app.address, team_id), &admin.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"][0]["id"], invite_id);
+
+    let revoke_url = format!("{}/admin/invites/{}/revoke", test_app.address, invite_id);
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &revoke_url, &admin.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &revoke_url, &admin.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    let (response, username) = register_with_code(&client, &test_app.address, &code).await;
+    assert_eq!(400, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "This invite code has been revoked");
+
+    let (registered,): (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM users WHERE username = $1)")
+        .bind(&username)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert!(!registered);
+
+    let (response, _) = register_with_code(&client, &test_app.address, "NOSUCHCD").await;
+    assert_eq!(400, response.status().as_u16());
+}
@@ -0,0 +1,108 @@
+//! Team kit tests
+//!
+//! - Away teams switch to their away color, then an alternate, when colors clash
+//! - Away colors and kit emoji are validated on team requests
+//! - Live game payloads carry the kits, fixed once the game starts
+
+use reqwest::Client;
+
+use riina_backend::league::team_kits::{assign_game_kits, colors_clash, validate_kit_emoji, TeamKit};
+use riina_backend::models::team::TeamUpdateRequest;
+
+mod common;
+use common::live_game_helpers::{setup_live_game_environment, start_test_game};
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn kit(home_color: &str, away_color: Option<&str>) -> TeamKit {
+    TeamKit { home_color: home_color.to_string(), away_color: away_color.map(str::to_string), emoji: None }
+}
+
+#[test]
+fn similar_colors_clash() {
+    assert!(colors_clash("#FF0000", "#EE1111"));
+    assert!(colors_clash("#4F46E5", "#4F46E5"));
+    assert!(!colors_clash("#FF0000", "#0000FF"));
+    assert!(!colors_clash("#FFFFFF", "#111827"));
+}
+
+#[test]
+fn away_teams_change_kits_only_when_colors_clash() {
+    let (home, away) = assign_game_kits(&kit("#FF0000", None), &kit("#0000FF", Some("#FFFF00")));
+    assert_eq!(home.color, "#FF0000");
+    assert_eq!(away.color, "#0000FF", "No clash, own color");
+
+    let (_, away) = assign_game_kits(&kit("#FF0000", None), &kit("#EE1111", Some("#FFFF00")));
+    assert_eq!(away.color, "#FFFF00", "Clash, away color");
+
+    let (_, away) = assign_game_kits(&kit("#FF0000", None), &kit("#EE1111", Some("#F01010")));
+    assert_eq!(away.color, "#FFFFFF", "Both clash, first alternate");
+
+    let (_, away) = assign_game_kits(&kit("#FAFAFA", None), &kit("#FFFFFF", None));
+    assert_eq!(away.color, "#111827", "Alternates that clash are skipped");
+}
+
+#[test]
+fn kit_requests_are_validated() {
+    let update = |team_color: Option<&str>, away_color: Option<&str>, kit_emoji: Option<&str>| TeamUpdateRequest {
+        team_name: None,
+        team_description: None,
+        team_color: team_color.map(str::to_string),
+        away_color: away_color.map(str::to_string),
+        kit_emoji: kit_emoji.map(str::to_string),
+    };
+
+    assert!(update(None, Some("#FFFFFF"), Some("🦊")).validate().is_ok());
+    assert!(update(None, Some("white"), None).validate().is_err());
+    assert!(update(Some("#FF0000"), Some("#EE1111"), None).validate().is_err(), "Away color clashes with team color");
+    assert!(update(None, None, Some("fox")).validate().is_err());
+
+    assert!(validate_kit_emoji("⚽🔥").is_ok());
+    assert!(validate_kit_emoji("").is_err());
+}
+
+#[tokio::test]
+async fn live_games_carry_kits_fixed_at_start() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let environment = setup_live_game_environment(&test_app).await;
+    let game_id = environment.first_game_id;
+
+    let (home_team_id, away_team_id) = sqlx::query_as::<_, (uuid::Uuid, uuid::Uuid)>(
+        "SELECT home_team_id, away_team_id FROM games WHERE id = $1",
+    )
+    .bind(game_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE teams SET team_color = '#FF0000', kit_emoji = '🔥' WHERE id = $1")
+        .bind(home_team_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE teams SET team_color = '#EE1111', away_color = '#FFFF00' WHERE id = $1")
+        .bind(away_team_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    start_test_game(&test_app, game_id).await;
+    riina_backend::services::TeamKitService::new(test_app.db_pool.clone())
+        .fix_game_kits(game_id)
+        .await
+        .unwrap();
+
+    // Editing the kit mid-game doesn't change the colors of the running game
+    sqlx::query("UPDATE teams SET away_color = '#00FF00' WHERE id = $1")
+        .bind(away_team_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let url = format!("{}/league/games/{}/live", test_app.address, game_id);
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &environment.home_user.token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["home_kit"]["color"], "#FF0000");
+    assert_eq!(body["data"]["home_kit"]["emoji"], "🔥");
+    assert_eq!(body["data"]["away_kit"]["color"], "#FFFF00");
+}
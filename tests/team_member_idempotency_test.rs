@@ -0,0 +1,163 @@
+//! Team member mutation tests
+//!
+//! Adding and removing members can be repeated safely, and league membership is exclusive:
+//! - Adding an existing member again succeeds without changes
+//! - Adding a user who plays for another team of the same league is a 409 naming that team
+//! - Removing a user who is not in the team succeeds without changes
+//! - Transferring moves the user in one step and can be repeated
+
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::admin_helpers::{create_admin_user_and_login, create_league};
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app, UserRegLoginResponse};
+
+struct LeagueTeams {
+    owner_a: UserRegLoginResponse,
+    owner_b: UserRegLoginResponse,
+    team_a: String,
+    team_b: String,
+}
+
+async fn setup_league_with_two_teams(address: &str, pool: &sqlx::PgPool) -> LeagueTeams {
+    let admin = create_admin_user_and_login(address, pool).await;
+    let league_id = create_league(address, &admin.token, 4).await;
+
+    let owner_a = create_test_user_and_login(address).await;
+    let owner_b = create_test_user_and_login(address).await;
+    let team_a = register_team(address, &owner_a, &league_id).await;
+    let team_b = register_team(address, &owner_b, &league_id).await;
+
+    LeagueTeams { owner_a, owner_b, team_a, team_b }
+}
+
+async fn register_team(address: &str, owner: &UserRegLoginResponse, league_id: &str) -> String {
+    let response = make_authenticated_request(
+        &Client::new(),
+        Method::POST,
+        &format!("{}/league/teams/register", address),
+        &owner.token,
+        Some(json!({
+            "team_name": format!("Team_{}", &Uuid::new_v4().to_string()[..8]),
+            "league_id": league_id
+        })),
+    )
+    .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]["team_id"].as_str().unwrap().to_string()
+}
+
+async fn add_member(address: &str, token: &str, team_id: &str, username: &str) -> reqwest::Response {
+    make_authenticated_request(
+        &Client::new(),
+        Method::POST,
+        &format!("{}/league/teams/{}/members", address, team_id),
+        token,
+        Some(json!({ "member_request": [{ "username": username, "role": "member" }] })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn adding_an_existing_member_again_changes_nothing() {
+    let test_app = spawn_app().await;
+    let teams = setup_league_with_two_teams(&test_app.address, &test_app.db_pool).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+
+    let first = add_member(&test_app.address, &teams.owner_a.token, &teams.team_a, &member.username).await;
+    assert_eq!(first.status().as_u16(), 201);
+    let first: serde_json::Value = first.json().await.unwrap();
+
+    let retry = add_member(&test_app.address, &teams.owner_a.token, &teams.team_a, &member.username).await;
+    assert_eq!(retry.status().as_u16(), 200);
+    let retry: serde_json::Value = retry.json().await.unwrap();
+    assert_eq!(retry["data"]["already_members"][0], member.user_id.to_string());
+    assert_eq!(retry["data"]["members"][0]["id"], first["data"]["members"][0]["id"]);
+
+    let memberships = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM team_members WHERE user_id = $1"#,
+        member.user_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(memberships, 1);
+}
+
+#[tokio::test]
+async fn adding_a_member_of_another_league_team_is_a_conflict() {
+    let test_app = spawn_app().await;
+    let teams = setup_league_with_two_teams(&test_app.address, &test_app.db_pool).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+
+    let response = add_member(&test_app.address, &teams.owner_a.token, &teams.team_a, &member.username).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = add_member(&test_app.address, &teams.owner_b.token, &teams.team_b, &member.username).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    let conflict = &body["data"]["conflicts"][0];
+    assert_eq!(conflict["user_id"], member.user_id.to_string());
+    assert_eq!(conflict["team_id"], teams.team_a);
+}
+
+#[tokio::test]
+async fn removing_a_non_member_succeeds_without_changes() {
+    let test_app = spawn_app().await;
+    let teams = setup_league_with_two_teams(&test_app.address, &test_app.db_pool).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let client = Client::new();
+
+    let response = add_member(&test_app.address, &teams.owner_a.token, &teams.team_a, &member.username).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let url = format!("{}/league/teams/{}/members/{}", test_app.address, teams.team_a, member.user_id);
+    for removed in [true, false] {
+        let response = make_authenticated_request(&client, Method::DELETE, &url, &teams.owner_a.token, None).await;
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["data"]["removed"], removed);
+    }
+}
+
+#[tokio::test]
+async fn transfer_moves_member_between_league_teams() {
+    let test_app = spawn_app().await;
+    let teams = setup_league_with_two_teams(&test_app.address, &test_app.db_pool).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let client = Client::new();
+
+    let response = add_member(&test_app.address, &teams.owner_a.token, &teams.team_a, &member.username).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    // The owner of team A also runs team B
+    sqlx::query!(
+        "INSERT INTO team_members (team_id, user_id, role, status) VALUES ($1, $2, 'admin', 'active')",
+        Uuid::parse_str(&teams.team_b).unwrap(),
+        teams.owner_a.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let url = format!("{}/league/teams/{}/members/{}/transfer", test_app.address, teams.team_a, member.user_id);
+    let body = json!({ "to_team_id": teams.team_b });
+    for _ in 0..2 {
+        let response = make_authenticated_request(&client, Method::POST, &url, &teams.owner_a.token, Some(body.clone())).await;
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["data"]["team_id"], teams.team_b);
+        assert_eq!(body["data"]["role"], "member");
+    }
+
+    let teams_of_member = sqlx::query_scalar!("SELECT team_id FROM team_members WHERE user_id = $1", member.user_id)
+        .fetch_all(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(teams_of_member, vec![Uuid::parse_str(&teams.team_b).unwrap()]);
+}
@@ -0,0 +1,86 @@
+//! Team notification settings tests
+//!
+//! - Which team events are notified, and when a team fell behind or a member hit a milestone
+//! - Captains change the settings; members can only read them
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::league::team_alerts::{fell_behind, is_workout_milestone, TeamAlert};
+use riina_backend::models::notification::TeamNotificationSettings;
+
+mod common;
+use common::admin_helpers::{add_user_to_team, create_admin_user_and_login, create_team, TeamConfig};
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn teams_get_every_alert_until_a_captain_turns_one_off() {
+    let mut settings = TeamNotificationSettings::defaults(Uuid::new_v4());
+    assert!(TeamAlert::ALL.iter().all(|alert| settings.allows(*alert)));
+
+    settings.fell_behind_enabled = false;
+    assert!(!settings.allows(TeamAlert::FellBehind));
+    assert!(settings.allows(TeamAlert::GameStart));
+    assert!(settings.allows(TeamAlert::MemberMilestone));
+}
+
+#[test]
+fn falling_behind_needs_a_lost_lead_or_tie() {
+    assert!(fell_behind(10, 5, 10, 12), "Lead lost");
+    assert!(fell_behind(10, 10, 10, 11), "Tie lost");
+    assert!(!fell_behind(10, 12, 10, 15), "Already behind");
+    assert!(!fell_behind(10, 5, 10, 10), "Only caught up");
+}
+
+#[test]
+fn workout_milestones() {
+    assert!(is_workout_milestone(10));
+    assert!(is_workout_milestone(100));
+    assert!(!is_workout_milestone(1));
+    assert!(!is_workout_milestone(11));
+}
+
+#[tokio::test]
+async fn captains_configure_team_notifications() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let captain = create_test_user_and_login(&test_app.address).await;
+    let member = create_test_user_and_login(&test_app.address).await;
+    let outsider = create_test_user_and_login(&test_app.address).await;
+
+    let team_id = create_team(
+        &test_app.address,
+        &admin.token,
+        TeamConfig { owner_id: Some(captain.user_id), ..Default::default() },
+    )
+    .await;
+    add_user_to_team(&test_app.address, &admin.token, &team_id, member.user_id).await;
+    let url = format!("{}/league/teams/{}/notification-settings", test_app.address, team_id);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &member.token, None).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["game_start_enabled"], true);
+    assert_eq!(body["data"]["fell_behind_enabled"], true);
+    assert!(body["data"]["updated_at"].is_null());
+
+    let change = json!({ "fell_behind_enabled": false });
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &url, &member.token, Some(change.clone())).await;
+    assert_eq!(response.status(), 403, "Members can't change team notifications");
+
+    let response = make_authenticated_request(&client, reqwest::Method::PUT, &url, &captain.token, Some(change)).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["fell_behind_enabled"], false);
+    assert_eq!(body["data"]["game_start_enabled"], true);
+    assert_eq!(body["data"]["member_milestone_enabled"], true);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &member.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["fell_behind_enabled"], false);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &url, &outsider.token, None).await;
+    assert_eq!(response.status(), 403);
+}
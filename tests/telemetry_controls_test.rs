@@ -0,0 +1,120 @@
+//! Runtime log control tests
+//!
+//! - The most specific route prefix decides a request's log level
+//! - Unknown levels, relative routes and sample rates outside 0.0 - 1.0 are rejected
+//! - Requests left out of the sample only log warnings and errors
+//! - Events of requests are dropped beyond the level of their route, other events are kept
+//! - Admins read and replace the controls through the admin API
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use reqwest::{Client, Method};
+use serde_json::json;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::Registry;
+
+use riina_backend::services::telemetry::{LogControls, LogControlsHandle, LogControlsSettings, RequestLogLayer};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{make_authenticated_request, spawn_app};
+
+fn controls(route_levels: &[(&str, &str)], sample_rate: f64) -> Result<LogControls, String> {
+    LogControls::try_from(LogControlsSettings {
+        route_levels: route_levels.iter().map(|(route, level)| (route.to_string(), level.to_string())).collect(),
+        sample_rate,
+    })
+}
+
+#[test]
+fn most_specific_route_decides_the_level() {
+    let controls = controls(&[("/health", "warn"), ("/health/upload_health", "error")], 1.0).unwrap();
+
+    assert_eq!(controls.route_level("/health/upload_health"), Some(LevelFilter::ERROR));
+    assert_eq!(controls.route_level("/health/workout/{workout_id}"), Some(LevelFilter::WARN));
+    assert_eq!(controls.route_level("/profile/user"), None);
+    assert_eq!(controls.settings().route_levels.get("/health").map(String::as_str), Some("warn"));
+}
+
+#[test]
+fn invalid_controls_are_rejected() {
+    assert!(controls(&[("/health", "loud")], 1.0).is_err());
+    assert!(controls(&[("health", "warn")], 1.0).is_err());
+    assert!(controls(&[], 1.5).is_err());
+    assert!(controls(&[], -0.1).is_err());
+}
+
+#[test]
+fn unsampled_requests_only_log_warnings() {
+    let controls = controls(&[("/health", "error")], 0.0).unwrap();
+
+    assert!(!controls.sample());
+    assert_eq!(controls.request_level("/profile/user", false), LevelFilter::WARN);
+    assert_eq!(controls.request_level("/profile/user", true), LevelFilter::TRACE);
+    assert_eq!(controls.request_level("/health/upload_health", false), LevelFilter::ERROR);
+}
+
+/// Collects the messages of the events that make it through
+#[derive(Clone, Default)]
+struct CapturedEvents(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber> Layer<S> for CapturedEvents {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        self.0.lock().unwrap().push(event.metadata().level().to_string());
+    }
+}
+
+#[test]
+fn request_events_are_dropped_beyond_their_route_level() {
+    let handle = LogControlsHandle::default();
+    handle.set(controls(&[("/health", "warn")], 1.0).unwrap());
+    let captured = CapturedEvents::default();
+    let subscriber = Registry::default()
+        .with(RequestLogLayer::new(handle))
+        .with(captured.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let upload = tracing::info_span!("HTTP request", http.route = %"/health/upload_health");
+        upload.in_scope(|| {
+            tracing::info!("Parsed heart rate samples");
+            tracing::warn!("Workout overlaps another one");
+        });
+        let profile = tracing::info_span!("HTTP request", http.route = %"/profile/user");
+        profile.in_scope(|| tracing::info!("Loaded profile"));
+        tracing::info!("Scheduler tick");
+    });
+
+    assert_eq!(*captured.0.lock().unwrap(), vec!["WARN", "INFO", "INFO"]);
+}
+
+#[tokio::test]
+async fn admins_adjust_log_controls_at_runtime() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let url = format!("{}/admin/telemetry/log-controls", test_app.address);
+
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(json!({
+        "route_levels": { "/health/upload_health": "chatty" },
+        "sample_rate": 1.0
+    }))).await;
+    assert_eq!(response.status(), 400);
+
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(json!({
+        "route_levels": { "/health/upload_health": "WARN" },
+        "sample_rate": 0.25
+    }))).await;
+    assert_eq!(response.status(), 200);
+
+    let response = make_authenticated_request(&client, Method::GET, &url, &admin.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["route_levels"]["/health/upload_health"], "warn");
+    assert_eq!(body["data"]["sample_rate"], 0.25);
+
+    let reset = json!({ "route_levels": BTreeMap::<String, String>::new(), "sample_rate": 1.0 });
+    let response = make_authenticated_request(&client, Method::PUT, &url, &admin.token, Some(reset)).await;
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,211 @@
+//! Threshold detection tests
+//!
+//! - The best sustained heart rate only counts stretches the recording fully covers
+//! - VT1 comes from enough low-drift workouts, VT2 from the hardest sustained effort above it
+//! - Implausible values are dropped and small changes aren't proposed
+//! - Accepting a proposal updates the profile and records the thresholds as detected; decided
+//!   proposals can't be decided again
+
+use chrono::{Duration, TimeZone, Utc};
+use reqwest::Client;
+use uuid::Uuid;
+
+use riina_backend::models::workout_data::HeartRateData;
+use riina_backend::workout::threshold_detection::{
+    best_sustained_hr, estimate_thresholds, ThresholdEstimate, WorkoutThresholdEvidence, MIN_AEROBIC_WORKOUTS,
+};
+
+mod common;
+use common::utils::{create_test_user_with_health_profile, make_authenticated_request, spawn_app};
+
+/// One sample a minute, at the given heart rates
+fn samples(heart_rates: &[i32]) -> Vec<HeartRateData> {
+    let start = Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap();
+    heart_rates
+        .iter()
+        .enumerate()
+        .map(|(minute, heart_rate)| HeartRateData {
+            timestamp: start + Duration::minutes(minute as i64),
+            heart_rate: *heart_rate,
+        })
+        .collect()
+}
+
+fn aerobic(steady_state_avg_hr: f32, hr_drift_pct: f32) -> WorkoutThresholdEvidence {
+    WorkoutThresholdEvidence {
+        steady_state_avg_hr: Some(steady_state_avg_hr),
+        hr_drift_pct: Some(hr_drift_pct),
+        best_sustained_hr: None,
+    }
+}
+
+fn sustained(best_sustained_hr: f32) -> WorkoutThresholdEvidence {
+    WorkoutThresholdEvidence { best_sustained_hr: Some(best_sustained_hr), ..Default::default() }
+}
+
+#[test]
+fn best_sustained_hr_needs_full_windows() {
+    assert_eq!(best_sustained_hr(&samples(&[150; 15]), 20), None);
+
+    // 10 easy minutes, then 25 hard ones
+    let mut heart_rates = vec![120; 10];
+    heart_rates.extend([170; 25]);
+    assert_eq!(best_sustained_hr(&samples(&heart_rates), 20), Some(170.0));
+
+    // Order and dropouts don't matter
+    let mut unordered = samples(&heart_rates);
+    unordered.reverse();
+    unordered.push(HeartRateData { timestamp: unordered[0].timestamp, heart_rate: 0 });
+    assert_eq!(best_sustained_hr(&unordered, 20), Some(170.0));
+}
+
+#[test]
+fn thresholds_come_from_drift_and_sustained_efforts() {
+    let mut evidence = vec![
+        aerobic(150.0, 2.0),
+        aerobic(148.0, -1.0),
+        aerobic(146.0, 3.0),
+        aerobic(140.0, 1.0),
+        // Drifted, so above VT1
+        aerobic(165.0, 9.0),
+        sustained(172.0),
+        sustained(168.0),
+    ];
+    let estimate = estimate_thresholds(&evidence, 110, 190);
+    assert_eq!(estimate.vt1, Some(148));
+    assert_eq!(estimate.vt2, Some(172));
+    assert_eq!(estimate.aerobic_workouts, 4);
+    assert_eq!(estimate.sustained_workouts, 2);
+
+    // Too few low-drift workouts for VT1
+    evidence.retain(|workout| workout.hr_drift_pct.is_none_or(|drift| drift > 2.5));
+    assert!(evidence.len() - 2 < MIN_AEROBIC_WORKOUTS);
+    let estimate = estimate_thresholds(&evidence, 110, 190);
+    assert_eq!(estimate.vt1, None);
+    assert_eq!(estimate.vt2, Some(172));
+}
+
+#[test]
+fn implausible_estimates_are_dropped() {
+    let evidence = vec![aerobic(100.0, 1.0), aerobic(102.0, 1.0), aerobic(104.0, 1.0), sustained(195.0)];
+    assert_eq!(estimate_thresholds(&evidence, 110, 190), ThresholdEstimate {
+        vt1: None,
+        vt2: None,
+        aerobic_workouts: 0,
+        sustained_workouts: 0,
+    });
+
+    // A VT2 not above VT1
+    let evidence = vec![aerobic(150.0, 1.0), aerobic(150.0, 1.0), aerobic(150.0, 1.0), sustained(149.0)];
+    let estimate = estimate_thresholds(&evidence, 110, 190);
+    assert_eq!(estimate.vt1, Some(150));
+    assert_eq!(estimate.vt2, None);
+}
+
+#[test]
+fn only_notable_changes_are_proposed() {
+    let estimate = ThresholdEstimate { vt1: Some(150), vt2: Some(172), ..Default::default() };
+    assert!(!estimate.differs_from(Some(149), Some(170)));
+    assert!(estimate.differs_from(Some(147), Some(172)));
+    assert!(estimate.differs_from(Some(150), None));
+    assert!(!ThresholdEstimate::default().differs_from(Some(140), Some(160)));
+}
+
+async fn insert_proposal(pool: &sqlx::PgPool, user_id: Uuid, vt1: i32) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO threshold_proposals (user_id, vt1_threshold, aerobic_workouts, sustained_workouts)
+         VALUES ($1, $2, 4, 0) RETURNING id"
+    )
+    .bind(user_id)
+    .bind(vt1)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn accepting_a_proposal_records_detected_thresholds() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app, &client).await;
+    let proposals_url = format!("{}/profile/health_profile/threshold-proposals", test_app.address);
+
+    let (vt1, vt2): (i32, i32) =
+        sqlx::query_as("SELECT vt1_threshold, vt2_threshold FROM user_health_profiles WHERE user_id = $1")
+            .bind(user.user_id)
+            .fetch_one(&test_app.db_pool)
+            .await
+            .unwrap();
+    let proposed_vt1 = vt1 + (vt2 - vt1) / 2;
+    let proposal_id = insert_proposal(&test_app.db_pool, user.user_id, proposed_vt1).await;
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{proposals_url}?pending_only=true"), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"][0]["id"], proposal_id.to_string());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{proposals_url}/{proposal_id}/accept"), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/profile/health_profile", test_app.address), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["vt1_threshold"], proposed_vt1);
+    assert_eq!(body["data"]["vt1_source"], "detected");
+    assert_eq!(body["data"]["vt2_threshold"], vt2);
+    assert_eq!(body["data"]["vt2_source"], "hr_reserve");
+
+    // Decided already, and other users' proposals aren't found
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{proposals_url}/{proposal_id}/decline"), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 409);
+    let other = create_test_user_with_health_profile(&test_app, &client).await;
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{proposals_url}/{proposal_id}/accept"), &other.token, None,
+    ).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn declining_a_proposal_keeps_the_profile() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app, &client).await;
+    let proposals_url = format!("{}/profile/health_profile/threshold-proposals", test_app.address);
+
+    let (vt1, max_hr): (i32, i32) =
+        sqlx::query_as("SELECT vt1_threshold, max_heart_rate FROM user_health_profiles WHERE user_id = $1")
+            .bind(user.user_id)
+            .fetch_one(&test_app.db_pool)
+            .await
+            .unwrap();
+    let declined = insert_proposal(&test_app.db_pool, user.user_id, vt1 + 3).await;
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{proposals_url}/{declined}/decline"), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["status"], "declined");
+
+    // A VT1 above max heart rate doesn't fit the profile
+    let unfitting = insert_proposal(&test_app.db_pool, user.user_id, max_hr + 5).await;
+    let response = make_authenticated_request(
+        &client, reqwest::Method::POST, &format!("{proposals_url}/{unfitting}/accept"), &user.token, None,
+    ).await;
+    assert_eq!(response.status(), 409);
+
+    let (current_vt1, source): (i32, String) =
+        sqlx::query_as("SELECT vt1_threshold, vt1_source FROM user_health_profiles WHERE user_id = $1")
+            .bind(user.user_id)
+            .fetch_one(&test_app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(current_vt1, vt1);
+    assert_eq!(source, "hr_reserve");
+}
@@ -0,0 +1,62 @@
+//! Trace propagation tests
+//!
+//! - Events published through Redis carry the trace they were published in
+//! - Whoever handles a published event continues that trace
+//! - Events published outside of a trace carry no trace context
+
+use opentelemetry::trace::{TraceContextExt, TracerProvider};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serde_json::json;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+use riina_backend::services::telemetry::{
+    attach_trace_context, continue_trace, trace_context_of, traced_message, TRACE_CONTEXT_FIELD,
+};
+
+fn traced<T>(f: impl FnOnce() -> T) -> T {
+    let provider = SdkTracerProvider::builder().build();
+    let subscriber = Registry::default().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    tracing::subscriber::with_default(subscriber, f)
+}
+
+fn trace_id(span: &tracing::Span) -> opentelemetry::trace::TraceId {
+    span.context().span().span_context().trace_id()
+}
+
+#[test]
+fn published_events_continue_their_trace() {
+    traced(|| {
+        let upload = tracing::info_span!("HTTP request", http.route = %"/health/upload_health");
+        let mut event = json!({ "event_type": "workout_data_processed" });
+        upload.in_scope(|| attach_trace_context(&mut event));
+        assert!(event[TRACE_CONTEXT_FIELD]["traceparent"].is_string());
+
+        let forward = tracing::info_span!("Forward Redis event");
+        continue_trace(&forward, &trace_context_of(&event));
+        assert_eq!(trace_id(&forward), trace_id(&upload));
+    });
+}
+
+#[test]
+fn serialized_events_carry_the_current_trace() {
+    traced(|| {
+        let job = tracing::info_span!("Scheduler job", job.name = %"game_evaluation");
+        let message = job.in_scope(|| traced_message(&json!({ "event_type": "games_evaluated" }))).unwrap();
+        let event: serde_json::Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(event["event_type"], "games_evaluated");
+
+        let forward = tracing::info_span!("Forward Redis event");
+        continue_trace(&forward, &trace_context_of(&event));
+        assert_eq!(trace_id(&forward), trace_id(&job));
+    });
+}
+
+#[test]
+fn events_outside_of_traces_carry_no_context() {
+    let mut event = json!({ "event_type": "workout_data_processed" });
+    attach_trace_context(&mut event);
+    assert!(event.get(TRACE_CONTEXT_FIELD).is_none());
+    assert!(trace_context_of(&event).is_empty());
+}
@@ -0,0 +1,123 @@
+//! Compression tests
+//!
+//! Covers compressed request and response bodies:
+//! - Workout uploads are accepted with Content-Encoding gzip and br
+//! - The upload size cap applies to the decompressed body
+//! - Workout history and the feed are compressed for clients that accept it
+
+use std::io::{Read, Write};
+
+use chrono::{Duration, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::config::settings::get_config;
+
+mod common;
+use common::utils::spawn_app;
+use common::workout_data_helpers::{
+    approve_workout_upload, create_test_user_with_health_profile, WorkoutData, WorkoutIntensity,
+};
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn brotli(body: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    brotli::CompressorReader::new(body, 4096, 5, 22).read_to_end(&mut compressed).unwrap();
+    compressed
+}
+
+async fn post_compressed_upload(
+    client: &Client,
+    address: &str,
+    token: &str,
+    encoding: &str,
+    body: Vec<u8>,
+) -> reqwest::Response {
+    client
+        .post(format!("{}/health/upload_health", address))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .header("Content-Encoding", encoding)
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+#[tokio::test]
+async fn compressed_workout_uploads_are_accepted() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let encodings: [(&str, fn(&[u8]) -> Vec<u8>); 2] = [("gzip", gzip), ("br", brotli)];
+    for (i, (encoding, compress)) in encodings.into_iter().enumerate() {
+        let start = Utc::now() - Duration::hours(3 + 2 * i as i64);
+        let mut workout_data = WorkoutData::new_with_hr_freq(WorkoutIntensity::Hard, start, 60, Some(1));
+        approve_workout_upload(&client, &test_app.address, &user.token, &mut workout_data).await.unwrap();
+        let body = serde_json::to_vec(&workout_data).unwrap();
+        let compressed = compress(&body);
+        assert!(compressed.len() < body.len() / 4);
+
+        let response = post_compressed_upload(&client, &test_app.address, &user.token, encoding, compressed).await;
+        let status = response.status().as_u16();
+        assert_eq!(200, status, "{} upload failed: {}", encoding, response.text().await.unwrap());
+    }
+
+    // The size cap counts decompressed bytes, so small archives of huge bodies are refused
+    let max_payload_bytes = get_config().expect("Failed to read configuration.").upload_limits.max_payload_bytes;
+    let mut inflated = b"{\"padding\":\"".to_vec();
+    inflated.resize(max_payload_bytes + 1024, b'a');
+    inflated.extend_from_slice(b"\"}");
+    let compressed = gzip(&inflated);
+    assert!(compressed.len() < max_payload_bytes / 100);
+
+    let response = post_compressed_upload(&client, &test_app.address, &user.token, "gzip", compressed).await;
+    assert_eq!(413, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["limit"], "payload_bytes");
+}
+
+#[tokio::test]
+async fn history_and_feed_are_compressed_when_accepted() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    for path in ["/health/history", "/feed/"] {
+        let response = client
+            .get(format!("{}{}", test_app.address, path))
+            .header("Authorization", format!("Bearer {}", user.token))
+            .header("Accept-Encoding", "gzip")
+            .send()
+            .await
+            .expect("Failed to execute request");
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(response.headers()["content-encoding"], "gzip", "{} was not compressed", path);
+
+        let compressed = response.bytes().await.unwrap();
+        let mut decompressed = String::new();
+        GzDecoder::new(&compressed[..]).read_to_string(&mut decompressed).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&decompressed).unwrap();
+        assert_eq!(body["success"], true, "{} returned {}", path, body);
+    }
+
+    // Clients that do not ask for compression get plain JSON
+    let response = client
+        .get(format!("{}/health/history", test_app.address))
+        .header("Authorization", format!("Bearer {}", user.token))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert!(response.headers().get("content-encoding").is_none());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["success"], json!(true));
+}
@@ -0,0 +1,80 @@
+//! Workout upload limit tests
+//!
+//! Covers the caps on `/health/upload_health`:
+//! - Bodies above the configured size are rejected with a 413 naming the byte limit
+//! - Workouts with too many heart rate samples are rejected with a 413 naming the sample limit
+//! - Malformed bodies are still a 400, and uploads within the limits go through
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::config::settings::get_config;
+
+mod common;
+use common::utils::spawn_app;
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+async fn post_raw_upload(client: &Client, address: &str, token: &str, body: Vec<u8>) -> reqwest::Response {
+    client
+        .post(format!("{}/health/upload_health", address))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to execute request")
+}
+
+#[tokio::test]
+async fn uploads_over_the_limits_name_the_exceeded_limit() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+    let limits = get_config().expect("Failed to read configuration.").upload_limits;
+
+    // Heart rate series longer than the cap, in a body that is well within the byte limit
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    let start = workout_data.workout_start;
+    workout_data.heart_rate = (0..=limits.max_heart_rate_samples as i64)
+        .map(|i| json!({ "timestamp": start + Duration::milliseconds(i * 20), "heart_rate": 120 }))
+        .collect();
+    let body = serde_json::to_vec(&workout_data).unwrap();
+    assert!(body.len() < limits.max_payload_bytes);
+
+    let response = post_raw_upload(&client, &test_app.address, &user.token, body).await;
+    assert_eq!(413, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["data"]["limit"], "heart_rate_samples");
+    assert_eq!(body["data"]["max"], limits.max_heart_rate_samples);
+
+    // Body larger than the byte cap
+    let mut oversized = b"{\"padding\":\"".to_vec();
+    oversized.resize(limits.max_payload_bytes + 1024, b'a');
+    oversized.extend_from_slice(b"\"}");
+    let announced = oversized.len();
+
+    let response = post_raw_upload(&client, &test_app.address, &user.token, oversized).await;
+    assert_eq!(413, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["limit"], "payload_bytes");
+    assert_eq!(body["data"]["max"], limits.max_payload_bytes);
+    assert_eq!(body["data"]["received"], announced);
+}
+
+#[tokio::test]
+async fn uploads_within_the_limits_are_parsed_as_before() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let response = post_raw_upload(&client, &test_app.address, &user.token, b"{\"heart_rate\": [".to_vec()).await;
+    assert_eq!(400, response.status().as_u16());
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(3), 30);
+    let response = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data).await;
+    assert!(response.is_ok(), "Upload within the limits failed: {:?}", response.err());
+}
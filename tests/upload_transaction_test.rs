@@ -0,0 +1,205 @@
+//! Workout upload transaction tests
+//!
+//! The workout, its post, the avatar stats and the game event are written in one transaction:
+//! - A successful upload stores all of them and publishes the event through the outbox
+//! - A retried upload of a stored workout is rejected without touching the avatar stats again
+//! - Outbox events left unpublished are published by `publish_pending`
+//! - Published outbox events are pruned after the retention, unpublished ones are kept
+
+use chrono::{Duration, Utc};
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::services::{event_outbox, EventOutbox};
+
+mod common;
+use common::redis_helpers::setup_redis_pubsub;
+use common::utils::{make_authenticated_request, spawn_app};
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+#[tokio::test]
+async fn upload_stores_workout_post_and_event_together() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut pubsub = setup_redis_pubsub(&format!("game:events:user:{}", user.user_id)).await;
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    let response = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Upload failed");
+    let sync_id: Uuid = response["data"]["sync_id"].as_str().unwrap().parse().unwrap();
+
+    let workout = sqlx::query!("SELECT stamina_gained, ml_classified_at FROM workout_data WHERE id = $1", sync_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert!(workout.stamina_gained > 0.0, "The workout is stored with its final stats");
+    assert!(workout.ml_classified_at.is_some());
+
+    let posts = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!" FROM posts WHERE workout_id = $1"#, sync_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(posts, 1);
+
+    let message = tokio::time::timeout(std::time::Duration::from_secs(5), pubsub.on_message().next())
+        .await
+        .expect("No game event was published")
+        .unwrap();
+    let event: serde_json::Value = serde_json::from_str(&message.get_payload::<String>().unwrap()).unwrap();
+    assert_eq!(event["event_type"], "workout_data_processed");
+    assert_eq!(event["sync_id"], sync_id.to_string());
+
+    let published_at = sqlx::query_scalar!(
+        "SELECT published_at FROM event_outbox WHERE payload->>'sync_id' = $1",
+        sync_id.to_string()
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert!(published_at.is_some());
+}
+
+#[tokio::test]
+async fn retried_upload_does_not_apply_stats_twice() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+    let user_id: Uuid = user.user_id;
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Hard, Utc::now() - Duration::hours(3), 45);
+    upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Upload failed");
+
+    let stamina_after_upload = sqlx::query_scalar!("SELECT stamina FROM user_avatars WHERE user_id = $1", user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    // Same request again, approval token included
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/upload_health", test_app.address),
+        &user.token,
+        Some(json!(workout_data)),
+    )
+    .await;
+    assert!(!response.status().is_success());
+
+    let stamina_after_retry = sqlx::query_scalar!("SELECT stamina FROM user_avatars WHERE user_id = $1", user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(stamina_after_upload, stamina_after_retry);
+
+    let (workouts, events) = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM workout_data WHERE user_id = $1) as "workouts!",
+            (SELECT COUNT(*) FROM event_outbox WHERE payload->>'user_id' = $1::text) as "events!"
+        "#,
+        user_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .map(|row| (row.workouts, row.events))
+    .unwrap();
+    assert_eq!(workouts, 1);
+    assert_eq!(events, 1);
+}
+
+#[tokio::test]
+async fn pending_outbox_events_are_published_later() {
+    let test_app = spawn_app().await;
+    let channel = format!("test:outbox:{}", Uuid::new_v4());
+    let mut pubsub = setup_redis_pubsub(&channel).await;
+
+    let mut conn = test_app.db_pool.acquire().await.unwrap();
+    let event_id = event_outbox::enqueue(&mut conn, std::slice::from_ref(&channel), &json!({ "event_type": "outbox_test" }))
+        .await
+        .unwrap();
+    drop(conn);
+
+    // Without Redis nothing goes out and the event stays pending
+    let published = EventOutbox::new(test_app.db_pool.clone(), None).publish(&[event_id]).await.unwrap();
+    assert_eq!(published, 0);
+
+    let redis_client = redis_client();
+    let outbox = EventOutbox::new(test_app.db_pool.clone(), Some(redis_client));
+    assert!(outbox.publish_pending().await.unwrap() >= 1);
+
+    let message = tokio::time::timeout(std::time::Duration::from_secs(5), pubsub.on_message().next())
+        .await
+        .expect("The pending event was not published")
+        .unwrap();
+    let event: serde_json::Value = serde_json::from_str(&message.get_payload::<String>().unwrap()).unwrap();
+    assert_eq!(event["event_type"], "outbox_test");
+
+    // Published events are not published again
+    assert_eq!(outbox.publish(&[event_id]).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn published_outbox_events_are_pruned_after_retention() {
+    let test_app = spawn_app().await;
+    let pool = &test_app.db_pool;
+    let retention = format!("{} days", event_outbox::PUBLISHED_RETENTION_DAYS + 1);
+
+    let mut event_ids = Vec::new();
+    let mut conn = pool.acquire().await.unwrap();
+    for _ in 0..3 {
+        let event_id = event_outbox::enqueue(&mut conn, &["test:outbox:prune".to_string()], &json!({ "event_type": "outbox_test" }))
+            .await
+            .unwrap();
+        event_ids.push(event_id);
+    }
+    drop(conn);
+    let (old_published, recent_published, old_unpublished) = (event_ids[0], event_ids[1], event_ids[2]);
+    sqlx::query("UPDATE event_outbox SET published_at = NOW() - $2::interval WHERE id = $1")
+        .bind(old_published)
+        .bind(&retention)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE event_outbox SET published_at = NOW() WHERE id = $1")
+        .bind(recent_published)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE event_outbox SET created_at = NOW() - $2::interval WHERE id = $1")
+        .bind(old_unpublished)
+        .bind(&retention)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let outbox = EventOutbox::new(pool.clone(), None);
+    assert!(outbox.prune_published().await.unwrap() >= 1);
+
+    let remaining: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM event_outbox WHERE id = ANY($1)")
+        .bind(&event_ids)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+    assert!(!remaining.contains(&old_published));
+    assert!(remaining.contains(&recent_published));
+    assert!(remaining.contains(&old_unpublished), "Unpublished events are kept for retries");
+}
+
+fn redis_client() -> std::sync::Arc<redis::Client> {
+    use riina_backend::config::{redis::RedisSettings, settings::get_config};
+    use secrecy::ExposeSecret;
+
+    let configuration = get_config().expect("Failed to read configuration.");
+    let client = redis::Client::open(RedisSettings::get_redis_url(&configuration.redis).expose_secret())
+        .expect("Failed to create Redis client");
+    std::sync::Arc::new(client)
+}
@@ -0,0 +1,89 @@
+//! User activity stats cache tests
+//!
+//! Covers the Redis cache behind profile stats and the league leaderboard:
+//! - Repeated profile views are served from the cache instead of re-aggregating workouts
+//! - Uploading a workout invalidates the uploader's cached stats
+//! - Deleting a workout (admin) invalidates the owner's cached stats
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, TestApp, UserRegLoginResponse};
+use common::admin_helpers::create_admin_user_and_login;
+use common::workout_data_helpers::{
+    create_test_user_with_health_profile, upload_workout_data_for_user, WorkoutData, WorkoutIntensity,
+};
+
+async fn profile_trailing_average(test_app: &TestApp, client: &Client, user: &UserRegLoginResponse) -> f64 {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/profile/user", test_app.address), &user.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]["trailing_average"].as_f64().unwrap()
+}
+
+async fn leaderboard_trailing_average(test_app: &TestApp, client: &Client, user: &UserRegLoginResponse) -> f64 {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/league/users/stats?page_size=200", test_app.address), &user.token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["user_id"] == user.user_id.to_string())
+        .map(|entry| entry["trailing_average"].as_f64().unwrap())
+        .unwrap_or(0.0)
+}
+
+async fn upload_workout(test_app: &TestApp, client: &Client, user: &UserRegLoginResponse, hours_ago: i64) -> String {
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Intense, Utc::now() - Duration::hours(hours_ago), 30);
+    let response = upload_workout_data_for_user(client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Workout upload should succeed");
+    response["data"]["sync_id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn cached_stats_are_invalidated_when_workouts_change() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let first_workout_id = upload_workout(&test_app, &client, &user, 30).await;
+    let after_upload = profile_trailing_average(&test_app, &client, &user).await;
+    assert!(after_upload > 0.0);
+
+    // Writes that bypass the API are not picked up until an event invalidates the entry
+    sqlx::query!(
+        "UPDATE workout_data SET stamina_gained = stamina_gained + 70 WHERE user_id = $1",
+        user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(profile_trailing_average(&test_app, &client, &user).await, after_upload);
+
+    // A new upload drops the cached stats, so both workouts count with their current points
+    let second_workout_id = upload_workout(&test_app, &client, &user, 20).await;
+    let after_second_upload = profile_trailing_average(&test_app, &client, &user).await;
+    assert!(
+        after_second_upload >= after_upload + 10.0 - 1e-3,
+        "Expected the direct update to be visible after the upload: {} -> {}", after_upload, after_second_upload
+    );
+
+    // Admin deletions invalidate the owner's stats as well
+    for workout_id in [first_workout_id, second_workout_id] {
+        let response = make_authenticated_request(
+            &client, reqwest::Method::DELETE,
+            &format!("{}/admin/workouts/{}", test_app.address, workout_id), &admin.token, None,
+        ).await;
+        assert_eq!(200, response.status().as_u16());
+    }
+    assert_eq!(profile_trailing_average(&test_app, &client, &user).await, 0.0);
+    assert_eq!(leaderboard_trailing_average(&test_app, &client, &user).await, 0.0);
+}
@@ -0,0 +1,118 @@
+//! User suspension tests
+//!
+//! - Suspensions last a validated number of hours, or until lifted for soft bans
+//! - Suspended users can read but not change anything, and see why in `/profile/status`
+//! - Lifting a suspension restores write access
+
+use chrono::{Duration, Utc};
+use reqwest::{Client, Method};
+use serde_json::json;
+use uuid::Uuid;
+
+use riina_backend::models::suspension::{CreateSuspensionRequest, UserSuspension, MAX_SUSPENSION_HOURS};
+
+mod common;
+use common::admin_helpers::create_admin_user_and_login;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+#[test]
+fn suspensions_are_validated_and_expire() {
+    let request = |reason: &str, duration_hours| CreateSuspensionRequest { reason: reason.to_string(), duration_hours };
+    assert!(request("Spam", Some(48)).validate().is_ok());
+    assert!(request("Spam", None).validate().is_ok());
+    assert!(request("  ", Some(48)).validate().is_err());
+    assert!(request("Spam", Some(0)).validate().is_err());
+    assert!(request("Spam", Some(MAX_SUSPENSION_HOURS + 1)).validate().is_err());
+
+    let now = Utc::now();
+    assert_eq!(request("Spam", Some(48)).ends_at(now), Some(now + Duration::hours(48)));
+    assert_eq!(request("Spam", None).ends_at(now), None);
+
+    let suspension = UserSuspension {
+        id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        reason: "Spam".to_string(),
+        starts_at: now,
+        ends_at: Some(now + Duration::hours(48)),
+        created_by: None,
+        lifted_at: None,
+        lifted_by: None,
+        lift_reason: None,
+        created_at: now,
+    };
+    assert!(suspension.is_active_at(now + Duration::hours(1)));
+    assert!(!suspension.is_active_at(now + Duration::hours(48)));
+    assert!(!suspension.is_active_at(now - Duration::hours(1)));
+
+    let lifted = UserSuspension { lifted_at: Some(now + Duration::hours(2)), ..suspension.clone() };
+    assert!(!lifted.is_active_at(now + Duration::hours(3)));
+
+    let soft_ban = UserSuspension { ends_at: None, ..suspension };
+    assert!(soft_ban.is_active_at(now + Duration::days(3650)));
+}
+
+#[tokio::test]
+async fn suspended_users_are_read_only_until_lifted() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let suspend = make_authenticated_request(
+        &client,
+        Method::POST,
+        &format!("{}/admin/users/{}/suspensions", test_app.address, user.user_id),
+        &admin.token,
+        Some(json!({ "reason": "Repeated spam in team chat", "duration_hours": 72 })),
+    )
+    .await;
+    assert_eq!(suspend.status(), 201);
+    let suspension: serde_json::Value = suspend.json().await.unwrap();
+    let suspension_id = suspension["data"]["id"].as_str().unwrap().to_string();
+
+    let again = make_authenticated_request(
+        &client,
+        Method::POST,
+        &format!("{}/admin/users/{}/suspensions", test_app.address, user.user_id),
+        &admin.token,
+        Some(json!({ "reason": "Again" })),
+    )
+    .await;
+    assert_eq!(again.status(), 409);
+
+    let consent_url = format!("{}/profile/consent", test_app.address);
+    let update_consent = || {
+        make_authenticated_request(
+            &client,
+            Method::PATCH,
+            &consent_url,
+            &user.token,
+            Some(json!({ "research_data_sharing": true })),
+        )
+    };
+    assert_eq!(update_consent().await.status(), 403);
+
+    let status = make_authenticated_request(
+        &client,
+        Method::GET,
+        &format!("{}/profile/status", test_app.address),
+        &user.token,
+        None,
+    )
+    .await;
+    assert_eq!(status.status(), 200);
+    let status: serde_json::Value = status.json().await.unwrap();
+    assert_eq!(status["data"]["suspension"]["reason"], "Repeated spam in team chat");
+
+    let lift = make_authenticated_request(
+        &client,
+        Method::POST,
+        &format!("{}/admin/suspensions/{}/lift", test_app.address, suspension_id),
+        &admin.token,
+        Some(json!({ "reason": "Appeal accepted" })),
+    )
+    .await;
+    assert_eq!(lift.status(), 200);
+
+    assert_eq!(update_consent().await.status(), 200);
+}
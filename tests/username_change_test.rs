@@ -0,0 +1,136 @@
+//! Username change tests
+//!
+//! Covers changing usernames and checking their availability:
+//! - `/registration/check-username` reports taken and malformed names
+//! - `PATCH /profile/username` renames the user, records the change and renames score events
+//! - a second change within the cooldown is refused, as is a name someone else uses
+
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, generate_valid_username_suffix, make_authenticated_request};
+
+#[tokio::test]
+async fn check_username_reports_availability() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let check = |username: String| {
+        let client = client.clone();
+        let url = format!("{}/registration/check-username", test_app.address);
+        async move {
+            let response = client.get(&url).query(&[("username", username)]).send().await.unwrap();
+            assert_eq!(200, response.status().as_u16());
+            let body: serde_json::Value = response.json().await.unwrap();
+            body["data"].clone()
+        }
+    };
+
+    let free = check(format!("free{}", generate_valid_username_suffix())).await;
+    assert_eq!(free["available"], true);
+    assert!(free["reason"].is_null());
+
+    // Taken names are found regardless of case
+    let taken = check(user.username.to_uppercase()).await;
+    assert_eq!(taken["available"], false);
+    assert_eq!(taken["reason"], "Username already exists");
+
+    let malformed = check("no spaces".to_string()).await;
+    assert_eq!(malformed["available"], false);
+    assert!(malformed["reason"].as_str().unwrap().contains("letters, numbers"));
+}
+
+#[tokio::test]
+async fn change_username_renames_user_and_score_events() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    sqlx::query(
+        "INSERT INTO live_score_events (user_id, username, team_id, team_side, score_points, power_contribution, description)
+         VALUES ($1, $2, $3, 'home', 10, 0, 'Morning ride')"
+    )
+    .bind(user.user_id)
+    .bind(&user.username)
+    .bind(Uuid::new_v4())
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let new_username = format!("renamed{}", generate_valid_username_suffix());
+    let url = format!("{}/profile/username", test_app.address);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &url, &user.token, Some(json!({ "username": new_username })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["username"], new_username);
+    assert!(body["data"]["next_change_at"].is_string());
+
+    // The fresh token works and carries the new name
+    let new_token = body["data"]["token"].as_str().unwrap().to_string();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/profile/user", test_app.address), &new_token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let (username,): (String,) = sqlx::query_as("SELECT username FROM users WHERE id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(username, new_username);
+
+    let (old_username, recorded_new): (String, String) = sqlx::query_as(
+        "SELECT old_username, new_username FROM username_history WHERE user_id = $1"
+    )
+    .bind(user.user_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(old_username, user.username);
+    assert_eq!(recorded_new, new_username);
+
+    let (event_username,): (String,) = sqlx::query_as("SELECT username FROM live_score_events WHERE user_id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(event_username, new_username);
+
+    // Once per cooldown period
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &url, &new_token,
+        Some(json!({ "username": format!("again{}", generate_valid_username_suffix()) })),
+    ).await;
+    assert_eq!(429, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn change_username_to_a_taken_name_fails() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let other = create_test_user_and_login(&test_app.address).await;
+
+    let url = format!("{}/profile/username", test_app.address);
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &url, &user.token, Some(json!({ "username": other.username.to_uppercase() })),
+    ).await;
+    assert_eq!(409, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &url, &user.token, Some(json!({ "username": "bad name" })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    let (changes,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM username_history WHERE user_id = $1")
+        .bind(user.user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(changes, 0);
+}
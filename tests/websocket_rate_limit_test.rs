@@ -0,0 +1,93 @@
+//! Game WebSocket message rate limiting tests
+//!
+//! - The leaky bucket lets bursts through and then only the drain rate
+//! - Clients flooding the socket get a single `rate_limited` frame and stay connected
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use riina_backend::utils::leaky_bucket::LeakyBucket;
+
+mod common;
+use common::utils::{create_test_user_and_login, spawn_app};
+
+#[test]
+fn bursts_pass_until_the_bucket_is_full() {
+    let start = Instant::now();
+    let mut bucket = LeakyBucket::new(3, 2.0, start);
+
+    assert!((0..3).all(|_| bucket.try_acquire(start).is_ok()));
+    let retry_after = bucket.try_acquire(start).unwrap_err();
+    assert_eq!(retry_after, Duration::from_millis(500));
+}
+
+#[test]
+fn the_bucket_drains_at_its_rate() {
+    let start = Instant::now();
+    let mut bucket = LeakyBucket::new(2, 1.0, start);
+    assert!(bucket.try_acquire(start).is_ok());
+    assert!(bucket.try_acquire(start).is_ok());
+    assert!(bucket.try_acquire(start).is_err());
+
+    // One message drained after a second, both after two
+    let later = start + Duration::from_secs(1);
+    assert!(bucket.try_acquire(later).is_ok());
+    assert!(bucket.try_acquire(later).is_err());
+
+    let much_later = start + Duration::from_secs(60);
+    assert!(bucket.try_acquire(much_later).is_ok());
+    assert!(bucket.try_acquire(much_later).is_ok());
+    assert!(bucket.try_acquire(much_later).is_err(), "An idle bucket doesn't bank more than its capacity");
+}
+
+#[tokio::test]
+async fn flooding_clients_are_rate_limited_but_stay_connected() {
+    let test_app = spawn_app().await;
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    let ws_url = format!("{}/game-ws?token={}", test_app.address.replace("http", "ws"), user.token);
+    let (mut ws_stream, _) = connect_async(ws_url.into_client_request().unwrap())
+        .await
+        .expect("Failed to connect to WebSocket server");
+
+    for _ in 0..30 {
+        ws_stream
+            .send(Message::Text(json!({ "type": "request_leaderboard" }).to_string()))
+            .await
+            .unwrap();
+    }
+
+    let (mut leaderboards, mut rate_limited) = (0, 0);
+    while let Ok(Some(Ok(message))) = tokio::time::timeout(Duration::from_secs(2), ws_stream.next()).await {
+        if let Message::Text(text) = message {
+            let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+            match event["event_type"].as_str() {
+                Some("leaderboard_update") => leaderboards += 1,
+                Some("rate_limited") => {
+                    rate_limited += 1;
+                    assert!(event["retry_after_ms"].as_u64().unwrap() > 0);
+                }
+                _ => {}
+            }
+        }
+    }
+    assert!(leaderboards < 30, "Some of the flood was dropped");
+    assert_eq!(rate_limited, 1, "One rate_limited frame per limited stretch");
+
+    // Once the bucket drained, messages are answered again
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    ws_stream
+        .send(Message::Text(json!({ "type": "request_leaderboard" }).to_string()))
+        .await
+        .unwrap();
+    let message = tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+        .await
+        .expect("No answer after the bucket drained")
+        .unwrap()
+        .unwrap();
+    assert!(message.to_text().unwrap().contains("leaderboard_update"));
+}
@@ -0,0 +1,103 @@
+//! Game WebSocket token renewal tests
+//!
+//! - `refresh_token` messages swap in a fresh token of the same user without reconnecting
+//! - Tokens of a logged out session are refused, as they are when connecting
+
+use chrono::{DateTime, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use riina_backend::config::settings::{get_config, get_jwt_settings};
+use riina_backend::middleware::auth::Claims;
+
+mod common;
+use common::utils::{create_test_user_and_login, make_authenticated_request, spawn_app};
+
+async fn next_event<S>(ws_stream: &mut S, event_type: &str) -> serde_json::Value
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .unwrap_or_else(|_| panic!("No {event_type} event received"))
+            .expect("WebSocket closed")
+            .expect("WebSocket error");
+        if let Message::Text(text) = message {
+            let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if event["event_type"] == event_type {
+                return event;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn spectators_renew_their_token_in_band() {
+    let test_app = spawn_app().await;
+    let spectator = create_test_user_and_login(&test_app.address).await;
+    let other_user = create_test_user_and_login(&test_app.address).await;
+
+    let ws_url = format!("{}/game-ws?token={}", test_app.address.replace("http", "ws"), spectator.token);
+    let (mut ws_stream, _) = connect_async(ws_url.into_client_request().unwrap())
+        .await
+        .expect("Failed to connect to WebSocket server");
+
+    ws_stream
+        .send(Message::Text(json!({ "type": "refresh_token", "token": spectator.token }).to_string()))
+        .await
+        .unwrap();
+    let refreshed = next_event(&mut ws_stream, "token_refreshed").await;
+    let expires_at: DateTime<Utc> = refreshed["expires_at"].as_str().unwrap().parse().unwrap();
+    assert!(expires_at > Utc::now());
+
+    ws_stream
+        .send(Message::Text(json!({ "type": "refresh_token", "token": other_user.token }).to_string()))
+        .await
+        .unwrap();
+    let failed = next_event(&mut ws_stream, "token_refresh_failed").await;
+    assert_eq!(failed["error"], "Token belongs to another user");
+
+    ws_stream
+        .send(Message::Text(json!({ "type": "refresh_token", "token": "not-a-jwt" }).to_string()))
+        .await
+        .unwrap();
+    let failed = next_event(&mut ws_stream, "token_refresh_failed").await;
+    assert_eq!(failed["error"], "Invalid token");
+
+    // A token of another device of the user, logged out since
+    let client = reqwest::Client::new();
+    let login: serde_json::Value = client
+        .post(format!("{}/login", test_app.address))
+        .json(&json!({ "username": spectator.username, "password": "password123" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let logged_out_token = login["token"].as_str().unwrap().to_string();
+    let claims: Claims = get_jwt_settings(&get_config().unwrap()).verify(&logged_out_token).unwrap();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::DELETE,
+        &format!("{}/auth/sessions/{}", test_app.address, claims.sid.unwrap()), &spectator.token, None,
+    ).await;
+    assert_eq!(response.status(), 200);
+    ws_stream
+        .send(Message::Text(json!({ "type": "refresh_token", "token": logged_out_token }).to_string()))
+        .await
+        .unwrap();
+    let failed = next_event(&mut ws_stream, "token_refresh_failed").await;
+    assert_eq!(failed["error"], "Session has been logged out");
+
+    // Failed renewals keep the connection on its current token
+    ws_stream
+        .send(Message::Text(json!({ "type": "request_leaderboard" }).to_string()))
+        .await
+        .unwrap();
+    next_event(&mut ws_stream, "leaderboard_update").await;
+}
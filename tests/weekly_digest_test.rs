@@ -0,0 +1,211 @@
+//! Weekly digest tests
+//!
+//! Covers the digest composed by the weekly scheduler job:
+//! - Own stats, team result, league movers and next fixture for the past week
+//! - Delivery into the notification center and `/notifications/digests`
+//! - Opting out via `/notifications/preferences`
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+use riina_backend::services::WeeklyDigestService;
+use riina_backend::services::weekly_digest_service::week_start_of;
+
+#[tokio::test]
+async fn weekly_digest_summarizes_the_past_week() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let player_a = create_test_user_and_login(&test_app.address).await;
+    let player_b = create_test_user_and_login(&test_app.address).await;
+    let player_a_id = parse_user_id_from_jwt_token(&player_a.token);
+    let player_b_id = parse_user_id_from_jwt_token(&player_b.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![player_a_id, player_b_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Digest Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+    let team_a = Uuid::parse_str(&league.team_ids[0]).unwrap();
+    let team_b = Uuid::parse_str(&league.team_ids[1]).unwrap();
+
+    let week_start = week_start_of(Utc::now().date_naive());
+    let period_start = week_start.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(7);
+
+    // Player A trained twice last week
+    for _ in 0..2 {
+        sqlx::query(
+            r#"
+            INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                      workout_start, workout_end, duration_minutes, stamina_gained)
+            VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, 30, 4.0)
+            "#
+        )
+        .bind(player_a_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(period_start + Duration::days(2))
+        .bind(period_start + Duration::days(2) + Duration::minutes(30))
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to insert workout");
+    }
+
+    // Team A won its game last week
+    let game = sqlx::query!(
+        r#"
+        UPDATE games
+        SET status = 'evaluated', game_start_time = $2, game_end_time = $3,
+            home_score = CASE WHEN home_team_id = $4 THEN 12 ELSE 8 END,
+            away_score = CASE WHEN away_team_id = $4 THEN 12 ELSE 8 END
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+        RETURNING id
+        "#,
+        season_id,
+        period_start,
+        period_start + Duration::days(5),
+        team_a
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to finish game");
+
+    // Team A climbed from second to first since the previous snapshot
+    for (team_id, previous, current) in [(team_a, 2, 1), (team_b, 1, 2)] {
+        sqlx::query("UPDATE league_standings SET position = $3 WHERE season_id = $1 AND team_id = $2")
+            .bind(season_id)
+            .bind(team_id)
+            .bind(current)
+            .execute(&test_app.db_pool)
+            .await
+            .expect("Failed to update standings");
+        sqlx::query(
+            r#"
+            INSERT INTO league_standing_snapshots (season_id, team_id, week_start, position)
+            VALUES ($1, $2, $3, $4)
+            "#
+        )
+        .bind(season_id)
+        .bind(team_id)
+        .bind(week_start - Duration::days(7))
+        .bind(previous)
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to insert standings snapshot");
+    }
+
+    let service = WeeklyDigestService::new(test_app.db_pool.clone());
+    let digest = service
+        .generate_for_user(player_a_id, week_start)
+        .await
+        .expect("Failed to generate digest")
+        .expect("Digest should be created");
+    assert_eq!(digest.delivered_channels, vec!["in_app".to_string()], "No push token is registered");
+
+    // A second run in the same week does not duplicate the digest
+    let again = service.generate_for_user(player_a_id, week_start).await.expect("Failed to rerun digest");
+    assert!(again.is_none());
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/notifications/digests", test_app.address),
+        &player_a.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let digests = body.as_array().unwrap();
+    assert_eq!(digests.len(), 1);
+
+    let content = &digests[0]["content"];
+    assert_eq!(content["stats"]["workouts"], 2);
+    assert_eq!(content["stats"]["active_minutes"], 60);
+    assert_eq!(content["team_id"], team_a.to_string().as_str());
+    assert_eq!(content["team_result"]["game_id"], game.id.to_string().as_str());
+    assert_eq!(content["team_result"]["outcome"], "win");
+    assert_eq!(content["team_result"]["team_score"], 12);
+
+    let movers = content["league_movers"].as_array().unwrap();
+    assert_eq!(movers.len(), 2);
+    let team_a_move = movers.iter().find(|m| m["team_id"] == team_a.to_string().as_str()).unwrap();
+    assert_eq!(team_a_move["change"], 1);
+
+    if let Some(fixture) = content["next_fixture"].as_object() {
+        assert_ne!(fixture["game_id"], json!(game.id), "The evaluated game is not the next fixture");
+    }
+
+    let notification_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE recipient_id = $1 AND notification_type = 'weekly_digest'"
+    )
+    .bind(player_a_id)
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(notification_count, 1);
+}
+
+#[tokio::test]
+async fn weekly_digest_respects_notification_preferences() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let user_id = parse_user_id_from_jwt_token(&user.token);
+    let week_start = week_start_of(Utc::now().date_naive());
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/notifications/preferences", test_app.address),
+        &user.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["weekly_digest_enabled"], true, "Digests are on by default");
+
+    // Keep the digest but turn off the in-app channel
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/notifications/preferences", test_app.address),
+        &user.token,
+        Some(json!({ "in_app_enabled": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["in_app_enabled"], false);
+    assert_eq!(body["push_enabled"], true, "Omitted fields keep their value");
+
+    let service = WeeklyDigestService::new(test_app.db_pool.clone());
+    let digest = service
+        .generate_for_user(user_id, week_start)
+        .await
+        .expect("Failed to generate digest")
+        .expect("Digest should be created");
+    assert!(digest.delivered_channels.is_empty());
+    assert!(digest.content["team_id"].is_null(), "User without a team gets stats only");
+
+    // Opting out of digests entirely skips next week's digest
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/notifications/preferences", test_app.address),
+        &user.token,
+        Some(json!({ "weekly_digest_enabled": false })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let next_week = service
+        .generate_for_user(user_id, week_start + Duration::days(7))
+        .await
+        .expect("Failed to generate digest");
+    assert!(next_week.is_none());
+}
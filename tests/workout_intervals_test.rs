@@ -0,0 +1,72 @@
+//! Workout interval detection tests
+//!
+//! Covers splitting a workout into work and rest segments from its heart rate,
+//! and leaving steady workouts without intervals.
+
+use chrono::{DateTime, Duration, Utc};
+
+use riina_backend::models::workout_data::HeartRateData;
+use riina_backend::workout::intervals::{detect_intervals, SegmentKind};
+
+/// One sample every 5 seconds; `hr_at` gives the heart rate for each second offset
+fn samples(start: DateTime<Utc>, seconds: i64, hr_at: impl Fn(i64) -> i32) -> Vec<HeartRateData> {
+    (0..=seconds / 5)
+        .map(|i| HeartRateData { timestamp: start + Duration::seconds(i * 5), heart_rate: hr_at(i * 5) })
+        .collect()
+}
+
+/// 5 minutes warm-up, then 8 rounds of 2 minutes work and 1 minute rest
+fn hiit(second: i64) -> i32 {
+    if second < 300 {
+        return 110;
+    }
+    if (second - 300) % 180 < 120 { 170 } else { 120 }
+}
+
+#[test]
+fn hiit_session_is_split_into_work_and_rest() {
+    let analysis = detect_intervals(&samples(Utc::now(), 300 + 8 * 180, hiit)).expect("HIIT should have intervals");
+
+    assert_eq!(analysis.work_intervals, 8);
+    assert_eq!(analysis.summary, "8 × 2min high intensity");
+    assert!((analysis.typical_work_seconds - 120).abs() <= 10, "got {}", analysis.typical_work_seconds);
+
+    let work: Vec<_> = analysis.segments.iter().filter(|s| s.kind == SegmentKind::Work).collect();
+    assert!(work.iter().all(|s| s.max_heart_rate == 170));
+    assert_eq!(analysis.segments.first().unwrap().kind, SegmentKind::Rest, "Warm-up is not work");
+}
+
+#[test]
+fn steady_workout_has_no_intervals() {
+    let analysis = detect_intervals(&samples(Utc::now(), 1800, |s| 140 + (s / 5 % 3) as i32));
+
+    assert_eq!(analysis, None);
+}
+
+#[test]
+fn short_spikes_do_not_split_intervals() {
+    // A 5 second dropout in every work interval is smoothed away
+    let analysis = detect_intervals(&samples(Utc::now(), 300 + 8 * 180, |s| {
+        if s > 300 && (s - 300) % 180 == 60 { 100 } else { hiit(s) }
+    }))
+    .unwrap();
+
+    assert_eq!(analysis.work_intervals, 8);
+}
+
+#[test]
+fn unordered_samples_are_sorted() {
+    let mut data = samples(Utc::now(), 300 + 8 * 180, hiit);
+    data.reverse();
+
+    assert_eq!(detect_intervals(&data).map(|a| a.work_intervals), Some(8));
+}
+
+#[test]
+fn short_labels_use_seconds() {
+    // 10 rounds of 40 seconds work and 40 seconds rest
+    let analysis = detect_intervals(&samples(Utc::now(), 800, |s| if s % 80 < 40 { 175 } else { 115 })).unwrap();
+
+    assert_eq!(analysis.work_intervals, 10);
+    assert_eq!(analysis.summary, "10 × 40s high intensity");
+}
@@ -0,0 +1,160 @@
+//! Workout notes and tags tests
+//!
+//! Covers `PATCH /health/workout/{id}` and where notes show up:
+//! - Owners set notes and tags; tags are normalized, blank notes clear them
+//! - Other users cannot edit the workout
+//! - History filters by tag and searches notes, tags and activity
+//! - Notes and tag matches appear on public posts, and only to the owner on private ones
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_health_profile_for_user};
+
+async fn upload_workout(client: &Client, address: &str, token: &str, hours_ago: i64) -> String {
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(hours_ago), 30);
+    let upload = upload_workout_data_for_user(client, address, token, &mut workout).await.unwrap();
+    upload["data"]["sync_id"].as_str().unwrap().to_string()
+}
+
+async fn history_ids(client: &Client, address: &str, token: &str, query: &str) -> Vec<String> {
+    let response = make_authenticated_request(
+        client, reqwest::Method::GET, &format!("{}/health/history?{}", address, query), token, None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"]["workouts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|workout| workout["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn owners_annotate_workouts_and_filter_history_by_tag() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let other = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+
+    let tempo_run = upload_workout(&client, &test_app.address, &user.token, 3).await;
+    let easy_run = upload_workout(&client, &test_app.address, &user.token, 6).await;
+    let workout_url = |id: &str| format!("{}/health/workout/{}", test_app.address, id);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &workout_url(&tempo_run), &user.token,
+        Some(json!({ "notes": "Negative splits along the river", "tags": ["#Tempo", "intervals ", "tempo", ""] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["tags"], json!(["tempo", "intervals"]));
+    assert_eq!(body["data"]["notes"], "Negative splits along the river");
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &workout_url(&easy_run), &user.token,
+        Some(json!({ "tags": ["recovery"] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    // Only the owner can annotate, within limits
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &workout_url(&tempo_run), &other.token,
+        Some(json!({ "notes": "Not mine" })),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+    let too_many: Vec<String> = (0..11).map(|i| format!("tag{}", i)).collect();
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &workout_url(&tempo_run), &user.token,
+        Some(json!({ "tags": too_many })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+
+    // Tag filter and search
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "tag=%23tempo").await, vec![tempo_run.clone()]);
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "tag=recovery").await, vec![easy_run.clone()]);
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "search=river").await, vec![tempo_run.clone()]);
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "search=RECOV").await, vec![easy_run.clone()]);
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "").await.len(), 2);
+
+    // Blank notes clear them, tags are untouched
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &workout_url(&tempo_run), &user.token,
+        Some(json!({ "notes": "  " })),
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["notes"].is_null());
+    assert_eq!(body["data"]["tags"], json!(["tempo", "intervals"]));
+}
+
+#[tokio::test]
+async fn notes_follow_post_visibility() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let viewer = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+
+    let workout_id = upload_workout(&client, &test_app.address, &user.token, 2).await;
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &format!("{}/health/workout/{}", test_app.address, workout_id), &user.token,
+        Some(json!({ "notes": "Felt strong today", "tags": ["long"] })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/posts/workout/{}", test_app.address, workout_id), &user.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workout_data"]["notes"], "Felt strong today");
+    let post_url = format!("{}/posts/{}", test_app.address, body["data"]["id"].as_str().unwrap());
+
+    // Public post: notes on the feed and the post
+    let history = format!("user_id={}&tag=long", user.user_id);
+    assert_eq!(history_ids(&client, &test_app.address, &viewer.token, &history).await, vec![workout_id.clone()]);
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/feed/?limit=50", test_app.address), &viewer.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let feed_post = body["data"]["posts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|post| post["workout_id"] == workout_id.as_str())
+        .cloned()
+        .expect("Workout post should be in the feed");
+    assert_eq!(feed_post["workout_data"]["notes"], "Felt strong today");
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &post_url, &viewer.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workout_data"]["notes"], "Felt strong today");
+
+    // Private post: only the owner sees them
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &post_url, &user.token, Some(json!({ "visibility": "private" })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &post_url, &viewer.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["workout_data"]["notes"].is_null());
+
+    let response = make_authenticated_request(
+        &client, reqwest::Method::GET, &format!("{}/health/workout/{}", test_app.address, workout_id), &viewer.token, None,
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["notes"].is_null());
+    assert_eq!(body["data"]["tags"], json!([]));
+
+    assert!(history_ids(&client, &test_app.address, &viewer.token, &history).await.is_empty());
+    assert_eq!(history_ids(&client, &test_app.address, &user.token, "tag=long").await, vec![workout_id.clone()]);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &post_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workout_data"]["notes"], "Felt strong today");
+}
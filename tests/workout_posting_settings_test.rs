@@ -0,0 +1,128 @@
+//! Workout auto-posting tests
+//!
+//! - Caption templates only accept known placeholders and render them per workout
+//! - `ask` and `never` keep the post of an upload private, `always` publishes it
+//! - Uploads use the user's caption template and flag posts that await confirmation
+//! - The settings endpoint reads back the saved settings, and the defaults before any were saved
+
+use reqwest::Client;
+use serde_json::json;
+
+use riina_backend::db::workout_data::get_workout_posting_settings;
+use riina_backend::models::post::{AutoPostMode, PostVisibility};
+use riina_backend::workout::post_caption::{render_caption, validate_caption_template, CaptionContext};
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_health_profile_for_user};
+
+#[test]
+fn caption_templates_only_use_known_placeholders() {
+    assert!(validate_caption_template("{activity} for {minutes} min, +{points} points").is_ok());
+    assert!(validate_caption_template("No placeholders").is_ok());
+    assert!(validate_caption_template("{mood} today").is_err());
+    assert!(validate_caption_template("{activity").is_err());
+    assert!(validate_caption_template(&"a".repeat(281)).is_err());
+}
+
+#[test]
+fn captions_fill_in_the_workout() {
+    let context = CaptionContext { activity: "Running", minutes: 45, points: 32, calories: None };
+    assert_eq!(
+        render_caption("{activity} for {minutes} min, +{points} points, {calories} kcal", &context),
+        "Running for 45 min, +32 points, – kcal"
+    );
+    assert_eq!(render_caption("{calories} kcal", &CaptionContext { calories: Some(400), ..context }), "400 kcal");
+}
+
+#[test]
+fn only_always_publishes_the_post() {
+    assert_eq!(AutoPostMode::Always.post_visibility(), PostVisibility::Public);
+    assert_eq!(AutoPostMode::Ask.post_visibility(), PostVisibility::Private);
+    assert_eq!(AutoPostMode::Never.post_visibility(), PostVisibility::Private);
+}
+
+#[tokio::test]
+async fn posting_settings_read_back_what_was_saved() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let user_id = user.user_id;
+    let settings_url = format!("{}/profile/posting-settings", test_app.address);
+
+    // Nothing saved yet: the defaults
+    let mut conn = test_app.db_pool.acquire().await.unwrap();
+    let defaults = get_workout_posting_settings(&mut conn, user_id).await.unwrap();
+    assert_eq!(defaults.auto_post, AutoPostMode::Always);
+    assert!(defaults.caption_template.is_none());
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &settings_url, &user.token, None).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["auto_post"], "always");
+    assert!(body["data"]["caption_template"].is_null());
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PATCH,
+        &settings_url,
+        &user.token,
+        Some(json!({ "auto_post": "never", "caption_template": "{activity} done" })),
+    ).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let saved = get_workout_posting_settings(&mut conn, user_id).await.unwrap();
+    assert_eq!(saved.auto_post, AutoPostMode::Never);
+    assert_eq!(saved.caption_template.as_deref(), Some("{activity} done"));
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &settings_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["auto_post"], "never");
+    assert_eq!(body["data"]["caption_template"], "{activity} done");
+}
+
+#[tokio::test]
+async fn uploads_follow_the_posting_settings() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+    let settings_url = format!("{}/profile/posting-settings", test_app.address);
+
+    let response = make_authenticated_request(&client, reqwest::Method::GET, &settings_url, &user.token, None).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["auto_post"], "always");
+
+    let invalid = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &settings_url, &user.token, Some(json!({ "caption_template": "{mood}" })),
+    ).await;
+    assert_eq!(invalid.status().as_u16(), 400);
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PATCH,
+        &settings_url,
+        &user.token,
+        Some(json!({ "auto_post": "ask", "caption_template": "{minutes} minutes of {activity}" })),
+    ).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let mut workout = WorkoutData::new_with_offset_hours(WorkoutIntensity::Moderate, 2, 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+    assert_eq!(upload["data"]["post_awaiting_confirmation"], true);
+
+    let (visibility, content): (String, Option<String>) =
+        sqlx::query_as("SELECT visibility::text, content FROM posts WHERE id = $1::uuid")
+            .bind(upload["data"]["post_id"].as_str().unwrap())
+            .fetch_one(&test_app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(visibility, "private");
+    assert_eq!(content.as_deref(), Some("30 minutes of Running"));
+
+    // Clearing the template keeps the mode
+    let response = make_authenticated_request(
+        &client, reqwest::Method::PATCH, &settings_url, &user.token, Some(json!({ "caption_template": "" })),
+    ).await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["auto_post"], "ask");
+    assert!(body["data"]["caption_template"].is_null());
+}
@@ -0,0 +1,118 @@
+//! Workout re-upload diff tests
+//!
+//! Covers how a re-sent workout is compared against the stored workout it conflicts with:
+//! - `/health/check_sync_status` returns a diff for every workout rejected as a duplicate
+//! - `/health/workout/{id}/compare` tells identical re-sends from amended data
+//! - Other users cannot compare against someone else's workout
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_test_user_with_health_profile};
+
+#[tokio::test]
+async fn rejected_duplicates_are_diffed_against_the_stored_workout() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::hours(2), 30);
+    workout_data.workout_uuid = Uuid::new_v4().to_string();
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Workout upload should succeed");
+    let stored_id = upload["data"]["sync_id"].as_str().unwrap().to_string();
+
+    // The device re-sends the workout with more calories
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/check_sync_status", test_app.address),
+        &user.token,
+        Some(json!({
+            "workouts": [{
+                "id": "resent",
+                "start": workout_data.workout_start,
+                "end": workout_data.workout_end,
+                "calories": workout_data.calories_burned + 50
+            }]
+        })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["data"]["unsynced_workouts"].as_array().unwrap().is_empty());
+    let duplicates = body["data"]["duplicate_workouts"].as_array().unwrap();
+    assert_eq!(duplicates.len(), 1);
+    let diff = &duplicates[0]["diff"];
+    assert_eq!(duplicates[0]["workout_id"], "resent");
+    assert_eq!(diff["conflicting_workout_id"], stored_id);
+    assert_eq!(diff["identical"], false);
+    assert_eq!(diff["calories"]["changed"], true);
+    assert_eq!(diff["calories"]["existing"], workout_data.calories_burned);
+    assert_eq!(diff["calories"]["incoming"], workout_data.calories_burned + 50);
+    // The sync check carries no samples, so only the stored count is known
+    assert!(diff["samples_count"]["existing"].as_i64().unwrap() > 0);
+    assert_eq!(diff["samples_count"]["changed"], false);
+    assert_eq!(diff["duration_seconds"]["changed"], false);
+
+    // An identical re-send compares as identical
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workout/{}/compare", test_app.address, stored_id),
+        &user.token,
+        Some(json!({
+            "workout_start": workout_data.workout_start,
+            "workout_end": workout_data.workout_end,
+            "calories_burned": workout_data.calories_burned,
+            "heart_rate": workout_data.heart_rate
+        })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["identical"], true);
+    assert_eq!(body["data"]["samples_count"]["existing"], body["data"]["samples_count"]["incoming"]);
+
+    // Amended data: workout extended by ten minutes, with a truncated sample series
+    let amended_end = workout_data.workout_end + Duration::minutes(10);
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workout/{}/compare", test_app.address, stored_id),
+        &user.token,
+        Some(json!({
+            "workout_start": workout_data.workout_start,
+            "workout_end": amended_end,
+            "heart_rate": &workout_data.heart_rate[..10]
+        })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let diff = &body["data"];
+    assert_eq!(diff["identical"], false);
+    assert_eq!(diff["end_offset_seconds"], 600);
+    assert_eq!(diff["duration_seconds"]["changed"], true);
+    assert_eq!(diff["duration_seconds"]["incoming"], 40 * 60);
+    assert_eq!(diff["samples_count"]["incoming"], 10);
+    assert_eq!(diff["samples_count"]["changed"], true);
+    // Calories were not sent, so they do not count as a change
+    assert_eq!(diff["calories"]["changed"], false);
+
+    // Someone else's workout cannot be compared
+    let other_user = create_test_user_and_login(&test_app.address).await;
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workout/{}/compare", test_app.address, stored_id),
+        &other_user.token,
+        Some(json!({
+            "workout_start": workout_data.workout_start,
+            "workout_end": workout_data.workout_end
+        })),
+    ).await;
+    assert_eq!(404, response.status().as_u16());
+}
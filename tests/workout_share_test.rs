@@ -0,0 +1,104 @@
+//! Workout share link tests
+//!
+//! Covers tokenized public links to a single workout:
+//! - Owners create one live link per workout; the public summary needs no login
+//! - The summary carries link preview fields and notes only for public posts
+//! - Revoked links and workouts made private are no longer served
+//! - Other users and private workouts cannot be shared
+
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::json;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request};
+use common::workout_data_helpers::{WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_health_profile_for_user};
+
+#[tokio::test]
+async fn owners_share_and_revoke_public_workout_links() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+    let other = create_test_user_and_login(&test_app.address).await;
+    create_health_profile_for_user(&client, &test_app.address, &user).await.unwrap();
+
+    let mut workout = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now(), 30);
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout).await.unwrap();
+    let workout_id = upload["data"]["sync_id"].as_str().unwrap().to_string();
+    let share_url = format!("{}/health/workout/{}/share", test_app.address, workout_id);
+
+    make_authenticated_request(
+        &client, reqwest::Method::PATCH, &format!("{}/health/workout/{}", test_app.address, workout_id), &user.token,
+        Some(json!({ "notes": "Hill repeats" })),
+    ).await;
+
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &share_url, &user.token, None).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let token = body["data"]["link"]["token"].as_str().unwrap().to_string();
+    let public_url = format!("{}{}", test_app.address, body["data"]["path"].as_str().unwrap());
+
+    // Sharing again returns the live link
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &share_url, &user.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["link"]["token"], token.as_str());
+
+    // Only the owner can share
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &share_url, &other.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    // Anyone with the link sees the summary
+    let response = client.get(&public_url).send().await.unwrap();
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    let summary = &body["data"];
+    assert_eq!(summary["username"], user.username.as_str());
+    assert_eq!(summary["notes"], "Hill repeats");
+    assert!(summary["og"]["title"].as_str().unwrap().starts_with(&format!("{}'s ", user.username)));
+    assert!(summary["og"]["description"].as_str().unwrap().contains("points"));
+    assert!(summary.get("heart_rate_data").is_none());
+
+    let view_count: i32 = sqlx::query_scalar("SELECT view_count FROM workout_share_links WHERE token = $1")
+        .bind(&token)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(view_count, 1);
+
+    // Friends-only post: still shared, notes no longer public
+    sqlx::query("UPDATE posts SET visibility = 'friends' WHERE workout_id = $1::uuid")
+        .bind(&workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let body: serde_json::Value = client.get(&public_url).send().await.unwrap().json().await.unwrap();
+    assert!(body["data"]["notes"].is_null());
+
+    // Private post: the link stops working and no new link can be made
+    sqlx::query("UPDATE posts SET visibility = 'private' WHERE workout_id = $1::uuid")
+        .bind(&workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(404, client.get(&public_url).send().await.unwrap().status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &share_url, &user.token, None).await;
+    assert_eq!(200, response.status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &share_url, &user.token, None).await;
+    assert_eq!(403, response.status().as_u16());
+
+    // Revoked links stay dead once the post is public again
+    sqlx::query("UPDATE posts SET visibility = 'public' WHERE workout_id = $1::uuid")
+        .bind(&workout_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(404, client.get(&public_url).send().await.unwrap().status().as_u16());
+    let response = make_authenticated_request(&client, reqwest::Method::DELETE, &share_url, &user.token, None).await;
+    assert_eq!(404, response.status().as_u16());
+
+    let response = make_authenticated_request(&client, reqwest::Method::POST, &share_url, &user.token, None).await;
+    assert_eq!(201, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_ne!(body["data"]["link"]["token"], token.as_str());
+}
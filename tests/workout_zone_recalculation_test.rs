@@ -0,0 +1,102 @@
+//! Retroactive zone recalculation tests
+//!
+//! Covers `/health/workouts/recalculate-zones` after a threshold change:
+//! - Past workouts get a recalculated zone view with the new thresholds
+//! - The original zones and the scored stats of the workout are unchanged
+//! - The time range limits which workouts are recalculated
+
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, make_authenticated_request, TestApp};
+use common::workout_data_helpers::{
+    WorkoutData, WorkoutIntensity, upload_workout_data_for_user, create_test_user_with_health_profile
+};
+
+async fn get_workout(test_app: &TestApp, client: &Client, token: &str, workout_id: &str) -> serde_json::Value {
+    let response = make_authenticated_request(
+        client,
+        reqwest::Method::GET,
+        &format!("{}/health/workout/{}", test_app.address, workout_id),
+        token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+#[tokio::test]
+async fn recalculating_zones_keeps_the_original_view_and_scores() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_with_health_profile(&test_app.address).await;
+
+    let mut workout_data = WorkoutData::new(WorkoutIntensity::Moderate, Utc::now() - Duration::days(2), 30);
+    workout_data.workout_uuid = Uuid::new_v4().to_string();
+    let upload = upload_workout_data_for_user(&client, &test_app.address, &user.token, &mut workout_data)
+        .await
+        .expect("Workout upload should succeed");
+    let workout_id = upload["data"]["sync_id"].as_str().unwrap().to_string();
+    let original = get_workout(&test_app, &client, &user.token, &workout_id).await;
+    assert!(original["recalculated_heart_rate_zones"].is_null());
+
+    // A lower resting heart rate moves all thresholds
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::PUT,
+        &format!("{}/profile/health_profile", test_app.address),
+        &user.token,
+        Some(json!({ "resting_heart_rate": 40 })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+
+    // A range that ends before the workout recalculates nothing
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workouts/recalculate-zones", test_app.address),
+        &user.token,
+        Some(json!({ "to": (Utc::now() - Duration::days(5)).to_rfc3339() })),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workouts_recalculated"], 0);
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workouts/recalculate-zones", test_app.address),
+        &user.token,
+        Some(json!({})),
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"]["workouts_recalculated"], 1);
+    assert_eq!(body["data"]["resting_heart_rate"], 40);
+
+    let recalculated = get_workout(&test_app, &client, &user.token, &workout_id).await;
+    assert_eq!(recalculated["heart_rate_zones"], original["heart_rate_zones"]);
+    assert_eq!(recalculated["stamina_gained"], original["stamina_gained"]);
+    assert_eq!(recalculated["strength_gained"], original["strength_gained"]);
+    assert!(recalculated["zones_recalculated_at"].is_string());
+
+    let zones = recalculated["recalculated_heart_rate_zones"].as_array().unwrap();
+    assert_eq!(zones.len(), original["heart_rate_zones"].as_array().unwrap().len());
+    assert_ne!(
+        recalculated["recalculated_heart_rate_zones"], original["heart_rate_zones"],
+        "New thresholds should change the zone boundaries"
+    );
+
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::POST,
+        &format!("{}/health/workouts/recalculate-zones", test_app.address),
+        &user.token,
+        Some(json!({ "from": Utc::now().to_rfc3339(), "to": (Utc::now() - Duration::days(1)).to_rfc3339() })),
+    ).await;
+    assert_eq!(400, response.status().as_u16());
+}
@@ -0,0 +1,166 @@
+//! Zone breakdown tests
+//!
+//! Covers the heart rate zone aggregates:
+//! - `/league/games/{id}/zones` sums zone minutes of credited workouts per team
+//! - `/league/seasons/{id}/zones` sums them over the whole season, optionally per team
+
+use reqwest::Client;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+mod common;
+use common::utils::{spawn_app, create_test_user_and_login, make_authenticated_request, parse_user_id_from_jwt_token};
+use common::admin_helpers::{create_admin_user_and_login, create_league_with_teams, create_league_season};
+
+/// Insert a workout with the given zone minutes and credit it to the game
+async fn insert_credited_workout(
+    pool: &sqlx::PgPool,
+    game_id: Uuid,
+    user_id: Uuid,
+    team_id: Uuid,
+    team_side: &str,
+    zones: serde_json::Value,
+) {
+    let now = Utc::now();
+    let workout_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO workout_data (user_id, device_id, heart_rate_data, workout_uuid,
+                                  workout_start, workout_end, heart_rate_zones, stamina_gained)
+        VALUES ($1, 'test-device', '[]'::jsonb, $2, $3, $4, $5, 5.0)
+        RETURNING id
+        "#
+    )
+    .bind(user_id)
+    .bind(Uuid::new_v4().to_string())
+    .bind(now - Duration::hours(2))
+    .bind(now - Duration::hours(1))
+    .bind(zones)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to insert workout");
+
+    sqlx::query(
+        r#"
+        INSERT INTO live_score_events (game_id, user_id, username, team_id, team_side,
+                                       score_points, power_contribution, description, workout_data_id)
+        VALUES ($1, $2, 'zone_player', $3, $4, 5.0, 0, 'Zone test event', $5)
+        "#
+    )
+    .bind(game_id)
+    .bind(user_id)
+    .bind(team_id)
+    .bind(team_side)
+    .bind(workout_id)
+    .execute(pool)
+    .await
+    .expect("Failed to insert score event");
+}
+
+fn zone_minutes(team: &serde_json::Value, zone: &str) -> f64 {
+    team["zones"].as_array().unwrap().iter()
+        .find(|z| z["zone"] == zone)
+        .and_then(|z| z["minutes"].as_f64())
+        .unwrap_or(0.0)
+}
+
+#[tokio::test]
+async fn zone_breakdown_aggregates_per_game_and_season() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let admin = create_admin_user_and_login(&test_app.address, &test_app.db_pool).await;
+    let player_a = create_test_user_and_login(&test_app.address).await;
+    let player_b = create_test_user_and_login(&test_app.address).await;
+    let player_a_id = parse_user_id_from_jwt_token(&player_a.token);
+    let player_b_id = parse_user_id_from_jwt_token(&player_b.token);
+
+    let league = create_league_with_teams(
+        &test_app.address, &admin.token, 2, 2, Some(vec![player_a_id, player_b_id]), true, None, None,
+    ).await;
+    let start_date = (Utc::now() + Duration::days(1)).to_rfc3339();
+    let season_id = create_league_season(
+        &test_app.address, &admin.token, &league.league_id, "Zone Season", &start_date,
+    ).await;
+    let season_id = Uuid::parse_str(&season_id).unwrap();
+
+    let game = sqlx::query!(
+        r#"
+        UPDATE games SET status = 'in_progress'
+        WHERE id = (SELECT id FROM games WHERE season_id = $1 ORDER BY week_number LIMIT 1)
+        RETURNING id, home_team_id, away_team_id
+        "#,
+        season_id
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .expect("Failed to start game");
+
+    let team_a = Uuid::parse_str(&league.team_ids[0]).unwrap();
+    let home_player = if game.home_team_id == team_a { player_a_id } else { player_b_id };
+
+    insert_credited_workout(&test_app.db_pool, game.id, home_player, game.home_team_id, "home", json!([
+        {"zone": "Easy", "minutes": 20.0, "stamina_gained": 2.0, "strength_gained": 0.0},
+        {"zone": "Hard", "minutes": 5.5, "stamina_gained": 1.0, "strength_gained": 1.0}
+    ])).await;
+    insert_credited_workout(&test_app.db_pool, game.id, home_player, game.home_team_id, "home", json!([
+        {"zone": "Easy", "minutes": 10.0, "stamina_gained": 1.0, "strength_gained": 0.0}
+    ])).await;
+
+    // Per game: the home team has both workouts, the away team none
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/games/{}/zones", test_app.address, game.id),
+        &player_a.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let home = &body["data"]["home_team"];
+    assert_eq!(home["team_id"], game.home_team_id.to_string().as_str());
+    assert_eq!(home["workout_count"], 2);
+    assert_eq!(zone_minutes(home, "Easy"), 30.0);
+    assert_eq!(zone_minutes(home, "Hard"), 5.5);
+    assert_eq!(home["total_minutes"].as_f64().unwrap(), 35.5);
+    assert_eq!(home["zones"][0]["zone"], "Easy", "Zones should be ordered from low to high intensity");
+
+    let away = &body["data"]["away_team"];
+    assert_eq!(away["workout_count"], 0);
+    assert_eq!(away["zones"].as_array().unwrap().len(), 0);
+
+    // Per season, filtered to the home team
+    let response = make_authenticated_request(
+        &client,
+        reqwest::Method::GET,
+        &format!("{}/league/seasons/{}/zones?team_id={}", test_app.address, season_id, game.home_team_id),
+        &player_a.token,
+        None,
+    ).await;
+    assert_eq!(200, response.status().as_u16());
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    let teams = body["data"]["teams"].as_array().unwrap();
+    assert_eq!(teams.len(), 1);
+    assert_eq!(zone_minutes(&teams[0], "Easy"), 30.0);
+    assert_eq!(teams[0]["total_minutes"].as_f64().unwrap(), 35.5);
+}
+
+#[tokio::test]
+async fn zone_breakdown_returns_404_for_unknown_game_and_season() {
+    let test_app = spawn_app().await;
+    let client = Client::new();
+    let user = create_test_user_and_login(&test_app.address).await;
+
+    for path in ["games", "seasons"] {
+        let response = make_authenticated_request(
+            &client,
+            reqwest::Method::GET,
+            &format!("{}/league/{}/{}/zones", test_app.address, path, Uuid::new_v4()),
+            &user.token,
+            None,
+        ).await;
+
+        assert_eq!(404, response.status().as_u16());
+    }
+}